@@ -4,14 +4,17 @@ use std::process::Command;
 
 use num_cpus;
 
-pub fn build_dpdk(rte_sdk: &Path, rte_target: &str) {
+use crate::link::LinkMode;
+
+pub fn build_dpdk(rte_sdk: &Path, rte_target: &str, mode: LinkMode) {
     let debug_mode = env::var("DEBUG")
         .map(|s| s.parse().unwrap_or_default())
         .unwrap_or_default();
 
     info!(
-        "building {} mode DPDK {} @ {:?}",
+        "building {} mode {} DPDK {} @ {:?}",
         if debug_mode { "debug" } else { "release" },
+        mode,
         rte_target,
         rte_sdk
     );
@@ -21,6 +24,10 @@ pub fn build_dpdk(rte_sdk: &Path, rte_target: &str) {
         .arg(format!("T={}", rte_target))
         .args(&["-j", &num_cpus::get().to_string()])
         .env("CONFIG_RTE_BUILD_COMBINE_LIBS", "y")
+        .env(
+            "CONFIG_RTE_BUILD_SHARED_LIB",
+            if mode == LinkMode::Shared { "y" } else { "n" },
+        )
         .env(
             "EXTRA_CFLAGS",
             if debug_mode {
@@ -11,10 +11,14 @@ mod build;
 mod cargo;
 mod cpu;
 mod gcc;
+mod link;
 mod rte;
 
 pub use crate::build::build_dpdk;
-pub use crate::cargo::{gen_cargo_config, OUT_DIR};
+pub use crate::cargo::OUT_DIR;
 pub use crate::cpu::gen_cpu_features;
 pub use crate::gcc::gcc_rte_config;
+pub use crate::link::{
+    check_pmd_libs, find_pmd_drivers, gen_cargo_config, is_driver_lib, is_pmd_lib, LinkMode, PmdDriver,
+};
 pub use crate::rte::*;
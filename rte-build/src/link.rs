@@ -0,0 +1,200 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// How the DPDK libraries are linked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkMode {
+    /// Link the static libraries, the drivers are linked with `--whole-archive`.
+    Static,
+    /// Link the shared libraries.
+    Shared,
+}
+
+impl LinkMode {
+    /// The link mode selected by the `shared` feature of the crate being built.
+    pub fn from_features() -> Self {
+        if env::var_os("CARGO_FEATURE_SHARED").is_some() {
+            LinkMode::Shared
+        } else {
+            LinkMode::Static
+        }
+    }
+
+    /// The file name of the library, e.g. `librte_eal.a` or `librte_eal.so`.
+    pub fn lib_file(self, lib: &str) -> String {
+        match self {
+            LinkMode::Static => format!("lib{}.a", lib),
+            LinkMode::Shared => format!("lib{}.so", lib),
+        }
+    }
+}
+
+impl fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LinkMode::Static => "static",
+            LinkMode::Shared => "shared",
+        })
+    }
+}
+
+/// The library is a PMD.
+pub fn is_pmd_lib(lib: &str) -> bool {
+    lib.starts_with("rte_pmd_")
+}
+
+/// The library is a driver which registers itself in a constructor, e.g. a PMD, a bus or a mempool driver.
+///
+/// Nothing references the symbols of a driver, so the linker drops it from a static archive
+/// unless the archive is linked with `--whole-archive`.
+pub fn is_driver_lib(lib: &str) -> bool {
+    is_pmd_lib(lib) || lib.starts_with("rte_bus_") || lib.starts_with("rte_mempool_")
+}
+
+/// Generate the link arguments of the DPDK libraries and their dependencies.
+///
+/// The static drivers are linked by their file names with `--whole-archive`, so their constructors
+/// are kept, the static libraries are not bundled into the rlib, and linked after the drivers.
+pub fn gen_cargo_config<S: AsRef<str>>(
+    rte_sdk_dir: &Path,
+    mode: LinkMode,
+    libs: impl Iterator<Item = S>,
+    deps: impl Iterator<Item = S>,
+) {
+    let (drivers, libs): (Vec<_>, Vec<_>) = libs.partition(|lib| is_driver_lib(lib.as_ref()));
+
+    info!(
+        "linking {} drivers and {} libraries of {} DPDK",
+        drivers.len(),
+        libs.len(),
+        mode
+    );
+
+    for lib in &drivers {
+        match mode {
+            LinkMode::Static => println!(
+                "cargo:rustc-link-lib=static:+whole-archive,-bundle,+verbatim={}",
+                mode.lib_file(lib.as_ref())
+            ),
+            LinkMode::Shared => println!("cargo:rustc-link-lib=dylib={}", lib.as_ref()),
+        }
+    }
+
+    for lib in &libs {
+        match mode {
+            LinkMode::Static => println!("cargo:rustc-link-lib=static:-bundle={}", lib.as_ref()),
+            LinkMode::Shared => println!("cargo:rustc-link-lib=dylib={}", lib.as_ref()),
+        }
+    }
+
+    for lib in deps {
+        match mode {
+            LinkMode::Static => println!("cargo:rustc-link-lib=static={}", lib.as_ref()),
+            LinkMode::Shared => println!("cargo:rustc-link-lib=dylib={}", lib.as_ref()),
+        }
+    }
+
+    println!(
+        "cargo:rustc-link-search=native={}",
+        rte_sdk_dir.join("lib").to_str().unwrap()
+    );
+    println!("cargo:include={}", rte_sdk_dir.join("include").to_str().unwrap());
+}
+
+/// A driver of the PMD library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PmdDriver {
+    /// The PMD library, e.g. `rte_pmd_ixgbe`.
+    pub lib: String,
+    /// The name of the driver, e.g. `net_ixgbe`.
+    pub name: String,
+    /// The driver is registered on the PCI bus, otherwise on the vdev or another bus.
+    pub pci: bool,
+}
+
+const PMD_INFO_STRING: &[u8] = b"PMD_INFO_STRING= ";
+
+/// Find the drivers of the PMD libraries in the PMD info strings embedded by `pmdinfogen`,
+/// e.g. `PMD_INFO_STRING= {"name" : "net_ixgbe", "pci_ids" : [...]}`.
+pub fn find_pmd_drivers<S: AsRef<str>>(rte_sdk_dir: &Path, mode: LinkMode, libs: &[S]) -> Vec<PmdDriver> {
+    let lib_dir = rte_sdk_dir.join("lib");
+    let mut drivers = vec![];
+
+    for lib in libs.iter().map(AsRef::as_ref).filter(|lib| is_pmd_lib(lib)) {
+        let path = lib_dir.join(mode.lib_file(lib));
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(err) => {
+                warn!("fail to read PMD library {:?}, {}", path, err);
+
+                continue;
+            }
+        };
+
+        for (name, pci) in parse_pmd_info(&data) {
+            if !drivers.iter().any(|drv: &PmdDriver| drv.name == name) {
+                drivers.push(PmdDriver {
+                    lib: lib.to_owned(),
+                    name,
+                    pci,
+                });
+            }
+        }
+    }
+
+    drivers
+}
+
+/// The names of the drivers in the PMD info strings, and whether they have the PCI ids.
+fn parse_pmd_info(data: &[u8]) -> Vec<(String, bool)> {
+    let mut drivers = vec![];
+    let mut rest = data;
+
+    while let Some(pos) = find(rest, PMD_INFO_STRING) {
+        rest = &rest[pos + PMD_INFO_STRING.len()..];
+
+        let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        let info = String::from_utf8_lossy(&rest[..end]);
+
+        if let Some(name) = info
+            .find("\"name\"")
+            .map(|pos| &info[pos + 6..])
+            .and_then(|s| s.splitn(3, '"').nth(1))
+        {
+            drivers.push((name.to_owned(), info.contains("\"pci_ids\"")));
+        }
+
+        rest = &rest[end..];
+    }
+
+    drivers
+}
+
+fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+    data.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Fail the build if no PMD driver will be linked, the application would find no port at runtime.
+///
+/// The shared PMDs could be loaded from `CONFIG_RTE_EAL_PMD_PATH` or with the `-d` option instead,
+/// so only a warning is emitted for the shared linking.
+pub fn check_pmd_libs(rte_sdk_dir: &Path, mode: LinkMode, drivers: &[PmdDriver]) {
+    if !drivers.is_empty() {
+        info!("found {} drivers in the PMD libraries", drivers.len());
+
+        return;
+    }
+
+    let msg = format!(
+        "no PMD driver was found in {:?} for the {} linking, no port would be found at runtime",
+        rte_sdk_dir.join("lib"),
+        mode
+    );
+
+    match mode {
+        LinkMode::Static => panic!("{}, build DPDK with the PMDs, or enable the `shared` feature", msg),
+        LinkMode::Shared => println!("cargo:warning={}, unless the PMDs are loaded with the `-d` option", msg),
+    }
+}
@@ -6,6 +6,8 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
 
+use crate::link::{LinkMode, PmdDriver};
+
 pub const MACHINE: &str = "native";
 pub const TOOLCHAIN: &str = "gcc";
 
@@ -190,6 +192,66 @@ pub fn gen_rte_config(rte_sdk_dir: &Path, dest_path: &Path) {
     }
 }
 
+/// The libraries which were built in the DPDK SDK for the link mode, the missing optional libraries are skipped.
+pub fn find_rte_libs<S: AsRef<str>>(rte_sdk_dir: &Path, mode: LinkMode, libs: impl Iterator<Item = S>) -> Vec<S> {
+    let lib_dir = rte_sdk_dir.join("lib");
+
+    libs.filter(|lib| {
+        let found = lib_dir.join(mode.lib_file(lib.as_ref())).exists();
+
+        if !found {
+            warn!("skip missing {} library {}", mode, lib.as_ref());
+        }
+
+        found
+    })
+    .collect()
+}
+
+pub fn gen_rte_features<S: AsRef<str>>(libs: &[S], drivers: &[PmdDriver], mode: LinkMode, dest_path: &Path) {
+    info!("generating DPDK features of {} {} libraries", libs.len(), mode);
+
+    let mut f = File::create(&dest_path).unwrap();
+
+    writeln!(
+        &mut f,
+        "/* automatically generated by {} v{}, DON'T EDIT IT */\n",
+        env::var("CARGO_PKG_NAME").unwrap(),
+        env::var("CARGO_PKG_VERSION").unwrap(),
+    )
+    .unwrap();
+
+    writeln!(&mut f, "/// The DPDK libraries are linked as the shared libraries.").unwrap();
+    writeln!(
+        &mut f,
+        "pub const RTE_SHARED_LINK: bool = {};",
+        mode == LinkMode::Shared
+    )
+    .unwrap();
+    writeln!(&mut f).unwrap();
+    writeln!(&mut f, "/// The DPDK libraries linked with the crate.").unwrap();
+    writeln!(&mut f, "pub const RTE_LINKED_LIBS: &[&str] = &[").unwrap();
+
+    for lib in libs {
+        writeln!(&mut f, "    {:?},", lib.as_ref()).unwrap();
+    }
+
+    writeln!(&mut f, "];").unwrap();
+    writeln!(&mut f).unwrap();
+    writeln!(
+        &mut f,
+        "/// The drivers of the linked PMD libraries, and whether they are the PCI drivers."
+    )
+    .unwrap();
+    writeln!(&mut f, "pub const RTE_PMD_DRIVERS: &[(&str, &str, bool)] = &[").unwrap();
+
+    for drv in drivers {
+        writeln!(&mut f, "    ({:?}, {:?}, {}),", drv.lib, drv.name, drv.pci).unwrap();
+    }
+
+    writeln!(&mut f, "];").unwrap();
+}
+
 pub fn apply_patches(rte_sdk_dir: &Path) {
     let mut patch = Command::new("patch")
         .stdin(Stdio::piped())
@@ -0,0 +1,143 @@
+//!
+//! The integers in network byte order, as stored in the protocol headers on the wire.
+//!
+//! The wrappers keep the network order values apart from the host order ones,
+//! so a field of the header can't be compared to a host order constant by mistake.
+//!
+use core::fmt;
+#[cfg(feature = "std")]
+use std::net::Ipv4Addr;
+
+macro_rules! wire_int {
+    ($(#[$attr:meta])* $name:ident($ty:ty, $size:expr)) => {
+        $(#[$attr])*
+        #[allow(non_camel_case_types)]
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name($ty);
+
+        impl $name {
+            /// Convert an integer in host byte order.
+            #[inline]
+            pub const fn new(n: $ty) -> Self {
+                $name(n.to_be())
+            }
+
+            /// Wrap an integer which is already in network byte order, e.g. a field of the raw header.
+            #[inline]
+            pub const fn from_raw(raw: $ty) -> Self {
+                $name(raw)
+            }
+
+            /// The integer in network byte order.
+            #[inline]
+            pub const fn to_raw(self) -> $ty {
+                self.0
+            }
+
+            /// The integer in host byte order.
+            #[inline]
+            pub fn get(self) -> $ty {
+                <$ty>::from_be(self.0)
+            }
+
+            /// Read the integer from the bytes on the wire.
+            #[inline]
+            pub fn from_bytes(bytes: [u8; $size]) -> Self {
+                $name(<$ty>::from_ne_bytes(bytes))
+            }
+
+            /// The bytes of the integer on the wire.
+            #[inline]
+            pub fn to_bytes(self) -> [u8; $size] {
+                self.0.to_ne_bytes()
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(n: $ty) -> Self {
+                $name::new(n)
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(n: $name) -> Self {
+                n.get()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.get(), f)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({:#x})", stringify!($name), self.get())
+            }
+        }
+
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::LowerHex::fmt(&self.get(), f)
+            }
+        }
+    };
+}
+
+wire_int!(
+    /// 16-bit integer in network byte order.
+    be16(u16, 2)
+);
+wire_int!(
+    /// 32-bit integer in network byte order.
+    be32(u32, 4)
+);
+wire_int!(
+    /// 64-bit integer in network byte order.
+    be64(u64, 8)
+);
+
+#[cfg(feature = "std")]
+impl From<Ipv4Addr> for be32 {
+    fn from(addr: Ipv4Addr) -> Self {
+        be32::new(u32::from(addr))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<be32> for Ipv4Addr {
+    fn from(addr: be32) -> Self {
+        Ipv4Addr::from(addr.get())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::prelude::v1::*;
+
+    use super::*;
+
+    #[test]
+    fn test_wire_int() {
+        let n = be16::new(0x0806);
+
+        assert_eq!(n.to_bytes(), [0x08, 0x06]);
+        assert_eq!(n, be16::from_bytes([0x08, 0x06]));
+        assert_eq!(n, be16::from_raw(0x0806u16.to_be()));
+        assert_eq!(n.get(), 0x0806);
+        assert_eq!(u16::from(n), 0x0806);
+        assert_eq!(n.to_string(), "2054");
+        assert_eq!(format!("{:?}", n), "be16(0x806)");
+        assert_eq!(format!("{:x}", n), "806");
+
+        let addr = Ipv4Addr::new(192, 168, 0, 1);
+        let n = be32::from(addr);
+
+        assert_eq!(n.to_bytes(), addr.octets());
+        assert_eq!(Ipv4Addr::from(n), addr);
+
+        assert_eq!(be64::new(1).to_bytes(), [0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+}
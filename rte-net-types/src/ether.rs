@@ -0,0 +1,441 @@
+//!
+//! The Ethernet addresses and frame types.
+//!
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::result;
+use core::str;
+
+#[cfg(feature = "std")]
+use std::error;
+#[cfg(feature = "std")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[cfg(all(feature = "std", feature = "rand"))]
+use rand::{thread_rng, Rng};
+
+use byteorder::be16;
+
+/// The length of an Ethernet address, in bytes.
+pub const ETHER_ADDR_LEN: usize = 6;
+
+/// The multicast or broadcast bit of the first byte of an Ethernet address.
+pub const ETHER_GROUP_ADDR: u8 = 0x01;
+/// The locally assigned bit of the first byte of an Ethernet address.
+pub const ETHER_LOCAL_ADMIN_ADDR: u8 = 0x02;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddrParseError(());
+
+impl fmt::Display for AddrParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("invalid MAC address syntax")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for AddrParseError {
+    fn description(&self) -> &str {
+        "invalid MAC address syntax"
+    }
+}
+
+/// A 48-bit (6 byte) buffer containing the MAC address
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EtherAddr([u8; ETHER_ADDR_LEN]);
+
+impl Deref for EtherAddr {
+    type Target = [u8; ETHER_ADDR_LEN];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for EtherAddr {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl EtherAddr {
+    /// Creates a new MAC address from six eight-bit octets.
+    ///
+    /// The result will represent the MAC address a:b:c:d:e:f.
+    #[inline]
+    pub fn new(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8) -> EtherAddr {
+        EtherAddr([a, b, c, d, e, f])
+    }
+
+    /// Fast copy an Ethernet address.
+    #[inline]
+    pub fn copy(from: &[u8; ETHER_ADDR_LEN], to: &mut [u8; ETHER_ADDR_LEN]) {
+        unsafe { ptr::copy_nonoverlapping(from.as_ptr(), to.as_mut_ptr(), ETHER_ADDR_LEN) }
+    }
+
+    /// Returns the six eight-bit integers that make up this address.
+    #[inline]
+    pub fn octets(&self) -> &[u8; ETHER_ADDR_LEN] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> [u8; ETHER_ADDR_LEN] {
+        self.0
+    }
+
+    pub fn from_bytes(b: &[u8]) -> result::Result<Self, AddrParseError> {
+        if b.len() != ETHER_ADDR_LEN {
+            return Err(AddrParseError(()));
+        }
+
+        let mut addr = [0; ETHER_ADDR_LEN];
+
+        addr.copy_from_slice(b);
+
+        Ok(EtherAddr(addr))
+    }
+
+    pub fn zeroed() -> Self {
+        EtherAddr([0; ETHER_ADDR_LEN])
+    }
+
+    pub fn broadcast() -> Self {
+        EtherAddr([0xffu8; ETHER_ADDR_LEN])
+    }
+
+    /// Generate a random Ethernet address that is locally administered and not multicast.
+    #[cfg(all(feature = "std", feature = "rand"))]
+    pub fn random() -> Self {
+        let mut addr = [0u8; ETHER_ADDR_LEN];
+
+        thread_rng().fill(&mut addr);
+
+        addr[0] &= !ETHER_GROUP_ADDR; // clear multicast bit
+        addr[0] |= ETHER_LOCAL_ADMIN_ADDR; // set local assignment bit
+
+        EtherAddr(addr)
+    }
+
+    /// Check if an Ethernet address is filled with zeros.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.0 == Self::zeroed().0
+    }
+
+    /// Check if an Ethernet address is a unicast address.
+    #[inline]
+    pub fn is_unicast(&self) -> bool {
+        (self.0[0] & ETHER_GROUP_ADDR) == 0
+    }
+
+    /// Check if an Ethernet address is a multicast address.
+    #[inline]
+    pub fn is_multicast(&self) -> bool {
+        (self.0[0] & ETHER_GROUP_ADDR) != 0
+    }
+
+    /// Check if an Ethernet address is a broadcast address.
+    #[inline]
+    pub fn is_broadcast(&self) -> bool {
+        self.0 == Self::broadcast().0
+    }
+
+    /// Check if an Ethernet address is a universally assigned address.
+    #[inline]
+    pub fn is_universal(&self) -> bool {
+        (self.0[0] & ETHER_LOCAL_ADMIN_ADDR) == 0
+    }
+
+    ///  Check if an Ethernet address is a locally assigned address.
+    #[inline]
+    pub fn is_local_admin(&self) -> bool {
+        (self.0[0] & ETHER_LOCAL_ADMIN_ADDR) != 0
+    }
+
+    /// Check if an Ethernet address is a valid address.
+    ///
+    /// Checks that the address is a unicast address and is not filled with zeros.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.is_unicast() && !self.is_zero()
+    }
+
+    /// The same address with the local assignment bit set.
+    #[inline]
+    pub fn to_local_admin(&self) -> Self {
+        let mut addr = *self;
+
+        addr.0[0] |= ETHER_LOCAL_ADMIN_ADDR;
+        addr
+    }
+
+    /// The organizationally unique identifier (OUI) of an Ethernet address.
+    ///
+    /// The multicast and local assignment bits are kept, they are not a part of the assigned OUI.
+    #[inline]
+    pub fn oui(&self) -> [u8; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+
+    /// The 24-bit OUI of an universally assigned address, e.g. `0x00_1b_21` for Intel.
+    #[inline]
+    pub fn oui_id(&self) -> Option<u32> {
+        if self.is_universal() {
+            Some(u32::from(self.0[0] & !ETHER_GROUP_ADDR) << 16 | u32::from(self.0[1]) << 8 | u32::from(self.0[2]))
+        } else {
+            None
+        }
+    }
+
+    /// The multicast Ethernet address of an IPv4 group, `01:00:5e` with the low 23 bits of the group.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn ipv4_mcast(group: &Ipv4Addr) -> Self {
+        let o = group.octets();
+
+        EtherAddr([0x01, 0x00, 0x5e, o[1] & 0x7f, o[2], o[3]])
+    }
+
+    /// The multicast Ethernet address of an IPv6 group, `33:33` with the low 32 bits of the group.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn ipv6_mcast(group: &Ipv6Addr) -> Self {
+        let o = group.octets();
+
+        EtherAddr([0x33, 0x33, o[12], o[13], o[14], o[15]])
+    }
+
+    /// The multicast Ethernet address of an IP group, or `None` if the address isn't multicast.
+    #[cfg(feature = "std")]
+    pub fn from_ip_mcast(group: &IpAddr) -> Option<Self> {
+        match *group {
+            IpAddr::V4(ref addr) if addr.is_multicast() => Some(Self::ipv4_mcast(addr)),
+            IpAddr::V6(ref addr) if addr.is_multicast() => Some(Self::ipv6_mcast(addr)),
+            _ => None,
+        }
+    }
+
+    /// Check if an Ethernet address is mapped from an IPv4 multicast group.
+    #[inline]
+    pub fn is_ipv4_mcast(&self) -> bool {
+        self.0[0] == 0x01 && self.0[1] == 0x00 && self.0[2] == 0x5e && self.0[3] & 0x80 == 0
+    }
+
+    /// Check if an Ethernet address is mapped from an IPv6 multicast group.
+    #[inline]
+    pub fn is_ipv6_mcast(&self) -> bool {
+        self.0[0] == 0x33 && self.0[1] == 0x33
+    }
+
+    /// The modified EUI-64 interface identifier (RFC 4291), with `ff:fe` inserted in the middle
+    /// and the universal/local bit inverted.
+    #[inline]
+    pub fn eui64(&self) -> [u8; 8] {
+        let o = &self.0;
+
+        [o[0] ^ ETHER_LOCAL_ADMIN_ADDR, o[1], o[2], 0xff, 0xfe, o[3], o[4], o[5]]
+    }
+
+    /// Get back the Ethernet address from a modified EUI-64 interface identifier.
+    pub fn from_eui64(id: &[u8; 8]) -> Option<Self> {
+        if id[3] == 0xff && id[4] == 0xfe {
+            Some(EtherAddr([
+                id[0] ^ ETHER_LOCAL_ADMIN_ADDR,
+                id[1],
+                id[2],
+                id[5],
+                id[6],
+                id[7],
+            ]))
+        } else {
+            None
+        }
+    }
+
+    /// The IPv6 address in the `/64` prefix with the EUI-64 interface identifier, as in SLAAC.
+    #[cfg(feature = "std")]
+    pub fn to_ipv6(&self, prefix: &Ipv6Addr) -> Ipv6Addr {
+        let mut o = prefix.octets();
+
+        o[8..].copy_from_slice(&self.eui64());
+
+        Ipv6Addr::from(o)
+    }
+
+    /// The IPv6 link-local address `fe80::/64` with the EUI-64 interface identifier.
+    #[cfg(feature = "std")]
+    pub fn to_ipv6_link_local(&self) -> Ipv6Addr {
+        self.to_ipv6(&Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0))
+    }
+}
+
+impl fmt::Display for EtherAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+impl From<[u8; 6]> for EtherAddr {
+    fn from(addr: [u8; 6]) -> EtherAddr {
+        EtherAddr(addr)
+    }
+}
+
+impl From<*const u8> for EtherAddr {
+    fn from(p: *const u8) -> EtherAddr {
+        let mut mac = [0u8; ETHER_ADDR_LEN];
+
+        unsafe {
+            ptr::copy_nonoverlapping(p, mac.as_mut_ptr(), ETHER_ADDR_LEN);
+        }
+
+        EtherAddr(mac)
+    }
+}
+
+impl From<*mut u8> for EtherAddr {
+    fn from(p: *mut u8) -> EtherAddr {
+        EtherAddr::from(p as *const u8)
+    }
+}
+
+impl str::FromStr for EtherAddr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut addr = [0; ETHER_ADDR_LEN];
+        let mut len = 0;
+
+        for b in s.split(':').filter_map(|part| u8::from_str_radix(part, 16).ok()) {
+            if len == ETHER_ADDR_LEN {
+                return Err(AddrParseError(()));
+            }
+
+            addr[len] = b;
+            len += 1;
+        }
+
+        EtherAddr::from_bytes(&addr[..len])
+    }
+}
+
+// Ethernet frame types
+
+/// IPv4 Protocol.
+pub const ETHER_TYPE_IPV4_BE: be16 = be16::new(0x0800);
+/// IPv6 Protocol.
+pub const ETHER_TYPE_IPV6_BE: be16 = be16::new(0x86DD);
+/// Arp Protocol.
+pub const ETHER_TYPE_ARP_BE: be16 = be16::new(0x0806);
+/// Reverse Arp Protocol.
+pub const ETHER_TYPE_RARP_BE: be16 = be16::new(0x8035);
+/// IEEE 802.1Q VLAN tagging.
+pub const ETHER_TYPE_VLAN_BE: be16 = be16::new(0x8100);
+/// IEEE 802.1AS 1588 Precise Time Protocol.
+pub const ETHER_TYPE_1588_BE: be16 = be16::new(0x88F7);
+/// Slow protocols (LACP and Marker).
+pub const ETHER_TYPE_SLOW_BE: be16 = be16::new(0x8809);
+/// Transparent Ethernet Bridging.
+pub const ETHER_TYPE_TEB_BE: be16 = be16::new(0x6558);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::prelude::v1::*;
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_macaddr() {
+        let addr = EtherAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f);
+
+        assert_eq!(addr.octets(), &[0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f]);
+        assert_eq!(addr.to_string(), "18:2b:3c:4d:5e:6f");
+
+        assert_eq!(addr, EtherAddr::from([0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f]));
+        assert_eq!(addr, EtherAddr::from_str("18:2b:3c:4d:5e:6f").unwrap());
+        assert!(EtherAddr::from_str("18:2b:3c:4d:5e").is_err());
+        assert!(EtherAddr::from_str("18:2b:3c:4d:5e:6f:70").is_err());
+
+        assert!(!addr.is_zero());
+        assert!(EtherAddr::zeroed().is_zero());
+
+        assert!(addr.is_unicast());
+
+        let local_addr = EtherAddr::new(0x13, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f);
+
+        assert!(!addr.is_multicast());
+        assert!(local_addr.is_multicast());
+
+        assert!(!addr.is_broadcast());
+        assert!(EtherAddr::broadcast().is_broadcast());
+
+        assert!(addr.is_universal());
+        assert!(!local_addr.is_universal());
+
+        assert!(!addr.is_local_admin());
+        assert!(local_addr.is_local_admin());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random() {
+        let rand_addr = EtherAddr::random();
+
+        assert!(rand_addr.is_unicast());
+        assert!(!rand_addr.is_multicast());
+        assert!(!rand_addr.is_broadcast());
+        assert!(!rand_addr.is_universal());
+        assert!(rand_addr.is_local_admin());
+        assert!(rand_addr.is_valid());
+    }
+
+    #[test]
+    fn test_eui64() {
+        let addr = EtherAddr::new(0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e);
+
+        assert_eq!(addr.oui(), [0x00, 0x1b, 0x21]);
+        assert_eq!(addr.oui_id(), Some(0x00_1b_21));
+        assert_eq!(addr.to_local_admin().oui_id(), None);
+        assert!(addr.to_local_admin().is_local_admin());
+
+        assert_eq!(addr.eui64(), [0x02, 0x1b, 0x21, 0xff, 0xfe, 0x3c, 0x4d, 0x5e]);
+        assert_eq!(EtherAddr::from_eui64(&addr.eui64()), Some(addr));
+        assert_eq!(EtherAddr::from_eui64(&[0; 8]), None);
+        assert_eq!(
+            addr.to_ipv6_link_local(),
+            "fe80::21b:21ff:fe3c:4d5e".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            addr.to_ipv6(&"2001:db8:1:2::".parse().unwrap()),
+            "2001:db8:1:2:21b:21ff:fe3c:4d5e".parse::<Ipv6Addr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mcast() {
+        let v4 = EtherAddr::ipv4_mcast(&Ipv4Addr::new(239, 129, 2, 3));
+
+        assert_eq!(v4, EtherAddr::new(0x01, 0x00, 0x5e, 0x01, 0x02, 0x03));
+        assert!(v4.is_multicast());
+        assert!(v4.is_ipv4_mcast());
+        assert!(!v4.is_ipv6_mcast());
+
+        let v6 = EtherAddr::ipv6_mcast(&"ff02::1:ff3c:4d5e".parse().unwrap());
+
+        assert_eq!(v6, EtherAddr::new(0x33, 0x33, 0xff, 0x3c, 0x4d, 0x5e));
+        assert!(v6.is_ipv6_mcast());
+
+        assert_eq!(
+            EtherAddr::from_ip_mcast(&"224.0.0.251".parse().unwrap()),
+            Some(EtherAddr::new(0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb))
+        );
+        assert_eq!(EtherAddr::from_ip_mcast(&"10.0.0.1".parse().unwrap()), None);
+        assert_eq!(EtherAddr::from_ip_mcast(&"fe80::1".parse().unwrap()), None);
+    }
+}
@@ -0,0 +1,315 @@
+//!
+//! The constants, checksums and options of the IP protocols.
+//!
+//! The checksums are processed in host byte order, as the sum of the 16-bit words
+//! is the same in both orders once it is stored back into the header.
+//!
+#[cfg(feature = "std")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// IPv4 version
+pub const IPVERSION: u8 = 4;
+/// The minimum length of IPv4 header, in bytes.
+pub const IPV4_HDR_LEN: usize = 20;
+/// The maximum length of IPv4 header, in bytes.
+pub const IPV4_MAX_HDR_LEN: usize = 60;
+/// Internet header length mask for version_ihl field.
+pub const IPV4_HDR_IHL_MASK: u8 = 0x0f;
+/// Internet header length is in 32 bits words.
+pub const IPV4_IHL_MULTIPLIER: usize = 4;
+
+/// Don't fragment flag.
+pub const IPV4_HDR_DF_FLAG: u16 = 1 << 14;
+/// More fragments flag.
+pub const IPV4_HDR_MF_FLAG: u16 = 1 << 13;
+/// Fragment offset mask.
+pub const IPV4_HDR_OFFSET_MASK: u16 = (1 << 13) - 1;
+
+/// End of option list.
+pub const IPOPT_END: u8 = 0;
+/// No operation.
+pub const IPOPT_NOOP: u8 = 1;
+/// Record packet route.
+pub const IPOPT_RR: u8 = 7;
+/// Timestamp.
+pub const IPOPT_TS: u8 = 68;
+/// Basic security.
+pub const IPOPT_SECURITY: u8 = 130;
+/// Loose source route.
+pub const IPOPT_LSRR: u8 = 131;
+/// Strict source route.
+pub const IPOPT_SSRR: u8 = 137;
+/// Router alert.
+pub const IPOPT_RA: u8 = 148;
+
+/// Echo Reply
+pub const IP_ICMP_ECHO_REPLY: u8 = 0;
+/// Echo Request
+pub const IP_ICMP_ECHO_REQUEST: u8 = 8;
+
+/// Sum the 16-bit words of a buffer, in host byte order, on top of a previous sum.
+pub fn raw_cksum_sum(buf: &[u8], sum: u32) -> u32 {
+    let mut chunks = buf.chunks_exact(2);
+    let sum = (&mut chunks).fold(sum, |sum, w| sum + u32::from(u16::from_ne_bytes([w[0], w[1]])));
+
+    match *chunks.remainder() {
+        [b] => sum + u32::from(u16::from_ne_bytes([b, 0])),
+        _ => sum,
+    }
+}
+
+/// Fold a 32-bit sum into a 16-bit checksum.
+pub fn raw_cksum_reduce(mut sum: u32) -> u16 {
+    while sum > 0xffff {
+        sum = (sum >> 16) + (sum & 0xffff);
+    }
+
+    sum as u16
+}
+
+/// Process the non-complemented checksum of a buffer.
+pub fn raw_cksum(buf: &[u8]) -> u16 {
+    raw_cksum_reduce(raw_cksum_sum(buf, 0))
+}
+
+/// Process the IPv4 checksum of an IPv4 header, including its options.
+///
+/// The checksum field must be set to 0 by the caller.
+pub fn ipv4_cksum(hdr: &[u8]) -> u16 {
+    match raw_cksum(hdr) {
+        0xffff => 0xffff,
+        cksum => !cksum,
+    }
+}
+
+#[cfg(feature = "std")]
+/// Complement a sum into the L4 checksum, the zero checksum is sent as `0xffff`.
+fn udptcp_cksum(sum: u32) -> u16 {
+    match raw_cksum_reduce(sum) {
+        0xffff => 0xffff,
+        cksum => !cksum,
+    }
+}
+
+#[cfg(feature = "std")]
+fn ipv4_phdr_sum(src: Ipv4Addr, dst: Ipv4Addr, proto: u8, len: usize) -> u32 {
+    let mut phdr = [0; 12];
+
+    phdr[..4].copy_from_slice(&src.octets());
+    phdr[4..8].copy_from_slice(&dst.octets());
+    phdr[9] = proto;
+    phdr[10..12].copy_from_slice(&(len as u16).to_be_bytes());
+
+    raw_cksum_sum(&phdr, 0)
+}
+
+#[cfg(feature = "std")]
+fn ipv6_phdr_sum(src: Ipv6Addr, dst: Ipv6Addr, proto: u8, len: usize) -> u32 {
+    let mut phdr = [0; 40];
+
+    phdr[..16].copy_from_slice(&src.octets());
+    phdr[16..32].copy_from_slice(&dst.octets());
+    phdr[32..36].copy_from_slice(&(len as u32).to_be_bytes());
+    phdr[39] = proto;
+
+    raw_cksum_sum(&phdr, 0)
+}
+
+#[cfg(feature = "std")]
+/// Process the UDP or TCP checksum over IPv4, including the pseudo-header.
+///
+/// The `l4` is the L4 header and payload, whose checksum field must be set to 0 by the caller.
+pub fn ipv4_udptcp_cksum(src: Ipv4Addr, dst: Ipv4Addr, proto: u8, l4: &[u8]) -> u16 {
+    udptcp_cksum(raw_cksum_sum(l4, ipv4_phdr_sum(src, dst, proto, l4.len())))
+}
+
+#[cfg(feature = "std")]
+/// Verify the UDP or TCP checksum over IPv4, the `l4` is the L4 header and payload.
+pub fn ipv4_udptcp_cksum_verify(src: Ipv4Addr, dst: Ipv4Addr, proto: u8, l4: &[u8]) -> bool {
+    raw_cksum_reduce(raw_cksum_sum(l4, ipv4_phdr_sum(src, dst, proto, l4.len()))) == 0xffff
+}
+
+#[cfg(feature = "std")]
+/// Process the UDP or TCP checksum over IPv6, including the pseudo-header.
+///
+/// The `l4` is the L4 header and payload, whose checksum field must be set to 0 by the caller.
+pub fn ipv6_udptcp_cksum(src: Ipv6Addr, dst: Ipv6Addr, proto: u8, l4: &[u8]) -> u16 {
+    udptcp_cksum(raw_cksum_sum(l4, ipv6_phdr_sum(src, dst, proto, l4.len())))
+}
+
+#[cfg(feature = "std")]
+/// Verify the UDP or TCP checksum over IPv6, the `l4` is the L4 header and payload.
+pub fn ipv6_udptcp_cksum_verify(src: Ipv6Addr, dst: Ipv6Addr, proto: u8, l4: &[u8]) -> bool {
+    raw_cksum_reduce(raw_cksum_sum(l4, ipv6_phdr_sum(src, dst, proto, l4.len()))) == 0xffff
+}
+
+/// An option of IPv4 header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ipv4Option<'a> {
+    /// End of option list.
+    End,
+    /// No operation.
+    Nop,
+    /// Record route, with the pointer and the recorded addresses.
+    RecordRoute(u8, &'a [u8]),
+    /// Internet timestamp, with the pointer, the overflow and flags, and the timestamps.
+    Timestamp(u8, u8, &'a [u8]),
+    /// Loose source and record route, with the pointer and the route addresses.
+    LooseSourceRoute(u8, &'a [u8]),
+    /// Strict source and record route, with the pointer and the route addresses.
+    StrictSourceRoute(u8, &'a [u8]),
+    /// Router alert, with its value.
+    RouterAlert(u16),
+    /// Other options, with the type and the data.
+    Other(u8, &'a [u8]),
+}
+
+impl<'a> Ipv4Option<'a> {
+    /// The option type.
+    pub fn kind(&self) -> u8 {
+        match *self {
+            Ipv4Option::End => IPOPT_END,
+            Ipv4Option::Nop => IPOPT_NOOP,
+            Ipv4Option::RecordRoute(..) => IPOPT_RR,
+            Ipv4Option::Timestamp(..) => IPOPT_TS,
+            Ipv4Option::LooseSourceRoute(..) => IPOPT_LSRR,
+            Ipv4Option::StrictSourceRoute(..) => IPOPT_SSRR,
+            Ipv4Option::RouterAlert(..) => IPOPT_RA,
+            Ipv4Option::Other(kind, _) => kind,
+        }
+    }
+
+    /// The option must be copied into all fragments.
+    pub fn is_copied(&self) -> bool {
+        self.kind() & 0x80 != 0
+    }
+}
+
+/// An iterator over the options of IPv4 header.
+///
+/// The iteration stops at the end of option list, or at the first malformed option.
+#[derive(Clone, Debug)]
+pub struct Ipv4Options<'a>(&'a [u8]);
+
+impl<'a> Ipv4Options<'a> {
+    /// Parse the options of IPv4 header from the bytes following the fixed header.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Ipv4Options(buf)
+    }
+}
+
+impl<'a> Iterator for Ipv4Options<'a> {
+    type Item = Ipv4Option<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = self.0;
+
+        match buf.first().cloned() {
+            None => None,
+            Some(IPOPT_END) => {
+                self.0 = &[];
+
+                Some(Ipv4Option::End)
+            }
+            Some(IPOPT_NOOP) => {
+                self.0 = &buf[1..];
+
+                Some(Ipv4Option::Nop)
+            }
+            Some(kind) => {
+                let len = buf.get(1).cloned().unwrap_or_default() as usize;
+
+                if len < 2 || len > buf.len() {
+                    self.0 = &[];
+
+                    return None;
+                }
+
+                let data = &buf[2..len];
+
+                self.0 = &buf[len..];
+
+                Some(match kind {
+                    IPOPT_RR if !data.is_empty() => Ipv4Option::RecordRoute(data[0], &data[1..]),
+                    IPOPT_TS if data.len() >= 2 => Ipv4Option::Timestamp(data[0], data[1], &data[2..]),
+                    IPOPT_LSRR if !data.is_empty() => Ipv4Option::LooseSourceRoute(data[0], &data[1..]),
+                    IPOPT_SSRR if !data.is_empty() => Ipv4Option::StrictSourceRoute(data[0], &data[1..]),
+                    IPOPT_RA if data.len() == 2 => Ipv4Option::RouterAlert(u16::from_be_bytes([data[0], data[1]])),
+                    _ => Ipv4Option::Other(kind, data),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::prelude::v1::*;
+
+    use super::*;
+
+    #[test]
+    fn test_raw_cksum() {
+        assert_eq!(raw_cksum(&[]), 0);
+        assert_eq!(raw_cksum(&[0x01]), u16::from_ne_bytes([0x01, 0]));
+        assert_eq!(raw_cksum_reduce(0x1_fffe), 0xffff);
+
+        let hdr = [
+            0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0x01, 0xc0, 0xa8,
+            0x00, 0xc7,
+        ];
+
+        assert_eq!(ipv4_cksum(&hdr).to_ne_bytes(), [0xb8, 0x61]);
+    }
+
+    #[test]
+    fn test_udptcp_cksum() {
+        let src = Ipv4Addr::new(192, 168, 0, 1);
+        let dst = Ipv4Addr::new(192, 168, 0, 199);
+        let proto = 17; // UDP
+        let mut udp = [0x04, 0x00, 0x00, 0x35, 0x00, 0x0b, 0x00, 0x00, 0x01, 0x02, 0x03];
+
+        let cksum = ipv4_udptcp_cksum(src, dst, proto, &udp);
+
+        udp[6..8].copy_from_slice(&cksum.to_ne_bytes());
+
+        assert!(ipv4_udptcp_cksum_verify(src, dst, proto, &udp));
+        assert!(!ipv4_udptcp_cksum_verify(src, dst, proto, &udp[..10]));
+
+        let src = "2001:db8::1".parse().unwrap();
+        let dst = "2001:db8::2".parse().unwrap();
+
+        udp[6..8].copy_from_slice(&[0, 0]);
+
+        let cksum = ipv6_udptcp_cksum(src, dst, proto, &udp);
+
+        assert_ne!(cksum, 0);
+
+        udp[6..8].copy_from_slice(&cksum.to_ne_bytes());
+
+        assert!(ipv6_udptcp_cksum_verify(src, dst, proto, &udp));
+        assert!(!ipv6_udptcp_cksum_verify(dst, dst, proto, &udp));
+    }
+
+    #[test]
+    fn test_ipv4_options() {
+        let opts = [
+            IPOPT_NOOP, IPOPT_RA, 4, 0, 0, IPOPT_RR, 7, 4, 10, 0, 0, 1, IPOPT_END, 0, 0, 0,
+        ];
+
+        assert_eq!(
+            Ipv4Options::new(&opts).collect::<Vec<_>>(),
+            vec![
+                Ipv4Option::Nop,
+                Ipv4Option::RouterAlert(0),
+                Ipv4Option::RecordRoute(4, &[10, 0, 0, 1]),
+                Ipv4Option::End,
+            ]
+        );
+
+        assert!(Ipv4Option::RouterAlert(0).is_copied());
+        assert!(!Ipv4Option::Nop.is_copied());
+
+        assert_eq!(Ipv4Options::new(&[IPOPT_RR, 8, 4]).next(), None);
+    }
+}
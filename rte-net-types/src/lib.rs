@@ -0,0 +1,28 @@
+//!
+//! The pure data types of the network protocols, which are shared with the `rte` crate.
+//!
+//! The types don't depend on the EAL or the DPDK libraries, so the unit tests and the tools
+//! without DPDK could parse and build the packets with the same logic as the datapath.
+//!
+//! The crate is `no_std`, the `std` feature, enabled by default, adds the conversions
+//! of the `std::net` addresses and the `std::error::Error` implementations,
+//! and the `rand` feature adds `EtherAddr::random`.
+//!
+#![no_std]
+#![allow(deprecated, clippy::not_unsafe_ptr_arg_deref)]
+
+#[cfg(any(feature = "std", test))]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+pub mod byteorder;
+pub mod ether;
+pub mod ip;
+pub mod portmask;
+
+pub use byteorder::{be16, be32, be64};
+pub use ether::EtherAddr;
+pub use portmask::PortMask;
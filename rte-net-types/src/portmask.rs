@@ -0,0 +1,178 @@
+//!
+//! The set of ports, as parsed from the hexadecimal port mask of the `-p` option.
+//!
+use core::fmt;
+use core::iter::FromIterator;
+use core::num::ParseIntError;
+use core::ops::{BitAnd, BitOr};
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use std::error;
+
+/// The error of parsing a port mask.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsePortMaskError(ParseIntError);
+
+impl fmt::Display for ParsePortMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid port mask, {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParsePortMaskError {
+    fn description(&self) -> &str {
+        "invalid port mask"
+    }
+}
+
+/// A set of up to 64 ports, which is parsed from the hexadecimal port mask of the `-p` option.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PortMask(u64);
+
+impl PortMask {
+    /// An empty mask.
+    pub fn new() -> Self {
+        PortMask(0)
+    }
+
+    /// The mask of the first `n` ports.
+    pub fn first(n: u32) -> Self {
+        PortMask(1u64.checked_shl(n).map_or(!0, |bit| bit - 1))
+    }
+
+    /// Parse the hexadecimal port mask, with or without the `0x` prefix.
+    pub fn from_hex_str(s: &str) -> Result<Self, ParsePortMaskError> {
+        let s = s.trim();
+        let digits = if s.starts_with("0x") || s.starts_with("0X") {
+            &s[2..]
+        } else {
+            s
+        };
+
+        u64::from_str_radix(digits, 16)
+            .map(PortMask)
+            .map_err(ParsePortMaskError)
+    }
+
+    /// The bits of the mask.
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// The mask contains the port.
+    pub fn contains(&self, port_id: u16) -> bool {
+        u32::from(port_id) < 64 && self.0 & (1 << port_id) != 0
+    }
+
+    /// Add the port to the mask.
+    pub fn insert(&mut self, port_id: u16) {
+        if u32::from(port_id) < 64 {
+            self.0 |= 1 << port_id
+        }
+    }
+
+    /// Remove the port from the mask.
+    pub fn remove(&mut self, port_id: u16) {
+        if u32::from(port_id) < 64 {
+            self.0 &= !(1 << port_id)
+        }
+    }
+
+    /// The number of ports in the mask.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The mask has no port.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate the ports in the mask.
+    pub fn iter(&self) -> impl Iterator<Item = u16> {
+        let bits = self.0;
+
+        (0..64).filter(move |i| bits & (1 << i) != 0).map(|i| i as u16)
+    }
+}
+
+impl BitAnd for PortMask {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        PortMask(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for PortMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        PortMask(self.0 | rhs.0)
+    }
+}
+
+impl From<u64> for PortMask {
+    fn from(bits: u64) -> Self {
+        PortMask(bits)
+    }
+}
+
+impl FromIterator<u16> for PortMask {
+    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+        let mut mask = PortMask::new();
+
+        for port_id in iter {
+            mask.insert(port_id);
+        }
+
+        mask
+    }
+}
+
+impl FromStr for PortMask {
+    type Err = ParsePortMaskError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex_str(s)
+    }
+}
+
+impl fmt::Display for PortMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::prelude::v1::*;
+
+    use super::*;
+
+    #[test]
+    fn test_port_mask() {
+        let mask = PortMask::from_hex_str("0x5").unwrap();
+
+        assert_eq!(mask, "5".parse().unwrap());
+        assert_eq!(mask.iter().collect::<Vec<_>>(), vec![0, 2]);
+        assert!(mask.contains(2));
+        assert!(!mask.contains(1));
+        assert!(!mask.contains(64));
+        assert_eq!(mask.len(), 2);
+        assert_eq!(mask.to_string(), "0x5");
+        assert_eq!(mask & [1, 2].iter().cloned().collect(), PortMask::from(0x4));
+
+        assert_eq!(PortMask::first(3), PortMask::from(0x7));
+        assert_eq!(PortMask::first(64).bits(), !0);
+
+        assert!(PortMask::from_hex_str("").is_err());
+        assert!(PortMask::from_hex_str("0xg").is_err());
+        assert!(PortMask::from_hex_str("0xg")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid port mask"));
+    }
+}
@@ -26,10 +26,10 @@ fn gen_rte_binding(rte_sdk_dir: &Path, dest_path: &Path) {
         .header(stub_header)
         .generate_comments(true)
         .generate_inline_functions(true)
-        .whitelist_type(r"(rte|cmdline|ether|eth|arp|vlan|vxlan)_.*")
+        .whitelist_type(r"(rte|cmdline|ether|eth|arp|vlan|vxlan|icmp|sctp|tcp|udp)_.*")
         .whitelist_function(r"(_rte|rte|cmdline|lcore|ether|eth|arp|is)_.*")
         .whitelist_var(
-            r"(RTE|CMDLINE|ETHER|ARP|VXLAN|BONDING|LCORE|MEMPOOL|ARP|PKT|EXT_ATTACHED|IND_ATTACHED|lcore|rte|cmdline|per_lcore)_.*",
+            r"(RTE|DEV|CMDLINE|ETHER|ARP|VXLAN|BONDING|CRC32|RING|LCORE|MEMPOOL|ARP|PKT|EXT_ATTACHED|IND_ATTACHED|lcore|rte|cmdline|per_lcore)_.*",
         )
         .derive_copy(true)
         .derive_debug(true)
@@ -70,13 +70,14 @@ fn main() {
     pretty_env_logger::init();
 
     let rte_sdk_dir = RTE_SDK.join(RTE_TARGET.as_str());
+    let mode = LinkMode::from_features();
 
-    info!("using DPDK @ {:?}", rte_sdk_dir);
+    info!("using {} DPDK @ {:?}", mode, rte_sdk_dir);
 
-    if !rte_sdk_dir.exists() || !rte_sdk_dir.join("lib/libdpdk.a").exists() {
+    if !rte_sdk_dir.exists() || !rte_sdk_dir.join("lib").join(mode.lib_file("dpdk")).exists() {
         apply_patches(RTE_SDK.as_path());
 
-        build_dpdk(RTE_SDK.as_path(), RTE_TARGET.as_str());
+        build_dpdk(RTE_SDK.as_path(), RTE_TARGET.as_str(), mode);
     }
 
     if cfg!(feature = "gen") {
@@ -89,16 +90,19 @@ fn main() {
 
     gcc_rte_config(&rte_sdk_dir)
         .file("src/stub.c")
+        .file("src/trace_points.c")
         .include("src")
         .compile("rte_stub");
 
-    gen_cargo_config(
-        &rte_sdk_dir,
-        RTE_CORE_LIBS
-            .iter()
-            .chain(RTE_PMD_LIBS.iter())
-            .chain(RTE_DEPS_LIBS.iter()),
-    );
+    let libs = find_rte_libs(&rte_sdk_dir, mode, RTE_CORE_LIBS.iter().chain(RTE_PMD_LIBS.iter()));
+
+    let drivers = find_pmd_drivers(&rte_sdk_dir, mode, &libs);
+
+    check_pmd_libs(&rte_sdk_dir, mode, &drivers);
+
+    gen_rte_features(&libs, &drivers, mode, &OUT_DIR.join("features.rs"));
+
+    gen_cargo_config(&rte_sdk_dir, mode, libs.into_iter(), RTE_DEPS_LIBS.iter());
 
     if cfg!(target_os = "linux") {
         println!("cargo:rustc-link-search=native=/usr/lib/x86_64-linux-gnu");
@@ -12,3 +12,5 @@ cfg_if! {
         include!("raw.rs");
     }
 }
+
+include!(concat!(env!("OUT_DIR"), "/features.rs"));
@@ -408,6 +408,9 @@ pub const RTE_MEMZONE_IOVA_CONTIG: u32 = 1048576;
 pub const RTE_MEMZONE_NAMESIZE: u32 = 32;
 pub const RTE_TAILQ_RING_NAME: &'static [u8; 9usize] = b"RTE_RING\0";
 pub const RTE_RING_MZ_PREFIX: &'static [u8; 4usize] = b"RG_\0";
+pub const RING_F_SP_ENQ: u32 = 1;
+pub const RING_F_SC_DEQ: u32 = 2;
+pub const RING_F_EXACT_SZ: u32 = 4;
 pub const RTE_RING_SZ_MASK: u32 = 2147483647;
 pub const RTE_MEMPOOL_HEADER_COOKIE1: i64 = -4982197544707871147;
 pub const RTE_MEMPOOL_HEADER_COOKIE2: i64 = -941548164385788331;
@@ -657,6 +660,48 @@ pub const RTE_ETH_MODULE_SFF_8636_LEN: u32 = 256;
 pub const RTE_ETH_MODULE_SFF_8436: u32 = 4;
 pub const RTE_ETH_MODULE_SFF_8436_LEN: u32 = 256;
 pub const RTE_RETA_GROUP_SIZE: u32 = 64;
+pub const DEV_RX_OFFLOAD_VLAN_STRIP: u32 = 1;
+pub const DEV_RX_OFFLOAD_IPV4_CKSUM: u32 = 2;
+pub const DEV_RX_OFFLOAD_UDP_CKSUM: u32 = 4;
+pub const DEV_RX_OFFLOAD_TCP_CKSUM: u32 = 8;
+pub const DEV_RX_OFFLOAD_TCP_LRO: u32 = 16;
+pub const DEV_RX_OFFLOAD_QINQ_STRIP: u32 = 32;
+pub const DEV_RX_OFFLOAD_OUTER_IPV4_CKSUM: u32 = 64;
+pub const DEV_RX_OFFLOAD_MACSEC_STRIP: u32 = 128;
+pub const DEV_RX_OFFLOAD_HEADER_SPLIT: u32 = 256;
+pub const DEV_RX_OFFLOAD_VLAN_FILTER: u32 = 512;
+pub const DEV_RX_OFFLOAD_VLAN_EXTEND: u32 = 1024;
+pub const DEV_RX_OFFLOAD_JUMBO_FRAME: u32 = 2048;
+pub const DEV_RX_OFFLOAD_SCATTER: u32 = 8192;
+pub const DEV_RX_OFFLOAD_TIMESTAMP: u32 = 16384;
+pub const DEV_RX_OFFLOAD_SECURITY: u32 = 32768;
+pub const DEV_RX_OFFLOAD_KEEP_CRC: u32 = 65536;
+pub const DEV_RX_OFFLOAD_SCTP_CKSUM: u32 = 131072;
+pub const DEV_RX_OFFLOAD_OUTER_UDP_CKSUM: u32 = 262144;
+pub const DEV_RX_OFFLOAD_CHECKSUM: u32 = 14;
+pub const DEV_RX_OFFLOAD_VLAN: u32 = 1537;
+pub const DEV_TX_OFFLOAD_VLAN_INSERT: u32 = 1;
+pub const DEV_TX_OFFLOAD_IPV4_CKSUM: u32 = 2;
+pub const DEV_TX_OFFLOAD_UDP_CKSUM: u32 = 4;
+pub const DEV_TX_OFFLOAD_TCP_CKSUM: u32 = 8;
+pub const DEV_TX_OFFLOAD_SCTP_CKSUM: u32 = 16;
+pub const DEV_TX_OFFLOAD_TCP_TSO: u32 = 32;
+pub const DEV_TX_OFFLOAD_UDP_TSO: u32 = 64;
+pub const DEV_TX_OFFLOAD_OUTER_IPV4_CKSUM: u32 = 128;
+pub const DEV_TX_OFFLOAD_QINQ_INSERT: u32 = 256;
+pub const DEV_TX_OFFLOAD_VXLAN_TNL_TSO: u32 = 512;
+pub const DEV_TX_OFFLOAD_GRE_TNL_TSO: u32 = 1024;
+pub const DEV_TX_OFFLOAD_IPIP_TNL_TSO: u32 = 2048;
+pub const DEV_TX_OFFLOAD_GENEVE_TNL_TSO: u32 = 4096;
+pub const DEV_TX_OFFLOAD_MACSEC_INSERT: u32 = 8192;
+pub const DEV_TX_OFFLOAD_MT_LOCKFREE: u32 = 16384;
+pub const DEV_TX_OFFLOAD_MULTI_SEGS: u32 = 32768;
+pub const DEV_TX_OFFLOAD_MBUF_FAST_FREE: u32 = 65536;
+pub const DEV_TX_OFFLOAD_SECURITY: u32 = 131072;
+pub const DEV_TX_OFFLOAD_UDP_TNL_TSO: u32 = 262144;
+pub const DEV_TX_OFFLOAD_IP_TNL_TSO: u32 = 524288;
+pub const DEV_TX_OFFLOAD_OUTER_UDP_CKSUM: u32 = 1048576;
+pub const DEV_TX_OFFLOAD_MATCH_METADATA: u32 = 2097152;
 pub const RTE_ETH_DEV_CAPA_RUNTIME_RX_QUEUE_SETUP: u32 = 1;
 pub const RTE_ETH_DEV_CAPA_RUNTIME_TX_QUEUE_SETUP: u32 = 2;
 pub const RTE_ETH_DEV_FALLBACK_RX_RINGSIZE: u32 = 512;
@@ -691,6 +736,12 @@ pub const BONDING_MODE_BROADCAST: u32 = 3;
 pub const BONDING_MODE_8023AD: u32 = 4;
 pub const BONDING_MODE_TLB: u32 = 5;
 pub const BONDING_MODE_ALB: u32 = 6;
+pub const RTE_DISTRIBUTOR_NAMESIZE: u32 = 32;
+pub const CRC32_SW: u32 = 1;
+pub const CRC32_SSE42: u32 = 2;
+pub const CRC32_x64: u32 = 4;
+pub const CRC32_SSE42_x64: u32 = 6;
+pub const CRC32_ARM64: u32 = 8;
 pub const ARP_HRD_ETHER: u32 = 1;
 pub const ARP_OP_REQUEST: u32 = 1;
 pub const ARP_OP_REPLY: u32 = 2;
@@ -24625,6 +24676,521 @@ extern "C" {
     #[doc = "  Delay period on success, negative value otherwise."]
     pub fn rte_eth_bond_link_up_prop_delay_get(bonded_port_id: u16) -> ::std::os::raw::c_int;
 }
+pub mod rte_bond_8023ad_selection {
+    pub type Type = u32;
+    pub const UNSELECTED: Type = 0;
+    pub const STANDBY: Type = 1;
+    pub const SELECTED: Type = 2;
+}
+pub mod rte_bond_8023ad_agg_selection {
+    #[doc = " Aggregator selection mode"]
+    pub type Type = u32;
+    pub const AGG_BANDWIDTH: Type = 0;
+    pub const AGG_COUNT: Type = 1;
+    pub const AGG_STABLE: Type = 2;
+}
+#[doc = " Function called on reception of LACP packets with the slave port id and"]
+#[doc = " the LACP packet. The function takes the ownership of the packet."]
+pub type rte_eth_bond_8023ad_ext_slowrx_fn =
+    ::std::option::Option<unsafe extern "C" fn(slave_id: u16, lacp_pkt: *mut rte_mbuf)>;
+#[doc = " Mode 4 configuration structure"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_eth_bond_8023ad_conf {
+    pub fast_periodic_ms: u32,
+    pub slow_periodic_ms: u32,
+    pub short_timeout_ms: u32,
+    pub long_timeout_ms: u32,
+    pub aggregate_wait_timeout_ms: u32,
+    pub tx_period_ms: u32,
+    pub rx_marker_period_ms: u32,
+    pub update_timeout_ms: u32,
+    pub slowrx_cb: rte_eth_bond_8023ad_ext_slowrx_fn,
+    pub agg_selection: rte_bond_8023ad_agg_selection::Type,
+}
+#[test]
+fn bindgen_test_layout_rte_eth_bond_8023ad_conf() {
+    assert_eq!(
+        ::std::mem::size_of::<rte_eth_bond_8023ad_conf>(),
+        48usize,
+        concat!("Size of: ", stringify!(rte_eth_bond_8023ad_conf))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<rte_eth_bond_8023ad_conf>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rte_eth_bond_8023ad_conf))
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).fast_periodic_ms as *const _
+                as usize
+        },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(fast_periodic_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).slow_periodic_ms as *const _
+                as usize
+        },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(slow_periodic_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).short_timeout_ms as *const _
+                as usize
+        },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(short_timeout_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).long_timeout_ms as *const _
+                as usize
+        },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(long_timeout_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).aggregate_wait_timeout_ms
+                as *const _ as usize
+        },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(aggregate_wait_timeout_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).tx_period_ms as *const _ as usize
+        },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(tx_period_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).rx_marker_period_ms as *const _
+                as usize
+        },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(rx_marker_period_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).update_timeout_ms as *const _
+                as usize
+        },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(update_timeout_ms)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).slowrx_cb as *const _ as usize
+        },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(slowrx_cb)
+        )
+    );
+    assert_eq!(
+        unsafe {
+            &(*(::std::ptr::null::<rte_eth_bond_8023ad_conf>())).agg_selection as *const _ as usize
+        },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(rte_eth_bond_8023ad_conf),
+            "::",
+            stringify!(agg_selection)
+        )
+    );
+}
+extern "C" {
+    #[doc = " Get the mode 4 configuration of the bonded device"]
+    #[doc = ""]
+    #[doc = " @param port_id Bonding device id"]
+    #[doc = " @param conf Pointer to the configuration structure to be filled"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise."]
+    pub fn rte_eth_bond_8023ad_conf_get(
+        port_id: u16,
+        conf: *mut rte_eth_bond_8023ad_conf,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Configure the mode 4 of the bonded device"]
+    #[doc = ""]
+    #[doc = " @param port_id Bonding device id"]
+    #[doc = " @param conf Pointer to the configuration, NULL for default"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise."]
+    pub fn rte_eth_bond_8023ad_setup(
+        port_id: u16,
+        conf: *mut rte_eth_bond_8023ad_conf,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " LACPDU transmit path for external 802.3ad state machine.  Caller retains"]
+    #[doc = " ownership of the packet on failure."]
+    #[doc = ""]
+    #[doc = " @param port_id Bonding device id"]
+    #[doc = " @param slave_id Port ID of valid slave device."]
+    #[doc = " @param lacp_pkt mbuf containing LACPDU."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise."]
+    pub fn rte_eth_bond_8023ad_ext_slowtx(
+        port_id: u16,
+        slave_id: u16,
+        lacp_pkt: *mut rte_mbuf,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Enable dedicated hw queues for 802.3ad control plane traffic on slaves"]
+    #[doc = ""]
+    #[doc = " This function creates an additional tx and rx queue on each slave for"]
+    #[doc = " dedicated 802.3ad control plane traffic . A flow filtering rule is"]
+    #[doc = " programmed on each slave to redirect all LACP slow packets to that rx queue"]
+    #[doc = " for processing in the LACP state machine, this removes the need to filter"]
+    #[doc = " these packets in the bonded devices data path. The additional tx queue is"]
+    #[doc = " used to enable the LACP state machine to enqueue LACP packets directly to"]
+    #[doc = " slave hw independently of the bonded devices data path."]
+    #[doc = ""]
+    #[doc = " To use this feature all slaves must support the programming of the flow"]
+    #[doc = " filter rule required for rx and have enough queues that one rx and tx queue"]
+    #[doc = " can be reserved for the LACP state machines control packets."]
+    #[doc = ""]
+    #[doc = " Bonding port must be stopped to change this configuration."]
+    #[doc = ""]
+    #[doc = " @param port_id      Bonding device id"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise."]
+    pub fn rte_eth_bond_8023ad_dedicated_queues_enable(port_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Disable slow queue on slaves"]
+    #[doc = ""]
+    #[doc = " This function disables hardware slow packet filter."]
+    #[doc = ""]
+    #[doc = " Bonding port must be stopped to change this configuration."]
+    #[doc = ""]
+    #[doc = " @see rte_eth_bond_8023ad_slow_pkt_hw_filter_enable"]
+    #[doc = ""]
+    #[doc = " @param port_id      Bonding device id"]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise."]
+    pub fn rte_eth_bond_8023ad_dedicated_queues_disable(port_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Get aggregator mode for 8023ad"]
+    #[doc = " @param port_id Bonding device id"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   agregator mode on success, negative value otherwise"]
+    pub fn rte_eth_bond_8023ad_agg_selection_get(port_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Set aggregator mode for 8023ad"]
+    #[doc = " @param port_id Bonding device id"]
+    #[doc = " @param agg_selection Aggregator mode"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise"]
+    pub fn rte_eth_bond_8023ad_agg_selection_set(
+        port_id: u16,
+        agg_selection: rte_bond_8023ad_agg_selection::Type,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Create a new ethdev port from a set of rings"]
+    #[doc = ""]
+    #[doc = " @param name"]
+    #[doc = "    name to be given to the new ethdev port"]
+    #[doc = " @param rx_queues"]
+    #[doc = "    pointer to array of rte_rings to be used as RX queues"]
+    #[doc = " @param nb_rx_queues"]
+    #[doc = "    number of elements in the rx_queues array"]
+    #[doc = " @param tx_queues"]
+    #[doc = "    pointer to array of rte_rings to be used as TX queues"]
+    #[doc = " @param nb_tx_queues"]
+    #[doc = "    number of elements in the tx_queues array"]
+    #[doc = " @param numa_node"]
+    #[doc = "    the numa node on which the memory for this port is to be allocated"]
+    #[doc = " @return"]
+    #[doc = "    the port number of the newly created the ethdev or -1 on error."]
+    pub fn rte_eth_from_rings(
+        name: *const ::std::os::raw::c_char,
+        rx_queues: *const *mut rte_ring,
+        nb_rx_queues: ::std::os::raw::c_uint,
+        tx_queues: *const *mut rte_ring,
+        nb_tx_queues: ::std::os::raw::c_uint,
+        numa_node: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Create a new ethdev port from a ring"]
+    #[doc = ""]
+    #[doc = " This function is a shortcut call for rte_eth_from_rings for the"]
+    #[doc = " case where one wants to take a single rte_ring and use it as though"]
+    #[doc = " it were an ethdev"]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "    the ring to be used as an ethdev"]
+    #[doc = " @return"]
+    #[doc = "    the port number of the newly created ethdev, or -1 on error"]
+    pub fn rte_eth_from_ring(r: *mut rte_ring) -> ::std::os::raw::c_int;
+}
+pub mod rte_distributor_alg_type {
+    pub type Type = u32;
+    #[doc = "< Single Packet Mode"]
+    pub const RTE_DIST_ALG_SINGLE: Type = 0;
+    #[doc = "< Burst Mode"]
+    pub const RTE_DIST_ALG_BURST: Type = 1;
+    #[doc = "< Number of Algorithm Types"]
+    pub const RTE_DIST_NUM_ALG_TYPES: Type = 2;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_distributor {
+    _unused: [u8; 0],
+}
+extern "C" {
+    #[doc = " Function to create a new distributor instance"]
+    #[doc = ""]
+    #[doc = " Reserves the memory needed for the distributor operation and"]
+    #[doc = " initializes the distributor to work with the configured number of workers."]
+    #[doc = ""]
+    #[doc = " @param name"]
+    #[doc = "   The name to be given to the distributor instance."]
+    #[doc = " @param socket_id"]
+    #[doc = "   The NUMA node on which the memory is to be allocated"]
+    #[doc = " @param num_workers"]
+    #[doc = "   The maximum number of workers that will request packets from this"]
+    #[doc = "   distributor"]
+    #[doc = " @param alg_type"]
+    #[doc = "   Call the legacy API, or use the new burst API. legacy uses 32-bit"]
+    #[doc = "   flow ID, and works on a single packet at a time. Latest uses 15-"]
+    #[doc = "   bit flow ID and works on up to 8 packets at a time to workers."]
+    #[doc = " @return"]
+    #[doc = "   The newly created distributor instance"]
+    pub fn rte_distributor_create(
+        name: *const ::std::os::raw::c_char,
+        socket_id: ::std::os::raw::c_uint,
+        num_workers: ::std::os::raw::c_uint,
+        alg_type: ::std::os::raw::c_uint,
+    ) -> *mut rte_distributor;
+}
+extern "C" {
+    #[doc = " Process a set of packets by distributing them among workers that request"]
+    #[doc = " packets. The distributor will ensure that no two packets that have the"]
+    #[doc = " same flow id, or tag, in the mbuf will be processed on different cores at"]
+    #[doc = " the same time."]
+    #[doc = ""]
+    #[doc = " The user is advocated to set tag for each mbuf before calling this function."]
+    #[doc = " If user doesn't set the tag, the tag value can be various values depending on"]
+    #[doc = " driver implementation and configuration."]
+    #[doc = ""]
+    #[doc = " This is not multi-thread safe and should only be called on a single lcore."]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param mbufs"]
+    #[doc = "   The mbufs to be distributed"]
+    #[doc = " @param num_mbufs"]
+    #[doc = "   The number of mbufs in the mbufs array"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of mbufs processed."]
+    pub fn rte_distributor_process(
+        d: *mut rte_distributor,
+        mbufs: *mut *mut rte_mbuf,
+        num_mbufs: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Get a set of mbufs that have been returned to the distributor by workers"]
+    #[doc = ""]
+    #[doc = " This should only be called on the same lcore as rte_distributor_process()"]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param mbufs"]
+    #[doc = "   The mbufs pointer array to be filled in"]
+    #[doc = " @param max_mbufs"]
+    #[doc = "   The size of the mbufs array"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of mbufs returned in the mbufs array."]
+    pub fn rte_distributor_returned_pkts(
+        d: *mut rte_distributor,
+        mbufs: *mut *mut rte_mbuf,
+        max_mbufs: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Flush the distributor component, so that there are no in-flight or"]
+    #[doc = " backlogged packets awaiting processing"]
+    #[doc = ""]
+    #[doc = " This should only be called on the same lcore as rte_distributor_process()"]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of queued/in-flight packets that were completed by this call."]
+    pub fn rte_distributor_flush(d: *mut rte_distributor) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Clears the array of returned packets used as the source for the"]
+    #[doc = " rte_distributor_returned_pkts() API call."]
+    #[doc = ""]
+    #[doc = " This should only be called on the same lcore as rte_distributor_process()"]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    pub fn rte_distributor_clear_returns(d: *mut rte_distributor);
+}
+extern "C" {
+    #[doc = " API called by a worker to get new packets to process. Any previous packets"]
+    #[doc = " given to the worker is assumed to have completed processing, and may be"]
+    #[doc = " optionally returned to the distributor via the oldpkt parameter."]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param worker_id"]
+    #[doc = "   The worker instance number to use - must be less that num_workers passed"]
+    #[doc = "   at distributor creation time."]
+    #[doc = " @param pkts"]
+    #[doc = "   The mbufs pointer array to be filled in (up to 8 packets)"]
+    #[doc = " @param oldpkt"]
+    #[doc = "   The previous packets, if any, being processed by the worker"]
+    #[doc = " @param retcount"]
+    #[doc = "   The number of packets being returned"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of packets in the pkts array"]
+    pub fn rte_distributor_get_pkt(
+        d: *mut rte_distributor,
+        worker_id: ::std::os::raw::c_uint,
+        pkts: *mut *mut rte_mbuf,
+        oldpkt: *mut *mut rte_mbuf,
+        retcount: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " API called by a worker to return a completed packet without requesting a"]
+    #[doc = " new packet, for example, because a worker thread is shutting down"]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param worker_id"]
+    #[doc = "   The worker instance number to use - must be less that num_workers passed"]
+    #[doc = "   at distributor creation time."]
+    #[doc = " @param oldpkt"]
+    #[doc = "   The previous packets being processed by the worker"]
+    #[doc = " @param num"]
+    #[doc = "   The number of packets in the oldpkt array"]
+    pub fn rte_distributor_return_pkt(
+        d: *mut rte_distributor,
+        worker_id: ::std::os::raw::c_uint,
+        oldpkt: *mut *mut rte_mbuf,
+        num: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " API called by a worker to request a new packet to process."]
+    #[doc = " Any previous packets given to the worker are assumed to have completed"]
+    #[doc = " processing, and may be optionally returned to the distributor via"]
+    #[doc = " the oldpkt parameter."]
+    #[doc = " Unlike rte_distributor_get_pkt(), this function does not wait for"]
+    #[doc = " new packets to be provided by the distributor."]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param worker_id"]
+    #[doc = "   The worker instance number to use - must be less that num_workers passed"]
+    #[doc = "   at distributor creation time."]
+    #[doc = " @param oldpkt"]
+    #[doc = "   The returning packets, if any, processed by the worker"]
+    #[doc = " @param count"]
+    #[doc = "   The number of returning packets"]
+    pub fn rte_distributor_request_pkt(
+        d: *mut rte_distributor,
+        worker_id: ::std::os::raw::c_uint,
+        oldpkt: *mut *mut rte_mbuf,
+        count: ::std::os::raw::c_uint,
+    );
+}
+extern "C" {
+    #[doc = " API called by a worker to check for new packets that were previously"]
+    #[doc = " requested by a call to rte_distributor_request_pkt(). It does not wait"]
+    #[doc = " for the new packets to be available, but returns if the request has"]
+    #[doc = " not yet been fulfilled by the distributor."]
+    #[doc = ""]
+    #[doc = " @param d"]
+    #[doc = "   The distributor instance to be used"]
+    #[doc = " @param worker_id"]
+    #[doc = "   The worker instance number to use - must be less that num_workers passed"]
+    #[doc = "   at distributor creation time."]
+    #[doc = " @param mbufs"]
+    #[doc = "   The array of mbufs being given to the worker"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of packets being given to the worker thread,"]
+    #[doc = "   -1 if no packets are yet available (burst API - RTE_DIST_ALG_BURST)"]
+    #[doc = "   0 if no packets are yet available (legacy single API - RTE_DIST_ALG_SINGLE)"]
+    pub fn rte_distributor_poll_pkt(
+        d: *mut rte_distributor,
+        worker_id: ::std::os::raw::c_uint,
+        mbufs: *mut *mut rte_mbuf,
+    ) -> ::std::os::raw::c_int;
+}
 #[doc = " ARP header IPv4 payload."]
 #[repr(C, packed)]
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
@@ -25026,2863 +25592,5960 @@ fn bindgen_test_layout_ipv6_hdr() {
         concat!("Offset of field: ", stringify!(ipv6_hdr), "::", stringify!(dst_addr))
     );
 }
-#[doc = " This structure is the header of a cirbuf type."]
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cirbuf {
-    #[doc = "< total len of the fifo (number of elements)"]
-    pub maxlen: ::std::os::raw::c_uint,
-    #[doc = "< indice of the first elt"]
-    pub start: ::std::os::raw::c_uint,
-    #[doc = "< indice of the last elt"]
-    pub end: ::std::os::raw::c_uint,
-    #[doc = "< current len of fifo"]
-    pub len: ::std::os::raw::c_uint,
-    pub buf: *mut ::std::os::raw::c_char,
+#[doc = " ICMP Header"]
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct icmp_hdr {
+    #[doc = "< ICMP packet type."]
+    pub icmp_type: u8,
+    #[doc = "< ICMP packet code."]
+    pub icmp_code: u8,
+    #[doc = "< ICMP packet checksum."]
+    pub icmp_cksum: u16,
+    #[doc = "< ICMP packet identifier."]
+    pub icmp_ident: u16,
+    #[doc = "< ICMP packet sequence number."]
+    pub icmp_seq_nb: u16,
 }
 #[test]
-fn bindgen_test_layout_cirbuf() {
+fn bindgen_test_layout_icmp_hdr() {
     assert_eq!(
-        ::std::mem::size_of::<cirbuf>(),
-        24usize,
-        concat!("Size of: ", stringify!(cirbuf))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cirbuf>(),
+        ::std::mem::size_of::<icmp_hdr>(),
         8usize,
-        concat!("Alignment of ", stringify!(cirbuf))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cirbuf>())).maxlen as *const _ as usize },
-        0usize,
-        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(maxlen))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cirbuf>())).start as *const _ as usize },
-        4usize,
-        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(start))
+        concat!("Size of: ", stringify!(icmp_hdr))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cirbuf>())).end as *const _ as usize },
-        8usize,
-        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(end))
+        ::std::mem::align_of::<icmp_hdr>(),
+        1usize,
+        concat!("Alignment of ", stringify!(icmp_hdr))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cirbuf>())).len as *const _ as usize },
-        12usize,
-        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(len))
+        unsafe { &(*(::std::ptr::null::<icmp_hdr>())).icmp_type as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(icmp_hdr),
+            "::",
+            stringify!(icmp_type)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cirbuf>())).buf as *const _ as usize },
-        16usize,
-        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(buf))
+        unsafe { &(*(::std::ptr::null::<icmp_hdr>())).icmp_code as *const _ as usize },
+        1usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(icmp_hdr),
+            "::",
+            stringify!(icmp_code)
+        )
     );
-}
-impl Default for cirbuf {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-extern "C" {
-    pub static mut cmdline_vt100_commands: [*const ::std::os::raw::c_char; 0usize];
-}
-pub mod cmdline_vt100_parser_state {
-    pub type Type = u32;
-    pub const CMDLINE_VT100_INIT: Type = 0;
-    pub const CMDLINE_VT100_ESCAPE: Type = 1;
-    pub const CMDLINE_VT100_ESCAPE_CSI: Type = 2;
-}
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_vt100 {
-    pub bufpos: u8,
-    pub buf: [::std::os::raw::c_char; 8usize],
-    pub state: cmdline_vt100_parser_state::Type,
-}
-#[test]
-fn bindgen_test_layout_cmdline_vt100() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_vt100>(),
-        16usize,
-        concat!("Size of: ", stringify!(cmdline_vt100))
+        unsafe { &(*(::std::ptr::null::<icmp_hdr>())).icmp_cksum as *const _ as usize },
+        2usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(icmp_hdr),
+            "::",
+            stringify!(icmp_cksum)
+        )
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_vt100>(),
+        unsafe { &(*(::std::ptr::null::<icmp_hdr>())).icmp_ident as *const _ as usize },
         4usize,
-        concat!("Alignment of ", stringify!(cmdline_vt100))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).bufpos as *const _ as usize },
-        0usize,
-        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(bufpos))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).buf as *const _ as usize },
-        1usize,
-        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(buf))
+        concat!(
+            "Offset of field: ",
+            stringify!(icmp_hdr),
+            "::",
+            stringify!(icmp_ident)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).state as *const _ as usize },
-        12usize,
-        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(state))
+        unsafe { &(*(::std::ptr::null::<icmp_hdr>())).icmp_seq_nb as *const _ as usize },
+        6usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(icmp_hdr),
+            "::",
+            stringify!(icmp_seq_nb)
+        )
     );
 }
-impl Default for cmdline_vt100 {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-pub mod rdline_status {
-    pub type Type = u32;
-    pub const RDLINE_INIT: Type = 0;
-    pub const RDLINE_RUNNING: Type = 1;
-    pub const RDLINE_EXITED: Type = 2;
-}
-pub type rdline_write_char_t = ::std::option::Option<
-    unsafe extern "C" fn(rdl: *mut rdline, arg1: ::std::os::raw::c_char) -> ::std::os::raw::c_int,
->;
-pub type rdline_validate_t = ::std::option::Option<
-    unsafe extern "C" fn(rdl: *mut rdline, buf: *const ::std::os::raw::c_char, size: ::std::os::raw::c_uint),
->;
-pub type rdline_complete_t = ::std::option::Option<
-    unsafe extern "C" fn(
-        rdl: *mut rdline,
-        buf: *const ::std::os::raw::c_char,
-        dstbuf: *mut ::std::os::raw::c_char,
-        dstsize: ::std::os::raw::c_uint,
-        state: *mut ::std::os::raw::c_int,
-    ) -> ::std::os::raw::c_int,
->;
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub struct rdline {
-    pub status: rdline_status::Type,
-    pub left: cirbuf,
-    pub right: cirbuf,
-    pub left_buf: [::std::os::raw::c_char; 514usize],
-    pub right_buf: [::std::os::raw::c_char; 512usize],
-    pub prompt: [::std::os::raw::c_char; 32usize],
-    pub prompt_size: ::std::os::raw::c_uint,
-    pub kill_buf: [::std::os::raw::c_char; 512usize],
-    pub kill_size: ::std::os::raw::c_uint,
-    pub history: cirbuf,
-    pub history_buf: [::std::os::raw::c_char; 8192usize],
-    pub history_cur_line: ::std::os::raw::c_int,
-    pub write_char: rdline_write_char_t,
-    pub validate: rdline_validate_t,
-    pub complete: rdline_complete_t,
-    pub vt100: cmdline_vt100,
-    pub opaque: *mut ::std::os::raw::c_void,
+#[doc = " SCTP Header"]
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct sctp_hdr {
+    #[doc = "< Source port."]
+    pub src_port: u16,
+    #[doc = "< Destin port."]
+    pub dst_port: u16,
+    #[doc = "< Validation tag."]
+    pub tag: u32,
+    #[doc = "< Checksum."]
+    pub cksum: u32,
 }
 #[test]
-fn bindgen_test_layout_rdline() {
+fn bindgen_test_layout_sctp_hdr() {
     assert_eq!(
-        ::std::mem::size_of::<rdline>(),
-        9912usize,
-        concat!("Size of: ", stringify!(rdline))
+        ::std::mem::size_of::<sctp_hdr>(),
+        12usize,
+        concat!("Size of: ", stringify!(sctp_hdr))
     );
     assert_eq!(
-        ::std::mem::align_of::<rdline>(),
-        8usize,
-        concat!("Alignment of ", stringify!(rdline))
+        ::std::mem::align_of::<sctp_hdr>(),
+        1usize,
+        concat!("Alignment of ", stringify!(sctp_hdr))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).status as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<sctp_hdr>())).src_port as *const _ as usize },
         0usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(status))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).left as *const _ as usize },
-        8usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(left))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).right as *const _ as usize },
-        32usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(right))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).left_buf as *const _ as usize },
-        56usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(left_buf))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).right_buf as *const _ as usize },
-        570usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(right_buf))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).prompt as *const _ as usize },
-        1082usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(prompt))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).prompt_size as *const _ as usize },
-        1116usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(prompt_size))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).kill_buf as *const _ as usize },
-        1120usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(kill_buf))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).kill_size as *const _ as usize },
-        1632usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(kill_size))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).history as *const _ as usize },
-        1640usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(history))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).history_buf as *const _ as usize },
-        1664usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(history_buf))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).history_cur_line as *const _ as usize },
-        9856usize,
         concat!(
             "Offset of field: ",
-            stringify!(rdline),
+            stringify!(sctp_hdr),
             "::",
-            stringify!(history_cur_line)
+            stringify!(src_port)
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).write_char as *const _ as usize },
-        9864usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(write_char))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).validate as *const _ as usize },
-        9872usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(validate))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).complete as *const _ as usize },
-        9880usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(complete))
+        unsafe { &(*(::std::ptr::null::<sctp_hdr>())).dst_port as *const _ as usize },
+        2usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sctp_hdr),
+            "::",
+            stringify!(dst_port)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).vt100 as *const _ as usize },
-        9888usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(vt100))
+        unsafe { &(*(::std::ptr::null::<sctp_hdr>())).tag as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sctp_hdr),
+            "::",
+            stringify!(tag)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<rdline>())).opaque as *const _ as usize },
-        9904usize,
-        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(opaque))
+        unsafe { &(*(::std::ptr::null::<sctp_hdr>())).cksum as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sctp_hdr),
+            "::",
+            stringify!(cksum)
+        )
     );
 }
-impl Default for rdline {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
+#[doc = " UDP Header"]
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct udp_hdr {
+    #[doc = "< UDP source port."]
+    pub src_port: u16,
+    #[doc = "< UDP destination port."]
+    pub dst_port: u16,
+    #[doc = "< UDP datagram length"]
+    pub dgram_len: u16,
+    #[doc = "< UDP datagram checksum"]
+    pub dgram_cksum: u16,
 }
-#[doc = " Stores a pointer to the ops struct, and the offset: the place to"]
-#[doc = " write the parsed result in the destination structure."]
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_hdr {
-    pub ops: *mut cmdline_token_ops,
-    pub offset: ::std::os::raw::c_uint,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_hdr() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_token_hdr>(),
-        16usize,
-        concat!("Size of: ", stringify!(cmdline_token_hdr))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline_token_hdr>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_hdr))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_hdr>())).ops as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_hdr),
-            "::",
-            stringify!(ops)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_hdr>())).offset as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_hdr),
-            "::",
-            stringify!(offset)
-        )
-    );
-}
-impl Default for cmdline_token_hdr {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
+#[doc = " TCP Header"]
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct tcp_hdr {
+    #[doc = "< TCP source port."]
+    pub src_port: u16,
+    #[doc = "< TCP destination port."]
+    pub dst_port: u16,
+    #[doc = "< TX data sequence number."]
+    pub sent_seq: u32,
+    #[doc = "< RX data acknowledgement sequence number."]
+    pub recv_ack: u32,
+    #[doc = "< Data offset."]
+    pub data_off: u8,
+    #[doc = "< TCP flags"]
+    pub tcp_flags: u8,
+    #[doc = "< RX flow control window."]
+    pub rx_win: u16,
+    #[doc = "< TCP checksum."]
+    pub cksum: u16,
+    #[doc = "< TCP urgent pointer, if any."]
+    pub tcp_urp: u16,
+}
+#[doc = " Flow rule attributes."]
+#[doc = ""]
+#[doc = " Priorities are set on a per rule based within groups."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_attr {
+    #[doc = "< Priority group."]
+    pub group: u32,
+    #[doc = "< Rule priority level within group."]
+    pub priority: u32,
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize], u32>,
+}
+impl rte_flow_attr {
+    #[inline]
+    pub fn ingress(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_ingress(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn egress(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_egress(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn transfer(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_transfer(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(2usize, 1u8, val as u64)
+        }
     }
 }
-pub type cmdline_parse_token_hdr_t = cmdline_token_hdr;
-#[doc = " A token is defined by this structure."]
-#[doc = ""]
-#[doc = " parse() takes the token as first argument, then the source buffer"]
-#[doc = " starting at the token we want to parse. The 3rd arg is a pointer"]
-#[doc = " where we store the parsed data (as binary). It returns the number of"]
-#[doc = " parsed chars on success and a negative value on error."]
-#[doc = ""]
-#[doc = " complete_get_nb() returns the number of possible values for this"]
-#[doc = " token if completion is possible. If it is NULL or if it returns 0,"]
-#[doc = " no completion is possible."]
+pub mod rte_flow_item_type {
+    #[doc = " Matching pattern item types."]
+    #[doc = ""]
+    #[doc = " Pattern items fall in two categories:"]
+    #[doc = ""]
+    #[doc = " - Matching protocol headers and packet data, usually associated with a"]
+    #[doc = "   specification structure."]
+    #[doc = ""]
+    #[doc = " - Matching meta-data or affecting pattern processing, often without a"]
+    #[doc = "   specification structure."]
+    pub type Type = u32;
+    pub const RTE_FLOW_ITEM_TYPE_END: Type = 0;
+    pub const RTE_FLOW_ITEM_TYPE_VOID: Type = 1;
+    pub const RTE_FLOW_ITEM_TYPE_INVERT: Type = 2;
+    pub const RTE_FLOW_ITEM_TYPE_ANY: Type = 3;
+    pub const RTE_FLOW_ITEM_TYPE_PF: Type = 4;
+    pub const RTE_FLOW_ITEM_TYPE_VF: Type = 5;
+    pub const RTE_FLOW_ITEM_TYPE_PHY_PORT: Type = 6;
+    pub const RTE_FLOW_ITEM_TYPE_PORT_ID: Type = 7;
+    pub const RTE_FLOW_ITEM_TYPE_RAW: Type = 8;
+    pub const RTE_FLOW_ITEM_TYPE_ETH: Type = 9;
+    pub const RTE_FLOW_ITEM_TYPE_VLAN: Type = 10;
+    pub const RTE_FLOW_ITEM_TYPE_IPV4: Type = 11;
+    pub const RTE_FLOW_ITEM_TYPE_IPV6: Type = 12;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP: Type = 13;
+    pub const RTE_FLOW_ITEM_TYPE_UDP: Type = 14;
+    pub const RTE_FLOW_ITEM_TYPE_TCP: Type = 15;
+    pub const RTE_FLOW_ITEM_TYPE_SCTP: Type = 16;
+    pub const RTE_FLOW_ITEM_TYPE_VXLAN: Type = 17;
+    pub const RTE_FLOW_ITEM_TYPE_E_TAG: Type = 18;
+    pub const RTE_FLOW_ITEM_TYPE_NVGRE: Type = 19;
+    pub const RTE_FLOW_ITEM_TYPE_MPLS: Type = 20;
+    pub const RTE_FLOW_ITEM_TYPE_GRE: Type = 21;
+    pub const RTE_FLOW_ITEM_TYPE_FUZZY: Type = 22;
+    pub const RTE_FLOW_ITEM_TYPE_GTP: Type = 23;
+    pub const RTE_FLOW_ITEM_TYPE_GTPC: Type = 24;
+    pub const RTE_FLOW_ITEM_TYPE_GTPU: Type = 25;
+    pub const RTE_FLOW_ITEM_TYPE_ESP: Type = 26;
+    pub const RTE_FLOW_ITEM_TYPE_GENEVE: Type = 27;
+    pub const RTE_FLOW_ITEM_TYPE_VXLAN_GPE: Type = 28;
+    pub const RTE_FLOW_ITEM_TYPE_ARP_ETH_IPV4: Type = 29;
+    pub const RTE_FLOW_ITEM_TYPE_IPV6_EXT: Type = 30;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6: Type = 31;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6_ND_NS: Type = 32;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6_ND_NA: Type = 33;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6_ND_OPT: Type = 34;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6_ND_OPT_SLA_ETH: Type = 35;
+    pub const RTE_FLOW_ITEM_TYPE_ICMP6_ND_OPT_TLA_ETH: Type = 36;
+    pub const RTE_FLOW_ITEM_TYPE_MARK: Type = 37;
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_ETH"]
 #[doc = ""]
-#[doc = " complete_get_elt() copy in dstbuf (the size is specified in the"]
-#[doc = " parameter) the i-th possible completion for this token.  returns 0"]
-#[doc = " on success or and a negative value on error."]
+#[doc = " Matches an Ethernet header."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_eth {
+    #[doc = "< Destination MAC."]
+    pub dst: ether_addr,
+    #[doc = "< Source MAC."]
+    pub src: ether_addr,
+    #[doc = "< EtherType or TPID."]
+    pub type_: rte_be16_t,
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_VLAN"]
 #[doc = ""]
-#[doc = " get_help() fills the dstbuf with the help for the token. It returns"]
-#[doc = " -1 on error and 0 on success."]
+#[doc = " Matches an 802.1Q/ad VLAN tag."]
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct cmdline_token_ops {
-    #[doc = " parse(token ptr, buf, res pts, buf len)"]
-    pub parse: ::std::option::Option<
-        unsafe extern "C" fn(
-            arg1: *mut cmdline_parse_token_hdr_t,
-            arg2: *const ::std::os::raw::c_char,
-            arg3: *mut ::std::os::raw::c_void,
-            arg4: ::std::os::raw::c_uint,
-        ) -> ::std::os::raw::c_int,
-    >,
-    #[doc = " return the num of possible choices for this token"]
-    pub complete_get_nb:
-        ::std::option::Option<unsafe extern "C" fn(arg1: *mut cmdline_parse_token_hdr_t) -> ::std::os::raw::c_int>,
-    #[doc = " return the elt x for this token (token, idx, dstbuf, size)"]
-    pub complete_get_elt: ::std::option::Option<
-        unsafe extern "C" fn(
-            arg1: *mut cmdline_parse_token_hdr_t,
-            arg2: ::std::os::raw::c_int,
-            arg3: *mut ::std::os::raw::c_char,
-            arg4: ::std::os::raw::c_uint,
-        ) -> ::std::os::raw::c_int,
-    >,
-    #[doc = " get help for this token (token, dstbuf, size)"]
-    pub get_help: ::std::option::Option<
-        unsafe extern "C" fn(
-            arg1: *mut cmdline_parse_token_hdr_t,
-            arg2: *mut ::std::os::raw::c_char,
-            arg3: ::std::os::raw::c_uint,
-        ) -> ::std::os::raw::c_int,
-    >,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_ops() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_token_ops>(),
-        32usize,
-        concat!("Size of: ", stringify!(cmdline_token_ops))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline_token_ops>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_ops))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).parse as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ops),
-            "::",
-            stringify!(parse)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).complete_get_nb as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ops),
-            "::",
-            stringify!(complete_get_nb)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).complete_get_elt as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ops),
-            "::",
-            stringify!(complete_get_elt)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).get_help as *const _ as usize },
-        24usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ops),
-            "::",
-            stringify!(get_help)
-        )
-    );
+pub struct rte_flow_item_vlan {
+    #[doc = "< Tag control information."]
+    pub tci: rte_be16_t,
+    #[doc = "< Inner EtherType or TPID."]
+    pub inner_type: rte_be16_t,
 }
-#[doc = " Store a instruction, which is a pointer to a callback function and"]
-#[doc = " its parameter that is called when the instruction is parsed, a help"]
-#[doc = " string, and a list of token composing this instruction."]
-#[doc = ""]
-#[doc = " When no tokens are defined (tokens[0] == NULL), they are retrieved"]
-#[doc = " dynamically by calling f() as follows:"]
-#[doc = ""]
-#[doc = " @code"]
+#[doc = " RTE_FLOW_ITEM_TYPE_IPV4"]
 #[doc = ""]
-#[doc = " f((struct cmdline_token_hdr **)&token_p,"]
-#[doc = "   NULL,"]
-#[doc = "   (struct cmdline_token_hdr **)&inst->tokens[num]);"]
+#[doc = " Matches an IPv4 header."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_ipv4 {
+    #[doc = "< IPv4 header definition."]
+    pub hdr: ipv4_hdr,
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_IPV6."]
 #[doc = ""]
-#[doc = " @endcode"]
+#[doc = " Matches an IPv6 header."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_ipv6 {
+    #[doc = "< IPv6 header definition."]
+    pub hdr: ipv6_hdr,
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_UDP."]
 #[doc = ""]
-#[doc = " The address of the resulting token is expected at the location pointed by"]
-#[doc = " the first argument. Can be set to NULL to end the list."]
+#[doc = " Matches a UDP header."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_udp {
+    #[doc = "< UDP header definition."]
+    pub hdr: udp_hdr,
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_TCP."]
 #[doc = ""]
-#[doc = " The cmdline argument (struct cmdline *) is always NULL."]
+#[doc = " Matches a TCP header."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_tcp {
+    #[doc = "< TCP header definition."]
+    pub hdr: tcp_hdr,
+}
+#[doc = " RTE_FLOW_ITEM_TYPE_VXLAN."]
 #[doc = ""]
-#[doc = " The last argument points to the inst->tokens[] entry to retrieve, which"]
-#[doc = " is not necessarily inside allocated memory and should neither be read nor"]
-#[doc = " written. Its sole purpose is to deduce the token entry index of interest"]
-#[doc = " as described in the example below."]
+#[doc = " Matches a VXLAN header (RFC 7348)."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_item_vxlan {
+    #[doc = "< Normally 0x08 (I flag)."]
+    pub flags: u8,
+    #[doc = "< Reserved, normally 0x000000."]
+    pub rsvd0: [u8; 3usize],
+    #[doc = "< VXLAN identifier."]
+    pub vni: [u8; 3usize],
+    #[doc = "< Reserved, normally 0x00."]
+    pub rsvd1: u8,
+}
+#[doc = " Matching pattern item definition."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct rte_flow_item {
+    #[doc = "< Item type."]
+    pub type_: rte_flow_item_type::Type,
+    #[doc = "< Pointer to item specification structure."]
+    pub spec: *const ::std::os::raw::c_void,
+    #[doc = "< Defines an inclusive range (spec to last)."]
+    pub last: *const ::std::os::raw::c_void,
+    #[doc = "< Bit-mask applied to spec and last."]
+    pub mask: *const ::std::os::raw::c_void,
+}
+impl Default for rte_flow_item {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub mod rte_flow_action_type {
+    #[doc = " Action types."]
+    #[doc = ""]
+    #[doc = " Each possible action is represented by a type."]
+    pub type Type = u32;
+    pub const RTE_FLOW_ACTION_TYPE_END: Type = 0;
+    pub const RTE_FLOW_ACTION_TYPE_VOID: Type = 1;
+    pub const RTE_FLOW_ACTION_TYPE_PASSTHRU: Type = 2;
+    pub const RTE_FLOW_ACTION_TYPE_JUMP: Type = 3;
+    pub const RTE_FLOW_ACTION_TYPE_MARK: Type = 4;
+    pub const RTE_FLOW_ACTION_TYPE_FLAG: Type = 5;
+    pub const RTE_FLOW_ACTION_TYPE_QUEUE: Type = 6;
+    pub const RTE_FLOW_ACTION_TYPE_DROP: Type = 7;
+    pub const RTE_FLOW_ACTION_TYPE_COUNT: Type = 8;
+    pub const RTE_FLOW_ACTION_TYPE_RSS: Type = 9;
+    pub const RTE_FLOW_ACTION_TYPE_PF: Type = 10;
+    pub const RTE_FLOW_ACTION_TYPE_VF: Type = 11;
+    pub const RTE_FLOW_ACTION_TYPE_PHY_PORT: Type = 12;
+    pub const RTE_FLOW_ACTION_TYPE_PORT_ID: Type = 13;
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_JUMP"]
 #[doc = ""]
-#[doc = " Note about constraints:"]
+#[doc = " Redirects packets to a group on the current device."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_action_jump {
+    pub group: u32,
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_MARK"]
 #[doc = ""]
-#[doc = " - Only the address of these tokens is dynamic, their storage should be"]
-#[doc = "   static like normal tokens."]
-#[doc = " - Dynamic token lists that need to maintain an internal context (e.g. in"]
-#[doc = "   order to determine the next token) must store it statically also. This"]
-#[doc = "   context must be reinitialized when the first token is requested, that"]
-#[doc = "   is, when &inst->tokens[0] is provided as the third argument."]
-#[doc = " - Dynamic token lists must be NULL-terminated to generate usable"]
-#[doc = "   commands."]
+#[doc = " Attaches an integer value to packets and sets PKT_RX_FDIR and"]
+#[doc = " PKT_RX_FDIR_ID mbuf flags."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_action_mark {
+    #[doc = "< Integer value to return with packets."]
+    pub id: u32,
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_QUEUE"]
 #[doc = ""]
-#[doc = " @code"]
+#[doc = " Assign packets to a given queue index."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_action_queue {
+    #[doc = "< Queue index to use."]
+    pub index: u16,
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_COUNT"]
 #[doc = ""]
-#[doc = " // Assuming first and third arguments are respectively named \"token_p\""]
-#[doc = " // and \"token\":"]
+#[doc = " Adds a counter action to a matched flow."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_action_count {
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize], u32>,
+    #[doc = "< Counter ID."]
+    pub id: u32,
+}
+impl rte_flow_action_count {
+    #[inline]
+    pub fn shared(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_shared(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_COUNT (query)"]
 #[doc = ""]
-#[doc = " int index = token - inst->tokens;"]
-#[doc = ""]
-#[doc = " if (!index) {"]
-#[doc = "     [...] // Clean up internal context if any."]
-#[doc = " }"]
-#[doc = " [...] // Then set up dyn_token according to index."]
-#[doc = ""]
-#[doc = " if (no_more_tokens)"]
-#[doc = "     *token_p = NULL;"]
-#[doc = " else"]
-#[doc = "     *token_p = &dyn_token;"]
+#[doc = " Query structure to retrieve and reset flow rule counters."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_flow_query_count {
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 4usize], u32>,
+    #[doc = "< Number of hits for this rule [out]."]
+    pub hits: u64,
+    #[doc = "< Number of bytes through this rule [out]."]
+    pub bytes: u64,
+}
+impl rte_flow_query_count {
+    #[inline]
+    pub fn reset(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_reset(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(0usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn hits_set(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(1usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_hits_set(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(1usize, 1u8, val as u64)
+        }
+    }
+    #[inline]
+    pub fn bytes_set(&self) -> u32 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 1u8) as u32) }
+    }
+    #[inline]
+    pub fn set_bytes_set(&mut self, val: u32) {
+        unsafe {
+            let val: u32 = ::std::mem::transmute(val);
+            self._bitfield_1.set(2usize, 1u8, val as u64)
+        }
+    }
+}
+#[doc = " RTE_FLOW_ACTION_TYPE_RSS"]
 #[doc = ""]
-#[doc = " @endcode"]
+#[doc = " Similar to QUEUE, except RSS is additionally performed on packets to"]
+#[doc = " spread them among several queues according to the provided parameters."]
 #[repr(C)]
-#[derive(Debug)]
-pub struct cmdline_inst {
-    pub f: ::std::option::Option<
-        unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: *mut cmdline, arg3: *mut ::std::os::raw::c_void),
-    >,
-    pub data: *mut ::std::os::raw::c_void,
-    pub help_str: *const ::std::os::raw::c_char,
-    pub tokens: __IncompleteArrayField<*mut cmdline_parse_token_hdr_t>,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct rte_flow_action_rss {
+    #[doc = "< RSS hash function to apply."]
+    pub func: rte_eth_hash_function::Type,
+    #[doc = " Packet encapsulation level RSS hash @p types apply to."]
+    pub level: u32,
+    #[doc = "< Specific RSS hash types (see ETH_RSS_*)."]
+    pub types: u64,
+    #[doc = "< Hash key length in bytes."]
+    pub key_len: u32,
+    #[doc = "< Number of entries in @p queue."]
+    pub queue_num: u32,
+    #[doc = "< Hash key."]
+    pub key: *const u8,
+    #[doc = "< Queue indices to use."]
+    pub queue: *const u16,
+}
+impl Default for rte_flow_action_rss {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-#[test]
-fn bindgen_test_layout_cmdline_inst() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_inst>(),
-        24usize,
-        concat!("Size of: ", stringify!(cmdline_inst))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline_inst>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_inst))
-    );
+#[doc = " Definition of a single action."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct rte_flow_action {
+    #[doc = "< Action type."]
+    pub type_: rte_flow_action_type::Type,
+    #[doc = "< Pointer to action configuration object."]
+    pub conf: *const ::std::os::raw::c_void,
 }
-impl Default for cmdline_inst {
+impl Default for rte_flow_action {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
-pub type cmdline_parse_inst_t = cmdline_inst;
-#[doc = " A context is identified by its name, and contains a list of"]
-#[doc = " instruction"]
+#[doc = " Opaque type returned after successfully creating a flow."]
 #[doc = ""]
-pub type cmdline_parse_ctx_t = *mut cmdline_parse_inst_t;
+#[doc = " This handle can be used to manage and query the related flow (e.g. to"]
+#[doc = " destroy it or retrieve counters)."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_flow {
+    _unused: [u8; 0],
+}
+pub mod rte_flow_error_type {
+    #[doc = " Verbose error types."]
+    #[doc = ""]
+    #[doc = " Most of them provide the type of the object referenced by struct"]
+    #[doc = " rte_flow_error.cause."]
+    pub type Type = u32;
+    pub const RTE_FLOW_ERROR_TYPE_NONE: Type = 0;
+    pub const RTE_FLOW_ERROR_TYPE_UNSPECIFIED: Type = 1;
+    pub const RTE_FLOW_ERROR_TYPE_HANDLE: Type = 2;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR_GROUP: Type = 3;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR_PRIORITY: Type = 4;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR_INGRESS: Type = 5;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR_EGRESS: Type = 6;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR_TRANSFER: Type = 7;
+    pub const RTE_FLOW_ERROR_TYPE_ATTR: Type = 8;
+    pub const RTE_FLOW_ERROR_TYPE_ITEM_NUM: Type = 9;
+    pub const RTE_FLOW_ERROR_TYPE_ITEM_SPEC: Type = 10;
+    pub const RTE_FLOW_ERROR_TYPE_ITEM_LAST: Type = 11;
+    pub const RTE_FLOW_ERROR_TYPE_ITEM_MASK: Type = 12;
+    pub const RTE_FLOW_ERROR_TYPE_ITEM: Type = 13;
+    pub const RTE_FLOW_ERROR_TYPE_ACTION_NUM: Type = 14;
+    pub const RTE_FLOW_ERROR_TYPE_ACTION_CONF: Type = 15;
+    pub const RTE_FLOW_ERROR_TYPE_ACTION: Type = 16;
+}
+#[doc = " Verbose error structure definition."]
+#[doc = ""]
+#[doc = " This object is normally allocated by applications and set by PMDs, the"]
+#[doc = " message points to a constant string which does not need to be freed by"]
+#[doc = " the application, however its pointer can be considered valid only as long"]
+#[doc = " as its associated DPDK port remains configured."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct rte_flow_error {
+    #[doc = "< Cause field and error types."]
+    pub type_: rte_flow_error_type::Type,
+    #[doc = "< Object responsible for the error."]
+    pub cause: *const ::std::os::raw::c_void,
+    #[doc = "< Human-readable error message."]
+    pub message: *const ::std::os::raw::c_char,
+}
+impl Default for rte_flow_error {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
 extern "C" {
-    #[doc = " Try to parse a buffer according to the specified context. The"]
-    #[doc = " argument buf must ends with \"\\n\\0\". The function returns"]
-    #[doc = " CMDLINE_PARSE_AMBIGUOUS, CMDLINE_PARSE_NOMATCH or"]
-    #[doc = " CMDLINE_PARSE_BAD_ARGS on error. Else it calls the associated"]
-    #[doc = " function (defined in the context) and returns 0"]
-    #[doc = " (CMDLINE_PARSE_SUCCESS)."]
-    pub fn cmdline_parse(cl: *mut cmdline, buf: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    #[doc = " Check whether a flow rule can be created on a given port."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 if flow rule is valid and can be created. A negative errno value"]
+    #[doc = "   otherwise (rte_errno is also set)."]
+    pub fn rte_flow_validate(
+        port_id: u16,
+        attr: *const rte_flow_attr,
+        pattern: *const rte_flow_item,
+        actions: *const rte_flow_action,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " complete() must be called with *state==0 (try to complete) or"]
-    #[doc = " with *state==-1 (just display choices), then called without"]
-    #[doc = " modifying *state until it returns CMDLINE_PARSE_COMPLETED_BUFFER or"]
-    #[doc = " CMDLINE_PARSE_COMPLETED_BUFFER."]
+    #[doc = " Create a flow rule on a given port."]
     #[doc = ""]
-    #[doc = " It returns < 0 on error."]
+    #[doc = " @return"]
+    #[doc = "   A valid handle in case of success, NULL otherwise and rte_errno is set"]
+    #[doc = "   to the positive version of one of the error codes defined for"]
+    #[doc = "   rte_flow_validate()."]
+    pub fn rte_flow_create(
+        port_id: u16,
+        attr: *const rte_flow_attr,
+        pattern: *const rte_flow_item,
+        actions: *const rte_flow_action,
+        error: *mut rte_flow_error,
+    ) -> *mut rte_flow;
+}
+extern "C" {
+    #[doc = " Destroy a flow rule on a given port."]
     #[doc = ""]
-    #[doc = " Else it returns:"]
-    #[doc = "   - CMDLINE_PARSE_COMPLETED_BUFFER on completion (one possible"]
-    #[doc = "     choice). In this case, the chars are appended in dst buffer."]
-    #[doc = "   - CMDLINE_PARSE_COMPLETE_AGAIN if there is several possible"]
-    #[doc = "     choices. In this case, you must call the function again,"]
-    #[doc = "     keeping the value of state intact."]
-    #[doc = "   - CMDLINE_PARSE_COMPLETED_BUFFER when the iteration is"]
-    #[doc = "     finished. The dst is not valid for this last call."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise and rte_errno is set."]
+    pub fn rte_flow_destroy(port_id: u16, flow: *mut rte_flow, error: *mut rte_flow_error) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Destroy all flow rules associated with a port."]
     #[doc = ""]
-    #[doc = " The returned dst buf ends with \\0."]
-    pub fn cmdline_complete(
-        cl: *mut cmdline,
-        buf: *const ::std::os::raw::c_char,
-        state: *mut ::std::os::raw::c_int,
-        dst: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise and rte_errno is set."]
+    pub fn rte_flow_flush(port_id: u16, error: *mut rte_flow_error) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    pub fn cmdline_isendoftoken(c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    #[doc = " Restrict ingress traffic to the defined flow rules."]
+    #[doc = ""]
+    #[doc = " Isolated mode guarantees that all ingress traffic comes from defined flow"]
+    #[doc = " rules only (current and future)."]
+    #[doc = ""]
+    #[doc = " This function must be called before the port is configured."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise and rte_errno is set."]
+    pub fn rte_flow_isolate(
+        port_id: u16,
+        set: ::std::os::raw::c_int,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    pub fn cmdline_isendofcommand(c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    #[doc = " Query an existing flow rule."]
+    #[doc = ""]
+    #[doc = " This function allows retrieving flow-specific data such as counters."]
+    #[doc = " Data is gathered by special actions which must be present in the flow"]
+    #[doc = " rule definition."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise and rte_errno is set."]
+    pub fn rte_flow_query(
+        port_id: u16,
+        flow: *mut rte_flow,
+        action: *const rte_flow_action,
+        data: *mut ::std::os::raw::c_void,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
 }
+pub const RTE_METRICS_MAX_NAME_LEN: u32 = 64;
+pub const RTE_METRICS_GLOBAL: i32 = -1;
+#[doc = " A name-key lookup for metrics."]
+#[doc = ""]
+#[doc = " An array of this structure is returned by rte_metrics_get_names()."]
+#[doc = " The struct rte_metric_value references these names via their array index."]
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_etheraddr {
-    pub hdr: cmdline_token_hdr,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_etheraddr() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_token_etheraddr>(),
-        16usize,
-        concat!("Size of: ", stringify!(cmdline_token_etheraddr))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline_token_etheraddr>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_etheraddr))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_etheraddr>())).hdr as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_etheraddr),
-            "::",
-            stringify!(hdr)
-        )
-    );
+#[derive(Copy, Clone)]
+pub struct rte_metric_name {
+    #[doc = " String describing metric"]
+    pub name: [::std::os::raw::c_char; 64usize],
 }
-impl Default for cmdline_token_etheraddr {
+impl Default for rte_metric_name {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
-pub type cmdline_parse_token_etheraddr_t = cmdline_token_etheraddr;
+#[doc = " Metric value structure."]
+#[doc = ""]
+#[doc = " This structure is used by rte_metrics_get_values() to return metrics,"]
+#[doc = " which are statistics that are not generated by PMDs. It maps a name key,"]
+#[doc = " which corresponds to an index in the array returned by"]
+#[doc = " rte_metrics_get_names()."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_metric_value {
+    #[doc = " Numeric identifier of metric."]
+    pub key: u16,
+    #[doc = " Value for metric"]
+    pub value: u64,
+}
 extern "C" {
-    pub static mut cmdline_token_etheraddr_ops: cmdline_token_ops;
+    #[doc = " Initializes metric module. This function must be called from"]
+    #[doc = " a primary process before metrics are used."]
+    #[doc = ""]
+    #[doc = " @param socket_id"]
+    #[doc = "   Socket to use for shared memory allocation."]
+    pub fn rte_metrics_init(socket_id: ::std::os::raw::c_int);
 }
 extern "C" {
-    pub fn cmdline_parse_etheraddr(
-        tk: *mut cmdline_parse_token_hdr_t,
-        srcbuf: *const ::std::os::raw::c_char,
-        res: *mut ::std::os::raw::c_void,
-        ressize: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
+    #[doc = " Get metric name-key lookup table."]
+    #[doc = ""]
+    #[doc = " @param names"]
+    #[doc = "   A struct rte_metric_name array of at least *capacity* in size to"]
+    #[doc = "   receive key names. If this is NULL, function returns the required"]
+    #[doc = "   number of elements for this array."]
+    #[doc = ""]
+    #[doc = " @param capacity"]
+    #[doc = "   Size (number of elements) of struct rte_metric_name array."]
+    #[doc = "   Disregarded if names is NULL."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   - Positive value above capacity: error, *names* is too small."]
+    #[doc = "     Return value is required size."]
+    #[doc = "   - Positive value equal or less than capacity: Success. Return"]
+    #[doc = "     value is number of elements filled in."]
+    #[doc = "   - Negative value: error."]
+    pub fn rte_metrics_get_names(names: *mut rte_metric_name, capacity: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    pub fn cmdline_get_help_etheraddr(
-        tk: *mut cmdline_parse_token_hdr_t,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
+    #[doc = " Get metric value table."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   Port id to query"]
+    #[doc = ""]
+    #[doc = " @param values"]
+    #[doc = "   A struct rte_metric_value array of at least *capacity* in size to"]
+    #[doc = "   receive metric ids and values. If this is NULL, function returns"]
+    #[doc = "   the required number of elements for this array."]
+    #[doc = ""]
+    #[doc = " @param capacity"]
+    #[doc = "   Size (number of elements) of struct rte_metric_value array."]
+    #[doc = "   Disregarded if names is NULL."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   - Positive value above capacity: error, *values* is too small."]
+    #[doc = "     Return value is required size."]
+    #[doc = "   - Positive value equal or less than capacity: Success. Return"]
+    #[doc = "     value is number of elements filled in."]
+    #[doc = "   - Negative value: error."]
+    pub fn rte_metrics_get_values(
+        port_id: ::std::os::raw::c_int,
+        values: *mut rte_metric_value,
+        capacity: u16,
     ) -> ::std::os::raw::c_int;
 }
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct cmdline_ipaddr {
-    pub family: u8,
-    pub addr: cmdline_ipaddr__bindgen_ty_1,
-    pub prefixlen: ::std::os::raw::c_uint,
+#[derive(Debug, Copy, Clone)]
+pub struct rte_stats_bitrates {
+    _unused: [u8; 0],
+}
+extern "C" {
+    #[doc = " Allocate a bitrate statistics structure"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   - Pointer to structure on success"]
+    #[doc = "   - NULL on error (zmalloc failure)"]
+    pub fn rte_stats_bitrate_create() -> *mut rte_stats_bitrates;
+}
+extern "C" {
+    #[doc = " Register bitrate statistics with the metric library."]
+    #[doc = ""]
+    #[doc = " @param bitrate_data"]
+    #[doc = "   Pointer allocated by rte_stats_bitrate_create()"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   Zero on success"]
+    #[doc = "   Negative on error"]
+    pub fn rte_stats_bitrate_reg(bitrate_data: *mut rte_stats_bitrates) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Calculate statistics for current time window. The period with which"]
+    #[doc = " this function is called should be the intended sampling window width."]
+    #[doc = ""]
+    #[doc = " @param bitrate_data"]
+    #[doc = "   Bitrate statistics data pointer"]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   Port id to calculate statistics for"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "  - Zero on success"]
+    #[doc = "  - Negative value on error"]
+    pub fn rte_stats_bitrate_calc(bitrate_data: *mut rte_stats_bitrates, port_id: u16) -> ::std::os::raw::c_int;
 }
+#[doc = " This structure is the header of a cirbuf type."]
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub union cmdline_ipaddr__bindgen_ty_1 {
-    pub ipv4: in_addr,
-    pub ipv6: in6_addr,
-    _bindgen_union_align: [u32; 4usize],
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cirbuf {
+    #[doc = "< total len of the fifo (number of elements)"]
+    pub maxlen: ::std::os::raw::c_uint,
+    #[doc = "< indice of the first elt"]
+    pub start: ::std::os::raw::c_uint,
+    #[doc = "< indice of the last elt"]
+    pub end: ::std::os::raw::c_uint,
+    #[doc = "< current len of fifo"]
+    pub len: ::std::os::raw::c_uint,
+    pub buf: *mut ::std::os::raw::c_char,
 }
 #[test]
-fn bindgen_test_layout_cmdline_ipaddr__bindgen_ty_1() {
+fn bindgen_test_layout_cirbuf() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_ipaddr__bindgen_ty_1>(),
-        16usize,
-        concat!("Size of: ", stringify!(cmdline_ipaddr__bindgen_ty_1))
+        ::std::mem::size_of::<cirbuf>(),
+        24usize,
+        concat!("Size of: ", stringify!(cirbuf))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_ipaddr__bindgen_ty_1>(),
-        4usize,
-        concat!("Alignment of ", stringify!(cmdline_ipaddr__bindgen_ty_1))
+        ::std::mem::align_of::<cirbuf>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cirbuf))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr__bindgen_ty_1>())).ipv4 as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cirbuf>())).maxlen as *const _ as usize },
         0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_ipaddr__bindgen_ty_1),
-            "::",
-            stringify!(ipv4)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr__bindgen_ty_1>())).ipv6 as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_ipaddr__bindgen_ty_1),
-            "::",
-            stringify!(ipv6)
-        )
-    );
-}
-impl Default for cmdline_ipaddr__bindgen_ty_1 {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-#[test]
-fn bindgen_test_layout_cmdline_ipaddr() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_ipaddr>(),
-        24usize,
-        concat!("Size of: ", stringify!(cmdline_ipaddr))
+        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(maxlen))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_ipaddr>(),
+        unsafe { &(*(::std::ptr::null::<cirbuf>())).start as *const _ as usize },
         4usize,
-        concat!("Alignment of ", stringify!(cmdline_ipaddr))
+        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(start))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).family as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_ipaddr),
-            "::",
-            stringify!(family)
-        )
+        unsafe { &(*(::std::ptr::null::<cirbuf>())).end as *const _ as usize },
+        8usize,
+        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(end))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).addr as *const _ as usize },
-        4usize,
-        concat!("Offset of field: ", stringify!(cmdline_ipaddr), "::", stringify!(addr))
+        unsafe { &(*(::std::ptr::null::<cirbuf>())).len as *const _ as usize },
+        12usize,
+        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(len))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).prefixlen as *const _ as usize },
-        20usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_ipaddr),
-            "::",
-            stringify!(prefixlen)
-        )
+        unsafe { &(*(::std::ptr::null::<cirbuf>())).buf as *const _ as usize },
+        16usize,
+        concat!("Offset of field: ", stringify!(cirbuf), "::", stringify!(buf))
     );
 }
-impl Default for cmdline_ipaddr {
+impl Default for cirbuf {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
-pub type cmdline_ipaddr_t = cmdline_ipaddr;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct cmdline_token_ipaddr_data {
-    pub flags: u8,
+extern "C" {
+    pub static mut cmdline_vt100_commands: [*const ::std::os::raw::c_char; 0usize];
 }
-#[test]
-fn bindgen_test_layout_cmdline_token_ipaddr_data() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline_token_ipaddr_data>(),
-        1usize,
-        concat!("Size of: ", stringify!(cmdline_token_ipaddr_data))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline_token_ipaddr_data>(),
-        1usize,
-        concat!("Alignment of ", stringify!(cmdline_token_ipaddr_data))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr_data>())).flags as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ipaddr_data),
-            "::",
-            stringify!(flags)
-        )
-    );
+pub mod cmdline_vt100_parser_state {
+    pub type Type = u32;
+    pub const CMDLINE_VT100_INIT: Type = 0;
+    pub const CMDLINE_VT100_ESCAPE: Type = 1;
+    pub const CMDLINE_VT100_ESCAPE_CSI: Type = 2;
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_ipaddr {
-    pub hdr: cmdline_token_hdr,
-    pub ipaddr_data: cmdline_token_ipaddr_data,
+pub struct cmdline_vt100 {
+    pub bufpos: u8,
+    pub buf: [::std::os::raw::c_char; 8usize],
+    pub state: cmdline_vt100_parser_state::Type,
 }
 #[test]
-fn bindgen_test_layout_cmdline_token_ipaddr() {
+fn bindgen_test_layout_cmdline_vt100() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_ipaddr>(),
-        24usize,
-        concat!("Size of: ", stringify!(cmdline_token_ipaddr))
+        ::std::mem::size_of::<cmdline_vt100>(),
+        16usize,
+        concat!("Size of: ", stringify!(cmdline_vt100))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_ipaddr>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_ipaddr))
+        ::std::mem::align_of::<cmdline_vt100>(),
+        4usize,
+        concat!("Alignment of ", stringify!(cmdline_vt100))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr>())).hdr as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).bufpos as *const _ as usize },
         0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ipaddr),
-            "::",
-            stringify!(hdr)
-        )
+        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(bufpos))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr>())).ipaddr_data as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_ipaddr),
-            "::",
-            stringify!(ipaddr_data)
-        )
+        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).buf as *const _ as usize },
+        1usize,
+        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(buf))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_vt100>())).state as *const _ as usize },
+        12usize,
+        concat!("Offset of field: ", stringify!(cmdline_vt100), "::", stringify!(state))
     );
 }
-impl Default for cmdline_token_ipaddr {
+impl Default for cmdline_vt100 {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
-pub type cmdline_parse_token_ipaddr_t = cmdline_token_ipaddr;
-extern "C" {
-    pub static mut cmdline_token_ipaddr_ops: cmdline_token_ops;
-}
-extern "C" {
-    pub fn cmdline_parse_ipaddr(
-        tk: *mut cmdline_parse_token_hdr_t,
-        srcbuf: *const ::std::os::raw::c_char,
-        res: *mut ::std::os::raw::c_void,
-        ressize: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_get_help_ipaddr(
-        tk: *mut cmdline_parse_token_hdr_t,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-pub mod cmdline_numtype {
+pub mod rdline_status {
     pub type Type = u32;
-    pub const UINT8: Type = 0;
-    pub const UINT16: Type = 1;
-    pub const UINT32: Type = 2;
-    pub const UINT64: Type = 3;
-    pub const INT8: Type = 4;
-    pub const INT16: Type = 5;
-    pub const INT32: Type = 6;
-    pub const INT64: Type = 7;
+    pub const RDLINE_INIT: Type = 0;
+    pub const RDLINE_RUNNING: Type = 1;
+    pub const RDLINE_EXITED: Type = 2;
 }
+pub type rdline_write_char_t = ::std::option::Option<
+    unsafe extern "C" fn(rdl: *mut rdline, arg1: ::std::os::raw::c_char) -> ::std::os::raw::c_int,
+>;
+pub type rdline_validate_t = ::std::option::Option<
+    unsafe extern "C" fn(rdl: *mut rdline, buf: *const ::std::os::raw::c_char, size: ::std::os::raw::c_uint),
+>;
+pub type rdline_complete_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        rdl: *mut rdline,
+        buf: *const ::std::os::raw::c_char,
+        dstbuf: *mut ::std::os::raw::c_char,
+        dstsize: ::std::os::raw::c_uint,
+        state: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int,
+>;
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_num_data {
-    pub type_: cmdline_numtype::Type,
+#[derive(Copy, Clone)]
+pub struct rdline {
+    pub status: rdline_status::Type,
+    pub left: cirbuf,
+    pub right: cirbuf,
+    pub left_buf: [::std::os::raw::c_char; 514usize],
+    pub right_buf: [::std::os::raw::c_char; 512usize],
+    pub prompt: [::std::os::raw::c_char; 32usize],
+    pub prompt_size: ::std::os::raw::c_uint,
+    pub kill_buf: [::std::os::raw::c_char; 512usize],
+    pub kill_size: ::std::os::raw::c_uint,
+    pub history: cirbuf,
+    pub history_buf: [::std::os::raw::c_char; 8192usize],
+    pub history_cur_line: ::std::os::raw::c_int,
+    pub write_char: rdline_write_char_t,
+    pub validate: rdline_validate_t,
+    pub complete: rdline_complete_t,
+    pub vt100: cmdline_vt100,
+    pub opaque: *mut ::std::os::raw::c_void,
 }
 #[test]
-fn bindgen_test_layout_cmdline_token_num_data() {
+fn bindgen_test_layout_rdline() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_num_data>(),
-        4usize,
-        concat!("Size of: ", stringify!(cmdline_token_num_data))
+        ::std::mem::size_of::<rdline>(),
+        9912usize,
+        concat!("Size of: ", stringify!(rdline))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_num_data>(),
-        4usize,
-        concat!("Alignment of ", stringify!(cmdline_token_num_data))
+        ::std::mem::align_of::<rdline>(),
+        8usize,
+        concat!("Alignment of ", stringify!(rdline))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_num_data>())).type_ as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<rdline>())).status as *const _ as usize },
         0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_num_data),
-            "::",
-            stringify!(type_)
-        )
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(status))
     );
-}
-impl Default for cmdline_token_num_data {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_num {
-    pub hdr: cmdline_token_hdr,
-    pub num_data: cmdline_token_num_data,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_num() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_num>(),
-        24usize,
-        concat!("Size of: ", stringify!(cmdline_token_num))
+        unsafe { &(*(::std::ptr::null::<rdline>())).left as *const _ as usize },
+        8usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(left))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_num>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_num))
+        unsafe { &(*(::std::ptr::null::<rdline>())).right as *const _ as usize },
+        32usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(right))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_num>())).hdr as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_num),
-            "::",
-            stringify!(hdr)
-        )
+        unsafe { &(*(::std::ptr::null::<rdline>())).left_buf as *const _ as usize },
+        56usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(left_buf))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_num>())).num_data as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_num),
-            "::",
-            stringify!(num_data)
-        )
+        unsafe { &(*(::std::ptr::null::<rdline>())).right_buf as *const _ as usize },
+        570usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(right_buf))
     );
-}
-impl Default for cmdline_token_num {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-pub type cmdline_parse_token_num_t = cmdline_token_num;
-extern "C" {
-    pub static mut cmdline_token_num_ops: cmdline_token_ops;
-}
-extern "C" {
-    pub fn cmdline_parse_num(
-        tk: *mut cmdline_parse_token_hdr_t,
-        srcbuf: *const ::std::os::raw::c_char,
-        res: *mut ::std::os::raw::c_void,
-        ressize: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_get_help_num(
-        tk: *mut cmdline_parse_token_hdr_t,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct cmdline_portlist {
-    pub map: u32,
-}
-#[test]
-fn bindgen_test_layout_cmdline_portlist() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_portlist>(),
-        4usize,
-        concat!("Size of: ", stringify!(cmdline_portlist))
+        unsafe { &(*(::std::ptr::null::<rdline>())).prompt as *const _ as usize },
+        1082usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(prompt))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_portlist>(),
-        4usize,
-        concat!("Alignment of ", stringify!(cmdline_portlist))
+        unsafe { &(*(::std::ptr::null::<rdline>())).prompt_size as *const _ as usize },
+        1116usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(prompt_size))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_portlist>())).map as *const _ as usize },
-        0usize,
-        concat!("Offset of field: ", stringify!(cmdline_portlist), "::", stringify!(map))
+        unsafe { &(*(::std::ptr::null::<rdline>())).kill_buf as *const _ as usize },
+        1120usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(kill_buf))
     );
-}
-pub type cmdline_portlist_t = cmdline_portlist;
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_portlist {
-    pub hdr: cmdline_token_hdr,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_portlist() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_portlist>(),
-        16usize,
-        concat!("Size of: ", stringify!(cmdline_token_portlist))
+        unsafe { &(*(::std::ptr::null::<rdline>())).kill_size as *const _ as usize },
+        1632usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(kill_size))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_portlist>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_portlist))
+        unsafe { &(*(::std::ptr::null::<rdline>())).history as *const _ as usize },
+        1640usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(history))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_portlist>())).hdr as *const _ as usize },
-        0usize,
+        unsafe { &(*(::std::ptr::null::<rdline>())).history_buf as *const _ as usize },
+        1664usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(history_buf))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rdline>())).history_cur_line as *const _ as usize },
+        9856usize,
         concat!(
             "Offset of field: ",
-            stringify!(cmdline_token_portlist),
+            stringify!(rdline),
             "::",
-            stringify!(hdr)
+            stringify!(history_cur_line)
         )
     );
-}
-impl Default for cmdline_token_portlist {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
-}
-pub type cmdline_parse_token_portlist_t = cmdline_token_portlist;
-extern "C" {
-    pub static mut cmdline_token_portlist_ops: cmdline_token_ops;
-}
-extern "C" {
-    pub fn cmdline_parse_portlist(
-        tk: *mut cmdline_parse_token_hdr_t,
-        srcbuf: *const ::std::os::raw::c_char,
-        res: *mut ::std::os::raw::c_void,
-        ressize: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_get_help_portlist(
-        tk: *mut cmdline_parse_token_hdr_t,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-pub type cmdline_fixed_string_t = [::std::os::raw::c_char; 128usize];
-pub type cmdline_multi_string_t = [::std::os::raw::c_char; 4096usize];
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_string_data {
-    pub str: *const ::std::os::raw::c_char,
-}
-#[test]
-fn bindgen_test_layout_cmdline_token_string_data() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_string_data>(),
-        8usize,
-        concat!("Size of: ", stringify!(cmdline_token_string_data))
+        unsafe { &(*(::std::ptr::null::<rdline>())).write_char as *const _ as usize },
+        9864usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(write_char))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_string_data>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_string_data))
+        unsafe { &(*(::std::ptr::null::<rdline>())).validate as *const _ as usize },
+        9872usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(validate))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_string_data>())).str as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(cmdline_token_string_data),
-            "::",
-            stringify!(str)
-        )
+        unsafe { &(*(::std::ptr::null::<rdline>())).complete as *const _ as usize },
+        9880usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(complete))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rdline>())).vt100 as *const _ as usize },
+        9888usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(vt100))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<rdline>())).opaque as *const _ as usize },
+        9904usize,
+        concat!("Offset of field: ", stringify!(rdline), "::", stringify!(opaque))
     );
 }
-impl Default for cmdline_token_string_data {
+impl Default for rdline {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
+#[doc = " Stores a pointer to the ops struct, and the offset: the place to"]
+#[doc = " write the parsed result in the destination structure."]
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct cmdline_token_string {
-    pub hdr: cmdline_token_hdr,
-    pub string_data: cmdline_token_string_data,
+pub struct cmdline_token_hdr {
+    pub ops: *mut cmdline_token_ops,
+    pub offset: ::std::os::raw::c_uint,
 }
 #[test]
-fn bindgen_test_layout_cmdline_token_string() {
+fn bindgen_test_layout_cmdline_token_hdr() {
     assert_eq!(
-        ::std::mem::size_of::<cmdline_token_string>(),
-        24usize,
-        concat!("Size of: ", stringify!(cmdline_token_string))
+        ::std::mem::size_of::<cmdline_token_hdr>(),
+        16usize,
+        concat!("Size of: ", stringify!(cmdline_token_hdr))
     );
     assert_eq!(
-        ::std::mem::align_of::<cmdline_token_string>(),
+        ::std::mem::align_of::<cmdline_token_hdr>(),
         8usize,
-        concat!("Alignment of ", stringify!(cmdline_token_string))
+        concat!("Alignment of ", stringify!(cmdline_token_hdr))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_string>())).hdr as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cmdline_token_hdr>())).ops as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
-            stringify!(cmdline_token_string),
+            stringify!(cmdline_token_hdr),
             "::",
-            stringify!(hdr)
+            stringify!(ops)
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline_token_string>())).string_data as *const _ as usize },
-        16usize,
+        unsafe { &(*(::std::ptr::null::<cmdline_token_hdr>())).offset as *const _ as usize },
+        8usize,
         concat!(
             "Offset of field: ",
-            stringify!(cmdline_token_string),
+            stringify!(cmdline_token_hdr),
             "::",
-            stringify!(string_data)
+            stringify!(offset)
         )
     );
 }
-impl Default for cmdline_token_string {
+impl Default for cmdline_token_hdr {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
-pub type cmdline_parse_token_string_t = cmdline_token_string;
-extern "C" {
-    pub static mut cmdline_token_string_ops: cmdline_token_ops;
-}
-extern "C" {
-    pub fn cmdline_parse_string(
-        tk: *mut cmdline_parse_token_hdr_t,
-        srcbuf: *const ::std::os::raw::c_char,
-        res: *mut ::std::os::raw::c_void,
-        ressize: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_complete_get_nb_string(tk: *mut cmdline_parse_token_hdr_t) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_complete_get_elt_string(
-        tk: *mut cmdline_parse_token_hdr_t,
-        idx: ::std::os::raw::c_int,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_get_help_string(
-        tk: *mut cmdline_parse_token_hdr_t,
-        dstbuf: *mut ::std::os::raw::c_char,
-        size: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
-}
-pub type cc_t = ::std::os::raw::c_uchar;
-pub type speed_t = ::std::os::raw::c_uint;
-pub type tcflag_t = ::std::os::raw::c_uint;
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct termios {
-    pub c_iflag: tcflag_t,
-    pub c_oflag: tcflag_t,
-    pub c_cflag: tcflag_t,
-    pub c_lflag: tcflag_t,
-    pub c_line: cc_t,
-    pub c_cc: [cc_t; 32usize],
-    pub c_ispeed: speed_t,
-    pub c_ospeed: speed_t,
+pub type cmdline_parse_token_hdr_t = cmdline_token_hdr;
+#[doc = " A token is defined by this structure."]
+#[doc = ""]
+#[doc = " parse() takes the token as first argument, then the source buffer"]
+#[doc = " starting at the token we want to parse. The 3rd arg is a pointer"]
+#[doc = " where we store the parsed data (as binary). It returns the number of"]
+#[doc = " parsed chars on success and a negative value on error."]
+#[doc = ""]
+#[doc = " complete_get_nb() returns the number of possible values for this"]
+#[doc = " token if completion is possible. If it is NULL or if it returns 0,"]
+#[doc = " no completion is possible."]
+#[doc = ""]
+#[doc = " complete_get_elt() copy in dstbuf (the size is specified in the"]
+#[doc = " parameter) the i-th possible completion for this token.  returns 0"]
+#[doc = " on success or and a negative value on error."]
+#[doc = ""]
+#[doc = " get_help() fills the dstbuf with the help for the token. It returns"]
+#[doc = " -1 on error and 0 on success."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct cmdline_token_ops {
+    #[doc = " parse(token ptr, buf, res pts, buf len)"]
+    pub parse: ::std::option::Option<
+        unsafe extern "C" fn(
+            arg1: *mut cmdline_parse_token_hdr_t,
+            arg2: *const ::std::os::raw::c_char,
+            arg3: *mut ::std::os::raw::c_void,
+            arg4: ::std::os::raw::c_uint,
+        ) -> ::std::os::raw::c_int,
+    >,
+    #[doc = " return the num of possible choices for this token"]
+    pub complete_get_nb:
+        ::std::option::Option<unsafe extern "C" fn(arg1: *mut cmdline_parse_token_hdr_t) -> ::std::os::raw::c_int>,
+    #[doc = " return the elt x for this token (token, idx, dstbuf, size)"]
+    pub complete_get_elt: ::std::option::Option<
+        unsafe extern "C" fn(
+            arg1: *mut cmdline_parse_token_hdr_t,
+            arg2: ::std::os::raw::c_int,
+            arg3: *mut ::std::os::raw::c_char,
+            arg4: ::std::os::raw::c_uint,
+        ) -> ::std::os::raw::c_int,
+    >,
+    #[doc = " get help for this token (token, dstbuf, size)"]
+    pub get_help: ::std::option::Option<
+        unsafe extern "C" fn(
+            arg1: *mut cmdline_parse_token_hdr_t,
+            arg2: *mut ::std::os::raw::c_char,
+            arg3: ::std::os::raw::c_uint,
+        ) -> ::std::os::raw::c_int,
+    >,
 }
 #[test]
-fn bindgen_test_layout_termios() {
+fn bindgen_test_layout_cmdline_token_ops() {
     assert_eq!(
-        ::std::mem::size_of::<termios>(),
-        60usize,
-        concat!("Size of: ", stringify!(termios))
+        ::std::mem::size_of::<cmdline_token_ops>(),
+        32usize,
+        concat!("Size of: ", stringify!(cmdline_token_ops))
     );
     assert_eq!(
-        ::std::mem::align_of::<termios>(),
-        4usize,
-        concat!("Alignment of ", stringify!(termios))
+        ::std::mem::align_of::<cmdline_token_ops>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_ops))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_iflag as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).parse as *const _ as usize },
         0usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_iflag))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_oflag as *const _ as usize },
-        4usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_oflag))
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ops),
+            "::",
+            stringify!(parse)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_cflag as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).complete_get_nb as *const _ as usize },
         8usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_cflag))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_lflag as *const _ as usize },
-        12usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_lflag))
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ops),
+            "::",
+            stringify!(complete_get_nb)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_line as *const _ as usize },
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).complete_get_elt as *const _ as usize },
         16usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_line))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_cc as *const _ as usize },
-        17usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_cc))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_ispeed as *const _ as usize },
-        52usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_ispeed))
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ops),
+            "::",
+            stringify!(complete_get_elt)
+        )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<termios>())).c_ospeed as *const _ as usize },
-        56usize,
-        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_ospeed))
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ops>())).get_help as *const _ as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ops),
+            "::",
+            stringify!(get_help)
+        )
     );
 }
+#[doc = " Store a instruction, which is a pointer to a callback function and"]
+#[doc = " its parameter that is called when the instruction is parsed, a help"]
+#[doc = " string, and a list of token composing this instruction."]
+#[doc = ""]
+#[doc = " When no tokens are defined (tokens[0] == NULL), they are retrieved"]
+#[doc = " dynamically by calling f() as follows:"]
+#[doc = ""]
+#[doc = " @code"]
+#[doc = ""]
+#[doc = " f((struct cmdline_token_hdr **)&token_p,"]
+#[doc = "   NULL,"]
+#[doc = "   (struct cmdline_token_hdr **)&inst->tokens[num]);"]
+#[doc = ""]
+#[doc = " @endcode"]
+#[doc = ""]
+#[doc = " The address of the resulting token is expected at the location pointed by"]
+#[doc = " the first argument. Can be set to NULL to end the list."]
+#[doc = ""]
+#[doc = " The cmdline argument (struct cmdline *) is always NULL."]
+#[doc = ""]
+#[doc = " The last argument points to the inst->tokens[] entry to retrieve, which"]
+#[doc = " is not necessarily inside allocated memory and should neither be read nor"]
+#[doc = " written. Its sole purpose is to deduce the token entry index of interest"]
+#[doc = " as described in the example below."]
+#[doc = ""]
+#[doc = " Note about constraints:"]
+#[doc = ""]
+#[doc = " - Only the address of these tokens is dynamic, their storage should be"]
+#[doc = "   static like normal tokens."]
+#[doc = " - Dynamic token lists that need to maintain an internal context (e.g. in"]
+#[doc = "   order to determine the next token) must store it statically also. This"]
+#[doc = "   context must be reinitialized when the first token is requested, that"]
+#[doc = "   is, when &inst->tokens[0] is provided as the third argument."]
+#[doc = " - Dynamic token lists must be NULL-terminated to generate usable"]
+#[doc = "   commands."]
+#[doc = ""]
+#[doc = " @code"]
+#[doc = ""]
+#[doc = " // Assuming first and third arguments are respectively named \"token_p\""]
+#[doc = " // and \"token\":"]
+#[doc = ""]
+#[doc = " int index = token - inst->tokens;"]
+#[doc = ""]
+#[doc = " if (!index) {"]
+#[doc = "     [...] // Clean up internal context if any."]
+#[doc = " }"]
+#[doc = " [...] // Then set up dyn_token according to index."]
+#[doc = ""]
+#[doc = " if (no_more_tokens)"]
+#[doc = "     *token_p = NULL;"]
+#[doc = " else"]
+#[doc = "     *token_p = &dyn_token;"]
+#[doc = ""]
+#[doc = " @endcode"]
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct cmdline {
-    pub s_in: ::std::os::raw::c_int,
-    pub s_out: ::std::os::raw::c_int,
-    pub ctx: *mut cmdline_parse_ctx_t,
-    pub rdl: rdline,
-    pub prompt: [::std::os::raw::c_char; 32usize],
-    pub oldterm: termios,
+#[derive(Debug)]
+pub struct cmdline_inst {
+    pub f: ::std::option::Option<
+        unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: *mut cmdline, arg3: *mut ::std::os::raw::c_void),
+    >,
+    pub data: *mut ::std::os::raw::c_void,
+    pub help_str: *const ::std::os::raw::c_char,
+    pub tokens: __IncompleteArrayField<*mut cmdline_parse_token_hdr_t>,
 }
 #[test]
-fn bindgen_test_layout_cmdline() {
-    assert_eq!(
-        ::std::mem::size_of::<cmdline>(),
-        10024usize,
-        concat!("Size of: ", stringify!(cmdline))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<cmdline>(),
-        8usize,
-        concat!("Alignment of ", stringify!(cmdline))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).s_in as *const _ as usize },
-        0usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(s_in))
-    );
+fn bindgen_test_layout_cmdline_inst() {
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).s_out as *const _ as usize },
-        4usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(s_out))
+        ::std::mem::size_of::<cmdline_inst>(),
+        24usize,
+        concat!("Size of: ", stringify!(cmdline_inst))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).ctx as *const _ as usize },
+        ::std::mem::align_of::<cmdline_inst>(),
         8usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(ctx))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).rdl as *const _ as usize },
-        16usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(rdl))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).prompt as *const _ as usize },
-        9928usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(prompt))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<cmdline>())).oldterm as *const _ as usize },
-        9960usize,
-        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(oldterm))
+        concat!("Alignment of ", stringify!(cmdline_inst))
     );
 }
-impl Default for cmdline {
+impl Default for cmdline_inst {
     fn default() -> Self {
         unsafe { ::std::mem::zeroed() }
     }
 }
+pub type cmdline_parse_inst_t = cmdline_inst;
+#[doc = " A context is identified by its name, and contains a list of"]
+#[doc = " instruction"]
+#[doc = ""]
+pub type cmdline_parse_ctx_t = *mut cmdline_parse_inst_t;
 extern "C" {
-    pub fn cmdline_new(
-        ctx: *mut cmdline_parse_ctx_t,
-        prompt: *const ::std::os::raw::c_char,
-        s_in: ::std::os::raw::c_int,
-        s_out: ::std::os::raw::c_int,
-    ) -> *mut cmdline;
-}
-extern "C" {
-    pub fn cmdline_set_prompt(cl: *mut cmdline, prompt: *const ::std::os::raw::c_char);
-}
+    #[doc = " Try to parse a buffer according to the specified context. The"]
+    #[doc = " argument buf must ends with \"\\n\\0\". The function returns"]
+    #[doc = " CMDLINE_PARSE_AMBIGUOUS, CMDLINE_PARSE_NOMATCH or"]
+    #[doc = " CMDLINE_PARSE_BAD_ARGS on error. Else it calls the associated"]
+    #[doc = " function (defined in the context) and returns 0"]
+    #[doc = " (CMDLINE_PARSE_SUCCESS)."]
+    pub fn cmdline_parse(cl: *mut cmdline, buf: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " complete() must be called with *state==0 (try to complete) or"]
+    #[doc = " with *state==-1 (just display choices), then called without"]
+    #[doc = " modifying *state until it returns CMDLINE_PARSE_COMPLETED_BUFFER or"]
+    #[doc = " CMDLINE_PARSE_COMPLETED_BUFFER."]
+    #[doc = ""]
+    #[doc = " It returns < 0 on error."]
+    #[doc = ""]
+    #[doc = " Else it returns:"]
+    #[doc = "   - CMDLINE_PARSE_COMPLETED_BUFFER on completion (one possible"]
+    #[doc = "     choice). In this case, the chars are appended in dst buffer."]
+    #[doc = "   - CMDLINE_PARSE_COMPLETE_AGAIN if there is several possible"]
+    #[doc = "     choices. In this case, you must call the function again,"]
+    #[doc = "     keeping the value of state intact."]
+    #[doc = "   - CMDLINE_PARSE_COMPLETED_BUFFER when the iteration is"]
+    #[doc = "     finished. The dst is not valid for this last call."]
+    #[doc = ""]
+    #[doc = " The returned dst buf ends with \\0."]
+    pub fn cmdline_complete(
+        cl: *mut cmdline,
+        buf: *const ::std::os::raw::c_char,
+        state: *mut ::std::os::raw::c_int,
+        dst: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_isendoftoken(c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_isendofcommand(c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_etheraddr {
+    pub hdr: cmdline_token_hdr,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_etheraddr() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_etheraddr>(),
+        16usize,
+        concat!("Size of: ", stringify!(cmdline_token_etheraddr))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_etheraddr>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_etheraddr))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_etheraddr>())).hdr as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_etheraddr),
+            "::",
+            stringify!(hdr)
+        )
+    );
+}
+impl Default for cmdline_token_etheraddr {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_parse_token_etheraddr_t = cmdline_token_etheraddr;
+extern "C" {
+    pub static mut cmdline_token_etheraddr_ops: cmdline_token_ops;
+}
+extern "C" {
+    pub fn cmdline_parse_etheraddr(
+        tk: *mut cmdline_parse_token_hdr_t,
+        srcbuf: *const ::std::os::raw::c_char,
+        res: *mut ::std::os::raw::c_void,
+        ressize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_get_help_etheraddr(
+        tk: *mut cmdline_parse_token_hdr_t,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct cmdline_ipaddr {
+    pub family: u8,
+    pub addr: cmdline_ipaddr__bindgen_ty_1,
+    pub prefixlen: ::std::os::raw::c_uint,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union cmdline_ipaddr__bindgen_ty_1 {
+    pub ipv4: in_addr,
+    pub ipv6: in6_addr,
+    _bindgen_union_align: [u32; 4usize],
+}
+#[test]
+fn bindgen_test_layout_cmdline_ipaddr__bindgen_ty_1() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_ipaddr__bindgen_ty_1>(),
+        16usize,
+        concat!("Size of: ", stringify!(cmdline_ipaddr__bindgen_ty_1))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_ipaddr__bindgen_ty_1>(),
+        4usize,
+        concat!("Alignment of ", stringify!(cmdline_ipaddr__bindgen_ty_1))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr__bindgen_ty_1>())).ipv4 as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_ipaddr__bindgen_ty_1),
+            "::",
+            stringify!(ipv4)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr__bindgen_ty_1>())).ipv6 as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_ipaddr__bindgen_ty_1),
+            "::",
+            stringify!(ipv6)
+        )
+    );
+}
+impl Default for cmdline_ipaddr__bindgen_ty_1 {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+#[test]
+fn bindgen_test_layout_cmdline_ipaddr() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_ipaddr>(),
+        24usize,
+        concat!("Size of: ", stringify!(cmdline_ipaddr))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_ipaddr>(),
+        4usize,
+        concat!("Alignment of ", stringify!(cmdline_ipaddr))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).family as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_ipaddr),
+            "::",
+            stringify!(family)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).addr as *const _ as usize },
+        4usize,
+        concat!("Offset of field: ", stringify!(cmdline_ipaddr), "::", stringify!(addr))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_ipaddr>())).prefixlen as *const _ as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_ipaddr),
+            "::",
+            stringify!(prefixlen)
+        )
+    );
+}
+impl Default for cmdline_ipaddr {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_ipaddr_t = cmdline_ipaddr;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct cmdline_token_ipaddr_data {
+    pub flags: u8,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_ipaddr_data() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_ipaddr_data>(),
+        1usize,
+        concat!("Size of: ", stringify!(cmdline_token_ipaddr_data))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_ipaddr_data>(),
+        1usize,
+        concat!("Alignment of ", stringify!(cmdline_token_ipaddr_data))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr_data>())).flags as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ipaddr_data),
+            "::",
+            stringify!(flags)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_ipaddr {
+    pub hdr: cmdline_token_hdr,
+    pub ipaddr_data: cmdline_token_ipaddr_data,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_ipaddr() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_ipaddr>(),
+        24usize,
+        concat!("Size of: ", stringify!(cmdline_token_ipaddr))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_ipaddr>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_ipaddr))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr>())).hdr as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ipaddr),
+            "::",
+            stringify!(hdr)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_ipaddr>())).ipaddr_data as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_ipaddr),
+            "::",
+            stringify!(ipaddr_data)
+        )
+    );
+}
+impl Default for cmdline_token_ipaddr {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_parse_token_ipaddr_t = cmdline_token_ipaddr;
+extern "C" {
+    pub static mut cmdline_token_ipaddr_ops: cmdline_token_ops;
+}
+extern "C" {
+    pub fn cmdline_parse_ipaddr(
+        tk: *mut cmdline_parse_token_hdr_t,
+        srcbuf: *const ::std::os::raw::c_char,
+        res: *mut ::std::os::raw::c_void,
+        ressize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_get_help_ipaddr(
+        tk: *mut cmdline_parse_token_hdr_t,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+pub mod cmdline_numtype {
+    pub type Type = u32;
+    pub const UINT8: Type = 0;
+    pub const UINT16: Type = 1;
+    pub const UINT32: Type = 2;
+    pub const UINT64: Type = 3;
+    pub const INT8: Type = 4;
+    pub const INT16: Type = 5;
+    pub const INT32: Type = 6;
+    pub const INT64: Type = 7;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_num_data {
+    pub type_: cmdline_numtype::Type,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_num_data() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_num_data>(),
+        4usize,
+        concat!("Size of: ", stringify!(cmdline_token_num_data))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_num_data>(),
+        4usize,
+        concat!("Alignment of ", stringify!(cmdline_token_num_data))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_num_data>())).type_ as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_num_data),
+            "::",
+            stringify!(type_)
+        )
+    );
+}
+impl Default for cmdline_token_num_data {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_num {
+    pub hdr: cmdline_token_hdr,
+    pub num_data: cmdline_token_num_data,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_num() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_num>(),
+        24usize,
+        concat!("Size of: ", stringify!(cmdline_token_num))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_num>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_num))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_num>())).hdr as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_num),
+            "::",
+            stringify!(hdr)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_num>())).num_data as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_num),
+            "::",
+            stringify!(num_data)
+        )
+    );
+}
+impl Default for cmdline_token_num {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_parse_token_num_t = cmdline_token_num;
+extern "C" {
+    pub static mut cmdline_token_num_ops: cmdline_token_ops;
+}
+extern "C" {
+    pub fn cmdline_parse_num(
+        tk: *mut cmdline_parse_token_hdr_t,
+        srcbuf: *const ::std::os::raw::c_char,
+        res: *mut ::std::os::raw::c_void,
+        ressize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_get_help_num(
+        tk: *mut cmdline_parse_token_hdr_t,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct cmdline_portlist {
+    pub map: u32,
+}
+#[test]
+fn bindgen_test_layout_cmdline_portlist() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_portlist>(),
+        4usize,
+        concat!("Size of: ", stringify!(cmdline_portlist))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_portlist>(),
+        4usize,
+        concat!("Alignment of ", stringify!(cmdline_portlist))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_portlist>())).map as *const _ as usize },
+        0usize,
+        concat!("Offset of field: ", stringify!(cmdline_portlist), "::", stringify!(map))
+    );
+}
+pub type cmdline_portlist_t = cmdline_portlist;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_portlist {
+    pub hdr: cmdline_token_hdr,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_portlist() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_portlist>(),
+        16usize,
+        concat!("Size of: ", stringify!(cmdline_token_portlist))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_portlist>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_portlist))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_portlist>())).hdr as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_portlist),
+            "::",
+            stringify!(hdr)
+        )
+    );
+}
+impl Default for cmdline_token_portlist {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_parse_token_portlist_t = cmdline_token_portlist;
+extern "C" {
+    pub static mut cmdline_token_portlist_ops: cmdline_token_ops;
+}
+extern "C" {
+    pub fn cmdline_parse_portlist(
+        tk: *mut cmdline_parse_token_hdr_t,
+        srcbuf: *const ::std::os::raw::c_char,
+        res: *mut ::std::os::raw::c_void,
+        ressize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_get_help_portlist(
+        tk: *mut cmdline_parse_token_hdr_t,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+pub type cmdline_fixed_string_t = [::std::os::raw::c_char; 128usize];
+pub type cmdline_multi_string_t = [::std::os::raw::c_char; 4096usize];
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_string_data {
+    pub str: *const ::std::os::raw::c_char,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_string_data() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_string_data>(),
+        8usize,
+        concat!("Size of: ", stringify!(cmdline_token_string_data))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_string_data>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_string_data))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_string_data>())).str as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_string_data),
+            "::",
+            stringify!(str)
+        )
+    );
+}
+impl Default for cmdline_token_string_data {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct cmdline_token_string {
+    pub hdr: cmdline_token_hdr,
+    pub string_data: cmdline_token_string_data,
+}
+#[test]
+fn bindgen_test_layout_cmdline_token_string() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline_token_string>(),
+        24usize,
+        concat!("Size of: ", stringify!(cmdline_token_string))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline_token_string>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline_token_string))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_string>())).hdr as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_string),
+            "::",
+            stringify!(hdr)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline_token_string>())).string_data as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(cmdline_token_string),
+            "::",
+            stringify!(string_data)
+        )
+    );
+}
+impl Default for cmdline_token_string {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type cmdline_parse_token_string_t = cmdline_token_string;
+extern "C" {
+    pub static mut cmdline_token_string_ops: cmdline_token_ops;
+}
+extern "C" {
+    pub fn cmdline_parse_string(
+        tk: *mut cmdline_parse_token_hdr_t,
+        srcbuf: *const ::std::os::raw::c_char,
+        res: *mut ::std::os::raw::c_void,
+        ressize: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_complete_get_nb_string(tk: *mut cmdline_parse_token_hdr_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_complete_get_elt_string(
+        tk: *mut cmdline_parse_token_hdr_t,
+        idx: ::std::os::raw::c_int,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_get_help_string(
+        tk: *mut cmdline_parse_token_hdr_t,
+        dstbuf: *mut ::std::os::raw::c_char,
+        size: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+pub type cc_t = ::std::os::raw::c_uchar;
+pub type speed_t = ::std::os::raw::c_uint;
+pub type tcflag_t = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct termios {
+    pub c_iflag: tcflag_t,
+    pub c_oflag: tcflag_t,
+    pub c_cflag: tcflag_t,
+    pub c_lflag: tcflag_t,
+    pub c_line: cc_t,
+    pub c_cc: [cc_t; 32usize],
+    pub c_ispeed: speed_t,
+    pub c_ospeed: speed_t,
+}
+#[test]
+fn bindgen_test_layout_termios() {
+    assert_eq!(
+        ::std::mem::size_of::<termios>(),
+        60usize,
+        concat!("Size of: ", stringify!(termios))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<termios>(),
+        4usize,
+        concat!("Alignment of ", stringify!(termios))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_iflag as *const _ as usize },
+        0usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_iflag))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_oflag as *const _ as usize },
+        4usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_oflag))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_cflag as *const _ as usize },
+        8usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_cflag))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_lflag as *const _ as usize },
+        12usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_lflag))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_line as *const _ as usize },
+        16usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_line))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_cc as *const _ as usize },
+        17usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_cc))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_ispeed as *const _ as usize },
+        52usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_ispeed))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<termios>())).c_ospeed as *const _ as usize },
+        56usize,
+        concat!("Offset of field: ", stringify!(termios), "::", stringify!(c_ospeed))
+    );
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct cmdline {
+    pub s_in: ::std::os::raw::c_int,
+    pub s_out: ::std::os::raw::c_int,
+    pub ctx: *mut cmdline_parse_ctx_t,
+    pub rdl: rdline,
+    pub prompt: [::std::os::raw::c_char; 32usize],
+    pub oldterm: termios,
+}
+#[test]
+fn bindgen_test_layout_cmdline() {
+    assert_eq!(
+        ::std::mem::size_of::<cmdline>(),
+        10024usize,
+        concat!("Size of: ", stringify!(cmdline))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<cmdline>(),
+        8usize,
+        concat!("Alignment of ", stringify!(cmdline))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).s_in as *const _ as usize },
+        0usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(s_in))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).s_out as *const _ as usize },
+        4usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(s_out))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).ctx as *const _ as usize },
+        8usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(ctx))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).rdl as *const _ as usize },
+        16usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(rdl))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).prompt as *const _ as usize },
+        9928usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(prompt))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<cmdline>())).oldterm as *const _ as usize },
+        9960usize,
+        concat!("Offset of field: ", stringify!(cmdline), "::", stringify!(oldterm))
+    );
+}
+impl Default for cmdline {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+extern "C" {
+    pub fn cmdline_new(
+        ctx: *mut cmdline_parse_ctx_t,
+        prompt: *const ::std::os::raw::c_char,
+        s_in: ::std::os::raw::c_int,
+        s_out: ::std::os::raw::c_int,
+    ) -> *mut cmdline;
+}
+extern "C" {
+    pub fn cmdline_set_prompt(cl: *mut cmdline, prompt: *const ::std::os::raw::c_char);
+}
+extern "C" {
+    pub fn cmdline_free(cl: *mut cmdline);
+}
+extern "C" {
+    pub fn cmdline_printf(cl: *const cmdline, fmt: *const ::std::os::raw::c_char, ...);
+}
+extern "C" {
+    pub fn cmdline_in(
+        cl: *mut cmdline,
+        buf: *const ::std::os::raw::c_char,
+        size: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_write_char(rdl: *mut rdline, c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " This function is nonblocking equivalent of ``cmdline_interact()``. It polls"]
+    #[doc = " *cl* for one character and interpret it. If return value is *RDLINE_EXITED*"]
+    #[doc = " it mean that ``cmdline_quit()`` was invoked."]
+    #[doc = ""]
+    #[doc = " @param cl"]
+    #[doc = "   The command line object."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   On success return object status - one of *enum rdline_status*."]
+    #[doc = "   On error return negative value."]
+    pub fn cmdline_poll(cl: *mut cmdline) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn cmdline_interact(cl: *mut cmdline);
+}
+extern "C" {
+    pub fn cmdline_quit(cl: *mut cmdline);
+}
+extern "C" {
+    pub fn cmdline_file_new(
+        ctx: *mut cmdline_parse_ctx_t,
+        prompt: *const ::std::os::raw::c_char,
+        path: *const ::std::os::raw::c_char,
+    ) -> *mut cmdline;
+}
+extern "C" {
+    pub fn cmdline_stdin_new(ctx: *mut cmdline_parse_ctx_t, prompt: *const ::std::os::raw::c_char) -> *mut cmdline;
+}
+extern "C" {
+    pub fn cmdline_stdin_exit(cl: *mut cmdline);
+}
+extern "C" {
+    #[doc = " Seed the pseudo-random generator."]
+    #[doc = ""]
+    #[doc = " The generator is automatically seeded by the EAL init with a timer"]
+    #[doc = " value. It may need to be re-seeded by the user with a real random"]
+    #[doc = " value."]
+    #[doc = ""]
+    #[doc = " @param seedval"]
+    #[doc = "   The value of the seed."]
+    pub fn _rte_srand(seedval: u64);
+}
+extern "C" {
+    #[doc = " Get a pseudo-random value."]
+    #[doc = ""]
+    #[doc = " This function generates pseudo-random numbers using the linear"]
+    #[doc = " congruential algorithm and 48-bit integer arithmetic, called twice"]
+    #[doc = " to generate a 64-bit value."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   A pseudo-random value between 0 and (1<<64)-1."]
+    pub fn _rte_rand() -> u64;
+}
+extern "C" {
+    #[doc = " Bitmap initialization"]
+    #[doc = ""]
+    #[doc = " @param n_bits"]
+    #[doc = "   Number of pre-allocated bits in array2."]
+    #[doc = " @param mem"]
+    #[doc = "   Base address of array1 and array2."]
+    #[doc = " @param mem_size"]
+    #[doc = "   Minimum expected size of bitmap."]
+    #[doc = " @return"]
+    #[doc = "   Handle to bitmap instance."]
+    pub fn _rte_bitmap_init(n_bits: u32, mem: *mut u8, mem_size: u32) -> *mut rte_bitmap;
+}
+extern "C" {
+    #[doc = " Bitmap free"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @return"]
+    #[doc = "   0 upon success, error code otherwise"]
+    pub fn _rte_bitmap_free(bmp: *mut rte_bitmap) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Bitmap reset"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    pub fn _rte_bitmap_reset(bmp: *mut rte_bitmap);
+}
+extern "C" {
+    #[doc = " Bitmap location prefetch into CPU L1 cache"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   Bit position"]
+    #[doc = " @return"]
+    #[doc = "   0 upon success, error code otherwise"]
+    pub fn _rte_bitmap_prefetch0(bmp: *mut rte_bitmap, pos: u32);
+}
+extern "C" {
+    #[doc = " Bitmap bit get"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   Bit position"]
+    #[doc = " @return"]
+    #[doc = "   0 when bit is cleared, non-zero when bit is set"]
+    pub fn _rte_bitmap_get(bmp: *mut rte_bitmap, pos: u32) -> u64;
+}
+extern "C" {
+    #[doc = " Bitmap bit set"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   Bit position"]
+    pub fn _rte_bitmap_set(bmp: *mut rte_bitmap, pos: u32);
+}
+extern "C" {
+    #[doc = " Bitmap slab set"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   Bit position identifying the array2 slab"]
+    #[doc = " @param slab"]
+    #[doc = "   Value to be assigned to the 64-bit slab in array2"]
+    pub fn _rte_bitmap_set_slab(bmp: *mut rte_bitmap, pos: u32, slab: u64);
+}
+extern "C" {
+    #[doc = " Bitmap bit clear"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   Bit position"]
+    pub fn _rte_bitmap_clear(bmp: *mut rte_bitmap, pos: u32);
+}
+extern "C" {
+    #[doc = " Bitmap scan (with automatic wrap-around)"]
+    #[doc = ""]
+    #[doc = " @param bmp"]
+    #[doc = "   Handle to bitmap instance"]
+    #[doc = " @param pos"]
+    #[doc = "   When function call returns 1, pos contains the position of the next set"]
+    #[doc = "   bit, otherwise not modified"]
+    #[doc = " @param slab"]
+    #[doc = "   When function call returns 1, slab contains the value of the entire 64-bit"]
+    #[doc = "   slab where the bit indicated by pos is located. Slabs are always 64-bit"]
+    #[doc = "   aligned, so the position of the first bit of the slab (this bit is not"]
+    #[doc = "   necessarily set) is pos / 64. Once a slab has been returned by the bitmap"]
+    #[doc = "   scan operation, the internal pointers of the bitmap are updated to point"]
+    #[doc = "   after this slab, so the same slab will not be returned again if it"]
+    #[doc = "   contains more than one bit which is set. When function call returns 0,"]
+    #[doc = "   slab is not modified."]
+    #[doc = " @return"]
+    #[doc = "   0 if there is no bit set in the bitmap, 1 otherwise"]
+    pub fn _rte_bitmap_scan(bmp: *mut rte_bitmap, pos: *mut u32, slab: *mut u64) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Bitmap memory footprint calculation"]
+    #[doc = ""]
+    #[doc = " @param n_bits"]
+    #[doc = "   Number of bits in the bitmap"]
+    #[doc = " @return"]
+    #[doc = "   Bitmap memory footprint measured in bytes on success, 0 on error"]
+    pub fn _rte_bitmap_get_memory_footprint(n_bits: u32) -> u32;
+}
+extern "C" {
+    #[doc = " Initialize the spinlock to an unlocked state."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    pub fn _rte_spinlock_init(sl: *mut rte_spinlock_t);
+}
+extern "C" {
+    #[doc = " Take the spinlock."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    pub fn _rte_spinlock_lock(sl: *mut rte_spinlock_t);
+}
+extern "C" {
+    #[doc = " Release the spinlock."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    pub fn _rte_spinlock_unlock(sl: *mut rte_spinlock_t);
+}
+extern "C" {
+    #[doc = " Try to take the lock."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    #[doc = " @return"]
+    #[doc = "   1 if the lock is successfully taken; 0 otherwise."]
+    pub fn _rte_spinlock_trylock(sl: *mut rte_spinlock_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Test if hardware transactional memory (lock elision) is supported"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   1 if the hardware transactional memory is supported; 0 otherwise."]
+    pub fn _rte_tm_supported() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Try to execute critical section in a hardware memory transaction,"]
+    #[doc = " if it fails or not available take the spinlock."]
+    #[doc = ""]
+    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
+    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
+    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
+    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
+    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    pub fn _rte_spinlock_lock_tm(sl: *mut rte_spinlock_t);
+}
+extern "C" {
+    #[doc = " Try to execute critical section in a hardware memory transaction,"]
+    #[doc = " if it fails or not available try to take the lock."]
+    #[doc = ""]
+    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
+    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
+    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
+    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
+    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    #[doc = " @return"]
+    #[doc = "   1 if the hardware memory transaction is successfully started"]
+    #[doc = "   or lock is successfully taken; 0 otherwise."]
+    pub fn _rte_spinlock_trylock_tm(sl: *mut rte_spinlock_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Commit hardware memory transaction or release the spinlock if"]
+    #[doc = " the spinlock is used as a fall-back"]
+    #[doc = ""]
+    #[doc = " @param sl"]
+    #[doc = "   A pointer to the spinlock."]
+    pub fn _rte_spinlock_unlock_tm(sl: *mut rte_spinlock_t);
+}
+extern "C" {
+    #[doc = " Initialize the recursive spinlock to an unlocked state."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    pub fn _rte_spinlock_recursive_init(slr: *mut rte_spinlock_recursive_t);
+}
+extern "C" {
+    #[doc = " Take the recursive spinlock."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    pub fn _rte_spinlock_recursive_lock(slr: *mut rte_spinlock_recursive_t);
+}
+extern "C" {
+    #[doc = " Release the recursive spinlock."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    pub fn _rte_spinlock_recursive_unlock(slr: *mut rte_spinlock_recursive_t);
+}
+extern "C" {
+    #[doc = " Try to take the recursive lock."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    #[doc = " @return"]
+    #[doc = "   1 if the lock is successfully taken; 0 otherwise."]
+    pub fn _rte_spinlock_recursive_trylock(slr: *mut rte_spinlock_recursive_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Try to execute critical section in a hardware memory transaction,"]
+    #[doc = " if it fails or not available take the recursive spinlocks"]
+    #[doc = ""]
+    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
+    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
+    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
+    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
+    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    pub fn _rte_spinlock_recursive_lock_tm(slr: *mut rte_spinlock_recursive_t);
+}
+extern "C" {
+    #[doc = " Commit hardware memory transaction or release the recursive spinlock"]
+    #[doc = " if the recursive spinlock is used as a fall-back"]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    pub fn _rte_spinlock_recursive_unlock_tm(slr: *mut rte_spinlock_recursive_t);
+}
+extern "C" {
+    #[doc = " Try to execute critical section in a hardware memory transaction,"]
+    #[doc = " if it fails or not available try to take the recursive lock"]
+    #[doc = ""]
+    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
+    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
+    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
+    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
+    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = ""]
+    #[doc = " @param slr"]
+    #[doc = "   A pointer to the recursive spinlock."]
+    #[doc = " @return"]
+    #[doc = "   1 if the hardware memory transaction is successfully started"]
+    #[doc = "   or lock is successfully taken; 0 otherwise."]
+    pub fn _rte_spinlock_recursive_trylock_tm(slr: *mut rte_spinlock_recursive_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Return the Application thread ID of the execution unit."]
+    #[doc = ""]
+    #[doc = " Note: in most cases the lcore id returned here will also correspond"]
+    #[doc = "   to the processor id of the CPU on which the thread is pinned, this"]
+    #[doc = "   will not be the case if the user has explicitly changed the thread to"]
+    #[doc = "   core affinities using --lcores EAL argument e.g. --lcores '(0-3)@10'"]
+    #[doc = "   to run threads with lcore IDs 0, 1, 2 and 3 on physical core 10.."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "  Logical core ID (in EAL thread) or LCORE_ID_ANY (in non-EAL thread)"]
+    pub fn _rte_lcore_id() -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Error number value, stored per-thread, which can be queried after"]
+    #[doc = " calls to certain functions to determine why those functions failed."]
+    #[doc = ""]
+    #[doc = " Uses standard values from errno.h wherever possible, with a small number"]
+    #[doc = " of additional possible values for RTE-specific conditions."]
+    pub fn _rte_errno() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Return the number of TSC cycles since boot"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   the number of cycles"]
+    pub fn _rte_get_tsc_cycles() -> u64;
+}
+extern "C" {
+    #[doc = " Get the number of cycles since boot from the default timer."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of cycles"]
+    pub fn _rte_get_timer_cycles() -> u64;
+}
+extern "C" {
+    #[doc = " Get the number of cycles in one second for the default timer."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   The number of cycles in one second."]
+    pub fn _rte_get_timer_hz() -> u64;
+}
+extern "C" {
+    #[doc = " Wait at least ms milliseconds."]
+    #[doc = ""]
+    #[doc = " @param ms"]
+    #[doc = "   The number of milliseconds to wait."]
+    pub fn _rte_delay_ms(ms: ::std::os::raw::c_uint);
+}
+extern "C" {
+    pub fn _rte_rdtsc() -> u64;
+}
+extern "C" {
+    pub fn _rte_rdtsc_precise() -> u64;
+}
+extern "C" {
+    #[doc = " Pause CPU execution for a short while"]
+    #[doc = ""]
+    #[doc = " This call is intended for tight loops which poll a shared resource or wait"]
+    #[doc = " for an event. A short pause within the loop may reduce the power consumption."]
+    pub fn _rte_pause();
+}
+extern "C" {
+    #[doc = " Enqueue one object on a ring."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-producer or the single-producer"]
+    #[doc = " version, depending on the default behaviour that was specified at"]
+    #[doc = " ring creation time (see flags)."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @param obj"]
+    #[doc = "   A pointer to the object to be added."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success; objects enqueued."]
+    #[doc = "   - -ENOBUFS: Not enough room in the ring to enqueue; no object is enqueued."]
+    pub fn _rte_ring_enqueue(r: *mut rte_ring, obj: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Dequeue one object from a ring."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-consumers or the single-consumer"]
+    #[doc = " version depending on the default behaviour that was specified at"]
+    #[doc = " ring creation time (see flags)."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @param obj_p"]
+    #[doc = "   A pointer to a void * pointer (object) that will be filled."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success, objects dequeued."]
+    #[doc = "   - -ENOENT: Not enough entries in the ring to dequeue, no object is"]
+    #[doc = "     dequeued."]
+    pub fn _rte_ring_dequeue(r: *mut rte_ring, obj_p: *mut *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Enqueue several objects on a ring."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects)."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to add in the ring from the obj_table."]
+    #[doc = " @param free_space"]
+    #[doc = "   if non-NULL, returns the amount of space in the ring after the"]
+    #[doc = "   enqueue operation has finished."]
+    #[doc = " @return"]
+    #[doc = "   - n: Actual number of objects enqueued."]
+    pub fn _rte_ring_enqueue_burst(
+        r: *mut rte_ring,
+        obj_table: *const *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+        free_space: *mut ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Dequeue multiple objects from a ring up to a maximum number."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects) that will be filled."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to dequeue from the ring to the obj_table."]
+    #[doc = " @param available"]
+    #[doc = "   If non-NULL, returns the number of remaining ring entries after the"]
+    #[doc = "   dequeue has finished."]
+    #[doc = " @return"]
+    #[doc = "   - Number of objects dequeued"]
+    pub fn _rte_ring_dequeue_burst(
+        r: *mut rte_ring,
+        obj_table: *mut *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+        available: *mut ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Return the number of entries in a ring."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @return"]
+    #[doc = "   The number of entries in the ring."]
+    pub fn _rte_ring_count(r: *const rte_ring) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Return the number of free entries in a ring."]
+    #[doc = ""]
+    #[doc = " @param r"]
+    #[doc = "   A pointer to the ring structure."]
+    #[doc = " @return"]
+    #[doc = "   The number of free entries in the ring."]
+    pub fn _rte_ring_free_count(r: *const rte_ring) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Return a pointer to the mempool owning this object."]
+    #[doc = ""]
+    #[doc = " @param obj"]
+    #[doc = "   An object that is owned by a pool. If this is not the case,"]
+    #[doc = "   the behavior is undefined."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the mempool structure."]
+    pub fn _rte_mempool_from_obj(obj: *mut ::std::os::raw::c_void) -> *mut rte_mempool;
+}
+extern "C" {
+    #[doc = " Return the IO address of elt, which is an element of the pool mp."]
+    #[doc = ""]
+    #[doc = " @param elt"]
+    #[doc = "   A pointer (virtual address) to the element of the pool."]
+    #[doc = " @return"]
+    #[doc = "   The IO address of the elt element."]
+    #[doc = "   If the mempool was created with MEMPOOL_F_NO_IOVA_CONTIG, the"]
+    #[doc = "   returned value is RTE_BAD_IOVA."]
+    pub fn _rte_mempool_virt2iova(elt: *const ::std::os::raw::c_void) -> rte_iova_t;
+}
+extern "C" {
+    #[doc = " Return a pointer to the private data in an mempool structure."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the private data."]
+    pub fn _rte_mempool_get_priv(mp: *mut rte_mempool) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    #[doc = " Flush a user-owned mempool cache to the specified mempool."]
+    #[doc = ""]
+    #[doc = " @param cache"]
+    #[doc = "   A pointer to the mempool cache."]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool."]
+    pub fn _rte_mempool_cache_flush(cache: *mut rte_mempool_cache, mp: *mut rte_mempool);
+}
+extern "C" {
+    #[doc = " Get a pointer to the per-lcore default mempool cache."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param lcore_id"]
+    #[doc = "   The logical core id."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the mempool cache or NULL if disabled or non-EAL thread."]
+    pub fn _rte_mempool_default_cache(mp: *mut rte_mempool, lcore_id: ::std::os::raw::c_uint)
+        -> *mut rte_mempool_cache;
+}
+extern "C" {
+    #[doc = " Put several objects back in the mempool."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects)."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to add in the mempool from the obj_table."]
+    #[doc = " @param cache"]
+    #[doc = "   A pointer to a mempool cache structure. May be NULL if not needed."]
+    pub fn _rte_mempool_generic_put(
+        mp: *mut rte_mempool,
+        obj_table: *const *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+        cache: *mut rte_mempool_cache,
+    );
+}
+extern "C" {
+    #[doc = " Put several objects back in the mempool."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-producer or the single-producer"]
+    #[doc = " version depending on the default behavior that was specified at"]
+    #[doc = " mempool creation time (see flags)."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects)."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to add in the mempool from obj_table."]
+    pub fn _rte_mempool_put_bulk(
+        mp: *mut rte_mempool,
+        obj_table: *const *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+    );
+}
+extern "C" {
+    #[doc = " Put one object back in the mempool."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-producer or the single-producer"]
+    #[doc = " version depending on the default behavior that was specified at"]
+    #[doc = " mempool creation time (see flags)."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj"]
+    #[doc = "   A pointer to the object to be added."]
+    pub fn _rte_mempool_put(mp: *mut rte_mempool, obj: *mut ::std::os::raw::c_void);
+}
+extern "C" {
+    #[doc = " Get several objects from the mempool."]
+    #[doc = ""]
+    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
+    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
+    #[doc = " the local cache and common pool are empty, even if cache from other"]
+    #[doc = " lcores are full."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects) that will be filled."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to get from mempool to obj_table."]
+    #[doc = " @param cache"]
+    #[doc = "   A pointer to a mempool cache structure. May be NULL if not needed."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success; objects taken."]
+    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
+    pub fn _rte_mempool_generic_get(
+        mp: *mut rte_mempool,
+        obj_table: *mut *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+        cache: *mut rte_mempool_cache,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Get several objects from the mempool."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-consumers or the single-consumer"]
+    #[doc = " version, depending on the default behaviour that was specified at"]
+    #[doc = " mempool creation time (see flags)."]
+    #[doc = ""]
+    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
+    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
+    #[doc = " the local cache and common pool are empty, even if cache from other"]
+    #[doc = " lcores are full."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj_table"]
+    #[doc = "   A pointer to a table of void * pointers (objects) that will be filled."]
+    #[doc = " @param n"]
+    #[doc = "   The number of objects to get from the mempool to obj_table."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success; objects taken"]
+    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
+    pub fn _rte_mempool_get_bulk(
+        mp: *mut rte_mempool,
+        obj_table: *mut *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Get one object from the mempool."]
+    #[doc = ""]
+    #[doc = " This function calls the multi-consumers or the single-consumer"]
+    #[doc = " version, depending on the default behavior that was specified at"]
+    #[doc = " mempool creation (see flags)."]
+    #[doc = ""]
+    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
+    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
+    #[doc = " the local cache and common pool are empty, even if cache from other"]
+    #[doc = " lcores are full."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param obj_p"]
+    #[doc = "   A pointer to a void * pointer (object) that will be filled."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success; objects taken."]
+    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
+    pub fn _rte_mempool_get(mp: *mut rte_mempool, obj_p: *mut *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " @warning"]
+    #[doc = " @b EXPERIMENTAL: this API may change without prior notice."]
+    #[doc = ""]
+    #[doc = " Get a contiguous blocks of objects from the mempool."]
+    #[doc = ""]
+    #[doc = " If cache is enabled, consider to flush it first, to reuse objects"]
+    #[doc = " as soon as possible."]
+    #[doc = ""]
+    #[doc = " The application should check that the driver supports the operation"]
+    #[doc = " by calling rte_mempool_ops_get_info() and checking that `contig_block_size`"]
+    #[doc = " is not zero."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   A pointer to the mempool structure."]
+    #[doc = " @param first_obj_table"]
+    #[doc = "   A pointer to a pointer to the first object in each block."]
+    #[doc = " @param n"]
+    #[doc = "   The number of blocks to get from mempool."]
+    #[doc = " @return"]
+    #[doc = "   - 0: Success; blocks taken."]
+    #[doc = "   - -ENOBUFS: Not enough entries in the mempool; no object is retrieved."]
+    #[doc = "   - -EOPNOTSUPP: The mempool driver does not support block dequeue"]
+    pub fn _rte_mempool_get_contig_blocks(
+        mp: *mut rte_mempool,
+        first_obj_table: *mut *mut ::std::os::raw::c_void,
+        n: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Prefetch the first part of the mbuf"]
+    #[doc = ""]
+    #[doc = " The first 64 bytes of the mbuf corresponds to fields that are used early"]
+    #[doc = " in the receive path. If the cache line of the architecture is higher than"]
+    #[doc = " 64B, the second part will also be prefetched."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pointer to the mbuf."]
+    pub fn _rte_mbuf_prefetch_part1(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Prefetch the second part of the mbuf"]
+    #[doc = ""]
+    #[doc = " The next 64 bytes of the mbuf corresponds to fields that are used in the"]
+    #[doc = " transmit path. If the cache line of the architecture is higher than 64B,"]
+    #[doc = " this function does nothing as it is expected that the full mbuf is"]
+    #[doc = " already in cache."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pointer to the mbuf."]
+    pub fn _rte_mbuf_prefetch_part2(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Return the IO address of the beginning of the mbuf data"]
+    #[doc = ""]
+    #[doc = " @param mb"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The IO address of the beginning of the mbuf data"]
+    pub fn _rte_mbuf_data_iova(mb: *const rte_mbuf) -> rte_iova_t;
+}
+extern "C" {
+    #[doc = " Return the default IO address of the beginning of the mbuf data"]
+    #[doc = ""]
+    #[doc = " This function is used by drivers in their receive function, as it"]
+    #[doc = " returns the location where data should be written by the NIC, taking"]
+    #[doc = " the default headroom in account."]
+    #[doc = ""]
+    #[doc = " @param mb"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The IO address of the beginning of the mbuf data"]
+    pub fn _rte_mbuf_data_iova_default(mb: *const rte_mbuf) -> rte_iova_t;
+}
+extern "C" {
+    #[doc = " Return the mbuf owning the data buffer address of an indirect mbuf."]
+    #[doc = ""]
+    #[doc = " @param mi"]
+    #[doc = "   The pointer to the indirect mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The address of the direct mbuf corresponding to buffer_addr."]
+    pub fn _rte_mbuf_from_indirect(mi: *mut rte_mbuf) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Return the buffer address embedded in the given mbuf."]
+    #[doc = ""]
+    #[doc = " @param md"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The address of the data buffer owned by the mbuf."]
+    pub fn _rte_mbuf_to_baddr(md: *mut rte_mbuf) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    #[doc = " Return the starting address of the private data area embedded in"]
+    #[doc = " the given mbuf."]
+    #[doc = ""]
+    #[doc = " Note that no check is made to ensure that a private data area"]
+    #[doc = " actually exists in the supplied mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The starting address of the private data area of the given mbuf."]
+    pub fn _rte_mbuf_to_priv(m: *mut rte_mbuf) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    #[doc = " Reads the value of an mbuf's refcnt."]
+    #[doc = " @param m"]
+    #[doc = "   Mbuf to read"]
+    #[doc = " @return"]
+    #[doc = "   Reference count number."]
+    pub fn _rte_mbuf_refcnt_read(m: *const rte_mbuf) -> u16;
+}
+extern "C" {
+    #[doc = " Sets an mbuf's refcnt to a defined value."]
+    #[doc = " @param m"]
+    #[doc = "   Mbuf to update"]
+    #[doc = " @param new_value"]
+    #[doc = "   Value set"]
+    pub fn _rte_mbuf_refcnt_set(m: *mut rte_mbuf, new_value: u16);
+}
+extern "C" {
+    #[doc = " Adds given value to an mbuf's refcnt and returns its new value."]
+    #[doc = " @param m"]
+    #[doc = "   Mbuf to update"]
+    #[doc = " @param value"]
+    #[doc = "   Value to add/subtract"]
+    #[doc = " @return"]
+    #[doc = "   Updated value"]
+    pub fn _rte_mbuf_refcnt_update(m: *mut rte_mbuf, value: i16) -> u16;
+}
+extern "C" {
+    #[doc = " Reads the refcnt of an external buffer."]
+    #[doc = ""]
+    #[doc = " @param shinfo"]
+    #[doc = "   Shared data of the external buffer."]
+    #[doc = " @return"]
+    #[doc = "   Reference count number."]
+    pub fn _rte_mbuf_ext_refcnt_read(shinfo: *const rte_mbuf_ext_shared_info) -> u16;
+}
+extern "C" {
+    #[doc = " Set refcnt of an external buffer."]
+    #[doc = ""]
+    #[doc = " @param shinfo"]
+    #[doc = "   Shared data of the external buffer."]
+    #[doc = " @param new_value"]
+    #[doc = "   Value set"]
+    pub fn _rte_mbuf_ext_refcnt_set(shinfo: *mut rte_mbuf_ext_shared_info, new_value: u16);
+}
+extern "C" {
+    #[doc = " Add given value to refcnt of an external buffer and return its new"]
+    #[doc = " value."]
+    #[doc = ""]
+    #[doc = " @param shinfo"]
+    #[doc = "   Shared data of the external buffer."]
+    #[doc = " @param value"]
+    #[doc = "   Value to add/subtract"]
+    #[doc = " @return"]
+    #[doc = "   Updated value"]
+    pub fn _rte_mbuf_ext_refcnt_update(shinfo: *mut rte_mbuf_ext_shared_info, value: i16) -> u16;
+}
+extern "C" {
+    #[doc = " Allocate an uninitialized mbuf from mempool *mp*."]
+    #[doc = ""]
+    #[doc = " This function can be used by PMDs (especially in RX functions) to"]
+    #[doc = " allocate an uninitialized mbuf. The driver is responsible of"]
+    #[doc = " initializing all the required fields. See rte_pktmbuf_reset()."]
+    #[doc = " For standard needs, prefer rte_pktmbuf_alloc()."]
+    #[doc = ""]
+    #[doc = " The caller can expect that the following fields of the mbuf structure"]
+    #[doc = " are initialized: buf_addr, buf_iova, buf_len, refcnt=1, nb_segs=1,"]
+    #[doc = " next=NULL, pool, priv_size. The other fields must be initialized"]
+    #[doc = " by the caller."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   The mempool from which mbuf is allocated."]
+    #[doc = " @return"]
+    #[doc = "   - The pointer to the new mbuf on success."]
+    #[doc = "   - NULL if allocation failed."]
+    pub fn _rte_mbuf_raw_alloc(mp: *mut rte_mempool) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Put mbuf back into its original mempool."]
+    #[doc = ""]
+    #[doc = " The caller must ensure that the mbuf is direct and properly"]
+    #[doc = " reinitialized (refcnt=1, next=NULL, nb_segs=1), as done by"]
+    #[doc = " rte_pktmbuf_prefree_seg()."]
+    #[doc = ""]
+    #[doc = " This function should be used with care, when optimization is"]
+    #[doc = " required. For standard needs, prefer rte_pktmbuf_free() or"]
+    #[doc = " rte_pktmbuf_free_seg()."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The mbuf to be freed."]
+    pub fn _rte_mbuf_raw_free(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Get the data room size of mbufs stored in a pktmbuf_pool"]
+    #[doc = ""]
+    #[doc = " The data room size is the amount of data that can be stored in a"]
+    #[doc = " mbuf including the headroom (RTE_PKTMBUF_HEADROOM)."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   The packet mbuf pool."]
+    #[doc = " @return"]
+    #[doc = "   The data room size of mbufs stored in this mempool."]
+    pub fn _rte_pktmbuf_data_room_size(mp: *mut rte_mempool) -> u16;
+}
+extern "C" {
+    #[doc = " Get the application private size of mbufs stored in a pktmbuf_pool"]
+    #[doc = ""]
+    #[doc = " The private size of mbuf is a zone located between the rte_mbuf"]
+    #[doc = " structure and the data buffer where an application can store data"]
+    #[doc = " associated to a packet."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   The packet mbuf pool."]
+    #[doc = " @return"]
+    #[doc = "   The private size of mbufs stored in this mempool."]
+    pub fn _rte_pktmbuf_priv_size(mp: *mut rte_mempool) -> u16;
+}
+extern "C" {
+    #[doc = " Reset the data_off field of a packet mbuf to its default value."]
+    #[doc = ""]
+    #[doc = " The given mbuf must have only one segment, which should be empty."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf's data_off field has to be reset."]
+    pub fn _rte_pktmbuf_reset_headroom(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Reset the fields of a packet mbuf to their default values."]
+    #[doc = ""]
+    #[doc = " The given mbuf must have only one segment."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf to be resetted."]
+    pub fn _rte_pktmbuf_reset(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Allocate a new mbuf from a mempool."]
+    #[doc = ""]
+    #[doc = " This new mbuf contains one segment, which has a length of 0. The pointer"]
+    #[doc = " to data is initialized to have some bytes of headroom in the buffer"]
+    #[doc = " (if buffer size allows)."]
+    #[doc = ""]
+    #[doc = " @param mp"]
+    #[doc = "   The mempool from which the mbuf is allocated."]
+    #[doc = " @return"]
+    #[doc = "   - The pointer to the new mbuf on success."]
+    #[doc = "   - NULL if allocation failed."]
+    pub fn _rte_pktmbuf_alloc(mp: *mut rte_mempool) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Allocate a bulk of mbufs, initialize refcnt and reset the fields to default"]
+    #[doc = " values."]
+    #[doc = ""]
+    #[doc = "  @param pool"]
+    #[doc = "    The mempool from which mbufs are allocated."]
+    #[doc = "  @param mbufs"]
+    #[doc = "    Array of pointers to mbufs"]
+    #[doc = "  @param count"]
+    #[doc = "    Array size"]
+    #[doc = "  @return"]
+    #[doc = "   - 0: Success"]
+    #[doc = "   - -ENOENT: Not enough entries in the mempool; no mbufs are retrieved."]
+    pub fn _rte_pktmbuf_alloc_bulk(
+        pool: *mut rte_mempool,
+        mbufs: *mut *mut rte_mbuf,
+        count: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Initialize shared data at the end of an external buffer before attaching"]
+    #[doc = " to a mbuf by ``rte_pktmbuf_attach_extbuf()``. This is not a mandatory"]
+    #[doc = " initialization but a helper function to simply spare a few bytes at the"]
+    #[doc = " end of the buffer for shared data. If shared data is allocated"]
+    #[doc = " separately, this should not be called but application has to properly"]
+    #[doc = " initialize the shared data according to its need."]
+    #[doc = ""]
+    #[doc = " Free callback and its argument is saved and the refcnt is set to 1."]
+    #[doc = ""]
+    #[doc = " @warning"]
+    #[doc = " The value of buf_len will be reduced to RTE_PTR_DIFF(shinfo, buf_addr)"]
+    #[doc = " after this initialization. This shall be used for"]
+    #[doc = " ``rte_pktmbuf_attach_extbuf()``"]
+    #[doc = ""]
+    #[doc = " @param buf_addr"]
+    #[doc = "   The pointer to the external buffer."]
+    #[doc = " @param [in,out] buf_len"]
+    #[doc = "   The pointer to length of the external buffer. Input value must be"]
+    #[doc = "   larger than the size of ``struct rte_mbuf_ext_shared_info`` and"]
+    #[doc = "   padding for alignment. If not enough, this function will return NULL."]
+    #[doc = "   Adjusted buffer length will be returned through this pointer."]
+    #[doc = " @param free_cb"]
+    #[doc = "   Free callback function to call when the external buffer needs to be"]
+    #[doc = "   freed."]
+    #[doc = " @param fcb_opaque"]
+    #[doc = "   Argument for the free callback function."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the initialized shared data on success, return NULL"]
+    #[doc = "   otherwise."]
+    pub fn _rte_pktmbuf_ext_shinfo_init_helper(
+        buf_addr: *mut ::std::os::raw::c_void,
+        buf_len: *mut u16,
+        free_cb: rte_mbuf_extbuf_free_callback_t,
+        fcb_opaque: *mut ::std::os::raw::c_void,
+    ) -> *mut rte_mbuf_ext_shared_info;
+}
+extern "C" {
+    #[doc = " Attach an external buffer to a mbuf."]
+    #[doc = ""]
+    #[doc = " User-managed anonymous buffer can be attached to an mbuf. When attaching"]
+    #[doc = " it, corresponding free callback function and its argument should be"]
+    #[doc = " provided via shinfo. This callback function will be called once all the"]
+    #[doc = " mbufs are detached from the buffer (refcnt becomes zero)."]
+    #[doc = ""]
+    #[doc = " The headroom for the attaching mbuf will be set to zero and this can be"]
+    #[doc = " properly adjusted after attachment. For example, ``rte_pktmbuf_adj()``"]
+    #[doc = " or ``rte_pktmbuf_reset_headroom()`` might be used."]
+    #[doc = ""]
+    #[doc = " More mbufs can be attached to the same external buffer by"]
+    #[doc = " ``rte_pktmbuf_attach()`` once the external buffer has been attached by"]
+    #[doc = " this API."]
+    #[doc = ""]
+    #[doc = " Detachment can be done by either ``rte_pktmbuf_detach_extbuf()`` or"]
+    #[doc = " ``rte_pktmbuf_detach()``."]
+    #[doc = ""]
+    #[doc = " Memory for shared data must be provided and user must initialize all of"]
+    #[doc = " the content properly, escpecially free callback and refcnt. The pointer"]
+    #[doc = " of shared data will be stored in m->shinfo."]
+    #[doc = " ``rte_pktmbuf_ext_shinfo_init_helper`` can help to simply spare a few"]
+    #[doc = " bytes at the end of buffer for the shared data, store free callback and"]
+    #[doc = " its argument and set the refcnt to 1. The following is an example:"]
+    #[doc = ""]
+    #[doc = "   struct rte_mbuf_ext_shared_info *shinfo ="]
+    #[doc = "          rte_pktmbuf_ext_shinfo_init_helper(buf_addr, &buf_len,"]
+    #[doc = "                                             free_cb, fcb_arg);"]
+    #[doc = "   rte_pktmbuf_attach_extbuf(m, buf_addr, buf_iova, buf_len, shinfo);"]
+    #[doc = "   rte_pktmbuf_reset_headroom(m);"]
+    #[doc = "   rte_pktmbuf_adj(m, data_len);"]
+    #[doc = ""]
+    #[doc = " Attaching an external buffer is quite similar to mbuf indirection in"]
+    #[doc = " replacing buffer addresses and length of a mbuf, but a few differences:"]
+    #[doc = " - When an indirect mbuf is attached, refcnt of the direct mbuf would be"]
+    #[doc = "   2 as long as the direct mbuf itself isn't freed after the attachment."]
+    #[doc = "   In such cases, the buffer area of a direct mbuf must be read-only. But"]
+    #[doc = "   external buffer has its own refcnt and it starts from 1. Unless"]
+    #[doc = "   multiple mbufs are attached to a mbuf having an external buffer, the"]
+    #[doc = "   external buffer is writable."]
+    #[doc = " - There's no need to allocate buffer from a mempool. Any buffer can be"]
+    #[doc = "   attached with appropriate free callback and its IO address."]
+    #[doc = " - Smaller metadata is required to maintain shared data such as refcnt."]
+    #[doc = ""]
+    #[doc = " @warning"]
+    #[doc = " @b EXPERIMENTAL: This API may change without prior notice."]
+    #[doc = " Once external buffer is enabled by allowing experimental API,"]
+    #[doc = " ``RTE_MBUF_DIRECT()`` and ``RTE_MBUF_INDIRECT()`` are no longer"]
+    #[doc = " exclusive. A mbuf can be considered direct if it is neither indirect nor"]
+    #[doc = " having external buffer."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @param buf_addr"]
+    #[doc = "   The pointer to the external buffer."]
+    #[doc = " @param buf_iova"]
+    #[doc = "   IO address of the external buffer."]
+    #[doc = " @param buf_len"]
+    #[doc = "   The size of the external buffer."]
+    #[doc = " @param shinfo"]
+    #[doc = "   User-provided memory for shared data of the external buffer."]
+    pub fn _rte_pktmbuf_attach_extbuf(
+        m: *mut rte_mbuf,
+        buf_addr: *mut ::std::os::raw::c_void,
+        buf_iova: rte_iova_t,
+        buf_len: u16,
+        shinfo: *mut rte_mbuf_ext_shared_info,
+    );
+}
+extern "C" {
+    #[doc = " Attach packet mbuf to another packet mbuf."]
+    #[doc = ""]
+    #[doc = " If the mbuf we are attaching to isn't a direct buffer and is attached to"]
+    #[doc = " an external buffer, the mbuf being attached will be attached to the"]
+    #[doc = " external buffer instead of mbuf indirection."]
+    #[doc = ""]
+    #[doc = " Otherwise, the mbuf will be indirectly attached. After attachment we"]
+    #[doc = " refer the mbuf we attached as 'indirect', while mbuf we attached to as"]
+    #[doc = " 'direct'.  The direct mbuf's reference counter is incremented."]
+    #[doc = ""]
+    #[doc = " Right now, not supported:"]
+    #[doc = "  - attachment for already indirect mbuf (e.g. - mi has to be direct)."]
+    #[doc = "  - mbuf we trying to attach (mi) is used by someone else"]
+    #[doc = "    e.g. it's reference counter is greater then 1."]
+    #[doc = ""]
+    #[doc = " @param mi"]
+    #[doc = "   The indirect packet mbuf."]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf we're attaching to."]
+    pub fn _rte_pktmbuf_attach(mi: *mut rte_mbuf, m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Detach a packet mbuf from external buffer or direct buffer."]
+    #[doc = ""]
+    #[doc = "  - decrement refcnt and free the external/direct buffer if refcnt"]
+    #[doc = "    becomes zero."]
+    #[doc = "  - restore original mbuf address and length values."]
+    #[doc = "  - reset pktmbuf data and data_len to their default values."]
+    #[doc = ""]
+    #[doc = " All other fields of the given packet mbuf will be left intact."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The indirect attached packet mbuf."]
+    pub fn _rte_pktmbuf_detach(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Decrease reference counter and unlink a mbuf segment"]
+    #[doc = ""]
+    #[doc = " This function does the same than a free, except that it does not"]
+    #[doc = " return the segment to its pool."]
+    #[doc = " It decreases the reference counter, and if it reaches 0, it is"]
+    #[doc = " detached from its parent for an indirect mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The mbuf to be unlinked"]
+    #[doc = " @return"]
+    #[doc = "   - (m) if it is the last reference. It can be recycled or freed."]
+    #[doc = "   - (NULL) if the mbuf still has remaining references on it."]
+    pub fn _rte_pktmbuf_prefree_seg(m: *mut rte_mbuf) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Free a segment of a packet mbuf into its original mempool."]
+    #[doc = ""]
+    #[doc = " Free an mbuf, without parsing other segments in case of chained"]
+    #[doc = " buffers."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf segment to be freed."]
+    pub fn _rte_pktmbuf_free_seg(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Free a packet mbuf back into its original mempool."]
+    #[doc = ""]
+    #[doc = " Free an mbuf, and all its segments in case of chained buffers. Each"]
+    #[doc = " segment is added back into its original mempool."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf to be freed. If NULL, the function does nothing."]
+    pub fn _rte_pktmbuf_free(m: *mut rte_mbuf);
+}
+extern "C" {
+    #[doc = " Creates a \"clone\" of the given packet mbuf."]
+    #[doc = ""]
+    #[doc = " Walks through all segments of the given packet mbuf, and for each of them:"]
+    #[doc = "  - Creates a new packet mbuf from the given pool."]
+    #[doc = "  - Attaches newly created mbuf to the segment."]
+    #[doc = " Then updates pkt_len and nb_segs of the \"clone\" packet mbuf to match values"]
+    #[doc = " from the original packet mbuf."]
+    #[doc = ""]
+    #[doc = " @param md"]
+    #[doc = "   The packet mbuf to be cloned."]
+    #[doc = " @param mp"]
+    #[doc = "   The mempool from which the \"clone\" mbufs are allocated."]
+    #[doc = " @return"]
+    #[doc = "   - The pointer to the new \"clone\" mbuf on success."]
+    #[doc = "   - NULL if allocation fails."]
+    pub fn _rte_pktmbuf_clone(md: *mut rte_mbuf, mp: *mut rte_mempool) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Adds given value to the refcnt of all packet mbuf segments."]
+    #[doc = ""]
+    #[doc = " Walks through all segments of given packet mbuf and for each of them"]
+    #[doc = " invokes rte_mbuf_refcnt_update()."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf whose refcnt to be updated."]
+    #[doc = " @param v"]
+    #[doc = "   The value to add to the mbuf's segments refcnt."]
+    pub fn _rte_pktmbuf_refcnt_update(m: *mut rte_mbuf, v: i16);
+}
+extern "C" {
+    #[doc = " Get the headroom in a packet mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The length of the headroom."]
+    pub fn _rte_pktmbuf_headroom(m: *const rte_mbuf) -> u16;
+}
+extern "C" {
+    #[doc = " Get the tailroom of a packet mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The length of the tailroom."]
+    pub fn _rte_pktmbuf_tailroom(m: *const rte_mbuf) -> u16;
+}
+extern "C" {
+    #[doc = " Get the last segment of the packet."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @return"]
+    #[doc = "   The last segment of the given mbuf."]
+    pub fn _rte_pktmbuf_lastseg(m: *mut rte_mbuf) -> *mut rte_mbuf;
+}
+extern "C" {
+    #[doc = " Prepend len bytes to an mbuf data area."]
+    #[doc = ""]
+    #[doc = " Returns a pointer to the new"]
+    #[doc = " data start address. If there is not enough headroom in the first"]
+    #[doc = " segment, the function will return NULL, without modifying the mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pkt mbuf."]
+    #[doc = " @param len"]
+    #[doc = "   The amount of data to prepend (in bytes)."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the start of the newly prepended data, or"]
+    #[doc = "   NULL if there is not enough headroom space in the first segment"]
+    pub fn _rte_pktmbuf_prepend(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    #[doc = " Append len bytes to an mbuf."]
+    #[doc = ""]
+    #[doc = " Append len bytes to an mbuf and return a pointer to the start address"]
+    #[doc = " of the added data. If there is not enough tailroom in the last"]
+    #[doc = " segment, the function will return NULL, without modifying the mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @param len"]
+    #[doc = "   The amount of data to append (in bytes)."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the start of the newly appended data, or"]
+    #[doc = "   NULL if there is not enough tailroom space in the last segment"]
+    pub fn _rte_pktmbuf_append(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    #[doc = " Remove len bytes at the beginning of an mbuf."]
+    #[doc = ""]
+    #[doc = " Returns a pointer to the start address of the new data area. If the"]
+    #[doc = " length is greater than the length of the first segment, then the"]
+    #[doc = " function will fail and return NULL, without modifying the mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @param len"]
+    #[doc = "   The amount of data to remove (in bytes)."]
+    #[doc = " @return"]
+    #[doc = "   A pointer to the new start of the data."]
+    pub fn _rte_pktmbuf_adj(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
-    pub fn cmdline_free(cl: *mut cmdline);
+    #[doc = " Remove len bytes of data at the end of the mbuf."]
+    #[doc = ""]
+    #[doc = " If the length is greater than the length of the last segment, the"]
+    #[doc = " function will fail and return -1 without modifying the mbuf."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @param len"]
+    #[doc = "   The amount of data to remove (in bytes)."]
+    #[doc = " @return"]
+    #[doc = "   - 0: On success."]
+    #[doc = "   - -1: On error."]
+    pub fn _rte_pktmbuf_trim(m: *mut rte_mbuf, len: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    pub fn cmdline_printf(cl: *const cmdline, fmt: *const ::std::os::raw::c_char, ...);
+    #[doc = " Test if mbuf data is contiguous."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
+    #[doc = " @return"]
+    #[doc = "   - 1, if all data is contiguous (one segment)."]
+    #[doc = "   - 0, if there is several segments."]
+    pub fn _rte_pktmbuf_is_contiguous(m: *const rte_mbuf) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    pub fn cmdline_in(
-        cl: *mut cmdline,
-        buf: *const ::std::os::raw::c_char,
-        size: ::std::os::raw::c_int,
-    ) -> ::std::os::raw::c_int;
+    #[doc = " Read len data bytes in a mbuf at specified offset."]
+    #[doc = ""]
+    #[doc = " If the data is contiguous, return the pointer in the mbuf data, else"]
+    #[doc = " copy the data in the buffer provided by the user and return its"]
+    #[doc = " pointer."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @param off"]
+    #[doc = "   The offset of the data in the mbuf."]
+    #[doc = " @param len"]
+    #[doc = "   The amount of bytes to read."]
+    #[doc = " @param buf"]
+    #[doc = "   The buffer where data is copied if it is not contiguous in mbuf"]
+    #[doc = "   data. Its length should be at least equal to the len parameter."]
+    #[doc = " @return"]
+    #[doc = "   The pointer to the data, either in the mbuf if it is contiguous,"]
+    #[doc = "   or in the user buffer. If mbuf is too small, NULL is returned."]
+    pub fn _rte_pktmbuf_read(
+        m: *const rte_mbuf,
+        off: u32,
+        len: u32,
+        buf: *mut ::std::os::raw::c_void,
+    ) -> *const ::std::os::raw::c_void;
 }
 extern "C" {
-    pub fn cmdline_write_char(rdl: *mut rdline, c: ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    #[doc = " Chain an mbuf to another, thereby creating a segmented packet."]
+    #[doc = ""]
+    #[doc = " Note: The implementation will do a linear walk over the segments to find"]
+    #[doc = " the tail entry. For cases when there are many segments, it's better to"]
+    #[doc = " chain the entries manually."]
+    #[doc = ""]
+    #[doc = " @param head"]
+    #[doc = "   The head of the mbuf chain (the first packet)"]
+    #[doc = " @param tail"]
+    #[doc = "   The mbuf to put last in the chain"]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   - 0, on success."]
+    #[doc = "   - -EOVERFLOW, if the chain segment limit exceeded"]
+    pub fn _rte_pktmbuf_chain(head: *mut rte_mbuf, tail: *mut rte_mbuf) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " This function is nonblocking equivalent of ``cmdline_interact()``. It polls"]
-    #[doc = " *cl* for one character and interpret it. If return value is *RDLINE_EXITED*"]
-    #[doc = " it mean that ``cmdline_quit()`` was invoked."]
+    #[doc = " Validate general requirements for Tx offload in mbuf."]
+    #[doc = ""]
+    #[doc = " This function checks correctness and completeness of Tx offload settings."]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf to be validated."]
+    #[doc = " @return"]
+    #[doc = "   0 if packet is valid"]
+    pub fn _rte_validate_tx_offload(m: *const rte_mbuf) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Linearize data in mbuf."]
+    #[doc = ""]
+    #[doc = " This function moves the mbuf data in the first segment if there is enough"]
+    #[doc = " tailroom. The subsequent segments are unchained and freed."]
+    #[doc = ""]
+    #[doc = " @param mbuf"]
+    #[doc = "   mbuf to linearize"]
+    #[doc = " @return"]
+    #[doc = "   - 0, on success"]
+    #[doc = "   - -1, on error"]
+    pub fn _rte_pktmbuf_linearize(mbuf: *mut rte_mbuf) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Retrieve a burst of input packets from a receive queue of an Ethernet"]
+    #[doc = " device. The retrieved packets are stored in *rte_mbuf* structures whose"]
+    #[doc = " pointers are supplied in the *rx_pkts* array."]
+    #[doc = ""]
+    #[doc = " The rte_eth_rx_burst() function loops, parsing the RX ring of the"]
+    #[doc = " receive queue, up to *nb_pkts* packets, and for each completed RX"]
+    #[doc = " descriptor in the ring, it performs the following operations:"]
+    #[doc = ""]
+    #[doc = " - Initialize the *rte_mbuf* data structure associated with the"]
+    #[doc = "   RX descriptor according to the information provided by the NIC into"]
+    #[doc = "   that RX descriptor."]
+    #[doc = ""]
+    #[doc = " - Store the *rte_mbuf* data structure into the next entry of the"]
+    #[doc = "   *rx_pkts* array."]
+    #[doc = ""]
+    #[doc = " - Replenish the RX descriptor with a new *rte_mbuf* buffer"]
+    #[doc = "   allocated from the memory pool associated with the receive queue at"]
+    #[doc = "   initialization time."]
+    #[doc = ""]
+    #[doc = " When retrieving an input packet that was scattered by the controller"]
+    #[doc = " into multiple receive descriptors, the rte_eth_rx_burst() function"]
+    #[doc = " appends the associated *rte_mbuf* buffers to the first buffer of the"]
+    #[doc = " packet."]
+    #[doc = ""]
+    #[doc = " The rte_eth_rx_burst() function returns the number of packets"]
+    #[doc = " actually retrieved, which is the number of *rte_mbuf* data structures"]
+    #[doc = " effectively supplied into the *rx_pkts* array."]
+    #[doc = " A return value equal to *nb_pkts* indicates that the RX queue contained"]
+    #[doc = " at least *rx_pkts* packets, and this is likely to signify that other"]
+    #[doc = " received packets remain in the input queue. Applications implementing"]
+    #[doc = " a \"retrieve as much received packets as possible\" policy can check this"]
+    #[doc = " specific case and keep invoking the rte_eth_rx_burst() function until"]
+    #[doc = " a value less than *nb_pkts* is returned."]
+    #[doc = ""]
+    #[doc = " This receive method has the following advantages:"]
+    #[doc = ""]
+    #[doc = " - It allows a run-to-completion network stack engine to retrieve and"]
+    #[doc = "   to immediately process received packets in a fast burst-oriented"]
+    #[doc = "   approach, avoiding the overhead of unnecessary intermediate packet"]
+    #[doc = "   queue/dequeue operations."]
+    #[doc = ""]
+    #[doc = " - Conversely, it also allows an asynchronous-oriented processing"]
+    #[doc = "   method to retrieve bursts of received packets and to immediately"]
+    #[doc = "   queue them for further parallel processing by another logical core,"]
+    #[doc = "   for instance. However, instead of having received packets being"]
+    #[doc = "   individually queued by the driver, this approach allows the caller"]
+    #[doc = "   of the rte_eth_rx_burst() function to queue a burst of retrieved"]
+    #[doc = "   packets at a time and therefore dramatically reduce the cost of"]
+    #[doc = "   enqueue/dequeue operations per packet."]
+    #[doc = ""]
+    #[doc = " - It allows the rte_eth_rx_burst() function of the driver to take"]
+    #[doc = "   advantage of burst-oriented hardware features (CPU cache,"]
+    #[doc = "   prefetch instructions, and so on) to minimize the number of CPU"]
+    #[doc = "   cycles per packet."]
+    #[doc = ""]
+    #[doc = " To summarize, the proposed receive API enables many"]
+    #[doc = " burst-oriented optimizations in both synchronous and asynchronous"]
+    #[doc = " packet processing environments with no overhead in both cases."]
+    #[doc = ""]
+    #[doc = " The rte_eth_rx_burst() function does not provide any error"]
+    #[doc = " notification to avoid the corresponding overhead. As a hint, the"]
+    #[doc = " upper-level application might check the status of the device link once"]
+    #[doc = " being systematically returned a 0 value for a given number of tries."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "   The index of the receive queue from which to retrieve input packets."]
+    #[doc = "   The value must be in the range [0, nb_rx_queue - 1] previously supplied"]
+    #[doc = "   to rte_eth_dev_configure()."]
+    #[doc = " @param rx_pkts"]
+    #[doc = "   The address of an array of pointers to *rte_mbuf* structures that"]
+    #[doc = "   must be large enough to store *nb_pkts* pointers in it."]
+    #[doc = " @param nb_pkts"]
+    #[doc = "   The maximum number of packets to retrieve."]
+    #[doc = " @return"]
+    #[doc = "   The number of packets actually retrieved, which is the number"]
+    #[doc = "   of pointers to *rte_mbuf* structures effectively supplied to the"]
+    #[doc = "   *rx_pkts* array."]
+    pub fn _rte_eth_rx_burst(port_id: u16, queue_id: u16, rx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
+}
+extern "C" {
+    #[doc = " Get the number of used descriptors of a rx queue"]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "  The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "  The queue id on the specific port."]
+    #[doc = " @return"]
+    #[doc = "  The number of used descriptors in the specific queue, or:"]
+    #[doc = "     (-EINVAL) if *port_id* or *queue_id* is invalid"]
+    #[doc = "     (-ENOTSUP) if the device does not support this function"]
+    pub fn _rte_eth_rx_queue_count(port_id: u16, queue_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Check if the DD bit of the specific RX descriptor in the queue has been set"]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "  The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "  The queue id on the specific port."]
+    #[doc = " @param offset"]
+    #[doc = "  The offset of the descriptor ID from tail."]
+    #[doc = " @return"]
+    #[doc = "  - (1) if the specific DD bit is set."]
+    #[doc = "  - (0) if the specific DD bit is not set."]
+    #[doc = "  - (-ENODEV) if *port_id* invalid."]
+    #[doc = "  - (-ENOTSUP) if the device does not support this function"]
+    pub fn _rte_eth_rx_descriptor_done(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Check the status of a Rx descriptor in the queue"]
+    #[doc = ""]
+    #[doc = " It should be called in a similar context than the Rx function:"]
+    #[doc = " - on a dataplane core"]
+    #[doc = " - not concurrently on the same queue"]
+    #[doc = ""]
+    #[doc = " Since it's a dataplane function, no check is performed on port_id and"]
+    #[doc = " queue_id. The caller must therefore ensure that the port is enabled"]
+    #[doc = " and the queue is configured and running."]
+    #[doc = ""]
+    #[doc = " Note: accessing to a random descriptor in the ring may trigger cache"]
+    #[doc = " misses and have a performance impact."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "  A valid port identifier of the Ethernet device which."]
+    #[doc = " @param queue_id"]
+    #[doc = "  A valid Rx queue identifier on this port."]
+    #[doc = " @param offset"]
+    #[doc = "  The offset of the descriptor starting from tail (0 is the next"]
+    #[doc = "  packet to be received by the driver)."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "  - (RTE_ETH_RX_DESC_AVAIL): Descriptor is available for the hardware to"]
+    #[doc = "    receive a packet."]
+    #[doc = "  - (RTE_ETH_RX_DESC_DONE): Descriptor is done, it is filled by hw, but"]
+    #[doc = "    not yet processed by the driver (i.e. in the receive queue)."]
+    #[doc = "  - (RTE_ETH_RX_DESC_UNAVAIL): Descriptor is unavailable, either hold by"]
+    #[doc = "    the driver and not yet returned to hw, or reserved by the hw."]
+    #[doc = "  - (-EINVAL) bad descriptor offset."]
+    #[doc = "  - (-ENOTSUP) if the device does not support this function."]
+    #[doc = "  - (-ENODEV) bad port or queue (only if compiled with debug)."]
+    pub fn _rte_eth_rx_descriptor_status(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Check the status of a Tx descriptor in the queue."]
+    #[doc = ""]
+    #[doc = " It should be called in a similar context than the Tx function:"]
+    #[doc = " - on a dataplane core"]
+    #[doc = " - not concurrently on the same queue"]
     #[doc = ""]
-    #[doc = " @param cl"]
-    #[doc = "   The command line object."]
+    #[doc = " Since it's a dataplane function, no check is performed on port_id and"]
+    #[doc = " queue_id. The caller must therefore ensure that the port is enabled"]
+    #[doc = " and the queue is configured and running."]
+    #[doc = ""]
+    #[doc = " Note: accessing to a random descriptor in the ring may trigger cache"]
+    #[doc = " misses and have a performance impact."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "  A valid port identifier of the Ethernet device which."]
+    #[doc = " @param queue_id"]
+    #[doc = "  A valid Tx queue identifier on this port."]
+    #[doc = " @param offset"]
+    #[doc = "  The offset of the descriptor starting from tail (0 is the place where"]
+    #[doc = "  the next packet will be send)."]
     #[doc = ""]
     #[doc = " @return"]
-    #[doc = "   On success return object status - one of *enum rdline_status*."]
-    #[doc = "   On error return negative value."]
-    pub fn cmdline_poll(cl: *mut cmdline) -> ::std::os::raw::c_int;
-}
-extern "C" {
-    pub fn cmdline_interact(cl: *mut cmdline);
-}
-extern "C" {
-    pub fn cmdline_quit(cl: *mut cmdline);
-}
-extern "C" {
-    pub fn cmdline_file_new(
-        ctx: *mut cmdline_parse_ctx_t,
-        prompt: *const ::std::os::raw::c_char,
-        path: *const ::std::os::raw::c_char,
-    ) -> *mut cmdline;
-}
-extern "C" {
-    pub fn cmdline_stdin_new(ctx: *mut cmdline_parse_ctx_t, prompt: *const ::std::os::raw::c_char) -> *mut cmdline;
-}
-extern "C" {
-    pub fn cmdline_stdin_exit(cl: *mut cmdline);
+    #[doc = "  - (RTE_ETH_TX_DESC_FULL) Descriptor is being processed by the hw, i.e."]
+    #[doc = "    in the transmit queue."]
+    #[doc = "  - (RTE_ETH_TX_DESC_DONE) Hardware is done with this descriptor, it can"]
+    #[doc = "    be reused by the driver."]
+    #[doc = "  - (RTE_ETH_TX_DESC_UNAVAIL): Descriptor is unavailable, reserved by the"]
+    #[doc = "    driver or the hardware."]
+    #[doc = "  - (-EINVAL) bad descriptor offset."]
+    #[doc = "  - (-ENOTSUP) if the device does not support this function."]
+    #[doc = "  - (-ENODEV) bad port or queue (only if compiled with debug)."]
+    pub fn _rte_eth_tx_descriptor_status(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Seed the pseudo-random generator."]
+    #[doc = " Send a burst of output packets on a transmit queue of an Ethernet device."]
     #[doc = ""]
-    #[doc = " The generator is automatically seeded by the EAL init with a timer"]
-    #[doc = " value. It may need to be re-seeded by the user with a real random"]
-    #[doc = " value."]
+    #[doc = " The rte_eth_tx_burst() function is invoked to transmit output packets"]
+    #[doc = " on the output queue *queue_id* of the Ethernet device designated by its"]
+    #[doc = " *port_id*."]
+    #[doc = " The *nb_pkts* parameter is the number of packets to send which are"]
+    #[doc = " supplied in the *tx_pkts* array of *rte_mbuf* structures, each of them"]
+    #[doc = " allocated from a pool created with rte_pktmbuf_pool_create()."]
+    #[doc = " The rte_eth_tx_burst() function loops, sending *nb_pkts* packets,"]
+    #[doc = " up to the number of transmit descriptors available in the TX ring of the"]
+    #[doc = " transmit queue."]
+    #[doc = " For each packet to send, the rte_eth_tx_burst() function performs"]
+    #[doc = " the following operations:"]
     #[doc = ""]
-    #[doc = " @param seedval"]
-    #[doc = "   The value of the seed."]
-    pub fn _rte_srand(seedval: u64);
-}
-extern "C" {
-    #[doc = " Get a pseudo-random value."]
+    #[doc = " - Pick up the next available descriptor in the transmit ring."]
     #[doc = ""]
-    #[doc = " This function generates pseudo-random numbers using the linear"]
-    #[doc = " congruential algorithm and 48-bit integer arithmetic, called twice"]
-    #[doc = " to generate a 64-bit value."]
+    #[doc = " - Free the network buffer previously sent with that descriptor, if any."]
+    #[doc = ""]
+    #[doc = " - Initialize the transmit descriptor with the information provided"]
+    #[doc = "   in the *rte_mbuf data structure."]
+    #[doc = ""]
+    #[doc = " In the case of a segmented packet composed of a list of *rte_mbuf* buffers,"]
+    #[doc = " the rte_eth_tx_burst() function uses several transmit descriptors"]
+    #[doc = " of the ring."]
+    #[doc = ""]
+    #[doc = " The rte_eth_tx_burst() function returns the number of packets it"]
+    #[doc = " actually sent. A return value equal to *nb_pkts* means that all packets"]
+    #[doc = " have been sent, and this is likely to signify that other output packets"]
+    #[doc = " could be immediately transmitted again. Applications that implement a"]
+    #[doc = " \"send as many packets to transmit as possible\" policy can check this"]
+    #[doc = " specific case and keep invoking the rte_eth_tx_burst() function until"]
+    #[doc = " a value less than *nb_pkts* is returned."]
+    #[doc = ""]
+    #[doc = " It is the responsibility of the rte_eth_tx_burst() function to"]
+    #[doc = " transparently free the memory buffers of packets previously sent."]
+    #[doc = " This feature is driven by the *tx_free_thresh* value supplied to the"]
+    #[doc = " rte_eth_dev_configure() function at device configuration time."]
+    #[doc = " When the number of free TX descriptors drops below this threshold, the"]
+    #[doc = " rte_eth_tx_burst() function must [attempt to] free the *rte_mbuf*  buffers"]
+    #[doc = " of those packets whose transmission was effectively completed."]
+    #[doc = ""]
+    #[doc = " If the PMD is DEV_TX_OFFLOAD_MT_LOCKFREE capable, multiple threads can"]
+    #[doc = " invoke this function concurrently on the same tx queue without SW lock."]
+    #[doc = " @see rte_eth_dev_info_get, struct rte_eth_txconf::offloads"]
+    #[doc = ""]
+    #[doc = " @see rte_eth_tx_prepare to perform some prior checks or adjustments"]
+    #[doc = " for offloads."]
     #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "   The index of the transmit queue through which output packets must be"]
+    #[doc = "   sent."]
+    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
+    #[doc = "   to rte_eth_dev_configure()."]
+    #[doc = " @param tx_pkts"]
+    #[doc = "   The address of an array of *nb_pkts* pointers to *rte_mbuf* structures"]
+    #[doc = "   which contain the output packets."]
+    #[doc = " @param nb_pkts"]
+    #[doc = "   The maximum number of packets to transmit."]
     #[doc = " @return"]
-    #[doc = "   A pseudo-random value between 0 and (1<<64)-1."]
-    pub fn _rte_rand() -> u64;
+    #[doc = "   The number of output packets actually stored in transmit descriptors of"]
+    #[doc = "   the transmit ring. The return value can be less than the value of the"]
+    #[doc = "   *tx_pkts* parameter when the transmit ring is full or has been filled up."]
+    pub fn _rte_eth_tx_burst(port_id: u16, queue_id: u16, tx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
 }
 extern "C" {
-    #[doc = " Bitmap initialization"]
+    #[doc = " Process a burst of output packets on a transmit queue of an Ethernet device."]
     #[doc = ""]
-    #[doc = " @param n_bits"]
-    #[doc = "   Number of pre-allocated bits in array2."]
-    #[doc = " @param mem"]
-    #[doc = "   Base address of array1 and array2."]
-    #[doc = " @param mem_size"]
-    #[doc = "   Minimum expected size of bitmap."]
+    #[doc = " The rte_eth_tx_prepare() function is invoked to prepare output packets to be"]
+    #[doc = " transmitted on the output queue *queue_id* of the Ethernet device designated"]
+    #[doc = " by its *port_id*."]
+    #[doc = " The *nb_pkts* parameter is the number of packets to be prepared which are"]
+    #[doc = " supplied in the *tx_pkts* array of *rte_mbuf* structures, each of them"]
+    #[doc = " allocated from a pool created with rte_pktmbuf_pool_create()."]
+    #[doc = " For each packet to send, the rte_eth_tx_prepare() function performs"]
+    #[doc = " the following operations:"]
+    #[doc = ""]
+    #[doc = " - Check if packet meets devices requirements for tx offloads."]
+    #[doc = ""]
+    #[doc = " - Check limitations about number of segments."]
+    #[doc = ""]
+    #[doc = " - Check additional requirements when debug is enabled."]
+    #[doc = ""]
+    #[doc = " - Update and/or reset required checksums when tx offload is set for packet."]
+    #[doc = ""]
+    #[doc = " Since this function can modify packet data, provided mbufs must be safely"]
+    #[doc = " writable (e.g. modified data cannot be in shared segment)."]
+    #[doc = ""]
+    #[doc = " The rte_eth_tx_prepare() function returns the number of packets ready to be"]
+    #[doc = " sent. A return value equal to *nb_pkts* means that all packets are valid and"]
+    #[doc = " ready to be sent, otherwise stops processing on the first invalid packet and"]
+    #[doc = " leaves the rest packets untouched."]
+    #[doc = ""]
+    #[doc = " When this functionality is not implemented in the driver, all packets are"]
+    #[doc = " are returned untouched."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = "   The value must be a valid port id."]
+    #[doc = " @param queue_id"]
+    #[doc = "   The index of the transmit queue through which output packets must be"]
+    #[doc = "   sent."]
+    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
+    #[doc = "   to rte_eth_dev_configure()."]
+    #[doc = " @param tx_pkts"]
+    #[doc = "   The address of an array of *nb_pkts* pointers to *rte_mbuf* structures"]
+    #[doc = "   which contain the output packets."]
+    #[doc = " @param nb_pkts"]
+    #[doc = "   The maximum number of packets to process."]
     #[doc = " @return"]
-    #[doc = "   Handle to bitmap instance."]
-    pub fn _rte_bitmap_init(n_bits: u32, mem: *mut u8, mem_size: u32) -> *mut rte_bitmap;
+    #[doc = "   The number of packets correct and ready to be sent. The return value can be"]
+    #[doc = "   less than the value of the *tx_pkts* parameter when some packet doesn't"]
+    #[doc = "   meet devices requirements with rte_errno set appropriately:"]
+    #[doc = "   - -EINVAL: offload flags are not correctly set"]
+    #[doc = "   - -ENOTSUP: the offload feature is not supported by the hardware"]
+    #[doc = ""]
+    pub fn _rte_eth_tx_prepare(port_id: u16, queue_id: u16, tx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
 }
 extern "C" {
-    #[doc = " Bitmap free"]
+    #[doc = " Send any packets queued up for transmission on a port and HW queue"]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
+    #[doc = " This causes an explicit flush of packets previously buffered via the"]
+    #[doc = " rte_eth_tx_buffer() function. It returns the number of packets successfully"]
+    #[doc = " sent to the NIC, and calls the error callback for any unsent packets. Unless"]
+    #[doc = " explicitly set up otherwise, the default callback simply frees the unsent"]
+    #[doc = " packets back to the owning mempool."]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "   The index of the transmit queue through which output packets must be"]
+    #[doc = "   sent."]
+    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
+    #[doc = "   to rte_eth_dev_configure()."]
+    #[doc = " @param buffer"]
+    #[doc = "   Buffer of packets to be transmit."]
     #[doc = " @return"]
-    #[doc = "   0 upon success, error code otherwise"]
-    pub fn _rte_bitmap_free(bmp: *mut rte_bitmap) -> ::std::os::raw::c_int;
+    #[doc = "   The number of packets successfully sent to the Ethernet device. The error"]
+    #[doc = "   callback is called for any packets which could not be sent."]
+    pub fn _rte_eth_tx_buffer_flush(port_id: u16, queue_id: u16, buffer: *mut rte_eth_dev_tx_buffer) -> u16;
 }
 extern "C" {
-    #[doc = " Bitmap reset"]
+    #[doc = " Buffer a single packet for future transmission on a port and queue"]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    pub fn _rte_bitmap_reset(bmp: *mut rte_bitmap);
+    #[doc = " This function takes a single mbuf/packet and buffers it for later"]
+    #[doc = " transmission on the particular port and queue specified. Once the buffer is"]
+    #[doc = " full of packets, an attempt will be made to transmit all the buffered"]
+    #[doc = " packets. In case of error, where not all packets can be transmitted, a"]
+    #[doc = " callback is called with the unsent packets as a parameter. If no callback"]
+    #[doc = " is explicitly set up, the unsent packets are just freed back to the owning"]
+    #[doc = " mempool. The function returns the number of packets actually sent i.e."]
+    #[doc = " 0 if no buffer flush occurred, otherwise the number of packets successfully"]
+    #[doc = " flushed"]
+    #[doc = ""]
+    #[doc = " @param port_id"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param queue_id"]
+    #[doc = "   The index of the transmit queue through which output packets must be"]
+    #[doc = "   sent."]
+    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
+    #[doc = "   to rte_eth_dev_configure()."]
+    #[doc = " @param buffer"]
+    #[doc = "   Buffer used to collect packets to be sent."]
+    #[doc = " @param tx_pkt"]
+    #[doc = "   Pointer to the packet mbuf to be sent."]
+    #[doc = " @return"]
+    #[doc = "   0 = packet has been buffered for later transmission"]
+    #[doc = "   N > 0 = packet has been buffered, and the buffer was subsequently flushed,"]
+    #[doc = "     causing N packets to be sent, and the error callback to be called for"]
+    #[doc = "     the rest."]
+    pub fn _rte_eth_tx_buffer(
+        port_id: u16,
+        queue_id: u16,
+        buffer: *mut rte_eth_dev_tx_buffer,
+        tx_pkt: *mut rte_mbuf,
+    ) -> u16;
 }
 extern "C" {
-    #[doc = " Bitmap location prefetch into CPU L1 cache"]
+    #[doc = " Extract VLAN tag information into mbuf"]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   Bit position"]
+    #[doc = " Software version of VLAN stripping"]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   0 upon success, error code otherwise"]
-    pub fn _rte_bitmap_prefetch0(bmp: *mut rte_bitmap, pos: u32);
+    #[doc = "   - 0: Success"]
+    #[doc = "   - 1: not a vlan packet"]
+    pub fn _rte_vlan_strip(m: *mut rte_mbuf) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Bitmap bit get"]
+    #[doc = " Insert VLAN tag into mbuf."]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   Bit position"]
+    #[doc = " Software version of VLAN unstripping"]
+    #[doc = ""]
+    #[doc = " @param m"]
+    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   0 when bit is cleared, non-zero when bit is set"]
-    pub fn _rte_bitmap_get(bmp: *mut rte_bitmap, pos: u32) -> u64;
+    #[doc = "   - 0: On success"]
+    #[doc = "   -EPERM: mbuf is is shared overwriting would be unsafe"]
+    #[doc = "   -ENOSPC: not enough headroom in mbuf"]
+    pub fn _rte_vlan_insert(m: *mut *mut rte_mbuf) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Bitmap bit set"]
+    #[doc = " Allow or disallow use of SSE4.2 instrinsics for CRC32 hash"]
+    #[doc = " calculation."]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   Bit position"]
-    pub fn _rte_bitmap_set(bmp: *mut rte_bitmap, pos: u32);
+    #[doc = " @param alg"]
+    #[doc = "   An OR of following flags:"]
+    #[doc = "   - (CRC32_SW) Don't use SSE4.2 intrinsics"]
+    #[doc = "   - (CRC32_SSE42) Use SSE4.2 intrinsics if available"]
+    #[doc = "   - (CRC32_SSE42_x64) Use 64-bit SSE4.2 intrinsic if available (default)"]
+    pub fn _rte_hash_crc_set_alg(alg: u8);
 }
 extern "C" {
-    #[doc = " Bitmap slab set"]
+    #[doc = " Use single crc32 instruction to perform a hash on a 4 byte value."]
+    #[doc = " Fall back to software crc32 implementation in case SSE4.2 is"]
+    #[doc = " not supported"]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   Bit position identifying the array2 slab"]
-    #[doc = " @param slab"]
-    #[doc = "   Value to be assigned to the 64-bit slab in array2"]
-    pub fn _rte_bitmap_set_slab(bmp: *mut rte_bitmap, pos: u32, slab: u64);
+    #[doc = " @param data"]
+    #[doc = "   Data to perform hash on."]
+    #[doc = " @param init_val"]
+    #[doc = "   Value to initialise hash generator."]
+    #[doc = " @return"]
+    #[doc = "   32bit calculated hash value."]
+    pub fn _rte_hash_crc_4byte(data: u32, init_val: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Bitmap bit clear"]
+    #[doc = " Use single crc32 instruction to perform a hash on a 8 byte value."]
+    #[doc = " Fall back to software crc32 implementation in case SSE4.2 is"]
+    #[doc = " not supported"]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   Bit position"]
-    pub fn _rte_bitmap_clear(bmp: *mut rte_bitmap, pos: u32);
+    #[doc = " @param data"]
+    #[doc = "   Data to perform hash on."]
+    #[doc = " @param init_val"]
+    #[doc = "   Value to initialise hash generator."]
+    #[doc = " @return"]
+    #[doc = "   32bit calculated hash value."]
+    pub fn _rte_hash_crc_8byte(data: u64, init_val: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Bitmap scan (with automatic wrap-around)"]
+    #[doc = " Calculate CRC32 hash on user-supplied byte array."]
     #[doc = ""]
-    #[doc = " @param bmp"]
-    #[doc = "   Handle to bitmap instance"]
-    #[doc = " @param pos"]
-    #[doc = "   When function call returns 1, pos contains the position of the next set"]
-    #[doc = "   bit, otherwise not modified"]
-    #[doc = " @param slab"]
-    #[doc = "   When function call returns 1, slab contains the value of the entire 64-bit"]
-    #[doc = "   slab where the bit indicated by pos is located. Slabs are always 64-bit"]
-    #[doc = "   aligned, so the position of the first bit of the slab (this bit is not"]
-    #[doc = "   necessarily set) is pos / 64. Once a slab has been returned by the bitmap"]
-    #[doc = "   scan operation, the internal pointers of the bitmap are updated to point"]
-    #[doc = "   after this slab, so the same slab will not be returned again if it"]
-    #[doc = "   contains more than one bit which is set. When function call returns 0,"]
-    #[doc = "   slab is not modified."]
+    #[doc = " @param data"]
+    #[doc = "   Data to perform hash on."]
+    #[doc = " @param data_len"]
+    #[doc = "   How many bytes to use to calculate hash value."]
+    #[doc = " @param init_val"]
+    #[doc = "   Value to initialise hash generator."]
     #[doc = " @return"]
-    #[doc = "   0 if there is no bit set in the bitmap, 1 otherwise"]
-    pub fn _rte_bitmap_scan(bmp: *mut rte_bitmap, pos: *mut u32, slab: *mut u64) -> ::std::os::raw::c_int;
+    #[doc = "   32bit calculated hash value."]
+    pub fn _rte_hash_crc(data: *const ::std::os::raw::c_void, data_len: u32, init_val: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Bitmap memory footprint calculation"]
+    #[doc = " The most generic version, hashes an arbitrary sequence"]
+    #[doc = " of bytes.  No alignment or length assumptions are made about"]
+    #[doc = " the input key."]
     #[doc = ""]
-    #[doc = " @param n_bits"]
-    #[doc = "   Number of bits in the bitmap"]
+    #[doc = " @param key"]
+    #[doc = "   Key to calculate hash of."]
+    #[doc = " @param length"]
+    #[doc = "   Length of key in bytes."]
+    #[doc = " @param initval"]
+    #[doc = "   Initialising value of hash."]
     #[doc = " @return"]
-    #[doc = "   Bitmap memory footprint measured in bytes on success, 0 on error"]
-    pub fn _rte_bitmap_get_memory_footprint(n_bits: u32) -> u32;
+    #[doc = "   Calculated hash value."]
+    pub fn _rte_jhash(key: *const ::std::os::raw::c_void, length: u32, initval: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Initialize the spinlock to an unlocked state."]
+    #[doc = " A special optimized version that handles 1 or more of uint32_ts."]
+    #[doc = " The length parameter here is the number of uint32_ts in the key."]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
-    pub fn _rte_spinlock_init(sl: *mut rte_spinlock_t);
+    #[doc = " @param k"]
+    #[doc = "   Key to calculate hash of."]
+    #[doc = " @param length"]
+    #[doc = "   Length of key in units of 4 bytes."]
+    #[doc = " @param initval"]
+    #[doc = "   Initialising value of hash."]
+    #[doc = " @return"]
+    #[doc = "   Calculated hash value."]
+    pub fn _rte_jhash_32b(k: *const u32, length: u32, initval: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Take the spinlock."]
+    #[doc = " A special ultra-optimized versions that knows it is hashing exactly"]
+    #[doc = " 3 words."]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
-    pub fn _rte_spinlock_lock(sl: *mut rte_spinlock_t);
+    #[doc = " @param a"]
+    #[doc = "   First word to calculate hash of."]
+    #[doc = " @param b"]
+    #[doc = "   Second word to calculate hash of."]
+    #[doc = " @param c"]
+    #[doc = "   Third word to calculate hash of."]
+    #[doc = " @param initval"]
+    #[doc = "   Initialising value of hash."]
+    #[doc = " @return"]
+    #[doc = "   Calculated hash value."]
+    pub fn _rte_jhash_3words(a: u32, b: u32, c: u32, initval: u32) -> u32;
 }
 extern "C" {
-    #[doc = " Release the spinlock."]
+    #[doc = " Prepare a new key for use with rte_softrss_be()"]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
-    pub fn _rte_spinlock_unlock(sl: *mut rte_spinlock_t);
+    #[doc = " @param orig"]
+    #[doc = "   pointer to the original key"]
+    #[doc = " @param targ"]
+    #[doc = "   pointer to the target key"]
+    #[doc = " @param len"]
+    #[doc = "   key length"]
+    pub fn _rte_convert_rss_key(orig: *const u32, targ: *mut u32, len: ::std::os::raw::c_int);
 }
 extern "C" {
-    #[doc = " Try to take the lock."]
+    #[doc = " Generic implementation. Can be used with original rss_key"]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
+    #[doc = " @param input_tuple"]
+    #[doc = "   Pointer to input tuple"]
+    #[doc = " @param input_len"]
+    #[doc = "   Length of input_tuple in 4-bytes chunks"]
+    #[doc = " @param rss_key"]
+    #[doc = "   Pointer to RSS hash key."]
     #[doc = " @return"]
-    #[doc = "   1 if the lock is successfully taken; 0 otherwise."]
-    pub fn _rte_spinlock_trylock(sl: *mut rte_spinlock_t) -> ::std::os::raw::c_int;
+    #[doc = "   Calculated hash value."]
+    pub fn _rte_softrss(input_tuple: *mut u32, input_len: u32, rss_key: *const u8) -> u32;
 }
 extern "C" {
-    #[doc = " Test if hardware transactional memory (lock elision) is supported"]
+    #[doc = " Optimized implementation."]
+    #[doc = " If you want the calculated hash value matches NIC RSS value"]
+    #[doc = " you have to use special converted key with rte_convert_rss_key() fn."]
     #[doc = ""]
+    #[doc = " @param input_tuple"]
+    #[doc = "   Pointer to input tuple"]
+    #[doc = " @param input_len"]
+    #[doc = "   Length of input_tuple in 4-bytes chunks"]
+    #[doc = " @param rss_key"]
+    #[doc = "   Pointer to RSS hash key."]
     #[doc = " @return"]
-    #[doc = "   1 if the hardware transactional memory is supported; 0 otherwise."]
-    pub fn _rte_tm_supported() -> ::std::os::raw::c_int;
+    #[doc = "   Calculated hash value."]
+    pub fn _rte_softrss_be(input_tuple: *mut u32, input_len: u32, rss_key: *const u8) -> u32;
 }
 extern "C" {
-    #[doc = " Try to execute critical section in a hardware memory transaction,"]
-    #[doc = " if it fails or not available take the spinlock."]
-    #[doc = ""]
-    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
-    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
-    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
-    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
-    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = " Test if trace is enabled."]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
-    pub fn _rte_spinlock_lock_tm(sl: *mut rte_spinlock_t);
+    #[doc = " @return"]
+    #[doc = "    1 if trace is enabled, 0 otherwise or if the trace library is unavailable."]
+    pub fn _rte_trace_is_enabled() -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Try to execute critical section in a hardware memory transaction,"]
-    #[doc = " if it fails or not available try to take the lock."]
-    #[doc = ""]
-    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
-    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
-    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
-    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
-    #[doc = " rte_eth_tx_burst() calls."]
+    #[doc = " Enable/Disable a set of tracepoints based on globbing pattern."]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
     #[doc = " @return"]
-    #[doc = "   1 if the hardware memory transaction is successfully started"]
-    #[doc = "   or lock is successfully taken; 0 otherwise."]
-    pub fn _rte_spinlock_trylock_tm(sl: *mut rte_spinlock_t) -> ::std::os::raw::c_int;
+    #[doc = "   - 1: Success and matched with at least one tracepoint."]
+    #[doc = "   - 0: Success but not matched with any pattern."]
+    #[doc = "   - (-ENOTSUP): The trace library is unavailable."]
+    #[doc = "   - <0: Failure to enable/disable the tracepoints."]
+    pub fn _rte_trace_pattern(
+        pattern: *const ::std::os::raw::c_char,
+        enable: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Commit hardware memory transaction or release the spinlock if"]
-    #[doc = " the spinlock is used as a fall-back"]
+    #[doc = " Enable/Disable a set of tracepoints based on regular expression."]
     #[doc = ""]
-    #[doc = " @param sl"]
-    #[doc = "   A pointer to the spinlock."]
-    pub fn _rte_spinlock_unlock_tm(sl: *mut rte_spinlock_t);
+    #[doc = " @return"]
+    #[doc = "   - 1: Success and matched with at least one tracepoint."]
+    #[doc = "   - 0: Success but not matched with any pattern."]
+    #[doc = "   - (-ENOTSUP): The trace library is unavailable."]
+    #[doc = "   - <0: Failure to enable/disable the tracepoints."]
+    pub fn _rte_trace_regexp(
+        regex: *const ::std::os::raw::c_char,
+        enable: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Initialize the recursive spinlock to an unlocked state."]
+    #[doc = " Set the trace mode, 0 for overwrite and 1 for discard."]
     #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    pub fn _rte_spinlock_recursive_init(slr: *mut rte_spinlock_recursive_t);
+    #[doc = " @return"]
+    #[doc = "   0 on success, or -ENOTSUP if the trace library is unavailable."]
+    pub fn _rte_trace_mode_set(mode: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Take the recursive spinlock."]
+    #[doc = " Get the trace mode, 0 for overwrite and 1 for discard."]
     #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    pub fn _rte_spinlock_recursive_lock(slr: *mut rte_spinlock_recursive_t);
+    #[doc = " @return"]
+    #[doc = "   The trace mode, or -ENOTSUP if the trace library is unavailable."]
+    pub fn _rte_trace_mode_get() -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Release the recursive spinlock."]
+    #[doc = " Save the trace buffers to the trace directory in CTF format."]
     #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    pub fn _rte_spinlock_recursive_unlock(slr: *mut rte_spinlock_recursive_t);
+    #[doc = " @return"]
+    #[doc = "   0 on success, or a negative value on error."]
+    pub fn _rte_trace_save() -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Try to take the recursive lock."]
+    #[doc = " Dump the trace metadata to a file."]
     #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
     #[doc = " @return"]
-    #[doc = "   1 if the lock is successfully taken; 0 otherwise."]
-    pub fn _rte_spinlock_recursive_trylock(slr: *mut rte_spinlock_recursive_t) -> ::std::os::raw::c_int;
+    #[doc = "   0 on success, or a negative value on error."]
+    pub fn _rte_trace_dump(f: *mut FILE) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Try to execute critical section in a hardware memory transaction,"]
-    #[doc = " if it fails or not available take the recursive spinlocks"]
-    #[doc = ""]
-    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
-    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
-    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
-    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
-    #[doc = " rte_eth_tx_burst() calls."]
-    #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    pub fn _rte_spinlock_recursive_lock_tm(slr: *mut rte_spinlock_recursive_t);
+    #[doc = " Emit the user-defined tracepoint `app.user.u64`, with a name and a value."]
+    pub fn _rte_trace_user_u64(name: *const ::std::os::raw::c_char, value: u64);
 }
 extern "C" {
-    #[doc = " Commit hardware memory transaction or release the recursive spinlock"]
-    #[doc = " if the recursive spinlock is used as a fall-back"]
-    #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    pub fn _rte_spinlock_recursive_unlock_tm(slr: *mut rte_spinlock_recursive_t);
+    #[doc = " Emit the user-defined tracepoint `app.user.str`, with a name and a message."]
+    pub fn _rte_trace_user_str(name: *const ::std::os::raw::c_char, msg: *const ::std::os::raw::c_char);
+}
+pub type __builtin_va_list = [__va_list_tag; 1usize];
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct __va_list_tag {
+    pub gp_offset: ::std::os::raw::c_uint,
+    pub fp_offset: ::std::os::raw::c_uint,
+    pub overflow_arg_area: *mut ::std::os::raw::c_void,
+    pub reg_save_area: *mut ::std::os::raw::c_void,
+}
+#[test]
+fn bindgen_test_layout___va_list_tag() {
+    assert_eq!(
+        ::std::mem::size_of::<__va_list_tag>(),
+        24usize,
+        concat!("Size of: ", stringify!(__va_list_tag))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<__va_list_tag>(),
+        8usize,
+        concat!("Alignment of ", stringify!(__va_list_tag))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).gp_offset as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(__va_list_tag),
+            "::",
+            stringify!(gp_offset)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).fp_offset as *const _ as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(__va_list_tag),
+            "::",
+            stringify!(fp_offset)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).overflow_arg_area as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(__va_list_tag),
+            "::",
+            stringify!(overflow_arg_area)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).reg_save_area as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(__va_list_tag),
+            "::",
+            stringify!(reg_save_area)
+        )
+    );
+}
+impl Default for __va_list_tag {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+#[doc = "< class handle."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_class {
+    pub _address: u8,
+}
+pub const RTE_CRYPTODEV_NAME_MAX_LEN: u32 = 64;
+pub const RTE_CRYPTODEV_FF_SYMMETRIC_CRYPTO: u32 = 1;
+pub const RTE_CRYPTODEV_FF_ASYMMETRIC_CRYPTO: u32 = 2;
+pub const RTE_CRYPTODEV_FF_SYM_OPERATION_CHAINING: u32 = 4;
+pub const RTE_CRYPTODEV_FF_CPU_SSE: u32 = 8;
+pub const RTE_CRYPTODEV_FF_CPU_AVX: u32 = 16;
+pub const RTE_CRYPTODEV_FF_CPU_AVX2: u32 = 32;
+pub const RTE_CRYPTODEV_FF_CPU_AESNI: u32 = 64;
+pub const RTE_CRYPTODEV_FF_HW_ACCELERATED: u32 = 128;
+pub mod rte_crypto_op_type {
+    #[doc = " Crypto operation types"]
+    pub type Type = u32;
+    #[doc = "< Undefined operation type"]
+    pub const RTE_CRYPTO_OP_TYPE_UNDEFINED: Type = 0;
+    #[doc = "< Symmetric operation"]
+    pub const RTE_CRYPTO_OP_TYPE_SYMMETRIC: Type = 1;
+    #[doc = "< Asymmetric operation"]
+    pub const RTE_CRYPTO_OP_TYPE_ASYMMETRIC: Type = 2;
+}
+pub mod rte_crypto_op_status {
+    #[doc = " Status of crypto operation"]
+    pub type Type = u32;
+    #[doc = "< Operation completed successfully"]
+    pub const RTE_CRYPTO_OP_STATUS_SUCCESS: Type = 0;
+    #[doc = "< Operation has not yet been processed by a crypto device"]
+    pub const RTE_CRYPTO_OP_STATUS_NOT_PROCESSED: Type = 1;
+    #[doc = "< Authentication verification failed"]
+    pub const RTE_CRYPTO_OP_STATUS_AUTH_FAILED: Type = 2;
+    #[doc = "< Operation failed due to invalid session arguments, or if"]
+    #[doc = " in session-less mode, failed to allocate private operation material."]
+    pub const RTE_CRYPTO_OP_STATUS_INVALID_SESSION: Type = 3;
+    #[doc = "< Operation failed due to invalid arguments in request"]
+    pub const RTE_CRYPTO_OP_STATUS_INVALID_ARGS: Type = 4;
+    #[doc = "< Error handling operation"]
+    pub const RTE_CRYPTO_OP_STATUS_ERROR: Type = 5;
+}
+pub mod rte_crypto_op_sess_type {
+    #[doc = " Crypto operation session type. This is used to specify whether a crypto"]
+    #[doc = " operation has session structure attached for immutable parameters or if all"]
+    #[doc = " operation information is included in the operation data structure."]
+    pub type Type = u32;
+    #[doc = "< Session based crypto operation"]
+    pub const RTE_CRYPTO_OP_WITH_SESSION: Type = 0;
+    #[doc = "< Session-less crypto operation"]
+    pub const RTE_CRYPTO_OP_SESSIONLESS: Type = 1;
+    #[doc = "< Security session crypto operation"]
+    pub const RTE_CRYPTO_OP_SECURITY_SESSION: Type = 2;
+}
+pub mod rte_crypto_auth_algorithm {
+    #[doc = " Authentication algorithms"]
+    pub type Type = u32;
+    #[doc = "< NULL hash algorithm."]
+    pub const RTE_CRYPTO_AUTH_NULL: Type = 1;
+    #[doc = "< AES-CBC-MAC algorithm. Only 128-bit keys are supported."]
+    pub const RTE_CRYPTO_AUTH_AES_CBC_MAC: Type = 2;
+    #[doc = "< AES CMAC algorithm."]
+    pub const RTE_CRYPTO_AUTH_AES_CMAC: Type = 3;
+    #[doc = "< AES GMAC algorithm."]
+    pub const RTE_CRYPTO_AUTH_AES_GMAC: Type = 4;
+    #[doc = "< AES XCBC algorithm."]
+    pub const RTE_CRYPTO_AUTH_AES_XCBC_MAC: Type = 5;
+    #[doc = "< KASUMI (F9) algorithm."]
+    pub const RTE_CRYPTO_AUTH_KASUMI_F9: Type = 6;
+    #[doc = "< MD5 algorithm"]
+    pub const RTE_CRYPTO_AUTH_MD5: Type = 7;
+    #[doc = "< HMAC using MD5 algorithm"]
+    pub const RTE_CRYPTO_AUTH_MD5_HMAC: Type = 8;
+    #[doc = "< 128 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA1: Type = 9;
+    #[doc = "< HMAC using 128 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA1_HMAC: Type = 10;
+    #[doc = "< 224 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA224: Type = 11;
+    #[doc = "< HMAC using 224 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA224_HMAC: Type = 12;
+    #[doc = "< 256 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA256: Type = 13;
+    #[doc = "< HMAC using 256 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA256_HMAC: Type = 14;
+    #[doc = "< 384 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA384: Type = 15;
+    #[doc = "< HMAC using 384 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA384_HMAC: Type = 16;
+    #[doc = "< 512 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA512: Type = 17;
+    #[doc = "< HMAC using 512 bit SHA algorithm."]
+    pub const RTE_CRYPTO_AUTH_SHA512_HMAC: Type = 18;
+    #[doc = "< SNOW 3G algorithm in UIA2 mode."]
+    pub const RTE_CRYPTO_AUTH_SNOW3G_UIA2: Type = 19;
+    #[doc = "< ZUC algorithm in EIA3 mode"]
+    pub const RTE_CRYPTO_AUTH_ZUC_EIA3: Type = 20;
+    pub const RTE_CRYPTO_AUTH_LIST_END: Type = 21;
+}
+pub mod rte_crypto_asym_xform_type {
+    #[doc = " Asymmetric crypto transformation types."]
+    #[doc = " Each xform type maps to one asymmetric algorithm"]
+    #[doc = " performing specific operation"]
+    pub type Type = u32;
+    #[doc = "< Invalid xform."]
+    pub const RTE_CRYPTO_ASYM_XFORM_UNSPECIFIED: Type = 0;
+    #[doc = "< Xform type None."]
+    #[doc = " May be supported by PMD to support"]
+    #[doc = " passthrough op for debugging purpose."]
+    pub const RTE_CRYPTO_ASYM_XFORM_NONE: Type = 1;
+    #[doc = "< RSA. Performs Encrypt, Decrypt, Sign and Verify."]
+    pub const RTE_CRYPTO_ASYM_XFORM_RSA: Type = 2;
+    #[doc = "< Diffie-Hellman."]
+    #[doc = " Performs Key Generate and Shared Secret Compute."]
+    pub const RTE_CRYPTO_ASYM_XFORM_DH: Type = 3;
+    #[doc = "< Digital Signature Algorithm"]
+    #[doc = " Performs Signature Generation and Verification."]
+    pub const RTE_CRYPTO_ASYM_XFORM_DSA: Type = 4;
+    #[doc = "< Modular Inverse"]
+    #[doc = " Perform Modulus inverse b^(-1) mod n"]
+    pub const RTE_CRYPTO_ASYM_XFORM_MODINV: Type = 5;
+    #[doc = "< Modular Exponentiation"]
+    #[doc = " Perform Modular Exponentiation b^e mod n"]
+    pub const RTE_CRYPTO_ASYM_XFORM_MODEX: Type = 6;
+    #[doc = "< End of list"]
+    pub const RTE_CRYPTO_ASYM_XFORM_TYPE_LIST_END: Type = 7;
+}
+pub mod rte_crypto_asym_op_type {
+    #[doc = " Asymmetric crypto operation type variants"]
+    pub type Type = u32;
+    #[doc = "< Asymmetric Encrypt operation"]
+    pub const RTE_CRYPTO_ASYM_OP_ENCRYPT: Type = 0;
+    #[doc = "< Asymmetric Decrypt operation"]
+    pub const RTE_CRYPTO_ASYM_OP_DECRYPT: Type = 1;
+    #[doc = "< Signature Generation operation"]
+    pub const RTE_CRYPTO_ASYM_OP_SIGN: Type = 2;
+    #[doc = "< Signature Verification operation"]
+    pub const RTE_CRYPTO_ASYM_OP_VERIFY: Type = 3;
+    #[doc = "< DH Private Key generation operation"]
+    pub const RTE_CRYPTO_ASYM_OP_PRIVATE_KEY_GENERATE: Type = 4;
+    #[doc = "< DH Public Key generation operation"]
+    pub const RTE_CRYPTO_ASYM_OP_PUBLIC_KEY_GENERATE: Type = 5;
+    #[doc = "< DH Shared Secret compute operation"]
+    pub const RTE_CRYPTO_ASYM_OP_SHARED_SECRET_COMPUTE: Type = 6;
+    pub const RTE_CRYPTO_ASYM_OP_LIST_END: Type = 7;
+}
+pub mod rte_crypto_rsa_padding_type {
+    #[doc = " Padding types for RSA signature."]
+    pub type Type = u32;
+    #[doc = "< RSA no padding scheme"]
+    pub const RTE_CRYPTO_RSA_PADDING_NONE: Type = 0;
+    #[doc = "< RSA PKCS#1 V1.5 Block Type 0 padding scheme"]
+    #[doc = " as described in rfc2313"]
+    pub const RTE_CRYPTO_RSA_PKCS1_V1_5_BT0: Type = 1;
+    #[doc = "< RSA PKCS#1 V1.5 Block Type 01 padding scheme"]
+    #[doc = " as described in rfc2313"]
+    pub const RTE_CRYPTO_RSA_PKCS1_V1_5_BT1: Type = 2;
+    #[doc = "< RSA PKCS#1 V1.5 Block Type 02 padding scheme"]
+    #[doc = " as described in rfc2313"]
+    pub const RTE_CRYPTO_RSA_PKCS1_V1_5_BT2: Type = 3;
+    #[doc = "< RSA PKCS#1 OAEP padding scheme"]
+    pub const RTE_CRYPTO_RSA_PADDING_OAEP: Type = 4;
+    #[doc = "< RSA PKCS#1 PSS padding scheme"]
+    pub const RTE_CRYPTO_RSA_PADDING_PSS: Type = 5;
+    pub const RTE_CRYPTO_RSA_PADDING_TYPE_LIST_END: Type = 6;
+}
+pub mod rte_crypto_rsa_priv_key_type {
+    #[doc = " RSA private key type enumeration"]
+    #[doc = ""]
+    #[doc = " enumerates private key format required to perform RSA crypto"]
+    #[doc = " transform."]
+    pub type Type = u32;
+    #[doc = "< RSA private key is an exponent"]
+    pub const RTE_RSA_KEY_TYPE_EXP: Type = 0;
+    #[doc = "< RSA private key is in quintuple format"]
+    #[doc = " See rte_crypto_rsa_priv_key_qt"]
+    pub const RTE_RSA_KET_TYPE_QT: Type = 1;
+}
+#[doc = " Buffer to hold crypto params required for asym operations."]
+#[doc = ""]
+#[doc = " These buffers can be used for both input to PMD and output from PMD. When"]
+#[doc = " these buffers are used as input, length should be set. When used as output,"]
+#[doc = " the PMD will set the length of the data written."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_crypto_param_t {
+    #[doc = "< pointer to buffer holding data"]
+    pub data: *mut u8,
+    #[doc = "< IO address of data buffer"]
+    pub iova: rte_iova_t,
+    #[doc = "< length of data in bytes"]
+    pub length: usize,
+}
+impl Default for rte_crypto_param_t {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
+}
+pub type rte_crypto_param = rte_crypto_param_t;
+#[doc = " Structure describing RSA private key in quintuple format."]
+#[doc = " See PKCS V1.5 RSA Cryptography Standard."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_rsa_priv_key_qt {
+    #[doc = "< p - Private key component P"]
+    pub p: rte_crypto_param,
+    #[doc = "< q - Private key component Q"]
+    pub q: rte_crypto_param,
+    #[doc = "< dP - Private CRT component"]
+    pub dP: rte_crypto_param,
+    #[doc = "< dQ - Private CRT component"]
+    pub dQ: rte_crypto_param,
+    #[doc = "< qInv - Private CRT component"]
+    pub qInv: rte_crypto_param,
+}
+#[doc = " Asymmetric RSA transform data"]
+#[doc = ""]
+#[doc = " Structure describing RSA xform params"]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct rte_crypto_rsa_xform {
+    #[doc = "< n - Prime modulus"]
+    pub n: rte_crypto_param,
+    #[doc = "< e - Public key exponent"]
+    pub e: rte_crypto_param,
+    #[doc = "< RSA private key type"]
+    pub key_type: rte_crypto_rsa_priv_key_type::Type,
+    pub __bindgen_anon_1: rte_crypto_rsa_xform__bindgen_ty_1,
 }
-extern "C" {
-    #[doc = " Try to execute critical section in a hardware memory transaction,"]
-    #[doc = " if it fails or not available try to take the recursive lock"]
-    #[doc = ""]
-    #[doc = " NOTE: An attempt to perform a HW I/O operation inside a hardware memory"]
-    #[doc = " transaction always aborts the transaction since the CPU is not able to"]
-    #[doc = " roll-back should the transaction fail. Therefore, hardware transactional"]
-    #[doc = " locks are not advised to be used around rte_eth_rx_burst() and"]
-    #[doc = " rte_eth_tx_burst() calls."]
-    #[doc = ""]
-    #[doc = " @param slr"]
-    #[doc = "   A pointer to the recursive spinlock."]
-    #[doc = " @return"]
-    #[doc = "   1 if the hardware memory transaction is successfully started"]
-    #[doc = "   or lock is successfully taken; 0 otherwise."]
-    pub fn _rte_spinlock_recursive_trylock_tm(slr: *mut rte_spinlock_recursive_t) -> ::std::os::raw::c_int;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union rte_crypto_rsa_xform__bindgen_ty_1 {
+    #[doc = "< d - Private key exponent"]
+    pub d: rte_crypto_param,
+    #[doc = "< qt - Private key in quintuple format"]
+    pub qt: rte_crypto_rsa_priv_key_qt,
+    _bindgen_union_align: [u64; 15usize],
+}
+impl Default for rte_crypto_rsa_xform__bindgen_ty_1 {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Return the Application thread ID of the execution unit."]
-    #[doc = ""]
-    #[doc = " Note: in most cases the lcore id returned here will also correspond"]
-    #[doc = "   to the processor id of the CPU on which the thread is pinned, this"]
-    #[doc = "   will not be the case if the user has explicitly changed the thread to"]
-    #[doc = "   core affinities using --lcores EAL argument e.g. --lcores '(0-3)@10'"]
-    #[doc = "   to run threads with lcore IDs 0, 1, 2 and 3 on physical core 10.."]
-    #[doc = ""]
-    #[doc = " @return"]
-    #[doc = "  Logical core ID (in EAL thread) or LCORE_ID_ANY (in non-EAL thread)"]
-    pub fn _rte_lcore_id() -> ::std::os::raw::c_uint;
+impl Default for rte_crypto_rsa_xform {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Error number value, stored per-thread, which can be queried after"]
-    #[doc = " calls to certain functions to determine why those functions failed."]
-    #[doc = ""]
-    #[doc = " Uses standard values from errno.h wherever possible, with a small number"]
-    #[doc = " of additional possible values for RTE-specific conditions."]
-    pub fn _rte_errno() -> ::std::os::raw::c_int;
+#[doc = " Asymmetric Modular exponentiation transform data"]
+#[doc = ""]
+#[doc = " Structure describing modular exponentiation xform param"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_modex_xform {
+    #[doc = "< modulus"]
+    #[doc = " Prime modulus of the modexp transform operation in octet-string"]
+    #[doc = " network byte order format."]
+    pub modulus: rte_crypto_param,
+    #[doc = "< exponent"]
+    #[doc = " Private exponent of the modexp transform operation in"]
+    #[doc = " octet-string network byte order format."]
+    pub exponent: rte_crypto_param,
+}
+#[doc = " Asymmetric modular inverse transform operation"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_modinv_xform {
+    #[doc = "< modulus"]
+    #[doc = " Prime modulus of the modinv transform operation in octet-string"]
+    #[doc = " network byte order format."]
+    pub modulus: rte_crypto_param,
+}
+#[doc = " Asymmetric DH transform data"]
+#[doc = ""]
+#[doc = " Structure describing deffie-hellman xform params"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_crypto_dh_xform {
+    #[doc = "< Setup xform for key generate or shared secret compute"]
+    pub type_: rte_crypto_asym_op_type::Type,
+    #[doc = "< p : Prime modulus data"]
+    #[doc = " DH prime modulus data in octet-string network byte order format."]
+    pub p: rte_crypto_param,
+    #[doc = "< g : Generator"]
+    #[doc = " DH group generator data in octet-string network byte order"]
+    #[doc = " format."]
+    pub g: rte_crypto_param,
+}
+impl Default for rte_crypto_dh_xform {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Return the number of TSC cycles since boot"]
-    #[doc = ""]
-    #[doc = " @return"]
-    #[doc = "   the number of cycles"]
-    pub fn _rte_get_tsc_cycles() -> u64;
+#[doc = " Asymmetric Digital Signature transform operation"]
+#[doc = ""]
+#[doc = " Structure describing DSA xform params"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_dsa_xform {
+    #[doc = "< p - Prime modulus"]
+    pub p: rte_crypto_param,
+    #[doc = "< q - Order of the subgroup"]
+    pub q: rte_crypto_param,
+    #[doc = "< g - Generator of the subgroup"]
+    pub g: rte_crypto_param,
+    #[doc = "< x: Private key of the signer in octet-string network"]
+    #[doc = " byte order format."]
+    pub x: rte_crypto_param,
+}
+#[doc = " Operations params for modular operations:"]
+#[doc = " exponentiation and multiplicative inverse"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_mod_op_param {
+    #[doc = "< Pointer to base of modular exponentiation/multiplicative"]
+    #[doc = " inverse data in octet-string network byte order format."]
+    pub base: rte_crypto_param,
+    #[doc = "< Pointer to the result of modular exponentiation/multiplicative inverse"]
+    #[doc = " data in octet-string network byte order format."]
+    pub result: rte_crypto_param,
+}
+#[doc = " Asymmetric crypto transform data"]
+#[doc = ""]
+#[doc = " Structure describing asym xforms."]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct rte_crypto_asym_xform {
+    #[doc = "< next xform in chain"]
+    pub next: *mut rte_crypto_asym_xform,
+    #[doc = "< Asymmetric crypto transform"]
+    pub xform_type: rte_crypto_asym_xform_type::Type,
+    pub __bindgen_anon_1: rte_crypto_asym_xform__bindgen_ty_1,
 }
-extern "C" {
-    #[doc = " Get the number of cycles since boot from the default timer."]
-    #[doc = ""]
-    #[doc = " @return"]
-    #[doc = "   The number of cycles"]
-    pub fn _rte_get_timer_cycles() -> u64;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union rte_crypto_asym_xform__bindgen_ty_1 {
+    #[doc = "< RSA xform parameters"]
+    pub rsa: rte_crypto_rsa_xform,
+    #[doc = "< DH xform parameters"]
+    pub dh: rte_crypto_dh_xform,
+    #[doc = "< DSA xform parameters"]
+    pub dsa: rte_crypto_dsa_xform,
+    #[doc = "< MODEX xform parameters"]
+    pub modex: rte_crypto_modex_xform,
+    #[doc = "< Modulus Inverse xform parameters"]
+    pub modinv: rte_crypto_modinv_xform,
+    _bindgen_union_align: [u64; 22usize],
+}
+impl Default for rte_crypto_asym_xform__bindgen_ty_1 {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Get the number of cycles in one second for the default timer."]
-    #[doc = ""]
-    #[doc = " @return"]
-    #[doc = "   The number of cycles in one second."]
-    pub fn _rte_get_timer_hz() -> u64;
+impl Default for rte_crypto_asym_xform {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Wait at least ms milliseconds."]
-    #[doc = ""]
-    #[doc = " @param ms"]
-    #[doc = "   The number of milliseconds to wait."]
-    pub fn _rte_delay_ms(ms: ::std::os::raw::c_uint);
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_cryptodev_asym_session {
+    _unused: [u8; 0],
 }
-extern "C" {
-    pub fn _rte_rdtsc() -> u64;
+#[doc = " RSA operation params"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_crypto_rsa_op_param {
+    #[doc = "< Type of RSA operation for transform"]
+    pub op_type: rte_crypto_asym_op_type::Type,
+    #[doc = "< Pointer to data"]
+    #[doc = " - to be encrypted for RSA public encrypt."]
+    #[doc = " - to be decrypted for RSA private decrypt."]
+    #[doc = " - to be signed for RSA sign generation."]
+    #[doc = " - to be authenticated for RSA sign verification."]
+    pub message: rte_crypto_param,
+    #[doc = "< Pointer to RSA signature data. If operation is RSA"]
+    #[doc = " sign @ref RTE_CRYPTO_ASYM_OP_SIGN, buffer will be"]
+    #[doc = " over-written with generated signature."]
+    #[doc = ""]
+    #[doc = " Length of the signature data will be equal to the"]
+    #[doc = " RSA prime modulus length."]
+    pub sign: rte_crypto_param,
+    #[doc = "< RSA padding scheme to be used for transform"]
+    pub pad: rte_crypto_rsa_padding_type::Type,
+    #[doc = "< Hash algorithm to be used for data hash if padding"]
+    #[doc = " scheme is either OAEP or PSS. Valid hash algorithms"]
+    #[doc = " are:"]
+    #[doc = " MD5, SHA1, SHA224, SHA256, SHA384, SHA512"]
+    pub md: rte_crypto_auth_algorithm::Type,
+    #[doc = "< Hash algorithm to be used for mask generation if"]
+    #[doc = " padding scheme is either OAEP or PSS. If padding"]
+    #[doc = " scheme is unspecified data hash algorithm is used"]
+    #[doc = " for mask generation. Valid hash algorithms are:"]
+    #[doc = " MD5, SHA1, SHA224, SHA256, SHA384, SHA512"]
+    pub mgf1md: rte_crypto_auth_algorithm::Type,
+}
+impl Default for rte_crypto_rsa_op_param {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    pub fn _rte_rdtsc_precise() -> u64;
+#[doc = " Diffie-Hellman Operations params."]
+#[doc = " @note:"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_crypto_dh_op_param {
+    #[doc = "< Output generated public key when xform type is"]
+    #[doc = " DH PUB_KEY_GENERATION."]
+    #[doc = " Input peer public key when xform type is DH"]
+    #[doc = " SHARED_SECRET_COMPUTATION"]
+    #[doc = " pub_key is in octet-string network byte order format."]
+    pub pub_key: rte_crypto_param,
+    #[doc = "< Output generated private key if xform type is"]
+    #[doc = " DH PRIVATE_KEY_GENERATION"]
+    #[doc = " Input when xform type is DH SHARED_SECRET_COMPUTATION."]
+    #[doc = " priv_key is in octet-string network byte order format."]
+    pub priv_key: rte_crypto_param,
+    #[doc = "< Output with calculated shared secret"]
+    #[doc = " when dh xform set up with op type = SHARED_SECRET_COMPUTATION."]
+    #[doc = " shared_secret is an octet-string network byte order format."]
+    pub shared_secret: rte_crypto_param,
+}
+#[doc = " DSA Operations params"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_crypto_dsa_op_param {
+    #[doc = "< Signature Generation or Verification"]
+    pub op_type: rte_crypto_asym_op_type::Type,
+    #[doc = "< input message to be signed or verified"]
+    pub message: rte_crypto_param,
+    #[doc = "< Per-message secret number, which is an integer"]
+    #[doc = " in the interval (1, q-1)."]
+    pub k: rte_crypto_param,
+    #[doc = "< dsa sign component 'r' value"]
+    #[doc = ""]
+    #[doc = " output if op_type = sign generate,"]
+    #[doc = " input if op_type = sign verify"]
+    pub r: rte_crypto_param,
+    #[doc = "< dsa sign component 's' value"]
+    #[doc = ""]
+    #[doc = " output if op_type = sign generate,"]
+    #[doc = " input if op_type = sign verify"]
+    pub s: rte_crypto_param,
+    #[doc = "< y : Public key of the signer."]
+    #[doc = " Public key data of the signer in Octet-string network byte order"]
+    #[doc = " format y = g^x mod p"]
+    pub y: rte_crypto_param,
+}
+impl Default for rte_crypto_dsa_op_param {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Return a pointer to the mempool owning this object."]
-    #[doc = ""]
-    #[doc = " @param obj"]
-    #[doc = "   An object that is owned by a pool. If this is not the case,"]
-    #[doc = "   the behavior is undefined."]
-    #[doc = " @return"]
-    #[doc = "   A pointer to the mempool structure."]
-    pub fn _rte_mempool_from_obj(obj: *mut ::std::os::raw::c_void) -> *mut rte_mempool;
+#[doc = " Asymmetric Cryptographic Operation."]
+#[doc = ""]
+#[doc = " Structure describing asymmetric crypto operation params."]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct rte_crypto_asym_op {
+    #[doc = "< Handle for the initialised session context"]
+    pub session: *mut rte_cryptodev_asym_session,
+    pub __bindgen_anon_1: rte_crypto_asym_op__bindgen_ty_1,
 }
-extern "C" {
-    #[doc = " Return the IO address of elt, which is an element of the pool mp."]
-    #[doc = ""]
-    #[doc = " @param elt"]
-    #[doc = "   A pointer (virtual address) to the element of the pool."]
-    #[doc = " @return"]
-    #[doc = "   The IO address of the elt element."]
-    #[doc = "   If the mempool was created with MEMPOOL_F_NO_IOVA_CONTIG, the"]
-    #[doc = "   returned value is RTE_BAD_IOVA."]
-    pub fn _rte_mempool_virt2iova(elt: *const ::std::os::raw::c_void) -> rte_iova_t;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union rte_crypto_asym_op__bindgen_ty_1 {
+    pub rsa: rte_crypto_rsa_op_param,
+    pub modex: rte_crypto_mod_op_param,
+    pub modinv: rte_crypto_mod_op_param,
+    pub dh: rte_crypto_dh_op_param,
+    pub dsa: rte_crypto_dsa_op_param,
+    _bindgen_union_align: [u64; 16usize],
+}
+impl Default for rte_crypto_asym_op__bindgen_ty_1 {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Return a pointer to the private data in an mempool structure."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @return"]
-    #[doc = "   A pointer to the private data."]
-    pub fn _rte_mempool_get_priv(mp: *mut rte_mempool) -> *mut ::std::os::raw::c_void;
+impl Default for rte_crypto_asym_op {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Flush a user-owned mempool cache to the specified mempool."]
-    #[doc = ""]
-    #[doc = " @param cache"]
-    #[doc = "   A pointer to the mempool cache."]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool."]
-    pub fn _rte_mempool_cache_flush(cache: *mut rte_mempool_cache, mp: *mut rte_mempool);
+#[doc = " Cryptographic Operation."]
+#[doc = ""]
+#[doc = " This structure contains data relating to performing cryptographic"]
+#[doc = " operations. This operation structure is used to contain any operation which"]
+#[doc = " is supported by the cryptodev API, PMDs should check the type parameter to"]
+#[doc = " verify that the operation is a support function of the device. Crypto"]
+#[doc = " operations are enqueued and dequeued in crypto PMDs using the"]
+#[doc = " rte_cryptodev_enqueue_burst() / rte_cryptodev_dequeue_burst() ."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_crypto_op {
+    #[doc = "< operation type"]
+    pub type_: u8,
+    #[doc = "< operation status - this is reset to"]
+    #[doc = " RTE_CRYPTO_OP_STATUS_NOT_PROCESSED on allocation"]
+    #[doc = " from mempool and will be set to"]
+    #[doc = " RTE_CRYPTO_OP_STATUS_SUCCESS after crypto operation"]
+    #[doc = " is successfully processed by a crypto PMD"]
+    pub status: u8,
+    #[doc = "< operation session type"]
+    pub sess_type: u8,
+    #[doc = "< Reserved bytes to fill 64 bits for future additions"]
+    pub reserved: [u8; 3usize],
+    #[doc = "< Offset to indicate start of private data (if any). The offset"]
+    #[doc = " is counted from the start of the rte_crypto_op including IV."]
+    #[doc = " The private data may be used by the application to store"]
+    #[doc = " information which should remain untouched in the library/driver"]
+    pub private_data_offset: u16,
+    #[doc = "< crypto operation mempool which operation is allocated from"]
+    pub mempool: *mut rte_mempool,
+    #[doc = "< physical address of crypto operation"]
+    pub phys_addr: rte_iova_t,
+}
+impl Default for rte_crypto_op {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Get a pointer to the per-lcore default mempool cache."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param lcore_id"]
-    #[doc = "   The logical core id."]
-    #[doc = " @return"]
-    #[doc = "   A pointer to the mempool cache or NULL if disabled or non-EAL thread."]
-    pub fn _rte_mempool_default_cache(mp: *mut rte_mempool, lcore_id: ::std::os::raw::c_uint)
-        -> *mut rte_mempool_cache;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_cryptodev_capabilities {
+    _unused: [u8; 0],
 }
-extern "C" {
-    #[doc = " Put several objects back in the mempool."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj_table"]
-    #[doc = "   A pointer to a table of void * pointers (objects)."]
-    #[doc = " @param n"]
-    #[doc = "   The number of objects to add in the mempool from the obj_table."]
-    #[doc = " @param cache"]
-    #[doc = "   A pointer to a mempool cache structure. May be NULL if not needed."]
-    pub fn _rte_mempool_generic_put(
-        mp: *mut rte_mempool,
-        obj_table: *const *mut ::std::os::raw::c_void,
-        n: ::std::os::raw::c_uint,
-        cache: *mut rte_mempool_cache,
-    );
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_cryptodev_asymmetric_xform_capability {
+    _unused: [u8; 0],
 }
-extern "C" {
-    #[doc = " Put several objects back in the mempool."]
-    #[doc = ""]
-    #[doc = " This function calls the multi-producer or the single-producer"]
-    #[doc = " version depending on the default behavior that was specified at"]
-    #[doc = " mempool creation time (see flags)."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj_table"]
-    #[doc = "   A pointer to a table of void * pointers (objects)."]
-    #[doc = " @param n"]
-    #[doc = "   The number of objects to add in the mempool from obj_table."]
-    pub fn _rte_mempool_put_bulk(
-        mp: *mut rte_mempool,
-        obj_table: *const *mut ::std::os::raw::c_void,
-        n: ::std::os::raw::c_uint,
-    );
+#[doc = " Structure used to describe crypto algorithms"]
+#[doc = " Each algorithm is specified by its xform type."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_cryptodev_asym_capability_idx {
+    #[doc = "< Asymmetric xform (algo) type"]
+    pub type_: rte_crypto_asym_xform_type::Type,
 }
-extern "C" {
-    #[doc = " Put one object back in the mempool."]
-    #[doc = ""]
-    #[doc = " This function calls the multi-producer or the single-producer"]
-    #[doc = " version depending on the default behavior that was specified at"]
-    #[doc = " mempool creation time (see flags)."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj"]
-    #[doc = "   A pointer to the object to be added."]
-    pub fn _rte_mempool_put(mp: *mut rte_mempool, obj: *mut ::std::os::raw::c_void);
+#[doc = "  Crypto device information"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_cryptodev_info {
+    #[doc = "< Driver name."]
+    pub driver_name: *const ::std::os::raw::c_char,
+    #[doc = "< Driver identifier"]
+    pub driver_id: u8,
+    #[doc = "< Generic device information."]
+    pub device: *mut rte_device,
+    #[doc = "< Feature flags exposes HW/SW features for the"]
+    #[doc = " given device"]
+    pub feature_flags: u64,
+    #[doc = "< Array of devices supported capabilities"]
+    pub capabilities: *const rte_cryptodev_capabilities,
+    #[doc = "< Maximum number of queues pairs supported by device."]
+    pub max_nb_queue_pairs: ::std::os::raw::c_uint,
+    #[doc = "< Minimum mbuf headroom required by device"]
+    pub min_mbuf_headroom_req: u16,
+    #[doc = "< Minimum mbuf tailroom required by device"]
+    pub min_mbuf_tailroom_req: u16,
+    pub sym: rte_cryptodev_info__bindgen_ty_1,
 }
-extern "C" {
-    #[doc = " Get several objects from the mempool."]
-    #[doc = ""]
-    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
-    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
-    #[doc = " the local cache and common pool are empty, even if cache from other"]
-    #[doc = " lcores are full."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj_table"]
-    #[doc = "   A pointer to a table of void * pointers (objects) that will be filled."]
-    #[doc = " @param n"]
-    #[doc = "   The number of objects to get from mempool to obj_table."]
-    #[doc = " @param cache"]
-    #[doc = "   A pointer to a mempool cache structure. May be NULL if not needed."]
-    #[doc = " @return"]
-    #[doc = "   - 0: Success; objects taken."]
-    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
-    pub fn _rte_mempool_generic_get(
-        mp: *mut rte_mempool,
-        obj_table: *mut *mut ::std::os::raw::c_void,
-        n: ::std::os::raw::c_uint,
-        cache: *mut rte_mempool_cache,
-    ) -> ::std::os::raw::c_int;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_cryptodev_info__bindgen_ty_1 {
+    #[doc = "< Maximum number of sessions supported by device."]
+    #[doc = " If 0, the device does not have any limitation in"]
+    #[doc = " number of sessions that can be used."]
+    pub max_nb_sessions: ::std::os::raw::c_uint,
+}
+impl Default for rte_cryptodev_info {
+    fn default() -> Self {
+        unsafe { ::std::mem::zeroed() }
+    }
 }
-extern "C" {
-    #[doc = " Get several objects from the mempool."]
-    #[doc = ""]
-    #[doc = " This function calls the multi-consumers or the single-consumer"]
-    #[doc = " version, depending on the default behaviour that was specified at"]
-    #[doc = " mempool creation time (see flags)."]
-    #[doc = ""]
-    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
-    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
-    #[doc = " the local cache and common pool are empty, even if cache from other"]
-    #[doc = " lcores are full."]
-    #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj_table"]
-    #[doc = "   A pointer to a table of void * pointers (objects) that will be filled."]
-    #[doc = " @param n"]
-    #[doc = "   The number of objects to get from the mempool to obj_table."]
-    #[doc = " @return"]
-    #[doc = "   - 0: Success; objects taken"]
-    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
-    pub fn _rte_mempool_get_bulk(
-        mp: *mut rte_mempool,
-        obj_table: *mut *mut ::std::os::raw::c_void,
-        n: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
+#[doc = " Crypto device configuration structure"]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_cryptodev_config {
+    #[doc = "< Socket to allocate resources on"]
+    pub socket_id: ::std::os::raw::c_int,
+    #[doc = "< Number of queue pairs to configure on device"]
+    pub nb_queue_pairs: u16,
+    #[doc = "< Feature flags to be disabled. Only the following features are"]
+    #[doc = " allowed to be disabled,"]
+    #[doc = "  - RTE_CRYPTODEV_FF_SYMMETRIC_CRYPTO"]
+    #[doc = "  - RTE_CRYPTODEV_FF_ASYMMETRIC_CRYPTO"]
+    #[doc = "  - RTE_CRYTPODEV_FF_SECURITY"]
+    pub ff_disable: u64,
+}
+#[doc = " Crypto device queue pair configuration structure."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct rte_cryptodev_qp_conf {
+    #[doc = "< Number of descriptors per queue pair"]
+    pub nb_descriptors: u32,
 }
 extern "C" {
-    #[doc = " Get one object from the mempool."]
-    #[doc = ""]
-    #[doc = " This function calls the multi-consumers or the single-consumer"]
-    #[doc = " version, depending on the default behavior that was specified at"]
-    #[doc = " mempool creation (see flags)."]
-    #[doc = ""]
-    #[doc = " If cache is enabled, objects will be retrieved first from cache,"]
-    #[doc = " subsequently from the common pool. Note that it can return -ENOENT when"]
-    #[doc = " the local cache and common pool are empty, even if cache from other"]
-    #[doc = " lcores are full."]
+    #[doc = " Get the total number of crypto devices that have been successfully"]
+    #[doc = " initialised."]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param obj_p"]
-    #[doc = "   A pointer to a void * pointer (object) that will be filled."]
     #[doc = " @return"]
-    #[doc = "   - 0: Success; objects taken."]
-    #[doc = "   - -ENOENT: Not enough entries in the mempool; no object is retrieved."]
-    pub fn _rte_mempool_get(mp: *mut rte_mempool, obj_p: *mut *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int;
+    #[doc = "   - The total number of usable crypto devices."]
+    pub fn rte_cryptodev_count() -> u8;
 }
 extern "C" {
-    #[doc = " @warning"]
-    #[doc = " @b EXPERIMENTAL: this API may change without prior notice."]
-    #[doc = ""]
-    #[doc = " Get a contiguous blocks of objects from the mempool."]
-    #[doc = ""]
-    #[doc = " If cache is enabled, consider to flush it first, to reuse objects"]
-    #[doc = " as soon as possible."]
+    #[doc = " Get the device identifier for the named crypto device."]
     #[doc = ""]
-    #[doc = " The application should check that the driver supports the operation"]
-    #[doc = " by calling rte_mempool_ops_get_info() and checking that `contig_block_size`"]
-    #[doc = " is not zero."]
+    #[doc = " @param\tname\tdevice name to select the device structure."]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   A pointer to the mempool structure."]
-    #[doc = " @param first_obj_table"]
-    #[doc = "   A pointer to a pointer to the first object in each block."]
-    #[doc = " @param n"]
-    #[doc = "   The number of blocks to get from mempool."]
     #[doc = " @return"]
-    #[doc = "   - 0: Success; blocks taken."]
-    #[doc = "   - -ENOBUFS: Not enough entries in the mempool; no object is retrieved."]
-    #[doc = "   - -EOPNOTSUPP: The mempool driver does not support block dequeue"]
-    pub fn _rte_mempool_get_contig_blocks(
-        mp: *mut rte_mempool,
-        first_obj_table: *mut *mut ::std::os::raw::c_void,
-        n: ::std::os::raw::c_uint,
-    ) -> ::std::os::raw::c_int;
+    #[doc = "   - Returns crypto device identifier on success."]
+    #[doc = "   - Return -1 on failure to find named crypto device."]
+    pub fn rte_cryptodev_get_dev_id(name: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Prefetch the first part of the mbuf"]
+    #[doc = " Get the crypto device name given a device identifier."]
     #[doc = ""]
-    #[doc = " The first 64 bytes of the mbuf corresponds to fields that are used early"]
-    #[doc = " in the receive path. If the cache line of the architecture is higher than"]
-    #[doc = " 64B, the second part will also be prefetched."]
+    #[doc = " @param dev_id"]
+    #[doc = "   The identifier of the device"]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pointer to the mbuf."]
-    pub fn _rte_mbuf_prefetch_part1(m: *mut rte_mbuf);
+    #[doc = " @return"]
+    #[doc = "   - Returns crypto device name."]
+    #[doc = "   - Returns NULL if crypto device is not present."]
+    pub fn rte_cryptodev_name_get(dev_id: u8) -> *const ::std::os::raw::c_char;
 }
 extern "C" {
-    #[doc = " Prefetch the second part of the mbuf"]
-    #[doc = ""]
-    #[doc = " The next 64 bytes of the mbuf corresponds to fields that are used in the"]
-    #[doc = " transmit path. If the cache line of the architecture is higher than 64B,"]
-    #[doc = " this function does nothing as it is expected that the full mbuf is"]
-    #[doc = " already in cache."]
+    #[doc = " Retrieve the contextual information of a device."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pointer to the mbuf."]
-    pub fn _rte_mbuf_prefetch_part2(m: *mut rte_mbuf);
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
+    #[doc = " @param\tdev_info\tA pointer to a structure of type"]
+    #[doc = "\t\t\t*rte_cryptodev_info* to be filled with the"]
+    #[doc = "\t\t\tcontextual information of the device."]
+    pub fn rte_cryptodev_info_get(dev_id: u8, dev_info: *mut rte_cryptodev_info);
 }
 extern "C" {
-    #[doc = " Return the IO address of the beginning of the mbuf data"]
+    #[doc = " Return the NUMA socket to which a device is connected"]
     #[doc = ""]
-    #[doc = " @param mb"]
-    #[doc = "   The pointer to the mbuf."]
+    #[doc = " @param dev_id"]
+    #[doc = "   The identifier of the device"]
     #[doc = " @return"]
-    #[doc = "   The IO address of the beginning of the mbuf data"]
-    pub fn _rte_mbuf_data_iova(mb: *const rte_mbuf) -> rte_iova_t;
+    #[doc = "   The NUMA socket id to which the device is connected or"]
+    #[doc = "   a default of zero if the socket could not be determined."]
+    #[doc = "   -1 if returned is the dev_id value is out of range."]
+    pub fn rte_cryptodev_socket_id(dev_id: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Return the default IO address of the beginning of the mbuf data"]
-    #[doc = ""]
-    #[doc = " This function is used by drivers in their receive function, as it"]
-    #[doc = " returns the location where data should be written by the NIC, taking"]
-    #[doc = " the default headroom in account."]
+    #[doc = " Configure a device."]
     #[doc = ""]
-    #[doc = " @param mb"]
-    #[doc = "   The pointer to the mbuf."]
-    #[doc = " @return"]
-    #[doc = "   The IO address of the beginning of the mbuf data"]
-    pub fn _rte_mbuf_data_iova_default(mb: *const rte_mbuf) -> rte_iova_t;
-}
-extern "C" {
-    #[doc = " Return the mbuf owning the data buffer address of an indirect mbuf."]
+    #[doc = " This function must be invoked first before any other function in the"]
+    #[doc = " API. This function can also be re-invoked when a device is in the"]
+    #[doc = " stopped state."]
     #[doc = ""]
-    #[doc = " @param mi"]
-    #[doc = "   The pointer to the indirect mbuf."]
-    #[doc = " @return"]
-    #[doc = "   The address of the direct mbuf corresponding to buffer_addr."]
-    pub fn _rte_mbuf_from_indirect(mi: *mut rte_mbuf) -> *mut rte_mbuf;
-}
-extern "C" {
-    #[doc = " Return the buffer address embedded in the given mbuf."]
+    #[doc = " @param\tdev_id\t\tThe identifier of the device to configure."]
+    #[doc = " @param\tconfig\t\tThe crypto device configuration structure."]
     #[doc = ""]
-    #[doc = " @param md"]
-    #[doc = "   The pointer to the mbuf."]
     #[doc = " @return"]
-    #[doc = "   The address of the data buffer owned by the mbuf."]
-    pub fn _rte_mbuf_to_baddr(md: *mut rte_mbuf) -> *mut ::std::os::raw::c_char;
+    #[doc = "   - 0: Success, device configured."]
+    #[doc = "   - <0: Error code returned by the driver configuration function."]
+    pub fn rte_cryptodev_configure(dev_id: u8, config: *mut rte_cryptodev_config) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Return the starting address of the private data area embedded in"]
-    #[doc = " the given mbuf."]
+    #[doc = " Start an device."]
     #[doc = ""]
-    #[doc = " Note that no check is made to ensure that a private data area"]
-    #[doc = " actually exists in the supplied mbuf."]
+    #[doc = " The device start step is the last one and consists of setting the configured"]
+    #[doc = " offload features and in starting the transmit and the receive units of the"]
+    #[doc = " device."]
+    #[doc = " On success, all basic functions exported by the API (link status,"]
+    #[doc = " receive/transmit, and so on) can be invoked."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pointer to the mbuf."]
-    #[doc = " @return"]
-    #[doc = "   The starting address of the private data area of the given mbuf."]
-    pub fn _rte_mbuf_to_priv(m: *mut rte_mbuf) -> *mut ::std::os::raw::c_void;
-}
-extern "C" {
-    #[doc = " Reads the value of an mbuf's refcnt."]
-    #[doc = " @param m"]
-    #[doc = "   Mbuf to read"]
-    #[doc = " @return"]
-    #[doc = "   Reference count number."]
-    pub fn _rte_mbuf_refcnt_read(m: *const rte_mbuf) -> u16;
-}
-extern "C" {
-    #[doc = " Sets an mbuf's refcnt to a defined value."]
-    #[doc = " @param m"]
-    #[doc = "   Mbuf to update"]
-    #[doc = " @param new_value"]
-    #[doc = "   Value set"]
-    pub fn _rte_mbuf_refcnt_set(m: *mut rte_mbuf, new_value: u16);
-}
-extern "C" {
-    #[doc = " Adds given value to an mbuf's refcnt and returns its new value."]
-    #[doc = " @param m"]
-    #[doc = "   Mbuf to update"]
-    #[doc = " @param value"]
-    #[doc = "   Value to add/subtract"]
+    #[doc = " @param dev_id"]
+    #[doc = "   The identifier of the device."]
     #[doc = " @return"]
-    #[doc = "   Updated value"]
-    pub fn _rte_mbuf_refcnt_update(m: *mut rte_mbuf, value: i16) -> u16;
+    #[doc = "   - 0: Success, device started."]
+    #[doc = "   - <0: Error code of the driver device start function."]
+    pub fn rte_cryptodev_start(dev_id: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Reads the refcnt of an external buffer."]
+    #[doc = " Stop an device. The device can be restarted with a call to"]
+    #[doc = " rte_cryptodev_start()"]
     #[doc = ""]
-    #[doc = " @param shinfo"]
-    #[doc = "   Shared data of the external buffer."]
-    #[doc = " @return"]
-    #[doc = "   Reference count number."]
-    pub fn _rte_mbuf_ext_refcnt_read(shinfo: *const rte_mbuf_ext_shared_info) -> u16;
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
+    pub fn rte_cryptodev_stop(dev_id: u8);
 }
 extern "C" {
-    #[doc = " Set refcnt of an external buffer."]
+    #[doc = " Close an device. The device cannot be restarted!"]
     #[doc = ""]
-    #[doc = " @param shinfo"]
-    #[doc = "   Shared data of the external buffer."]
-    #[doc = " @param new_value"]
-    #[doc = "   Value set"]
-    pub fn _rte_mbuf_ext_refcnt_set(shinfo: *mut rte_mbuf_ext_shared_info, new_value: u16);
-}
-extern "C" {
-    #[doc = " Add given value to refcnt of an external buffer and return its new"]
-    #[doc = " value."]
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
     #[doc = ""]
-    #[doc = " @param shinfo"]
-    #[doc = "   Shared data of the external buffer."]
-    #[doc = " @param value"]
-    #[doc = "   Value to add/subtract"]
     #[doc = " @return"]
-    #[doc = "   Updated value"]
-    pub fn _rte_mbuf_ext_refcnt_update(shinfo: *mut rte_mbuf_ext_shared_info, value: i16) -> u16;
+    #[doc = "  - 0 on successfully closing device"]
+    #[doc = "  - <0 on failure to close device"]
+    pub fn rte_cryptodev_close(dev_id: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Allocate an uninitialized mbuf from mempool *mp*."]
+    #[doc = " Allocate and set up a receive queue pair for a device."]
     #[doc = ""]
-    #[doc = " This function can be used by PMDs (especially in RX functions) to"]
-    #[doc = " allocate an uninitialized mbuf. The driver is responsible of"]
-    #[doc = " initializing all the required fields. See rte_pktmbuf_reset()."]
-    #[doc = " For standard needs, prefer rte_pktmbuf_alloc()."]
     #[doc = ""]
-    #[doc = " The caller can expect that the following fields of the mbuf structure"]
-    #[doc = " are initialized: buf_addr, buf_iova, buf_len, refcnt=1, nb_segs=1,"]
-    #[doc = " next=NULL, pool, priv_size. The other fields must be initialized"]
-    #[doc = " by the caller."]
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
+    #[doc = " @param\tqueue_pair_id\tThe index of the queue pairs to set up. The"]
+    #[doc = "\t\t\t\tvalue must be in the range [0, nb_queue_pair"]
+    #[doc = "\t\t\t\t- 1] previously supplied to"]
+    #[doc = "\t\t\t\trte_cryptodev_configure()."]
+    #[doc = " @param\tqp_conf\t\tThe pointer to the configuration data to be"]
+    #[doc = "\t\t\t\tused for the queue pair. NULL value is"]
+    #[doc = "\t\t\t\tallowed, in which case default configuration"]
+    #[doc = "\t\t\t\twill be used."]
+    #[doc = " @param\tsocket_id\tThe *socket_id* argument is the socket"]
+    #[doc = "\t\t\t\tidentifier in case of NUMA. The value can be"]
+    #[doc = "\t\t\t\t*SOCKET_ID_ANY* if there is no NUMA constraint"]
+    #[doc = "\t\t\t\tfor the DMA memory allocated for the receive"]
+    #[doc = "\t\t\t\tqueue pair."]
+    #[doc = " @param\tsession_pool\tPointer to device session mempool, used"]
+    #[doc = "\t\t\t\tfor session-less operations."]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   The mempool from which mbuf is allocated."]
     #[doc = " @return"]
-    #[doc = "   - The pointer to the new mbuf on success."]
-    #[doc = "   - NULL if allocation failed."]
-    pub fn _rte_mbuf_raw_alloc(mp: *mut rte_mempool) -> *mut rte_mbuf;
+    #[doc = "   - 0: Success, queue pair correctly set up."]
+    #[doc = "   - <0: Queue pair configuration failed"]
+    pub fn rte_cryptodev_queue_pair_setup(
+        dev_id: u8,
+        queue_pair_id: u16,
+        qp_conf: *const rte_cryptodev_qp_conf,
+        socket_id: ::std::os::raw::c_int,
+        session_pool: *mut rte_mempool,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Put mbuf back into its original mempool."]
-    #[doc = ""]
-    #[doc = " The caller must ensure that the mbuf is direct and properly"]
-    #[doc = " reinitialized (refcnt=1, next=NULL, nb_segs=1), as done by"]
-    #[doc = " rte_pktmbuf_prefree_seg()."]
+    #[doc = " Get the number of queue pairs on a specific crypto device"]
     #[doc = ""]
-    #[doc = " This function should be used with care, when optimization is"]
-    #[doc = " required. For standard needs, prefer rte_pktmbuf_free() or"]
-    #[doc = " rte_pktmbuf_free_seg()."]
-    #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The mbuf to be freed."]
-    pub fn _rte_mbuf_raw_free(m: *mut rte_mbuf);
+    #[doc = " @param\tdev_id\t\tCrypto device identifier."]
+    #[doc = " @return"]
+    #[doc = "   - The number of configured queue pairs."]
+    pub fn rte_cryptodev_queue_pair_count(dev_id: u8) -> u16;
 }
 extern "C" {
-    #[doc = " Get the data room size of mbufs stored in a pktmbuf_pool"]
+    #[doc = "  Provide capabilities available for defined device and xform"]
     #[doc = ""]
-    #[doc = " The data room size is the amount of data that can be stored in a"]
-    #[doc = " mbuf including the headroom (RTE_PKTMBUF_HEADROOM)."]
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
+    #[doc = " @param\tidx\t\tDescription of asym crypto xform."]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   The packet mbuf pool."]
     #[doc = " @return"]
-    #[doc = "   The data room size of mbufs stored in this mempool."]
-    pub fn _rte_pktmbuf_data_room_size(mp: *mut rte_mempool) -> u16;
+    #[doc = "   - Return description of the asymmetric crypto capability if exist."]
+    #[doc = "   - Return NULL if the capability not exist."]
+    pub fn rte_cryptodev_asym_capability_get(
+        dev_id: u8,
+        idx: *const rte_cryptodev_asym_capability_idx,
+    ) -> *const rte_cryptodev_asymmetric_xform_capability;
 }
 extern "C" {
-    #[doc = " Get the application private size of mbufs stored in a pktmbuf_pool"]
+    #[doc = " Check if op type is supported"]
     #[doc = ""]
-    #[doc = " The private size of mbuf is a zone located between the rte_mbuf"]
-    #[doc = " structure and the data buffer where an application can store data"]
-    #[doc = " associated to a packet."]
+    #[doc = " @param\tcapability\tDescription of the asymmetric crypto capability."]
+    #[doc = " @param\top_type\t\top type"]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   The packet mbuf pool."]
     #[doc = " @return"]
-    #[doc = "   The private size of mbufs stored in this mempool."]
-    pub fn _rte_pktmbuf_priv_size(mp: *mut rte_mempool) -> u16;
+    #[doc = "   - Return 1 if the op type is supported"]
+    #[doc = "   - Return 0 if unsupported"]
+    pub fn rte_cryptodev_asym_xform_capability_check_optype(
+        capability: *const rte_cryptodev_asymmetric_xform_capability,
+        op_type: rte_crypto_asym_op_type::Type,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Reset the data_off field of a packet mbuf to its default value."]
+    #[doc = " Check if modulus length is in supported range"]
     #[doc = ""]
-    #[doc = " The given mbuf must have only one segment, which should be empty."]
+    #[doc = " @param\tcapability\tDescription of the asymmetric crypto capability."]
+    #[doc = " @param\tmodlen\t\tmodulus length."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf's data_off field has to be reset."]
-    pub fn _rte_pktmbuf_reset_headroom(m: *mut rte_mbuf);
+    #[doc = " @return"]
+    #[doc = "   - Return 0 if the parameters are in range of the capability."]
+    #[doc = "   - Return -1 if the parameters are out of range of the capability."]
+    pub fn rte_cryptodev_asym_xform_capability_check_modlen(
+        capability: *const rte_cryptodev_asymmetric_xform_capability,
+        modlen: u16,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Reset the fields of a packet mbuf to their default values."]
+    #[doc = " Create asymmetric crypto session header (generic with no private data)"]
     #[doc = ""]
-    #[doc = " The given mbuf must have only one segment."]
-    #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf to be resetted."]
-    pub fn _rte_pktmbuf_reset(m: *mut rte_mbuf);
+    #[doc = " @param   mempool    mempool to allocate asymmetric session"]
+    #[doc = "                     objects from"]
+    #[doc = " @return"]
+    #[doc = "  - On success return pointer to asym-session"]
+    #[doc = "  - On failure returns NULL"]
+    pub fn rte_cryptodev_asym_session_create(mempool: *mut rte_mempool) -> *mut rte_cryptodev_asym_session;
 }
 extern "C" {
-    #[doc = " Allocate a new mbuf from a mempool."]
+    #[doc = " Frees asymmetric crypto session header, after checking that all"]
+    #[doc = " the device private data has been freed, returning it"]
+    #[doc = " to its original mempool."]
     #[doc = ""]
-    #[doc = " This new mbuf contains one segment, which has a length of 0. The pointer"]
-    #[doc = " to data is initialized to have some bytes of headroom in the buffer"]
-    #[doc = " (if buffer size allows)."]
+    #[doc = " @param   sess     Session header to be freed."]
     #[doc = ""]
-    #[doc = " @param mp"]
-    #[doc = "   The mempool from which the mbuf is allocated."]
-    #[doc = " @return"]
-    #[doc = "   - The pointer to the new mbuf on success."]
-    #[doc = "   - NULL if allocation failed."]
-    pub fn _rte_pktmbuf_alloc(mp: *mut rte_mempool) -> *mut rte_mbuf;
+    #[doc = " @return"]
+    #[doc = "  - 0 if successful."]
+    #[doc = "  - -EINVAL if session is NULL."]
+    #[doc = "  - -EBUSY if not all device private data has been freed."]
+    pub fn rte_cryptodev_asym_session_free(sess: *mut rte_cryptodev_asym_session) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Allocate a bulk of mbufs, initialize refcnt and reset the fields to default"]
-    #[doc = " values."]
+    #[doc = " Initialize asymmetric session on a device with specific asymmetric xform"]
     #[doc = ""]
-    #[doc = "  @param pool"]
-    #[doc = "    The mempool from which mbufs are allocated."]
-    #[doc = "  @param mbufs"]
-    #[doc = "    Array of pointers to mbufs"]
-    #[doc = "  @param count"]
-    #[doc = "    Array size"]
-    #[doc = "  @return"]
-    #[doc = "   - 0: Success"]
-    #[doc = "   - -ENOENT: Not enough entries in the mempool; no mbufs are retrieved."]
-    pub fn _rte_pktmbuf_alloc_bulk(
-        pool: *mut rte_mempool,
-        mbufs: *mut *mut rte_mbuf,
-        count: ::std::os::raw::c_uint,
+    #[doc = " @param   dev_id   ID of device that we want the session to be used on"]
+    #[doc = " @param   sess     Session to be set up on a device"]
+    #[doc = " @param   xforms   Asymmetric crypto transform operations to apply on flow"]
+    #[doc = "                   processed with this session"]
+    #[doc = " @param   mempool  Mempool to be used for internal allocation."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "  - On success, zero."]
+    #[doc = "  - -EINVAL if input parameters are invalid."]
+    #[doc = "  - -ENOTSUP if crypto device does not support the crypto transform."]
+    #[doc = "  - -ENOMEM if the private session could not be allocated."]
+    pub fn rte_cryptodev_asym_session_init(
+        dev_id: u8,
+        sess: *mut rte_cryptodev_asym_session,
+        xforms: *mut rte_crypto_asym_xform,
+        mempool: *mut rte_mempool,
     ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Initialize shared data at the end of an external buffer before attaching"]
-    #[doc = " to a mbuf by ``rte_pktmbuf_attach_extbuf()``. This is not a mandatory"]
-    #[doc = " initialization but a helper function to simply spare a few bytes at the"]
-    #[doc = " end of the buffer for shared data. If shared data is allocated"]
-    #[doc = " separately, this should not be called but application has to properly"]
-    #[doc = " initialize the shared data according to its need."]
-    #[doc = ""]
-    #[doc = " Free callback and its argument is saved and the refcnt is set to 1."]
-    #[doc = ""]
-    #[doc = " @warning"]
-    #[doc = " The value of buf_len will be reduced to RTE_PTR_DIFF(shinfo, buf_addr)"]
-    #[doc = " after this initialization. This shall be used for"]
-    #[doc = " ``rte_pktmbuf_attach_extbuf()``"]
+    #[doc = " Frees private data for the device id, based on its device type,"]
+    #[doc = " returning it to its mempool. It is the application's responsibility"]
+    #[doc = " to ensure that private session data is not cleared while there are"]
+    #[doc = " still in-flight operations using it."]
     #[doc = ""]
-    #[doc = " @param buf_addr"]
-    #[doc = "   The pointer to the external buffer."]
-    #[doc = " @param [in,out] buf_len"]
-    #[doc = "   The pointer to length of the external buffer. Input value must be"]
-    #[doc = "   larger than the size of ``struct rte_mbuf_ext_shared_info`` and"]
-    #[doc = "   padding for alignment. If not enough, this function will return NULL."]
-    #[doc = "   Adjusted buffer length will be returned through this pointer."]
-    #[doc = " @param free_cb"]
-    #[doc = "   Free callback function to call when the external buffer needs to be"]
-    #[doc = "   freed."]
-    #[doc = " @param fcb_opaque"]
-    #[doc = "   Argument for the free callback function."]
+    #[doc = " @param   dev_id   ID of device that uses the asymmetric session."]
+    #[doc = " @param   sess     Asymmetric session setup on device using"]
+    #[doc = "\t\t\t\t\t the device id"]
     #[doc = ""]
     #[doc = " @return"]
-    #[doc = "   A pointer to the initialized shared data on success, return NULL"]
-    #[doc = "   otherwise."]
-    pub fn _rte_pktmbuf_ext_shinfo_init_helper(
-        buf_addr: *mut ::std::os::raw::c_void,
-        buf_len: *mut u16,
-        free_cb: rte_mbuf_extbuf_free_callback_t,
-        fcb_opaque: *mut ::std::os::raw::c_void,
-    ) -> *mut rte_mbuf_ext_shared_info;
+    #[doc = "  - 0 if successful."]
+    #[doc = "  - -EINVAL if device is invalid or session is NULL."]
+    pub fn rte_cryptodev_asym_session_clear(dev_id: u8, sess: *mut rte_cryptodev_asym_session) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Attach an external buffer to a mbuf."]
+    #[doc = " Get the size of the asymmetric session header, for all registered drivers."]
     #[doc = ""]
-    #[doc = " User-managed anonymous buffer can be attached to an mbuf. When attaching"]
-    #[doc = " it, corresponding free callback function and its argument should be"]
-    #[doc = " provided via shinfo. This callback function will be called once all the"]
-    #[doc = " mbufs are detached from the buffer (refcnt becomes zero)."]
+    #[doc = " @return"]
+    #[doc = "   Size of the asymmetric header session."]
+    pub fn rte_cryptodev_asym_get_header_session_size() -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Get the size of the private data for asymmetric session"]
+    #[doc = " on device"]
     #[doc = ""]
-    #[doc = " The headroom for the attaching mbuf will be set to zero and this can be"]
-    #[doc = " properly adjusted after attachment. For example, ``rte_pktmbuf_adj()``"]
-    #[doc = " or ``rte_pktmbuf_reset_headroom()`` might be used."]
+    #[doc = " @param\tdev_id\t\tThe device identifier."]
     #[doc = ""]
-    #[doc = " More mbufs can be attached to the same external buffer by"]
-    #[doc = " ``rte_pktmbuf_attach()`` once the external buffer has been attached by"]
-    #[doc = " this API."]
+    #[doc = " @return"]
+    #[doc = "   - Size of the asymmetric private data, if successful"]
+    #[doc = "   - 0 if device is invalid or does not have private session"]
+    pub fn rte_cryptodev_asym_get_private_session_size(dev_id: u8) -> ::std::os::raw::c_uint;
+}
+extern "C" {
+    #[doc = " Creates a crypto operation pool"]
     #[doc = ""]
-    #[doc = " Detachment can be done by either ``rte_pktmbuf_detach_extbuf()`` or"]
-    #[doc = " ``rte_pktmbuf_detach()``."]
+    #[doc = " @param\tname\t\tpool name"]
+    #[doc = " @param\ttype\t\tcrypto operation type, use"]
+    #[doc = "\t\t\t\tRTE_CRYPTO_OP_TYPE_UNDEFINED for a pool which"]
+    #[doc = "\t\t\t\tsupports all operation types"]
+    #[doc = " @param\tnb_elts\t\tnumber of elements in pool"]
+    #[doc = " @param\tcache_size\tNumber of elements to cache on lcore, see"]
+    #[doc = "\t\t\t\t*rte_mempool_create* for further details about"]
+    #[doc = "\t\t\t\tcache size"]
+    #[doc = " @param\tpriv_size\tSize of private data to allocate with each"]
+    #[doc = "\t\t\t\toperation"]
+    #[doc = " @param\tsocket_id\tSocket to allocate memory on"]
     #[doc = ""]
-    #[doc = " Memory for shared data must be provided and user must initialize all of"]
-    #[doc = " the content properly, escpecially free callback and refcnt. The pointer"]
-    #[doc = " of shared data will be stored in m->shinfo."]
-    #[doc = " ``rte_pktmbuf_ext_shinfo_init_helper`` can help to simply spare a few"]
-    #[doc = " bytes at the end of buffer for the shared data, store free callback and"]
-    #[doc = " its argument and set the refcnt to 1. The following is an example:"]
+    #[doc = " @return"]
+    #[doc = "  - On success pointer to mempool"]
+    #[doc = "  - On failure NULL"]
+    pub fn rte_crypto_op_pool_create(
+        name: *const ::std::os::raw::c_char,
+        type_: rte_crypto_op_type::Type,
+        nb_elts: ::std::os::raw::c_uint,
+        cache_size: ::std::os::raw::c_uint,
+        priv_size: u16,
+        socket_id: ::std::os::raw::c_int,
+    ) -> *mut rte_mempool;
+}
+extern "C" {
+    #[doc = " Allocate a crypto operation from a mempool with default parameters set"]
     #[doc = ""]
-    #[doc = "   struct rte_mbuf_ext_shared_info *shinfo ="]
-    #[doc = "          rte_pktmbuf_ext_shinfo_init_helper(buf_addr, &buf_len,"]
-    #[doc = "                                             free_cb, fcb_arg);"]
-    #[doc = "   rte_pktmbuf_attach_extbuf(m, buf_addr, buf_iova, buf_len, shinfo);"]
-    #[doc = "   rte_pktmbuf_reset_headroom(m);"]
-    #[doc = "   rte_pktmbuf_adj(m, data_len);"]
+    #[doc = " @param\tmempool\tcrypto operation mempool"]
+    #[doc = " @param\ttype\toperation type to allocate"]
     #[doc = ""]
-    #[doc = " Attaching an external buffer is quite similar to mbuf indirection in"]
-    #[doc = " replacing buffer addresses and length of a mbuf, but a few differences:"]
-    #[doc = " - When an indirect mbuf is attached, refcnt of the direct mbuf would be"]
-    #[doc = "   2 as long as the direct mbuf itself isn't freed after the attachment."]
-    #[doc = "   In such cases, the buffer area of a direct mbuf must be read-only. But"]
-    #[doc = "   external buffer has its own refcnt and it starts from 1. Unless"]
-    #[doc = "   multiple mbufs are attached to a mbuf having an external buffer, the"]
-    #[doc = "   external buffer is writable."]
-    #[doc = " - There's no need to allocate buffer from a mempool. Any buffer can be"]
-    #[doc = "   attached with appropriate free callback and its IO address."]
-    #[doc = " - Smaller metadata is required to maintain shared data such as refcnt."]
+    #[doc = " @returns"]
+    #[doc = " - On success returns a valid rte_crypto_op structure"]
+    #[doc = " - On failure returns NULL"]
+    pub fn _rte_crypto_op_alloc(mempool: *mut rte_mempool, type_: rte_crypto_op_type::Type) -> *mut rte_crypto_op;
+}
+extern "C" {
+    #[doc = " Free crypto operation structure"]
+    #[doc = " If operation has been allocate from a rte_mempool, then the operation will"]
+    #[doc = " be returned to the mempool."]
     #[doc = ""]
-    #[doc = " @warning"]
-    #[doc = " @b EXPERIMENTAL: This API may change without prior notice."]
-    #[doc = " Once external buffer is enabled by allowing experimental API,"]
-    #[doc = " ``RTE_MBUF_DIRECT()`` and ``RTE_MBUF_INDIRECT()`` are no longer"]
-    #[doc = " exclusive. A mbuf can be considered direct if it is neither indirect nor"]
-    #[doc = " having external buffer."]
+    #[doc = " @param\top\tsymmetric crypto operation"]
+    pub fn _rte_crypto_op_free(op: *mut rte_crypto_op);
+}
+extern "C" {
+    #[doc = " Get the asymmetric operation of a crypto operation."]
+    pub fn _rte_crypto_op_asym(op: *mut rte_crypto_op) -> *mut rte_crypto_asym_op;
+}
+extern "C" {
+    #[doc = " Attach a asymmetric session to a crypto operation"]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pointer to the mbuf."]
-    #[doc = " @param buf_addr"]
-    #[doc = "   The pointer to the external buffer."]
-    #[doc = " @param buf_iova"]
-    #[doc = "   IO address of the external buffer."]
-    #[doc = " @param buf_len"]
-    #[doc = "   The size of the external buffer."]
-    #[doc = " @param shinfo"]
-    #[doc = "   User-provided memory for shared data of the external buffer."]
-    pub fn _rte_pktmbuf_attach_extbuf(
-        m: *mut rte_mbuf,
-        buf_addr: *mut ::std::os::raw::c_void,
-        buf_iova: rte_iova_t,
-        buf_len: u16,
-        shinfo: *mut rte_mbuf_ext_shared_info,
-    );
+    #[doc = " @param\top\tcrypto operation, must be of type asymmetric"]
+    #[doc = " @param\tsess\tcryptodev session"]
+    pub fn _rte_crypto_op_attach_asym_session(
+        op: *mut rte_crypto_op,
+        sess: *mut rte_cryptodev_asym_session,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Attach packet mbuf to another packet mbuf."]
+    #[doc = " Enqueue a burst of operations for processing on a crypto device."]
     #[doc = ""]
-    #[doc = " If the mbuf we are attaching to isn't a direct buffer and is attached to"]
-    #[doc = " an external buffer, the mbuf being attached will be attached to the"]
-    #[doc = " external buffer instead of mbuf indirection."]
+    #[doc = " @param\tdev_id\t\tThe identifier of the device."]
+    #[doc = " @param\tqp_id\t\tThe index of the queue pair which packets are"]
+    #[doc = "\t\t\t\tto be enqueued for processing."]
+    #[doc = " @param\tops\t\tThe address of an array of *nb_ops* pointers"]
+    #[doc = "\t\t\t\tto *rte_crypto_op* structures which contain"]
+    #[doc = "\t\t\t\tthe crypto operations to be processed."]
+    #[doc = " @param\tnb_ops\t\tThe number of operations to process."]
     #[doc = ""]
-    #[doc = " Otherwise, the mbuf will be indirectly attached. After attachment we"]
-    #[doc = " refer the mbuf we attached as 'indirect', while mbuf we attached to as"]
-    #[doc = " 'direct'.  The direct mbuf's reference counter is incremented."]
+    #[doc = " @return"]
+    #[doc = " The number of operations actually enqueued on the crypto device."]
+    pub fn _rte_cryptodev_enqueue_burst(dev_id: u8, qp_id: u16, ops: *mut *mut rte_crypto_op, nb_ops: u16) -> u16;
+}
+extern "C" {
+    #[doc = " Dequeue a burst of processed crypto operations from a queue on the crypto"]
+    #[doc = " device."]
     #[doc = ""]
-    #[doc = " Right now, not supported:"]
-    #[doc = "  - attachment for already indirect mbuf (e.g. - mi has to be direct)."]
-    #[doc = "  - mbuf we trying to attach (mi) is used by someone else"]
-    #[doc = "    e.g. it's reference counter is greater then 1."]
+    #[doc = " @param\tdev_id\t\tThe symmetric crypto device identifier"]
+    #[doc = " @param\tqp_id\t\tThe index of the queue pair from which to"]
+    #[doc = "\t\t\t\tretrieve processed packets."]
+    #[doc = " @param\tops\t\tThe address of an array of pointers to"]
+    #[doc = "\t\t\t\t*rte_crypto_op* structures that must be"]
+    #[doc = "\t\t\t\tlarge enough to store *nb_ops* pointers in it."]
+    #[doc = " @param\tnb_ops\t\tThe maximum number of operations to dequeue."]
     #[doc = ""]
-    #[doc = " @param mi"]
-    #[doc = "   The indirect packet mbuf."]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf we're attaching to."]
-    pub fn _rte_pktmbuf_attach(mi: *mut rte_mbuf, m: *mut rte_mbuf);
+    #[doc = " @return"]
+    #[doc = "   - The number of operations actually dequeued."]
+    pub fn _rte_cryptodev_dequeue_burst(dev_id: u8, qp_id: u16, ops: *mut *mut rte_crypto_op, nb_ops: u16) -> u16;
 }
 extern "C" {
-    #[doc = " Detach a packet mbuf from external buffer or direct buffer."]
+    #[doc = " Set the VF MAC address."]
     #[doc = ""]
-    #[doc = "  - decrement refcnt and free the external/direct buffer if refcnt"]
-    #[doc = "    becomes zero."]
-    #[doc = "  - restore original mbuf address and length values."]
-    #[doc = "  - reset pktmbuf data and data_len to their default values."]
+    #[doc = " @param port"]
+    #[doc = "   port identifier of the device"]
+    #[doc = " @param vf"]
+    #[doc = "   VF id"]
+    #[doc = " @param mac_addr"]
+    #[doc = "   VF MAC address"]
     #[doc = ""]
-    #[doc = " All other fields of the given packet mbuf will be left intact."]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_mac_addr(port: u16, vf: u16, mac_addr: *mut ether_addr) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Enable/Disable VF MAC anti spoofing."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The indirect attached packet mbuf."]
-    pub fn _rte_pktmbuf_detach(m: *mut rte_mbuf);
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF on which to set MAC anti spoofing."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable VFs MAC anti spoofing."]
+    #[doc = "   0 - Disable VFs MAC anti spoofing."]
+    #[doc = ""]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_mac_anti_spoof(port: u16, vf: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Decrease reference counter and unlink a mbuf segment"]
+    #[doc = " Enable/Disable VF VLAN anti spoofing."]
     #[doc = ""]
-    #[doc = " This function does the same than a free, except that it does not"]
-    #[doc = " return the segment to its pool."]
-    #[doc = " It decreases the reference counter, and if it reaches 0, it is"]
-    #[doc = " detached from its parent for an indirect mbuf."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF on which to set VLAN anti spoofing."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable VFs VLAN anti spoofing."]
+    #[doc = "   0 - Disable VFs VLAN anti spoofing."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The mbuf to be unlinked"]
     #[doc = " @return"]
-    #[doc = "   - (m) if it is the last reference. It can be recycled or freed."]
-    #[doc = "   - (NULL) if the mbuf still has remaining references on it."]
-    pub fn _rte_pktmbuf_prefree_seg(m: *mut rte_mbuf) -> *mut rte_mbuf;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_vlan_anti_spoof(port: u16, vf: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Free a segment of a packet mbuf into its original mempool."]
+    #[doc = " Enable/Disable vf vlan insert"]
     #[doc = ""]
-    #[doc = " Free an mbuf, without parsing other segments in case of chained"]
-    #[doc = " buffers."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   ID specifying VF."]
+    #[doc = " @param vlan_id"]
+    #[doc = "   0 - Disable VF's vlan insert."]
+    #[doc = "   n - Enable; n is inserted as the vlan id."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf segment to be freed."]
-    pub fn _rte_pktmbuf_free_seg(m: *mut rte_mbuf);
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_vlan_insert(port: u16, vf: u16, vlan_id: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Free a packet mbuf back into its original mempool."]
+    #[doc = " Enable or disable a VF traffic receive of an Ethernet device."]
     #[doc = ""]
-    #[doc = " Free an mbuf, and all its segments in case of chained buffers. Each"]
-    #[doc = " segment is added back into its original mempool."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vlan"]
+    #[doc = "   The index of VLAN ID filter."]
+    #[doc = " @param vf_mask"]
+    #[doc = "   Bitmap listing which VF pools to filter on."]
+    #[doc = " @param vlan_on"]
+    #[doc = "   1 - Enable VF's VLAN filter."]
+    #[doc = "   0 - Disable VF's VLAN filter."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf to be freed. If NULL, the function does nothing."]
-    pub fn _rte_pktmbuf_free(m: *mut rte_mbuf);
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_vlan_filter(port: u16, vlan: u16, vf_mask: u64, vlan_on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Creates a \"clone\" of the given packet mbuf."]
+    #[doc = " Set RX L2 Filtering mode of a VF of an Ethernet device."]
     #[doc = ""]
-    #[doc = " Walks through all segments of the given packet mbuf, and for each of them:"]
-    #[doc = "  - Creates a new packet mbuf from the given pool."]
-    #[doc = "  - Attaches newly created mbuf to the segment."]
-    #[doc = " Then updates pkt_len and nb_segs of the \"clone\" packet mbuf to match values"]
-    #[doc = " from the original packet mbuf."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
+    #[doc = " @param rx_mask"]
+    #[doc = "   The RX mode mask, which is one or more of accepting Untagged Packets,"]
+    #[doc = "   packets that match the PFUTA table, Broadcast and Multicast Promiscuous."]
+    #[doc = "   ETH_VMDQ_ACCEPT_UNTAG,ETH_VMDQ_ACCEPT_HASH_UC,"]
+    #[doc = "   ETH_VMDQ_ACCEPT_BROADCAST and ETH_VMDQ_ACCEPT_MULTICAST will be used"]
+    #[doc = "   in rx_mode."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable a VF RX mode."]
+    #[doc = "   0 - Disable a VF RX mode."]
     #[doc = ""]
-    #[doc = " @param md"]
-    #[doc = "   The packet mbuf to be cloned."]
-    #[doc = " @param mp"]
-    #[doc = "   The mempool from which the \"clone\" mbufs are allocated."]
     #[doc = " @return"]
-    #[doc = "   - The pointer to the new \"clone\" mbuf on success."]
-    #[doc = "   - NULL if allocation fails."]
-    pub fn _rte_pktmbuf_clone(md: *mut rte_mbuf, mp: *mut rte_mempool) -> *mut rte_mbuf;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_rxmode(port: u16, vf: u16, rx_mask: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Adds given value to the refcnt of all packet mbuf segments."]
+    #[doc = " Enable or disable a VF traffic receive of an Ethernet device."]
     #[doc = ""]
-    #[doc = " Walks through all segments of given packet mbuf and for each of them"]
-    #[doc = " invokes rte_mbuf_refcnt_update()."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable a VF traffic receive."]
+    #[doc = "   0 - Disable a VF traffic receive."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf whose refcnt to be updated."]
-    #[doc = " @param v"]
-    #[doc = "   The value to add to the mbuf's segments refcnt."]
-    pub fn _rte_pktmbuf_refcnt_update(m: *mut rte_mbuf, v: i16);
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_rx(port: u16, vf: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Get the headroom in a packet mbuf."]
+    #[doc = " Enable or disable a VF traffic transmit of the Ethernet device."]
+    #[doc = ""]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable a VF traffic transmit."]
+    #[doc = "   0 - Disable a VF traffic transmit."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   The length of the headroom."]
-    pub fn _rte_pktmbuf_headroom(m: *const rte_mbuf) -> u16;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_tx(port: u16, vf: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Get the tailroom of a packet mbuf."]
+    #[doc = " Set the rate limitation for a vf on an Ethernet device."]
+    #[doc = ""]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
+    #[doc = " @param tx_rate"]
+    #[doc = "   The tx rate allocated from the total link speed for this VF id."]
+    #[doc = " @param q_msk"]
+    #[doc = "   The queue mask which need to set the rate."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   The length of the tailroom."]
-    pub fn _rte_pktmbuf_tailroom(m: *const rte_mbuf) -> u16;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_vf_rate_limit(port: u16, vf: u16, tx_rate: u16, q_msk: u64) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Get the last segment of the packet."]
+    #[doc = " Notify VF when PF link status changes."]
+    #[doc = ""]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   The last segment of the given mbuf."]
-    pub fn _rte_pktmbuf_lastseg(m: *mut rte_mbuf) -> *mut rte_mbuf;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_ping_vf(port: u16, vf: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Prepend len bytes to an mbuf data area."]
+    #[doc = " Enable/Disable TX loopback"]
     #[doc = ""]
-    #[doc = " Returns a pointer to the new"]
-    #[doc = " data start address. If there is not enough headroom in the first"]
-    #[doc = " segment, the function will return NULL, without modifying the mbuf."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable TX loopback."]
+    #[doc = "   0 - Disable TX loopback."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pkt mbuf."]
-    #[doc = " @param len"]
-    #[doc = "   The amount of data to prepend (in bytes)."]
     #[doc = " @return"]
-    #[doc = "   A pointer to the start of the newly prepended data, or"]
-    #[doc = "   NULL if there is not enough headroom space in the first segment"]
-    pub fn _rte_pktmbuf_prepend(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    pub fn rte_pmd_ixgbe_set_tx_loopback(port: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Append len bytes to an mbuf."]
+    #[doc = " Set the VF MAC address."]
     #[doc = ""]
-    #[doc = " Append len bytes to an mbuf and return a pointer to the start address"]
-    #[doc = " of the added data. If there is not enough tailroom in the last"]
-    #[doc = " segment, the function will return NULL, without modifying the mbuf."]
+    #[doc = "  PF should set MAC address before VF initialized, if PF sets the MAC"]
+    #[doc = "  address after VF initialized, new MAC address won't be effective until"]
+    #[doc = "  VF reinitialize."]
+    #[doc = ""]
+    #[doc = "  This will remove all existing MAC filters."]
+    #[doc = ""]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF id."]
+    #[doc = " @param mac_addr"]
+    #[doc = "   VF MAC address."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
-    #[doc = " @param len"]
-    #[doc = "   The amount of data to append (in bytes)."]
     #[doc = " @return"]
-    #[doc = "   A pointer to the start of the newly appended data, or"]
-    #[doc = "   NULL if there is not enough tailroom space in the last segment"]
-    pub fn _rte_pktmbuf_append(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_mac_addr(port: u16, vf_id: u16, mac_addr: *mut ether_addr) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Remove len bytes at the beginning of an mbuf."]
+    #[doc = " Enable/Disable VF MAC anti spoofing."]
     #[doc = ""]
-    #[doc = " Returns a pointer to the start address of the new data area. If the"]
-    #[doc = " length is greater than the length of the first segment, then the"]
-    #[doc = " function will fail and return NULL, without modifying the mbuf."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to set MAC anti spoofing."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable VFs MAC anti spoofing."]
+    #[doc = "   0 - Disable VFs MAC anti spoofing."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
-    #[doc = " @param len"]
-    #[doc = "   The amount of data to remove (in bytes)."]
     #[doc = " @return"]
-    #[doc = "   A pointer to the new start of the data."]
-    pub fn _rte_pktmbuf_adj(m: *mut rte_mbuf, len: u16) -> *mut ::std::os::raw::c_char;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_mac_anti_spoof(port: u16, vf_id: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Remove len bytes of data at the end of the mbuf."]
+    #[doc = " Enable/Disable VF VLAN anti spoofing."]
     #[doc = ""]
-    #[doc = " If the length is greater than the length of the last segment, the"]
-    #[doc = " function will fail and return -1 without modifying the mbuf."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to set VLAN anti spoofing."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable VFs VLAN anti spoofing."]
+    #[doc = "   0 - Disable VFs VLAN anti spoofing."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
-    #[doc = " @param len"]
-    #[doc = "   The amount of data to remove (in bytes)."]
     #[doc = " @return"]
-    #[doc = "   - 0: On success."]
-    #[doc = "   - -1: On error."]
-    pub fn _rte_pktmbuf_trim(m: *mut rte_mbuf, len: u16) -> ::std::os::raw::c_int;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_vlan_anti_spoof(port: u16, vf_id: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Test if mbuf data is contiguous."]
+    #[doc = " Enable/Disable vf unicast promiscuous mode."]
+    #[doc = ""]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to set."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable."]
+    #[doc = "   0 - Disable."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
     #[doc = " @return"]
-    #[doc = "   - 1, if all data is contiguous (one segment)."]
-    #[doc = "   - 0, if there is several segments."]
-    pub fn _rte_pktmbuf_is_contiguous(m: *const rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_unicast_promisc(port: u16, vf_id: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Read len data bytes in a mbuf at specified offset."]
+    #[doc = " Enable/Disable vf multicast promiscuous mode."]
     #[doc = ""]
-    #[doc = " If the data is contiguous, return the pointer in the mbuf data, else"]
-    #[doc = " copy the data in the buffer provided by the user and return its"]
-    #[doc = " pointer."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to set."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable."]
+    #[doc = "   0 - Disable."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The pointer to the mbuf."]
-    #[doc = " @param off"]
-    #[doc = "   The offset of the data in the mbuf."]
-    #[doc = " @param len"]
-    #[doc = "   The amount of bytes to read."]
-    #[doc = " @param buf"]
-    #[doc = "   The buffer where data is copied if it is not contiguous in mbuf"]
-    #[doc = "   data. Its length should be at least equal to the len parameter."]
     #[doc = " @return"]
-    #[doc = "   The pointer to the data, either in the mbuf if it is contiguous,"]
-    #[doc = "   or in the user buffer. If mbuf is too small, NULL is returned."]
-    pub fn _rte_pktmbuf_read(
-        m: *const rte_mbuf,
-        off: u32,
-        len: u32,
-        buf: *mut ::std::os::raw::c_void,
-    ) -> *const ::std::os::raw::c_void;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_multicast_promisc(port: u16, vf_id: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Chain an mbuf to another, thereby creating a segmented packet."]
-    #[doc = ""]
-    #[doc = " Note: The implementation will do a linear walk over the segments to find"]
-    #[doc = " the tail entry. For cases when there are many segments, it's better to"]
-    #[doc = " chain the entries manually."]
+    #[doc = " Enable/Disable vf broadcast mode"]
     #[doc = ""]
-    #[doc = " @param head"]
-    #[doc = "   The head of the mbuf chain (the first packet)"]
-    #[doc = " @param tail"]
-    #[doc = "   The mbuf to put last in the chain"]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   ID specifying VF."]
+    #[doc = " @param on"]
+    #[doc = "   0 - Disable broadcast."]
+    #[doc = "   1 - Enable broadcast."]
     #[doc = ""]
     #[doc = " @return"]
-    #[doc = "   - 0, on success."]
-    #[doc = "   - -EOVERFLOW, if the chain segment limit exceeded"]
-    pub fn _rte_pktmbuf_chain(head: *mut rte_mbuf, tail: *mut rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_broadcast(port: u16, vf_id: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Validate general requirements for Tx offload in mbuf."]
+    #[doc = " Enable/Disable vf vlan insert"]
     #[doc = ""]
-    #[doc = " This function checks correctness and completeness of Tx offload settings."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   ID specifying VF."]
+    #[doc = " @param vlan_id"]
+    #[doc = "   0 - Disable VF's vlan insert."]
+    #[doc = "   n - Enable; n is inserted as the vlan id."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf to be validated."]
     #[doc = " @return"]
-    #[doc = "   0 if packet is valid"]
-    pub fn _rte_validate_tx_offload(m: *const rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_vlan_insert(port: u16, vf_id: u16, vlan_id: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Linearize data in mbuf."]
+    #[doc = " Enable/Disable VF VLAN filter"]
     #[doc = ""]
-    #[doc = " This function moves the mbuf data in the first segment if there is enough"]
-    #[doc = " tailroom. The subsequent segments are unchained and freed."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vlan_id"]
+    #[doc = "   ID specifying VLAN"]
+    #[doc = " @param vf_mask"]
+    #[doc = "   Mask to filter VF's"]
+    #[doc = " @param on"]
+    #[doc = "   0 - Disable VF's VLAN filter."]
+    #[doc = "   1 - Enable VF's VLAN filter."]
     #[doc = ""]
-    #[doc = " @param mbuf"]
-    #[doc = "   mbuf to linearize"]
     #[doc = " @return"]
-    #[doc = "   - 0, on success"]
-    #[doc = "   - -1, on error"]
-    pub fn _rte_pktmbuf_linearize(mbuf: *mut rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_vlan_filter(port: u16, vlan_id: u16, vf_mask: u64, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Retrieve a burst of input packets from a receive queue of an Ethernet"]
-    #[doc = " device. The retrieved packets are stored in *rte_mbuf* structures whose"]
-    #[doc = " pointers are supplied in the *rx_pkts* array."]
-    #[doc = ""]
-    #[doc = " The rte_eth_rx_burst() function loops, parsing the RX ring of the"]
-    #[doc = " receive queue, up to *nb_pkts* packets, and for each completed RX"]
-    #[doc = " descriptor in the ring, it performs the following operations:"]
-    #[doc = ""]
-    #[doc = " - Initialize the *rte_mbuf* data structure associated with the"]
-    #[doc = "   RX descriptor according to the information provided by the NIC into"]
-    #[doc = "   that RX descriptor."]
-    #[doc = ""]
-    #[doc = " - Store the *rte_mbuf* data structure into the next entry of the"]
-    #[doc = "   *rx_pkts* array."]
+    #[doc = " Set VF's max bandwidth."]
     #[doc = ""]
-    #[doc = " - Replenish the RX descriptor with a new *rte_mbuf* buffer"]
-    #[doc = "   allocated from the memory pool associated with the receive queue at"]
-    #[doc = "   initialization time."]
+    #[doc = "  Per VF bandwidth limitation and per TC bandwidth limitation cannot"]
+    #[doc = "  be enabled in parallel. If per TC bandwidth is enabled, this function"]
+    #[doc = "  will disable it."]
     #[doc = ""]
-    #[doc = " When retrieving an input packet that was scattered by the controller"]
-    #[doc = " into multiple receive descriptors, the rte_eth_rx_burst() function"]
-    #[doc = " appends the associated *rte_mbuf* buffers to the first buffer of the"]
-    #[doc = " packet."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   ID specifying VF."]
+    #[doc = " @param bw"]
+    #[doc = "   Bandwidth for this VF."]
+    #[doc = "   The value should be an absolute bandwidth in Mbps."]
+    #[doc = "   The bandwidth is a L2 bandwidth counting the bytes of ethernet packets."]
+    #[doc = "   Not count the bytes added by physical layer."]
     #[doc = ""]
-    #[doc = " The rte_eth_rx_burst() function returns the number of packets"]
-    #[doc = " actually retrieved, which is the number of *rte_mbuf* data structures"]
-    #[doc = " effectively supplied into the *rx_pkts* array."]
-    #[doc = " A return value equal to *nb_pkts* indicates that the RX queue contained"]
-    #[doc = " at least *rx_pkts* packets, and this is likely to signify that other"]
-    #[doc = " received packets remain in the input queue. Applications implementing"]
-    #[doc = " a \"retrieve as much received packets as possible\" policy can check this"]
-    #[doc = " specific case and keep invoking the rte_eth_rx_burst() function until"]
-    #[doc = " a value less than *nb_pkts* is returned."]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_vf_max_bw(port: u16, vf_id: u16, bw: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Get VF's statistics"]
     #[doc = ""]
-    #[doc = " This receive method has the following advantages:"]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to get."]
+    #[doc = " @param stats"]
+    #[doc = "   A pointer to a structure of type *rte_eth_stats* to be filled with"]
+    #[doc = "   the values of device counters for the following set of statistics:"]
+    #[doc = "   - *ipackets* with the total of successfully received packets."]
+    #[doc = "   - *opackets* with the total of successfully transmitted packets."]
+    #[doc = "   - *ibytes*   with the total of successfully received bytes."]
+    #[doc = "   - *obytes*   with the total of successfully transmitted bytes."]
+    #[doc = "   - *ierrors*  with the total of erroneous received packets."]
+    #[doc = "   - *oerrors*  with the total of failed transmitted packets."]
     #[doc = ""]
-    #[doc = " - It allows a run-to-completion network stack engine to retrieve and"]
-    #[doc = "   to immediately process received packets in a fast burst-oriented"]
-    #[doc = "   approach, avoiding the overhead of unnecessary intermediate packet"]
-    #[doc = "   queue/dequeue operations."]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_get_vf_stats(port: u16, vf_id: u16, stats: *mut rte_eth_stats) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Clear VF's statistics"]
     #[doc = ""]
-    #[doc = " - Conversely, it also allows an asynchronous-oriented processing"]
-    #[doc = "   method to retrieve bursts of received packets and to immediately"]
-    #[doc = "   queue them for further parallel processing by another logical core,"]
-    #[doc = "   for instance. However, instead of having received packets being"]
-    #[doc = "   individually queued by the driver, this approach allows the caller"]
-    #[doc = "   of the rte_eth_rx_burst() function to queue a burst of retrieved"]
-    #[doc = "   packets at a time and therefore dramatically reduce the cost of"]
-    #[doc = "   enqueue/dequeue operations per packet."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf_id"]
+    #[doc = "   VF on which to get."]
     #[doc = ""]
-    #[doc = " - It allows the rte_eth_rx_burst() function of the driver to take"]
-    #[doc = "   advantage of burst-oriented hardware features (CPU cache,"]
-    #[doc = "   prefetch instructions, and so on) to minimize the number of CPU"]
-    #[doc = "   cycles per packet."]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_reset_vf_stats(port: u16, vf_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Notify VF when PF link status changes."]
     #[doc = ""]
-    #[doc = " To summarize, the proposed receive API enables many"]
-    #[doc = " burst-oriented optimizations in both synchronous and asynchronous"]
-    #[doc = " packet processing environments with no overhead in both cases."]
+    #[doc = " @param port"]
+    #[doc = "   The port identifier of the Ethernet device."]
+    #[doc = " @param vf"]
+    #[doc = "   VF id."]
     #[doc = ""]
-    #[doc = " The rte_eth_rx_burst() function does not provide any error"]
-    #[doc = " notification to avoid the corresponding overhead. As a hint, the"]
-    #[doc = " upper-level application might check the status of the device link once"]
-    #[doc = " being systematically returned a 0 value for a given number of tries."]
+    #[doc = " @return"]
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_ping_vfs(port: u16, vf: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Enable/Disable TX loopback"]
     #[doc = ""]
-    #[doc = " @param port_id"]
+    #[doc = " @param port"]
     #[doc = "   The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "   The index of the receive queue from which to retrieve input packets."]
-    #[doc = "   The value must be in the range [0, nb_rx_queue - 1] previously supplied"]
-    #[doc = "   to rte_eth_dev_configure()."]
-    #[doc = " @param rx_pkts"]
-    #[doc = "   The address of an array of pointers to *rte_mbuf* structures that"]
-    #[doc = "   must be large enough to store *nb_pkts* pointers in it."]
-    #[doc = " @param nb_pkts"]
-    #[doc = "   The maximum number of packets to retrieve."]
+    #[doc = " @param on"]
+    #[doc = "   1 - Enable TX loopback."]
+    #[doc = "   0 - Disable TX loopback."]
+    #[doc = ""]
     #[doc = " @return"]
-    #[doc = "   The number of packets actually retrieved, which is the number"]
-    #[doc = "   of pointers to *rte_mbuf* structures effectively supplied to the"]
-    #[doc = "   *rx_pkts* array."]
-    pub fn _rte_eth_rx_burst(port_id: u16, queue_id: u16, rx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
+    #[doc = "   - (0) if successful."]
+    #[doc = "   - (-ENODEV) if *port* invalid."]
+    #[doc = "   - (-EINVAL) if bad parameter."]
+    #[doc = "   - (-ENOTSUP) not supported by firmware."]
+    pub fn rte_pmd_i40e_set_tx_loopback(port: u16, on: u8) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Get the number of used descriptors of a rx queue"]
-    #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "  The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "  The queue id on the specific port."]
+    #[doc = " Function returning version string"]
     #[doc = " @return"]
-    #[doc = "  The number of used descriptors in the specific queue, or:"]
-    #[doc = "     (-EINVAL) if *port_id* or *queue_id* is invalid"]
-    #[doc = "     (-ENOTSUP) if the device does not support this function"]
-    pub fn _rte_eth_rx_queue_count(port_id: u16, queue_id: u16) -> ::std::os::raw::c_int;
+    #[doc = "     string"]
+    pub fn _rte_version() -> *const ::std::os::raw::c_char;
+}
+#[doc = " The outer headers of an offloaded tunnel, which mirrors `struct rte_flow_tunnel`."]
+#[doc = ""]
+#[doc = " The IPv4 addresses are stored in the leading 4 bytes of the address fields."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct _rte_flow_tunnel {
+    #[doc = "< The tunnel item type, e.g. RTE_FLOW_ITEM_TYPE_VXLAN."]
+    pub type_: rte_flow_item_type::Type,
+    #[doc = "< The tunnel identification."]
+    pub tun_id: u64,
+    #[doc = "< The outer source address."]
+    pub src_addr: [u8; 16usize],
+    #[doc = "< The outer destination address."]
+    pub dst_addr: [u8; 16usize],
+    #[doc = "< The outer source L4 port."]
+    pub tp_src: rte_be16_t,
+    #[doc = "< The outer destination L4 port."]
+    pub tp_dst: rte_be16_t,
+    #[doc = "< The tunnel flags."]
+    pub tun_flags: u16,
+    #[doc = "< The outer addresses are IPv6."]
+    pub is_ipv6: u8,
+    #[doc = "< The outer TOS or traffic class."]
+    pub tos: u8,
+    #[doc = "< The outer TTL or hop limit."]
+    pub ttl: u8,
+    #[doc = "< The outer IPv6 flow label."]
+    pub label: u32,
+}
+#[doc = " The restore info of a partially offloaded packet, which mirrors `struct rte_flow_restore_info`."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct _rte_flow_restore_info {
+    #[doc = "< The RTE_FLOW_RESTORE_INFO_* flags."]
+    pub flags: u64,
+    #[doc = "< The group of the missed flow rule."]
+    pub group_id: u32,
+    #[doc = "< The tunnel of the packet."]
+    pub tunnel: _rte_flow_tunnel,
 }
 extern "C" {
-    #[doc = " Check if the DD bit of the specific RX descriptor in the queue has been set"]
+    #[doc = " Allocate the PMD actions which decapsulate an offloaded tunnel,"]
+    #[doc = " they must be placed at the start of the actions of the decap rule."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "  The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "  The queue id on the specific port."]
-    #[doc = " @param offset"]
-    #[doc = "  The offset of the descriptor ID from tail."]
     #[doc = " @return"]
-    #[doc = "  - (1) if the specific DD bit is set."]
-    #[doc = "  - (0) if the specific DD bit is not set."]
-    #[doc = "  - (-ENODEV) if *port_id* invalid."]
-    #[doc = "  - (-ENOTSUP) if the device does not support this function"]
-    pub fn _rte_eth_rx_descriptor_done(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API."]
+    pub fn _rte_flow_tunnel_decap_set(
+        port_id: u16,
+        tunnel: *mut _rte_flow_tunnel,
+        actions: *mut *mut rte_flow_action,
+        num_of_actions: *mut u32,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Check the status of a Rx descriptor in the queue"]
+    #[doc = " Allocate the PMD items which match the packets of an offloaded tunnel,"]
+    #[doc = " they must be placed at the start of the pattern of the tunnel rule."]
     #[doc = ""]
-    #[doc = " It should be called in a similar context than the Rx function:"]
-    #[doc = " - on a dataplane core"]
-    #[doc = " - not concurrently on the same queue"]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API."]
+    pub fn _rte_flow_tunnel_match(
+        port_id: u16,
+        tunnel: *mut _rte_flow_tunnel,
+        items: *mut *mut rte_flow_item,
+        num_of_items: *mut u32,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Retrieve the restore info of a packet, which missed the offloaded rules after a partial processing."]
     #[doc = ""]
-    #[doc = " Since it's a dataplane function, no check is performed on port_id and"]
-    #[doc = " queue_id. The caller must therefore ensure that the port is enabled"]
-    #[doc = " and the queue is configured and running."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API."]
+    pub fn _rte_flow_get_restore_info(
+        port_id: u16,
+        m: *mut rte_mbuf,
+        info: *mut _rte_flow_restore_info,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Release the PMD actions allocated by _rte_flow_tunnel_decap_set()."]
+    pub fn _rte_flow_tunnel_action_decap_release(
+        port_id: u16,
+        actions: *mut rte_flow_action,
+        num_of_actions: u32,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Release the PMD items allocated by _rte_flow_tunnel_match()."]
+    pub fn _rte_flow_tunnel_item_release(
+        port_id: u16,
+        items: *mut rte_flow_item,
+        num_of_items: u32,
+        error: *mut rte_flow_error,
+    ) -> ::std::os::raw::c_int;
+}
+pub const RTE_PDUMP_ALL_QUEUES: u32 = 65535;
+pub const RTE_PDUMP_FLAG_RX: _bindgen_ty_pdump = 1;
+pub const RTE_PDUMP_FLAG_TX: _bindgen_ty_pdump = 2;
+pub const RTE_PDUMP_FLAG_RXTX: _bindgen_ty_pdump = 3;
+pub type _bindgen_ty_pdump = u32;
+pub mod rte_pdump_socktype {
+    pub type Type = u32;
+    pub const RTE_PDUMP_SOCKET_SERVER: Type = 1;
+    pub const RTE_PDUMP_SOCKET_CLIENT: Type = 2;
+}
+extern "C" {
+    #[doc = " Initialize packet capturing handling"]
     #[doc = ""]
-    #[doc = " Note: accessing to a random descriptor in the ring may trigger cache"]
-    #[doc = " misses and have a performance impact."]
+    #[doc = " Register the IPC action for communication with target (primary) process."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "  A valid port identifier of the Ethernet device which."]
-    #[doc = " @param queue_id"]
-    #[doc = "  A valid Rx queue identifier on this port."]
-    #[doc = " @param offset"]
-    #[doc = "  The offset of the descriptor starting from tail (0 is the next"]
-    #[doc = "  packet to be received by the driver)."]
+    #[doc = " @param path"]
+    #[doc = " This parameter is going to be deprecated; it was used for specifying the"]
+    #[doc = " directory path for server socket."]
     #[doc = ""]
     #[doc = " @return"]
-    #[doc = "  - (RTE_ETH_RX_DESC_AVAIL): Descriptor is available for the hardware to"]
-    #[doc = "    receive a packet."]
-    #[doc = "  - (RTE_ETH_RX_DESC_DONE): Descriptor is done, it is filled by hw, but"]
-    #[doc = "    not yet processed by the driver (i.e. in the receive queue)."]
-    #[doc = "  - (RTE_ETH_RX_DESC_UNAVAIL): Descriptor is unavailable, either hold by"]
-    #[doc = "    the driver and not yet returned to hw, or reserved by the hw."]
-    #[doc = "  - (-EINVAL) bad descriptor offset."]
-    #[doc = "  - (-ENOTSUP) if the device does not support this function."]
-    #[doc = "  - (-ENODEV) bad port or queue (only if compiled with debug)."]
-    pub fn _rte_eth_rx_descriptor_status(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
+    #[doc = "    0 on success, -1 on error"]
+    pub fn rte_pdump_init(path: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Check the status of a Tx descriptor in the queue."]
-    #[doc = ""]
-    #[doc = " It should be called in a similar context than the Tx function:"]
-    #[doc = " - on a dataplane core"]
-    #[doc = " - not concurrently on the same queue"]
+    #[doc = " Un initialize packet capturing handling"]
     #[doc = ""]
-    #[doc = " Since it's a dataplane function, no check is performed on port_id and"]
-    #[doc = " queue_id. The caller must therefore ensure that the port is enabled"]
-    #[doc = " and the queue is configured and running."]
+    #[doc = " Unregister the IPC action for communication with target (primary) process."]
     #[doc = ""]
-    #[doc = " Note: accessing to a random descriptor in the ring may trigger cache"]
-    #[doc = " misses and have a performance impact."]
+    #[doc = " @return"]
+    #[doc = "    0 on success, -1 on error"]
+    pub fn rte_pdump_uninit() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Enables packet capturing on given port and queue."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "  A valid port identifier of the Ethernet device which."]
-    #[doc = " @param queue_id"]
-    #[doc = "  A valid Tx queue identifier on this port."]
-    #[doc = " @param offset"]
-    #[doc = "  The offset of the descriptor starting from tail (0 is the place where"]
-    #[doc = "  the next packet will be send)."]
+    #[doc = " @param port"]
+    #[doc = "  port on which packet capturing should be enabled."]
+    #[doc = " @param queue"]
+    #[doc = "  queue of a given port on which packet capturing should be enabled."]
+    #[doc = "  users should pass on value UINT16_MAX to enable packet capturing on all"]
+    #[doc = "  queues of a given port."]
+    #[doc = " @param flags"]
+    #[doc = "  flags specifies RTE_PDUMP_FLAG_RX/RTE_PDUMP_FLAG_TX/RTE_PDUMP_FLAG_RXTX"]
+    #[doc = "  on which packet capturing should be enabled for a given port and queue."]
+    #[doc = " @param ring"]
+    #[doc = "  ring on which captured packets will be enqueued for user."]
+    #[doc = " @param mp"]
+    #[doc = "  mempool on to which original packets will be mirrored or duplicated."]
+    #[doc = " @param filter"]
+    #[doc = "  place holder for packet filtering."]
     #[doc = ""]
     #[doc = " @return"]
-    #[doc = "  - (RTE_ETH_TX_DESC_FULL) Descriptor is being processed by the hw, i.e."]
-    #[doc = "    in the transmit queue."]
-    #[doc = "  - (RTE_ETH_TX_DESC_DONE) Hardware is done with this descriptor, it can"]
-    #[doc = "    be reused by the driver."]
-    #[doc = "  - (RTE_ETH_TX_DESC_UNAVAIL): Descriptor is unavailable, reserved by the"]
-    #[doc = "    driver or the hardware."]
-    #[doc = "  - (-EINVAL) bad descriptor offset."]
-    #[doc = "  - (-ENOTSUP) if the device does not support this function."]
-    #[doc = "  - (-ENODEV) bad port or queue (only if compiled with debug)."]
-    pub fn _rte_eth_tx_descriptor_status(port_id: u16, queue_id: u16, offset: u16) -> ::std::os::raw::c_int;
+    #[doc = "    0 on success, -1 on error, rte_errno is set accordingly."]
+    pub fn rte_pdump_enable(
+        port: u16,
+        queue: u16,
+        flags: u32,
+        ring: *mut rte_ring,
+        mp: *mut rte_mempool,
+        filter: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Send a burst of output packets on a transmit queue of an Ethernet device."]
-    #[doc = ""]
-    #[doc = " The rte_eth_tx_burst() function is invoked to transmit output packets"]
-    #[doc = " on the output queue *queue_id* of the Ethernet device designated by its"]
-    #[doc = " *port_id*."]
-    #[doc = " The *nb_pkts* parameter is the number of packets to send which are"]
-    #[doc = " supplied in the *tx_pkts* array of *rte_mbuf* structures, each of them"]
-    #[doc = " allocated from a pool created with rte_pktmbuf_pool_create()."]
-    #[doc = " The rte_eth_tx_burst() function loops, sending *nb_pkts* packets,"]
-    #[doc = " up to the number of transmit descriptors available in the TX ring of the"]
-    #[doc = " transmit queue."]
-    #[doc = " For each packet to send, the rte_eth_tx_burst() function performs"]
-    #[doc = " the following operations:"]
+    #[doc = " Enables packet capturing on given device id and queue."]
+    #[doc = " device_id can be name or pci address of device."]
     #[doc = ""]
-    #[doc = " - Pick up the next available descriptor in the transmit ring."]
+    #[doc = " @return"]
+    #[doc = "    0 on success, -1 on error, rte_errno is set accordingly."]
+    pub fn rte_pdump_enable_by_deviceid(
+        device_id: *mut ::std::os::raw::c_char,
+        queue: u16,
+        flags: u32,
+        ring: *mut rte_ring,
+        mp: *mut rte_mempool,
+        filter: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Disables packet capturing on given port and queue."]
     #[doc = ""]
-    #[doc = " - Free the network buffer previously sent with that descriptor, if any."]
+    #[doc = " @return"]
+    #[doc = "    0 on success, -1 on error, rte_errno is set accordingly."]
+    pub fn rte_pdump_disable(port: u16, queue: u16, flags: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Disables packet capturing on given device_id and queue."]
+    #[doc = " device_id can be name or pci address of device."]
     #[doc = ""]
-    #[doc = " - Initialize the transmit descriptor with the information provided"]
-    #[doc = "   in the *rte_mbuf data structure."]
+    #[doc = " @return"]
+    #[doc = "    0 on success, -1 on error, rte_errno is set accordingly."]
+    pub fn rte_pdump_disable_by_deviceid(
+        device_id: *mut ::std::os::raw::c_char,
+        queue: u16,
+        flags: u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Allows applications to set server and client socket paths."]
+    #[doc = " If specified path is null default path will be selected, i.e."]
+    #[doc = " \"/var/run/\" for root user and \"$HOME\" for non root user."]
     #[doc = ""]
-    #[doc = " In the case of a segmented packet composed of a list of *rte_mbuf* buffers,"]
-    #[doc = " the rte_eth_tx_burst() function uses several transmit descriptors"]
-    #[doc = " of the ring."]
+    #[doc = " @return"]
+    #[doc = "    0 on success, -EINVAL on error"]
+    pub fn rte_pdump_set_socket_dir(
+        path: *const ::std::os::raw::c_char,
+        type_: rte_pdump_socktype::Type,
+    ) -> ::std::os::raw::c_int;
+}
+pub const _RTE_ETH_MAX_HAIRPIN_PEERS: u32 = 32;
+#[doc = " The hairpin capability of a port, which mirrors `struct rte_eth_hairpin_cap`."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct _rte_eth_hairpin_cap {
+    #[doc = "< The max number of hairpin queues."]
+    pub max_nb_queues: u16,
+    #[doc = "< The max number of TX queues bound to a RX queue."]
+    pub max_rx_2_tx: u16,
+    #[doc = "< The max number of RX queues bound to a TX queue."]
+    pub max_tx_2_rx: u16,
+    #[doc = "< The max number of descriptors of a hairpin queue."]
+    pub max_nb_desc: u16,
+}
+#[doc = " The peer queue of a hairpin queue, which mirrors `struct rte_eth_hairpin_peer`."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct _rte_eth_hairpin_peer {
+    #[doc = "< The peer port."]
+    pub port: u16,
+    #[doc = "< The peer queue."]
+    pub queue: u16,
+}
+#[doc = " The configuration of a hairpin queue, which mirrors `struct rte_eth_hairpin_conf`."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct _rte_eth_hairpin_conf {
+    #[doc = "< The number of the peers."]
+    pub peer_count: u16,
+    #[doc = "< The TX flow rules are inserted explicitly by the application."]
+    pub tx_explicit: u8,
+    #[doc = "< The queues are bound manually with _rte_eth_hairpin_bind()."]
+    pub manual_bind: u8,
+    #[doc = "< The peer queues."]
+    pub peers: [_rte_eth_hairpin_peer; 32usize],
+}
+extern "C" {
+    #[doc = " Retrieve the hairpin capability of a port."]
     #[doc = ""]
-    #[doc = " The rte_eth_tx_burst() function returns the number of packets it"]
-    #[doc = " actually sent. A return value equal to *nb_pkts* means that all packets"]
-    #[doc = " have been sent, and this is likely to signify that other output packets"]
-    #[doc = " could be immediately transmitted again. Applications that implement a"]
-    #[doc = " \"send as many packets to transmit as possible\" policy can check this"]
-    #[doc = " specific case and keep invoking the rte_eth_tx_burst() function until"]
-    #[doc = " a value less than *nb_pkts* is returned."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue."]
+    pub fn _rte_eth_dev_hairpin_capability_get(port_id: u16, cap: *mut _rte_eth_hairpin_cap) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Allocate and set up a hairpin receive queue, which forwards the packets to its peer TX queues."]
     #[doc = ""]
-    #[doc = " It is the responsibility of the rte_eth_tx_burst() function to"]
-    #[doc = " transparently free the memory buffers of packets previously sent."]
-    #[doc = " This feature is driven by the *tx_free_thresh* value supplied to the"]
-    #[doc = " rte_eth_dev_configure() function at device configuration time."]
-    #[doc = " When the number of free TX descriptors drops below this threshold, the"]
-    #[doc = " rte_eth_tx_burst() function must [attempt to] free the *rte_mbuf*  buffers"]
-    #[doc = " of those packets whose transmission was effectively completed."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue."]
+    pub fn _rte_eth_rx_hairpin_queue_setup(
+        port_id: u16,
+        rx_queue_id: u16,
+        nb_rx_desc: u16,
+        conf: *const _rte_eth_hairpin_conf,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Allocate and set up a hairpin transmit queue, which sends the packets of its peer RX queues."]
     #[doc = ""]
-    #[doc = " If the PMD is DEV_TX_OFFLOAD_MT_LOCKFREE capable, multiple threads can"]
-    #[doc = " invoke this function concurrently on the same tx queue without SW lock."]
-    #[doc = " @see rte_eth_dev_info_get, struct rte_eth_txconf::offloads"]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue."]
+    pub fn _rte_eth_tx_hairpin_queue_setup(
+        port_id: u16,
+        tx_queue_id: u16,
+        nb_tx_desc: u16,
+        conf: *const _rte_eth_hairpin_conf,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Bind the hairpin TX queues of a port to the RX queues of its peer port, RTE_MAX_ETHPORTS for all the peers."]
     #[doc = ""]
-    #[doc = " @see rte_eth_tx_prepare to perform some prior checks or adjustments"]
-    #[doc = " for offloads."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no hairpin binding."]
+    pub fn _rte_eth_hairpin_bind(tx_port: u16, rx_port: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Unbind the hairpin TX queues of a port from the RX queues of its peer port, RTE_MAX_ETHPORTS for all the peers."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "   The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "   The index of the transmit queue through which output packets must be"]
-    #[doc = "   sent."]
-    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
-    #[doc = "   to rte_eth_dev_configure()."]
-    #[doc = " @param tx_pkts"]
-    #[doc = "   The address of an array of *nb_pkts* pointers to *rte_mbuf* structures"]
-    #[doc = "   which contain the output packets."]
-    #[doc = " @param nb_pkts"]
-    #[doc = "   The maximum number of packets to transmit."]
     #[doc = " @return"]
-    #[doc = "   The number of output packets actually stored in transmit descriptors of"]
-    #[doc = "   the transmit ring. The return value can be less than the value of the"]
-    #[doc = "   *tx_pkts* parameter when the transmit ring is full or has been filled up."]
-    pub fn _rte_eth_tx_burst(port_id: u16, queue_id: u16, tx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no hairpin binding."]
+    pub fn _rte_eth_hairpin_unbind(tx_port: u16, rx_port: u16) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Process a burst of output packets on a transmit queue of an Ethernet device."]
+    #[doc = " Retrieve the peer ports of the hairpin queues of a port, the TX peers if direction is 1, or the RX peers."]
     #[doc = ""]
-    #[doc = " The rte_eth_tx_prepare() function is invoked to prepare output packets to be"]
-    #[doc = " transmitted on the output queue *queue_id* of the Ethernet device designated"]
-    #[doc = " by its *port_id*."]
-    #[doc = " The *nb_pkts* parameter is the number of packets to be prepared which are"]
-    #[doc = " supplied in the *tx_pkts* array of *rte_mbuf* structures, each of them"]
-    #[doc = " allocated from a pool created with rte_pktmbuf_pool_create()."]
-    #[doc = " For each packet to send, the rte_eth_tx_prepare() function performs"]
-    #[doc = " the following operations:"]
+    #[doc = " @return"]
+    #[doc = "   the number of the peer ports on success, a negative errno value otherwise."]
+    pub fn _rte_eth_hairpin_get_peer_ports(
+        port_id: u16,
+        peer_ports: *mut u16,
+        len: usize,
+        direction: u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Run the traffic management and the pipelines of a softnic port, on the lcore which polls it."]
     #[doc = ""]
-    #[doc = " - Check if packet meets devices requirements for tx offloads."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the softnic PMD is not linked."]
+    pub fn _rte_pmd_softnic_run(port_id: u16) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Service the CLI connection of a softnic port, which executes the pipeline commands."]
     #[doc = ""]
-    #[doc = " - Check limitations about number of segments."]
+    #[doc = " @return"]
+    #[doc = "   0 on success, a negative errno value otherwise, -ENOTSUP if the softnic PMD is not linked."]
+    pub fn _rte_pmd_softnic_manage(port_id: u16) -> ::std::os::raw::c_int;
+}
+pub const RTE_LPM_NAMESIZE: u32 = 32;
+pub const RTE_LPM_MAX_DEPTH: u32 = 32;
+pub const RTE_LPM_TBL24_NUM_ENTRIES: u32 = 16777216;
+pub const RTE_LPM_TBL8_GROUP_NUM_ENTRIES: u32 = 256;
+pub const RTE_LPM_LOOKUP_SUCCESS: u32 = 16777216;
+pub const RTE_LPM6_MAX_DEPTH: u32 = 128;
+pub const RTE_LPM6_IPV6_ADDR_SIZE: u32 = 16;
+pub const RTE_LPM6_NAMESIZE: u32 = 32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_lpm {
+    _unused: [u8; 0],
+}
+#[doc = " LPM configuration structure."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_lpm_config {
+    #[doc = "< Max number of rules."]
+    pub max_rules: u32,
+    #[doc = "< Number of tbl8s to allocate."]
+    pub number_tbl8s: u32,
+    #[doc = "< This field is currently unused."]
+    pub flags: ::std::os::raw::c_int,
+}
+extern "C" {
+    #[doc = " Create an LPM object."]
     #[doc = ""]
-    #[doc = " - Check additional requirements when debug is enabled."]
+    #[doc = " @param name"]
+    #[doc = "   LPM object name"]
+    #[doc = " @param socket_id"]
+    #[doc = "   NUMA socket ID for LPM table memory allocation"]
+    #[doc = " @param config"]
+    #[doc = "   Structure containing the configuration"]
+    #[doc = " @return"]
+    #[doc = "   Handle to LPM object on success, NULL otherwise with rte_errno set"]
+    #[doc = "   to an appropriate values. Possible rte_errno values include:"]
+    #[doc = "    - E_RTE_NO_CONFIG - function could not get pointer to rte_config structure"]
+    #[doc = "    - E_RTE_SECONDARY - function was called from a secondary process instance"]
+    #[doc = "    - EINVAL - invalid parameter passed to function"]
+    #[doc = "    - ENOSPC - the maximum number of memzones has already been allocated"]
+    #[doc = "    - EEXIST - a memzone with the same name already exists"]
+    #[doc = "    - ENOMEM - no appropriate memory area found in which to create memzone"]
+    pub fn rte_lpm_create(
+        name: *const ::std::os::raw::c_char,
+        socket_id: ::std::os::raw::c_int,
+        config: *const rte_lpm_config,
+    ) -> *mut rte_lpm;
+}
+extern "C" {
+    #[doc = " Find an existing LPM object and return a pointer to it."]
     #[doc = ""]
-    #[doc = " - Update and/or reset required checksums when tx offload is set for packet."]
+    #[doc = " @param name"]
+    #[doc = "   Name of the lpm object as passed to rte_lpm_create()"]
+    #[doc = " @return"]
+    #[doc = "   Pointer to lpm object or NULL if object not found with rte_errno"]
+    #[doc = "   set appropriately. Possible rte_errno values include:"]
+    #[doc = "    - ENOENT - required entry not available to return."]
+    pub fn rte_lpm_find_existing(name: *const ::std::os::raw::c_char) -> *mut rte_lpm;
+}
+extern "C" {
+    #[doc = " Free an LPM object."]
     #[doc = ""]
-    #[doc = " Since this function can modify packet data, provided mbufs must be safely"]
-    #[doc = " writable (e.g. modified data cannot be in shared segment)."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @return"]
+    #[doc = "   None"]
+    pub fn rte_lpm_free(lpm: *mut rte_lpm);
+}
+extern "C" {
+    #[doc = " Add a rule to the LPM table."]
     #[doc = ""]
-    #[doc = " The rte_eth_tx_prepare() function returns the number of packets ready to be"]
-    #[doc = " sent. A return value equal to *nb_pkts* means that all packets are valid and"]
-    #[doc = " ready to be sent, otherwise stops processing on the first invalid packet and"]
-    #[doc = " leaves the rest packets untouched."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be added to the LPM table"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to be added to the LPM table"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the rule to be added to the LPM table"]
+    #[doc = " @return"]
+    #[doc = "   0 on success, negative value otherwise"]
+    pub fn rte_lpm_add(lpm: *mut rte_lpm, ip: u32, depth: u8, next_hop: u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Check if a rule is present in the LPM table,"]
+    #[doc = " and provide its next hop if it is."]
     #[doc = ""]
-    #[doc = " When this functionality is not implemented in the driver, all packets are"]
-    #[doc = " are returned untouched."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be searched"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to searched"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the rule (valid only if it is found)"]
+    #[doc = " @return"]
+    #[doc = "   1 if the rule exists, 0 if it does not, a negative value on failure"]
+    pub fn rte_lpm_is_rule_present(
+        lpm: *mut rte_lpm,
+        ip: u32,
+        depth: u8,
+        next_hop: *mut u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Delete a rule from the LPM table."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "   The port identifier of the Ethernet device."]
-    #[doc = "   The value must be a valid port id."]
-    #[doc = " @param queue_id"]
-    #[doc = "   The index of the transmit queue through which output packets must be"]
-    #[doc = "   sent."]
-    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
-    #[doc = "   to rte_eth_dev_configure()."]
-    #[doc = " @param tx_pkts"]
-    #[doc = "   The address of an array of *nb_pkts* pointers to *rte_mbuf* structures"]
-    #[doc = "   which contain the output packets."]
-    #[doc = " @param nb_pkts"]
-    #[doc = "   The maximum number of packets to process."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be deleted from the LPM table"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to be deleted from the LPM table"]
     #[doc = " @return"]
-    #[doc = "   The number of packets correct and ready to be sent. The return value can be"]
-    #[doc = "   less than the value of the *tx_pkts* parameter when some packet doesn't"]
-    #[doc = "   meet devices requirements with rte_errno set appropriately:"]
-    #[doc = "   - -EINVAL: offload flags are not correctly set"]
-    #[doc = "   - -ENOTSUP: the offload feature is not supported by the hardware"]
+    #[doc = "   0 on success, negative value otherwise"]
+    pub fn rte_lpm_delete(lpm: *mut rte_lpm, ip: u32, depth: u8) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Delete all rules from the LPM table."]
     #[doc = ""]
-    pub fn _rte_eth_tx_prepare(port_id: u16, queue_id: u16, tx_pkts: *mut *mut rte_mbuf, nb_pkts: u16) -> u16;
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    pub fn rte_lpm_delete_all(lpm: *mut rte_lpm);
 }
 extern "C" {
-    #[doc = " Send any packets queued up for transmission on a port and HW queue"]
+    #[doc = " Lookup an IP into the LPM table."]
     #[doc = ""]
-    #[doc = " This causes an explicit flush of packets previously buffered via the"]
-    #[doc = " rte_eth_tx_buffer() function. It returns the number of packets successfully"]
-    #[doc = " sent to the NIC, and calls the error callback for any unsent packets. Unless"]
-    #[doc = " explicitly set up otherwise, the default callback simply frees the unsent"]
-    #[doc = " packets back to the owning mempool."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP to be looked up in the LPM table"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the most specific rule found for IP (valid on lookup hit only)"]
+    #[doc = " @return"]
+    #[doc = "   -EINVAL for incorrect arguments, -ENOENT on lookup miss, 0 on lookup hit"]
+    pub fn _rte_lpm_lookup(lpm: *mut rte_lpm, ip: u32, next_hop: *mut u32) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Lookup multiple IP addresses in an LPM table."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "   The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "   The index of the transmit queue through which output packets must be"]
-    #[doc = "   sent."]
-    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
-    #[doc = "   to rte_eth_dev_configure()."]
-    #[doc = " @param buffer"]
-    #[doc = "   Buffer of packets to be transmit."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ips"]
+    #[doc = "   Array of IPs to be looked up in the LPM table"]
+    #[doc = " @param next_hops"]
+    #[doc = "   Next hop of the most specific rule found for IP (valid on lookup hit only)."]
+    #[doc = "   This is an array of four byte values. The most significant byte in each"]
+    #[doc = "   value says whether the lookup was successful (bitmask"]
+    #[doc = "   RTE_LPM_LOOKUP_SUCCESS is set). The three least significant bytes is the"]
+    #[doc = "   actual next hop."]
+    #[doc = " @param n"]
+    #[doc = "   Number of elements in ips (and next_hops) array to lookup."]
     #[doc = " @return"]
-    #[doc = "   The number of packets successfully sent to the Ethernet device. The error"]
-    #[doc = "   callback is called for any packets which could not be sent."]
-    pub fn _rte_eth_tx_buffer_flush(port_id: u16, queue_id: u16, buffer: *mut rte_eth_dev_tx_buffer) -> u16;
+    #[doc = "   -EINVAL for incorrect arguments, otherwise 0"]
+    pub fn _rte_lpm_lookup_bulk(
+        lpm: *const rte_lpm,
+        ips: *const u32,
+        next_hops: *mut u32,
+        n: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct rte_lpm6 {
+    _unused: [u8; 0],
+}
+#[doc = " LPM configuration structure."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct rte_lpm6_config {
+    #[doc = "< Max number of rules."]
+    pub max_rules: u32,
+    #[doc = "< Number of tbl8s to allocate."]
+    pub number_tbl8s: u32,
+    #[doc = "< This field is currently unused."]
+    pub flags: ::std::os::raw::c_int,
 }
 extern "C" {
-    #[doc = " Buffer a single packet for future transmission on a port and queue"]
+    #[doc = " Create an LPM object."]
     #[doc = ""]
-    #[doc = " This function takes a single mbuf/packet and buffers it for later"]
-    #[doc = " transmission on the particular port and queue specified. Once the buffer is"]
-    #[doc = " full of packets, an attempt will be made to transmit all the buffered"]
-    #[doc = " packets. In case of error, where not all packets can be transmitted, a"]
-    #[doc = " callback is called with the unsent packets as a parameter. If no callback"]
-    #[doc = " is explicitly set up, the unsent packets are just freed back to the owning"]
-    #[doc = " mempool. The function returns the number of packets actually sent i.e."]
-    #[doc = " 0 if no buffer flush occurred, otherwise the number of packets successfully"]
-    #[doc = " flushed"]
+    #[doc = " @param name"]
+    #[doc = "   LPM object name"]
+    #[doc = " @param socket_id"]
+    #[doc = "   NUMA socket ID for LPM table memory allocation"]
+    #[doc = " @param config"]
+    #[doc = "   Structure containing the configuration"]
+    #[doc = " @return"]
+    #[doc = "   Handle to LPM object on success, NULL otherwise with rte_errno set"]
+    #[doc = "   to an appropriate values."]
+    pub fn rte_lpm6_create(
+        name: *const ::std::os::raw::c_char,
+        socket_id: ::std::os::raw::c_int,
+        config: *const rte_lpm6_config,
+    ) -> *mut rte_lpm6;
+}
+extern "C" {
+    #[doc = " Find an existing LPM object and return a pointer to it."]
     #[doc = ""]
-    #[doc = " @param port_id"]
-    #[doc = "   The port identifier of the Ethernet device."]
-    #[doc = " @param queue_id"]
-    #[doc = "   The index of the transmit queue through which output packets must be"]
-    #[doc = "   sent."]
-    #[doc = "   The value must be in the range [0, nb_tx_queue - 1] previously supplied"]
-    #[doc = "   to rte_eth_dev_configure()."]
-    #[doc = " @param buffer"]
-    #[doc = "   Buffer used to collect packets to be sent."]
-    #[doc = " @param tx_pkt"]
-    #[doc = "   Pointer to the packet mbuf to be sent."]
+    #[doc = " @param name"]
+    #[doc = "   Name of the lpm object as passed to rte_lpm6_create()"]
     #[doc = " @return"]
-    #[doc = "   0 = packet has been buffered for later transmission"]
-    #[doc = "   N > 0 = packet has been buffered, and the buffer was subsequently flushed,"]
-    #[doc = "     causing N packets to be sent, and the error callback to be called for"]
-    #[doc = "     the rest."]
-    pub fn _rte_eth_tx_buffer(
-        port_id: u16,
-        queue_id: u16,
-        buffer: *mut rte_eth_dev_tx_buffer,
-        tx_pkt: *mut rte_mbuf,
-    ) -> u16;
+    #[doc = "   Pointer to lpm object or NULL if object not found with rte_errno"]
+    #[doc = "   set appropriately. Possible rte_errno values include:"]
+    #[doc = "    - ENOENT - required entry not available to return."]
+    pub fn rte_lpm6_find_existing(name: *const ::std::os::raw::c_char) -> *mut rte_lpm6;
 }
 extern "C" {
-    #[doc = " Extract VLAN tag information into mbuf"]
+    #[doc = " Free an LPM object."]
     #[doc = ""]
-    #[doc = " Software version of VLAN stripping"]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @return"]
+    #[doc = "   None"]
+    pub fn rte_lpm6_free(lpm: *mut rte_lpm6);
+}
+extern "C" {
+    #[doc = " Add a rule to the LPM table."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be added to the LPM table"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to be added to the LPM table"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the rule to be added to the LPM table"]
     #[doc = " @return"]
-    #[doc = "   - 0: Success"]
-    #[doc = "   - 1: not a vlan packet"]
-    pub fn _rte_vlan_strip(m: *mut rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   0 on success, negative value otherwise"]
+    pub fn rte_lpm6_add(lpm: *mut rte_lpm6, ip: *mut u8, depth: u8, next_hop: u32) -> ::std::os::raw::c_int;
 }
 extern "C" {
-    #[doc = " Insert VLAN tag into mbuf."]
+    #[doc = " Check if a rule is present in the LPM table,"]
+    #[doc = " and provide its next hop if it is."]
     #[doc = ""]
-    #[doc = " Software version of VLAN unstripping"]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be searched"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to searched"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the rule (valid only if it is found)"]
+    #[doc = " @return"]
+    #[doc = "   1 if the rule exists, 0 if it does not, a negative value on failure"]
+    pub fn rte_lpm6_is_rule_present(
+        lpm: *mut rte_lpm6,
+        ip: *mut u8,
+        depth: u8,
+        next_hop: *mut u32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Delete a rule from the LPM table."]
     #[doc = ""]
-    #[doc = " @param m"]
-    #[doc = "   The packet mbuf."]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP of the rule to be deleted from the LPM table"]
+    #[doc = " @param depth"]
+    #[doc = "   Depth of the rule to be deleted from the LPM table"]
     #[doc = " @return"]
-    #[doc = "   - 0: On success"]
-    #[doc = "   -EPERM: mbuf is is shared overwriting would be unsafe"]
-    #[doc = "   -ENOSPC: not enough headroom in mbuf"]
-    pub fn _rte_vlan_insert(m: *mut *mut rte_mbuf) -> ::std::os::raw::c_int;
+    #[doc = "   0 on success, negative value otherwise"]
+    pub fn rte_lpm6_delete(lpm: *mut rte_lpm6, ip: *mut u8, depth: u8) -> ::std::os::raw::c_int;
 }
-pub type __builtin_va_list = [__va_list_tag; 1usize];
-#[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct __va_list_tag {
-    pub gp_offset: ::std::os::raw::c_uint,
-    pub fp_offset: ::std::os::raw::c_uint,
-    pub overflow_arg_area: *mut ::std::os::raw::c_void,
-    pub reg_save_area: *mut ::std::os::raw::c_void,
+extern "C" {
+    #[doc = " Delete all rules from the LPM table."]
+    #[doc = ""]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    pub fn rte_lpm6_delete_all(lpm: *mut rte_lpm6);
 }
-#[test]
-fn bindgen_test_layout___va_list_tag() {
-    assert_eq!(
-        ::std::mem::size_of::<__va_list_tag>(),
-        24usize,
-        concat!("Size of: ", stringify!(__va_list_tag))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<__va_list_tag>(),
-        8usize,
-        concat!("Alignment of ", stringify!(__va_list_tag))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).gp_offset as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(__va_list_tag),
-            "::",
-            stringify!(gp_offset)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).fp_offset as *const _ as usize },
-        4usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(__va_list_tag),
-            "::",
-            stringify!(fp_offset)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).overflow_arg_area as *const _ as usize },
-        8usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(__va_list_tag),
-            "::",
-            stringify!(overflow_arg_area)
-        )
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__va_list_tag>())).reg_save_area as *const _ as usize },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(__va_list_tag),
-            "::",
-            stringify!(reg_save_area)
-        )
-    );
+extern "C" {
+    #[doc = " Lookup an IP into the LPM table."]
+    #[doc = ""]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ip"]
+    #[doc = "   IP to be looked up in the LPM table"]
+    #[doc = " @param next_hop"]
+    #[doc = "   Next hop of the most specific rule found for IP (valid on lookup hit only)"]
+    #[doc = " @return"]
+    #[doc = "   -EINVAL for incorrect arguments, -ENOENT on lookup miss, 0 on lookup hit"]
+    pub fn rte_lpm6_lookup(lpm: *const rte_lpm6, ip: *mut u8, next_hop: *mut u32) -> ::std::os::raw::c_int;
 }
-impl Default for __va_list_tag {
-    fn default() -> Self {
-        unsafe { ::std::mem::zeroed() }
-    }
+extern "C" {
+    #[doc = " Lookup multiple IP addresses in an LPM table."]
+    #[doc = ""]
+    #[doc = " @param lpm"]
+    #[doc = "   LPM object handle"]
+    #[doc = " @param ips"]
+    #[doc = "   Array of IPs to be looked up in the LPM table"]
+    #[doc = " @param next_hops"]
+    #[doc = "   Next hop of the most specific rule found for IP (valid on lookup hit only)."]
+    #[doc = "   This is an array of two byte values. The next hop will be stored on"]
+    #[doc = "   each position on success; otherwise the position will be set to -1."]
+    #[doc = " @param n"]
+    #[doc = "   Number of elements in ips (and next_hops) array to lookup."]
+    #[doc = " @return"]
+    #[doc = "   -EINVAL for incorrect arguments, otherwise 0"]
+    pub fn rte_lpm6_lookup_bulk_func(
+        lpm: *const rte_lpm6,
+        ips: *mut [u8; 16usize],
+        next_hops: *mut i32,
+        n: ::std::os::raw::c_uint,
+    ) -> ::std::os::raw::c_int;
 }
-#[doc = "< class handle."]
-#[repr(C)]
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
-pub struct rte_class {
-    pub _address: u8,
+extern "C" {
+    #[doc = " Check whether a driver has been registered on its bus."]
+    #[doc = ""]
+    #[doc = " @param name"]
+    #[doc = "   The name of the driver, e.g. `net_ixgbe`."]
+    #[doc = " @param pci"]
+    #[doc = "   The driver is a PCI driver, otherwise it is looked up on the vdev bus."]
+    #[doc = " @return"]
+    #[doc = "   1 if the driver is registered, 0 if not,"]
+    #[doc = "   or -1 if it isn't a PCI or vdev driver and there are the other buses which drivers can't be listed."]
+    pub fn _rte_driver_registered(name: *const ::std::os::raw::c_char, pci: ::std::os::raw::c_int)
+        -> ::std::os::raw::c_int;
 }
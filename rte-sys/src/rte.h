@@ -17,6 +17,7 @@
 #include <rte_malloc.h>
 #include <rte_memcpy.h>
 #include <rte_memory.h>
+#include <rte_pause.h>
 #include <rte_per_lcore.h>
 #include <rte_prefetch.h>
 #include <rte_spinlock.h>
@@ -39,6 +40,19 @@
 #include <rte_ethdev.h>
 #include <rte_kni.h>
 #include <rte_eth_bond.h>
+#include <rte_eth_bond_8023ad.h>
+#include <rte_eth_ring.h>
+#include <rte_pmd_ixgbe.h>
+#include <rte_pmd_i40e.h>
+#include <rte_distributor.h>
+#include <rte_cryptodev.h>
+
+#include <rte_hash_crc.h>
+#include <rte_jhash.h>
+#include <rte_thash.h>
+
+#include <rte_lpm.h>
+#include <rte_lpm6.h>
 
 #include <rte_ether.h>
 #include <rte_arp.h>
@@ -47,6 +61,11 @@
 #include <rte_tcp.h>
 #include <rte_udp.h>
 #include <rte_sctp.h>
+#include <rte_flow.h>
+
+#include <rte_metrics.h>
+#include <rte_bitrate.h>
+#include <rte_pdump.h>
 
 #include <cmdline_rdline.h>
 #include <cmdline_parse.h>
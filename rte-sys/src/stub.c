@@ -1,4 +1,5 @@
 #include "rte.h"
+#include "trace.h"
 
 void
 _rte_srand(uint64_t seedval) {
@@ -175,6 +176,41 @@ _rte_rdtsc_precise(void) {
     return rte_rdtsc_precise();
 }
 
+void
+_rte_pause(void) {
+    rte_pause();
+}
+
+int
+_rte_ring_enqueue(struct rte_ring *r, void *obj) {
+    return rte_ring_enqueue(r, obj);
+}
+
+int
+_rte_ring_dequeue(struct rte_ring *r, void **obj_p) {
+    return rte_ring_dequeue(r, obj_p);
+}
+
+unsigned
+_rte_ring_enqueue_burst(struct rte_ring *r, void * const *obj_table, unsigned int n, unsigned int *free_space) {
+    return rte_ring_enqueue_burst(r, obj_table, n, free_space);
+}
+
+unsigned
+_rte_ring_dequeue_burst(struct rte_ring *r, void **obj_table, unsigned int n, unsigned int *available) {
+    return rte_ring_dequeue_burst(r, obj_table, n, available);
+}
+
+unsigned
+_rte_ring_count(const struct rte_ring *r) {
+    return rte_ring_count(r);
+}
+
+unsigned
+_rte_ring_free_count(const struct rte_ring *r) {
+    return rte_ring_free_count(r);
+}
+
 struct rte_mempool *
 _rte_mempool_from_obj(void *obj) {
     return rte_mempool_from_obj(obj);
@@ -508,3 +544,528 @@ int
 _rte_vlan_insert(struct rte_mbuf **m) {
     return rte_vlan_insert(m);
 }
+
+void
+_rte_hash_crc_set_alg(uint8_t alg) {
+    rte_hash_crc_set_alg(alg);
+}
+
+uint32_t
+_rte_hash_crc_4byte(uint32_t data, uint32_t init_val) {
+    return rte_hash_crc_4byte(data, init_val);
+}
+
+uint32_t
+_rte_hash_crc_8byte(uint64_t data, uint32_t init_val) {
+    return rte_hash_crc_8byte(data, init_val);
+}
+
+uint32_t
+_rte_hash_crc(const void *data, uint32_t data_len, uint32_t init_val) {
+    return rte_hash_crc(data, data_len, init_val);
+}
+
+uint32_t
+_rte_jhash(const void *key, uint32_t length, uint32_t initval) {
+    return rte_jhash(key, length, initval);
+}
+
+uint32_t
+_rte_jhash_32b(const uint32_t *k, uint32_t length, uint32_t initval) {
+    return rte_jhash_32b(k, length, initval);
+}
+
+uint32_t
+_rte_jhash_3words(uint32_t a, uint32_t b, uint32_t c, uint32_t initval) {
+    return rte_jhash_3words(a, b, c, initval);
+}
+
+void
+_rte_convert_rss_key(const uint32_t *orig, uint32_t *targ, int len) {
+    rte_convert_rss_key(orig, targ, len);
+}
+
+uint32_t
+_rte_softrss(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key) {
+    return rte_softrss(input_tuple, input_len, rss_key);
+}
+
+uint32_t
+_rte_softrss_be(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key) {
+    return rte_softrss_be(input_tuple, input_len, rss_key);
+}
+
+struct rte_crypto_op *
+_rte_crypto_op_alloc(struct rte_mempool *mempool, enum rte_crypto_op_type type) {
+    return rte_crypto_op_alloc(mempool, type);
+}
+
+void
+_rte_crypto_op_free(struct rte_crypto_op *op) {
+    rte_crypto_op_free(op);
+}
+
+struct rte_crypto_asym_op *
+_rte_crypto_op_asym(struct rte_crypto_op *op) {
+    return op->asym;
+}
+
+int
+_rte_crypto_op_attach_asym_session(struct rte_crypto_op *op, struct rte_cryptodev_asym_session *sess) {
+    return rte_crypto_op_attach_asym_session(op, sess);
+}
+
+uint16_t
+_rte_cryptodev_enqueue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops) {
+    return rte_cryptodev_enqueue_burst(dev_id, qp_id, ops, nb_ops);
+}
+
+uint16_t
+_rte_cryptodev_dequeue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops) {
+    return rte_cryptodev_dequeue_burst(dev_id, qp_id, ops, nb_ops);
+}
+
+const char *
+_rte_version(void) {
+    return rte_version();
+}
+
+int
+_rte_lpm_lookup(struct rte_lpm *lpm, uint32_t ip, uint32_t *next_hop) {
+    return rte_lpm_lookup(lpm, ip, next_hop);
+}
+
+int
+_rte_lpm_lookup_bulk(const struct rte_lpm *lpm, const uint32_t *ips, uint32_t *next_hops, unsigned n) {
+    return rte_lpm_lookup_bulk(lpm, ips, next_hops, n);
+}
+
+#include <rte_bus.h>
+#include <rte_bus_pci.h>
+
+static int
+_rte_cmp_other_bus(const struct rte_bus *bus, const void *data) {
+    return strcmp(bus->name, "pci") == 0 || strcmp(bus->name, "vdev") == 0;
+}
+
+int
+_rte_driver_registered(const char *name, int pci) {
+    struct rte_bus *bus;
+    struct rte_pci_bus *pci_bus;
+    struct rte_pci_driver *drv;
+
+    if (pci) {
+        bus = rte_bus_find_by_name("pci");
+
+        if (bus == NULL) {
+            return 0;
+        }
+
+        pci_bus = container_of(bus, struct rte_pci_bus, bus);
+
+        TAILQ_FOREACH(drv, &pci_bus->driver_list, next) {
+            if (strcmp(drv->driver.name, name) == 0) {
+                return 1;
+            }
+        }
+
+        return 0;
+    }
+
+    // the vdev bus parses a device name with the name or alias of its drivers
+    bus = rte_bus_find_by_name("vdev");
+
+    if (bus != NULL && bus->parse != NULL && bus->parse(name, NULL) == 0) {
+        return 1;
+    }
+
+    // the drivers of the other buses can't be listed
+    return rte_bus_find(NULL, _rte_cmp_other_bus, NULL) != NULL ? -1 : 0;
+}
+
+#if RTE_VERSION >= RTE_VERSION_NUM(20, 11, 0, 0)
+
+static void
+_rte_flow_tunnel_to(struct rte_flow_tunnel *dst, const struct _rte_flow_tunnel *src) {
+    memset(dst, 0, sizeof(*dst));
+
+    dst->type = src->type;
+    dst->tun_id = src->tun_id;
+    dst->is_ipv6 = src->is_ipv6;
+
+    if (src->is_ipv6) {
+        memcpy(dst->ipv6.src_addr, src->src_addr, 16);
+        memcpy(dst->ipv6.dst_addr, src->dst_addr, 16);
+    } else {
+        memcpy(&dst->ipv4.src_addr, src->src_addr, 4);
+        memcpy(&dst->ipv4.dst_addr, src->dst_addr, 4);
+    }
+
+    dst->tp_src = src->tp_src;
+    dst->tp_dst = src->tp_dst;
+    dst->tun_flags = src->tun_flags;
+    dst->tos = src->tos;
+    dst->ttl = src->ttl;
+    dst->label = src->label;
+}
+
+static void
+_rte_flow_tunnel_from(struct _rte_flow_tunnel *dst, const struct rte_flow_tunnel *src) {
+    memset(dst, 0, sizeof(*dst));
+
+    dst->type = src->type;
+    dst->tun_id = src->tun_id;
+    dst->is_ipv6 = src->is_ipv6;
+
+    if (src->is_ipv6) {
+        memcpy(dst->src_addr, src->ipv6.src_addr, 16);
+        memcpy(dst->dst_addr, src->ipv6.dst_addr, 16);
+    } else {
+        memcpy(dst->src_addr, &src->ipv4.src_addr, 4);
+        memcpy(dst->dst_addr, &src->ipv4.dst_addr, 4);
+    }
+
+    dst->tp_src = src->tp_src;
+    dst->tp_dst = src->tp_dst;
+    dst->tun_flags = src->tun_flags;
+    dst->tos = src->tos;
+    dst->ttl = src->ttl;
+    dst->label = src->label;
+}
+
+int
+_rte_flow_tunnel_decap_set(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                           struct rte_flow_action **actions, uint32_t *num_of_actions,
+                           struct rte_flow_error *error) {
+    struct rte_flow_tunnel t;
+
+    _rte_flow_tunnel_to(&t, tunnel);
+
+    return rte_flow_tunnel_decap_set(port_id, &t, actions, num_of_actions, error);
+}
+
+int
+_rte_flow_tunnel_match(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                       struct rte_flow_item **items, uint32_t *num_of_items,
+                       struct rte_flow_error *error) {
+    struct rte_flow_tunnel t;
+
+    _rte_flow_tunnel_to(&t, tunnel);
+
+    return rte_flow_tunnel_match(port_id, &t, items, num_of_items, error);
+}
+
+int
+_rte_flow_get_restore_info(uint16_t port_id, struct rte_mbuf *m,
+                           struct _rte_flow_restore_info *info, struct rte_flow_error *error) {
+    struct rte_flow_restore_info i;
+    int ret = rte_flow_get_restore_info(port_id, m, &i, error);
+
+    if (ret == 0) {
+        info->flags = i.flags;
+        info->group_id = i.group_id;
+
+        _rte_flow_tunnel_from(&info->tunnel, &i.tunnel);
+    }
+
+    return ret;
+}
+
+int
+_rte_flow_tunnel_action_decap_release(uint16_t port_id, struct rte_flow_action *actions,
+                                      uint32_t num_of_actions, struct rte_flow_error *error) {
+    return rte_flow_tunnel_action_decap_release(port_id, actions, num_of_actions, error);
+}
+
+int
+_rte_flow_tunnel_item_release(uint16_t port_id, struct rte_flow_item *items,
+                              uint32_t num_of_items, struct rte_flow_error *error) {
+    return rte_flow_tunnel_item_release(port_id, items, num_of_items, error);
+}
+
+#else
+
+static int
+_rte_flow_tunnel_unsupported(struct rte_flow_error *error) {
+    return rte_flow_error_set(error, ENOTSUP, RTE_FLOW_ERROR_TYPE_UNSPECIFIED, NULL,
+                              "tunnel offload requires DPDK 20.11 or later");
+}
+
+int
+_rte_flow_tunnel_decap_set(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                           struct rte_flow_action **actions, uint32_t *num_of_actions,
+                           struct rte_flow_error *error) {
+    return _rte_flow_tunnel_unsupported(error);
+}
+
+int
+_rte_flow_tunnel_match(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                       struct rte_flow_item **items, uint32_t *num_of_items,
+                       struct rte_flow_error *error) {
+    return _rte_flow_tunnel_unsupported(error);
+}
+
+int
+_rte_flow_get_restore_info(uint16_t port_id, struct rte_mbuf *m,
+                           struct _rte_flow_restore_info *info, struct rte_flow_error *error) {
+    return _rte_flow_tunnel_unsupported(error);
+}
+
+int
+_rte_flow_tunnel_action_decap_release(uint16_t port_id, struct rte_flow_action *actions,
+                                      uint32_t num_of_actions, struct rte_flow_error *error) {
+    return _rte_flow_tunnel_unsupported(error);
+}
+
+int
+_rte_flow_tunnel_item_release(uint16_t port_id, struct rte_flow_item *items,
+                              uint32_t num_of_items, struct rte_flow_error *error) {
+    return _rte_flow_tunnel_unsupported(error);
+}
+
+#endif
+
+#if RTE_VERSION >= RTE_VERSION_NUM(19, 11, 0, 0)
+
+int
+_rte_eth_dev_hairpin_capability_get(uint16_t port_id, struct _rte_eth_hairpin_cap *cap) {
+    struct rte_eth_hairpin_cap c;
+    int ret = rte_eth_dev_hairpin_capability_get(port_id, &c);
+
+    if (ret == 0) {
+        cap->max_nb_queues = c.max_nb_queues;
+        cap->max_rx_2_tx = c.max_rx_2_tx;
+        cap->max_tx_2_rx = c.max_tx_2_rx;
+        cap->max_nb_desc = c.max_nb_desc;
+    }
+
+    return ret;
+}
+
+static int
+_rte_eth_hairpin_conf_to(struct rte_eth_hairpin_conf *dst, const struct _rte_eth_hairpin_conf *src) {
+    uint16_t i;
+
+    if (src->peer_count > RTE_ETH_MAX_HAIRPIN_PEERS || src->peer_count > _RTE_ETH_MAX_HAIRPIN_PEERS)
+        return -EINVAL;
+
+    memset(dst, 0, sizeof(*dst));
+
+    dst->peer_count = src->peer_count;
+
+#if RTE_VERSION >= RTE_VERSION_NUM(20, 11, 0, 0)
+    dst->tx_explicit = src->tx_explicit;
+    dst->manual_bind = src->manual_bind;
+#else
+    if (src->tx_explicit || src->manual_bind)
+        return -ENOTSUP;
+#endif
+
+    for (i = 0; i < src->peer_count; i++) {
+        dst->peers[i].port = src->peers[i].port;
+        dst->peers[i].queue = src->peers[i].queue;
+    }
+
+    return 0;
+}
+
+int
+_rte_eth_rx_hairpin_queue_setup(uint16_t port_id, uint16_t rx_queue_id, uint16_t nb_rx_desc,
+                                const struct _rte_eth_hairpin_conf *conf) {
+    struct rte_eth_hairpin_conf c;
+    int ret = _rte_eth_hairpin_conf_to(&c, conf);
+
+    return ret ? ret : rte_eth_rx_hairpin_queue_setup(port_id, rx_queue_id, nb_rx_desc, &c);
+}
+
+int
+_rte_eth_tx_hairpin_queue_setup(uint16_t port_id, uint16_t tx_queue_id, uint16_t nb_tx_desc,
+                                const struct _rte_eth_hairpin_conf *conf) {
+    struct rte_eth_hairpin_conf c;
+    int ret = _rte_eth_hairpin_conf_to(&c, conf);
+
+    return ret ? ret : rte_eth_tx_hairpin_queue_setup(port_id, tx_queue_id, nb_tx_desc, &c);
+}
+
+#else
+
+int
+_rte_eth_dev_hairpin_capability_get(uint16_t port_id, struct _rte_eth_hairpin_cap *cap) {
+    return -ENOTSUP;
+}
+
+int
+_rte_eth_rx_hairpin_queue_setup(uint16_t port_id, uint16_t rx_queue_id, uint16_t nb_rx_desc,
+                                const struct _rte_eth_hairpin_conf *conf) {
+    return -ENOTSUP;
+}
+
+int
+_rte_eth_tx_hairpin_queue_setup(uint16_t port_id, uint16_t tx_queue_id, uint16_t nb_tx_desc,
+                                const struct _rte_eth_hairpin_conf *conf) {
+    return -ENOTSUP;
+}
+
+#endif
+
+#if RTE_VERSION >= RTE_VERSION_NUM(20, 11, 0, 0)
+
+int
+_rte_eth_hairpin_bind(uint16_t tx_port, uint16_t rx_port) {
+    return rte_eth_hairpin_bind(tx_port, rx_port);
+}
+
+int
+_rte_eth_hairpin_unbind(uint16_t tx_port, uint16_t rx_port) {
+    return rte_eth_hairpin_unbind(tx_port, rx_port);
+}
+
+int
+_rte_eth_hairpin_get_peer_ports(uint16_t port_id, uint16_t *peer_ports, size_t len, uint32_t direction) {
+    return rte_eth_hairpin_get_peer_ports(port_id, peer_ports, len, direction);
+}
+
+#else
+
+int
+_rte_eth_hairpin_bind(uint16_t tx_port, uint16_t rx_port) {
+    return -ENOTSUP;
+}
+
+int
+_rte_eth_hairpin_unbind(uint16_t tx_port, uint16_t rx_port) {
+    return -ENOTSUP;
+}
+
+int
+_rte_eth_hairpin_get_peer_ports(uint16_t port_id, uint16_t *peer_ports, size_t len, uint32_t direction) {
+    return -ENOTSUP;
+}
+
+#endif
+
+#ifdef RTE_LIBRTE_PMD_SOFTNIC
+
+#include <rte_eth_softnic.h>
+
+int
+_rte_pmd_softnic_run(uint16_t port_id) {
+    return rte_pmd_softnic_run(port_id);
+}
+
+int
+_rte_pmd_softnic_manage(uint16_t port_id) {
+    return rte_pmd_softnic_manage(port_id);
+}
+
+#else
+
+int
+_rte_pmd_softnic_run(uint16_t port_id) {
+    return -ENOTSUP;
+}
+
+int
+_rte_pmd_softnic_manage(uint16_t port_id) {
+    return -ENOTSUP;
+}
+
+#endif
+
+#ifdef HAS_RTE_TRACE
+
+int
+_rte_trace_is_enabled(void) {
+    return rte_trace_is_enabled();
+}
+
+int
+_rte_trace_pattern(const char *pattern, int enable) {
+    return rte_trace_pattern(pattern, enable);
+}
+
+int
+_rte_trace_regexp(const char *regex, int enable) {
+    return rte_trace_regexp(regex, enable);
+}
+
+int
+_rte_trace_mode_set(int mode) {
+    rte_trace_mode_set(mode ? RTE_TRACE_MODE_DISCARD : RTE_TRACE_MODE_OVERWRITE);
+
+    return 0;
+}
+
+int
+_rte_trace_mode_get(void) {
+    return rte_trace_mode_get() == RTE_TRACE_MODE_DISCARD;
+}
+
+int
+_rte_trace_save(void) {
+    return rte_trace_save();
+}
+
+int
+_rte_trace_dump(FILE *f) {
+    rte_trace_dump(f);
+
+    return 0;
+}
+
+void
+_rte_trace_user_u64(const char *name, uint64_t value) {
+    _rte_trace_point_user_u64(name, value);
+}
+
+void
+_rte_trace_user_str(const char *name, const char *msg) {
+    _rte_trace_point_user_str(name, msg);
+}
+
+#else
+
+int
+_rte_trace_is_enabled(void) {
+    return 0;
+}
+
+int
+_rte_trace_pattern(const char *pattern, int enable) {
+    return -ENOTSUP;
+}
+
+int
+_rte_trace_regexp(const char *regex, int enable) {
+    return -ENOTSUP;
+}
+
+int
+_rte_trace_mode_set(int mode) {
+    return -ENOTSUP;
+}
+
+int
+_rte_trace_mode_get(void) {
+    return -ENOTSUP;
+}
+
+int
+_rte_trace_save(void) {
+    return -ENOTSUP;
+}
+
+int
+_rte_trace_dump(FILE *f) {
+    return -ENOTSUP;
+}
+
+void
+_rte_trace_user_u64(const char *name, uint64_t value) {
+}
+
+void
+_rte_trace_user_str(const char *name, const char *msg) {
+}
+
+#endif
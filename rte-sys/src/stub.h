@@ -3,6 +3,11 @@
 #include <rte_bitmap.h>
 #include <rte_spinlock.h>
 #include <rte_mbuf.h>
+#include <rte_hash_crc.h>
+#include <rte_jhash.h>
+#include <rte_thash.h>
+#include <rte_cryptodev.h>
+#include <rte_lpm.h>
 
 /**
  * Seed the pseudo-random generator.
@@ -400,9 +405,113 @@ _rte_rdtsc(void);
 uint64_t
 _rte_rdtsc_precise(void);
 
+/**
+ * Pause CPU execution for a short while
+ *
+ * This call is intended for tight loops which poll a shared resource or wait
+ * for an event. A short pause within the loop may reduce the power consumption.
+ */
+void
+_rte_pause(void);
+
 uint64_t
 _rte_get_tsc_cycles(void);
 
+/**
+ * Enqueue one object on a ring.
+ *
+ * This function calls the multi-producer or the single-producer
+ * version, depending on the default behaviour that was specified at
+ * ring creation time (see flags).
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @param obj
+ *   A pointer to the object to be added.
+ * @return
+ *   - 0: Success; objects enqueued.
+ *   - -ENOBUFS: Not enough room in the ring to enqueue; no object is enqueued.
+ */
+int
+_rte_ring_enqueue(struct rte_ring *r, void *obj);
+
+/**
+ * Dequeue one object from a ring.
+ *
+ * This function calls the multi-consumers or the single-consumer
+ * version depending on the default behaviour that was specified at
+ * ring creation time (see flags).
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @param obj_p
+ *   A pointer to a void * pointer (object) that will be filled.
+ * @return
+ *   - 0: Success, objects dequeued.
+ *   - -ENOENT: Not enough entries in the ring to dequeue, no object is
+ *     dequeued.
+ */
+int
+_rte_ring_dequeue(struct rte_ring *r, void **obj_p);
+
+/**
+ * Enqueue several objects on a ring.
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @param obj_table
+ *   A pointer to a table of void * pointers (objects).
+ * @param n
+ *   The number of objects to add in the ring from the obj_table.
+ * @param free_space
+ *   if non-NULL, returns the amount of space in the ring after the
+ *   enqueue operation has finished.
+ * @return
+ *   - n: Actual number of objects enqueued.
+ */
+unsigned
+_rte_ring_enqueue_burst(struct rte_ring *r, void * const *obj_table, unsigned int n, unsigned int *free_space);
+
+/**
+ * Dequeue multiple objects from a ring up to a maximum number.
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @param obj_table
+ *   A pointer to a table of void * pointers (objects) that will be filled.
+ * @param n
+ *   The number of objects to dequeue from the ring to the obj_table.
+ * @param available
+ *   If non-NULL, returns the number of remaining ring entries after the
+ *   dequeue has finished.
+ * @return
+ *   - Number of objects dequeued
+ */
+unsigned
+_rte_ring_dequeue_burst(struct rte_ring *r, void **obj_table, unsigned int n, unsigned int *available);
+
+/**
+ * Return the number of entries in a ring.
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @return
+ *   The number of entries in the ring.
+ */
+unsigned
+_rte_ring_count(const struct rte_ring *r);
+
+/**
+ * Return the number of free entries in a ring.
+ *
+ * @param r
+ *   A pointer to the ring structure.
+ * @return
+ *   The number of free entries in the ring.
+ */
+unsigned
+_rte_ring_free_count(const struct rte_ring *r);
+
 /**
  * Return a pointer to the mempool owning this object.
  *
@@ -1702,3 +1811,554 @@ _rte_vlan_strip(struct rte_mbuf *m);
  */
 int
 _rte_vlan_insert(struct rte_mbuf **m);
+
+/**
+ * Allow or disallow use of SSE4.2 instrinsics for CRC32 hash
+ * calculation.
+ *
+ * @param alg
+ *   An OR of following flags:
+ *   - (CRC32_SW) Don't use SSE4.2 intrinsics
+ *   - (CRC32_SSE42) Use SSE4.2 intrinsics if available
+ *   - (CRC32_SSE42_x64) Use 64-bit SSE4.2 intrinsic if available (default)
+ *
+ */
+void
+_rte_hash_crc_set_alg(uint8_t alg);
+
+/**
+ * Use single crc32 instruction to perform a hash on a 4 byte value.
+ * Fall back to software crc32 implementation in case SSE4.2 is
+ * not supported
+ *
+ * @param data
+ *   Data to perform hash on.
+ * @param init_val
+ *   Value to initialise hash generator.
+ * @return
+ *   32bit calculated hash value.
+ */
+uint32_t
+_rte_hash_crc_4byte(uint32_t data, uint32_t init_val);
+
+/**
+ * Use single crc32 instruction to perform a hash on a 8 byte value.
+ * Fall back to software crc32 implementation in case SSE4.2 is
+ * not supported
+ *
+ * @param data
+ *   Data to perform hash on.
+ * @param init_val
+ *   Value to initialise hash generator.
+ * @return
+ *   32bit calculated hash value.
+ */
+uint32_t
+_rte_hash_crc_8byte(uint64_t data, uint32_t init_val);
+
+/**
+ * Calculate CRC32 hash on user-supplied byte array.
+ *
+ * @param data
+ *   Data to perform hash on.
+ * @param data_len
+ *   How many bytes to use to calculate hash value.
+ * @param init_val
+ *   Value to initialise hash generator.
+ * @return
+ *   32bit calculated hash value.
+ */
+uint32_t
+_rte_hash_crc(const void *data, uint32_t data_len, uint32_t init_val);
+
+/**
+ * The most generic version, hashes an arbitrary sequence
+ * of bytes.  No alignment or length assumptions are made about
+ * the input key.
+ *
+ * @param key
+ *   Key to calculate hash of.
+ * @param length
+ *   Length of key in bytes.
+ * @param initval
+ *   Initialising value of hash.
+ * @return
+ *   Calculated hash value.
+ */
+uint32_t
+_rte_jhash(const void *key, uint32_t length, uint32_t initval);
+
+/**
+ * A special optimized version that handles 1 or more of uint32_ts.
+ * The length parameter here is the number of uint32_ts in the key.
+ *
+ * @param k
+ *   Key to calculate hash of.
+ * @param length
+ *   Length of key in units of 4 bytes.
+ * @param initval
+ *   Initialising value of hash.
+ * @return
+ *   Calculated hash value.
+ */
+uint32_t
+_rte_jhash_32b(const uint32_t *k, uint32_t length, uint32_t initval);
+
+/**
+ * A special ultra-optimized versions that knows it is hashing exactly
+ * 3 words.
+ *
+ * @param a
+ *   First word to calculate hash of.
+ * @param b
+ *   Second word to calculate hash of.
+ * @param c
+ *   Third word to calculate hash of.
+ * @param initval
+ *   Initialising value of hash.
+ * @return
+ *   Calculated hash value.
+ */
+uint32_t
+_rte_jhash_3words(uint32_t a, uint32_t b, uint32_t c, uint32_t initval);
+
+/**
+ * Prepare a new key for use with rte_softrss_be()
+ *
+ * @param orig
+ *   pointer to the original key
+ * @param targ
+ *   pointer to the target key
+ * @param len
+ *   key length
+ */
+void
+_rte_convert_rss_key(const uint32_t *orig, uint32_t *targ, int len);
+
+/**
+ * Generic implementation. Can be used with original rss_key
+ *
+ * @param input_tuple
+ *   Pointer to input tuple
+ * @param input_len
+ *   Length of input_tuple in 4-bytes chunks
+ * @param rss_key
+ *   Pointer to RSS hash key.
+ * @return
+ *   Calculated hash value.
+ */
+uint32_t
+_rte_softrss(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key);
+
+/**
+ * Optimized implementation.
+ * If you want the calculated hash value matches NIC RSS value
+ * you have to use special converted key with rte_convert_rss_key() fn.
+ *
+ * @param input_tuple
+ *   Pointer to input tuple
+ * @param input_len
+ *   Length of input_tuple in 4-bytes chunks
+ * @param rss_key
+ *   Pointer to RSS hash key.
+ * @return
+ *   Calculated hash value.
+ */
+uint32_t
+_rte_softrss_be(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key);
+
+/**
+ * Allocate a crypto operation from a mempool with default parameters set
+ *
+ * @param	mempool	crypto operation mempool
+ * @param	type	operation type to allocate
+ *
+ * @returns
+ * - On success returns a valid rte_crypto_op structure
+ * - On failure returns NULL
+ */
+struct rte_crypto_op *
+_rte_crypto_op_alloc(struct rte_mempool *mempool, enum rte_crypto_op_type type);
+
+/**
+ * Free crypto operation structure
+ * If operation has been allocate from a rte_mempool, then the operation will
+ * be returned to the mempool.
+ *
+ * @param	op	symmetric crypto operation
+ */
+void
+_rte_crypto_op_free(struct rte_crypto_op *op);
+
+/**
+ * Get the asymmetric operation of a crypto operation.
+ */
+struct rte_crypto_asym_op *
+_rte_crypto_op_asym(struct rte_crypto_op *op);
+
+/**
+ * Attach a asymmetric session to a crypto operation
+ *
+ * @param	op	crypto operation, must be of type asymmetric
+ * @param	sess	cryptodev session
+ */
+int
+_rte_crypto_op_attach_asym_session(struct rte_crypto_op *op, struct rte_cryptodev_asym_session *sess);
+
+/**
+ * Enqueue a burst of operations for processing on a crypto device.
+ *
+ * @param	dev_id		The identifier of the device.
+ * @param	qp_id		The index of the queue pair which packets are
+ *				to be enqueued for processing.
+ * @param	ops		The address of an array of *nb_ops* pointers
+ *				to *rte_crypto_op* structures which contain
+ *				the crypto operations to be processed.
+ * @param	nb_ops		The number of operations to process.
+ *
+ * @return
+ * The number of operations actually enqueued on the crypto device.
+ */
+uint16_t
+_rte_cryptodev_enqueue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops);
+
+/**
+ * Dequeue a burst of processed crypto operations from a queue on the crypto
+ * device.
+ *
+ * @param	dev_id		The symmetric crypto device identifier
+ * @param	qp_id		The index of the queue pair from which to
+ *				retrieve processed packets.
+ * @param	ops		The address of an array of pointers to
+ *				*rte_crypto_op* structures that must be
+ *				large enough to store *nb_ops* pointers in it.
+ * @param	nb_ops		The maximum number of operations to dequeue.
+ *
+ * @return
+ *   - The number of operations actually dequeued.
+ */
+uint16_t
+_rte_cryptodev_dequeue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops);
+
+/**
+ * The outer headers of an offloaded tunnel, which mirrors `struct rte_flow_tunnel`.
+ *
+ * The IPv4 addresses are stored in the leading 4 bytes of the address fields.
+ */
+struct _rte_flow_tunnel {
+    enum rte_flow_item_type type; /**< The tunnel item type, e.g. RTE_FLOW_ITEM_TYPE_VXLAN. */
+    uint64_t tun_id;              /**< The tunnel identification. */
+    uint8_t src_addr[16];         /**< The outer source address. */
+    uint8_t dst_addr[16];         /**< The outer destination address. */
+    rte_be16_t tp_src;            /**< The outer source L4 port. */
+    rte_be16_t tp_dst;            /**< The outer destination L4 port. */
+    uint16_t tun_flags;           /**< The tunnel flags. */
+    uint8_t is_ipv6;              /**< The outer addresses are IPv6. */
+    uint8_t tos;                  /**< The outer TOS or traffic class. */
+    uint8_t ttl;                  /**< The outer TTL or hop limit. */
+    uint32_t label;               /**< The outer IPv6 flow label. */
+};
+
+/**
+ * The restore info of a partially offloaded packet, which mirrors `struct rte_flow_restore_info`.
+ */
+struct _rte_flow_restore_info {
+    uint64_t flags;                  /**< The RTE_FLOW_RESTORE_INFO_* flags. */
+    uint32_t group_id;               /**< The group of the missed flow rule. */
+    struct _rte_flow_tunnel tunnel;  /**< The tunnel of the packet. */
+};
+
+/**
+ * Allocate the PMD actions which decapsulate an offloaded tunnel,
+ * they must be placed at the start of the actions of the decap rule.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API.
+ */
+int
+_rte_flow_tunnel_decap_set(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                           struct rte_flow_action **actions, uint32_t *num_of_actions,
+                           struct rte_flow_error *error);
+
+/**
+ * Allocate the PMD items which match the packets of an offloaded tunnel,
+ * they must be placed at the start of the pattern of the tunnel rule.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API.
+ */
+int
+_rte_flow_tunnel_match(uint16_t port_id, struct _rte_flow_tunnel *tunnel,
+                       struct rte_flow_item **items, uint32_t *num_of_items,
+                       struct rte_flow_error *error);
+
+/**
+ * Retrieve the restore info of a packet, which missed the offloaded rules after a partial processing.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no tunnel offload API.
+ */
+int
+_rte_flow_get_restore_info(uint16_t port_id, struct rte_mbuf *m,
+                           struct _rte_flow_restore_info *info, struct rte_flow_error *error);
+
+/**
+ * Release the PMD actions allocated by _rte_flow_tunnel_decap_set().
+ */
+int
+_rte_flow_tunnel_action_decap_release(uint16_t port_id, struct rte_flow_action *actions,
+                                      uint32_t num_of_actions, struct rte_flow_error *error);
+
+/**
+ * Release the PMD items allocated by _rte_flow_tunnel_match().
+ */
+int
+_rte_flow_tunnel_item_release(uint16_t port_id, struct rte_flow_item *items,
+                              uint32_t num_of_items, struct rte_flow_error *error);
+
+/**
+ * The max number of the peers of a hairpin queue.
+ */
+#define _RTE_ETH_MAX_HAIRPIN_PEERS 32
+
+/**
+ * The hairpin capability of a port, which mirrors `struct rte_eth_hairpin_cap`.
+ */
+struct _rte_eth_hairpin_cap {
+    uint16_t max_nb_queues; /**< The max number of hairpin queues. */
+    uint16_t max_rx_2_tx;   /**< The max number of TX queues bound to a RX queue. */
+    uint16_t max_tx_2_rx;   /**< The max number of RX queues bound to a TX queue. */
+    uint16_t max_nb_desc;   /**< The max number of descriptors of a hairpin queue. */
+};
+
+/**
+ * The peer queue of a hairpin queue, which mirrors `struct rte_eth_hairpin_peer`.
+ */
+struct _rte_eth_hairpin_peer {
+    uint16_t port;  /**< The peer port. */
+    uint16_t queue; /**< The peer queue. */
+};
+
+/**
+ * The configuration of a hairpin queue, which mirrors `struct rte_eth_hairpin_conf`.
+ */
+struct _rte_eth_hairpin_conf {
+    uint16_t peer_count;   /**< The number of the peers. */
+    uint8_t tx_explicit;   /**< The TX flow rules are inserted explicitly by the application. */
+    uint8_t manual_bind;   /**< The queues are bound manually with _rte_eth_hairpin_bind(). */
+    struct _rte_eth_hairpin_peer peers[_RTE_ETH_MAX_HAIRPIN_PEERS]; /**< The peer queues. */
+};
+
+/**
+ * Retrieve the hairpin capability of a port.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue.
+ */
+int
+_rte_eth_dev_hairpin_capability_get(uint16_t port_id, struct _rte_eth_hairpin_cap *cap);
+
+/**
+ * Allocate and set up a hairpin receive queue, which forwards the packets to its peer TX queues.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue.
+ */
+int
+_rte_eth_rx_hairpin_queue_setup(uint16_t port_id, uint16_t rx_queue_id, uint16_t nb_rx_desc,
+                                const struct _rte_eth_hairpin_conf *conf);
+
+/**
+ * Allocate and set up a hairpin transmit queue, which sends the packets of its peer RX queues.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK or PMD has no hairpin queue.
+ */
+int
+_rte_eth_tx_hairpin_queue_setup(uint16_t port_id, uint16_t tx_queue_id, uint16_t nb_tx_desc,
+                                const struct _rte_eth_hairpin_conf *conf);
+
+/**
+ * Bind the hairpin TX queues of a port to the RX queues of its peer port, RTE_MAX_ETHPORTS for all the peers.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no hairpin binding.
+ */
+int
+_rte_eth_hairpin_bind(uint16_t tx_port, uint16_t rx_port);
+
+/**
+ * Unbind the hairpin TX queues of a port from the RX queues of its peer port, RTE_MAX_ETHPORTS for all the peers.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the DPDK has no hairpin binding.
+ */
+int
+_rte_eth_hairpin_unbind(uint16_t tx_port, uint16_t rx_port);
+
+/**
+ * Retrieve the peer ports of the hairpin queues of a port, the TX peers if direction is 1, or the RX peers.
+ *
+ * @return
+ *   the number of the peer ports on success, a negative errno value otherwise.
+ */
+int
+_rte_eth_hairpin_get_peer_ports(uint16_t port_id, uint16_t *peer_ports, size_t len, uint32_t direction);
+
+/**
+ * Run the traffic management and the pipelines of a softnic port, on the lcore which polls it.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the softnic PMD is not linked.
+ */
+int
+_rte_pmd_softnic_run(uint16_t port_id);
+
+/**
+ * Service the CLI connection of a softnic port, which executes the pipeline commands.
+ *
+ * @return
+ *   0 on success, a negative errno value otherwise, -ENOTSUP if the softnic PMD is not linked.
+ */
+int
+_rte_pmd_softnic_manage(uint16_t port_id);
+
+/**
+ * Function returning version string
+ *
+ * @return
+ *     string
+ */
+const char *
+_rte_version(void);
+
+/**
+ * Lookup an IP into the LPM table.
+ *
+ * @param lpm
+ *   LPM object handle
+ * @param ip
+ *   IP to be looked up in the LPM table
+ * @param next_hop
+ *   Next hop of the most specific rule found for IP (valid on lookup hit only)
+ * @return
+ *   -EINVAL for incorrect arguments, -ENOENT on lookup miss, 0 on lookup hit
+ */
+int
+_rte_lpm_lookup(struct rte_lpm *lpm, uint32_t ip, uint32_t *next_hop);
+
+/**
+ * Lookup multiple IP addresses in an LPM table.
+ *
+ * @param lpm
+ *   LPM object handle
+ * @param ips
+ *   Array of IPs to be looked up in the LPM table
+ * @param next_hops
+ *   Next hop of the most specific rule found for IP (valid on lookup hit only).
+ *   This is an array of four byte values. The most significant byte in each
+ *   value says whether the lookup was successful (bitmask
+ *   RTE_LPM_LOOKUP_SUCCESS is set). The three least significant bytes is the
+ *   actual next hop.
+ * @param n
+ *   Number of elements in ips (and next_hops) array to lookup.
+ * @return
+ *   -EINVAL for incorrect arguments, otherwise 0
+ */
+int
+_rte_lpm_lookup_bulk(const struct rte_lpm *lpm, const uint32_t *ips, uint32_t *next_hops, unsigned n);
+
+/**
+ * Check whether a driver has been registered on its bus.
+ *
+ * @param name
+ *   The name of the driver, e.g. `net_ixgbe`.
+ * @param pci
+ *   The driver is a PCI driver, otherwise it is looked up on the vdev bus.
+ * @return
+ *   1 if the driver is registered, 0 if not,
+ *   or -1 if it isn't a PCI or vdev driver and there are the other buses which drivers can't be listed.
+ */
+int
+_rte_driver_registered(const char *name, int pci);
+
+/**
+ * Test if trace is enabled.
+ *
+ * @return
+ *    1 if trace is enabled, 0 otherwise or if the trace library is unavailable.
+ */
+int
+_rte_trace_is_enabled(void);
+
+/**
+ * Enable/Disable a set of tracepoints based on globbing pattern.
+ *
+ * @return
+ *   - 1: Success and matched with at least one tracepoint.
+ *   - 0: Success but not matched with any pattern.
+ *   - (-ENOTSUP): The trace library is unavailable.
+ *   - <0: Failure to enable/disable the tracepoints.
+ */
+int
+_rte_trace_pattern(const char *pattern, int enable);
+
+/**
+ * Enable/Disable a set of tracepoints based on regular expression.
+ *
+ * @return
+ *   - 1: Success and matched with at least one tracepoint.
+ *   - 0: Success but not matched with any pattern.
+ *   - (-ENOTSUP): The trace library is unavailable.
+ *   - <0: Failure to enable/disable the tracepoints.
+ */
+int
+_rte_trace_regexp(const char *regex, int enable);
+
+/**
+ * Set the trace mode, 0 for overwrite and 1 for discard.
+ *
+ * @return
+ *   0 on success, or -ENOTSUP if the trace library is unavailable.
+ */
+int
+_rte_trace_mode_set(int mode);
+
+/**
+ * Get the trace mode, 0 for overwrite and 1 for discard.
+ *
+ * @return
+ *   The trace mode, or -ENOTSUP if the trace library is unavailable.
+ */
+int
+_rte_trace_mode_get(void);
+
+/**
+ * Save the trace buffers to the trace directory in CTF format.
+ *
+ * @return
+ *   0 on success, or a negative value on error.
+ */
+int
+_rte_trace_save(void);
+
+/**
+ * Dump the trace metadata to a file.
+ *
+ * @return
+ *   0 on success, or a negative value on error.
+ */
+int
+_rte_trace_dump(FILE *f);
+
+/**
+ * Emit the user-defined tracepoint `app.user.u64`, with a name and a value.
+ */
+void
+_rte_trace_user_u64(const char *name, uint64_t value);
+
+/**
+ * Emit the user-defined tracepoint `app.user.str`, with a name and a message.
+ */
+void
+_rte_trace_user_str(const char *name, const char *msg);
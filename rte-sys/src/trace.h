@@ -0,0 +1,24 @@
+#pragma once
+
+#include <rte_version.h>
+
+#if RTE_VERSION >= RTE_VERSION_NUM(20, 5, 0, 0)
+#define HAS_RTE_TRACE 1
+
+#include <rte_trace.h>
+#include <rte_trace_point.h>
+
+RTE_TRACE_POINT(
+    _rte_trace_point_user_u64,
+    RTE_TRACE_POINT_ARGS(const char *name, uint64_t value),
+    rte_trace_point_emit_string(name);
+    rte_trace_point_emit_u64(value);
+)
+
+RTE_TRACE_POINT(
+    _rte_trace_point_user_str,
+    RTE_TRACE_POINT_ARGS(const char *name, const char *msg),
+    rte_trace_point_emit_string(name);
+    rte_trace_point_emit_string(msg);
+)
+#endif
@@ -0,0 +1,15 @@
+#include <rte_version.h>
+
+#if RTE_VERSION >= RTE_VERSION_NUM(20, 5, 0, 0)
+#include <rte_trace_point_register.h>
+
+#include "trace.h"
+
+#if RTE_VERSION < RTE_VERSION_NUM(21, 5, 0, 0)
+RTE_TRACE_POINT_DEFINE(_rte_trace_point_user_u64);
+RTE_TRACE_POINT_DEFINE(_rte_trace_point_user_str);
+#endif
+
+RTE_TRACE_POINT_REGISTER(_rte_trace_point_user_u64, app.user.u64)
+RTE_TRACE_POINT_REGISTER(_rte_trace_point_user_str, app.user.str)
+#endif
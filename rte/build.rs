@@ -19,14 +19,8 @@ fn main() {
         .file("examples/kni/kni_core.c")
         .compile("libkni_core.a");
 
-    gen_cargo_config(
-        &rte_sdk_dir,
-        RTE_CORE_LIBS
-            .iter()
-            .chain(RTE_PMD_LIBS.iter())
-            .chain(RTE_DEPS_LIBS.iter()),
-    );
-
+    // the DPDK libraries are linked by `rte-sys`,
+    // linking the drivers again with `--whole-archive` would define their symbols twice
     if cfg!(target_os = "linux") {
         println!("cargo:rustc-link-search=native=/usr/lib/x86_64-linux-gnu");
     }
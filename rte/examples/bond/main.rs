@@ -10,17 +10,17 @@ use std::cell::RefCell;
 use std::env;
 use std::mem;
 use std::net;
-use std::ptr::NonNull;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use rte::arp::{ARP_HRD_ETHER, ARP_OP_REPLY, ARP_OP_REQUEST};
+use rte::arp::{ArpExt, ARP_HRD_ETHER, ARP_OP_REPLY_BE, ARP_OP_REQUEST_BE};
 use rte::bond::BondedDevice;
+use rte::byteorder::{be16, be32};
 use rte::ethdev::EthDevice;
-use rte::ether::{ETHER_TYPE_IPv4, ETHER_ADDR_LEN, ETHER_TYPE_ARP};
+use rte::ether::{EtherExt, ETHER_ADDR_LEN, ETHER_TYPE_ARP_BE, ETHER_TYPE_IPV4_BE};
+use rte::ip::Ipv4Ext;
 use rte::lcore::RTE_MAX_LCORE;
 use rte::mbuf::MBufPool;
-use rte::memory::AsMutRef;
 use rte::*;
 
 const EXIT_FAILURE: i32 = -1;
@@ -49,7 +49,7 @@ struct AppConfig {
     bond_ip: net::Ipv4Addr,
     bond_mac_addr: ether::EtherAddr,
     bonded_port_id: PortId,
-    pktmbuf_pool: mempool::MemoryPool,
+    pktmbuf_pool: Option<mempool::MemoryPool>,
     port_packets: [AtomicUsize; 4],
 }
 
@@ -82,7 +82,7 @@ impl AppConfig {
     }
 }
 
-fn slave_port_init(port_id: ethdev::PortId, port_conf: &ethdev::EthConf, pktmbuf_pool: &mut mempool::MemoryPool) {
+fn slave_port_init(port_id: ethdev::PortId, port_conf: &ethdev::EthConf, pktmbuf_pool: mempool::MemPoolRef) {
     info!("Setup port {}", port_id);
 
     let dev = port_id;
@@ -98,20 +98,11 @@ fn slave_port_init(port_id: ethdev::PortId, port_conf: &ethdev::EthConf, pktmbuf
     dev.tx_queue_setup(0, RTE_TX_DESC_DEFAULT, None)
         .expect(&format!("fail to setup device tx queue: port={}", port_id));
 
-    // Start device
-    dev.start().expect(&format!("fail to start device: port={}", port_id));
-
-    dev.promiscuous_enable();
-
     info!("Port {} MAC: {}", port_id, dev.mac_addr());
 }
 
-fn bond_port_init(
-    slave_count: u16,
-    port_conf: &ethdev::EthConf,
-    pktmbuf_pool: &mut mempool::MemoryPool,
-) -> ethdev::PortId {
-    let dev = bond::create("bond0", bond::BondMode::AdaptiveLB, 0).expect("Faled to create bond port");
+fn bond_port_init(slave_count: u16, port_conf: &ethdev::EthConf, pktmbuf_pool: mempool::MemPoolRef) -> ethdev::PortId {
+    let dev = bond::create("bond0", bond::BondMode::AdaptiveLB, Socket::current()).expect("Faled to create bond port");
 
     let bonded_port_id = dev;
 
@@ -133,33 +124,29 @@ fn bond_port_init(
         ));
     }
 
-    // Start device
-    dev.start()
-        .expect(&format!("fail to start device: port={}", bonded_port_id));
-
-    dev.promiscuous_enable();
-
     info!("Bonded port {} MAC: {}", bonded_port_id, dev.mac_addr());
 
     dev
 }
 
-fn strip_vlan_hdr(ether_hdr: *const ether::EtherHdr) -> (*const libc::c_void, u16) {
-    unsafe {
-        if (*ether_hdr).ether_type != ether::ETHER_TYPE_VLAN_BE {
-            (ether_hdr.offset(1) as *const libc::c_void, (*ether_hdr).ether_type)
-        } else {
-            let mut vlan_hdr = ether_hdr.offset(1) as *const ether::VlanHdr;
+fn strip_vlan_hdr(ether_hdr: &mbuf::HeaderView<ether::EtherHdr>) -> Option<(usize, be16)> {
+    let mut next_hdr = ether_hdr.next::<ether::VlanHdr>();
+    let mut next_proto = ether_hdr.ether_type();
+    let mut off = mem::size_of::<ether::EtherHdr>();
 
-            while (*vlan_hdr).eth_proto == ether::ETHER_TYPE_VLAN_BE {
-                vlan_hdr = vlan_hdr.offset(1);
-            }
+    while next_proto == ether::ETHER_TYPE_VLAN_BE {
+        let vlan_hdr = next_hdr?;
 
-            debug!("VLAN taged frame, offset: {}", vlan_hdr as usize - ether_hdr as usize);
+        next_proto = vlan_hdr.ether_type();
+        next_hdr = vlan_hdr.next();
+        off += mem::size_of::<ether::VlanHdr>();
+    }
 
-            (vlan_hdr.offset(1) as *const libc::c_void, (*vlan_hdr).eth_proto)
-        }
+    if off > mem::size_of::<ether::EtherHdr>() {
+        debug!("VLAN taged frame, offset: {}", off);
     }
+
+    Some((off, next_proto))
 }
 
 // Main thread that does the work, reading from INPUT_PORT and writing to OUTPUT_PORT
@@ -168,11 +155,11 @@ fn lcore_main(app_conf: Option<&AppConfig>) -> i32 {
 
     let app_conf = app_conf.unwrap();
     let dev = app_conf.bonded_port_id;
-    let mut pkts: [Option<mbuf::MBuf>; MAX_PKT_BURST] = unsafe { mem::zeroed() };
-    let bond_ip = u32::from(app_conf.bond_ip).to_be();
+    let mut pkts = mbuf::ArrayBurst::<MAX_PKT_BURST>::new();
+    let bond_ip = be32::from(app_conf.bond_ip);
 
     while app_conf.lcore_main_is_running.load(Ordering::Relaxed) {
-        let rx_cnt = dev.rx_burst(0, &mut pkts[..]);
+        let rx_cnt = pkts.rx(dev, 0);
 
         // If didn't receive any packets, wait and go to next iteration
         if rx_cnt == 0 {
@@ -186,68 +173,89 @@ fn lcore_main(app_conf: Option<&AppConfig>) -> i32 {
         app_conf.port_packets[0].fetch_add(rx_cnt, Ordering::Relaxed);
 
         // Search incoming data for ARP packets and prepare response
-        for pkt in pkts.into_iter().take(rx_cnt) {
-            if let Some(m) = pkt {
-                let mut p = m.mtod::<ether::EtherHdr>();
-                let ether_hdr = unsafe { p.as_mut() };
-                let (next_hdr, next_proto) = strip_vlan_hdr(ether_hdr);
-
-                match next_proto {
-                    ether::ETHER_TYPE_ARP_BE => {
-                        app_conf.port_packets[1].fetch_add(1, Ordering::Relaxed);
-
-                        if let Some(mut arp_hdr) = (next_hdr as *mut arp::ArpHdr).as_mut_ref() {
-                            if arp_hdr.arp_data.arp_tip == bond_ip {
-                                debug!(
-                                    "received ARP {:x} packet from {}",
-                                    arp_hdr.arp_op.to_le(),
-                                    ether::EtherAddr::from(arp_hdr.arp_data.arp_sha)
+        for mut m in pkts.drain() {
+            let (next_off, next_proto) = match m.header::<ether::EtherHdr>(0).and_then(|h| strip_vlan_hdr(&h)) {
+                Some(next) => next,
+                None => continue,
+            };
+
+            let reply = match next_proto {
+                ETHER_TYPE_ARP_BE => {
+                    app_conf.port_packets[1].fetch_add(1, Ordering::Relaxed);
+
+                    match m
+                        .header_mut::<ether::EtherHdr>(0)
+                        .and_then(|h| h.split_at::<arp::ArpHdr>(next_off))
+                    {
+                        Some((ether_hdr, mut arp_hdr)) if arp_hdr.tip() == bond_ip => {
+                            let arp_hdr = &mut *arp_hdr;
+
+                            debug!(
+                                "received ARP {:x} packet from {}",
+                                arp_hdr.op(),
+                                ether::EtherAddr::from(arp_hdr.arp_data.arp_sha.addr_bytes)
+                            );
+
+                            if arp_hdr.op() == ARP_OP_REQUEST_BE {
+                                arp_hdr.set_op(ARP_OP_REPLY_BE);
+
+                                ether::EtherAddr::copy(&ether_hdr.s_addr.addr_bytes, &mut ether_hdr.d_addr.addr_bytes);
+                                ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
+
+                                ether::EtherAddr::copy(
+                                    &arp_hdr.arp_data.arp_sha.addr_bytes,
+                                    &mut arp_hdr.arp_data.arp_tha.addr_bytes,
+                                );
+                                ether::EtherAddr::copy(
+                                    &app_conf.bond_mac_addr,
+                                    &mut arp_hdr.arp_data.arp_sha.addr_bytes,
                                 );
 
-                                if arp_hdr.arp_op == (ARP_OP_REQUEST as u16).to_be() {
-                                    arp_hdr.arp_op = (ARP_OP_REPLY as u16).to_be();
-
-                                    ether::EtherAddr::copy(
-                                        &ether_hdr.s_addr.addr_bytes,
-                                        &mut ether_hdr.d_addr.addr_bytes,
-                                    );
-                                    ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
-
-                                    ether::EtherAddr::copy(
-                                        &arp_hdr.arp_data.arp_sha.addr_bytes,
-                                        &mut arp_hdr.arp_data.arp_tha.addr_bytes,
-                                    );
-                                    ether::EtherAddr::copy(
-                                        &app_conf.bond_mac_addr,
-                                        &mut arp_hdr.arp_data.arp_sha.addr_bytes,
-                                    );
-
-                                    arp_hdr.arp_data.arp_tip = arp_hdr.arp_data.arp_sip;
-                                    arp_hdr.arp_data.arp_sip = bond_ip;
-
-                                    let _ = dev.tx_burst(0, &mut [m]);
-                                }
+                                let sip = arp_hdr.sip();
+
+                                arp_hdr.set_tip(sip);
+                                arp_hdr.set_sip(bond_ip);
+
+                                true
+                            } else {
+                                false
                             }
                         }
+                        _ => false,
                     }
-                    ether::ETHER_TYPE_IPV4_BE => {
-                        app_conf.port_packets[2].fetch_add(1, Ordering::Relaxed);
+                }
+                ETHER_TYPE_IPV4_BE => {
+                    app_conf.port_packets[2].fetch_add(1, Ordering::Relaxed);
 
-                        if let Some(mut ipv4_hdr) = (next_hdr as *mut ip::Ipv4Hdr).as_mut_ref() {
-                            if ipv4_hdr.dst_addr == bond_ip {
-                                debug!("received IP packet from {}", net::Ipv4Addr::from(ipv4_hdr.src_addr));
+                    match m
+                        .header_mut::<ether::EtherHdr>(0)
+                        .and_then(|h| h.split_at::<ip::Ipv4Hdr>(next_off))
+                    {
+                        Some((ether_hdr, mut ipv4_hdr)) if ipv4_hdr.dst() == bond_ip => {
+                            debug!("received IP packet from {}", net::Ipv4Addr::from(ipv4_hdr.src()));
 
-                                ether::EtherAddr::copy(&ether_hdr.s_addr.addr_bytes, &mut ether_hdr.d_addr.addr_bytes);
-                                ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
+                            ether::EtherAddr::copy(&ether_hdr.s_addr.addr_bytes, &mut ether_hdr.d_addr.addr_bytes);
+                            ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
 
-                                ipv4_hdr.dst_addr = ipv4_hdr.src_addr;
-                                ipv4_hdr.src_addr = bond_ip;
+                            let src = ipv4_hdr.src();
 
-                                let _ = dev.tx_burst(0, &mut [m]);
-                            }
+                            ipv4_hdr.set_dst(src);
+                            ipv4_hdr.set_src(bond_ip);
+
+                            true
                         }
+                        _ => false,
                     }
-                    _ => {}
+                }
+                _ => false,
+            };
+
+            if reply {
+                let mut pkts = [m];
+
+                if dev.tx_burst(0, &mut pkts) == 1 {
+                    // the sent packet will be freed by the driver
+                    mem::forget(pkts);
                 }
             }
         }
@@ -270,38 +278,36 @@ impl CmdActionResult {
 
         match self.ip.to_ipaddr() {
             net::IpAddr::V4(ip) => {
-                let mut m = app_conf.pktmbuf_pool.alloc().unwrap();
+                let mut m = app_conf.pktmbuf_pool.as_mut().unwrap().alloc().unwrap();
 
-                let pkt_size = mem::size_of::<ether::EtherHdr>() + mem::size_of::<arp::ArpHdr>();
+                let mut ether_hdr = ether::EtherHdr::default();
 
-                m.data_len = pkt_size as u16;
-                m.pkt_len = pkt_size as u32;
+                ether_hdr.set_ether_type(ETHER_TYPE_ARP_BE);
 
-                let mut p = m.mtod::<ether::EtherHdr>();
-                {
-                    let ether_hdr = unsafe { p.as_mut() };
-                    ether_hdr.ether_type = (ETHER_TYPE_ARP as u16).to_be();
+                ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
+                ether::EtherAddr::copy(&ether::EtherAddr::broadcast(), &mut ether_hdr.d_addr.addr_bytes);
 
-                    ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut ether_hdr.s_addr.addr_bytes);
-                    ether::EtherAddr::copy(&ether::EtherAddr::broadcast(), &mut ether_hdr.d_addr.addr_bytes);
-                }
+                m.push_tail(&ether_hdr).unwrap();
 
-                let mut p = unsafe { NonNull::new_unchecked(p.as_ptr().add(1) as *mut arp::ArpHdr) };
-                let arp_hdr = unsafe { p.as_mut() };
+                let arp_hdr = m.push_tail(&arp::ArpHdr::default()).unwrap();
 
-                arp_hdr.arp_hrd = (ARP_HRD_ETHER as u16).to_be();
-                arp_hdr.arp_pro = (ETHER_TYPE_IPv4 as u16).to_be();
+                arp_hdr.arp_hrd = be16::new(ARP_HRD_ETHER as u16).to_raw();
+                arp_hdr.arp_pro = ETHER_TYPE_IPV4_BE.to_raw();
                 arp_hdr.arp_hln = ETHER_ADDR_LEN as u8;
                 arp_hdr.arp_pln = mem::size_of::<u32>() as u8;
-                arp_hdr.arp_op = (ARP_OP_REQUEST as u16).to_be();
+                arp_hdr.set_op(ARP_OP_REQUEST_BE);
 
                 ether::EtherAddr::copy(&app_conf.bond_mac_addr, &mut arp_hdr.arp_data.arp_sha.addr_bytes);
                 ether::EtherAddr::copy(&ether::EtherAddr::zeroed(), &mut arp_hdr.arp_data.arp_tha.addr_bytes);
 
-                arp_hdr.arp_data.arp_sip = u32::from(app_conf.bond_ip).to_be();
-                arp_hdr.arp_data.arp_tip = u32::from(ip).to_be();
+                arp_hdr.set_sip(be32::from(app_conf.bond_ip));
+                arp_hdr.set_tip(be32::from(ip));
+
+                let mut pkts = [m];
+
+                if app_conf.bonded_port_id.tx_burst(0, &mut pkts) == 1 {
+                    mem::forget(pkts);
 
-                if app_conf.bonded_port_id.tx_burst(0, &mut [m]) == 1 {
                     debug!("send ARP request to {}", ip);
                 }
             }
@@ -465,7 +471,7 @@ fn main() {
 
     devargs::dump(&*stdout);
 
-    let nb_ports = ethdev::count();
+    let nb_ports = ethdev::count_avail();
 
     if nb_ports == 0 {
         eal::exit(EXIT_FAILURE, "Give at least one port\n");
@@ -476,13 +482,13 @@ fn main() {
     }
 
     // create the mbuf pool
-    let mut pktmbuf_pool = mbuf::pool_create(
+    let pktmbuf_pool = mbuf::pool_create(
         "mbuf_pool",
         NB_MBUF,
         MEMPOOL_CACHE_SZ,
         0,
         mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
-        rte::socket_id() as i32,
+        rte::socket_id(),
     )
     .expect("fail to initial mbuf pool");
 
@@ -499,10 +505,19 @@ fn main() {
 
     // initialize all ports
     for portid in 0..nb_ports {
-        slave_port_init(portid, &port_conf, &mut pktmbuf_pool);
+        slave_port_init(portid, &port_conf, pktmbuf_pool.to_ref());
     }
 
-    let bonded_dev = bond_port_init(nb_ports, &port_conf, &mut pktmbuf_pool);
+    let bonded_dev = bond_port_init(nb_ports, &port_conf, pktmbuf_pool.to_ref());
+
+    // start the slaves before the bonded device
+    let ports: Vec<ethdev::PortId> = (0..nb_ports).chain(Some(bonded_dev)).collect();
+
+    ethdev::start_all(&ports).expect("fail to start devices");
+
+    for dev in &ports {
+        dev.promiscuous_enable();
+    }
 
     // check state of lcores
     lcore::foreach_slave(|lcore_id| {
@@ -524,7 +539,7 @@ fn main() {
         bonded_port_id: bonded_dev.portid(),
         lcore_main_is_running: AtomicBool::new(true),
         lcore_main_core_id: slave_core_id,
-        pktmbuf_pool,
+        pktmbuf_pool: Some(pktmbuf_pool),
         ..AppConfig::default()
     };
 
@@ -1,7 +1,7 @@
 use std::os::raw::c_void;
 
 use rte::cmdline::*;
-use rte::ethdev::{EthDevice, EthDeviceInfo};
+use rte::ethdev::{EthDevice, EthDeviceInfo, FcMode, FlowCtrlConf, RxOffload, TxOffload};
 use rte::{self, *};
 
 use ethtool::*;
@@ -64,94 +64,300 @@ impl CmdIntParams {
         self.port as ethdev::PortId
     }
 
-    fn open(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) {
+    fn valid_dev(&self) -> Result<ethdev::PortId> {
+        let dev = self.dev();
+
+        if dev.is_valid() {
+            Ok(dev)
+        } else {
+            Err(ErrorKind::CmdLineError(format!("port {} is invalid", self.port)).into())
+        }
+    }
+
+    fn open(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
         debug!("execute `{}` command for port {}", self.cmd, self.port);
 
-        let res = app_cfg.unwrap().lock_port(self.dev(), |app_port, dev| {
-            dev.stop();
+        let msg = app_cfg
+            .unwrap()
+            .lock_port(self.dev(), |app_port, dev| {
+                dev.stop();
 
-            if let Err(err) = dev.start() {
-                Err(format!("Error: failed to start port {}, {}", self.port, err))
-            } else {
-                app_port.port_active = true;
+                if let Err(err) = dev.start() {
+                    Err(format!("failed to start port {}, {}", self.port, err))
+                } else {
+                    app_port.port_active = true;
 
-                Ok(format!("port {} started", self.port))
-            }
-        });
+                    Ok(format!("port {} started", self.port))
+                }
+            })
+            .map_err(ErrorKind::CmdLineError)?;
 
-        cl.println(res.unwrap_or_else(|err| format!("Error: {}", err))).unwrap();
+        cl.println(msg)?;
+
+        Ok(())
     }
 
-    fn stop(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) {
+    fn stop(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
         debug!("execute `{}` command for port {}", self.cmd, self.port);
 
-        let res = app_cfg.unwrap().lock_port(self.dev(), |app_port, dev| {
-            if !dev.is_up() {
-                Err(format!("Port {} already stopped", self.port))
-            } else {
-                dev.stop();
+        let msg = app_cfg
+            .unwrap()
+            .lock_port(self.dev(), |app_port, dev| {
+                if !dev.is_up() {
+                    Err(format!("Port {} already stopped", self.port))
+                } else {
+                    dev.stop();
 
-                app_port.port_active = false;
+                    app_port.port_active = false;
 
-                Ok(format!("port {} stopped", self.port))
-            }
-        });
+                    Ok(format!("port {} stopped", self.port))
+                }
+            })
+            .map_err(ErrorKind::CmdLineError)?;
+
+        cl.println(msg)?;
 
-        cl.println(res.unwrap_or_else(|err| format!("Error: {}", err))).unwrap();
+        Ok(())
     }
 
-    fn rxmode(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn rxmode(&mut self, _cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute `{}` command for port {}", self.cmd, self.port);
 
-        let dev = self.dev();
-
-        if !dev.is_valid() {
-            cl.println(format!("Error: port {} is invalid", self.port)).unwrap();
-        } else {
-            // // Set VF vf_rx_mode, VF unsupport status is discard
-            // for vf in 0..dev.info().max_vfs {
-            //     if let Err(err) = dev.set_vf_rxmode(vf, ethdev::ETH_VMDQ_ACCEPT_UNTAG, false) {
-            //         cl.println(format!(
-            //             "Error: failed to set VF rx mode for port {}, {}",
-            //             self.port, err
-            //         )).unwrap();
-            //     }
-            // }
-
-            // Enable Rx vlan filter, VF unspport status is discard
-            if let Err(err) = dev.set_vlan_offload(ethdev::EthVlanOffloadMode::ETH_VLAN_FILTER_MASK) {
-                cl.println(format!(
-                    "Error: failed to set VLAN offload mode for port {}, {}",
+        let dev = self.valid_dev()?;
+
+        // // Set VF vf_rx_mode, VF unsupport status is discard
+        // for vf in 0..dev.info().max_vfs {
+        //     if let Err(err) = dev.set_vf_rxmode(vf, ethdev::ETH_VMDQ_ACCEPT_UNTAG, false) {
+        //         cl.println(format!(
+        //             "Error: failed to set VF rx mode for port {}, {}",
+        //             self.port, err
+        //         )).unwrap();
+        //     }
+        // }
+
+        // Enable Rx vlan filter, VF unspport status is discard
+        dev.set_vlan_offload(ethdev::EthVlanOffloadMode::ETH_VLAN_FILTER_MASK)
+            .map_err(|err| {
+                ErrorKind::CmdLineError(format!(
+                    "failed to set VLAN offload mode for port {}, {}",
                     self.port, err
                 ))
-                .unwrap();
-            }
-        }
+            })?;
+
+        Ok(())
     }
 
-    fn portstats(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn portstats(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute `{}` command for port {}", self.cmd, self.port);
 
-        let dev = self.dev();
+        let stats = self
+            .valid_dev()?
+            .stats()
+            .map_err(|err| ErrorKind::CmdLineError(format!("port {} fail to fetch statistics, {}", self.port, err)))?;
 
-        cl.println(if !dev.is_valid() {
-            format!("Error: port {} is invalid", self.port)
-        } else {
-            match dev.stats() {
-                Ok(stats) => format!(
-                    "Port {} stats\n   In: {} ({} bytes)\n  Out: {} ({} bytes)\n  \
-                     Err: {}",
-                    self.port,
-                    stats.ipackets,
-                    stats.ibytes,
-                    stats.opackets,
-                    stats.obytes,
-                    stats.ierrors + stats.oerrors
-                ),
-                Err(err) => format!("Error: port {} fail to fetch statistics, {}", self.port, err),
-            }
-        })
-        .unwrap();
+        cl.println(format!(
+            "Port {} stats\n   In: {} ({} bytes)\n  Out: {} ({} bytes)\n  \
+             Err: {}",
+            self.port,
+            stats.ipackets,
+            stats.ibytes,
+            stats.opackets,
+            stats.obytes,
+            stats.ierrors + stats.oerrors
+        ))?;
+
+        Ok(())
+    }
+
+    fn module(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
+        debug!("execute `{}` command for port {}", self.cmd, self.port);
+
+        let module = self.valid_dev()?.module_eeprom().map_err(|err| {
+            ErrorKind::CmdLineError(format!("port {} fail to read module EEPROM, {}", self.port, err))
+        })?;
+
+        cl.println(format!("Port {} module\n{}", self.port, module))?;
+
+        Ok(())
+    }
+}
+
+impl CmdIntParams {
+    fn pause_get(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
+        debug!("execute get `{}` command for port {}", self.cmd, self.port);
+
+        let conf = self
+            .valid_dev()?
+            .flow_ctrl()
+            .map_err(|err| ErrorKind::CmdLineError(format!("port {} fail to get flow control, {}", self.port, err)))?;
+        let mode = conf.mode();
+        let on_off = |b: bool| if b { "on" } else { "off" };
+
+        cl.println(format!(
+            "Port {} pause parameters:\n  Autonegotiate: {}\n  RX: {}\n  TX: {}",
+            self.port,
+            on_off(conf.autoneg != 0),
+            on_off(mode.rx_pause()),
+            on_off(mode.tx_pause())
+        ))?;
+
+        Ok(())
+    }
+
+    fn ring_get(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
+        debug!("execute get `{}` command for port {}", self.cmd, self.port);
+
+        let (nb_rx_desc, nb_tx_desc) = app_cfg
+            .unwrap()
+            .lock_port(self.dev(), |app_port, _| Ok((app_port.nb_rx_desc, app_port.nb_tx_desc)))
+            .map_err(ErrorKind::CmdLineError)?;
+        let info = self.dev().info();
+
+        cl.println(format!(
+            "Port {} ring parameters:\n  Pre-set maximums:\n    RX: {}\n    TX: {}\n  \
+             Current hardware settings:\n    RX: {}\n    TX: {}",
+            self.port, info.rx_desc_lim.nb_max, info.tx_desc_lim.nb_max, nb_rx_desc, nb_tx_desc
+        ))?;
+
+        Ok(())
+    }
+
+    fn features_get(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
+        debug!("execute get `{}` command for port {}", self.cmd, self.port);
+
+        let dev = self.valid_dev()?;
+        let info = dev.info();
+        let (rx_offloads, tx_offloads) = (dev.rx_offloads(), dev.tx_offloads());
+
+        cl.println(format!("Port {} features:", self.port))?;
+
+        for name in RxOffload::from_bits_truncate(info.rx_offload_capa).names() {
+            let on = RxOffload::from_name(name).is_some_and(|offload| rx_offloads.contains(offload));
+
+            cl.println(format!(
+                "  rx-{}: {}",
+                feature_name(name),
+                if on { "on" } else { "off" }
+            ))?;
+        }
+
+        for name in TxOffload::from_bits_truncate(info.tx_offload_capa).names() {
+            let on = TxOffload::from_name(name).is_some_and(|offload| tx_offloads.contains(offload));
+
+            cl.println(format!(
+                "  tx-{}: {}",
+                feature_name(name),
+                if on { "on" } else { "off" }
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Format the offload name like Linux ethtool, eg. `VLAN_STRIP` to `vlan-strip`.
+fn feature_name(name: &str) -> String {
+    name.to_lowercase().replace('_', "-")
+}
+
+struct CmdPauseParams {
+    cmd: FixedStr,
+    port: u16,
+    mode: FixedStr,
+}
+
+impl CmdPauseParams {
+    fn set(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
+        debug!("execute set `{}` command for port {}", self.cmd, self.port);
+
+        let dev = self.port as ethdev::PortId;
+        let mode = match self.mode.to_str() {
+            "all" => FcMode::Full,
+            "rx" => FcMode::RxPause,
+            "tx" => FcMode::TxPause,
+            _ => FcMode::None,
+        };
+
+        dev.flow_ctrl()
+            .and_then(|conf| dev.set_flow_ctrl(&conf.with_mode(mode)))
+            .map_err(|err| ErrorKind::CmdLineError(format!("port {} fail to set flow control, {}", self.port, err)))?;
+
+        cl.println(format!("Port {} pause was changed to {}", self.port, self.mode))?;
+
+        Ok(())
+    }
+}
+
+struct CmdRingSizeParams {
+    cmd: FixedStr,
+    port: u16,
+    rx_desc: u16,
+    tx_desc: u16,
+}
+
+impl CmdRingSizeParams {
+    fn set(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
+        debug!("execute set `{}` command for port {}", self.cmd, self.port);
+
+        let (rx_desc, tx_desc) = (self.rx_desc, self.tx_desc);
+        let (nb_rx_desc, nb_tx_desc) = app_cfg
+            .unwrap()
+            .lock_port(self.port as ethdev::PortId, |app_port, dev| {
+                app_port.reconfigure(dev, rx_desc, tx_desc, dev.rx_offloads(), dev.tx_offloads())?;
+
+                Ok((app_port.nb_rx_desc, app_port.nb_tx_desc))
+            })
+            .map_err(ErrorKind::CmdLineError)?;
+
+        cl.println(format!(
+            "Port {} ring was changed to RX {} TX {}",
+            self.port, nb_rx_desc, nb_tx_desc
+        ))?;
+
+        Ok(())
+    }
+}
+
+struct CmdFeatureParams {
+    cmd: FixedStr,
+    port: u16,
+    name: FixedStr,
+    state: FixedStr,
+}
+
+impl CmdFeatureParams {
+    fn set(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
+        debug!("execute set `{}` command for port {}", self.cmd, self.port);
+
+        let name = self.name.to_str();
+        let on = self.state.to_str() == "on";
+
+        app_cfg
+            .unwrap()
+            .lock_port(self.port as ethdev::PortId, |app_port, dev| {
+                let (mut rx_offloads, mut tx_offloads) = (dev.rx_offloads(), dev.tx_offloads());
+
+                if let Some(feature) = name.strip_prefix("rx-") {
+                    let offload = RxOffload::from_name(feature).ok_or_else(|| format!("unknown feature {}", name))?;
+
+                    rx_offloads.set(offload, on);
+                } else if let Some(feature) = name.strip_prefix("tx-") {
+                    let offload = TxOffload::from_name(feature).ok_or_else(|| format!("unknown feature {}", name))?;
+
+                    tx_offloads.set(offload, on);
+                } else {
+                    return Err(format!("unknown feature {}, expect rx-* or tx-*", name));
+                }
+
+                let (nb_rx_desc, nb_tx_desc) = (app_port.nb_rx_desc, app_port.nb_tx_desc);
+
+                app_port.reconfigure(dev, nb_rx_desc, nb_tx_desc, rx_offloads, tx_offloads)
+            })
+            .map_err(ErrorKind::CmdLineError)?;
+
+        cl.println(format!("Port {} feature {} was turned {}", self.port, name, self.state))?;
+
+        Ok(())
     }
 }
 
@@ -177,30 +383,30 @@ impl CmdIntMtuParams {
         }
     }
 
-    fn mtu_get(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn mtu_get(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute get `{}` command for port {}", self.cmd, self.port);
 
         let dev = self.dev();
 
-        cl.println(if !dev.is_valid() {
-            format!("Error: port {} is invalid", self.port)
-        } else {
-            format!("Port {} MTU: {}", self.port, dev.mtu().unwrap())
-        })
-        .unwrap();
+        if !dev.is_valid() {
+            return Err(ErrorKind::CmdLineError(format!("port {} is invalid", self.port)).into());
+        }
+
+        cl.println(format!("Port {} MTU: {}", self.port, dev.mtu()?))?;
+
+        Ok(())
     }
 
-    fn mtu_set(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn mtu_set(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute set `{}` command for port {}", self.cmd, self.port);
 
-        let dev = self.dev();
+        self.dev()
+            .set_mtu(self.mtu)
+            .map_err(|err| ErrorKind::CmdLineError(format!("Fail to change MTU of port {}, {}", self.port, err)))?;
 
-        cl.println(if let Err(err) = dev.set_mtu(self.mtu) {
-            format!("Error: Fail to change mac address of port {}, {}", self.port, err)
-        } else {
-            format!("Port {} MTU was changed to {}", self.port, self.mtu)
-        })
-        .unwrap();
+        cl.println(format!("Port {} MTU was changed to {}", self.port, self.mtu))?;
+
+        Ok(())
     }
 }
 
@@ -226,33 +432,39 @@ impl CmdIntMacParams {
         }
     }
 
-    fn get(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn get(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute get `{}` command for port {}", self.cmd, self.port);
 
         let dev = self.dev();
 
-        cl.println(if !dev.is_valid() {
-            format!("Error: port {} is invalid", self.port)
-        } else {
-            format!("Port {} MAC Address: {}", self.port, dev.mac_addr())
-        })
-        .unwrap();
+        if !dev.is_valid() {
+            return Err(ErrorKind::CmdLineError(format!("port {} is invalid", self.port)).into());
+        }
+
+        cl.println(format!("Port {} MAC Address: {}", self.port, dev.mac_addr()))?;
+
+        Ok(())
     }
 
-    fn set(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) {
+    fn set(&mut self, cl: &CmdLine, app_cfg: Option<&AppConfig>) -> Result<()> {
         debug!("execute set `{}` command for port {}", self.cmd, self.port);
 
-        let res = app_cfg.unwrap().lock_port(self.dev(), |app_port, dev| {
-            if let Err(err) = dev.set_mac_addr(&self.mac) {
-                Err(format!("Fail to change mac address of port {}, {}", self.port, err))
-            } else {
-                app_port.port_dirty = true;
+        let msg = app_cfg
+            .unwrap()
+            .lock_port(self.dev(), |app_port, dev| {
+                if let Err(err) = dev.set_mac_addr(&self.mac) {
+                    Err(format!("Fail to change mac address of port {}, {}", self.port, err))
+                } else {
+                    app_port.port_dirty = true;
 
-                Ok(format!("Port {} mac address was changed to {}", self.port, self.mac))
-            }
-        });
+                    Ok(format!("Port {} mac address was changed to {}", self.port, self.mac))
+                }
+            })
+            .map_err(ErrorKind::CmdLineError)?;
+
+        cl.println(msg)?;
 
-        cl.println(res.unwrap_or_else(|err| format!("Error: {}", err))).unwrap();
+        Ok(())
     }
 
     fn validate(&mut self, cl: &CmdLine, _: Option<&c_void>) {
@@ -279,33 +491,66 @@ impl CmdVlanParams {
         self.port as ethdev::PortId
     }
 
-    fn change(&mut self, cl: &CmdLine, _: Option<&c_void>) {
+    fn change(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
         debug!("execute `{}` command for port {}", self.cmd, self.port);
 
         let dev = self.dev();
 
-        cl.println(if !dev.is_valid() {
-            format!("Error: port {} is invalid", self.port)
-        } else {
-            match self.mode.to_str() {
-                "add" => match dev.set_vlan_filter(self.vlan_id, true) {
-                    Ok(_) => format!("VLAN vid {} added to port {}", self.vlan_id, self.port),
-                    Err(err) => format!(
-                        "Error: fail to add VLAN vid {} to port {}, {}",
-                        self.vlan_id, self.port, err
-                    ),
-                },
-                "del" => match dev.set_vlan_filter(self.vlan_id, false) {
-                    Ok(_) => format!("VLAN vid {} removed from port {}", self.vlan_id, self.port),
-                    Err(err) => format!(
-                        "Error: fail to remove VLAN vid {} to port {}, {}",
+        if !dev.is_valid() {
+            return Err(ErrorKind::CmdLineError(format!("port {} is invalid", self.port)).into());
+        }
+
+        let msg = match self.mode.to_str() {
+            "add" => dev
+                .set_vlan_filter(self.vlan_id, true)
+                .map(|_| format!("VLAN vid {} added to port {}", self.vlan_id, self.port))
+                .map_err(|err| format!("fail to add VLAN vid {} to port {}, {}", self.vlan_id, self.port, err)),
+            "del" => dev
+                .set_vlan_filter(self.vlan_id, false)
+                .map(|_| format!("VLAN vid {} removed from port {}", self.vlan_id, self.port))
+                .map_err(|err| {
+                    format!(
+                        "fail to remove VLAN vid {} to port {}, {}",
                         self.vlan_id, self.port, err
-                    ),
-                },
-                mode @ _ => format!("Error: Bad mode {}", mode),
-            }
-        })
-        .unwrap();
+                    )
+                }),
+            mode => Err(format!("Bad mode {}", mode)),
+        }
+        .map_err(ErrorKind::CmdLineError)?;
+
+        cl.println(msg)?;
+
+        Ok(())
+    }
+}
+
+struct CmdRingParams {
+    cmd: FixedStr,
+    port: u16,
+    dir: FixedStr,
+    queue: u16,
+}
+
+impl CmdRingParams {
+    fn dump(&mut self, cl: &CmdLine, _: Option<&c_void>) -> Result<()> {
+        debug!("execute `{}` command for port {}", self.cmd, self.port);
+
+        let queue = match self.dir.to_str() {
+            "rx" => ethdev::QueueHandle::Rx(self.port, self.queue),
+            _ => ethdev::QueueHandle::Tx(self.port, self.queue),
+        };
+
+        if !self.port.is_valid() {
+            return Err(ErrorKind::CmdLineError(format!("port {} is invalid", self.port)).into());
+        }
+
+        let dump = queue
+            .dump_descriptors(..)
+            .map_err(|err| ErrorKind::CmdLineError(format!("fail to dump descriptors of {}, {}", queue, err)))?;
+
+        cl.println(dump)?;
+
+        Ok(())
     }
 }
 
@@ -320,6 +565,7 @@ pub fn main(app_cfg: &mut AppConfig) {
     let pcmd_stop_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "stop");
     let pcmd_rxmode_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "rxmode");
     let pcmd_portstats_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "portstats");
+    let pcmd_module_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "module");
 
     let pcmd_int_token_port = TOKEN_NUM_INITIALIZER!(CmdIntParams, port, u16);
 
@@ -342,6 +588,32 @@ pub fn main(app_cfg: &mut AppConfig) {
     let pcmd_vlan_token_mode = TOKEN_STRING_INITIALIZER!(CmdVlanParams, mode, "add#del");
     let pcmd_vlan_token_vlan_id = TOKEN_NUM_INITIALIZER!(CmdVlanParams, vlan_id, u16);
 
+    // Ethernet flow control commands
+    let pcmd_pause_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "pause");
+    let pcmd_pause_set_token_cmd = TOKEN_STRING_INITIALIZER!(CmdPauseParams, cmd, "pause");
+    let pcmd_pause_token_port = TOKEN_NUM_INITIALIZER!(CmdPauseParams, port, u16);
+    let pcmd_pause_token_mode = TOKEN_STRING_INITIALIZER!(CmdPauseParams, mode, "all#rx#tx#none");
+
+    // Ring size commands
+    let pcmd_ring_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "ring");
+    let pcmd_ring_set_token_cmd = TOKEN_STRING_INITIALIZER!(CmdRingSizeParams, cmd, "ring");
+    let pcmd_ring_token_port = TOKEN_NUM_INITIALIZER!(CmdRingSizeParams, port, u16);
+    let pcmd_ring_token_rx_desc = TOKEN_NUM_INITIALIZER!(CmdRingSizeParams, rx_desc, u16);
+    let pcmd_ring_token_tx_desc = TOKEN_NUM_INITIALIZER!(CmdRingSizeParams, tx_desc, u16);
+
+    // Offload feature commands
+    let pcmd_features_token_cmd = TOKEN_STRING_INITIALIZER!(CmdIntParams, cmd, "features");
+    let pcmd_features_set_token_cmd = TOKEN_STRING_INITIALIZER!(CmdFeatureParams, cmd, "features");
+    let pcmd_features_token_port = TOKEN_NUM_INITIALIZER!(CmdFeatureParams, port, u16);
+    let pcmd_features_token_name = TOKEN_STRING_INITIALIZER!(CmdFeatureParams, name);
+    let pcmd_features_token_state = TOKEN_STRING_INITIALIZER!(CmdFeatureParams, state, "on#off");
+
+    // Descriptor ring commands
+    let pcmd_ringdump_token_cmd = TOKEN_STRING_INITIALIZER!(CmdRingParams, cmd, "ringdump");
+    let pcmd_ringdump_token_port = TOKEN_NUM_INITIALIZER!(CmdRingParams, port, u16);
+    let pcmd_ringdump_token_dir = TOKEN_STRING_INITIALIZER!(CmdRingParams, dir, "rx#tx");
+    let pcmd_ringdump_token_queue = TOKEN_NUM_INITIALIZER!(CmdRingParams, queue, u16);
+
     let pcmd_quit = inst(
         CmdGetParams::quit,
         None,
@@ -363,34 +635,41 @@ pub fn main(app_cfg: &mut AppConfig) {
         &[&pcmd_link_token_cmd],
     );
 
-    let pcmd_open = inst(
+    let pcmd_open = try_inst(
         CmdIntParams::open,
         Some(app_cfg),
         "open <port_id>\n     Open port",
         &[&pcmd_open_token_cmd, &pcmd_int_token_port],
     );
 
-    let pcmd_stop = inst(
+    let pcmd_stop = try_inst(
         CmdIntParams::stop,
         Some(app_cfg),
         "stop <port_id>\n     Stop port",
         &[&pcmd_stop_token_cmd, &pcmd_int_token_port],
     );
 
-    let pcmd_rxmode = inst(
+    let pcmd_rxmode = try_inst(
         CmdIntParams::rxmode,
         None,
         "rxmode <port_id>\n     Toggle port Rx mode",
         &[&pcmd_rxmode_token_cmd, &pcmd_int_token_port],
     );
 
-    let pcmd_portstats = inst(
+    let pcmd_portstats = try_inst(
         CmdIntParams::portstats,
         None,
         "portstats <port_id>\n     Print port eth statistics",
         &[&pcmd_portstats_token_cmd, &pcmd_int_token_port],
     );
 
+    let pcmd_module = try_inst(
+        CmdIntParams::module,
+        None,
+        "module <port_id>\n     Print plugin module info and diagnostics",
+        &[&pcmd_module_token_cmd, &pcmd_int_token_port],
+    );
+
     let pcmd_mtu_list = inst(
         CmdIntMtuParams::mtu_list,
         Some(app_cfg),
@@ -398,14 +677,14 @@ pub fn main(app_cfg: &mut AppConfig) {
         &[&pcmd_mtu_token_cmd],
     );
 
-    let pcmd_mtu_get = inst(
+    let pcmd_mtu_get = try_inst(
         CmdIntMtuParams::mtu_get,
         None,
         "mtu <port_id>\n     Show MTU",
         &[&pcmd_mtu_token_cmd, &pcmd_intmtu_token_port],
     );
 
-    let pcmd_mtu_set = inst(
+    let pcmd_mtu_set = try_inst(
         CmdIntMtuParams::mtu_set,
         None,
         "mtu <port_id> <mtu_value>\n     Change MTU",
@@ -419,14 +698,14 @@ pub fn main(app_cfg: &mut AppConfig) {
         &[&pcmd_macaddr_token_cmd],
     );
 
-    let pcmd_macaddr_get = inst(
+    let pcmd_macaddr_get = try_inst(
         CmdIntMacParams::get,
         None,
         "macaddr <port_id>\n     Get MAC address",
         &[&pcmd_macaddr_token_cmd, &pcmd_intmac_token_port],
     );
 
-    let pcmd_macaddr_set = inst(
+    let pcmd_macaddr_set = try_inst(
         CmdIntMacParams::set,
         Some(app_cfg),
         "macaddr <port_id> <mac_addr>\n     Set MAC address",
@@ -441,7 +720,7 @@ pub fn main(app_cfg: &mut AppConfig) {
         &[&pcmd_validate_token_cmd, &pcmd_intmac_token_mac],
     );
 
-    let pcmd_vlan = inst(
+    let pcmd_vlan = try_inst(
         CmdVlanParams::change,
         None,
         "vlan <port_id> <add|del> <vlan_id>\n     Add/remove VLAN id",
@@ -453,6 +732,74 @@ pub fn main(app_cfg: &mut AppConfig) {
         ],
     );
 
+    let pcmd_ringdump = try_inst(
+        CmdRingParams::dump,
+        None,
+        "ringdump <port_id> <rx|tx> <queue_id>\n     Dump descriptor ring occupancy",
+        &[
+            &pcmd_ringdump_token_cmd,
+            &pcmd_ringdump_token_port,
+            &pcmd_ringdump_token_dir,
+            &pcmd_ringdump_token_queue,
+        ],
+    );
+
+    let pcmd_pause_get = try_inst(
+        CmdIntParams::pause_get,
+        None,
+        "pause <port_id>\n     Show pause parameters",
+        &[&pcmd_pause_token_cmd, &pcmd_int_token_port],
+    );
+
+    let pcmd_pause_set = try_inst(
+        CmdPauseParams::set,
+        None,
+        "pause <port_id> <all|rx|tx|none>\n     Change pause parameters",
+        &[
+            &pcmd_pause_set_token_cmd,
+            &pcmd_pause_token_port,
+            &pcmd_pause_token_mode,
+        ],
+    );
+
+    let pcmd_ring_get = try_inst(
+        CmdIntParams::ring_get,
+        Some(app_cfg),
+        "ring <port_id>\n     Show RX/TX ring parameters",
+        &[&pcmd_ring_token_cmd, &pcmd_int_token_port],
+    );
+
+    let pcmd_ring_set = try_inst(
+        CmdRingSizeParams::set,
+        Some(app_cfg),
+        "ring <port_id> <rx_desc> <tx_desc>\n     Change RX/TX ring parameters",
+        &[
+            &pcmd_ring_set_token_cmd,
+            &pcmd_ring_token_port,
+            &pcmd_ring_token_rx_desc,
+            &pcmd_ring_token_tx_desc,
+        ],
+    );
+
+    let pcmd_features_get = try_inst(
+        CmdIntParams::features_get,
+        None,
+        "features <port_id>\n     Show offload features",
+        &[&pcmd_features_token_cmd, &pcmd_int_token_port],
+    );
+
+    let pcmd_features_set = try_inst(
+        CmdFeatureParams::set,
+        Some(app_cfg),
+        "features <port_id> <rx-*|tx-*> <on|off>\n     Change offload features",
+        &[
+            &pcmd_features_set_token_cmd,
+            &pcmd_features_token_port,
+            &pcmd_features_token_name,
+            &pcmd_features_token_state,
+        ],
+    );
+
     let cmds = &[
         &pcmd_quit,
         &pcmd_drvinfo,
@@ -461,6 +808,7 @@ pub fn main(app_cfg: &mut AppConfig) {
         &pcmd_stop,
         &pcmd_rxmode,
         &pcmd_portstats,
+        &pcmd_module,
         &pcmd_mtu_list,
         &pcmd_mtu_get,
         &pcmd_mtu_set,
@@ -469,6 +817,13 @@ pub fn main(app_cfg: &mut AppConfig) {
         &pcmd_macaddr_set,
         &pcmd_macaddr_validate,
         &pcmd_vlan,
+        &pcmd_ringdump,
+        &pcmd_pause_get,
+        &pcmd_pause_set,
+        &pcmd_ring_get,
+        &pcmd_ring_set,
+        &pcmd_features_get,
+        &pcmd_features_set,
     ];
 
     new(cmds).open_stdin("EthApp> ").expect("fail to open stdin").interact();
@@ -11,8 +11,7 @@ pub const MAX_PORTS: u16 = RTE_MAX_ETHPORTS as u16;
 pub const MAX_BURST_LENGTH: usize = 32;
 
 pub struct TxQueuePort {
-    pub cnt_unsent: usize,
-    pub buf_frames: [Option<mbuf::MBuf>; MAX_BURST_LENGTH],
+    pub buf_frames: mbuf::ArrayVec<[mbuf::MBuf; MAX_BURST_LENGTH]>,
 }
 
 pub struct AppPort {
@@ -21,7 +20,9 @@ pub struct AppPort {
     pub port_id: u8,
     pub port_active: bool,
     pub port_dirty: bool,
-    pub pkt_pool: mempool::MemoryPool,
+    pub pkt_pool: Option<mempool::MemoryPool>,
+    pub nb_rx_desc: u16,
+    pub nb_tx_desc: u16,
 }
 
 impl Default for AppPort {
@@ -30,6 +31,45 @@ impl Default for AppPort {
     }
 }
 
+impl AppPort {
+    /// Reconfigure the port with the descriptor numbers and offloads, the port is restarted if it was active.
+    pub fn reconfigure(
+        &mut self,
+        dev: ethdev::PortId,
+        nb_rx_desc: u16,
+        nb_tx_desc: u16,
+        rx_offloads: ethdev::RxOffload,
+        tx_offloads: ethdev::TxOffload,
+    ) -> result::Result<(), String> {
+        let (nb_rx_desc, nb_tx_desc) = dev
+            .adjust_nb_rx_tx_desc(nb_rx_desc, nb_tx_desc)
+            .map_err(|err| format!("fail to adjust descriptors of port {}, {}", dev, err))?;
+        let pool = self
+            .pkt_pool
+            .as_ref()
+            .ok_or_else(|| format!("port {} has no mbuf pool", dev))?;
+
+        dev.stop();
+
+        dev.set_offloads(rx_offloads, tx_offloads)
+            .map_err(|err| format!("fail to configure offloads of port {}, {}", dev, err))?;
+        dev.rx_queue_setup(0, nb_rx_desc, None, pool.to_ref())
+            .map_err(|err| format!("fail to setup rx queue of port {}, {}", dev, err))?;
+        dev.tx_queue_setup(0, nb_tx_desc, None)
+            .map_err(|err| format!("fail to setup tx queue of port {}, {}", dev, err))?;
+
+        self.nb_rx_desc = nb_rx_desc;
+        self.nb_tx_desc = nb_tx_desc;
+
+        if self.port_active {
+            dev.start()
+                .map_err(|err| format!("fail to start port {}, {}", dev, err))?;
+        }
+
+        Ok(())
+    }
+}
+
 pub struct AppConfig {
     pub ports: Vec<Mutex<AppPort>>,
     pub exit_now: bool,
@@ -34,13 +34,13 @@ fn setup_ports(app_cfg: &mut AppConfig) {
 
             let size_pktpool = dev_info.rx_desc_lim.nb_max + dev_info.tx_desc_lim.nb_max + PKTPOOL_EXTRA_SIZE;
 
-            app_port.pkt_pool = mbuf::pool_create(
+            let pkt_pool = mbuf::pool_create(
                 &format!("pkt_pool_{}", portid),
                 size_pktpool as u32,
                 PKTPOOL_CACHE,
                 0,
                 mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
-                rte::socket_id() as i32,
+                rte::socket_id(),
             )
             .expect("create mbuf pool failed");
 
@@ -54,13 +54,18 @@ fn setup_ports(app_cfg: &mut AppConfig) {
                 .expect(&format!("fail to configure device: port={}", portid));
 
             // init one RX queue
-            dev.rx_queue_setup(0, PORT_RX_QUEUE_SIZE, None, &mut app_port.pkt_pool)
+            dev.rx_queue_setup(0, PORT_RX_QUEUE_SIZE, None, pkt_pool.to_ref())
                 .expect(&format!("fail to setup device rx queue: port={}", portid));
 
+            app_port.pkt_pool = Some(pkt_pool);
+
             // init one TX queue on each port
             dev.tx_queue_setup(0, PORT_TX_QUEUE_SIZE, None)
                 .expect(&format!("fail to setup device tx queue: port={}", portid));
 
+            app_port.nb_rx_desc = PORT_RX_QUEUE_SIZE;
+            app_port.nb_tx_desc = PORT_TX_QUEUE_SIZE;
+
             // Start device
             dev.start().expect(&format!("fail to start device: port={}", portid));
 
@@ -100,24 +105,17 @@ fn slave_main(app_cfg: Option<&mut AppConfig>) -> i32 {
                 let txq = &mut app_port.txq;
 
                 // Incoming frames
-                let cnt_recv_frames = dev.rx_burst(0, &mut txq.buf_frames[txq.cnt_unsent..]);
+                let cnt_unsent = txq.buf_frames.len();
 
-                if cnt_recv_frames > 0 {
-                    let frames = &txq.buf_frames[txq.cnt_unsent..txq.cnt_unsent + cnt_recv_frames];
-                    for frame in frames {
-                        process_frame(&app_port.mac_addr, frame.as_ref().unwrap());
+                if dev.rx_burst_mbuf(0, &mut txq.buf_frames) > 0 {
+                    for frame in &txq.buf_frames[cnt_unsent..] {
+                        process_frame(&app_port.mac_addr, frame);
                     }
-
-                    txq.cnt_unsent += cnt_recv_frames
                 }
 
-                // Outgoing frames
-                if txq.cnt_unsent > 0 {
-                    let cnt_sent = dev.tx_burst(0, &mut txq.buf_frames[..txq.cnt_unsent]);
-
-                    for i in cnt_sent..txq.cnt_unsent {
-                        txq.buf_frames[i - cnt_sent] = txq.buf_frames[i].take();
-                    }
+                // Outgoing frames, the unsent frames are kept in the queue
+                if !txq.buf_frames.is_empty() {
+                    dev.tx_burst_mbuf(0, &mut txq.buf_frames);
                 }
             }
         }
@@ -134,7 +132,7 @@ fn main() {
     // Init runtime enviornment
     eal::init(&args).expect("Cannot init EAL");
 
-    let cnt_ports = match ethdev::count() {
+    let cnt_ports = match ethdev::count_avail() {
         0 => {
             eal::exit(EXIT_FAILURE, "No available NIC ports!\n");
 
@@ -21,8 +21,9 @@ use std::str::FromStr;
 use nix::sys::signal;
 
 use rte::ethdev::EthDevice;
-use rte::ffi::{ETHER_MAX_LEN, RTE_MAX_ETHPORTS, RTE_PKTMBUF_HEADROOM};
+use rte::ffi::{RTE_CACHE_LINE_SIZE, RTE_MAX_ETHPORTS, RTE_PKTMBUF_HEADROOM};
 use rte::lcore::RTE_MAX_LCORE;
+use rte::malloc::{self, DpdkBox};
 use rte::*;
 
 const EXIT_FAILURE: i32 = -1;
@@ -77,11 +78,12 @@ struct kni_port_params {
 
 struct Conf {
     // mask of enabled ports
-    enabled_port_mask: u32,
+    enabled_port_mask: PortMask,
 
     promiscuous_on: bool,
 
-    port_params: [Option<kni_port_params>; RTE_MAX_ETHPORTS as usize],
+    // per port parameters, shared with the C code as an array of pointers
+    port_params: [Option<DpdkBox<kni_port_params>>; RTE_MAX_ETHPORTS as usize],
 }
 
 impl fmt::Debug for Conf {
@@ -126,7 +128,8 @@ impl Conf {
             return Err(format!("Port {} has been configured", port_id));
         }
 
-        let mut param: kni_port_params = unsafe { mem::zeroed() };
+        let mut param = unsafe { malloc::alloc_zeroed::<kni_port_params>(Socket::ANY, RTE_CACHE_LINE_SIZE) }
+            .map_err(|err| format!("Fail to allocate port parameters, {}", err))?;
 
         param.port_id = port_id as u8;
         param.lcore_rx = try!(fields.next().ok_or("Invalid config parameter, missed lcore_rx field"));
@@ -250,8 +253,8 @@ fn parse_args(args: &Vec<String>) -> result::Result<Conf, String> {
     let mut conf = Conf::new();
 
     if let Some(arg) = matches.opt_str("p") {
-        match u32::from_str_radix(arg.as_str(), 16) {
-            Ok(mask) if mask != 0 => conf.enabled_port_mask = mask,
+        match arg.parse::<PortMask>() {
+            Ok(mask) if !mask.is_empty() => conf.enabled_port_mask = mask,
             _ => {
                 println!("invalid portmask, {}", arg);
 
@@ -284,7 +287,7 @@ fn init_kni(conf: &Conf) -> Result<()> {
 }
 
 // Initialise a single port on an Ethernet device
-fn init_port(conf: &Conf, dev: ethdev::PortId, port_conf: &ethdev::EthConf, pktmbuf_pool: &mut mempool::MemoryPool) {
+fn init_port(conf: &Conf, dev: ethdev::PortId, port_conf: &ethdev::EthConf, pktmbuf_pool: mempool::MemPoolRef) {
     let portid = dev.portid();
 
     // Initialise device and RX/TX queues
@@ -311,153 +314,101 @@ fn init_port(conf: &Conf, dev: ethdev::PortId, port_conf: &ethdev::EthConf, pktm
     }
 }
 
-extern "C" fn kni_change_mtu(port_id: u16, new_mtu: libc::c_uint) -> libc::c_int {
+fn kni_change_mtu(port_id: ethdev::PortId, new_mtu: u32) -> Result<()> {
     debug!("port {} change MTU to {}", port_id, new_mtu);
 
-    let nb_sys_ports = ethdev::count();
+    let conf = ethdev::EthConfBuilder::from(&port_id.current_conf())
+        .max_rx_pkt_len(new_mtu + KNI_ENET_HEADER_SIZE + KNI_ENET_FCS_SIZE)
+        .build_for_port(port_id)?;
 
-    if port_id > nb_sys_ports || port_id as u32 > RTE_MAX_ETHPORTS {
-        error!("Invalid port id {}", port_id);
+    // Set new MTU, the queues are setup again and the port is restarted
+    port_id.reconfigure(move |port_conf| *port_conf = conf)?;
 
-        return -libc::EINVAL;
-    }
-
-    if new_mtu > ETHER_MAX_LEN {
-        let dev = port_id as ethdev::PortId;
-
-        dev.stop();
-
-        // Set new MTU
-        let mut port_conf = ethdev::EthConf::default();
-
-        let mut rxmode: ethdev::EthRxMode = Default::default();
-
-        rxmode.max_rx_pkt_len = new_mtu + KNI_ENET_HEADER_SIZE + KNI_ENET_FCS_SIZE;
-
-        port_conf.rxmode = Some(rxmode);
-
-        if let Err(err) = dev.configure(1, 1, &port_conf) {
-            error!("Fail to reconfigure port {}, {}", port_id, err);
-
-            if let Some(&RteError(errno)) = err.downcast_ref::<RteError>() {
-                return errno;
-            }
-        }
-
-        if let Err(err) = dev.start() {
-            error!("Failed to start port {}, {}", port_id, err);
-
-            if let Some(&RteError(errno)) = err.downcast_ref::<RteError>() {
-                return errno;
-            }
-        }
-    }
-
-    0
+    Ok(())
 }
 
-extern "C" fn kni_config_network_interface(port_id: u16, if_up: u8) -> libc::c_int {
+fn kni_config_network_interface(port_id: ethdev::PortId, if_up: bool) -> Result<()> {
     debug!(
         "port {} change status to {}",
         port_id,
-        if if_up != 0 { "up" } else { "down" }
+        if if_up { "up" } else { "down" }
     );
 
-    let nb_sys_ports = ethdev::count();
-
-    if port_id > nb_sys_ports || port_id as u32 > RTE_MAX_ETHPORTS {
-        error!("Invalid port id {}", port_id);
-
-        return -libc::EINVAL;
-    }
-
-    let dev = port_id as ethdev::PortId;
+    let dev = port_id;
 
     dev.stop();
 
-    if if_up != 0 {
-        if let Err(err) = dev.start() {
-            error!("Failed to start port {}, {}", port_id, err);
-
-            if let Some(&RteError(errno)) = err.downcast_ref::<RteError>() {
-                return errno;
-            }
-        }
+    if if_up {
+        dev.start()?;
     }
 
-    0
+    Ok(())
 }
 
-extern "C" fn kni_config_mac_address(port_id: u16, mac_addr: *mut u8) -> libc::c_int {
-    debug!(
-        "port {} change mac address to {}",
-        port_id,
-        ether::EtherAddr::from(mac_addr)
-    );
+fn kni_config_mac_address(port_id: ethdev::PortId, mac_addr: ether::EtherAddr) -> Result<()> {
+    debug!("port {} change mac address to {}", port_id, mac_addr);
+
+    port_id.set_mac_addr(mac_addr.octets())?;
 
-    0
+    Ok(())
 }
 
-extern "C" fn kni_config_promiscusity(port_id: u16, on: u8) -> libc::c_int {
+fn kni_config_promiscusity(port_id: ethdev::PortId, on: bool) -> Result<()> {
     debug!(
         "port {} change promiscusity to {}",
         port_id,
-        if on == 0 { "off" } else { "on" }
+        if on { "on" } else { "off" }
     );
 
-    0
+    if on {
+        port_id.promiscuous_enable();
+    } else {
+        port_id.promiscuous_disable();
+    }
+
+    Ok(())
 }
 
-fn kni_alloc(conf: &mut Conf, dev: ethdev::PortId, pktmbuf_pool: &mut mempool::MemoryPool) {
+fn kni_alloc(conf: &mut Conf, dev: ethdev::PortId, pktmbuf_pool: mempool::MemPoolRef) {
     let portid = dev.portid();
 
     if let Some(ref mut param) = conf.port_params[portid as usize] {
-        param.nb_kni = cmp::max(param.nb_lcore_k, 1);
-
-        for i in 0..param.nb_kni {
-            let name = if param.nb_lcore_k > 0 {
-                format!("vEth{}_{}", portid, i)
-            } else {
-                format!("vEth{}", portid)
-            };
-
-            let mut conf = kni::KniDeviceConf::default();
-
-            conf.name = name.as_str();
-            conf.group_id = portid as u16;
-            conf.mbuf_size = MAX_PACKET_SZ;
-
-            let mut kni = (if i == 0 {
-                // The first KNI device associated to a port is the master,
-                // for multiple kernel thread environment.
-                // let dev_info = dev.info();
-                // let dev = dev_info
-                //     .dev()
-                //     .expect(&format!("port {} haven't PCI dev info", dev.portid()));
-
-                // conf.pci_addr = dev.addr;
-                // conf.pci_id = dev.id;
-
-                let ops = kni::KniDeviceOps {
-                    port_id: portid,
-                    change_mtu: Some(kni_change_mtu),
-                    config_network_if: Some(kni_config_network_interface),
-                    config_mac_address: Some(kni_config_mac_address),
-                    config_promiscusity: Some(kni_config_promiscusity),
-                };
-
-                kni::alloc(pktmbuf_pool, &conf, Some(&ops))
-            } else {
-                kni::alloc(pktmbuf_pool, &conf, None)
-            })
-            .expect(&format!("Fail to create kni for port: {}", portid));
-
-            param.kni[i as usize] = kni.into_raw();
-
-            debug!(
-                "allocated kni device `{}` @{:p} for port #{}",
-                conf.name, param.kni[i as usize], portid
-            );
+        let name = format!("vEth{}", portid);
+
+        let conf = kni::KniDeviceConf {
+            name: name.as_str(),
+            group_id: portid,
+            mbuf_size: MAX_PACKET_SZ,
+            mac_addr: dev.mac_addr(),
+            mtu: dev
+                .mtu()
+                .unwrap_or_else(|_| panic!("fail to get MTU of port: {}", portid)),
+            ..Default::default()
+        };
+
+        // The first KNI device associated to a port is the master,
+        // for multiple kernel thread environment.
+        let handlers = kni::KniHandlers::new()
+            .on_change_mtu(kni_change_mtu)
+            .on_config_network_if(kni_config_network_interface)
+            .on_config_mac_address(kni_config_mac_address)
+            .on_config_promiscusity(kni_config_promiscusity);
+
+        let devices = kni::alloc_group(
+            pktmbuf_pool,
+            &conf,
+            portid,
+            &param.lcore_k[..param.nb_lcore_k as usize],
+            handlers,
+        )
+        .unwrap_or_else(|_| panic!("Fail to create kni for port: {}", portid));
+
+        param.nb_kni = devices.len() as u32;
+
+        for (i, kni) in devices.into_iter().enumerate() {
+            param.kni[i] = kni.into_raw();
+
+            debug!("allocated kni device @{:p} for port #{}", param.kni[i], portid);
         }
     }
 }
@@ -468,6 +419,8 @@ fn kni_free_kni(conf: &Conf, dev: ethdev::PortId) {
             let _ = kni::KniDevice::from_raw(*kni);
         }
 
+        kni::remove_handlers(dev);
+
         dev.stop();
     }
 }
@@ -607,18 +560,18 @@ fn main() {
     }
 
     // create the mbuf pool
-    let mut pktmbuf_pool = mbuf::pool_create(
+    let pktmbuf_pool = mbuf::pool_create(
         "mbuf_pool",
         NB_MBUF,
         MEMPOOL_CACHE_SZ,
         0,
         MBUF_DATA_SZ as u16,
-        rte::socket_id() as i32,
+        rte::socket_id(),
     )
     .expect("fail to initial mbuf pool");
 
     let enabled_devices: Vec<ethdev::PortId> = ethdev::devices()
-        .filter(|dev| ((1 << dev.portid()) & conf.enabled_port_mask) != 0)
+        .filter(|dev| conf.enabled_port_mask.contains(dev.portid()))
         .collect();
 
     if enabled_devices.is_empty() {
@@ -632,9 +585,9 @@ fn main() {
     let port_conf = ethdev::EthConf::default();
 
     for dev in &enabled_devices {
-        init_port(&conf, dev.portid(), &port_conf, &mut pktmbuf_pool);
+        init_port(&conf, dev.portid(), &port_conf, pktmbuf_pool.to_ref());
 
-        kni_alloc(&mut conf, dev.portid(), &mut pktmbuf_pool);
+        kni_alloc(&mut conf, dev.portid(), pktmbuf_pool.to_ref());
     }
 
     check_all_ports_link_status(&enabled_devices);
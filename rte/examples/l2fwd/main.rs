@@ -14,11 +14,13 @@ use std::mem;
 use std::path::Path;
 use std::process;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 use nix::sys::signal;
 
-use rte::ethdev::{EthDevice, EthDeviceInfo, TxBuffer};
+use rte::ethdev::{EthDevice, EthDeviceInfo, RxQueue, TxBuffer, TxQueue};
 use rte::ffi::RTE_MAX_ETHPORTS;
+use rte::l2switch::{L2Switch, SwitchConf};
 use rte::lcore::RTE_MAX_LCORE;
 use rte::memory::AsMutRef;
 use rte::*;
@@ -73,7 +75,7 @@ fn print_usage(program: &String, opts: getopts::Options) -> ! {
 }
 
 // Parse the argument given in the command line of the application
-fn parse_args(args: &Vec<String>) -> (u32, u32, u32) {
+fn parse_args(args: &[String]) -> (PortMask, u32, u32, bool) {
     let mut opts = getopts::Options::new();
     let program = args[0].clone();
 
@@ -86,6 +88,11 @@ fn parse_args(args: &Vec<String>) -> (u32, u32, u32) {
          86400 maximum)",
         "PERIOD",
     );
+    opts.optflag(
+        "L",
+        "",
+        "run a MAC learning switch between the ports on the master lcore, instead of forwarding the port pairs",
+    );
     opts.optflag("h", "help", "print this help menu");
 
     let matches = match opts.parse(&args[1..]) {
@@ -101,13 +108,13 @@ fn parse_args(args: &Vec<String>) -> (u32, u32, u32) {
         print_usage(&program, opts);
     }
 
-    let mut enabled_port_mask: u32 = 0; // mask of enabled ports
+    let mut enabled_port_mask = PortMask::new(); // mask of enabled ports
     let mut rx_queue_per_lcore: u32 = 1;
     let mut timer_period_seconds: u32 = 10; // default period is 10 seconds
 
     if let Some(arg) = matches.opt_str("p") {
-        match u32::from_str_radix(arg.as_str(), 16) {
-            Ok(mask) if mask != 0 => enabled_port_mask = mask,
+        match arg.parse::<PortMask>() {
+            Ok(mask) if !mask.is_empty() => enabled_port_mask = mask,
             _ => {
                 println!("invalid portmask, {}", arg);
 
@@ -138,7 +145,12 @@ fn parse_args(args: &Vec<String>) -> (u32, u32, u32) {
         }
     }
 
-    (enabled_port_mask, rx_queue_per_lcore, timer_period_seconds)
+    (
+        enabled_port_mask,
+        rx_queue_per_lcore,
+        timer_period_seconds,
+        matches.opt_present("L"),
+    )
 }
 
 // Check the link status of all ports in up to 9s, and print them finally
@@ -218,6 +230,42 @@ fn l2fwd_launch_one_lcore(conf: Option<&Conf>) -> i32 {
     unsafe { l2fwd_main_loop(qconf.rx_port_list.as_ptr(), qconf.n_rx_port) }
 }
 
+// Switch the packets between all the ports, learning their MAC addresses
+fn l2switch_main_loop(
+    ports: Vec<ethdev::PortId>,
+    mut rxs: Vec<RxQueue>,
+    mut txs: Vec<TxQueue>,
+    timer_period: Duration,
+) -> L2Switch {
+    let mut sw = L2Switch::new(ports.clone(), SwitchConf::default());
+    let mut last_aging = Instant::now();
+    let mut last_stats = last_aging;
+
+    info!("entering learning switch loop on lcore {}", lcore::current().unwrap());
+
+    while unsafe { l2fwd_force_quit == 0 } {
+        let now = Instant::now();
+
+        for (port_id, rx) in ports.iter().zip(rxs.iter_mut()) {
+            sw.switch(*port_id, rx, &mut txs, MAX_PKT_BURST, now);
+        }
+
+        if now.duration_since(last_aging) > Duration::from_secs(1) {
+            sw.age(now);
+
+            last_aging = now;
+        }
+
+        if timer_period > Duration::from_secs(0) && now.duration_since(last_stats) > timer_period {
+            println!("{:?}, {} learned addresses", sw.stats(), sw.table().len());
+
+            last_stats = now;
+        }
+    }
+
+    sw
+}
+
 extern "C" fn handle_sigint(sig: libc::c_int) {
     match signal::Signal::from_c_int(sig).unwrap() {
         signal::SIGINT | signal::SIGTERM => unsafe {
@@ -268,10 +316,10 @@ fn main() {
 
     debug!("eal args: {:?}, l2fwd args: {:?}", eal_args, opt_args);
 
-    let (enabled_port_mask, rx_queue_per_lcore, timer_period_seconds) = parse_args(&opt_args);
+    let (enabled_port_mask, rx_queue_per_lcore, timer_period_seconds, learning) = parse_args(&opt_args);
 
     unsafe {
-        l2fwd_enabled_port_mask = enabled_port_mask;
+        l2fwd_enabled_port_mask = enabled_port_mask.bits() as u32;
         l2fwd_timer_period = timer_period_seconds as i64 * TIMER_MILLISECOND * 1000;
     }
 
@@ -279,18 +327,18 @@ fn main() {
     eal::init(&eal_args).expect("fail to initial EAL");
 
     // create the mbuf pool
-    let mut l2fwd_pktmbuf_pool = mbuf::pool_create(
+    let l2fwd_pktmbuf_pool = mbuf::pool_create(
         "mbuf_pool",
         NB_MBUF,
         32,
         0,
         mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
-        rte::socket_id() as i32,
+        rte::socket_id(),
     )
     .unwrap();
 
     let enabled_devices: Vec<ethdev::PortId> = ethdev::devices()
-        .filter(|dev| ((1 << dev.portid()) & enabled_port_mask) != 0)
+        .filter(|dev| enabled_port_mask.contains(dev.portid()))
         .collect();
 
     if enabled_devices.is_empty() {
@@ -355,7 +403,8 @@ fn main() {
         println!("Lcore {}: RX port {}", rx_lcore_id, portid);
     }
 
-    let port_conf = ethdev::EthConf::default();
+    let mut rxs = vec![];
+    let mut txs = vec![];
 
     // Initialise each port
     for dev in &enabled_devices {
@@ -364,9 +413,17 @@ fn main() {
         // init port
         print!("Initializing port {}... ", portid);
 
+        let port_conf = ethdev::Profile::L2Forwarding
+            .for_port(dev.portid())
+            .unwrap_or_else(|_| panic!("fail to get configuration: port={}", portid));
+
         dev.configure(1, 1, &port_conf)
             .expect(&format!("fail to configure device: port={}", portid));
 
+        let (nb_rxd, nb_txd) = dev
+            .adjust_nb_rx_tx_desc(conf.nb_rxd, conf.nb_txd)
+            .unwrap_or_else(|_| panic!("fail to adjust number of descriptors: port={}", portid));
+
         let mac_addr = dev.mac_addr();
 
         unsafe {
@@ -374,12 +431,16 @@ fn main() {
         }
 
         // init one RX queue
-        dev.rx_queue_setup(0, conf.nb_rxd, None, &mut l2fwd_pktmbuf_pool)
-            .expect(&format!("fail to setup device rx queue: port={}", portid));
+        rxs.push(
+            dev.rx_queue_setup(0, nb_rxd, None, l2fwd_pktmbuf_pool.to_ref())
+                .unwrap_or_else(|_| panic!("fail to setup device rx queue: port={}", portid)),
+        );
 
         // init one TX queue on each port
-        dev.tx_queue_setup(0, conf.nb_txd, None)
-            .expect(&format!("fail to setup device tx queue: port={}", portid));
+        txs.push(
+            dev.tx_queue_setup(0, nb_txd, None)
+                .unwrap_or_else(|_| panic!("fail to setup device tx queue: port={}", portid)),
+        );
 
         // Initialize TX buffers
         let buf = ethdev::alloc_buffer(MAX_PKT_BURST, dev.socket_id())
@@ -393,17 +454,20 @@ fn main() {
             l2fwd_tx_buffers[portid] = buf;
         }
 
-        // Start device
-        dev.start().expect(&format!("fail to start device: port={}", portid));
-
         println!("Done: ");
+    }
+
+    ethdev::start_all(&enabled_devices).expect("fail to start devices");
+
+    for dev in &enabled_devices {
+        let portid = dev.portid();
 
         dev.promiscuous_enable();
 
         println!(
             "  Port {}, MAC address: {} (promiscuous {})",
             portid,
-            mac_addr,
+            dev.mac_addr(),
             dev.is_promiscuous_enabled()
                 .map(|enabled| if enabled { "enabled" } else { "disabled" })
                 .expect(&format!("fail to enable promiscuous mode for device: port={}", portid))
@@ -412,14 +476,27 @@ fn main() {
 
     check_all_ports_link_status(&enabled_devices);
 
-    // launch per-lcore init on every lcore
-    launch::mp_remote_launch(l2fwd_launch_one_lcore, Some(&conf), false).unwrap();
+    if learning {
+        // the switch owns the only TX queue of each port, so it runs on the master lcore alone
+        let sw = l2switch_main_loop(
+            enabled_devices.clone(),
+            rxs,
+            txs,
+            Duration::from_secs(timer_period_seconds as u64),
+        );
+
+        println!("{:?}", sw.stats());
+    } else {
+        // launch per-lcore init on every lcore
+        launch::mp_remote_launch(l2fwd_launch_one_lcore, Some(&conf), false).unwrap();
+
+        launch::mp_wait_lcore();
+    }
 
-    launch::mp_wait_lcore();
+    ethdev::stop_all(&enabled_devices);
 
     for dev in &enabled_devices {
         print!("Closing port {}...", dev.portid());
-        dev.stop();
         dev.close();
         println!(" Done");
 
@@ -0,0 +1,716 @@
+#[macro_use]
+extern crate log;
+extern crate getopts;
+extern crate libc;
+extern crate nix;
+extern crate pretty_env_logger;
+extern crate rte;
+
+use std::collections::HashMap;
+use std::env;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use nix::sys::signal;
+
+use rte::ethdev::{EthDevice, EthDeviceInfo, TxRetryPolicy};
+use rte::ether::{EtherAddr, ETHER_HDR_LEN};
+use rte::hashers::FiveTuple;
+use rte::ip::Ipv4Ext;
+use rte::lcore::RTE_MAX_LCORE;
+use rte::mbuf::{ArrayBurst, MBuf};
+use rte::route::{Route, RouteTable};
+use rte::*;
+
+const EXIT_FAILURE: i32 = -1;
+
+const MAX_PKT_BURST: usize = 32;
+
+const MAX_PORTS: usize = 32;
+
+const NB_MBUF: u32 = 8192;
+
+const MEMPOOL_CACHE_SIZE: u32 = 256;
+
+// Configurable number of RX/TX ring descriptors
+const RTE_TEST_RX_DESC_DEFAULT: u16 = 1024;
+const RTE_TEST_TX_DESC_DEFAULT: u16 = 1024;
+
+const IPPROTO_TCP: u8 = 6;
+
+static FORCE_QUIT: AtomicBool = AtomicBool::new(false);
+
+/// The lookup method of the destination port.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+    /// Longest prefix match on the destination address.
+    Lpm,
+    /// Exact match on the 5-tuple.
+    Em,
+}
+
+// The IPv4 LPM routes, 1.1.1.0/24 to port 0, 2.1.1.0/24 to port 1, ...
+const IPV4_L3FWD_LPM_ROUTES: usize = 8;
+
+// The IPv4 EM route, (src, dst, sport, dport, proto) -> port
+type EmRoute = ([u8; 4], [u8; 4], u16, u16, u8, PortId);
+
+// The IPv4 EM routes
+const IPV4_L3FWD_EM_ROUTES: [EmRoute; 4] = [
+    ([100, 10, 0, 1], [101, 0, 0, 0], 11, 101, IPPROTO_TCP, 0),
+    ([200, 20, 0, 1], [201, 0, 0, 0], 12, 102, IPPROTO_TCP, 1),
+    ([100, 30, 0, 1], [111, 0, 0, 0], 11, 101, IPPROTO_TCP, 2),
+    ([200, 40, 0, 1], [211, 0, 0, 0], 12, 102, IPPROTO_TCP, 3),
+];
+
+/// Hash the flow keys with the CRC32 hash.
+#[derive(Default)]
+struct CrcHasher(u32);
+
+impl Hasher for CrcHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = hashers::crc(bytes, self.0);
+    }
+
+    fn finish(&self) -> u64 {
+        u64::from(self.0)
+    }
+}
+
+type EmTable = HashMap<FiveTuple, PortId, BuildHasherDefault<CrcHasher>>;
+
+/// A RX queue polled by a lcore.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct RxQueue {
+    port_id: PortId,
+    queue_id: QueueId,
+    lcore_id: u32,
+}
+
+#[derive(Default)]
+struct LcoreStats {
+    rx: AtomicUsize,
+    tx: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+struct Conf {
+    mode: Mode,
+    enabled_ports: Vec<PortId>,
+    rx_queues: Vec<RxQueue>,
+    // the TX queue of each lcore on every port
+    tx_queues: HashMap<u32, QueueId>,
+    ports_eth_addr: [EtherAddr; MAX_PORTS],
+    dest_eth_addr: [EtherAddr; MAX_PORTS],
+    lpm: RouteTable,
+    em: EmTable,
+    stats: Vec<LcoreStats>,
+}
+
+impl Conf {
+    // Look up the output ports of a burst of packets, the LPM routes are looked up in bulk.
+    fn lookup_bulk(&self, tuples: &[Option<FiveTuple>], ports: &mut [Option<PortId>]) {
+        match self.mode {
+            Mode::Lpm => {
+                let mut addrs = [IpAddr::V4(Ipv4Addr::UNSPECIFIED); MAX_PKT_BURST];
+                let mut pos = [0; MAX_PKT_BURST];
+                let mut routes = [None; MAX_PKT_BURST];
+                let mut n = 0;
+
+                for (i, tuple) in tuples.iter().enumerate() {
+                    if let Some(ref tuple) = *tuple {
+                        addrs[n] = tuple.dst_addr;
+                        pos[n] = i;
+                        n += 1;
+                    }
+                }
+
+                self.lpm.lookup_bulk(&addrs[..n], &mut routes[..n]);
+
+                for (&i, route) in pos[..n].iter().zip(routes[..n].iter()) {
+                    ports[i] = route.map(|route| route.port);
+                }
+            }
+            Mode::Em => {
+                for (tuple, port) in tuples.iter().zip(ports.iter_mut()) {
+                    *port = tuple.and_then(|tuple| self.em.get(&tuple).cloned());
+                }
+            }
+        }
+    }
+}
+
+struct Args {
+    enabled_port_mask: PortMask,
+    mode: Mode,
+    config: Option<Vec<RxQueue>>,
+    timer_period: u32,
+}
+
+// display usage
+fn print_usage(program: &str, opts: &getopts::Options) -> ! {
+    let brief = format!("Usage: {} [EAL options] -- [options]", program);
+
+    print!("{}", opts.usage(&brief));
+
+    process::exit(-1);
+}
+
+// Parse the `(port,queue,lcore)[,(port,queue,lcore)]` configuration
+fn parse_config(arg: &str) -> Option<Vec<RxQueue>> {
+    let mut queues = vec![];
+
+    for s in arg.split(')') {
+        let s = s.trim_matches(|c| c == ',' || c == ' ');
+
+        if s.is_empty() {
+            continue;
+        }
+
+        let fields = s
+            .trim_start_matches('(')
+            .split(',')
+            .map(|f| u32::from_str(f.trim()).ok())
+            .collect::<Option<Vec<u32>>>()?;
+
+        match fields.as_slice() {
+            &[port_id, queue_id, lcore_id] if port_id < MAX_PORTS as u32 && lcore_id < RTE_MAX_LCORE => {
+                queues.push(RxQueue {
+                    port_id: port_id as PortId,
+                    queue_id: queue_id as QueueId,
+                    lcore_id,
+                })
+            }
+            _ => return None,
+        }
+    }
+
+    if queues.is_empty() {
+        None
+    } else {
+        Some(queues)
+    }
+}
+
+// Parse the argument given in the command line of the application
+fn parse_args(args: &[String]) -> Args {
+    let mut opts = getopts::Options::new();
+    let program = args[0].clone();
+
+    opts.optopt("p", "", "hexadecimal bitmask of ports to configure", "PORTMASK");
+    opts.optflag("E", "", "enable exact match");
+    opts.optflag("L", "", "enable longest prefix match (default)");
+    opts.optopt(
+        "",
+        "config",
+        "(port,queue,lcore)[,(port,queue,lcore)], default is one queue per port polled by the slave lcores",
+        "CONFIG",
+    );
+    opts.optopt(
+        "T",
+        "",
+        "statistics will be refreshed each PERIOD seconds (0 to disable, 10 default)",
+        "PERIOD",
+    );
+    opts.optflag("h", "help", "print this help menu");
+
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(err) => {
+            println!("Invalid L3FWD arguments, {}", err);
+
+            print_usage(&program, &opts);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(&program, &opts);
+    }
+
+    if matches.opt_present("E") && matches.opt_present("L") {
+        println!("only one of the -E and -L options could be set");
+
+        print_usage(&program, &opts);
+    }
+
+    let mut args = Args {
+        enabled_port_mask: PortMask::new(),
+        mode: if matches.opt_present("E") { Mode::Em } else { Mode::Lpm },
+        config: None,
+        timer_period: 10,
+    };
+
+    if let Some(arg) = matches.opt_str("p") {
+        match arg.parse::<PortMask>() {
+            Ok(mask) if !mask.is_empty() => args.enabled_port_mask = mask,
+            _ => {
+                println!("invalid portmask, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    if let Some(arg) = matches.opt_str("config") {
+        match parse_config(&arg) {
+            Some(config) => args.config = Some(config),
+            None => {
+                println!("invalid config, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    if let Some(arg) = matches.opt_str("T") {
+        match u32::from_str(arg.as_str()) {
+            Ok(t) => args.timer_period = t,
+            _ => {
+                println!("invalid timer period, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    args
+}
+
+fn setup_lpm(enabled_ports: &[PortId]) -> RouteTable {
+    let mut lpm = RouteTable::new().expect("fail to create LPM table");
+
+    for i in 0..IPV4_L3FWD_LPM_ROUTES {
+        let port = i as PortId;
+
+        if !enabled_ports.contains(&port) {
+            continue;
+        }
+
+        let prefix = Ipv4Addr::new(i as u8 + 1, 1, 1, 0);
+
+        lpm.add(IpAddr::V4(prefix), 24, Route { port, gateway: None })
+            .expect("fail to add LPM route");
+
+        println!("LPM: adding route {}/24 ({})", prefix, port);
+    }
+
+    lpm
+}
+
+fn setup_em(enabled_ports: &[PortId]) -> EmTable {
+    let mut em = EmTable::default();
+
+    for &(src, dst, src_port, dst_port, proto, port) in &IPV4_L3FWD_EM_ROUTES {
+        if !enabled_ports.contains(&port) {
+            continue;
+        }
+
+        let tuple = FiveTuple::v4(src.into(), dst.into(), src_port, dst_port, proto);
+
+        em.insert(tuple, port);
+
+        println!("EM: adding route {:?} ({})", tuple, port);
+    }
+
+    em
+}
+
+// The 5-tuple of a IPv4 packet, or `None` if the packet should be dropped.
+fn l3fwd_tuple(m: &MBuf) -> Option<FiveTuple> {
+    let d = filter::dissect_mbuf(m)?;
+
+    if d.nb_vlans > 0 {
+        return None;
+    }
+
+    let tuple = match (d.src_ip, d.dst_ip) {
+        (Some(src_addr @ IpAddr::V4(_)), Some(dst_addr @ IpAddr::V4(_))) => FiveTuple {
+            src_addr,
+            dst_addr,
+            src_port: d.src_port.unwrap_or_default(),
+            dst_port: d.dst_port.unwrap_or_default(),
+            proto: d.proto.unwrap_or_default(),
+        },
+        _ => return None,
+    };
+
+    Some(tuple)
+}
+
+// Forward a IPv4 packet to the looked up port, returns the output port, or `None` if the packet should be dropped.
+fn l3fwd_simple_forward(conf: &Conf, m: &mut MBuf, dst_port: Option<PortId>) -> Option<PortId> {
+    let dst_port = dst_port?;
+
+    if !conf.enabled_ports.contains(&dst_port) {
+        return None;
+    }
+
+    let (ether_hdr, mut ipv4_hdr) = m
+        .header_mut::<ether::EtherHdr>(0)
+        .and_then(|h| h.split_at::<ip::Ipv4Hdr>(ETHER_HDR_LEN as usize))?;
+
+    if ipv4_hdr.time_to_live <= 1 {
+        return None;
+    }
+
+    ipv4_hdr.time_to_live -= 1;
+    ipv4_hdr.update_cksum();
+
+    ether::EtherAddr::copy(
+        conf.dest_eth_addr[dst_port as usize].octets(),
+        &mut ether_hdr.d_addr.addr_bytes,
+    );
+    ether::EtherAddr::copy(
+        conf.ports_eth_addr[dst_port as usize].octets(),
+        &mut ether_hdr.s_addr.addr_bytes,
+    );
+
+    Some(dst_port)
+}
+
+fn send_burst(port_id: PortId, queue_id: QueueId, pkts: &mut ArrayBurst<MAX_PKT_BURST>, stats: &LcoreStats) {
+    let res = pkts.tx_all(port_id, queue_id, &TxRetryPolicy::default());
+
+    stats.tx.fetch_add(res.sent, Ordering::Relaxed);
+    stats.dropped.fetch_add(res.dropped, Ordering::Relaxed);
+}
+
+// main processing loop
+fn l3fwd_main_loop(conf: Option<&Conf>) -> i32 {
+    let conf = conf.unwrap();
+    let lcore_id = lcore::current().unwrap();
+    let rx_queues: Vec<RxQueue> = conf
+        .rx_queues
+        .iter()
+        .filter(|q| q.lcore_id == *lcore_id)
+        .cloned()
+        .collect();
+
+    if rx_queues.is_empty() {
+        info!("lcore {} has nothing to do", lcore_id);
+
+        return 0;
+    }
+
+    info!("entering main loop on lcore {}", lcore_id);
+
+    for q in &rx_queues {
+        info!(" -- lcoreid={} portid={} rxqueueid={}", lcore_id, q.port_id, q.queue_id);
+    }
+
+    let tx_queue_id = conf.tx_queues[&*lcore_id];
+    let stats = &conf.stats[*lcore_id as usize];
+    let mut rx_pkts = ArrayBurst::<MAX_PKT_BURST>::new();
+    let mut tuples = [None; MAX_PKT_BURST];
+    let mut dst_ports = [None; MAX_PKT_BURST];
+    let mut tx_pkts: Vec<ArrayBurst<MAX_PKT_BURST>> = (0..MAX_PORTS).map(|_| ArrayBurst::new()).collect();
+
+    while !FORCE_QUIT.load(Ordering::Relaxed) {
+        for q in &rx_queues {
+            let nb_rx = rx_pkts.rx(q.port_id, q.queue_id);
+
+            if nb_rx == 0 {
+                continue;
+            }
+
+            stats.rx.fetch_add(nb_rx, Ordering::Relaxed);
+
+            for (tuple, m) in tuples.iter_mut().zip(rx_pkts.iter()) {
+                *tuple = l3fwd_tuple(m);
+            }
+
+            conf.lookup_bulk(&tuples[..nb_rx], &mut dst_ports[..nb_rx]);
+
+            for (mut m, &dst_port) in rx_pkts.drain().zip(dst_ports.iter()) {
+                let dst_port = match l3fwd_simple_forward(conf, &mut m, dst_port) {
+                    Some(port) => port,
+                    None => {
+                        stats.dropped.fetch_add(1, Ordering::Relaxed);
+
+                        continue;
+                    }
+                };
+
+                let pkts = &mut tx_pkts[dst_port as usize];
+
+                if let Err(m) = pkts.push(m) {
+                    send_burst(dst_port, tx_queue_id, pkts, stats);
+
+                    let _ = pkts.push(m);
+                }
+            }
+        }
+
+        // drain the TX bursts, so the packets don't stay when the traffic is low
+        for &port_id in &conf.enabled_ports {
+            let pkts = &mut tx_pkts[port_id as usize];
+
+            if !pkts.is_empty() {
+                send_burst(port_id, tx_queue_id, pkts, stats);
+            }
+        }
+    }
+
+    0
+}
+
+fn print_stats(conf: &Conf, lcores: &[lcore::Id], elapsed: Duration) {
+    let secs = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_micros()) / 1e6;
+
+    println!("\nLcore statistics ====================================");
+
+    let (mut total_rx, mut total_tx, mut total_dropped) = (0, 0, 0);
+
+    for lcore_id in lcores {
+        let stats = &conf.stats[**lcore_id as usize];
+        let rx = stats.rx.swap(0, Ordering::Relaxed);
+        let tx = stats.tx.swap(0, Ordering::Relaxed);
+        let dropped = stats.dropped.swap(0, Ordering::Relaxed);
+
+        println!(
+            "Lcore {:>3}: rx {:>12} pps, tx {:>12} pps, dropped {:>12} pps",
+            lcore_id,
+            (rx as f64 / secs) as u64,
+            (tx as f64 / secs) as u64,
+            (dropped as f64 / secs) as u64
+        );
+
+        total_rx += rx;
+        total_tx += tx;
+        total_dropped += dropped;
+    }
+
+    println!(
+        "Total    : rx {:>12} pps, tx {:>12} pps, dropped {:>12} pps",
+        (total_rx as f64 / secs) as u64,
+        (total_tx as f64 / secs) as u64,
+        (total_dropped as f64 / secs) as u64
+    );
+    println!("=====================================================");
+}
+
+extern "C" fn handle_sigint(sig: libc::c_int) {
+    match signal::Signal::from_c_int(sig).unwrap() {
+        signal::SIGINT | signal::SIGTERM => {
+            println!("Signal {} received, preparing to exit...", sig);
+
+            FORCE_QUIT.store(true, Ordering::Relaxed);
+        }
+        _ => info!("unexpect signo: {}", sig),
+    }
+}
+
+fn handle_signals() -> nix::Result<()> {
+    let sig_action = signal::SigAction::new(
+        signal::SigHandler::Handler(handle_sigint),
+        signal::SaFlags::empty(),
+        signal::SigSet::empty(),
+    );
+    unsafe {
+        signal::sigaction(signal::SIGINT, &sig_action)?;
+        signal::sigaction(signal::SIGTERM, &sig_action)?;
+    }
+
+    Ok(())
+}
+
+fn prepare_args(args: &mut [String]) -> (Vec<String>, Vec<String>) {
+    let program = String::from(Path::new(&args[0]).file_name().unwrap().to_str().unwrap());
+
+    if let Some(pos) = args.iter().position(|arg| arg == "--") {
+        let (eal_args, opt_args) = args.split_at_mut(pos);
+
+        opt_args[0] = program;
+
+        (eal_args.to_vec(), opt_args.to_vec())
+    } else {
+        (args[..1].to_vec(), args.to_vec())
+    }
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    handle_signals().expect("fail to handle signals");
+
+    let mut args: Vec<String> = env::args().collect();
+
+    let (eal_args, opt_args) = prepare_args(&mut args);
+
+    debug!("eal args: {:?}, l3fwd args: {:?}", eal_args, opt_args);
+
+    let args = parse_args(&opt_args);
+
+    // init EAL
+    eal::init(&eal_args).expect("fail to initial EAL");
+
+    let enabled_ports: Vec<PortId> = ethdev::devices()
+        .filter(|dev| dev.portid() < MAX_PORTS as PortId && args.enabled_port_mask.contains(dev.portid()))
+        .collect();
+
+    if enabled_ports.is_empty() {
+        eal::exit(EXIT_FAILURE, "All available ports are disabled. Please set portmask.\n");
+    }
+
+    let slaves: Vec<lcore::Id> = lcore::enabled().into_iter().filter(|id| !id.is_master()).collect();
+
+    if slaves.is_empty() {
+        eal::exit(EXIT_FAILURE, "missing slave lcores.\n");
+    }
+
+    // default to one RX queue per port, polled by the slave lcores in turn
+    let rx_queues = args.config.unwrap_or_else(|| {
+        enabled_ports
+            .iter()
+            .zip(slaves.iter().cycle())
+            .map(|(&port_id, lcore_id)| RxQueue {
+                port_id,
+                queue_id: 0,
+                lcore_id: **lcore_id,
+            })
+            .collect()
+    });
+
+    for q in &rx_queues {
+        if !enabled_ports.contains(&q.port_id) {
+            eal::exit(
+                EXIT_FAILURE,
+                &format!("port {} is not enabled in port mask\n", q.port_id),
+            );
+        }
+
+        if !slaves.iter().any(|id| **id == q.lcore_id) {
+            eal::exit(
+                EXIT_FAILURE,
+                &format!("lcore {} is not enabled as a slave lcore\n", q.lcore_id),
+            );
+        }
+    }
+
+    // one TX queue per lcore on every port
+    let mut lcores: Vec<u32> = rx_queues.iter().map(|q| q.lcore_id).collect();
+
+    lcores.sort();
+    lcores.dedup();
+
+    let tx_queues: HashMap<u32, QueueId> = lcores
+        .iter()
+        .enumerate()
+        .map(|(idx, &lcore_id)| (lcore_id, idx as QueueId))
+        .collect();
+
+    // create the mbuf pool
+    let pktmbuf_pool = mbuf::pool_create(
+        "mbuf_pool",
+        NB_MBUF * enabled_ports.len() as u32,
+        MEMPOOL_CACHE_SIZE,
+        0,
+        mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        rte::socket_id(),
+    )
+    .expect("fail to initial mbuf pool");
+
+    let mut ports_eth_addr = [EtherAddr::zeroed(); MAX_PORTS];
+    let mut dest_eth_addr = [EtherAddr::zeroed(); MAX_PORTS];
+
+    // Initialise each port
+    for &portid in &enabled_ports {
+        let nb_rx_queue = rx_queues
+            .iter()
+            .filter(|q| q.port_id == portid)
+            .map(|q| q.queue_id + 1)
+            .max()
+            .unwrap_or(0);
+        let nb_tx_queue = tx_queues.len() as QueueId;
+
+        print!(
+            "Initializing port {} ... Creating queues: nb_rxq={} nb_txq={}... ",
+            portid, nb_rx_queue, nb_tx_queue
+        );
+
+        let port_conf = ethdev::Profile::RssL3L4
+            .for_port(portid)
+            .unwrap_or_else(|_| panic!("fail to get configuration: port={}", portid));
+
+        portid
+            .configure(nb_rx_queue, nb_tx_queue, &port_conf)
+            .unwrap_or_else(|_| panic!("fail to configure device: port={}", portid));
+
+        ports_eth_addr[portid as usize] = portid.mac_addr();
+        // 02:00:00:00:00:xx
+        dest_eth_addr[portid as usize] = EtherAddr::new(0x02, 0, 0, 0, 0, portid as u8);
+
+        print!(
+            "Address: {}, Destination: {}, ",
+            portid.mac_addr(),
+            dest_eth_addr[portid as usize]
+        );
+
+        for q in rx_queues.iter().filter(|q| q.port_id == portid) {
+            portid
+                .rx_queue_setup(q.queue_id, RTE_TEST_RX_DESC_DEFAULT, None, pktmbuf_pool.to_ref())
+                .unwrap_or_else(|_| panic!("fail to setup device rx queue: port={} queue={}", portid, q.queue_id));
+        }
+
+        for queue_id in 0..nb_tx_queue {
+            portid
+                .tx_queue_setup(queue_id, RTE_TEST_TX_DESC_DEFAULT, None)
+                .unwrap_or_else(|_| panic!("fail to setup device tx queue: port={} queue={}", portid, queue_id));
+        }
+
+        println!("Done: driver `{}`", portid.info().driver_name());
+    }
+
+    ethdev::start_all(&enabled_ports).expect("fail to start devices");
+
+    for dev in &enabled_ports {
+        dev.promiscuous_enable();
+    }
+
+    let conf = Conf {
+        mode: args.mode,
+        lpm: setup_lpm(&enabled_ports),
+        em: setup_em(&enabled_ports),
+        enabled_ports,
+        rx_queues,
+        tx_queues,
+        ports_eth_addr,
+        dest_eth_addr,
+        stats: (0..RTE_MAX_LCORE).map(|_| LcoreStats::default()).collect(),
+    };
+
+    println!("L3FWD: {:?} mode", conf.mode);
+
+    // launch per-lcore init on every slave lcore
+    launch::mp_remote_launch(l3fwd_main_loop, Some(&conf), true).unwrap();
+
+    let period = Duration::from_secs(u64::from(args.timer_period));
+    let mut last = Instant::now();
+
+    while !FORCE_QUIT.load(Ordering::Relaxed) {
+        delay_ms(100);
+
+        if args.timer_period > 0 && last.elapsed() >= period {
+            print_stats(&conf, &slaves, last.elapsed());
+
+            last = Instant::now();
+        }
+    }
+
+    launch::mp_wait_lcore();
+
+    ethdev::stop_all(&conf.enabled_ports);
+
+    for dev in &conf.enabled_ports {
+        print!("Closing port {}...", dev);
+        dev.close();
+        println!(" Done");
+    }
+
+    println!("Bye...");
+}
@@ -0,0 +1,479 @@
+#[macro_use]
+extern crate log;
+extern crate getopts;
+extern crate libc;
+extern crate nix;
+extern crate pretty_env_logger;
+extern crate rte;
+
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use nix::sys::signal;
+
+use rte::ethdev::{EthDevice, EthDeviceInfo};
+use rte::ether::EtherAddr;
+use rte::mbuf::{ArrayBurst, MBuf};
+use rte::mcast::{self, McastGroupTable};
+use rte::*;
+
+const EXIT_FAILURE: i32 = -1;
+
+const MAX_PKT_BURST: usize = 32;
+
+const MAX_PORTS: usize = 32;
+
+const NB_MBUF: u32 = 8192;
+
+const MEMPOOL_CACHE_SIZE: u32 = 256;
+
+// Configurable number of RX/TX ring descriptors
+const RTE_TEST_RX_DESC_DEFAULT: u16 = 1024;
+const RTE_TEST_TX_DESC_DEFAULT: u16 = 1024;
+
+// The interval to expire the members which were not refreshed
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(1);
+
+static FORCE_QUIT: AtomicBool = AtomicBool::new(false);
+
+struct Args {
+    enabled_port_mask: PortMask,
+    membership_interval: u64,
+    mc_filter: bool,
+    timer_period: u32,
+}
+
+#[derive(Default)]
+struct Stats {
+    rx: usize,
+    tx: usize,
+    snooped: usize,
+    dropped: usize,
+}
+
+fn print_usage(program: &str, opts: &getopts::Options) -> ! {
+    let brief = format!("Usage: {} [EAL options] -- [options]", program);
+
+    print!("{}", opts.usage(&brief));
+
+    process::exit(-1);
+}
+
+// Parse the argument given in the command line of the application
+fn parse_args(args: &[String]) -> Args {
+    let mut opts = getopts::Options::new();
+    let program = args[0].clone();
+
+    opts.optopt("p", "", "hexadecimal bitmask of ports to configure", "PORTMASK");
+    opts.optopt(
+        "m",
+        "membership-interval",
+        "expire the members not refreshed in SECS seconds (260 default)",
+        "SECS",
+    );
+    opts.optflag(
+        "M",
+        "mc-filter",
+        "filter the multicast frames by the known groups instead of the allmulticast mode, \
+         the IGMPv1/v2 and MLDv1 reports of the new groups are not received",
+    );
+    opts.optopt(
+        "T",
+        "",
+        "statistics will be refreshed each PERIOD seconds (0 to disable, 10 default)",
+        "PERIOD",
+    );
+    opts.optflag("h", "help", "print this help menu");
+
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(err) => {
+            println!("Invalid MCAST arguments, {}", err);
+
+            print_usage(&program, &opts);
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(&program, &opts);
+    }
+
+    let mut args = Args {
+        enabled_port_mask: PortMask::new(),
+        membership_interval: mcast::DEFAULT_MEMBERSHIP_INTERVAL.as_secs(),
+        mc_filter: matches.opt_present("M"),
+        timer_period: 10,
+    };
+
+    if let Some(arg) = matches.opt_str("p") {
+        match arg.parse::<PortMask>() {
+            Ok(mask) if !mask.is_empty() => args.enabled_port_mask = mask,
+            _ => {
+                println!("invalid portmask, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    if let Some(arg) = matches.opt_str("m") {
+        match u64::from_str(arg.as_str()) {
+            Ok(secs) if secs > 0 => args.membership_interval = secs,
+            _ => {
+                println!("invalid membership interval, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    if let Some(arg) = matches.opt_str("T") {
+        match u32::from_str(arg.as_str()) {
+            Ok(t) => args.timer_period = t,
+            _ => {
+                println!("invalid timer period, {}", arg);
+
+                print_usage(&program, &opts);
+            }
+        }
+    }
+
+    args
+}
+
+// The well-known groups of the routers and the IGMPv3/MLDv2 reports, which are always received.
+fn well_known_groups() -> Vec<IpAddr> {
+    vec![
+        Ipv4Addr::new(224, 0, 0, 1).into(),
+        Ipv4Addr::new(224, 0, 0, 2).into(),
+        Ipv4Addr::new(224, 0, 0, 22).into(),
+        Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1).into(),
+        Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 2).into(),
+        Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x16).into(),
+    ]
+}
+
+// Program the multicast filter of the ports with the known groups,
+// or fallback to the allmulticast mode if the device can't filter them.
+fn update_mc_filter(enabled_ports: &[PortId], table: &McastGroupTable) {
+    let mut addrs: Vec<EtherAddr> = well_known_groups().iter().map(mcast::mcast_ether_addr).collect();
+
+    addrs.extend(table.mac_addrs());
+
+    for &port_id in enabled_ports {
+        if let Err(err) = port_id.set_mc_addr_list(&addrs) {
+            if !port_id.is_allmulticast_enabled().unwrap_or(false) {
+                warn!(
+                    "port {} fail to filter {} multicast addresses, fallback to allmulticast, {}",
+                    port_id,
+                    addrs.len(),
+                    err
+                );
+
+                port_id.allmulticast_enable();
+            }
+        }
+    }
+}
+
+fn send_burst(port_id: PortId, pkts: &mut ArrayBurst<MAX_PKT_BURST>, stats: &mut Stats) {
+    stats.tx += pkts.tx(port_id, 0);
+
+    if !pkts.is_empty() {
+        stats.dropped += pkts.len();
+
+        pkts.clear();
+    }
+}
+
+fn enqueue(port_id: PortId, m: MBuf, tx_pkts: &mut [ArrayBurst<MAX_PKT_BURST>], stats: &mut Stats) {
+    let pkts = &mut tx_pkts[port_id as usize];
+
+    if let Err(m) = pkts.push(m) {
+        send_burst(port_id, pkts, stats);
+
+        let _ = pkts.push(m);
+    }
+}
+
+// The output ports of a frame received on the port, or `None` if it should be dropped.
+fn mcast_forward(
+    table: &mut McastGroupTable,
+    enabled_ports: &[PortId],
+    in_port: PortId,
+    m: &MBuf,
+    now: Instant,
+    stats: &mut Stats,
+) -> Option<Vec<PortId>> {
+    let flood = || {
+        enabled_ports
+            .iter()
+            .cloned()
+            .filter(|&port_id| port_id != in_port)
+            .collect()
+    };
+
+    // the IGMP/MLD messages are flooded, so the routers and other snoopers see them
+    if table.snoop_mbuf(in_port, m, now) {
+        stats.snooped += 1;
+
+        return Some(flood());
+    }
+
+    let d = filter::dissect_mbuf(m)?;
+
+    if !d.dst_mac.is_multicast() || d.dst_mac.is_broadcast() {
+        return None;
+    }
+
+    match d.dst_ip {
+        Some(ref group) if group.is_multicast() => Some(table.forward_ports(group, in_port).unwrap_or_else(flood)),
+        _ => None,
+    }
+}
+
+fn mcast_main_loop(args: &Args, enabled_ports: &[PortId]) {
+    let mut table = McastGroupTable::new(Duration::from_secs(args.membership_interval));
+    let mut stats = Stats::default();
+    let mut rx_pkts = ArrayBurst::<MAX_PKT_BURST>::new();
+    let mut tx_pkts: Vec<ArrayBurst<MAX_PKT_BURST>> = (0..MAX_PORTS).map(|_| ArrayBurst::new()).collect();
+    let mut mac_addrs = table.mac_addrs();
+
+    if args.mc_filter {
+        update_mc_filter(enabled_ports, &table);
+    }
+
+    let period = Duration::from_secs(u64::from(args.timer_period));
+    let mut last_expire = Instant::now();
+    let mut last_stats = Instant::now();
+
+    while !FORCE_QUIT.load(Ordering::Relaxed) {
+        let now = Instant::now();
+        let snooped = stats.snooped;
+        let mut expired = 0;
+
+        for &in_port in enabled_ports {
+            let nb_rx = rx_pkts.rx(in_port, 0);
+
+            if nb_rx == 0 {
+                continue;
+            }
+
+            stats.rx += nb_rx;
+
+            for m in rx_pkts.drain() {
+                let out_ports = match mcast_forward(&mut table, enabled_ports, in_port, &m, now, &mut stats) {
+                    Some(ports) => ports,
+                    None => {
+                        stats.dropped += 1;
+
+                        continue;
+                    }
+                };
+
+                // the frame is shared by the output ports, the last one takes the reference
+                let (&last, others) = match out_ports.split_last() {
+                    Some(ports) => ports,
+                    None => {
+                        stats.dropped += 1;
+
+                        continue;
+                    }
+                };
+
+                for &port_id in others {
+                    enqueue(port_id, m.clone(), &mut tx_pkts, &mut stats);
+                }
+
+                enqueue(last, m, &mut tx_pkts, &mut stats);
+            }
+        }
+
+        // drain the TX bursts, so the packets don't stay when the traffic is low
+        for &port_id in enabled_ports {
+            let pkts = &mut tx_pkts[port_id as usize];
+
+            if !pkts.is_empty() {
+                send_burst(port_id, pkts, &mut stats);
+            }
+        }
+
+        if now.duration_since(last_expire) >= EXPIRE_INTERVAL {
+            expired = table.expire(now);
+
+            if expired > 0 {
+                debug!("{} members expired", expired);
+            }
+
+            last_expire = now;
+        }
+
+        // the groups may be changed, update the multicast filter of the ports
+        if args.mc_filter && (stats.snooped != snooped || expired > 0) && table.mac_addrs() != mac_addrs {
+            mac_addrs = table.mac_addrs();
+
+            update_mc_filter(enabled_ports, &table);
+        }
+
+        if args.timer_period > 0 && now.duration_since(last_stats) >= period {
+            print_stats(&table, &stats);
+
+            stats = Stats::default();
+            last_stats = now;
+        }
+    }
+}
+
+fn print_stats(table: &McastGroupTable, stats: &Stats) {
+    println!("\nMulticast groups ====================================");
+
+    for group in table.groups() {
+        let mut members: Vec<PortId> = table.members(group).collect();
+
+        members.sort();
+
+        println!("{:<40} -> ports {:?}", group, members);
+    }
+
+    let mut routers: Vec<PortId> = table.router_ports().collect();
+
+    routers.sort();
+
+    println!("Router ports: {:?}", routers);
+    println!(
+        "Packets rx: {} tx: {} snooped: {} dropped: {}",
+        stats.rx, stats.tx, stats.snooped, stats.dropped
+    );
+    println!("=====================================================");
+}
+
+extern "C" fn handle_sigint(sig: libc::c_int) {
+    match signal::Signal::from_c_int(sig).unwrap() {
+        signal::SIGINT | signal::SIGTERM => {
+            println!("Signal {} received, preparing to exit...", sig);
+
+            FORCE_QUIT.store(true, Ordering::Relaxed);
+        }
+        _ => info!("unexpect signo: {}", sig),
+    }
+}
+
+fn handle_signals() -> nix::Result<()> {
+    let sig_action = signal::SigAction::new(
+        signal::SigHandler::Handler(handle_sigint),
+        signal::SaFlags::empty(),
+        signal::SigSet::empty(),
+    );
+    unsafe {
+        signal::sigaction(signal::SIGINT, &sig_action)?;
+        signal::sigaction(signal::SIGTERM, &sig_action)?;
+    }
+
+    Ok(())
+}
+
+fn prepare_args(args: &mut [String]) -> (Vec<String>, Vec<String>) {
+    let program = String::from(Path::new(&args[0]).file_name().unwrap().to_str().unwrap());
+
+    if let Some(pos) = args.iter().position(|arg| arg == "--") {
+        let (eal_args, opt_args) = args.split_at_mut(pos);
+
+        opt_args[0] = program;
+
+        (eal_args.to_vec(), opt_args.to_vec())
+    } else {
+        (args[..1].to_vec(), args.to_vec())
+    }
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    handle_signals().expect("fail to handle signals");
+
+    let mut args: Vec<String> = env::args().collect();
+
+    let (eal_args, opt_args) = prepare_args(&mut args);
+
+    debug!("eal args: {:?}, mcast args: {:?}", eal_args, opt_args);
+
+    let args = parse_args(&opt_args);
+
+    // init EAL
+    eal::init(&eal_args).expect("fail to initial EAL");
+
+    let enabled_ports: Vec<PortId> = ethdev::devices()
+        .filter(|dev| dev.portid() < MAX_PORTS as PortId && args.enabled_port_mask.contains(dev.portid()))
+        .collect();
+
+    if enabled_ports.len() < 2 {
+        eal::exit(EXIT_FAILURE, "At least 2 ports are needed. Please set portmask.\n");
+    }
+
+    // create the mbuf pool
+    let pktmbuf_pool = mbuf::pool_create(
+        "mbuf_pool",
+        NB_MBUF * enabled_ports.len() as u32,
+        MEMPOOL_CACHE_SIZE,
+        0,
+        mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        rte::socket_id(),
+    )
+    .expect("fail to initial mbuf pool");
+
+    let port_conf = ethdev::EthConf::default();
+
+    // Initialise each port
+    for &portid in &enabled_ports {
+        print!("Initializing port {}... ", portid);
+
+        portid
+            .configure(1, 1, &port_conf)
+            .unwrap_or_else(|_| panic!("fail to configure device: port={}", portid));
+
+        portid
+            .rx_queue_setup(0, RTE_TEST_RX_DESC_DEFAULT, None, pktmbuf_pool.to_ref())
+            .unwrap_or_else(|_| panic!("fail to setup device rx queue: port={}", portid));
+
+        portid
+            .tx_queue_setup(0, RTE_TEST_TX_DESC_DEFAULT, None)
+            .unwrap_or_else(|_| panic!("fail to setup device tx queue: port={}", portid));
+
+        println!(
+            "Done: address {}, driver `{}`",
+            portid.mac_addr(),
+            portid.info().driver_name()
+        );
+    }
+
+    ethdev::start_all(&enabled_ports).expect("fail to start devices");
+
+    // the reports of IGMPv2 and MLDv1 are sent to the groups, which must be received to snoop them
+    if !args.mc_filter {
+        for dev in &enabled_ports {
+            dev.allmulticast_enable();
+        }
+    }
+
+    println!(
+        "MCAST: forwarding on ports {:?}, membership interval {}s",
+        enabled_ports, args.membership_interval
+    );
+
+    mcast_main_loop(&args, &enabled_ports);
+
+    ethdev::stop_all(&enabled_ports);
+
+    for dev in &enabled_ports {
+        print!("Closing port {}...", dev);
+        dev.close();
+        println!(" Done");
+    }
+
+    println!("Bye...");
+}
@@ -0,0 +1,508 @@
+#[macro_use]
+extern crate log;
+extern crate getopts;
+extern crate libc;
+extern crate nix;
+extern crate pretty_env_logger;
+extern crate rte;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, prelude::*};
+use std::mem;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use nix::sys::signal;
+
+use rte::ethdev::EthDevice;
+use rte::ether::{EtherAddr, ETHER_ADDR_LEN};
+use rte::mbuf::MBufPool;
+use rte::utils::{FromRaw, IntoRaw};
+use rte::*;
+
+const EXIT_FAILURE: i32 = -1;
+
+const MAX_PKT_BURST: usize = 32;
+
+const NB_MBUF: u32 = 8192;
+
+const MEMPOOL_CACHE_SIZE: u32 = 256;
+
+const RTE_TEST_RX_DESC_DEFAULT: u16 = 128;
+const RTE_TEST_TX_DESC_DEFAULT: u16 = 512;
+
+// The pcap file format, https://wiki.wireshark.org/Development/LibpcapFileFormat
+const PCAP_MAGIC: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const PCAP_FILE_HDR_LEN: usize = 24;
+const PCAP_PKT_HDR_LEN: usize = 16;
+const LINKTYPE_ETHERNET: u32 = 1;
+
+static FORCE_QUIT: AtomicBool = AtomicBool::new(false);
+
+struct Conf {
+    portid: PortId,
+    pps: Option<u64>,
+    mbps: Option<u64>,
+    loops: u64,
+    src_mac: Option<EtherAddr>,
+    dst_mac: Option<EtherAddr>,
+    vlan: Option<u16>,
+    timer_period: u64,
+    filename: String,
+}
+
+// display usage
+fn print_usage(program: &String, opts: getopts::Options) -> ! {
+    let brief = format!("Usage: {} [EAL options] -- [options] FILE", program);
+
+    print!("{}", opts.usage(&brief));
+
+    process::exit(-1);
+}
+
+// Parse the argument given in the command line of the application
+fn parse_args(args: &[String]) -> Conf {
+    let mut opts = getopts::Options::new();
+    let program = args[0].clone();
+
+    opts.optopt("p", "port", "port to transmit the packets (default is 0)", "PORT");
+    opts.optopt("r", "pps", "transmit rate in packets per second", "PPS");
+    opts.optopt("m", "mbps", "transmit rate in megabits per second", "MBPS");
+    opts.optopt(
+        "l",
+        "loop",
+        "number of times to replay the file (0 to loop forever, 1 default)",
+        "LOOPS",
+    );
+    opts.optopt("s", "src-mac", "rewrite the source MAC address", "MAC");
+    opts.optopt("d", "dst-mac", "rewrite the destination MAC address", "MAC");
+    opts.optopt("v", "vlan", "insert a 802.1q VLAN tag", "TCI");
+    opts.optopt(
+        "T",
+        "",
+        "statistics will be refreshed each PERIOD seconds (0 to disable, 1 default)",
+        "PERIOD",
+    );
+    opts.optflag("h", "help", "print this help menu");
+
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(err) => {
+            println!("Invalid arguments, {}", err);
+
+            print_usage(&program, opts);
+        }
+    };
+
+    if matches.opt_present("h") || matches.free.len() != 1 {
+        print_usage(&program, opts);
+    }
+
+    if matches.opt_present("r") && matches.opt_present("m") {
+        println!("only one of packets or megabits rate could be specified");
+
+        print_usage(&program, opts);
+    }
+
+    macro_rules! parse_opt {
+        ($name:expr, $what:expr, $check:expr) => {
+            match matches.opt_str($name) {
+                Some(arg) => match FromStr::from_str(arg.as_str()) {
+                    Ok(v) if $check(&v) => Some(v),
+                    _ => {
+                        println!("invalid {}, {}", $what, arg);
+
+                        print_usage(&program, opts);
+                    }
+                },
+                None => None,
+            }
+        };
+    }
+
+    Conf {
+        portid: parse_opt!("p", "port", |_: &PortId| true).unwrap_or(0),
+        pps: parse_opt!("r", "packets rate", |&n: &u64| n > 0),
+        mbps: parse_opt!("m", "megabits rate", |&n: &u64| n > 0),
+        loops: parse_opt!("l", "loop count", |_: &u64| true).unwrap_or(1),
+        src_mac: parse_opt!("s", "source MAC address", |_: &EtherAddr| true),
+        dst_mac: parse_opt!("d", "destination MAC address", |_: &EtherAddr| true),
+        vlan: parse_opt!("v", "VLAN tag", |&tci: &u16| tci & 0xfff != 0),
+        timer_period: parse_opt!("T", "timer period", |_: &u64| true).unwrap_or(1),
+        filename: matches.free[0].clone(),
+    }
+}
+
+// Load the Ethernet frames from a pcap file
+fn load_pcap<P: AsRef<Path>>(path: P, max_frame_len: usize) -> io::Result<Vec<Vec<u8>>> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+
+    let mut f = File::open(path)?;
+    let mut hdr = [0u8; PCAP_FILE_HDR_LEN];
+
+    f.read_exact(&mut hdr)?;
+
+    let magic = [hdr[0], hdr[1], hdr[2], hdr[3]];
+    let swapped = match u32::from_le_bytes(magic) {
+        PCAP_MAGIC | PCAP_MAGIC_NANOS => false,
+        _ => match u32::from_be_bytes(magic) {
+            PCAP_MAGIC | PCAP_MAGIC_NANOS => true,
+            _ => return Err(invalid("not a pcap file")),
+        },
+    };
+    let read_u32 = |b: &[u8]| {
+        let b = [b[0], b[1], b[2], b[3]];
+
+        if swapped {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        }
+    };
+
+    let linktype = read_u32(&hdr[20..24]);
+
+    if linktype != LINKTYPE_ETHERNET {
+        return Err(invalid(&format!("unsupported link type {}", linktype)));
+    }
+
+    let mut frames = vec![];
+    let mut hdr = [0u8; PCAP_PKT_HDR_LEN];
+
+    loop {
+        match f.read_exact(&mut hdr) {
+            Ok(_) => {}
+            Err(ref err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err),
+        }
+
+        let caplen = read_u32(&hdr[8..12]) as usize;
+        let mut frame = vec![0; caplen];
+
+        f.read_exact(&mut frame)?;
+
+        if caplen < ETHER_ADDR_LEN * 2 + 2 || caplen > max_frame_len {
+            warn!("skip frame #{} with {} bytes", frames.len(), caplen);
+        } else {
+            frames.push(frame);
+        }
+    }
+
+    Ok(frames)
+}
+
+// Build a packet from the frame, rewrite the MAC addresses and insert the VLAN tag
+fn build_packet(mut pool: mempool::MemPoolRef, frame: &[u8], conf: &Conf) -> Result<mbuf::MBuf> {
+    let mut m = pool.alloc()?;
+
+    unsafe {
+        let p = m.append(frame.len())?.as_ptr();
+
+        p.copy_from_nonoverlapping(frame.as_ptr(), frame.len());
+
+        if let Some(ref dst_mac) = conf.dst_mac {
+            p.copy_from_nonoverlapping(dst_mac.octets().as_ptr(), ETHER_ADDR_LEN);
+        }
+
+        if let Some(ref src_mac) = conf.src_mac {
+            p.add(ETHER_ADDR_LEN)
+                .copy_from_nonoverlapping(src_mac.octets().as_ptr(), ETHER_ADDR_LEN);
+        }
+    }
+
+    if let Some(tci) = conf.vlan {
+        m.vlan_tci = tci;
+
+        let mut p = m.into_raw();
+
+        let res = ether::vlan_insert(&mut p);
+
+        m = mbuf::MBuf::from_raw(p).unwrap();
+
+        res?;
+    }
+
+    Ok(m)
+}
+
+// A tsc-based pacer, which allows to send packets when the credit is earned
+struct Pacer {
+    // cycles per packet, or per byte
+    cycles_per_unit: f64,
+    per_byte: bool,
+    // the credit can't be accumulated more than 1ms
+    max_credit: u64,
+    next_tsc: u64,
+}
+
+impl Pacer {
+    fn new(conf: &Conf) -> Option<Self> {
+        let hz = get_tsc_hz() as f64;
+
+        let (cycles_per_unit, per_byte) = match (conf.pps, conf.mbps) {
+            (Some(pps), _) => (hz / pps as f64, false),
+            (_, Some(mbps)) => (hz * 8.0 / (mbps as f64 * 1_000_000.0), true),
+            _ => return None,
+        };
+
+        Some(Pacer {
+            cycles_per_unit,
+            per_byte,
+            max_credit: get_tsc_hz() / 1000,
+            next_tsc: rdtsc(),
+        })
+    }
+
+    // The number of the frames which could be sent right now
+    fn ready(&self, frames: &[Vec<u8>]) -> usize {
+        let now = rdtsc();
+        let mut tsc = self.next_tsc;
+        let mut n = 0;
+
+        for frame in frames {
+            if tsc > now {
+                break;
+            }
+
+            tsc += self.cost(frame.len());
+            n += 1;
+        }
+
+        n
+    }
+
+    fn sent(&mut self, frames: &[Vec<u8>]) {
+        let now = rdtsc();
+
+        for frame in frames {
+            self.next_tsc += self.cost(frame.len());
+        }
+
+        // don't burst out when the port was stalled
+        if self.next_tsc + self.max_credit < now {
+            self.next_tsc = now - self.max_credit;
+        }
+    }
+
+    fn cost(&self, len: usize) -> u64 {
+        if self.per_byte {
+            (self.cycles_per_unit * len as f64) as u64
+        } else {
+            self.cycles_per_unit as u64
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Stats {
+    tx_pkts: u64,
+    tx_bytes: u64,
+    dropped: u64,
+}
+
+fn print_stats(dev: PortId, stats: &Stats, last: &Stats, elapsed: f64) {
+    let pps = (stats.tx_pkts - last.tx_pkts) as f64 / elapsed;
+    let mbps = (stats.tx_bytes - last.tx_bytes) as f64 * 8.0 / elapsed / 1_000_000.0;
+
+    print!(
+        "\rPort {}: sent {} packets, {} bytes, dropped {}, {:.0} pps, {:.2} Mbps",
+        dev, stats.tx_pkts, stats.tx_bytes, stats.dropped, pps, mbps
+    );
+
+    if let Ok(hw) = dev.stats() {
+        print!(", hw opackets {}, oerrors {}", hw.opackets, hw.oerrors);
+    }
+
+    io::stdout().flush().unwrap();
+}
+
+fn replay(txq: &ethdev::TxQueue, pool: mempool::MemPoolRef, frames: &[Vec<u8>], conf: &Conf) -> Stats {
+    let hz = get_tsc_hz();
+    let timer_period = conf.timer_period * hz;
+
+    let mut pacer = Pacer::new(conf);
+    let mut stats = Stats::default();
+    let mut last_stats = stats;
+    let mut last_tsc = rdtsc();
+    let mut pkts = Vec::with_capacity(MAX_PKT_BURST);
+    let mut round = 0;
+
+    while !FORCE_QUIT.load(Ordering::Relaxed) && (conf.loops == 0 || round < conf.loops) {
+        let mut off = 0;
+
+        while off < frames.len() && !FORCE_QUIT.load(Ordering::Relaxed) {
+            let end = frames.len().min(off + MAX_PKT_BURST);
+            let batch = match pacer {
+                Some(ref pacer) => &frames[off..off + pacer.ready(&frames[off..end])],
+                None => &frames[off..end],
+            };
+
+            for frame in batch {
+                match build_packet(pool, frame, conf) {
+                    Ok(m) => pkts.push(m),
+                    Err(err) => {
+                        debug!("fail to build packet, {}", err);
+
+                        break;
+                    }
+                }
+            }
+
+            let n = txq.tx_burst(&mut pkts);
+
+            for m in pkts.drain(..n) {
+                stats.tx_pkts += 1;
+                stats.tx_bytes += m.pkt_len() as u64;
+
+                mem::forget(m);
+            }
+
+            // the unsent packets are freed and retried later
+            pkts.clear();
+
+            if let Some(ref mut pacer) = pacer {
+                pacer.sent(&frames[off..off + n]);
+            }
+
+            off += n;
+
+            let now = rdtsc();
+
+            if timer_period > 0 && now - last_tsc >= timer_period {
+                print_stats(txq.portid(), &stats, &last_stats, (now - last_tsc) as f64 / hz as f64);
+
+                last_stats = stats;
+                last_tsc = now;
+            }
+        }
+
+        stats.dropped += (frames.len() - off) as u64;
+        round += 1;
+    }
+
+    stats
+}
+
+extern "C" fn handle_sigint(sig: libc::c_int) {
+    match signal::Signal::from_c_int(sig).unwrap() {
+        signal::SIGINT | signal::SIGTERM => {
+            println!("Signal {} received, preparing to exit...", sig);
+
+            FORCE_QUIT.store(true, Ordering::Relaxed);
+        }
+        _ => info!("unexpect signo: {}", sig),
+    }
+}
+
+fn handle_signals() -> nix::Result<()> {
+    let sig_action = signal::SigAction::new(
+        signal::SigHandler::Handler(handle_sigint),
+        signal::SaFlags::empty(),
+        signal::SigSet::empty(),
+    );
+    unsafe {
+        signal::sigaction(signal::SIGINT, &sig_action)?;
+        signal::sigaction(signal::SIGTERM, &sig_action)?;
+    }
+
+    Ok(())
+}
+
+fn prepare_args(args: &mut [String]) -> (Vec<String>, Vec<String>) {
+    let program = String::from(Path::new(&args[0]).file_name().unwrap().to_str().unwrap());
+
+    if let Some(pos) = args.iter().position(|arg| arg == "--") {
+        let (eal_args, opt_args) = args.split_at_mut(pos);
+
+        opt_args[0] = program;
+
+        (eal_args.to_vec(), opt_args.to_vec())
+    } else {
+        (args[..1].to_vec(), args.to_vec())
+    }
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    handle_signals().expect("fail to handle signals");
+
+    let mut args: Vec<String> = env::args().collect();
+
+    let (eal_args, opt_args) = prepare_args(&mut args);
+
+    debug!("eal args: {:?}, pcap-replay args: {:?}", eal_args, opt_args);
+
+    let conf = parse_args(&opt_args);
+
+    // init EAL
+    eal::init(&eal_args).expect("fail to initial EAL");
+
+    let dev = conf.portid;
+
+    if !dev.is_valid() {
+        eal::exit(EXIT_FAILURE, &format!("Invalid port {}.\n", dev));
+    }
+
+    // create the mbuf pool
+    let pool = mbuf::pool_create(
+        "mbuf_pool",
+        NB_MBUF,
+        MEMPOOL_CACHE_SIZE,
+        0,
+        mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        dev.socket_id(),
+    )
+    .expect("fail to create mbuf pool");
+
+    let max_frame_len = mbuf::RTE_MBUF_DEFAULT_DATAROOM as usize - if conf.vlan.is_some() { 4 } else { 0 };
+
+    let frames = load_pcap(&conf.filename, max_frame_len).unwrap_or_else(|_| panic!("fail to load {}", conf.filename));
+
+    println!("Loaded {} frames from {}", frames.len(), conf.filename);
+
+    if frames.is_empty() {
+        eal::exit(EXIT_FAILURE, "Nothing to replay.\n");
+    }
+
+    // init port
+    print!("Initializing port {}... ", dev);
+
+    dev.configure(1, 1, &ethdev::EthConf::default())
+        .unwrap_or_else(|_| panic!("fail to configure device: port={}", dev));
+
+    dev.rx_queue_setup(0, RTE_TEST_RX_DESC_DEFAULT, None, pool.to_ref())
+        .unwrap_or_else(|_| panic!("fail to setup device rx queue: port={}", dev));
+
+    let txq = dev
+        .tx_queue_setup(0, RTE_TEST_TX_DESC_DEFAULT, None)
+        .unwrap_or_else(|_| panic!("fail to setup device tx queue: port={}", dev));
+
+    dev.start()
+        .unwrap_or_else(|_| panic!("fail to start device: port={}", dev));
+
+    println!("Done: ");
+
+    println!("  Port {}, MAC address: {}", dev, dev.mac_addr());
+
+    let started = rdtsc();
+    let stats = replay(&txq, pool.to_ref(), &frames, &conf);
+    let elapsed = (rdtsc() - started) as f64 / get_tsc_hz() as f64;
+
+    println!();
+    println!(
+        "Sent {} packets, {} bytes in {:.3}s, dropped {}",
+        stats.tx_pkts, stats.tx_bytes, elapsed, stats.dropped
+    );
+
+    print!("Closing port {}...", dev);
+    dev.stop();
+    dev.close();
+    println!(" Done");
+
+    println!("Bye...");
+}
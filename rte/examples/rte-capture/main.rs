@@ -0,0 +1,492 @@
+#[macro_use]
+extern crate log;
+extern crate getopts;
+extern crate libc;
+extern crate nix;
+extern crate pretty_env_logger;
+extern crate rte;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, prelude::*, BufWriter};
+use std::path::Path;
+use std::process;
+use std::ptr;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use nix::sys::signal;
+
+use rte::ethdev::EthDevice;
+use rte::memory::Socket;
+use rte::mempool::MemoryPool;
+use rte::pcapng::{Rotation, Writer};
+use rte::pdump::{self, Capture, Direction};
+use rte::ring::{Ring, RingFlags};
+use rte::utils::FromRaw;
+use rte::*;
+
+const EXIT_FAILURE: i32 = -1;
+
+const MAX_PKT_BURST: usize = 32;
+
+const NB_MBUF: u32 = 8192;
+
+const MEMPOOL_CACHE_SIZE: u32 = 256;
+
+const RING_SIZE: usize = 4096;
+
+static FORCE_QUIT: AtomicBool = AtomicBool::new(false);
+
+struct Conf {
+    // the captured ports and queues
+    targets: Vec<(PortId, QueueId)>,
+    flags: Direction,
+    snaplen: u32,
+    rotation: Rotation,
+    max_files: Option<usize>,
+    timer_period: u64,
+    filename: String,
+}
+
+// display usage
+fn print_usage(program: &String, opts: getopts::Options) -> ! {
+    let brief = format!(
+        "Usage: {} [EAL options] -- [options] -w FILE\n\nThe primary process must call `pdump::init()`.",
+        program
+    );
+
+    print!("{}", opts.usage(&brief));
+
+    process::exit(-1);
+}
+
+// Parse a port and an optional queue, e.g. `0` or `0:1`
+fn parse_target(s: &str) -> Option<(PortId, QueueId)> {
+    let mut parts = s.splitn(2, ':');
+    let port_id = parts.next()?.parse().ok()?;
+    let queue_id = match parts.next() {
+        Some(queue_id) => queue_id.parse().ok()?,
+        None => pdump::ALL_QUEUES,
+    };
+
+    Some((port_id, queue_id))
+}
+
+// Parse the argument given in the command line of the application
+fn parse_args(args: &[String]) -> Conf {
+    let mut opts = getopts::Options::new();
+    let program = args[0].clone();
+
+    opts.optmulti(
+        "p",
+        "port",
+        "port and optional queue to capture, e.g. 0 or 0:1 (default is all queues of port 0)",
+        "PORT[:QUEUE]",
+    );
+    opts.optopt(
+        "d",
+        "direction",
+        "direction to capture, rx, tx or rxtx (default)",
+        "DIR",
+    );
+    opts.optopt(
+        "s",
+        "snaplen",
+        "snap length of the packets (0 for 65535, default)",
+        "BYTES",
+    );
+    opts.optopt("w", "write", "write the packets to the pcapng file", "FILE");
+    opts.optopt("C", "", "rotate the file when it's larger than SIZE megabytes", "SIZE");
+    opts.optopt("G", "", "rotate the file every SECS seconds", "SECS");
+    opts.optopt(
+        "W",
+        "",
+        "keep at most COUNT rotated files, the oldest is overwritten",
+        "COUNT",
+    );
+    opts.optopt(
+        "T",
+        "",
+        "statistics will be refreshed each PERIOD seconds (0 to disable, 1 default)",
+        "PERIOD",
+    );
+    opts.optflag("h", "help", "print this help menu");
+
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(err) => {
+            println!("Invalid arguments, {}", err);
+
+            print_usage(&program, opts);
+        }
+    };
+
+    if matches.opt_present("h") || !matches.opt_present("w") {
+        print_usage(&program, opts);
+    }
+
+    macro_rules! parse_opt {
+        ($name:expr, $what:expr, $check:expr) => {
+            match matches.opt_str($name) {
+                Some(arg) => match FromStr::from_str(arg.as_str()) {
+                    Ok(v) if $check(&v) => Some(v),
+                    _ => {
+                        println!("invalid {}, {}", $what, arg);
+
+                        print_usage(&program, opts);
+                    }
+                },
+                None => None,
+            }
+        };
+    }
+
+    let mut targets = vec![];
+
+    for arg in matches.opt_strs("p") {
+        match parse_target(&arg) {
+            Some(target) => targets.push(target),
+            None => {
+                println!("invalid port, {}", arg);
+
+                print_usage(&program, opts);
+            }
+        }
+    }
+
+    if targets.is_empty() {
+        targets.push((0, pdump::ALL_QUEUES));
+    }
+
+    let flags = match matches.opt_str("d").as_deref() {
+        Some("rx") => Direction::RTE_PDUMP_FLAG_RX,
+        Some("tx") => Direction::RTE_PDUMP_FLAG_TX,
+        Some("rxtx") | None => Direction::RTE_PDUMP_FLAG_RXTX,
+        Some(dir) => {
+            println!("invalid direction, {}", dir);
+
+            print_usage(&program, opts);
+        }
+    };
+
+    let mut rotation = Rotation::default();
+
+    if let Some(size) = parse_opt!("C", "file size", |&n: &u64| n > 0) {
+        rotation = rotation.with_max_size(size * 1_000_000);
+    }
+    if let Some(secs) = parse_opt!("G", "rotate seconds", |&n: &u64| n > 0) {
+        rotation = rotation.with_max_duration(Duration::from_secs(secs));
+    }
+
+    Conf {
+        targets,
+        flags,
+        snaplen: parse_opt!("s", "snap length", |&n: &u32| n <= pcapng::MAX_SNAPLEN).unwrap_or(0),
+        rotation,
+        max_files: parse_opt!("W", "file count", |&n: &usize| n > 0),
+        timer_period: parse_opt!("T", "timer period", |_: &u64| true).unwrap_or(1),
+        filename: matches.opt_str("w").unwrap(),
+    }
+}
+
+// A capture of a port and queue in a direction, which is an interface of the pcapng file.
+struct Tap {
+    name: String,
+    ring: Ring,
+    // the capture is disabled before the ring is freed
+    capture: Option<Capture>,
+    packets: u64,
+}
+
+impl Tap {
+    fn open(port_id: PortId, queue_id: QueueId, flags: Direction, pool: &MemoryPool) -> rte::Result<Self> {
+        let dir = if flags == Direction::RTE_PDUMP_FLAG_RX {
+            "rx"
+        } else {
+            "tx"
+        };
+        let name = if queue_id == pdump::ALL_QUEUES {
+            format!("port{}-{}", port_id, dir)
+        } else {
+            format!("port{}-q{}-{}", port_id, queue_id, dir)
+        };
+
+        let ring = ring::create(
+            format!("cap_{}", name),
+            RING_SIZE,
+            port_id.socket_id(),
+            RingFlags::SC_DEQ,
+        )?;
+        let capture = Capture::enable(port_id, queue_id, flags, &ring, pool)?;
+
+        Ok(Tap {
+            name,
+            ring,
+            capture: Some(capture),
+            packets: 0,
+        })
+    }
+
+    fn close(mut self) {
+        self.capture.take();
+
+        // drain the packets copied before the capture was disabled
+        let mut pkts = [ptr::null_mut::<ffi::rte_mbuf>(); MAX_PKT_BURST];
+
+        loop {
+            let n = self.ring.dequeue_burst(&mut pkts);
+
+            if n == 0 {
+                break;
+            }
+
+            for &p in &pkts[..n] {
+                mbuf::MBuf::from_raw(p);
+            }
+        }
+
+        self.ring.free();
+    }
+}
+
+// The pcapng file which is rotated by size or time
+struct Output<'a> {
+    conf: &'a Conf,
+    writer: Writer<BufWriter<File>>,
+    opened: Instant,
+    seq: usize,
+}
+
+impl<'a> Output<'a> {
+    fn create(conf: &'a Conf, taps: &[Tap], seq: usize) -> rte::Result<Self> {
+        let path = if conf.rotation == Rotation::default() {
+            conf.filename.clone()
+        } else {
+            format!("{}{}", conf.filename, seq % conf.max_files.unwrap_or(usize::MAX))
+        };
+
+        let mut writer = Writer::new(BufWriter::new(File::create(&path)?), "rte-capture")?;
+
+        for tap in taps {
+            writer.add_interface(&tap.name, conf.snaplen)?;
+        }
+
+        debug!("writing to {}", path);
+
+        Ok(Output {
+            conf,
+            writer,
+            opened: Instant::now(),
+            seq,
+        })
+    }
+
+    fn rotate(&mut self, taps: &[Tap]) -> rte::Result<()> {
+        if self
+            .conf
+            .rotation
+            .is_due(self.writer.written(), self.opened, Instant::now())
+        {
+            self.writer.flush()?;
+
+            *self = Output::create(self.conf, taps, self.seq + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn print_stats(taps: &[Tap], elapsed: f64, last: u64) -> u64 {
+    let total = taps.iter().map(|tap| tap.packets).sum::<u64>();
+
+    print!(
+        "\rCaptured {} packets, {:.0} pps",
+        total,
+        (total - last) as f64 / elapsed
+    );
+
+    for tap in taps {
+        print!(", {} {}", tap.name, tap.packets);
+    }
+
+    io::stdout().flush().unwrap();
+
+    total
+}
+
+fn capture(conf: &Conf, taps: &mut [Tap]) -> rte::Result<()> {
+    let hz = get_tsc_hz();
+    let timer_period = conf.timer_period * hz;
+
+    let mut output = Output::create(conf, taps, 0)?;
+    let mut pkts = [ptr::null_mut::<ffi::rte_mbuf>(); MAX_PKT_BURST];
+    let mut last_total = 0;
+    let mut last_tsc = rdtsc();
+
+    while !FORCE_QUIT.load(Ordering::Relaxed) {
+        let mut idle = true;
+
+        for (idx, tap) in taps.iter_mut().enumerate() {
+            let n = tap.ring.dequeue_burst(&mut pkts);
+
+            if n == 0 {
+                continue;
+            }
+
+            idle = false;
+
+            let ts = SystemTime::now();
+
+            for p in &mut pkts[..n] {
+                let m = mbuf::MBuf::from_raw(*p).unwrap();
+
+                *p = ptr::null_mut();
+
+                output.writer.write_mbuf(idx as u32, ts, &m)?;
+                tap.packets += 1;
+            }
+        }
+
+        output.rotate(taps)?;
+
+        let now = rdtsc();
+
+        if timer_period > 0 && now - last_tsc >= timer_period {
+            last_total = print_stats(taps, (now - last_tsc) as f64 / hz as f64, last_total);
+            last_tsc = now;
+
+            output.writer.flush()?;
+        }
+
+        if idle {
+            // nothing was captured, don't spin on the rings
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    output.writer.flush()
+}
+
+extern "C" fn handle_sigint(sig: libc::c_int) {
+    match signal::Signal::from_c_int(sig).unwrap() {
+        signal::SIGINT | signal::SIGTERM => {
+            println!("Signal {} received, preparing to exit...", sig);
+
+            FORCE_QUIT.store(true, Ordering::Relaxed);
+        }
+        _ => info!("unexpect signo: {}", sig),
+    }
+}
+
+fn handle_signals() -> nix::Result<()> {
+    let sig_action = signal::SigAction::new(
+        signal::SigHandler::Handler(handle_sigint),
+        signal::SaFlags::empty(),
+        signal::SigSet::empty(),
+    );
+    unsafe {
+        signal::sigaction(signal::SIGINT, &sig_action)?;
+        signal::sigaction(signal::SIGTERM, &sig_action)?;
+    }
+
+    Ok(())
+}
+
+fn prepare_args(args: &mut [String]) -> (Vec<String>, Vec<String>) {
+    let program = String::from(Path::new(&args[0]).file_name().unwrap().to_str().unwrap());
+
+    let (mut eal_args, opt_args) = if let Some(pos) = args.iter().position(|arg| arg == "--") {
+        let (eal_args, opt_args) = args.split_at_mut(pos);
+
+        opt_args[0] = program;
+
+        (eal_args.to_vec(), opt_args.to_vec())
+    } else {
+        (args[..1].to_vec(), args.to_vec())
+    };
+
+    // always attach to the running primary process
+    if !eal_args.iter().any(|arg| arg.starts_with("--proc-type")) {
+        eal_args.push("--proc-type=secondary".to_owned());
+    }
+
+    (eal_args, opt_args)
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    handle_signals().expect("fail to handle signals");
+
+    let mut args: Vec<String> = env::args().collect();
+
+    let (eal_args, opt_args) = prepare_args(&mut args);
+
+    debug!("eal args: {:?}, rte-capture args: {:?}", eal_args, opt_args);
+
+    let conf = parse_args(&opt_args);
+
+    // init EAL
+    eal::init(&eal_args).expect("fail to initial EAL");
+
+    if eal::process_type() != eal::ProcType::Secondary {
+        eal::exit(EXIT_FAILURE, "rte-capture must run as a secondary process.\n");
+    }
+
+    for &(port_id, _) in &conf.targets {
+        if !port_id.is_valid() {
+            eal::exit(EXIT_FAILURE, &format!("Invalid port {}.\n", port_id));
+        }
+    }
+
+    // the captured packets are copied to the mbufs of the pool, which is shared with the primary process
+    let pool = mbuf::pool_create(
+        "capture_pool",
+        NB_MBUF,
+        MEMPOOL_CACHE_SIZE,
+        0,
+        mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        Socket::current(),
+    )
+    .expect("fail to create mbuf pool");
+
+    let mut taps = vec![];
+
+    for &(port_id, queue_id) in &conf.targets {
+        for &flags in &[Direction::RTE_PDUMP_FLAG_RX, Direction::RTE_PDUMP_FLAG_TX] {
+            if !conf.flags.contains(flags) {
+                continue;
+            }
+
+            match Tap::open(port_id, queue_id, flags, &pool) {
+                Ok(tap) => {
+                    println!("Capturing {}", tap.name);
+
+                    taps.push(tap)
+                }
+                Err(err) => eal::exit(
+                    EXIT_FAILURE,
+                    &format!("fail to enable capture on port {}, {}\n", port_id, err),
+                ),
+            }
+        }
+    }
+
+    if let Err(err) = capture(&conf, &mut taps) {
+        println!();
+        println!("fail to capture, {}", err);
+    }
+
+    println!();
+
+    for tap in taps {
+        println!("  {}: {} packets", tap.name, tap.packets);
+
+        tap.close();
+    }
+
+    println!("Bye...");
+}
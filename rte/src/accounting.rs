@@ -0,0 +1,856 @@
+//!
+//! Per-flow byte and packet accounting.
+//!
+//! The packets are accounted per flow, keyed by their 5-tuple, and the mark of the matched
+//! rte_flow rule, e.g. a `FlowAction::Mark` rule of the `flow::FlowTable`, is kept with the flow,
+//! so the usage could be attributed to the rules installed on the port.
+//!
+//! The accounting table is owned by a polling lcore and updated on the fast path without any lock,
+//! the lcore should `tick` it periodically, which
+//!
+//! - calculates the per-flow bitrates with the same EWMA as the rte_bitrate library,
+//! - expires the idle flows and reports the long-lived ones,
+//! - updates the registered metrics.
+//!
+//! The returned records could be sent to a NetFlow/IPFIX collector with an `IpfixExporter`.
+//!
+//! ```no_run
+//! use std::time::{Duration, SystemTime};
+//!
+//! use rte::accounting::{AccountingConfig, FlowAccounting, IpfixExporter};
+//! use rte::*;
+//!
+//! let mut table = FlowAccounting::new(AccountingConfig::default());
+//! let mut exporter = IpfixExporter::new("127.0.0.1:4739", 1).unwrap();
+//! let mut last_tick = SystemTime::now();
+//!
+//! loop {
+//!     let pkts: Vec<mbuf::MBuf> = vec![]; // received from a port
+//!
+//!     table.account_burst(&pkts);
+//!
+//!     let now = SystemTime::now();
+//!
+//!     if now.duration_since(last_tick).unwrap_or_default() >= Duration::from_secs(1) {
+//!         let records = table.tick(now);
+//!
+//!         exporter.export(&records, now).unwrap();
+//!         last_tick = now;
+//!     }
+//! }
+//! ```
+//!
+use std::collections::hash_map::{Entry, HashMap};
+use std::io;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::ptr::NonNull;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ffi;
+
+use errors::{rte_error, Result, RteError};
+use ethdev::PortId;
+use filter::{dissect_mbuf, Dissection};
+use mbuf::MBuf;
+use metrics::{Metric, Registry};
+
+/// The weight of the latest sample in the EWMA bitrate, in percent, the same as rte_bitrate.
+const EWMA_ALPHA_PERCENT: u64 = 20;
+
+/// The key of an accounted flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    /// The source IP address.
+    pub src: IpAddr,
+    /// The destination IP address.
+    pub dst: IpAddr,
+    /// The IP protocol.
+    pub proto: u8,
+    /// The source port of TCP, UDP or SCTP, or zero.
+    pub src_port: u16,
+    /// The destination port of TCP, UDP or SCTP, or zero.
+    pub dst_port: u16,
+}
+
+impl FlowKey {
+    /// The key of the dissected packet, the non-IP packets have no key.
+    pub fn from_dissection(d: &Dissection) -> Option<FlowKey> {
+        match (d.src_ip, d.dst_ip) {
+            (Some(src), Some(dst)) => Some(FlowKey {
+                src,
+                dst,
+                proto: d.proto.unwrap_or(0),
+                src_port: d.src_port.unwrap_or(0),
+                dst_port: d.dst_port.unwrap_or(0),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The counters of an accounted flow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowCounters {
+    /// The number of packets.
+    pub packets: u64,
+    /// The number of bytes.
+    pub bytes: u64,
+    /// The mark of the matched rte_flow rule, if any.
+    pub mark: Option<u32>,
+    /// The time of the first packet.
+    pub first_seen: SystemTime,
+    /// The time of the last packet.
+    pub last_seen: SystemTime,
+    /// The EWMA bitrate of the flow, in bits per second, updated at each tick.
+    pub ewma_bps: u64,
+    /// The peak bitrate of the flow, in bits per second, updated at each tick.
+    pub peak_bps: u64,
+    ticked_bytes: u64,
+    exported_packets: u64,
+    exported_bytes: u64,
+    exported_at: SystemTime,
+}
+
+impl FlowCounters {
+    fn new(mark: Option<u32>, now: SystemTime) -> Self {
+        FlowCounters {
+            packets: 0,
+            bytes: 0,
+            mark,
+            first_seen: now,
+            last_seen: now,
+            ewma_bps: 0,
+            peak_bps: 0,
+            ticked_bytes: 0,
+            exported_packets: 0,
+            exported_bytes: 0,
+            exported_at: now,
+        }
+    }
+
+    fn record(&mut self, key: FlowKey, reason: EndReason) -> FlowRecord {
+        let record = FlowRecord {
+            key,
+            mark: self.mark,
+            packets: self.packets - self.exported_packets,
+            bytes: self.bytes - self.exported_bytes,
+            start: self.first_seen,
+            end: self.last_seen,
+            reason,
+        };
+
+        self.exported_packets = self.packets;
+        self.exported_bytes = self.bytes;
+        self.exported_at = self.last_seen;
+
+        record
+    }
+}
+
+/// The reason of a flow record, as the IPFIX `flowEndReason`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EndReason {
+    /// The flow was idle for the idle timeout.
+    IdleTimeout = 1,
+    /// The flow was active for the active timeout, and is still accounted.
+    ActiveTimeout = 2,
+    /// The flow was flushed.
+    ForcedEnd = 4,
+}
+
+/// The counters of a flow, since the previous record of the same flow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowRecord {
+    /// The key of the flow.
+    pub key: FlowKey,
+    /// The mark of the matched rte_flow rule, if any.
+    pub mark: Option<u32>,
+    /// The number of packets since the previous record.
+    pub packets: u64,
+    /// The number of bytes since the previous record.
+    pub bytes: u64,
+    /// The time of the first packet of the flow.
+    pub start: SystemTime,
+    /// The time of the last packet of the flow.
+    pub end: SystemTime,
+    /// The reason of the record.
+    pub reason: EndReason,
+}
+
+/// The timeouts and the capacity of the accounting table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccountingConfig {
+    /// The maximum number of flows, the packets of the new flows are not accounted when the table is full.
+    pub max_flows: usize,
+    /// A flow without packet for the idle timeout is expired.
+    pub idle_timeout: Duration,
+    /// A flow is reported every active timeout while it is active.
+    pub active_timeout: Duration,
+}
+
+impl Default for AccountingConfig {
+    fn default() -> Self {
+        AccountingConfig {
+            max_flows: 65536,
+            idle_timeout: Duration::from_secs(15),
+            active_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The counters of the accounting table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountingStats {
+    /// The number of accounted packets.
+    pub packets: u64,
+    /// The number of accounted bytes.
+    pub bytes: u64,
+    /// The number of non-IP packets, which are not accounted.
+    pub unclassified: u64,
+    /// The number of packets which are not accounted because the table is full.
+    pub overflows: u64,
+    /// The number of flows created.
+    pub flows: u64,
+    /// The number of flow records.
+    pub records: u64,
+}
+
+struct AccountingMetrics {
+    flows: Arc<Metric>,
+    packets: Arc<Metric>,
+    bytes: Arc<Metric>,
+    overflows: Arc<Metric>,
+    records: Arc<Metric>,
+    bitrate: Arc<Metric>,
+}
+
+/// A per-flow accounting table.
+pub struct FlowAccounting {
+    conf: AccountingConfig,
+    flows: HashMap<FlowKey, FlowCounters>,
+    stats: AccountingStats,
+    last_tick: Option<SystemTime>,
+    metrics: Option<AccountingMetrics>,
+}
+
+impl FlowAccounting {
+    /// Create an empty accounting table.
+    pub fn new(conf: AccountingConfig) -> Self {
+        FlowAccounting {
+            conf,
+            flows: HashMap::new(),
+            stats: AccountingStats::default(),
+            last_tick: None,
+            metrics: None,
+        }
+    }
+
+    /// Export the number of flows, the accounted packets and bytes and the total bitrate as metrics.
+    pub fn register_metrics(&mut self, registry: &Registry, labels: &[(&str, &str)]) -> &mut Self {
+        self.metrics = Some(AccountingMetrics {
+            flows: registry.gauge("rte_flow_accounting_flows", "The number of accounted flows.", labels),
+            packets: registry.counter(
+                "rte_flow_accounting_packets_total",
+                "The number of accounted packets.",
+                labels,
+            ),
+            bytes: registry.counter(
+                "rte_flow_accounting_bytes_total",
+                "The number of accounted bytes.",
+                labels,
+            ),
+            overflows: registry.counter(
+                "rte_flow_accounting_overflows_total",
+                "The number of packets not accounted because the table is full.",
+                labels,
+            ),
+            records: registry.counter(
+                "rte_flow_accounting_records_total",
+                "The number of flow records.",
+                labels,
+            ),
+            bitrate: registry.gauge(
+                "rte_flow_accounting_bitrate_bps",
+                "The sum of the EWMA bitrates of the accounted flows.",
+                labels,
+            ),
+        });
+
+        self.update_metrics();
+        self
+    }
+
+    /// The timeouts and the capacity of the table.
+    pub fn config(&self) -> &AccountingConfig {
+        &self.conf
+    }
+
+    /// The counters of the table.
+    pub fn stats(&self) -> &AccountingStats {
+        &self.stats
+    }
+
+    /// The number of accounted flows.
+    pub fn len(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// The table has no flow.
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+
+    /// The counters of a flow.
+    pub fn get(&self, key: &FlowKey) -> Option<&FlowCounters> {
+        self.flows.get(key)
+    }
+
+    /// An iterator over the accounted flows.
+    pub fn flows(&self) -> impl Iterator<Item = (&FlowKey, &FlowCounters)> {
+        self.flows.iter()
+    }
+
+    /// Account a packet.
+    ///
+    /// Returns `false` if the packet is not accounted, because it isn't an IP packet or the table is full.
+    pub fn account(&mut self, m: &MBuf, now: SystemTime) -> bool {
+        match dissect_mbuf(m).as_ref().and_then(FlowKey::from_dissection) {
+            Some(key) => self.update(key, m.fdir_id(), m.pkt_len(), now),
+            None => {
+                self.stats.unclassified += 1;
+
+                false
+            }
+        }
+    }
+
+    /// Account a burst of packets, returns the number of accounted packets.
+    pub fn account_burst(&mut self, pkts: &[MBuf]) -> usize {
+        if pkts.is_empty() {
+            return 0;
+        }
+
+        let now = SystemTime::now();
+
+        pkts.iter().filter(|m| self.account(m, now)).count()
+    }
+
+    /// Account the bytes of a flow.
+    ///
+    /// Returns `false` if the flow is new and the table is full.
+    pub fn update(&mut self, key: FlowKey, mark: Option<u32>, len: usize, now: SystemTime) -> bool {
+        let len = len as u64;
+        let full = self.flows.len() >= self.conf.max_flows;
+        let counters = match self.flows.entry(key) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(_) if full => {
+                self.stats.overflows += 1;
+
+                return false;
+            }
+            Entry::Vacant(e) => {
+                self.stats.flows += 1;
+
+                e.insert(FlowCounters::new(mark, now))
+            }
+        };
+
+        counters.packets += 1;
+        counters.bytes += len;
+        counters.last_seen = now;
+
+        if mark.is_some() {
+            counters.mark = mark;
+        }
+
+        self.stats.packets += 1;
+        self.stats.bytes += len;
+
+        true
+    }
+
+    /// Update the bitrates, expire the idle flows and report the active ones.
+    ///
+    /// It should be called periodically, the interval between the ticks is the bitrate sampling window.
+    pub fn tick(&mut self, now: SystemTime) -> Vec<FlowRecord> {
+        let elapsed = self
+            .last_tick
+            .and_then(|last| now.duration_since(last).ok())
+            .unwrap_or_default();
+        let elapsed_us = elapsed.as_micros() as u64;
+        let conf = self.conf;
+        let mut records = vec![];
+
+        self.last_tick = Some(now);
+
+        self.flows.retain(|&key, counters| {
+            if let Some(bps) = ((counters.bytes - counters.ticked_bytes) * 8 * 1_000_000).checked_div(elapsed_us) {
+                counters.ewma_bps = if counters.ewma_bps == 0 {
+                    bps
+                } else {
+                    (bps * EWMA_ALPHA_PERCENT + counters.ewma_bps * (100 - EWMA_ALPHA_PERCENT)) / 100
+                };
+                counters.peak_bps = counters.peak_bps.max(bps);
+            }
+            counters.ticked_bytes = counters.bytes;
+
+            let idle = now.duration_since(counters.last_seen).unwrap_or_default();
+
+            if idle >= conf.idle_timeout {
+                if counters.packets > counters.exported_packets {
+                    records.push(counters.record(key, EndReason::IdleTimeout));
+                }
+
+                false
+            } else {
+                let active = now.duration_since(counters.exported_at).unwrap_or_default();
+
+                if active >= conf.active_timeout && counters.packets > counters.exported_packets {
+                    records.push(counters.record(key, EndReason::ActiveTimeout));
+                }
+
+                true
+            }
+        });
+
+        self.stats.records += records.len() as u64;
+        self.update_metrics();
+
+        records
+    }
+
+    /// Remove all the flows, and report their remaining counters.
+    pub fn flush(&mut self) -> Vec<FlowRecord> {
+        let records = self
+            .flows
+            .drain()
+            .filter(|(_, counters)| counters.packets > counters.exported_packets)
+            .map(|(key, mut counters)| counters.record(key, EndReason::ForcedEnd))
+            .collect::<Vec<_>>();
+
+        self.stats.records += records.len() as u64;
+        self.update_metrics();
+
+        records
+    }
+
+    fn update_metrics(&self) {
+        if let Some(ref metrics) = self.metrics {
+            metrics.flows.set(self.flows.len() as u64);
+            metrics.packets.set(self.stats.packets);
+            metrics.bytes.set(self.stats.bytes);
+            metrics.overflows.set(self.stats.overflows);
+            metrics.records.set(self.stats.records);
+            metrics.bitrate.set(self.flows.values().map(|c| c.ewma_bps).sum());
+        }
+    }
+}
+
+/// The port bitrates calculated by the rte_bitrate library.
+///
+/// The mean, EWMA and peak bitrates of the ports are published to the rte_metrics library,
+/// which must be initialized with `metrics::init` first, and are exported by `metrics::Registry`.
+pub struct PortBitrate(NonNull<ffi::rte_stats_bitrates>);
+
+impl PortBitrate {
+    /// Allocate the bitrate statistics and register them with the rte_metrics library.
+    pub fn new() -> Result<PortBitrate> {
+        let p = NonNull::new(unsafe { ffi::rte_stats_bitrate_create() }).ok_or_else(rte_error)?;
+        let ret = unsafe { ffi::rte_stats_bitrate_reg(p.as_ptr()) };
+
+        if ret < 0 {
+            Err(RteError(-ret).into())
+        } else {
+            Ok(PortBitrate(p))
+        }
+    }
+
+    /// Calculate the bitrates of a port, the interval between the calls is the sampling window.
+    pub fn calc(&mut self, port_id: PortId) -> Result<()> {
+        let ret = unsafe { ffi::rte_stats_bitrate_calc(self.0.as_ptr(), port_id) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+}
+
+/// The IANA information elements of the flow records.
+mod ie {
+    pub const OCTET_DELTA_COUNT: u16 = 1;
+    pub const PACKET_DELTA_COUNT: u16 = 2;
+    pub const PROTOCOL_IDENTIFIER: u16 = 4;
+    pub const SOURCE_TRANSPORT_PORT: u16 = 7;
+    pub const SOURCE_IPV4_ADDRESS: u16 = 8;
+    pub const DESTINATION_TRANSPORT_PORT: u16 = 11;
+    pub const DESTINATION_IPV4_ADDRESS: u16 = 12;
+    pub const SOURCE_IPV6_ADDRESS: u16 = 27;
+    pub const DESTINATION_IPV6_ADDRESS: u16 = 28;
+    pub const FLOW_END_REASON: u16 = 136;
+    pub const FLOW_START_MILLISECONDS: u16 = 152;
+    pub const FLOW_END_MILLISECONDS: u16 = 153;
+}
+
+/// The IPFIX protocol version, a.k.a. NetFlow v10.
+pub const IPFIX_VERSION: u16 = 10;
+
+const IPFIX_HDR_LEN: usize = 16;
+const SET_HDR_LEN: usize = 4;
+const TEMPLATE_SET_ID: u16 = 2;
+const IPV4_TEMPLATE_ID: u16 = 256;
+const IPV6_TEMPLATE_ID: u16 = 257;
+
+const fn template(src: u16, dst: u16, addr_len: u16) -> [(u16, u16); 10] {
+    [
+        (src, addr_len),
+        (dst, addr_len),
+        (ie::PROTOCOL_IDENTIFIER, 1),
+        (ie::SOURCE_TRANSPORT_PORT, 2),
+        (ie::DESTINATION_TRANSPORT_PORT, 2),
+        (ie::OCTET_DELTA_COUNT, 8),
+        (ie::PACKET_DELTA_COUNT, 8),
+        (ie::FLOW_START_MILLISECONDS, 8),
+        (ie::FLOW_END_MILLISECONDS, 8),
+        (ie::FLOW_END_REASON, 1),
+    ]
+}
+
+const IPV4_TEMPLATE: [(u16, u16); 10] = template(ie::SOURCE_IPV4_ADDRESS, ie::DESTINATION_IPV4_ADDRESS, 4);
+const IPV6_TEMPLATE: [(u16, u16); 10] = template(ie::SOURCE_IPV6_ADDRESS, ie::DESTINATION_IPV6_ADDRESS, 16);
+
+fn record_len(fields: &[(u16, u16)]) -> usize {
+    fields.iter().map(|&(_, len)| len as usize).sum()
+}
+
+fn millis(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// Encode the flow records into IPFIX messages.
+#[derive(Clone, Debug)]
+struct IpfixEncoder {
+    domain_id: u32,
+    max_len: usize,
+    sequence: u32,
+}
+
+impl IpfixEncoder {
+    fn new(domain_id: u32, max_len: usize) -> Self {
+        IpfixEncoder {
+            domain_id,
+            max_len,
+            sequence: 0,
+        }
+    }
+
+    /// Encode the records into the messages no longer than the maximum length,
+    /// the templates are sent ahead of the data sets of the first message.
+    fn encode(&mut self, records: &[FlowRecord], now: SystemTime, with_templates: bool) -> Vec<Vec<u8>> {
+        let mut messages = vec![];
+        let mut msg = self.begin(now);
+        let mut set: Option<(u16, usize)> = None;
+
+        if with_templates {
+            msg.extend_from_slice(&TEMPLATE_SET_ID.to_be_bytes());
+            msg.extend_from_slice(&0u16.to_be_bytes());
+
+            for &(id, fields) in &[(IPV4_TEMPLATE_ID, &IPV4_TEMPLATE), (IPV6_TEMPLATE_ID, &IPV6_TEMPLATE)] {
+                msg.extend_from_slice(&id.to_be_bytes());
+                msg.extend_from_slice(&(fields.len() as u16).to_be_bytes());
+
+                for &(ie, len) in fields.iter() {
+                    msg.extend_from_slice(&ie.to_be_bytes());
+                    msg.extend_from_slice(&len.to_be_bytes());
+                }
+            }
+
+            let len = msg.len() - IPFIX_HDR_LEN;
+
+            msg[IPFIX_HDR_LEN + 2..IPFIX_HDR_LEN + 4].copy_from_slice(&(len as u16).to_be_bytes());
+        }
+
+        for record in records {
+            let (template_id, len) = match record.key.src {
+                IpAddr::V4(_) => (IPV4_TEMPLATE_ID, record_len(&IPV4_TEMPLATE)),
+                IpAddr::V6(_) => (IPV6_TEMPLATE_ID, record_len(&IPV6_TEMPLATE)),
+            };
+            let same_set = set.is_some_and(|(id, _)| id == template_id);
+            let needed = len + if same_set { 0 } else { SET_HDR_LEN };
+
+            if msg.len() + needed > self.max_len && msg.len() > IPFIX_HDR_LEN {
+                Self::end_set(&mut msg, set.take());
+                messages.push(self.finish(msg));
+                msg = self.begin(now);
+            }
+
+            if set.is_none_or(|(id, _)| id != template_id) {
+                Self::end_set(&mut msg, set.take());
+
+                set = Some((template_id, msg.len()));
+                msg.extend_from_slice(&template_id.to_be_bytes());
+                msg.extend_from_slice(&0u16.to_be_bytes());
+            }
+
+            Self::encode_record(&mut msg, record);
+            self.sequence = self.sequence.wrapping_add(1);
+        }
+
+        if msg.len() > IPFIX_HDR_LEN {
+            Self::end_set(&mut msg, set.take());
+            messages.push(self.finish(msg));
+        }
+
+        messages
+    }
+
+    fn begin(&self, now: SystemTime) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(self.max_len);
+
+        msg.extend_from_slice(&IPFIX_VERSION.to_be_bytes());
+        msg.extend_from_slice(&0u16.to_be_bytes());
+        msg.extend_from_slice(&((millis(now) / 1000) as u32).to_be_bytes());
+        // the sequence number is the number of data records sent before this message
+        msg.extend_from_slice(&self.sequence.to_be_bytes());
+        msg.extend_from_slice(&self.domain_id.to_be_bytes());
+
+        msg
+    }
+
+    fn end_set(msg: &mut [u8], set: Option<(u16, usize)>) {
+        if let Some((_, off)) = set {
+            let len = (msg.len() - off) as u16;
+
+            msg[off + 2..off + 4].copy_from_slice(&len.to_be_bytes());
+        }
+    }
+
+    fn finish(&self, mut msg: Vec<u8>) -> Vec<u8> {
+        let len = msg.len() as u16;
+
+        msg[2..4].copy_from_slice(&len.to_be_bytes());
+        msg
+    }
+
+    fn encode_record(msg: &mut Vec<u8>, record: &FlowRecord) {
+        let key = &record.key;
+
+        match (key.src, key.dst) {
+            (IpAddr::V4(src), IpAddr::V4(dst)) => {
+                msg.extend_from_slice(&src.octets());
+                msg.extend_from_slice(&dst.octets());
+            }
+            (src, dst) => {
+                msg.extend_from_slice(&to_ipv6(src).octets());
+                msg.extend_from_slice(&to_ipv6(dst).octets());
+            }
+        }
+
+        msg.push(key.proto);
+        msg.extend_from_slice(&key.src_port.to_be_bytes());
+        msg.extend_from_slice(&key.dst_port.to_be_bytes());
+        msg.extend_from_slice(&record.bytes.to_be_bytes());
+        msg.extend_from_slice(&record.packets.to_be_bytes());
+        msg.extend_from_slice(&millis(record.start).to_be_bytes());
+        msg.extend_from_slice(&millis(record.end).to_be_bytes());
+        msg.push(record.reason as u8);
+    }
+}
+
+fn to_ipv6(addr: IpAddr) -> Ipv6Addr {
+    match addr {
+        IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+        IpAddr::V6(addr) => addr,
+    }
+}
+
+/// Send the flow records to a NetFlow/IPFIX (v10) collector over UDP.
+pub struct IpfixExporter {
+    socket: UdpSocket,
+    encoder: IpfixEncoder,
+    template_interval: Duration,
+    template_sent: Option<SystemTime>,
+}
+
+impl IpfixExporter {
+    /// The default maximum length of the messages, which fits in the ethernet MTU.
+    pub const MAX_MESSAGE_LEN: usize = 1400;
+
+    /// Create an exporter to the collector, with the observation domain id.
+    pub fn new<A: ToSocketAddrs>(collector: A, domain_id: u32) -> Result<IpfixExporter> {
+        let mut last_err = None;
+
+        for addr in collector.to_socket_addrs()? {
+            let local: SocketAddr = match addr {
+                SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+                SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+            };
+
+            match UdpSocket::bind(local).and_then(|socket| socket.connect(addr).map(|_| socket)) {
+                Ok(socket) => {
+                    return Ok(IpfixExporter {
+                        socket,
+                        encoder: IpfixEncoder::new(domain_id, Self::MAX_MESSAGE_LEN),
+                        template_interval: Duration::from_secs(60),
+                        template_sent: None,
+                    })
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err
+            .unwrap_or_else(|| io::ErrorKind::AddrNotAvailable.into())
+            .into())
+    }
+
+    /// Set the maximum length of the messages.
+    pub fn with_max_message_len(mut self, max_len: usize) -> Self {
+        self.encoder.max_len = max_len;
+        self
+    }
+
+    /// Set the interval to resend the templates, the collectors forget the templates after a while.
+    pub fn with_template_interval(mut self, interval: Duration) -> Self {
+        self.template_interval = interval;
+        self
+    }
+
+    /// The address of the collector.
+    pub fn collector(&self) -> Result<SocketAddr> {
+        Ok(self.socket.peer_addr()?)
+    }
+
+    /// Send the records, returns the number of messages sent.
+    pub fn export(&mut self, records: &[FlowRecord], now: SystemTime) -> Result<usize> {
+        let with_templates = self
+            .template_sent
+            .is_none_or(|sent| now.duration_since(sent).unwrap_or_default() >= self.template_interval);
+
+        if records.is_empty() && !with_templates {
+            return Ok(0);
+        }
+
+        let messages = self.encoder.encode(records, now, with_templates);
+
+        for msg in &messages {
+            self.socket.send(msg)?;
+        }
+
+        if with_templates {
+            self.template_sent = Some(now);
+        }
+
+        Ok(messages.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn key(src: u8) -> FlowKey {
+        FlowKey {
+            src: Ipv4Addr::new(10, 0, 0, src).into(),
+            dst: Ipv4Addr::new(10, 0, 0, 1).into(),
+            proto: 17,
+            src_port: 1234,
+            dst_port: 53,
+        }
+    }
+
+    #[test]
+    fn test_accounting() {
+        let conf = AccountingConfig {
+            max_flows: 2,
+            idle_timeout: Duration::from_secs(10),
+            active_timeout: Duration::from_secs(30),
+        };
+        let mut table = FlowAccounting::new(conf);
+        let t0 = UNIX_EPOCH + Duration::from_secs(1000);
+
+        assert!(table.update(key(2), None, 100, t0));
+        assert!(table.update(key(2), Some(7), 100, t0));
+        assert!(table.update(key(3), None, 1000, t0));
+        assert!(!table.update(key(4), None, 1000, t0));
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.stats().overflows, 1);
+        assert_eq!(table.get(&key(2)).unwrap().mark, Some(7));
+        assert!(table.tick(t0).is_empty());
+
+        // 1000 bytes per second
+        assert!(table.update(key(3), None, 1000, t0 + Duration::from_secs(1)));
+        assert!(table.tick(t0 + Duration::from_secs(1)).is_empty());
+        assert_eq!(table.get(&key(3)).unwrap().ewma_bps, 8000);
+        assert!(table.update(key(3), None, 500, t0 + Duration::from_secs(5)));
+
+        let records = table.tick(t0 + Duration::from_secs(12));
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].key, key(2));
+        assert_eq!(records[0].packets, 2);
+        assert_eq!(records[0].bytes, 200);
+        assert_eq!(records[0].reason, EndReason::IdleTimeout);
+        assert_eq!(table.len(), 1);
+
+        assert!(table.update(key(3), None, 500, t0 + Duration::from_secs(31)));
+
+        let records = table.tick(t0 + Duration::from_secs(31));
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].packets, 4);
+        assert_eq!(records[0].bytes, 3000);
+        assert_eq!(records[0].reason, EndReason::ActiveTimeout);
+
+        assert!(table.update(key(3), None, 500, t0 + Duration::from_secs(32)));
+
+        let records = table.flush();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].packets, 1);
+        assert_eq!(records[0].bytes, 500);
+        assert_eq!(records[0].reason, EndReason::ForcedEnd);
+        assert!(table.is_empty());
+        assert_eq!(table.stats().records, 3);
+    }
+
+    #[test]
+    fn test_ipfix_encode() {
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+        let record = FlowRecord {
+            key: key(2),
+            mark: None,
+            packets: 2,
+            bytes: 200,
+            start: now,
+            end: now,
+            reason: EndReason::IdleTimeout,
+        };
+        let ipv4_len = record_len(&IPV4_TEMPLATE);
+
+        assert_eq!(ipv4_len, 46);
+
+        let mut encoder = IpfixEncoder::new(1, IPFIX_HDR_LEN + SET_HDR_LEN + ipv4_len * 2);
+        let messages = encoder.encode(&[record; 3], now, false);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].len(), IPFIX_HDR_LEN + SET_HDR_LEN + ipv4_len * 2);
+        assert_eq!(&messages[0][..4], &[0, 10, 0, 112]);
+        assert_eq!(&messages[0][4..8], &1000u32.to_be_bytes());
+        assert_eq!(&messages[0][8..12], &0u32.to_be_bytes());
+        assert_eq!(&messages[0][16..20], &[1, 0, 0, 96]);
+        assert_eq!(&messages[0][20..24], &[10, 0, 0, 2]);
+        assert_eq!(&messages[1][8..12], &2u32.to_be_bytes());
+        assert_eq!(messages[1].len(), IPFIX_HDR_LEN + SET_HDR_LEN + ipv4_len);
+
+        let mut encoder = IpfixEncoder::new(1, IpfixExporter::MAX_MESSAGE_LEN);
+        let messages = encoder.encode(&[], now, true);
+
+        assert_eq!(messages.len(), 1);
+        // 2 templates of 10 fields
+        assert_eq!(messages[0].len(), IPFIX_HDR_LEN + SET_HDR_LEN + 2 * (4 + 10 * 4));
+        assert_eq!(&messages[0][16..20], &[0, 2, 0, 92]);
+    }
+}
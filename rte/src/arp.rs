@@ -1,5 +1,8 @@
 use ffi;
 
+use common::byteorder::{be16, be32};
+use mbuf;
+
 pub use ffi::{
     ARP_HRD_ETHER, ARP_OP_INVREPLY, ARP_OP_INVREQUEST, ARP_OP_REPLY, ARP_OP_REQUEST, ARP_OP_REVREPLY, ARP_OP_REVREQUEST,
 };
@@ -9,3 +12,78 @@ pub type ArpIpv4 = ffi::arp_ipv4;
 
 /// ARP header.
 pub type ArpHdr = ffi::arp_hdr;
+
+/// ARP opcode request, in network byte order.
+pub const ARP_OP_REQUEST_BE: be16 = be16::new(ARP_OP_REQUEST as u16);
+/// ARP opcode reply, in network byte order.
+pub const ARP_OP_REPLY_BE: be16 = be16::new(ARP_OP_REPLY as u16);
+
+unsafe impl mbuf::Header for ArpHdr {}
+
+pub trait ArpExt {
+    /// The format of hardware address, in network byte order.
+    fn hrd(&self) -> be16;
+
+    /// The format of protocol address, in network byte order.
+    fn pro(&self) -> be16;
+
+    /// The ARP opcode, in network byte order.
+    fn op(&self) -> be16;
+
+    /// Set the ARP opcode, in network byte order.
+    fn set_op(&mut self, op: be16);
+
+    /// The sender IP address, in network byte order.
+    fn sip(&self) -> be32;
+
+    /// Set the sender IP address, in network byte order.
+    fn set_sip(&mut self, ip: be32);
+
+    /// The target IP address, in network byte order.
+    fn tip(&self) -> be32;
+
+    /// Set the target IP address, in network byte order.
+    fn set_tip(&mut self, ip: be32);
+}
+
+impl ArpExt for ArpHdr {
+    #[inline]
+    fn hrd(&self) -> be16 {
+        be16::from_raw(self.arp_hrd)
+    }
+
+    #[inline]
+    fn pro(&self) -> be16 {
+        be16::from_raw(self.arp_pro)
+    }
+
+    #[inline]
+    fn op(&self) -> be16 {
+        be16::from_raw(self.arp_op)
+    }
+
+    #[inline]
+    fn set_op(&mut self, op: be16) {
+        self.arp_op = op.to_raw()
+    }
+
+    #[inline]
+    fn sip(&self) -> be32 {
+        be32::from_raw(self.arp_data.arp_sip)
+    }
+
+    #[inline]
+    fn set_sip(&mut self, ip: be32) {
+        self.arp_data.arp_sip = ip.to_raw()
+    }
+
+    #[inline]
+    fn tip(&self) -> be32 {
+        be32::from_raw(self.arp_data.arp_tip)
+    }
+
+    #[inline]
+    fn set_tip(&mut self, ip: be32) {
+        self.arp_data.arp_tip = ip.to_raw()
+    }
+}
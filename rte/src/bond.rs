@@ -1,11 +1,19 @@
+use std::collections::VecDeque;
 use std::mem;
+use std::time::{Duration, Instant};
+
+use libc;
 
 use ffi;
 
-use errors::Result;
-use ethdev;
+use common::name::{Name, NameKind};
+use common::{get_tsc_hz, rdtsc};
+use errors::{AsResult, Result, RteError};
+use ethdev::{self, EthDevice, RawEthDeviceStats, StatsDelta, StatsSnapshot};
 use ether;
-use memory::SocketId;
+use mbuf;
+use memory::Socket;
+use utils::AsRaw;
 
 /// Supported modes of operation of link bonding library
 #[repr(u8)]
@@ -107,16 +115,44 @@ impl From<u8> for TransmitPolicy {
     }
 }
 
+/// Aggregator selection mode of 802.3AD (Mode 4)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum AggSelection {
+    /// Select the aggregator with the highest bandwidth
+    Bandwidth = ffi::rte_bond_8023ad_agg_selection::AGG_BANDWIDTH,
+    /// Select the aggregator with the most ports
+    Count = ffi::rte_bond_8023ad_agg_selection::AGG_COUNT,
+    /// Keep the aggregator until all its ports are down
+    Stable = ffi::rte_bond_8023ad_agg_selection::AGG_STABLE,
+}
+
+impl From<u32> for AggSelection {
+    fn from(v: u32) -> Self {
+        unsafe { mem::transmute(v) }
+    }
+}
+
+/// Mode 4 configuration
+pub type AutoNegConf = ffi::rte_eth_bond_8023ad_conf;
+
 /// Create a bonded rte_eth_dev device
-pub fn create(name: &str, mode: BondMode, socket_id: SocketId) -> Result<ethdev::PortId> {
-    let port_id = unsafe { ffi::rte_eth_bond_create(try!(to_cptr!(name)), mode as u8, socket_id as u8) };
+///
+/// The device is allocated on the socket of the current lcore, if any socket is given.
+pub fn create(name: &str, mode: BondMode, socket: Socket) -> Result<ethdev::PortId> {
+    let name = Name::new(NameKind::EthDev, name)?;
+    // the socket id is an `u8`, which can't be `SOCKET_ID_ANY`
+    let socket_id = socket.validate()?.id().or_else(|| Socket::current().id()).unwrap_or(0);
+    let port_id = unsafe { ffi::rte_eth_bond_create(name.as_ptr(), mode as u8, socket_id as u8) };
 
     rte_check!(port_id; ok => { port_id as ethdev::PortId })
 }
 
 /// Free a bonded rte_eth_dev device
 pub fn free(name: &str) -> Result<()> {
-    rte_check!(unsafe { ffi::rte_eth_bond_free(try!(to_cptr!(name))) })
+    let name = Name::new(NameKind::EthDev, name)?;
+
+    rte_check!(unsafe { ffi::rte_eth_bond_free(name.as_ptr()) })
 }
 
 pub trait BondedDevice {
@@ -156,6 +192,60 @@ pub trait BondedDevice {
     /// Set the transmit policy for bonded device to use when it is operating in balance mode,
     /// this parameter is otherwise ignored in other modes of operation.
     fn set_xmit_policy(&self, policy: TransmitPolicy) -> Result<&Self>;
+
+    /// Get the mode 4 configuration of the bonded device.
+    fn autoneg_conf(&self) -> Result<AutoNegConf>;
+
+    /// Configure the mode 4 of the bonded device, or use the default configuration.
+    fn setup_autoneg(&self, conf: Option<&AutoNegConf>) -> Result<&Self>;
+
+    /// Get the aggregator mode of the bonded device in mode 4.
+    fn agg_selection(&self) -> Result<AggSelection>;
+
+    /// Set the aggregator mode of the bonded device in mode 4.
+    fn set_agg_selection(&self, agg_selection: AggSelection) -> Result<&Self>;
+
+    /// Enable dedicated hw queues for 802.3ad control plane traffic on slaves.
+    ///
+    /// An additional rx and tx queue is created on each slave, and a flow rule
+    /// redirects the LACP packets to the rx queue, so the LACP state machine
+    /// handles them regardless of the rx/tx cadence of the bonded device.
+    ///
+    /// All slaves must support the flow rule and have enough queues,
+    /// and the bonded device must be stopped to change this configuration.
+    fn enable_dedicated_queues(&self) -> Result<&Self>;
+
+    /// Disable dedicated hw queues for 802.3ad control plane traffic on slaves.
+    ///
+    /// The bonded device must be stopped to change this configuration.
+    fn disable_dedicated_queues(&self) -> Result<&Self>;
+
+    /// Transmit a LACPDU on the slave for the external 802.3ad state machine.
+    ///
+    /// The packet is returned on failure.
+    fn send_lacpdu(&self, slave: ethdev::PortId, lacp_pkt: mbuf::MBuf) -> ::std::result::Result<&Self, mbuf::MBuf>;
+
+    /// Get the general I/O statistics of a slave of the bonded device.
+    fn slave_stats(&self, slave: ethdev::PortId) -> Result<RawEthDeviceStats>;
+
+    /// Get the general I/O statistics of all the slaves of the bonded device.
+    fn slaves_stats(&self) -> Result<Vec<(ethdev::PortId, RawEthDeviceStats)>>;
+
+    /// Get the interval of the link status monitoring of the slaves.
+    fn link_monitoring(&self) -> Result<Duration>;
+
+    /// Set the interval of the link status monitoring of the slaves,
+    /// which polls the link status of the slaves without the link status change interrupt.
+    fn set_link_monitoring(&self, interval: Duration) -> Result<&Self>;
+
+    /// Force the redistribution of the traffic over the slaves in the adaptive TLB or ALB mode.
+    ///
+    /// The mode is applied again, which resets the client table of ALB mode,
+    /// so the peers are assigned to the slaves again on their next ARP exchange,
+    /// and the TLB slaves are ordered again by their load at the next reorder period.
+    ///
+    /// Fails with `ENOTSUP` in the other modes.
+    fn rebalance(&self) -> Result<&Self>;
 }
 
 impl BondedDevice for ethdev::PortId {
@@ -172,9 +262,9 @@ impl BondedDevice for ethdev::PortId {
     }
 
     fn mode(&self) -> Result<BondMode> {
-        let mode = unsafe { ffi::rte_eth_bond_mode_get(*self) };
-
-        rte_check!(mode; ok => { BondMode::from(mode as u8) })
+        unsafe { ffi::rte_eth_bond_mode_get(*self) }
+            .as_result()
+            .map(|mode| BondMode::from(mode as u8))
     }
 
     fn set_mode(&self, mode: BondMode) -> Result<&Self> {
@@ -200,19 +290,23 @@ impl BondedDevice for ethdev::PortId {
 
         let num = unsafe { ffi::rte_eth_bond_slaves_get(*self, slaves.as_mut_ptr(), slaves.len() as u16) };
 
-        rte_check!(num; ok => {
-            Vec::from(&slaves[..num as usize])
-        })
+        if num < 0 {
+            Err(RteError(-num).into())
+        } else {
+            Ok(Vec::from(&slaves[..num as usize]))
+        }
     }
 
     fn active_slaves(&self) -> Result<Vec<ethdev::PortId>> {
         let mut slaves = [0u16; ffi::RTE_MAX_ETHPORTS as usize];
 
-        let num = unsafe { ffi::rte_eth_bond_slaves_get(*self, slaves.as_mut_ptr(), slaves.len() as u16) };
+        let num = unsafe { ffi::rte_eth_bond_active_slaves_get(*self, slaves.as_mut_ptr(), slaves.len() as u16) };
 
-        rte_check!(num; ok => {
-            Vec::from(&slaves[..num as usize])
-        })
+        if num < 0 {
+            Err(RteError(-num).into())
+        } else {
+            Ok(Vec::from(&slaves[..num as usize]))
+        }
     }
 
     fn set_mac_addr(&self, mac_addr: &ether::EtherAddr) -> Result<&Self> {
@@ -238,4 +332,366 @@ impl BondedDevice for ethdev::PortId {
             ffi::rte_eth_bond_xmit_policy_set(*self, policy as u8)
         }; ok => { self })
     }
+
+    fn autoneg_conf(&self) -> Result<AutoNegConf> {
+        let mut conf = AutoNegConf::default();
+
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_8023ad_conf_get(*self, &mut conf)
+        }; ok => { conf })
+    }
+
+    fn setup_autoneg(&self, conf: Option<&AutoNegConf>) -> Result<&Self> {
+        let mut conf = conf.cloned();
+
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_8023ad_setup(*self, conf.as_mut().map_or(::std::ptr::null_mut(), |c| c as *mut _))
+        }; ok => { self })
+    }
+
+    fn agg_selection(&self) -> Result<AggSelection> {
+        let agg_selection = unsafe { ffi::rte_eth_bond_8023ad_agg_selection_get(*self) };
+
+        rte_check!(agg_selection; ok => { AggSelection::from(agg_selection as u32) })
+    }
+
+    fn set_agg_selection(&self, agg_selection: AggSelection) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_8023ad_agg_selection_set(*self, agg_selection as u32)
+        }; ok => { self })
+    }
+
+    fn enable_dedicated_queues(&self) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_8023ad_dedicated_queues_enable(*self)
+        }; ok => { self })
+    }
+
+    fn disable_dedicated_queues(&self) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_8023ad_dedicated_queues_disable(*self)
+        }; ok => { self })
+    }
+
+    fn send_lacpdu(&self, slave: ethdev::PortId, lacp_pkt: mbuf::MBuf) -> ::std::result::Result<&Self, mbuf::MBuf> {
+        if unsafe { ffi::rte_eth_bond_8023ad_ext_slowtx(*self, slave, lacp_pkt.as_raw()) } == 0 {
+            mem::forget(lacp_pkt);
+
+            Ok(self)
+        } else {
+            Err(lacp_pkt)
+        }
+    }
+
+    fn slave_stats(&self, slave: ethdev::PortId) -> Result<RawEthDeviceStats> {
+        if !self.slaves()?.contains(&slave) {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        slave.stats()
+    }
+
+    fn slaves_stats(&self) -> Result<Vec<(ethdev::PortId, RawEthDeviceStats)>> {
+        self.slaves()?
+            .into_iter()
+            .map(|slave| slave.stats().map(|stats| (slave, stats)))
+            .collect()
+    }
+
+    fn link_monitoring(&self) -> Result<Duration> {
+        unsafe { ffi::rte_eth_bond_link_monitoring_get(*self) }
+            .as_result()
+            .map(|ms| Duration::from_millis(ms as u64))
+    }
+
+    fn set_link_monitoring(&self, interval: Duration) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_bond_link_monitoring_set(*self, interval.as_millis() as u32)
+        }; ok => { self })
+    }
+
+    fn rebalance(&self) -> Result<&Self> {
+        match self.mode()? {
+            mode @ BondMode::AdaptiveTLB | mode @ BondMode::AdaptiveLB => self.set_mode(mode),
+            _ => Err(RteError(libc::ENOTSUP).into()),
+        }
+    }
+}
+
+/// The utilization of a slave between two samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SlaveUtilization {
+    /// The port id of the slave.
+    pub port_id: ethdev::PortId,
+    /// The changes of the counters of the slave.
+    pub delta: StatsDelta,
+    /// The share of the transmitted bytes of the bonded device.
+    pub tx_share: f64,
+    /// The share of the received bytes of the bonded device.
+    pub rx_share: f64,
+}
+
+/// The utilization of the slaves of a bonded device between two samples.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Utilization {
+    /// The time elapsed between the samples.
+    pub elapsed: Duration,
+    /// The utilization of the slaves.
+    pub slaves: Vec<SlaveUtilization>,
+}
+
+impl Utilization {
+    fn new(elapsed: Duration, deltas: Vec<(ethdev::PortId, StatsDelta)>) -> Self {
+        let tx_bytes = deltas.iter().map(|(_, delta)| delta.obytes).sum::<u64>();
+        let rx_bytes = deltas.iter().map(|(_, delta)| delta.ibytes).sum::<u64>();
+        let share = |bytes: u64, total: u64| if total == 0 { 0.0 } else { bytes as f64 / total as f64 };
+
+        Utilization {
+            elapsed,
+            slaves: deltas
+                .into_iter()
+                .map(|(port_id, delta)| SlaveUtilization {
+                    port_id,
+                    delta,
+                    tx_share: share(delta.obytes, tx_bytes),
+                    rx_share: share(delta.ibytes, rx_bytes),
+                })
+                .collect(),
+        }
+    }
+
+    /// The skew of the transmitted bytes, the ratio of the busiest slave to the average.
+    ///
+    /// It is 1.0 when the traffic is evenly distributed, or there is no traffic,
+    /// and the number of slaves when all the traffic goes through a single slave.
+    pub fn tx_skew(&self) -> f64 {
+        skew(self.slaves.iter().map(|slave| slave.tx_share))
+    }
+
+    /// The skew of the received bytes, the ratio of the busiest slave to the average.
+    pub fn rx_skew(&self) -> f64 {
+        skew(self.slaves.iter().map(|slave| slave.rx_share))
+    }
+}
+
+fn skew<I: ExactSizeIterator<Item = f64>>(shares: I) -> f64 {
+    let n = shares.len();
+    let max = shares.fold(0.0, f64::max);
+
+    if n == 0 || max == 0.0 {
+        1.0
+    } else {
+        max * n as f64
+    }
+}
+
+/// The hook invoked before a rebalance, which could veto it by returning `false`.
+pub type RebalanceHook = Box<dyn FnMut(ethdev::PortId, &Utilization) -> bool + Send>;
+
+/// Monitor the utilization of the slaves of a bonded device, and rebalance the traffic when it is skewed.
+pub struct BondMonitor {
+    port_id: ethdev::PortId,
+    snapshots: Vec<(ethdev::PortId, StatsSnapshot)>,
+    max_skew: f64,
+    holdoff: Duration,
+    last_rebalance: Option<Instant>,
+    hook: Option<RebalanceHook>,
+}
+
+impl BondMonitor {
+    /// Create a monitor of the bonded device, which takes the first samples of the slaves.
+    pub fn new(port_id: ethdev::PortId) -> Result<Self> {
+        let mut monitor = BondMonitor {
+            port_id,
+            snapshots: vec![],
+            max_skew: 1.5,
+            holdoff: Duration::from_secs(10),
+            last_rebalance: None,
+            hook: None,
+        };
+
+        monitor.sample()?;
+
+        Ok(monitor)
+    }
+
+    /// Set the transmit skew above which the traffic is rebalanced.
+    pub fn with_max_skew(mut self, max_skew: f64) -> Self {
+        self.max_skew = max_skew;
+        self
+    }
+
+    /// Set the minimum interval between two rebalances.
+    pub fn with_holdoff(mut self, holdoff: Duration) -> Self {
+        self.holdoff = holdoff;
+        self
+    }
+
+    /// Set the hook invoked before a rebalance.
+    pub fn on_rebalance<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(ethdev::PortId, &Utilization) -> bool + Send + 'static,
+    {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Sample the statistics of the slaves, returns their utilization since the previous sample.
+    ///
+    /// The slaves added since the previous sample are accounted from this sample.
+    pub fn sample(&mut self) -> Result<Utilization> {
+        let mut snapshots = vec![];
+        let mut deltas = vec![];
+        let mut elapsed = Duration::default();
+
+        for slave in self.port_id.slaves()? {
+            let cur = StatsSnapshot::take(&slave)?;
+
+            if let Some((_, prev)) = self.snapshots.iter().find(|&&(port_id, _)| port_id == slave) {
+                elapsed = cur.elapsed(prev);
+                deltas.push((slave, cur.delta(prev)));
+            }
+
+            snapshots.push((slave, cur));
+        }
+
+        self.snapshots = snapshots;
+
+        Ok(Utilization::new(elapsed, deltas))
+    }
+
+    /// Sample the slaves, and rebalance the traffic if the transmit skew exceeds the maximum
+    /// in the adaptive TLB or ALB mode, at most once in the holdoff interval.
+    ///
+    /// Returns the utilization and whether the traffic was rebalanced.
+    pub fn poll(&mut self) -> Result<(Utilization, bool)> {
+        let utilization = self.sample()?;
+
+        let held = self.last_rebalance.is_some_and(|last| last.elapsed() < self.holdoff);
+        let adaptive = matches!(self.port_id.mode()?, BondMode::AdaptiveTLB | BondMode::AdaptiveLB);
+
+        if held || !adaptive || utilization.tx_skew() <= self.max_skew {
+            return Ok((utilization, false));
+        }
+
+        let port_id = self.port_id;
+
+        if let Some(ref mut hook) = self.hook {
+            if !hook(port_id, &utilization) {
+                return Ok((utilization, false));
+            }
+        }
+
+        self.port_id.rebalance()?;
+        self.last_rebalance = Some(Instant::now());
+
+        Ok((utilization, true))
+    }
+}
+
+/// The maximum interval between two rx/tx bursts on a bonded device in mode 4.
+pub const AUTONEG_MAX_INTERVAL: Duration = Duration::from_millis(100);
+
+const AUTONEG_POLL_BURST: usize = 32;
+const AUTONEG_MAX_STASHED: usize = 1024;
+
+/// Keep the LACP state machine of a bonded device in mode 4 running.
+///
+/// Without dedicated queues, the LACP packets are handled in the rx/tx bursts
+/// of the bonded device, which must be invoked at least every 100ms.
+///
+/// The keeper wraps the rx/tx bursts of a queue, and its `poll` method,
+/// called from the idle path or a timer of the same lcore, invokes an empty
+/// tx burst or a rx burst when the queue has not been used for a while.
+/// The packets received by `poll` are returned by the next `rx_burst`.
+pub struct AutoNegKeeper {
+    port_id: ethdev::PortId,
+    queue_id: ethdev::QueueId,
+    interval: u64,
+    last_rx: u64,
+    last_tx: u64,
+    stashed: VecDeque<mbuf::MBuf>,
+}
+
+impl AutoNegKeeper {
+    /// Create a keeper for the queue of bonded device, with half of the maximum interval.
+    pub fn new(port_id: ethdev::PortId, queue_id: ethdev::QueueId) -> Self {
+        Self::with_interval(port_id, queue_id, AUTONEG_MAX_INTERVAL / 2)
+    }
+
+    /// Create a keeper for the queue of bonded device, with the interval to poll the queue.
+    pub fn with_interval(port_id: ethdev::PortId, queue_id: ethdev::QueueId, interval: Duration) -> Self {
+        let now = rdtsc();
+        let interval =
+            get_tsc_hz() * interval.as_secs() + get_tsc_hz() * u64::from(interval.subsec_micros()) / 1_000_000;
+
+        AutoNegKeeper {
+            port_id,
+            queue_id,
+            interval,
+            last_rx: now,
+            last_tx: now,
+            stashed: VecDeque::new(),
+        }
+    }
+
+    /// Retrieve a burst of packets, including the packets received by `poll`.
+    pub fn rx_burst(&mut self, rx_pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        let mut n = 0;
+
+        while n < rx_pkts.len() {
+            match self.stashed.pop_front() {
+                Some(m) => {
+                    mem::forget(rx_pkts[n].replace(m));
+                    n += 1;
+                }
+                None => break,
+            }
+        }
+
+        if n < rx_pkts.len() {
+            n += self.port_id.rx_burst(self.queue_id, &mut rx_pkts[n..]);
+
+            self.last_rx = rdtsc();
+        }
+
+        n
+    }
+
+    /// Send a burst of packets.
+    pub fn tx_burst<T: AsRaw<Raw = mbuf::RawMBuf>>(&mut self, tx_pkts: &mut [T]) -> usize {
+        let n = self.port_id.tx_burst(self.queue_id, tx_pkts);
+
+        self.last_tx = rdtsc();
+
+        n
+    }
+
+    /// Invoke the rx/tx bursts if the queue has not been used for the interval.
+    ///
+    /// Returns the number of packets stashed for the next `rx_burst`.
+    pub fn poll(&mut self) -> usize {
+        let now = rdtsc();
+
+        if now.wrapping_sub(self.last_tx) >= self.interval {
+            let mut empty: [mbuf::MBuf; 0] = [];
+
+            self.tx_burst(&mut empty);
+        }
+
+        if now.wrapping_sub(self.last_rx) >= self.interval {
+            let mut pkts: [Option<mbuf::MBuf>; AUTONEG_POLL_BURST] = Default::default();
+            let n = self.port_id.rx_burst(self.queue_id, &mut pkts);
+
+            self.last_rx = now;
+
+            for m in pkts.iter_mut().take(n) {
+                if self.stashed.len() < AUTONEG_MAX_STASHED {
+                    self.stashed.extend(m.take());
+                }
+            }
+        }
+
+        self.stashed.len()
+    }
 }
@@ -0,0 +1,364 @@
+//!
+//! Adaptive RX burst size, based on the queue occupancy.
+//!
+//! A fixed burst size is a trade-off: a small burst spins on the nearly empty queue and wastes
+//! the cycles, a large burst lets the packets build up in the ring and adds latency.
+//!
+//! The controller watches how the bursts are filled and how many descriptors are used in the
+//! RX queue, grows the burst when the packets build up, shrinks it when the bursts are sparse,
+//! and backs off the polling with a small delay when the queue is idle, within the user-set bounds.
+//!
+//! ```no_run
+//! use rte::burst::{BurstConfig, BurstController};
+//! use rte::ethdev::EthDevice;
+//! use rte::*;
+//!
+//! let port_id = 0;
+//! let mut ctrl = BurstController::new(port_id, 0, BurstConfig::default());
+//! let mut pkts: Vec<Option<mbuf::MBuf>> = (0..ctrl.config().max_burst).map(|_| None).collect();
+//!
+//! loop {
+//!     let n = port_id.rx_burst(0, &mut pkts[..ctrl.burst_size()]);
+//!
+//!     // handle the packets
+//!
+//!     ctrl.update(n);
+//!     ctrl.wait();
+//! }
+//! ```
+//!
+use std::cmp;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::delay_us;
+use ethdev::{PortId, QueueHandle, QueueId};
+use metrics::{Metric, Registry};
+
+/// The bounds and thresholds of the adaptive burst size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BurstConfig {
+    /// The minimum burst size.
+    pub min_burst: usize,
+    /// The maximum burst size, which should be no larger than the packet array.
+    pub max_burst: usize,
+    /// The maximum delay added to the idle polls.
+    pub max_delay: Duration,
+    /// The delay step added for each consecutive idle poll.
+    pub delay_step: Duration,
+    /// The ratio of used descriptors, above which the packets are building up in the RX queue.
+    pub high_watermark: f32,
+    /// The ratio of used descriptors, below which the RX queue is nearly drained.
+    pub low_watermark: f32,
+}
+
+impl Default for BurstConfig {
+    fn default() -> Self {
+        BurstConfig {
+            min_burst: 4,
+            max_burst: 64,
+            max_delay: Duration::from_micros(50),
+            delay_step: Duration::from_micros(5),
+            high_watermark: 0.5,
+            low_watermark: 0.1,
+        }
+    }
+}
+
+/// The decision of the controller after a poll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// Keep the burst size and the delay.
+    Steady,
+    /// The packets are building up, grow the burst size.
+    Grow,
+    /// The bursts are sparse, halve the burst size.
+    Shrink,
+    /// The queue is idle, add a delay before the next poll.
+    Backoff,
+}
+
+/// The counters of the controller decisions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BurstStats {
+    /// The number of polls.
+    pub polls: u64,
+    /// The number of received packets.
+    pub packets: u64,
+    /// The number of polls which filled the whole burst.
+    pub full_polls: u64,
+    /// The number of polls which received nothing.
+    pub idle_polls: u64,
+    /// The number of times the burst size grew.
+    pub grows: u64,
+    /// The number of times the burst size shrank.
+    pub shrinks: u64,
+    /// The number of times the delay increased.
+    pub backoffs: u64,
+}
+
+impl BurstStats {
+    /// The average number of packets per poll.
+    pub fn avg_burst(&self) -> f64 {
+        if self.polls == 0 {
+            0.0
+        } else {
+            self.packets as f64 / self.polls as f64
+        }
+    }
+}
+
+struct BurstMetrics {
+    burst_size: Arc<Metric>,
+    delay_us: Arc<Metric>,
+    grows: Arc<Metric>,
+    shrinks: Arc<Metric>,
+    backoffs: Arc<Metric>,
+}
+
+/// Adapt the burst size and the poll delay of a RX queue.
+pub struct BurstController {
+    queue: QueueHandle,
+    nb_desc: usize,
+    conf: BurstConfig,
+    burst: usize,
+    delay_us: u32,
+    stats: BurstStats,
+    metrics: Option<BurstMetrics>,
+}
+
+impl BurstController {
+    /// Create a controller of the RX queue, which starts from the maximum burst size without delay.
+    ///
+    /// The queue must be set up, the used descriptors are ignored if the driver can't report them.
+    pub fn new(port_id: PortId, queue_id: QueueId, conf: BurstConfig) -> Self {
+        let queue = QueueHandle::Rx(port_id, queue_id);
+        let nb_desc = queue.nb_desc().unwrap_or(0) as usize;
+
+        Self::with_nb_desc(queue, nb_desc, conf)
+    }
+
+    fn with_nb_desc(queue: QueueHandle, nb_desc: usize, conf: BurstConfig) -> Self {
+        let min_burst = cmp::max(conf.min_burst, 1);
+        let conf = BurstConfig {
+            min_burst,
+            max_burst: cmp::max(conf.max_burst, min_burst),
+            ..conf
+        };
+
+        BurstController {
+            queue,
+            nb_desc,
+            conf,
+            burst: conf.max_burst,
+            delay_us: 0,
+            stats: BurstStats::default(),
+            metrics: None,
+        }
+    }
+
+    /// Export the burst size, the delay and the decision counters as metrics, labeled with the queue.
+    pub fn register_metrics(&mut self, registry: &Registry) -> &mut Self {
+        let port = self.queue.portid().to_string();
+        let queue = self.queue.queue_id().to_string();
+        let labels = [("port", port.as_str()), ("queue", queue.as_str())];
+
+        let metrics = BurstMetrics {
+            burst_size: registry.gauge("rte_rx_burst_size", "The adaptive RX burst size.", &labels),
+            delay_us: registry.gauge("rte_rx_poll_delay_us", "The delay added to the idle RX polls.", &labels),
+            grows: registry.counter(
+                "rte_rx_burst_grows_total",
+                "The number of times the burst grew.",
+                &labels,
+            ),
+            shrinks: registry.counter(
+                "rte_rx_burst_shrinks_total",
+                "The number of times the burst shrank.",
+                &labels,
+            ),
+            backoffs: registry.counter(
+                "rte_rx_poll_backoffs_total",
+                "The number of times the poll delay increased.",
+                &labels,
+            ),
+        };
+
+        metrics.burst_size.set(self.burst as u64);
+        metrics.delay_us.set(u64::from(self.delay_us));
+
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The queue of the controller.
+    pub fn queue(&self) -> QueueHandle {
+        self.queue
+    }
+
+    /// The bounds and thresholds of the controller.
+    pub fn config(&self) -> &BurstConfig {
+        &self.conf
+    }
+
+    /// The burst size of the next poll.
+    #[inline]
+    pub fn burst_size(&self) -> usize {
+        self.burst
+    }
+
+    /// The delay before the next poll.
+    #[inline]
+    pub fn delay(&self) -> Duration {
+        Duration::from_micros(u64::from(self.delay_us))
+    }
+
+    /// The counters of the controller decisions.
+    pub fn stats(&self) -> &BurstStats {
+        &self.stats
+    }
+
+    /// Wait for the delay before the next poll, if any.
+    #[inline]
+    pub fn wait(&self) {
+        if self.delay_us > 0 {
+            delay_us(self.delay_us)
+        }
+    }
+
+    /// Update the controller with the number of packets received by the last poll.
+    ///
+    /// The used descriptors of the queue are only queried when the burst was filled,
+    /// so the idle polls stay cheap.
+    pub fn update(&mut self, received: usize) -> Decision {
+        let used = if received >= self.burst && self.nb_desc > 0 {
+            self.queue.used_count().ok()
+        } else {
+            None
+        };
+
+        self.decide(received, used)
+    }
+
+    fn decide(&mut self, received: usize, used: Option<usize>) -> Decision {
+        self.stats.polls += 1;
+        self.stats.packets += received as u64;
+
+        let (burst, delay_us) = if received == 0 {
+            self.stats.idle_polls += 1;
+
+            let step = self.conf.delay_step.as_micros() as u32;
+            let max_delay = self.conf.max_delay.as_micros() as u32;
+
+            (self.burst, cmp::min(self.delay_us.saturating_add(step), max_delay))
+        } else if received >= self.burst {
+            self.stats.full_polls += 1;
+
+            let occupancy = used.map(|used| used as f32 / self.nb_desc as f32);
+
+            match occupancy {
+                Some(occupancy) if occupancy >= self.conf.high_watermark => (self.conf.max_burst, 0),
+                Some(occupancy) if occupancy < self.conf.low_watermark => (self.burst, 0),
+                _ => (cmp::min(self.burst * 2, self.conf.max_burst), 0),
+            }
+        } else if received * 2 < self.burst {
+            (cmp::max(self.burst / 2, self.conf.min_burst), 0)
+        } else {
+            (self.burst, 0)
+        };
+
+        let decision = if burst > self.burst {
+            self.stats.grows += 1;
+
+            Decision::Grow
+        } else if burst < self.burst {
+            self.stats.shrinks += 1;
+
+            Decision::Shrink
+        } else if delay_us > self.delay_us {
+            self.stats.backoffs += 1;
+
+            Decision::Backoff
+        } else {
+            Decision::Steady
+        };
+
+        if burst != self.burst || delay_us != self.delay_us {
+            self.burst = burst;
+            self.delay_us = delay_us;
+
+            if let Some(ref metrics) = self.metrics {
+                metrics.burst_size.set(burst as u64);
+                metrics.delay_us.set(u64::from(delay_us));
+
+                match decision {
+                    Decision::Grow => metrics.grows.inc(),
+                    Decision::Shrink => metrics.shrinks.inc(),
+                    Decision::Backoff => metrics.backoffs.inc(),
+                    Decision::Steady => {}
+                }
+            }
+        }
+
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> BurstController {
+        BurstController::with_nb_desc(QueueHandle::Rx(0, 0), 512, BurstConfig::default())
+    }
+
+    #[test]
+    fn test_grow_and_shrink() {
+        let mut ctrl = controller();
+
+        assert_eq!(ctrl.burst_size(), 64);
+
+        assert_eq!(ctrl.decide(10, None), Decision::Shrink);
+        assert_eq!(ctrl.burst_size(), 32);
+        assert_eq!(ctrl.decide(10, None), Decision::Shrink);
+        assert_eq!(ctrl.decide(2, None), Decision::Shrink);
+        assert_eq!(ctrl.decide(1, None), Decision::Shrink);
+        assert_eq!(ctrl.burst_size(), 4);
+        assert_eq!(ctrl.decide(1, None), Decision::Steady);
+        assert_eq!(ctrl.burst_size(), 4);
+
+        // the queue is nearly drained, keep the burst
+        assert_eq!(ctrl.decide(4, Some(16)), Decision::Steady);
+        // the queue is filling up, double the burst
+        assert_eq!(ctrl.decide(4, Some(128)), Decision::Grow);
+        assert_eq!(ctrl.burst_size(), 8);
+        // the packets build up, jump to the maximum burst
+        assert_eq!(ctrl.decide(8, Some(300)), Decision::Grow);
+        assert_eq!(ctrl.burst_size(), 64);
+
+        let stats = ctrl.stats();
+
+        assert_eq!(stats.polls, 8);
+        assert_eq!(stats.full_polls, 3);
+        assert_eq!(stats.grows, 2);
+        assert_eq!(stats.shrinks, 4);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut ctrl = controller();
+
+        for _ in 0..10 {
+            assert_eq!(ctrl.decide(0, None), Decision::Backoff);
+        }
+
+        assert_eq!(ctrl.delay(), Duration::from_micros(50));
+        assert_eq!(ctrl.decide(0, None), Decision::Steady);
+        assert_eq!(ctrl.stats().idle_polls, 11);
+        assert_eq!(ctrl.stats().backoffs, 10);
+
+        // any received packet stops delaying
+        assert_eq!(ctrl.decide(40, None), Decision::Steady);
+        assert_eq!(ctrl.delay(), Duration::from_micros(0));
+        assert_eq!(ctrl.burst_size(), 64);
+    }
+}
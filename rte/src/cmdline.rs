@@ -1,10 +1,13 @@
-use std::ffi::CStr;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ops::{Deref, DerefMut};
 use std::os::raw::{c_char, c_void};
+use std::os::unix::ffi::OsStrExt;
 use std::path::Path;
 use std::ptr;
 use std::string;
@@ -13,8 +16,13 @@ use libc;
 
 use ffi;
 
-use errors::{AsResult, ErrorKind::CmdLineParseError, Result};
+use errors::{
+    AsResult,
+    ErrorKind::{CmdLineError, CmdLineParseError},
+    Result,
+};
 use ether;
+use ipset::Prefix;
 
 pub type RawTokenHeader = ffi::cmdline_token_hdr;
 pub type RawTokenPtr = *const RawTokenHeader;
@@ -101,21 +109,60 @@ impl fmt::Display for IpNetAddr {
 }
 
 impl IpNetAddr {
-    pub fn as_ipv4(&self) -> &Ipv4Addr {
-        unsafe { &*(&self.0.addr as *const _ as *const std::net::Ipv4Addr) }
+    /// The address family, `AF_INET` or `AF_INET6`.
+    pub fn family(&self) -> i32 {
+        i32::from(self.0.family)
     }
 
-    pub fn as_ipv6(&self) -> &Ipv6Addr {
-        unsafe { &*(&self.0.addr as *const _ as *const std::net::Ipv6Addr) }
+    pub fn is_ipv4(&self) -> bool {
+        self.family() == libc::AF_INET
     }
 
-    pub fn to_ipaddr(&self) -> IpAddr {
-        if self.0.family == libc::AF_INET as u8 {
-            IpAddr::V4(*self.as_ipv4())
+    pub fn is_ipv6(&self) -> bool {
+        self.family() == libc::AF_INET6
+    }
+
+    /// The IPv4 address, or `None` if it's an IPv6 address.
+    pub fn as_ipv4(&self) -> Option<&Ipv4Addr> {
+        if self.is_ipv4() {
+            Some(unsafe { &*(&self.0.addr as *const _ as *const std::net::Ipv4Addr) })
         } else {
-            IpAddr::V6(*self.as_ipv6())
+            None
+        }
+    }
+
+    /// The IPv6 address, or `None` if it's an IPv4 address.
+    pub fn as_ipv6(&self) -> Option<&Ipv6Addr> {
+        if self.is_ipv6() {
+            Some(unsafe { &*(&self.0.addr as *const _ as *const std::net::Ipv6Addr) })
+        } else {
+            None
+        }
+    }
+
+    pub fn to_ipaddr(&self) -> IpAddr {
+        match self.family() {
+            libc::AF_INET => IpAddr::V4(*self.as_ipv4().unwrap()),
+            libc::AF_INET6 => IpAddr::V6(*self.as_ipv6().unwrap()),
+            family => unreachable!("unexpected address family {}", family),
         }
     }
+
+    /// The prefix length of a network parsed by a `TOKEN_IPNET_INITIALIZER` token,
+    /// which requires the CIDR notation, or `0` for an address.
+    pub fn prefix_len(&self) -> u8 {
+        self.0.prefixlen as u8
+    }
+
+    /// The address and prefix length of a network, e.g. `10.0.0.0/8` or `2001:db8::/32`.
+    pub fn to_ipnet(&self) -> (IpAddr, u8) {
+        (self.to_ipaddr(), self.prefix_len())
+    }
+
+    /// The network prefix, the host bits of the address are cleared.
+    pub fn to_prefix(&self) -> Option<Prefix> {
+        Prefix::new(self.to_ipaddr(), self.prefix_len())
+    }
 }
 
 pub struct EtherAddr(RawEtherAddr);
@@ -348,37 +395,106 @@ macro_rules! TOKEN_PORTLIST_INITIALIZER {
 
 pub type InstHandler<T, D> = fn(inst: &mut T, cmdline: &CmdLine, data: Option<D>);
 
+/// A handler which could fail, the error is printed and recorded as a failure of the command line.
+pub type TryInstHandler<T, D> = fn(inst: &mut T, cmdline: &CmdLine, data: Option<D>) -> Result<()>;
+
+enum Handler<T, D> {
+    Infallible(InstHandler<T, D>),
+    Fallible(TryInstHandler<T, D>),
+}
+
 struct InstHandlerContext<T, D> {
-    handler: InstHandler<T, D>,
+    handler: Handler<T, D>,
     data: Option<D>,
 }
 
-unsafe extern "C" fn _inst_handler_stub<T, D>(inst: *mut c_void, cl: *mut RawCmdLine, ctxt: *mut c_void) {
-    let ctxt = Box::from_raw(ctxt as *mut InstHandlerContext<T, D>);
+unsafe extern "C" fn _inst_handler_stub<T, D: Clone>(inst: *mut c_void, cl: *mut RawCmdLine, ctxt: *mut c_void) {
+    // the context is owned by the `Inst`, since the handler could be invoked many times
+    let ctxt = &*(ctxt as *const InstHandlerContext<T, D>);
+    let inst = (inst as *mut T).as_mut().unwrap();
+    let cmdline = CmdLine::Borrowed(cl);
+
+    let res = match ctxt.handler {
+        Handler::Infallible(handler) => {
+            handler(inst, &cmdline, ctxt.data.clone());
 
-    (ctxt.handler)((inst as *mut T).as_mut().unwrap(), &CmdLine::Borrowed(cl), ctxt.data);
+            Ok(())
+        }
+        Handler::Fallible(handler) => handler(inst, &cmdline, ctxt.data.clone()),
+    };
+
+    if let Err(err) = res {
+        let _ = cmdline.println(format!("Error: {}", err));
+
+        if cmdline.fail(err.to_string()) {
+            cmdline.quit();
+        }
+    }
+}
+
+unsafe fn _drop_inst_handler_context<T, D>(ctxt: *mut c_void) {
+    Box::from_raw(ctxt as *mut InstHandlerContext<T, D>);
+}
+
+#[derive(Clone, Debug, Default)]
+struct Status {
+    failures: usize,
+    last_error: Option<String>,
+    abort_on_error: bool,
+}
+
+thread_local! {
+    // the status of the command lines, which are driven by the current thread
+    static STATUS: RefCell<HashMap<usize, Status>> = RefCell::new(HashMap::new());
 }
 
 pub type RawInstPtr = *mut ffi::cmdline_inst;
 
-pub struct Inst(RawInstPtr);
+pub struct Inst {
+    inst: RawInstPtr,
+    drop_context: unsafe fn(*mut c_void),
+}
 
 impl Drop for Inst {
     fn drop(&mut self) {
         unsafe {
-            libc::free((*self.0).help_str as *mut libc::c_void);
-            libc::free(self.0 as *mut libc::c_void);
+            (self.drop_context)((*self.inst).data);
+
+            libc::free((*self.inst).help_str as *mut libc::c_void);
+            libc::free(self.inst as *mut libc::c_void);
         }
     }
 }
 
 impl Inst {
     pub fn as_raw(&self) -> RawInstPtr {
-        self.0
+        self.inst
     }
 }
 
-pub fn inst<T, D>(handler: InstHandler<T, D>, data: Option<D>, help: &'static str, tokens: &[&Token<T>]) -> Inst {
+pub fn inst<T, D: Clone>(
+    handler: InstHandler<T, D>,
+    data: Option<D>,
+    help: &'static str,
+    tokens: &[&Token<T>],
+) -> Inst {
+    new_inst(Handler::Infallible(handler), data, help, tokens)
+}
+
+/// Create an instruction with a handler which could fail.
+///
+/// The error of the handler is printed as `Error: ...`, and recorded as a failure of the command line,
+/// the command line quits on the first error if `CmdLine::set_abort_on_error` was set.
+pub fn try_inst<T, D: Clone>(
+    handler: TryInstHandler<T, D>,
+    data: Option<D>,
+    help: &'static str,
+    tokens: &[&Token<T>],
+) -> Inst {
+    new_inst(Handler::Fallible(handler), data, help, tokens)
+}
+
+fn new_inst<T, D: Clone>(handler: Handler<T, D>, data: Option<D>, help: &'static str, tokens: &[&Token<T>]) -> Inst {
     unsafe {
         let help_str = libc::calloc(1, help.len() + 1) as *mut c_char;
 
@@ -404,7 +520,10 @@ pub fn inst<T, D>(handler: InstHandler<T, D>, data: Option<D>, help: &'static st
             tokens.len(),
         );
 
-        Inst(inst)
+        Inst {
+            inst,
+            drop_context: _drop_inst_handler_context::<T, D>,
+        }
     }
 }
 
@@ -442,16 +561,32 @@ impl Context {
     }
 
     pub fn open_file<P: AsRef<Path>>(&self, prompt: &str, path: P) -> Result<CmdLine> {
-        let cl = unsafe {
-            ffi::cmdline_file_new(
-                self.0 as *mut *mut _,
-                try!(to_cptr!(prompt)),
-                path.as_ref().as_os_str().to_str().unwrap().as_ptr() as *const i8,
-            )
-        };
+        let prompt = CString::new(prompt)?;
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())?;
+
+        let cl = unsafe { ffi::cmdline_file_new(self.0 as *mut *mut _, prompt.as_ptr(), path.as_ptr()) };
 
         rte_check!(cl, NonNull; ok => { CmdLine::Owned(cl) })
     }
+
+    /// Execute the commands of a script file, and fail if any command failed.
+    ///
+    /// The remaining commands are skipped after the first failure if `abort_on_error` is set.
+    pub fn run_file<P: AsRef<Path>>(&self, path: P, abort_on_error: bool) -> Result<()> {
+        let cl = self.open_file("", path)?;
+
+        cl.set_abort_on_error(abort_on_error).interact();
+
+        match cl.failures() {
+            0 => Ok(()),
+            n => Err(CmdLineError(format!(
+                "{} commands failed, {}",
+                n,
+                cl.last_error().unwrap_or_default()
+            ))
+            .into()),
+        }
+    }
 }
 
 pub struct StdInCmdLine(CmdLine);
@@ -543,6 +678,8 @@ pub enum CmdLine {
 impl Drop for CmdLine {
     fn drop(&mut self) {
         if let CmdLine::Owned(cl) = *self {
+            STATUS.with(|status| status.borrow_mut().remove(&(cl as usize)));
+
             unsafe { ffi::cmdline_free(cl) }
         }
     }
@@ -612,9 +749,16 @@ impl CmdLine {
     }
 
     pub fn parse<T: string::ToString>(&self, buf: T) -> Result<&Self> {
+        let failures = self.failures();
         let status = unsafe { ffi::cmdline_parse(self.as_raw(), try!(to_cptr!(buf.to_string()))) };
 
-        status.ok_or(CmdLineParseError(status)).map(|_| self)
+        status.ok_or(CmdLineParseError(status))?;
+
+        if self.failures() > failures {
+            Err(CmdLineError(self.last_error().unwrap_or_default()).into())
+        } else {
+            Ok(self)
+        }
     }
 
     pub fn complete<T: string::ToString>(
@@ -635,4 +779,85 @@ impl CmdLine {
 
         rte_check!(status; ok => { ParseCompleteStatus::from(status as u32) })
     }
+
+    /// Quit the command line on the first failed command.
+    pub fn set_abort_on_error(&self, abort_on_error: bool) -> &CmdLine {
+        self.with_status(|status| status.abort_on_error = abort_on_error);
+
+        self
+    }
+
+    /// The number of failed commands.
+    pub fn failures(&self) -> usize {
+        self.with_status(|status| status.failures)
+    }
+
+    /// The error of the last failed command.
+    pub fn last_error(&self) -> Option<String> {
+        self.with_status(|status| status.last_error.clone())
+    }
+
+    /// The exit code of the process, `EXIT_FAILURE` if any command failed.
+    pub fn exit_code(&self) -> i32 {
+        if self.failures() == 0 {
+            libc::EXIT_SUCCESS
+        } else {
+            libc::EXIT_FAILURE
+        }
+    }
+
+    /// Reset the failures of the command line.
+    pub fn reset_failures(&self) -> &CmdLine {
+        self.with_status(|status| {
+            status.failures = 0;
+            status.last_error = None;
+        });
+
+        self
+    }
+
+    /// Record a failed command, returns whether the command line should quit.
+    fn fail(&self, err: String) -> bool {
+        self.with_status(|status| {
+            status.failures += 1;
+            status.last_error = Some(err);
+            status.abort_on_error
+        })
+    }
+
+    fn with_status<F: FnOnce(&mut Status) -> T, T>(&self, f: F) -> T {
+        STATUS.with(|status| f(status.borrow_mut().entry(self.as_raw() as usize).or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipnet_addr() {
+        let mut raw = RawIpNetAddr {
+            family: libc::AF_INET6 as u8,
+            prefixlen: 32,
+            ..Default::default()
+        };
+
+        raw.addr.ipv6.__in6_u.__u6_addr8 = "2001:db8::1".parse::<Ipv6Addr>().unwrap().octets();
+
+        let net = IpNetAddr(raw);
+
+        assert!(net.is_ipv6());
+        assert_eq!(net.as_ipv4(), None);
+        assert_eq!(net.to_ipnet(), ("2001:db8::1".parse().unwrap(), 32));
+        assert_eq!(net.to_prefix().unwrap().to_string(), "2001:db8::/32");
+
+        raw.family = libc::AF_INET as u8;
+        raw.addr.ipv4.s_addr = u32::from_ne_bytes([10, 1, 2, 3]);
+        raw.prefixlen = 8;
+
+        let net = IpNetAddr(raw);
+
+        assert_eq!(net.as_ipv4(), Some(&Ipv4Addr::new(10, 1, 2, 3)));
+        assert_eq!(net.to_prefix().unwrap().to_string(), "10.0.0.0/8");
+    }
 }
@@ -0,0 +1,117 @@
+//! RTE bus
+//!
+//! The buses registered by the drivers, which scan and probe the devices attached on them.
+//!
+use std::ffi::CStr;
+use std::io;
+use std::os::raw::{c_int, c_void};
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+
+use cfile;
+
+use ffi::{self, rte_bus_scan_mode::*};
+use num_traits::FromPrimitive;
+
+use eal::IovaMode;
+use errors::{AsResult, Result};
+use utils::{self, AsCString};
+
+/// Bus scan policies
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum ScanMode {
+    Undefined = RTE_BUS_SCAN_UNDEFINED,
+    Whitelist = RTE_BUS_SCAN_WHITELIST,
+    Blacklist = RTE_BUS_SCAN_BLACKLIST,
+}
+
+pub type RawBus = ffi::rte_bus;
+pub type RawBusPtr = *mut ffi::rte_bus;
+
+/// A registered bus.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug)]
+pub struct Bus(RawBusPtr);
+
+impl Bus {
+    /// Find the registered bus for a given name.
+    pub fn find_by_name(name: &str) -> Option<Bus> {
+        let name = name.as_cstring();
+        let p = unsafe { ffi::rte_bus_find_by_name(name.as_ptr()) };
+
+        if p.is_null() {
+            None
+        } else {
+            Some(Bus(p))
+        }
+    }
+
+    /// Name of the bus.
+    pub fn name(&self) -> &str {
+        unsafe { CStr::from_ptr((*self.0).name).to_str().unwrap() }
+    }
+
+    /// Scan policy of the bus.
+    pub fn scan_mode(&self) -> ScanMode {
+        ScanMode::from_u32(unsafe { (*self.0).conf.scan_mode }).unwrap_or(ScanMode::Undefined)
+    }
+
+    /// The iommu class of devices bound on to the bus.
+    pub fn iommu_class(&self) -> Option<IovaMode> {
+        unsafe { (*self.0).get_iommu_class.map(|f| f().into()) }
+    }
+}
+
+unsafe extern "C" fn any_bus(_bus: *const ffi::rte_bus, _data: *const c_void) -> c_int {
+    0
+}
+
+/// The registered buses.
+pub fn buses() -> Vec<Bus> {
+    let mut buses = vec![];
+    let mut p: RawBusPtr = ptr::null_mut();
+
+    loop {
+        p = unsafe { ffi::rte_bus_find(p, Some(any_bus), ptr::null()) };
+
+        if p.is_null() {
+            break;
+        }
+
+        buses.push(Bus(p));
+    }
+
+    buses
+}
+
+/// Scan all the buses.
+pub fn scan() -> Result<()> {
+    unsafe { ffi::rte_bus_scan() }.as_result().map(|_| ())
+}
+
+/// For each device on the buses, perform a driver 'match' and call the driver-specific probe for device initialization.
+pub fn probe() -> Result<()> {
+    unsafe { ffi::rte_bus_probe() }.as_result().map(|_| ())
+}
+
+/// Get the common iommu class of devices bound on to buses available in the system.
+pub fn iommu_class() -> IovaMode {
+    unsafe { ffi::rte_bus_get_iommu_class() }.into()
+}
+
+/// Dump information of all the buses registered with EAL.
+pub fn dump<S: AsRawFd>(s: &S) {
+    if let Ok(f) = cfile::fdopen(s, "w") {
+        unsafe {
+            ffi::rte_bus_dump(f.stream() as *mut ffi::FILE);
+        }
+    }
+}
+
+/// Dump information of all the buses registered with EAL to the writer.
+pub fn dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    utils::capture(w, |f| unsafe { ffi::rte_bus_dump(f) })?;
+
+    Ok(())
+}
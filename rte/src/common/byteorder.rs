@@ -1,3 +1,10 @@
+//!
+//! The integers in network byte order, as stored in the protocol headers on the wire.
+//!
+//! The wrappers are defined in the `rte-net-types` crate, so they could be used without the EAL.
+//!
+pub use rte_net_types::byteorder::{be16, be32, be64};
+
 #[macro_export]
 macro_rules! rte_cpu_to_be_16 {
     ($n:expr) => {
@@ -0,0 +1,290 @@
+//! Cooperative yielding for the long control-plane operations on the master lcore.
+//!
+//! The master lcore usually runs the interactive command line, the alarms and the stats reporter
+//! in the same loop, a long operation like the rule compilation or the table rebuild
+//! would freeze all of them until it finished.
+//!
+//! The services registered with `every` or `after` are run by the current thread, when it calls `run_pending`
+//! from its main loop, or `yield_point` from the long operations. `yield_point` is cheap enough to be called
+//! for each rule or entry, it only runs the due services once per time slice.
+//!
+//! The services are run one at a time, a service calling `yield_point` or `run_pending` won't run the others.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use rte::cmdline::ReadlineStatus;
+//! use rte::*;
+//!
+//! let cl = cmdline::new(&[]).open_stdin("example> ").unwrap();
+//!
+//! coop::every("stats", Duration::from_secs(1), || println!("{:?}", mbuf::balance::snapshot()));
+//!
+//! loop {
+//!     match cl.poll() {
+//!         Ok(ReadlineStatus::Running) => coop::run_pending(),
+//!         _ => break,
+//!     }
+//! }
+//! ```
+//!
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use common::{get_tsc_hz, rdtsc};
+
+/// The default time slice between two rounds of the services at the yield points.
+pub const DEFAULT_SLICE: Duration = Duration::from_millis(10);
+
+/// The identifier of a registered service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ServiceId(u64);
+
+/// The statistics of the cooperative yielding of the current thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of the rounds of the services at the yield points.
+    pub yields: u64,
+    /// The number of the services have been run.
+    pub runs: u64,
+}
+
+type Callback = Box<dyn FnMut()>;
+
+struct Service {
+    name: String,
+    period: u64,
+    deadline: u64,
+    oneshot: bool,
+    callback: Option<Callback>,
+}
+
+#[derive(Default)]
+struct Services {
+    next_id: u64,
+    services: BTreeMap<u64, Service>,
+    stats: Stats,
+}
+
+impl Services {
+    fn add(&mut self, name: &str, period: u64, deadline: u64, oneshot: bool, callback: Callback) -> ServiceId {
+        let id = self.next_id;
+
+        self.next_id += 1;
+        self.services.insert(
+            id,
+            Service {
+                name: name.to_owned(),
+                period,
+                deadline,
+                oneshot,
+                callback: Some(callback),
+            },
+        );
+
+        ServiceId(id)
+    }
+
+    /// Take the callbacks of the due services out, the oneshot services are removed.
+    fn take_due(&mut self, now: u64) -> Vec<(u64, Callback)> {
+        let due = self
+            .services
+            .iter_mut()
+            .filter(|(_, service)| service.deadline <= now)
+            .filter_map(|(&id, service)| service.callback.take().map(|callback| (id, callback)))
+            .collect::<Vec<_>>();
+
+        for &(id, _) in &due {
+            if self.services[&id].oneshot {
+                self.services.remove(&id);
+            }
+        }
+
+        self.stats.runs += due.len() as u64;
+
+        due
+    }
+
+    /// Put the callback back and schedule the next run, unless the service was cancelled when it was running.
+    fn put_back(&mut self, id: u64, now: u64, callback: Callback) {
+        if let Some(service) = self.services.get_mut(&id) {
+            service.deadline = now + service.period;
+            service.callback = Some(callback);
+        }
+    }
+}
+
+thread_local! {
+    static SERVICES: RefCell<Services> = RefCell::new(Services::default());
+    static RUNNING: Cell<bool> = const { Cell::new(false) };
+    static SLICE: Cell<u64> = const { Cell::new(0) };
+    static NEXT_YIELD: Cell<u64> = const { Cell::new(0) };
+}
+
+fn cycles(d: Duration) -> u64 {
+    let hz = get_tsc_hz();
+
+    d.as_secs() * hz + u64::from(d.subsec_nanos()) * hz / 1_000_000_000
+}
+
+/// Run the callback of the current thread periodically.
+pub fn every<F>(name: &str, period: Duration, callback: F) -> ServiceId
+where
+    F: FnMut() + 'static,
+{
+    let period = cycles(period);
+
+    SERVICES.with(|services| {
+        services
+            .borrow_mut()
+            .add(name, period, rdtsc() + period, false, Box::new(callback))
+    })
+}
+
+/// Run the callback of the current thread once after the delay.
+pub fn after<F>(name: &str, delay: Duration, callback: F) -> ServiceId
+where
+    F: FnOnce() + 'static,
+{
+    let mut callback = Some(callback);
+    let deadline = rdtsc() + cycles(delay);
+
+    SERVICES.with(|services| {
+        services.borrow_mut().add(
+            name,
+            0,
+            deadline,
+            true,
+            Box::new(move || {
+                if let Some(callback) = callback.take() {
+                    callback()
+                }
+            }),
+        )
+    })
+}
+
+/// Cancel a service, returns its name if it is still registered.
+pub fn cancel(id: ServiceId) -> Option<String> {
+    SERVICES.with(|services| services.borrow_mut().services.remove(&id.0).map(|service| service.name))
+}
+
+/// The names of the registered services of the current thread.
+pub fn services() -> Vec<(ServiceId, String)> {
+    SERVICES.with(|services| {
+        services
+            .borrow()
+            .services
+            .iter()
+            .map(|(&id, service)| (ServiceId(id), service.name.clone()))
+            .collect()
+    })
+}
+
+/// The statistics of the current thread.
+pub fn stats() -> Stats {
+    SERVICES.with(|services| services.borrow().stats)
+}
+
+/// Set the time slice between two rounds of the services at the yield points of the current thread.
+pub fn set_slice(slice: Duration) {
+    SLICE.with(|s| s.set(cycles(slice)));
+}
+
+/// Run the due services of the current thread.
+pub fn run_pending() {
+    if RUNNING.with(|running| running.replace(true)) {
+        return;
+    }
+
+    let now = rdtsc();
+    let due = SERVICES.with(|services| services.borrow_mut().take_due(now));
+
+    for (id, mut callback) in due {
+        callback();
+
+        SERVICES.with(|services| services.borrow_mut().put_back(id, rdtsc(), callback));
+    }
+
+    RUNNING.with(|running| running.set(false));
+}
+
+/// Yield to the due services of the current thread, if the time slice has elapsed.
+///
+/// This should be called periodically from the long control-plane operations.
+#[inline]
+pub fn yield_point() {
+    let now = rdtsc();
+
+    if now < NEXT_YIELD.with(Cell::get) {
+        return;
+    }
+
+    let slice = SLICE.with(|slice| {
+        if slice.get() == 0 {
+            slice.set(cycles(DEFAULT_SLICE));
+        }
+
+        slice.get()
+    });
+
+    NEXT_YIELD.with(|next| next.set(now + slice));
+    SERVICES.with(|services| services.borrow_mut().stats.yields += 1);
+
+    run_pending();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_services() {
+        let mut services = Services::default();
+        let runs = Rc::new(Cell::new(0));
+
+        let periodic = {
+            let runs = runs.clone();
+
+            services.add("periodic", 10, 10, false, Box::new(move || runs.set(runs.get() + 1)))
+        };
+        let oneshot = services.add("oneshot", 0, 15, true, Box::new(|| {}));
+
+        assert!(services.take_due(5).is_empty());
+
+        let due = services.take_due(10);
+
+        assert_eq!(
+            due.iter().map(|&(id, _)| ServiceId(id)).collect::<Vec<_>>(),
+            vec![periodic]
+        );
+
+        // the running service is not run again
+        assert!(services.take_due(20).iter().all(|&(id, _)| ServiceId(id) != periodic));
+        assert!(!services.services.contains_key(&oneshot.0));
+
+        for (id, mut callback) in due {
+            callback();
+
+            services.put_back(id, 20, callback);
+        }
+
+        assert_eq!(runs.get(), 1);
+        assert_eq!(services.services[&periodic.0].deadline, 30);
+        assert_eq!(services.stats.runs, 2);
+
+        // the cancelled service is not put back
+        let mut due = services.take_due(30);
+
+        services.services.remove(&periodic.0);
+
+        let (id, callback) = due.pop().unwrap();
+
+        services.put_back(id, 30, callback);
+
+        assert!(services.services.is_empty());
+    }
+}
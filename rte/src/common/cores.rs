@@ -0,0 +1,418 @@
+//!
+//! Build the core lists and masks of the EAL arguments.
+//!
+//! The EAL only reports an opaque `invalid coremask` when a core is offline or out of range,
+//! `CoreSet` builds the set from ranges, sockets or the online cores, and checks it against
+//! the CPU topology detected from sysfs before `eal::init`, with a readable error.
+//!
+//! ```no_run
+//! use rte::cores::{CoreSet, CpuTopology};
+//! use rte::*;
+//!
+//! let topo = CpuTopology::detect().unwrap();
+//! let cores = CoreSet::on_socket(&topo, 0).unwrap();
+//! let master = cores.first().unwrap();
+//!
+//! let mut args = vec!["app".to_owned()];
+//!
+//! args.extend(cores.eal_args(&topo, Some(master)).unwrap());
+//!
+//! eal::init(&args).unwrap();
+//! ```
+//!
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::iter::FromIterator;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use failure::Error;
+
+use ffi;
+
+use errors::{ErrorKind, Result};
+
+const SYSFS_CPU_ONLINE: &str = "/sys/devices/system/cpu/online";
+const SYSFS_NODE_DIR: &str = "/sys/devices/system/node";
+
+fn invalid<S: Into<String>>(msg: S) -> Error {
+    ErrorKind::InvalidCoreSet(msg.into()).into()
+}
+
+/// The online CPUs and their NUMA sockets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpuTopology {
+    sockets: BTreeMap<u32, u32>,
+}
+
+impl CpuTopology {
+    /// Detect the online CPUs and their NUMA nodes from sysfs.
+    ///
+    /// The CPUs are on socket 0 if the kernel has no NUMA support.
+    pub fn detect() -> Result<Self> {
+        let online: CoreSet = fs::read_to_string(SYSFS_CPU_ONLINE)?.trim().parse()?;
+        let mut sockets: BTreeMap<u32, u32> = online.iter().map(|cpu| (cpu, 0)).collect();
+
+        if let Ok(entries) = fs::read_dir(SYSFS_NODE_DIR) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let name = entry.file_name();
+                let node = match name.to_str().and_then(|s| s.trim_start_matches("node").parse().ok()) {
+                    Some(node) if name.to_string_lossy().starts_with("node") => node,
+                    _ => continue,
+                };
+
+                let cpulist = fs::read_to_string(entry.path().join("cpulist"))?;
+
+                for cpu in cpulist.trim().parse::<CoreSet>()?.iter() {
+                    if let Some(socket) = sockets.get_mut(&cpu) {
+                        *socket = node;
+                    }
+                }
+            }
+        }
+
+        Ok(CpuTopology { sockets })
+    }
+
+    /// The online CPUs of the `(cpu, socket)` pairs.
+    pub fn new<I: IntoIterator<Item = (u32, u32)>>(cpus: I) -> Self {
+        CpuTopology {
+            sockets: cpus.into_iter().collect(),
+        }
+    }
+
+    /// The online CPUs.
+    pub fn online(&self) -> CoreSet {
+        self.sockets.keys().cloned().collect()
+    }
+
+    /// Test if the CPU is online.
+    pub fn is_online(&self, cpu: u32) -> bool {
+        self.sockets.contains_key(&cpu)
+    }
+
+    /// The socket of the online CPU.
+    pub fn socket_of(&self, cpu: u32) -> Option<u32> {
+        self.sockets.get(&cpu).cloned()
+    }
+
+    /// The sockets of the online CPUs.
+    pub fn sockets(&self) -> BTreeSet<u32> {
+        self.sockets.values().cloned().collect()
+    }
+}
+
+/// A set of cores, which is passed to the EAL as a core list or mask.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CoreSet(BTreeSet<u32>);
+
+impl CoreSet {
+    /// An empty set.
+    pub fn new() -> Self {
+        CoreSet::default()
+    }
+
+    /// The set of a single core.
+    pub fn single(cpu: u32) -> Self {
+        Some(cpu).into_iter().collect()
+    }
+
+    /// The set of a range of cores, for example `0..=3`.
+    pub fn range(cpus: RangeInclusive<u32>) -> Self {
+        cpus.collect()
+    }
+
+    /// All the online cores.
+    pub fn all(topo: &CpuTopology) -> Self {
+        topo.online()
+    }
+
+    /// All the online cores on the socket.
+    pub fn on_socket(topo: &CpuTopology, socket: u32) -> Result<Self> {
+        let cores: CoreSet = topo
+            .sockets
+            .iter()
+            .filter(|&(_, &s)| s == socket)
+            .map(|(&cpu, _)| cpu)
+            .collect();
+
+        if cores.is_empty() {
+            Err(invalid(format!(
+                "no online core on socket {}, the sockets are {}",
+                socket,
+                topo.sockets()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )))
+        } else {
+            Ok(cores)
+        }
+    }
+
+    /// Parse a core list, for example `0-3,8,10-11`.
+    pub fn parse_list(s: &str) -> Result<Self> {
+        let mut cores = CoreSet::new();
+
+        for part in s.split(',').map(str::trim) {
+            let mut bounds = part.splitn(2, '-').map(|n| {
+                n.trim()
+                    .parse::<u32>()
+                    .map_err(|_| invalid(format!("`{}` is not a core or a range of cores in `{}`", part, s)))
+            });
+
+            let first = bounds.next().unwrap()?;
+            let last = bounds.next().unwrap_or(Ok(first))?;
+
+            if first > last {
+                return Err(invalid(format!("the range `{}` is reversed in `{}`", part, s)));
+            }
+
+            cores.0.extend(first..=last);
+        }
+
+        Ok(cores)
+    }
+
+    /// Parse a hexadecimal core mask, for example `0xf`.
+    pub fn parse_mask(s: &str) -> Result<Self> {
+        let hex = s.trim_start_matches("0x").trim_start_matches("0X");
+
+        if hex.is_empty() {
+            return Err(invalid(format!("`{}` is not a hexadecimal core mask", s)));
+        }
+
+        let mut cores = CoreSet::new();
+
+        for (i, c) in hex.chars().rev().enumerate() {
+            let digit = c
+                .to_digit(16)
+                .ok_or_else(|| invalid(format!("`{}` is not a hexadecimal core mask", s)))?;
+
+            cores.0.extend(
+                (0..4)
+                    .filter(|bit| digit & (1 << bit) != 0)
+                    .map(|bit| i as u32 * 4 + bit),
+            );
+        }
+
+        Ok(cores)
+    }
+
+    /// Add a core.
+    pub fn with(mut self, cpu: u32) -> Self {
+        self.0.insert(cpu);
+        self
+    }
+
+    /// Remove a core.
+    pub fn without(mut self, cpu: u32) -> Self {
+        self.0.remove(&cpu);
+        self
+    }
+
+    /// Add the cores of another set.
+    pub fn union(mut self, other: &CoreSet) -> Self {
+        self.0.extend(other.iter());
+        self
+    }
+
+    /// Keep the cores on the socket.
+    pub fn filter_socket(self, topo: &CpuTopology, socket: u32) -> Self {
+        self.0
+            .into_iter()
+            .filter(|&cpu| topo.socket_of(cpu) == Some(socket))
+            .collect()
+    }
+
+    /// All the cores but the master one, which is the first core if not specified.
+    pub fn all_but_master(&self, master: Option<u32>) -> Self {
+        match master.or_else(|| self.first()) {
+            Some(master) => self.clone().without(master),
+            None => self.clone(),
+        }
+    }
+
+    /// Test if the set contains the core.
+    pub fn contains(&self, cpu: u32) -> bool {
+        self.0.contains(&cpu)
+    }
+
+    /// The number of cores.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The set has no core.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The lowest core, which the EAL uses as the master lcore by default.
+    pub fn first(&self) -> Option<u32> {
+        self.0.iter().next().cloned()
+    }
+
+    /// Iterate the cores in ascending order.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = u32> + 'a {
+        self.0.iter().cloned()
+    }
+
+    /// The core list of the `-l` option, with the consecutive cores folded into ranges.
+    pub fn to_list(&self) -> String {
+        let mut ranges: Vec<(u32, u32)> = vec![];
+
+        for cpu in self.iter() {
+            match ranges.last_mut() {
+                Some(&mut (_, ref mut last)) if *last + 1 == cpu => *last = cpu,
+                _ => ranges.push((cpu, cpu)),
+            }
+        }
+
+        ranges
+            .iter()
+            .map(|&(first, last)| {
+                if first == last {
+                    first.to_string()
+                } else {
+                    format!("{}-{}", first, last)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// The hexadecimal core mask of the `-c` option, which has no limit on the number of cores.
+    pub fn to_mask(&self) -> String {
+        let nibbles = self.0.iter().next_back().map_or(1, |&last| last as usize / 4 + 1);
+        let mut digits = vec![0u32; nibbles];
+
+        for cpu in self.iter() {
+            digits[cpu as usize / 4] |= 1 << (cpu % 4);
+        }
+
+        let hex: String = digits
+            .iter()
+            .rev()
+            .map(|&d| ::std::char::from_digit(d, 16).unwrap())
+            .collect();
+
+        format!("0x{}", hex)
+    }
+
+    /// Check the cores are online, and in the range of the EAL.
+    pub fn validate(&self, topo: &CpuTopology) -> Result<()> {
+        if self.is_empty() {
+            return Err(invalid("no core is selected"));
+        }
+
+        let out_of_range: CoreSet = self.iter().filter(|&cpu| cpu >= ffi::RTE_MAX_LCORE).collect();
+
+        if !out_of_range.is_empty() {
+            return Err(invalid(format!(
+                "cores {} are out of range, the EAL supports up to {} cores",
+                out_of_range,
+                ffi::RTE_MAX_LCORE
+            )));
+        }
+
+        let offline: CoreSet = self.iter().filter(|&cpu| !topo.is_online(cpu)).collect();
+
+        if !offline.is_empty() {
+            return Err(invalid(format!(
+                "cores {} are not online, the online cores are {}",
+                offline,
+                topo.online()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The validated EAL arguments of the cores, and the master lcore if specified.
+    pub fn eal_args(&self, topo: &CpuTopology, master: Option<u32>) -> Result<Vec<String>> {
+        self.validate(topo)?;
+
+        let mut args = vec!["-l".to_owned(), self.to_list()];
+
+        if let Some(master) = master {
+            if !self.contains(master) {
+                return Err(invalid(format!("the master core {} is not in cores {}", master, self)));
+            }
+
+            args.push("--master-lcore".to_owned());
+            args.push(master.to_string());
+        }
+
+        Ok(args)
+    }
+}
+
+impl FromIterator<u32> for CoreSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        CoreSet(iter.into_iter().collect())
+    }
+}
+
+/// Parse a hexadecimal core mask starting with `0x`, or a core list.
+impl FromStr for CoreSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        if s.starts_with("0x") || s.starts_with("0X") {
+            CoreSet::parse_mask(s)
+        } else {
+            CoreSet::parse_list(s)
+        }
+    }
+}
+
+impl fmt::Display for CoreSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_list())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_list() {
+        let cores = CoreSet::parse_list("0-3,8, 10-11").unwrap();
+
+        assert_eq!(cores.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(cores.to_list(), "0-3,8,10-11");
+        assert_eq!(cores.to_mask(), "0xd0f");
+        assert_eq!("0xd0f".parse::<CoreSet>().unwrap(), cores);
+        assert_eq!(CoreSet::range(64..=65).to_mask(), "0x30000000000000000");
+
+        assert!(CoreSet::parse_list("3-1").is_err());
+        assert!(CoreSet::parse_list("a").is_err());
+        assert!(CoreSet::parse_mask("0xg").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let topo = CpuTopology::new(vec![(0, 0), (1, 0), (2, 1), (3, 1)]);
+
+        assert_eq!(CoreSet::on_socket(&topo, 1).unwrap().to_list(), "2-3");
+        assert!(CoreSet::on_socket(&topo, 2).is_err());
+        assert_eq!(CoreSet::all(&topo).all_but_master(None).to_list(), "1-3");
+
+        assert_eq!(
+            CoreSet::range(0..=5).validate(&topo).unwrap_err().to_string(),
+            "invalid core set, cores 4-5 are not online, the online cores are 0-3"
+        );
+        assert_eq!(
+            CoreSet::range(0..=3).eal_args(&topo, Some(1)).unwrap(),
+            vec!["-l", "0-3", "--master-lcore", "1"]
+        );
+        assert!(CoreSet::range(2..=3).eal_args(&topo, Some(1)).is_err());
+        assert!(CoreSet::new().validate(&topo).is_err());
+    }
+}
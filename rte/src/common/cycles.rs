@@ -34,3 +34,9 @@ pub fn rdtsc() -> u64 {
 pub fn rdtsc_precise() -> u64 {
     unsafe { ffi::_rte_rdtsc_precise() }
 }
+
+/// Pause CPU execution for a short while, e.g. in a tight loop polling a shared resource.
+#[inline]
+pub fn pause() {
+    unsafe { ffi::_rte_pause() }
+}
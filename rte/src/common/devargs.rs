@@ -1,3 +1,4 @@
+use std::io;
 use std::os::unix::io::AsRawFd;
 
 use cfile;
@@ -5,6 +6,7 @@ use cfile;
 use ffi;
 
 use errors::Result;
+use utils;
 
 /// Type of generic device
 #[repr(u32)]
@@ -33,3 +35,10 @@ pub fn dump<S: AsRawFd>(s: &S) {
         }
     }
 }
+
+/// This function dumps the list of user device and their arguments to the writer.
+pub fn dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    utils::capture(w, |f| unsafe { ffi::rte_devargs_dump(f) })?;
+
+    Ok(())
+}
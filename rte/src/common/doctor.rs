@@ -0,0 +1,815 @@
+//!
+//! Diagnose the environment before the EAL initialization.
+//!
+//! The EAL reports most of the environment problems with an opaque `EAL init failed`,
+//! `doctor` checks the version, the optional libraries and the linked PMDs of DPDK, the hugepages of the sockets, the drivers and IOMMU groups of the PCI devices,
+//! the CPU governor and isolation of the cores, the running primary process of the file prefix,
+//! and the size of the mbuf pool against the descriptors, with a hint to fix each problem.
+//!
+//! ```no_run
+//! use rte::*;
+//!
+//! let report = rte::doctor_with(&DoctorConfig::new().with_pci_device("0000:01:00.0").with_cores("1-3".parse().unwrap()));
+//!
+//! if !report.is_ok() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+//!
+use std::fmt;
+use std::fs;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use libc;
+
+use ffi;
+
+use common::cores::{CoreSet, CpuTopology};
+use common::features::features;
+use common::version::{runtime_version, Version};
+
+const SYSFS_NODE_DIR: &str = "/sys/devices/system/node";
+const SYSFS_HUGEPAGES_DIR: &str = "/sys/kernel/mm/hugepages";
+const SYSFS_PCI_DEVICES_DIR: &str = "/sys/bus/pci/devices";
+const SYSFS_IOMMU_GROUPS_DIR: &str = "/sys/kernel/iommu_groups";
+const SYSFS_VFIO_NOIOMMU: &str = "/sys/module/vfio/parameters/enable_unsafe_noiommu_mode";
+const SYSFS_CPU_DIR: &str = "/sys/devices/system/cpu";
+const PROC_MOUNTS: &str = "/proc/mounts";
+
+/// The PCI drivers which the DPDK PMDs could use.
+const UIO_DRIVERS: &[&str] = &["vfio-pci", "igb_uio", "uio_pci_generic"];
+
+/// The kernel drivers which work with the bifurcated PMDs, e.g. mlx5.
+const BIFURCATED_DRIVERS: &[&str] = &["mlx4_core", "mlx5_core"];
+
+/// The memory overhead of a mbuf in the pool, the mbuf header and the mempool object header.
+const MBUF_OVERHEAD: u64 = 128 + 64;
+
+/// The severity of a check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Severity {
+    /// The check passed.
+    Ok,
+    /// The environment works, but it could be tuned.
+    Info,
+    /// The environment may work with a degraded performance.
+    Warning,
+    /// The EAL initialization or the application will fail.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Severity::Ok => "ok",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The result of a check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Check {
+    /// The name of the check, e.g. `hugepages`.
+    pub name: &'static str,
+    /// The severity of the result.
+    pub severity: Severity,
+    /// What was found.
+    pub message: String,
+    /// How to fix it.
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn ok<S: Into<String>>(name: &'static str, message: S) -> Self {
+        Check {
+            name,
+            severity: Severity::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn problem<S: Into<String>, H: Into<String>>(name: &'static str, severity: Severity, message: S, hint: H) -> Self {
+        Check {
+            name,
+            severity,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// The report of the checks, which could be serialized as JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// No check failed with an error.
+    pub fn is_ok(&self) -> bool {
+        self.severity() < Severity::Error
+    }
+
+    /// The highest severity of the checks.
+    pub fn severity(&self) -> Severity {
+        self.checks.iter().map(|c| c.severity).max().unwrap_or(Severity::Ok)
+    }
+
+    /// The checks failed with an error.
+    pub fn errors(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|c| c.severity == Severity::Error)
+    }
+
+    /// The checks passed with a warning.
+    pub fn warnings(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|c| c.severity == Severity::Warning)
+    }
+
+    fn push(&mut self, check: Check) {
+        self.checks.push(check)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{:>7}] {}: {}", check.severity, check.name, check.message)?;
+
+            if let Some(ref hint) = check.hint {
+                writeln!(f, "          hint: {}", hint)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The mbuf pool and the descriptors which take the mbufs from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MbufRequirement {
+    /// The number of mbufs in the pool.
+    pub nb_mbufs: u32,
+    /// The data room size of a mbuf, including the headroom.
+    pub data_room_size: u32,
+    /// The per-lcore cache size of the pool.
+    pub cache_size: u32,
+    /// The number of ports.
+    pub nb_ports: u32,
+    /// The number of RX queues of a port.
+    pub nb_rx_queues: u32,
+    /// The number of TX queues of a port.
+    pub nb_tx_queues: u32,
+    /// The number of RX descriptors of a queue.
+    pub nb_rx_desc: u32,
+    /// The number of TX descriptors of a queue.
+    pub nb_tx_desc: u32,
+    /// The number of lcores using the pool.
+    pub nb_lcores: u32,
+    /// The burst size of the lcores.
+    pub burst_size: u32,
+}
+
+impl MbufRequirement {
+    /// The minimum number of mbufs held by the descriptors, the caches and the bursts in flight.
+    pub fn min_mbufs(&self) -> u64 {
+        let desc = u64::from(self.nb_ports)
+            * (u64::from(self.nb_rx_queues) * u64::from(self.nb_rx_desc)
+                + u64::from(self.nb_tx_queues) * u64::from(self.nb_tx_desc));
+        let lcores = u64::from(self.nb_lcores) * (u64::from(self.cache_size) + u64::from(self.burst_size));
+
+        desc + lcores
+    }
+
+    /// The memory of the pool, in bytes.
+    pub fn pool_size(&self) -> u64 {
+        u64::from(self.nb_mbufs) * (u64::from(self.data_room_size) + MBUF_OVERHEAD)
+    }
+}
+
+/// What the application is going to use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoctorConfig {
+    /// The PCI addresses of the devices, e.g. `0000:01:00.0`.
+    pub pci_devices: Vec<String>,
+    /// The cores of the lcores.
+    pub cores: Option<CoreSet>,
+    /// The master core, which is not required to be isolated.
+    pub master: Option<u32>,
+    /// The file prefix of the process.
+    pub file_prefix: String,
+    /// The mbuf pool.
+    pub mbufs: Option<MbufRequirement>,
+}
+
+impl Default for DoctorConfig {
+    fn default() -> Self {
+        DoctorConfig {
+            pci_devices: vec![],
+            cores: None,
+            master: None,
+            file_prefix: "rte".to_owned(),
+            mbufs: None,
+        }
+    }
+}
+
+impl DoctorConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the PCI device.
+    pub fn with_pci_device<S: Into<String>>(mut self, addr: S) -> Self {
+        self.pci_devices.push(addr.into());
+        self
+    }
+
+    /// Check the cores.
+    pub fn with_cores(mut self, cores: CoreSet) -> Self {
+        self.cores = Some(cores);
+        self
+    }
+
+    /// Set the master core.
+    pub fn with_master(mut self, master: u32) -> Self {
+        self.master = Some(master);
+        self
+    }
+
+    /// Set the file prefix, `rte` by default.
+    pub fn with_file_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.file_prefix = prefix.into();
+        self
+    }
+
+    /// Check the size of the mbuf pool.
+    pub fn with_mbufs(mut self, mbufs: MbufRequirement) -> Self {
+        self.mbufs = Some(mbufs);
+        self
+    }
+}
+
+/// Diagnose the environment with the default config.
+pub fn doctor() -> Report {
+    doctor_with(&DoctorConfig::default())
+}
+
+/// Diagnose the environment for the config.
+pub fn doctor_with(conf: &DoctorConfig) -> Report {
+    let mut report = Report::default();
+    let topo = CpuTopology::detect().ok();
+
+    report.push(check_version());
+    report.push(check_pmds());
+
+    let free_mem = check_hugepages(&mut report, conf, topo.as_ref());
+
+    for addr in &conf.pci_devices {
+        report.push(check_pci_device(addr));
+    }
+
+    if let Some(ref cores) = conf.cores {
+        check_cores(&mut report, cores, conf.master, topo.as_ref());
+    }
+
+    report.push(check_primary_process(&conf.file_prefix));
+
+    if let Some(ref mbufs) = conf.mbufs {
+        report.push(check_mbufs(mbufs, free_mem));
+    }
+
+    report
+}
+
+fn read_trimmed<P: AsRef<Path>>(path: P) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_owned())
+}
+
+fn read_number<P: AsRef<Path>>(path: P) -> Option<u64> {
+    read_trimmed(path).and_then(|s| s.parse().ok())
+}
+
+/// The free hugepage memory in bytes, of the hugepage directory, e.g. `hugepages-2048kB`.
+fn free_hugepages(dir: &Path) -> u64 {
+    let mut free = 0;
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let size_kb = match parse_hugepage_size(&name) {
+                Some(size) => size,
+                None => continue,
+            };
+
+            free += read_number(entry.path().join("free_hugepages")).unwrap_or_default() * size_kb * 1024;
+        }
+    }
+
+    free
+}
+
+/// Parse the page size in kB of the hugepage directory, e.g. `hugepages-1048576kB`.
+fn parse_hugepage_size(name: &str) -> Option<u64> {
+    if name.starts_with("hugepages-") && name.ends_with("kB") {
+        name["hugepages-".len()..name.len() - 2].parse().ok()
+    } else {
+        None
+    }
+}
+
+/// The mount points of hugetlbfs.
+fn hugetlbfs_mounts() -> Vec<String> {
+    read_trimmed(PROC_MOUNTS)
+        .map(|mounts| {
+            mounts
+                .lines()
+                .filter_map(|line| {
+                    let fields = line.split_whitespace().collect::<Vec<_>>();
+
+                    if fields.len() > 2 && fields[2] == "hugetlbfs" {
+                        Some(fields[1].to_owned())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1 << 30 {
+        format!("{:.1} GB", bytes as f64 / (1u64 << 30) as f64)
+    } else {
+        format!("{} MB", bytes >> 20)
+    }
+}
+
+/// Check the hugepages of the sockets, returns the free hugepage memory.
+fn check_hugepages(report: &mut Report, conf: &DoctorConfig, topo: Option<&CpuTopology>) -> u64 {
+    const NAME: &str = "hugepages";
+
+    let mounts = hugetlbfs_mounts();
+
+    if mounts.is_empty() {
+        report.push(Check::problem(
+            NAME,
+            Severity::Error,
+            "hugetlbfs is not mounted",
+            "mount -t hugetlbfs nodev /dev/hugepages",
+        ));
+    } else {
+        report.push(Check::ok(NAME, format!("hugetlbfs mounted at {}", mounts.join(", "))));
+    }
+
+    let mut sockets = fs::read_dir(SYSFS_NODE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+
+                    if let Some(id) = name.strip_prefix("node") {
+                        id.parse::<u32>()
+                            .ok()
+                            .map(|node| (node, free_hugepages(&entry.path().join("hugepages"))))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if sockets.is_empty() {
+        sockets.push((0, free_hugepages(Path::new(SYSFS_HUGEPAGES_DIR))));
+    }
+
+    sockets.sort();
+
+    let total = sockets.iter().map(|&(_, free)| free).sum();
+
+    if total == 0 {
+        report.push(Check::problem(
+            NAME,
+            Severity::Error,
+            "no free hugepages",
+            "reserve hugepages, e.g. `echo 1024 > /sys/kernel/mm/hugepages/hugepages-2048kB/nr_hugepages`",
+        ));
+
+        return 0;
+    }
+
+    let used_sockets = match (conf.cores.as_ref(), topo) {
+        (Some(cores), Some(topo)) => cores.iter().filter_map(|cpu| topo.socket_of(cpu)).collect(),
+        _ => sockets.iter().map(|&(node, _)| node).collect::<Vec<_>>(),
+    };
+
+    for &(node, free) in &sockets {
+        if free == 0 && used_sockets.contains(&node) {
+            report.push(Check::problem(
+                NAME,
+                Severity::Warning,
+                format!("no free hugepages on socket {}, which has the cores in use", node),
+                format!(
+                    "reserve hugepages on the socket, e.g. `echo 1024 > {}/node{}/hugepages/hugepages-2048kB/nr_hugepages`",
+                    SYSFS_NODE_DIR, node
+                ),
+            ));
+        } else {
+            report.push(Check::ok(
+                NAME,
+                format!("{} free on socket {}", format_size(free), node),
+            ));
+        }
+    }
+
+    total
+}
+
+/// Check the driver and IOMMU group of the PCI device.
+fn check_pci_device(addr: &str) -> Check {
+    const NAME: &str = "pci";
+
+    let dev = Path::new(SYSFS_PCI_DEVICES_DIR).join(addr);
+
+    if !dev.exists() {
+        return Check::problem(
+            NAME,
+            Severity::Error,
+            format!("device {} not found", addr),
+            "check the address with `lspci -D` or `dpdk-devbind.py --status`",
+        );
+    }
+
+    let driver = fs::read_link(dev.join("driver"))
+        .ok()
+        .and_then(|p| p.file_name().map(|name| name.to_string_lossy().into_owned()));
+
+    match driver {
+        None => Check::problem(
+            NAME,
+            Severity::Error,
+            format!("device {} is not bound to any driver", addr),
+            format!("dpdk-devbind.py --bind=vfio-pci {}", addr),
+        ),
+        Some(ref driver) if driver == "vfio-pci" => check_iommu_group(addr, &dev),
+        Some(ref driver) if UIO_DRIVERS.contains(&driver.as_str()) => Check::problem(
+            NAME,
+            Severity::Info,
+            format!("device {} is bound to {}", addr, driver),
+            format!("vfio-pci is recommended, dpdk-devbind.py --bind=vfio-pci {}", addr),
+        ),
+        Some(ref driver) if BIFURCATED_DRIVERS.contains(&driver.as_str()) => Check::ok(
+            NAME,
+            format!(
+                "device {} is bound to {}, which works with the bifurcated PMD",
+                addr, driver
+            ),
+        ),
+        Some(driver) => Check::problem(
+            NAME,
+            Severity::Error,
+            format!("device {} is bound to the kernel driver {}", addr, driver),
+            format!("dpdk-devbind.py --bind=vfio-pci {}", addr),
+        ),
+    }
+}
+
+fn check_iommu_group(addr: &str, dev: &Path) -> Check {
+    const NAME: &str = "iommu";
+
+    let noiommu = read_trimmed(SYSFS_VFIO_NOIOMMU).is_some_and(|s| s == "Y");
+    let has_iommu = fs::read_dir(SYSFS_IOMMU_GROUPS_DIR).is_ok_and(|mut entries| entries.next().is_some());
+
+    if !has_iommu && !noiommu {
+        return Check::problem(
+            NAME,
+            Severity::Error,
+            format!("device {} is bound to vfio-pci, but the IOMMU is disabled", addr),
+            "add `intel_iommu=on iommu=pt` to the kernel command line, \
+             or enable the no-IOMMU mode of vfio",
+        );
+    }
+
+    let group = fs::read_link(dev.join("iommu_group"))
+        .ok()
+        .and_then(|p| p.file_name().map(|name| name.to_string_lossy().into_owned()));
+
+    match group {
+        Some(group) => {
+            let path = if noiommu && !has_iommu {
+                PathBuf::from(format!("/dev/vfio/noiommu-{}", group))
+            } else {
+                PathBuf::from(format!("/dev/vfio/{}", group))
+            };
+
+            match fs::OpenOptions::new().read(true).write(true).open(&path) {
+                Ok(_) => Check::ok(
+                    NAME,
+                    format!("device {} is bound to vfio-pci, IOMMU group {}", addr, group),
+                ),
+                Err(err) => Check::problem(
+                    NAME,
+                    Severity::Error,
+                    format!("can't open {} of device {}, {}", path.display(), addr, err),
+                    "run as root, or grant the access to the vfio group, \
+                     and unbind the other devices of the IOMMU group from the kernel drivers",
+                ),
+            }
+        }
+        None => Check::problem(
+            NAME,
+            Severity::Error,
+            format!("device {} has no IOMMU group", addr),
+            "enable the IOMMU in the BIOS and the kernel command line",
+        ),
+    }
+}
+
+/// The isolated CPUs, from `isolcpus` of the kernel command line.
+fn isolated_cpus() -> CoreSet {
+    read_trimmed(Path::new(SYSFS_CPU_DIR).join("isolated"))
+        .and_then(|s| {
+            if s.is_empty() {
+                Some(CoreSet::new())
+            } else {
+                s.parse().ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Check the governor and isolation of the cores.
+fn check_cores(report: &mut Report, cores: &CoreSet, master: Option<u32>, topo: Option<&CpuTopology>) {
+    if let Some(topo) = topo {
+        if let Err(err) = cores.validate(topo) {
+            report.push(Check::problem(
+                "cores",
+                Severity::Error,
+                err.to_string(),
+                "use the online cores, see `lscpu`",
+            ));
+
+            return;
+        }
+    }
+
+    let slow = cores
+        .iter()
+        .filter(|&cpu| {
+            read_trimmed(format!("{}/cpu{}/cpufreq/scaling_governor", SYSFS_CPU_DIR, cpu))
+                .is_some_and(|governor| governor != "performance")
+        })
+        .collect::<CoreSet>();
+
+    if slow.is_empty() {
+        report.push(Check::ok("governor", format!("cores {} are not throttled", cores)));
+    } else {
+        report.push(Check::problem(
+            "governor",
+            Severity::Warning,
+            format!("cores {} are not using the performance governor", slow),
+            "cpupower frequency-set -g performance",
+        ));
+    }
+
+    let workers = cores.all_but_master(master.or_else(|| cores.first()));
+    let isolated = isolated_cpus();
+    let shared = workers
+        .iter()
+        .filter(|&cpu| !isolated.contains(cpu))
+        .collect::<CoreSet>();
+
+    if shared.is_empty() {
+        report.push(Check::ok("isolcpus", format!("worker cores {} are isolated", workers)));
+    } else {
+        report.push(Check::problem(
+            "isolcpus",
+            Severity::Info,
+            format!("worker cores {} are not isolated from the scheduler", shared),
+            format!(
+                "add `isolcpus={} nohz_full={}` to the kernel command line",
+                shared, shared
+            ),
+        ));
+    }
+}
+
+/// Check the version of the linked DPDK library against the compiled one.
+fn check_version() -> Check {
+    const NAME: &str = "dpdk";
+
+    let compiled = Version::compiled();
+
+    match runtime_version() {
+        Ok(ref runtime) if runtime.num() != compiled.num() => Check::problem(
+            NAME,
+            Severity::Warning,
+            format!(
+                "{} is linked, but the bindings were compiled with {}",
+                runtime, compiled
+            ),
+            "rebuild the application against the installed DPDK",
+        ),
+        Ok(runtime) => Check::ok(NAME, format!("{} with {}", runtime, features())),
+        Err(err) => Check::problem(
+            NAME,
+            Severity::Warning,
+            format!("unknown version of the linked DPDK library, {}", err),
+            "rebuild the application against the installed DPDK",
+        ),
+    }
+}
+
+/// Check the linked PMDs are loaded, otherwise their drivers are never registered.
+fn check_pmds() -> Check {
+    const NAME: &str = "pmds";
+
+    let features = features();
+    let dropped = features.dropped_pmds();
+
+    if !dropped.is_empty() {
+        Check::problem(
+            NAME,
+            Severity::Error,
+            format!(
+                "{} are dropped by the linker, no port of them would be found",
+                dropped.join(", ")
+            ),
+            "link with `-C link-arg=-Wl,--no-as-needed`, or load the PMDs with the `-d` option",
+        )
+    } else if features.pmds().next().is_none() && !features.shared() {
+        Check::problem(
+            NAME,
+            Severity::Warning,
+            "no PMD is linked, no port would be found",
+            "rebuild DPDK with the PMDs of the NICs",
+        )
+    } else {
+        Check::ok(
+            NAME,
+            format!(
+                "{} {} PMDs are linked",
+                features.pmds().count(),
+                if features.shared() { "shared" } else { "static" }
+            ),
+        )
+    }
+}
+
+/// The runtime directory of the file prefix.
+fn runtime_dir(prefix: &str) -> PathBuf {
+    let base = if unsafe { libc::getuid() } == 0 {
+        PathBuf::from("/var/run")
+    } else {
+        ::std::env::var_os("XDG_RUNTIME_DIR").map_or_else(|| PathBuf::from("/tmp"), PathBuf::from)
+    };
+
+    base.join("dpdk").join(prefix)
+}
+
+/// Check if a primary process is holding the config file of the prefix.
+fn check_primary_process(prefix: &str) -> Check {
+    const NAME: &str = "primary";
+
+    let config = runtime_dir(prefix).join("config");
+
+    let f = match fs::File::open(&config) {
+        Ok(f) => f,
+        Err(_) => return Check::ok(NAME, format!("no primary process with file prefix {}", prefix)),
+    };
+
+    let mut lock = libc::flock {
+        l_type: libc::F_WRLCK as i16,
+        l_whence: libc::SEEK_SET as i16,
+        l_start: 0,
+        l_len: 0,
+        l_pid: 0,
+    };
+
+    if unsafe { libc::fcntl(f.as_raw_fd(), libc::F_GETLK, &mut lock) } == 0 && lock.l_type != libc::F_UNLCK as i16 {
+        Check::problem(
+            NAME,
+            Severity::Error,
+            format!("primary process {} is running with file prefix {}", lock.l_pid, prefix),
+            "use another `--file-prefix`, or `--proc-type=secondary` to attach to it",
+        )
+    } else {
+        Check::ok(NAME, format!("no primary process with file prefix {}", prefix))
+    }
+}
+
+/// Check the size of the mbuf pool against the descriptors and the free hugepages.
+fn check_mbufs(mbufs: &MbufRequirement, free_mem: u64) -> Check {
+    const NAME: &str = "mbufs";
+
+    let min = mbufs.min_mbufs();
+
+    if u64::from(mbufs.nb_mbufs) < min {
+        Check::problem(
+            NAME,
+            Severity::Error,
+            format!(
+                "{} mbufs is less than {} held by the descriptors and lcore caches",
+                mbufs.nb_mbufs, min
+            ),
+            format!("create the pool with at least {} mbufs, or reduce the descriptors", min),
+        )
+    } else if mbufs.cache_size > ffi::RTE_MEMPOOL_CACHE_MAX_SIZE {
+        Check::problem(
+            NAME,
+            Severity::Error,
+            format!(
+                "cache size {} exceeds {}",
+                mbufs.cache_size,
+                ffi::RTE_MEMPOOL_CACHE_MAX_SIZE
+            ),
+            format!("use a cache size up to {}", ffi::RTE_MEMPOOL_CACHE_MAX_SIZE),
+        )
+    } else if free_mem > 0 && mbufs.pool_size() > free_mem {
+        Check::problem(
+            NAME,
+            Severity::Error,
+            format!(
+                "the pool needs {}, but only {} hugepages are free",
+                format_size(mbufs.pool_size()),
+                format_size(free_mem)
+            ),
+            "reserve more hugepages, or create the pool with less mbufs",
+        )
+    } else {
+        Check::ok(
+            NAME,
+            format!("{} mbufs for at least {} held by the descriptors", mbufs.nb_mbufs, min),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mbuf_requirement() {
+        let mut mbufs = MbufRequirement {
+            nb_mbufs: 8192,
+            data_room_size: ffi::RTE_MBUF_DEFAULT_BUF_SIZE,
+            cache_size: 256,
+            nb_ports: 2,
+            nb_rx_queues: 2,
+            nb_tx_queues: 2,
+            nb_rx_desc: 1024,
+            nb_tx_desc: 1024,
+            nb_lcores: 2,
+            burst_size: 32,
+        };
+
+        assert_eq!(mbufs.min_mbufs(), 2 * (2 * 1024 + 2 * 1024) + 2 * (256 + 32));
+        assert_eq!(check_mbufs(&mbufs, 0).severity, Severity::Error);
+
+        mbufs.nb_mbufs = 16384;
+
+        assert_eq!(check_mbufs(&mbufs, 0).severity, Severity::Ok);
+        assert_eq!(check_mbufs(&mbufs, 1 << 20).severity, Severity::Error);
+
+        assert_eq!(parse_hugepage_size("hugepages-2048kB"), Some(2048));
+        assert_eq!(parse_hugepage_size("hugepages-1048576kB"), Some(1048576));
+        assert_eq!(parse_hugepage_size("nr_hugepages"), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut report = Report::default();
+
+        assert!(report.is_ok());
+
+        report.push(Check::ok("hugepages", "1.0 GB free on socket 0"));
+        report.push(Check::problem(
+            "governor",
+            Severity::Warning,
+            "cores 1 are slow",
+            "cpupower",
+        ));
+
+        assert!(report.is_ok());
+        assert_eq!(report.severity(), Severity::Warning);
+        assert_eq!(report.warnings().count(), 1);
+
+        report.push(Check::problem("pci", Severity::Error, "not found", "lspci"));
+
+        assert!(!report.is_ok());
+        assert_eq!(report.errors().count(), 1);
+        assert_eq!(
+            report.to_string(),
+            "[     ok] hugepages: 1.0 GB free on socket 0\n\
+             [warning] governor: cores 1 are slow\n          hint: cpupower\n\
+             [  error] pci: not found\n          hint: lspci\n"
+        );
+    }
+}
@@ -5,12 +5,15 @@ use std::os::raw::c_char;
 use std::path::PathBuf;
 use std::ptr;
 
-use ffi::{self, rte_proc_type_t::*};
+use ffi::{self, rte_iova_mode::*, rte_proc_type_t::*};
+use num_traits::FromPrimitive;
 
-use errors::{AsResult, Result};
+use common::features::{self, features};
+use errors::{AsResult, ErrorKind::DroppedPmds, Result};
 use utils::AsCString;
 
 pub use common::config;
+pub use common::trace::TraceMode;
 pub use launch::{mp_remote_launch, mp_wait_lcore, remote_launch};
 
 #[repr(i32)]
@@ -22,6 +25,39 @@ pub enum ProcType {
     Invalid = RTE_PROC_INVALID,
 }
 
+impl fmt::Display for ProcType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ProcType::Auto => "auto",
+            ProcType::Primary => "primary",
+            ProcType::Secondary => "secondary",
+            ProcType::Invalid => "invalid",
+        })
+    }
+}
+
+/// IOVA mapping mode.
+///
+/// IOVA mapping mode is iommu programming mode of a device.
+/// That device (for example: IOMMU backed DMA device) based
+/// on IOVA mode will generate physical or virtual address.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum IovaMode {
+    /// Don't care mode
+    DontCare = RTE_IOVA_DC,
+    /// DMA using physical address
+    PhysAddr = RTE_IOVA_PA,
+    /// DMA using virtual address
+    VirtAddr = RTE_IOVA_VA,
+}
+
+impl From<ffi::rte_iova_mode::Type> for IovaMode {
+    fn from(mode: ffi::rte_iova_mode::Type) -> Self {
+        IovaMode::from_u32(mode).unwrap_or(IovaMode::DontCare)
+    }
+}
+
 extern "C" {
     // fn vdrvinitfn_pmd_af_packet_drv();
     // fn vdrvinitfn_bbdev_null_pmd_drv();
@@ -103,6 +139,18 @@ pub fn init<S: fmt::Debug + AsRef<str>>(args: &[S]) -> Result<i32> {
         init_pmd_drivers();
     }
 
+    // the PMDs dropped by the linker are never registered, unless they are loaded from the driver paths by EAL
+    let paths = driver_paths(args);
+    let dropped: Vec<_> = features()
+        .dropped_pmds()
+        .into_iter()
+        .filter(|lib| !paths.iter().any(|path| features::is_loaded_from(lib, path)))
+        .collect();
+
+    if !dropped.is_empty() {
+        return Err(DroppedPmds(dropped.join(", ")).into());
+    }
+
     let parsed = if args.is_empty() {
         unsafe { ffi::rte_eal_init(0, ptr::null_mut()) }
     } else {
@@ -117,6 +165,78 @@ pub fn init<S: fmt::Debug + AsRef<str>>(args: &[S]) -> Result<i32> {
     parsed.as_result().map(|_| parsed)
 }
 
+// the EAL options which have an argument
+const EAL_SHORT_OPTS_WITH_ARG: &str = "bcdlmnrsw";
+const EAL_LONG_OPTS_WITH_ARG: &[&str] = &[
+    "base-virtaddr",
+    "driver-path",
+    "file-prefix",
+    "huge-dir",
+    "iova-mode",
+    "lcores",
+    "log-level",
+    "master-lcore",
+    "mbuf-pool-ops-name",
+    "pci-blacklist",
+    "pci-whitelist",
+    "proc-type",
+    "socket-limit",
+    "socket-mem",
+    "vdev",
+    "vfio-intr",
+];
+
+/// The paths which EAL loads the shared drivers from, `CONFIG_RTE_EAL_PMD_PATH`
+/// and the `-d` or `--driver-path` options, which are parsed like `getopt_long` does.
+fn driver_paths<S: AsRef<str>>(args: &[S]) -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    if !ffi::CONFIG_RTE_EAL_PMD_PATH.is_empty() {
+        paths.push(PathBuf::from(ffi::CONFIG_RTE_EAL_PMD_PATH));
+    }
+
+    // skip the program name
+    let mut args = args.iter().map(AsRef::as_ref).skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        } else if let Some(opt) = arg.strip_prefix("--") {
+            let (name, value) = match opt.find('=') {
+                Some(pos) => (&opt[..pos], Some(&opt[pos + 1..])),
+                None => (opt, None),
+            };
+
+            if EAL_LONG_OPTS_WITH_ARG.contains(&name) {
+                let value = value.or_else(|| args.next());
+
+                if name == "driver-path" {
+                    paths.extend(value.map(PathBuf::from));
+                }
+            }
+        } else if arg.starts_with('-') {
+            // the short options could be grouped, e.g. `-vd librte_pmd_ixgbe.so`
+            for (pos, c) in arg.char_indices().skip(1) {
+                if EAL_SHORT_OPTS_WITH_ARG.contains(c) {
+                    let value = if pos + 1 < arg.len() {
+                        Some(&arg[pos + 1..])
+                    } else {
+                        args.next()
+                    };
+
+                    if c == 'd' {
+                        paths.extend(value.map(PathBuf::from));
+                    }
+
+                    break;
+                }
+            }
+        }
+    }
+
+    paths
+}
+
 /// Clean up the Environment Abstraction Layer (EAL)
 pub fn cleanup() -> Result<()> {
     unsafe { ffi::rte_eal_cleanup() }.as_result().map(|_| ())
@@ -163,3 +283,199 @@ pub fn runtime_dir() -> PathBuf {
             .into_owned()
     })
 }
+
+/// Get the iova mode
+pub fn iova_mode() -> IovaMode {
+    unsafe { ffi::rte_eal_iova_mode() }.into()
+}
+
+/// A builder of the EAL arguments.
+///
+/// ```no_run
+/// use rte::eal::{self, IovaMode};
+///
+/// eal::Builder::new("app")
+///     .core_list("0-3")
+///     .in_memory()
+///     .iova_mode(IovaMode::VirtAddr)
+///     .init()
+///     .expect("Cannot init EAL");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder {
+    args: Vec<String>,
+}
+
+impl Builder {
+    /// Create a builder with the program name.
+    pub fn new<S: Into<String>>(program: S) -> Self {
+        Builder {
+            args: vec![program.into()],
+        }
+    }
+
+    /// Add a raw argument.
+    pub fn arg<S: Into<String>>(&mut self, arg: S) -> &mut Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add raw arguments.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(|s| s.into()));
+        self
+    }
+
+    fn opt<S: ToString>(&mut self, name: &str, value: S) -> &mut Self {
+        self.arg(format!("{}={}", name, value.to_string()))
+    }
+
+    /// Hexadecimal bitmask of cores to run on.
+    pub fn core_mask(&mut self, mask: u64) -> &mut Self {
+        self.arg("-c").arg(format!("{:x}", mask))
+    }
+
+    /// List of cores to run on, e.g. `0-3,8`.
+    pub fn core_list(&mut self, list: &str) -> &mut Self {
+        self.arg("-l").arg(list)
+    }
+
+    /// Core ID that is used as master.
+    pub fn master_lcore(&mut self, lcore_id: u32) -> &mut Self {
+        self.opt("--master-lcore", lcore_id)
+    }
+
+    /// Number of memory channels.
+    pub fn memory_channels(&mut self, n: u32) -> &mut Self {
+        self.arg("-n").arg(n.to_string())
+    }
+
+    /// Memory to allocate on each socket, in megabytes.
+    pub fn socket_mem(&mut self, mem: &[usize]) -> &mut Self {
+        let mem = mem.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(",");
+
+        self.opt("--socket-mem", mem)
+    }
+
+    /// Type of this process.
+    pub fn proc_type(&mut self, proc_type: ProcType) -> &mut Self {
+        self.opt("--proc-type", proc_type)
+    }
+
+    /// Prefix for hugepage filenames.
+    pub fn file_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.opt("--file-prefix", prefix)
+    }
+
+    /// Directory where hugetlbfs is mounted.
+    pub fn huge_dir(&mut self, dir: &str) -> &mut Self {
+        self.opt("--huge-dir", dir)
+    }
+
+    /// Do not create any shared data structures and run entirely in memory.
+    pub fn in_memory(&mut self) -> &mut Self {
+        self.arg("--in-memory")
+    }
+
+    /// Use malloc instead of hugetlbfs.
+    pub fn no_huge(&mut self) -> &mut Self {
+        self.arg("--no-huge")
+    }
+
+    /// Disable PCI bus.
+    pub fn no_pci(&mut self) -> &mut Self {
+        self.arg("--no-pci")
+    }
+
+    /// Disable shared config (mmap'd files).
+    pub fn no_shconf(&mut self) -> &mut Self {
+        self.arg("--no-shconf")
+    }
+
+    /// Force IOVA mode to a specific value.
+    ///
+    /// `IovaMode::DontCare` leaves the decision to the buses.
+    pub fn iova_mode(&mut self, mode: IovaMode) -> &mut Self {
+        match mode {
+            IovaMode::DontCare => self,
+            IovaMode::PhysAddr => self.opt("--iova-mode", "pa"),
+            IovaMode::VirtAddr => self.opt("--iova-mode", "va"),
+        }
+    }
+
+    /// Add a virtual device.
+    pub fn vdev(&mut self, devargs: &str) -> &mut Self {
+        self.opt("--vdev", devargs)
+    }
+
+    /// Set global log level.
+    pub fn log_level(&mut self, level: u32) -> &mut Self {
+        self.opt("--log-level", level)
+    }
+
+    /// Enable the tracepoints whose names match the regular expression, e.g. `lib.eal.*`.
+    pub fn trace(&mut self, regex: &str) -> &mut Self {
+        self.opt("--trace", regex)
+    }
+
+    /// Directory where the traces are saved, with a sub-directory named by the file prefix and time.
+    pub fn trace_dir(&mut self, dir: &str) -> &mut Self {
+        self.opt("--trace-dir", dir)
+    }
+
+    /// Size of the per-thread trace buffer, in bytes.
+    pub fn trace_bufsz(&mut self, size: usize) -> &mut Self {
+        self.opt("--trace-bufsz", size)
+    }
+
+    /// Mode when the trace buffer is full.
+    pub fn trace_mode(&mut self, mode: TraceMode) -> &mut Self {
+        self.opt("--trace-mode", mode)
+    }
+
+    /// The arguments to initialize the EAL.
+    pub fn build(&self) -> Vec<String> {
+        self.args.clone()
+    }
+
+    /// Initialize the Environment Abstraction Layer (EAL) with the arguments.
+    pub fn init(&self) -> Result<i32> {
+        init(&self.args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_driver_paths() {
+        let args = [
+            "app",
+            "-l",
+            "-d",
+            "-vd",
+            "librte_pmd_ixgbe.so",
+            "--file-prefix",
+            "-d",
+            "--driver-path=/usr/lib/dpdk",
+            "-dlibrte_pmd_i40e.so",
+            "--",
+            "-d",
+            "librte_pmd_ring.so",
+        ];
+        let paths = driver_paths(&args);
+
+        // `-d` is the argument of `-l` and `--file-prefix`
+        assert!(paths.ends_with(&[
+            PathBuf::from("librte_pmd_ixgbe.so"),
+            PathBuf::from("/usr/lib/dpdk"),
+            PathBuf::from("librte_pmd_i40e.so"),
+        ]));
+        assert!(driver_paths(&["app", "-d"]).iter().all(|path| path.as_os_str() != "-d"));
+    }
+}
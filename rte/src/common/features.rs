@@ -0,0 +1,275 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::fs;
+use std::os::raw::c_int;
+use std::path::Path;
+
+use libc;
+
+use ffi;
+
+use utils::AsCString;
+
+/// The optional DPDK libraries, which may be disabled in the config of the DPDK SDK.
+pub const OPTIONAL_LIBS: &[&str] = &[
+    "kni",
+    "pmd_bond",
+    "sched",
+    "cryptodev",
+    "distributor",
+    "ip_frag",
+    "pdump",
+    "bpf",
+    "power",
+    "vhost",
+    "eventdev",
+    "pmd_ixgbe",
+    "pmd_i40e",
+    "pmd_softnic",
+];
+
+/// The DPDK libraries linked with the crate, detected by `rte-build`.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Features {
+    libs: &'static [&'static str],
+    drivers: &'static [(&'static str, &'static str, bool)],
+    shared: bool,
+}
+
+/// The DPDK libraries linked with the crate.
+pub fn features() -> Features {
+    Features {
+        libs: ffi::RTE_LINKED_LIBS,
+        drivers: ffi::RTE_PMD_DRIVERS,
+        shared: ffi::RTE_SHARED_LINK,
+    }
+}
+
+impl Features {
+    /// The names of the linked libraries, e.g. `rte_kni`.
+    pub fn libs(&self) -> &'static [&'static str] {
+        self.libs
+    }
+
+    /// The libraries are linked as the shared libraries, otherwise the static ones.
+    pub fn shared(&self) -> bool {
+        self.shared
+    }
+
+    /// The linked PMDs, e.g. `rte_pmd_ixgbe`.
+    pub fn pmds(&self) -> impl Iterator<Item = &'static str> {
+        self.libs.iter().cloned().filter(|lib| lib.starts_with("rte_pmd_"))
+    }
+
+    /// The drivers of the linked PMD, e.g. `net_ixgbe` of `rte_pmd_ixgbe`, which are found when building.
+    pub fn drivers<'a>(&'a self, pmd: &'a str) -> impl Iterator<Item = &'static str> + 'a {
+        self.drivers
+            .iter()
+            .filter(move |&&(lib, _, _)| lib == pmd)
+            .map(|&(_, name, _)| name)
+    }
+
+    /// The linked PMDs which drivers are not registered in the process.
+    ///
+    /// Nothing references the symbols of a PMD, so it is dropped by the linker,
+    /// e.g. a static PMD without `--whole-archive` or a shared PMD with `--as-needed`,
+    /// then its drivers are never registered and no port is found.
+    ///
+    /// The drivers are looked up on the PCI and vdev buses, the PMDs which drivers can't be found there,
+    /// e.g. the drivers of the other buses, are only checked if they are the shared libraries loaded in the process.
+    pub fn dropped_pmds(&self) -> Vec<&'static str> {
+        let objects = if self.shared { loaded_objects() } else { vec![] };
+
+        self.pmds()
+            .filter(|lib| match self.is_registered(lib) {
+                Some(registered) => !registered,
+                None => self.shared && !is_loaded(lib, &objects),
+            })
+            .collect()
+    }
+
+    /// Any driver of the PMD is registered, or `None` if it is unknown.
+    fn is_registered(&self, pmd: &str) -> Option<bool> {
+        let mut registered = None;
+
+        for &(_, name, pci) in self.drivers.iter().filter(|&&(lib, _, _)| lib == pmd) {
+            let name = name.as_cstring();
+
+            match unsafe { ffi::_rte_driver_registered(name.as_ptr(), pci as c_int) } {
+                0 => registered = Some(false),
+                ret if ret > 0 => return Some(true),
+                _ => {}
+            }
+        }
+
+        registered
+    }
+
+    /// The library is linked, the name could omit the `rte_` prefix, e.g. `kni` or `pmd_bond`.
+    pub fn has(&self, name: &str) -> bool {
+        let name = name.strip_prefix("rte_").unwrap_or(name);
+
+        self.libs.iter().any(|lib| lib.strip_prefix("rte_") == Some(name))
+    }
+
+    /// The optional libraries which are not linked.
+    pub fn missing(&self) -> impl Iterator<Item = &'static str> {
+        let features = *self;
+
+        OPTIONAL_LIBS.iter().cloned().filter(move |name| !features.has(name))
+    }
+
+    /// The KNI library is linked.
+    pub fn kni(&self) -> bool {
+        self.has("kni")
+    }
+
+    /// The link bonding PMD is linked.
+    pub fn bond(&self) -> bool {
+        self.has("pmd_bond")
+    }
+
+    /// The hierarchical scheduler library is linked.
+    pub fn sched(&self) -> bool {
+        self.has("sched")
+    }
+
+    /// The crypto device library is linked.
+    pub fn cryptodev(&self) -> bool {
+        self.has("cryptodev")
+    }
+
+    /// The packet distributor library is linked.
+    pub fn distributor(&self) -> bool {
+        self.has("distributor")
+    }
+
+    /// The IP fragmentation and reassembly library is linked.
+    pub fn ip_frag(&self) -> bool {
+        self.has("ip_frag")
+    }
+
+    /// The packet capture framework is linked.
+    pub fn pdump(&self) -> bool {
+        self.has("pdump")
+    }
+
+    /// The soft NIC PMD is linked.
+    pub fn softnic(&self) -> bool {
+        self.has("pmd_softnic")
+    }
+}
+
+/// The paths of the shared objects loaded in the process.
+fn loaded_objects() -> Vec<String> {
+    unsafe extern "C" fn collect(
+        info: *mut libc::dl_phdr_info,
+        _size: libc::size_t,
+        data: *mut libc::c_void,
+    ) -> libc::c_int {
+        let objects = &mut *(data as *mut Vec<String>);
+
+        if !(*info).dlpi_name.is_null() {
+            objects.push(CStr::from_ptr((*info).dlpi_name).to_string_lossy().into_owned());
+        }
+
+        0
+    }
+
+    let mut objects = Vec::new();
+
+    unsafe {
+        libc::dl_iterate_phdr(Some(collect), &mut objects as *mut Vec<String> as *mut libc::c_void);
+    }
+
+    objects
+}
+
+/// The shared library is loaded, with or without the version suffix, e.g. `librte_pmd_ixgbe.so.2.1`.
+fn is_loaded(lib: &str, objects: &[String]) -> bool {
+    objects.iter().any(|path| is_lib_file(lib, Path::new(path)))
+}
+
+/// The PMD is loaded by EAL from the driver path, which is the shared library or a directory of them.
+pub fn is_loaded_from(lib: &str, path: &Path) -> bool {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .any(|entry| is_lib_file(lib, &entry.path()))
+            })
+            .unwrap_or(false)
+    } else {
+        is_lib_file(lib, path)
+    }
+}
+
+/// The file is the shared library, with or without the version suffix.
+fn is_lib_file(lib: &str, path: &Path) -> bool {
+    let name = format!("lib{}.so", lib);
+
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|file_name| file_name == name || file_name.starts_with(&format!("{}.", name)))
+}
+
+impl fmt::Display for Features {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, name) in OPTIONAL_LIBS.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}{}", if self.has(name) { '+' } else { '-' }, name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features() {
+        let features = Features {
+            libs: &["rte_eal", "rte_kni", "rte_pmd_bond"],
+            drivers: &[],
+            shared: false,
+        };
+
+        assert!(features.kni());
+        assert!(features.has("rte_kni"));
+        assert!(features.bond());
+        assert!(!features.sched());
+        assert!(!features.has("eal_foo"));
+        assert!(features.missing().all(|name| name != "kni"));
+        assert!(features.to_string().starts_with("+kni, +pmd_bond, -sched"));
+        assert_eq!(features.pmds().collect::<Vec<_>>(), vec!["rte_pmd_bond"]);
+        assert!(features.dropped_pmds().is_empty());
+    }
+
+    #[test]
+    fn test_is_loaded() {
+        let objects = vec![
+            "/usr/lib/librte_eal.so.9.0".to_owned(),
+            "/usr/lib/librte_pmd_ixgbe.so.2.1".to_owned(),
+            "librte_pmd_ring.so".to_owned(),
+        ];
+
+        assert!(is_loaded("rte_pmd_ixgbe", &objects));
+        assert!(is_loaded("rte_pmd_ring", &objects));
+        assert!(!is_loaded("rte_pmd_i40e", &objects));
+        assert!(!is_loaded("rte_pmd", &objects));
+        assert!(is_loaded_from(
+            "rte_pmd_ixgbe",
+            Path::new("/usr/lib/librte_pmd_ixgbe.so")
+        ));
+        assert!(!is_loaded_from(
+            "rte_pmd_ixgbe",
+            Path::new("/usr/lib/librte_pmd_ixgbe.a")
+        ));
+    }
+}
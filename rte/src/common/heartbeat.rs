@@ -0,0 +1,197 @@
+//! Lightweight heartbeat bus between lcores.
+//!
+//! Each datapath lcore publishes a TSC-stamped counter into its own cache-aligned slot of a shared table,
+//! without any atomic read-modify-write or false sharing, so it is cheap enough to beat on every loop.
+//!
+//! A monitor task periodically scans the table, and invokes the user callback
+//! when a registered lcore has not beaten longer than the threshold.
+//!
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{get_tsc_hz, rdtsc};
+use lcore::{self, RTE_MAX_LCORE};
+
+#[repr(align(64))]
+#[derive(Debug, Default)]
+struct Slot {
+    registered: AtomicBool,
+    beats: AtomicU64,
+    tsc: AtomicU64,
+}
+
+/// The last heartbeat of a lcore.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Beat {
+    /// The number of heartbeats.
+    pub beats: u64,
+    /// The TSC of the last heartbeat.
+    pub tsc: u64,
+}
+
+/// A shared table of the heartbeats of lcores.
+#[derive(Debug)]
+pub struct HeartbeatBus {
+    slots: Vec<Slot>,
+}
+
+impl Default for HeartbeatBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeartbeatBus {
+    /// Create a heartbeat table for all the lcores.
+    pub fn new() -> Self {
+        HeartbeatBus {
+            slots: (0..RTE_MAX_LCORE).map(|_| Slot::default()).collect(),
+        }
+    }
+
+    /// Register a lcore to be monitored, the lcore is considered alive from now.
+    pub fn register(&self, lcore_id: lcore::Id) {
+        let slot = &self.slots[*lcore_id as usize];
+
+        slot.beats.store(0, Ordering::Relaxed);
+        slot.tsc.store(rdtsc(), Ordering::Relaxed);
+        slot.registered.store(true, Ordering::Release);
+    }
+
+    /// Unregister a lcore, for example, before the lcore goes to sleep or exits.
+    pub fn unregister(&self, lcore_id: lcore::Id) {
+        self.slots[*lcore_id as usize]
+            .registered
+            .store(false, Ordering::Release)
+    }
+
+    /// The lcore is registered to be monitored.
+    pub fn is_registered(&self, lcore_id: lcore::Id) -> bool {
+        self.slots[*lcore_id as usize].registered.load(Ordering::Acquire)
+    }
+
+    /// Publish a heartbeat of the current lcore.
+    ///
+    /// This should be called from the main loop of the datapath lcore,
+    /// and does nothing if called from a non-EAL thread.
+    #[inline]
+    pub fn beat(&self) {
+        if let Some(lcore_id) = lcore::current() {
+            let slot = &self.slots[*lcore_id as usize];
+
+            // only the owner lcore writes its slot
+            slot.tsc.store(rdtsc(), Ordering::Relaxed);
+            slot.beats
+                .store(slot.beats.load(Ordering::Relaxed).wrapping_add(1), Ordering::Release);
+        }
+    }
+
+    /// The last heartbeat of a registered lcore.
+    pub fn last_beat(&self, lcore_id: lcore::Id) -> Option<Beat> {
+        let slot = &self.slots[*lcore_id as usize];
+
+        if slot.registered.load(Ordering::Acquire) {
+            let beats = slot.beats.load(Ordering::Acquire);
+            let tsc = slot.tsc.load(Ordering::Relaxed);
+
+            Some(Beat { beats, tsc })
+        } else {
+            None
+        }
+    }
+}
+
+/// A stalled lcore detected by the monitor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stall {
+    /// The stalled lcore.
+    pub lcore_id: lcore::Id,
+    /// The last heartbeat of the lcore.
+    pub last_beat: Beat,
+    /// How long the lcore has not beaten.
+    pub elapsed: Duration,
+}
+
+/// The callback invoked when a lcore is stalled.
+///
+/// It is invoked once per stall, and again only after the lcore has recovered and stalled again.
+pub type StallCallback = Box<dyn FnMut(&Stall) + Send>;
+
+/// A monitor which detects the stalled lcores from the heartbeat bus.
+pub struct Monitor {
+    bus: Arc<HeartbeatBus>,
+    threshold: u64,
+    stalled: Vec<bool>,
+    callback: StallCallback,
+}
+
+impl Monitor {
+    /// Create a monitor, a lcore is stalled if it has not beaten longer than the threshold.
+    pub fn new(bus: Arc<HeartbeatBus>, threshold: Duration, callback: StallCallback) -> Self {
+        let hz = get_tsc_hz();
+
+        Monitor {
+            bus,
+            threshold: threshold.as_secs() * hz + u64::from(threshold.subsec_nanos()) * hz / 1_000_000_000,
+            stalled: vec![false; RTE_MAX_LCORE as usize],
+            callback,
+        }
+    }
+
+    /// The heartbeat bus.
+    pub fn bus(&self) -> &Arc<HeartbeatBus> {
+        &self.bus
+    }
+
+    /// The lcore is stalled.
+    pub fn is_stalled(&self, lcore_id: lcore::Id) -> bool {
+        self.stalled[*lcore_id as usize]
+    }
+
+    /// Scan the heartbeat table, and invoke the callback for the newly stalled lcores.
+    ///
+    /// This should be called periodically, much more frequently than the threshold.
+    ///
+    /// Returns the number of the newly stalled lcores.
+    pub fn check(&mut self) -> usize {
+        let now = rdtsc();
+        let hz = get_tsc_hz();
+        let mut n = 0;
+
+        for id in 0..RTE_MAX_LCORE {
+            let lcore_id = lcore::id(id);
+            let last_beat = match self.bus.last_beat(lcore_id) {
+                Some(beat) => beat,
+                None => {
+                    self.stalled[id as usize] = false;
+                    continue;
+                }
+            };
+            let cycles = now.saturating_sub(last_beat.tsc);
+
+            if cycles < self.threshold {
+                self.stalled[id as usize] = false;
+            } else if !self.stalled[id as usize] {
+                self.stalled[id as usize] = true;
+
+                let stall = Stall {
+                    lcore_id,
+                    last_beat,
+                    elapsed: Duration::new(cycles / hz, ((cycles % hz) * 1_000_000_000 / hz) as u32),
+                };
+
+                warn!(
+                    "lcore {} stalled for {:?}, last heartbeat #{}",
+                    lcore_id, stall.elapsed, last_beat.beats
+                );
+
+                (self.callback)(&stall);
+
+                n += 1;
+            }
+        }
+
+        n
+    }
+}
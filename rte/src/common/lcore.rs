@@ -9,7 +9,7 @@ use ffi;
 
 use common::config;
 use errors::{rte_error, Result};
-use memory::SocketId;
+use memory::Socket;
 
 pub use ffi::LCORE_ID_ANY;
 pub use ffi::RTE_MAX_LCORE;
@@ -75,8 +75,8 @@ impl Id {
     }
 
     /// Get the ID of the physical socket of the specified lcore
-    pub fn socket_id(self) -> SocketId {
-        unsafe { ffi::lcore_config[self.0 as usize].socket_id as SocketId }
+    pub fn socket_id(self) -> Socket {
+        Socket::from(unsafe { ffi::lcore_config[self.0 as usize].socket_id } as i32)
     }
 
     /// Test if an lcore is enabled.
@@ -187,8 +187,8 @@ pub fn next_id(lcore_id: u32, skip_master: bool, wrap: bool) -> Option<u32> {
 }
 
 /// Return the ID of the physical socket of the logical core we are running on.
-pub fn socket_id() -> u32 {
-    unsafe { ffi::rte_socket_id() }
+pub fn socket_id() -> Socket {
+    Socket::current()
 }
 
 /// Return number of physical sockets detected on the system.
@@ -205,13 +205,13 @@ pub fn socket_count() -> u32 {
 /// This will return socket id at a particular position in list of all detected
 /// physical socket id's. For example, on a machine with sockets [0, 8], passing
 /// 1 as a parameter will return 8.
-pub fn socket_id_by_idx(idx: u32) -> Result<SocketId> {
+pub fn socket_id_by_idx(idx: u32) -> Result<Socket> {
     let id = unsafe { ffi::rte_socket_id_by_idx(idx) };
 
     if id < 0 {
         Err(rte_error())
     } else {
-        Ok(id)
+        Ok(Socket::from(id))
     }
 }
 
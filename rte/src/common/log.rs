@@ -1,3 +1,4 @@
+use std::io;
 use std::mem;
 use std::os::unix::io::AsRawFd;
 
@@ -5,7 +6,7 @@ use cfile;
 
 use errors::{AsResult, ErrorKind::*, Result};
 use ffi;
-use utils::AsCString;
+use utils::{self, AsCString};
 
 /// SDK log type
 #[repr(u32)]
@@ -178,6 +179,13 @@ pub fn dump<S: AsRawFd>(s: &S) -> Result<()> {
     Ok(())
 }
 
+/// Dump log information to the writer.
+pub fn dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    utils::capture(w, |f| unsafe { ffi::rte_log_dump(f) })?;
+
+    Ok(())
+}
+
 /// Generates a log message.
 ///
 /// The message will be sent in the stream defined by the previous call
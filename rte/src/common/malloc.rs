@@ -1,12 +1,23 @@
+use std::cmp;
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
 use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::os::raw::c_void;
 use std::os::unix::io::AsRawFd;
-use std::ptr;
+use std::ptr::{self, NonNull};
+use std::slice;
 
 use cfile;
+use libc;
 
 use ffi;
 
+use errors::{Result, RteError};
+use memory::Socket;
+use utils::{self, AsCString};
+
 #[macro_export]
 macro_rules! rte_new {
     ($t:ty) => {
@@ -85,24 +96,24 @@ pub fn realloc(ptr: *mut c_void, size: usize, align: u32) -> *mut c_void {
 ///
 /// The memory is not cleared.
 ///
-pub fn malloc_socket(tag: &'static str, size: usize, align: u32, socket_id: i32) -> *mut c_void {
-    unsafe { ffi::rte_malloc_socket(tag.as_ptr() as *const i8, size, align, socket_id) }
+pub fn malloc_socket(tag: &'static str, size: usize, align: u32, socket: Socket) -> *mut c_void {
+    unsafe { ffi::rte_malloc_socket(tag.as_ptr() as *const i8, size, align, socket.raw()) }
 }
 
 /// Allocate zero'ed memory from the heap.
 ///
 /// Equivalent to rte_malloc() except that the memory zone is initialised with zeros.
 ///
-pub fn zmalloc_socket(tag: &'static str, size: usize, align: u32, socket_id: i32) -> *mut c_void {
-    unsafe { ffi::rte_zmalloc_socket(tag.as_ptr() as *const i8, size, align, socket_id) }
+pub fn zmalloc_socket(tag: &'static str, size: usize, align: u32, socket: Socket) -> *mut c_void {
+    unsafe { ffi::rte_zmalloc_socket(tag.as_ptr() as *const i8, size, align, socket.raw()) }
 }
 
 /// Replacement function for calloc(), using huge-page memory.
 ///
 /// Memory area is initialised with zeros.
 ///
-pub fn calloc_socket(tag: &'static str, num: usize, size: usize, align: u32, socket_id: i32) -> *mut c_void {
-    unsafe { ffi::rte_calloc_socket(tag.as_ptr() as *const i8, num, size, align, socket_id) }
+pub fn calloc_socket(tag: &'static str, num: usize, size: usize, align: u32, socket: Socket) -> *mut c_void {
+    unsafe { ffi::rte_calloc_socket(tag.as_ptr() as *const i8, num, size, align, socket.raw()) }
 }
 
 /// Frees the memory space pointed to by the provided pointer.
@@ -111,11 +122,11 @@ pub fn free(ptr: *mut c_void) {
 }
 
 /// Get heap statistics for the specified heap.
-pub fn get_socket_stats(socket_id: i32) -> Option<ffi::rte_malloc_socket_stats> {
+pub fn get_socket_stats(socket: Socket) -> Option<ffi::rte_malloc_socket_stats> {
     unsafe {
         let mut stats: ffi::rte_malloc_socket_stats = mem::zeroed();
 
-        if ffi::rte_malloc_get_socket_stats(socket_id, &mut stats) == 0 {
+        if ffi::rte_malloc_get_socket_stats(socket.raw(), &mut stats) == 0 {
             Some(stats)
         } else {
             None
@@ -134,3 +145,293 @@ pub fn dump_stats<S: AsRawFd>(s: &S, tag: Option<&str>) {
         }
     }
 }
+
+/// Dump statistics to the writer.
+pub fn dump_stats_to<W: io::Write + ?Sized>(w: &mut W, tag: Option<&str>) -> Result<()> {
+    let tag = tag.map(|s| s.as_cstring());
+
+    utils::capture(w, |f| unsafe {
+        ffi::rte_malloc_dump_stats(f as *mut _, tag.as_ref().map_or_else(ptr::null, |s| s.as_ptr()))
+    })?;
+
+    Ok(())
+}
+
+/// The alignment of the allocation, at least the alignment of the type.
+fn align_of<T>(align: u32) -> u32 {
+    cmp::max(align, mem::align_of::<T>() as u32)
+}
+
+/// Allocate the memory of `n` values from the heap of the socket.
+fn alloc_array<T>(n: usize, socket: Socket, align: u32) -> Result<NonNull<T>> {
+    let size = mem::size_of::<T>().checked_mul(n).ok_or(RteError(libc::EINVAL))?;
+
+    NonNull::new(unsafe { ffi::rte_malloc_socket(ptr::null(), cmp::max(size, 1), align_of::<T>(align), socket.raw()) })
+        .map(|p| p.cast())
+        .ok_or_else(|| RteError(libc::ENOMEM).into())
+}
+
+/// Allocate a value from the heap of the socket, which is initialized with the default value.
+///
+/// The alignment must be a power of two, and is at least the alignment of the type,
+/// or cache line aligned if it is 0.
+pub fn alloc<T: Default>(socket: Socket, align: u32) -> Result<DpdkBox<T>> {
+    DpdkBox::new(T::default(), socket, align)
+}
+
+/// Allocate a zero'ed value from the heap of the socket.
+///
+/// # Safety
+///
+/// All zero bytes must be a valid value of the type, e.g. the C structures.
+pub unsafe fn alloc_zeroed<T>(socket: Socket, align: u32) -> Result<DpdkBox<T>> {
+    let p = NonNull::new(ffi::rte_zmalloc_socket(
+        ptr::null(),
+        cmp::max(mem::size_of::<T>(), 1),
+        align_of::<T>(align),
+        socket.raw(),
+    ))
+    .ok_or(RteError(libc::ENOMEM))?;
+
+    Ok(DpdkBox(p.cast(), PhantomData))
+}
+
+/// An owned value allocated from the huge-page heap, which is freed when it is dropped.
+///
+/// The box has the same layout as a pointer, and `Option<DpdkBox<T>>` as a nullable pointer,
+/// so it could be shared with C code.
+#[repr(transparent)]
+pub struct DpdkBox<T>(NonNull<T>, PhantomData<T>);
+
+unsafe impl<T: Send> Send for DpdkBox<T> {}
+unsafe impl<T: Sync> Sync for DpdkBox<T> {}
+
+impl<T> DpdkBox<T> {
+    /// Move the value to the heap of the socket.
+    pub fn new(value: T, socket: Socket, align: u32) -> Result<Self> {
+        let p = alloc_array::<T>(1, socket, align)?;
+
+        unsafe { ptr::write(p.as_ptr(), value) };
+
+        Ok(DpdkBox(p, PhantomData))
+    }
+
+    /// Construct a box from a raw pointer allocated by `rte_malloc`.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to an initialized value, and the box takes the ownership.
+    pub unsafe fn from_raw(p: *mut T) -> Option<Self> {
+        NonNull::new(p).map(|p| DpdkBox(p, PhantomData))
+    }
+
+    /// Consume the box and return the raw pointer, which should be freed with `malloc::free`.
+    pub fn into_raw(self) -> *mut T {
+        let p = self.0.as_ptr();
+
+        mem::forget(self);
+
+        p
+    }
+
+    /// The raw pointer of the value.
+    pub fn as_ptr(&self) -> *mut T {
+        self.0.as_ptr()
+    }
+
+    /// The IO address of the value, which could be used by the devices.
+    pub fn iova(&self) -> ffi::rte_iova_t {
+        unsafe { ffi::rte_malloc_virt2iova(self.0.as_ptr() as *const c_void) }
+    }
+}
+
+impl<T> Deref for DpdkBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.0.as_ref() }
+    }
+}
+
+impl<T> DerefMut for DpdkBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.0.as_mut() }
+    }
+}
+
+impl<T> Drop for DpdkBox<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.0.as_ptr());
+
+            ffi::rte_free(self.0.as_ptr() as *mut c_void);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DpdkBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A growable array allocated from the huge-page heap of a socket.
+pub struct DpdkVec<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    cap: usize,
+    socket: Socket,
+    align: u32,
+}
+
+unsafe impl<T: Send> Send for DpdkVec<T> {}
+unsafe impl<T: Sync> Sync for DpdkVec<T> {}
+
+impl<T> DpdkVec<T> {
+    /// Create an empty array with the capacity on the socket.
+    pub fn with_capacity(cap: usize, socket: Socket, align: u32) -> Result<Self> {
+        let cap = cmp::max(cap, 1);
+
+        Ok(DpdkVec {
+            ptr: alloc_array(cap, socket, align)?,
+            len: 0,
+            cap,
+            socket,
+            align,
+        })
+    }
+
+    /// The number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of values the array could hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// The socket of the heap.
+    pub fn socket(&self) -> Socket {
+        self.socket
+    }
+
+    /// The raw pointer of the values.
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// The IO address of the values, which could be used by the devices.
+    pub fn iova(&self) -> ffi::rte_iova_t {
+        unsafe { ffi::rte_malloc_virt2iova(self.ptr.as_ptr() as *const c_void) }
+    }
+
+    /// Reserve the capacity for at least `additional` more values.
+    ///
+    /// The array is reallocated on the same socket, with the same alignment.
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        let need = self.len.checked_add(additional).ok_or(RteError(libc::EINVAL))?;
+
+        if need <= self.cap {
+            return Ok(());
+        }
+
+        let cap = cmp::max(need, self.cap * 2);
+
+        // `rte_realloc` may move the block to any socket, so allocate a new block on the socket and move the values
+        let p = alloc_array::<T>(cap, self.socket, self.align)?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(self.ptr.as_ptr(), p.as_ptr(), self.len);
+
+            ffi::rte_free(self.ptr.as_ptr() as *mut c_void);
+        }
+
+        self.ptr = p;
+        self.cap = cap;
+
+        Ok(())
+    }
+
+    /// Append a value to the back of the array.
+    pub fn push(&mut self, value: T) -> Result<()> {
+        self.reserve(1)?;
+
+        unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value) };
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Remove the last value and return it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+
+            Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.len)) })
+        }
+    }
+
+    /// Shorten the array to `len`, drop the rest of the values.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    /// Drop all the values.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+}
+
+impl<T: Clone> DpdkVec<T> {
+    /// Clone and append all the values of the slice.
+    pub fn extend_from_slice(&mut self, values: &[T]) -> Result<()> {
+        self.reserve(values.len())?;
+
+        for value in values {
+            unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value.clone()) };
+
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Deref for DpdkVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for DpdkVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for DpdkVec<T> {
+    fn drop(&mut self) {
+        self.clear();
+
+        unsafe { ffi::rte_free(self.ptr.as_ptr() as *mut c_void) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DpdkVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
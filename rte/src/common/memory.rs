@@ -1,6 +1,125 @@
-pub type SocketId = i32;
+use std::fmt;
 
-pub const SOCKET_ID_ANY: SocketId = -1;
+use ffi;
+
+use errors::{self, ErrorKind};
+use ethdev::PortId;
+use lcore;
+
+/// The raw id of any NUMA socket.
+pub const SOCKET_ID_ANY: i32 = -1;
+
+/// A NUMA socket, or any socket.
+///
+/// The memory of the objects used by a lcore or a port should be allocated on their socket,
+/// use `Socket::current()` or `Socket::of_port()` instead of the raw socket ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Socket(i32);
+
+impl Socket {
+    /// Any socket, the memory could be allocated on any socket.
+    pub const ANY: Socket = Socket(SOCKET_ID_ANY);
+
+    /// The socket of the physical id, which must be detected on the system.
+    pub fn new(id: u32) -> errors::Result<Socket> {
+        Socket(id as i32).validate()
+    }
+
+    /// The socket of the lcore we are running on, or any socket for the unregistered non-EAL threads.
+    pub fn current() -> Socket {
+        Socket::from(unsafe { ffi::rte_socket_id() } as i32)
+    }
+
+    /// The socket of the lcore.
+    pub fn of_lcore(lcore_id: lcore::Id) -> Socket {
+        lcore_id.socket_id()
+    }
+
+    /// The socket the port is connected to, or any socket if it is unknown.
+    pub fn of_port(port_id: PortId) -> Socket {
+        Socket::from(unsafe { ffi::rte_eth_dev_socket_id(port_id) })
+    }
+
+    /// All the sockets detected on the system.
+    pub fn all() -> Vec<Socket> {
+        (0..lcore::socket_count())
+            .map(|idx| Socket::from(unsafe { ffi::rte_socket_id_by_idx(idx) }))
+            .filter(|socket| !socket.is_any())
+            .collect()
+    }
+
+    /// The socket is any socket.
+    pub fn is_any(self) -> bool {
+        self.0 < 0
+    }
+
+    /// The physical id of the socket, or `None` for any socket.
+    pub fn id(self) -> Option<u32> {
+        if self.is_any() {
+            None
+        } else {
+            Some(self.0 as u32)
+        }
+    }
+
+    /// The raw socket id, which is `SOCKET_ID_ANY` for any socket.
+    pub fn raw(self) -> i32 {
+        self.0
+    }
+
+    /// The socket is any socket or detected on the system.
+    pub fn is_valid(self) -> bool {
+        self.is_any() || Socket::all().contains(&self)
+    }
+
+    /// Check the socket is any socket or detected on the system.
+    pub fn validate(self) -> errors::Result<Socket> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(ErrorKind::InvalidSocket(self.0).into())
+        }
+    }
+}
+
+impl Default for Socket {
+    fn default() -> Self {
+        Socket::ANY
+    }
+}
+
+impl fmt::Display for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.id() {
+            Some(id) => write!(f, "socket {}", id),
+            None => f.write_str("any socket"),
+        }
+    }
+}
+
+/// Convert a raw socket id, any negative id is any socket.
+impl From<i32> for Socket {
+    fn from(id: i32) -> Self {
+        if id < 0 {
+            Socket::ANY
+        } else {
+            Socket(id)
+        }
+    }
+}
+
+impl From<Socket> for i32 {
+    fn from(socket: Socket) -> i32 {
+        socket.0
+    }
+}
+
+/// Convert to the unsigned socket id of some APIs, where any socket is `u32::MAX`.
+impl From<Socket> for u32 {
+    fn from(socket: Socket) -> u32 {
+        socket.0 as u32
+    }
+}
 
 pub trait AsRef<'a, T: 'a> {
     fn as_ref(self) -> Option<&'a T>;
@@ -53,3 +172,21 @@ impl<'a, T: 'a, E> AsMutRef<'a, T> for Result<*mut T, E> {
         self.ok().map(|p| unsafe { &mut *p })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket() {
+        assert!(Socket::ANY.is_any());
+        assert_eq!(Socket::ANY.id(), None);
+        assert_eq!(Socket::from(-2), Socket::ANY);
+        assert_eq!(Socket::from(1).id(), Some(1));
+        assert_eq!(i32::from(Socket::ANY), SOCKET_ID_ANY);
+        assert_eq!(u32::from(Socket::ANY), u32::MAX);
+        assert_eq!(u32::from(Socket::from(1)), 1);
+        assert_eq!(Socket::from(1).to_string(), "socket 1");
+        assert_eq!(Socket::ANY.to_string(), "any socket");
+    }
+}
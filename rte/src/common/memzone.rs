@@ -1,4 +1,7 @@
-use ffi::rte_memzone;
+use ffi::{self, rte_memzone};
+
+use common::name::{Name, NameKind};
+use errors::Result;
 
 /// RTE Memzone
 ///
@@ -19,3 +22,13 @@ pub struct MemoryZone(*const rte_memzone);
 pub fn from_raw(zone: *const rte_memzone) -> MemoryZone {
     MemoryZone(zone)
 }
+
+/// Lookup for a memzone.
+///
+/// Get a pointer to a descriptor of an already reserved memory zone identified by the name given as an argument.
+pub fn lookup<S: AsRef<str>>(name: S) -> Result<MemoryZone> {
+    let name = Name::new(NameKind::MemZone, name)?;
+    let zone = unsafe { ffi::rte_memzone_lookup(name.as_ptr()) };
+
+    rte_check!(zone, NonNull; ok => { MemoryZone(zone) })
+}
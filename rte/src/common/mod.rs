@@ -1,10 +1,17 @@
 pub mod bitmap;
+pub mod bus;
 mod config;
+pub mod cores;
+pub mod coop;
+mod doctor;
 pub mod eal;
+mod features;
+pub mod heartbeat;
 pub mod keepalive;
 pub mod launch;
 pub mod lcore;
 pub mod log;
+pub mod profile;
 mod rand;
 mod version;
 #[macro_use]
@@ -14,14 +21,20 @@ pub mod devargs;
 #[macro_use]
 pub mod debug;
 pub mod spinlock;
+pub mod trace;
 #[macro_use]
 pub mod byteorder;
 mod cycles;
 pub mod memory;
 pub mod memzone;
+pub mod name;
 
 pub use self::config::{config, Config, MemoryConfig};
 pub use self::cycles::*;
+pub use self::doctor::{doctor, doctor_with, Check, DoctorConfig, MbufRequirement, Report, Severity};
+pub use self::features::{features, Features, OPTIONAL_LIBS};
 pub use self::lcore::{socket_count, socket_id};
+pub use self::memory::Socket;
+pub use self::name::{Name, NameKind};
 pub use self::rand::{rand, srand};
-pub use self::version::version;
+pub use self::version::{runtime_version, version, Version};
@@ -0,0 +1,195 @@
+//!
+//! The names of the DPDK objects.
+//!
+//! The C library copies the object names into the fixed size arrays with `snprintf`,
+//! a name which is too long is silently truncated, or rejected with a bare `ENAMETOOLONG`,
+//! and two long names may end up with the same truncated name.
+//!
+//! `Name` checks the name against the limit of the object kind at construction.
+//!
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ops::Deref;
+use std::os::raw::c_char;
+
+use ffi;
+
+use errors::{ErrorKind, Result};
+use mempool;
+use ring;
+
+/// The kind of object which is named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NameKind {
+    /// A memory zone.
+    MemZone,
+    /// A ring, which is stored in a memory zone named with the `RG_` prefix.
+    Ring,
+    /// A memory pool, which is stored in a ring named with the `MP_` prefix.
+    MemPool,
+    /// The operations of a memory pool.
+    MemPoolOps,
+    /// A KNI interface.
+    Kni,
+    /// A distributor instance.
+    Distributor,
+    /// An IPv4 LPM table.
+    Lpm,
+    /// An IPv6 LPM table.
+    Lpm6,
+    /// An ethernet device.
+    EthDev,
+    /// A generic device.
+    Dev,
+}
+
+impl NameKind {
+    /// The size of the name buffer, including the NUL terminator.
+    pub fn size(self) -> usize {
+        match self {
+            NameKind::MemZone => ffi::RTE_MEMZONE_NAMESIZE as usize,
+            NameKind::Ring => *ring::RTE_RING_NAMESIZE,
+            NameKind::MemPool => *mempool::RTE_MEMPOOL_NAMESIZE,
+            NameKind::MemPoolOps => ffi::RTE_MEMPOOL_OPS_NAMESIZE as usize,
+            NameKind::Kni => ffi::RTE_KNI_NAMESIZE as usize,
+            NameKind::Distributor => ffi::RTE_DISTRIBUTOR_NAMESIZE as usize,
+            NameKind::Lpm => ffi::RTE_LPM_NAMESIZE as usize,
+            NameKind::Lpm6 => ffi::RTE_LPM6_NAMESIZE as usize,
+            NameKind::EthDev => ffi::RTE_ETH_NAME_MAX_LEN as usize,
+            NameKind::Dev => ffi::RTE_DEV_NAME_MAX_LEN as usize,
+        }
+    }
+
+    /// The maximum length of the name, excluding the NUL terminator.
+    pub fn max_len(self) -> usize {
+        self.size() - 1
+    }
+}
+
+impl fmt::Display for NameKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            NameKind::MemZone => "memzone",
+            NameKind::Ring => "ring",
+            NameKind::MemPool => "mempool",
+            NameKind::MemPoolOps => "mempool ops",
+            NameKind::Kni => "KNI",
+            NameKind::Distributor => "distributor",
+            NameKind::Lpm => "LPM",
+            NameKind::Lpm6 => "LPM6",
+            NameKind::EthDev => "ethdev",
+            NameKind::Dev => "device",
+        })
+    }
+}
+
+/// A NUL terminated name, which fits in the name buffer of its object kind.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Name {
+    kind: NameKind,
+    name: CString,
+}
+
+impl Name {
+    /// Create a name of the object kind.
+    ///
+    /// Fails if the name is empty, too long or contains a NUL byte.
+    pub fn new<S: AsRef<str>>(kind: NameKind, name: S) -> Result<Name> {
+        let s = name.as_ref();
+
+        if s.is_empty() {
+            return Err(ErrorKind::InvalidName(format!("{} name is empty", kind)).into());
+        }
+
+        if s.len() > kind.max_len() {
+            return Err(ErrorKind::InvalidName(format!(
+                "{} name `{}` is too long, {} > {} bytes",
+                kind,
+                s,
+                s.len(),
+                kind.max_len()
+            ))
+            .into());
+        }
+
+        let name = CString::new(s).map_err(|_| {
+            ErrorKind::InvalidName(format!("{} name `{}` contains a NUL byte", kind, s.escape_default()))
+        })?;
+
+        Ok(Name { kind, name })
+    }
+
+    /// The object kind of the name.
+    pub fn kind(&self) -> NameKind {
+        self.kind
+    }
+
+    /// The name as a C string.
+    pub fn as_c_str(&self) -> &CStr {
+        &self.name
+    }
+
+    /// The raw pointer of the NUL terminated name, which is valid as long as the name.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.name.as_ptr()
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // the name was built from a `str`
+        self.name.to_str().unwrap()
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_size() {
+        assert_eq!(NameKind::MemZone.size(), 32);
+        assert_eq!(NameKind::Ring.size(), 29);
+        assert_eq!(NameKind::MemPool.size(), 26);
+        assert_eq!(NameKind::MemPool.max_len(), 25);
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Name::new(NameKind::MemPool, "mbuf_pool").unwrap();
+
+        assert_eq!(name.kind(), NameKind::MemPool);
+        assert_eq!(&*name, "mbuf_pool");
+        assert_eq!(name.as_c_str().to_bytes_with_nul(), b"mbuf_pool\0");
+
+        let s = "a".repeat(25);
+
+        assert!(Name::new(NameKind::MemPool, &s).is_ok());
+        assert!(Name::new(NameKind::Ring, &s).is_ok());
+
+        let s = "a".repeat(26);
+
+        assert_eq!(
+            Name::new(NameKind::MemPool, &s).unwrap_err().to_string(),
+            format!("invalid name, mempool name `{}` is too long, 26 > 25 bytes", s)
+        );
+        assert!(Name::new(NameKind::Ring, &s).is_ok());
+
+        assert!(Name::new(NameKind::Ring, "").is_err());
+        assert!(Name::new(NameKind::Ring, "a\0b").is_err());
+    }
+}
@@ -0,0 +1,515 @@
+//! Cycle-accurate per-stage pipeline profiler.
+//!
+//! The datapath code brackets each pipeline stage with `Profiler::enter` and `Profiler::exit`,
+//! the elapsed TSC cycles, call count and packet count are accumulated into per-lcore counters,
+//! which are only written by the owner lcore, without any atomic read-modify-write.
+//!
+//! The report sums the counters of all the lcores, and shows the cycles per call and per packet of each stage.
+//!
+//! A stage could declare a cycle budget, the calls which exceed it are counted as the overruns,
+//! and when a stage overruns its budget for the consecutive calls, the overrun is returned by
+//! `Profiler::exit` and passed to the overrun handler, so the datapath could shed load,
+//! log it or reduce the burst size.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use rte::profile::{Budget, Profiler};
+//!
+//! let mut profiler = Profiler::with_stages(&["rx", "fwd"]);
+//! let fwd = profiler.stage("fwd").unwrap();
+//!
+//! profiler.set_budget(fwd, Budget::from_duration(Duration::from_micros(20)).with_max_overruns(8));
+//! profiler.set_overrun_handler(|overrun| {
+//!     eprintln!("stage {} overran {} times on lcore {}", overrun.stage, overrun.consecutive, overrun.lcore_id)
+//! });
+//!
+//! let mut burst_size = 32;
+//!
+//! loop {
+//!     profiler.enter(fwd);
+//!
+//!     // forward a burst of `burst_size` packets
+//!
+//!     if profiler.exit(fwd, burst_size).is_some() {
+//!         burst_size = (burst_size / 2).max(1);
+//!     }
+//! #   break;
+//! }
+//! ```
+//!
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use common::{get_tsc_hz, rdtsc};
+use lcore::{self, RTE_MAX_LCORE};
+
+/// The identifier of a pipeline stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StageId(pub usize);
+
+impl fmt::Display for StageId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[repr(align(64))]
+#[derive(Debug, Default)]
+struct Counters {
+    start: AtomicU64,
+    cycles: AtomicU64,
+    calls: AtomicU64,
+    packets: AtomicU64,
+    overruns: AtomicU64,
+    // the consecutive overruns of the budget
+    streak: AtomicU64,
+}
+
+impl Counters {
+    #[inline]
+    fn add(counter: &AtomicU64, n: u64) {
+        // only the owner lcore writes its counters
+        counter.store(counter.load(Ordering::Relaxed).wrapping_add(n), Ordering::Relaxed)
+    }
+
+    fn stats(&self) -> StageStats {
+        StageStats {
+            cycles: self.cycles.load(Ordering::Relaxed),
+            calls: self.calls.load(Ordering::Relaxed),
+            packets: self.packets.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.cycles.store(0, Ordering::Relaxed);
+        self.calls.store(0, Ordering::Relaxed);
+        self.packets.store(0, Ordering::Relaxed);
+        self.overruns.store(0, Ordering::Relaxed);
+        self.streak.store(0, Ordering::Relaxed);
+    }
+}
+
+/// The accumulated statistics of a stage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StageStats {
+    /// The TSC cycles spent in the stage.
+    pub cycles: u64,
+    /// The number of calls of the stage.
+    pub calls: u64,
+    /// The number of packets processed by the stage.
+    pub packets: u64,
+    /// The number of calls which exceeded the cycle budget of the stage.
+    pub overruns: u64,
+}
+
+impl StageStats {
+    /// The average cycles per call.
+    pub fn cycles_per_call(&self) -> Option<f64> {
+        if self.calls == 0 {
+            None
+        } else {
+            Some(self.cycles as f64 / self.calls as f64)
+        }
+    }
+
+    /// The average cycles per packet.
+    pub fn cycles_per_packet(&self) -> Option<f64> {
+        if self.packets == 0 {
+            None
+        } else {
+            Some(self.cycles as f64 / self.packets as f64)
+        }
+    }
+
+    fn merge(mut self, other: StageStats) -> Self {
+        self.cycles += other.cycles;
+        self.calls += other.calls;
+        self.packets += other.packets;
+        self.overruns += other.overruns;
+        self
+    }
+}
+
+/// The cycle budget of a stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    /// The max TSC cycles of a call.
+    pub cycles: u64,
+    /// The number of the consecutive overruns which trigger the handler.
+    pub max_overruns: u32,
+}
+
+impl Budget {
+    /// A budget of the TSC cycles, which is triggered by each overrun.
+    pub fn new(cycles: u64) -> Self {
+        Budget {
+            cycles,
+            max_overruns: 1,
+        }
+    }
+
+    /// A budget of the duration in the TSC cycles.
+    pub fn from_duration(budget: Duration) -> Self {
+        Self::new((budget.as_nanos() * u128::from(get_tsc_hz()) / 1_000_000_000) as u64)
+    }
+
+    /// Trigger the handler after the consecutive overruns, at least one.
+    pub fn with_max_overruns(mut self, max_overruns: u32) -> Self {
+        self.max_overruns = max_overruns.max(1);
+        self
+    }
+
+    /// Account a call of the cycles, returns the consecutive overruns if they trigger the handler.
+    ///
+    /// The streak restarts after a call within the budget or a trigger.
+    fn check(&self, cycles: u64, streak: &mut u64) -> Option<u32> {
+        if cycles <= self.cycles {
+            *streak = 0;
+
+            return None;
+        }
+
+        *streak += 1;
+
+        if *streak < u64::from(self.max_overruns) {
+            None
+        } else {
+            let consecutive = *streak as u32;
+
+            *streak = 0;
+
+            Some(consecutive)
+        }
+    }
+}
+
+/// The consecutive overruns of the cycle budget of a stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overrun {
+    /// The stage which overran its budget.
+    pub stage: StageId,
+    /// The lcore which runs the stage.
+    pub lcore_id: lcore::Id,
+    /// The TSC cycles of the last call.
+    pub cycles: u64,
+    /// The budget of the stage.
+    pub budget: u64,
+    /// The number of the consecutive overruns.
+    pub consecutive: u32,
+    /// The number of packets processed by the last call.
+    pub packets: usize,
+}
+
+/// The handler of the overruns, which is called on the lcore running the stage.
+struct OverrunHandler(Box<dyn Fn(&Overrun) + Send + Sync>);
+
+impl fmt::Debug for OverrunHandler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("OverrunHandler")
+    }
+}
+
+/// A profiler of the pipeline stages.
+#[derive(Debug)]
+pub struct Profiler {
+    names: Vec<String>,
+    lcores: Vec<Vec<Counters>>,
+    budgets: Vec<Option<Budget>>,
+    handler: Option<OverrunHandler>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    /// Create a profiler without any stage.
+    pub fn new() -> Self {
+        Profiler {
+            names: vec![],
+            lcores: (0..RTE_MAX_LCORE).map(|_| vec![]).collect(),
+            budgets: vec![],
+            handler: None,
+        }
+    }
+
+    /// Create a profiler with the named stages, the stage ids are allocated in order.
+    pub fn with_stages<S: AsRef<str>>(names: &[S]) -> Self {
+        let mut profiler = Self::new();
+
+        for name in names {
+            profiler.add_stage(name.as_ref());
+        }
+
+        profiler
+    }
+
+    /// Add a named stage, returns its id.
+    pub fn add_stage(&mut self, name: &str) -> StageId {
+        let id = StageId(self.names.len());
+
+        self.names.push(name.to_owned());
+        self.budgets.push(None);
+
+        for counters in &mut self.lcores {
+            counters.push(Counters::default());
+        }
+
+        id
+    }
+
+    /// Find a stage by name.
+    pub fn stage(&self, name: &str) -> Option<StageId> {
+        self.names.iter().position(|n| n == name).map(StageId)
+    }
+
+    /// The name of the stage.
+    pub fn stage_name(&self, stage: StageId) -> Option<&str> {
+        self.names.get(stage.0).map(|s| s.as_str())
+    }
+
+    /// The number of stages.
+    pub fn stages(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Set the cycle budget of a stage, or remove it with `None`.
+    pub fn set_budget<B: Into<Option<Budget>>>(&mut self, stage: StageId, budget: B) {
+        if let Some(slot) = self.budgets.get_mut(stage.0) {
+            *slot = budget.into();
+        }
+    }
+
+    /// The cycle budget of a stage.
+    pub fn budget(&self, stage: StageId) -> Option<Budget> {
+        self.budgets.get(stage.0).cloned().and_then(|budget| budget)
+    }
+
+    /// Set the handler of the overruns, which is called on the lcore running the stage.
+    pub fn set_overrun_handler<F: Fn(&Overrun) + Send + Sync + 'static>(&mut self, handler: F) {
+        self.handler = Some(OverrunHandler(Box::new(handler)));
+    }
+
+    #[inline]
+    fn counters(&self, stage: StageId) -> Option<&Counters> {
+        lcore::current().and_then(|lcore_id| self.lcores[*lcore_id as usize].get(stage.0))
+    }
+
+    /// Enter a stage on the current lcore.
+    ///
+    /// The different stages could be nested, but a stage must not be reentered before it exits.
+    /// It does nothing if called from a non-EAL thread.
+    #[inline]
+    pub fn enter(&self, stage: StageId) {
+        if let Some(counters) = self.counters(stage) {
+            counters.start.store(rdtsc(), Ordering::Relaxed);
+        }
+    }
+
+    /// Exit a stage on the current lcore, which has processed some packets.
+    ///
+    /// Returns the overrun if the stage has exceeded its budget for the consecutive calls,
+    /// which is also passed to the overrun handler.
+    #[inline]
+    pub fn exit(&self, stage: StageId, packets: usize) -> Option<Overrun> {
+        let lcore_id = lcore::current()?;
+        let counters = self.lcores[*lcore_id as usize].get(stage.0)?;
+        let cycles = rdtsc().wrapping_sub(counters.start.load(Ordering::Relaxed));
+
+        Counters::add(&counters.cycles, cycles);
+        Counters::add(&counters.calls, 1);
+        Counters::add(&counters.packets, packets as u64);
+
+        let budget = self.budget(stage)?;
+
+        if cycles > budget.cycles {
+            Counters::add(&counters.overruns, 1);
+        }
+
+        let mut streak = counters.streak.load(Ordering::Relaxed);
+        let triggered = budget.check(cycles, &mut streak);
+
+        counters.streak.store(streak, Ordering::Relaxed);
+
+        let overrun = Overrun {
+            stage,
+            lcore_id,
+            cycles,
+            budget: budget.cycles,
+            consecutive: triggered?,
+            packets,
+        };
+
+        if let Some(ref handler) = self.handler {
+            (handler.0)(&overrun);
+        }
+
+        Some(overrun)
+    }
+
+    /// Profile a closure as a stage, which returns the number of processed packets.
+    #[inline]
+    pub fn run<F: FnOnce() -> usize>(&self, stage: StageId, f: F) -> usize {
+        self.enter(stage);
+
+        let packets = f();
+
+        self.exit(stage, packets);
+
+        packets
+    }
+
+    /// The statistics of a stage on a lcore.
+    pub fn lcore_stats(&self, lcore_id: lcore::Id, stage: StageId) -> StageStats {
+        self.lcores[*lcore_id as usize]
+            .get(stage.0)
+            .map(Counters::stats)
+            .unwrap_or_default()
+    }
+
+    /// The statistics of a stage summed over all the lcores.
+    pub fn stats(&self, stage: StageId) -> StageStats {
+        self.lcores
+            .iter()
+            .filter_map(|counters| counters.get(stage.0))
+            .fold(StageStats::default(), |stats, counters| stats.merge(counters.stats()))
+    }
+
+    /// Reset all the counters.
+    ///
+    /// The counters updated by the lcores at the same time may be lost.
+    pub fn reset(&self) {
+        for counters in self.lcores.iter().flat_map(|counters| counters.iter()) {
+            counters.reset()
+        }
+    }
+
+    /// Take a report of all the stages.
+    pub fn report(&self) -> Report {
+        Report {
+            stages: (0..self.names.len())
+                .map(|id| (self.names[id].clone(), self.stats(StageId(id))))
+                .collect(),
+        }
+    }
+}
+
+/// A deadline of a polling loop in the TSC cycles, e.g. to stop polling more queues
+/// when the cycle budget of the iteration is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deadline(u64);
+
+impl Deadline {
+    /// A deadline after the TSC cycles from now.
+    pub fn after(cycles: u64) -> Self {
+        Deadline(rdtsc().wrapping_add(cycles))
+    }
+
+    /// A deadline after the budget from now.
+    pub fn after_budget(budget: &Budget) -> Self {
+        Self::after(budget.cycles)
+    }
+
+    /// The remaining TSC cycles before the deadline.
+    #[inline]
+    pub fn remaining(&self) -> u64 {
+        self.0.saturating_sub(rdtsc())
+    }
+
+    /// The deadline has passed.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        rdtsc() >= self.0
+    }
+}
+
+/// A report of the pipeline stages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The name and statistics of the stages.
+    pub stages: Vec<(String, StageStats)>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<20} {:>16} {:>12} {:>16} {:>12} {:>12}",
+            "stage", "cycles", "calls", "packets", "cycles/call", "cycles/pkt"
+        )?;
+
+        for (name, stats) in &self.stages {
+            let avg = |v: Option<f64>| v.map_or_else(|| "-".to_owned(), |v| format!("{:.1}", v));
+
+            writeln!(
+                f,
+                "{:<20} {:>16} {:>12} {:>16} {:>12} {:>12}",
+                name,
+                stats.cycles,
+                stats.calls,
+                stats.packets,
+                avg(stats.cycles_per_call()),
+                avg(stats.cycles_per_packet())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let stats = StageStats {
+            cycles: 1200,
+            calls: 4,
+            packets: 100,
+            overruns: 1,
+        };
+
+        assert_eq!(stats.cycles_per_call(), Some(300.0));
+        assert_eq!(stats.cycles_per_packet(), Some(12.0));
+        assert_eq!(StageStats::default().cycles_per_packet(), None);
+        assert_eq!(stats.merge(stats).packets, 200);
+        assert_eq!(stats.merge(stats).overruns, 2);
+
+        let report = Report {
+            stages: vec![("rx".to_owned(), stats), ("tx".to_owned(), StageStats::default())],
+        };
+        let lines = report.to_string();
+        let lines: Vec<&str> = lines.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("rx "));
+        assert!(lines[1].ends_with(" 300.0         12.0"));
+        assert!(lines[2].ends_with(" -            -"));
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = Budget::new(100).with_max_overruns(3);
+        let mut streak = 0;
+
+        assert_eq!(Budget::new(100).with_max_overruns(0).max_overruns, 1);
+
+        assert_eq!(budget.check(150, &mut streak), None);
+        assert_eq!(budget.check(150, &mut streak), None);
+        assert_eq!(budget.check(100, &mut streak), None);
+        assert_eq!(streak, 0);
+
+        assert_eq!(budget.check(101, &mut streak), None);
+        assert_eq!(budget.check(200, &mut streak), None);
+        assert_eq!(budget.check(300, &mut streak), Some(3));
+        assert_eq!(streak, 0);
+
+        assert_eq!(Budget::new(100).check(101, &mut streak), Some(1));
+    }
+}
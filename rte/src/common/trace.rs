@@ -0,0 +1,164 @@
+//!
+//! Control of the DPDK trace library.
+//!
+//! The tracepoints are recorded into per-thread buffers, and saved to the trace directory
+//! in Common Trace Format (CTF), which can be analyzed offline with `babeltrace` or Trace Compass.
+//!
+//! The trace library is available since DPDK 20.05, the functions fail with `ENOTSUP` error
+//! if it is unavailable in the linked DPDK.
+//!
+//! The tracepoints are configured with the EAL arguments, see `eal::Builder::trace`,
+//! or enabled and disabled at runtime by name.
+//!
+//! ```no_run
+//! use rte::trace;
+//!
+//! trace::enable("app.user.*").expect("Cannot enable tracepoints");
+//!
+//! trace::emit_u64("rx_burst", 32);
+//! trace::emit_str("state", "draining");
+//!
+//! trace::save().expect("Cannot save traces");
+//! ```
+use std::fmt;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::str;
+
+use cfile;
+use libc;
+
+use errors::{Result, RteError};
+use ffi;
+use utils::{self, AsCString};
+
+/// The mode when the trace buffer is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceMode {
+    /// Overwrite the oldest events in the trace buffer.
+    Overwrite,
+    /// Discard the new events.
+    Discard,
+}
+
+impl fmt::Display for TraceMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TraceMode::Overwrite => "overwrite",
+            TraceMode::Discard => "discard",
+        })
+    }
+}
+
+impl str::FromStr for TraceMode {
+    type Err = RteError;
+
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s {
+            "o" | "overwrite" => Ok(TraceMode::Overwrite),
+            "d" | "discard" => Ok(TraceMode::Discard),
+            _ => Err(RteError(libc::EINVAL)),
+        }
+    }
+}
+
+fn check(ret: i32) -> Result<i32> {
+    if ret < 0 {
+        Err(RteError(-ret).into())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Test if trace is enabled, at least one tracepoint is enabled.
+pub fn is_enabled() -> bool {
+    unsafe { ffi::_rte_trace_is_enabled() != 0 }
+}
+
+/// Enable the tracepoints whose names match the globbing pattern, e.g. `lib.ethdev.*`.
+///
+/// Returns whether any tracepoint matched the pattern.
+pub fn enable<S: AsRef<str>>(pattern: S) -> Result<bool> {
+    let pattern = pattern.as_ref().as_cstring();
+
+    check(unsafe { ffi::_rte_trace_pattern(pattern.as_ptr(), 1) }).map(|n| n > 0)
+}
+
+/// Disable the tracepoints whose names match the globbing pattern.
+///
+/// Returns whether any tracepoint matched the pattern.
+pub fn disable<S: AsRef<str>>(pattern: S) -> Result<bool> {
+    let pattern = pattern.as_ref().as_cstring();
+
+    check(unsafe { ffi::_rte_trace_pattern(pattern.as_ptr(), 0) }).map(|n| n > 0)
+}
+
+/// Enable the tracepoints whose names match the regular expression.
+///
+/// Returns whether any tracepoint matched the regular expression.
+pub fn enable_regex<S: AsRef<str>>(regex: S) -> Result<bool> {
+    let regex = regex.as_ref().as_cstring();
+
+    check(unsafe { ffi::_rte_trace_regexp(regex.as_ptr(), 1) }).map(|n| n > 0)
+}
+
+/// Disable the tracepoints whose names match the regular expression.
+///
+/// Returns whether any tracepoint matched the regular expression.
+pub fn disable_regex<S: AsRef<str>>(regex: S) -> Result<bool> {
+    let regex = regex.as_ref().as_cstring();
+
+    check(unsafe { ffi::_rte_trace_regexp(regex.as_ptr(), 0) }).map(|n| n > 0)
+}
+
+/// Get the mode when the trace buffer is full.
+pub fn mode() -> Result<TraceMode> {
+    check(unsafe { ffi::_rte_trace_mode_get() }).map(|mode| {
+        if mode == 0 {
+            TraceMode::Overwrite
+        } else {
+            TraceMode::Discard
+        }
+    })
+}
+
+/// Set the mode when the trace buffer is full.
+pub fn set_mode(mode: TraceMode) -> Result<()> {
+    check(unsafe { ffi::_rte_trace_mode_set((mode == TraceMode::Discard) as i32) }).map(|_| ())
+}
+
+/// Save the trace buffers and metadata to the trace directory, in CTF format.
+///
+/// The traces are saved when the EAL is cleaned up, it's useful to save them before exiting abnormally.
+pub fn save() -> Result<()> {
+    check(unsafe { ffi::_rte_trace_save() }).map(|_| ())
+}
+
+/// Dump the trace configuration and the registered tracepoints.
+pub fn dump<S: AsRawFd>(s: &S) -> Result<()> {
+    let mut f = cfile::fdopen(s, "w")?;
+
+    check(unsafe { ffi::_rte_trace_dump(&mut **f as *mut _ as *mut _) }).map(|_| ())
+}
+
+/// Dump the trace configuration and the registered tracepoints to the writer.
+pub fn dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    check(utils::capture(w, |f| unsafe { ffi::_rte_trace_dump(f as *mut _) })?).map(|_| ())
+}
+
+/// Emit the user-defined tracepoint `app.user.u64`, with a name and a value.
+///
+/// The tracepoint is cheap when it is disabled, but the name is copied to a C string for each call.
+pub fn emit_u64(name: &str, value: u64) {
+    let name = name.as_cstring();
+
+    unsafe { ffi::_rte_trace_user_u64(name.as_ptr(), value) }
+}
+
+/// Emit the user-defined tracepoint `app.user.str`, with a name and a message.
+pub fn emit_str(name: &str, msg: &str) {
+    let name = name.as_cstring();
+    let msg = msg.as_cstring();
+
+    unsafe { ffi::_rte_trace_user_str(name.as_ptr(), msg.as_ptr()) }
+}
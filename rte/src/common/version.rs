@@ -1,7 +1,14 @@
+use std::cmp::Ordering;
 use std::ffi::CStr;
+use std::fmt;
+use std::str::FromStr;
+
+use failure::Error;
 
 use ffi;
 
+use errors::{ErrorKind, Result};
+
 /// Patch level number i.e. the z in yy.mm.z
 pub use ffi::RTE_VER_MINOR;
 /// Minor version/month number i.e. the mm in yy.mm.z
@@ -54,3 +61,152 @@ pub fn version() -> String {
         )
     }
 }
+
+/// The version of DPDK, e.g. `DPDK 18.11.1` or `DPDK 19.02.0-rc1`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Version {
+    /// String that appears before the version number
+    pub prefix: String,
+    /// Major version/year number i.e. the yy in yy.mm.z
+    pub year: u32,
+    /// Minor version/month number i.e. the mm in yy.mm.z
+    pub month: u32,
+    /// Patch level number i.e. the z in yy.mm.z
+    pub minor: u32,
+    /// Extra string to be appended to version number
+    pub suffix: String,
+    /// Patch release number, 0-15 are the release candidates, 16 is the release
+    pub release: u32,
+}
+
+impl Version {
+    /// The version which the crate was compiled with.
+    pub fn compiled() -> Self {
+        Version {
+            prefix: RTE_VER_PREFIX.to_string(),
+            year: RTE_VER_YEAR,
+            month: RTE_VER_MONTH,
+            minor: RTE_VER_MINOR,
+            suffix: RTE_VER_SUFFIX.to_string(),
+            release: RTE_VER_RELEASE,
+        }
+    }
+
+    /// All version numbers in one to compare with `RTE_VERSION`
+    pub fn num(&self) -> u32 {
+        RTE_VERSION_NUM!(self.year, self.month, self.minor, self.release)
+    }
+
+    /// The version is a release candidate.
+    pub fn is_rc(&self) -> bool {
+        self.release < 16
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.num().cmp(&other.num())
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}.{:02}.{}", self.prefix, self.year, self.month, self.minor)?;
+
+        if !self.suffix.is_empty() {
+            write!(f, "{}{}", self.suffix, self.release % 16)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || -> Error { ErrorKind::InvalidVersion(s.to_owned()).into() };
+
+        let mut words = s.trim().rsplitn(2, ' ');
+        let ver = words.next().ok_or_else(invalid)?;
+        let prefix = words.next().unwrap_or_default();
+
+        // the suffix starts from the first character which isn't a digit or dot
+        let (num, extra) = ver.split_at(ver.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(ver.len()));
+        let mut nums = num.split('.').map(u32::from_str);
+
+        let mut next = || nums.next().and_then(|n| n.ok()).ok_or_else(invalid);
+        let (year, month, minor) = (next()?, next()?, next()?);
+
+        if nums.next().is_some() {
+            return Err(invalid());
+        }
+
+        let (suffix, release) = if extra.is_empty() {
+            (String::new(), 16)
+        } else {
+            let pos = extra.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            let release = extra[pos..].parse::<u32>().map_err(|_| invalid())?;
+
+            if release >= 16 {
+                return Err(invalid());
+            }
+
+            (extra[..pos].to_owned(), release)
+        };
+
+        Ok(Version {
+            prefix: prefix.to_owned(),
+            year,
+            month,
+            minor,
+            suffix,
+            release,
+        })
+    }
+}
+
+/// The version of the DPDK library linked at runtime.
+pub fn runtime_version() -> Result<Version> {
+    unsafe { CStr::from_ptr(ffi::_rte_version()) }.to_str()?.parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version() {
+        let v = "DPDK 18.11.1".parse::<Version>().unwrap();
+
+        assert_eq!((v.year, v.month, v.minor, v.release), (18, 11, 1, 16));
+        assert!(!v.is_rc());
+        assert_eq!(v.to_string(), "DPDK 18.11.1");
+        assert_eq!(v.num(), 0x120b_0110);
+
+        let rc = "DPDK 19.02.0-rc1".parse::<Version>().unwrap();
+
+        assert_eq!((rc.suffix.as_str(), rc.release), ("-rc", 1));
+        assert!(rc.is_rc());
+        assert_eq!(rc.to_string(), "DPDK 19.02.0-rc1");
+        assert!(rc > v);
+        assert!("DPDK 19.02.0".parse::<Version>().unwrap() > rc);
+
+        for s in &[
+            "",
+            "DPDK",
+            "DPDK 18.11",
+            "DPDK 18.11.1.1",
+            "DPDK 18.11.1-rc",
+            "DPDK 18.11.x",
+        ] {
+            assert!(s.parse::<Version>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,651 @@
+//!
+//! TCP connection tracking and SYN cookies.
+//!
+//! The `Conntrack` table follows the TCP connections keyed by the 5-tuple of their originator,
+//! with a minimal state machine of the handshake, the FIN and RST, and classifies each packet
+//! as a new, established or invalid one, as the `ctstate` of netfilter.
+//!
+//! The `SynCookies` encode the MSS and a timestamp into the sequence number of the SYN-ACK,
+//! so a mitigation application could answer the SYN floods without keeping any state,
+//! and only track the connections whose final ACK carries a valid cookie.
+//!
+//! The table is owned by a polling lcore and updated on the fast path without any lock,
+//! the lcore should `expire` it periodically.
+//!
+//! ```no_run
+//! use std::time::{Instant, SystemTime};
+//!
+//! use rte::conntrack::{ConntrackConfig, Conntrack, CtVerdict, SynCookies, TcpSegment};
+//! use rte::*;
+//!
+//! let mut ct = Conntrack::new(ConntrackConfig::default());
+//! let cookies = SynCookies::new();
+//!
+//! let pkts: Vec<mbuf::MBuf> = vec![]; // received from a port
+//!
+//! for m in &pkts {
+//!     let seg = match TcpSegment::parse_mbuf(m) {
+//!         Some(seg) => seg,
+//!         None => continue,
+//!     };
+//!
+//!     match ct.update(&seg, Instant::now()) {
+//!         CtVerdict::Invalid if seg.is_ack() => {
+//!             if cookies.validate(&seg, SystemTime::now()).is_some() {
+//!                 ct.establish(&seg, Instant::now());
+//!             }
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use libc;
+use rand::{thread_rng, Rng};
+
+use ffi;
+
+use filter::dissect_mbuf;
+use hashers::{self, FiveTuple};
+use mbuf::{self, MBuf};
+
+/// TCP Header
+pub type TcpHdr = ffi::tcp_hdr;
+
+unsafe impl mbuf::Header for TcpHdr {
+    const LAYER: mbuf::HeaderLayer = mbuf::HeaderLayer::L4;
+}
+
+/// The minimum length of TCP header.
+pub const TCP_HDR_LEN: usize = 20;
+
+bitflags! {
+    /// The flags of TCP header.
+    pub struct TcpFlags: u8 {
+        /// No more data from sender.
+        const TCP_FIN_FLAG = 0x01;
+        /// Synchronize sequence numbers.
+        const TCP_SYN_FLAG = 0x02;
+        /// Reset the connection.
+        const TCP_RST_FLAG = 0x04;
+        /// Push function.
+        const TCP_PSH_FLAG = 0x08;
+        /// Acknowledgment field significant.
+        const TCP_ACK_FLAG = 0x10;
+        /// Urgent pointer field significant.
+        const TCP_URG_FLAG = 0x20;
+    }
+}
+
+/// The tracked fields of a TCP segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TcpSegment {
+    /// The 5-tuple of the segment, in its direction.
+    pub tuple: FiveTuple,
+    /// The flags of TCP header.
+    pub flags: TcpFlags,
+    /// The sequence number in host byte order.
+    pub seq: u32,
+    /// The acknowledgment number in host byte order.
+    pub ack: u32,
+}
+
+impl TcpSegment {
+    /// Parse the TCP segment of a packet, which starts from the Ethernet header.
+    ///
+    /// Returns `None` if it's not a TCP packet, or a non-first fragment.
+    pub fn parse_mbuf(m: &MBuf) -> Option<Self> {
+        let d = dissect_mbuf(m)?;
+
+        if d.proto != Some(libc::IPPROTO_TCP as u8) {
+            return None;
+        }
+
+        let mut buf = [0; TCP_HDR_LEN];
+        let hdr = m.read(d.l4_off?, &mut buf)?;
+
+        Some(TcpSegment {
+            tuple: FiveTuple {
+                src_addr: d.src_ip?,
+                dst_addr: d.dst_ip?,
+                src_port: d.src_port?,
+                dst_port: d.dst_port?,
+                proto: libc::IPPROTO_TCP as u8,
+            },
+            flags: TcpFlags::from_bits_truncate(hdr[13]),
+            seq: u32::from_be_bytes([hdr[4], hdr[5], hdr[6], hdr[7]]),
+            ack: u32::from_be_bytes([hdr[8], hdr[9], hdr[10], hdr[11]]),
+        })
+    }
+
+    /// A SYN segment without ACK, which opens a connection.
+    pub fn is_syn(&self) -> bool {
+        self.flags & (TcpFlags::TCP_SYN_FLAG | TcpFlags::TCP_ACK_FLAG | TcpFlags::TCP_RST_FLAG)
+            == TcpFlags::TCP_SYN_FLAG
+    }
+
+    /// A SYN-ACK segment, which answers the SYN.
+    pub fn is_syn_ack(&self) -> bool {
+        self.flags & (TcpFlags::TCP_SYN_FLAG | TcpFlags::TCP_ACK_FLAG | TcpFlags::TCP_RST_FLAG)
+            == TcpFlags::TCP_SYN_FLAG | TcpFlags::TCP_ACK_FLAG
+    }
+
+    /// An ACK segment without SYN or RST, which may complete the handshake.
+    pub fn is_ack(&self) -> bool {
+        self.flags & (TcpFlags::TCP_SYN_FLAG | TcpFlags::TCP_ACK_FLAG | TcpFlags::TCP_RST_FLAG)
+            == TcpFlags::TCP_ACK_FLAG
+    }
+
+    /// A FIN segment.
+    pub fn is_fin(&self) -> bool {
+        self.flags.contains(TcpFlags::TCP_FIN_FLAG)
+    }
+
+    /// A RST segment.
+    pub fn is_rst(&self) -> bool {
+        self.flags.contains(TcpFlags::TCP_RST_FLAG)
+    }
+}
+
+/// The state of a tracked TCP connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TcpState {
+    /// The SYN was sent by the originator.
+    SynSent,
+    /// The SYN-ACK was sent by the responder.
+    SynReceived,
+    /// The handshake was completed.
+    Established,
+    /// A FIN was sent by one side.
+    FinWait,
+    /// The FINs were sent by both sides.
+    TimeWait,
+    /// The connection was reset.
+    Close,
+}
+
+/// The direction of a packet in its connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CtDir {
+    /// From the originator to the responder.
+    Original = 0,
+    /// From the responder to the originator.
+    Reply = 1,
+}
+
+/// The classification of a packet by the connection tracking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CtVerdict {
+    /// The packet opens a new connection.
+    New,
+    /// The packet belongs to a tracked connection.
+    Established,
+    /// The packet doesn't match the state of any connection.
+    Invalid,
+    /// The packet is not a TCP packet.
+    Untracked,
+}
+
+/// A tracked TCP connection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conn {
+    /// The 5-tuple of the originator.
+    pub tuple: FiveTuple,
+    /// The state of the connection.
+    pub state: TcpState,
+    /// The number of packets in the original and reply directions.
+    pub packets: [u64; 2],
+    /// The time of the first packet.
+    pub first_seen: Instant,
+    /// The time of the last packet.
+    pub last_seen: Instant,
+    fin: [bool; 2],
+}
+
+impl Conn {
+    fn new(tuple: FiveTuple, state: TcpState, now: Instant) -> Self {
+        Conn {
+            tuple,
+            state,
+            packets: [0; 2],
+            first_seen: now,
+            last_seen: now,
+            fin: [false; 2],
+        }
+    }
+
+    /// Apply a segment to the state machine, returns the verdict of the segment.
+    fn transit(&mut self, dir: CtDir, seg: &TcpSegment, now: Instant) -> CtVerdict {
+        use self::CtDir::*;
+        use self::TcpState::*;
+
+        if seg.is_rst() {
+            self.state = Close;
+        } else {
+            self.state = match (self.state, dir) {
+                (SynSent, Original) if seg.is_syn() => SynSent,
+                (SynSent, Reply) if seg.is_syn_ack() => SynReceived,
+                (SynReceived, Reply) if seg.is_syn_ack() => SynReceived,
+                (SynReceived, Original) if seg.is_ack() => Established,
+                (SynSent, _) | (SynReceived, _) => return CtVerdict::Invalid,
+                // the port is reused by a new connection of the originator
+                (TimeWait, Original) | (Close, Original) if seg.is_syn() => {
+                    *self = Conn::new(self.tuple, SynSent, now);
+
+                    SynSent
+                }
+                (Close, _) => return CtVerdict::Invalid,
+                (_, _) if seg.flags.contains(TcpFlags::TCP_SYN_FLAG) => return CtVerdict::Invalid,
+                (_, dir) if seg.is_fin() => {
+                    self.fin[dir as usize] = true;
+
+                    if self.fin[0] && self.fin[1] {
+                        TimeWait
+                    } else {
+                        FinWait
+                    }
+                }
+                (state, _) => state,
+            };
+        }
+
+        self.packets[dir as usize] += 1;
+        self.last_seen = now;
+
+        match self.state {
+            SynSent if self.packets[Reply as usize] == 0 => CtVerdict::New,
+            _ => CtVerdict::Established,
+        }
+    }
+}
+
+/// The timeouts and the capacity of the connection tracking table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConntrackConfig {
+    /// The maximum number of connections, the new connections are invalid when the table is full.
+    pub max_conns: usize,
+    /// The timeout of the half-open connections.
+    pub syn_timeout: Duration,
+    /// The idle timeout of the established connections.
+    pub established_timeout: Duration,
+    /// The timeout of the closing connections, after a FIN.
+    pub fin_timeout: Duration,
+    /// The timeout of the closed connections, after the FINs of both sides or a RST.
+    pub close_timeout: Duration,
+}
+
+impl Default for ConntrackConfig {
+    fn default() -> Self {
+        ConntrackConfig {
+            max_conns: 65536,
+            syn_timeout: Duration::from_secs(30),
+            established_timeout: Duration::from_secs(7200),
+            fin_timeout: Duration::from_secs(120),
+            close_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ConntrackConfig {
+    /// The timeout of the connection in the state.
+    pub fn timeout(&self, state: TcpState) -> Duration {
+        match state {
+            TcpState::SynSent | TcpState::SynReceived => self.syn_timeout,
+            TcpState::Established => self.established_timeout,
+            TcpState::FinWait => self.fin_timeout,
+            TcpState::TimeWait | TcpState::Close => self.close_timeout,
+        }
+    }
+}
+
+/// The counters of the connection tracking table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConntrackStats {
+    /// The number of new packets.
+    pub new: u64,
+    /// The number of established packets.
+    pub established: u64,
+    /// The number of invalid packets.
+    pub invalid: u64,
+    /// The number of non-TCP packets.
+    pub untracked: u64,
+    /// The number of new connections which were not tracked because the table is full.
+    pub overflows: u64,
+    /// The number of expired connections.
+    pub expired: u64,
+}
+
+/// A TCP connection tracking table.
+#[derive(Clone, Debug)]
+pub struct Conntrack {
+    conf: ConntrackConfig,
+    conns: HashMap<FiveTuple, Conn>,
+    stats: ConntrackStats,
+}
+
+impl Conntrack {
+    /// Create an empty table.
+    pub fn new(conf: ConntrackConfig) -> Self {
+        Conntrack {
+            conf,
+            conns: HashMap::new(),
+            stats: ConntrackStats::default(),
+        }
+    }
+
+    /// The timeouts and the capacity of the table.
+    pub fn config(&self) -> &ConntrackConfig {
+        &self.conf
+    }
+
+    /// The counters of the table.
+    pub fn stats(&self) -> &ConntrackStats {
+        &self.stats
+    }
+
+    /// The number of tracked connections.
+    pub fn len(&self) -> usize {
+        self.conns.len()
+    }
+
+    /// The table has no connection.
+    pub fn is_empty(&self) -> bool {
+        self.conns.is_empty()
+    }
+
+    /// The connection of the 5-tuple in any direction, and the direction of the 5-tuple.
+    pub fn get(&self, tuple: &FiveTuple) -> Option<(&Conn, CtDir)> {
+        self.conns
+            .get(tuple)
+            .map(|conn| (conn, CtDir::Original))
+            .or_else(|| self.conns.get(&tuple.reverse()).map(|conn| (conn, CtDir::Reply)))
+    }
+
+    /// An iterator over the tracked connections.
+    pub fn conns(&self) -> impl Iterator<Item = &Conn> {
+        self.conns.values()
+    }
+
+    /// Track a packet.
+    pub fn track(&mut self, m: &MBuf, now: Instant) -> CtVerdict {
+        match TcpSegment::parse_mbuf(m) {
+            Some(seg) => self.update(&seg, now),
+            None => {
+                self.stats.untracked += 1;
+
+                CtVerdict::Untracked
+            }
+        }
+    }
+
+    /// Track a burst of packets, returns the verdicts of the packets.
+    pub fn track_burst(&mut self, pkts: &[MBuf]) -> Vec<CtVerdict> {
+        let now = Instant::now();
+
+        pkts.iter().map(|m| self.track(m, now)).collect()
+    }
+
+    /// Track a TCP segment.
+    ///
+    /// A connection is only opened by a SYN, the other segments without connection are invalid.
+    pub fn update(&mut self, seg: &TcpSegment, now: Instant) -> CtVerdict {
+        let verdict = if let Some(conn) = self.conns.get_mut(&seg.tuple) {
+            conn.transit(CtDir::Original, seg, now)
+        } else if let Some(conn) = self.conns.get_mut(&seg.tuple.reverse()) {
+            conn.transit(CtDir::Reply, seg, now)
+        } else if seg.is_syn() {
+            self.insert(seg, TcpState::SynSent, now)
+        } else {
+            CtVerdict::Invalid
+        };
+
+        match verdict {
+            CtVerdict::New => self.stats.new += 1,
+            CtVerdict::Established => self.stats.established += 1,
+            CtVerdict::Invalid => self.stats.invalid += 1,
+            CtVerdict::Untracked => self.stats.untracked += 1,
+        }
+
+        verdict
+    }
+
+    /// Track an established connection of the originator of the segment,
+    /// whose handshake was not seen, e.g. the final ACK with a valid SYN cookie.
+    ///
+    /// Returns `false` if the table is full.
+    pub fn establish(&mut self, seg: &TcpSegment, now: Instant) -> bool {
+        if let Some(conn) = self.conns.get_mut(&seg.tuple) {
+            *conn = Conn::new(seg.tuple, TcpState::Established, conn.first_seen);
+            conn.packets[CtDir::Original as usize] += 1;
+            conn.last_seen = now;
+
+            return true;
+        }
+
+        if self.conns.contains_key(&seg.tuple.reverse()) {
+            return true;
+        }
+
+        self.insert(seg, TcpState::Established, now) != CtVerdict::Invalid
+    }
+
+    fn insert(&mut self, seg: &TcpSegment, state: TcpState, now: Instant) -> CtVerdict {
+        if self.conns.len() >= self.conf.max_conns {
+            self.stats.overflows += 1;
+
+            return CtVerdict::Invalid;
+        }
+
+        let mut conn = Conn::new(seg.tuple, state, now);
+
+        conn.packets[CtDir::Original as usize] += 1;
+
+        self.conns.insert(seg.tuple, conn);
+
+        if state == TcpState::SynSent {
+            CtVerdict::New
+        } else {
+            CtVerdict::Established
+        }
+    }
+
+    /// Remove the connections which were idle for the timeout of their state,
+    /// returns the number of expired connections.
+    pub fn expire(&mut self, now: Instant) -> usize {
+        let conf = self.conf;
+        let n = self.conns.len();
+
+        self.conns
+            .retain(|_, conn| now.saturating_duration_since(conn.last_seen) < conf.timeout(conn.state));
+
+        let expired = n - self.conns.len();
+
+        self.stats.expired += expired as u64;
+
+        expired
+    }
+
+    /// Remove all the connections.
+    pub fn clear(&mut self) {
+        self.conns.clear()
+    }
+}
+
+/// The MSS values encoded in the SYN cookies.
+pub const SYNCOOKIE_MSS: [u16; 4] = [536, 1300, 1440, 1460];
+
+/// The maximum age of a SYN cookie, in minutes.
+pub const MAX_SYNCOOKIE_AGE: u32 = 2;
+
+const COOKIE_BITS: u32 = 24;
+const COOKIE_MASK: u32 = (1 << COOKIE_BITS) - 1;
+
+/// The SYN cookies generator and validator.
+///
+/// The cookies are validated with the same secrets, which should be shared by the lcores
+/// when the SYN and the final ACK of a connection may be received by different queues.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SynCookies {
+    secrets: [u32; 2],
+}
+
+impl Default for SynCookies {
+    fn default() -> Self {
+        SynCookies::new()
+    }
+}
+
+impl SynCookies {
+    /// Create with the random secrets.
+    pub fn new() -> Self {
+        SynCookies::with_secrets(thread_rng().gen(), thread_rng().gen())
+    }
+
+    /// Create with the secrets.
+    pub fn with_secrets(secret1: u32, secret2: u32) -> Self {
+        SynCookies {
+            secrets: [secret1, secret2],
+        }
+    }
+
+    fn hash(&self, tuple: &FiveTuple, count: u32, c: usize) -> u32 {
+        let mut key = tuple.to_bytes();
+
+        key.extend_from_slice(&count.to_be_bytes());
+
+        hashers::jhash(&key, self.secrets[c])
+    }
+
+    fn count(now: SystemTime) -> u32 {
+        (now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 60) as u32
+    }
+
+    /// Generate the sequence number of the SYN-ACK which answers the SYN,
+    /// with the largest encoded MSS not greater than the MSS of the SYN.
+    pub fn generate(&self, syn: &TcpSegment, mss: u16, now: SystemTime) -> u32 {
+        let count = Self::count(now);
+        let mss_idx = SYNCOOKIE_MSS.iter().rposition(|&m| m <= mss).unwrap_or(0) as u32;
+
+        self.hash(&syn.tuple, 0, 0)
+            .wrapping_add(syn.seq)
+            .wrapping_add(count << COOKIE_BITS)
+            .wrapping_add(self.hash(&syn.tuple, count, 1).wrapping_add(mss_idx) & COOKIE_MASK)
+    }
+
+    /// Validate the cookie acknowledged by the final ACK of the handshake,
+    /// returns the MSS encoded in the cookie, or `None` if the cookie is invalid or expired.
+    pub fn validate(&self, ack: &TcpSegment, now: SystemTime) -> Option<u16> {
+        let seq = ack.seq.wrapping_sub(1);
+        let cookie = ack
+            .ack
+            .wrapping_sub(1)
+            .wrapping_sub(self.hash(&ack.tuple, 0, 0))
+            .wrapping_sub(seq);
+        let count = Self::count(now);
+        let diff = count.wrapping_sub(cookie >> COOKIE_BITS) & (u32::MAX >> COOKIE_BITS);
+
+        if diff >= MAX_SYNCOOKIE_AGE {
+            return None;
+        }
+
+        let mss_idx = cookie.wrapping_sub(self.hash(&ack.tuple, count.wrapping_sub(diff), 1)) & COOKIE_MASK;
+
+        SYNCOOKIE_MSS.get(mss_idx as usize).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn seg(tuple: FiveTuple, flags: TcpFlags, seq: u32, ack: u32) -> TcpSegment {
+        TcpSegment { tuple, flags, seq, ack }
+    }
+
+    #[test]
+    fn test_conntrack() {
+        let now = Instant::now();
+        let mut ct = Conntrack::new(ConntrackConfig::default());
+        let t = FiveTuple::v4(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            12345,
+            80,
+            libc::IPPROTO_TCP as u8,
+        );
+        let r = t.reverse();
+
+        assert_eq!(
+            ct.update(&seg(t, TcpFlags::TCP_ACK_FLAG, 1, 1), now),
+            CtVerdict::Invalid
+        );
+        assert_eq!(ct.update(&seg(t, TcpFlags::TCP_SYN_FLAG, 1, 0), now), CtVerdict::New);
+        assert_eq!(ct.update(&seg(t, TcpFlags::TCP_SYN_FLAG, 1, 0), now), CtVerdict::New);
+        assert_eq!(
+            ct.update(&seg(r, TcpFlags::TCP_SYN_FLAG | TcpFlags::TCP_ACK_FLAG, 100, 2), now),
+            CtVerdict::Established
+        );
+        assert_eq!(ct.get(&t).unwrap().0.state, TcpState::SynReceived);
+        assert_eq!(
+            ct.update(&seg(t, TcpFlags::TCP_ACK_FLAG, 2, 101), now),
+            CtVerdict::Established
+        );
+        assert_eq!(ct.get(&r).unwrap().0.state, TcpState::Established);
+        assert_eq!(ct.get(&r).unwrap().1, CtDir::Reply);
+
+        ct.update(&seg(t, TcpFlags::TCP_FIN_FLAG | TcpFlags::TCP_ACK_FLAG, 2, 101), now);
+        assert_eq!(ct.get(&t).unwrap().0.state, TcpState::FinWait);
+        ct.update(&seg(r, TcpFlags::TCP_FIN_FLAG | TcpFlags::TCP_ACK_FLAG, 101, 3), now);
+        assert_eq!(ct.get(&t).unwrap().0.state, TcpState::TimeWait);
+
+        assert_eq!(ct.expire(now + Duration::from_secs(5)), 0);
+        assert_eq!(ct.expire(now + Duration::from_secs(10)), 1);
+        assert!(ct.is_empty());
+
+        ct.update(&seg(t, TcpFlags::TCP_SYN_FLAG, 1, 0), now);
+        ct.update(&seg(r, TcpFlags::TCP_RST_FLAG | TcpFlags::TCP_ACK_FLAG, 0, 2), now);
+        assert_eq!(ct.get(&t).unwrap().0.state, TcpState::Close);
+        assert_eq!(
+            ct.update(&seg(t, TcpFlags::TCP_ACK_FLAG, 2, 1), now),
+            CtVerdict::Invalid
+        );
+
+        assert_eq!(ct.stats().new, 3);
+        assert_eq!(ct.stats().invalid, 2);
+    }
+
+    #[test]
+    fn test_syn_cookies() {
+        let now = SystemTime::now();
+        let cookies = SynCookies::with_secrets(1, 2);
+        let t = FiveTuple::v4(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            12345,
+            80,
+            libc::IPPROTO_TCP as u8,
+        );
+        let syn = seg(t, TcpFlags::TCP_SYN_FLAG, 1000, 0);
+        let cookie = cookies.generate(&syn, 1450, now);
+        let ack = seg(t, TcpFlags::TCP_ACK_FLAG, 1001, cookie.wrapping_add(1));
+
+        assert_eq!(cookies.validate(&ack, now), Some(1440));
+        assert_eq!(cookies.validate(&ack, now + Duration::from_secs(60)), Some(1440));
+        assert_eq!(cookies.validate(&ack, now + Duration::from_secs(180)), None);
+        assert_eq!(
+            cookies.validate(&seg(t, TcpFlags::TCP_ACK_FLAG, 1001, cookie.wrapping_add(0x101)), now),
+            None
+        );
+        assert_eq!(SynCookies::with_secrets(3, 4).validate(&ack, now), None);
+
+        let mut ct = Conntrack::new(ConntrackConfig::default());
+
+        assert!(ct.establish(&ack, Instant::now()));
+        assert_eq!(ct.get(&t).unwrap().0.state, TcpState::Established);
+    }
+}
@@ -0,0 +1,1505 @@
+//!
+//! Crypto devices
+//!
+//! The asymmetric crypto sessions and operations, e.g. RSA and DSA sign/verify,
+//! Diffie-Hellman key exchange and modular exponentiation, which could be offloaded to
+//! the hardware devices, e.g. QAT, or the `crypto_openssl` software PMD.
+//!
+//! The big numbers of the keys and operations are octet-strings in network byte order,
+//! which are copied to the buffers allocated from the huge-page memory, so the devices could DMA them.
+//!
+//! ECDSA is not supported by the asymmetric crypto API of DPDK 18.11.
+//!
+//! A `CryptoPool` selects the device by the capabilities, which prefers the hardware devices,
+//! and falls back to the software PMDs, e.g. `crypto_openssl`, creating the vdev if it is absent.
+//!
+use std::ffi::CStr;
+use std::mem;
+use std::ops::{Deref, Range};
+use std::os::raw::c_void;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use libc;
+use num_traits::FromPrimitive;
+
+use ffi;
+
+use common::{dev, features, get_tsc_hz, pause, rdtsc};
+use errors::{AsResult, ErrorKind, Result, RteError};
+use malloc;
+use memory::Socket;
+use mempool::{MemPool, MemoryPool};
+use utils::{AsCString, AsRaw};
+
+pub use ffi::RTE_CRYPTODEV_NAME_MAX_LEN;
+
+/// The identifier of a crypto device.
+pub type DevId = u8;
+
+/// The index of a queue pair of a crypto device.
+pub type QueuePairId = u16;
+
+bitflags! {
+    /// The features of a crypto device.
+    pub struct CryptoDevFeatures: u64 {
+        /// Symmetric crypto operations are supported
+        const RTE_CRYPTODEV_FF_SYMMETRIC_CRYPTO = ffi::RTE_CRYPTODEV_FF_SYMMETRIC_CRYPTO as u64;
+        /// Asymmetric crypto operations are supported
+        const RTE_CRYPTODEV_FF_ASYMMETRIC_CRYPTO = ffi::RTE_CRYPTODEV_FF_ASYMMETRIC_CRYPTO as u64;
+        /// Chaining symmetric crypto operations are supported
+        const RTE_CRYPTODEV_FF_SYM_OPERATION_CHAINING = ffi::RTE_CRYPTODEV_FF_SYM_OPERATION_CHAINING as u64;
+        /// Utilises CPU SIMD SSE instructions
+        const RTE_CRYPTODEV_FF_CPU_SSE = ffi::RTE_CRYPTODEV_FF_CPU_SSE as u64;
+        /// Utilises CPU SIMD AVX instructions
+        const RTE_CRYPTODEV_FF_CPU_AVX = ffi::RTE_CRYPTODEV_FF_CPU_AVX as u64;
+        /// Utilises CPU SIMD AVX2 instructions
+        const RTE_CRYPTODEV_FF_CPU_AVX2 = ffi::RTE_CRYPTODEV_FF_CPU_AVX2 as u64;
+        /// Utilises CPU AES-NI instructions
+        const RTE_CRYPTODEV_FF_CPU_AESNI = ffi::RTE_CRYPTODEV_FF_CPU_AESNI as u64;
+        /// Operations are off-loaded to an external hardware accelerator
+        const RTE_CRYPTODEV_FF_HW_ACCELERATED = ffi::RTE_CRYPTODEV_FF_HW_ACCELERATED as u64;
+    }
+}
+
+/// Get the total number of crypto devices that have been successfully initialised.
+pub fn count() -> usize {
+    unsafe { ffi::rte_cryptodev_count() as usize }
+}
+
+/// Iterate the crypto devices.
+pub fn devices() -> Range<DevId> {
+    0..count() as DevId
+}
+
+/// Get the device identifier for the named crypto device.
+pub fn get_dev_id<S: AsRef<str>>(name: S) -> Result<DevId> {
+    let name = name.as_cstring();
+    let dev_id = unsafe { ffi::rte_cryptodev_get_dev_id(name.as_ptr()) };
+
+    if dev_id < 0 {
+        Err(RteError(libc::ENODEV).into())
+    } else {
+        Ok(dev_id as DevId)
+    }
+}
+
+pub type RawCryptoDevInfo = ffi::rte_cryptodev_info;
+
+pub trait CryptoDevice {
+    /// The name of a crypto device.
+    fn name(&self) -> Option<String>;
+
+    /// The NUMA socket to which a crypto device is connected.
+    fn socket_id(&self) -> Socket;
+
+    /// Retrieve the contextual information of a crypto device.
+    fn info(&self) -> RawCryptoDevInfo;
+
+    /// The driver name of a crypto device.
+    fn driver_name(&self) -> String;
+
+    /// The features of a crypto device.
+    fn features(&self) -> CryptoDevFeatures;
+
+    /// Configure a crypto device with the number of queue pairs.
+    fn configure(&self, nb_queue_pairs: u16, socket: Socket) -> Result<&Self>;
+
+    /// Allocate and set up a queue pair, the session pool is used for session-less operations.
+    fn queue_pair_setup<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        nb_descriptors: u32,
+        socket: Socket,
+        session_pool: Option<&P>,
+    ) -> Result<&Self>;
+
+    /// Get the number of configured queue pairs.
+    fn queue_pair_count(&self) -> usize;
+
+    /// Start a crypto device.
+    fn start(&self) -> Result<&Self>;
+
+    /// Stop a crypto device, which can be restarted with `start`.
+    fn stop(&self) -> &Self;
+
+    /// Close a stopped crypto device. The device cannot be restarted!
+    fn close(&self) -> Result<()>;
+
+    /// The capability of a crypto device for the asymmetric transform.
+    fn asym_capability(&self, xform_type: AsymXformType) -> Option<AsymCapability>;
+
+    /// Enqueue a burst of operations for processing on a queue pair.
+    ///
+    /// The enqueued operations are owned by the device and removed from the front of `ops`,
+    /// until they are dequeued with `dequeue_burst`.
+    fn enqueue_burst(&self, qp_id: QueuePairId, ops: &mut Vec<CryptoOp>) -> usize;
+
+    /// Dequeue a burst of processed operations from a queue pair.
+    fn dequeue_burst(&self, qp_id: QueuePairId, ops: &mut [Option<CryptoOp>]) -> usize;
+}
+
+impl CryptoDevice for DevId {
+    fn name(&self) -> Option<String> {
+        unsafe {
+            ffi::rte_cryptodev_name_get(*self)
+                .as_ref()
+                .map(|name| CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    }
+
+    fn socket_id(&self) -> Socket {
+        unsafe { ffi::rte_cryptodev_socket_id(*self) }.into()
+    }
+
+    fn info(&self) -> RawCryptoDevInfo {
+        let mut info = RawCryptoDevInfo::default();
+
+        unsafe { ffi::rte_cryptodev_info_get(*self, &mut info) }
+
+        info
+    }
+
+    fn driver_name(&self) -> String {
+        let info = self.info();
+
+        if info.driver_name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(info.driver_name).to_string_lossy().into_owned() }
+        }
+    }
+
+    fn features(&self) -> CryptoDevFeatures {
+        CryptoDevFeatures::from_bits_truncate(self.info().feature_flags)
+    }
+
+    fn configure(&self, nb_queue_pairs: u16, socket: Socket) -> Result<&Self> {
+        let mut conf = ffi::rte_cryptodev_config {
+            socket_id: socket.raw(),
+            nb_queue_pairs,
+            ff_disable: 0,
+        };
+
+        rte_check!(unsafe { ffi::rte_cryptodev_configure(*self, &mut conf) }; ok => { self })
+    }
+
+    fn queue_pair_setup<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        nb_descriptors: u32,
+        socket: Socket,
+        session_pool: Option<&P>,
+    ) -> Result<&Self> {
+        let conf = ffi::rte_cryptodev_qp_conf { nb_descriptors };
+
+        rte_check!(unsafe {
+            ffi::rte_cryptodev_queue_pair_setup(
+                *self,
+                qp_id,
+                &conf,
+                socket.raw(),
+                session_pool.map_or(ptr::null_mut(), |p| p.as_raw()),
+            )
+        }; ok => { self })
+    }
+
+    fn queue_pair_count(&self) -> usize {
+        unsafe { ffi::rte_cryptodev_queue_pair_count(*self) as usize }
+    }
+
+    fn start(&self) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_cryptodev_start(*self) }; ok => { self })
+    }
+
+    fn stop(&self) -> &Self {
+        unsafe { ffi::rte_cryptodev_stop(*self) };
+
+        self
+    }
+
+    fn close(&self) -> Result<()> {
+        rte_check!(unsafe { ffi::rte_cryptodev_close(*self) })
+    }
+
+    fn asym_capability(&self, xform_type: AsymXformType) -> Option<AsymCapability> {
+        let idx = ffi::rte_cryptodev_asym_capability_idx {
+            type_: xform_type as u32,
+        };
+
+        NonNull::new(unsafe { ffi::rte_cryptodev_asym_capability_get(*self, &idx) } as *mut _).map(AsymCapability)
+    }
+
+    fn enqueue_burst(&self, qp_id: QueuePairId, ops: &mut Vec<CryptoOp>) -> usize {
+        if ops.is_empty() {
+            return 0;
+        }
+
+        let n = unsafe {
+            ffi::_rte_cryptodev_enqueue_burst(*self, qp_id, ops.as_mut_ptr() as *mut _, ops.len() as u16) as usize
+        };
+
+        for op in ops.drain(..n) {
+            mem::forget(op);
+        }
+
+        n
+    }
+
+    fn dequeue_burst(&self, qp_id: QueuePairId, ops: &mut [Option<CryptoOp>]) -> usize {
+        for op in ops.iter_mut() {
+            op.take();
+        }
+
+        unsafe {
+            ffi::_rte_cryptodev_dequeue_burst(*self, qp_id, ops.as_mut_ptr() as *mut _, ops.len() as u16) as usize
+        }
+    }
+}
+
+/// The capability of a crypto device for an asymmetric transform.
+#[derive(Clone, Copy, Debug)]
+pub struct AsymCapability(NonNull<ffi::rte_cryptodev_asymmetric_xform_capability>);
+
+impl AsymCapability {
+    /// The operation is supported by the transform.
+    pub fn supports(&self, op_type: AsymOpType) -> bool {
+        unsafe { ffi::rte_cryptodev_asym_xform_capability_check_optype(self.0.as_ptr(), op_type as u32) != 0 }
+    }
+
+    /// The modulus length is in the supported range of the transform.
+    pub fn supports_modlen(&self, modlen: u16) -> bool {
+        unsafe { ffi::rte_cryptodev_asym_xform_capability_check_modlen(self.0.as_ptr(), modlen) == 0 }
+    }
+}
+
+/// The asymmetric crypto transform types.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum AsymXformType {
+    /// RSA, performs encrypt, decrypt, sign and verify.
+    Rsa = ffi::rte_crypto_asym_xform_type::RTE_CRYPTO_ASYM_XFORM_RSA,
+    /// Diffie-Hellman, performs key generate and shared secret compute.
+    Dh = ffi::rte_crypto_asym_xform_type::RTE_CRYPTO_ASYM_XFORM_DH,
+    /// Digital Signature Algorithm, performs signature generation and verification.
+    Dsa = ffi::rte_crypto_asym_xform_type::RTE_CRYPTO_ASYM_XFORM_DSA,
+    /// Modular inverse, `b^(-1) mod n`.
+    ModInv = ffi::rte_crypto_asym_xform_type::RTE_CRYPTO_ASYM_XFORM_MODINV,
+    /// Modular exponentiation, `b^e mod n`.
+    ModEx = ffi::rte_crypto_asym_xform_type::RTE_CRYPTO_ASYM_XFORM_MODEX,
+}
+
+/// The asymmetric crypto operation types.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum AsymOpType {
+    Encrypt = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_ENCRYPT,
+    Decrypt = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_DECRYPT,
+    Sign = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_SIGN,
+    Verify = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_VERIFY,
+    PrivateKeyGenerate = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_PRIVATE_KEY_GENERATE,
+    PublicKeyGenerate = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_PUBLIC_KEY_GENERATE,
+    SharedSecretCompute = ffi::rte_crypto_asym_op_type::RTE_CRYPTO_ASYM_OP_SHARED_SECRET_COMPUTE,
+}
+
+/// The padding schemes of RSA.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum RsaPadding {
+    None = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PADDING_NONE,
+    Pkcs1V15Bt0 = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PKCS1_V1_5_BT0,
+    Pkcs1V15Bt1 = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PKCS1_V1_5_BT1,
+    Pkcs1V15Bt2 = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PKCS1_V1_5_BT2,
+    Oaep = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PADDING_OAEP,
+    Pss = ffi::rte_crypto_rsa_padding_type::RTE_CRYPTO_RSA_PADDING_PSS,
+}
+
+/// The hash algorithms for the OAEP and PSS paddings of RSA.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum HashAlgorithm {
+    Md5 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_MD5,
+    Sha1 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA1,
+    Sha224 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA224,
+    Sha256 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA256,
+    Sha384 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA384,
+    Sha512 = ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA512,
+}
+
+/// The status of a crypto operation.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum OpStatus {
+    Success = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_SUCCESS,
+    NotProcessed = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_NOT_PROCESSED,
+    AuthFailed = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_AUTH_FAILED,
+    InvalidSession = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_INVALID_SESSION,
+    InvalidArgs = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_INVALID_ARGS,
+    Error = ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_ERROR,
+}
+
+/// The types of crypto operation.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum OpType {
+    /// A pool supports all operation types.
+    Undefined = ffi::rte_crypto_op_type::RTE_CRYPTO_OP_TYPE_UNDEFINED,
+    Symmetric = ffi::rte_crypto_op_type::RTE_CRYPTO_OP_TYPE_SYMMETRIC,
+    Asymmetric = ffi::rte_crypto_op_type::RTE_CRYPTO_OP_TYPE_ASYMMETRIC,
+}
+
+/// Create a crypto operation pool.
+pub fn op_pool_create<S: AsRef<str>>(
+    name: S,
+    op_type: OpType,
+    n: u32,
+    cache_size: u32,
+    priv_size: u16,
+    socket: Socket,
+) -> Result<MemoryPool> {
+    let name = name.as_cstring();
+    let socket = socket.validate()?;
+
+    let p = unsafe {
+        ffi::rte_crypto_op_pool_create(name.as_ptr(), op_type as u32, n, cache_size, priv_size, socket.raw())
+    };
+
+    p.as_result().map(|p| MemoryPool::from(p.as_ptr()))
+}
+
+/// Create a pool of asymmetric sessions, which is large enough for the private data of the devices.
+pub fn asym_session_pool_create<S: AsRef<str>>(
+    name: S,
+    devs: &[DevId],
+    n: u32,
+    cache_size: u32,
+    socket: Socket,
+) -> Result<MemoryPool> {
+    let name = name.as_cstring();
+    let socket = socket.validate()?;
+
+    let header_size = unsafe { ffi::rte_cryptodev_asym_get_header_session_size() };
+    let private_size = devs
+        .iter()
+        .map(|&dev_id| unsafe { ffi::rte_cryptodev_asym_get_private_session_size(dev_id) })
+        .max()
+        .unwrap_or_default();
+
+    let p = unsafe {
+        ffi::rte_mempool_create(
+            name.as_ptr(),
+            n,
+            header_size.max(private_size),
+            cache_size,
+            0,
+            None,
+            ptr::null_mut(),
+            None,
+            ptr::null_mut(),
+            socket.raw(),
+            0,
+        )
+    };
+
+    p.as_result().map(|p| MemoryPool::from(p.as_ptr()))
+}
+
+/// A buffer of the big number or message, which is allocated from the huge-page memory.
+pub struct CryptoBuf {
+    ptr: NonNull<u8>,
+    len: usize,
+    cap: usize,
+}
+
+unsafe impl Send for CryptoBuf {}
+
+impl Drop for CryptoBuf {
+    fn drop(&mut self) {
+        malloc::free(self.ptr.as_ptr() as *mut c_void)
+    }
+}
+
+impl CryptoBuf {
+    /// Allocate an empty buffer, which could be written by the device.
+    pub fn with_capacity(cap: usize) -> Result<Self> {
+        let ptr = malloc::zmalloc("crypto_buf", cap.max(1), 0) as *mut u8;
+
+        NonNull::new(ptr)
+            .map(|ptr| CryptoBuf { ptr, len: 0, cap })
+            .ok_or_else(|| RteError(libc::ENOMEM).into())
+    }
+
+    /// Allocate a buffer with the data.
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        let mut buf = Self::with_capacity(data.len())?;
+
+        unsafe { ptr::copy_nonoverlapping(data.as_ptr(), buf.ptr.as_ptr(), data.len()) }
+
+        buf.len = data.len();
+
+        Ok(buf)
+    }
+
+    /// The length of the data.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The buffer has no data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// The data of the buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Copy the data out of the buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
+    /// The parameter to read the data.
+    fn param(&self) -> ffi::rte_crypto_param {
+        ffi::rte_crypto_param {
+            data: self.ptr.as_ptr(),
+            iova: unsafe { ffi::rte_malloc_virt2iova(self.ptr.as_ptr() as *const c_void) },
+            length: self.len,
+        }
+    }
+
+    /// The parameter to write the whole buffer.
+    fn param_out(&mut self) -> ffi::rte_crypto_param {
+        ffi::rte_crypto_param {
+            length: self.cap,
+            ..self.param()
+        }
+    }
+
+    /// Update the length of the data written by the device.
+    fn written(&mut self, param: &ffi::rte_crypto_param) {
+        self.len = param.length.min(self.cap);
+    }
+}
+
+/// The private key of RSA.
+pub enum RsaPrivKey {
+    /// The private exponent.
+    Exp(CryptoBuf),
+    /// The quintuple of the Chinese Remainder Theorem.
+    Qt {
+        p: CryptoBuf,
+        q: CryptoBuf,
+        dp: CryptoBuf,
+        dq: CryptoBuf,
+        qinv: CryptoBuf,
+    },
+}
+
+/// The key of RSA, the private key is only required to sign or decrypt.
+pub struct RsaKey {
+    /// The modulus.
+    pub n: CryptoBuf,
+    /// The public exponent.
+    pub e: CryptoBuf,
+    /// The private key.
+    pub private: Option<RsaPrivKey>,
+}
+
+impl RsaKey {
+    /// The RSA public key.
+    pub fn public(n: &[u8], e: &[u8]) -> Result<Self> {
+        Ok(RsaKey {
+            n: CryptoBuf::from_slice(n)?,
+            e: CryptoBuf::from_slice(e)?,
+            private: None,
+        })
+    }
+
+    /// The RSA key with the private exponent.
+    pub fn with_exp(n: &[u8], e: &[u8], d: &[u8]) -> Result<Self> {
+        Ok(RsaKey {
+            private: Some(RsaPrivKey::Exp(CryptoBuf::from_slice(d)?)),
+            ..Self::public(n, e)?
+        })
+    }
+
+    /// The RSA key with the private key in the quintuple format.
+    pub fn with_qt(n: &[u8], e: &[u8], p: &[u8], q: &[u8], dp: &[u8], dq: &[u8], qinv: &[u8]) -> Result<Self> {
+        Ok(RsaKey {
+            private: Some(RsaPrivKey::Qt {
+                p: CryptoBuf::from_slice(p)?,
+                q: CryptoBuf::from_slice(q)?,
+                dp: CryptoBuf::from_slice(dp)?,
+                dq: CryptoBuf::from_slice(dq)?,
+                qinv: CryptoBuf::from_slice(qinv)?,
+            }),
+            ..Self::public(n, e)?
+        })
+    }
+}
+
+/// The domain parameters and private key of DSA.
+pub struct DsaKey {
+    /// The prime modulus.
+    pub p: CryptoBuf,
+    /// The order of the subgroup.
+    pub q: CryptoBuf,
+    /// The generator of the subgroup.
+    pub g: CryptoBuf,
+    /// The private key of the signer.
+    pub x: CryptoBuf,
+}
+
+impl DsaKey {
+    pub fn new(p: &[u8], q: &[u8], g: &[u8], x: &[u8]) -> Result<Self> {
+        Ok(DsaKey {
+            p: CryptoBuf::from_slice(p)?,
+            q: CryptoBuf::from_slice(q)?,
+            g: CryptoBuf::from_slice(g)?,
+            x: CryptoBuf::from_slice(x)?,
+        })
+    }
+}
+
+/// The group of Diffie-Hellman.
+pub struct DhGroup {
+    /// The prime modulus.
+    pub p: CryptoBuf,
+    /// The generator.
+    pub g: CryptoBuf,
+}
+
+impl DhGroup {
+    pub fn new(p: &[u8], g: &[u8]) -> Result<Self> {
+        Ok(DhGroup {
+            p: CryptoBuf::from_slice(p)?,
+            g: CryptoBuf::from_slice(g)?,
+        })
+    }
+}
+
+/// The asymmetric crypto transform of a session.
+pub enum AsymXform {
+    Rsa(RsaKey),
+    Dsa(DsaKey),
+    /// The Diffie-Hellman group, and the operation of the session,
+    /// one of the key generations or the shared secret computation.
+    Dh(DhGroup, AsymOpType),
+    /// The modulus and exponent of modular exponentiation.
+    ModEx {
+        modulus: CryptoBuf,
+        exponent: CryptoBuf,
+    },
+    /// The modulus of modular inverse.
+    ModInv {
+        modulus: CryptoBuf,
+    },
+}
+
+impl AsymXform {
+    /// The type of the transform.
+    pub fn xform_type(&self) -> AsymXformType {
+        match *self {
+            AsymXform::Rsa(_) => AsymXformType::Rsa,
+            AsymXform::Dsa(_) => AsymXformType::Dsa,
+            AsymXform::Dh(..) => AsymXformType::Dh,
+            AsymXform::ModEx { .. } => AsymXformType::ModEx,
+            AsymXform::ModInv { .. } => AsymXformType::ModInv,
+        }
+    }
+
+    /// The length of the modulus, which is the length of the outputs.
+    pub fn modlen(&self) -> usize {
+        match *self {
+            AsymXform::Rsa(ref key) => key.n.len(),
+            AsymXform::Dsa(ref key) => key.p.len(),
+            AsymXform::Dh(ref group, _) => group.p.len(),
+            AsymXform::ModEx { ref modulus, .. } | AsymXform::ModInv { ref modulus } => modulus.len(),
+        }
+    }
+
+    fn to_raw(&self) -> ffi::rte_crypto_asym_xform {
+        let mut xform = ffi::rte_crypto_asym_xform {
+            xform_type: self.xform_type() as u32,
+            ..Default::default()
+        };
+
+        match *self {
+            AsymXform::Rsa(ref key) => {
+                let rsa = unsafe { &mut xform.__bindgen_anon_1.rsa };
+
+                rsa.n = key.n.param();
+                rsa.e = key.e.param();
+
+                match key.private {
+                    Some(RsaPrivKey::Exp(ref d)) => {
+                        rsa.key_type = ffi::rte_crypto_rsa_priv_key_type::RTE_RSA_KEY_TYPE_EXP;
+                        rsa.__bindgen_anon_1.d = d.param();
+                    }
+                    Some(RsaPrivKey::Qt {
+                        ref p,
+                        ref q,
+                        ref dp,
+                        ref dq,
+                        ref qinv,
+                    }) => {
+                        rsa.key_type = ffi::rte_crypto_rsa_priv_key_type::RTE_RSA_KET_TYPE_QT;
+                        rsa.__bindgen_anon_1.qt = ffi::rte_crypto_rsa_priv_key_qt {
+                            p: p.param(),
+                            q: q.param(),
+                            dP: dp.param(),
+                            dQ: dq.param(),
+                            qInv: qinv.param(),
+                        };
+                    }
+                    None => {}
+                }
+            }
+            AsymXform::Dsa(ref key) => {
+                xform.__bindgen_anon_1.dsa = ffi::rte_crypto_dsa_xform {
+                    p: key.p.param(),
+                    q: key.q.param(),
+                    g: key.g.param(),
+                    x: key.x.param(),
+                };
+            }
+            AsymXform::Dh(ref group, op_type) => {
+                xform.__bindgen_anon_1.dh = ffi::rte_crypto_dh_xform {
+                    type_: op_type as u32,
+                    p: group.p.param(),
+                    g: group.g.param(),
+                };
+            }
+            AsymXform::ModEx {
+                ref modulus,
+                ref exponent,
+            } => {
+                xform.__bindgen_anon_1.modex = ffi::rte_crypto_modex_xform {
+                    modulus: modulus.param(),
+                    exponent: exponent.param(),
+                };
+            }
+            AsymXform::ModInv { ref modulus } => {
+                xform.__bindgen_anon_1.modinv = ffi::rte_crypto_modinv_xform {
+                    modulus: modulus.param(),
+                };
+            }
+        }
+
+        xform
+    }
+}
+
+/// The parameters of an asymmetric crypto operation, the outputs are written by the device.
+pub enum AsymOp {
+    RsaSign {
+        message: CryptoBuf,
+        sign: CryptoBuf,
+        padding: RsaPadding,
+        hash: Option<HashAlgorithm>,
+    },
+    RsaVerify {
+        message: CryptoBuf,
+        sign: CryptoBuf,
+        padding: RsaPadding,
+        hash: Option<HashAlgorithm>,
+    },
+    DsaSign {
+        message: CryptoBuf,
+        /// The per-message secret number, or empty to let the device generate it.
+        k: CryptoBuf,
+        r: CryptoBuf,
+        s: CryptoBuf,
+    },
+    DsaVerify {
+        message: CryptoBuf,
+        r: CryptoBuf,
+        s: CryptoBuf,
+        /// The public key of the signer.
+        y: CryptoBuf,
+    },
+    DhPrivateKey {
+        priv_key: CryptoBuf,
+    },
+    DhPublicKey {
+        priv_key: CryptoBuf,
+        pub_key: CryptoBuf,
+    },
+    DhSharedSecret {
+        priv_key: CryptoBuf,
+        peer_key: CryptoBuf,
+        shared_secret: CryptoBuf,
+    },
+    ModEx {
+        base: CryptoBuf,
+        result: CryptoBuf,
+    },
+    ModInv {
+        base: CryptoBuf,
+        result: CryptoBuf,
+    },
+}
+
+impl AsymOp {
+    /// Fill the raw operation, which refers to the buffers until it is completed.
+    fn fill(&mut self, op: &mut ffi::rte_crypto_asym_op) {
+        let params = &mut op.__bindgen_anon_1;
+
+        match *self {
+            AsymOp::RsaSign {
+                ref message,
+                ref mut sign,
+                padding,
+                hash,
+            } => {
+                params.rsa = ffi::rte_crypto_rsa_op_param {
+                    op_type: AsymOpType::Sign as u32,
+                    message: message.param(),
+                    sign: sign.param_out(),
+                    pad: padding as u32,
+                    md: hash.map_or(0, |h| h as u32),
+                    mgf1md: hash.map_or(0, |h| h as u32),
+                }
+            }
+            AsymOp::RsaVerify {
+                ref message,
+                ref sign,
+                padding,
+                hash,
+            } => {
+                params.rsa = ffi::rte_crypto_rsa_op_param {
+                    op_type: AsymOpType::Verify as u32,
+                    message: message.param(),
+                    sign: sign.param(),
+                    pad: padding as u32,
+                    md: hash.map_or(0, |h| h as u32),
+                    mgf1md: hash.map_or(0, |h| h as u32),
+                }
+            }
+            AsymOp::DsaSign {
+                ref message,
+                ref k,
+                ref mut r,
+                ref mut s,
+            } => {
+                params.dsa = ffi::rte_crypto_dsa_op_param {
+                    op_type: AsymOpType::Sign as u32,
+                    message: message.param(),
+                    k: k.param(),
+                    r: r.param_out(),
+                    s: s.param_out(),
+                    y: Default::default(),
+                }
+            }
+            AsymOp::DsaVerify {
+                ref message,
+                ref r,
+                ref s,
+                ref y,
+            } => {
+                params.dsa = ffi::rte_crypto_dsa_op_param {
+                    op_type: AsymOpType::Verify as u32,
+                    message: message.param(),
+                    k: Default::default(),
+                    r: r.param(),
+                    s: s.param(),
+                    y: y.param(),
+                }
+            }
+            AsymOp::DhPrivateKey { ref mut priv_key } => {
+                params.dh = ffi::rte_crypto_dh_op_param {
+                    priv_key: priv_key.param_out(),
+                    ..Default::default()
+                }
+            }
+            AsymOp::DhPublicKey {
+                ref priv_key,
+                ref mut pub_key,
+            } => {
+                params.dh = ffi::rte_crypto_dh_op_param {
+                    pub_key: pub_key.param_out(),
+                    priv_key: priv_key.param(),
+                    ..Default::default()
+                }
+            }
+            AsymOp::DhSharedSecret {
+                ref priv_key,
+                ref peer_key,
+                ref mut shared_secret,
+            } => {
+                params.dh = ffi::rte_crypto_dh_op_param {
+                    pub_key: peer_key.param(),
+                    priv_key: priv_key.param(),
+                    shared_secret: shared_secret.param_out(),
+                }
+            }
+            AsymOp::ModEx {
+                ref base,
+                ref mut result,
+            } => {
+                params.modex = ffi::rte_crypto_mod_op_param {
+                    base: base.param(),
+                    result: result.param_out(),
+                }
+            }
+            AsymOp::ModInv {
+                ref base,
+                ref mut result,
+            } => {
+                params.modinv = ffi::rte_crypto_mod_op_param {
+                    base: base.param(),
+                    result: result.param_out(),
+                }
+            }
+        }
+    }
+
+    /// Update the outputs with the lengths written by the device.
+    fn complete(&mut self, op: &ffi::rte_crypto_asym_op) {
+        let params = &op.__bindgen_anon_1;
+
+        unsafe {
+            match *self {
+                AsymOp::RsaSign { ref mut sign, .. } => sign.written(&params.rsa.sign),
+                AsymOp::DsaSign {
+                    ref mut r, ref mut s, ..
+                } => {
+                    r.written(&params.dsa.r);
+                    s.written(&params.dsa.s);
+                }
+                AsymOp::DhPrivateKey { ref mut priv_key } => priv_key.written(&params.dh.priv_key),
+                AsymOp::DhPublicKey { ref mut pub_key, .. } => pub_key.written(&params.dh.pub_key),
+                AsymOp::DhSharedSecret {
+                    ref mut shared_secret, ..
+                } => shared_secret.written(&params.dh.shared_secret),
+                AsymOp::ModEx { ref mut result, .. } => result.written(&params.modex.result),
+                AsymOp::ModInv { ref mut result, .. } => result.written(&params.modinv.result),
+                AsymOp::RsaVerify { .. } | AsymOp::DsaVerify { .. } => {}
+            }
+        }
+    }
+}
+
+pub type RawCryptoOp = ffi::rte_crypto_op;
+
+/// A crypto operation, which is returned to its mempool when dropped.
+raw!(pub CryptoOp(RawCryptoOp));
+
+unsafe impl Send for CryptoOp {}
+
+impl Drop for CryptoOp {
+    fn drop(&mut self) {
+        unsafe { ffi::_rte_crypto_op_free(self.as_raw()) }
+    }
+}
+
+impl CryptoOp {
+    /// Allocate a crypto operation from the pool.
+    pub fn alloc<P: MemPool>(pool: &P, op_type: OpType) -> Result<Self> {
+        unsafe { ffi::_rte_crypto_op_alloc(pool.as_raw(), op_type as u32) }
+            .as_result()
+            .map(|p| CryptoOp::from(p.as_ptr()))
+    }
+
+    /// The status of the operation.
+    pub fn status(&self) -> OpStatus {
+        OpStatus::from_u8(self.status).unwrap_or(OpStatus::Error)
+    }
+
+    /// The operation was processed successfully.
+    pub fn is_success(&self) -> bool {
+        self.status() == OpStatus::Success
+    }
+
+    /// The asymmetric operation.
+    pub fn asym(&self) -> &ffi::rte_crypto_asym_op {
+        unsafe { &*ffi::_rte_crypto_op_asym(self.as_raw()) }
+    }
+
+    /// The mutable asymmetric operation.
+    pub fn asym_mut(&mut self) -> &mut ffi::rte_crypto_asym_op {
+        unsafe { &mut *ffi::_rte_crypto_op_asym(self.as_raw()) }
+    }
+
+    /// Attach an asymmetric session to the operation.
+    pub fn attach_asym_session(&mut self, sess: &AsymSession) -> Result<&mut Self> {
+        rte_check!(unsafe {
+            ffi::_rte_crypto_op_attach_asym_session(self.as_raw(), sess.sess.as_ptr())
+        }; ok => { self })
+    }
+
+    /// Fill the asymmetric operation with the parameters.
+    ///
+    /// # Safety
+    ///
+    /// The buffers of the parameters are referred by the device,
+    /// so they must outlive the operation until it is dequeued.
+    pub unsafe fn set_asym_params(&mut self, params: &mut AsymOp) {
+        params.fill(self.asym_mut())
+    }
+
+    /// Update the outputs of the parameters with the dequeued operation.
+    pub fn get_asym_results(&self, params: &mut AsymOp) {
+        params.complete(self.asym())
+    }
+}
+
+/// The timeout of a synchronous asymmetric operation.
+const ASYM_OP_TIMEOUT_SECS: u64 = 5;
+
+/// An asymmetric crypto session initialized on a device with the transform.
+pub struct AsymSession {
+    dev_id: DevId,
+    sess: NonNull<ffi::rte_cryptodev_asym_session>,
+    xform: AsymXform,
+}
+
+unsafe impl Send for AsymSession {}
+
+impl Drop for AsymSession {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rte_cryptodev_asym_session_clear(self.dev_id, self.sess.as_ptr());
+            ffi::rte_cryptodev_asym_session_free(self.sess.as_ptr());
+        }
+    }
+}
+
+impl AsymSession {
+    /// Create an asymmetric session from the pool, and initialize it on the device.
+    pub fn new<P: MemPool>(dev_id: DevId, pool: &P, xform: AsymXform) -> Result<Self> {
+        let caps = dev_id
+            .asym_capability(xform.xform_type())
+            .ok_or_else(|| ErrorKind::CryptoError(format!("{:?} is not supported", xform.xform_type())))?;
+
+        if xform.modlen() > u16::MAX as usize || !caps.supports_modlen(xform.modlen() as u16) {
+            return Err(ErrorKind::CryptoError(format!("modulus length {} is not supported", xform.modlen())).into());
+        }
+
+        let sess = unsafe { ffi::rte_cryptodev_asym_session_create(pool.as_raw()) }.as_result()?;
+        let mut raw = xform.to_raw();
+
+        let ret = unsafe { ffi::rte_cryptodev_asym_session_init(dev_id, sess.as_ptr(), &mut raw, pool.as_raw()) };
+
+        if ret < 0 {
+            unsafe { ffi::rte_cryptodev_asym_session_free(sess.as_ptr()) };
+
+            return Err(RteError(-ret).into());
+        }
+
+        Ok(AsymSession { dev_id, sess, xform })
+    }
+
+    /// The device of the session.
+    pub fn dev_id(&self) -> DevId {
+        self.dev_id
+    }
+
+    /// The transform of the session.
+    pub fn xform(&self) -> &AsymXform {
+        &self.xform
+    }
+
+    /// Process the operation on the queue pair, and wait for its completion.
+    ///
+    /// The queue pair must be dedicated to the synchronous operations, which are dequeued one by one.
+    pub fn process<P: MemPool>(&self, qp_id: QueuePairId, pool: &P, mut params: AsymOp) -> Result<(OpStatus, AsymOp)> {
+        let mut op = CryptoOp::alloc(pool, OpType::Asymmetric)?;
+
+        op.attach_asym_session(self)?;
+
+        unsafe { op.set_asym_params(&mut params) };
+
+        let raw = op.as_raw();
+        let mut ops = vec![op];
+        let deadline = rdtsc() + get_tsc_hz() * ASYM_OP_TIMEOUT_SECS;
+
+        while self.dev_id.enqueue_burst(qp_id, &mut ops) == 0 {
+            if rdtsc() > deadline {
+                return Err(RteError(libc::EBUSY).into());
+            }
+
+            pause();
+        }
+
+        let mut done: [Option<CryptoOp>; 1] = [None];
+
+        while self.dev_id.dequeue_burst(qp_id, &mut done) == 0 {
+            if rdtsc() > deadline {
+                // the device still refers to the buffers
+                mem::forget(params);
+
+                return Err(RteError(libc::ETIMEDOUT).into());
+            }
+
+            pause();
+        }
+
+        let op = done[0].take().unwrap();
+
+        if op.as_raw() != raw {
+            return Err(ErrorKind::CryptoError("unexpected operation dequeued".to_owned()).into());
+        }
+
+        op.get_asym_results(&mut params);
+
+        Ok((op.status(), params))
+    }
+
+    /// Sign the message with the RSA private key.
+    pub fn rsa_sign<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        pool: &P,
+        message: &[u8],
+        padding: RsaPadding,
+        hash: Option<HashAlgorithm>,
+    ) -> Result<Vec<u8>> {
+        let params = AsymOp::RsaSign {
+            message: CryptoBuf::from_slice(message)?,
+            sign: CryptoBuf::with_capacity(self.xform.modlen())?,
+            padding,
+            hash,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::RsaSign { sign, .. } => Ok(sign.to_vec()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Verify the RSA signature of the message.
+    pub fn rsa_verify<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        pool: &P,
+        message: &[u8],
+        sign: &[u8],
+        padding: RsaPadding,
+        hash: Option<HashAlgorithm>,
+    ) -> Result<bool> {
+        let params = AsymOp::RsaVerify {
+            message: CryptoBuf::from_slice(message)?,
+            sign: CryptoBuf::from_slice(sign)?,
+            padding,
+            hash,
+        };
+
+        let (status, _) = self.process(qp_id, pool, params)?;
+
+        self.verified(status)
+    }
+
+    /// Sign the message with the DSA private key, returns the `r` and `s` components.
+    pub fn dsa_sign<P: MemPool>(&self, qp_id: QueuePairId, pool: &P, message: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let modlen = self.xform.modlen();
+        let params = AsymOp::DsaSign {
+            message: CryptoBuf::from_slice(message)?,
+            k: CryptoBuf::with_capacity(0)?,
+            r: CryptoBuf::with_capacity(modlen)?,
+            s: CryptoBuf::with_capacity(modlen)?,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::DsaSign { r, s, .. } => Ok((r.to_vec(), s.to_vec())),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Verify the DSA signature of the message with the public key of the signer.
+    pub fn dsa_verify<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        pool: &P,
+        message: &[u8],
+        r: &[u8],
+        s: &[u8],
+        y: &[u8],
+    ) -> Result<bool> {
+        let params = AsymOp::DsaVerify {
+            message: CryptoBuf::from_slice(message)?,
+            r: CryptoBuf::from_slice(r)?,
+            s: CryptoBuf::from_slice(s)?,
+            y: CryptoBuf::from_slice(y)?,
+        };
+
+        let (status, _) = self.process(qp_id, pool, params)?;
+
+        self.verified(status)
+    }
+
+    /// Generate the Diffie-Hellman private key, the session must be set up for `PrivateKeyGenerate`.
+    pub fn dh_private_key<P: MemPool>(&self, qp_id: QueuePairId, pool: &P) -> Result<Vec<u8>> {
+        let params = AsymOp::DhPrivateKey {
+            priv_key: CryptoBuf::with_capacity(self.xform.modlen())?,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::DhPrivateKey { priv_key } => Ok(priv_key.to_vec()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Generate the Diffie-Hellman public key, the session must be set up for `PublicKeyGenerate`.
+    pub fn dh_public_key<P: MemPool>(&self, qp_id: QueuePairId, pool: &P, priv_key: &[u8]) -> Result<Vec<u8>> {
+        let params = AsymOp::DhPublicKey {
+            priv_key: CryptoBuf::from_slice(priv_key)?,
+            pub_key: CryptoBuf::with_capacity(self.xform.modlen())?,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::DhPublicKey { pub_key, .. } => Ok(pub_key.to_vec()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Compute the Diffie-Hellman shared secret with the peer public key,
+    /// the session must be set up for `SharedSecretCompute`.
+    pub fn dh_shared_secret<P: MemPool>(
+        &self,
+        qp_id: QueuePairId,
+        pool: &P,
+        priv_key: &[u8],
+        peer_key: &[u8],
+    ) -> Result<Vec<u8>> {
+        let params = AsymOp::DhSharedSecret {
+            priv_key: CryptoBuf::from_slice(priv_key)?,
+            peer_key: CryptoBuf::from_slice(peer_key)?,
+            shared_secret: CryptoBuf::with_capacity(self.xform.modlen())?,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::DhSharedSecret { shared_secret, .. } => Ok(shared_secret.to_vec()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Compute the modular exponentiation `base^e mod n`.
+    pub fn modex<P: MemPool>(&self, qp_id: QueuePairId, pool: &P, base: &[u8]) -> Result<Vec<u8>> {
+        let params = AsymOp::ModEx {
+            base: CryptoBuf::from_slice(base)?,
+            result: CryptoBuf::with_capacity(self.xform.modlen())?,
+        };
+
+        let (status, params) = self.process(qp_id, pool, params)?;
+
+        self.expect_success(status)?;
+
+        match params {
+            AsymOp::ModEx { result, .. } => Ok(result.to_vec()),
+            _ => unreachable!(),
+        }
+    }
+
+    fn expect_success(&self, status: OpStatus) -> Result<()> {
+        if status == OpStatus::Success {
+            Ok(())
+        } else {
+            Err(ErrorKind::CryptoError(format!("{:?} operation failed, {:?}", self.xform.xform_type(), status)).into())
+        }
+    }
+
+    fn verified(&self, status: OpStatus) -> Result<bool> {
+        match status {
+            OpStatus::Success => Ok(true),
+            // the PMDs report a mismatched signature as an error
+            OpStatus::AuthFailed | OpStatus::Error => Ok(false),
+            _ => self.expect_success(status).map(|_| false),
+        }
+    }
+}
+
+/// The software crypto PMDs, which are the fallbacks when no hardware device satisfies the requirements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoftwarePmd {
+    /// The Intel multi-buffer PMD, which only supports the symmetric crypto.
+    AesniMb,
+    /// The OpenSSL PMD, which supports both the symmetric and asymmetric crypto.
+    Openssl,
+}
+
+impl SoftwarePmd {
+    /// The default fallbacks, in order of preference.
+    pub const DEFAULT: &'static [SoftwarePmd] = &[SoftwarePmd::AesniMb, SoftwarePmd::Openssl];
+
+    /// The driver name of the PMD, which prefixes the device names.
+    pub fn driver_name(self) -> &'static str {
+        match self {
+            SoftwarePmd::AesniMb => "crypto_aesni_mb",
+            SoftwarePmd::Openssl => "crypto_openssl",
+        }
+    }
+
+    /// The PMD supports the asymmetric crypto.
+    pub fn is_asym(self) -> bool {
+        self == SoftwarePmd::Openssl
+    }
+}
+
+/// The requirements of the sessions on a crypto device.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CryptoReqs {
+    /// The transforms and their operations.
+    pub xforms: Vec<(AsymXformType, Vec<AsymOpType>)>,
+    /// The modulus length of the transforms.
+    pub modlen: Option<u16>,
+}
+
+impl CryptoReqs {
+    pub fn with_xform(mut self, xform_type: AsymXformType, ops: &[AsymOpType]) -> Self {
+        self.xforms.push((xform_type, ops.to_vec()));
+        self
+    }
+
+    pub fn with_modlen(mut self, modlen: u16) -> Self {
+        self.modlen = Some(modlen);
+        self
+    }
+
+    /// The requirements are satisfied by the capabilities of the device.
+    pub fn is_satisfied_by(&self, dev_id: DevId) -> bool {
+        self.xforms.iter().all(|&(xform_type, ref ops)| {
+            dev_id.asym_capability(xform_type).is_some_and(|caps| {
+                ops.iter().all(|&op| caps.supports(op)) && self.modlen.is_none_or(|len| caps.supports_modlen(len))
+            })
+        })
+    }
+
+    /// The requirements could be satisfied by the software PMD.
+    fn is_satisfiable_by(&self, pmd: SoftwarePmd) -> bool {
+        self.xforms.is_empty() || pmd.is_asym()
+    }
+}
+
+/// Select a crypto device which satisfies the requirements.
+///
+/// The hardware devices are preferred, then the existing software devices,
+/// otherwise a device of the first fallback PMD, which could satisfy the requirements, is created.
+pub fn select(reqs: &CryptoReqs, fallbacks: &[SoftwarePmd]) -> Result<DevId> {
+    if !features().cryptodev() {
+        return Err(RteError(libc::ENOTSUP).into());
+    }
+
+    let found = devices()
+        .filter(|&dev_id| reqs.is_satisfied_by(dev_id))
+        .min_by_key(|dev_id| {
+            !dev_id
+                .features()
+                .contains(CryptoDevFeatures::RTE_CRYPTODEV_FF_HW_ACCELERATED)
+        });
+
+    if let Some(dev_id) = found {
+        return Ok(dev_id);
+    }
+
+    for &pmd in fallbacks.iter().filter(|&&pmd| reqs.is_satisfiable_by(pmd)) {
+        let name = format!("{}{}", pmd.driver_name(), count());
+
+        match dev::hotplug_add("vdev", &name, "").and_then(|_| get_dev_id(&name)) {
+            Ok(dev_id) if reqs.is_satisfied_by(dev_id) => return Ok(dev_id),
+            Ok(_) => {
+                // leave the unused device to the other requirements
+                debug!("crypto device `{}` doesn't satisfy the requirements", name);
+            }
+            Err(err) => debug!("fail to create crypto device `{}`, {}", name, err),
+        }
+    }
+
+    Err(ErrorKind::CryptoError(format!("no crypto device satisfies {:?}", reqs)).into())
+}
+
+/// The configuration of a crypto pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CryptoPoolConf {
+    /// The number of the sessions.
+    pub nb_sessions: u32,
+    /// The number of the operations.
+    pub nb_ops: u32,
+    /// The number of the descriptors of the queue pair.
+    pub nb_descriptors: u32,
+}
+
+impl Default for CryptoPoolConf {
+    fn default() -> Self {
+        CryptoPoolConf {
+            nb_sessions: 1024,
+            nb_ops: 1024,
+            nb_descriptors: 2048,
+        }
+    }
+}
+
+impl CryptoPoolConf {
+    pub fn with_sessions(mut self, n: u32) -> Self {
+        self.nb_sessions = n;
+        self
+    }
+
+    pub fn with_ops(mut self, n: u32) -> Self {
+        self.nb_ops = n;
+        self
+    }
+
+    pub fn with_descriptors(mut self, n: u32) -> Self {
+        self.nb_descriptors = n;
+        self
+    }
+}
+
+/// A pool of the sessions on a crypto device selected by the requirements,
+/// so the applications don't code to a specific PMD.
+///
+/// The pool owns the device, which is configured with a queue pair for the synchronous operations,
+/// and stopped when the pool is dropped.
+///
+/// ```no_run
+/// use rte::cryptodev::{AsymOpType, AsymXform, AsymXformType, CryptoPool, CryptoReqs, RsaKey, RsaPadding};
+///
+/// let reqs = CryptoReqs::default()
+///     .with_xform(AsymXformType::Rsa, &[AsymOpType::Sign, AsymOpType::Verify])
+///     .with_modlen(256);
+/// let pool = CryptoPool::new("rsa", &reqs, &Default::default()).unwrap();
+/// # let (n, e, d) = (&[0u8; 256][..], &[1u8, 0, 1][..], &[0u8; 256][..]);
+/// let sess = pool.session(AsymXform::Rsa(RsaKey::with_exp(n, e, d).unwrap())).unwrap();
+///
+/// let sign = sess.rsa_sign(b"hello", RsaPadding::Pkcs1V15Bt1, None).unwrap();
+/// ```
+pub struct CryptoPool {
+    dev_id: DevId,
+    sess_pool: MemoryPool,
+    op_pool: MemoryPool,
+}
+
+impl Drop for CryptoPool {
+    fn drop(&mut self) {
+        self.dev_id.stop();
+    }
+}
+
+impl CryptoPool {
+    /// The queue pair of the synchronous operations.
+    const QUEUE_PAIR: QueuePairId = 0;
+
+    /// Select a device with the default fallbacks, and create the pools of the sessions and operations.
+    pub fn new(name: &str, reqs: &CryptoReqs, conf: &CryptoPoolConf) -> Result<Self> {
+        Self::with_fallbacks(name, reqs, conf, SoftwarePmd::DEFAULT)
+    }
+
+    /// Select a device with the fallbacks, and create the pools of the sessions and operations.
+    pub fn with_fallbacks(
+        name: &str,
+        reqs: &CryptoReqs,
+        conf: &CryptoPoolConf,
+        fallbacks: &[SoftwarePmd],
+    ) -> Result<Self> {
+        let dev_id = select(reqs, fallbacks)?;
+        let socket = dev_id.socket_id();
+
+        let sess_pool = asym_session_pool_create(format!("{}_sess", name), &[dev_id], conf.nb_sessions, 0, socket)?;
+        let op_pool = op_pool_create(format!("{}_op", name), OpType::Asymmetric, conf.nb_ops, 0, 0, socket)?;
+
+        dev_id
+            .configure(1, socket)?
+            .queue_pair_setup(Self::QUEUE_PAIR, conf.nb_descriptors, socket, Some(&sess_pool))?
+            .start()?;
+
+        Ok(CryptoPool {
+            dev_id,
+            sess_pool,
+            op_pool,
+        })
+    }
+
+    /// The selected device.
+    pub fn dev_id(&self) -> DevId {
+        self.dev_id
+    }
+
+    /// The selected device is a hardware accelerator.
+    pub fn is_hw_accelerated(&self) -> bool {
+        self.dev_id
+            .features()
+            .contains(CryptoDevFeatures::RTE_CRYPTODEV_FF_HW_ACCELERATED)
+    }
+
+    /// Create a session with the transform on the device.
+    pub fn session(&self, xform: AsymXform) -> Result<CryptoSession<'_>> {
+        AsymSession::new(self.dev_id, &self.sess_pool, xform).map(|sess| CryptoSession { pool: self, sess })
+    }
+}
+
+/// A session of a crypto pool, which processes the operations on the queue pair of the pool.
+pub struct CryptoSession<'a> {
+    pool: &'a CryptoPool,
+    sess: AsymSession,
+}
+
+impl<'a> Deref for CryptoSession<'a> {
+    type Target = AsymSession;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sess
+    }
+}
+
+impl<'a> CryptoSession<'a> {
+    /// Process the operation, and wait for its completion.
+    pub fn process(&self, params: AsymOp) -> Result<(OpStatus, AsymOp)> {
+        self.sess.process(CryptoPool::QUEUE_PAIR, &self.pool.op_pool, params)
+    }
+
+    /// Sign the message with the RSA private key.
+    pub fn rsa_sign(&self, message: &[u8], padding: RsaPadding, hash: Option<HashAlgorithm>) -> Result<Vec<u8>> {
+        self.sess
+            .rsa_sign(CryptoPool::QUEUE_PAIR, &self.pool.op_pool, message, padding, hash)
+    }
+
+    /// Verify the RSA signature of the message.
+    pub fn rsa_verify(
+        &self,
+        message: &[u8],
+        sign: &[u8],
+        padding: RsaPadding,
+        hash: Option<HashAlgorithm>,
+    ) -> Result<bool> {
+        self.sess
+            .rsa_verify(CryptoPool::QUEUE_PAIR, &self.pool.op_pool, message, sign, padding, hash)
+    }
+
+    /// Sign the message with the DSA private key, returns the `r` and `s` components.
+    pub fn dsa_sign(&self, message: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.sess.dsa_sign(CryptoPool::QUEUE_PAIR, &self.pool.op_pool, message)
+    }
+
+    /// Compute the modular exponentiation `base^e mod n`.
+    pub fn modex(&self, base: &[u8]) -> Result<Vec<u8>> {
+        self.sess.modex(CryptoPool::QUEUE_PAIR, &self.pool.op_pool, base)
+    }
+}
@@ -0,0 +1,264 @@
+//!
+//! Control messages between the control thread and the datapath lcores.
+//!
+//! The control messages, e.g. "add rule" or "drain queue", are serialized with `bincode`
+//! into the fixed-size slots allocated from a mempool, and the slots are passed to the datapath
+//! through a request ring. The datapath acknowledges each request with a response,
+//! which is passed back through an acknowledgment ring in the same way.
+//!
+//! The channel has a single `Sender` and a single `Receiver`, which can be moved to other threads.
+//!
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bincode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use ffi;
+use libc;
+
+use errors::{Result, RteError};
+use memory::Socket;
+use mempool::{self, MemoryPool, MemoryPoolFlags};
+use ring::{self, Ring, RingFlags};
+use utils::AsRaw;
+
+/// The size of the slot which holds a serialized control message.
+pub const CTRLMSG_SLOT_SIZE: usize = 256;
+
+const CTRLMSG_HDR_LEN: usize = 16;
+
+/// The maximum length of a serialized control message.
+pub const CTRLMSG_MAX_LEN: usize = CTRLMSG_SLOT_SIZE - CTRLMSG_HDR_LEN;
+
+#[repr(C)]
+struct Slot {
+    seq: u64,
+    len: u32,
+    _pad: u32,
+    data: [u8; CTRLMSG_MAX_LEN],
+}
+
+struct Shared {
+    requests: Ring,
+    acks: Ring,
+    slots: MemoryPool,
+}
+
+// the rings and mempool are thread safe, and each ring has a single producer and a single consumer.
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rte_ring_free(self.requests.as_raw());
+            ffi::rte_ring_free(self.acks.as_raw());
+        }
+    }
+}
+
+impl Shared {
+    fn encode<T: Serialize>(&self, ring: &Ring, seq: u64, msg: &T) -> Result<()> {
+        let len = bincode::serialized_size(msg)? as usize;
+
+        if len > CTRLMSG_MAX_LEN {
+            return Err(RteError(libc::EMSGSIZE).into());
+        }
+
+        let mut obj = ptr::null_mut();
+
+        if unsafe { ffi::_rte_mempool_get(self.slots.as_raw(), &mut obj) } != 0 {
+            return Err(RteError(libc::ENOBUFS).into());
+        }
+
+        let slot = unsafe { &mut *(obj as *mut Slot) };
+
+        slot.seq = seq;
+        slot.len = len as u32;
+
+        let res = bincode::serialize_into(&mut slot.data[..len], msg)
+            .map_err(|err| err.into())
+            .and_then(|_| ring.enqueue(slot as *mut Slot));
+
+        if res.is_err() {
+            unsafe { ffi::_rte_mempool_put(self.slots.as_raw(), obj) }
+        }
+
+        res
+    }
+
+    fn decode<T: DeserializeOwned>(&self, ring: &Ring) -> Result<Option<(u64, T)>> {
+        if let Some(obj) = ring.dequeue::<Slot>() {
+            let slot = unsafe { &*obj };
+            let res = bincode::deserialize(&slot.data[..slot.len as usize]);
+            let seq = slot.seq;
+
+            unsafe { ffi::_rte_mempool_put(self.slots.as_raw(), obj as *mut _) }
+
+            Ok(Some((seq, res?)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Create a control message channel named name,
+/// which holds up to `count` pending requests and `count` pending acknowledgments.
+pub fn channel<S, Req, Resp>(name: S, count: usize, socket: Socket) -> Result<(Sender<Req, Resp>, Receiver<Req, Resp>)>
+where
+    S: AsRef<str>,
+    Req: Serialize + DeserializeOwned,
+    Resp: Serialize + DeserializeOwned,
+{
+    let name = name.as_ref();
+    let flags = RingFlags::SP_ENQ | RingFlags::SC_DEQ | RingFlags::EXACT_SZ;
+
+    let slots = mempool::create::<_, (), (), Slot>(
+        format!("{}_slots", name),
+        count as u32 * 2,
+        0,
+        0,
+        None,
+        None,
+        None,
+        None,
+        socket,
+        MemoryPoolFlags::empty(),
+    )?;
+    let requests = ring::create(format!("{}_req", name), count, socket, flags)?;
+    let acks = match ring::create(format!("{}_ack", name), count, socket, flags) {
+        Ok(acks) => acks,
+        Err(err) => {
+            requests.free();
+
+            return Err(err);
+        }
+    };
+
+    let shared = Arc::new(Shared { requests, acks, slots });
+
+    Ok((
+        Sender {
+            shared: shared.clone(),
+            next_seq: 0,
+            phantom: PhantomData,
+        },
+        Receiver {
+            shared,
+            phantom: PhantomData,
+        },
+    ))
+}
+
+/// The sending half of a control message channel, which is used by the control thread.
+pub struct Sender<Req, Resp> {
+    shared: Arc<Shared>,
+    next_seq: u64,
+    phantom: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp> Sender<Req, Resp>
+where
+    Req: Serialize,
+    Resp: DeserializeOwned,
+{
+    /// Send a request without waiting for its acknowledgment, returns the sequence number of request.
+    ///
+    /// Returns `ENOBUFS` error if there are too many pending requests,
+    /// or `EMSGSIZE` error if the serialized request is larger than `CTRLMSG_MAX_LEN`.
+    pub fn send(&mut self, req: &Req) -> Result<u64> {
+        let seq = self.next_seq;
+
+        self.shared.encode(&self.shared.requests, seq, req)?;
+        self.next_seq += 1;
+
+        Ok(seq)
+    }
+
+    /// Receive an acknowledgment with the sequence number of request, returns `None` if no acknowledgment is pending.
+    pub fn try_recv_ack(&mut self) -> Result<Option<(u64, Resp)>> {
+        self.shared.decode(&self.shared.acks)
+    }
+
+    /// Send a request and wait for its acknowledgment until timeout.
+    ///
+    /// The stale acknowledgments of the previous requests are dropped,
+    /// returns `ETIMEDOUT` error if the request is not acknowledged in time.
+    pub fn call(&mut self, req: &Req, timeout: Duration) -> Result<Resp> {
+        let deadline = Instant::now() + timeout;
+        let seq = self.send(req)?;
+
+        loop {
+            match self.try_recv_ack()? {
+                Some((ack, resp)) if ack == seq => return Ok(resp),
+                Some((ack, _)) => debug!("drop stale acknowledgment #{}, waiting for #{}", ack, seq),
+                None if Instant::now() >= deadline => return Err(RteError(libc::ETIMEDOUT).into()),
+                None => thread::yield_now(),
+            }
+        }
+    }
+
+    /// The number of requests which haven't been received by the datapath.
+    pub fn pending(&self) -> usize {
+        self.shared.requests.count()
+    }
+}
+
+/// A request received from a control message channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request<T> {
+    /// The sequence number of request, which should be acknowledged.
+    pub seq: u64,
+    /// The request message.
+    pub msg: T,
+}
+
+/// The receiving half of a control message channel, which is used by the datapath lcore.
+pub struct Receiver<Req, Resp> {
+    shared: Arc<Shared>,
+    phantom: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp> Receiver<Req, Resp>
+where
+    Req: DeserializeOwned,
+    Resp: Serialize,
+{
+    /// Receive a request, returns `None` if no request is pending.
+    pub fn try_recv(&mut self) -> Result<Option<Request<Req>>> {
+        self.shared
+            .decode(&self.shared.requests)
+            .map(|req| req.map(|(seq, msg)| Request { seq, msg }))
+    }
+
+    /// Acknowledge a request with the response.
+    pub fn ack(&mut self, seq: u64, resp: &Resp) -> Result<()> {
+        self.shared.encode(&self.shared.acks, seq, resp)
+    }
+
+    /// Handle up to `max` pending requests, and acknowledge each of them with the response of handler.
+    ///
+    /// Returns the number of handled requests, it is cheap enough to be called in the datapath loop.
+    pub fn poll<F>(&mut self, max: usize, mut handler: F) -> Result<usize>
+    where
+        F: FnMut(Req) -> Resp,
+    {
+        for n in 0..max {
+            match self.try_recv()? {
+                Some(Request { seq, msg }) => {
+                    let resp = handler(msg);
+
+                    self.ack(seq, &resp)?;
+                }
+                None => return Ok(n),
+            }
+        }
+
+        Ok(max)
+    }
+}
@@ -0,0 +1,344 @@
+//!
+//! Dump the diagnostic state of DPDK on the fatal conditions.
+//!
+//! `dump_all` writes a bundle of the state to a directory, one file per section:
+//!
+//! - `version.txt`, the version and the optional libraries of DPDK.
+//! - `lcores.txt`, the sockets, roles and launch states of the enabled lcores.
+//! - `ports.txt`, the link, configuration, statistics and non-zero extended statistics of the ports.
+//! - `mempools.txt`, `rings.txt` and `malloc.txt`, the usage of the mempools, rings and heaps.
+//! - `trace.txt` and `log.txt`, the tracepoints and log types, the trace buffers are saved to the trace directory.
+//! - the sections registered by the application, e.g. the installed flow rules of a `FlowTable`.
+//!
+//! The sections are written independently, a failed section is recorded in `errors.txt`
+//! and the others are still written, since the state may be partially broken on a fatal condition.
+//!
+//! The bundle could be dumped automatically to a new directory under a base directory,
+//! when a thread panics, the keepalive detects a dead lcore, or the heartbeat monitor detects a stalled lcore.
+//!
+//! ```no_run
+//! use std::sync::{Arc, Mutex};
+//! use std::time::Duration;
+//!
+//! use rte::heartbeat::{HeartbeatBus, Monitor};
+//! use rte::flow::FlowTable;
+//! use rte::*;
+//!
+//! let table = Arc::new(Mutex::new(FlowTable::new(0)));
+//!
+//! diag::register_flow_table("flows-port0.json", table.clone());
+//! diag::install_panic_hook("/var/log/myapp");
+//!
+//! let keepalive = keepalive::create(diag::keepalive_failure, Some("/var/log/myapp".into())).unwrap();
+//! let monitor = Monitor::new(
+//!     Arc::new(HeartbeatBus::new()),
+//!     Duration::from_millis(100),
+//!     diag::stall_callback("/var/log/myapp"),
+//! );
+//! ```
+//!
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::heartbeat::{Stall, StallCallback};
+use common::{features, lcore, malloc, runtime_version, trace, version};
+use errors::Result;
+use ethdev::{self, EthDevice, PortId, XStatsIds};
+use flow::FlowTable;
+use mempool;
+use ring;
+
+/// A section written by the application, which is dumped to a file of the bundle.
+pub type Section = Box<dyn Fn(&mut dyn Write) -> Result<()> + Send + Sync>;
+
+lazy_static! {
+    static ref SECTIONS: Mutex<BTreeMap<String, Arc<Section>>> = Mutex::new(BTreeMap::new());
+}
+
+/// Only one bundle is dumped automatically, the fatal conditions tend to cascade.
+static DUMPED: AtomicBool = AtomicBool::new(false);
+
+/// Register a section of the bundle, which is written to the file name, replacing a section with the same name.
+pub fn register<S, F>(name: S, f: F)
+where
+    S: Into<String>,
+    F: Fn(&mut dyn Write) -> Result<()> + Send + Sync + 'static,
+{
+    SECTIONS.lock().unwrap().insert(name.into(), Arc::new(Box::new(f)));
+}
+
+/// Unregister a section of the bundle.
+pub fn unregister(name: &str) -> bool {
+    SECTIONS.lock().unwrap().remove(name).is_some()
+}
+
+/// Register the installed rules of a flow table as a section of the bundle, in JSON.
+pub fn register_flow_table<S: Into<String>>(name: S, table: Arc<Mutex<FlowTable>>) {
+    register(name, move |w| match table.try_lock() {
+        Ok(table) => table.save(w),
+        Err(_) => {
+            writeln!(w, "the flow table is locked")?;
+
+            Ok(())
+        }
+    })
+}
+
+/// The result of a dump.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dump {
+    /// The directory of the bundle.
+    pub dir: PathBuf,
+    /// The files of the written sections.
+    pub written: Vec<String>,
+    /// The files of the failed sections, and their errors.
+    pub failed: Vec<(String, String)>,
+}
+
+impl Dump {
+    /// All the sections were written.
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    fn section<F>(&mut self, name: &str, f: F)
+    where
+        F: FnOnce(&mut File) -> Result<()>,
+    {
+        let res: Result<()> = File::create(self.dir.join(name))
+            .map_err(From::from)
+            .and_then(|mut file| f(&mut file).and_then(|_| file.flush().map_err(From::from)));
+
+        match res {
+            Ok(_) => self.written.push(name.to_owned()),
+            Err(err) => self.failed.push((name.to_owned(), err.to_string())),
+        }
+    }
+}
+
+/// Dump the diagnostic state of DPDK to the directory, which is created if it doesn't exist.
+pub fn dump_all<P: AsRef<Path>>(path: P) -> Result<Dump> {
+    let dir = path.as_ref().to_owned();
+
+    fs::create_dir_all(&dir)?;
+
+    let mut dump = Dump {
+        dir,
+        ..Default::default()
+    };
+
+    dump.section("version.txt", dump_version);
+    dump.section("lcores.txt", dump_lcores);
+    dump.section("ports.txt", dump_ports);
+    dump.section("mempools.txt", mempool::list_dump_to);
+    dump.section("rings.txt", ring::list_dump_to);
+    dump.section("malloc.txt", |f| malloc::dump_stats_to(f, None));
+    dump.section("trace.txt", |f| {
+        if trace::is_enabled() {
+            trace::save()?;
+        }
+
+        trace::dump_to(f)
+    });
+    dump.section("log.txt", ::common::log::dump_to);
+
+    // the sections are cloned, so a section could register another one without a deadlock
+    let sections = SECTIONS.lock().unwrap().clone();
+
+    for (name, section) in sections {
+        dump.section(&name, |f| {
+            let w: &mut dyn Write = f;
+
+            (*section)(w)
+        });
+    }
+
+    if !dump.failed.is_empty() {
+        let mut f = File::create(dump.dir.join("errors.txt"))?;
+
+        for (name, err) in &dump.failed {
+            writeln!(f, "{}: {}", name, err)?;
+        }
+    }
+
+    Ok(dump)
+}
+
+/// Dump the diagnostic state to a new directory under the base directory,
+/// which is named with the reason, the process id and the timestamp.
+pub fn dump_new<P: AsRef<Path>>(base: P, reason: &str) -> Result<Dump> {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    dump_all(
+        base.as_ref()
+            .join(format!("rte-diag-{}-{}-{}", reason, process::id(), ts)),
+    )
+}
+
+/// Dump the diagnostic state once for the fatal condition, and report it to the stderr.
+fn dump_fatal(base: &Path, reason: &str) {
+    if DUMPED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    match dump_new(base, reason) {
+        Ok(ref dump) if dump.is_ok() => eprintln!("diagnostic state dumped to {:?}", dump.dir),
+        Ok(dump) => eprintln!(
+            "diagnostic state dumped to {:?}, {} sections failed",
+            dump.dir,
+            dump.failed.len()
+        ),
+        Err(err) => eprintln!("fail to dump diagnostic state, {}", err),
+    }
+}
+
+/// Install a panic hook which dumps the diagnostic state under the base directory,
+/// before the previous hook is invoked.
+pub fn install_panic_hook<P: Into<PathBuf>>(base: P) {
+    let base = base.into();
+    let prev = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        dump_fatal(&base, "panic");
+
+        prev(info)
+    }))
+}
+
+/// A keepalive failure callback, which dumps the diagnostic state under the base directory.
+pub fn keepalive_failure(base: Option<PathBuf>, lcore_id: lcore::Id) {
+    error!("lcore {} failed", lcore_id);
+
+    dump_fatal(&base.unwrap_or_else(|| PathBuf::from(".")), "keepalive")
+}
+
+/// A heartbeat stall callback, which dumps the diagnostic state under the base directory.
+pub fn stall_callback<P: Into<PathBuf>>(base: P) -> StallCallback {
+    let base = base.into();
+
+    Box::new(move |stall: &Stall| {
+        error!("lcore {} stalled for {:?}", stall.lcore_id, stall.elapsed);
+
+        dump_fatal(&base, "stall")
+    })
+}
+
+fn dump_version(w: &mut File) -> Result<()> {
+    writeln!(w, "compiled: {}", version())?;
+
+    match runtime_version() {
+        Ok(v) => writeln!(w, "runtime: {}", v)?,
+        Err(err) => writeln!(w, "runtime: {}", err)?,
+    }
+
+    writeln!(w, "features: {}", features())?;
+
+    Ok(())
+}
+
+fn dump_lcores(w: &mut File) -> Result<()> {
+    let master = lcore::master();
+
+    for lcore_id in lcore::enabled() {
+        writeln!(
+            w,
+            "lcore {}: socket {}, role {:?}, state {:?}{}",
+            lcore_id,
+            lcore_id.socket_id(),
+            lcore_id.role(),
+            lcore_id.state(),
+            if lcore_id == master { ", master" } else { "" }
+        )?;
+    }
+
+    Ok(())
+}
+
+fn dump_ports(w: &mut File) -> Result<()> {
+    for port_id in ethdev::devices() {
+        if let Err(err) = dump_port(w, port_id) {
+            writeln!(w, "  error: {}", err)?;
+        }
+
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+fn dump_port(w: &mut File, port_id: PortId) -> Result<()> {
+    use ethdev::EthDeviceInfo;
+
+    let info = port_id.info();
+    let link = port_id.link_nowait();
+
+    writeln!(
+        w,
+        "port {}: driver {}, socket {}",
+        port_id,
+        info.driver_name(),
+        port_id.socket_id()
+    )?;
+    writeln!(w, "  mac: {}", port_id.mac_addr())?;
+    writeln!(
+        w,
+        "  link: {}, {} Mbps, {}-duplex{}",
+        if link.up { "up" } else { "down" },
+        link.speed,
+        if link.duplex { "full" } else { "half" },
+        if link.autoneg { ", autoneg" } else { "" }
+    )?;
+    writeln!(
+        w,
+        "  queues: rx {}/{}, tx {}/{}",
+        info.nb_rx_queues, info.max_rx_queues, info.nb_tx_queues, info.max_tx_queues
+    )?;
+
+    match port_id.mtu() {
+        Ok(mtu) => writeln!(w, "  mtu: {}", mtu)?,
+        Err(err) => writeln!(w, "  mtu: {}", err)?,
+    }
+
+    writeln!(
+        w,
+        "  promiscuous: {}",
+        port_id.is_promiscuous_enabled().unwrap_or_default()
+    )?;
+    writeln!(w, "  rx offloads: {:?}", port_id.rx_offloads())?;
+    writeln!(w, "  tx offloads: {:?}", port_id.tx_offloads())?;
+
+    let stats = port_id.stats()?;
+
+    writeln!(
+        w,
+        "  stats: ipackets {}, opackets {}, ibytes {}, obytes {}, imissed {}, ierrors {}, oerrors {}, rx_nombuf {}",
+        stats.ipackets,
+        stats.opackets,
+        stats.ibytes,
+        stats.obytes,
+        stats.imissed,
+        stats.ierrors,
+        stats.oerrors,
+        stats.rx_nombuf
+    )?;
+
+    let xstats = XStatsIds::resolve(port_id, &["*"])?;
+
+    for (name, value) in xstats.get()? {
+        if value != 0 {
+            writeln!(w, "  {}: {}", name, value)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//!
+//! RTE distributor
+//!
+//! The distributor is a component which is designed to pass packets
+//! one-at-a-time to workers, with dynamic load balancing.
+//!
+//! Packets with the same tag, or flow id, are never processed on different workers
+//! at the same time, so the packet order within a flow is preserved.
+//!
+use std::mem;
+use std::os::raw::c_uint;
+
+use ffi;
+
+use common::name::{Name, NameKind};
+use errors::{AsResult, Result};
+use mbuf;
+use memory::Socket;
+use utils::AsRaw;
+
+pub use ffi::RTE_DISTRIBUTOR_NAMESIZE;
+
+/// The maximum number of packets exchanged with a worker at a time in burst mode.
+pub const RTE_DIST_BURST_SIZE: usize = 8;
+
+/// The worker instance number, less than the number of workers of the distributor.
+pub type WorkerId = u32;
+
+/// The algorithm of distributor.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum Algorithm {
+    /// Single packet mode, uses 32-bit flow id and works on a single packet at a time.
+    Single = ffi::rte_distributor_alg_type::RTE_DIST_ALG_SINGLE,
+    /// Burst mode, uses 15-bit flow id and works on up to 8 packets at a time to workers.
+    Burst = ffi::rte_distributor_alg_type::RTE_DIST_ALG_BURST,
+}
+
+pub type RawDistributor = ffi::rte_distributor;
+pub type RawDistributorPtr = *mut ffi::rte_distributor;
+
+/// The RTE distributor structure.
+raw!(pub Distributor(RawDistributor));
+
+impl Clone for Distributor {
+    fn clone(&self) -> Self {
+        Distributor(self.0)
+    }
+}
+
+/// Create a new distributor instance.
+///
+/// Reserves the memory needed for the distributor operation and
+/// initializes the distributor to work with the configured number of workers.
+///
+/// The memory of distributor is never released.
+pub fn create<S: AsRef<str>>(name: S, socket: Socket, num_workers: usize, alg: Algorithm) -> Result<Distributor> {
+    let name = Name::new(NameKind::Distributor, name)?;
+    let socket = socket.validate()?;
+
+    unsafe { ffi::rte_distributor_create(name.as_ptr(), socket.into(), num_workers as c_uint, alg as u32) }
+        .as_result()
+        .map(Distributor)
+}
+
+/// Take the ownership of the packets, they will be released by whom they were passed to.
+fn forget_pkts(pkts: &mut [Option<mbuf::MBuf>]) {
+    mbuf::balance::on_put_burst(pkts.iter().flatten().map(|m| m.as_raw()));
+
+    for m in pkts.iter_mut() {
+        mem::forget(m.take())
+    }
+}
+
+/// The slots written by the distributor must be empty, or their packets would leak.
+fn check_empty(pkts: &[Option<mbuf::MBuf>]) {
+    assert!(pkts.iter().all(Option::is_none), "the output slots must be empty");
+}
+
+/// The packets handed back by the distributor are owned by the application again.
+fn acquire_pkts(pkts: &[Option<mbuf::MBuf>]) {
+    #[cfg(feature = "debug-mbuf")]
+    pkts.iter().flatten().for_each(mbuf::debug::on_acquire);
+
+    mbuf::balance::on_get_burst(pkts.iter().flatten().map(|m| m.as_raw()));
+}
+
+impl Distributor {
+    /// Process a set of packets by distributing them among workers that request packets.
+    ///
+    /// The distributor will ensure that no two packets that have the same flow id, or tag,
+    /// in the mbuf will be processed on different cores at the same time.
+    ///
+    /// The packets are moved to the workers, and the slots are left empty, the empty slots are skipped.
+    ///
+    /// This is not multi-thread safe and should only be called on a single lcore.
+    pub fn process(&self, pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        // the distributor dereferences each mbuf, so the packets are compacted before the empty slots
+        let mut nb_pkts = 0;
+
+        for i in 0..pkts.len() {
+            if pkts[i].is_some() {
+                pkts.swap(nb_pkts, i);
+                nb_pkts += 1;
+            }
+        }
+
+        let pkts = &mut pkts[..nb_pkts];
+        let n = unsafe { ffi::rte_distributor_process(self.as_raw(), pkts.as_mut_ptr() as *mut _, pkts.len() as u32) };
+
+        forget_pkts(pkts);
+
+        n as usize
+    }
+
+    /// Get a set of packets that have been returned to the distributor by workers.
+    ///
+    /// The `pkts` must be empty, the returned packets are written from the front.
+    ///
+    /// This should only be called on the same lcore as `process()`.
+    pub fn returned_pkts(&self, pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        check_empty(pkts);
+
+        let n = unsafe {
+            ffi::rte_distributor_returned_pkts(self.as_raw(), pkts.as_mut_ptr() as *mut _, pkts.len() as u32) as usize
+        };
+
+        acquire_pkts(&pkts[..n]);
+
+        n
+    }
+
+    /// Flush the distributor component,
+    /// so that there are no in-flight or backlogged packets awaiting processing.
+    ///
+    /// This should only be called on the same lcore as `process()`.
+    ///
+    /// Returns the number of queued/in-flight packets that were completed by this call.
+    pub fn flush(&self) -> usize {
+        unsafe { ffi::rte_distributor_flush(self.as_raw()) as usize }
+    }
+
+    /// Clears the array of returned packets used as the source for the `returned_pkts()`.
+    ///
+    /// This should only be called on the same lcore as `process()`.
+    pub fn clear_returns(&self) -> &Self {
+        unsafe { ffi::rte_distributor_clear_returns(self.as_raw()) };
+
+        self
+    }
+
+    /// Called by a worker to get new packets to process.
+    ///
+    /// Any previous packets given to the worker is assumed to have completed processing,
+    /// and may be optionally returned to the distributor via the `old_pkts` parameter.
+    ///
+    /// The `pkts` must have room for `RTE_DIST_BURST_SIZE` packets, and those slots must be empty.
+    ///
+    /// Returns the number of packets in the `pkts`.
+    pub fn get_pkt(
+        &self,
+        worker_id: WorkerId,
+        pkts: &mut [Option<mbuf::MBuf>],
+        old_pkts: &mut [Option<mbuf::MBuf>],
+    ) -> usize {
+        assert!(pkts.len() >= RTE_DIST_BURST_SIZE);
+        check_empty(&pkts[..RTE_DIST_BURST_SIZE]);
+        assert!(old_pkts.len() <= RTE_DIST_BURST_SIZE);
+
+        let n = unsafe {
+            ffi::rte_distributor_get_pkt(
+                self.as_raw(),
+                worker_id,
+                pkts.as_mut_ptr() as *mut _,
+                old_pkts.as_mut_ptr() as *mut _,
+                old_pkts.len() as u32,
+            )
+        };
+
+        forget_pkts(old_pkts);
+        acquire_pkts(&pkts[..n as usize]);
+
+        n as usize
+    }
+
+    /// Called by a worker to return completed packets without requesting a new packet,
+    /// for example, because a worker thread is shutting down.
+    pub fn return_pkt(&self, worker_id: WorkerId, old_pkts: &mut [Option<mbuf::MBuf>]) -> &Self {
+        assert!(old_pkts.len() <= RTE_DIST_BURST_SIZE);
+
+        unsafe {
+            ffi::rte_distributor_return_pkt(
+                self.as_raw(),
+                worker_id,
+                old_pkts.as_mut_ptr() as *mut _,
+                old_pkts.len() as i32,
+            )
+        };
+
+        forget_pkts(old_pkts);
+
+        self
+    }
+
+    /// Called by a worker to request new packets to process, without waiting for them.
+    ///
+    /// Any previous packets given to the worker are assumed to have completed processing,
+    /// and may be optionally returned to the distributor via the `old_pkts` parameter.
+    ///
+    /// The new packets should be checked with `poll_pkt()`.
+    pub fn request_pkt(&self, worker_id: WorkerId, old_pkts: &mut [Option<mbuf::MBuf>]) -> &Self {
+        assert!(old_pkts.len() <= RTE_DIST_BURST_SIZE);
+
+        unsafe {
+            ffi::rte_distributor_request_pkt(
+                self.as_raw(),
+                worker_id,
+                old_pkts.as_mut_ptr() as *mut _,
+                old_pkts.len() as u32,
+            )
+        };
+
+        forget_pkts(old_pkts);
+
+        self
+    }
+
+    /// Called by a worker to check for new packets that were previously requested by `request_pkt()`.
+    ///
+    /// The `pkts` must have room for `RTE_DIST_BURST_SIZE` packets, and those slots must be empty.
+    ///
+    /// Returns the number of packets given to the worker,
+    /// or `None` if the request has not yet been fulfilled by the distributor.
+    pub fn poll_pkt(&self, worker_id: WorkerId, pkts: &mut [Option<mbuf::MBuf>]) -> Option<usize> {
+        assert!(pkts.len() >= RTE_DIST_BURST_SIZE);
+        check_empty(&pkts[..RTE_DIST_BURST_SIZE]);
+
+        let n = unsafe { ffi::rte_distributor_poll_pkt(self.as_raw(), worker_id, pkts.as_mut_ptr() as *mut _) };
+
+        if n < 0 {
+            None
+        } else {
+            acquire_pkts(&pkts[..n as usize]);
+
+            Some(n as usize)
+        }
+    }
+}
@@ -0,0 +1,706 @@
+//!
+//! Equal-cost multi-path next-hop groups for the load-balanced L3 forwarding with failover.
+//!
+//! A `NextHopGroup` spreads the flows over its next hops in proportion to their weights.
+//! Each next hop owns a share of the hash buckets of the group, and a packet is forwarded
+//! to the owner of the bucket of its RSS or 5-tuple hash, so the packets of a flow take the same path.
+//!
+//! A next hop could be marked down, e.g. by a BFD session or a link status change, without
+//! rebuilding the group. The flows of its buckets move to the owners of the following live buckets,
+//! while the flows of the live next hops keep their paths.
+//!
+//! The `MultipathTable` maps the networks to the groups with a `PrefixTable` on `rte_lpm`, the routes and
+//! the groups are published together in an immutable snapshot. A new configuration is applied
+//! atomically, each lcore reads the table through its own `MultipathReader`, which picks up
+//! the latest snapshot when it is refreshed, e.g. once per burst.
+//!
+//! ```no_run
+//! use std::net::Ipv4Addr;
+//! use std::sync::Arc;
+//!
+//! use rte::ecmp::{GroupConf, Health, MultipathConf, MultipathReader, MultipathTable, NextHop};
+//! use rte::route::Route;
+//!
+//! let uplink = |port, gateway: Ipv4Addr| Route {
+//!     port,
+//!     gateway: Some(gateway.into()),
+//! };
+//!
+//! let table = Arc::new(MultipathTable::new().unwrap());
+//!
+//! table
+//!     .apply(&MultipathConf {
+//!         groups: vec![GroupConf {
+//!             id: 1,
+//!             next_hops: vec![
+//!                 NextHop::new(uplink(0, Ipv4Addr::new(10, 0, 0, 1)), 2),
+//!                 NextHop::new(uplink(1, Ipv4Addr::new(10, 0, 1, 1)), 1),
+//!             ],
+//!         }],
+//!         routes: vec![(Ipv4Addr::UNSPECIFIED.into(), 0, 1)],
+//!     })
+//!     .unwrap();
+//!
+//! // the gateway of port 1 is unreachable
+//! table.set_health(&uplink(1, Ipv4Addr::new(10, 0, 1, 1)), Health::Down);
+//!
+//! // on each forwarding lcore
+//! let mut reader = MultipathReader::new(&table);
+//!
+//! loop {
+//!     reader.refresh();
+//!
+//!     let route = reader.lookup(Ipv4Addr::new(8, 8, 8, 8).into(), 0x1234_5678);
+//! #   break;
+//! }
+//! ```
+//!
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use libc;
+
+use errors::{Result, RteError};
+use filter::dissect_mbuf;
+use hashers::{softrss, FiveTuple, DEFAULT_RSS_KEY};
+use lpm;
+use mbuf::MBuf;
+use route::{PrefixTable, Route};
+
+/// The id of a next-hop group.
+pub type GroupId = u32;
+
+/// The default number of the hash buckets of a group.
+pub const DEFAULT_BUCKETS: usize = 256;
+
+/// A weighted next hop of a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NextHop {
+    /// The route to the next hop.
+    pub route: Route,
+    /// The weight of the next hop, a next hop of weight 0 only takes the flows when the others are down.
+    pub weight: u32,
+}
+
+impl NextHop {
+    /// Create a next hop of the weight.
+    pub fn new(route: Route, weight: u32) -> Self {
+        NextHop { route, weight }
+    }
+}
+
+/// The health state of a next hop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Health {
+    /// The next hop takes its share of the flows.
+    Up,
+    /// The flows of the next hop move to the other next hops.
+    Down,
+}
+
+/// A group of the weighted next hops, which are selected by the flow hash.
+#[derive(Debug)]
+pub struct NextHopGroup {
+    next_hops: Vec<NextHop>,
+    up: Vec<AtomicBool>,
+    live: AtomicUsize,
+    buckets: Vec<u16>,
+    rss_key: Vec<u8>,
+}
+
+impl NextHopGroup {
+    /// Create a group of the next hops with `DEFAULT_BUCKETS` hash buckets.
+    pub fn new(next_hops: Vec<NextHop>) -> Result<Self> {
+        Self::with_buckets(next_hops, DEFAULT_BUCKETS)
+    }
+
+    /// Create a group of the next hops with the number of hash buckets,
+    /// more buckets follow the weights more precisely.
+    ///
+    /// Fails with `EINVAL` if there is no next hop, too many next hops, or no bucket.
+    pub fn with_buckets(next_hops: Vec<NextHop>, nb_buckets: usize) -> Result<Self> {
+        if next_hops.is_empty() || next_hops.len() > usize::from(u16::MAX) || nb_buckets == 0 {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        let buckets = fill_buckets(&next_hops, nb_buckets);
+
+        Ok(NextHopGroup {
+            up: next_hops.iter().map(|_| AtomicBool::new(true)).collect(),
+            live: AtomicUsize::new(next_hops.len()),
+            next_hops,
+            buckets,
+            rss_key: DEFAULT_RSS_KEY.to_vec(),
+        })
+    }
+
+    /// Use the RSS key of the port to hash the packets without RSS hash,
+    /// so they take the same path as the other packets of their flows.
+    pub fn with_rss_key(mut self, rss_key: &[u8]) -> Self {
+        self.rss_key = rss_key.to_vec();
+        self
+    }
+
+    /// The next hops of the group.
+    pub fn next_hops(&self) -> &[NextHop] {
+        &self.next_hops
+    }
+
+    /// The number of the hash buckets.
+    pub fn nb_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// The number of the live next hops.
+    pub fn live(&self) -> usize {
+        self.live.load(Ordering::Relaxed)
+    }
+
+    /// The health state of the next hop at the index.
+    pub fn health(&self, idx: usize) -> Option<Health> {
+        self.up.get(idx).map(|up| {
+            if up.load(Ordering::Relaxed) {
+                Health::Up
+            } else {
+                Health::Down
+            }
+        })
+    }
+
+    /// Change the health state of the next hop at the index, returns the previous state.
+    pub fn set_health(&self, idx: usize, health: Health) -> Option<Health> {
+        let up = self.up.get(idx)?;
+        let was_up = up.swap(health == Health::Up, Ordering::Relaxed);
+
+        match (was_up, health) {
+            (true, Health::Down) => {
+                self.live.fetch_sub(1, Ordering::Relaxed);
+            }
+            (false, Health::Up) => {
+                self.live.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+
+        Some(if was_up { Health::Up } else { Health::Down })
+    }
+
+    /// Change the health state of the next hops of the route, returns the number of the matched next hops.
+    pub fn set_route_health(&self, route: &Route, health: Health) -> usize {
+        let mut matched = 0;
+
+        for (idx, next_hop) in self.next_hops.iter().enumerate() {
+            if next_hop.route == *route {
+                self.set_health(idx, health);
+                matched += 1;
+            }
+        }
+
+        matched
+    }
+
+    /// Select the live next hop of the flow hash, or `None` if all the next hops are down.
+    pub fn select(&self, hash: u32) -> Option<&NextHop> {
+        if self.live() == 0 {
+            return None;
+        }
+
+        let is_up = |&idx: &usize| self.up[idx].load(Ordering::Relaxed);
+        let n = self.buckets.len();
+        let start = hash as usize % n;
+
+        // the next hops without bucket only take the flows when all the bucket owners are down
+        (start..n)
+            .chain(0..start)
+            .map(|bucket| usize::from(self.buckets[bucket]))
+            .find(is_up)
+            .or_else(|| {
+                let n = self.next_hops.len();
+                let start = hash as usize % n;
+
+                (start..n).chain(0..start).find(is_up)
+            })
+            .map(|idx| &self.next_hops[idx])
+    }
+
+    /// Select the live next hop of the flow, hashed with the Toeplitz hash as the NIC does.
+    pub fn select_tuple(&self, tuple: &FiveTuple) -> Option<&NextHop> {
+        self.select(tuple.softrss(&self.rss_key))
+    }
+
+    /// Select the live next hop of a packet, which starts from the Ethernet header.
+    ///
+    /// The RSS hash of the NIC is used if any, otherwise the packet is hashed on its 5-tuple,
+    /// or its addresses if it has no L4 ports, the non-IP packets use the first bucket.
+    pub fn select_mbuf(&self, m: &MBuf) -> Option<&NextHop> {
+        let hash = m.rss_hash().unwrap_or_else(|| self.hash_mbuf(m));
+
+        self.select(hash)
+    }
+
+    fn hash_mbuf(&self, m: &MBuf) -> u32 {
+        let d = match dissect_mbuf(m) {
+            Some(d) => d,
+            None => return 0,
+        };
+
+        match (d.src_ip, d.dst_ip, d.proto, d.src_port, d.dst_port) {
+            (Some(src_addr), Some(dst_addr), Some(proto), Some(src_port), Some(dst_port)) => FiveTuple {
+                src_addr,
+                dst_addr,
+                src_port,
+                dst_port,
+                proto,
+            }
+            .softrss(&self.rss_key),
+            (Some(src_addr), Some(dst_addr), Some(proto), _, _) => {
+                let input = FiveTuple {
+                    src_addr,
+                    dst_addr,
+                    src_port: 0,
+                    dst_port: 0,
+                    proto,
+                }
+                .to_rss_input(false);
+
+                softrss(&input, &self.rss_key)
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Distribute the buckets to the next hops in proportion to their weights,
+/// the next hops of weight 0 are only used when all the others are down.
+///
+/// The buckets of a next hop are interleaved with the others, so the flows
+/// of a down next hop are spread over the live ones.
+fn fill_buckets(next_hops: &[NextHop], nb_buckets: usize) -> Vec<u16> {
+    let total = next_hops.iter().map(|next_hop| u64::from(next_hop.weight)).sum::<u64>();
+
+    if total == 0 {
+        return (0..nb_buckets)
+            .map(|bucket| (bucket % next_hops.len()) as u16)
+            .collect();
+    }
+
+    // the shares of the next hops, the remaining buckets go to the largest remainders
+    let mut shares = next_hops
+        .iter()
+        .map(|next_hop| u64::from(next_hop.weight) * nb_buckets as u64)
+        .map(|n| ((n / total) as usize, n % total))
+        .collect::<Vec<_>>();
+    let mut remaining = nb_buckets - shares.iter().map(|&(share, _)| share).sum::<usize>();
+    let mut order = (0..shares.len()).collect::<Vec<_>>();
+
+    order.sort_by_key(|&idx| Reverse(shares[idx].1));
+
+    for idx in order {
+        if remaining == 0 {
+            break;
+        }
+
+        shares[idx].0 += 1;
+        remaining -= 1;
+    }
+
+    // the smooth weighted round robin over the shares
+    let mut current = vec![0i64; shares.len()];
+    let mut buckets = Vec::with_capacity(nb_buckets);
+
+    for _ in 0..nb_buckets {
+        for (cur, &(share, _)) in current.iter_mut().zip(&shares) {
+            *cur += share as i64;
+        }
+
+        let idx = (0..current.len())
+            .max_by_key(|&idx| (current[idx], -(idx as i64)))
+            .unwrap();
+
+        current[idx] -= nb_buckets as i64;
+        buckets.push(idx as u16);
+    }
+
+    buckets
+}
+
+/// The declarative configuration of a next-hop group.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupConf {
+    /// The id of the group, which is referenced by the routes.
+    pub id: GroupId,
+    /// The next hops of the group.
+    pub next_hops: Vec<NextHop>,
+}
+
+/// The declarative configuration of the multi-path routes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultipathConf {
+    /// The next-hop groups.
+    pub groups: Vec<GroupConf>,
+    /// The networks and the ids of their groups.
+    pub routes: Vec<(IpAddr, u8, GroupId)>,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    prefixes: Arc<PrefixTable<GroupId>>,
+    groups: HashMap<GroupId, Arc<NextHopGroup>>,
+}
+
+impl Snapshot {
+    fn lookup(&self, dst: IpAddr) -> Option<&NextHopGroup> {
+        self.prefixes
+            .lookup(dst)
+            .and_then(|id| self.groups.get(&id))
+            .map(|group| &**group)
+    }
+}
+
+/// A longest prefix match table of the networks and their next-hop groups, which is replaced atomically.
+#[derive(Debug)]
+pub struct MultipathTable {
+    current: Mutex<Arc<Snapshot>>,
+    generation: AtomicU64,
+}
+
+impl MultipathTable {
+    /// Create an empty table.
+    pub fn new() -> Result<Self> {
+        Ok(MultipathTable {
+            current: Mutex::new(Arc::new(Snapshot {
+                prefixes: Arc::new(PrefixTable::new()?),
+                groups: HashMap::new(),
+            })),
+            generation: AtomicU64::new(0),
+        })
+    }
+
+    /// The generation of the table, which is increased by each change.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    fn snapshot(&self) -> (Arc<Snapshot>, u64) {
+        let current = self.current.lock().unwrap();
+
+        (current.clone(), self.generation.load(Ordering::Acquire))
+    }
+
+    /// Build a new snapshot from the current one and publish it.
+    fn update<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Snapshot) -> Result<(Snapshot, T)>,
+    {
+        let mut current = self.current.lock().unwrap();
+        let (snapshot, res) = f(&current)?;
+
+        *current = Arc::new(snapshot);
+        self.generation.fetch_add(1, Ordering::Release);
+
+        Ok(res)
+    }
+
+    /// Change a copy of the groups, the prefixes are shared with the current snapshot.
+    fn update_groups<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut HashMap<GroupId, Arc<NextHopGroup>>) -> T,
+    {
+        self.update(|current| {
+            let mut groups = current.groups.clone();
+            let res = f(&mut groups);
+
+            Ok((
+                Snapshot {
+                    prefixes: current.prefixes.clone(),
+                    groups,
+                },
+                res,
+            ))
+        })
+        .unwrap()
+    }
+
+    /// Change a copy of the prefixes, the groups are shared with the current snapshot.
+    fn update_prefixes<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut PrefixTable<GroupId>, &HashMap<GroupId, Arc<NextHopGroup>>) -> Result<T>,
+    {
+        self.update(|current| {
+            let mut prefixes = current.prefixes.try_clone()?;
+            let res = f(&mut prefixes, &current.groups)?;
+
+            Ok((
+                Snapshot {
+                    prefixes: Arc::new(prefixes),
+                    groups: current.groups.clone(),
+                },
+                res,
+            ))
+        })
+    }
+
+    /// Replace the routes and the groups with the configuration.
+    ///
+    /// The next hops of a group, which are still in the group of the same id, keep their health states.
+    /// Fails with `EINVAL` if a group or a route is invalid, or `ENOENT` if a route references an unknown group.
+    pub fn apply(&self, conf: &MultipathConf) -> Result<()> {
+        self.update(|current| {
+            let mut groups = HashMap::with_capacity(conf.groups.len());
+
+            for group_conf in &conf.groups {
+                let group = NextHopGroup::new(group_conf.next_hops.clone())?;
+
+                if let Some(old) = current.groups.get(&group_conf.id) {
+                    inherit_health(&group, old);
+                }
+
+                groups.insert(group_conf.id, Arc::new(group));
+            }
+
+            let mut prefixes = PrefixTable::with_conf(current.prefixes.conf())?;
+
+            for &(prefix, depth, id) in &conf.routes {
+                check_depth(prefix, depth)?;
+
+                if !groups.contains_key(&id) {
+                    return Err(RteError(libc::ENOENT).into());
+                }
+
+                prefixes.add(prefix, depth, id)?;
+            }
+
+            Ok((
+                Snapshot {
+                    prefixes: Arc::new(prefixes),
+                    groups,
+                },
+                (),
+            ))
+        })
+    }
+
+    /// Add or replace a group, returns the previous group of the id.
+    pub fn insert_group(&self, id: GroupId, group: NextHopGroup) -> Option<Arc<NextHopGroup>> {
+        self.update_groups(|groups| groups.insert(id, Arc::new(group)))
+    }
+
+    /// Remove a group, the routes to the group are dropped until it is added again.
+    pub fn remove_group(&self, id: GroupId) -> Option<Arc<NextHopGroup>> {
+        self.update_groups(|groups| groups.remove(&id))
+    }
+
+    /// The group of the id.
+    pub fn group(&self, id: GroupId) -> Option<Arc<NextHopGroup>> {
+        self.snapshot().0.groups.get(&id).cloned()
+    }
+
+    /// Add a route of the network to the group, returns the previous group of the network.
+    ///
+    /// Fails with `EINVAL` if the prefix length exceeds the address length, or `ENOENT` if the group is unknown.
+    pub fn add_route(&self, prefix: IpAddr, depth: u8, id: GroupId) -> Result<Option<GroupId>> {
+        check_depth(prefix, depth)?;
+
+        self.update_prefixes(|prefixes, groups| {
+            if !groups.contains_key(&id) {
+                return Err(RteError(libc::ENOENT).into());
+            }
+
+            prefixes.add(prefix, depth, id)
+        })
+    }
+
+    /// Remove the route of the network, returns the group of the network.
+    ///
+    /// Fails with `EINVAL` if the prefix length exceeds the address length.
+    pub fn remove_route(&self, prefix: IpAddr, depth: u8) -> Result<Option<GroupId>> {
+        check_depth(prefix, depth)?;
+
+        self.update_prefixes(|prefixes, _| Ok(prefixes.remove(prefix, depth)))
+    }
+
+    /// Change the health state of the next hops of the route in all the groups,
+    /// returns the number of the matched next hops.
+    ///
+    /// The readers see the change immediately, without refreshing their snapshots.
+    pub fn set_health(&self, route: &Route, health: Health) -> usize {
+        self.snapshot()
+            .0
+            .groups
+            .values()
+            .map(|group| group.set_route_health(route, health))
+            .sum()
+    }
+}
+
+/// The prefix length must not exceed the address length.
+fn check_depth(prefix: IpAddr, depth: u8) -> Result<()> {
+    let max_depth = if prefix.is_ipv4() {
+        lpm::RTE_LPM_MAX_DEPTH
+    } else {
+        lpm::RTE_LPM6_MAX_DEPTH
+    };
+
+    if u32::from(depth) > max_depth {
+        Err(RteError(libc::EINVAL).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Copy the health states of the next hops which are still in the group.
+fn inherit_health(group: &NextHopGroup, old: &NextHopGroup) {
+    for (idx, next_hop) in group.next_hops.iter().enumerate() {
+        if let Some(old_idx) = old.next_hops.iter().position(|old| old.route == next_hop.route) {
+            if let Some(health) = old.health(old_idx) {
+                group.set_health(idx, health);
+            }
+        }
+    }
+}
+
+/// A per-lcore reader of a `MultipathTable`, which keeps using its snapshot until it is refreshed.
+#[derive(Debug)]
+pub struct MultipathReader {
+    table: Arc<MultipathTable>,
+    snapshot: Arc<Snapshot>,
+    generation: u64,
+}
+
+impl MultipathReader {
+    /// Create a reader of the current snapshot of the table.
+    pub fn new(table: &Arc<MultipathTable>) -> Self {
+        let (snapshot, generation) = table.snapshot();
+
+        MultipathReader {
+            table: table.clone(),
+            snapshot,
+            generation,
+        }
+    }
+
+    /// Pick up the latest snapshot of the table, returns `true` if it was changed.
+    pub fn refresh(&mut self) -> bool {
+        if self.table.generation() == self.generation {
+            return false;
+        }
+
+        let (snapshot, generation) = self.table.snapshot();
+
+        self.snapshot = snapshot;
+        self.generation = generation;
+
+        true
+    }
+
+    /// The generation of the snapshot.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The group of the longest matched prefix.
+    pub fn group(&self, dst: IpAddr) -> Option<&NextHopGroup> {
+        self.snapshot.lookup(dst)
+    }
+
+    /// Find the route of the destination for the flow hash.
+    pub fn lookup(&self, dst: IpAddr, hash: u32) -> Option<Route> {
+        self.group(dst)
+            .and_then(|group| group.select(hash))
+            .map(|next_hop| next_hop.route)
+    }
+
+    /// Find the route of the destination for the packet, which starts from the Ethernet header.
+    pub fn lookup_mbuf(&self, dst: IpAddr, m: &MBuf) -> Option<Route> {
+        self.group(dst)
+            .and_then(|group| group.select_mbuf(m))
+            .map(|next_hop| next_hop.route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn route(port: u16) -> Route {
+        Route {
+            port,
+            gateway: Some(Ipv4Addr::new(10, 0, port as u8, 1).into()),
+        }
+    }
+
+    #[test]
+    fn test_fill_buckets() {
+        let next_hops = vec![
+            NextHop::new(route(0), 3),
+            NextHop::new(route(1), 1),
+            NextHop::new(route(2), 0),
+        ];
+        let buckets = fill_buckets(&next_hops, 256);
+        let count = |idx| buckets.iter().filter(|&&bucket| bucket == idx).count();
+
+        assert_eq!(buckets.len(), 256);
+        assert_eq!((count(0), count(1), count(2)), (192, 64, 0));
+        assert_eq!(&buckets[..4], &[0, 0, 1, 0]);
+
+        let buckets = fill_buckets(&next_hops[..2], 3);
+
+        assert_eq!(buckets, vec![0, 1, 0]);
+
+        let buckets = fill_buckets(&[NextHop::new(route(0), 0), NextHop::new(route(1), 0)], 4);
+
+        assert_eq!(buckets, vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_failover() {
+        let group = NextHopGroup::with_buckets(
+            vec![
+                NextHop::new(route(0), 1),
+                NextHop::new(route(1), 1),
+                NextHop::new(route(2), 1),
+                NextHop::new(route(3), 0),
+            ],
+            12,
+        )
+        .unwrap();
+
+        assert!(NextHopGroup::new(vec![]).is_err());
+        assert_eq!(group.live(), 4);
+
+        let paths = (0..12)
+            .map(|hash| group.select(hash).unwrap().route.port)
+            .collect::<Vec<_>>();
+
+        assert!(!paths.contains(&3));
+
+        assert_eq!(group.set_health(1, Health::Down), Some(Health::Up));
+        assert_eq!(group.live(), 3);
+
+        for hash in 0..12 {
+            let port = group.select(hash).unwrap().route.port;
+
+            assert_ne!(port, 1);
+
+            if paths[hash as usize] != 1 {
+                assert_eq!(port, paths[hash as usize]);
+            }
+        }
+
+        assert_eq!(group.set_route_health(&route(0), Health::Down), 1);
+        assert_eq!(group.set_route_health(&route(2), Health::Down), 1);
+        assert_eq!(group.select(7).unwrap().route.port, 3);
+
+        group.set_health(3, Health::Down);
+
+        assert_eq!(group.live(), 0);
+        assert!(group.select(7).is_none());
+        assert_eq!(group.health(3), Some(Health::Down));
+        assert_eq!(group.health(4), None);
+    }
+}
@@ -0,0 +1,337 @@
+//!
+//! Packet editing with incremental checksum updates.
+//!
+//! The MAC addresses, IPv4 addresses and TCP/UDP ports of a packet are rewritten in place,
+//! and the IPv4 header checksum and TCP/UDP checksum are updated incrementally as RFC 1624,
+//! instead of recalculating them over the whole packet.
+//!
+//! The packet must start with an Ethernet header, optionally followed by VLAN tags,
+//! and the headers must be in the first segment.
+//!
+//! If the L4 checksum offload is requested for the packet, the L4 checksum field is
+//! considered as the pseudo-header checksum, which is adjusted for the changed addresses.
+//!
+//! The L4 header of the non-first fragments can't be updated,
+//! so the ports and L4 checksum of a fragmented packet must be rewritten before fragmentation.
+//!
+use std::net::Ipv4Addr;
+use std::slice;
+
+use libc;
+
+use ether::{ETHER_TYPE_IPv4, EtherAddr, ETHER_ADDR_LEN, ETHER_HDR_LEN, ETHER_TYPE_QINQ, ETHER_TYPE_VLAN};
+use ip::{IPV4_HDR_IHL_MASK, IPV4_HDR_LEN, IPV4_HDR_OFFSET_MASK, IPV4_IHL_MULTIPLIER, IPVERSION};
+use mbuf::{MBuf, OffloadFlags};
+
+const VLAN_HDR_LEN: usize = 4;
+const TCP_CKSUM_OFF: usize = 16;
+const UDP_CKSUM_OFF: usize = 6;
+
+/// The source or destination field of a header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    Src,
+    Dst,
+}
+
+/// Incrementally update a checksum for the changed data, as RFC 1624.
+///
+/// The checksum and data are in network byte order, and the data must be 16-bit aligned in the header.
+pub fn cksum_adjust(cksum: u16, old: &[u8], new: &[u8]) -> u16 {
+    !pseudo_cksum_adjust(!cksum, old, new)
+}
+
+/// Update a non-complemented sum, for example, the pseudo-header checksum for L4 checksum offload.
+fn pseudo_cksum_adjust(sum: u16, old: &[u8], new: &[u8]) -> u16 {
+    assert_eq!(old.len(), new.len());
+    assert_eq!(old.len() % 2, 0);
+
+    let word = |b: &[u8]| u32::from(u16::from_ne_bytes([b[0], b[1]]));
+    let mut sum = old
+        .chunks(2)
+        .zip(new.chunks(2))
+        .fold(u32::from(sum), |sum, (o, n)| sum + (!word(o) & 0xffff) + word(n));
+
+    while sum > 0xffff {
+        sum = (sum >> 16) + (sum & 0xffff);
+    }
+
+    sum as u16
+}
+
+struct Layout {
+    l3: usize,
+    l4: Option<(usize, u8)>,
+}
+
+/// Locate the IPv4 header and the L4 header of the first fragment.
+fn ipv4_layout(data: &[u8]) -> Option<Layout> {
+    let mut off = ETHER_ADDR_LEN * 2;
+
+    loop {
+        if data.len() < off + 2 {
+            return None;
+        }
+
+        let ether_type = u32::from(u16::from_be_bytes([data[off], data[off + 1]]));
+
+        if ether_type == ETHER_TYPE_VLAN || ether_type == ETHER_TYPE_QINQ {
+            off += VLAN_HDR_LEN;
+        } else if ether_type == ETHER_TYPE_IPv4 {
+            break;
+        } else {
+            return None;
+        }
+    }
+
+    let l3 = off + 2;
+
+    if data.len() < l3 + IPV4_HDR_LEN || data[l3] >> 4 != IPVERSION {
+        return None;
+    }
+
+    let hdr_len = (data[l3] & IPV4_HDR_IHL_MASK) as usize * IPV4_IHL_MULTIPLIER;
+
+    // a malformed header shorter than the minimum, or truncated options
+    if hdr_len < IPV4_HDR_LEN || data.len() < l3 + hdr_len {
+        return None;
+    }
+
+    let frag_offset = u16::from_be_bytes([data[l3 + 6], data[l3 + 7]]) & IPV4_HDR_OFFSET_MASK;
+    let proto = data[l3 + 9];
+    let l4 = l3 + hdr_len;
+
+    let l4 = match i32::from(proto) {
+        libc::IPPROTO_TCP if frag_offset == 0 && data.len() >= l4 + TCP_CKSUM_OFF + 2 => Some((l4, proto)),
+        libc::IPPROTO_UDP if frag_offset == 0 && data.len() >= l4 + UDP_CKSUM_OFF + 2 => Some((l4, proto)),
+        _ => None,
+    };
+
+    Some(Layout { l3, l4 })
+}
+
+/// Replace the bytes of the header, and update the checksum at `cksum_off`.
+fn replace(data: &mut [u8], off: usize, new: &[u8], cksum_off: usize, pseudo: bool, zero_is_none: bool) {
+    let cksum = u16::from_ne_bytes([data[cksum_off], data[cksum_off + 1]]);
+
+    if !(zero_is_none && cksum == 0) {
+        let old = &data[off..off + new.len()];
+        let cksum = if pseudo {
+            pseudo_cksum_adjust(cksum, old, new)
+        } else {
+            match cksum_adjust(cksum, old, new) {
+                0 if zero_is_none => 0xffff,
+                cksum => cksum,
+            }
+        };
+
+        data[cksum_off..cksum_off + 2].copy_from_slice(&cksum.to_ne_bytes());
+    }
+}
+
+fn l4_cksum_off(proto: u8) -> (usize, bool) {
+    if i32::from(proto) == libc::IPPROTO_TCP {
+        (TCP_CKSUM_OFF, false)
+    } else {
+        (UDP_CKSUM_OFF, true)
+    }
+}
+
+fn rewrite_ipv4_addr_in(data: &mut [u8], which: Endpoint, addr: Ipv4Addr, l4_offload: bool) -> Option<Ipv4Addr> {
+    let layout = ipv4_layout(data)?;
+    let off = layout.l3 + if which == Endpoint::Src { 12 } else { 16 };
+    let old = Ipv4Addr::new(data[off], data[off + 1], data[off + 2], data[off + 3]);
+    let new = addr.octets();
+
+    if let Some((l4, proto)) = layout.l4 {
+        let (cksum_off, zero_is_none) = l4_cksum_off(proto);
+
+        replace(data, off, &new, l4 + cksum_off, l4_offload, zero_is_none && !l4_offload);
+    }
+
+    replace(data, off, &new, layout.l3 + 10, false, false);
+
+    data[off..off + 4].copy_from_slice(&new);
+
+    Some(old)
+}
+
+fn rewrite_l4_port_in(data: &mut [u8], which: Endpoint, port: u16, l4_offload: bool) -> Option<u16> {
+    let (l4, proto) = ipv4_layout(data)?.l4?;
+    let off = l4 + if which == Endpoint::Src { 0 } else { 2 };
+    let old = u16::from_be_bytes([data[off], data[off + 1]]);
+    let new = port.to_be_bytes();
+
+    // the ports are not covered by the pseudo-header checksum
+    if !l4_offload {
+        let (cksum_off, zero_is_none) = l4_cksum_off(proto);
+
+        replace(data, off, &new, l4 + cksum_off, false, zero_is_none);
+    }
+
+    data[off..off + 2].copy_from_slice(&new);
+
+    Some(old)
+}
+
+fn first_segment(m: &mut MBuf) -> (&mut [u8], bool) {
+    let l4_offload = m
+        .offload()
+        .intersects(OffloadFlags::PKT_TX_L4_MASK | OffloadFlags::PKT_TX_TCP_SEG);
+    let data = unsafe { slice::from_raw_parts_mut(m.mtod::<u8>().as_ptr(), m.data_len()) };
+
+    (data, l4_offload)
+}
+
+/// Rewrite the source or destination MAC address, returns the previous address.
+pub fn rewrite_mac(m: &mut MBuf, which: Endpoint, addr: &EtherAddr) -> Option<EtherAddr> {
+    let (data, _) = first_segment(m);
+
+    if data.len() < ETHER_HDR_LEN as usize {
+        return None;
+    }
+
+    let off = if which == Endpoint::Src { ETHER_ADDR_LEN } else { 0 };
+    let old = EtherAddr::from_bytes(&data[off..off + ETHER_ADDR_LEN]).ok();
+
+    data[off..off + ETHER_ADDR_LEN].copy_from_slice(addr.octets());
+
+    old
+}
+
+/// Rewrite the source or destination IPv4 address, returns the previous address.
+///
+/// The IPv4 header checksum, and the TCP/UDP checksum of the first fragment are updated incrementally.
+///
+/// Returns `None` if the packet is not an IPv4 packet.
+pub fn rewrite_ipv4_addr(m: &mut MBuf, which: Endpoint, addr: Ipv4Addr) -> Option<Ipv4Addr> {
+    let (data, l4_offload) = first_segment(m);
+
+    rewrite_ipv4_addr_in(data, which, addr, l4_offload)
+}
+
+/// Rewrite the source or destination TCP/UDP port, returns the previous port.
+///
+/// The TCP/UDP checksum is updated incrementally.
+///
+/// Returns `None` if the packet is not a TCP/UDP over IPv4 packet, or is a non-first fragment.
+pub fn rewrite_l4_port(m: &mut MBuf, which: Endpoint, port: u16) -> Option<u16> {
+    let (data, l4_offload) = first_segment(m);
+
+    rewrite_l4_port_in(data, which, port, l4_offload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cksum(buf: &[u8]) -> u16 {
+        let mut sum = buf.chunks(2).fold(0u32, |sum, w| {
+            sum + u32::from(u16::from_ne_bytes([w[0], *w.get(1).unwrap_or(&0)]))
+        });
+
+        while sum > 0xffff {
+            sum = (sum >> 16) + (sum & 0xffff);
+        }
+
+        !(sum as u16)
+    }
+
+    fn udp_cksum(pkt: &[u8]) -> u16 {
+        let ip = &pkt[ETHER_HDR_LEN as usize..];
+        let udp = &ip[IPV4_HDR_LEN..];
+        let mut buf = vec![];
+
+        buf.extend_from_slice(&ip[12..20]);
+        buf.extend_from_slice(&[0, libc::IPPROTO_UDP as u8]);
+        buf.extend_from_slice(&(udp.len() as u16).to_be_bytes());
+        buf.extend_from_slice(&udp[..6]);
+        buf.extend_from_slice(&udp[8..]);
+
+        cksum(&buf)
+    }
+
+    fn packet() -> Vec<u8> {
+        let mut pkt = vec![0u8; 12];
+
+        pkt.extend_from_slice(&(ETHER_TYPE_VLAN as u16).to_be_bytes());
+        pkt.extend_from_slice(&[0, 100]);
+        pkt.extend_from_slice(&(ETHER_TYPE_IPv4 as u16).to_be_bytes());
+        pkt.extend_from_slice(&[0x45, 0, 0, 33, 0, 1, 0x40, 0, 64, libc::IPPROTO_UDP as u8, 0, 0]);
+        pkt.extend_from_slice(&[192, 168, 1, 1, 10, 0, 0, 1]);
+        pkt.extend_from_slice(&[0x30, 0x39, 0, 53, 0, 13, 0, 0]);
+        pkt.extend_from_slice(b"hello");
+
+        let (ip, udp) = (
+            ETHER_HDR_LEN as usize + VLAN_HDR_LEN,
+            ETHER_HDR_LEN as usize + VLAN_HDR_LEN + 20,
+        );
+        let c = cksum(&pkt[ip..udp]);
+        pkt[ip + 10..ip + 12].copy_from_slice(&c.to_ne_bytes());
+
+        pkt
+    }
+
+    fn check(pkt: &[u8]) {
+        let ip = ETHER_HDR_LEN as usize + VLAN_HDR_LEN;
+        let udp = ip + IPV4_HDR_LEN;
+
+        assert_eq!(cksum(&pkt[ip..udp]), 0);
+
+        let mut untagged = pkt[..12].to_vec();
+        untagged.extend_from_slice(&pkt[16..]);
+
+        let c = udp_cksum(&untagged);
+        assert_eq!(u16::from_ne_bytes([pkt[udp + 6], pkt[udp + 7]]), c);
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let mut pkt = packet();
+        let udp = ETHER_HDR_LEN as usize + VLAN_HDR_LEN + IPV4_HDR_LEN;
+
+        let c = udp_cksum(&{
+            let mut untagged = pkt[..12].to_vec();
+            untagged.extend_from_slice(&pkt[16..]);
+            untagged
+        });
+        pkt[udp + 6..udp + 8].copy_from_slice(&c.to_ne_bytes());
+        check(&pkt);
+
+        assert_eq!(
+            rewrite_ipv4_addr_in(&mut pkt, Endpoint::Src, Ipv4Addr::new(172, 16, 254, 3), false),
+            Some(Ipv4Addr::new(192, 168, 1, 1))
+        );
+        check(&pkt);
+
+        assert_eq!(
+            rewrite_ipv4_addr_in(&mut pkt, Endpoint::Dst, Ipv4Addr::new(8, 8, 4, 4), false),
+            Some(Ipv4Addr::new(10, 0, 0, 1))
+        );
+        check(&pkt);
+
+        assert_eq!(rewrite_l4_port_in(&mut pkt, Endpoint::Src, 40000, false), Some(12345));
+        assert_eq!(rewrite_l4_port_in(&mut pkt, Endpoint::Dst, 5353, false), Some(53));
+        check(&pkt);
+
+        // the UDP checksum is not used
+        pkt[udp + 6..udp + 8].copy_from_slice(&[0, 0]);
+        rewrite_l4_port_in(&mut pkt, Endpoint::Dst, 53, false);
+        assert_eq!(&pkt[udp + 6..udp + 8], &[0, 0]);
+
+        assert!(rewrite_l4_port_in(&mut pkt[..udp], Endpoint::Dst, 53, false).is_none());
+
+        // the IHL is less than the minimum, or exceeds the packet
+        let ip = ETHER_HDR_LEN as usize + VLAN_HDR_LEN;
+
+        for &ihl in &[0x44, 0x4f] {
+            let mut pkt = packet();
+
+            pkt[ip] = ihl;
+
+            assert!(ipv4_layout(&pkt).is_none());
+            assert!(rewrite_ipv4_addr_in(&mut pkt, Endpoint::Src, Ipv4Addr::new(172, 16, 254, 3), false).is_none());
+            assert!(rewrite_l4_port_in(&mut pkt, Endpoint::Src, 40000, false).is_none());
+        }
+    }
+}
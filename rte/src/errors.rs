@@ -123,8 +123,37 @@ pub enum ErrorKind {
     InvalidLogLevel(u32),
     #[fail(display = "cmdline parse error, {}", _0)]
     CmdLineParseError(i32),
+    #[fail(display = "cmdline error, {}", _0)]
+    CmdLineError(String),
     #[fail(display = "{}", _0)]
     OsError(i32),
+    #[fail(display = "invalid filter, {}", _0)]
+    InvalidFilter(String),
+    #[fail(display = "flow error, {}", _0)]
+    FlowError(String),
+    #[fail(display = "invalid name, {}", _0)]
+    InvalidName(String),
+    #[fail(display = "invalid NUMA socket, {}", _0)]
+    InvalidSocket(i32),
+    #[fail(display = "invalid core set, {}", _0)]
+    InvalidCoreSet(String),
+    #[fail(display = "invalid port mask, {}", _0)]
+    InvalidPortMask(String),
+    #[fail(display = "invalid packet log, {}", _0)]
+    InvalidPktLog(String),
+    #[fail(display = "crypto error, {}", _0)]
+    CryptoError(String),
+    #[fail(display = "invalid version, {}", _0)]
+    InvalidVersion(String),
+    #[fail(display = "invalid table, {}", _0)]
+    InvalidTable(String),
+    #[fail(
+        display = "PMDs dropped by the linker, {}, link with `-Wl,--no-as-needed` or load them with the `-d` option",
+        _0
+    )]
+    DroppedPmds(String),
+    #[fail(display = "unsupported RSS hash of flow types, {}", _0)]
+    UnsupportedRss(String),
 }
 
 pub fn rte_error() -> Error {
@@ -1,22 +1,39 @@
+use std::cell::Cell;
+use std::cmp;
 use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
 use std::mem;
-use std::ops::Range;
-use std::os::raw::c_void;
+use std::ops::{Bound, Deref, RangeBounds};
+use std::os::raw::{c_char, c_void};
+use std::os::unix::io::RawFd;
 use std::ptr;
+use std::slice;
+use std::sync::Mutex;
+use std::time::Duration;
 
+use arrayvec::{Array, ArrayVec};
+use failure::{Error, Fail};
 use libc;
+use num_traits::FromPrimitive;
 
 use ffi;
 
+use bond::BondedDevice;
+use common::{delay_us, get_tsc_hz, pause, rdtsc};
 use dev;
-use errors::{AsResult, ErrorKind::OsError, Result};
+use errors::{rte_error, AsResult, ErrorKind, ErrorKind::OsError, Result, RteError};
 use ether;
+use flow;
 use malloc;
-use mbuf;
-use memory::SocketId;
-use mempool;
+use mbuf::{self, MBufPool};
+use memory::Socket;
+use mempool::{self, MemPool};
+use sfp;
 use utils::AsRaw;
 
+pub use rte_net_types::portmask::{ParsePortMaskError, PortMask};
+
 pub type PortId = u16;
 pub type QueueId = u16;
 
@@ -41,20 +58,42 @@ pub trait EthDevice {
     /// Retrieve the contextual information of an Ethernet device.
     fn info(&self) -> RawEthDeviceInfo;
 
+    /// The flow types whose RSS hash is supported by the device.
+    fn supported_rss(&self) -> RssHashFunc;
+
     /// Retrieve the general I/O statistics of an Ethernet device.
     fn stats(&self) -> Result<RawEthDeviceStats>;
 
     /// Reset the general I/O statistics of an Ethernet device.
     fn reset_stats(&self) -> &Self;
 
+    /// Retrieve the names of the extended statistics of an Ethernet device, indexed by the id.
+    fn xstats_names(&self) -> Result<Vec<String>>;
+
+    /// Retrieve the extended statistics of an Ethernet device with their names,
+    /// e.g. the per-queue and driver-specific counters.
+    ///
+    /// To poll a few of them periodically, resolve their ids once with `XStatsIds`.
+    fn xstats(&self) -> Result<Vec<(String, u64)>>;
+
+    /// Reset the extended statistics of an Ethernet device, including the general ones.
+    fn xstats_reset(&self) -> &Self;
+
+    /// Map a receive queue to a per-queue statistics counter, in `[0, RTE_ETHDEV_QUEUE_STAT_CNTRS)`.
+    fn set_rx_queue_stats_mapping(&self, queue_id: QueueId, stat_idx: u8) -> Result<&Self>;
+
+    /// Map a transmit queue to a per-queue statistics counter, in `[0, RTE_ETHDEV_QUEUE_STAT_CNTRS)`.
+    fn set_tx_queue_stats_mapping(&self, queue_id: QueueId, stat_idx: u8) -> Result<&Self>;
+
     /// Retrieve the Ethernet address of an Ethernet device.
     fn mac_addr(&self) -> ether::EtherAddr;
 
     /// Set the default MAC address.
     fn set_mac_addr(&self, addr: &[u8; ether::ETHER_ADDR_LEN]) -> Result<&Self>;
 
-    /// Return the NUMA socket to which an Ethernet device is connected
-    fn socket_id(&self) -> SocketId;
+    /// Return the NUMA socket to which an Ethernet device is connected,
+    /// or any socket if it can't be determined.
+    fn socket_id(&self) -> Socket;
 
     /// Check if port_id of device is attached
     fn is_valid(&self) -> bool;
@@ -65,21 +104,78 @@ pub trait EthDevice {
     /// receive descriptors from a memory zone associated with *socket_id*
     /// and initializes each receive descriptor with a network buffer allocated
     /// from the memory pool *mb_pool*.
-    fn rx_queue_setup(
+    ///
+    /// Returns the handle of the queue, which could be moved to the lcore polling it.
+    fn rx_queue_setup<'a>(
         &self,
         rx_queue_id: QueueId,
         nb_rx_desc: u16,
-        rx_conf: Option<ffi::rte_eth_rxconf>,
-        mb_pool: &mut mempool::MemoryPool,
-    ) -> Result<&Self>;
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>>;
+
+    /// The maximum length of the received packets,
+    /// which is `max_rx_pkt_len` of the RX mode if jumbo frame is enabled, or `ETHER_MAX_LEN`.
+    fn max_rx_pkt_len(&self) -> u32;
+
+    /// The maximum size of the packets aggregated by LRO, or `None` if LRO isn't enabled.
+    ///
+    /// The DPDK 18.11 has no dedicated limit, the drivers aggregate the packets up to the maximum RX packet length.
+    fn max_lro_pkt_size(&self) -> Option<u32>;
 
-    /// Allocate and set up a transmit queue for an Ethernet device.
-    fn tx_queue_setup(
+    /// The RX offloads enabled on all the queues of the port.
+    fn rx_offloads(&self) -> RxOffload;
+
+    /// The packet types which could be recognized by the PMD, in the layers of the mask, e.g. `RTE_PTYPE_L2_MASK`.
+    fn supported_ptypes(&self, ptype_mask: u32) -> Result<Vec<u32>>;
+
+    /// Allocate and set up a receive queue, which may scatter the received packets into chained mbufs.
+    ///
+    /// If the maximum RX packet length doesn't fit in the data room of a single mbuf,
+    /// the scattered RX is enabled on the queue, or fails with `ENOTSUP` error
+    /// if the device supports neither the per-queue offload nor it was enabled on the port.
+    fn rx_queue_setup_scattered<'a>(
         &self,
-        tx_queue_id: QueueId,
-        nb_tx_desc: u16,
-        tx_conf: Option<ffi::rte_eth_txconf>,
-    ) -> Result<&Self>;
+        rx_queue_id: QueueId,
+        nb_rx_desc: u16,
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>>;
+
+    /// The TX offloads enabled on all the queues of the port.
+    fn tx_offloads(&self) -> TxOffload;
+
+    /// Allocate and set up a transmit queue for an Ethernet device, returns the handle of the queue.
+    fn tx_queue_setup(&self, tx_queue_id: QueueId, nb_tx_desc: u16, tx_conf: Option<EthTxConf>) -> Result<TxQueue>;
+
+    /// Retrieve the hairpin capability of an Ethernet device.
+    ///
+    /// It fails with `ENOTSUP` error if the DPDK or the device doesn't support the hairpin queues.
+    fn hairpin_capability(&self) -> Result<HairpinCap>;
+
+    /// Allocate and set up a hairpin receive queue, which forwards the received packets
+    /// to its peer transmit queues in the hardware, without involving the CPU.
+    ///
+    /// The hairpin queues are set up after the normal queues of the device,
+    /// and the packets are steered to a hairpin queue with a rte_flow rule, e.g. `FlowAction::Queue`.
+    fn rx_hairpin_queue_setup(&self, rx_queue_id: QueueId, nb_rx_desc: u16, conf: &HairpinConf) -> Result<&Self>;
+
+    /// Allocate and set up a hairpin transmit queue, which sends the packets of its peer receive queues.
+    fn tx_hairpin_queue_setup(&self, tx_queue_id: QueueId, nb_tx_desc: u16, conf: &HairpinConf) -> Result<&Self>;
+
+    /// Bind the hairpin transmit queues of the device to the receive queues of the peer port,
+    /// or all the peer ports with `RTE_MAX_ETHPORTS`, after both ports are started.
+    ///
+    /// It's only required if the queues were set up with `HairpinConf::with_manual_bind`.
+    fn hairpin_bind(&self, rx_port: PortId) -> Result<&Self>;
+
+    /// Unbind the hairpin transmit queues of the device from the receive queues of the peer port,
+    /// or all the peer ports with `RTE_MAX_ETHPORTS`.
+    fn hairpin_unbind(&self, rx_port: PortId) -> Result<&Self>;
+
+    /// The peer ports of the hairpin queues, the ports of the peer receive queues if `tx`,
+    /// or the ports of the peer transmit queues.
+    fn hairpin_peer_ports(&self, tx: bool) -> Result<Vec<PortId>>;
 
     /// Enable receipt in promiscuous mode for an Ethernet device.
     fn promiscuous_enable(&self) -> &Self;
@@ -90,6 +186,19 @@ pub trait EthDevice {
     /// Return the value of promiscuous mode for an Ethernet device.
     fn is_promiscuous_enabled(&self) -> Result<bool>;
 
+    /// Enable the receipt of any multicast frame by an Ethernet device.
+    fn allmulticast_enable(&self) -> &Self;
+
+    /// Disable the receipt of all multicast frames by an Ethernet device.
+    fn allmulticast_disable(&self) -> &Self;
+
+    /// Return the value of allmulticast mode for an Ethernet device.
+    fn is_allmulticast_enabled(&self) -> Result<bool>;
+
+    /// Set the list of multicast addresses to filter on an Ethernet device,
+    /// an empty list flushes the filtered addresses.
+    fn set_mc_addr_list(&self, addrs: &[ether::EtherAddr]) -> Result<&Self>;
+
     /// Retrieve the MTU of an Ethernet device.
     fn mtu(&self) -> Result<u16>;
 
@@ -141,6 +250,38 @@ pub trait EthDevice {
     /// Stop specified TX queue of a port
     fn tx_queue_stop(&self, tx_queue_id: QueueId) -> Result<&Self>;
 
+    /// Retrieve the information of a RX queue, e.g. its mempool, number of descriptors and offloads.
+    fn rx_queue_info(&self, rx_queue_id: QueueId) -> Result<RxQueueInfo>;
+
+    /// Retrieve the information of a TX queue, e.g. its number of descriptors and offloads.
+    fn tx_queue_info(&self, tx_queue_id: QueueId) -> Result<TxQueueInfo>;
+
+    /// Set up and start a receive queue, even after the device has been started.
+    ///
+    /// The queue must be within the number of RX queues configured for the device,
+    /// so the port should be configured with the most queues it may use, and only set up some of them at first.
+    /// It fails with `ENOTSUP` error if the device has been started and doesn't support
+    /// `RTE_ETH_DEV_CAPA_RUNTIME_RX_QUEUE_SETUP`.
+    fn add_rx_queue<'a>(
+        &self,
+        rx_queue_id: QueueId,
+        nb_rx_desc: u16,
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>>;
+
+    /// Stop a receive queue of a started device, which could be added back later.
+    fn remove_rx_queue(&self, rx_queue: RxQueue) -> Result<&Self>;
+
+    /// Set up and start a transmit queue, even after the device has been started.
+    ///
+    /// It fails with `ENOTSUP` error if the device has been started and doesn't support
+    /// `RTE_ETH_DEV_CAPA_RUNTIME_TX_QUEUE_SETUP`.
+    fn add_tx_queue(&self, tx_queue_id: QueueId, nb_tx_desc: u16, tx_conf: Option<EthTxConf>) -> Result<TxQueue>;
+
+    /// Stop a transmit queue of a started device, which could be added back later.
+    fn remove_tx_queue(&self, tx_queue: TxQueue) -> Result<&Self>;
+
     /// Start an Ethernet device.
     fn start(&self) -> Result<&Self>;
 
@@ -153,527 +294,4093 @@ pub trait EthDevice {
     /// Retrieve a burst of input packets from a receive queue of an Ethernet device.
     fn rx_burst(&self, queue_id: QueueId, rx_pkts: &mut [Option<mbuf::MBuf>]) -> usize;
 
+    /// Retrieve a burst of input packets into the uninitialized array,
+    /// returns the number of received packets, which are initialized at the front of the array.
+    fn rx_burst_uninit(&self, queue_id: QueueId, rx_pkts: &mut [mem::MaybeUninit<mbuf::MBuf>]) -> usize;
+
+    /// Retrieve a burst of input packets into the free room of the array, returns the number of received packets.
+    fn rx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, queue_id: QueueId, rx_pkts: &mut ArrayVec<A>) -> usize;
+
     /// Send a burst of output packets on a transmit queue of an Ethernet device.
     fn tx_burst<T: AsRaw<Raw = mbuf::RawMBuf>>(&self, queue_id: QueueId, rx_pkts: &mut [T]) -> usize;
 
+    /// Send a burst of output packets, returns the number of sent packets.
+    ///
+    /// The sent packets are owned by the driver and removed from the front of the array,
+    /// the unsent packets are left in the array to be retried or dropped.
+    fn tx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, queue_id: QueueId, tx_pkts: &mut ArrayVec<A>) -> usize;
+
+    /// Send all the packets on a transmit queue, retrying the unsent ones with the policy.
+    ///
+    /// The sent packets are owned by the driver, the packets still unsent after the retries are freed.
+    fn tx_burst_all(&self, queue_id: QueueId, tx_pkts: Vec<mbuf::MBuf>, policy: &TxRetryPolicy) -> TxBurstResult;
+
     /// Read VLAN Offload configuration from an Ethernet device
     fn vlan_offload(&self) -> Result<EthVlanOffloadMode>;
 
     /// Set VLAN offload configuration on an Ethernet device
     fn set_vlan_offload(&self, mode: EthVlanOffloadMode) -> Result<&Self>;
+
+    /// Retrieve the link speeds the Ethernet device was configured with.
+    fn link_speeds(&self) -> LinkSpeed;
+
+    /// Change the link speeds to be advertised or forced on an Ethernet device.
+    ///
+    /// The device is reconfigured with its current queue numbers and configuration, including the RSS key,
+    /// so it must be stopped first, and its queues must be set up again before it is restarted.
+    ///
+    /// Use `LinkSpeed::fixed()` to pin a speed and disable link autonegotiation,
+    /// or `LinkSpeed::ETH_LINK_SPEED_AUTONEG` to advertise all supported speeds.
+    fn set_link_speeds(&self, speeds: LinkSpeed) -> Result<&Self>;
+
+    /// Change the RX and TX offloads enabled on all the queues of the port.
+    ///
+    /// The device is reconfigured with its current queue numbers and configuration, including the RSS key,
+    /// so it must be stopped first, and its queues must be set up again before it is restarted.
+    fn set_offloads(&self, rx_offloads: RxOffload, tx_offloads: TxOffload) -> Result<&Self>;
+
+    /// Retrieve the link flow control configuration, e.g. the pause frames.
+    fn flow_ctrl(&self) -> Result<EthFcConf>;
+
+    /// Configure the link flow control.
+    fn set_flow_ctrl(&self, conf: &EthFcConf) -> Result<&Self>;
+
+    /// Adjust the number of RX and TX descriptors to the limits of the device,
+    /// returns the adjusted numbers.
+    fn adjust_nb_rx_tx_desc(&self, nb_rx_desc: u16, nb_tx_desc: u16) -> Result<(u16, u16)>;
+
+    /// The current configuration of an Ethernet device, as saved by the ethdev layer.
+    fn current_conf(&self) -> EthConf;
+
+    /// Reconfigure an Ethernet device at runtime, the callback changes the current configuration.
+    ///
+    /// The new configuration is validated against the device capabilities before the device is stopped.
+    /// The device is configured with the same number of queues, the queues are setup again
+    /// with their saved descriptors, configuration and mempool, then the device is restarted
+    /// if it was started, and the promiscuous and allmulticast modes are restored.
+    ///
+    /// If the device rejects the new configuration, the previous configuration is restored.
+    ///
+    /// Returns `ENOTSUP` before the device is stopped, if the driver doesn't report the queue information,
+    /// or the device has the hairpin queues, then the caller must stop and configure the device,
+    /// and setup its queues again.
+    fn reconfigure<F: FnOnce(&mut EthConf)>(&self, with: F) -> Result<&Self>;
+
+    /// Retrieve size of device EEPROM
+    fn eeprom_length(&self) -> Result<usize>;
+
+    /// Retrieve the device EEPROM starting from the offset, which fills the whole buffer.
+    fn eeprom(&self, offset: usize, buf: &mut [u8]) -> Result<()>;
+
+    /// Program the device EEPROM starting from the offset with the provided data.
+    fn set_eeprom(&self, offset: usize, data: &[u8]) -> Result<&Self>;
+
+    /// Retrieve the type and size of plugin module EEPROM
+    fn module_info(&self) -> Result<sfp::ModuleInfo>;
+
+    /// Retrieve the raw data of plugin module EEPROM
+    fn module_eeprom_data(&self) -> Result<(sfp::ModuleType, Vec<u8>)>;
+
+    /// Restrict the ingress traffic to the flow rules, the other packets are dropped by the NIC.
+    ///
+    /// It must be called before the port is configured.
+    fn flow_isolate(&self, on: bool) -> Result<&Self>;
+
+    /// Enable the interrupt of the RX queue, which fires when a packet is received.
+    ///
+    /// The port must be configured with `intr_conf.rxq` enabled.
+    fn rx_intr_enable(&self, queue_id: QueueId) -> Result<&Self>;
+
+    /// Disable the interrupt of the RX queue.
+    fn rx_intr_disable(&self, queue_id: QueueId) -> Result<&Self>;
+
+    /// Get the fd of the interrupt of the RX queue, which could be waited on with epoll.
+    fn rx_intr_fd(&self, queue_id: QueueId) -> Result<RawFd>;
+
+    /// Retrieve and parse the plugin module EEPROM, e.g. the vendor, part number and diagnostics of SFP/QSFP modules.
+    fn module_eeprom(&self) -> Result<sfp::Module> {
+        let (kind, data) = self.module_eeprom_data()?;
+
+        sfp::Module::parse(kind, &data).ok_or_else(|| RteError(libc::ENOTSUP).into())
+    }
 }
 
-/// Get the total number of Ethernet devices that have been successfully initialized
-/// by the matching Ethernet driver during the PCI probing phase.
+/// Get the number of ports which are usable for the application.
 ///
-/// All devices whose port identifier is in the range [0, rte::ethdev::count() - 1]
-/// can be operated on by network applications immediately after invoking rte_eal_init().
-/// If the application unplugs a port using hotplug function,
-/// The enabled port numbers may be noncontiguous.
-/// In the case, the applications need to manage enabled port by themselves.
+/// The ports owned by another entity, for example the slaves of a bonded device,
+/// are not counted, and the port ids may be noncontiguous after a port is unplugged,
+/// use `devices()` to enumerate them.
+#[deprecated(note = "use `count_avail()` or `count_total()` instead")]
 pub fn count() -> u16 {
-    unsafe { ffi::rte_eth_dev_count() }
+    count_avail()
 }
 
-pub fn devices() -> Range<PortId> {
-    0..count()
+/// Get the number of ports which are usable for the application.
+///
+/// The ports owned by another entity are not counted.
+pub fn count_avail() -> u16 {
+    unsafe { ffi::rte_eth_dev_count_avail() }
 }
 
-impl EthDevice for PortId {
-    fn portid(&self) -> PortId {
-        *self
-    }
+/// Get the total number of ports which are allocated.
+///
+/// Some devices may not be available for the application.
+pub fn count_total() -> u16 {
+    unsafe { ffi::rte_eth_dev_count_total() }
+}
 
-    fn configure(&self, nb_rx_queue: QueueId, nb_tx_queue: QueueId, conf: &EthConf) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_dev_configure(*self,
-                                       nb_rx_queue,
-                                       nb_tx_queue,
-                                       RawEthConf::from(conf).as_raw())
-        }; ok => { self })
-    }
+/// The valid ports which are not owned by another entity.
+///
+/// The ports are enumerated lazily, so a port which is unplugged during the iteration is skipped.
+#[derive(Clone, Copy, Debug)]
+pub struct ValidPorts {
+    next: u32,
+}
 
-    fn info(&self) -> RawEthDeviceInfo {
-        let mut info: RawEthDeviceInfo = Default::default();
+impl Iterator for ValidPorts {
+    type Item = PortId;
 
-        unsafe { ffi::rte_eth_dev_info_get(*self, &mut info) }
+    fn next(&mut self) -> Option<PortId> {
+        if self.next >= ffi::RTE_MAX_ETHPORTS {
+            return None;
+        }
 
-        info
-    }
+        let port_id =
+            unsafe { ffi::rte_eth_find_next_owned_by(self.next as PortId, u64::from(ffi::RTE_ETH_DEV_NO_OWNER)) };
 
-    fn stats(&self) -> Result<RawEthDeviceStats> {
-        let mut stats: RawEthDeviceStats = Default::default();
+        if port_id >= u64::from(ffi::RTE_MAX_ETHPORTS) {
+            self.next = ffi::RTE_MAX_ETHPORTS;
 
-        rte_check!(unsafe {
-            ffi::rte_eth_stats_get(*self, &mut stats)
-        }; ok => { stats })
+            None
+        } else {
+            self.next = port_id as u32 + 1;
+
+            Some(port_id as PortId)
+        }
     }
+}
 
-    fn reset_stats(&self) -> &Self {
-        unsafe { ffi::rte_eth_stats_reset(*self) };
+/// Iterate the valid ports which are not owned by another entity, like `RTE_ETH_FOREACH_DEV`.
+pub fn valid_ports() -> ValidPorts {
+    ValidPorts { next: 0 }
+}
 
-        self
-    }
+/// Iterate the ports which are usable for the application.
+///
+/// The unplugged ports and the ones owned by another entity are skipped.
+pub fn devices() -> ValidPorts {
+    valid_ports()
+}
 
-    fn mac_addr(&self) -> ether::EtherAddr {
-        unsafe {
-            let mut addr: ffi::ether_addr = mem::zeroed();
+/// The port masks checked against the ports of the EAL.
+pub trait PortMaskExt: Sized {
+    /// The mask of all the ports supported by the EAL.
+    fn all() -> Self;
 
-            ffi::rte_eth_macaddr_get(*self, &mut addr);
+    /// The mask of the ports which are usable for the application.
+    fn available() -> Self;
 
-            ether::EtherAddr::from(addr.addr_bytes)
-        }
-    }
+    /// Check the mask has no port beyond the ones supported by the EAL.
+    fn checked(self) -> Result<Self>;
 
-    fn set_mac_addr(&self, addr: &[u8; ether::ETHER_ADDR_LEN]) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_dev_default_mac_addr_set(*self, addr.as_ptr() as * mut _)
-        }; ok => { self })
-    }
+    /// The ports in the mask which are usable for the application.
+    fn enabled(&self) -> Self;
+}
 
-    fn socket_id(&self) -> SocketId {
-        unsafe { ffi::rte_eth_dev_socket_id(*self) }
+impl PortMaskExt for PortMask {
+    fn all() -> Self {
+        PortMask::first(ffi::RTE_MAX_ETHPORTS)
     }
 
-    fn is_valid(&self) -> bool {
-        unsafe { ffi::rte_eth_dev_is_valid_port(*self) != 0 }
+    fn available() -> Self {
+        devices().collect()
     }
 
-    fn rx_queue_setup(
-        &self,
-        rx_queue_id: QueueId,
-        nb_rx_desc: u16,
-        rx_conf: Option<ffi::rte_eth_rxconf>,
-        mb_pool: &mut mempool::MemoryPool,
-    ) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_rx_queue_setup(*self,
-                                        rx_queue_id,
-                                        nb_rx_desc,
-                                        self.socket_id() as u32,
-                                        rx_conf.as_ref().map(|conf| conf as *const _).unwrap_or(ptr::null()),
-                                        mb_pool.as_raw())
-        }; ok => { self })
+    fn checked(self) -> Result<Self> {
+        if self & Self::all() != self {
+            Err(ErrorKind::InvalidPortMask(format!(
+                "{}, the EAL supports up to {} ports",
+                self,
+                ffi::RTE_MAX_ETHPORTS
+            ))
+            .into())
+        } else {
+            Ok(self)
+        }
     }
 
-    fn tx_queue_setup(
-        &self,
-        tx_queue_id: QueueId,
-        nb_tx_desc: u16,
-        tx_conf: Option<ffi::rte_eth_txconf>,
-    ) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_tx_queue_setup(*self,
-                                        tx_queue_id,
-                                        nb_tx_desc,
-                                        self.socket_id() as u32,
-                                        tx_conf.as_ref().map(|conf| conf as *const _).unwrap_or(ptr::null()))
-        }; ok => { self })
+    fn enabled(&self) -> Self {
+        *self & Self::available()
     }
+}
 
-    fn promiscuous_enable(&self) -> &Self {
-        unsafe { ffi::rte_eth_promiscuous_enable(*self) };
+/// The errors of the ports, which failed in a multi-port operation.
+#[derive(Debug)]
+pub struct PortErrors(pub Vec<(PortId, Error)>);
 
-        self
+impl fmt::Display for PortErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &(port_id, ref err)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "port {}: {}", port_id, err)?;
+        }
+
+        Ok(())
     }
+}
 
-    fn promiscuous_disable(&self) -> &Self {
-        unsafe { ffi::rte_eth_promiscuous_disable(*self) };
+impl Fail for PortErrors {}
+
+/// The way to wait before retrying the unsent packets of a burst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxBackoff {
+    /// Retry immediately.
+    Spin,
+    /// Pause the CPU for the number of times before retrying.
+    Pause(u32),
+    /// Wait the number of microseconds before retrying.
+    DelayUs(u32),
+}
 
-        self
+impl TxBackoff {
+    fn wait(&self) {
+        match *self {
+            TxBackoff::Spin => {}
+            TxBackoff::Pause(n) => {
+                for _ in 0..n {
+                    pause();
+                }
+            }
+            TxBackoff::DelayUs(us) => delay_us(us),
+        }
     }
+}
 
-    fn is_promiscuous_enabled(&self) -> Result<bool> {
-        let ret = unsafe { ffi::rte_eth_promiscuous_get(*self) };
+/// The default number of retries, when the transmit queue is full.
+pub const DEFAULT_TX_RETRIES: usize = 4;
+
+/// The default microseconds waiting for the transmit queue to drain.
+pub const DEFAULT_TX_WAIT_US: u32 = 10;
+
+/// The policy to retry the packets which the transmit queue could not take.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxRetryPolicy {
+    /// The maximum number of retries after the first burst.
+    pub max_retries: usize,
+    /// The way to wait before each retry.
+    pub backoff: TxBackoff,
+    /// Reset the retries when some packets were sent.
+    pub reset_on_progress: bool,
+}
 
-        rte_check!(ret; ok => { ret != 0 })
+impl Default for TxRetryPolicy {
+    fn default() -> Self {
+        TxRetryPolicy {
+            max_retries: DEFAULT_TX_RETRIES,
+            backoff: TxBackoff::DelayUs(DEFAULT_TX_WAIT_US),
+            reset_on_progress: false,
+        }
     }
+}
 
-    fn mtu(&self) -> Result<u16> {
-        let mut mtu: u16 = 0;
+impl TxRetryPolicy {
+    /// Drop the unsent packets without retry.
+    pub fn no_retry() -> Self {
+        TxRetryPolicy {
+            max_retries: 0,
+            backoff: TxBackoff::Spin,
+            reset_on_progress: false,
+        }
+    }
 
-        rte_check!(unsafe { ffi::rte_eth_dev_get_mtu(*self, &mut mtu)}; ok => { mtu })
+    /// Set the maximum number of retries.
+    pub fn with_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
     }
 
-    fn set_mtu(&self, mtu: u16) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_set_mtu(*self, mtu) }; ok => { self })
+    /// Set the way to wait before each retry.
+    pub fn with_backoff(mut self, backoff: TxBackoff) -> Self {
+        self.backoff = backoff;
+        self
     }
 
-    fn set_vlan_filter(&self, vlan_id: u16, on: bool) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_dev_vlan_filter(*self, vlan_id, bool_value!(on) as i32)
-        }; ok => { self })
+    /// Reset the retries when some packets were sent.
+    pub fn with_reset_on_progress(mut self, reset: bool) -> Self {
+        self.reset_on_progress = reset;
+        self
     }
 
-    fn link(&self) -> EthLink {
-        let mut link = rte_sys::rte_eth_link::default();
+    /// Send `total` packets with the `send` function, which sends the packets from the offset.
+    pub(crate) fn run<F: FnMut(usize) -> usize>(&self, total: usize, mut send: F) -> TxBurstResult {
+        let mut res = TxBurstResult::default();
+        let mut retries = self.max_retries;
 
-        unsafe { ffi::rte_eth_link_get(*self, &mut link as *mut _) }
+        while res.sent < total {
+            let n = send(res.sent);
 
-        EthLink {
-            speed: link.link_speed,
-            duplex: link.link_duplex() != 0,
-            autoneg: link.link_autoneg() != 0,
-            up: link.link_status() != 0,
-        }
-    }
+            res.sent += n;
 
-    fn link_nowait(&self) -> EthLink {
-        let mut link = rte_sys::rte_eth_link::default();
+            if res.sent == total {
+                break;
+            }
 
-        unsafe { ffi::rte_eth_link_get_nowait(*self, &mut link as *mut _) }
+            if n > 0 && self.reset_on_progress {
+                retries = self.max_retries;
+            }
 
-        EthLink {
-            speed: link.link_speed,
-            duplex: link.link_duplex() != 0,
-            autoneg: link.link_autoneg() != 0,
-            up: link.link_status() != 0,
+            if retries == 0 {
+                break;
+            }
+
+            self.backoff.wait();
+
+            retries -= 1;
+            res.retries += 1;
         }
-    }
 
-    fn set_link_up(&self) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_set_link_up(*self) }; ok => { self })
+        res.dropped = total - res.sent;
+        res
     }
+}
 
-    fn set_link_down(&self) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_set_link_down(*self) }; ok => { self })
-    }
+/// The result of sending a burst with `tx_burst_all`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TxBurstResult {
+    /// The number of packets sent.
+    pub sent: usize,
+    /// The number of packets freed after the retries.
+    pub dropped: usize,
+    /// The number of retries.
+    pub retries: usize,
+}
 
-    fn rx_queue_start(&self, rx_queue_id: QueueId) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_rx_queue_start(*self, rx_queue_id) }; ok => { self })
+impl ::std::ops::AddAssign for TxBurstResult {
+    fn add_assign(&mut self, other: Self) {
+        self.sent += other.sent;
+        self.dropped += other.dropped;
+        self.retries += other.retries;
     }
+}
 
-    fn rx_queue_stop(&self, rx_queue_id: QueueId) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_rx_queue_stop(*self, rx_queue_id) }; ok => { self })
-    }
+/// The driver name of the bonded device.
+const BONDING_DRIVER_NAME: &str = "net_bonding";
 
-    fn tx_queue_start(&self, tx_queue_id: QueueId) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_tx_queue_start(*self, tx_queue_id) }; ok => { self })
+/// The ports which must be started before the port, e.g. the slaves of a bonded device.
+fn dependencies(port_id: PortId) -> Vec<PortId> {
+    if port_id.info().driver_name() == BONDING_DRIVER_NAME {
+        port_id.slaves().unwrap_or_default()
+    } else {
+        vec![]
     }
+}
 
-    fn tx_queue_stop(&self, tx_queue_id: QueueId) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_tx_queue_stop(*self, tx_queue_id) }; ok => { self })
-    }
+/// Sort the ports in dependency order, the dependencies in the list come before their dependents.
+fn dependency_order<F: Fn(PortId) -> Vec<PortId>>(ports: &[PortId], deps: F) -> Vec<PortId> {
+    fn visit<F: Fn(PortId) -> Vec<PortId>>(
+        port_id: PortId,
+        ports: &[PortId],
+        deps: &F,
+        visiting: &mut Vec<PortId>,
+        order: &mut Vec<PortId>,
+    ) {
+        if order.contains(&port_id) || visiting.contains(&port_id) {
+            return;
+        }
 
-    fn start(&self) -> Result<&Self> {
-        rte_check!(unsafe { ffi::rte_eth_dev_start(*self) }; ok => { self })
-    }
+        visiting.push(port_id);
 
-    fn stop(&self) -> &Self {
-        unsafe { ffi::rte_eth_dev_stop(*self) };
+        for dep in deps(port_id) {
+            if ports.contains(&dep) {
+                visit(dep, ports, deps, visiting, order);
+            }
+        }
 
-        self
+        visiting.pop();
+        order.push(port_id);
     }
 
-    fn close(&self) -> &Self {
-        unsafe { ffi::rte_eth_dev_close(*self) };
+    let mut order = Vec::with_capacity(ports.len());
 
-        self
+    for &port_id in ports {
+        visit(port_id, ports, &deps, &mut vec![], &mut order);
     }
 
-    fn rx_burst(&self, queue_id: QueueId, rx_pkts: &mut [Option<mbuf::MBuf>]) -> usize {
-        unsafe {
-            ffi::_rte_eth_rx_burst(*self, queue_id, rx_pkts.as_mut_ptr() as *mut _, rx_pkts.len() as u16) as usize
+    order
+}
+
+/// The port has been started.
+fn is_started(port_id: PortId) -> bool {
+    dev_data(port_id).dev_started() != 0
+}
+
+/// Start the ports in dependency order, the slaves are started before their bonded device.
+///
+/// The ports are configured and their queues are setup before, and should be started before
+/// the KNI devices are attached to them.
+///
+/// All the ports are tried, if any of them fails to start,
+/// the ports started by this call are stopped in reverse order, and the errors are returned as `PortErrors`.
+pub fn start_all(ports: &[PortId]) -> Result<()> {
+    let mut started = vec![];
+    let mut errors = vec![];
+
+    for port_id in dependency_order(ports, dependencies) {
+        if is_started(port_id) {
+            continue;
+        }
+
+        match port_id.start() {
+            Ok(_) => started.push(port_id),
+            Err(err) => errors.push((port_id, err)),
         }
     }
 
-    fn tx_burst<T: AsRaw<Raw = mbuf::RawMBuf>>(&self, queue_id: QueueId, rx_pkts: &mut [T]) -> usize {
-        unsafe {
-            if rx_pkts.is_empty() {
-                ffi::_rte_eth_tx_burst(*self, queue_id, ptr::null_mut(), 0) as usize
-            } else {
-                ffi::_rte_eth_tx_burst(*self, queue_id, rx_pkts.as_mut_ptr() as *mut _, rx_pkts.len() as u16) as usize
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        for port_id in started.into_iter().rev() {
+            port_id.stop();
+        }
+
+        Err(PortErrors(errors).into())
+    }
+}
+
+/// Stop the ports in reverse dependency order, the bonded device is stopped before its slaves.
+pub fn stop_all(ports: &[PortId]) {
+    for port_id in dependency_order(ports, dependencies).into_iter().rev() {
+        port_id.stop();
+    }
+}
+
+/// Register the event callbacks for all the ports, including the ports probed later.
+pub const RTE_ETH_ALL: PortId = ffi::RTE_ETH_ALL as PortId;
+
+/// The event of an Ethernet device.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
+pub enum EthEvent {
+    /// Unknown event type.
+    Unknown = ffi::rte_eth_event_type::RTE_ETH_EVENT_UNKNOWN,
+    /// The link status changed, the `lsc_intr` must be enabled in the configuration.
+    LinkStatusChange = ffi::rte_eth_event_type::RTE_ETH_EVENT_INTR_LSC,
+    /// The state of a queue changed.
+    QueueState = ffi::rte_eth_event_type::RTE_ETH_EVENT_QUEUE_STATE,
+    /// The device must be reset with `rte_eth_dev_reset`.
+    Reset = ffi::rte_eth_event_type::RTE_ETH_EVENT_INTR_RESET,
+    /// A message from the VF is received by the PF.
+    VfMbox = ffi::rte_eth_event_type::RTE_ETH_EVENT_VF_MBOX,
+    /// An event of the MACsec offload.
+    Macsec = ffi::rte_eth_event_type::RTE_ETH_EVENT_MACSEC,
+    /// The device is being removed, the `rmv` interrupt must be enabled in the configuration.
+    Remove = ffi::rte_eth_event_type::RTE_ETH_EVENT_INTR_RMV,
+    /// The port is probed.
+    New = ffi::rte_eth_event_type::RTE_ETH_EVENT_NEW,
+    /// The port is released.
+    Destroy = ffi::rte_eth_event_type::RTE_ETH_EVENT_DESTROY,
+    /// An event of the IPsec offload.
+    Ipsec = ffi::rte_eth_event_type::RTE_ETH_EVENT_IPSEC,
+}
+
+/// A callback of the events of Ethernet devices, which is unregistered when dropped.
+///
+/// The callbacks are called on the interrupt thread of the EAL, or on the lcore which probes
+/// or releases the port for the `New` and `Destroy` events. They should not block, and must not
+/// drop their own registration, e.g. a `Remove` event could be handled by sending a message
+/// to the main lcore, which detaches the device.
+pub struct EventCallback {
+    port_id: PortId,
+    event: EthEvent,
+    cb_fn: ffi::rte_eth_dev_cb_fn,
+    ctx: *mut c_void,
+    drop_ctx: unsafe fn(*mut c_void),
+}
+
+unsafe impl Send for EventCallback {}
+
+impl Drop for EventCallback {
+    fn drop(&mut self) {
+        loop {
+            let ret =
+                unsafe { ffi::rte_eth_dev_callback_unregister(self.port_id, self.event as u32, self.cb_fn, self.ctx) };
+
+            match ret {
+                0 => {
+                    unsafe { (self.drop_ctx)(self.ctx) };
+
+                    break;
+                }
+                // the callback is running on the other thread
+                ret if ret == -libc::EAGAIN => pause(),
+                ret => {
+                    // the callback may still be called, e.g. the port has been released
+                    warn!(
+                        "failed to unregister the {:?} event callback of port {}, {}",
+                        self.event,
+                        self.port_id,
+                        RteError(-ret)
+                    );
+
+                    break;
+                }
             }
         }
     }
+}
 
-    fn vlan_offload(&self) -> Result<EthVlanOffloadMode> {
-        let mode = unsafe { ffi::rte_eth_dev_get_vlan_offload(*self) };
+impl EventCallback {
+    /// The port of the callback, or `RTE_ETH_ALL` for all the ports.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
 
-        rte_check!(mode; ok => { EthVlanOffloadMode::from_bits_truncate(mode) })
+    /// The event of the callback.
+    pub fn event(&self) -> EthEvent {
+        self.event
     }
+}
 
-    fn set_vlan_offload(&self, mode: EthVlanOffloadMode) -> Result<&Self> {
-        rte_check!(unsafe {
-            ffi::rte_eth_dev_set_vlan_offload(*self, mode.bits)
-        }; ok => { self })
+/// Register a callback for the event of the port, or of all the ports with `RTE_ETH_ALL`.
+///
+/// ```no_run
+/// use rte::ethdev::{self, EthDevice, EthEvent};
+///
+/// let lsc = ethdev::event_callback_register(ethdev::RTE_ETH_ALL, EthEvent::LinkStatusChange, |port_id, _| {
+///     println!("port {} link {}", port_id, if port_id.link_nowait().up { "up" } else { "down" });
+/// })
+/// .unwrap();
+/// ```
+pub fn event_callback_register<F>(port_id: PortId, event: EthEvent, callback: F) -> Result<EventCallback>
+where
+    F: FnMut(PortId, EthEvent) + Send + 'static,
+{
+    let ctx = Box::into_raw(Box::new(Mutex::new(callback)));
+    let cb_fn: ffi::rte_eth_dev_cb_fn = Some(event_stub::<F>);
+
+    let ret = unsafe { ffi::rte_eth_dev_callback_register(port_id, event as u32, cb_fn, ctx as *mut _) };
+
+    if ret != 0 {
+        unsafe { drop_event_context::<F>(ctx as *mut _) };
+
+        return Err(RteError(-ret).into());
     }
+
+    Ok(EventCallback {
+        port_id,
+        event,
+        cb_fn,
+        ctx: ctx as *mut _,
+        drop_ctx: drop_event_context::<F>,
+    })
 }
 
-pub trait EthDeviceInfo {
-    /// Device Driver name.
-    fn driver_name(&self) -> &str;
+unsafe fn drop_event_context<F>(ctx: *mut c_void) {
+    Box::from_raw(ctx as *mut Mutex<F>);
+}
 
-    fn dev(&self) -> Option<dev::Device>;
+unsafe extern "C" fn event_stub<F>(
+    port_id: PortId,
+    event: ffi::rte_eth_event_type::Type,
+    cb_arg: *mut c_void,
+    _ret_param: *mut c_void,
+) -> libc::c_int
+where
+    F: FnMut(PortId, EthEvent),
+{
+    // the callbacks of all the ports may be called on the different threads
+    let callback = &*(cb_arg as *const Mutex<F>);
+
+    if let Ok(mut callback) = callback.lock() {
+        (callback)(port_id, EthEvent::from_u32(event).unwrap_or(EthEvent::Unknown));
+    }
+
+    0
 }
 
-pub type RawEthDeviceInfo = ffi::rte_eth_dev_info;
+/// A callback of the packets received on a RX queue, which is removed when dropped.
+///
+/// A RX burst running on the other lcore may still call the removed callback,
+/// so the closure is leaked when the callback is dropped, unless it is removed with `remove`.
+pub struct RxCallback {
+    port_id: PortId,
+    queue_id: QueueId,
+    cb: *const ffi::rte_eth_rxtx_callback,
+    ctx: *mut c_void,
+    drop_ctx: unsafe fn(*mut c_void),
+}
 
-impl EthDeviceInfo for RawEthDeviceInfo {
-    #[inline]
-    fn driver_name(&self) -> &str {
-        unsafe { CStr::from_ptr(self.driver_name).to_str().unwrap() }
+unsafe impl Send for RxCallback {}
+
+impl Drop for RxCallback {
+    fn drop(&mut self) {
+        // the closure is leaked, a running RX burst may still call it
+        self.unlink();
     }
+}
 
-    #[inline]
-    fn dev(&self) -> Option<dev::Device> {
-        if self.device.is_null() {
-            None
-        } else {
-            Some(self.device.into())
+impl RxCallback {
+    fn unlink(&self) -> bool {
+        let ret = unsafe { ffi::rte_eth_remove_rx_callback(self.port_id, self.queue_id, self.cb) };
+
+        if ret != 0 {
+            warn!(
+                "failed to remove the RX callback of port {} queue {}, {}",
+                self.port_id,
+                self.queue_id,
+                RteError(-ret)
+            );
         }
+
+        ret == 0
     }
-}
 
-pub trait EthDeviceStats {}
+    /// Remove the callback and free the closure.
+    ///
+    /// # Safety
+    ///
+    /// No RX burst of the queue may be running or started later with the removed callback,
+    /// e.g. the port was stopped, or the lcores polling the queue have been synchronized since the removal.
+    pub unsafe fn remove(self) {
+        if self.unlink() {
+            (self.drop_ctx)(self.ctx);
+        }
 
-pub type RawEthDeviceStats = ffi::rte_eth_stats;
+        mem::forget(self);
+    }
 
-impl EthDeviceStats for RawEthDeviceStats {}
+    /// The port of the callback.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
 
-bitflags! {
-    /// Definitions used for VMDQ pool rx mode setting
-    pub struct EthVmdqRxMode : u16 {
-        /// accept untagged packets.
-        const ETH_VMDQ_ACCEPT_UNTAG     = 0x0001;
-        /// accept packets in multicast table .
-        const ETH_VMDQ_ACCEPT_HASH_MC   = 0x0002;
-        /// accept packets in unicast table.
-        const ETH_VMDQ_ACCEPT_HASH_UC   = 0x0004;
-        /// accept broadcast packets.
-        const ETH_VMDQ_ACCEPT_BROADCAST = 0x0008;
-        /// multicast promiscuous.
-        const ETH_VMDQ_ACCEPT_MULTICAST = 0x0010;
+    /// The RX queue of the callback.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
     }
 }
 
-/// A set of values to identify what method is to be used to route packets to multiple queues.
-bitflags! {
-    pub struct EthRxMultiQueueMode: u32 {
-        const ETH_MQ_RX_RSS_FLAG    = 0x1;
-        const ETH_MQ_RX_DCB_FLAG    = 0x2;
-        const ETH_MQ_RX_VMDQ_FLAG   = 0x4;
+/// A callback of the packets sent to a TX queue, which is removed when dropped.
+///
+/// A TX burst running on the other lcore may still call the removed callback,
+/// so the closure is leaked when the callback is dropped, unless it is removed with `remove`.
+pub struct TxCallback {
+    port_id: PortId,
+    queue_id: QueueId,
+    cb: *const ffi::rte_eth_rxtx_callback,
+    ctx: *mut c_void,
+    drop_ctx: unsafe fn(*mut c_void),
+}
+
+unsafe impl Send for TxCallback {}
+
+impl Drop for TxCallback {
+    fn drop(&mut self) {
+        // the closure is leaked, a running TX burst may still call it
+        self.unlink();
     }
 }
 
-bitflags! {
-    /// Definitions used for VLAN Offload functionality
-    pub struct EthVlanOffloadMode: i32 {
-        /// VLAN Strip  On/Off
-        const ETH_VLAN_STRIP_OFFLOAD  = 0x0001;
-        /// VLAN Filter On/Off
-        const ETH_VLAN_FILTER_OFFLOAD = 0x0002;
-        /// VLAN Extend On/Off
-        const ETH_VLAN_EXTEND_OFFLOAD = 0x0004;
+impl TxCallback {
+    fn unlink(&self) -> bool {
+        let ret = unsafe { ffi::rte_eth_remove_tx_callback(self.port_id, self.queue_id, self.cb) };
+
+        if ret != 0 {
+            warn!(
+                "failed to remove the TX callback of port {} queue {}, {}",
+                self.port_id,
+                self.queue_id,
+                RteError(-ret)
+            );
+        }
 
-        /// VLAN Strip  setting mask
-        const ETH_VLAN_STRIP_MASK     = 0x0001;
-        /// VLAN Filter  setting mask
-        const ETH_VLAN_FILTER_MASK    = 0x0002;
-        /// VLAN Extend  setting mask
-        const ETH_VLAN_EXTEND_MASK    = 0x0004;
-        /// VLAN ID is in lower 12 bits
-        const ETH_VLAN_ID_MAX         = 0x0FFF;
+        ret == 0
+    }
+
+    /// Remove the callback and free the closure.
+    ///
+    /// # Safety
+    ///
+    /// No TX burst of the queue may be running or started later with the removed callback,
+    /// e.g. the port was stopped, or the lcores polling the queue have been synchronized since the removal.
+    pub unsafe fn remove(self) {
+        if self.unlink() {
+            (self.drop_ctx)(self.ctx);
+        }
+
+        mem::forget(self);
+    }
+
+    /// The port of the callback.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The TX queue of the callback.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
     }
 }
 
-/**
- * A set of values to identify what method is to be used to transmit
- * packets using multi-TCs.
- */
-pub type EthTxMultiQueueMode = ffi::rte_eth_tx_mq_mode::Type;
+/// The packets of a RX or TX burst, which are borrowed by a queue callback.
+///
+/// The packets are still owned by the burst, so they could be read and cloned,
+/// and their data and metadata could be modified, but they can't be replaced, moved out or freed.
+pub struct BurstView<'a> {
+    pkts: &'a mut [mbuf::MBuf],
+}
 
-/// The RSS offload types are defined based on flow types which are defined
-/// in rte_eth_ctrl.h. Different NIC hardwares may support different RSS offload
-/// types. The supported flow types or RSS offload types can be queried by
-/// rte_eth_dev_info_get().
-bitflags! {
-    pub struct RssHashFunc: u64 {
-        const ETH_RSS_IPV4               = 1 << ffi::RTE_ETH_FLOW_IPV4;
-        const ETH_RSS_FRAG_IPV4          = 1 << ffi::RTE_ETH_FLOW_FRAG_IPV4;
-        const ETH_RSS_NONFRAG_IPV4_TCP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_TCP;
-        const ETH_RSS_NONFRAG_IPV4_UDP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_UDP;
-        const ETH_RSS_NONFRAG_IPV4_SCTP  = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_SCTP;
-        const ETH_RSS_NONFRAG_IPV4_OTHER = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_OTHER;
-        const ETH_RSS_IPV6               = 1 << ffi::RTE_ETH_FLOW_IPV6;
-        const ETH_RSS_FRAG_IPV6          = 1 << ffi::RTE_ETH_FLOW_FRAG_IPV6;
-        const ETH_RSS_NONFRAG_IPV6_TCP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_TCP;
-        const ETH_RSS_NONFRAG_IPV6_UDP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_UDP;
-        const ETH_RSS_NONFRAG_IPV6_SCTP  = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_SCTP;
-        const ETH_RSS_NONFRAG_IPV6_OTHER = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_OTHER;
-        const ETH_RSS_L2_PAYLOAD         = 1 << ffi::RTE_ETH_FLOW_L2_PAYLOAD;
-        const ETH_RSS_IPV6_EX            = 1 << ffi::RTE_ETH_FLOW_IPV6_EX;
-        const ETH_RSS_IPV6_TCP_EX        = 1 << ffi::RTE_ETH_FLOW_IPV6_TCP_EX;
-        const ETH_RSS_IPV6_UDP_EX        = 1 << ffi::RTE_ETH_FLOW_IPV6_UDP_EX;
+impl<'a> Deref for BurstView<'a> {
+    type Target = [mbuf::MBuf];
 
-        const ETH_RSS_IP =
-            Self::ETH_RSS_IPV4.bits |
-            Self::ETH_RSS_FRAG_IPV4.bits |
-            Self::ETH_RSS_NONFRAG_IPV4_OTHER.bits |
-            Self::ETH_RSS_IPV6.bits |
-            Self::ETH_RSS_FRAG_IPV6.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_OTHER.bits |
-            Self::ETH_RSS_IPV6_EX.bits;
+    fn deref(&self) -> &Self::Target {
+        self.pkts
+    }
+}
 
-        const ETH_RSS_UDP =
-            Self::ETH_RSS_NONFRAG_IPV4_UDP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_UDP.bits |
-            Self::ETH_RSS_IPV6_UDP_EX.bits;
+impl<'a> BurstView<'a> {
+    unsafe fn new(pkts: *mut *mut ffi::rte_mbuf, nb_pkts: u16) -> Self {
+        // the `MBuf` is a transparent wrapper of the mbuf pointer, the packets are borrowed from the burst
+        BurstView {
+            pkts: slice::from_raw_parts_mut(pkts as *mut mbuf::MBuf, nb_pkts as usize),
+        }
+    }
 
-        const ETH_RSS_TCP =
-            Self::ETH_RSS_NONFRAG_IPV4_TCP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_TCP.bits |
-            Self::ETH_RSS_IPV6_TCP_EX.bits;
+    /// Get a mutable view of the header at an offset into the data of the first segment of the packet.
+    pub fn header_mut<'b, T: mbuf::Header>(&'b mut self, idx: usize, off: usize) -> Option<mbuf::HeaderViewMut<'b, T>> {
+        self.pkts.get_mut(idx).and_then(|m| m.header_mut(off))
+    }
 
-        const ETH_RSS_SCTP =
-            Self::ETH_RSS_NONFRAG_IPV4_SCTP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_SCTP.bits;
+    /// The data of the first segment of the packet.
+    pub fn bytes_mut(&mut self, idx: usize) -> Option<&mut [u8]> {
+        self.pkts
+            .get_mut(idx)
+            .map(|m| unsafe { slice::from_raw_parts_mut(m.mtod::<u8>().as_ptr(), m.data_len()) })
+    }
 
-        /**< Mask of valid RSS hash protocols */
-        const ETH_RSS_PROTO_MASK =
-            Self::ETH_RSS_IPV4.bits |
-            Self::ETH_RSS_FRAG_IPV4.bits |
-            Self::ETH_RSS_NONFRAG_IPV4_TCP.bits |
-            Self::ETH_RSS_NONFRAG_IPV4_UDP.bits |
-            Self::ETH_RSS_NONFRAG_IPV4_SCTP.bits |
-            Self::ETH_RSS_NONFRAG_IPV4_OTHER.bits |
-            Self::ETH_RSS_IPV6.bits |
-            Self::ETH_RSS_FRAG_IPV6.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_TCP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_UDP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_SCTP.bits |
-            Self::ETH_RSS_NONFRAG_IPV6_OTHER.bits |
-            Self::ETH_RSS_L2_PAYLOAD.bits |
-            Self::ETH_RSS_IPV6_EX.bits |
-            Self::ETH_RSS_IPV6_TCP_EX.bits |
-            Self::ETH_RSS_IPV6_UDP_EX.bits;
+    /// Set the RX timestamp of the packet.
+    pub fn set_rx_timestamp(&mut self, idx: usize, timestamp: u64) {
+        self.pkts[idx].set_rx_timestamp(timestamp)
+    }
+
+    /// Set the RSS hash of the packet.
+    pub fn set_rss_hash(&mut self, idx: usize, hash: u32) {
+        self.pkts[idx].set_rss_hash(hash)
+    }
+
+    /// Set the user defined tags of the packet.
+    pub fn set_usr(&mut self, idx: usize, usr: u32) {
+        self.pkts[idx].set_usr(usr)
     }
 }
 
-pub struct EthRssConf {
-    pub key: Option<[u8; 40]>,
-    pub hash: RssHashFunc,
+/// Add a callback of the packets received on the RX queue of the port.
+///
+/// The callback is called with each RX burst on the polling lcore, after the packets were received
+/// and before they are returned to the application, e.g. to stamp or count the packets.
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use rte::ethdev;
+///
+/// let received = Arc::new(AtomicUsize::new(0));
+/// let counter = received.clone();
+///
+/// let cb = ethdev::add_rx_callback(0, 0, move |pkts| {
+///     counter.fetch_add(pkts.len(), Ordering::Relaxed);
+/// })
+/// .unwrap();
+/// ```
+pub fn add_rx_callback<F>(port_id: PortId, queue_id: QueueId, callback: F) -> Result<RxCallback>
+where
+    F: FnMut(&mut BurstView) + Send + 'static,
+{
+    let ctx = Box::into_raw(Box::new(callback));
+
+    let cb = unsafe { ffi::rte_eth_add_rx_callback(port_id, queue_id, Some(rx_callback_stub::<F>), ctx as *mut _) };
+
+    if cb.is_null() {
+        unsafe { drop_callback_context::<F>(ctx as *mut _) };
+
+        return Err(rte_error());
+    }
+
+    Ok(RxCallback {
+        port_id,
+        queue_id,
+        cb,
+        ctx: ctx as *mut _,
+        drop_ctx: drop_callback_context::<F>,
+    })
 }
 
-impl Default for EthRssConf {
-    fn default() -> Self {
-        unsafe { mem::zeroed() }
+/// Add a callback of the packets sent to the TX queue of the port.
+///
+/// The callback is called with each TX burst on the sending lcore, before the packets are sent,
+/// e.g. to count the packets or measure their latency.
+pub fn add_tx_callback<F>(port_id: PortId, queue_id: QueueId, callback: F) -> Result<TxCallback>
+where
+    F: FnMut(&mut BurstView) + Send + 'static,
+{
+    let ctx = Box::into_raw(Box::new(callback));
+
+    let cb = unsafe { ffi::rte_eth_add_tx_callback(port_id, queue_id, Some(tx_callback_stub::<F>), ctx as *mut _) };
+
+    if cb.is_null() {
+        unsafe { drop_callback_context::<F>(ctx as *mut _) };
+
+        return Err(rte_error());
     }
+
+    Ok(TxCallback {
+        port_id,
+        queue_id,
+        cb,
+        ctx: ctx as *mut _,
+        drop_ctx: drop_callback_context::<F>,
+    })
 }
 
-#[derive(Default)]
-pub struct RxAdvConf {
-    /// Port RSS configuration
-    pub rss_conf: Option<EthRssConf>,
-    pub vmdq_dcb_conf: Option<ffi::rte_eth_vmdq_dcb_conf>,
-    pub dcb_rx_conf: Option<ffi::rte_eth_dcb_rx_conf>,
-    pub vmdq_rx_conf: Option<ffi::rte_eth_vmdq_rx_conf>,
+unsafe fn drop_callback_context<F>(ctx: *mut c_void) {
+    Box::from_raw(ctx as *mut F);
 }
 
-pub enum TxAdvConf {}
+unsafe extern "C" fn rx_callback_stub<F>(
+    _port_id: PortId,
+    _queue_id: QueueId,
+    pkts: *mut *mut ffi::rte_mbuf,
+    nb_pkts: u16,
+    _max_pkts: u16,
+    user_param: *mut c_void,
+) -> u16
+where
+    F: FnMut(&mut BurstView),
+{
+    let callback = &mut *(user_param as *mut F);
+
+    (callback)(&mut BurstView::new(pkts, nb_pkts));
+
+    nb_pkts
+}
 
-/// Device supported speeds bitmap flags
-bitflags! {
-    pub struct LinkSpeed: u32 {
-        /**< Autonegotiate (all speeds) */
-        const ETH_LINK_SPEED_AUTONEG  = 0 <<  0;
-        /**< Disable autoneg (fixed speed) */
-        const ETH_LINK_SPEED_FIXED    = 1 <<  0;
-        /**<  10 Mbps half-duplex */
-        const ETH_LINK_SPEED_10M_HD   = 1 <<  1;
-         /**<  10 Mbps full-duplex */
-        const ETH_LINK_SPEED_10M      = 1 <<  2;
-        /**< 100 Mbps half-duplex */
-        const ETH_LINK_SPEED_100M_HD  = 1 <<  3;
-        /**< 100 Mbps full-duplex */
-        const ETH_LINK_SPEED_100M     = 1 <<  4;
-        const ETH_LINK_SPEED_1G       = 1 <<  5;
-        const ETH_LINK_SPEED_2_5G     = 1 <<  6;
-        const ETH_LINK_SPEED_5G       = 1 <<  7;
-        const ETH_LINK_SPEED_10G      = 1 <<  8;
-        const ETH_LINK_SPEED_20G      = 1 <<  9;
-        const ETH_LINK_SPEED_25G      = 1 << 10;
-        const ETH_LINK_SPEED_40G      = 1 << 11;
-        const ETH_LINK_SPEED_50G      = 1 << 12;
-        const ETH_LINK_SPEED_56G      = 1 << 13;
-        const ETH_LINK_SPEED_100G     = 1 << 14;
+unsafe extern "C" fn tx_callback_stub<F>(
+    _port_id: PortId,
+    _queue_id: QueueId,
+    pkts: *mut *mut ffi::rte_mbuf,
+    nb_pkts: u16,
+    user_param: *mut c_void,
+) -> u16
+where
+    F: FnMut(&mut BurstView),
+{
+    let callback = &mut *(user_param as *mut F);
+
+    (callback)(&mut BurstView::new(pkts, nb_pkts));
+
+    nb_pkts
+}
+
+/// The queues of a stopped Ethernet device, which will be setup again after it is reconfigured.
+struct SavedQueues {
+    rx: Vec<RxQueueInfo>,
+    tx: Vec<TxQueueInfo>,
+}
+
+impl SavedQueues {
+    /// Save the queues set up with the previous configuration.
+    ///
+    /// The queue information of some drivers includes the port offloads of the previous configuration,
+    /// which are removed from the saved queue offloads, the new port offloads are enabled again by the queue setup.
+    fn get(port_id: PortId, prev: &EthConf) -> Result<Self> {
+        let data = dev_data(port_id);
+        let info = port_id.info();
+
+        let rx_offloads = RxOffload::from_bits_truncate(info.rx_queue_offload_capa)
+            - prev
+                .rxmode
+                .as_ref()
+                .map_or_else(RxOffload::empty, |rxmode| RxOffload::from_bits_truncate(rxmode.offloads));
+        let tx_offloads = TxOffload::from_bits_truncate(info.tx_queue_offload_capa)
+            - prev
+                .txmode
+                .as_ref()
+                .map_or_else(TxOffload::empty, |txmode| TxOffload::from_bits_truncate(txmode.offloads));
+
+        let rx = (0..data.nb_rx_queues)
+            .map(|queue_id| {
+                let mut qinfo = port_id.rx_queue_info(queue_id)?;
+
+                if qinfo.mempool().is_none() {
+                    // the hairpin queues have no mempool
+                    return Err(RteError(libc::ENOTSUP).into());
+                }
+
+                qinfo.offloads &= rx_offloads;
+                qinfo.conf = qinfo.conf.with_offloads(qinfo.offloads);
+
+                Ok(qinfo)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let tx = (0..data.nb_tx_queues)
+            .map(|queue_id| {
+                let mut qinfo = port_id.tx_queue_info(queue_id)?;
+
+                qinfo.offloads &= tx_offloads;
+                qinfo.conf = qinfo.conf.with_offloads(qinfo.offloads);
+
+                Ok(qinfo)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SavedQueues { rx, tx })
+    }
+
+    /// Configure the stopped device and setup the saved queues again.
+    fn apply(&self, port_id: PortId, conf: &EthConf) -> Result<()> {
+        port_id.configure(self.rx.len() as QueueId, self.tx.len() as QueueId, conf)?;
+
+        for (queue_id, qinfo) in self.rx.iter().enumerate() {
+            let mb_pool = qinfo.mempool().ok_or(RteError(libc::ENOTSUP))?;
+
+            port_id.rx_queue_setup(queue_id as QueueId, qinfo.nb_desc, Some(qinfo.conf), mb_pool)?;
+        }
+
+        for (queue_id, qinfo) in self.tx.iter().enumerate() {
+            port_id.tx_queue_setup(queue_id as QueueId, qinfo.nb_desc, Some(qinfo.conf))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check the configuration against the capabilities of the device.
+fn validate_conf(info: &RawEthDeviceInfo, conf: &EthConf) -> Result<()> {
+    if let Some(ref rxmode) = conf.rxmode {
+        let offloads = RxOffload::from_bits_truncate(rxmode.offloads);
+
+        if !RxOffload::from_bits_truncate(info.rx_offload_capa).contains(offloads) {
+            return Err(RteError(libc::ENOTSUP).into());
+        }
+
+        if offloads.contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME) && rxmode.max_rx_pkt_len > info.max_rx_pktlen {
+            return Err(RteError(libc::EINVAL).into());
+        }
+    }
+
+    if let Some(ref txmode) = conf.txmode {
+        if !TxOffload::from_bits_truncate(info.tx_offload_capa).contains(TxOffload::from_bits_truncate(txmode.offloads))
+        {
+            return Err(RteError(libc::ENOTSUP).into());
+        }
+    }
+
+    if let Some(rss_conf) = conf
+        .rx_adv_conf
+        .as_ref()
+        .and_then(|adv_conf| adv_conf.rss_conf.as_ref())
+    {
+        let supported = RssHashFunc::from_bits_truncate(info.flow_type_rss_offloads);
+        let unsupported = rss_conf.hash - supported;
+
+        if !unsupported.is_empty() {
+            return Err(ErrorKind::UnsupportedRss(format!("{}, the device supports {}", unsupported, supported)).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Configure the device with its current queue numbers and the changed configuration.
+///
+/// The saved RSS key points to the caller's memory of the previous configuration,
+/// so the key is read back from the device and configured again, a custom key is kept.
+fn configure_current<F: FnOnce(&mut ffi::rte_eth_conf)>(port_id: PortId, with: F) -> Result<()> {
+    let data = dev_data(port_id);
+    let mut conf = data.dev_conf;
+    let mut key = vec![0u8; port_id.info().hash_key_size as usize];
+
+    conf.rx_adv_conf.rss_conf.rss_key = ptr::null_mut();
+    conf.rx_adv_conf.rss_conf.rss_key_len = 0;
+
+    if EthRxMultiQueueMode::from_bits_truncate(conf.rxmode.mq_mode).contains(EthRxMultiQueueMode::ETH_MQ_RX_RSS_FLAG)
+        && !key.is_empty()
+    {
+        let mut raw = ffi::rte_eth_rss_conf {
+            rss_key: key.as_mut_ptr(),
+            rss_key_len: key.len() as u8,
+            rss_hf: 0,
+        };
+
+        if unsafe { ffi::rte_eth_dev_rss_hash_conf_get(port_id, &mut raw) } == 0 {
+            conf.rx_adv_conf.rss_conf.rss_key = key.as_mut_ptr();
+            conf.rx_adv_conf.rss_conf.rss_key_len = key.len() as u8;
+        } else {
+            warn!("port {} fail to read the RSS key, the default key of the driver is used", port_id);
+        }
+    }
+
+    with(&mut conf);
+
+    let ret = unsafe { ffi::rte_eth_dev_configure(port_id, data.nb_rx_queues, data.nb_tx_queues, &conf) };
+
+    rte_check!(ret; err => { RteError(-ret).into() })
+}
+
+/// The shared data of an Ethernet device, as saved by the ethdev layer.
+fn dev_data(port_id: PortId) -> &'static ffi::rte_eth_dev_data {
+    unsafe {
+        let devices = ptr::addr_of!(ffi::rte_eth_devices) as *const ffi::rte_eth_dev;
+
+        &*(*devices.add(port_id as usize)).data
+    }
+}
+
+impl EthDevice for PortId {
+    fn portid(&self) -> PortId {
+        *self
+    }
+
+    fn configure(&self, nb_rx_queue: QueueId, nb_tx_queue: QueueId, conf: &EthConf) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_configure(*self,
+                                       nb_rx_queue,
+                                       nb_tx_queue,
+                                       RawEthConf::from(conf).as_raw())
+        }; ok => { self })
+    }
+
+    fn info(&self) -> RawEthDeviceInfo {
+        let mut info: RawEthDeviceInfo = Default::default();
+
+        unsafe { ffi::rte_eth_dev_info_get(*self, &mut info) }
+
+        info
+    }
+
+    fn supported_rss(&self) -> RssHashFunc {
+        RssHashFunc::from_bits_truncate(self.info().flow_type_rss_offloads)
+    }
+
+    fn stats(&self) -> Result<RawEthDeviceStats> {
+        let mut stats: RawEthDeviceStats = Default::default();
+
+        rte_check!(unsafe {
+            ffi::rte_eth_stats_get(*self, &mut stats)
+        }; ok => { stats })
+    }
+
+    fn reset_stats(&self) -> &Self {
+        unsafe { ffi::rte_eth_stats_reset(*self) };
+
+        self
+    }
+
+    fn xstats_names(&self) -> Result<Vec<String>> {
+        xstats_name_table(*self)
+    }
+
+    fn xstats(&self) -> Result<Vec<(String, u64)>> {
+        let names = xstats_name_table(*self)?;
+        let mut xstats = vec![ffi::rte_eth_xstat::default(); names.len()];
+
+        let ret = unsafe { ffi::rte_eth_xstats_get(*self, xstats.as_mut_ptr(), xstats.len() as u32) };
+
+        if ret < 0 {
+            return Err(RteError(-ret).into());
+        }
+        // the statistics were changed since the names were retrieved, e.g. the queues were reconfigured
+        if ret as usize > xstats.len() {
+            return Err(RteError(libc::EAGAIN).into());
+        }
+
+        Ok(xstats
+            .iter()
+            .take(ret as usize)
+            .filter_map(|xstat| names.get(xstat.id as usize).map(|name| (name.clone(), xstat.value)))
+            .collect())
+    }
+
+    fn xstats_reset(&self) -> &Self {
+        unsafe { ffi::rte_eth_xstats_reset(*self) };
+
+        self
+    }
+
+    fn set_rx_queue_stats_mapping(&self, queue_id: QueueId, stat_idx: u8) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_set_rx_queue_stats_mapping(*self, queue_id, stat_idx)
+        }; ok => { self })
+    }
+
+    fn set_tx_queue_stats_mapping(&self, queue_id: QueueId, stat_idx: u8) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_set_tx_queue_stats_mapping(*self, queue_id, stat_idx)
+        }; ok => { self })
+    }
+
+    fn mac_addr(&self) -> ether::EtherAddr {
+        unsafe {
+            let mut addr: ffi::ether_addr = mem::zeroed();
+
+            ffi::rte_eth_macaddr_get(*self, &mut addr);
+
+            ether::EtherAddr::from(addr.addr_bytes)
+        }
+    }
+
+    fn set_mac_addr(&self, addr: &[u8; ether::ETHER_ADDR_LEN]) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_default_mac_addr_set(*self, addr.as_ptr() as * mut _)
+        }; ok => { self })
+    }
+
+    fn socket_id(&self) -> Socket {
+        Socket::of_port(*self)
+    }
+
+    fn is_valid(&self) -> bool {
+        unsafe { ffi::rte_eth_dev_is_valid_port(*self) != 0 }
+    }
+
+    fn rx_queue_setup<'a>(
+        &self,
+        rx_queue_id: QueueId,
+        nb_rx_desc: u16,
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>> {
+        if let Err(err) = mb_pool.check_rx_pool() {
+            error!(
+                "port {} rxq {} can't receive packets, mempool `{}` is not a pktmbuf pool with room for the packet data",
+                self,
+                rx_queue_id,
+                mb_pool.name()
+            );
+
+            return Err(err);
+        }
+
+        let socket = self.socket_id();
+
+        if !socket.is_any() && !mb_pool.socket().is_any() && mb_pool.socket() != socket {
+            warn!(
+                "port {} rxq {} is on {}, but the mbufs of mempool `{}` are allocated on {}",
+                self,
+                rx_queue_id,
+                socket,
+                mb_pool.name(),
+                mb_pool.socket()
+            );
+        }
+
+        rte_check!(unsafe {
+            ffi::rte_eth_rx_queue_setup(*self,
+                                        rx_queue_id,
+                                        nb_rx_desc,
+                                        socket.into(),
+                                        rx_conf.as_ref().map(|conf| conf as *const _).unwrap_or(ptr::null()),
+                                        mb_pool.as_raw())
+        }; ok => {
+            #[cfg(debug_assertions)]
+            affinity::on_setup(QueueHandle::Rx(*self, rx_queue_id));
+
+            RxQueue::new(*self, rx_queue_id, mb_pool)
+        })
+    }
+
+    fn max_rx_pkt_len(&self) -> u32 {
+        let rxmode = &dev_data(*self).dev_conf.rxmode;
+
+        if RxOffload::from_bits_truncate(rxmode.offloads).contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME) {
+            rxmode.max_rx_pkt_len
+        } else {
+            ether::ETHER_MAX_LEN
+        }
+    }
+
+    fn max_lro_pkt_size(&self) -> Option<u32> {
+        if self.rx_offloads().contains(RxOffload::DEV_RX_OFFLOAD_TCP_LRO) {
+            Some(self.max_rx_pkt_len())
+        } else {
+            None
+        }
+    }
+
+    fn rx_offloads(&self) -> RxOffload {
+        RxOffload::from_bits_truncate(dev_data(*self).dev_conf.rxmode.offloads)
+    }
+
+    fn supported_ptypes(&self, ptype_mask: u32) -> Result<Vec<u32>> {
+        let n = unsafe { ffi::rte_eth_dev_get_supported_ptypes(*self, ptype_mask, ptr::null_mut(), 0) };
+
+        if n < 0 {
+            return Err(RteError(-n).into());
+        }
+
+        let mut ptypes = vec![0; n as usize];
+
+        let n = unsafe { ffi::rte_eth_dev_get_supported_ptypes(*self, ptype_mask, ptypes.as_mut_ptr(), n) };
+
+        if n < 0 {
+            return Err(RteError(-n).into());
+        }
+
+        ptypes.truncate(n as usize);
+
+        Ok(ptypes)
+    }
+
+    fn rx_queue_setup_scattered<'a>(
+        &self,
+        rx_queue_id: QueueId,
+        nb_rx_desc: u16,
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>> {
+        let info = self.info();
+        let mut rx_conf = rx_conf.unwrap_or(info.default_rxconf);
+        let max_rx_pkt_len = self.max_rx_pkt_len();
+        let segs = rx_segments(max_rx_pkt_len as usize, mb_pool.data_room_size()).ok_or(RteError(libc::EINVAL))?;
+
+        if segs > 1 && !self.rx_offloads().contains(RxOffload::DEV_RX_OFFLOAD_SCATTER) {
+            if RxOffload::from_bits_truncate(info.rx_queue_offload_capa).contains(RxOffload::DEV_RX_OFFLOAD_SCATTER) {
+                debug!(
+                    "port {} queue {} scatters packets up to {} bytes into {} segments",
+                    self, rx_queue_id, max_rx_pkt_len, segs
+                );
+
+                rx_conf = rx_conf.with_scatter();
+            } else {
+                return Err(RteError(libc::ENOTSUP).into());
+            }
+        }
+
+        self.rx_queue_setup(rx_queue_id, nb_rx_desc, Some(rx_conf), mb_pool)
+    }
+
+    fn tx_offloads(&self) -> TxOffload {
+        TxOffload::from_bits_truncate(dev_data(*self).dev_conf.txmode.offloads)
+    }
+
+    fn tx_queue_setup(&self, tx_queue_id: QueueId, nb_tx_desc: u16, tx_conf: Option<EthTxConf>) -> Result<TxQueue> {
+        #[cfg(debug_assertions)]
+        let offloads = self.tx_offloads() | tx_conf.unwrap_or_else(|| self.info().default_txconf).offloads();
+
+        rte_check!(unsafe {
+            ffi::rte_eth_tx_queue_setup(*self,
+                                        tx_queue_id,
+                                        nb_tx_desc,
+                                        self.socket_id().into(),
+                                        tx_conf.as_ref().map(|conf| conf as *const _).unwrap_or(ptr::null()))
+        }; ok => {
+            #[cfg(debug_assertions)]
+            affinity::on_setup(QueueHandle::Tx(*self, tx_queue_id));
+            #[cfg(debug_assertions)]
+            fast_free::on_setup(*self, tx_queue_id, offloads);
+
+            TxQueue::new(*self, tx_queue_id)
+        })
+    }
+
+    fn hairpin_capability(&self) -> Result<HairpinCap> {
+        let mut cap = HairpinCap::default();
+
+        let ret = unsafe { ffi::_rte_eth_dev_hairpin_capability_get(*self, &mut cap) };
+
+        rte_check!(ret; ok => { cap }; err => { RteError(-ret).into() })
+    }
+
+    fn rx_hairpin_queue_setup(&self, rx_queue_id: QueueId, nb_rx_desc: u16, conf: &HairpinConf) -> Result<&Self> {
+        let conf = conf.to_raw()?;
+
+        let ret = unsafe { ffi::_rte_eth_rx_hairpin_queue_setup(*self, rx_queue_id, nb_rx_desc, &conf) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn tx_hairpin_queue_setup(&self, tx_queue_id: QueueId, nb_tx_desc: u16, conf: &HairpinConf) -> Result<&Self> {
+        let conf = conf.to_raw()?;
+
+        let ret = unsafe { ffi::_rte_eth_tx_hairpin_queue_setup(*self, tx_queue_id, nb_tx_desc, &conf) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn hairpin_bind(&self, rx_port: PortId) -> Result<&Self> {
+        let ret = unsafe { ffi::_rte_eth_hairpin_bind(*self, rx_port) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn hairpin_unbind(&self, rx_port: PortId) -> Result<&Self> {
+        let ret = unsafe { ffi::_rte_eth_hairpin_unbind(*self, rx_port) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn hairpin_peer_ports(&self, tx: bool) -> Result<Vec<PortId>> {
+        let mut ports = vec![0; ffi::RTE_MAX_ETHPORTS as usize];
+
+        let n = unsafe {
+            ffi::_rte_eth_hairpin_get_peer_ports(*self, ports.as_mut_ptr(), ports.len(), bool_value!(tx) as u32)
+        };
+
+        if n < 0 {
+            return Err(RteError(-n).into());
+        }
+
+        ports.truncate(n as usize);
+
+        Ok(ports)
+    }
+
+    fn promiscuous_enable(&self) -> &Self {
+        unsafe { ffi::rte_eth_promiscuous_enable(*self) };
+
+        self
+    }
+
+    fn promiscuous_disable(&self) -> &Self {
+        unsafe { ffi::rte_eth_promiscuous_disable(*self) };
+
+        self
+    }
+
+    fn is_promiscuous_enabled(&self) -> Result<bool> {
+        let ret = unsafe { ffi::rte_eth_promiscuous_get(*self) };
+
+        rte_check!(ret; ok => { ret != 0 })
+    }
+
+    fn allmulticast_enable(&self) -> &Self {
+        unsafe { ffi::rte_eth_allmulticast_enable(*self) };
+
+        self
+    }
+
+    fn allmulticast_disable(&self) -> &Self {
+        unsafe { ffi::rte_eth_allmulticast_disable(*self) };
+
+        self
+    }
+
+    fn is_allmulticast_enabled(&self) -> Result<bool> {
+        let ret = unsafe { ffi::rte_eth_allmulticast_get(*self) };
+
+        rte_check!(ret; ok => { ret != 0 })
+    }
+
+    fn set_mc_addr_list(&self, addrs: &[ether::EtherAddr]) -> Result<&Self> {
+        let mut addrs: Vec<ffi::ether_addr> = addrs
+            .iter()
+            .map(|addr| ffi::ether_addr {
+                addr_bytes: addr.into_bytes(),
+            })
+            .collect();
+        let mc_addr_set = if addrs.is_empty() {
+            ptr::null_mut()
+        } else {
+            addrs.as_mut_ptr()
+        };
+
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_set_mc_addr_list(*self, mc_addr_set, addrs.len() as u32)
+        }; ok => { self })
+    }
+
+    fn mtu(&self) -> Result<u16> {
+        let mut mtu: u16 = 0;
+
+        rte_check!(unsafe { ffi::rte_eth_dev_get_mtu(*self, &mut mtu)}; ok => { mtu })
+    }
+
+    fn set_mtu(&self, mtu: u16) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_set_mtu(*self, mtu) }; ok => { self })
+    }
+
+    fn set_vlan_filter(&self, vlan_id: u16, on: bool) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_vlan_filter(*self, vlan_id, bool_value!(on) as i32)
+        }; ok => { self })
+    }
+
+    fn link(&self) -> EthLink {
+        let mut link = rte_sys::rte_eth_link::default();
+
+        unsafe { ffi::rte_eth_link_get(*self, &mut link as *mut _) }
+
+        EthLink {
+            speed: link.link_speed,
+            duplex: link.link_duplex() != 0,
+            autoneg: link.link_autoneg() != 0,
+            up: link.link_status() != 0,
+        }
+    }
+
+    fn link_nowait(&self) -> EthLink {
+        let mut link = rte_sys::rte_eth_link::default();
+
+        unsafe { ffi::rte_eth_link_get_nowait(*self, &mut link as *mut _) }
+
+        EthLink {
+            speed: link.link_speed,
+            duplex: link.link_duplex() != 0,
+            autoneg: link.link_autoneg() != 0,
+            up: link.link_status() != 0,
+        }
+    }
+
+    fn set_link_up(&self) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_set_link_up(*self) }; ok => { self })
+    }
+
+    fn set_link_down(&self) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_set_link_down(*self) }; ok => { self })
+    }
+
+    fn rx_queue_start(&self, rx_queue_id: QueueId) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_rx_queue_start(*self, rx_queue_id) }; ok => { self })
+    }
+
+    fn rx_queue_stop(&self, rx_queue_id: QueueId) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_rx_queue_stop(*self, rx_queue_id) }; ok => { self })
+    }
+
+    fn tx_queue_start(&self, tx_queue_id: QueueId) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_tx_queue_start(*self, tx_queue_id) }; ok => { self })
+    }
+
+    fn tx_queue_stop(&self, tx_queue_id: QueueId) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_tx_queue_stop(*self, tx_queue_id) }; ok => { self })
+    }
+
+    fn rx_queue_info(&self, rx_queue_id: QueueId) -> Result<RxQueueInfo> {
+        let mut qinfo = RawRxQueueInfo::default();
+
+        let ret = unsafe { ffi::rte_eth_rx_queue_info_get(*self, rx_queue_id, &mut qinfo) };
+
+        rte_check!(ret; ok => { RxQueueInfo::from(&qinfo) }; err => { RteError(-ret).into() })
+    }
+
+    fn tx_queue_info(&self, tx_queue_id: QueueId) -> Result<TxQueueInfo> {
+        let mut qinfo = RawTxQueueInfo::default();
+
+        let ret = unsafe { ffi::rte_eth_tx_queue_info_get(*self, tx_queue_id, &mut qinfo) };
+
+        rte_check!(ret; ok => { TxQueueInfo::from(&qinfo) }; err => { RteError(-ret).into() })
+    }
+
+    fn add_rx_queue<'a>(
+        &self,
+        rx_queue_id: QueueId,
+        nb_rx_desc: u16,
+        rx_conf: Option<EthRxConf>,
+        mb_pool: mempool::MemPoolRef<'a>,
+    ) -> Result<RxQueue<'a>> {
+        if !is_started(*self) {
+            return self.rx_queue_setup(rx_queue_id, nb_rx_desc, rx_conf, mb_pool);
+        }
+
+        if !self
+            .info()
+            .dev_capa()
+            .contains(DevCapa::RTE_ETH_DEV_CAPA_RUNTIME_RX_QUEUE_SETUP)
+        {
+            return Err(RteError(libc::ENOTSUP).into());
+        }
+
+        // the queues set up after the device has been started must be started explicitly
+        let rx_queue = self.rx_queue_setup(rx_queue_id, nb_rx_desc, rx_conf, mb_pool)?;
+
+        rx_queue.start()?;
+
+        Ok(rx_queue)
+    }
+
+    fn remove_rx_queue(&self, rx_queue: RxQueue) -> Result<&Self> {
+        if is_started(*self) {
+            rx_queue.stop()?;
+        }
+
+        Ok(self)
+    }
+
+    fn add_tx_queue(&self, tx_queue_id: QueueId, nb_tx_desc: u16, tx_conf: Option<EthTxConf>) -> Result<TxQueue> {
+        if !is_started(*self) {
+            return self.tx_queue_setup(tx_queue_id, nb_tx_desc, tx_conf);
+        }
+
+        if !self
+            .info()
+            .dev_capa()
+            .contains(DevCapa::RTE_ETH_DEV_CAPA_RUNTIME_TX_QUEUE_SETUP)
+        {
+            return Err(RteError(libc::ENOTSUP).into());
+        }
+
+        let tx_queue = self.tx_queue_setup(tx_queue_id, nb_tx_desc, tx_conf)?;
+
+        tx_queue.start()?;
+
+        Ok(tx_queue)
+    }
+
+    fn remove_tx_queue(&self, tx_queue: TxQueue) -> Result<&Self> {
+        if is_started(*self) {
+            tx_queue.stop()?;
+        }
+
+        Ok(self)
+    }
+
+    fn start(&self) -> Result<&Self> {
+        rte_check!(unsafe { ffi::rte_eth_dev_start(*self) }; ok => { self })
+    }
+
+    fn stop(&self) -> &Self {
+        unsafe { ffi::rte_eth_dev_stop(*self) };
+
+        self
+    }
+
+    fn close(&self) -> &Self {
+        unsafe { ffi::rte_eth_dev_close(*self) };
+
+        #[cfg(debug_assertions)]
+        affinity::forget(*self);
+        #[cfg(debug_assertions)]
+        fast_free::forget(*self);
+
+        self
+    }
+
+    fn rx_burst(&self, queue_id: QueueId, rx_pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        #[cfg(debug_assertions)]
+        affinity::on_poll(QueueHandle::Rx(*self, queue_id));
+
+        let n = unsafe {
+            ffi::_rte_eth_rx_burst(*self, queue_id, rx_pkts.as_mut_ptr() as *mut _, rx_pkts.len() as u16) as usize
+        };
+
+        #[cfg(feature = "debug-mbuf")]
+        mbuf::debug::on_alloc_bulk(&rx_pkts[..n]);
+
+        mbuf::balance::on_get_burst(rx_pkts[..n].iter().flatten().map(|m| m.as_raw()));
+
+        n
+    }
+
+    fn rx_burst_uninit(&self, queue_id: QueueId, rx_pkts: &mut [mem::MaybeUninit<mbuf::MBuf>]) -> usize {
+        #[cfg(debug_assertions)]
+        affinity::on_poll(QueueHandle::Rx(*self, queue_id));
+
+        let n = unsafe {
+            ffi::_rte_eth_rx_burst(*self, queue_id, rx_pkts.as_mut_ptr() as *mut _, rx_pkts.len() as u16) as usize
+        };
+
+        #[cfg(feature = "debug-mbuf")]
+        rx_pkts[..n]
+            .iter()
+            .for_each(|m| mbuf::debug::on_alloc(unsafe { &*m.as_ptr() }));
+
+        mbuf::balance::on_get_burst(rx_pkts[..n].iter().map(|m| unsafe { (*m.as_ptr()).as_raw() }));
+
+        n
+    }
+
+    fn rx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, queue_id: QueueId, rx_pkts: &mut ArrayVec<A>) -> usize {
+        let len = rx_pkts.len();
+
+        unsafe {
+            let room = slice::from_raw_parts_mut(
+                rx_pkts.as_mut_ptr().add(len) as *mut mem::MaybeUninit<mbuf::MBuf>,
+                rx_pkts.capacity() - len,
+            );
+            let n = self.rx_burst_uninit(queue_id, room);
+
+            rx_pkts.set_len(len + n);
+
+            n
+        }
+    }
+
+    fn tx_burst<T: AsRaw<Raw = mbuf::RawMBuf>>(&self, queue_id: QueueId, rx_pkts: &mut [T]) -> usize {
+        #[cfg(debug_assertions)]
+        affinity::on_poll(QueueHandle::Tx(*self, queue_id));
+        #[cfg(debug_assertions)]
+        fast_free::check_burst(*self, queue_id, rx_pkts);
+
+        #[cfg(feature = "debug-mbuf")]
+        let pkts = mbuf::debug::check_burst(rx_pkts);
+
+        let n = unsafe {
+            if rx_pkts.is_empty() {
+                ffi::_rte_eth_tx_burst(*self, queue_id, ptr::null_mut(), 0) as usize
+            } else {
+                ffi::_rte_eth_tx_burst(*self, queue_id, rx_pkts.as_mut_ptr() as *mut _, rx_pkts.len() as u16) as usize
+            }
+        };
+
+        #[cfg(feature = "debug-mbuf")]
+        mbuf::debug::on_transmit(&pkts[..n]);
+
+        // the mempool of the transmitted mbufs is still readable, even if they were freed by the driver
+        mbuf::balance::on_put_burst(rx_pkts[..n].iter().map(|m| m.as_raw()));
+
+        n
+    }
+
+    fn tx_burst_all(&self, queue_id: QueueId, mut tx_pkts: Vec<mbuf::MBuf>, policy: &TxRetryPolicy) -> TxBurstResult {
+        let res = policy.run(tx_pkts.len(), |off| self.tx_burst(queue_id, &mut tx_pkts[off..]));
+
+        for m in tx_pkts.drain(..res.sent) {
+            mem::forget(m);
+        }
+
+        res
+    }
+
+    fn tx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, queue_id: QueueId, tx_pkts: &mut ArrayVec<A>) -> usize {
+        let n = self.tx_burst(queue_id, tx_pkts);
+
+        for m in tx_pkts.drain(..n) {
+            mem::forget(m);
+        }
+
+        n
+    }
+
+    fn vlan_offload(&self) -> Result<EthVlanOffloadMode> {
+        let mode = unsafe { ffi::rte_eth_dev_get_vlan_offload(*self) };
+
+        rte_check!(mode; ok => { EthVlanOffloadMode::from_bits_truncate(mode) })
+    }
+
+    fn set_vlan_offload(&self, mode: EthVlanOffloadMode) -> Result<&Self> {
+        rte_check!(unsafe {
+            ffi::rte_eth_dev_set_vlan_offload(*self, mode.bits)
+        }; ok => { self })
+    }
+
+    fn link_speeds(&self) -> LinkSpeed {
+        LinkSpeed::from_bits_truncate(dev_data(*self).dev_conf.link_speeds)
+    }
+
+    fn set_link_speeds(&self, speeds: LinkSpeed) -> Result<&Self> {
+        configure_current(*self, |conf| conf.link_speeds = speeds.bits).map(|_| self)
+    }
+
+    fn set_offloads(&self, rx_offloads: RxOffload, tx_offloads: TxOffload) -> Result<&Self> {
+        configure_current(*self, |conf| {
+            conf.rxmode.offloads = rx_offloads.bits;
+            conf.txmode.offloads = tx_offloads.bits;
+        })
+        .map(|_| self)
+    }
+
+    fn flow_ctrl(&self) -> Result<EthFcConf> {
+        let mut conf = EthFcConf::default();
+
+        let ret = unsafe { ffi::rte_eth_dev_flow_ctrl_get(*self, &mut conf) };
+
+        rte_check!(ret; ok => { conf }; err => { RteError(-ret).into() })
+    }
+
+    fn set_flow_ctrl(&self, conf: &EthFcConf) -> Result<&Self> {
+        let mut conf = *conf;
+
+        let ret = unsafe { ffi::rte_eth_dev_flow_ctrl_set(*self, &mut conf) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn adjust_nb_rx_tx_desc(&self, mut nb_rx_desc: u16, mut nb_tx_desc: u16) -> Result<(u16, u16)> {
+        let ret = unsafe { ffi::rte_eth_dev_adjust_nb_rx_tx_desc(*self, &mut nb_rx_desc, &mut nb_tx_desc) };
+
+        rte_check!(ret; ok => { (nb_rx_desc, nb_tx_desc) }; err => { RteError(-ret).into() })
+    }
+
+    fn current_conf(&self) -> EthConf {
+        let mut conf = EthConf::from(&dev_data(*self).dev_conf);
+
+        // the saved RSS key points to the caller's memory, read it back from the device
+        if let Some(rss_conf) = conf
+            .rx_adv_conf
+            .as_mut()
+            .and_then(|adv_conf| adv_conf.rss_conf.as_mut())
+        {
+            let mut key = [0; 40];
+            let mut raw = ffi::rte_eth_rss_conf {
+                rss_key: key.as_mut_ptr(),
+                rss_key_len: key.len() as u8,
+                rss_hf: 0,
+            };
+
+            if unsafe { ffi::rte_eth_dev_rss_hash_conf_get(*self, &mut raw) } == 0 {
+                rss_conf.key = Some(key);
+            }
+        }
+
+        conf
+    }
+
+    fn reconfigure<F: FnOnce(&mut EthConf)>(&self, with: F) -> Result<&Self> {
+        let prev = self.current_conf();
+        let mut conf = self.current_conf();
+
+        let queues = SavedQueues::get(*self, &prev)?;
+        let started = is_started(*self);
+        let promiscuous = self.is_promiscuous_enabled().unwrap_or(false);
+        let allmulticast = self.is_allmulticast_enabled().unwrap_or(false);
+
+        with(&mut conf);
+
+        validate_conf(&self.info(), &conf)?;
+
+        self.stop();
+
+        let res = queues.apply(*self, &conf);
+
+        if let Err(ref err) = res {
+            warn!("port {} rejected the new configuration, {}", self, err);
+
+            if let Err(err) = queues.apply(*self, &prev) {
+                error!("port {} fail to restore the previous configuration, {}", self, err);
+            }
+        }
+
+        if started {
+            self.start()?;
+        }
+
+        if promiscuous {
+            self.promiscuous_enable();
+        } else {
+            self.promiscuous_disable();
+        }
+
+        if allmulticast {
+            self.allmulticast_enable();
+        } else {
+            self.allmulticast_disable();
+        }
+
+        res?;
+
+        Ok(self)
+    }
+
+    fn eeprom_length(&self) -> Result<usize> {
+        let ret = unsafe { ffi::rte_eth_dev_get_eeprom_length(*self) };
+
+        if ret < 0 {
+            Err(RteError(ret).into())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    fn eeprom(&self, offset: usize, buf: &mut [u8]) -> Result<()> {
+        let mut info = ffi::rte_dev_eeprom_info {
+            data: buf.as_mut_ptr() as *mut _,
+            offset: offset as u32,
+            length: buf.len() as u32,
+            magic: 0,
+        };
+
+        let ret = unsafe { ffi::rte_eth_dev_get_eeprom(*self, &mut info) };
+
+        rte_check!(ret)
+    }
+
+    fn set_eeprom(&self, offset: usize, data: &[u8]) -> Result<&Self> {
+        let mut info = ffi::rte_dev_eeprom_info {
+            data: data.as_ptr() as *mut _,
+            offset: offset as u32,
+            length: data.len() as u32,
+            magic: 0,
+        };
+
+        let ret = unsafe { ffi::rte_eth_dev_set_eeprom(*self, &mut info) };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn module_info(&self) -> Result<sfp::ModuleInfo> {
+        let mut info = ffi::rte_eth_dev_module_info::default();
+
+        let ret = unsafe { ffi::rte_eth_dev_get_module_info(*self, &mut info) };
+
+        rte_check!(ret; ok => { info.into() })
+    }
+
+    fn flow_isolate(&self, on: bool) -> Result<&Self> {
+        flow::isolate(*self, on).map(|_| self)
+    }
+
+    fn rx_intr_enable(&self, queue_id: QueueId) -> Result<&Self> {
+        let ret = unsafe { ffi::rte_eth_dev_rx_intr_enable(*self, queue_id) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn rx_intr_disable(&self, queue_id: QueueId) -> Result<&Self> {
+        let ret = unsafe { ffi::rte_eth_dev_rx_intr_disable(*self, queue_id) };
+
+        rte_check!(ret; ok => { self }; err => { RteError(-ret).into() })
+    }
+
+    fn rx_intr_fd(&self, queue_id: QueueId) -> Result<RawFd> {
+        unsafe { ffi::rte_eth_dev_rx_intr_ctl_q_get_fd(*self, queue_id) }.as_result()
+    }
+
+    fn module_eeprom_data(&self) -> Result<(sfp::ModuleType, Vec<u8>)> {
+        let module = self.module_info()?;
+        let mut data = vec![0; module.eeprom_len];
+        let mut info = ffi::rte_dev_eeprom_info {
+            data: data.as_mut_ptr() as *mut _,
+            offset: 0,
+            length: data.len() as u32,
+            magic: 0,
+        };
+
+        let ret = unsafe { ffi::rte_eth_dev_get_module_eeprom(*self, &mut info) };
+
+        rte_check!(ret; ok => { (module.kind, data) })
+    }
+}
+
+pub trait EthDeviceInfo {
+    /// Device Driver name.
+    fn driver_name(&self) -> &str;
+
+    fn dev(&self) -> Option<dev::Device>;
+
+    /// The generic capabilities of the device.
+    fn dev_capa(&self) -> DevCapa;
+
+    /// The limits of the segments of the transmitted packets.
+    fn tx_seg_limits(&self) -> TxSegLimits;
+
+    /// The limits of the number of RX descriptors.
+    fn rx_desc_lim(&self) -> DescLimits;
+
+    /// The limits of the number of TX descriptors.
+    fn tx_desc_lim(&self) -> DescLimits;
+}
+
+pub type RawEthDeviceInfo = ffi::rte_eth_dev_info;
+
+impl EthDeviceInfo for RawEthDeviceInfo {
+    #[inline]
+    fn driver_name(&self) -> &str {
+        unsafe { CStr::from_ptr(self.driver_name).to_str().unwrap() }
+    }
+
+    #[inline]
+    fn dev(&self) -> Option<dev::Device> {
+        if self.device.is_null() {
+            None
+        } else {
+            Some(self.device.into())
+        }
+    }
+
+    #[inline]
+    fn dev_capa(&self) -> DevCapa {
+        DevCapa::from_bits_truncate(self.dev_capa)
+    }
+
+    #[inline]
+    fn tx_seg_limits(&self) -> TxSegLimits {
+        TxSegLimits::new(self.tx_desc_lim.nb_seg_max, self.tx_desc_lim.nb_mtu_seg_max)
+    }
+
+    #[inline]
+    fn rx_desc_lim(&self) -> DescLimits {
+        DescLimits::from(&self.rx_desc_lim)
+    }
+
+    #[inline]
+    fn tx_desc_lim(&self) -> DescLimits {
+        DescLimits::from(&self.tx_desc_lim)
+    }
+}
+
+/// The limits of the number of descriptors of a queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DescLimits {
+    /// The max number of descriptors, zero means no limit.
+    pub nb_max: u16,
+    /// The min number of descriptors.
+    pub nb_min: u16,
+    /// The number of descriptors should be a multiple of the alignment.
+    pub nb_align: u16,
+}
+
+impl From<&ffi::rte_eth_desc_lim> for DescLimits {
+    fn from(lim: &ffi::rte_eth_desc_lim) -> Self {
+        DescLimits {
+            nb_max: lim.nb_max,
+            nb_min: lim.nb_min,
+            nb_align: lim.nb_align,
+        }
+    }
+}
+
+impl DescLimits {
+    /// Adjust the number of descriptors to the limits, as `adjust_nb_rx_tx_desc` does.
+    ///
+    /// The number is rounded up to the alignment, then clamped between the min and max numbers.
+    pub fn adjust(&self, nb_desc: u16) -> u16 {
+        let mut nb = u32::from(nb_desc);
+
+        if self.nb_align != 0 {
+            let align = u32::from(self.nb_align);
+
+            nb = nb.div_ceil(align) * align;
+        }
+
+        if self.nb_max != 0 {
+            nb = cmp::min(nb, u32::from(self.nb_max));
+        }
+
+        cmp::max(nb, u32::from(self.nb_min)) as u16
+    }
+
+    /// The number of descriptors is within the limits.
+    pub fn contains(&self, nb_desc: u16) -> bool {
+        self.adjust(nb_desc) == nb_desc
+    }
+}
+
+/// The limits of the segments of the transmitted packets.
+///
+/// The drivers may silently drop the packets with more segments than the hardware could fetch,
+/// so the chained mbufs should be checked, or linearized, before they are sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxSegLimits {
+    /// The max number of the segments of a TSO packet.
+    pub nb_seg_max: u16,
+    /// The max number of the segments of a non-TSO packet, or of each packet sent by TSO.
+    pub nb_mtu_seg_max: u16,
+}
+
+impl Default for TxSegLimits {
+    fn default() -> Self {
+        TxSegLimits {
+            nb_seg_max: u16::MAX,
+            nb_mtu_seg_max: u16::MAX,
+        }
+    }
+}
+
+impl TxSegLimits {
+    /// Create the limits, a zero limit means no limit.
+    pub fn new(nb_seg_max: u16, nb_mtu_seg_max: u16) -> Self {
+        let or_max = |n| if n == 0 { u16::MAX } else { n };
+
+        TxSegLimits {
+            nb_seg_max: or_max(nb_seg_max),
+            nb_mtu_seg_max: or_max(nb_mtu_seg_max),
+        }
+    }
+
+    /// A non-TSO packet of the segments could be sent.
+    pub fn fits(&self, nb_segs: usize) -> bool {
+        nb_segs <= self.nb_mtu_seg_max as usize
+    }
+
+    /// A TSO packet of the segments could be sent,
+    /// the `hdr_len` bytes of the headers are replicated in each packet of `mss` bytes payload.
+    pub fn fits_tso(&self, seg_lens: &[usize], hdr_len: usize, mss: usize) -> bool {
+        mss != 0
+            && seg_lens.len() <= self.nb_seg_max as usize
+            && tso_segs_per_packet(seg_lens, hdr_len, mss) <= self.nb_mtu_seg_max as usize
+    }
+}
+
+/// The max number of the segments fetched for a packet sent by TSO, including the segments of the headers.
+fn tso_segs_per_packet(seg_lens: &[usize], hdr_len: usize, mss: usize) -> usize {
+    let mut max = 0;
+    let mut off = 0;
+    let mut hdr_segs = 0;
+    let mut window = None;
+    let mut count = 0;
+
+    for &len in seg_lens.iter().filter(|&&len| len > 0) {
+        let (start, end) = (off, off + len);
+
+        off = end;
+
+        if start < hdr_len {
+            hdr_segs += 1;
+        }
+
+        if end <= hdr_len {
+            continue;
+        }
+
+        let first = start.saturating_sub(hdr_len) / mss;
+        let last = (end - hdr_len - 1) / mss;
+
+        for w in first..=last {
+            if window != Some(w) {
+                window = Some(w);
+                count = hdr_segs;
+            }
+
+            // the segments with the headers are already counted in each packet
+            if start >= hdr_len {
+                count += 1;
+            }
+
+            max = cmp::max(max, count);
+        }
+    }
+
+    cmp::max(max, hdr_segs)
+}
+
+bitflags! {
+    /// The generic capabilities of the device.
+    pub struct DevCapa: u64 {
+        /// The RX queues could be set up after the device has been started.
+        const RTE_ETH_DEV_CAPA_RUNTIME_RX_QUEUE_SETUP = ffi::RTE_ETH_DEV_CAPA_RUNTIME_RX_QUEUE_SETUP as u64;
+        /// The TX queues could be set up after the device has been started.
+        const RTE_ETH_DEV_CAPA_RUNTIME_TX_QUEUE_SETUP = ffi::RTE_ETH_DEV_CAPA_RUNTIME_TX_QUEUE_SETUP as u64;
+    }
+}
+
+pub trait EthDeviceStats {}
+
+pub type RawEthDeviceStats = ffi::rte_eth_stats;
+
+impl EthDeviceStats for RawEthDeviceStats {}
+
+/// The extended statistics matching the name patterns, which are resolved to ids once,
+/// so they could be read by id without retrieving the name table every polling interval.
+///
+/// The pattern matches the names with `*` for any characters and `?` for one character, e.g. `rx_q*_errors`.
+#[derive(Clone, Debug)]
+pub struct XStatsIds {
+    port_id: PortId,
+    names: Vec<String>,
+    ids: Vec<u64>,
+}
+
+impl XStatsIds {
+    /// Resolve the extended statistics of the port matching any of the patterns.
+    pub fn resolve<S: AsRef<str>>(port_id: PortId, patterns: &[S]) -> Result<Self> {
+        let mut names = vec![];
+        let mut ids = vec![];
+
+        for (id, name) in xstats_name_table(port_id)?.into_iter().enumerate() {
+            if patterns.iter().any(|p| glob_match(p.as_ref(), &name)) {
+                names.push(name);
+                ids.push(id as u64);
+            }
+        }
+
+        Ok(XStatsIds { port_id, names, ids })
+    }
+
+    /// The port of the extended statistics.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The number of resolved extended statistics.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// No extended statistic matches the patterns.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The names of the resolved extended statistics.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The ids of the resolved extended statistics.
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    /// The queue of the resolved extended statistic, e.g. `rx_q3_errors` or `rx_queue_3_packets`.
+    pub fn queue_id(&self, idx: usize) -> Option<QueueId> {
+        self.names.get(idx).and_then(|name| xstat_queue_id(name))
+    }
+
+    /// Read the values of the resolved extended statistics into the slice, in the order of `names()`.
+    pub fn read(&self, values: &mut [u64]) -> Result<usize> {
+        if self.ids.is_empty() {
+            return Ok(0);
+        }
+
+        let n = cmp::min(values.len(), self.ids.len());
+        let ret =
+            unsafe { ffi::rte_eth_xstats_get_by_id(self.port_id, self.ids.as_ptr(), values.as_mut_ptr(), n as u32) };
+
+        if ret < 0 {
+            Err(RteError(-ret).into())
+        } else if ret as usize > n {
+            Err(RteError(libc::ENOSPC).into())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    /// Read the values of the resolved extended statistics.
+    pub fn values(&self) -> Result<Vec<u64>> {
+        let mut values = vec![0; self.ids.len()];
+        let n = self.read(&mut values)?;
+
+        values.truncate(n);
+
+        Ok(values)
+    }
+
+    /// Read the resolved extended statistics with their names.
+    pub fn get(&self) -> Result<Vec<(&str, u64)>> {
+        self.values()
+            .map(|values| self.names.iter().map(|s| s.as_str()).zip(values).collect())
+    }
+}
+
+/// Retrieve the names of the extended statistics, indexed by the id.
+fn xstats_name_table(port_id: PortId) -> Result<Vec<String>> {
+    unsafe {
+        let n = ffi::rte_eth_xstats_get_names(port_id, ptr::null_mut(), 0);
+
+        if n < 0 {
+            return Err(RteError(-n).into());
+        }
+
+        let mut names = vec![ffi::rte_eth_xstat_name { name: [0; 64] }; n as usize];
+        let ret = ffi::rte_eth_xstats_get_names(port_id, names.as_mut_ptr(), n as u32);
+
+        if ret < 0 {
+            return Err(RteError(-ret).into());
+        }
+        if ret > n {
+            return Err(RteError(libc::EAGAIN).into());
+        }
+
+        Ok(names
+            .iter()
+            .take(ret as usize)
+            .map(|name| CStr::from_ptr(name.name.as_ptr()).to_string_lossy().into_owned())
+            .collect())
+    }
+}
+
+/// Match the name with a globbing pattern, `*` for any characters and `?` for one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (p, s) = (pattern.as_bytes(), name.as_bytes());
+    let (mut pi, mut si) = (0, 0);
+    let mut backtrack = None;
+
+    while si < s.len() {
+        if pi < p.len() && (p[pi] == b'?' || p[pi] == s[si]) {
+            pi += 1;
+            si += 1;
+        } else if pi < p.len() && p[pi] == b'*' {
+            backtrack = Some((pi, si));
+            pi += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // let the star consume one more character
+            pi = star + 1;
+            si = matched + 1;
+            backtrack = Some((star, si));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == b'*')
+}
+
+/// Parse the queue from the name of a per-queue extended statistic.
+fn xstat_queue_id(name: &str) -> Option<QueueId> {
+    name.split('_')
+        .zip(name.split('_').skip(1))
+        .filter_map(|(prev, cur)| {
+            if prev == "queue" {
+                cur.parse().ok()
+            } else if let Some(id) = cur.strip_prefix('q') {
+                let digits = id.bytes().take_while(u8::is_ascii_digit).count();
+
+                if digits > 0 {
+                    id[..digits].parse().ok()
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
+/// A reading of the general I/O statistics, with the TSC when it was taken.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StatsSnapshot {
+    pub stats: RawEthDeviceStats,
+    pub tsc: u64,
+}
+
+impl StatsSnapshot {
+    /// Take a snapshot of the general I/O statistics of an Ethernet device.
+    pub fn take<T: EthDevice>(dev: &T) -> Result<Self> {
+        dev.stats().map(|stats| StatsSnapshot { stats, tsc: rdtsc() })
+    }
+
+    /// The time elapsed since the previous snapshot.
+    pub fn elapsed(&self, prev: &StatsSnapshot) -> Duration {
+        let cycles = self.tsc.saturating_sub(prev.tsc);
+        let hz = get_tsc_hz();
+
+        Duration::new(cycles / hz, ((cycles % hz) * 1_000_000_000 / hz) as u32)
+    }
+
+    /// The changes of counters since the previous snapshot.
+    ///
+    /// The counters are considered as zero if they were reset between the snapshots.
+    pub fn delta(&self, prev: &StatsSnapshot) -> StatsDelta {
+        let (cur, prev) = (&self.stats, &prev.stats);
+
+        StatsDelta {
+            ipackets: cur.ipackets.saturating_sub(prev.ipackets),
+            opackets: cur.opackets.saturating_sub(prev.opackets),
+            ibytes: cur.ibytes.saturating_sub(prev.ibytes),
+            obytes: cur.obytes.saturating_sub(prev.obytes),
+            imissed: cur.imissed.saturating_sub(prev.imissed),
+            ierrors: cur.ierrors.saturating_sub(prev.ierrors),
+            oerrors: cur.oerrors.saturating_sub(prev.oerrors),
+            rx_nombuf: cur.rx_nombuf.saturating_sub(prev.rx_nombuf),
+        }
+    }
+
+    /// The rates of counters since the previous snapshot.
+    pub fn rates(&self, prev: &StatsSnapshot) -> Rates {
+        Rates::from_delta(&self.delta(prev), self.elapsed(prev))
+    }
+}
+
+/// The changes of the general I/O statistics between two readings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatsDelta {
+    /// Number of successfully received packets.
+    pub ipackets: u64,
+    /// Number of successfully transmitted packets.
+    pub opackets: u64,
+    /// Number of successfully received bytes.
+    pub ibytes: u64,
+    /// Number of successfully transmitted bytes.
+    pub obytes: u64,
+    /// Number of RX packets dropped by the HW, because there are no available buffer.
+    pub imissed: u64,
+    /// Number of erroneous received packets.
+    pub ierrors: u64,
+    /// Number of failed transmitted packets.
+    pub oerrors: u64,
+    /// Number of RX mbuf allocation failures.
+    pub rx_nombuf: u64,
+}
+
+/// The rates of the general I/O statistics, in units per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rates {
+    /// Received packets per second.
+    pub rx_pps: f64,
+    /// Transmitted packets per second.
+    pub tx_pps: f64,
+    /// Received bits per second.
+    pub rx_bps: f64,
+    /// Transmitted bits per second.
+    pub tx_bps: f64,
+    /// Missed RX packets per second.
+    pub rx_missed_pps: f64,
+    /// Erroneous received packets per second.
+    pub rx_errors_pps: f64,
+    /// Failed transmitted packets per second.
+    pub tx_errors_pps: f64,
+}
+
+impl Rates {
+    /// Compute the rates from the changes of counters in the elapsed time.
+    pub fn from_delta(delta: &StatsDelta, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1_000_000_000.0;
+
+        if secs == 0.0 {
+            return Rates::default();
+        }
+
+        Rates {
+            rx_pps: delta.ipackets as f64 / secs,
+            tx_pps: delta.opackets as f64 / secs,
+            rx_bps: delta.ibytes as f64 * 8.0 / secs,
+            tx_bps: delta.obytes as f64 * 8.0 / secs,
+            rx_missed_pps: delta.imissed as f64 / secs,
+            rx_errors_pps: delta.ierrors as f64 / secs,
+            tx_errors_pps: delta.oerrors as f64 / secs,
+        }
+    }
+}
+
+bitflags! {
+    /// Definitions used for VMDQ pool rx mode setting
+    pub struct EthVmdqRxMode : u16 {
+        /// accept untagged packets.
+        const ETH_VMDQ_ACCEPT_UNTAG     = 0x0001;
+        /// accept packets in multicast table .
+        const ETH_VMDQ_ACCEPT_HASH_MC   = 0x0002;
+        /// accept packets in unicast table.
+        const ETH_VMDQ_ACCEPT_HASH_UC   = 0x0004;
+        /// accept broadcast packets.
+        const ETH_VMDQ_ACCEPT_BROADCAST = 0x0008;
+        /// multicast promiscuous.
+        const ETH_VMDQ_ACCEPT_MULTICAST = 0x0010;
+    }
+}
+
+/// A set of values to identify what method is to be used to route packets to multiple queues.
+bitflags! {
+    pub struct EthRxMultiQueueMode: u32 {
+        const ETH_MQ_RX_RSS_FLAG    = 0x1;
+        const ETH_MQ_RX_DCB_FLAG    = 0x2;
+        const ETH_MQ_RX_VMDQ_FLAG   = 0x4;
+    }
+}
+
+bitflags! {
+    /// Definitions used for VLAN Offload functionality
+    pub struct EthVlanOffloadMode: i32 {
+        /// VLAN Strip  On/Off
+        const ETH_VLAN_STRIP_OFFLOAD  = 0x0001;
+        /// VLAN Filter On/Off
+        const ETH_VLAN_FILTER_OFFLOAD = 0x0002;
+        /// VLAN Extend On/Off
+        const ETH_VLAN_EXTEND_OFFLOAD = 0x0004;
+
+        /// VLAN Strip  setting mask
+        const ETH_VLAN_STRIP_MASK     = 0x0001;
+        /// VLAN Filter  setting mask
+        const ETH_VLAN_FILTER_MASK    = 0x0002;
+        /// VLAN Extend  setting mask
+        const ETH_VLAN_EXTEND_MASK    = 0x0004;
+        /// VLAN ID is in lower 12 bits
+        const ETH_VLAN_ID_MAX         = 0x0FFF;
+    }
+}
+
+/**
+ * A set of values to identify what method is to be used to transmit
+ * packets using multi-TCs.
+ */
+pub type EthTxMultiQueueMode = ffi::rte_eth_tx_mq_mode::Type;
+
+/// The RSS offload types are defined based on flow types which are defined
+/// in rte_eth_ctrl.h. Different NIC hardwares may support different RSS offload
+/// types. The supported flow types or RSS offload types can be queried by
+/// rte_eth_dev_info_get().
+bitflags! {
+    pub struct RssHashFunc: u64 {
+        const ETH_RSS_IPV4               = 1 << ffi::RTE_ETH_FLOW_IPV4;
+        const ETH_RSS_FRAG_IPV4          = 1 << ffi::RTE_ETH_FLOW_FRAG_IPV4;
+        const ETH_RSS_NONFRAG_IPV4_TCP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_TCP;
+        const ETH_RSS_NONFRAG_IPV4_UDP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_UDP;
+        const ETH_RSS_NONFRAG_IPV4_SCTP  = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_SCTP;
+        const ETH_RSS_NONFRAG_IPV4_OTHER = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV4_OTHER;
+        const ETH_RSS_IPV6               = 1 << ffi::RTE_ETH_FLOW_IPV6;
+        const ETH_RSS_FRAG_IPV6          = 1 << ffi::RTE_ETH_FLOW_FRAG_IPV6;
+        const ETH_RSS_NONFRAG_IPV6_TCP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_TCP;
+        const ETH_RSS_NONFRAG_IPV6_UDP   = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_UDP;
+        const ETH_RSS_NONFRAG_IPV6_SCTP  = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_SCTP;
+        const ETH_RSS_NONFRAG_IPV6_OTHER = 1 << ffi::RTE_ETH_FLOW_NONFRAG_IPV6_OTHER;
+        const ETH_RSS_L2_PAYLOAD         = 1 << ffi::RTE_ETH_FLOW_L2_PAYLOAD;
+        const ETH_RSS_IPV6_EX            = 1 << ffi::RTE_ETH_FLOW_IPV6_EX;
+        const ETH_RSS_IPV6_TCP_EX        = 1 << ffi::RTE_ETH_FLOW_IPV6_TCP_EX;
+        const ETH_RSS_IPV6_UDP_EX        = 1 << ffi::RTE_ETH_FLOW_IPV6_UDP_EX;
+
+        const ETH_RSS_IP =
+            Self::ETH_RSS_IPV4.bits |
+            Self::ETH_RSS_FRAG_IPV4.bits |
+            Self::ETH_RSS_NONFRAG_IPV4_OTHER.bits |
+            Self::ETH_RSS_IPV6.bits |
+            Self::ETH_RSS_FRAG_IPV6.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_OTHER.bits |
+            Self::ETH_RSS_IPV6_EX.bits;
+
+        const ETH_RSS_UDP =
+            Self::ETH_RSS_NONFRAG_IPV4_UDP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_UDP.bits |
+            Self::ETH_RSS_IPV6_UDP_EX.bits;
+
+        const ETH_RSS_TCP =
+            Self::ETH_RSS_NONFRAG_IPV4_TCP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_TCP.bits |
+            Self::ETH_RSS_IPV6_TCP_EX.bits;
+
+        const ETH_RSS_SCTP =
+            Self::ETH_RSS_NONFRAG_IPV4_SCTP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_SCTP.bits;
+
+        /**< Mask of valid RSS hash protocols */
+        const ETH_RSS_PROTO_MASK =
+            Self::ETH_RSS_IPV4.bits |
+            Self::ETH_RSS_FRAG_IPV4.bits |
+            Self::ETH_RSS_NONFRAG_IPV4_TCP.bits |
+            Self::ETH_RSS_NONFRAG_IPV4_UDP.bits |
+            Self::ETH_RSS_NONFRAG_IPV4_SCTP.bits |
+            Self::ETH_RSS_NONFRAG_IPV4_OTHER.bits |
+            Self::ETH_RSS_IPV6.bits |
+            Self::ETH_RSS_FRAG_IPV6.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_TCP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_UDP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_SCTP.bits |
+            Self::ETH_RSS_NONFRAG_IPV6_OTHER.bits |
+            Self::ETH_RSS_L2_PAYLOAD.bits |
+            Self::ETH_RSS_IPV6_EX.bits |
+            Self::ETH_RSS_IPV6_TCP_EX.bits |
+            Self::ETH_RSS_IPV6_UDP_EX.bits;
+    }
+}
+
+impl RssHashFunc {
+    /// The flow types of the RSS hash functions, with their names.
+    pub const FLOW_TYPES: &'static [(RssHashFunc, &'static str)] = &[
+        (RssHashFunc::ETH_RSS_IPV4, "ipv4"),
+        (RssHashFunc::ETH_RSS_FRAG_IPV4, "ipv4-frag"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV4_TCP, "ipv4-tcp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV4_UDP, "ipv4-udp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV4_SCTP, "ipv4-sctp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV4_OTHER, "ipv4-other"),
+        (RssHashFunc::ETH_RSS_IPV6, "ipv6"),
+        (RssHashFunc::ETH_RSS_FRAG_IPV6, "ipv6-frag"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV6_TCP, "ipv6-tcp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV6_UDP, "ipv6-udp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV6_SCTP, "ipv6-sctp"),
+        (RssHashFunc::ETH_RSS_NONFRAG_IPV6_OTHER, "ipv6-other"),
+        (RssHashFunc::ETH_RSS_L2_PAYLOAD, "l2-payload"),
+        (RssHashFunc::ETH_RSS_IPV6_EX, "ipv6-ex"),
+        (RssHashFunc::ETH_RSS_IPV6_TCP_EX, "ipv6-tcp-ex"),
+        (RssHashFunc::ETH_RSS_IPV6_UDP_EX, "ipv6-udp-ex"),
+    ];
+
+    /// The names of the flow types.
+    pub fn flow_types(self) -> impl Iterator<Item = &'static str> {
+        RssHashFunc::FLOW_TYPES
+            .iter()
+            .filter(move |&&(hash, _)| self.contains(hash))
+            .map(|&(_, name)| name)
+    }
+}
+
+/// The names of the flow types, e.g. `ipv4, ipv4-tcp`.
+impl fmt::Display for RssHashFunc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("none");
+        }
+
+        for (i, name) in self.flow_types().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct EthRssConf {
+    pub key: Option<[u8; 40]>,
+    pub hash: RssHashFunc,
+}
+
+impl Default for EthRssConf {
+    fn default() -> Self {
+        unsafe { mem::zeroed() }
+    }
+}
+
+#[derive(Default)]
+pub struct RxAdvConf {
+    /// Port RSS configuration
+    pub rss_conf: Option<EthRssConf>,
+    pub vmdq_dcb_conf: Option<ffi::rte_eth_vmdq_dcb_conf>,
+    pub dcb_rx_conf: Option<ffi::rte_eth_dcb_rx_conf>,
+    pub vmdq_rx_conf: Option<ffi::rte_eth_vmdq_rx_conf>,
+}
+
+pub enum TxAdvConf {}
+
+/// Ethernet numeric link speeds in Mbps
+pub const ETH_SPEED_NUM_NONE: u32 = 0;
+pub const ETH_SPEED_NUM_10M: u32 = 10;
+pub const ETH_SPEED_NUM_100M: u32 = 100;
+pub const ETH_SPEED_NUM_1G: u32 = 1_000;
+pub const ETH_SPEED_NUM_2_5G: u32 = 2_500;
+pub const ETH_SPEED_NUM_5G: u32 = 5_000;
+pub const ETH_SPEED_NUM_10G: u32 = 10_000;
+pub const ETH_SPEED_NUM_20G: u32 = 20_000;
+pub const ETH_SPEED_NUM_25G: u32 = 25_000;
+pub const ETH_SPEED_NUM_40G: u32 = 40_000;
+pub const ETH_SPEED_NUM_50G: u32 = 50_000;
+pub const ETH_SPEED_NUM_56G: u32 = 56_000;
+pub const ETH_SPEED_NUM_100G: u32 = 100_000;
+
+/// Device supported speeds bitmap flags
+bitflags! {
+    pub struct LinkSpeed: u32 {
+        /**< Autonegotiate (all speeds) */
+        const ETH_LINK_SPEED_AUTONEG  = 0 <<  0;
+        /**< Disable autoneg (fixed speed) */
+        const ETH_LINK_SPEED_FIXED    = 1 <<  0;
+        /**<  10 Mbps half-duplex */
+        const ETH_LINK_SPEED_10M_HD   = 1 <<  1;
+         /**<  10 Mbps full-duplex */
+        const ETH_LINK_SPEED_10M      = 1 <<  2;
+        /**< 100 Mbps half-duplex */
+        const ETH_LINK_SPEED_100M_HD  = 1 <<  3;
+        /**< 100 Mbps full-duplex */
+        const ETH_LINK_SPEED_100M     = 1 <<  4;
+        const ETH_LINK_SPEED_1G       = 1 <<  5;
+        const ETH_LINK_SPEED_2_5G     = 1 <<  6;
+        const ETH_LINK_SPEED_5G       = 1 <<  7;
+        const ETH_LINK_SPEED_10G      = 1 <<  8;
+        const ETH_LINK_SPEED_20G      = 1 <<  9;
+        const ETH_LINK_SPEED_25G      = 1 << 10;
+        const ETH_LINK_SPEED_40G      = 1 << 11;
+        const ETH_LINK_SPEED_50G      = 1 << 12;
+        const ETH_LINK_SPEED_56G      = 1 << 13;
+        const ETH_LINK_SPEED_100G     = 1 << 14;
+    }
+}
+
+impl Default for LinkSpeed {
+    fn default() -> Self {
+        LinkSpeed::ETH_LINK_SPEED_AUTONEG
+    }
+}
+
+/// Link speed flags and their numeric speed (Mbps) and duplex.
+const LINK_SPEEDS: [(LinkSpeed, u32, bool); 14] = [
+    (LinkSpeed::ETH_LINK_SPEED_10M_HD, ETH_SPEED_NUM_10M, false),
+    (LinkSpeed::ETH_LINK_SPEED_10M, ETH_SPEED_NUM_10M, true),
+    (LinkSpeed::ETH_LINK_SPEED_100M_HD, ETH_SPEED_NUM_100M, false),
+    (LinkSpeed::ETH_LINK_SPEED_100M, ETH_SPEED_NUM_100M, true),
+    (LinkSpeed::ETH_LINK_SPEED_1G, ETH_SPEED_NUM_1G, true),
+    (LinkSpeed::ETH_LINK_SPEED_2_5G, ETH_SPEED_NUM_2_5G, true),
+    (LinkSpeed::ETH_LINK_SPEED_5G, ETH_SPEED_NUM_5G, true),
+    (LinkSpeed::ETH_LINK_SPEED_10G, ETH_SPEED_NUM_10G, true),
+    (LinkSpeed::ETH_LINK_SPEED_20G, ETH_SPEED_NUM_20G, true),
+    (LinkSpeed::ETH_LINK_SPEED_25G, ETH_SPEED_NUM_25G, true),
+    (LinkSpeed::ETH_LINK_SPEED_40G, ETH_SPEED_NUM_40G, true),
+    (LinkSpeed::ETH_LINK_SPEED_50G, ETH_SPEED_NUM_50G, true),
+    (LinkSpeed::ETH_LINK_SPEED_56G, ETH_SPEED_NUM_56G, true),
+    (LinkSpeed::ETH_LINK_SPEED_100G, ETH_SPEED_NUM_100G, true),
+];
+
+impl LinkSpeed {
+    /// Convert a numerical speed in Mbps to a speed flag, `None` if the speed is unknown.
+    pub fn from_mbps(speed: u32, full_duplex: bool) -> Option<Self> {
+        LINK_SPEEDS
+            .iter()
+            .find(|&&(_, mbps, duplex)| mbps == speed && (duplex == full_duplex || mbps > ETH_SPEED_NUM_100M))
+            .map(|&(flag, _, _)| flag)
+    }
+
+    /// A fixed link speed in Mbps which disables link autonegotiation.
+    pub fn fixed(speed: u32, full_duplex: bool) -> Option<Self> {
+        Self::from_mbps(speed, full_duplex).map(|flag| flag | LinkSpeed::ETH_LINK_SPEED_FIXED)
+    }
+
+    /// Link autonegotiation is enabled.
+    pub fn is_autoneg(&self) -> bool {
+        !self.contains(LinkSpeed::ETH_LINK_SPEED_FIXED)
+    }
+
+    /// The numerical speeds in Mbps of the flag set, in ascending order.
+    pub fn to_mbps(&self) -> Vec<u32> {
+        let mut speeds = LINK_SPEEDS
+            .iter()
+            .filter(|&&(flag, _, _)| self.contains(flag))
+            .map(|&(_, mbps, _)| mbps)
+            .collect::<Vec<_>>();
+
+        speeds.dedup();
+        speeds
+    }
+
+    /// The highest numerical speed in Mbps of the flag set.
+    pub fn max_mbps(&self) -> Option<u32> {
+        self.to_mbps().last().cloned()
+    }
+}
+
+/// Convert a numerical speed in Mbps to a bitmap flag that can be used in
+/// the bitmap link_speeds of the `EthConf` structure.
+pub fn speed_bitflag(speed: u32, full_duplex: bool) -> LinkSpeed {
+    LinkSpeed::from_bits_truncate(unsafe { ffi::rte_eth_speed_bitflag(speed, full_duplex as i32) })
+}
+
+bitflags! {
+    /// The RX offloads of the port or queue.
+    pub struct RxOffload: u64 {
+        const DEV_RX_OFFLOAD_VLAN_STRIP = ffi::DEV_RX_OFFLOAD_VLAN_STRIP as u64;
+        const DEV_RX_OFFLOAD_IPV4_CKSUM = ffi::DEV_RX_OFFLOAD_IPV4_CKSUM as u64;
+        const DEV_RX_OFFLOAD_UDP_CKSUM = ffi::DEV_RX_OFFLOAD_UDP_CKSUM as u64;
+        const DEV_RX_OFFLOAD_TCP_CKSUM = ffi::DEV_RX_OFFLOAD_TCP_CKSUM as u64;
+        const DEV_RX_OFFLOAD_TCP_LRO = ffi::DEV_RX_OFFLOAD_TCP_LRO as u64;
+        const DEV_RX_OFFLOAD_QINQ_STRIP = ffi::DEV_RX_OFFLOAD_QINQ_STRIP as u64;
+        const DEV_RX_OFFLOAD_OUTER_IPV4_CKSUM = ffi::DEV_RX_OFFLOAD_OUTER_IPV4_CKSUM as u64;
+        const DEV_RX_OFFLOAD_MACSEC_STRIP = ffi::DEV_RX_OFFLOAD_MACSEC_STRIP as u64;
+        const DEV_RX_OFFLOAD_HEADER_SPLIT = ffi::DEV_RX_OFFLOAD_HEADER_SPLIT as u64;
+        const DEV_RX_OFFLOAD_VLAN_FILTER = ffi::DEV_RX_OFFLOAD_VLAN_FILTER as u64;
+        const DEV_RX_OFFLOAD_VLAN_EXTEND = ffi::DEV_RX_OFFLOAD_VLAN_EXTEND as u64;
+        /// Receive the jumbo frames.
+        const DEV_RX_OFFLOAD_JUMBO_FRAME = ffi::DEV_RX_OFFLOAD_JUMBO_FRAME as u64;
+        /// Scatter the received packets into the multiple segments.
+        const DEV_RX_OFFLOAD_SCATTER = ffi::DEV_RX_OFFLOAD_SCATTER as u64;
+        /// Timestamp the received packets in the `timestamp` field of mbuf.
+        const DEV_RX_OFFLOAD_TIMESTAMP = ffi::DEV_RX_OFFLOAD_TIMESTAMP as u64;
+        const DEV_RX_OFFLOAD_SECURITY = ffi::DEV_RX_OFFLOAD_SECURITY as u64;
+        /// Keep the CRC of the received packets.
+        const DEV_RX_OFFLOAD_KEEP_CRC = ffi::DEV_RX_OFFLOAD_KEEP_CRC as u64;
+        const DEV_RX_OFFLOAD_SCTP_CKSUM = ffi::DEV_RX_OFFLOAD_SCTP_CKSUM as u64;
+        const DEV_RX_OFFLOAD_OUTER_UDP_CKSUM = ffi::DEV_RX_OFFLOAD_OUTER_UDP_CKSUM as u64;
+        /// Verify the IPv4, UDP and TCP checksum.
+        const DEV_RX_OFFLOAD_CHECKSUM = ffi::DEV_RX_OFFLOAD_CHECKSUM as u64;
+        /// Strip, filter and extend VLAN.
+        const DEV_RX_OFFLOAD_VLAN = ffi::DEV_RX_OFFLOAD_VLAN as u64;
+    }
+}
+
+bitflags! {
+    /// The TX offloads of the port or queue.
+    pub struct TxOffload: u64 {
+        const DEV_TX_OFFLOAD_VLAN_INSERT = ffi::DEV_TX_OFFLOAD_VLAN_INSERT as u64;
+        const DEV_TX_OFFLOAD_IPV4_CKSUM = ffi::DEV_TX_OFFLOAD_IPV4_CKSUM as u64;
+        const DEV_TX_OFFLOAD_UDP_CKSUM = ffi::DEV_TX_OFFLOAD_UDP_CKSUM as u64;
+        const DEV_TX_OFFLOAD_TCP_CKSUM = ffi::DEV_TX_OFFLOAD_TCP_CKSUM as u64;
+        const DEV_TX_OFFLOAD_SCTP_CKSUM = ffi::DEV_TX_OFFLOAD_SCTP_CKSUM as u64;
+        const DEV_TX_OFFLOAD_TCP_TSO = ffi::DEV_TX_OFFLOAD_TCP_TSO as u64;
+        const DEV_TX_OFFLOAD_UDP_TSO = ffi::DEV_TX_OFFLOAD_UDP_TSO as u64;
+        const DEV_TX_OFFLOAD_OUTER_IPV4_CKSUM = ffi::DEV_TX_OFFLOAD_OUTER_IPV4_CKSUM as u64;
+        const DEV_TX_OFFLOAD_QINQ_INSERT = ffi::DEV_TX_OFFLOAD_QINQ_INSERT as u64;
+        const DEV_TX_OFFLOAD_VXLAN_TNL_TSO = ffi::DEV_TX_OFFLOAD_VXLAN_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_GRE_TNL_TSO = ffi::DEV_TX_OFFLOAD_GRE_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_IPIP_TNL_TSO = ffi::DEV_TX_OFFLOAD_IPIP_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_GENEVE_TNL_TSO = ffi::DEV_TX_OFFLOAD_GENEVE_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_MACSEC_INSERT = ffi::DEV_TX_OFFLOAD_MACSEC_INSERT as u64;
+        /// Multiple threads can invoke tx_burst() concurrently on the same TX queue without SW lock.
+        const DEV_TX_OFFLOAD_MT_LOCKFREE = ffi::DEV_TX_OFFLOAD_MT_LOCKFREE as u64;
+        /// Transmit the multi-segment packets.
+        const DEV_TX_OFFLOAD_MULTI_SEGS = ffi::DEV_TX_OFFLOAD_MULTI_SEGS as u64;
+        /// The mbufs are from the same mempool, have refcnt = 1, and are direct and non-segmented.
+        const DEV_TX_OFFLOAD_MBUF_FAST_FREE = ffi::DEV_TX_OFFLOAD_MBUF_FAST_FREE as u64;
+        const DEV_TX_OFFLOAD_SECURITY = ffi::DEV_TX_OFFLOAD_SECURITY as u64;
+        const DEV_TX_OFFLOAD_UDP_TNL_TSO = ffi::DEV_TX_OFFLOAD_UDP_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_IP_TNL_TSO = ffi::DEV_TX_OFFLOAD_IP_TNL_TSO as u64;
+        const DEV_TX_OFFLOAD_OUTER_UDP_CKSUM = ffi::DEV_TX_OFFLOAD_OUTER_UDP_CKSUM as u64;
+        /// Match the metadata of the packets in the flow rules.
+        const DEV_TX_OFFLOAD_MATCH_METADATA = ffi::DEV_TX_OFFLOAD_MATCH_METADATA as u64;
+    }
+}
+
+impl RxOffload {
+    /// The names of the offloads, e.g. `VLAN_STRIP`.
+    pub fn names(&self) -> Vec<&'static str> {
+        offload_names(self.bits, |offload| unsafe {
+            ffi::rte_eth_dev_rx_offload_name(offload)
+        })
+    }
+
+    /// Find the offload by name, case insensitive, e.g. `vlan_strip` or `vlan-strip`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        offload_from_name(name, |offload| unsafe { ffi::rte_eth_dev_rx_offload_name(offload) })
+            .map(Self::from_bits_truncate)
+    }
+}
+
+impl TxOffload {
+    /// The names of the offloads, e.g. `TCP_TSO`.
+    pub fn names(&self) -> Vec<&'static str> {
+        offload_names(self.bits, |offload| unsafe {
+            ffi::rte_eth_dev_tx_offload_name(offload)
+        })
+    }
+
+    /// Find the offload by name, case insensitive, e.g. `tcp_tso` or `tcp-tso`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        offload_from_name(name, |offload| unsafe { ffi::rte_eth_dev_tx_offload_name(offload) })
+            .map(Self::from_bits_truncate)
+    }
+}
+
+fn offload_name<F: Fn(u64) -> *const c_char>(offload: u64, f: &F) -> &'static str {
+    let name = f(offload);
+
+    if name.is_null() {
+        "UNKNOWN"
+    } else {
+        unsafe { CStr::from_ptr(name) }.to_str().unwrap_or("UNKNOWN")
+    }
+}
+
+fn offload_names<F: Fn(u64) -> *const c_char>(bits: u64, f: F) -> Vec<&'static str> {
+    (0..64)
+        .map(|i| 1u64 << i)
+        .filter(|offload| bits & offload != 0)
+        .map(|offload| offload_name(offload, &f))
+        .collect()
+}
+
+fn offload_from_name<F: Fn(u64) -> *const c_char>(name: &str, f: F) -> Option<u64> {
+    let name = name.replace('-', "_");
+
+    (0..64)
+        .map(|i| 1u64 << i)
+        .find(|&offload| offload_name(offload, &f).eq_ignore_ascii_case(&name))
+}
+
+/// The link flow control configuration.
+pub type EthFcConf = ffi::rte_eth_fc_conf;
+
+/// The link flow control mode.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum FcMode {
+    /// Disable flow control.
+    None = ffi::rte_eth_fc_mode::RTE_FC_NONE,
+    /// Receive the pause frames, and pause the transmission.
+    RxPause = ffi::rte_eth_fc_mode::RTE_FC_RX_PAUSE,
+    /// Send the pause frames when the receive ring is full.
+    TxPause = ffi::rte_eth_fc_mode::RTE_FC_TX_PAUSE,
+    /// Receive and send the pause frames.
+    Full = ffi::rte_eth_fc_mode::RTE_FC_FULL,
+}
+
+impl FcMode {
+    /// Create the mode from the RX and TX pause, like `ethtool -A`.
+    pub fn new(rx_pause: bool, tx_pause: bool) -> Self {
+        match (rx_pause, tx_pause) {
+            (false, false) => FcMode::None,
+            (true, false) => FcMode::RxPause,
+            (false, true) => FcMode::TxPause,
+            (true, true) => FcMode::Full,
+        }
+    }
+
+    /// Receive the pause frames.
+    pub fn rx_pause(self) -> bool {
+        self == FcMode::RxPause || self == FcMode::Full
+    }
+
+    /// Send the pause frames.
+    pub fn tx_pause(self) -> bool {
+        self == FcMode::TxPause || self == FcMode::Full
+    }
+}
+
+/// The link flow control configuration.
+pub trait FlowCtrlConf: Sized {
+    /// The flow control mode.
+    fn mode(&self) -> FcMode;
+
+    /// Set the flow control mode.
+    fn with_mode(self, mode: FcMode) -> Self;
+
+    /// Negotiate the pause frames with the link partner.
+    fn with_autoneg(self, autoneg: bool) -> Self;
+}
+
+impl FlowCtrlConf for EthFcConf {
+    fn mode(&self) -> FcMode {
+        FcMode::from_u32(self.mode).unwrap_or(FcMode::None)
+    }
+
+    fn with_mode(mut self, mode: FcMode) -> Self {
+        self.mode = mode as u32;
+        self
+    }
+
+    fn with_autoneg(mut self, autoneg: bool) -> Self {
+        self.autoneg = bool_value!(autoneg);
+        self
+    }
+}
+
+pub type EthRxMode = ffi::rte_eth_rxmode;
+pub type EthTxMode = ffi::rte_eth_txmode;
+
+/// The prefetch, host and write-back threshold registers of a descriptor ring.
+pub type EthThresh = ffi::rte_eth_thresh;
+
+/// Create the ring threshold registers.
+pub fn eth_thresh(pthresh: u8, hthresh: u8, wthresh: u8) -> EthThresh {
+    EthThresh {
+        pthresh,
+        hthresh,
+        wthresh,
+    }
+}
+
+/// The number of mbuf segments to hold a received packet of `max_rx_pkt_len` bytes,
+/// the packet is scattered into chained mbufs if more than one segment is needed.
+///
+/// Returns `None` if the data room of mbuf is not larger than the headroom.
+pub fn rx_segments(max_rx_pkt_len: usize, data_room_size: usize) -> Option<usize> {
+    let buf_len = data_room_size
+        .checked_sub(ffi::RTE_PKTMBUF_HEADROOM as usize)
+        .filter(|&len| len > 0)?;
+
+    Some(max_rx_pkt_len.div_ceil(buf_len))
+}
+
+/// RX ring configuration.
+pub type EthRxConf = ffi::rte_eth_rxconf;
+
+/// TX ring configuration.
+pub type EthTxConf = ffi::rte_eth_txconf;
+
+/// The RX queue configuration.
+///
+/// A zero threshold means the default value of the driver,
+/// and the drivers may silently ignore or adjust the values which are unsupported by the hardware.
+pub trait RxQueueConf: Sized {
+    /// Set the RX ring threshold registers.
+    fn with_thresh(self, thresh: EthThresh) -> Self;
+
+    /// Set the threshold to refill the RX descriptors in a batch.
+    fn with_free_thresh(self, free_thresh: u16) -> Self;
+
+    /// Drop packets if no descriptors are available, instead of back-pressure the other queues.
+    fn with_drop_en(self, drop_en: bool) -> Self;
+
+    /// Packets are dropped if no descriptors are available.
+    fn drop_en(&self) -> bool;
+
+    /// Don't start the queue with the device, it is started later with `rx_queue_start`.
+    fn with_deferred_start(self, deferred_start: bool) -> Self;
+
+    /// The queue isn't started with the device.
+    fn deferred_start(&self) -> bool;
+
+    /// Set the per-queue RX offloads, which must be supported by `rx_queue_offload_capa` of the device.
+    fn with_offloads(self, offloads: RxOffload) -> Self;
+
+    /// The per-queue RX offloads.
+    fn offloads(&self) -> RxOffload;
+
+    /// Scatter the packets larger than the data room of mbuf into chained mbufs.
+    fn with_scatter(self) -> Self {
+        let offloads = self.offloads() | RxOffload::DEV_RX_OFFLOAD_SCATTER;
+
+        self.with_offloads(offloads)
+    }
+
+    /// Preset for the lowest latency, the descriptors are written back as soon as possible.
+    fn latency_optimized(self) -> Self {
+        self.with_thresh(eth_thresh(8, 8, 0)).with_free_thresh(32)
+    }
+
+    /// Preset for the highest throughput, the descriptors are written back and refilled in batches.
+    fn throughput_optimized(self) -> Self {
+        self.with_thresh(eth_thresh(8, 8, 4)).with_free_thresh(64)
+    }
+}
+
+impl RxQueueConf for EthRxConf {
+    fn with_thresh(mut self, thresh: EthThresh) -> Self {
+        self.rx_thresh = thresh;
+        self
+    }
+
+    fn with_free_thresh(mut self, free_thresh: u16) -> Self {
+        self.rx_free_thresh = free_thresh;
+        self
+    }
+
+    fn with_drop_en(mut self, drop_en: bool) -> Self {
+        self.rx_drop_en = bool_value!(drop_en);
+        self
+    }
+
+    fn drop_en(&self) -> bool {
+        self.rx_drop_en != 0
+    }
+
+    fn with_deferred_start(mut self, deferred_start: bool) -> Self {
+        self.rx_deferred_start = bool_value!(deferred_start);
+        self
+    }
+
+    fn deferred_start(&self) -> bool {
+        self.rx_deferred_start != 0
+    }
+
+    fn with_offloads(mut self, offloads: RxOffload) -> Self {
+        self.offloads = offloads.bits;
+        self
+    }
+
+    fn offloads(&self) -> RxOffload {
+        RxOffload::from_bits_truncate(self.offloads)
+    }
+}
+
+/// The TX queue configuration.
+///
+/// A zero threshold means the default value of the driver,
+/// and the drivers may silently ignore or adjust the values which are unsupported by the hardware.
+pub trait TxQueueConf: Sized {
+    /// Set the TX ring threshold registers.
+    fn with_thresh(self, thresh: EthThresh) -> Self;
+
+    /// Set the threshold to request the status report of sent descriptors.
+    fn with_rs_thresh(self, rs_thresh: u16) -> Self;
+
+    /// Set the threshold to free the transmitted mbufs.
+    fn with_free_thresh(self, free_thresh: u16) -> Self;
+
+    /// Don't start the queue with the device, it is started later with `tx_queue_start`.
+    fn with_deferred_start(self, deferred_start: bool) -> Self;
+
+    /// The queue isn't started with the device.
+    fn deferred_start(&self) -> bool;
+
+    /// Set the per-queue TX offloads, which must be supported by `tx_queue_offload_capa` of the device.
+    fn with_offloads(self, offloads: TxOffload) -> Self;
+
+    /// The per-queue TX offloads.
+    fn offloads(&self) -> TxOffload;
+
+    /// Release the transmitted mbufs to their mempool in bulk, without checking their reference counter.
+    ///
+    /// All the mbufs sent on the queue must come from the same mempool, and have a reference counter of 1,
+    /// which is asserted by `tx_burst` in the debug builds.
+    fn with_fast_free(self) -> Self {
+        let offloads = self.offloads() | TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE;
+
+        self.with_offloads(offloads)
+    }
+
+    /// Preset for the lowest latency, the sent descriptors are reported and freed in small batches.
+    fn latency_optimized(self) -> Self {
+        self.with_thresh(eth_thresh(32, 0, 0))
+            .with_rs_thresh(16)
+            .with_free_thresh(16)
+    }
+
+    /// Preset for the highest throughput, the sent descriptors are reported and freed in large batches.
+    fn throughput_optimized(self) -> Self {
+        self.with_thresh(eth_thresh(36, 0, 0))
+            .with_rs_thresh(32)
+            .with_free_thresh(64)
+    }
+}
+
+impl TxQueueConf for EthTxConf {
+    fn with_thresh(mut self, thresh: EthThresh) -> Self {
+        self.tx_thresh = thresh;
+        self
+    }
+
+    fn with_rs_thresh(mut self, rs_thresh: u16) -> Self {
+        self.tx_rs_thresh = rs_thresh;
+        self
+    }
+
+    fn with_free_thresh(mut self, free_thresh: u16) -> Self {
+        self.tx_free_thresh = free_thresh;
+        self
+    }
+
+    fn with_deferred_start(mut self, deferred_start: bool) -> Self {
+        self.tx_deferred_start = bool_value!(deferred_start);
+        self
+    }
+
+    fn deferred_start(&self) -> bool {
+        self.tx_deferred_start != 0
+    }
+
+    fn with_offloads(mut self, offloads: TxOffload) -> Self {
+        self.offloads = offloads.bits;
+        self
+    }
+
+    fn offloads(&self) -> TxOffload {
+        TxOffload::from_bits_truncate(self.offloads)
+    }
+}
+
+/// The hairpin capability of an Ethernet device.
+pub type HairpinCap = ffi::_rte_eth_hairpin_cap;
+
+/// The max number of the peers of a hairpin queue.
+pub const MAX_HAIRPIN_PEERS: usize = ffi::_RTE_ETH_MAX_HAIRPIN_PEERS as usize;
+
+/// The configuration of a hairpin queue.
+///
+/// ```no_run
+/// use rte::ethdev::{EthDevice, HairpinConf};
+/// use rte::flow::{FlowAction, FlowAttr, FlowItem, FlowRule, FlowTable};
+/// use rte::PortId;
+///
+/// let (rx_port, tx_port): (PortId, PortId) = (0, 1);
+///
+/// // loop the packets received on the hairpin queue 1 of port 0 to the hairpin queue 1 of port 1
+/// rx_port.rx_hairpin_queue_setup(1, 0, &HairpinConf::default().with_peer(tx_port, 1)).unwrap();
+/// tx_port.tx_hairpin_queue_setup(1, 0, &HairpinConf::default().with_peer(rx_port, 1)).unwrap();
+///
+/// // steer the packets to the hairpin queue with a flow rule
+/// FlowTable::new(rx_port)
+///     .create(FlowRule {
+///         attr: FlowAttr::ingress(),
+///         pattern: vec![FlowItem::eth(), FlowItem::ipv4()],
+///         actions: vec![FlowAction::Queue(1)],
+///     })
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HairpinConf {
+    /// The peer queues, the transmit queues of a receive queue, or the receive queues of a transmit queue.
+    pub peers: Vec<(PortId, QueueId)>,
+    /// The TX flow rules are inserted explicitly by the application.
+    pub tx_explicit: bool,
+    /// The queues are bound manually with `EthDevice::hairpin_bind`, for the peers on another port.
+    pub manual_bind: bool,
+}
+
+impl HairpinConf {
+    /// Add a peer queue.
+    pub fn with_peer(mut self, port_id: PortId, queue_id: QueueId) -> Self {
+        self.peers.push((port_id, queue_id));
+        self
+    }
+
+    /// Insert the TX flow rules explicitly.
+    pub fn with_tx_explicit(mut self, tx_explicit: bool) -> Self {
+        self.tx_explicit = tx_explicit;
+        self
+    }
+
+    /// Bind the queues manually.
+    pub fn with_manual_bind(mut self, manual_bind: bool) -> Self {
+        self.manual_bind = manual_bind;
+        self
+    }
+
+    fn to_raw(&self) -> Result<ffi::_rte_eth_hairpin_conf> {
+        if self.peers.is_empty() || self.peers.len() > MAX_HAIRPIN_PEERS {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        let mut conf = ffi::_rte_eth_hairpin_conf {
+            peer_count: self.peers.len() as u16,
+            tx_explicit: bool_value!(self.tx_explicit),
+            manual_bind: bool_value!(self.manual_bind),
+            ..Default::default()
+        };
+
+        for (peer, &(port, queue)) in conf.peers.iter_mut().zip(&self.peers) {
+            peer.port = port;
+            peer.queue = queue;
+        }
+
+        Ok(conf)
+    }
+}
+
+/// The status of a descriptor in the RX or TX ring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DescStatus {
+    /// The RX descriptor is available for the hardware to receive a packet.
+    Avail,
+    /// The RX descriptor holds a received packet, or the TX descriptor has been sent and could be reclaimed.
+    Done,
+    /// The TX descriptor is waiting to be sent by the hardware.
+    Full,
+    /// The descriptor is used by the driver and not yet returned to the hardware.
+    Unavail,
+}
+
+impl DescStatus {
+    fn symbol(self) -> char {
+        match self {
+            DescStatus::Avail => '.',
+            DescStatus::Done => 'D',
+            DescStatus::Full => 'F',
+            DescStatus::Unavail => 'x',
+        }
+    }
+}
+
+/// A RX or TX queue of an Ethernet device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueueHandle {
+    /// The RX queue.
+    Rx(PortId, QueueId),
+    /// The TX queue.
+    Tx(PortId, QueueId),
+}
+
+impl fmt::Display for QueueHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            QueueHandle::Rx(port_id, queue_id) => write!(f, "port {} rxq {}", port_id, queue_id),
+            QueueHandle::Tx(port_id, queue_id) => write!(f, "port {} txq {}", port_id, queue_id),
+        }
+    }
+}
+
+impl QueueHandle {
+    /// The port of the queue.
+    pub fn portid(&self) -> PortId {
+        match *self {
+            QueueHandle::Rx(port_id, _) | QueueHandle::Tx(port_id, _) => port_id,
+        }
+    }
+
+    /// The queue id.
+    pub fn queue_id(&self) -> QueueId {
+        match *self {
+            QueueHandle::Rx(_, queue_id) | QueueHandle::Tx(_, queue_id) => queue_id,
+        }
+    }
+
+    /// The configured number of descriptors, if the driver supports the queue information.
+    pub fn nb_desc(&self) -> Result<u16> {
+        match *self {
+            QueueHandle::Rx(port_id, queue_id) => {
+                let mut qinfo = ffi::rte_eth_rxq_info::default();
+
+                let ret = unsafe { ffi::rte_eth_rx_queue_info_get(port_id, queue_id, &mut qinfo) };
+
+                rte_check!(ret; ok => { qinfo.nb_desc }; err => { RteError(-ret).into() })
+            }
+            QueueHandle::Tx(port_id, queue_id) => {
+                let mut qinfo = ffi::rte_eth_txq_info::default();
+
+                let ret = unsafe { ffi::rte_eth_tx_queue_info_get(port_id, queue_id, &mut qinfo) };
+
+                rte_check!(ret; ok => { qinfo.nb_desc }; err => { RteError(-ret).into() })
+            }
+        }
+    }
+
+    /// The number of used descriptors of a RX queue, which hold the received packets.
+    pub fn used_count(&self) -> Result<usize> {
+        match *self {
+            QueueHandle::Rx(port_id, queue_id) => {
+                let ret = unsafe { ffi::_rte_eth_rx_queue_count(port_id, queue_id) };
+
+                if ret < 0 {
+                    Err(RteError(-ret).into())
+                } else {
+                    Ok(ret as usize)
+                }
+            }
+            QueueHandle::Tx(..) => Err(RteError(libc::ENOTSUP).into()),
+        }
+    }
+
+    /// The status of a descriptor.
+    ///
+    /// The offset is relative to the next descriptor which will be processed by the driver,
+    /// e.g. the next received packet of RX queue, or the next descriptor to be used for sending.
+    pub fn descriptor_status(&self, offset: u16) -> Result<DescStatus> {
+        let ret = unsafe {
+            match *self {
+                QueueHandle::Rx(port_id, queue_id) => ffi::_rte_eth_rx_descriptor_status(port_id, queue_id, offset),
+                QueueHandle::Tx(port_id, queue_id) => ffi::_rte_eth_tx_descriptor_status(port_id, queue_id, offset),
+            }
+        };
+
+        if ret < 0 {
+            return Err(RteError(-ret).into());
+        }
+
+        Ok(match (*self, ret as u32) {
+            (QueueHandle::Rx(..), ffi::RTE_ETH_RX_DESC_AVAIL) => DescStatus::Avail,
+            (QueueHandle::Rx(..), ffi::RTE_ETH_RX_DESC_DONE) => DescStatus::Done,
+            (QueueHandle::Tx(..), ffi::RTE_ETH_TX_DESC_FULL) => DescStatus::Full,
+            (QueueHandle::Tx(..), ffi::RTE_ETH_TX_DESC_DONE) => DescStatus::Done,
+            _ => DescStatus::Unavail,
+        })
+    }
+
+    /// Dump the status of the descriptors in a range of offsets, which defaults to the whole ring.
+    ///
+    /// The driver must support the descriptor status, and the queue information if the range is unbounded.
+    pub fn dump_descriptors<R: RangeBounds<u16>>(&self, range: R) -> Result<DescriptorDump> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.nb_desc()?,
+        };
+
+        let statuses = (start..end)
+            .map(|offset| self.descriptor_status(offset))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DescriptorDump {
+            queue: *self,
+            start,
+            statuses,
+        })
+    }
+}
+
+/// A snapshot of the descriptor status in a ring, which shows the ring occupancy map.
+///
+/// The descriptors are shown as `.` for available, `D` for done, `F` for full and `x` for unavailable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescriptorDump {
+    /// The queue of the ring.
+    pub queue: QueueHandle,
+    /// The offset of the first descriptor.
+    pub start: u16,
+    /// The status of the descriptors.
+    pub statuses: Vec<DescStatus>,
+}
+
+impl DescriptorDump {
+    const LINE_WIDTH: usize = 64;
+
+    /// The number of descriptors in the status.
+    pub fn count(&self, status: DescStatus) -> usize {
+        self.statuses.iter().filter(|&&s| s == status).count()
+    }
+}
+
+impl fmt::Display for DescriptorDump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} descriptors from offset {}",
+            self.queue,
+            self.statuses.len(),
+            self.start
+        )?;
+
+        for &status in &[
+            DescStatus::Avail,
+            DescStatus::Done,
+            DescStatus::Full,
+            DescStatus::Unavail,
+        ] {
+            let n = self.count(status);
+
+            if n > 0 {
+                write!(f, ", {:?} {}", status, n)?;
+            }
+        }
+
+        writeln!(f)?;
+
+        for (i, line) in self.statuses.chunks(Self::LINE_WIDTH).enumerate() {
+            writeln!(
+                f,
+                "{:>5}: {}",
+                self.start as usize + i * Self::LINE_WIDTH,
+                line.iter().map(|s| s.symbol()).collect::<String>()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub type RawRxQueueInfo = ffi::rte_eth_rxq_info;
+pub type RawTxQueueInfo = ffi::rte_eth_txq_info;
+
+/// The information of a RX queue.
+#[derive(Clone, Copy, Debug)]
+pub struct RxQueueInfo {
+    mp: *mut mempool::RawMemoryPool,
+    /// The configuration of the queue.
+    pub conf: EthRxConf,
+    /// The offloads enabled on the queue.
+    pub offloads: RxOffload,
+    /// The packets larger than the data room of mbuf are scattered into chained mbufs.
+    pub scattered: bool,
+    /// The number of descriptors of the queue.
+    pub nb_desc: u16,
+}
+
+impl From<&RawRxQueueInfo> for RxQueueInfo {
+    fn from(qinfo: &RawRxQueueInfo) -> Self {
+        RxQueueInfo {
+            mp: qinfo.mp,
+            conf: qinfo.conf,
+            offloads: qinfo.conf.offloads(),
+            scattered: qinfo.scattered_rx != 0,
+            nb_desc: qinfo.nb_desc,
+        }
+    }
+}
+
+impl RxQueueInfo {
+    /// The mempool of the received packets, the hairpin queues have no mempool.
+    pub fn mempool<'a>(&'a self) -> Option<mempool::MemPoolRef<'a>> {
+        if self.mp.is_null() {
+            None
+        } else {
+            Some(unsafe { mempool::MemPoolRef::from_ptr(self.mp) })
+        }
+    }
+}
+
+/// The information of a TX queue.
+#[derive(Clone, Copy, Debug)]
+pub struct TxQueueInfo {
+    /// The configuration of the queue.
+    pub conf: EthTxConf,
+    /// The offloads enabled on the queue.
+    pub offloads: TxOffload,
+    /// The number of descriptors of the queue.
+    pub nb_desc: u16,
+}
+
+impl From<&RawTxQueueInfo> for TxQueueInfo {
+    fn from(qinfo: &RawTxQueueInfo) -> Self {
+        TxQueueInfo {
+            conf: qinfo.conf,
+            offloads: qinfo.conf.offloads(),
+            nb_desc: qinfo.nb_desc,
+        }
+    }
+}
+
+/// A RX queue of an Ethernet device, which is returned by `rx_queue_setup`.
+///
+/// The queue owns its id, so the queues of the different ports can't be mixed up.
+/// It could be moved to the lcore which polls it, but not shared between the lcores.
+///
+/// The queue borrows the mempool of the received packets, which must outlive it.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct RxQueue<'a> {
+    port_id: PortId,
+    queue_id: QueueId,
+    mb_pool: mempool::MemPoolRef<'a>,
+    _marker: PhantomData<Cell<()>>,
+}
+
+impl<'a> fmt::Display for RxQueue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.handle().fmt(f)
+    }
+}
+
+impl<'a> RxQueue<'a> {
+    fn new(port_id: PortId, queue_id: QueueId, mb_pool: mempool::MemPoolRef<'a>) -> Self {
+        RxQueue {
+            port_id,
+            queue_id,
+            mb_pool,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The port of the queue.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The queue id.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
+    }
+
+    /// The mempool of the received packets.
+    pub fn mempool(&self) -> mempool::MemPoolRef<'a> {
+        self.mb_pool
+    }
+
+    /// The handle of the queue.
+    pub fn handle(&self) -> QueueHandle {
+        QueueHandle::Rx(self.port_id, self.queue_id)
+    }
+
+    /// Retrieve the information of the queue.
+    pub fn info(&self) -> Result<RawRxQueueInfo> {
+        let mut qinfo = RawRxQueueInfo::default();
+
+        let ret = unsafe { ffi::rte_eth_rx_queue_info_get(self.port_id, self.queue_id, &mut qinfo) };
+
+        rte_check!(ret; ok => { qinfo }; err => { RteError(-ret).into() })
+    }
+
+    /// Start the queue, which was deferred or stopped.
+    pub fn start(&self) -> Result<&Self> {
+        self.port_id.rx_queue_start(self.queue_id).map(|_| self)
+    }
+
+    /// Stop the queue.
+    pub fn stop(&self) -> Result<&Self> {
+        self.port_id.rx_queue_stop(self.queue_id).map(|_| self)
+    }
+
+    /// The number of used descriptors, which hold the received packets.
+    pub fn used_count(&self) -> Result<usize> {
+        self.handle().used_count()
+    }
+
+    /// Retrieve a burst of input packets from the queue.
+    #[inline]
+    pub fn rx_burst(&self, rx_pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        self.port_id.rx_burst(self.queue_id, rx_pkts)
+    }
+
+    /// Retrieve a burst of input packets into the uninitialized slots, returns the number of initialized ones.
+    #[inline]
+    pub fn rx_burst_uninit(&self, rx_pkts: &mut [mem::MaybeUninit<mbuf::MBuf>]) -> usize {
+        self.port_id.rx_burst_uninit(self.queue_id, rx_pkts)
+    }
+
+    /// Retrieve a burst of input packets into the spare capacity of the `ArrayVec`.
+    #[inline]
+    pub fn rx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, rx_pkts: &mut ArrayVec<A>) -> usize {
+        self.port_id.rx_burst_mbuf(self.queue_id, rx_pkts)
+    }
+    /// Add a callback of the packets received on the queue, which is removed when dropped.
+    pub fn add_callback<F>(&self, callback: F) -> Result<RxCallback>
+    where
+        F: FnMut(&mut BurstView) + Send + 'static,
+    {
+        add_rx_callback(self.port_id, self.queue_id, callback)
+    }
+}
+
+/// A TX queue of an Ethernet device, which is returned by `tx_queue_setup`.
+///
+/// The queue owns its id, so the queues of the different ports can't be mixed up.
+/// It could be moved to the lcore which polls it, but not shared between the lcores.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct TxQueue {
+    port_id: PortId,
+    queue_id: QueueId,
+    _marker: PhantomData<Cell<()>>,
+}
+
+impl fmt::Display for TxQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.handle().fmt(f)
+    }
+}
+
+impl TxQueue {
+    fn new(port_id: PortId, queue_id: QueueId) -> Self {
+        TxQueue {
+            port_id,
+            queue_id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The port of the queue.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The queue id.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
+    }
+
+    /// The handle of the queue.
+    pub fn handle(&self) -> QueueHandle {
+        QueueHandle::Tx(self.port_id, self.queue_id)
+    }
+
+    /// Retrieve the information of the queue.
+    pub fn info(&self) -> Result<RawTxQueueInfo> {
+        let mut qinfo = RawTxQueueInfo::default();
+
+        let ret = unsafe { ffi::rte_eth_tx_queue_info_get(self.port_id, self.queue_id, &mut qinfo) };
+
+        rte_check!(ret; ok => { qinfo }; err => { RteError(-ret).into() })
+    }
+
+    /// Start the queue, which was deferred or stopped.
+    pub fn start(&self) -> Result<&Self> {
+        self.port_id.tx_queue_start(self.queue_id).map(|_| self)
+    }
+
+    /// Stop the queue.
+    pub fn stop(&self) -> Result<&Self> {
+        self.port_id.tx_queue_stop(self.queue_id).map(|_| self)
+    }
+
+    /// Send a burst of output packets, returns the number of the packets which were sent.
+    #[inline]
+    pub fn tx_burst<T: AsRaw<Raw = mbuf::RawMBuf>>(&self, tx_pkts: &mut [T]) -> usize {
+        self.port_id.tx_burst(self.queue_id, tx_pkts)
+    }
+
+    /// Send the packets of the `ArrayVec`, the sent ones are removed from it.
+    #[inline]
+    pub fn tx_burst_mbuf<A: Array<Item = mbuf::MBuf>>(&self, tx_pkts: &mut ArrayVec<A>) -> usize {
+        self.port_id.tx_burst_mbuf(self.queue_id, tx_pkts)
+    }
+
+    /// Send all the packets, retrying per the policy.
+    pub fn tx_burst_all(&self, tx_pkts: Vec<mbuf::MBuf>, policy: &TxRetryPolicy) -> TxBurstResult {
+        self.port_id.tx_burst_all(self.queue_id, tx_pkts, policy)
+    }
+    /// Add a callback of the packets sent to the queue, which is removed when dropped.
+    pub fn add_callback<F>(&self, callback: F) -> Result<TxCallback>
+    where
+        F: FnMut(&mut BurstView) + Send + 'static,
+    {
+        add_tx_callback(self.port_id, self.queue_id, callback)
+    }
+}
+
+#[derive(Default)]
+pub struct EthConf {
+    /// bitmap of ETH_LINK_SPEED_XXX of speeds to be used.
+    ///
+    /// ETH_LINK_SPEED_FIXED disables link autonegotiation, and a unique speed shall be set.
+    /// Otherwise, the bitmap defines the set of speeds to be advertised.
+    /// If the special value ETH_LINK_SPEED_AUTONEG (0) is used,
+    /// all speeds supported are advertised.
+    pub link_speeds: LinkSpeed,
+    /// Port RX configuration.
+    pub rxmode: Option<EthRxMode>,
+    /// Port TX configuration.
+    pub txmode: Option<EthTxMode>,
+    /// Loopback operation mode.
+    ///
+    /// By default the value is 0, meaning the loopback mode is disabled.
+    /// Read the datasheet of given ethernet controller for details.
+    /// The possible values of this field are defined in implementation of each driver.
+    pub lpbk_mode: u32,
+    /// Port RX filtering configuration (union).
+    pub rx_adv_conf: Option<RxAdvConf>,
+    /// Port TX DCB configuration (union).
+    pub tx_adv_conf: Option<TxAdvConf>,
+    /// Currently,Priority Flow Control(PFC) are supported,
+    /// if DCB with PFC is needed, and the variable must be set ETH_DCB_PFC_SUPPORT.
+    pub dcb_capability_en: u32,
+    pub fdir_conf: Option<ffi::rte_fdir_conf>,
+    pub intr_conf: Option<ffi::rte_intr_conf>,
+}
+
+pub type RawEthConfPtr = *const ffi::rte_eth_conf;
+
+pub struct RawEthConf(ffi::rte_eth_conf);
+
+impl RawEthConf {
+    fn as_raw(&self) -> RawEthConfPtr {
+        &self.0
+    }
+}
+
+impl<'a> From<&'a EthConf> for RawEthConf {
+    fn from(c: &EthConf) -> Self {
+        let mut conf = ffi::rte_eth_conf {
+            link_speeds: c.link_speeds.bits,
+            lpbk_mode: c.lpbk_mode,
+            dcb_capability_en: c.dcb_capability_en,
+            ..Default::default()
+        };
+
+        if let Some(ref rxmode) = c.rxmode {
+            conf.rxmode = *rxmode
+        }
+
+        if let Some(ref txmode) = c.txmode {
+            conf.txmode = *txmode
+        }
+
+        if let Some(ref adv_conf) = c.rx_adv_conf {
+            if let Some(ref rss_conf) = adv_conf.rss_conf {
+                let (rss_key, rss_key_len) = rss_conf
+                    .key
+                    .map_or_else(|| (ptr::null(), 0), |key| (key.as_ptr(), key.len() as u8));
+
+                conf.rx_adv_conf.rss_conf.rss_key = rss_key as *mut _;
+                conf.rx_adv_conf.rss_conf.rss_key_len = rss_key_len;
+                conf.rx_adv_conf.rss_conf.rss_hf = rss_conf.hash.bits;
+            }
+
+            if let Some(vmdq_dcb_conf) = adv_conf.vmdq_dcb_conf {
+                conf.rx_adv_conf.vmdq_dcb_conf = vmdq_dcb_conf;
+            }
+
+            if let Some(dcb_rx_conf) = adv_conf.dcb_rx_conf {
+                conf.rx_adv_conf.dcb_rx_conf = dcb_rx_conf;
+            }
+
+            if let Some(vmdq_rx_conf) = adv_conf.vmdq_rx_conf {
+                conf.rx_adv_conf.vmdq_rx_conf = vmdq_rx_conf;
+            }
+        }
+
+        if let Some(fdir_conf) = c.fdir_conf {
+            conf.fdir_conf = fdir_conf;
+        }
+
+        if let Some(intr_conf) = c.intr_conf {
+            conf.intr_conf = intr_conf;
+        }
+
+        RawEthConf(conf)
+    }
+}
+
+impl From<&ffi::rte_eth_conf> for EthConf {
+    fn from(c: &ffi::rte_eth_conf) -> Self {
+        let rss_conf = &c.rx_adv_conf.rss_conf;
+
+        EthConf {
+            link_speeds: LinkSpeed::from_bits_truncate(c.link_speeds),
+            rxmode: Some(c.rxmode),
+            txmode: Some(c.txmode),
+            lpbk_mode: c.lpbk_mode,
+            rx_adv_conf: Some(RxAdvConf {
+                rss_conf: if rss_conf.rss_hf == 0 {
+                    None
+                } else {
+                    Some(EthRssConf {
+                        key: None,
+                        hash: RssHashFunc::from_bits_truncate(rss_conf.rss_hf),
+                    })
+                },
+                vmdq_dcb_conf: Some(c.rx_adv_conf.vmdq_dcb_conf),
+                dcb_rx_conf: Some(c.rx_adv_conf.dcb_rx_conf),
+                vmdq_rx_conf: Some(c.rx_adv_conf.vmdq_rx_conf),
+            }),
+            tx_adv_conf: None,
+            dcb_capability_en: c.dcb_capability_en,
+            fdir_conf: Some(c.fdir_conf),
+            intr_conf: Some(c.intr_conf),
+        }
+    }
+}
+
+/// The built-in configuration presets of a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Forward the Ethernet frames as is, without RSS or RX offloads,
+    /// the transmitted mbufs are freed fast if supported.
+    L2Forwarding,
+    /// Spread the packets over the RX queues by the RSS hash of the IP addresses and L4 ports,
+    /// and verify the IP and L4 checksums if supported.
+    RssL3L4,
+    /// Receive the jumbo frames, aggregated by the LRO and scattered into the multiple segments if supported,
+    /// and transmit the multi-segment and TSO packets if supported.
+    JumboLro,
+    /// Avoid the aggregation and interrupts, the checksums are offloaded if supported,
+    /// the short rings of `nb_desc` bound the queuing delay.
+    LowLatency,
+}
+
+impl Profile {
+    /// The maximum length of the frames received by the `JumboLro` profile, for the MTU of 9000 bytes.
+    pub const JUMBO_FRAME_LEN: u32 = 9018;
+
+    /// The recommended number of the RX and TX descriptors of a queue,
+    /// which should be adjusted with `adjust_nb_rx_tx_desc`.
+    pub fn nb_desc(self) -> (u16, u16) {
+        match self {
+            Profile::LowLatency => (128, 128),
+            Profile::JumboLro => (1024, 2048),
+            _ => (1024, 1024),
+        }
+    }
+
+    /// The configuration of the profile, validated against the capabilities of the device.
+    ///
+    /// The optional offloads are only enabled if the device supports them,
+    /// returns `ENOTSUP` if the device doesn't support the required one.
+    pub fn conf(self, info: &RawEthDeviceInfo) -> Result<EthConf> {
+        let rx_capa = RxOffload::from_bits_truncate(info.rx_offload_capa);
+        let tx_capa = TxOffload::from_bits_truncate(info.tx_offload_capa);
+
+        let mut rxmode = EthRxMode::default();
+        let mut txmode = EthTxMode::default();
+        let mut conf = EthConf::default();
+
+        match self {
+            Profile::L2Forwarding => {
+                txmode.offloads = (tx_capa & TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE).bits;
+            }
+            Profile::RssL3L4 => {
+                let hash = (RssHashFunc::ETH_RSS_IP | RssHashFunc::ETH_RSS_TCP | RssHashFunc::ETH_RSS_UDP)
+                    & RssHashFunc::from_bits_truncate(info.flow_type_rss_offloads);
+
+                if hash.is_empty() {
+                    return Err(RteError(libc::ENOTSUP).into());
+                }
+
+                rxmode.mq_mode = ffi::rte_eth_rx_mq_mode::ETH_MQ_RX_RSS;
+                rxmode.offloads = (rx_capa & RxOffload::DEV_RX_OFFLOAD_CHECKSUM).bits;
+
+                conf.rx_adv_conf = Some(RxAdvConf {
+                    rss_conf: Some(EthRssConf { key: None, hash }),
+                    ..RxAdvConf::default()
+                });
+            }
+            Profile::JumboLro => {
+                if !rx_capa.contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME) {
+                    return Err(RteError(libc::ENOTSUP).into());
+                }
+
+                rxmode.offloads = (RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME
+                    | rx_capa & (RxOffload::DEV_RX_OFFLOAD_TCP_LRO | RxOffload::DEV_RX_OFFLOAD_SCATTER))
+                    .bits;
+                rxmode.max_rx_pkt_len = cmp::min(Self::JUMBO_FRAME_LEN, info.max_rx_pktlen);
+                txmode.offloads =
+                    (tx_capa & (TxOffload::DEV_TX_OFFLOAD_MULTI_SEGS | TxOffload::DEV_TX_OFFLOAD_TCP_TSO)).bits;
+            }
+            Profile::LowLatency => {
+                rxmode.offloads = (rx_capa & RxOffload::DEV_RX_OFFLOAD_CHECKSUM).bits;
+                txmode.offloads = (tx_capa
+                    & (TxOffload::DEV_TX_OFFLOAD_IPV4_CKSUM
+                        | TxOffload::DEV_TX_OFFLOAD_UDP_CKSUM
+                        | TxOffload::DEV_TX_OFFLOAD_TCP_CKSUM))
+                    .bits;
+                conf.intr_conf = Some(ffi::rte_intr_conf::default());
+            }
+        }
+
+        conf.rxmode = Some(rxmode);
+        conf.txmode = Some(txmode);
+
+        validate_conf(info, &conf)?;
+
+        Ok(conf)
+    }
+
+    /// The configuration of the profile for the port.
+    pub fn for_port(self, port_id: PortId) -> Result<EthConf> {
+        self.conf(&port_id.info())
+    }
+}
+
+impl EthConf {
+    /// Create a builder of the configuration.
+    pub fn builder() -> EthConfBuilder {
+        EthConfBuilder::new()
+    }
+}
+
+/// A builder of the port configuration, which fills the raw RX and TX modes consistently.
+///
+/// ```no_run
+/// use rte::ethdev::{EthConfBuilder, EthDevice, RssHashFunc, RxOffload};
+///
+/// let port_id = 0;
+/// let conf = EthConfBuilder::new()
+///     .rss(RssHashFunc::ETH_RSS_IP | RssHashFunc::ETH_RSS_TCP)
+///     .max_rx_pkt_len(9018)
+///     .rx_offloads(RxOffload::DEV_RX_OFFLOAD_CHECKSUM)
+///     .build_for_port(port_id)
+///     .unwrap();
+///
+/// port_id.configure(4, 4, &conf).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EthConfBuilder {
+    link_speeds: LinkSpeed,
+    rx_mq_mode: Option<EthRxMultiQueueMode>,
+    tx_mq_mode: EthTxMultiQueueMode,
+    max_rx_pkt_len: Option<u32>,
+    max_lro_pkt_size: Option<u32>,
+    rx_offloads: RxOffload,
+    tx_offloads: TxOffload,
+    rss_hash: Option<RssHashFunc>,
+    rss_supported: bool,
+    rss_key: Option<[u8; 40]>,
+    lpbk_mode: u32,
+    lsc_intr: bool,
+    rxq_intr: bool,
+}
+
+impl Default for EthConfBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the settings of the configuration, except the VMDq, DCB and flow director ones.
+impl From<&EthConf> for EthConfBuilder {
+    fn from(conf: &EthConf) -> Self {
+        let mut builder = Self::new();
+
+        builder.link_speeds = conf.link_speeds;
+        builder.lpbk_mode = conf.lpbk_mode;
+
+        if let Some(ref rxmode) = conf.rxmode {
+            builder.rx_mq_mode = Some(EthRxMultiQueueMode::from_bits_truncate(rxmode.mq_mode));
+            builder.rx_offloads = RxOffload::from_bits_truncate(rxmode.offloads);
+
+            if builder.rx_offloads.contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME) {
+                builder.max_rx_pkt_len = Some(rxmode.max_rx_pkt_len);
+            }
+
+            if builder.rx_offloads.contains(RxOffload::DEV_RX_OFFLOAD_TCP_LRO) {
+                builder.max_lro_pkt_size = builder.max_rx_pkt_len;
+            }
+        }
+
+        if let Some(ref txmode) = conf.txmode {
+            builder.tx_mq_mode = txmode.mq_mode;
+            builder.tx_offloads = TxOffload::from_bits_truncate(txmode.offloads);
+        }
+
+        if let Some(rss_conf) = conf
+            .rx_adv_conf
+            .as_ref()
+            .and_then(|adv_conf| adv_conf.rss_conf.as_ref())
+        {
+            builder.rss_hash = Some(rss_conf.hash);
+            builder.rss_key = rss_conf.key;
+        }
+
+        if let Some(ref intr_conf) = conf.intr_conf {
+            builder.lsc_intr = intr_conf.lsc() != 0;
+            builder.rxq_intr = intr_conf.rxq() != 0;
+        }
+
+        builder
+    }
+}
+
+impl EthConfBuilder {
+    /// Create a builder of the default configuration.
+    pub fn new() -> Self {
+        EthConfBuilder {
+            link_speeds: LinkSpeed::default(),
+            rx_mq_mode: None,
+            tx_mq_mode: ffi::rte_eth_tx_mq_mode::ETH_MQ_TX_NONE,
+            max_rx_pkt_len: None,
+            max_lro_pkt_size: None,
+            rx_offloads: RxOffload::empty(),
+            tx_offloads: TxOffload::empty(),
+            rss_hash: None,
+            rss_supported: false,
+            rss_key: None,
+            lpbk_mode: 0,
+            lsc_intr: false,
+            rxq_intr: false,
+        }
+    }
+
+    /// The speeds to be advertised, or the fixed speed with `ETH_LINK_SPEED_FIXED`.
+    pub fn link_speeds(&mut self, speeds: LinkSpeed) -> &mut Self {
+        self.link_speeds = speeds;
+        self
+    }
+
+    /// The method to route the received packets to the multiple queues,
+    /// which defaults to RSS if the RSS hash is set.
+    pub fn rx_mq_mode(&mut self, mode: EthRxMultiQueueMode) -> &mut Self {
+        self.rx_mq_mode = Some(mode);
+        self
+    }
+
+    /// The method to transmit the packets with the multiple TCs.
+    pub fn tx_mq_mode(&mut self, mode: EthTxMultiQueueMode) -> &mut Self {
+        self.tx_mq_mode = mode;
+        self
+    }
+
+    /// Spread the received packets over the queues by the RSS hash of the flow types.
+    ///
+    /// `build_for` fails if the device doesn't support any of the flow types.
+    pub fn rss(&mut self, hash: RssHashFunc) -> &mut Self {
+        self.rss_hash = Some(hash);
+        self.rss_supported = false;
+        self
+    }
+
+    /// Spread the received packets over the queues by the RSS hash of the flow types supported by the device.
+    ///
+    /// `build_for` only keeps the flow types supported by the device, and fails if none of them is supported.
+    pub fn rss_supported(&mut self, hash: RssHashFunc) -> &mut Self {
+        self.rss_hash = Some(hash);
+        self.rss_supported = true;
+        self
+    }
+
+    /// The RSS hash key, or the default key of the driver.
+    pub fn rss_key(&mut self, key: [u8; 40]) -> &mut Self {
+        self.rss_key = Some(key);
+        self
+    }
+
+    /// The maximum length of the received frames, the jumbo frame is enabled if it exceeds `ETHER_MAX_LEN`.
+    pub fn max_rx_pkt_len(&mut self, len: u32) -> &mut Self {
+        self.max_rx_pkt_len = Some(len);
+        self.rx_offloads
+            .set(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME, len > ether::ETHER_MAX_LEN);
+        self
+    }
+
+    /// Enable LRO, which aggregates the received TCP segments into the packets up to `max_size` bytes.
+    ///
+    /// The DPDK 18.11 has no dedicated limit of LRO, the drivers aggregate the packets
+    /// up to the maximum RX packet length, which is raised to `max_size` if it is lower.
+    pub fn lro(&mut self, max_size: u32) -> &mut Self {
+        self.max_lro_pkt_size = Some(max_size);
+        self.rx_offloads |= RxOffload::DEV_RX_OFFLOAD_TCP_LRO;
+        self
     }
-}
 
-impl Default for LinkSpeed {
-    fn default() -> Self {
-        LinkSpeed::ETH_LINK_SPEED_AUTONEG
+    /// Enable the RX offloads on all the queues of the port.
+    pub fn rx_offloads(&mut self, offloads: RxOffload) -> &mut Self {
+        self.rx_offloads |= offloads;
+        self
     }
-}
 
-pub type EthRxMode = ffi::rte_eth_rxmode;
-pub type EthTxMode = ffi::rte_eth_txmode;
+    /// Enable the TX offloads on all the queues of the port.
+    pub fn tx_offloads(&mut self, offloads: TxOffload) -> &mut Self {
+        self.tx_offloads |= offloads;
+        self
+    }
 
-#[derive(Default)]
-pub struct EthConf {
-    /// bitmap of ETH_LINK_SPEED_XXX of speeds to be used.
-    ///
-    /// ETH_LINK_SPEED_FIXED disables link autonegotiation, and a unique speed shall be set.
-    /// Otherwise, the bitmap defines the set of speeds to be advertised.
-    /// If the special value ETH_LINK_SPEED_AUTONEG (0) is used,
-    /// all speeds supported are advertised.
-    pub link_speeds: LinkSpeed,
-    /// Port RX configuration.
-    pub rxmode: Option<EthRxMode>,
-    /// Port TX configuration.
-    pub txmode: Option<EthTxMode>,
-    /// Loopback operation mode.
-    ///
-    /// By default the value is 0, meaning the loopback mode is disabled.
-    /// Read the datasheet of given ethernet controller for details.
-    /// The possible values of this field are defined in implementation of each driver.
-    pub lpbk_mode: u32,
-    /// Port RX filtering configuration (union).
-    pub rx_adv_conf: Option<RxAdvConf>,
-    /// Port TX DCB configuration (union).
-    pub tx_adv_conf: Option<TxAdvConf>,
-    /// Currently,Priority Flow Control(PFC) are supported,
-    /// if DCB with PFC is needed, and the variable must be set ETH_DCB_PFC_SUPPORT.
-    pub dcb_capability_en: u32,
-    pub fdir_conf: Option<ffi::rte_fdir_conf>,
-    pub intr_conf: Option<ffi::rte_intr_conf>,
-}
+    /// Enable the RX and TX offloads on all the queues of the port.
+    pub fn offloads(&mut self, rx_offloads: RxOffload, tx_offloads: TxOffload) -> &mut Self {
+        self.rx_offloads(rx_offloads).tx_offloads(tx_offloads)
+    }
 
-pub type RawEthConfPtr = *const ffi::rte_eth_conf;
+    /// The loopback mode of the driver, 0 disables the loopback.
+    pub fn loopback(&mut self, mode: u32) -> &mut Self {
+        self.lpbk_mode = mode;
+        self
+    }
 
-pub struct RawEthConf(ffi::rte_eth_conf);
+    /// Enable the link status change interrupt.
+    pub fn lsc_intr(&mut self, on: bool) -> &mut Self {
+        self.lsc_intr = on;
+        self
+    }
 
-impl RawEthConf {
-    fn as_raw(&self) -> RawEthConfPtr {
-        &self.0
+    /// Enable the RX queue interrupts.
+    pub fn rxq_intr(&mut self, on: bool) -> &mut Self {
+        self.rxq_intr = on;
+        self
     }
-}
 
-impl<'a> From<&'a EthConf> for RawEthConf {
-    fn from(c: &EthConf) -> Self {
-        let mut conf: ffi::rte_eth_conf = Default::default();
+    /// Build the configuration, returns `EINVAL` if the settings are inconsistent.
+    pub fn build(&self) -> Result<EthConf> {
+        let mut rxmode = EthRxMode::default();
+        let mut txmode = EthTxMode::default();
+        let mut conf = EthConf::default();
 
-        if let Some(ref rxmode) = c.rxmode {
-            conf.rxmode = *rxmode
+        let mut rx_offloads = self.rx_offloads;
+        let max_rx_pkt_len = match self.max_lro_pkt_size {
+            Some(size) if size < ether::ETHER_MIN_LEN => return Err(RteError(libc::EINVAL).into()),
+            Some(size) if self.max_rx_pkt_len.is_none_or(|len| len < size) => {
+                rx_offloads.set(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME, size > ether::ETHER_MAX_LEN);
+
+                Some(size)
+            }
+            _ => self.max_rx_pkt_len,
+        };
+
+        match max_rx_pkt_len {
+            Some(len) if len < ether::ETHER_MIN_LEN => return Err(RteError(libc::EINVAL).into()),
+            Some(len) => rxmode.max_rx_pkt_len = len,
+            None if rx_offloads.contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME) => {
+                return Err(RteError(libc::EINVAL).into())
+            }
+            None => {}
         }
 
-        if let Some(ref txmode) = c.txmode {
-            conf.txmode = *txmode
+        let mq_mode = match (self.rss_hash, self.rx_mq_mode) {
+            (Some(hash), _) if hash.is_empty() => return Err(RteError(libc::EINVAL).into()),
+            (Some(_), Some(mode)) if !mode.contains(EthRxMultiQueueMode::ETH_MQ_RX_RSS_FLAG) => {
+                return Err(RteError(libc::EINVAL).into())
+            }
+            (Some(_), None) => EthRxMultiQueueMode::ETH_MQ_RX_RSS_FLAG,
+            (_, mode) => mode.unwrap_or_else(EthRxMultiQueueMode::empty),
+        };
+
+        rxmode.mq_mode = mq_mode.bits;
+        rxmode.offloads = rx_offloads.bits;
+        txmode.mq_mode = self.tx_mq_mode;
+        txmode.offloads = self.tx_offloads.bits;
+
+        if let Some(hash) = self.rss_hash {
+            conf.rx_adv_conf = Some(RxAdvConf {
+                rss_conf: Some(EthRssConf {
+                    key: self.rss_key,
+                    hash,
+                }),
+                ..RxAdvConf::default()
+            });
         }
 
-        if let Some(ref adv_conf) = c.rx_adv_conf {
-            if let Some(ref rss_conf) = adv_conf.rss_conf {
-                let (rss_key, rss_key_len) = rss_conf
-                    .key
-                    .map_or_else(|| (ptr::null(), 0), |key| (key.as_ptr(), key.len() as u8));
+        if self.lsc_intr || self.rxq_intr {
+            let mut intr_conf = ffi::rte_intr_conf::default();
 
-                conf.rx_adv_conf.rss_conf.rss_key = rss_key as *mut _;
-                conf.rx_adv_conf.rss_conf.rss_key_len = rss_key_len;
-                conf.rx_adv_conf.rss_conf.rss_hf = rss_conf.hash.bits;
+            intr_conf.set_lsc(bool_value!(self.lsc_intr) as u32);
+            intr_conf.set_rxq(bool_value!(self.rxq_intr) as u32);
+
+            conf.intr_conf = Some(intr_conf);
+        }
+
+        conf.link_speeds = self.link_speeds;
+        conf.lpbk_mode = self.lpbk_mode;
+        conf.rxmode = Some(rxmode);
+        conf.txmode = Some(txmode);
+
+        Ok(conf)
+    }
+
+    /// Build the configuration, validated against the capabilities of the device.
+    ///
+    /// Returns `ENOTSUP` if the device doesn't support the offloads,
+    /// or `UnsupportedRss` naming the flow types whose RSS hash isn't supported.
+    pub fn build_for(&self, info: &RawEthDeviceInfo) -> Result<EthConf> {
+        let mut builder = *self;
+
+        if let (Some(hash), true) = (self.rss_hash, self.rss_supported) {
+            let supported = RssHashFunc::from_bits_truncate(info.flow_type_rss_offloads);
+
+            if (hash & supported).is_empty() {
+                return Err(ErrorKind::UnsupportedRss(format!("{}, the device supports {}", hash, supported)).into());
             }
+
+            builder.rss_hash = Some(hash & supported);
         }
 
-        RawEthConf(conf)
+        let conf = builder.build()?;
+
+        validate_conf(info, &conf)?;
+
+        Ok(conf)
+    }
+
+    /// Build the configuration for the port.
+    pub fn build_for_port(&self, port_id: PortId) -> Result<EthConf> {
+        self.build_for(&port_id.info())
     }
 }
 
@@ -705,9 +4412,9 @@ pub trait TxBuffer {
 }
 
 /// Initialize default values for buffered transmitting
-pub fn alloc_buffer(size: usize, socket_id: i32) -> Result<RawTxBufferPtr> {
+pub fn alloc_buffer(size: usize, socket: Socket) -> Result<RawTxBufferPtr> {
     unsafe {
-        malloc::zmalloc_socket("tx_buffer", rte_eth_tx_buffer_size(size), 0, socket_id)
+        malloc::zmalloc_socket("tx_buffer", rte_eth_tx_buffer_size(size), 0, socket)
             .ok_or(OsError(libc::ENOMEM))
             .map(|p| p.as_ptr() as *mut _)
             .and_then(|p| ffi::rte_eth_tx_buffer_init(p, size as u16).as_result().map(|_| p))
@@ -747,3 +4454,715 @@ impl TxBuffer for RawTxBuffer {
         }; ok => { self })
     }
 }
+
+/// Assertions of the queue-to-lcore affinity.
+///
+/// The burst functions of the PMDs are not thread safe on the same queue,
+/// polling a queue from two lcores at the same time silently corrupts the state of the driver.
+///
+/// In the debug builds, the lcore which set up each queue and the lcore which last polled it are recorded
+/// by the safe wrappers, and polling a queue from another lcore than the previous one panics,
+/// unless the queue was declared as `share`d, i.e. the application serializes the bursts itself,
+/// or it was handed over to another lcore with `release`.
+///
+/// Nothing is recorded in the release builds. The non-EAL threads are all seen as `LCORE_ID_ANY`.
+pub mod affinity {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::RwLock;
+
+    use ffi;
+
+    use lcore;
+
+    use super::QueueHandle;
+
+    #[derive(Debug, Default)]
+    struct Owner {
+        setup: AtomicU64,
+        poller: AtomicU64,
+        shared: AtomicBool,
+    }
+
+    lazy_static! {
+        static ref QUEUES: RwLock<HashMap<QueueHandle, Owner>> = RwLock::new(HashMap::new());
+    }
+
+    fn with_owner<T, F: FnOnce(&Owner) -> T>(queue: QueueHandle, f: F) -> T {
+        if let Some(owner) = QUEUES.read().unwrap().get(&queue) {
+            return f(owner);
+        }
+
+        f(QUEUES.write().unwrap().entry(queue).or_default())
+    }
+
+    fn get<T, F: FnOnce(&Owner) -> T>(queue: QueueHandle, f: F) -> Option<T> {
+        QUEUES.read().unwrap().get(&queue).map(f)
+    }
+
+    // the lcore ids are recorded plus one, zero means none.
+    fn current() -> u64 {
+        u64::from(unsafe { ffi::_rte_lcore_id() }) + 1
+    }
+
+    fn lcore_id(v: u64) -> Option<lcore::Id> {
+        if v == 0 {
+            None
+        } else {
+            Some(lcore::id((v - 1) as u32))
+        }
+    }
+
+    fn describe(v: u64) -> String {
+        match lcore_id(v) {
+            Some(id) if *id == ffi::LCORE_ID_ANY => "a non-EAL thread".to_owned(),
+            Some(id) => format!("lcore {}", id),
+            None => "nowhere".to_owned(),
+        }
+    }
+
+    /// Record the lcore which sets up the queue, the queue has no poller until it is polled again.
+    pub fn on_setup(queue: QueueHandle) {
+        with_owner(queue, |owner| {
+            owner.setup.store(current(), Ordering::Release);
+            owner.poller.store(0, Ordering::Release);
+        })
+    }
+
+    /// Record the lcore which polls the queue.
+    ///
+    /// Panics if the queue was polled from another lcore, unless it is shared.
+    pub fn on_poll(queue: QueueHandle) {
+        let cur = current();
+
+        with_owner(queue, |owner| {
+            let prev = owner.poller.swap(cur, Ordering::AcqRel);
+
+            if prev != 0 && prev != cur && !owner.shared.load(Ordering::Acquire) {
+                panic!(
+                    "{} polled from {}, but it was polled from {}, \
+                     share the queue if the bursts are serialized, or release it before handing it over",
+                    queue,
+                    describe(cur),
+                    describe(prev)
+                );
+            }
+        })
+    }
+
+    /// The lcore which set up the queue.
+    pub fn setup_lcore(queue: QueueHandle) -> Option<lcore::Id> {
+        get(queue, |owner| owner.setup.load(Ordering::Acquire)).and_then(lcore_id)
+    }
+
+    /// The lcore which last polled the queue.
+    pub fn last_poller(queue: QueueHandle) -> Option<lcore::Id> {
+        get(queue, |owner| owner.poller.load(Ordering::Acquire)).and_then(lcore_id)
+    }
+
+    /// Declare the queue is polled from multiple lcores, which serialize the bursts themselves.
+    pub fn share(queue: QueueHandle) {
+        with_owner(queue, |owner| owner.shared.store(true, Ordering::Release))
+    }
+
+    /// Declare the queue is polled from a single lcore again.
+    pub fn unshare(queue: QueueHandle) {
+        with_owner(queue, |owner| {
+            owner.shared.store(false, Ordering::Release);
+            owner.poller.store(0, Ordering::Release);
+        })
+    }
+
+    /// Release the queue, so it could be polled from another lcore.
+    pub fn release(queue: QueueHandle) {
+        get(queue, |owner| owner.poller.store(0, Ordering::Release));
+    }
+
+    /// Forget the queues of a port, e.g. after it was closed.
+    pub fn forget(port_id: super::PortId) {
+        QUEUES.write().unwrap().retain(|queue, _| queue.portid() != port_id)
+    }
+}
+
+/// Validation of the `DEV_TX_OFFLOAD_MBUF_FAST_FREE` offload.
+///
+/// With the offload, the driver puts the transmitted mbufs back to the mempool of the first one in bulk,
+/// which silently corrupts the mempools if the mbufs come from several mempools or are still referenced.
+///
+/// In the debug builds, the queues set up with the offload are recorded, and `tx_burst` panics
+/// if an mbuf sent on such a queue is indirect, has a reference counter other than 1,
+/// or comes from another mempool than the previous mbufs sent on the queue.
+pub mod fast_free {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::RwLock;
+
+    use ffi;
+
+    use mbuf::{OffloadFlags, RawMBuf};
+    use utils::AsRaw;
+
+    use super::{PortId, QueueId, TxOffload};
+
+    lazy_static! {
+        // the mempool of the mbufs sent on the queues with the offload, zero until the first burst.
+        static ref QUEUES: RwLock<HashMap<(PortId, QueueId), AtomicUsize>> = RwLock::new(HashMap::new());
+    }
+
+    /// Record whether the offload is enabled on the queue.
+    pub fn on_setup(port_id: PortId, queue_id: QueueId, offloads: TxOffload) {
+        let mut queues = QUEUES.write().unwrap();
+
+        if offloads.contains(TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE) {
+            queues.insert((port_id, queue_id), AtomicUsize::new(0));
+        } else {
+            queues.remove(&(port_id, queue_id));
+        }
+    }
+
+    /// The offload is enabled on the queue.
+    pub fn enabled(port_id: PortId, queue_id: QueueId) -> bool {
+        QUEUES.read().unwrap().contains_key(&(port_id, queue_id))
+    }
+
+    /// Check the mbuf could be released in bulk to the mempool, returns its mempool.
+    pub fn check(m: &RawMBuf, pool: usize) -> ::std::result::Result<usize, String> {
+        let pool = if pool == 0 { m.pool as usize } else { pool };
+        let mut seg = m as *const RawMBuf;
+
+        while let Some(m) = unsafe { seg.as_ref() } {
+            let flags = OffloadFlags::from_bits_truncate(m.ol_flags);
+            let refcnt = unsafe { ffi::_rte_mbuf_refcnt_read(m) };
+
+            if flags.intersects(OffloadFlags::IND_ATTACHED_MBUF | OffloadFlags::EXT_ATTACHED_MBUF) {
+                return Err(format!("mbuf {:p} is attached to another buffer", m));
+            }
+            if refcnt != 1 {
+                return Err(format!("mbuf {:p} has reference counter {}", m, refcnt));
+            }
+            if m.pool as usize != pool {
+                return Err(format!(
+                    "mbuf {:p} comes from mempool {:p}, instead of {:#x}",
+                    m, m.pool, pool
+                ));
+            }
+
+            seg = m.next;
+        }
+
+        Ok(pool)
+    }
+
+    /// Check the mbufs about to be sent on the queue, if the offload is enabled.
+    pub fn check_burst<T: AsRaw<Raw = RawMBuf>>(port_id: PortId, queue_id: QueueId, pkts: &[T]) {
+        if let Some(queue_pool) = QUEUES.read().unwrap().get(&(port_id, queue_id)) {
+            let mut pool = queue_pool.load(Ordering::Acquire);
+
+            for m in pkts {
+                match check(unsafe { &*m.as_raw() }, pool) {
+                    Ok(p) => pool = p,
+                    Err(reason) => panic!(
+                        "port {} txq {} enables DEV_TX_OFFLOAD_MBUF_FAST_FREE, but {}",
+                        port_id, queue_id, reason
+                    ),
+                }
+            }
+
+            queue_pool.store(pool, Ordering::Release);
+        }
+    }
+
+    /// Forget the queues of a port, e.g. after it was closed.
+    pub fn forget(port_id: PortId) {
+        QUEUES.write().unwrap().retain(|&(port, _), _| port != port_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_mask() {
+        let mask = PortMask::from_hex_str("0x5").unwrap();
+
+        assert_eq!(mask.checked().unwrap(), mask);
+        assert_eq!(PortMask::all().len(), ffi::RTE_MAX_ETHPORTS as usize);
+        assert!(PortMask::from(1u64 << ffi::RTE_MAX_ETHPORTS).checked().is_err());
+    }
+
+    #[test]
+    fn test_hairpin_conf() {
+        assert!(HairpinConf::default().to_raw().is_err());
+
+        let conf = HairpinConf::default()
+            .with_peer(1, 2)
+            .with_peer(3, 4)
+            .with_manual_bind(true)
+            .to_raw()
+            .unwrap();
+
+        assert_eq!(conf.peer_count, 2);
+        assert_eq!(conf.tx_explicit, 0);
+        assert_eq!(conf.manual_bind, 1);
+        assert_eq!((conf.peers[1].port, conf.peers[1].queue), (3, 4));
+
+        let conf = (0..=MAX_HAIRPIN_PEERS as u16).fold(HairpinConf::default(), |conf, queue| conf.with_peer(0, queue));
+
+        assert!(conf.to_raw().is_err());
+    }
+
+    #[test]
+    fn test_fc_mode() {
+        assert_eq!(FcMode::new(true, true), FcMode::Full);
+        assert!(FcMode::RxPause.rx_pause());
+        assert!(!FcMode::RxPause.tx_pause());
+
+        let conf = EthFcConf::default().with_mode(FcMode::TxPause).with_autoneg(true);
+
+        assert_eq!(conf.mode(), FcMode::TxPause);
+        assert_eq!(conf.autoneg, 1);
+    }
+
+    #[test]
+    fn test_eth_conf() {
+        let mut raw = ffi::rte_eth_conf {
+            link_speeds: LinkSpeed::ETH_LINK_SPEED_10G.bits,
+            lpbk_mode: 1,
+            ..Default::default()
+        };
+
+        raw.rxmode.offloads = RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME.bits;
+        raw.rxmode.max_rx_pkt_len = 9000;
+        raw.rx_adv_conf.rss_conf.rss_hf = RssHashFunc::ETH_RSS_IP.bits;
+
+        let conf = EthConf::from(&raw);
+
+        assert_eq!(conf.link_speeds, LinkSpeed::ETH_LINK_SPEED_10G);
+        assert_eq!(conf.lpbk_mode, 1);
+
+        let conf = RawEthConf::from(&conf).0;
+
+        assert_eq!(conf.link_speeds, raw.link_speeds);
+        assert_eq!(conf.lpbk_mode, raw.lpbk_mode);
+        assert_eq!(conf.rxmode, raw.rxmode);
+        assert_eq!(conf.rx_adv_conf.rss_conf.rss_hf, RssHashFunc::ETH_RSS_IP.bits);
+
+        let mut info = RawEthDeviceInfo::default();
+
+        assert!(validate_conf(&info, &EthConf::from(&raw)).is_err());
+
+        info.rx_offload_capa = RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME.bits;
+        info.flow_type_rss_offloads = RssHashFunc::ETH_RSS_IP.bits;
+        info.max_rx_pktlen = 9000;
+
+        assert!(validate_conf(&info, &EthConf::from(&raw)).is_ok());
+
+        raw.rxmode.max_rx_pkt_len = 9600;
+
+        assert!(validate_conf(&info, &EthConf::from(&raw)).is_err());
+    }
+
+    #[test]
+    fn test_eth_conf_builder() {
+        let conf = EthConfBuilder::new()
+            .rss(RssHashFunc::ETH_RSS_IP)
+            .max_rx_pkt_len(9000)
+            .offloads(
+                RxOffload::DEV_RX_OFFLOAD_CHECKSUM,
+                TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE,
+            )
+            .lsc_intr(true)
+            .build()
+            .unwrap();
+        let rxmode = conf.rxmode.unwrap();
+
+        assert_eq!(rxmode.mq_mode, EthRxMultiQueueMode::ETH_MQ_RX_RSS_FLAG.bits);
+        assert_eq!(rxmode.max_rx_pkt_len, 9000);
+        assert_eq!(
+            RxOffload::from_bits_truncate(rxmode.offloads),
+            RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME | RxOffload::DEV_RX_OFFLOAD_CHECKSUM
+        );
+        assert_eq!(conf.intr_conf.unwrap().lsc(), 1);
+
+        let builder = EthConfBuilder::from(&conf);
+
+        assert_eq!(builder.max_rx_pkt_len, Some(9000));
+        assert_eq!(builder.rss_hash, Some(RssHashFunc::ETH_RSS_IP));
+
+        let conf = EthConfBuilder::from(&conf).max_rx_pkt_len(1500).build().unwrap();
+
+        assert!(!RxOffload::from_bits_truncate(conf.rxmode.unwrap().offloads)
+            .contains(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME));
+
+        assert!(EthConfBuilder::new().max_rx_pkt_len(32).build().is_err());
+        assert!(EthConfBuilder::new()
+            .rx_offloads(RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME)
+            .build()
+            .is_err());
+        assert!(EthConfBuilder::new().rss(RssHashFunc::empty()).build().is_err());
+        assert!(EthConfBuilder::new()
+            .rss(RssHashFunc::ETH_RSS_IP)
+            .rx_mq_mode(EthRxMultiQueueMode::ETH_MQ_RX_VMDQ_FLAG)
+            .build()
+            .is_err());
+
+        let mut info = RawEthDeviceInfo::default();
+        let builder = *EthConfBuilder::new().rss(RssHashFunc::ETH_RSS_IP);
+
+        assert!(builder.build_for(&info).is_err());
+
+        info.flow_type_rss_offloads = RssHashFunc::ETH_RSS_IP.bits;
+
+        assert!(builder.build_for(&info).is_ok());
+    }
+
+    #[test]
+    fn test_rss_validation() {
+        let info = RawEthDeviceInfo {
+            flow_type_rss_offloads: (RssHashFunc::ETH_RSS_IPV4 | RssHashFunc::ETH_RSS_NONFRAG_IPV4_TCP).bits,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            (RssHashFunc::ETH_RSS_IPV4 | RssHashFunc::ETH_RSS_NONFRAG_IPV4_TCP).to_string(),
+            "ipv4, ipv4-tcp"
+        );
+        assert_eq!(RssHashFunc::empty().to_string(), "none");
+
+        let err = EthConfBuilder::new()
+            .rss(RssHashFunc::ETH_RSS_IPV4 | RssHashFunc::ETH_RSS_IPV6)
+            .build_for(&info)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "unsupported RSS hash of flow types, ipv6, the device supports ipv4, ipv4-tcp"
+        );
+
+        let conf = EthConfBuilder::new()
+            .rss_supported(RssHashFunc::ETH_RSS_IP | RssHashFunc::ETH_RSS_TCP)
+            .build_for(&info)
+            .unwrap();
+        let rss_conf = conf.rx_adv_conf.unwrap().rss_conf.unwrap();
+
+        assert_eq!(
+            rss_conf.hash,
+            RssHashFunc::ETH_RSS_IPV4 | RssHashFunc::ETH_RSS_NONFRAG_IPV4_TCP
+        );
+
+        assert!(EthConfBuilder::new()
+            .rss_supported(RssHashFunc::ETH_RSS_IPV6)
+            .build_for(&info)
+            .is_err());
+    }
+
+    #[test]
+    fn test_lro() {
+        let conf = EthConfBuilder::new().lro(16384).build().unwrap();
+        let rxmode = conf.rxmode.unwrap();
+
+        assert_eq!(rxmode.max_rx_pkt_len, 16384);
+        assert_eq!(
+            RxOffload::from_bits_truncate(rxmode.offloads),
+            RxOffload::DEV_RX_OFFLOAD_TCP_LRO | RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME
+        );
+        assert_eq!(EthConfBuilder::from(&conf).max_lro_pkt_size, Some(16384));
+
+        let conf = EthConfBuilder::new().max_rx_pkt_len(9000).lro(1500).build().unwrap();
+
+        assert_eq!(conf.rxmode.unwrap().max_rx_pkt_len, 9000);
+        assert!(EthConfBuilder::new().lro(32).build().is_err());
+    }
+
+    #[test]
+    fn test_tx_seg_limits() {
+        let limits = TxSegLimits::new(8, 3);
+
+        assert_eq!(TxSegLimits::new(0, 0), TxSegLimits::default());
+        assert!(limits.fits(3));
+        assert!(!limits.fits(4));
+
+        // the payload of 3000 bytes is sent in 3 packets, each of them fetches up to 3 segments
+        let seg_lens = [54 + 100, 1000, 1000, 1000];
+
+        assert_eq!(tso_segs_per_packet(&seg_lens, 54, 1460), 3);
+        assert!(limits.fits_tso(&seg_lens, 54, 1460));
+        assert!(!TxSegLimits::new(8, 2).fits_tso(&seg_lens, 54, 1460));
+        assert!(!TxSegLimits::new(3, 3).fits_tso(&seg_lens, 54, 1460));
+        assert!(!limits.fits_tso(&seg_lens, 54, 0));
+
+        // the headers in a separate segment
+        assert_eq!(tso_segs_per_packet(&[54, 1460, 1460], 54, 1460), 2);
+        assert_eq!(tso_segs_per_packet(&[54], 54, 1460), 1);
+        assert_eq!(tso_segs_per_packet(&[54 + 2920], 54, 1460), 1);
+    }
+
+    #[test]
+    fn test_desc_limits() {
+        let lim = DescLimits {
+            nb_max: 4096,
+            nb_min: 64,
+            nb_align: 32,
+        };
+
+        assert_eq!(lim.adjust(100), 128);
+        assert_eq!(lim.adjust(16), 64);
+        assert_eq!(lim.adjust(8192), 4096);
+        assert_eq!(lim.adjust(u16::MAX), 4096);
+        assert!(lim.contains(512));
+        assert!(!lim.contains(500));
+        assert_eq!(DescLimits::default().adjust(100), 100);
+    }
+
+    #[test]
+    fn test_profile() {
+        let mut info = RawEthDeviceInfo::default();
+
+        let conf = Profile::L2Forwarding.conf(&info).unwrap();
+
+        assert_eq!(conf.rxmode.unwrap().offloads, 0);
+        assert_eq!(conf.txmode.unwrap().offloads, 0);
+        assert!(Profile::RssL3L4.conf(&info).is_err());
+        assert!(Profile::JumboLro.conf(&info).is_err());
+
+        info.rx_offload_capa = (RxOffload::DEV_RX_OFFLOAD_CHECKSUM | RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME).bits;
+        info.tx_offload_capa = TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE.bits;
+        info.flow_type_rss_offloads = RssHashFunc::ETH_RSS_IPV4.bits;
+        info.max_rx_pktlen = 9000;
+
+        let conf = Profile::L2Forwarding.conf(&info).unwrap();
+
+        assert_eq!(
+            conf.txmode.unwrap().offloads,
+            TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE.bits
+        );
+
+        let conf = Profile::RssL3L4.conf(&info).unwrap();
+
+        assert_eq!(conf.rxmode.unwrap().mq_mode, ffi::rte_eth_rx_mq_mode::ETH_MQ_RX_RSS);
+        assert_eq!(conf.rxmode.unwrap().offloads, RxOffload::DEV_RX_OFFLOAD_CHECKSUM.bits);
+        assert_eq!(
+            conf.rx_adv_conf.unwrap().rss_conf.unwrap().hash,
+            RssHashFunc::ETH_RSS_IPV4
+        );
+
+        let conf = Profile::JumboLro.conf(&info).unwrap();
+
+        assert_eq!(
+            conf.rxmode.unwrap().offloads,
+            RxOffload::DEV_RX_OFFLOAD_JUMBO_FRAME.bits
+        );
+        assert_eq!(conf.rxmode.unwrap().max_rx_pkt_len, 9000);
+        assert!(Profile::LowLatency.nb_desc().0 < Profile::RssL3L4.nb_desc().0);
+    }
+
+    #[test]
+    fn test_link_speed() {
+        assert_eq!(LinkSpeed::from_mbps(10, false), Some(LinkSpeed::ETH_LINK_SPEED_10M_HD));
+        assert_eq!(LinkSpeed::from_mbps(100, true), Some(LinkSpeed::ETH_LINK_SPEED_100M));
+        assert_eq!(LinkSpeed::from_mbps(25_000, false), Some(LinkSpeed::ETH_LINK_SPEED_25G));
+        assert_eq!(LinkSpeed::from_mbps(123, true), None);
+
+        let fixed = LinkSpeed::fixed(ETH_SPEED_NUM_10G, true).unwrap();
+
+        assert!(!fixed.is_autoneg());
+        assert_eq!(fixed.to_mbps(), vec![ETH_SPEED_NUM_10G]);
+
+        let speeds = LinkSpeed::ETH_LINK_SPEED_10G | LinkSpeed::ETH_LINK_SPEED_25G | LinkSpeed::ETH_LINK_SPEED_1G;
+
+        assert!(speeds.is_autoneg());
+        assert_eq!(
+            speeds.to_mbps(),
+            vec![ETH_SPEED_NUM_1G, ETH_SPEED_NUM_10G, ETH_SPEED_NUM_25G]
+        );
+        assert_eq!(speeds.max_mbps(), Some(ETH_SPEED_NUM_25G));
+        assert_eq!(LinkSpeed::default().max_mbps(), None);
+    }
+
+    #[test]
+    fn test_queue_conf() {
+        let rx_conf = EthRxConf::default().latency_optimized().with_drop_en(true);
+
+        assert_eq!(rx_conf.rx_thresh, eth_thresh(8, 8, 0));
+        assert_eq!(rx_conf.rx_free_thresh, 32);
+        assert!(rx_conf.drop_en());
+        assert!(!rx_conf.with_drop_en(false).drop_en());
+        assert!(!rx_conf.deferred_start());
+        assert!(rx_conf.with_deferred_start(true).deferred_start());
+
+        let rx_conf = EthRxConf::default()
+            .with_offloads(RxOffload::DEV_RX_OFFLOAD_CHECKSUM)
+            .with_scatter();
+
+        assert_eq!(
+            rx_conf.offloads(),
+            RxOffload::DEV_RX_OFFLOAD_CHECKSUM | RxOffload::DEV_RX_OFFLOAD_SCATTER
+        );
+
+        let tx_conf = EthTxConf::default().throughput_optimized();
+
+        assert_eq!(tx_conf.tx_thresh, eth_thresh(36, 0, 0));
+        assert_eq!(tx_conf.tx_rs_thresh, 32);
+        assert_eq!(tx_conf.tx_free_thresh, 64);
+        assert!(tx_conf.with_deferred_start(true).deferred_start());
+
+        let tx_conf = EthTxConf::default()
+            .with_offloads(TxOffload::DEV_TX_OFFLOAD_IPV4_CKSUM)
+            .with_fast_free();
+
+        assert_eq!(
+            tx_conf.offloads(),
+            TxOffload::DEV_TX_OFFLOAD_IPV4_CKSUM | TxOffload::DEV_TX_OFFLOAD_MBUF_FAST_FREE
+        );
+    }
+
+    #[test]
+    fn test_rx_segments() {
+        let headroom = ffi::RTE_PKTMBUF_HEADROOM as usize;
+
+        assert_eq!(rx_segments(1518, 2048 + headroom), Some(1));
+        assert_eq!(rx_segments(2048, 2048 + headroom), Some(1));
+        assert_eq!(rx_segments(2049, 2048 + headroom), Some(2));
+        assert_eq!(rx_segments(9018, 2048 + headroom), Some(5));
+        assert_eq!(rx_segments(1518, headroom), None);
+    }
+
+    #[test]
+    fn test_stats_rates() {
+        let mut prev = StatsSnapshot::default();
+        let mut cur = StatsSnapshot::default();
+
+        prev.stats.ipackets = 100;
+        prev.stats.obytes = 1000;
+        cur.stats.ipackets = 300;
+        cur.stats.obytes = 6000;
+        cur.stats.ierrors = 4;
+
+        let delta = cur.delta(&prev);
+
+        assert_eq!(delta.ipackets, 200);
+        assert_eq!(delta.obytes, 5000);
+        assert_eq!(delta.ierrors, 4);
+        assert_eq!(prev.delta(&cur), StatsDelta::default());
+
+        let rates = Rates::from_delta(&delta, Duration::from_millis(500));
+
+        assert_eq!(rates.rx_pps, 400.0);
+        assert_eq!(rates.tx_bps, 80_000.0);
+        assert_eq!(rates.rx_errors_pps, 8.0);
+        assert_eq!(Rates::from_delta(&delta, Duration::from_secs(0)), Rates::default());
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        // port 3 is bonded with slaves 0 and 2, port 4 is bonded with port 3
+        let deps = |port_id| match port_id {
+            3 => vec![0, 2],
+            4 => vec![3, 5],
+            _ => vec![],
+        };
+
+        assert_eq!(dependency_order(&[4, 3, 1, 2, 0], deps), vec![0, 2, 3, 4, 1]);
+        assert_eq!(dependency_order(&[3, 1], deps), vec![3, 1]);
+        assert_eq!(dependency_order(&[1, 1, 0], deps), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_descriptor_dump() {
+        let mut statuses = vec![DescStatus::Done; 3];
+
+        statuses.extend(vec![DescStatus::Avail; 66]);
+        statuses.push(DescStatus::Unavail);
+
+        let dump = DescriptorDump {
+            queue: QueueHandle::Rx(0, 1),
+            start: 0,
+            statuses,
+        };
+
+        assert_eq!(dump.count(DescStatus::Avail), 66);
+
+        let s = dump.to_string();
+        let lines: Vec<&str> = s.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "port 0 rxq 1: 70 descriptors from offset 0, Avail 66, Done 3, Unavail 1"
+        );
+        assert_eq!(lines[1], format!("    0: DDD{}", ".".repeat(61)));
+        assert_eq!(lines[2], "   64: .....x");
+    }
+
+    #[test]
+    fn test_tx_retry_policy() {
+        let policy = TxRetryPolicy::default().with_backoff(TxBackoff::Spin);
+
+        // the queue takes 8 packets at most
+        let mut ring = 8;
+        let res = policy.run(32, |off| {
+            let n = ::std::cmp::min(ring, 32 - off);
+            ring -= n;
+            n
+        });
+
+        assert_eq!(
+            res,
+            TxBurstResult {
+                sent: 8,
+                dropped: 24,
+                retries: DEFAULT_TX_RETRIES
+            }
+        );
+
+        // the queue drains 4 packets between retries
+        let mut ring = 8;
+        let res = policy.with_reset_on_progress(true).run(32, |off| {
+            let n = ::std::cmp::min(ring, 32 - off);
+            ring = 4;
+            n
+        });
+
+        assert_eq!(
+            res,
+            TxBurstResult {
+                sent: 32,
+                dropped: 0,
+                retries: 6
+            }
+        );
+
+        let res = TxRetryPolicy::no_retry().run(32, |_| 0);
+
+        assert_eq!(
+            res,
+            TxBurstResult {
+                sent: 0,
+                dropped: 32,
+                retries: 0
+            }
+        );
+        assert_eq!(policy.run(0, |_| unreachable!()), TxBurstResult::default());
+    }
+
+    #[test]
+    fn test_xstats_pattern() {
+        assert!(glob_match("rx_q*_errors", "rx_q0_errors"));
+        assert!(glob_match("rx_q*_errors", "rx_q12_errors"));
+        assert!(!glob_match("rx_q*_errors", "rx_q0_bytes"));
+        assert!(!glob_match("rx_q*_errors", "tx_q0_errors"));
+        assert!(glob_match("?x_good_packets", "tx_good_packets"));
+        assert!(glob_match("*", "rx_missed_errors"));
+        assert!(glob_match("*_errors*", "rx_errors"));
+        assert!(glob_match("rx_*_*", "rx_q1_bytes"));
+        assert!(!glob_match("rx_*_*", "rx_bytes"));
+
+        assert_eq!(xstat_queue_id("rx_q3_errors"), Some(3));
+        assert_eq!(xstat_queue_id("tx_q15_bytes"), Some(15));
+        assert_eq!(xstat_queue_id("rx_queue_7_packets"), Some(7));
+        assert_eq!(xstat_queue_id("rx_q0packets"), Some(0));
+        assert_eq!(xstat_queue_id("rx_good_packets"), None);
+        assert_eq!(xstat_queue_id("rx_qos_drops"), None);
+    }
+}
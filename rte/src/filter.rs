@@ -0,0 +1,972 @@
+//!
+//! Packet filter with BPF-like expressions.
+//!
+//! The expression is a subset of the pcap filter language, e.g. `tcp and dst port 443`,
+//! which is compiled to a closure over the dissected packet headers,
+//! so the debugging captures could select the interesting packets in the RX callbacks.
+//!
+//! The supported primitives are
+//!
+//! - `ether [src|dst] [host] MAC`, `ether proto N`
+//! - `arp`, `rarp`, `ip`, `ip6`, `vlan [ID]`
+//! - `tcp`, `udp`, `sctp`, `icmp`, `icmp6`, `ip proto N`, `ip6 proto N`
+//! - `[src|dst] host ADDR`, `[src|dst] net ADDR/LEN`, `[src|dst] port N`, `[src|dst] portrange N-M`
+//! - `len OP N` where OP is one of `<`, `<=`, `>`, `>=`, `==`, `!=`, `less N`, `greater N`
+//!
+//! The primitives could be combined with `and` (`&&`), `or` (`||`), `not` (`!`) and parentheses,
+//! `not` binds tighter than `and`, which binds tighter than `or`.
+//! A protocol could qualify the following port primitive, e.g. `udp port 53` means `udp and port 53`.
+//!
+//! The rte_bpf library isn't bound by this crate, the expressions are always compiled to Rust closures.
+//!
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_void;
+use std::slice;
+use std::str;
+
+use failure::Error;
+use libc;
+
+use ffi;
+
+use errors::{rte_error, ErrorKind::InvalidFilter, Result};
+use ethdev::{PortId, QueueId};
+use ether::{
+    ETHER_TYPE_IPv4, ETHER_TYPE_IPv6, EtherAddr, ETHER_ADDR_LEN, ETHER_HDR_LEN, ETHER_TYPE_ARP, ETHER_TYPE_QINQ,
+    ETHER_TYPE_RARP, ETHER_TYPE_VLAN,
+};
+use ip::{IPV4_HDR_IHL_MASK, IPV4_HDR_LEN, IPV4_HDR_MF_FLAG, IPV4_HDR_OFFSET_MASK, IPV4_IHL_MULTIPLIER};
+use mbuf::MBuf;
+use meta::Verdict;
+
+/// The maximum bytes of a segmented packet which are read for the dissection.
+pub const DISSECT_LEN: usize = 128;
+
+const IPV6_HDR_LEN: usize = 40;
+const VLAN_HDR_LEN: usize = 4;
+const MAX_VLAN_TAGS: usize = 2;
+
+/// The headers of a packet which could be matched by the filter.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dissection {
+    /// The length of the packet.
+    pub len: usize,
+    /// The source MAC address.
+    pub src_mac: EtherAddr,
+    /// The destination MAC address.
+    pub dst_mac: EtherAddr,
+    /// The VLAN ids of the tags, from the outermost one.
+    pub vlans: [u16; MAX_VLAN_TAGS],
+    /// The number of VLAN tags.
+    pub nb_vlans: usize,
+    /// The ether type after the VLAN tags.
+    pub ether_type: u16,
+    /// The source IP address.
+    pub src_ip: Option<IpAddr>,
+    /// The destination IP address.
+    pub dst_ip: Option<IpAddr>,
+    /// The IP protocol, or the upper layer protocol after the IPv6 extension headers.
+    pub proto: Option<u8>,
+    /// The source port of TCP, UDP or SCTP, the non-first fragments have no port.
+    pub src_port: Option<u16>,
+    /// The destination port of TCP, UDP or SCTP, the non-first fragments have no port.
+    pub dst_port: Option<u16>,
+    /// The offset of the L4 header in the packet, the non-first fragments have no L4 header.
+    pub l4_off: Option<usize>,
+    /// The fragment header of an IPv4 or IPv6 fragment.
+    pub frag: Option<Fragment>,
+}
+
+/// The fragment header of an IPv4 or IPv6 fragment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fragment {
+    /// The identification of the original datagram, IPv4 only uses the low 16 bits.
+    pub id: u32,
+    /// The offset of the fragment in the original datagram, in bytes.
+    pub offset: usize,
+    /// The fragment isn't the last one.
+    pub more: bool,
+}
+
+impl Fragment {
+    /// The first fragment carries the L4 header.
+    pub fn is_first(&self) -> bool {
+        self.offset == 0
+    }
+}
+
+impl Dissection {
+    /// The VLAN ids of the tags.
+    pub fn vlans(&self) -> &[u16] {
+        &self.vlans[..self.nb_vlans]
+    }
+}
+
+#[inline]
+fn be16(data: &[u8], off: usize) -> Option<u16> {
+    data.get(off..off + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Dissect the headers of a packet, which starts from the Ethernet header.
+///
+/// The `len` is the length of the whole packet, the `data` may only contain the leading part of it.
+pub fn dissect(data: &[u8], len: usize) -> Dissection {
+    let mut d = Dissection {
+        len,
+        ..Default::default()
+    };
+
+    if data.len() < ETHER_HDR_LEN as usize {
+        return d;
+    }
+
+    d.dst_mac = EtherAddr::from_bytes(&data[..ETHER_ADDR_LEN]).unwrap_or_default();
+    d.src_mac = EtherAddr::from_bytes(&data[ETHER_ADDR_LEN..ETHER_ADDR_LEN * 2]).unwrap_or_default();
+
+    let mut off = ETHER_ADDR_LEN * 2;
+    let mut ether_type = be16(data, off).unwrap_or_default();
+
+    off += 2;
+
+    while ether_type == ETHER_TYPE_VLAN as u16 || ether_type == ETHER_TYPE_QINQ as u16 {
+        match (be16(data, off), be16(data, off + 2)) {
+            (Some(tci), Some(next)) => {
+                if d.nb_vlans < MAX_VLAN_TAGS {
+                    d.vlans[d.nb_vlans] = tci & 0x0fff;
+                    d.nb_vlans += 1;
+                }
+
+                ether_type = next;
+                off += VLAN_HDR_LEN;
+            }
+            _ => return d,
+        }
+    }
+
+    d.ether_type = ether_type;
+
+    let l4 = if ether_type == ETHER_TYPE_IPv4 as u16 {
+        dissect_ipv4(&mut d, &data[off..])
+    } else if ether_type == ETHER_TYPE_IPv6 as u16 {
+        dissect_ipv6(&mut d, &data[off..])
+    } else {
+        None
+    };
+
+    if let Some(l4) = l4 {
+        d.l4_off = Some(off + l4);
+
+        match d.proto.map(i32::from) {
+            Some(libc::IPPROTO_TCP) | Some(libc::IPPROTO_UDP) | Some(libc::IPPROTO_SCTP) => {
+                d.src_port = be16(data, off + l4);
+                d.dst_port = be16(data, off + l4 + 2);
+            }
+            _ => {}
+        }
+    }
+
+    d
+}
+
+/// Dissect the headers of a mbuf.
+///
+/// The leading `DISSECT_LEN` bytes of a segmented packet are read for the dissection.
+pub fn dissect_mbuf(m: &MBuf) -> Option<Dissection> {
+    let pkt_len = m.pkt_len();
+    let data_len = m.data_len();
+
+    if data_len >= pkt_len.min(DISSECT_LEN) {
+        let data = unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), data_len) };
+
+        Some(dissect(data, pkt_len))
+    } else {
+        let mut buf = [0; DISSECT_LEN];
+
+        m.read(0, &mut buf[..pkt_len.min(DISSECT_LEN)])
+            .map(|data| dissect(data, pkt_len))
+    }
+}
+
+/// Dissect the IPv4 header, returns the offset of the L4 header of the first fragment.
+fn dissect_ipv4(d: &mut Dissection, data: &[u8]) -> Option<usize> {
+    if data.len() < IPV4_HDR_LEN {
+        return None;
+    }
+
+    let ihl = (data[0] & IPV4_HDR_IHL_MASK) as usize * IPV4_IHL_MULTIPLIER;
+    let frag = be16(data, 6)?;
+    let frag_offset = frag & IPV4_HDR_OFFSET_MASK;
+
+    if frag & (IPV4_HDR_MF_FLAG | IPV4_HDR_OFFSET_MASK) != 0 {
+        d.frag = Some(Fragment {
+            id: u32::from(be16(data, 4)?),
+            offset: frag_offset as usize * 8,
+            more: frag & IPV4_HDR_MF_FLAG != 0,
+        });
+    }
+
+    d.proto = Some(data[9]);
+    d.src_ip = Some(Ipv4Addr::new(data[12], data[13], data[14], data[15]).into());
+    d.dst_ip = Some(Ipv4Addr::new(data[16], data[17], data[18], data[19]).into());
+
+    if frag_offset == 0 && ihl >= IPV4_HDR_LEN {
+        Some(ihl)
+    } else {
+        None
+    }
+}
+
+/// Dissect the IPv6 header and extension headers, returns the offset of the L4 header of the first fragment.
+fn dissect_ipv6(d: &mut Dissection, data: &[u8]) -> Option<usize> {
+    if data.len() < IPV6_HDR_LEN {
+        return None;
+    }
+
+    let mut addr = [0; 16];
+
+    addr.copy_from_slice(&data[8..24]);
+    d.src_ip = Some(Ipv6Addr::from(addr).into());
+    addr.copy_from_slice(&data[24..40]);
+    d.dst_ip = Some(Ipv6Addr::from(addr).into());
+
+    let mut next = data[6];
+    let mut off = IPV6_HDR_LEN;
+    let mut first_frag = true;
+
+    loop {
+        match i32::from(next) {
+            libc::IPPROTO_HOPOPTS | libc::IPPROTO_ROUTING | libc::IPPROTO_DSTOPTS => {
+                let hdr = data.get(off..off + 2)?;
+
+                next = hdr[0];
+                off += (hdr[1] as usize + 1) * 8;
+            }
+            libc::IPPROTO_FRAGMENT => {
+                let hdr = data.get(off..off + 8)?;
+                let frag = u16::from_be_bytes([hdr[2], hdr[3]]);
+
+                d.frag = Some(Fragment {
+                    id: u32::from_be_bytes([hdr[4], hdr[5], hdr[6], hdr[7]]),
+                    offset: (frag >> 3) as usize * 8,
+                    more: frag & 1 != 0,
+                });
+
+                next = hdr[0];
+                first_frag = frag >> 3 == 0;
+                off += 8;
+            }
+            _ => break,
+        }
+    }
+
+    d.proto = Some(next);
+
+    if first_frag {
+        Some(off)
+    } else {
+        None
+    }
+}
+
+/// The direction of an address or port qualifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dir {
+    Src,
+    Dst,
+    Any,
+}
+
+impl Dir {
+    fn matches<T, F: Fn(T) -> bool>(self, src: Option<T>, dst: Option<T>, f: F) -> bool {
+        match self {
+            Dir::Src => src.is_some_and(f),
+            Dir::Dst => dst.is_some_and(f),
+            Dir::Any => src.is_some_and(&f) || dst.is_some_and(&f),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    EtherType(u16),
+    EtherHost(Dir, EtherAddr),
+    Vlan(Option<u16>),
+    Proto(u8),
+    IpProto(u16, u8),
+    Host(Dir, IpAddr),
+    Net(Dir, IpAddr, u8),
+    Port(Dir, u16, u16),
+    Len(Cmp, usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Cmp {
+    fn eval(self, lhs: usize, rhs: usize) -> bool {
+        match self {
+            Cmp::Lt => lhs < rhs,
+            Cmp::Le => lhs <= rhs,
+            Cmp::Gt => lhs > rhs,
+            Cmp::Ge => lhs >= rhs,
+            Cmp::Eq => lhs == rhs,
+            Cmp::Ne => lhs != rhs,
+        }
+    }
+}
+
+fn prefix_matches(addr: IpAddr, net: IpAddr, depth: u8) -> bool {
+    match (addr, net) {
+        (IpAddr::V4(addr), IpAddr::V4(net)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(depth)).unwrap_or(0);
+
+            u32::from(addr) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(addr), IpAddr::V6(net)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(depth)).unwrap_or(0);
+
+            u128::from(addr) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+type Predicate = Box<dyn Fn(&Dissection) -> bool + Send + Sync>;
+
+impl Expr {
+    fn compile(self) -> Predicate {
+        match self {
+            Expr::And(lhs, rhs) => {
+                let (lhs, rhs) = (lhs.compile(), rhs.compile());
+
+                Box::new(move |d| lhs(d) && rhs(d))
+            }
+            Expr::Or(lhs, rhs) => {
+                let (lhs, rhs) = (lhs.compile(), rhs.compile());
+
+                Box::new(move |d| lhs(d) || rhs(d))
+            }
+            Expr::Not(expr) => {
+                let expr = expr.compile();
+
+                Box::new(move |d| !expr(d))
+            }
+            Expr::EtherType(ether_type) => Box::new(move |d| d.ether_type == ether_type),
+            Expr::EtherHost(dir, mac) => {
+                Box::new(move |d| dir.matches(Some(d.src_mac), Some(d.dst_mac), |addr| addr == mac))
+            }
+            Expr::Vlan(None) => Box::new(|d| d.nb_vlans > 0),
+            Expr::Vlan(Some(id)) => Box::new(move |d| d.vlans().contains(&id)),
+            Expr::Proto(proto) => Box::new(move |d| d.proto == Some(proto)),
+            Expr::IpProto(ether_type, proto) => Box::new(move |d| d.ether_type == ether_type && d.proto == Some(proto)),
+            Expr::Host(dir, host) => Box::new(move |d| dir.matches(d.src_ip, d.dst_ip, |addr| addr == host)),
+            Expr::Net(dir, net, depth) => {
+                Box::new(move |d| dir.matches(d.src_ip, d.dst_ip, |addr| prefix_matches(addr, net, depth)))
+            }
+            Expr::Port(dir, lo, hi) => {
+                Box::new(move |d| dir.matches(d.src_port, d.dst_port, |port| lo <= port && port <= hi))
+            }
+            Expr::Len(cmp, len) => Box::new(move |d| cmp.eval(d.len, len)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Op(&'a str),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut rest = s;
+
+    loop {
+        rest = rest.trim_start();
+
+        let c = match rest.chars().next() {
+            Some(c) => c,
+            None => break,
+        };
+
+        let len = if c.is_ascii_alphanumeric() {
+            rest.find(|c: char| !(c.is_ascii_alphanumeric() || ".:/-_".contains(c)))
+                .unwrap_or(rest.len())
+        } else if ["&&", "||", "<=", ">=", "==", "!="]
+            .iter()
+            .any(|op| rest.starts_with(op))
+        {
+            2
+        } else if "()!<>=".contains(c) {
+            1
+        } else {
+            return Err(InvalidFilter(format!("unexpected character `{}`", c)).into());
+        };
+
+        let (token, remaining) = rest.split_at(len);
+
+        tokens.push(if c.is_ascii_alphanumeric() {
+            Token::Word(token)
+        } else {
+            Token::Op(token)
+        });
+
+        rest = remaining;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+
+        if token.is_some() {
+            self.pos += 1;
+        }
+
+        token
+    }
+
+    fn eat(&mut self, words: &[&str]) -> bool {
+        let matched = match self.peek() {
+            Some(&Token::Word(w)) | Some(&Token::Op(w)) => words.contains(&w),
+            None => false,
+        };
+
+        if matched {
+            self.pos += 1;
+        }
+
+        matched
+    }
+
+    fn word(&mut self, what: &str) -> Result<&'a str> {
+        match self.next() {
+            Some(Token::Word(w)) => Ok(w),
+            Some(Token::Op(op)) => Err(InvalidFilter(format!("expected {}, found `{}`", what, op)).into()),
+            None => Err(InvalidFilter(format!("expected {}", what)).into()),
+        }
+    }
+
+    fn value<T: str::FromStr>(&mut self, what: &str) -> Result<T> {
+        let w = self.word(what)?;
+
+        w.parse()
+            .map_err(|_| InvalidFilter(format!("invalid {} `{}`", what, w)).into())
+    }
+
+    fn number(&mut self, what: &str) -> Result<usize> {
+        let w = self.word(what)?;
+        let n = if let Some(hex) = w.strip_prefix("0x") {
+            usize::from_str_radix(hex, 16)
+        } else {
+            w.parse()
+        };
+
+        n.map_err(|_| InvalidFilter(format!("invalid {} `{}`", what, w)).into())
+    }
+
+    fn small(&mut self, what: &str, max: usize) -> Result<usize> {
+        let n = self.number(what)?;
+
+        if n > max {
+            Err(InvalidFilter(format!("{} `{}` out of range", what, n)).into())
+        } else {
+            Ok(n)
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.and_expr()?;
+
+        while self.eat(&["or", "||"]) {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and_expr()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+
+        while self.eat(&["and", "&&"]) {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat(&["not", "!"]) {
+            Ok(Expr::Not(Box::new(self.unary()?)))
+        } else if self.eat(&["("]) {
+            let expr = self.expr()?;
+
+            if self.eat(&[")"]) {
+                Ok(expr)
+            } else {
+                Err(InvalidFilter("expected `)`".to_owned()).into())
+            }
+        } else {
+            self.primitive()
+        }
+    }
+
+    fn dir(&mut self) -> Dir {
+        if self.eat(&["src"]) {
+            Dir::Src
+        } else if self.eat(&["dst"]) {
+            Dir::Dst
+        } else {
+            Dir::Any
+        }
+    }
+
+    fn is_port_qualifier(&self) -> bool {
+        match self.peek() {
+            Some(&Token::Word(w)) => ["src", "dst", "port", "portrange"].contains(&w),
+            _ => false,
+        }
+    }
+
+    fn proto(&mut self, proto: i32) -> Result<Expr> {
+        let expr = Expr::Proto(proto as u8);
+
+        if self.is_port_qualifier() {
+            Ok(Expr::And(Box::new(expr), Box::new(self.qualified()?)))
+        } else {
+            Ok(expr)
+        }
+    }
+
+    fn primitive(&mut self) -> Result<Expr> {
+        let w = match self.peek() {
+            Some(&Token::Word(w)) => w,
+            Some(&Token::Op(op)) => return Err(InvalidFilter(format!("unexpected `{}`", op)).into()),
+            None => return Err(InvalidFilter("unexpected end of expression".to_owned()).into()),
+        };
+
+        match w {
+            "ether" => {
+                self.pos += 1;
+
+                if self.eat(&["proto"]) {
+                    Ok(Expr::EtherType(self.small("ether type", 0xffff)? as u16))
+                } else {
+                    let dir = self.dir();
+
+                    self.eat(&["host"]);
+
+                    Ok(Expr::EtherHost(dir, self.value("MAC address")?))
+                }
+            }
+            "arp" | "rarp" | "ip" | "ip6" => {
+                self.pos += 1;
+
+                let ether_type = match w {
+                    "arp" => ETHER_TYPE_ARP,
+                    "rarp" => ETHER_TYPE_RARP,
+                    "ip" => ETHER_TYPE_IPv4,
+                    _ => ETHER_TYPE_IPv6,
+                } as u16;
+
+                if w.starts_with("ip") && self.eat(&["proto"]) {
+                    Ok(Expr::IpProto(ether_type, self.small("IP protocol", 0xff)? as u8))
+                } else {
+                    Ok(Expr::EtherType(ether_type))
+                }
+            }
+            "vlan" => {
+                self.pos += 1;
+
+                match self.peek() {
+                    Some(&Token::Word(w)) if w.starts_with(|c: char| c.is_ascii_digit()) => {
+                        Ok(Expr::Vlan(Some(self.small("VLAN id", 0x0fff)? as u16)))
+                    }
+                    _ => Ok(Expr::Vlan(None)),
+                }
+            }
+            "tcp" | "udp" | "sctp" | "icmp" | "icmp6" => {
+                self.pos += 1;
+
+                self.proto(match w {
+                    "tcp" => libc::IPPROTO_TCP,
+                    "udp" => libc::IPPROTO_UDP,
+                    "sctp" => libc::IPPROTO_SCTP,
+                    "icmp" => libc::IPPROTO_ICMP,
+                    _ => libc::IPPROTO_ICMPV6,
+                })
+            }
+            "len" => {
+                self.pos += 1;
+
+                let cmp = match self.next() {
+                    Some(Token::Op("<")) => Cmp::Lt,
+                    Some(Token::Op("<=")) => Cmp::Le,
+                    Some(Token::Op(">")) => Cmp::Gt,
+                    Some(Token::Op(">=")) => Cmp::Ge,
+                    Some(Token::Op("=")) | Some(Token::Op("==")) => Cmp::Eq,
+                    Some(Token::Op("!=")) => Cmp::Ne,
+                    _ => return Err(InvalidFilter("expected comparison operator after `len`".to_owned()).into()),
+                };
+
+                Ok(Expr::Len(cmp, self.number("length")?))
+            }
+            "less" => {
+                self.pos += 1;
+
+                Ok(Expr::Len(Cmp::Le, self.number("length")?))
+            }
+            "greater" => {
+                self.pos += 1;
+
+                Ok(Expr::Len(Cmp::Ge, self.number("length")?))
+            }
+            _ => self.qualified(),
+        }
+    }
+
+    fn qualified(&mut self) -> Result<Expr> {
+        let dir = self.dir();
+        let w = self.word("primitive")?;
+
+        match w {
+            "host" => Ok(Expr::Host(dir, self.value("IP address")?)),
+            "net" => {
+                let net = self.word("network")?;
+                let (addr, depth) = match net.find('/') {
+                    Some(idx) => (&net[..idx], Some(&net[idx + 1..])),
+                    None => (net, None),
+                };
+                let addr: IpAddr = addr
+                    .parse()
+                    .map_err(|_| InvalidFilter(format!("invalid network `{}`", net)))?;
+                let max = if addr.is_ipv4() { 32 } else { 128 };
+                let depth = match depth {
+                    Some(depth) => depth
+                        .parse()
+                        .ok()
+                        .filter(|&depth| depth <= max)
+                        .ok_or_else(|| InvalidFilter(format!("invalid network `{}`", net)))?,
+                    None => max,
+                };
+
+                Ok(Expr::Net(dir, addr, depth))
+            }
+            "port" => {
+                let port = self.small("port", 0xffff)? as u16;
+
+                Ok(Expr::Port(dir, port, port))
+            }
+            "portrange" => {
+                let range = self.word("port range")?;
+                let err = || InvalidFilter(format!("invalid port range `{}`", range));
+                let mut ports = range.splitn(2, '-').map(|port| port.parse::<u16>());
+
+                match (ports.next(), ports.next()) {
+                    (Some(Ok(lo)), Some(Ok(hi))) if lo <= hi => Ok(Expr::Port(dir, lo, hi)),
+                    _ => Err(err().into()),
+                }
+            }
+            _ if dir != Dir::Any => {
+                // `src ADDR` is the shorthand of `src host ADDR`
+                w.parse()
+                    .map(|addr| Expr::Host(dir, addr))
+                    .map_err(|_| InvalidFilter(format!("invalid IP address `{}`", w)).into())
+            }
+            _ => Err(InvalidFilter(format!("unknown primitive `{}`", w)).into()),
+        }
+    }
+}
+
+/// A compiled packet filter.
+pub struct Filter {
+    expr: String,
+    pred: Predicate,
+}
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Filter").field(&self.expr).finish()
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+impl str::FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Filter::new(s)
+    }
+}
+
+impl Filter {
+    /// Compile a filter expression, an empty expression matches all the packets.
+    pub fn new(expr: &str) -> Result<Filter> {
+        let mut parser = Parser {
+            tokens: tokenize(expr)?,
+            pos: 0,
+        };
+
+        let pred = if parser.tokens.is_empty() {
+            Box::new(|_: &Dissection| true)
+        } else {
+            let ast = parser.expr()?;
+
+            if let Some(token) = parser.next() {
+                return Err(InvalidFilter(format!("unexpected {:?}", token)).into());
+            }
+
+            ast.compile()
+        };
+
+        Ok(Filter {
+            expr: expr.trim().to_owned(),
+            pred,
+        })
+    }
+
+    /// The filter expression.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// The dissected packet matches the filter.
+    #[inline]
+    pub fn matches_dissection(&self, d: &Dissection) -> bool {
+        (self.pred)(d)
+    }
+
+    /// The packet data matches the filter, which starts from the Ethernet header.
+    #[inline]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.matches_dissection(&dissect(data, data.len()))
+    }
+
+    /// The mbuf matches the filter.
+    ///
+    /// The leading `DISSECT_LEN` bytes of a segmented packet are read for the dissection.
+    pub fn matches_mbuf(&self, m: &MBuf) -> bool {
+        dissect_mbuf(m).is_some_and(|d| self.matches_dissection(&d))
+    }
+
+    /// Select the matched mbufs of a burst.
+    pub fn select<'a>(&'a self, pkts: &'a [MBuf]) -> impl Iterator<Item = &'a MBuf> + 'a {
+        pkts.iter().filter(move |m| self.matches_mbuf(m))
+    }
+
+    /// Set the verdict of the matched mbufs of a burst, returns the number of the matched mbufs.
+    ///
+    /// The mbufs decided by a previous rule are skipped, so the first matched rule wins like an ACL.
+    pub fn classify(&self, pkts: &mut [MBuf], verdict: Verdict) -> usize {
+        pkts.iter_mut()
+            .filter(|m| !m.meta().is_decided() && self.matches_mbuf(m))
+            .map(|m| m.set_verdict(verdict))
+            .count()
+    }
+}
+
+#[repr(C)]
+struct CaptureContext<F> {
+    filter: Filter,
+    callback: F,
+}
+
+/// A capture of the received packets which match a filter, which is removed when dropped.
+///
+/// The capture must be dropped when the RX queue isn't polled, e.g. after the port was stopped,
+/// because a RX burst running on the other lcore may still use the removed callback.
+pub struct RxCapture {
+    port_id: PortId,
+    queue_id: QueueId,
+    cb: *const ffi::rte_eth_rxtx_callback,
+    ctx: *mut c_void,
+    drop_ctx: unsafe fn(*mut c_void),
+}
+
+unsafe impl Send for RxCapture {}
+
+impl Drop for RxCapture {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rte_eth_remove_rx_callback(self.port_id, self.queue_id, self.cb);
+
+            (self.drop_ctx)(self.ctx);
+        }
+    }
+}
+
+impl RxCapture {
+    /// The filter of the capture.
+    pub fn filter(&self) -> &Filter {
+        unsafe { &(*(self.ctx as *const CaptureContext<()>)).filter }
+    }
+}
+
+/// Capture the received packets on a RX queue which match the filter.
+///
+/// The callback is called with each matched packet in the RX burst on the polling lcore,
+/// the packet is still delivered to the application, it could be cloned to keep a reference.
+pub fn capture<F>(port_id: PortId, queue_id: QueueId, filter: Filter, callback: F) -> Result<RxCapture>
+where
+    F: FnMut(&MBuf) + Send + 'static,
+{
+    let ctx = Box::into_raw(Box::new(CaptureContext { filter, callback }));
+
+    let cb = unsafe { ffi::rte_eth_add_rx_callback(port_id, queue_id, Some(capture_stub::<F>), ctx as *mut _) };
+
+    if cb.is_null() {
+        unsafe { drop_capture_context::<F>(ctx as *mut _) };
+
+        return Err(rte_error());
+    }
+
+    Ok(RxCapture {
+        port_id,
+        queue_id,
+        cb,
+        ctx: ctx as *mut _,
+        drop_ctx: drop_capture_context::<F>,
+    })
+}
+
+unsafe fn drop_capture_context<F>(ctx: *mut c_void) {
+    Box::from_raw(ctx as *mut CaptureContext<F>);
+}
+
+unsafe extern "C" fn capture_stub<F>(
+    _port_id: PortId,
+    _queue_id: QueueId,
+    pkts: *mut *mut ffi::rte_mbuf,
+    nb_pkts: u16,
+    _max_pkts: u16,
+    user_param: *mut c_void,
+) -> u16
+where
+    F: FnMut(&MBuf),
+{
+    let ctx = &mut *(user_param as *mut CaptureContext<F>);
+
+    // the `MBuf` is a transparent wrapper of the mbuf pointer, the packets are borrowed from the burst
+    let pkts = slice::from_raw_parts(pkts as *const MBuf, nb_pkts as usize);
+
+    for m in pkts {
+        if ctx.filter.matches_mbuf(m) {
+            (ctx.callback)(m)
+        }
+    }
+
+    nb_pkts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn udp_packet(vlan: Option<u16>, src: [u8; 4], dst: [u8; 4], sport: u16, dport: u16) -> Vec<u8> {
+        let mut pkt = vec![0x02, 0, 0, 0, 0, 2, 0x02, 0, 0, 0, 0, 1];
+
+        if let Some(vlan) = vlan {
+            pkt.extend_from_slice(&(ETHER_TYPE_VLAN as u16).to_be_bytes());
+            pkt.extend_from_slice(&vlan.to_be_bytes());
+        }
+
+        pkt.extend_from_slice(&(ETHER_TYPE_IPv4 as u16).to_be_bytes());
+        pkt.extend_from_slice(&[0x45, 0, 0, 28, 0, 1, 0x40, 0, 64, libc::IPPROTO_UDP as u8, 0, 0]);
+        pkt.extend_from_slice(&src);
+        pkt.extend_from_slice(&dst);
+        pkt.extend_from_slice(&sport.to_be_bytes());
+        pkt.extend_from_slice(&dport.to_be_bytes());
+        pkt.extend_from_slice(&[0, 8, 0, 0]);
+        pkt
+    }
+
+    #[test]
+    fn test_dissect() {
+        let pkt = udp_packet(Some(100), [10, 0, 0, 1], [192, 168, 1, 1], 1234, 53);
+        let d = dissect(&pkt, pkt.len());
+
+        assert_eq!(d.vlans(), &[100]);
+        assert_eq!(d.ether_type, ETHER_TYPE_IPv4 as u16);
+        assert_eq!(d.src_ip, Some("10.0.0.1".parse().unwrap()));
+        assert_eq!(d.proto, Some(libc::IPPROTO_UDP as u8));
+        assert_eq!((d.src_port, d.dst_port), (Some(1234), Some(53)));
+        assert_eq!(d.frag, None);
+
+        // truncated
+        let d = dissect(&pkt[..38], pkt.len());
+
+        assert_eq!(d.dst_ip, Some("192.168.1.1".parse().unwrap()));
+        assert_eq!(d.src_port, None);
+    }
+
+    #[test]
+    fn test_filter() {
+        let pkt = udp_packet(None, [10, 0, 0, 1], [192, 168, 1, 1], 1234, 53);
+
+        for &(expr, matched) in &[
+            ("", true),
+            ("udp", true),
+            ("tcp", false),
+            ("udp port 53", true),
+            ("udp src port 53", false),
+            ("tcp and dst port 443", false),
+            ("ip and not tcp", true),
+            ("!(tcp || icmp) && ip", true),
+            ("src host 10.0.0.1", true),
+            ("dst 10.0.0.1", false),
+            ("dst net 192.168.0.0/16", true),
+            ("net 172.16.0.0/12", false),
+            ("portrange 1000-2000", true),
+            ("vlan", false),
+            ("ip6 or arp", false),
+            ("ip proto 17", true),
+            ("ether src 02:00:00:00:00:01", true),
+            ("ether dst host 02:00:00:00:00:01", false),
+            ("len >= 42", true),
+            ("less 41", false),
+            ("tcp or udp and port 80", false),
+        ] {
+            assert_eq!(Filter::new(expr).unwrap().matches(&pkt), matched, "{}", expr);
+        }
+
+        for expr in &[
+            "tcp and",
+            "(udp",
+            "port 65536",
+            "net 10.0.0.0/33",
+            "foo",
+            "udp udp",
+            "len 10",
+            "portrange 2-1",
+        ] {
+            assert!(Filter::new(expr).is_err(), "{}", expr);
+        }
+    }
+}
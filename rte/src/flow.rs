@@ -0,0 +1,1211 @@
+//!
+//! Generic flow rules, which match the ingress packets with a pattern and apply the actions in the NIC.
+//!
+//! The rules are described with the serializable `FlowRule`, and installed through a `FlowTable`
+//! of the port, which remembers the installed rules, so they could be saved to a file,
+//! and reinstalled after the application or the port restarts, to keep the NIC state deterministic.
+//! The rules with a `Count` action could be queried for the number of the matched packets and bytes.
+//!
+//! With the isolated mode, see `EthDevice::flow_isolate`, all the traffic which doesn't match
+//! the explicit flow rules is dropped by the NIC, instead of being received by the RSS queues.
+//!
+//! The tunnel offload API lets the NIC decapsulate a tunnel, e.g. the VXLAN port of a virtual switch,
+//! with the private actions of `TunnelDecap` and match the inner packets with the private items
+//! of `TunnelMatch` in another group. A packet which misses the rules of the group after a partial
+//! offloading is received with its `restore_info`, so the software could continue the processing.
+//! The API requires DPDK 20.11 or later, otherwise it fails with `ENOTSUP`.
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! use rte::flow::{FlowAction, FlowAttr, FlowItem, FlowRule, FlowTable};
+//!
+//! let mut table = FlowTable::new(0);
+//!
+//! let id = table
+//!     .create(
+//!         FlowRule::new(FlowAttr::ingress())
+//!             .with_item(FlowItem::eth())
+//!             .with_item(FlowItem::ipv4())
+//!             .with_item(FlowItem::Udp {
+//!                 src_port: None,
+//!                 dst_port: Some(4789),
+//!             })
+//!             .with_item(FlowItem::vxlan(100))
+//!             .with_action(FlowAction::Count)
+//!             .with_action(FlowAction::Queue(1)),
+//!     )
+//!     .expect("Cannot create flow rule");
+//!
+//! println!("{:?}", table.query(id, false).unwrap());
+//!
+//! table.save(File::create("flows.json").unwrap()).unwrap();
+//!
+//! // after restart
+//!
+//! let mut table = FlowTable::new(0);
+//!
+//! table.restore(File::open("flows.json").unwrap()).unwrap();
+//! ```
+//!
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::CStr;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_void;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use failure::Error;
+use serde_json;
+
+use ffi::{self, rte_flow_action_type::*, rte_flow_item_type::*};
+
+use common::byteorder::{be16, be32};
+use common::coop;
+use errors::{ErrorKind::FlowError, Result, RteError};
+use ethdev::PortId;
+use mbuf::MBuf;
+use utils::AsRaw;
+
+/// The attributes of a flow rule.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowAttr {
+    /// The group of rule, the rules in the group 0 are always processed first.
+    pub group: u32,
+    /// The priority level of rule within the group, lower values denote higher priority.
+    pub priority: u32,
+    /// The rule applies to the ingress traffic.
+    pub ingress: bool,
+    /// The rule applies to the egress traffic.
+    pub egress: bool,
+}
+
+impl FlowAttr {
+    /// The attributes of an ingress rule in the group 0 with the highest priority.
+    pub fn ingress() -> Self {
+        FlowAttr {
+            ingress: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// A matching pattern item, the unspecified fields match any value.
+///
+/// The addresses are matched with a prefix length.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlowItem {
+    /// Match an Ethernet header.
+    Eth {
+        dst: Option<[u8; 6]>,
+        src: Option<[u8; 6]>,
+        ether_type: Option<u16>,
+    },
+    /// Match an 802.1Q VLAN tag.
+    Vlan { vid: Option<u16> },
+    /// Match an IPv4 header.
+    Ipv4 {
+        src: Option<(Ipv4Addr, u8)>,
+        dst: Option<(Ipv4Addr, u8)>,
+        proto: Option<u8>,
+    },
+    /// Match an IPv6 header.
+    Ipv6 {
+        src: Option<(Ipv6Addr, u8)>,
+        dst: Option<(Ipv6Addr, u8)>,
+        proto: Option<u8>,
+    },
+    /// Match an UDP header.
+    Udp {
+        src_port: Option<u16>,
+        dst_port: Option<u16>,
+    },
+    /// Match a TCP header.
+    Tcp {
+        src_port: Option<u16>,
+        dst_port: Option<u16>,
+    },
+    /// Match a VXLAN header.
+    Vxlan { vni: Option<u32> },
+}
+
+impl FlowItem {
+    /// Match any Ethernet header.
+    pub fn eth() -> Self {
+        FlowItem::Eth {
+            dst: None,
+            src: None,
+            ether_type: None,
+        }
+    }
+
+    /// Match any IPv4 header.
+    pub fn ipv4() -> Self {
+        FlowItem::Ipv4 {
+            src: None,
+            dst: None,
+            proto: None,
+        }
+    }
+
+    /// Match any IPv6 header.
+    pub fn ipv6() -> Self {
+        FlowItem::Ipv6 {
+            src: None,
+            dst: None,
+            proto: None,
+        }
+    }
+
+    /// Match any UDP header.
+    pub fn udp() -> Self {
+        FlowItem::Udp {
+            src_port: None,
+            dst_port: None,
+        }
+    }
+
+    /// Match any TCP header.
+    pub fn tcp() -> Self {
+        FlowItem::Tcp {
+            src_port: None,
+            dst_port: None,
+        }
+    }
+
+    /// Match a VXLAN header with the VNI.
+    pub fn vxlan(vni: u32) -> Self {
+        FlowItem::Vxlan { vni: Some(vni) }
+    }
+}
+
+/// An action applied to the matched packets.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlowAction {
+    /// Leave the packets to the next rules with a lower priority.
+    PassThru,
+    /// Redirect the packets to a group.
+    Jump(u32),
+    /// Attach a value to the packets, which is reported with `PKT_RX_FDIR_ID` flag.
+    Mark(u32),
+    /// Flag the packets with `PKT_RX_FDIR` flag.
+    Flag,
+    /// Assign the packets to a RX queue.
+    Queue(u16),
+    /// Drop the packets.
+    Drop,
+    /// Count the packets and bytes, see `FlowTable::query`.
+    Count,
+    /// Spread the packets among the RX queues with the default hash function.
+    Rss {
+        /// The RSS hash types, e.g. the bits of `RssHashFunc`, or 0 for the default types of the PMD.
+        types: u64,
+        /// The RX queues.
+        queues: Vec<u16>,
+    },
+}
+
+/// A flow rule, which matches the packets with the pattern and applies the actions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowRule {
+    pub attr: FlowAttr,
+    pub pattern: Vec<FlowItem>,
+    pub actions: Vec<FlowAction>,
+}
+
+impl FlowRule {
+    /// A rule with the attributes, which has no pattern item and action yet.
+    pub fn new(attr: FlowAttr) -> Self {
+        FlowRule {
+            attr,
+            pattern: vec![],
+            actions: vec![],
+        }
+    }
+
+    /// An ingress rule in the group 0 with the highest priority.
+    pub fn ingress() -> Self {
+        Self::new(FlowAttr::ingress())
+    }
+
+    /// Append an item to the pattern.
+    pub fn with_item(mut self, item: FlowItem) -> Self {
+        self.pattern.push(item);
+        self
+    }
+
+    /// Append an action to the actions.
+    pub fn with_action(mut self, action: FlowAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// The rule counts the matched packets.
+    pub fn is_counted(&self) -> bool {
+        self.actions.contains(&FlowAction::Count)
+    }
+}
+
+/// The counters of a flow rule with the `Count` action.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlowCount {
+    /// The number of the matched packets, if it is supported by the PMD.
+    pub hits: Option<u64>,
+    /// The number of the matched bytes, if it is supported by the PMD.
+    pub bytes: Option<u64>,
+}
+
+fn prefix_mask(len: u8, bits: u8) -> u128 {
+    match len {
+        0 => 0,
+        len if len >= bits => !0u128 >> (128 - u32::from(bits)),
+        len => (!0u128 >> (128 - u32::from(bits))) & !((1u128 << (bits - len)) - 1),
+    }
+}
+
+fn ipv4_prefix(addr: Option<(Ipv4Addr, u8)>) -> (u32, u32) {
+    addr.map_or((0, 0), |(addr, len)| {
+        let mask = prefix_mask(len, 32) as u32;
+
+        (be32::new(u32::from(addr) & mask).to_raw(), be32::new(mask).to_raw())
+    })
+}
+
+fn ipv6_prefix(addr: Option<(Ipv6Addr, u8)>) -> ([u8; 16], [u8; 16]) {
+    addr.map_or(([0; 16], [0; 16]), |(addr, len)| {
+        let mask = prefix_mask(len, 128);
+
+        ((u128::from(addr) & mask).to_be_bytes(), mask.to_be_bytes())
+    })
+}
+
+fn exact16(value: Option<u16>) -> (u16, u16) {
+    value.map_or((0, 0), |value| (be16::new(value).to_raw(), !0))
+}
+
+enum Spec {
+    Eth(ffi::rte_flow_item_eth),
+    Vlan(ffi::rte_flow_item_vlan),
+    Ipv4(ffi::rte_flow_item_ipv4),
+    Ipv6(ffi::rte_flow_item_ipv6),
+    Udp(ffi::rte_flow_item_udp),
+    Tcp(ffi::rte_flow_item_tcp),
+    Vxlan(ffi::rte_flow_item_vxlan),
+}
+
+impl Spec {
+    fn as_ptr(&self) -> *const c_void {
+        match *self {
+            Spec::Eth(ref spec) => spec as *const _ as *const _,
+            Spec::Vlan(ref spec) => spec as *const _ as *const _,
+            Spec::Ipv4(ref spec) => spec as *const _ as *const _,
+            Spec::Ipv6(ref spec) => spec as *const _ as *const _,
+            Spec::Udp(ref spec) => spec as *const _ as *const _,
+            Spec::Tcp(ref spec) => spec as *const _ as *const _,
+            Spec::Vxlan(ref spec) => spec as *const _ as *const _,
+        }
+    }
+}
+
+impl FlowItem {
+    /// The item type, the specification and the mask.
+    fn to_raw(&self) -> (ffi::rte_flow_item_type::Type, Spec, Spec) {
+        match *self {
+            FlowItem::Eth { dst, src, ether_type } => {
+                let mut spec = ffi::rte_flow_item_eth::default();
+                let mut mask = ffi::rte_flow_item_eth::default();
+
+                if let Some(dst) = dst {
+                    spec.dst.addr_bytes = dst;
+                    mask.dst.addr_bytes = [0xff; 6];
+                }
+                if let Some(src) = src {
+                    spec.src.addr_bytes = src;
+                    mask.src.addr_bytes = [0xff; 6];
+                }
+                let (ether_type, ether_type_mask) = exact16(ether_type);
+
+                spec.type_ = ether_type;
+                mask.type_ = ether_type_mask;
+
+                (RTE_FLOW_ITEM_TYPE_ETH, Spec::Eth(spec), Spec::Eth(mask))
+            }
+            FlowItem::Vlan { vid } => {
+                let spec = ffi::rte_flow_item_vlan {
+                    tci: be16::new(vid.unwrap_or_default()).to_raw(),
+                    inner_type: 0,
+                };
+                let mask = ffi::rte_flow_item_vlan {
+                    tci: be16::new(vid.map_or(0, |_| 0x0fff)).to_raw(),
+                    inner_type: 0,
+                };
+
+                (RTE_FLOW_ITEM_TYPE_VLAN, Spec::Vlan(spec), Spec::Vlan(mask))
+            }
+            FlowItem::Ipv4 { src, dst, proto } => {
+                let mut spec = ffi::rte_flow_item_ipv4::default();
+                let mut mask = ffi::rte_flow_item_ipv4::default();
+
+                let (addr, addr_mask) = ipv4_prefix(src);
+                spec.hdr.src_addr = addr;
+                mask.hdr.src_addr = addr_mask;
+
+                let (addr, addr_mask) = ipv4_prefix(dst);
+                spec.hdr.dst_addr = addr;
+                mask.hdr.dst_addr = addr_mask;
+
+                spec.hdr.next_proto_id = proto.unwrap_or_default();
+                mask.hdr.next_proto_id = proto.map_or(0, |_| 0xff);
+
+                (RTE_FLOW_ITEM_TYPE_IPV4, Spec::Ipv4(spec), Spec::Ipv4(mask))
+            }
+            FlowItem::Ipv6 { src, dst, proto } => {
+                let mut spec = ffi::rte_flow_item_ipv6::default();
+                let mut mask = ffi::rte_flow_item_ipv6::default();
+
+                let (addr, addr_mask) = ipv6_prefix(src);
+                spec.hdr.src_addr = addr;
+                mask.hdr.src_addr = addr_mask;
+
+                let (addr, addr_mask) = ipv6_prefix(dst);
+                spec.hdr.dst_addr = addr;
+                mask.hdr.dst_addr = addr_mask;
+
+                spec.hdr.proto = proto.unwrap_or_default();
+                mask.hdr.proto = proto.map_or(0, |_| 0xff);
+
+                (RTE_FLOW_ITEM_TYPE_IPV6, Spec::Ipv6(spec), Spec::Ipv6(mask))
+            }
+            FlowItem::Udp { src_port, dst_port } => {
+                let mut spec = ffi::rte_flow_item_udp::default();
+                let mut mask = ffi::rte_flow_item_udp::default();
+
+                let (src_port, src_port_mask) = exact16(src_port);
+                let (dst_port, dst_port_mask) = exact16(dst_port);
+
+                spec.hdr.src_port = src_port;
+                spec.hdr.dst_port = dst_port;
+                mask.hdr.src_port = src_port_mask;
+                mask.hdr.dst_port = dst_port_mask;
+
+                (RTE_FLOW_ITEM_TYPE_UDP, Spec::Udp(spec), Spec::Udp(mask))
+            }
+            FlowItem::Tcp { src_port, dst_port } => {
+                let mut spec = ffi::rte_flow_item_tcp::default();
+                let mut mask = ffi::rte_flow_item_tcp::default();
+
+                let (src_port, src_port_mask) = exact16(src_port);
+                let (dst_port, dst_port_mask) = exact16(dst_port);
+
+                spec.hdr.src_port = src_port;
+                spec.hdr.dst_port = dst_port;
+                mask.hdr.src_port = src_port_mask;
+                mask.hdr.dst_port = dst_port_mask;
+
+                (RTE_FLOW_ITEM_TYPE_TCP, Spec::Tcp(spec), Spec::Tcp(mask))
+            }
+            FlowItem::Vxlan { vni } => {
+                let mut spec = ffi::rte_flow_item_vxlan::default();
+                let mut mask = ffi::rte_flow_item_vxlan::default();
+
+                if let Some(vni) = vni {
+                    spec.vni.copy_from_slice(&vni.to_be_bytes()[1..]);
+                    mask.vni = [0xff; 3];
+                }
+
+                (RTE_FLOW_ITEM_TYPE_VXLAN, Spec::Vxlan(spec), Spec::Vxlan(mask))
+            }
+        }
+    }
+}
+
+enum Conf {
+    None,
+    Jump(ffi::rte_flow_action_jump),
+    Mark(ffi::rte_flow_action_mark),
+    Queue(ffi::rte_flow_action_queue),
+    Count(ffi::rte_flow_action_count),
+    // the queues are owned by the configuration, which points to them
+    Rss(ffi::rte_flow_action_rss, Vec<u16>),
+}
+
+impl Conf {
+    fn as_ptr(&self) -> *const c_void {
+        match *self {
+            Conf::None => ptr::null(),
+            Conf::Jump(ref conf) => conf as *const _ as *const _,
+            Conf::Mark(ref conf) => conf as *const _ as *const _,
+            Conf::Queue(ref conf) => conf as *const _ as *const _,
+            Conf::Count(ref conf) => conf as *const _ as *const _,
+            Conf::Rss(ref conf, _) => conf as *const _ as *const _,
+        }
+    }
+}
+
+impl FlowAction {
+    fn to_raw(&self) -> (ffi::rte_flow_action_type::Type, Conf) {
+        match *self {
+            FlowAction::PassThru => (RTE_FLOW_ACTION_TYPE_PASSTHRU, Conf::None),
+            FlowAction::Jump(group) => (
+                RTE_FLOW_ACTION_TYPE_JUMP,
+                Conf::Jump(ffi::rte_flow_action_jump { group }),
+            ),
+            FlowAction::Mark(id) => (RTE_FLOW_ACTION_TYPE_MARK, Conf::Mark(ffi::rte_flow_action_mark { id })),
+            FlowAction::Flag => (RTE_FLOW_ACTION_TYPE_FLAG, Conf::None),
+            FlowAction::Queue(index) => (
+                RTE_FLOW_ACTION_TYPE_QUEUE,
+                Conf::Queue(ffi::rte_flow_action_queue { index }),
+            ),
+            FlowAction::Drop => (RTE_FLOW_ACTION_TYPE_DROP, Conf::None),
+            FlowAction::Count => (RTE_FLOW_ACTION_TYPE_COUNT, Conf::Count(Default::default())),
+            FlowAction::Rss { types, ref queues } => {
+                let queues = queues.clone();
+                let conf = ffi::rte_flow_action_rss {
+                    types,
+                    queue_num: queues.len() as u32,
+                    queue: queues.as_ptr(),
+                    ..Default::default()
+                };
+
+                (RTE_FLOW_ACTION_TYPE_RSS, Conf::Rss(conf, queues))
+            }
+        }
+    }
+}
+
+/// The raw attributes, pattern and actions of a rule, which own the specifications they point to.
+///
+/// The specifications are never changed after the pattern and actions are built,
+/// so they stay in place even if the rule is moved.
+struct RawRule {
+    attr: ffi::rte_flow_attr,
+    pattern: Vec<ffi::rte_flow_item>,
+    actions: Vec<ffi::rte_flow_action>,
+    _specs: Vec<(ffi::rte_flow_item_type::Type, Spec, Spec)>,
+    _confs: Vec<(ffi::rte_flow_action_type::Type, Conf)>,
+}
+
+impl From<&FlowRule> for RawRule {
+    fn from(rule: &FlowRule) -> Self {
+        let mut attr = ffi::rte_flow_attr {
+            group: rule.attr.group,
+            priority: rule.attr.priority,
+            ..Default::default()
+        };
+
+        attr.set_ingress(rule.attr.ingress as u32);
+        attr.set_egress(rule.attr.egress as u32);
+
+        let specs = rule.pattern.iter().map(FlowItem::to_raw).collect::<Vec<_>>();
+        let mut pattern = Vec::with_capacity(specs.len() + 1);
+
+        for &(kind, ref spec, ref mask) in &specs {
+            pattern.push(ffi::rte_flow_item {
+                type_: kind,
+                spec: spec.as_ptr(),
+                last: ptr::null(),
+                mask: mask.as_ptr(),
+            });
+        }
+
+        pattern.push(ffi::rte_flow_item {
+            type_: RTE_FLOW_ITEM_TYPE_END,
+            ..Default::default()
+        });
+
+        let confs = rule.actions.iter().map(FlowAction::to_raw).collect::<Vec<_>>();
+        let mut actions = Vec::with_capacity(confs.len() + 1);
+
+        for &(kind, ref conf) in &confs {
+            actions.push(ffi::rte_flow_action {
+                type_: kind,
+                conf: conf.as_ptr(),
+            });
+        }
+
+        actions.push(ffi::rte_flow_action {
+            type_: RTE_FLOW_ACTION_TYPE_END,
+            conf: ptr::null(),
+        });
+
+        RawRule {
+            attr,
+            pattern,
+            actions,
+            _specs: specs,
+            _confs: confs,
+        }
+    }
+}
+
+/// Convert the verbose flow error to an error.
+pub(crate) fn flow_error(ret: i32, error: &ffi::rte_flow_error) -> Error {
+    if error.message.is_null() {
+        RteError(ret.abs()).into()
+    } else {
+        let msg = unsafe { CStr::from_ptr(error.message) }.to_string_lossy();
+
+        FlowError(format!("{} ({})", msg, RteError(ret.abs()))).into()
+    }
+}
+
+/// Restrict the ingress traffic of the port to the flow rules, or restore the default behavior.
+///
+/// It must be called before the port is configured.
+pub fn isolate(port_id: PortId, on: bool) -> Result<()> {
+    let mut error = ffi::rte_flow_error::default();
+
+    let ret = unsafe { ffi::rte_flow_isolate(port_id, on as i32, &mut error) };
+
+    rte_check!(ret; err => { flow_error(ret, &error) })
+}
+
+/// Check whether a flow rule could be created on the port.
+pub fn validate(port_id: PortId, rule: &FlowRule) -> Result<()> {
+    let raw = RawRule::from(rule);
+    let mut error = ffi::rte_flow_error::default();
+
+    let ret = unsafe {
+        ffi::rte_flow_validate(
+            port_id,
+            &raw.attr,
+            raw.pattern.as_ptr(),
+            raw.actions.as_ptr(),
+            &mut error,
+        )
+    };
+
+    rte_check!(ret; err => { flow_error(ret, &error) })
+}
+
+/// The type of an offloaded tunnel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TunnelType {
+    Vxlan,
+    VxlanGpe,
+    Gre,
+    NvGre,
+    Geneve,
+}
+
+impl TunnelType {
+    fn to_raw(self) -> ffi::rte_flow_item_type::Type {
+        match self {
+            TunnelType::Vxlan => RTE_FLOW_ITEM_TYPE_VXLAN,
+            TunnelType::VxlanGpe => RTE_FLOW_ITEM_TYPE_VXLAN_GPE,
+            TunnelType::Gre => RTE_FLOW_ITEM_TYPE_GRE,
+            TunnelType::NvGre => RTE_FLOW_ITEM_TYPE_NVGRE,
+            TunnelType::Geneve => RTE_FLOW_ITEM_TYPE_GENEVE,
+        }
+    }
+
+    fn from_raw(kind: ffi::rte_flow_item_type::Type) -> Option<Self> {
+        match kind {
+            RTE_FLOW_ITEM_TYPE_VXLAN => Some(TunnelType::Vxlan),
+            RTE_FLOW_ITEM_TYPE_VXLAN_GPE => Some(TunnelType::VxlanGpe),
+            RTE_FLOW_ITEM_TYPE_GRE => Some(TunnelType::Gre),
+            RTE_FLOW_ITEM_TYPE_NVGRE => Some(TunnelType::NvGre),
+            RTE_FLOW_ITEM_TYPE_GENEVE => Some(TunnelType::Geneve),
+            _ => None,
+        }
+    }
+}
+
+/// The outer headers of an offloaded tunnel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowTunnel {
+    /// The type of tunnel.
+    pub kind: TunnelType,
+    /// The tunnel id, e.g. the VNI of VXLAN or the key of GRE.
+    pub tun_id: u64,
+    /// The outer source address.
+    pub src: IpAddr,
+    /// The outer destination address.
+    pub dst: IpAddr,
+    /// The outer source L4 port.
+    pub src_port: u16,
+    /// The outer destination L4 port.
+    pub dst_port: u16,
+    /// The tunnel flags.
+    pub flags: u16,
+    /// The outer TOS or traffic class.
+    pub tos: u8,
+    /// The outer TTL or hop limit.
+    pub ttl: u8,
+    /// The outer IPv6 flow label.
+    pub label: u32,
+}
+
+impl FlowTunnel {
+    /// A tunnel between the outer addresses with the tunnel id.
+    pub fn new(kind: TunnelType, tun_id: u64, src: IpAddr, dst: IpAddr) -> Self {
+        FlowTunnel {
+            kind,
+            tun_id,
+            src,
+            dst,
+            src_port: 0,
+            dst_port: 0,
+            flags: 0,
+            tos: 0,
+            ttl: 0,
+            label: 0,
+        }
+    }
+
+    fn to_raw(self) -> ffi::_rte_flow_tunnel {
+        let is_ipv6 = self.src.is_ipv6() || self.dst.is_ipv6();
+        let octets = |addr: IpAddr| {
+            let mut buf = [0; 16];
+
+            match addr {
+                IpAddr::V4(addr) if is_ipv6 => buf.copy_from_slice(&addr.to_ipv6_mapped().octets()),
+                IpAddr::V4(addr) => buf[..4].copy_from_slice(&addr.octets()),
+                IpAddr::V6(addr) => buf.copy_from_slice(&addr.octets()),
+            }
+
+            buf
+        };
+
+        ffi::_rte_flow_tunnel {
+            type_: self.kind.to_raw(),
+            tun_id: self.tun_id,
+            src_addr: octets(self.src),
+            dst_addr: octets(self.dst),
+            tp_src: be16::new(self.src_port).to_raw(),
+            tp_dst: be16::new(self.dst_port).to_raw(),
+            tun_flags: self.flags,
+            is_ipv6: is_ipv6 as u8,
+            tos: self.tos,
+            ttl: self.ttl,
+            label: self.label,
+        }
+    }
+
+    fn from_raw(raw: &ffi::_rte_flow_tunnel) -> Option<Self> {
+        let addr = |buf: &[u8; 16]| -> IpAddr {
+            if raw.is_ipv6 != 0 {
+                Ipv6Addr::from(*buf).into()
+            } else {
+                Ipv4Addr::new(buf[0], buf[1], buf[2], buf[3]).into()
+            }
+        };
+
+        Some(FlowTunnel {
+            kind: TunnelType::from_raw(raw.type_)?,
+            tun_id: raw.tun_id,
+            src: addr(&raw.src_addr),
+            dst: addr(&raw.dst_addr),
+            src_port: be16::from_raw(raw.tp_src).get(),
+            dst_port: be16::from_raw(raw.tp_dst).get(),
+            flags: raw.tun_flags,
+            tos: raw.tos,
+            ttl: raw.ttl,
+            label: raw.label,
+        })
+    }
+}
+
+/// The private actions of the PMD which decapsulate an offloaded tunnel.
+///
+/// The actions are placed before the actions of the decap rule, see `FlowTable::create_tunnel`,
+/// and released when dropped.
+pub struct TunnelDecap {
+    port_id: PortId,
+    actions: *mut ffi::rte_flow_action,
+    len: u32,
+}
+
+impl TunnelDecap {
+    /// Allocate the decap actions of the tunnel on the port.
+    pub fn new(port_id: PortId, tunnel: &FlowTunnel) -> Result<Self> {
+        let mut raw = tunnel.to_raw();
+        let mut actions = ptr::null_mut();
+        let mut len = 0;
+        let mut error = ffi::rte_flow_error::default();
+
+        let ret = unsafe { ffi::_rte_flow_tunnel_decap_set(port_id, &mut raw, &mut actions, &mut len, &mut error) };
+
+        rte_check!(ret; ok => { TunnelDecap { port_id, actions, len } }; err => { flow_error(ret, &error) })
+    }
+
+    /// The port of the actions.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    fn actions(&self) -> &[ffi::rte_flow_action] {
+        if self.actions.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.actions, self.len as usize) }
+        }
+    }
+}
+
+impl Drop for TunnelDecap {
+    fn drop(&mut self) {
+        let mut error = ffi::rte_flow_error::default();
+
+        unsafe { ffi::_rte_flow_tunnel_action_decap_release(self.port_id, self.actions, self.len, &mut error) };
+    }
+}
+
+/// The private items of the PMD which match the packets of an offloaded tunnel.
+///
+/// The items are placed before the pattern of the tunnel rule, see `FlowTable::create_tunnel`,
+/// and released when dropped.
+pub struct TunnelMatch {
+    port_id: PortId,
+    items: *mut ffi::rte_flow_item,
+    len: u32,
+}
+
+impl TunnelMatch {
+    /// Allocate the match items of the tunnel on the port.
+    pub fn new(port_id: PortId, tunnel: &FlowTunnel) -> Result<Self> {
+        let mut raw = tunnel.to_raw();
+        let mut items = ptr::null_mut();
+        let mut len = 0;
+        let mut error = ffi::rte_flow_error::default();
+
+        let ret = unsafe { ffi::_rte_flow_tunnel_match(port_id, &mut raw, &mut items, &mut len, &mut error) };
+
+        rte_check!(ret; ok => { TunnelMatch { port_id, items, len } }; err => { flow_error(ret, &error) })
+    }
+
+    /// The port of the items.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    fn items(&self) -> &[ffi::rte_flow_item] {
+        if self.items.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.items, self.len as usize) }
+        }
+    }
+}
+
+impl Drop for TunnelMatch {
+    fn drop(&mut self) {
+        let mut error = ffi::rte_flow_error::default();
+
+        unsafe { ffi::_rte_flow_tunnel_item_release(self.port_id, self.items, self.len, &mut error) };
+    }
+}
+
+bitflags! {
+    /// The flags of the restore info.
+    pub struct RestoreInfoFlags: u64 {
+        /// The packet was received from an offloaded tunnel.
+        const RTE_FLOW_RESTORE_INFO_TUNNEL = 1 << 0;
+        /// The packet is still encapsulated.
+        const RTE_FLOW_RESTORE_INFO_ENCAPSULATED = 1 << 1;
+        /// The group of the missed rule is valid.
+        const RTE_FLOW_RESTORE_INFO_GROUP_ID = 1 << 2;
+    }
+}
+
+/// The restore info of a packet, which missed the offloaded rules after a partial processing in the NIC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RestoreInfo {
+    /// The flags of the restore info.
+    pub flags: RestoreInfoFlags,
+    /// The group of the missed rule.
+    pub group_id: Option<u32>,
+    /// The tunnel which the packet was received from.
+    pub tunnel: Option<FlowTunnel>,
+}
+
+impl RestoreInfo {
+    /// The packet is still encapsulated, the outer headers were not removed by the NIC.
+    pub fn is_encapsulated(&self) -> bool {
+        self.flags
+            .contains(RestoreInfoFlags::RTE_FLOW_RESTORE_INFO_ENCAPSULATED)
+    }
+}
+
+/// Retrieve the restore info of a received packet, to continue the processing in software.
+pub fn restore_info(port_id: PortId, m: &MBuf) -> Result<RestoreInfo> {
+    let mut info = ffi::_rte_flow_restore_info::default();
+    let mut error = ffi::rte_flow_error::default();
+
+    let ret = unsafe { ffi::_rte_flow_get_restore_info(port_id, m.as_raw(), &mut info, &mut error) };
+
+    rte_check!(ret; err => { flow_error(ret, &error) })?;
+
+    let flags = RestoreInfoFlags::from_bits_truncate(info.flags);
+
+    Ok(RestoreInfo {
+        flags,
+        group_id: if flags.contains(RestoreInfoFlags::RTE_FLOW_RESTORE_INFO_GROUP_ID) {
+            Some(info.group_id)
+        } else {
+            None
+        },
+        tunnel: if flags.contains(RestoreInfoFlags::RTE_FLOW_RESTORE_INFO_TUNNEL) {
+            FlowTunnel::from_raw(&info.tunnel)
+        } else {
+            None
+        },
+    })
+}
+
+/// Copy the marks of the `MARK` action of the matched flow rules to the flow id in the metadata of a burst,
+/// returns the number of the marked mbufs.
+pub fn tag_marked(pkts: &mut [MBuf]) -> usize {
+    pkts.iter_mut()
+        .filter_map(|m| m.fdir_id().map(|id| m.set_flow_id(id)))
+        .count()
+}
+
+/// The flow rules installed on a port.
+///
+/// The rules are left in the NIC when the table is dropped, until the port is closed or flushed.
+pub struct FlowTable {
+    port_id: PortId,
+    next_id: u32,
+    flows: BTreeMap<u32, (FlowRule, NonNull<ffi::rte_flow>)>,
+    tunnels: BTreeSet<u32>,
+}
+
+// the flow handles could be destroyed from any thread of the process
+unsafe impl Send for FlowTable {}
+
+impl FlowTable {
+    /// Create an empty table of the port.
+    pub fn new(port_id: PortId) -> Self {
+        FlowTable {
+            port_id,
+            next_id: 0,
+            flows: BTreeMap::new(),
+            tunnels: BTreeSet::new(),
+        }
+    }
+
+    /// The port of the table.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The number of installed rules.
+    pub fn len(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// The table has no installed rule.
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+
+    /// The installed rules and their ids, in order of creation.
+    pub fn rules(&self) -> Vec<(u32, &FlowRule)> {
+        self.flows.iter().map(|(&id, (rule, _))| (id, rule)).collect()
+    }
+
+    /// Check whether a flow rule could be created on the port.
+    pub fn validate(&self, rule: &FlowRule) -> Result<()> {
+        validate(self.port_id, rule)
+    }
+
+    /// Install a flow rule, returns its id in the table.
+    pub fn create(&mut self, rule: FlowRule) -> Result<u32> {
+        let raw = RawRule::from(&rule);
+
+        self.install(rule, &raw)
+    }
+
+    /// Install a flow rule of an offloaded tunnel, returns its id in the table.
+    ///
+    /// The private items and actions of the tunnel are placed before the pattern and actions of the rule.
+    /// The tunnel rules are not saved, since the private items and actions are only valid for the running port.
+    pub fn create_tunnel(
+        &mut self,
+        rule: FlowRule,
+        items: Option<&TunnelMatch>,
+        actions: Option<&TunnelDecap>,
+    ) -> Result<u32> {
+        let mut raw = RawRule::from(&rule);
+
+        if let Some(items) = items {
+            raw.pattern.splice(0..0, items.items().iter().cloned());
+        }
+        if let Some(actions) = actions {
+            raw.actions.splice(0..0, actions.actions().iter().cloned());
+        }
+
+        let id = self.install(rule, &raw)?;
+
+        self.tunnels.insert(id);
+
+        Ok(id)
+    }
+
+    fn install(&mut self, rule: FlowRule, raw: &RawRule) -> Result<u32> {
+        let mut error = ffi::rte_flow_error::default();
+
+        let flow = unsafe {
+            ffi::rte_flow_create(
+                self.port_id,
+                &raw.attr,
+                raw.pattern.as_ptr(),
+                raw.actions.as_ptr(),
+                &mut error,
+            )
+        };
+
+        let flow = NonNull::new(flow).ok_or_else(|| flow_error(unsafe { ffi::rte_errno() }, &error))?;
+        let id = self.next_id;
+
+        self.next_id += 1;
+        self.flows.insert(id, (rule, flow));
+
+        Ok(id)
+    }
+
+    /// Query the counters of a flow rule with the `Count` action by its id, and reset them if `reset`.
+    pub fn query(&self, id: u32, reset: bool) -> Result<FlowCount> {
+        let flow = match self.flows.get(&id) {
+            Some((rule, _)) if !rule.is_counted() => return Err(RteError(::libc::ENOTSUP).into()),
+            Some(&(_, flow)) => flow,
+            None => return Err(RteError(::libc::ENOENT).into()),
+        };
+        let action = ffi::rte_flow_action {
+            type_: RTE_FLOW_ACTION_TYPE_COUNT,
+            conf: ptr::null(),
+        };
+        let mut count = ffi::rte_flow_query_count::default();
+        let mut error = ffi::rte_flow_error::default();
+
+        count.set_reset(reset as u32);
+
+        let ret = unsafe {
+            ffi::rte_flow_query(
+                self.port_id,
+                flow.as_ptr(),
+                &action,
+                &mut count as *mut _ as *mut c_void,
+                &mut error,
+            )
+        };
+
+        rte_check!(ret; ok => {
+            FlowCount {
+                hits: if count.hits_set() != 0 { Some(count.hits) } else { None },
+                bytes: if count.bytes_set() != 0 { Some(count.bytes) } else { None },
+            }
+        }; err => { flow_error(ret, &error) })
+    }
+
+    /// Destroy a flow rule by its id.
+    pub fn destroy(&mut self, id: u32) -> Result<FlowRule> {
+        let flow = match self.flows.get(&id) {
+            Some(&(_, flow)) => flow,
+            None => return Err(RteError(::libc::ENOENT).into()),
+        };
+        let mut error = ffi::rte_flow_error::default();
+
+        let ret = unsafe { ffi::rte_flow_destroy(self.port_id, flow.as_ptr(), &mut error) };
+
+        rte_check!(ret; err => { flow_error(ret, &error) })?;
+
+        self.tunnels.remove(&id);
+
+        Ok(self.flows.remove(&id).unwrap().0)
+    }
+
+    /// Destroy all the flow rules of the port, including the rules not created by the table.
+    pub fn flush(&mut self) -> Result<()> {
+        let mut error = ffi::rte_flow_error::default();
+
+        let ret = unsafe { ffi::rte_flow_flush(self.port_id, &mut error) };
+
+        rte_check!(ret; err => { flow_error(ret, &error) })?;
+
+        self.flows.clear();
+        self.tunnels.clear();
+
+        Ok(())
+    }
+
+    /// Save the installed rules in JSON, in order of creation, except the tunnel rules.
+    pub fn save<W: Write>(&self, w: W) -> Result<()> {
+        let rules = self
+            .flows
+            .iter()
+            .filter(|&(id, _)| !self.tunnels.contains(id))
+            .map(|(_, (rule, _))| rule)
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer_pretty(w, &rules)?;
+
+        Ok(())
+    }
+
+    /// Reinstall the saved rules in order, returns the ids of the installed rules.
+    ///
+    /// It stops at the first rule which fails to be installed, the rules installed before are kept.
+    /// The services of the current thread are run at a yield point after each rule.
+    pub fn restore<R: Read>(&mut self, r: R) -> Result<Vec<u32>> {
+        let rules: Vec<FlowRule> = serde_json::from_reader(r)?;
+
+        rules
+            .into_iter()
+            .map(|rule| {
+                let id = self.create(rule);
+
+                coop::yield_point();
+
+                id
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_mask() {
+        assert_eq!(prefix_mask(0, 32), 0);
+        assert_eq!(prefix_mask(8, 32), 0xff00_0000);
+        assert_eq!(prefix_mask(24, 32), 0xffff_ff00);
+        assert_eq!(prefix_mask(32, 32), 0xffff_ffff);
+        assert_eq!(prefix_mask(64, 128), 0xffff_ffff_ffff_ffff_0000_0000_0000_0000);
+
+        assert_eq!(
+            ipv4_prefix(Some(("10.1.2.3".parse().unwrap(), 16))),
+            (be32::new(0x0a01_0000).to_raw(), be32::new(0xffff_0000).to_raw())
+        );
+    }
+
+    #[test]
+    fn test_raw_rule() {
+        let rule = FlowRule {
+            attr: FlowAttr::ingress(),
+            pattern: vec![
+                FlowItem::eth(),
+                FlowItem::Ipv4 {
+                    src: None,
+                    dst: Some(("192.168.0.1".parse().unwrap(), 32)),
+                    proto: Some(17),
+                },
+                FlowItem::Udp {
+                    src_port: None,
+                    dst_port: Some(53),
+                },
+            ],
+            actions: vec![FlowAction::Mark(7), FlowAction::Queue(3)],
+        };
+
+        let raw = RawRule::from(&rule);
+
+        assert_eq!(raw.attr.ingress(), 1);
+        assert_eq!(raw.attr.egress(), 0);
+        assert_eq!(
+            raw.pattern.iter().map(|item| item.type_).collect::<Vec<_>>(),
+            vec![
+                RTE_FLOW_ITEM_TYPE_ETH,
+                RTE_FLOW_ITEM_TYPE_IPV4,
+                RTE_FLOW_ITEM_TYPE_UDP,
+                RTE_FLOW_ITEM_TYPE_END
+            ]
+        );
+
+        let udp = unsafe { &*(raw.pattern[2].spec as *const ffi::rte_flow_item_udp) };
+        let udp_mask = unsafe { &*(raw.pattern[2].mask as *const ffi::rte_flow_item_udp) };
+
+        assert_eq!({ udp.hdr.dst_port }, be16::new(53).to_raw());
+        assert_eq!({ udp_mask.hdr.dst_port }, 0xffff);
+        assert_eq!({ udp_mask.hdr.src_port }, 0);
+
+        assert_eq!(raw.actions.len(), 3);
+        assert_eq!(raw.actions[1].type_, RTE_FLOW_ACTION_TYPE_QUEUE);
+        assert_eq!(
+            unsafe { &*(raw.actions[1].conf as *const ffi::rte_flow_action_queue) }.index,
+            3
+        );
+        assert_eq!(raw.actions[2].type_, RTE_FLOW_ACTION_TYPE_END);
+
+        let json = serde_json::to_string(&rule).unwrap();
+
+        assert_eq!(serde_json::from_str::<FlowRule>(&json).unwrap(), rule);
+    }
+
+    #[test]
+    fn test_rule_builder() {
+        let rule = FlowRule::ingress()
+            .with_item(FlowItem::eth())
+            .with_item(FlowItem::ipv4())
+            .with_item(FlowItem::udp())
+            .with_item(FlowItem::vxlan(0x12_3456))
+            .with_action(FlowAction::Count)
+            .with_action(FlowAction::Rss {
+                types: 0,
+                queues: vec![0, 1, 2, 3],
+            });
+
+        assert!(rule.is_counted());
+
+        let raw = RawRule::from(&rule);
+
+        assert_eq!(raw.pattern[3].type_, RTE_FLOW_ITEM_TYPE_VXLAN);
+
+        let vxlan = unsafe { &*(raw.pattern[3].spec as *const ffi::rte_flow_item_vxlan) };
+        let vxlan_mask = unsafe { &*(raw.pattern[3].mask as *const ffi::rte_flow_item_vxlan) };
+
+        assert_eq!(vxlan.vni, [0x12, 0x34, 0x56]);
+        assert_eq!(vxlan_mask.vni, [0xff; 3]);
+        assert_eq!(vxlan_mask.flags, 0);
+
+        assert_eq!(
+            raw.actions.iter().map(|action| action.type_).collect::<Vec<_>>(),
+            vec![
+                RTE_FLOW_ACTION_TYPE_COUNT,
+                RTE_FLOW_ACTION_TYPE_RSS,
+                RTE_FLOW_ACTION_TYPE_END
+            ]
+        );
+
+        let rss = unsafe { &*(raw.actions[1].conf as *const ffi::rte_flow_action_rss) };
+
+        assert_eq!(
+            unsafe { slice::from_raw_parts(rss.queue, rss.queue_num as usize) },
+            &[0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_flow_tunnel() {
+        let mut tunnel = FlowTunnel::new(
+            TunnelType::Vxlan,
+            100,
+            "10.0.0.1".parse().unwrap(),
+            "10.0.0.2".parse().unwrap(),
+        );
+
+        tunnel.dst_port = 4789;
+
+        let raw = tunnel.to_raw();
+
+        assert_eq!(raw.type_, RTE_FLOW_ITEM_TYPE_VXLAN);
+        assert_eq!(raw.is_ipv6, 0);
+        assert_eq!(&raw.dst_addr[..4], &[10, 0, 0, 2]);
+        assert_eq!(raw.tp_dst, be16::new(4789).to_raw());
+        assert_eq!(FlowTunnel::from_raw(&raw), Some(tunnel));
+
+        tunnel.dst = "::1".parse().unwrap();
+
+        let raw = tunnel.to_raw();
+
+        assert_eq!(raw.is_ipv6, 1);
+        assert_eq!(
+            FlowTunnel::from_raw(&raw).map(|t| t.src),
+            Some("::ffff:10.0.0.1".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,581 @@
+//!
+//! Socket-like UDP endpoints for the lightweight control protocols.
+//!
+//! The control protocols like BFD or a simple DNS responder only need to receive and send a few datagrams,
+//! a `Stack` implements them in-process without a full TCP/IP stack. The stack owns the addresses of a port
+//! and a `NextHopResolver`, the sockets are bound to the local addresses, and receive the UDP datagrams
+//! which are fed to the stack with `input`. The datagrams sent with `send_to` are resolved by ARP or NDP,
+//! and queued until `flush`.
+//!
+//! Only UDP is supported, and the VLAN tagged packets, the IP fragments and the segmented packets
+//! are returned to the caller. The ARP requests for the port address are answered, but the Neighbor
+//! Solicitations are only learned, so the IPv6 peers should know the MAC of the port.
+//!
+//! ```no_run
+//! use std::net::Ipv4Addr;
+//!
+//! use rte::flowsock::Stack;
+//! use rte::route::{Interface, Route, RouteTable};
+//! use rte::*;
+//!
+//! let port_id: ethdev::PortId = 0;
+//! let pool = mbuf::pool_create("pool", 1024, 32, 0, 2048, Socket::ANY).unwrap();
+//!
+//! let mut routes = RouteTable::new().unwrap();
+//!
+//! routes.add(Ipv4Addr::new(192, 0, 2, 0).into(), 24, Route { port: port_id, gateway: None }).unwrap();
+//!
+//! let iface = Interface {
+//!     mac: port_id.mac_addr(),
+//!     ipv4: Some(Ipv4Addr::new(192, 0, 2, 1)),
+//!     ipv6: None,
+//! };
+//! let mut stack = Stack::new(port_id, 0, pool.to_ref(), iface, routes);
+//! let sock = stack.bind("0.0.0.0:53".parse().unwrap()).unwrap();
+//!
+//! let mut pkts: Vec<Option<mbuf::MBuf>> = (0..32).map(|_| None).collect();
+//!
+//! loop {
+//!     let n = port_id.rx_burst(0, &mut pkts);
+//!
+//!     for m in pkts[..n].iter_mut().filter_map(Option::take) {
+//!         if let Some(m) = stack.input(m) {
+//!             // not for the stack
+//!         }
+//!     }
+//!
+//!     while let Some(req) = stack.recv_from(sock) {
+//!         let resp = req.payload().to_vec(); // answer the query
+//!
+//!         stack.send_to(sock, &resp, req.src).unwrap();
+//!     }
+//!
+//!     stack.expire();
+//!     stack.flush();
+//! }
+//! ```
+//!
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::slice;
+
+use libc;
+
+use errors::{Result, RteError};
+use ethdev::{EthDevice, PortId, QueueId};
+use ether::{ETHER_TYPE_IPv4, ETHER_TYPE_IPv6, ETHER_HDR_LEN, ETHER_TYPE_ARP};
+use filter::dissect_mbuf;
+use ip;
+use mbuf::{MBuf, MBufPool, OffloadFlags};
+use mempool;
+use route::{Action, Interface, NextHopResolver, ResolverConf, RouteTable};
+
+/// The maximum number of datagrams queued for a socket, the later ones are dropped.
+pub const MAX_RX_QUEUE: usize = 256;
+
+/// The first port allocated to the sockets bound to the port 0.
+pub const EPHEMERAL_PORT_START: u16 = 49152;
+
+/// The default hop limit of the sent datagrams.
+pub const DEFAULT_TTL: u8 = 64;
+
+const UDP_HDR_LEN: usize = 8;
+const IPV6_HDR_LEN: usize = 40;
+
+/// The identifier of a bound socket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SocketId(usize);
+
+/// A received UDP datagram.
+pub struct Datagram {
+    /// The source address.
+    pub src: SocketAddr,
+    /// The destination address.
+    pub dst: SocketAddr,
+    mbuf: MBuf,
+    off: usize,
+    len: usize,
+}
+
+impl fmt::Debug for Datagram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Datagram")
+            .field("src", &self.src)
+            .field("dst", &self.dst)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Datagram {
+    /// The payload of the datagram.
+    pub fn payload(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.mbuf.mtod_offset::<u8>(self.off).as_ptr(), self.len) }
+    }
+
+    /// The mbuf of the datagram, which starts from the Ethernet header.
+    pub fn into_mbuf(self) -> MBuf {
+        self.mbuf
+    }
+}
+
+/// The statistics of a socket.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SocketStats {
+    /// The number of the received datagrams.
+    pub rx_datagrams: u64,
+    /// The number of the datagrams dropped because the queue is full.
+    pub rx_dropped: u64,
+    /// The number of the sent datagrams, including the ones waiting for the next-hop.
+    pub tx_datagrams: u64,
+    /// The number of the datagrams dropped because the next-hop is unreachable.
+    pub tx_dropped: u64,
+}
+
+/// The statistics of a stack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StackStats {
+    /// The number of the UDP datagrams with a bad length or checksum.
+    pub rx_bad: u64,
+    /// The number of the UDP datagrams to a port without socket.
+    pub rx_no_socket: u64,
+}
+
+struct Socket {
+    local: SocketAddr,
+    ttl: u8,
+    rx: VecDeque<Datagram>,
+    stats: SocketStats,
+}
+
+/// The UDP sockets over a port.
+pub struct Stack<'a> {
+    port_id: PortId,
+    queue_id: QueueId,
+    pool: mempool::MemPoolRef<'a>,
+    iface: Interface,
+    resolver: NextHopResolver,
+    sockets: BTreeMap<usize, Socket>,
+    bindings: HashMap<SocketAddr, usize>,
+    next_id: usize,
+    next_port: u16,
+    ip_id: u16,
+    tx: Vec<MBuf>,
+    stats: StackStats,
+}
+
+impl<'a> Stack<'a> {
+    /// Create a stack with the addresses of the port, the datagrams are sent on the TX queue of the port,
+    /// and the mbufs of them are allocated from the mempool.
+    ///
+    /// The routes of the routing table should be on the port.
+    pub fn new(
+        port_id: PortId,
+        queue_id: QueueId,
+        pool: mempool::MemPoolRef<'a>,
+        iface: Interface,
+        routes: RouteTable,
+    ) -> Self {
+        let mut resolver = NextHopResolver::new(routes, &ResolverConf::default());
+
+        resolver.add_interface(port_id, iface);
+
+        Stack {
+            port_id,
+            queue_id,
+            pool,
+            iface,
+            resolver,
+            sockets: BTreeMap::new(),
+            bindings: HashMap::new(),
+            next_id: 0,
+            next_port: EPHEMERAL_PORT_START,
+            ip_id: 0,
+            tx: Vec::new(),
+            stats: StackStats::default(),
+        }
+    }
+
+    /// The next-hop resolver, e.g. to add the routes or the static neighbors.
+    pub fn resolver(&mut self) -> &mut NextHopResolver {
+        &mut self.resolver
+    }
+
+    /// The statistics of the stack.
+    pub fn stats(&self) -> &StackStats {
+        &self.stats
+    }
+
+    fn is_local(&self, addr: IpAddr) -> bool {
+        match addr {
+            IpAddr::V4(addr) => self.iface.ipv4 == Some(addr),
+            IpAddr::V6(addr) => self.iface.ipv6 == Some(addr),
+        }
+    }
+
+    /// Bind a socket to the local address, the unspecified address matches any address of the family.
+    ///
+    /// A port is allocated for the port 0.
+    pub fn bind(&mut self, mut addr: SocketAddr) -> Result<SocketId> {
+        if !addr.ip().is_unspecified() && !self.is_local(addr.ip()) {
+            return Err(RteError(libc::EADDRNOTAVAIL).into());
+        }
+
+        if addr.port() == 0 {
+            let range = u32::from(u16::MAX - EPHEMERAL_PORT_START) + 1;
+            let next = u32::from(self.next_port - EPHEMERAL_PORT_START);
+            let port = (0..range)
+                .map(|off| EPHEMERAL_PORT_START + ((next + off) % range) as u16)
+                .find(|&port| !self.bindings.contains_key(&SocketAddr::new(addr.ip(), port)))
+                .ok_or(RteError(libc::EADDRINUSE))?;
+
+            self.next_port = port.checked_add(1).unwrap_or(EPHEMERAL_PORT_START);
+
+            addr.set_port(port);
+        }
+
+        if self.bindings.contains_key(&addr) {
+            return Err(RteError(libc::EADDRINUSE).into());
+        }
+
+        let id = self.next_id;
+
+        self.next_id += 1;
+        self.bindings.insert(addr, id);
+        self.sockets.insert(
+            id,
+            Socket {
+                local: addr,
+                ttl: DEFAULT_TTL,
+                rx: VecDeque::new(),
+                stats: SocketStats::default(),
+            },
+        );
+
+        Ok(SocketId(id))
+    }
+
+    /// Close a socket, the queued datagrams are freed.
+    pub fn close(&mut self, id: SocketId) -> bool {
+        self.sockets
+            .remove(&id.0)
+            .map(|sock| self.bindings.remove(&sock.local))
+            .is_some()
+    }
+
+    /// The local address of a socket.
+    pub fn local_addr(&self, id: SocketId) -> Option<SocketAddr> {
+        self.sockets.get(&id.0).map(|sock| sock.local)
+    }
+
+    /// The statistics of a socket.
+    pub fn socket_stats(&self, id: SocketId) -> Option<SocketStats> {
+        self.sockets.get(&id.0).map(|sock| sock.stats)
+    }
+
+    /// Set the TTL or hop limit of the datagrams sent by a socket, e.g. 255 for the single-hop BFD.
+    pub fn set_ttl(&mut self, id: SocketId, ttl: u8) -> Result<()> {
+        self.sockets
+            .get_mut(&id.0)
+            .map(|sock| sock.ttl = ttl)
+            .ok_or_else(|| RteError(libc::EBADF).into())
+    }
+
+    /// Feed a packet received on the port to the stack.
+    ///
+    /// The ARP packets and the UDP datagrams to the bound sockets are consumed,
+    /// the NDP packets are learned by the resolver, and returned with the other packets.
+    pub fn input(&mut self, m: MBuf) -> Option<MBuf> {
+        let d = match dissect_mbuf(&m) {
+            Some(d) => d,
+            None => return Some(m),
+        };
+
+        if d.nb_vlans > 0 {
+            return Some(m);
+        }
+
+        if d.ether_type == ETHER_TYPE_ARP as u16 {
+            let out = self.resolver.input(self.pool, self.port_id, &m);
+
+            self.queue(out);
+
+            return None;
+        }
+
+        match d.proto.map(i32::from) {
+            Some(libc::IPPROTO_ICMPV6) => {
+                let out = self.resolver.input(self.pool, self.port_id, &m);
+
+                self.queue(out);
+
+                Some(m)
+            }
+            Some(libc::IPPROTO_UDP) if d.frag.is_none() && m.data_len() == m.pkt_len() => {
+                match (d.src_ip, d.dst_ip, d.src_port, d.dst_port, d.l4_off) {
+                    (Some(src_ip), Some(dst_ip), Some(src_port), Some(dst_port), Some(l4_off))
+                        if self.is_local(dst_ip) =>
+                    {
+                        self.deliver(
+                            m,
+                            SocketAddr::new(src_ip, src_port),
+                            SocketAddr::new(dst_ip, dst_port),
+                            l4_off,
+                        )
+                    }
+                    _ => Some(m),
+                }
+            }
+            _ => Some(m),
+        }
+    }
+
+    fn deliver(&mut self, m: MBuf, src: SocketAddr, dst: SocketAddr, l4_off: usize) -> Option<MBuf> {
+        let data = unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), m.data_len()) };
+        let udp = &data[l4_off..];
+        let len = if udp.len() < UDP_HDR_LEN {
+            0
+        } else {
+            u16::from_be_bytes([udp[4], udp[5]]) as usize
+        };
+
+        if len < UDP_HDR_LEN || len > udp.len() || !verify_cksum(&m, src.ip(), dst.ip(), &udp[..len]) {
+            self.stats.rx_bad += 1;
+
+            return None;
+        }
+
+        let unspecified = match dst.ip() {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        let id = self
+            .bindings
+            .get(&dst)
+            .or_else(|| self.bindings.get(&SocketAddr::new(unspecified, dst.port())))
+            .cloned();
+        let sockets = &mut self.sockets;
+        let sock = id.and_then(|id| sockets.get_mut(&id));
+
+        match sock {
+            Some(sock) => {
+                if sock.rx.len() < MAX_RX_QUEUE {
+                    sock.stats.rx_datagrams += 1;
+                    sock.rx.push_back(Datagram {
+                        src,
+                        dst,
+                        mbuf: m,
+                        off: l4_off + UDP_HDR_LEN,
+                        len: len - UDP_HDR_LEN,
+                    });
+                } else {
+                    sock.stats.rx_dropped += 1;
+                }
+
+                None
+            }
+            None => {
+                self.stats.rx_no_socket += 1;
+
+                Some(m)
+            }
+        }
+    }
+
+    /// Receive a datagram of the socket.
+    pub fn recv_from(&mut self, id: SocketId) -> Option<Datagram> {
+        self.sockets.get_mut(&id.0).and_then(|sock| sock.rx.pop_front())
+    }
+
+    /// Send a datagram to the remote address, which is queued until `flush`.
+    ///
+    /// The datagram is queued by the resolver if the next-hop is not resolved yet.
+    pub fn send_to(&mut self, id: SocketId, payload: &[u8], dst: SocketAddr) -> Result<()> {
+        let (local, ttl) = self
+            .sockets
+            .get(&id.0)
+            .map(|sock| (sock.local, sock.ttl))
+            .ok_or(RteError(libc::EBADF))?;
+
+        let src_ip = match (local.ip(), dst.ip()) {
+            (IpAddr::V4(ip), IpAddr::V4(_)) if !ip.is_unspecified() => IpAddr::V4(ip),
+            (IpAddr::V6(ip), IpAddr::V6(_)) if !ip.is_unspecified() => IpAddr::V6(ip),
+            (IpAddr::V4(_), IpAddr::V4(_)) => self.iface.ipv4.map(IpAddr::V4).ok_or(RteError(libc::EADDRNOTAVAIL))?,
+            (IpAddr::V6(_), IpAddr::V6(_)) => self.iface.ipv6.map(IpAddr::V6).ok_or(RteError(libc::EADDRNOTAVAIL))?,
+            _ => return Err(RteError(libc::EAFNOSUPPORT).into()),
+        };
+
+        if UDP_HDR_LEN + payload.len() + IPV6_HDR_LEN > usize::from(u16::MAX) {
+            return Err(RteError(libc::EMSGSIZE).into());
+        }
+
+        self.ip_id = self.ip_id.wrapping_add(1);
+
+        let m = udp_packet(
+            self.pool,
+            &self.iface,
+            SocketAddr::new(src_ip, local.port()),
+            dst,
+            ttl,
+            self.ip_id,
+            payload,
+        )?;
+
+        let res = match self.resolver.resolve(self.pool, m, dst.ip()) {
+            Action::Forward(port_id, m) => {
+                self.queue(vec![(port_id, m)]);
+
+                Ok(())
+            }
+            Action::Queued(req) => {
+                self.queue(req.into_iter().collect());
+
+                Ok(())
+            }
+            Action::Drop(_) => Err(RteError(libc::EHOSTUNREACH).into()),
+        };
+
+        if let Some(sock) = self.sockets.get_mut(&id.0) {
+            if res.is_ok() {
+                sock.stats.tx_datagrams += 1;
+            } else {
+                sock.stats.tx_dropped += 1;
+            }
+        }
+
+        res
+    }
+
+    fn queue(&mut self, pkts: Vec<(PortId, MBuf)>) {
+        let port_id = self.port_id;
+
+        self.tx
+            .extend(pkts.into_iter().filter(|&(port, _)| port == port_id).map(|(_, m)| m));
+    }
+
+    /// Resend the requests for the unresolved next-hops, and drop the datagrams which are waiting too long.
+    ///
+    /// This should be called periodically.
+    pub fn expire(&mut self) {
+        let out = self.resolver.expire(self.pool);
+
+        self.queue(out);
+    }
+
+    /// Send the queued packets on the TX queue of the port, returns the number of the sent packets.
+    ///
+    /// The packets which are not sent are kept for the next flush.
+    pub fn flush(&mut self) -> usize {
+        if self.tx.is_empty() {
+            return 0;
+        }
+
+        let n = self.port_id.tx_burst(self.queue_id, &mut self.tx);
+
+        for m in self.tx.drain(..n) {
+            mem::forget(m);
+        }
+
+        n
+    }
+}
+
+/// Verify the UDP checksum, the RX offload flags are trusted if the driver verified it.
+fn verify_cksum(m: &MBuf, src: IpAddr, dst: IpAddr, udp: &[u8]) -> bool {
+    let flags = m.offload() & OffloadFlags::PKT_RX_L4_CKSUM_MASK;
+
+    if flags == OffloadFlags::PKT_RX_L4_CKSUM_GOOD {
+        return true;
+    }
+    if flags == OffloadFlags::PKT_RX_L4_CKSUM_BAD {
+        return false;
+    }
+
+    let proto = libc::IPPROTO_UDP as u8;
+
+    match (src, dst) {
+        // the zero checksum means no checksum for IPv4
+        (IpAddr::V4(_), IpAddr::V4(_)) if udp[6..8] == [0, 0] => true,
+        (IpAddr::V4(src), IpAddr::V4(dst)) => ip::ipv4_udptcp_cksum_verify(src, dst, proto, udp),
+        (IpAddr::V6(src), IpAddr::V6(dst)) => ip::ipv6_udptcp_cksum_verify(src, dst, proto, udp),
+        _ => false,
+    }
+}
+
+/// Build an Ethernet/IP/UDP packet, the destination MAC is set by the resolver.
+fn udp_packet(
+    mut pool: mempool::MemPoolRef,
+    iface: &Interface,
+    src: SocketAddr,
+    dst: SocketAddr,
+    ttl: u8,
+    ip_id: u16,
+    payload: &[u8],
+) -> Result<MBuf> {
+    let udp_len = UDP_HDR_LEN + payload.len();
+    let (ether_type, ip_len) = if src.is_ipv4() {
+        (ETHER_TYPE_IPv4, ip::IPV4_HDR_LEN)
+    } else {
+        (ETHER_TYPE_IPv6, IPV6_HDR_LEN)
+    };
+    let len = ETHER_HDR_LEN as usize + ip_len + udp_len;
+
+    let mut m = pool.alloc()?;
+
+    {
+        let p = m.append(len)?;
+        let buf = unsafe { slice::from_raw_parts_mut(p.as_ptr(), len) };
+        let (ether, buf) = buf.split_at_mut(ETHER_HDR_LEN as usize);
+        let (hdr, udp) = buf.split_at_mut(ip_len);
+
+        ether[..6].copy_from_slice(&[0; 6]);
+        ether[6..12].copy_from_slice(iface.mac.octets());
+        ether[12..14].copy_from_slice(&(ether_type as u16).to_be_bytes());
+
+        udp[0..2].copy_from_slice(&src.port().to_be_bytes());
+        udp[2..4].copy_from_slice(&dst.port().to_be_bytes());
+        udp[4..6].copy_from_slice(&(udp_len as u16).to_be_bytes());
+        udp[6..8].copy_from_slice(&[0, 0]);
+        udp[UDP_HDR_LEN..].copy_from_slice(payload);
+
+        let proto = libc::IPPROTO_UDP as u8;
+
+        let cksum = match (src.ip(), dst.ip()) {
+            (IpAddr::V4(src), IpAddr::V4(dst)) => {
+                hdr[0] = ip::IPVERSION << 4 | (ip::IPV4_HDR_LEN / ip::IPV4_IHL_MULTIPLIER) as u8;
+                hdr[1] = 0;
+                hdr[2..4].copy_from_slice(&((ip_len + udp_len) as u16).to_be_bytes());
+                hdr[4..6].copy_from_slice(&ip_id.to_be_bytes());
+                hdr[6..8].copy_from_slice(&ip::IPV4_HDR_DF_FLAG.to_be_bytes());
+                hdr[8] = ttl;
+                hdr[9] = proto;
+                hdr[10..12].copy_from_slice(&[0, 0]);
+                hdr[12..16].copy_from_slice(&src.octets());
+                hdr[16..20].copy_from_slice(&dst.octets());
+
+                let cksum = ip::ipv4_cksum(hdr);
+
+                hdr[10..12].copy_from_slice(&cksum.to_ne_bytes());
+
+                ip::ipv4_udptcp_cksum(src, dst, proto, udp)
+            }
+            (IpAddr::V6(src), IpAddr::V6(dst)) => {
+                hdr[0..4].copy_from_slice(&(6u32 << 28).to_be_bytes());
+                hdr[4..6].copy_from_slice(&(udp_len as u16).to_be_bytes());
+                hdr[6] = proto;
+                hdr[7] = ttl;
+                hdr[8..24].copy_from_slice(&src.octets());
+                hdr[24..40].copy_from_slice(&dst.octets());
+
+                ip::ipv6_udptcp_cksum(src, dst, proto, udp)
+            }
+            _ => return Err(RteError(libc::EAFNOSUPPORT).into()),
+        };
+
+        udp[6..8].copy_from_slice(&cksum.to_ne_bytes());
+    }
+
+    Ok(m)
+}
@@ -0,0 +1,424 @@
+//!
+//! Fragment-aware RX steering.
+//!
+//! The NIC calculates the RSS hash of a TCP or UDP packet over its 5-tuple, but only the first fragment
+//! of an IP datagram carries the L4 header, the other fragments are hashed over the addresses,
+//! so they land on another lcore than the first fragment, and the datagram can't be reassembled.
+//!
+//! `FragAware` steers the packets of a RX queue to the workers in software:
+//!
+//! - the unfragmented packets and the first fragments are hashed on the 5-tuple, as the NIC does,
+//! - the fragments are tracked by the source, destination, protocol and identification of their datagram,
+//!   the following fragments of a datagram reach the same worker as the fragment which was seen first,
+//! - the fragment of an untracked datagram without the L4 header is hashed on the 3-tuple
+//!   of the source, destination and protocol.
+//!
+//! If the first fragment arrives after the others, the whole datagram follows the 3-tuple hash,
+//! so it is still reassembled on one worker, which may differ from the worker of its flow.
+//! Unset `hash_l4` to hash all the packets on the 3-tuple, which keeps a whole flow on one worker
+//! at the cost of the balance.
+//!
+//! The stage is owned by the RX lcore and updated without any lock, the lcore should `expire` it periodically.
+//! The packets could be passed to the workers with `steer_burst`, or tagged for the distributor with `tag`.
+//!
+//! ```no_run
+//! use std::time::Instant;
+//!
+//! use rte::ethdev::EthDevice;
+//! use rte::fragaware::{FragAware, FragAwareConfig};
+//! use rte::*;
+//!
+//! let mut stage = FragAware::new(FragAwareConfig {
+//!     workers: 4,
+//!     ..Default::default()
+//! });
+//!
+//! let port_id = 0;
+//! let mut pkts: Vec<Option<mbuf::MBuf>> = (0..32).map(|_| None).collect();
+//!
+//! loop {
+//!     let n = port_id.rx_burst(0, &mut pkts);
+//!     let now = Instant::now();
+//!
+//!     let bursts = stage.steer_burst(pkts[..n].iter_mut().filter_map(Option::take), now);
+//!
+//!     for (worker, pkts) in bursts.into_iter().enumerate() {
+//!         // enqueue the packets to the ring of the worker
+//!     }
+//!
+//!     stage.expire(now);
+//! }
+//! ```
+//!
+use std::cmp;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use filter::{dissect_mbuf, Dissection};
+use hashers::{softrss, FiveTuple, DEFAULT_RSS_KEY};
+use mbuf::MBuf;
+
+/// The configuration of the fragment-aware steering.
+#[derive(Clone, Copy, Debug)]
+pub struct FragAwareConfig {
+    /// The number of workers.
+    pub workers: usize,
+    /// The RSS key of the Toeplitz hash.
+    pub rss_key: [u8; 40],
+    /// Hash the unfragmented packets and the first fragments on the 5-tuple, otherwise on the 3-tuple.
+    pub hash_l4: bool,
+    /// The maximum number of the tracked datagrams.
+    pub max_datagrams: usize,
+    /// The timeout of the tracked datagrams since their last fragment.
+    pub timeout: Duration,
+}
+
+impl Default for FragAwareConfig {
+    fn default() -> Self {
+        FragAwareConfig {
+            workers: 1,
+            rss_key: DEFAULT_RSS_KEY,
+            hash_l4: true,
+            max_datagrams: 4096,
+            timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The counters of the fragment-aware steering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FragAwareStats {
+    /// The number of steered packets.
+    pub packets: u64,
+    /// The number of fragments.
+    pub fragments: u64,
+    /// The number of fragments of the tracked datagrams.
+    pub tracked: u64,
+    /// The number of fragments without L4 header, whose datagram was not tracked.
+    pub untracked: u64,
+    /// The number of non-IP packets, which are steered to the first worker.
+    pub non_ip: u64,
+    /// The number of datagrams which were not tracked because the table is full.
+    pub overflows: u64,
+    /// The number of expired datagrams.
+    pub expired: u64,
+}
+
+/// The key of a fragmented datagram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct DatagramKey {
+    src_addr: IpAddr,
+    dst_addr: IpAddr,
+    proto: u8,
+    id: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Datagram {
+    hash: u32,
+    last_seen: Instant,
+}
+
+/// A RX stage which steers the fragments of a datagram to the same worker.
+#[derive(Clone, Debug)]
+pub struct FragAware {
+    conf: FragAwareConfig,
+    datagrams: HashMap<DatagramKey, Datagram>,
+    stats: FragAwareStats,
+}
+
+impl FragAware {
+    /// Create a stage without tracked datagram.
+    pub fn new(conf: FragAwareConfig) -> Self {
+        FragAware {
+            conf: FragAwareConfig {
+                workers: cmp::max(conf.workers, 1),
+                ..conf
+            },
+            datagrams: HashMap::new(),
+            stats: FragAwareStats::default(),
+        }
+    }
+
+    /// The configuration of the stage.
+    pub fn config(&self) -> &FragAwareConfig {
+        &self.conf
+    }
+
+    /// The counters of the stage.
+    pub fn stats(&self) -> &FragAwareStats {
+        &self.stats
+    }
+
+    /// The number of tracked datagrams.
+    pub fn len(&self) -> usize {
+        self.datagrams.len()
+    }
+
+    /// The stage has no tracked datagram.
+    pub fn is_empty(&self) -> bool {
+        self.datagrams.is_empty()
+    }
+
+    /// The worker of the steering hash.
+    pub fn worker(&self, hash: u32) -> usize {
+        hash as usize % self.conf.workers
+    }
+
+    /// The steering hash of a packet, which starts from the Ethernet header.
+    pub fn hash(&mut self, m: &MBuf, now: Instant) -> u32 {
+        match dissect_mbuf(m) {
+            Some(d) => self.hash_dissection(&d, now),
+            None => {
+                self.stats.packets += 1;
+                self.stats.non_ip += 1;
+
+                0
+            }
+        }
+    }
+
+    /// The steering hash of the dissected headers of a packet.
+    pub fn hash_dissection(&mut self, d: &Dissection, now: Instant) -> u32 {
+        self.stats.packets += 1;
+
+        let (src_addr, dst_addr, proto) = match (d.src_ip, d.dst_ip, d.proto) {
+            (Some(src_addr), Some(dst_addr), Some(proto)) => (src_addr, dst_addr, proto),
+            _ => {
+                self.stats.non_ip += 1;
+
+                return 0;
+            }
+        };
+
+        let frag = match d.frag {
+            Some(frag) => frag,
+            None => return self.flow_hash(d, src_addr, dst_addr, proto),
+        };
+
+        self.stats.fragments += 1;
+
+        let key = DatagramKey {
+            src_addr,
+            dst_addr,
+            proto,
+            id: frag.id,
+        };
+
+        if let Some(datagram) = self.datagrams.get_mut(&key) {
+            datagram.last_seen = now;
+
+            self.stats.tracked += 1;
+
+            return datagram.hash;
+        }
+
+        let hash = if frag.is_first() {
+            self.flow_hash(d, src_addr, dst_addr, proto)
+        } else {
+            self.stats.untracked += 1;
+
+            self.l3_hash(src_addr, dst_addr, proto)
+        };
+
+        if self.datagrams.len() < self.conf.max_datagrams {
+            self.datagrams.insert(key, Datagram { hash, last_seen: now });
+        } else {
+            self.stats.overflows += 1;
+        }
+
+        hash
+    }
+
+    fn flow_hash(&self, d: &Dissection, src_addr: IpAddr, dst_addr: IpAddr, proto: u8) -> u32 {
+        match (self.conf.hash_l4, d.src_port, d.dst_port) {
+            (true, Some(src_port), Some(dst_port)) => FiveTuple {
+                src_addr,
+                dst_addr,
+                src_port,
+                dst_port,
+                proto,
+            }
+            .softrss(&self.conf.rss_key),
+            _ => self.l3_hash(src_addr, dst_addr, proto),
+        }
+    }
+
+    fn l3_hash(&self, src_addr: IpAddr, dst_addr: IpAddr, proto: u8) -> u32 {
+        let mut input = FiveTuple {
+            src_addr,
+            dst_addr,
+            src_port: 0,
+            dst_port: 0,
+            proto,
+        }
+        .to_rss_input(false);
+
+        input.push(u32::from(proto));
+
+        softrss(&input, &self.conf.rss_key)
+    }
+
+    /// Steer a packet, returns its worker.
+    pub fn steer(&mut self, m: &MBuf, now: Instant) -> usize {
+        let hash = self.hash(m, now);
+
+        self.worker(hash)
+    }
+
+    /// Tag a packet with its steering hash for the distributor, returns its worker.
+    pub fn tag(&mut self, m: &mut MBuf, now: Instant) -> usize {
+        let hash = self.hash(m, now);
+
+        m.set_usr(hash);
+
+        self.worker(hash)
+    }
+
+    /// Steer a burst of packets, returns the packets of each worker in the received order.
+    pub fn steer_burst<I>(&mut self, pkts: I, now: Instant) -> Vec<Vec<MBuf>>
+    where
+        I: IntoIterator<Item = MBuf>,
+    {
+        let mut bursts = (0..self.conf.workers).map(|_| vec![]).collect::<Vec<_>>();
+
+        for m in pkts {
+            let worker = self.steer(&m, now);
+
+            bursts[worker].push(m);
+        }
+
+        bursts
+    }
+
+    /// Remove the datagrams which were idle for the timeout, returns the number of expired datagrams.
+    pub fn expire(&mut self, now: Instant) -> usize {
+        let timeout = self.conf.timeout;
+        let n = self.datagrams.len();
+
+        self.datagrams
+            .retain(|_, datagram| now.saturating_duration_since(datagram.last_seen) < timeout);
+
+        let expired = n - self.datagrams.len();
+
+        self.stats.expired += expired as u64;
+
+        expired
+    }
+
+    /// Remove all the tracked datagrams.
+    pub fn clear(&mut self) {
+        self.datagrams.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libc;
+
+    use super::*;
+    use ether::ETHER_TYPE_IPv4;
+    use filter::dissect;
+
+    fn udp_fragment(src: [u8; 4], sport: u16, id: u16, offset: u16, more: bool) -> Vec<u8> {
+        let mut pkt = vec![0x02, 0, 0, 0, 0, 2, 0x02, 0, 0, 0, 0, 1];
+        let frag = (offset / 8) | if more { 0x2000 } else { 0 };
+
+        pkt.extend_from_slice(&(ETHER_TYPE_IPv4 as u16).to_be_bytes());
+        pkt.extend_from_slice(&[0x45, 0, 0, 28]);
+        pkt.extend_from_slice(&id.to_be_bytes());
+        pkt.extend_from_slice(&frag.to_be_bytes());
+        pkt.extend_from_slice(&[64, libc::IPPROTO_UDP as u8, 0, 0]);
+        pkt.extend_from_slice(&src);
+        pkt.extend_from_slice(&[192, 168, 1, 1]);
+        pkt.extend_from_slice(&sport.to_be_bytes());
+        pkt.extend_from_slice(&[0, 53, 0, 8, 0, 0]);
+        pkt
+    }
+
+    fn hash(stage: &mut FragAware, pkt: &[u8], now: Instant) -> u32 {
+        stage.hash_dissection(&dissect(pkt, pkt.len()), now)
+    }
+
+    #[test]
+    fn test_fragments() {
+        let now = Instant::now();
+        let mut stage = FragAware::new(FragAwareConfig {
+            workers: 4,
+            ..Default::default()
+        });
+
+        let d = dissect(&udp_fragment([10, 0, 0, 1], 1234, 7, 8, true), 0);
+
+        assert_eq!(d.src_port, None);
+        assert_eq!(d.frag.map(|frag| (frag.id, frag.offset, frag.more)), Some((7, 8, true)));
+
+        // the first fragment follows the flow
+        let flow = hash(&mut stage, &udp_fragment([10, 0, 0, 1], 1234, 0, 0, false), now);
+
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 1], 1234, 1, 0, true), now),
+            flow
+        );
+        assert_eq!(hash(&mut stage, &udp_fragment([10, 0, 0, 1], 0, 1, 8, true), now), flow);
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 1], 0, 1, 16, false), now),
+            flow
+        );
+
+        // the first fragment arrives late
+        let l3 = hash(&mut stage, &udp_fragment([10, 0, 0, 2], 0, 2, 16, false), now);
+
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 2], 1234, 2, 0, true), now),
+            l3
+        );
+
+        assert_eq!(stage.len(), 2);
+        assert_eq!(
+            *stage.stats(),
+            FragAwareStats {
+                packets: 6,
+                fragments: 5,
+                tracked: 3,
+                untracked: 1,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(stage.expire(now), 0);
+        assert_eq!(stage.expire(now + Duration::from_secs(1)), 2);
+        assert!(stage.is_empty());
+    }
+
+    #[test]
+    fn test_l3_only() {
+        let now = Instant::now();
+        let mut stage = FragAware::new(FragAwareConfig {
+            workers: 4,
+            hash_l4: false,
+            max_datagrams: 1,
+            ..Default::default()
+        });
+
+        let flow = hash(&mut stage, &udp_fragment([10, 0, 0, 1], 1234, 0, 0, false), now);
+
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 1], 4321, 0, 0, false), now),
+            flow
+        );
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 1], 1234, 1, 0, true), now),
+            flow
+        );
+        assert_eq!(
+            hash(&mut stage, &udp_fragment([10, 0, 0, 1], 0, 2, 8, false), now),
+            flow
+        );
+
+        assert_eq!(stage.len(), 1);
+        assert_eq!(stage.stats().overflows, 1);
+        assert!(stage.worker(flow) < 4);
+    }
+}
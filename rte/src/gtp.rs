@@ -0,0 +1,319 @@
+//!
+//! GTP-U (GPRS Tunnelling Protocol User Plane) header parsing.
+//!
+//! Only the GTPv1-U header is supported, the optional sequence number,
+//! N-PDU number and extension headers are parsed when present.
+//!
+use std::mem;
+
+use mbuf;
+
+/// The UDP port of GTP-U.
+pub const GTPU_UDP_PORT: u16 = 2152;
+
+/// The GTP version 1.
+pub const GTP_VERSION_1: u8 = 1;
+
+/// The echo request message.
+pub const GTP_MSG_ECHO_REQUEST: u8 = 1;
+/// The echo response message.
+pub const GTP_MSG_ECHO_RESPONSE: u8 = 2;
+/// The error indication message.
+pub const GTP_MSG_ERROR_INDICATION: u8 = 26;
+/// The supported extension headers notification message.
+pub const GTP_MSG_SUPPORTED_EXT_HDR_NOTIFY: u8 = 31;
+/// The end marker message.
+pub const GTP_MSG_END_MARKER: u8 = 254;
+/// The G-PDU message which carries the user packet (T-PDU).
+pub const GTP_MSG_GPDU: u8 = 255;
+
+/// No more extension headers.
+pub const GTP_EXT_NONE: u8 = 0;
+/// The PDU session container extension header, used by 5G.
+pub const GTP_EXT_PDU_SESSION_CONTAINER: u8 = 0x85;
+
+bitflags! {
+    /// The flags of GTP header.
+    pub struct GtpFlags: u8 {
+        /// The N-PDU number is present.
+        const GTP_PN_FLAG = 0x01;
+        /// The sequence number is present.
+        const GTP_S_FLAG = 0x02;
+        /// The next extension header is present.
+        const GTP_E_FLAG = 0x04;
+        /// The protocol type, 1 for GTP and 0 for GTP'.
+        const GTP_PT_FLAG = 0x10;
+        /// The optional fields are present.
+        const GTP_OPT_FLAGS = Self::GTP_PN_FLAG.bits | Self::GTP_S_FLAG.bits | Self::GTP_E_FLAG.bits;
+    }
+}
+
+/// GTP-U Header
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct GtpHdr {
+    /// The version, protocol type and flags.
+    pub gtp_hdr_info: u8,
+    /// The message type.
+    pub msg_type: u8,
+    /// The length of the payload after the mandatory header, in network byte order.
+    pub plen: u16,
+    /// The tunnel endpoint identifier, in network byte order.
+    pub teid: u32,
+}
+
+unsafe impl mbuf::Header for GtpHdr {}
+
+/// The length of the mandatory GTP header.
+pub const GTP_HDR_LEN: usize = 8;
+
+/// The length of the optional fields, the sequence number, N-PDU number and the next extension header type.
+pub const GTP_OPT_LEN: usize = 4;
+
+impl GtpHdr {
+    /// Create a GTPv1-U header of G-PDU message without optional fields.
+    pub fn gpdu(teid: u32, payload_len: u16) -> Self {
+        GtpHdr {
+            gtp_hdr_info: (GTP_VERSION_1 << 5) | GtpFlags::GTP_PT_FLAG.bits,
+            msg_type: GTP_MSG_GPDU,
+            plen: payload_len.to_be(),
+            teid: teid.to_be(),
+        }
+    }
+
+    /// The GTP version.
+    pub fn version(&self) -> u8 {
+        self.gtp_hdr_info >> 5
+    }
+
+    /// The flags of GTP header.
+    pub fn flags(&self) -> GtpFlags {
+        GtpFlags::from_bits_truncate(self.gtp_hdr_info)
+    }
+
+    /// The tunnel endpoint identifier in host byte order.
+    pub fn teid(&self) -> u32 {
+        u32::from_be(self.teid)
+    }
+
+    /// Set the tunnel endpoint identifier.
+    pub fn set_teid(&mut self, teid: u32) {
+        self.teid = teid.to_be()
+    }
+
+    /// The length of the payload after the mandatory header in host byte order.
+    pub fn payload_len(&self) -> u16 {
+        u16::from_be(self.plen)
+    }
+}
+
+/// A parsed GTP-U packet.
+#[derive(Clone, Copy, Debug)]
+pub struct GtpPacket<'a> {
+    buf: &'a [u8],
+    hdr_len: usize,
+}
+
+impl<'a> GtpPacket<'a> {
+    /// Parse a GTPv1-U packet, which starts from the GTP header.
+    ///
+    /// Returns `None` if the packet is truncated or not a GTPv1-U packet.
+    pub fn parse(buf: &'a [u8]) -> Option<Self> {
+        if buf.len() < GTP_HDR_LEN {
+            return None;
+        }
+
+        let hdr = GtpHdr {
+            gtp_hdr_info: buf[0],
+            msg_type: buf[1],
+            plen: u16::from_ne_bytes([buf[2], buf[3]]),
+            teid: 0,
+        };
+
+        if hdr.version() != GTP_VERSION_1 || !hdr.flags().contains(GtpFlags::GTP_PT_FLAG) {
+            return None;
+        }
+
+        let len = GTP_HDR_LEN + hdr.payload_len() as usize;
+
+        if buf.len() < len {
+            return None;
+        }
+
+        let buf = &buf[..len];
+        let mut hdr_len = GTP_HDR_LEN;
+
+        if hdr.flags().intersects(GtpFlags::GTP_OPT_FLAGS) {
+            hdr_len += GTP_OPT_LEN;
+
+            if buf.len() < hdr_len {
+                return None;
+            }
+
+            if hdr.flags().contains(GtpFlags::GTP_E_FLAG) {
+                let mut next = buf[hdr_len - 1];
+
+                while next != GTP_EXT_NONE {
+                    // the extension header length is in 4 octets unit
+                    let ext_len = *buf.get(hdr_len)? as usize * 4;
+
+                    if ext_len == 0 || buf.len() < hdr_len + ext_len {
+                        return None;
+                    }
+
+                    next = buf[hdr_len + ext_len - 1];
+                    hdr_len += ext_len;
+                }
+            }
+        }
+
+        Some(GtpPacket { buf, hdr_len })
+    }
+
+    /// The mandatory GTP header.
+    pub fn header(&self) -> GtpHdr {
+        let mut hdr = GtpHdr::default();
+
+        unsafe {
+            (&mut hdr as *mut GtpHdr as *mut u8).copy_from_nonoverlapping(self.buf.as_ptr(), mem::size_of::<GtpHdr>());
+        }
+
+        hdr
+    }
+
+    /// The message type.
+    pub fn msg_type(&self) -> u8 {
+        self.buf[1]
+    }
+
+    /// The tunnel endpoint identifier.
+    pub fn teid(&self) -> u32 {
+        u32::from_be_bytes([self.buf[4], self.buf[5], self.buf[6], self.buf[7]])
+    }
+
+    fn flags(&self) -> GtpFlags {
+        GtpFlags::from_bits_truncate(self.buf[0])
+    }
+
+    /// The sequence number, if present.
+    pub fn seq(&self) -> Option<u16> {
+        if self.flags().contains(GtpFlags::GTP_S_FLAG) {
+            Some(u16::from_be_bytes([self.buf[8], self.buf[9]]))
+        } else {
+            None
+        }
+    }
+
+    /// The N-PDU number, if present.
+    pub fn npdu(&self) -> Option<u8> {
+        if self.flags().contains(GtpFlags::GTP_PN_FLAG) {
+            Some(self.buf[10])
+        } else {
+            None
+        }
+    }
+
+    /// The extension headers, with their types and contents.
+    pub fn extensions(&self) -> Extensions<'a> {
+        let (next, off) = if self.flags().contains(GtpFlags::GTP_E_FLAG) {
+            (self.buf[GTP_HDR_LEN + GTP_OPT_LEN - 1], GTP_HDR_LEN + GTP_OPT_LEN)
+        } else {
+            (GTP_EXT_NONE, self.hdr_len)
+        };
+
+        Extensions {
+            buf: &self.buf[..self.hdr_len],
+            next,
+            off,
+        }
+    }
+
+    /// The QoS flow identifier in the PDU session container extension header, used by 5G.
+    pub fn qfi(&self) -> Option<u8> {
+        self.extensions()
+            .find(|&(ty, content)| ty == GTP_EXT_PDU_SESSION_CONTAINER && content.len() >= 2)
+            .map(|(_, content)| content[1] & 0x3f)
+    }
+
+    /// The length of the header with the optional fields and extension headers.
+    pub fn header_len(&self) -> usize {
+        self.hdr_len
+    }
+
+    /// The payload, e.g. the user packet (T-PDU) of G-PDU message.
+    pub fn payload(&self) -> &'a [u8] {
+        &self.buf[self.hdr_len..]
+    }
+}
+
+/// An iterator over the extension headers of a GTP packet.
+pub struct Extensions<'a> {
+    buf: &'a [u8],
+    next: u8,
+    off: usize,
+}
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == GTP_EXT_NONE || self.off >= self.buf.len() {
+            return None;
+        }
+
+        let ty = self.next;
+        let len = self.buf[self.off] as usize * 4;
+        let content = &self.buf[self.off + 1..self.off + len - 1];
+
+        self.next = self.buf[self.off + len - 1];
+        self.off += len;
+
+        Some((ty, content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gtp() {
+        let payload = [0x45u8, 0, 0, 20];
+        let hdr = GtpHdr::gpdu(0x1234_5678, payload.len() as u16);
+        let mut buf = vec![hdr.gtp_hdr_info, hdr.msg_type, 0, 4, 0x12, 0x34, 0x56, 0x78];
+
+        buf.extend_from_slice(&payload);
+
+        let pkt = GtpPacket::parse(&buf).unwrap();
+
+        assert_eq!(pkt.header(), hdr);
+        assert_eq!(pkt.header().version(), GTP_VERSION_1);
+        assert_eq!(pkt.msg_type(), GTP_MSG_GPDU);
+        assert_eq!(pkt.teid(), 0x1234_5678);
+        assert_eq!(pkt.seq(), None);
+        assert_eq!(pkt.extensions().count(), 0);
+        assert_eq!(pkt.payload(), &payload);
+
+        // with sequence number and PDU session container
+        let buf = [
+            0x36, 0xff, 0, 12, 0, 0, 0, 1, 0x12, 0x34, 0, 0x85, 1, 0x10, 0x09, 0, 0x45, 0, 0, 20,
+        ];
+        let pkt = GtpPacket::parse(&buf).unwrap();
+
+        assert_eq!(pkt.teid(), 1);
+        assert_eq!(pkt.seq(), Some(0x1234));
+        assert_eq!(pkt.npdu(), None);
+        assert_eq!(
+            pkt.extensions().collect::<Vec<_>>(),
+            vec![(GTP_EXT_PDU_SESSION_CONTAINER, &[0x10, 0x09][..])]
+        );
+        assert_eq!(pkt.qfi(), Some(9));
+        assert_eq!(pkt.header_len(), 16);
+        assert_eq!(pkt.payload(), &payload);
+
+        // truncated
+        assert!(GtpPacket::parse(&buf[..15]).is_none());
+        // GTP'
+        assert!(GtpPacket::parse(&[0x20, 0xff, 0, 0, 0, 0, 0, 0]).is_none());
+    }
+}
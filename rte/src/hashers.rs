@@ -0,0 +1,250 @@
+//!
+//! RTE hash functions
+//!
+//! The CRC32 hash uses the SSE4.2 or ARMv8 CRC32 instructions when available,
+//! and falls back to a software implementation otherwise.
+//!
+//! The software Toeplitz hash produces the same value as the RSS hash
+//! calculated by the NIC, with the same key and input tuple.
+//!
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ffi;
+
+bitflags! {
+    /// The CRC32 hash algorithms.
+    pub struct CrcAlg: u8 {
+        /// Don't use SSE4.2 intrinsics
+        const SW        = ffi::CRC32_SW as u8;
+        /// Use SSE4.2 intrinsics if available
+        const SSE42     = ffi::CRC32_SSE42 as u8;
+        /// Use 64-bit SSE4.2 intrinsic if available (default)
+        const X64       = ffi::CRC32_x64 as u8;
+        /// Use 64-bit SSE4.2 intrinsic if available (default)
+        const SSE42_X64 = ffi::CRC32_SSE42_x64 as u8;
+        /// Use ARMv8 CRC32 instruction if available
+        const ARM64     = ffi::CRC32_ARM64 as u8;
+    }
+}
+
+/// Allow or disallow use of hardware instructions for CRC32 hash calculation.
+pub fn set_crc_alg(alg: CrcAlg) {
+    unsafe { ffi::_rte_hash_crc_set_alg(alg.bits) }
+}
+
+/// Calculate CRC32 hash on user-supplied byte array.
+pub fn crc(data: &[u8], init_val: u32) -> u32 {
+    unsafe { ffi::_rte_hash_crc(data.as_ptr() as *const _, data.len() as u32, init_val) }
+}
+
+/// Calculate CRC32 hash on a 4 byte value.
+pub fn crc_4byte(data: u32, init_val: u32) -> u32 {
+    unsafe { ffi::_rte_hash_crc_4byte(data, init_val) }
+}
+
+/// Calculate CRC32 hash on a 8 byte value.
+pub fn crc_8byte(data: u64, init_val: u32) -> u32 {
+    unsafe { ffi::_rte_hash_crc_8byte(data, init_val) }
+}
+
+/// Calculate Jenkins hash on an arbitrary sequence of bytes.
+pub fn jhash(key: &[u8], initval: u32) -> u32 {
+    unsafe { ffi::_rte_jhash(key.as_ptr() as *const _, key.len() as u32, initval) }
+}
+
+/// Calculate Jenkins hash on a sequence of 32-bit words.
+pub fn jhash_32b(key: &[u32], initval: u32) -> u32 {
+    unsafe { ffi::_rte_jhash_32b(key.as_ptr(), key.len() as u32, initval) }
+}
+
+/// Calculate Jenkins hash on exactly 3 words.
+pub fn jhash_3words(a: u32, b: u32, c: u32, initval: u32) -> u32 {
+    unsafe { ffi::_rte_jhash_3words(a, b, c, initval) }
+}
+
+/// Calculate Jenkins hash on exactly 2 words.
+pub fn jhash_2words(a: u32, b: u32, initval: u32) -> u32 {
+    jhash_3words(a, b, 0, initval)
+}
+
+/// Calculate Jenkins hash on exactly 1 word.
+pub fn jhash_1word(a: u32, initval: u32) -> u32 {
+    jhash_3words(a, 0, 0, initval)
+}
+
+/// The default RSS key, used by most of the NICs.
+pub const DEFAULT_RSS_KEY: [u8; 40] = [
+    0x6d, 0x5a, 0x56, 0xda, 0x25, 0x5b, 0x0e, 0xc2, 0x41, 0x67, 0x25, 0x3d, 0x43, 0xa3, 0x8f, 0xb0, 0xd0, 0xca, 0x2b,
+    0xcb, 0xae, 0x7b, 0x30, 0xb4, 0x77, 0xcb, 0x2d, 0xa3, 0x80, 0x30, 0xf2, 0x0c, 0x6a, 0x42, 0xb7, 0x3b, 0xbe, 0xac,
+    0x01, 0xfa,
+];
+
+/// The symmetric RSS key, which gives the same hash value for both directions of a flow.
+pub const SYMMETRIC_RSS_KEY: [u8; 40] = [
+    0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d,
+    0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a, 0x6d, 0x5a,
+    0x6d, 0x5a,
+];
+
+/// Calculate the Toeplitz hash of the input tuple in 4-bytes chunks, with the original RSS key.
+///
+/// The RSS key must be at least 4 bytes longer than the input tuple.
+pub fn softrss(input: &[u32], rss_key: &[u8]) -> u32 {
+    assert!(rss_key.len() >= (input.len() + 1) * mem::size_of::<u32>());
+
+    unsafe { ffi::_rte_softrss(input.as_ptr() as *mut _, input.len() as u32, rss_key.as_ptr()) }
+}
+
+/// Calculate the Toeplitz hash of the input tuple in 4-bytes chunks, with the converted RSS key.
+///
+/// The RSS key must be converted with `convert_rss_key()`.
+pub fn softrss_be(input: &[u32], rss_key: &[u8]) -> u32 {
+    assert!(rss_key.len() >= (input.len() + 1) * mem::size_of::<u32>());
+
+    unsafe { ffi::_rte_softrss_be(input.as_ptr() as *mut _, input.len() as u32, rss_key.as_ptr()) }
+}
+
+/// Prepare a new RSS key for use with `softrss_be()`.
+pub fn convert_rss_key(rss_key: &[u8]) -> Vec<u8> {
+    let mut key = vec![0; rss_key.len() & !3];
+
+    unsafe {
+        ffi::_rte_convert_rss_key(
+            rss_key.as_ptr() as *const u32,
+            key.as_mut_ptr() as *mut u32,
+            key.len() as i32,
+        )
+    }
+
+    key
+}
+
+/// The 5-tuple of a flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FiveTuple {
+    pub src_addr: IpAddr,
+    pub dst_addr: IpAddr,
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub proto: u8,
+}
+
+impl FiveTuple {
+    /// Create an IPv4 5-tuple.
+    pub fn v4(src_addr: Ipv4Addr, dst_addr: Ipv4Addr, src_port: u16, dst_port: u16, proto: u8) -> Self {
+        FiveTuple {
+            src_addr: src_addr.into(),
+            dst_addr: dst_addr.into(),
+            src_port,
+            dst_port,
+            proto,
+        }
+    }
+
+    /// Create an IPv6 5-tuple.
+    pub fn v6(src_addr: Ipv6Addr, dst_addr: Ipv6Addr, src_port: u16, dst_port: u16, proto: u8) -> Self {
+        FiveTuple {
+            src_addr: src_addr.into(),
+            dst_addr: dst_addr.into(),
+            src_port,
+            dst_port,
+            proto,
+        }
+    }
+
+    /// The 5-tuple of the reverse direction.
+    pub fn reverse(&self) -> Self {
+        FiveTuple {
+            src_addr: self.dst_addr,
+            dst_addr: self.src_addr,
+            src_port: self.dst_port,
+            dst_port: self.src_port,
+            proto: self.proto,
+        }
+    }
+
+    /// The 5-tuple in network byte order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(37);
+
+        for addr in &[self.src_addr, self.dst_addr] {
+            match *addr {
+                IpAddr::V4(addr) => buf.extend_from_slice(&addr.octets()),
+                IpAddr::V6(addr) => buf.extend_from_slice(&addr.octets()),
+            }
+        }
+
+        buf.extend_from_slice(&self.src_port.to_be_bytes());
+        buf.extend_from_slice(&self.dst_port.to_be_bytes());
+        buf.push(self.proto);
+
+        buf
+    }
+
+    /// The input tuple of RSS hash in 4-bytes chunks,
+    /// the addresses are followed by the L4 ports if `with_ports` is set.
+    pub fn to_rss_input(&self, with_ports: bool) -> Vec<u32> {
+        let mut input = Vec::with_capacity(9);
+
+        for addr in &[self.src_addr, self.dst_addr] {
+            match *addr {
+                IpAddr::V4(addr) => input.push(u32::from(addr)),
+                IpAddr::V6(addr) => input.extend(
+                    addr.octets()
+                        .chunks(4)
+                        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]])),
+                ),
+            }
+        }
+
+        if with_ports {
+            input.push(u32::from(self.src_port) << 16 | u32::from(self.dst_port));
+        }
+
+        input
+    }
+
+    /// Calculate CRC32 hash of the 5-tuple.
+    pub fn crc(&self, init_val: u32) -> u32 {
+        crc(&self.to_bytes(), init_val)
+    }
+
+    /// Calculate Jenkins hash of the 5-tuple.
+    pub fn jhash(&self, initval: u32) -> u32 {
+        jhash(&self.to_bytes(), initval)
+    }
+
+    /// Calculate the Toeplitz hash of the 5-tuple, as the L4 RSS hash calculated by the NIC.
+    pub fn softrss(&self, rss_key: &[u8]) -> u32 {
+        softrss(&self.to_rss_input(true), rss_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_five_tuple() {
+        let t = FiveTuple::v4(
+            Ipv4Addr::new(66, 9, 149, 187),
+            Ipv4Addr::new(161, 142, 100, 80),
+            2794,
+            1766,
+            6,
+        );
+
+        assert_eq!(t.to_rss_input(false), vec![0x420995bb, 0xa18e6450]);
+        assert_eq!(t.to_rss_input(true), vec![0x420995bb, 0xa18e6450, 0x0aea06e6]);
+        assert_eq!(
+            t.to_bytes(),
+            vec![66, 9, 149, 187, 161, 142, 100, 80, 0x0a, 0xea, 0x06, 0xe6, 6]
+        );
+        assert_eq!(t.reverse().reverse(), t);
+
+        let t = FiveTuple::v6(Ipv6Addr::LOCALHOST, Ipv6Addr::UNSPECIFIED, 1, 2, 17);
+
+        assert_eq!(t.to_rss_input(true), vec![0, 0, 0, 1, 0, 0, 0, 0, 0x00010002]);
+    }
+}
@@ -0,0 +1,388 @@
+//!
+//! Ring-buffered history of the port statistics.
+//!
+//! The `StatsHistory` keeps the most recent snapshots of the basic statistics of the ports,
+//! in the rings preallocated when it is created, so the periodic polling never allocates.
+//! The CLI or the telemetry could query the time series of the rates, e.g. the RX pps of the last 60s,
+//! for a simple trend view without an external monitoring system.
+//!
+//! The history is usually polled by a service of the master lcore, see `poll_every`.
+//!
+//! ```no_run
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//! use std::time::Duration;
+//!
+//! use rte::history::{self, StatsHistory};
+//! use rte::*;
+//!
+//! let history = Rc::new(RefCell::new(StatsHistory::new(&[0, 1], 60).unwrap()));
+//!
+//! history::poll_every(&history, Duration::from_secs(1));
+//!
+//! loop {
+//!     coop::run_pending();
+//!
+//!     for rate in history.borrow().rates(0, Duration::from_secs(60)) {
+//!         println!("{} pps", rate.rx_pps);
+//!     }
+//! #   break;
+//! }
+//! ```
+//!
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use libc;
+
+use common::coop::{self, ServiceId};
+use common::{get_tsc_hz, rdtsc};
+use errors::{Result, RteError};
+use ethdev::{EthDevice, PortId, RawEthDeviceStats};
+
+/// The basic counters of a port.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PortCounters {
+    pub ipackets: u64,
+    pub opackets: u64,
+    pub ibytes: u64,
+    pub obytes: u64,
+    pub imissed: u64,
+    pub ierrors: u64,
+    pub oerrors: u64,
+    pub rx_nombuf: u64,
+}
+
+impl From<&RawEthDeviceStats> for PortCounters {
+    fn from(stats: &RawEthDeviceStats) -> Self {
+        PortCounters {
+            ipackets: stats.ipackets,
+            opackets: stats.opackets,
+            ibytes: stats.ibytes,
+            obytes: stats.obytes,
+            imissed: stats.imissed,
+            ierrors: stats.ierrors,
+            oerrors: stats.oerrors,
+            rx_nombuf: stats.rx_nombuf,
+        }
+    }
+}
+
+/// A snapshot of the counters of a port.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Sample {
+    /// The TSC when the snapshot was taken.
+    pub tsc: u64,
+    pub counters: PortCounters,
+}
+
+/// The rates of a port between two snapshots, per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Rate {
+    /// The TSC at the end of the interval.
+    pub tsc: u64,
+    /// The length of the interval.
+    pub interval: Duration,
+    pub rx_pps: u64,
+    pub tx_pps: u64,
+    pub rx_bps: u64,
+    pub tx_bps: u64,
+    /// The packets missed by the RX queues.
+    pub missed_pps: u64,
+    /// The erroneous packets of both directions.
+    pub error_pps: u64,
+}
+
+impl Rate {
+    fn between(prev: &Sample, next: &Sample, hz: u64) -> Rate {
+        let cycles = next.tsc.saturating_sub(prev.tsc);
+        // the counters may be reset between the snapshots
+        let per_sec = |prev: u64, next: u64| {
+            if cycles == 0 {
+                0
+            } else {
+                (u128::from(next.saturating_sub(prev)) * u128::from(hz) / u128::from(cycles)) as u64
+            }
+        };
+        let (prev_counters, next_counters) = (&prev.counters, &next.counters);
+
+        Rate {
+            tsc: next.tsc,
+            interval: Duration::from_nanos((u128::from(cycles) * 1_000_000_000 / u128::from(hz)) as u64),
+            rx_pps: per_sec(prev_counters.ipackets, next_counters.ipackets),
+            tx_pps: per_sec(prev_counters.opackets, next_counters.opackets),
+            rx_bps: per_sec(prev_counters.ibytes, next_counters.ibytes) * 8,
+            tx_bps: per_sec(prev_counters.obytes, next_counters.obytes) * 8,
+            missed_pps: per_sec(prev_counters.imissed, next_counters.imissed),
+            error_pps: per_sec(
+                prev_counters.ierrors + prev_counters.oerrors,
+                next_counters.ierrors + next_counters.oerrors,
+            ),
+        }
+    }
+}
+
+/// A preallocated ring, which overwrites the oldest item when it is full.
+struct Ring<T> {
+    buf: Vec<T>,
+    head: usize,
+    len: usize,
+}
+
+impl<T: Copy + Default> Ring<T> {
+    fn new(capacity: usize) -> Self {
+        Ring {
+            buf: vec![T::default(); capacity],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, item: T) {
+        let capacity = self.buf.len();
+
+        self.buf[self.head] = item;
+        self.head = (self.head + 1) % capacity;
+        self.len = (self.len + 1).min(capacity);
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// The items from the oldest to the newest.
+    fn iter<'a>(&'a self) -> impl DoubleEndedIterator<Item = &'a T> + 'a {
+        let capacity = self.buf.len();
+        let start = self.head + capacity - self.len;
+
+        (0..self.len).map(move |i| &self.buf[(start + i) % capacity])
+    }
+}
+
+/// The history of the statistics of the ports.
+pub struct StatsHistory {
+    hz: u64,
+    capacity: usize,
+    ports: Vec<(PortId, Ring<Sample>)>,
+}
+
+impl StatsHistory {
+    /// Create the history of the ports, which keeps `capacity` snapshots of each port.
+    pub fn new(ports: &[PortId], capacity: usize) -> Result<Self> {
+        if ports.iter().any(|port_id| !port_id.is_valid()) {
+            return Err(RteError(libc::ENODEV).into());
+        }
+
+        Self::with_hz(ports, capacity, get_tsc_hz())
+    }
+
+    fn with_hz(ports: &[PortId], capacity: usize, hz: u64) -> Result<Self> {
+        if capacity < 2 || hz == 0 {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        Ok(StatsHistory {
+            hz,
+            capacity,
+            ports: ports.iter().map(|&port_id| (port_id, Ring::new(capacity))).collect(),
+        })
+    }
+
+    /// The ports of the history.
+    pub fn ports(&self) -> Vec<PortId> {
+        self.ports.iter().map(|&(port_id, _)| port_id).collect()
+    }
+
+    /// The max number of the snapshots of each port.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn ring(&self, port_id: PortId) -> Option<&Ring<Sample>> {
+        self.ports.iter().find(|&&(id, _)| id == port_id).map(|(_, ring)| ring)
+    }
+
+    fn record(&mut self, port_id: PortId, sample: Sample) {
+        if let Some(&mut (_, ref mut ring)) = self.ports.iter_mut().find(|&&mut (id, _)| id == port_id) {
+            ring.push(sample);
+        }
+    }
+
+    /// Take a snapshot of the statistics of each port.
+    ///
+    /// All the ports are polled even if some of them fail, the first error is returned.
+    pub fn poll(&mut self) -> Result<()> {
+        let mut res = Ok(());
+
+        for &mut (port_id, ref mut ring) in &mut self.ports {
+            match port_id.stats() {
+                Ok(stats) => ring.push(Sample {
+                    tsc: rdtsc(),
+                    counters: PortCounters::from(&stats),
+                }),
+                Err(err) => {
+                    if res.is_ok() {
+                        res = Err(err)
+                    }
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Forget the snapshots, e.g. after the statistics of the ports were reset.
+    pub fn clear(&mut self) {
+        for &mut (_, ref mut ring) in &mut self.ports {
+            ring.clear();
+        }
+    }
+
+    /// The snapshots of the port, from the oldest to the newest.
+    pub fn samples(&self, port_id: PortId) -> Vec<Sample> {
+        self.ring(port_id)
+            .map(|ring| ring.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The newest snapshot of the port.
+    pub fn latest(&self, port_id: PortId) -> Option<Sample> {
+        self.ring(port_id).and_then(|ring| ring.iter().next_back().cloned())
+    }
+
+    /// The snapshots of the port within the window before the newest one, from the oldest to the newest.
+    fn window(&self, port_id: PortId, window: Duration) -> Vec<Sample> {
+        let cycles = (window.as_nanos() * u128::from(self.hz) / 1_000_000_000) as u64;
+        let mut samples = match self.ring(port_id) {
+            Some(ring) => match ring.iter().next_back() {
+                Some(newest) => {
+                    let since = newest.tsc.saturating_sub(cycles);
+
+                    ring.iter()
+                        .rev()
+                        .take_while(|sample| sample.tsc >= since)
+                        .cloned()
+                        .collect()
+                }
+                None => vec![],
+            },
+            None => vec![],
+        };
+
+        samples.reverse();
+        samples
+    }
+
+    /// The time series of the rates of the port within the window, from the oldest to the newest.
+    pub fn rates(&self, port_id: PortId, window: Duration) -> Vec<Rate> {
+        self.window(port_id, window)
+            .windows(2)
+            .map(|pair| Rate::between(&pair[0], &pair[1], self.hz))
+            .collect()
+    }
+
+    /// The average rates of the port within the window, e.g. the pps of the last 60s.
+    pub fn rate(&self, port_id: PortId, window: Duration) -> Option<Rate> {
+        let samples = self.window(port_id, window);
+
+        match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) if first.tsc < last.tsc => Some(Rate::between(first, last, self.hz)),
+            _ => None,
+        }
+    }
+}
+
+/// Poll the history periodically by a service of the current thread.
+///
+/// The service skips the polling when the history is borrowed, and does nothing after it was dropped,
+/// it should be cancelled with `coop::cancel`.
+pub fn poll_every(history: &Rc<RefCell<StatsHistory>>, period: Duration) -> ServiceId {
+    let history = Rc::downgrade(history);
+
+    coop::every("stats history", period, move || {
+        if let Some(history) = history.upgrade() {
+            if let Ok(mut history) = history.try_borrow_mut() {
+                if let Err(err) = history.poll() {
+                    debug!("fail to poll the stats history, {}", err);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(tsc: u64, ipackets: u64, ibytes: u64) -> Sample {
+        Sample {
+            tsc,
+            counters: PortCounters {
+                ipackets,
+                ibytes,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_ring() {
+        let mut ring = Ring::new(3);
+
+        assert_eq!(ring.iter().count(), 0);
+
+        ring.push(1);
+        ring.push(2);
+
+        assert_eq!(ring.iter().cloned().collect::<Vec<_>>(), vec![1, 2]);
+
+        ring.push(3);
+        ring.push(4);
+
+        assert_eq!(ring.iter().cloned().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(ring.iter().next_back(), Some(&4));
+        assert_eq!(ring.buf.capacity(), 3);
+
+        ring.clear();
+
+        assert_eq!(ring.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_stats_history() {
+        assert!(StatsHistory::with_hz(&[0], 1, 1000).is_err());
+
+        let mut history = StatsHistory::with_hz(&[0, 1], 4, 1000).unwrap();
+
+        for i in 0..6 {
+            history.record(0, sample(i * 1000, i * 100, i * 6400));
+        }
+
+        assert_eq!(history.samples(0).len(), 4);
+        assert_eq!(history.samples(0)[0].tsc, 2000);
+        assert_eq!(history.latest(0), Some(sample(5000, 500, 32000)));
+        assert!(history.samples(1).is_empty());
+        assert!(history.samples(2).is_empty());
+
+        let rates = history.rates(0, Duration::from_secs(2));
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].tsc, 4000);
+        assert_eq!(rates[0].interval, Duration::from_secs(1));
+        assert_eq!(rates[0].rx_pps, 100);
+        assert_eq!(rates[0].rx_bps, 6400 * 8);
+
+        let rate = history.rate(0, Duration::from_secs(60)).unwrap();
+
+        assert_eq!(rate.interval, Duration::from_secs(3));
+        assert_eq!(rate.rx_pps, 100);
+        assert_eq!(history.rate(1, Duration::from_secs(60)), None);
+
+        // the counters were reset
+        history.record(0, sample(6000, 10, 640));
+
+        assert_eq!(history.rates(0, Duration::from_secs(1))[0].rx_pps, 0);
+    }
+}
@@ -0,0 +1,341 @@
+//!
+//! RX queue interrupts, which let the lcores sleep on the idle queues.
+//!
+//! Each RX queue with the interrupt enabled owns an event fd, `RxInterruptSet` registers
+//! the fds of many queues, on any ports, in one epoll fd, so a single thread or a user
+//! event loop could wait on all of them, and poll the queues which have packets.
+//!
+//! The port must be configured with `intr_conf.rxq` enabled, and the interrupt is one-shot:
+//! it must be enabled again before waiting, after the queue was drained.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use rte::ethdev::EthDevice;
+//! use rte::intr::RxInterruptSet;
+//! use rte::*;
+//!
+//! let mut set = RxInterruptSet::new().unwrap();
+//!
+//! set.add(0, 0).unwrap();
+//! set.add(1, 0).unwrap();
+//!
+//! loop {
+//!     set.arm().unwrap();
+//!
+//!     for queue in set.wait(Some(Duration::from_millis(100))).unwrap() {
+//!         // poll the queue with `rx_burst` until it is drained
+//!     }
+//! }
+//! ```
+//!
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::Duration;
+
+use libc;
+
+#[cfg(feature = "mio")]
+use mio;
+
+use errors::{os_error, Result};
+use ethdev::{EthDevice, PortId, QueueHandle, QueueId};
+
+/// The maximum number of events returned by one wait.
+const MAX_EVENTS: usize = 64;
+
+/// The epoll data of the RX queue, which packs the port and queue ids.
+#[inline]
+pub fn token(port_id: PortId, queue_id: QueueId) -> u64 {
+    u64::from(port_id) << 16 | u64::from(queue_id)
+}
+
+/// The RX queue of the epoll data.
+#[inline]
+pub fn from_token(token: u64) -> QueueHandle {
+    QueueHandle::Rx((token >> 16) as PortId, token as QueueId)
+}
+
+/// The `mio` token of the RX queue.
+#[cfg(feature = "mio")]
+#[inline]
+pub fn mio_token(port_id: PortId, queue_id: QueueId) -> mio::Token {
+    mio::Token(token(port_id, queue_id) as usize)
+}
+
+/// The RX queue of the `mio` token.
+#[cfg(feature = "mio")]
+#[inline]
+pub fn from_mio_token(token: mio::Token) -> QueueHandle {
+    from_token(token.0 as u64)
+}
+
+/// The interrupt of a RX queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RxInterrupt {
+    port_id: PortId,
+    queue_id: QueueId,
+    fd: RawFd,
+}
+
+impl RxInterrupt {
+    /// Get the interrupt of the RX queue.
+    pub fn new(port_id: PortId, queue_id: QueueId) -> Result<Self> {
+        let fd = port_id.rx_intr_fd(queue_id)?;
+
+        Ok(RxInterrupt { port_id, queue_id, fd })
+    }
+
+    /// The RX queue of the interrupt.
+    pub fn queue(&self) -> QueueHandle {
+        QueueHandle::Rx(self.port_id, self.queue_id)
+    }
+
+    /// The epoll data of the interrupt.
+    pub fn token(&self) -> u64 {
+        token(self.port_id, self.queue_id)
+    }
+
+    /// Enable the interrupt, which fires once when a packet is received.
+    pub fn enable(&self) -> Result<()> {
+        self.port_id.rx_intr_enable(self.queue_id).map(|_| ())
+    }
+
+    /// Disable the interrupt, before polling the queue.
+    pub fn disable(&self) -> Result<()> {
+        self.port_id.rx_intr_disable(self.queue_id).map(|_| ())
+    }
+
+    /// Drain the pending events of the fd, so the level triggered epoll won't fire again.
+    pub fn ack(&self) -> Result<()> {
+        let mut buf = [0u8; 8];
+
+        loop {
+            let n = unsafe { libc::read(self.fd, buf.as_mut_ptr() as *mut _, buf.len()) };
+
+            if n >= 0 {
+                return Ok(());
+            }
+
+            match io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                Some(libc::EAGAIN) => return Ok(()),
+                // the VFIO event fd is 8 bytes, the UIO fd reads a 4 bytes counter
+                Some(libc::EINVAL) => {
+                    let n = unsafe { libc::read(self.fd, buf.as_mut_ptr() as *mut _, 4) };
+
+                    return if n < 0 { Err(os_error()) } else { Ok(()) };
+                }
+                _ => return Err(os_error()),
+            }
+        }
+    }
+}
+
+impl AsRawFd for RxInterrupt {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+#[cfg(feature = "mio")]
+impl mio::Evented for RxInterrupt {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.fd).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.fd).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        mio::unix::EventedFd(&self.fd).deregister(poll)
+    }
+}
+
+/// A set of RX queue interrupts, registered in one epoll fd.
+#[derive(Debug)]
+pub struct RxInterruptSet {
+    epfd: RawFd,
+    intrs: HashMap<u64, RxInterrupt>,
+}
+
+impl RxInterruptSet {
+    /// Create an empty set.
+    pub fn new() -> Result<Self> {
+        let epfd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+
+        if epfd < 0 {
+            Err(os_error())
+        } else {
+            Ok(RxInterruptSet {
+                epfd,
+                intrs: HashMap::new(),
+            })
+        }
+    }
+
+    /// The number of queues in the set.
+    pub fn len(&self) -> usize {
+        self.intrs.len()
+    }
+
+    /// The set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.intrs.is_empty()
+    }
+
+    /// The interrupts in the set.
+    pub fn interrupts(&self) -> impl Iterator<Item = &RxInterrupt> {
+        self.intrs.values()
+    }
+
+    /// Add the interrupt of the RX queue to the set.
+    pub fn add(&mut self, port_id: PortId, queue_id: QueueId) -> Result<&mut Self> {
+        let intr = RxInterrupt::new(port_id, queue_id)?;
+        let token = intr.token();
+
+        if !self.intrs.contains_key(&token) {
+            self.ctl(libc::EPOLL_CTL_ADD, intr.fd, token)?;
+            self.intrs.insert(token, intr);
+        }
+
+        Ok(self)
+    }
+
+    /// Remove the interrupt of the RX queue from the set.
+    pub fn remove(&mut self, port_id: PortId, queue_id: QueueId) -> Result<&mut Self> {
+        if let Some(intr) = self.intrs.remove(&token(port_id, queue_id)) {
+            self.ctl(libc::EPOLL_CTL_DEL, intr.fd, intr.token())?;
+        }
+
+        Ok(self)
+    }
+
+    fn ctl(&self, op: libc::c_int, fd: RawFd, token: u64) -> Result<()> {
+        let mut event = libc::epoll_event {
+            events: libc::EPOLLIN as u32,
+            u64: token,
+        };
+
+        if unsafe { libc::epoll_ctl(self.epfd, op, fd, &mut event) } < 0 {
+            Err(os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Enable the interrupts of all the queues, before waiting.
+    pub fn arm(&self) -> Result<()> {
+        for intr in self.intrs.values() {
+            intr.enable()?;
+        }
+
+        Ok(())
+    }
+
+    /// Wait for the queues which received packets, or until the timeout expires.
+    ///
+    /// The ready queues are acknowledged and their interrupts disabled,
+    /// the caller should poll them until they are drained, then `arm` the set again.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<Vec<QueueHandle>> {
+        let timeout = timeout.map_or(-1, |d| d.as_millis().min(i32::MAX as u128) as i32);
+        let mut events: [libc::epoll_event; MAX_EVENTS] = unsafe { mem::zeroed() };
+
+        let n = loop {
+            let n = unsafe { libc::epoll_wait(self.epfd, events.as_mut_ptr(), MAX_EVENTS as i32, timeout) };
+
+            if n >= 0 {
+                break n as usize;
+            }
+
+            if io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                return Err(os_error());
+            }
+        };
+
+        let mut queues = Vec::with_capacity(n);
+
+        for event in &events[..n] {
+            let token = event.u64;
+
+            if let Some(intr) = self.intrs.get(&token) {
+                intr.ack()?;
+                intr.disable()?;
+
+                queues.push(intr.queue());
+            }
+        }
+
+        Ok(queues)
+    }
+}
+
+impl AsRawFd for RxInterruptSet {
+    fn as_raw_fd(&self) -> RawFd {
+        self.epfd
+    }
+}
+
+impl Drop for RxInterruptSet {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.epfd);
+        }
+    }
+}
+
+/// The epoll fd of the set is readable when any queue is ready,
+/// so the whole set could be nested in a `mio` event loop.
+#[cfg(feature = "mio")]
+impl mio::Evented for RxInterruptSet {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.epfd).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.epfd).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        mio::unix::EventedFd(&self.epfd).deregister(poll)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token() {
+        assert_eq!(token(1, 2), 0x10002);
+        assert_eq!(from_token(token(1, 2)), QueueHandle::Rx(1, 2));
+        assert_eq!(from_token(token(0xffff, 0xffff)), QueueHandle::Rx(0xffff, 0xffff));
+    }
+}
@@ -1,7 +1,327 @@
+use std::mem;
+use std::slice;
+
+use libc;
+
 use ffi;
 
+use common::byteorder::{be16, be32};
+use ether::{self, EtherExt};
+use mbuf;
+
+pub use rte_net_types::ip::{
+    ipv4_cksum, ipv4_udptcp_cksum, ipv4_udptcp_cksum_verify, ipv6_udptcp_cksum, ipv6_udptcp_cksum_verify, raw_cksum,
+    raw_cksum_reduce, raw_cksum_sum, Ipv4Option, Ipv4Options, IPOPT_END, IPOPT_LSRR, IPOPT_NOOP, IPOPT_RA, IPOPT_RR,
+    IPOPT_SECURITY, IPOPT_SSRR, IPOPT_TS, IPV4_HDR_DF_FLAG, IPV4_HDR_IHL_MASK, IPV4_HDR_LEN, IPV4_HDR_MF_FLAG,
+    IPV4_HDR_OFFSET_MASK, IPV4_IHL_MULTIPLIER, IPV4_MAX_HDR_LEN, IPVERSION, IP_ICMP_ECHO_REPLY, IP_ICMP_ECHO_REQUEST,
+};
+
 /// IPv4 Header
 pub type Ipv4Hdr = ffi::ipv4_hdr;
 
 /// IPv6 Header
 pub type Ipv6Hdr = ffi::ipv6_hdr;
+
+/// ICMP Header
+pub type IcmpHdr = ffi::icmp_hdr;
+
+unsafe impl mbuf::Header for Ipv4Hdr {
+    const LAYER: mbuf::HeaderLayer = mbuf::HeaderLayer::L3;
+}
+unsafe impl mbuf::Header for Ipv6Hdr {
+    const LAYER: mbuf::HeaderLayer = mbuf::HeaderLayer::L3;
+}
+unsafe impl mbuf::Header for IcmpHdr {
+    const LAYER: mbuf::HeaderLayer = mbuf::HeaderLayer::L4;
+}
+
+pub trait Ipv4Ext {
+    /// The version of IP protocol.
+    fn version(&self) -> u8;
+
+    /// The length of IPv4 header, including the options, in bytes.
+    fn header_len(&self) -> usize;
+
+    /// The length of IPv4 packet, including the header, in network byte order.
+    fn total_len(&self) -> be16;
+
+    /// The source address, in network byte order.
+    fn src(&self) -> be32;
+
+    /// Set the source address, in network byte order.
+    fn set_src(&mut self, addr: be32);
+
+    /// The destination address, in network byte order.
+    fn dst(&self) -> be32;
+
+    /// Set the destination address, in network byte order.
+    fn set_dst(&mut self, addr: be32);
+
+    /// The packet is a fragment.
+    fn is_fragment(&self) -> bool;
+
+    /// The options following the IPv4 header.
+    ///
+    /// The header must be followed by the number of bytes announced in its IHL field,
+    /// as it is the case when it points into the data of a packet mbuf.
+    fn options(&self) -> Ipv4Options<'_>;
+
+    /// Process the IPv4 checksum of the header and its options.
+    fn cksum(&self) -> u16;
+
+    /// Update the checksum field of the header.
+    fn update_cksum(&mut self);
+}
+
+impl Ipv4Ext for Ipv4Hdr {
+    #[inline]
+    fn version(&self) -> u8 {
+        self.version_ihl >> 4
+    }
+
+    #[inline]
+    fn header_len(&self) -> usize {
+        (self.version_ihl & IPV4_HDR_IHL_MASK) as usize * IPV4_IHL_MULTIPLIER
+    }
+
+    #[inline]
+    fn total_len(&self) -> be16 {
+        be16::from_raw(self.total_length)
+    }
+
+    #[inline]
+    fn src(&self) -> be32 {
+        be32::from_raw(self.src_addr)
+    }
+
+    #[inline]
+    fn set_src(&mut self, addr: be32) {
+        self.src_addr = addr.to_raw()
+    }
+
+    #[inline]
+    fn dst(&self) -> be32 {
+        be32::from_raw(self.dst_addr)
+    }
+
+    #[inline]
+    fn set_dst(&mut self, addr: be32) {
+        self.dst_addr = addr.to_raw()
+    }
+
+    #[inline]
+    fn is_fragment(&self) -> bool {
+        be16::from_raw(self.fragment_offset).get() & (IPV4_HDR_MF_FLAG | IPV4_HDR_OFFSET_MASK) != 0
+    }
+
+    fn options(&self) -> Ipv4Options<'_> {
+        let len = self.header_len().saturating_sub(IPV4_HDR_LEN);
+
+        Ipv4Options::new(unsafe { slice::from_raw_parts((self as *const Self as *const u8).add(IPV4_HDR_LEN), len) })
+    }
+
+    fn cksum(&self) -> u16 {
+        let hdr = unsafe { slice::from_raw_parts(self as *const Self as *const u8, self.header_len()) };
+        let sum = raw_cksum_sum(hdr, 0) + u32::from(!self.hdr_checksum);
+
+        match raw_cksum_reduce(sum) {
+            0xffff => 0xffff,
+            cksum => !cksum,
+        }
+    }
+
+    fn update_cksum(&mut self) {
+        self.hdr_checksum = 0;
+        self.hdr_checksum = self.cksum();
+    }
+}
+
+pub trait IcmpExt {
+    /// Create an ICMP echo request header, the identifier and sequence number are in host byte order.
+    fn echo_request(ident: u16, seq_nb: u16) -> Self;
+
+    /// Create an ICMP echo reply header, the identifier and sequence number are in host byte order.
+    fn echo_reply(ident: u16, seq_nb: u16) -> Self;
+
+    /// The packet is an echo request.
+    fn is_echo_request(&self) -> bool;
+
+    /// The packet is an echo reply.
+    fn is_echo_reply(&self) -> bool;
+
+    /// Process the ICMP checksum of the header and the payload following it.
+    fn cksum(&self, payload: &[u8]) -> u16;
+
+    /// Update the checksum field of the header.
+    fn update_cksum(&mut self, payload: &[u8]);
+}
+
+impl IcmpExt for IcmpHdr {
+    fn echo_request(ident: u16, seq_nb: u16) -> Self {
+        IcmpHdr {
+            icmp_type: IP_ICMP_ECHO_REQUEST,
+            icmp_code: 0,
+            icmp_cksum: 0,
+            icmp_ident: be16::new(ident).to_raw(),
+            icmp_seq_nb: be16::new(seq_nb).to_raw(),
+        }
+    }
+
+    fn echo_reply(ident: u16, seq_nb: u16) -> Self {
+        IcmpHdr {
+            icmp_type: IP_ICMP_ECHO_REPLY,
+            icmp_code: 0,
+            icmp_cksum: 0,
+            icmp_ident: be16::new(ident).to_raw(),
+            icmp_seq_nb: be16::new(seq_nb).to_raw(),
+        }
+    }
+
+    #[inline]
+    fn is_echo_request(&self) -> bool {
+        self.icmp_type == IP_ICMP_ECHO_REQUEST && self.icmp_code == 0
+    }
+
+    #[inline]
+    fn is_echo_reply(&self) -> bool {
+        self.icmp_type == IP_ICMP_ECHO_REPLY && self.icmp_code == 0
+    }
+
+    fn cksum(&self, payload: &[u8]) -> u16 {
+        let mut hdr = *self;
+
+        hdr.icmp_cksum = 0;
+
+        let hdr = unsafe { slice::from_raw_parts(&hdr as *const Self as *const u8, mem::size_of::<Self>()) };
+
+        !raw_cksum_reduce(raw_cksum_sum(payload, raw_cksum_sum(hdr, 0)))
+    }
+
+    fn update_cksum(&mut self, payload: &[u8]) {
+        self.icmp_cksum = self.cksum(payload);
+    }
+}
+
+/// Turn the ICMP echo requests of a burst into echo replies.
+///
+/// The Ethernet and IP addresses of the echo requests are swapped and their ICMP type
+/// and checksum are updated in place, then the replies are moved to the front of the burst,
+/// ready to be sent back on the port they were received from.
+///
+/// Returns the number of echo replies.
+pub fn ping_responder(pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+    let mut replies = 0;
+
+    for i in 0..pkts.len() {
+        let is_reply = pkts[i].as_mut().is_some_and(|m| unsafe { echo_reply(m) });
+
+        if is_reply {
+            pkts.swap(replies, i);
+            replies += 1;
+        }
+    }
+
+    replies
+}
+
+unsafe fn echo_reply(m: &mut mbuf::MBuf) -> bool {
+    if m.data_len() < ether::ETHER_HDR_LEN as usize + IPV4_HDR_LEN {
+        return false;
+    }
+
+    let eth_hdr = m.mtod::<ether::EtherHdr>().as_mut();
+
+    if eth_hdr.ether_type() != ether::ETHER_TYPE_IPV4_BE {
+        return false;
+    }
+
+    let ip_hdr = m.mtod_offset::<Ipv4Hdr>(ether::ETHER_HDR_LEN as usize).as_mut();
+    let ip_hdr_len = ip_hdr.header_len();
+
+    if ip_hdr.version() != IPVERSION
+        || ip_hdr.next_proto_id != libc::IPPROTO_ICMP as u8
+        || ip_hdr.is_fragment()
+        || m.data_len() < ether::ETHER_HDR_LEN as usize + ip_hdr_len + mem::size_of::<IcmpHdr>()
+    {
+        return false;
+    }
+
+    let icmp_hdr = m
+        .mtod_offset::<IcmpHdr>(ether::ETHER_HDR_LEN as usize + ip_hdr_len)
+        .as_mut();
+
+    if !icmp_hdr.is_echo_request() {
+        return false;
+    }
+
+    let (s_addr, d_addr) = (eth_hdr.s_addr, eth_hdr.d_addr);
+    eth_hdr.s_addr = d_addr;
+    eth_hdr.d_addr = s_addr;
+
+    let (src_addr, dst_addr) = (ip_hdr.src_addr, ip_hdr.dst_addr);
+    ip_hdr.src_addr = dst_addr;
+    ip_hdr.dst_addr = src_addr;
+
+    // incrementally update the checksum for the changed type, RFC 1624
+    let sum = u32::from(!icmp_hdr.icmp_cksum)
+        + u32::from(!(u16::from(IP_ICMP_ECHO_REQUEST) << 8).to_be())
+        + u32::from((u16::from(IP_ICMP_ECHO_REPLY) << 8).to_be());
+
+    icmp_hdr.icmp_type = IP_ICMP_ECHO_REPLY;
+    icmp_hdr.icmp_cksum = !raw_cksum_reduce(sum);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipv4_cksum() {
+        let mut hdr = [
+            0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0x01, 0xc0, 0xa8,
+            0x00, 0xc7,
+        ];
+
+        let cksum = ipv4_cksum(&hdr);
+
+        assert_eq!(cksum.to_ne_bytes(), [0xb8, 0x61]);
+
+        hdr[10..12].copy_from_slice(&cksum.to_ne_bytes());
+
+        let ip_hdr = unsafe { &*(hdr.as_ptr() as *const Ipv4Hdr) };
+
+        assert_eq!(ip_hdr.version(), IPVERSION);
+        assert_eq!(ip_hdr.header_len(), IPV4_HDR_LEN);
+        assert!(!ip_hdr.is_fragment());
+        assert_eq!(ip_hdr.cksum(), cksum);
+        assert_eq!(ip_hdr.options().next(), None);
+    }
+
+    #[test]
+    fn test_icmp_cksum() {
+        let mut hdr = IcmpHdr::echo_request(0x1234, 1);
+        let payload = b"abcdefghijklmnopqrstuvwabcdefghi";
+
+        assert!(hdr.is_echo_request());
+        assert_eq!(u16::from_be(hdr.icmp_ident), 0x1234);
+        assert_eq!(u16::from_be(hdr.icmp_seq_nb), 1);
+
+        hdr.update_cksum(payload);
+
+        let sum = raw_cksum_sum(
+            payload,
+            raw_cksum_sum(&[8, 0], 0) + u32::from(hdr.icmp_cksum) + 0x3412 + 0x0100,
+        );
+
+        assert_eq!(raw_cksum_reduce(sum), 0xffff);
+
+        let mut reply = IcmpHdr::echo_reply(0x1234, 1);
+
+        reply.update_cksum(payload);
+
+        assert!(reply.is_echo_reply());
+        assert_eq!(u16::from_be(reply.icmp_cksum), u16::from_be(hdr.icmp_cksum) + 0x0800);
+    }
+}
@@ -0,0 +1,332 @@
+//!
+//! IP prefix set for allowlist and denylist matching.
+//!
+//! The `IpSet` keeps the IPv4 and IPv6 prefixes in a `PrefixTable` on `rte_lpm` and `rte_lpm6`,
+//! an address is a member if it is covered by any of the prefixes,
+//! and a burst of addresses is looked up in bulk.
+//!
+//! The prefixes could be added and removed at runtime,
+//! and a burst of packets could be classified into members and non-members by their source or destination address.
+//!
+use std::error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::result;
+use std::str;
+
+use common::coop;
+use edit::Endpoint;
+use errors::{ErrorKind::InvalidTable, Result};
+use filter::dissect_mbuf;
+use mbuf::MBuf;
+use route::{PrefixConf, PrefixTable};
+use table::{Table, TableKind};
+
+/// The maximum number of packets which could be classified in a burst.
+pub const MAX_BURST_SIZE: usize = 64;
+
+/// An IP network prefix in CIDR notation, e.g. `10.0.0.0/8`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Prefix {
+    addr: IpAddr,
+    depth: u8,
+}
+
+impl Prefix {
+    /// Create a prefix, the host bits of the address are cleared.
+    ///
+    /// Returns `None` if the prefix length exceeds the address length.
+    pub fn new(addr: IpAddr, depth: u8) -> Option<Self> {
+        match addr {
+            IpAddr::V4(addr) if depth <= 32 => Some(Prefix {
+                addr: Ipv4Addr::from(mask_v4(addr, depth)).into(),
+                depth,
+            }),
+            IpAddr::V6(addr) if depth <= 128 => Some(Prefix {
+                addr: Ipv6Addr::from(mask_v6(addr, depth)).into(),
+                depth,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The network address.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// The prefix length.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The prefix covers the address.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.addr, addr) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => mask_v4(addr, self.depth) == u32::from(net),
+            (IpAddr::V6(net), IpAddr::V6(addr)) => mask_v6(addr, self.depth) == u128::from(net),
+            _ => false,
+        }
+    }
+}
+
+impl From<Ipv4Addr> for Prefix {
+    fn from(addr: Ipv4Addr) -> Self {
+        Prefix {
+            addr: addr.into(),
+            depth: 32,
+        }
+    }
+}
+
+impl From<Ipv6Addr> for Prefix {
+    fn from(addr: Ipv6Addr) -> Self {
+        Prefix {
+            addr: addr.into(),
+            depth: 128,
+        }
+    }
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.depth)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefixParseError(());
+
+impl fmt::Display for PrefixParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(error::Error::description(self))
+    }
+}
+
+impl error::Error for PrefixParseError {
+    fn description(&self) -> &str {
+        "invalid IP prefix syntax"
+    }
+}
+
+impl str::FromStr for Prefix {
+    type Err = PrefixParseError;
+
+    /// Parse a prefix in CIDR notation, a bare address is a host prefix.
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr: IpAddr = parts
+            .next()
+            .and_then(|addr| addr.parse().ok())
+            .ok_or(PrefixParseError(()))?;
+        let depth = match parts.next() {
+            Some(depth) => depth.parse().map_err(|_| PrefixParseError(()))?,
+            None if addr.is_ipv4() => 32,
+            None => 128,
+        };
+
+        Prefix::new(addr, depth).ok_or(PrefixParseError(()))
+    }
+}
+
+fn mask_v4(addr: Ipv4Addr, depth: u8) -> u32 {
+    if depth == 0 {
+        0
+    } else {
+        u32::from(addr) & (!0u32 << (32 - u32::from(depth)))
+    }
+}
+
+fn mask_v6(addr: Ipv6Addr, depth: u8) -> u128 {
+    if depth == 0 {
+        0
+    } else {
+        u128::from(addr) & (!0u128 << (128 - u32::from(depth)))
+    }
+}
+
+/// A set of IPv4 and IPv6 prefixes.
+#[derive(Debug)]
+pub struct IpSet {
+    prefixes: PrefixTable<Prefix>,
+}
+
+impl IpSet {
+    /// Create an empty set.
+    pub fn new() -> Result<Self> {
+        IpSet::with_conf(&PrefixConf::default())
+    }
+
+    /// Create an empty set with the capacity.
+    pub fn with_conf(conf: &PrefixConf) -> Result<Self> {
+        PrefixTable::with_conf(conf).map(|prefixes| IpSet { prefixes })
+    }
+
+    /// Create a copy of the set with the same capacity.
+    pub fn try_clone(&self) -> Result<Self> {
+        self.prefixes.try_clone().map(|prefixes| IpSet { prefixes })
+    }
+
+    /// Add a prefix, returns `false` if the set already has it.
+    ///
+    /// Fails with `ENOSPC` if the set is full.
+    pub fn insert(&mut self, prefix: Prefix) -> Result<bool> {
+        self.prefixes
+            .add(prefix.addr, prefix.depth, prefix)
+            .map(|prev| prev.is_none())
+    }
+
+    /// Remove a prefix, returns `false` if the set doesn't have it.
+    ///
+    /// Only the same prefix is removed, the addresses may still be covered by a shorter or longer prefix.
+    pub fn remove(&mut self, prefix: Prefix) -> bool {
+        self.prefixes.remove(prefix.addr, prefix.depth).is_some()
+    }
+
+    /// The set has the prefix.
+    pub fn has_prefix(&self, prefix: Prefix) -> bool {
+        self.prefixes.get(prefix.addr, prefix.depth).is_some()
+    }
+
+    /// The address is covered by any prefix of the set.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        self.matched_prefix(addr).is_some()
+    }
+
+    /// The longest prefix of the set which covers the address.
+    #[inline]
+    pub fn matched_prefix(&self, addr: IpAddr) -> Option<Prefix> {
+        self.prefixes.lookup(addr)
+    }
+
+    /// Look up a bulk of addresses, returns the bit mask of the members.
+    pub fn lookup_bulk(&self, addrs: &[IpAddr]) -> u64 {
+        assert!(addrs.len() <= MAX_BURST_SIZE);
+
+        let mut matched = [None; MAX_BURST_SIZE];
+
+        self.prefixes.lookup_bulk(addrs, &mut matched[..addrs.len()]);
+
+        matched[..addrs.len()]
+            .iter()
+            .enumerate()
+            .filter(|&(_, prefix)| prefix.is_some())
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Classify a burst of packets by the source or destination address, returns the bit mask of the members.
+    ///
+    /// The non-IP packets are non-members.
+    pub fn classify(&self, pkts: &[MBuf], which: Endpoint) -> u64 {
+        assert!(pkts.len() <= MAX_BURST_SIZE);
+
+        let mut addrs = [IpAddr::V4(Ipv4Addr::UNSPECIFIED); MAX_BURST_SIZE];
+        let mut pos = [0; MAX_BURST_SIZE];
+        let mut n = 0;
+
+        for (i, m) in pkts.iter().enumerate() {
+            if let Some(addr) = dissect_mbuf(m).and_then(|d| if which == Endpoint::Src { d.src_ip } else { d.dst_ip }) {
+                addrs[n] = addr;
+                pos[n] = i;
+                n += 1;
+            }
+        }
+
+        let mask = self.lookup_bulk(&addrs[..n]);
+
+        pos[..n]
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| mask & (1 << j) != 0)
+            .fold(0, |mask, (_, &i)| mask | (1 << i))
+    }
+
+    /// Split the packets into members and non-members by the source or destination address.
+    pub fn partition<I: IntoIterator<Item = MBuf>>(&self, pkts: I, which: Endpoint) -> (Vec<MBuf>, Vec<MBuf>) {
+        pkts.into_iter().partition(|m| {
+            dissect_mbuf(m)
+                .and_then(|d| if which == Endpoint::Src { d.src_ip } else { d.dst_ip })
+                .is_some_and(|addr| self.contains(addr))
+        })
+    }
+
+    /// The prefixes of the set.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = Prefix> + 'a {
+        self.prefixes.iter().map(|(_, _, prefix)| prefix)
+    }
+
+    /// The number of prefixes.
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// The set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// Remove all the prefixes.
+    pub fn clear(&mut self) {
+        self.prefixes.clear()
+    }
+
+    /// Add the prefixes to the set.
+    pub fn extend<I: IntoIterator<Item = Prefix>>(&mut self, iter: I) -> Result<()> {
+        for prefix in iter {
+            self.insert(prefix)?;
+
+            coop::yield_point();
+        }
+
+        Ok(())
+    }
+}
+
+impl Table for IpSet {
+    type Entry = (IpAddr, u8);
+
+    const KIND: TableKind = TableKind::IpSet;
+
+    fn entry_count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries<'a>(&'a self) -> Box<dyn Iterator<Item = Self::Entry> + 'a> {
+        Box::new(self.iter().map(|prefix| (prefix.addr, prefix.depth)))
+    }
+
+    fn from_entries(entries: Vec<Self::Entry>) -> Result<Self> {
+        let prefixes = entries
+            .into_iter()
+            .map(|(addr, depth)| {
+                Prefix::new(addr, depth)
+                    .ok_or_else(|| InvalidTable(format!("invalid prefix {}/{}", addr, depth)).into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let conf = PrefixConf::for_networks(prefixes.iter().map(|prefix| (prefix.addr, prefix.depth)));
+        let mut set = IpSet::with_conf(&conf)?;
+
+        set.extend(prefixes)?;
+
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix() {
+        let prefix: Prefix = "10.1.2.3/8".parse().unwrap();
+
+        assert_eq!(prefix.addr(), IpAddr::from([10, 0, 0, 0]));
+        assert_eq!(prefix.to_string(), "10.0.0.0/8");
+        assert!(prefix.contains("10.255.0.1".parse().unwrap()));
+        assert!(!prefix.contains("11.0.0.1".parse().unwrap()));
+        assert_eq!("::1".parse::<Prefix>().unwrap().depth(), 128);
+        assert!("10.0.0.0/33".parse::<Prefix>().is_err());
+        assert!("10.0.0/8".parse::<Prefix>().is_err());
+    }
+}
@@ -1,18 +1,22 @@
-use std::cmp;
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
+use std::sync::Mutex;
 
 use libc;
 
 use ffi;
 
-use errors::{rte_error, Result};
+use common::name::{Name, NameKind};
+use errors::{rte_error, AsResult, Result, RteError};
+use ethdev::PortId;
 use ether;
 use mbuf;
 use mempool;
 use pci;
+use utils::AsRaw;
 
 /// Initialize and preallocate KNI subsystem
 pub fn init(max_kni_ifaces: usize) -> Result<()> {
@@ -35,10 +39,12 @@ pub fn close() {
 /// the traditional Linux application talking to.
 ///
 pub fn alloc(
-    pktmbuf_pool: &mut mempool::RawMemoryPool,
+    pktmbuf_pool: mempool::MemPoolRef,
     conf: &KniDeviceConf,
     opts: Option<&KniDeviceOps>,
 ) -> Result<KniDevice> {
+    let name = Name::new(NameKind::Kni, conf.name)?;
+
     unsafe {
         let mut kni_conf = ffi::rte_kni_conf {
             name: mem::zeroed(),
@@ -53,17 +59,73 @@ pub fn alloc(
         };
 
         ptr::copy(
-            conf.name.as_ptr(),
-            kni_conf.name.as_mut_ptr() as *mut u8,
-            cmp::min(conf.name.len(), kni_conf.name.len() - 1),
+            name.as_ptr(),
+            kni_conf.name.as_mut_ptr(),
+            name.as_c_str().to_bytes_with_nul().len(),
         );
 
-        let p = ffi::rte_kni_alloc(pktmbuf_pool, &kni_conf, mem::transmute(opts));
+        let p = ffi::rte_kni_alloc(
+            pktmbuf_pool.as_raw(),
+            &kni_conf,
+            opts.map_or(ptr::null_mut(), |ops| ops as *const _ as *mut _),
+        );
 
         rte_check!(p, NonNull; ok => { KniDevice(p)})
     }
 }
 
+/// Allocate KNI interface, and handle the kernel requests of the port with the Rust closures.
+pub fn alloc_with_handlers(
+    pktmbuf_pool: mempool::MemPoolRef,
+    conf: &KniDeviceConf,
+    port_id: PortId,
+    handlers: KniHandlers,
+) -> Result<KniDevice> {
+    let ops = install_handlers(port_id, handlers);
+
+    alloc(pktmbuf_pool, conf, Some(&ops)).inspect_err(|err| {
+        remove_handlers(port_id);
+    })
+}
+
+/// Allocate the KNI interfaces of a port in the multiple kernel threads mode.
+///
+/// One KNI interface named `<name>_<n>` is allocated for each core, and its kernel thread is bound to the core.
+/// The first one is the master interface which handles the kernel requests with the Rust closures.
+///
+/// If no core is specified, a single KNI interface is allocated with the original name.
+pub fn alloc_group(
+    pktmbuf_pool: mempool::MemPoolRef,
+    conf: &KniDeviceConf,
+    port_id: PortId,
+    kthread_cores: &[u32],
+    handlers: KniHandlers,
+) -> Result<Vec<KniDevice>> {
+    if kthread_cores.is_empty() {
+        return alloc_with_handlers(pktmbuf_pool, conf, port_id, handlers).map(|kni| vec![kni]);
+    }
+
+    let mut handlers = Some(handlers);
+    let mut devices = Vec::with_capacity(kthread_cores.len());
+
+    for (i, &core_id) in kthread_cores.iter().enumerate() {
+        let name = format!("{}_{}", conf.name, i);
+        let conf = KniDeviceConf {
+            name: &name,
+            core_id,
+            flags: conf.flags | KniFlag::FORCE_BIND,
+            ..*conf
+        };
+
+        devices.push(match handlers.take() {
+            Some(handlers) => alloc_with_handlers(pktmbuf_pool, &conf, port_id, handlers)?,
+            None => alloc(pktmbuf_pool, &conf, None)?,
+        });
+    }
+
+    Ok(devices)
+}
+
 bitflags! {
     pub struct KniFlag: u8 {
         const FORCE_BIND = 1;
@@ -106,6 +168,142 @@ pub type ConfigNetworkInterfaceCallback = fn(port_id: u8, if_up: u8) -> libc::c_
 
 pub type KniDeviceOps = ffi::rte_kni_ops;
 
+/// Handler of changing MTU
+pub type ChangeMtuHandler = Box<dyn FnMut(PortId, u32) -> Result<()> + Send>;
+
+/// Handler of configuring network interface up or down
+pub type ConfigNetworkInterfaceHandler = Box<dyn FnMut(PortId, bool) -> Result<()> + Send>;
+
+/// Handler of configuring MAC address
+pub type ConfigMacAddressHandler = Box<dyn FnMut(PortId, ether::EtherAddr) -> Result<()> + Send>;
+
+/// Handler of configuring promiscuous mode
+pub type ConfigPromiscusityHandler = Box<dyn FnMut(PortId, bool) -> Result<()> + Send>;
+
+/// The Rust closures to handle the requests from the kernel side interface.
+///
+/// The handlers are invoked in `KniDevice::handle_requests`,
+/// the kernel request is failed with the errno of `RteError`, or `EINVAL` for other errors.
+#[derive(Default)]
+pub struct KniHandlers {
+    change_mtu: Option<ChangeMtuHandler>,
+    config_network_if: Option<ConfigNetworkInterfaceHandler>,
+    config_mac_address: Option<ConfigMacAddressHandler>,
+    config_promiscusity: Option<ConfigPromiscusityHandler>,
+}
+
+impl KniHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle the request of changing MTU.
+    pub fn on_change_mtu<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(PortId, u32) -> Result<()> + Send + 'static,
+    {
+        self.change_mtu = Some(Box::new(f));
+        self
+    }
+
+    /// Handle the request of configuring network interface up or down.
+    pub fn on_config_network_if<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(PortId, bool) -> Result<()> + Send + 'static,
+    {
+        self.config_network_if = Some(Box::new(f));
+        self
+    }
+
+    /// Handle the request of configuring MAC address.
+    pub fn on_config_mac_address<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(PortId, ether::EtherAddr) -> Result<()> + Send + 'static,
+    {
+        self.config_mac_address = Some(Box::new(f));
+        self
+    }
+
+    /// Handle the request of configuring promiscuous mode.
+    pub fn on_config_promiscusity<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(PortId, bool) -> Result<()> + Send + 'static,
+    {
+        self.config_promiscusity = Some(Box::new(f));
+        self
+    }
+
+    fn ops(&self, port_id: PortId) -> KniDeviceOps {
+        KniDeviceOps {
+            port_id,
+            change_mtu: self.change_mtu.as_ref().map(|_| change_mtu_stub as _),
+            config_network_if: self.config_network_if.as_ref().map(|_| config_network_if_stub as _),
+            config_mac_address: self.config_mac_address.as_ref().map(|_| config_mac_address_stub as _),
+            config_promiscusity: self.config_promiscusity.as_ref().map(|_| config_promiscusity_stub as _),
+        }
+    }
+}
+
+lazy_static! {
+    static ref HANDLERS: Mutex<HashMap<PortId, KniHandlers>> = Mutex::new(HashMap::new());
+}
+
+fn install_handlers(port_id: PortId, handlers: KniHandlers) -> KniDeviceOps {
+    let ops = handlers.ops(port_id);
+
+    HANDLERS.lock().unwrap().insert(port_id, handlers);
+
+    ops
+}
+
+/// Remove the Rust closures which handle the kernel requests of the port.
+///
+/// It should be called after the KNI device was released or its handlers were unregistered.
+pub fn remove_handlers(port_id: PortId) -> bool {
+    HANDLERS.lock().unwrap().remove(&port_id).is_some()
+}
+
+fn handle<F>(port_id: PortId, f: F) -> libc::c_int
+where
+    F: FnOnce(&mut KniHandlers) -> Option<Result<()>>,
+{
+    // the handlers must not install or remove handlers, which will dead lock.
+    match HANDLERS.lock().unwrap().get_mut(&port_id).and_then(f) {
+        Some(Ok(())) => 0,
+        Some(Err(err)) => {
+            warn!("fail to handle KNI request of port {}, {}", port_id, err);
+
+            match err.downcast_ref::<RteError>() {
+                Some(&RteError(errno)) if errno != 0 => -errno.abs(),
+                _ => -libc::EINVAL,
+            }
+        }
+        None => -libc::EOPNOTSUPP,
+    }
+}
+
+unsafe extern "C" fn change_mtu_stub(port_id: PortId, new_mtu: libc::c_uint) -> libc::c_int {
+    handle(port_id, |h| h.change_mtu.as_mut().map(|f| f(port_id, new_mtu)))
+}
+
+unsafe extern "C" fn config_network_if_stub(port_id: PortId, if_up: u8) -> libc::c_int {
+    handle(port_id, |h| {
+        h.config_network_if.as_mut().map(|f| f(port_id, if_up != 0))
+    })
+}
+
+unsafe extern "C" fn config_mac_address_stub(port_id: PortId, mac_addr: *mut u8) -> libc::c_int {
+    let mac_addr = ether::EtherAddr::from(mac_addr);
+
+    handle(port_id, |h| h.config_mac_address.as_mut().map(|f| f(port_id, mac_addr)))
+}
+
+unsafe extern "C" fn config_promiscusity_stub(port_id: PortId, to_on: u8) -> libc::c_int {
+    handle(port_id, |h| {
+        h.config_promiscusity.as_mut().map(|f| f(port_id, to_on != 0))
+    })
+}
+
 pub type RawKniDevice = ffi::rte_kni;
 pub type RawKniDevicePtr = *mut ffi::rte_kni;
 
@@ -160,7 +358,8 @@ impl KniDevice {
 
     /// Get the KNI context of its name.
     pub fn get(name: &str) -> Result<KniDevice> {
-        let p = unsafe { ffi::rte_kni_get(try!(to_cptr!(name))) };
+        let name = Name::new(NameKind::Kni, name)?;
+        let p = unsafe { ffi::rte_kni_get(name.as_ptr()) };
 
         rte_check!(p, NonNull; ok => { KniDevice(p) })
     }
@@ -198,7 +397,7 @@ impl KniDevice {
     /// It handles allocating the mbufs for KNI interface alloc queue.
     ///
     pub fn tx_burst(&self, mbufs: &mut [mbuf::RawMBufPtr]) -> usize {
-        unsafe { ffi::rte_kni_rx_burst(self.0, mbufs.as_mut_ptr(), mbufs.len() as u32) as usize }
+        unsafe { ffi::rte_kni_tx_burst(self.0, mbufs.as_mut_ptr(), mbufs.len() as u32) as usize }
     }
 
     /// Register KNI request handling for a specified port,
@@ -209,6 +408,26 @@ impl KniDevice {
         }; ok => { self })
     }
 
+    /// Register the Rust closures to handle the KNI requests for a specified port.
+    pub fn set_handlers(&self, port_id: PortId, handlers: KniHandlers) -> Result<&Self> {
+        let mut ops = install_handlers(port_id, handlers);
+
+        rte_check!(unsafe {
+            ffi::rte_kni_register_handlers(self.0, &mut ops)
+        }; ok => { self }; err => {
+            remove_handlers(port_id);
+
+            rte_error()
+        })
+    }
+
+    /// Update the link state of the KNI interface in the kernel, returns the previous link state.
+    pub fn update_link(&self, up: bool) -> Result<bool> {
+        unsafe { ffi::rte_kni_update_link(self.0, up as libc::c_uint) }
+            .as_result()
+            .map(|linkup| linkup != 0)
+    }
+
     /// Unregister KNI request handling for a specified port.
     pub fn unregister_handlers(&self) -> Result<&Self> {
         rte_check!(unsafe { ffi::rte_kni_unregister_handlers(self.0) }; ok => { self })
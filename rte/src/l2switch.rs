@@ -0,0 +1,501 @@
+//!
+//! A MAC learning switch.
+//!
+//! The `MacTable` learns the port of the source MAC address of each received packet,
+//! and forgets the addresses which were not seen for the aging time.
+//! With `per_vlan`, each VLAN has its own addresses, as the independent VLAN learning of a bridge,
+//! otherwise all the VLANs share the same table.
+//!
+//! The `L2Switch` forwards the packets to the learned port of their destination,
+//! floods the packets of the unknown, broadcast and multicast destinations to the other ports,
+//! and filters the packets whose destination was learned on the ingress port.
+//!
+//! The switch is owned by a polling lcore, which receives and sends the bursts
+//! with the generic `PacketRx` and `PacketTx` traits, and should `age` the table periodically.
+//!
+//! ```no_run
+//! use std::time::{Duration, Instant};
+//!
+//! use rte::ethdev::{RxQueue, TxQueue};
+//! use rte::l2switch::{L2Switch, SwitchConf};
+//! use rte::*;
+//!
+//! let ports: Vec<ethdev::PortId> = vec![0, 1];
+//! let mut rxs: Vec<RxQueue> = vec![]; // queue 0 of each port
+//! let mut txs: Vec<TxQueue> = vec![]; // queue 0 of each port
+//!
+//! let mut sw = L2Switch::new(ports.clone(), SwitchConf::default().with_per_vlan(true));
+//! let mut last_aging = Instant::now();
+//!
+//! loop {
+//!     let now = Instant::now();
+//!
+//!     for (port_id, rx) in ports.iter().zip(rxs.iter_mut()) {
+//!         sw.switch(*port_id, rx, &mut txs, 32, now);
+//!     }
+//!
+//!     if now.duration_since(last_aging) > Duration::from_secs(1) {
+//!         sw.age(now);
+//!
+//!         last_aging = now;
+//!     }
+//! }
+//! ```
+//!
+use std::collections::hash_map::{Entry, HashMap};
+use std::mem;
+use std::time::{Duration, Instant};
+
+use ethdev::PortId;
+use ether::EtherAddr;
+use filter::dissect_mbuf;
+use mbuf::{MBuf, OffloadFlags};
+use pktio::{PacketRx, PacketTx};
+
+/// The VLAN id of the untagged packets, or of all the packets without `per_vlan`.
+pub const DEFAULT_VLAN: u16 = 0;
+
+const VLAN_ID_MASK: u16 = 0x0fff;
+
+/// The capacity and the aging time of the switch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwitchConf {
+    /// The maximum number of the learned addresses, the new addresses are not learned when the table is full.
+    pub max_entries: usize,
+    /// The time after which an address which was not seen is forgotten.
+    pub aging: Duration,
+    /// Each VLAN has its own table.
+    pub per_vlan: bool,
+}
+
+impl Default for SwitchConf {
+    fn default() -> Self {
+        SwitchConf {
+            max_entries: 4096,
+            aging: Duration::from_secs(300),
+            per_vlan: false,
+        }
+    }
+}
+
+impl SwitchConf {
+    /// Set the maximum number of the learned addresses.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the aging time of the learned addresses.
+    pub fn with_aging(mut self, aging: Duration) -> Self {
+        self.aging = aging;
+        self
+    }
+
+    /// Learn the addresses per VLAN.
+    pub fn with_per_vlan(mut self, per_vlan: bool) -> Self {
+        self.per_vlan = per_vlan;
+        self
+    }
+}
+
+/// A learned address.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MacEntry {
+    /// The port on which the address was seen.
+    pub port_id: PortId,
+    /// The last time the address was seen.
+    pub last_seen: Instant,
+}
+
+/// The result of learning a source address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Learned {
+    /// A new address was learned.
+    New,
+    /// The address was seen on the same port.
+    Refreshed,
+    /// The address moved from the port.
+    Moved(PortId),
+    /// The address is not learned because the table is full.
+    Full,
+    /// A multicast or zero address is never learned.
+    Ignored,
+}
+
+/// The table of the learned addresses, keyed by the VLAN and the MAC address.
+#[derive(Clone, Debug)]
+pub struct MacTable {
+    max_entries: usize,
+    aging: Duration,
+    entries: HashMap<(u16, EtherAddr), MacEntry>,
+}
+
+impl MacTable {
+    /// Create an empty table.
+    pub fn new(max_entries: usize, aging: Duration) -> Self {
+        MacTable {
+            max_entries,
+            aging,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// The number of the learned addresses.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The table has no address.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// An iterator over the learned addresses with their VLAN.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &EtherAddr, &MacEntry)> {
+        self.entries
+            .iter()
+            .map(|(&(vlan, ref addr), entry)| (vlan, addr, entry))
+    }
+
+    /// Learn that the source address was seen on the port.
+    pub fn learn(&mut self, vlan: u16, addr: EtherAddr, port_id: PortId, now: Instant) -> Learned {
+        if addr.is_multicast() || addr.is_zero() {
+            return Learned::Ignored;
+        }
+
+        let full = self.entries.len() >= self.max_entries;
+
+        match self.entries.entry((vlan, addr)) {
+            Entry::Occupied(mut e) => {
+                let entry = e.get_mut();
+                let old = mem::replace(&mut entry.port_id, port_id);
+
+                entry.last_seen = now;
+
+                if old == port_id {
+                    Learned::Refreshed
+                } else {
+                    Learned::Moved(old)
+                }
+            }
+            Entry::Vacant(_) if full => Learned::Full,
+            Entry::Vacant(e) => {
+                e.insert(MacEntry {
+                    port_id,
+                    last_seen: now,
+                });
+
+                Learned::New
+            }
+        }
+    }
+
+    /// The port of the destination address, the expired addresses are ignored until they are aged.
+    pub fn lookup(&self, vlan: u16, addr: &EtherAddr, now: Instant) -> Option<PortId> {
+        self.entries
+            .get(&(vlan, *addr))
+            .filter(|entry| now.duration_since(entry.last_seen) <= self.aging)
+            .map(|entry| entry.port_id)
+    }
+
+    /// Forget the expired addresses, returns the number of the forgotten addresses.
+    pub fn age(&mut self, now: Instant) -> usize {
+        let aging = self.aging;
+        let len = self.entries.len();
+
+        self.entries
+            .retain(|_, entry| now.duration_since(entry.last_seen) <= aging);
+
+        len - self.entries.len()
+    }
+
+    /// Forget the addresses learned on the port, e.g. when its link is down.
+    pub fn flush_port(&mut self, port_id: PortId) -> usize {
+        let len = self.entries.len();
+
+        self.entries.retain(|_, entry| entry.port_id != port_id);
+
+        len - self.entries.len()
+    }
+
+    /// Forget all the addresses.
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+}
+
+/// The forwarding decision of a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Forward {
+    /// Send the packet to the port.
+    Port(PortId),
+    /// Send the packet to all the other ports.
+    Flood,
+    /// Drop the packet, its destination is on the ingress port.
+    Filter,
+}
+
+/// The counters of the switch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwitchStats {
+    /// The number of the received packets.
+    pub received: u64,
+    /// The number of the packets which were forwarded to a learned port.
+    pub forwarded: u64,
+    /// The number of the flooded packets.
+    pub flooded: u64,
+    /// The number of the packets whose destination is on the ingress port.
+    pub filtered: u64,
+    /// The number of the packets which could not be dissected.
+    pub invalid: u64,
+    /// The number of the learned addresses.
+    pub learned: u64,
+    /// The number of the addresses which moved to another port.
+    pub moved: u64,
+    /// The number of the addresses which were not learned because the table is full.
+    pub overflows: u64,
+    /// The number of the aged addresses.
+    pub aged: u64,
+    /// The number of the packets which were dropped because a TX queue is full.
+    pub tx_dropped: u64,
+}
+
+/// A MAC learning switch between the ports.
+#[derive(Debug)]
+pub struct L2Switch {
+    conf: SwitchConf,
+    ports: Vec<PortId>,
+    table: MacTable,
+    stats: SwitchStats,
+    pending: Vec<Vec<MBuf>>,
+    burst: Vec<MBuf>,
+}
+
+impl L2Switch {
+    /// Create a switch between the ports.
+    pub fn new(ports: Vec<PortId>, conf: SwitchConf) -> Self {
+        let pending = ports.iter().map(|_| Vec::new()).collect();
+
+        L2Switch {
+            conf,
+            ports,
+            table: MacTable::new(conf.max_entries, conf.aging),
+            stats: SwitchStats::default(),
+            pending,
+            burst: Vec::new(),
+        }
+    }
+
+    /// The capacity and the aging time of the switch.
+    pub fn config(&self) -> &SwitchConf {
+        &self.conf
+    }
+
+    /// The ports of the switch.
+    pub fn ports(&self) -> &[PortId] {
+        &self.ports
+    }
+
+    /// The learned addresses.
+    pub fn table(&self) -> &MacTable {
+        &self.table
+    }
+
+    /// The learned addresses.
+    pub fn table_mut(&mut self) -> &mut MacTable {
+        &mut self.table
+    }
+
+    /// The counters of the switch.
+    pub fn stats(&self) -> &SwitchStats {
+        &self.stats
+    }
+
+    /// Forget the expired addresses.
+    pub fn age(&mut self, now: Instant) -> usize {
+        let n = self.table.age(now);
+
+        self.stats.aged += n as u64;
+
+        n
+    }
+
+    /// Learn the source address of a packet received on the port, and decide where to forward it.
+    pub fn decide(&mut self, in_port: PortId, vlan: u16, src: EtherAddr, dst: EtherAddr, now: Instant) -> Forward {
+        let vlan = if self.conf.per_vlan { vlan } else { DEFAULT_VLAN };
+
+        self.stats.received += 1;
+
+        match self.table.learn(vlan, src, in_port, now) {
+            Learned::New => self.stats.learned += 1,
+            Learned::Moved(_) => self.stats.moved += 1,
+            Learned::Full => self.stats.overflows += 1,
+            Learned::Refreshed | Learned::Ignored => {}
+        }
+
+        let forward = if dst.is_multicast() {
+            Forward::Flood
+        } else {
+            match self.table.lookup(vlan, &dst, now) {
+                Some(port_id) if port_id == in_port => Forward::Filter,
+                Some(port_id) => Forward::Port(port_id),
+                None => Forward::Flood,
+            }
+        };
+
+        match forward {
+            Forward::Port(_) => self.stats.forwarded += 1,
+            Forward::Flood => self.stats.flooded += 1,
+            Forward::Filter => self.stats.filtered += 1,
+        }
+
+        forward
+    }
+
+    /// Learn the source address of a packet received on the port, and decide where to forward it.
+    ///
+    /// The VLAN is the one stripped by the hardware, or the outermost tag of the packet.
+    pub fn classify(&mut self, in_port: PortId, m: &MBuf, now: Instant) -> Forward {
+        let d = match dissect_mbuf(m) {
+            Some(d) => d,
+            None => {
+                self.stats.invalid += 1;
+
+                return Forward::Filter;
+            }
+        };
+
+        let vlan = if m.offload().contains(OffloadFlags::PKT_RX_VLAN_STRIPPED) {
+            m.vlan_tci & VLAN_ID_MASK
+        } else if d.nb_vlans > 0 {
+            d.vlans[0]
+        } else {
+            DEFAULT_VLAN
+        };
+
+        self.decide(in_port, vlan, d.src_mac, d.dst_mac, now)
+    }
+
+    /// Receive a burst from the port, and send the packets to the TX queues of the ports, in the order of `ports`.
+    ///
+    /// The flooded packets are cloned, so they share the data between the ports,
+    /// the packets which could not be sent are dropped.
+    ///
+    /// Returns the number of the received packets.
+    pub fn switch<R: PacketRx, T: PacketTx>(
+        &mut self,
+        in_port: PortId,
+        rx: &mut R,
+        txs: &mut [T],
+        burst: usize,
+        now: Instant,
+    ) -> usize {
+        debug_assert_eq!(txs.len(), self.ports.len());
+
+        let mut pkts = mem::take(&mut self.burst);
+        let n = rx.rx(&mut pkts, burst);
+
+        for m in pkts.drain(..) {
+            match self.classify(in_port, &m, now) {
+                Forward::Port(port_id) => {
+                    if let Some(idx) = self.ports.iter().position(|&p| p == port_id) {
+                        self.pending[idx].push(m);
+                    }
+                }
+                Forward::Flood => {
+                    let mut outs = self.ports.iter().enumerate().filter(|&(_, &p)| p != in_port);
+
+                    if let Some((last, _)) = outs.next_back() {
+                        for (idx, _) in outs {
+                            self.pending[idx].push(m.clone());
+                        }
+
+                        self.pending[last].push(m);
+                    }
+                }
+                Forward::Filter => {}
+            }
+        }
+
+        self.burst = pkts;
+
+        for (tx, pending) in txs.iter_mut().zip(self.pending.iter_mut()) {
+            if pending.is_empty() {
+                continue;
+            }
+
+            tx.tx(pending);
+
+            self.stats.tx_dropped += pending.len() as u64;
+
+            pending.clear();
+        }
+
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mac_table() {
+        let now = Instant::now();
+        let mut t = MacTable::new(2, Duration::from_secs(10));
+        let a = EtherAddr::new(0, 1, 2, 3, 4, 5);
+        let b = EtherAddr::new(0, 1, 2, 3, 4, 6);
+        let c = EtherAddr::new(0, 1, 2, 3, 4, 7);
+
+        assert_eq!(t.learn(0, EtherAddr::broadcast(), 0, now), Learned::Ignored);
+        assert_eq!(t.learn(0, a, 0, now), Learned::New);
+        assert_eq!(t.learn(0, a, 0, now), Learned::Refreshed);
+        assert_eq!(t.learn(0, a, 1, now), Learned::Moved(0));
+        assert_eq!(t.learn(1, a, 0, now), Learned::New);
+        assert_eq!(t.learn(0, b, 0, now), Learned::Full);
+        assert_eq!(t.len(), 2);
+
+        assert_eq!(t.lookup(0, &a, now), Some(1));
+        assert_eq!(t.lookup(1, &a, now), Some(0));
+        assert_eq!(t.lookup(0, &c, now), None);
+
+        let later = now + Duration::from_secs(11);
+
+        assert_eq!(t.lookup(0, &a, later), None);
+        assert_eq!(t.learn(0, a, 1, later), Learned::Refreshed);
+        assert_eq!(t.age(later), 1);
+        assert_eq!(t.flush_port(1), 1);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn test_decide() {
+        let now = Instant::now();
+        let a = EtherAddr::new(0, 1, 2, 3, 4, 5);
+        let b = EtherAddr::new(0, 1, 2, 3, 4, 6);
+
+        let mut sw = L2Switch::new(vec![0, 1, 2], SwitchConf::default().with_per_vlan(true));
+
+        assert_eq!(sw.decide(0, 10, a, b, now), Forward::Flood);
+        assert_eq!(sw.decide(1, 10, b, a, now), Forward::Port(0));
+        assert_eq!(sw.decide(0, 10, a, b, now), Forward::Port(1));
+        assert_eq!(sw.decide(0, 20, a, b, now), Forward::Flood);
+        assert_eq!(sw.decide(2, 10, b, EtherAddr::broadcast(), now), Forward::Flood);
+        assert_eq!(sw.decide(1, 10, b, a, now), Forward::Port(0));
+        assert_eq!(sw.decide(1, 10, a, b, now), Forward::Filter);
+
+        let stats = sw.stats();
+
+        assert_eq!(stats.received, 7);
+        assert_eq!(stats.forwarded, 3);
+        assert_eq!(stats.flooded, 3);
+        assert_eq!(stats.filtered, 1);
+        assert_eq!(stats.learned, 3);
+        assert_eq!(stats.moved, 3);
+
+        let mut shared = L2Switch::new(vec![0, 1], SwitchConf::default());
+
+        assert_eq!(shared.decide(0, 10, a, b, now), Forward::Flood);
+        assert_eq!(shared.decide(1, 20, b, a, now), Forward::Port(0));
+    }
+}
@@ -15,16 +15,25 @@ extern crate bitflags;
 extern crate failure;
 #[macro_use]
 extern crate lazy_static;
+extern crate arrayvec;
+extern crate bincode;
 extern crate cfile;
 extern crate errno;
 extern crate itertools;
 extern crate libc;
+#[cfg(feature = "mio")]
+extern crate mio;
 extern crate rand;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
 extern crate time;
 #[macro_use]
 extern crate num_derive;
 extern crate num_traits;
 
+extern crate rte_net_types;
 extern crate rte_sys;
 
 pub mod ffi;
@@ -38,18 +47,58 @@ mod common;
 #[macro_use]
 pub mod utils;
 
+pub mod ctrlmsg;
+pub mod diag;
 pub mod mbuf;
 pub mod mempool;
+pub mod meta;
+pub mod metrics;
 pub mod ring;
 
+pub mod cryptodev;
+pub mod distributor;
+pub mod hashers;
+pub mod lpm;
+
 pub mod bond;
+pub mod burst;
 pub mod ethdev;
+pub mod history;
+pub mod intr;
 pub mod kni;
 pub mod pci;
+pub mod pktio;
+pub mod poller;
+pub mod testing;
+pub mod timerwheel;
+pub mod timestamp;
 
+pub mod accounting;
 pub mod arp;
+pub mod conntrack;
+pub mod ecmp;
+pub mod edit;
 pub mod ether;
+pub mod filter;
+pub mod flow;
+pub mod flowsock;
+pub mod fragaware;
+pub mod gtp;
 pub mod ip;
+pub mod ipset;
+pub mod l2switch;
+pub mod mcast;
+pub mod meter;
+pub mod pcapng;
+pub mod pdump;
+pub mod pktlog;
+pub mod route;
+pub mod sctp;
+pub mod sfp;
+pub mod slowpath;
+pub mod softnic;
+pub mod table;
+pub mod sriov;
 
 #[macro_use]
 pub mod cmdline;
@@ -57,6 +106,7 @@ pub mod cmdline;
 pub use self::common::*;
 pub use self::errors::{ErrorKind, Result, RteError};
 pub use self::ethdev::PortId;
+pub use self::ethdev::{PortMask, PortMaskExt};
 pub use self::ethdev::QueueId;
 
 #[cfg(test)]
@@ -0,0 +1,214 @@
+//!
+//! RTE Longest Prefix Match (LPM) tables of the IPv4 and IPv6 routes.
+//!
+//! The `Lpm` and `Lpm6` map the networks to the next hop ids, a lookup finds the id of the longest matched prefix.
+//! The IPv4 table uses the DIR-24-8 algorithm, most lookups take a single memory access.
+//!
+//! The prefix length must be in `1..=32` for IPv4 and `1..=128` for IPv6, the default route is not supported.
+//!
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_uint;
+
+use ffi;
+
+use common::name::{Name, NameKind};
+use errors::{AsResult, Result, RteError};
+use memory::Socket;
+use utils::AsRaw;
+
+pub use ffi::{RTE_LPM6_MAX_DEPTH, RTE_LPM6_NAMESIZE, RTE_LPM_LOOKUP_SUCCESS, RTE_LPM_MAX_DEPTH, RTE_LPM_NAMESIZE};
+
+/// The maximum next hop id of the IPv4 table, which is stored in 24 bits.
+pub const RTE_LPM_MAX_NEXT_HOP: u32 = (1 << 24) - 1;
+
+/// The maximum next hop id of the IPv6 table, which is stored in 21 bits.
+pub const RTE_LPM6_MAX_NEXT_HOP: u32 = (1 << 21) - 1;
+
+/// The capacity of a LPM table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LpmConf {
+    /// The maximum number of the rules.
+    pub max_rules: u32,
+    /// The number of the tbl8 groups, which are used by the rules longer than 24 bits.
+    pub number_tbl8s: u32,
+}
+
+pub type RawLpm = ffi::rte_lpm;
+pub type RawLpmPtr = *mut ffi::rte_lpm;
+
+/// The IPv4 LPM table, which is owned and freed when dropped.
+raw!(pub Lpm(RawLpm));
+
+// the lookups only read the table, which is changed through `&mut self`
+unsafe impl Send for Lpm {}
+unsafe impl Sync for Lpm {}
+
+impl Drop for Lpm {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_lpm_free(self.as_raw()) }
+    }
+}
+
+/// Create an IPv4 LPM table.
+pub fn create<S: AsRef<str>>(name: S, socket: Socket, conf: &LpmConf) -> Result<Lpm> {
+    let name = Name::new(NameKind::Lpm, name)?;
+    let socket = socket.validate()?;
+    let conf = ffi::rte_lpm_config {
+        max_rules: conf.max_rules,
+        number_tbl8s: conf.number_tbl8s,
+        flags: 0,
+    };
+
+    unsafe { ffi::rte_lpm_create(name.as_ptr(), socket.raw(), &conf) }
+        .as_result()
+        .map(Lpm)
+}
+
+impl Lpm {
+    /// Add a rule to the table, or update the next hop of the rule.
+    pub fn add(&mut self, ip: Ipv4Addr, depth: u8, next_hop: u32) -> Result<()> {
+        let ret = unsafe { ffi::rte_lpm_add(self.as_raw(), ip.into(), depth, next_hop) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+
+    /// The next hop of the rule if the table has it.
+    pub fn rule(&self, ip: Ipv4Addr, depth: u8) -> Option<u32> {
+        let mut next_hop = 0;
+
+        if unsafe { ffi::rte_lpm_is_rule_present(self.as_raw(), ip.into(), depth, &mut next_hop) } == 1 {
+            Some(next_hop)
+        } else {
+            None
+        }
+    }
+
+    /// Delete a rule from the table.
+    pub fn delete(&mut self, ip: Ipv4Addr, depth: u8) -> Result<()> {
+        let ret = unsafe { ffi::rte_lpm_delete(self.as_raw(), ip.into(), depth) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+
+    /// Delete all the rules from the table.
+    pub fn delete_all(&mut self) {
+        unsafe { ffi::rte_lpm_delete_all(self.as_raw()) }
+    }
+
+    /// Find the next hop of the longest matched prefix.
+    #[inline]
+    pub fn lookup(&self, ip: Ipv4Addr) -> Option<u32> {
+        let mut next_hop = 0;
+
+        if unsafe { ffi::_rte_lpm_lookup(self.as_raw(), ip.into(), &mut next_hop) } == 0 {
+            Some(next_hop)
+        } else {
+            None
+        }
+    }
+
+    /// Look up a bulk of addresses in host byte order,
+    /// the next hops are tagged with `RTE_LPM_LOOKUP_SUCCESS` if matched.
+    #[inline]
+    pub fn lookup_bulk(&self, ips: &[u32], next_hops: &mut [u32]) {
+        assert!(next_hops.len() >= ips.len());
+
+        unsafe {
+            ffi::_rte_lpm_lookup_bulk(self.as_raw(), ips.as_ptr(), next_hops.as_mut_ptr(), ips.len() as c_uint);
+        }
+    }
+}
+
+pub type RawLpm6 = ffi::rte_lpm6;
+pub type RawLpm6Ptr = *mut ffi::rte_lpm6;
+
+/// The IPv6 LPM table, which is owned and freed when dropped.
+raw!(pub Lpm6(RawLpm6));
+
+// the lookups only read the table, which is changed through `&mut self`
+unsafe impl Send for Lpm6 {}
+unsafe impl Sync for Lpm6 {}
+
+impl Drop for Lpm6 {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_lpm6_free(self.as_raw()) }
+    }
+}
+
+/// Create an IPv6 LPM table.
+pub fn create6<S: AsRef<str>>(name: S, socket: Socket, conf: &LpmConf) -> Result<Lpm6> {
+    let name = Name::new(NameKind::Lpm6, name)?;
+    let socket = socket.validate()?;
+    let conf = ffi::rte_lpm6_config {
+        max_rules: conf.max_rules,
+        number_tbl8s: conf.number_tbl8s,
+        flags: 0,
+    };
+
+    unsafe { ffi::rte_lpm6_create(name.as_ptr(), socket.raw(), &conf) }
+        .as_result()
+        .map(Lpm6)
+}
+
+impl Lpm6 {
+    /// Add a rule to the table, or update the next hop of the rule.
+    pub fn add(&mut self, ip: Ipv6Addr, depth: u8, next_hop: u32) -> Result<()> {
+        let mut ip = ip.octets();
+        let ret = unsafe { ffi::rte_lpm6_add(self.as_raw(), ip.as_mut_ptr(), depth, next_hop) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+
+    /// The next hop of the rule if the table has it.
+    pub fn rule(&self, ip: Ipv6Addr, depth: u8) -> Option<u32> {
+        let mut ip = ip.octets();
+        let mut next_hop = 0;
+
+        if unsafe { ffi::rte_lpm6_is_rule_present(self.as_raw(), ip.as_mut_ptr(), depth, &mut next_hop) } == 1 {
+            Some(next_hop)
+        } else {
+            None
+        }
+    }
+
+    /// Delete a rule from the table.
+    pub fn delete(&mut self, ip: Ipv6Addr, depth: u8) -> Result<()> {
+        let mut ip = ip.octets();
+        let ret = unsafe { ffi::rte_lpm6_delete(self.as_raw(), ip.as_mut_ptr(), depth) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+
+    /// Delete all the rules from the table.
+    pub fn delete_all(&mut self) {
+        unsafe { ffi::rte_lpm6_delete_all(self.as_raw()) }
+    }
+
+    /// Find the next hop of the longest matched prefix.
+    #[inline]
+    pub fn lookup(&self, ip: Ipv6Addr) -> Option<u32> {
+        let mut ip = ip.octets();
+        let mut next_hop = 0;
+
+        if unsafe { ffi::rte_lpm6_lookup(self.as_raw(), ip.as_mut_ptr(), &mut next_hop) } == 0 {
+            Some(next_hop)
+        } else {
+            None
+        }
+    }
+
+    /// Look up a bulk of addresses, the next hops are `-1` if not matched.
+    #[inline]
+    pub fn lookup_bulk(&self, ips: &mut [[u8; 16]], next_hops: &mut [i32]) {
+        assert!(next_hops.len() >= ips.len());
+
+        unsafe {
+            ffi::rte_lpm6_lookup_bulk_func(
+                self.as_raw(),
+                ips.as_mut_ptr(),
+                next_hops.as_mut_ptr(),
+                ips.len() as c_uint,
+            );
+        }
+    }
+}
@@ -21,20 +21,35 @@
 //! Addison-Wesley, 1995, ISBN 0-201-63354-X from Richard Stevens"
 //! http://www.kohala.com/start/tcpipiv2.html
 //!
+use std::cmp;
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::os::raw::c_void;
 use std::os::unix::io::AsRawFd;
 use std::ptr::{self, NonNull};
 use std::slice;
+use std::sync::Mutex;
 
 use cfile;
+use itertools::Itertools;
+use libc;
 
 use ffi;
 
-use errors::{AsResult, Result};
-use mempool;
-use utils::{AsCString, AsRaw, CallbackContext, IntoRaw};
+use common::byteorder;
+use common::name::{Name, NameKind};
+use common::{get_tsc_hz, rdtsc};
+use errors::{AsResult, Result, RteError};
+use ethdev::{PortId, QueueId, TxBurstResult, TxRetryPolicy, TxSegLimits};
+use memory::Socket;
+use mempool::{self, MemPool};
+use utils::{self, AsRaw, CallbackContext, FromRaw, IntoRaw};
 
+pub use arrayvec::ArrayVec;
 pub use ffi::{RTE_MBUF_DEFAULT_BUF_SIZE, RTE_MBUF_DEFAULT_DATAROOM, RTE_MBUF_MAX_NB_SEGS, RTE_MBUF_PRIV_ALIGN};
 
 // Packet Offload Features Flags. It also carry packet type information.
@@ -294,6 +309,14 @@ bitflags! {
     }
 }
 
+/// The flags of RX results stored in the `hash` field of mbuf.
+const HASH_RESULT_FLAGS: OffloadFlags = OffloadFlags {
+    bits: ffi::PKT_RX_RSS_HASH as u64
+        | ffi::PKT_RX_FDIR as u64
+        | ffi::PKT_RX_FDIR_ID as u64
+        | ffi::PKT_RX_FDIR_FLX as u64,
+};
+
 pub type RawMBuf = ffi::rte_mbuf;
 pub type RawMBufPtr = *mut ffi::rte_mbuf;
 
@@ -305,12 +328,18 @@ impl Clone for MBuf {
     fn clone(&self) -> Self {
         let mut m = MBuf(self.0);
         m.refcnt_update(1);
+        balance::on_get(m.as_raw());
         m
     }
 }
 
 impl Drop for MBuf {
     fn drop(&mut self) {
+        #[cfg(feature = "debug-mbuf")]
+        debug::on_free(self);
+
+        balance::on_put(self.as_raw());
+
         if self.refcnt_update(-1) == 0 {
             self.free()
         }
@@ -329,6 +358,22 @@ impl MBuf {
         unsafe { ffi::_rte_mbuf_prefetch_part2(self.as_raw()) }
     }
 
+    /// Take the ownership of an mbuf handed back by DPDK, e.g. dequeued from a ring.
+    ///
+    /// Unlike `from_raw`, the mbuf is no longer marked as freed or transmitted by the `debug-mbuf` feature.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be null or point to a valid mbuf, which is not owned by another `MBuf`.
+    pub unsafe fn acquire(raw: RawMBufPtr) -> Option<Self> {
+        let m = MBuf::from_raw(raw)?;
+
+        #[cfg(feature = "debug-mbuf")]
+        debug::on_acquire(&m);
+
+        Some(m)
+    }
+
     /// Return the mbuf owning the data buffer address of an indirect mbuf.
     pub fn from_indirect(other: &MBuf) -> Self {
         unsafe { ffi::_rte_mbuf_from_indirect(other.as_raw()) }.into()
@@ -474,6 +519,22 @@ impl MBuf {
         self.mtod_offset(0)
     }
 
+    /// Get a read-only view of the header at an offset into the data of the first segment.
+    ///
+    /// Returns `None` if the header is out of the segment data, or not aligned for the type.
+    #[inline]
+    pub fn header<T: Header>(&self, off: usize) -> Option<HeaderView<'_, T>> {
+        HeaderView::new(self, off)
+    }
+
+    /// Get a mutable view of the header at an offset into the data of the first segment.
+    ///
+    /// Returns `None` if the header is out of the segment data, or not aligned for the type.
+    #[inline]
+    pub fn header_mut<T: Header>(&mut self, off: usize) -> Option<HeaderViewMut<'_, T>> {
+        HeaderViewMut::new(self, off)
+    }
+
     /// Return the IO address of the beginning of the mbuf data
     #[inline]
     pub fn data_iova(&self) -> ffi::rte_iova_t {
@@ -510,6 +571,144 @@ impl MBuf {
         self.data_len as usize
     }
 
+    /// Input port.
+    #[inline]
+    pub fn port(&self) -> PortId {
+        self.port
+    }
+
+    /// Set the input port.
+    #[inline]
+    pub fn set_port(&mut self, port_id: PortId) {
+        self.port = port_id
+    }
+
+    /// The L2/L3/L4 and tunnel packet type recognized by the PMD, `RTE_PTYPE_UNKNOWN` if not recognized.
+    #[inline]
+    pub fn packet_type(&self) -> u32 {
+        unsafe { self.__bindgen_anon_3.packet_type }
+    }
+
+    /// The name of the packet type, e.g. `L2_ETHER L3_IPV4 L4_TCP`.
+    pub fn packet_type_name(&self) -> String {
+        let mut buf = [0u8; 256];
+
+        unsafe { ffi::rte_get_ptype_name(self.packet_type(), buf.as_mut_ptr() as *mut _, buf.len()) };
+
+        unsafe { CStr::from_ptr(buf.as_ptr() as *const _) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The RSS hash calculated by the NIC, valid if `PKT_RX_RSS_HASH` is set.
+    #[inline]
+    pub fn rss_hash(&self) -> Option<u32> {
+        if self.offload().contains(OffloadFlags::PKT_RX_RSS_HASH) {
+            Some(unsafe { self.__bindgen_anon_4.hash.rss })
+        } else {
+            None
+        }
+    }
+
+    /// Set the RSS hash, which will be reused instead of recomputing it.
+    #[inline]
+    pub fn set_rss_hash(&mut self, hash: u32) {
+        self.__bindgen_anon_4.hash.rss = hash;
+        self.ol_flags |= OffloadFlags::PKT_RX_RSS_HASH.bits;
+    }
+
+    /// The RX timestamp of the packet, valid if `PKT_RX_TIMESTAMP` is set.
+    ///
+    /// The unit and time reference are not normalized, but are always the same for a given port,
+    /// see `timestamp::source` for the clock of the port.
+    #[inline]
+    pub fn rx_timestamp(&self) -> Option<u64> {
+        if self.offload().contains(OffloadFlags::PKT_RX_TIMESTAMP) {
+            Some(self.timestamp)
+        } else {
+            None
+        }
+    }
+
+    /// Set the RX timestamp of the packet.
+    #[inline]
+    pub fn set_rx_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+        self.ol_flags |= OffloadFlags::PKT_RX_TIMESTAMP.bits;
+    }
+
+    /// The filter identifier of the matched flow director filter, valid if `PKT_RX_FDIR_ID` is set.
+    ///
+    /// It is the mark of the `MARK` action of the matched flow rule.
+    #[inline]
+    pub fn fdir_id(&self) -> Option<u32> {
+        if self
+            .offload()
+            .contains(OffloadFlags::PKT_RX_FDIR | OffloadFlags::PKT_RX_FDIR_ID)
+        {
+            Some(unsafe { self.__bindgen_anon_4.hash.fdir.hi })
+        } else {
+            None
+        }
+    }
+
+    /// The hash and filter id of the matched flow director filter,
+    /// valid if `PKT_RX_FDIR` is set without the `PKT_RX_FDIR_FLX`.
+    #[inline]
+    pub fn fdir_hash(&self) -> Option<(u16, u16)> {
+        let flags = self.offload();
+
+        if flags.contains(OffloadFlags::PKT_RX_FDIR) && !flags.contains(OffloadFlags::PKT_RX_FDIR_FLX) {
+            let fdir = unsafe { self.__bindgen_anon_4.hash.fdir.__bindgen_anon_1.__bindgen_anon_1 };
+
+            Some((fdir.hash, fdir.id))
+        } else {
+            None
+        }
+    }
+
+    /// The flexible bytes reported by the matched flow director filter, as `(hi, lo)`,
+    /// valid if `PKT_RX_FDIR_FLX` is set.
+    #[inline]
+    pub fn fdir_flex_bytes(&self) -> Option<(u32, u32)> {
+        if self
+            .offload()
+            .contains(OffloadFlags::PKT_RX_FDIR | OffloadFlags::PKT_RX_FDIR_FLX)
+        {
+            let fdir = unsafe { self.__bindgen_anon_4.hash.fdir };
+
+            Some((fdir.hi, unsafe { fdir.__bindgen_anon_1.lo }))
+        } else {
+            None
+        }
+    }
+
+    /// The queue selected for the packet, stored in the scheduler metadata.
+    #[inline]
+    pub fn queue(&self) -> QueueId {
+        unsafe { self.__bindgen_anon_4.hash.sched.lo as QueueId }
+    }
+
+    /// Select the queue for the packet, which overwrites the RSS and flow director results.
+    #[inline]
+    pub fn set_queue(&mut self, queue_id: QueueId) {
+        self.__bindgen_anon_4.hash.sched.lo = u32::from(queue_id);
+        self.ol_flags &= !HASH_RESULT_FLAGS.bits;
+    }
+
+    /// User defined tags, shares the storage with the RSS hash.
+    #[inline]
+    pub fn usr(&self) -> u32 {
+        unsafe { self.__bindgen_anon_4.hash.usr }
+    }
+
+    /// Set user defined tags, which overwrites the RSS and flow director results.
+    #[inline]
+    pub fn set_usr(&mut self, usr: u32) {
+        self.__bindgen_anon_4.hash.usr = usr;
+        self.ol_flags &= !HASH_RESULT_FLAGS.bits;
+    }
+
     /// Prepend len bytes to an mbuf data area.
     pub fn prepend(&mut self, len: usize) -> Result<NonNull<u8>> {
         unsafe { ffi::_rte_pktmbuf_prepend(self.as_raw(), len as u16) }
@@ -524,6 +723,118 @@ impl MBuf {
             .map(|p| p.cast())
     }
 
+    /// Prepend the header to the data, and return a mutable view of it.
+    ///
+    /// The `l2_len`, `l3_len` or `l4_len` of the mbuf is increased by the size of the header,
+    /// according to its `Header::LAYER`, so the headers should be pushed from the innermost one.
+    ///
+    /// Returns `ENOSPC` error if there is not enough headroom,
+    /// or `EINVAL` error if the prepended header is not aligned for the type.
+    pub fn push_header<T: Header>(&mut self, hdr: &T) -> Result<HeaderViewMut<'_, T>> {
+        let len = mem::size_of::<T>();
+
+        if len > self.headroom() as usize {
+            return Err(RteError(libc::ENOSPC).into());
+        }
+        if !(self.mtod::<u8>().as_ptr() as usize - len).is_multiple_of(mem::align_of::<T>()) {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        let p = self.prepend(len)?.cast::<T>();
+
+        unsafe { ptr::write(p.as_ptr(), *hdr) };
+
+        self.add_header_len(T::LAYER, len);
+
+        Ok(HeaderViewMut {
+            m: self,
+            off: 0,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Append the header to the data of the last segment, and return a mutable reference to it.
+    ///
+    /// The `l2_len`, `l3_len` or `l4_len` of the mbuf is increased by the size of the header,
+    /// according to its `Header::LAYER`, so the headers should be pushed from the outermost one.
+    ///
+    /// Returns `ENOSPC` error if there is not enough tailroom,
+    /// or `EINVAL` error if the appended header is not aligned for the type.
+    pub fn push_tail<T: Header>(&mut self, hdr: &T) -> Result<&mut T> {
+        let len = mem::size_of::<T>();
+        let last = unsafe { &*ffi::_rte_pktmbuf_lastseg(self.as_raw()) };
+        let tail = last.buf_addr as usize + last.data_off as usize + last.data_len as usize;
+
+        if len > unsafe { ffi::_rte_pktmbuf_tailroom(last as *const _ as *mut _) } as usize {
+            return Err(RteError(libc::ENOSPC).into());
+        }
+        if !tail.is_multiple_of(mem::align_of::<T>()) {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        let p = self.append(len)?.cast::<T>();
+
+        unsafe { ptr::write(p.as_ptr(), *hdr) };
+
+        self.add_header_len(T::LAYER, len);
+
+        Ok(unsafe { &mut *p.as_ptr() })
+    }
+
+    fn add_header_len(&mut self, layer: HeaderLayer, len: usize) {
+        match layer {
+            HeaderLayer::L2 => {
+                let l2_len = self.l2_len() + len;
+                self.set_l2_len(l2_len)
+            }
+            HeaderLayer::L3 => {
+                let l3_len = self.l3_len() + len;
+                self.set_l3_len(l3_len)
+            }
+            HeaderLayer::L4 => {
+                let l4_len = self.l4_len() + len;
+                self.set_l4_len(l4_len)
+            }
+            HeaderLayer::Payload => {}
+        }
+    }
+
+    /// The length of the L2 header, for the TX offloads.
+    #[inline]
+    pub fn l2_len(&self) -> usize {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.l2_len() as usize }
+    }
+
+    /// Set the length of the L2 header, for the TX offloads.
+    #[inline]
+    pub fn set_l2_len(&mut self, len: usize) {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.set_l2_len(len as u64) }
+    }
+
+    /// The length of the L3 header, for the TX offloads.
+    #[inline]
+    pub fn l3_len(&self) -> usize {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.l3_len() as usize }
+    }
+
+    /// Set the length of the L3 header, for the TX offloads.
+    #[inline]
+    pub fn set_l3_len(&mut self, len: usize) {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.set_l3_len(len as u64) }
+    }
+
+    /// The length of the L4 header, for the TX offloads.
+    #[inline]
+    pub fn l4_len(&self) -> usize {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.l4_len() as usize }
+    }
+
+    /// Set the length of the L4 header, for the TX offloads.
+    #[inline]
+    pub fn set_l4_len(&mut self, len: usize) {
+        unsafe { self.__bindgen_anon_6.__bindgen_anon_1.set_l4_len(len as u64) }
+    }
+
     /// Remove len bytes at the beginning of an mbuf.
     pub fn adj(&mut self, len: usize) -> Result<NonNull<u8>> {
         unsafe { ffi::_rte_pktmbuf_adj(self.as_raw(), len as u16) }
@@ -570,6 +881,47 @@ impl MBuf {
             .map(|_| ())
     }
 
+    /// Validate the Tx offload and the segments of the packet against the limits of the device.
+    ///
+    /// Returns `EINVAL` if the packet has more segments than the device could send,
+    /// per packet or per each packet sent by TSO, it should be linearized or dropped before `tx_burst`.
+    pub fn validate_tx_segs(&self, limits: &TxSegLimits) -> Result<()> {
+        self.validate_tx_offload()?;
+
+        let offload = self.offload();
+        let fits = if offload.intersects(OffloadFlags::PKT_TX_TCP_SEG | OffloadFlags::PKT_TX_UDP_SEG) {
+            let mut seg_lens = Vec::with_capacity(self.nb_segs as usize);
+            let mut seg = self.as_raw() as *const ffi::rte_mbuf;
+
+            while let Some(m) = unsafe { seg.as_ref() } {
+                seg_lens.push(m.data_len as usize);
+                seg = m.next;
+            }
+
+            let (hdr_len, mss) = unsafe {
+                let tx_offload = &self.__bindgen_anon_6.__bindgen_anon_1;
+
+                (
+                    (tx_offload.outer_l2_len() + tx_offload.outer_l3_len()) as usize
+                        + self.l2_len()
+                        + self.l3_len()
+                        + self.l4_len(),
+                    tx_offload.tso_segsz() as usize,
+                )
+            };
+
+            limits.fits_tso(&seg_lens, hdr_len, mss)
+        } else {
+            limits.fits(self.nb_segs as usize)
+        };
+
+        if fits {
+            Ok(())
+        } else {
+            Err(RteError(libc::EINVAL).into())
+        }
+    }
+
     /// Linearize data in mbuf.
     ///
     /// This function moves the mbuf data in the first segment if there is enough tailroom.
@@ -588,6 +940,206 @@ impl MBuf {
             }
         }
     }
+
+    /// Dump an mbuf structure to the writer.
+    pub fn dump_to<W: io::Write + ?Sized>(&self, w: &mut W, dump_len: usize) -> Result<()> {
+        utils::capture(w, |f| unsafe {
+            ffi::rte_pktmbuf_dump(f, self.as_raw(), dump_len as u32)
+        })?;
+
+        Ok(())
+    }
+}
+
+/// A header which could be viewed in place from the packet data.
+///
+/// # Safety
+///
+/// The type must be plain old data, which is valid for any bit pattern, e.g. the packed protocol headers.
+pub unsafe trait Header: Copy {
+    /// The layer of the header, which decides the TX offload length updated when it is pushed.
+    const LAYER: HeaderLayer = HeaderLayer::Payload;
+}
+
+/// The layer of a header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderLayer {
+    /// The L2 header, e.g. Ethernet or VLAN, counted in `l2_len`.
+    L2,
+    /// The L3 header, e.g. IPv4 or IPv6, counted in `l3_len`.
+    L3,
+    /// The L4 header, e.g. TCP or SCTP, counted in `l4_len`.
+    L4,
+    /// The other data, which is not counted in any offload length.
+    Payload,
+}
+
+unsafe impl Header for u8 {}
+unsafe impl Header for u16 {}
+unsafe impl Header for u32 {}
+unsafe impl Header for byteorder::be16 {}
+unsafe impl Header for byteorder::be32 {}
+unsafe impl Header for byteorder::be64 {}
+
+#[inline]
+fn header_ptr<T: Header>(m: &MBuf, off: usize) -> Option<NonNull<T>> {
+    if off.checked_add(mem::size_of::<T>())? > m.data_len() {
+        return None;
+    }
+
+    let p = m.mtod_offset::<T>(off);
+
+    if (p.as_ptr() as usize).is_multiple_of(mem::align_of::<T>()) {
+        Some(p)
+    } else {
+        None
+    }
+}
+
+/// A read-only view of the header borrowed from a mbuf.
+pub struct HeaderView<'m, T> {
+    m: &'m MBuf,
+    off: usize,
+    phantom: PhantomData<&'m T>,
+}
+
+impl<'m, T: Header> HeaderView<'m, T> {
+    fn new(m: &'m MBuf, off: usize) -> Option<Self> {
+        header_ptr::<T>(m, off).map(|_| HeaderView {
+            m,
+            off,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The offset of the header into the data.
+    pub fn offset(&self) -> usize {
+        self.off
+    }
+
+    /// The mbuf of the header.
+    pub fn mbuf(&self) -> &'m MBuf {
+        self.m
+    }
+
+    /// View the next header which immediately follows this header.
+    pub fn next<U: Header>(&self) -> Option<HeaderView<'m, U>> {
+        self.next_at(mem::size_of::<T>())
+    }
+
+    /// View the next header at an offset from the start of this header, e.g. after the IPv4 options.
+    pub fn next_at<U: Header>(&self, len: usize) -> Option<HeaderView<'m, U>> {
+        HeaderView::new(self.m, self.off.checked_add(len)?)
+    }
+
+    /// The remaining data of the segment, start from this header.
+    pub fn bytes(&self) -> &'m [u8] {
+        unsafe {
+            slice::from_raw_parts(
+                self.m.mtod_offset::<u8>(self.off).as_ptr(),
+                self.m.data_len() - self.off,
+            )
+        }
+    }
+}
+
+impl<'m, T: Header> Deref for HeaderView<'m, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.m.mtod_offset::<T>(self.off).as_ptr() }
+    }
+}
+
+/// A mutable view of the header borrowed from a mbuf.
+pub struct HeaderViewMut<'m, T> {
+    m: &'m mut MBuf,
+    off: usize,
+    phantom: PhantomData<&'m mut T>,
+}
+
+impl<'m, T: Header> HeaderViewMut<'m, T> {
+    fn new(m: &'m mut MBuf, off: usize) -> Option<Self> {
+        header_ptr::<T>(m, off)?;
+
+        Some(HeaderViewMut {
+            m,
+            off,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The offset of the header into the data.
+    pub fn offset(&self) -> usize {
+        self.off
+    }
+
+    /// View the next header which immediately follows this header, consumes this view.
+    pub fn into_next<U: Header>(self) -> Option<HeaderViewMut<'m, U>> {
+        self.into_next_at(mem::size_of::<T>())
+    }
+
+    /// View the next header at an offset from the start of this header, consumes this view.
+    pub fn into_next_at<U: Header>(self, len: usize) -> Option<HeaderViewMut<'m, U>> {
+        HeaderViewMut::new(self.m, self.off.checked_add(len)?)
+    }
+
+    /// View the next header which immediately follows this header, reborrow this view.
+    pub fn next_mut<U: Header>(&mut self) -> Option<HeaderViewMut<'_, U>> {
+        HeaderViewMut::new(self.m, self.off.checked_add(mem::size_of::<T>())?)
+    }
+
+    /// Split the view into this header and the view of the next header, which immediately follows this header.
+    pub fn split<U: Header>(self) -> Option<(&'m mut T, HeaderViewMut<'m, U>)> {
+        let len = mem::size_of::<T>();
+
+        self.split_at(len)
+    }
+
+    /// Split the view into this header and the view of the next header at an offset from the start of this header.
+    pub fn split_at<U: Header>(self, len: usize) -> Option<(&'m mut T, HeaderViewMut<'m, U>)> {
+        if len < mem::size_of::<T>() {
+            return None;
+        }
+
+        let off = self.off.checked_add(len)?;
+
+        header_ptr::<U>(self.m, off)?;
+
+        let hdr = unsafe { &mut *self.m.mtod_offset::<T>(self.off).as_ptr() };
+        let next = HeaderViewMut {
+            m: self.m,
+            off,
+            phantom: PhantomData,
+        };
+
+        // `hdr` and `next` point to the disjoint ranges of the data
+        Some((hdr, next))
+    }
+
+    /// The remaining data of the segment, start from this header.
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.m.mtod_offset::<u8>(self.off).as_ptr(),
+                self.m.data_len() - self.off,
+            )
+        }
+    }
+}
+
+impl<'m, T: Header> Deref for HeaderViewMut<'m, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.m.mtod_offset::<T>(self.off).as_ptr() }
+    }
+}
+
+impl<'m, T: Header> DerefMut for HeaderViewMut<'m, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.m.mtod_offset::<T>(self.off).as_ptr() }
+    }
 }
 
 pub type RawExtSharedInfo = ffi::rte_mbuf_ext_shared_info;
@@ -680,6 +1232,8 @@ impl MBuf {
     /// Attach packet mbuf to another packet mbuf.
     pub fn attach(&mut self, m: &MBuf) {
         unsafe { ffi::_rte_pktmbuf_attach(self.as_raw(), m.as_raw()) }
+
+        m.copy_meta_to(self);
     }
 
     /// Detach a packet mbuf from external buffer or direct buffer.
@@ -688,6 +1242,16 @@ impl MBuf {
     }
 }
 
+/// The private data of a pktmbuf pool.
+pub type PktMBufPoolPrivate = ffi::rte_pktmbuf_pool_private;
+
+/// Check the elements of a mempool could hold the mbufs described by the pool private data.
+fn is_pktmbuf_layout(elt_size: usize, private: &PktMBufPoolPrivate) -> bool {
+    (private.mbuf_priv_size as u32).is_multiple_of(ffi::RTE_MBUF_PRIV_ALIGN)
+        && elt_size
+            >= mem::size_of::<RawMBuf>() + private.mbuf_priv_size as usize + private.mbuf_data_room_size as usize
+}
+
 pub trait MBufPool {
     /// Get the data room size of mbufs stored in a pktmbuf_pool
     fn data_room_size(&self) -> usize;
@@ -695,17 +1259,42 @@ pub trait MBufPool {
     /// Get the application private size of mbufs stored in a pktmbuf_pool
     fn priv_size(&self) -> usize;
 
+    /// The private data of the pktmbuf pool, or `None` if the mempool is not a pktmbuf pool.
+    ///
+    /// The private data must fit the element size, and the first populated element
+    /// must be a mbuf initialized for the mempool.
+    fn pool_private(&self) -> Option<PktMBufPoolPrivate>;
+
+    /// The mempool is a pktmbuf pool.
+    fn is_pktmbuf_pool(&self) -> bool {
+        self.pool_private().is_some()
+    }
+
+    /// Check the mempool could be used to receive packets, returns `EINVAL` if it isn't a pktmbuf pool,
+    /// or the data room of the mbufs has no space for the packet data after the headroom.
+    fn check_rx_pool(&self) -> Result<()> {
+        match self.pool_private() {
+            Some(private) if private.mbuf_data_room_size as u32 > ffi::RTE_PKTMBUF_HEADROOM => Ok(()),
+            _ => Err(RteError(libc::EINVAL).into()),
+        }
+    }
+
     /// Allocate a new mbuf from a mempool.
     fn alloc(&mut self) -> Result<MBuf>;
 
     /// Allocate a bulk of mbufs, initialize refcnt and reset the fields to default values.
     fn alloc_bulk(&mut self, mbufs: &mut [Option<MBuf>]) -> Result<()>;
 
+    /// Allocate a bulk of mbufs into the uninitialized array, returns the number of allocated mbufs.
+    ///
+    /// The mbufs are allocated all or nothing, so the whole array is initialized on success.
+    fn alloc_bulk_uninit(&mut self, mbufs: &mut [mem::MaybeUninit<MBuf>]) -> Result<usize>;
+
     /// Creates a "clone" of the given packet mbuf.
     fn clone(&mut self, mbuf: &MBuf) -> Result<MBuf>;
 }
 
-impl MBufPool for mempool::MemoryPool {
+impl<P: mempool::MemPool> MBufPool for P {
     fn data_room_size(&self) -> usize {
         unsafe { ffi::_rte_pktmbuf_data_room_size(self.as_raw()) as usize }
     }
@@ -714,42 +1303,426 @@ impl MBufPool for mempool::MemoryPool {
         unsafe { ffi::_rte_pktmbuf_priv_size(self.as_raw()) as usize }
     }
 
+    fn pool_private(&self) -> Option<PktMBufPoolPrivate> {
+        if (self.private_data_size as usize) < mem::size_of::<PktMBufPoolPrivate>() {
+            return None;
+        }
+
+        let private = unsafe { *self.get_priv::<PktMBufPoolPrivate>() };
+
+        if !is_pktmbuf_layout(self.elt_size as usize, &private) {
+            return None;
+        }
+
+        let hdr = self.elt_list.stqh_first;
+
+        if !hdr.is_null() {
+            // the object follows its header
+            let m = unsafe { &*(hdr.add(1) as *const RawMBuf) };
+
+            if m.pool != self.as_raw() || m.priv_size != private.mbuf_priv_size {
+                return None;
+            }
+        }
+
+        Some(private)
+    }
+
     fn alloc(&mut self) -> Result<MBuf> {
-        unsafe { ffi::_rte_pktmbuf_alloc(self.as_raw()) }.as_result().map(MBuf)
+        let m = unsafe { ffi::_rte_pktmbuf_alloc(self.as_raw()) }
+            .as_result()
+            .map(MBuf)?;
+
+        #[cfg(feature = "debug-mbuf")]
+        debug::on_alloc(&m);
+
+        balance::on_get(m.as_raw());
+
+        Ok(m)
     }
 
     fn alloc_bulk(&mut self, mbufs: &mut [Option<MBuf>]) -> Result<()> {
         unsafe { ffi::_rte_pktmbuf_alloc_bulk(self.as_raw(), mbufs.as_mut_ptr() as *mut _, mbufs.len() as u32) }
-            .as_result()
-            .map(|_| ())
+            .as_result()?;
+
+        #[cfg(feature = "debug-mbuf")]
+        debug::on_alloc_bulk(mbufs);
+
+        balance::on_get_burst(mbufs.iter().flatten().map(|m| m.as_raw()));
+
+        Ok(())
+    }
+
+    fn alloc_bulk_uninit(&mut self, mbufs: &mut [mem::MaybeUninit<MBuf>]) -> Result<usize> {
+        unsafe { ffi::_rte_pktmbuf_alloc_bulk(self.as_raw(), mbufs.as_mut_ptr() as *mut _, mbufs.len() as u32) }
+            .as_result()?;
+
+        #[cfg(feature = "debug-mbuf")]
+        mbufs.iter().for_each(|m| debug::on_alloc(unsafe { &*m.as_ptr() }));
+
+        balance::on_get_burst(mbufs.iter().map(|m| unsafe { (*m.as_ptr()).as_raw() }));
+
+        Ok(mbufs.len())
     }
 
     fn clone(&mut self, mbuf: &MBuf) -> Result<MBuf> {
-        unsafe { ffi::_rte_pktmbuf_clone(mbuf.as_raw(), self.as_raw()) }
+        let mut m = unsafe { ffi::_rte_pktmbuf_clone(mbuf.as_raw(), self.as_raw()) }
             .as_result()
-            .map(MBuf)
+            .map(MBuf)?;
+
+        mbuf.copy_meta_to(&mut m);
+
+        #[cfg(feature = "debug-mbuf")]
+        debug::on_alloc(&m);
+
+        balance::on_get(m.as_raw());
+
+        Ok(m)
     }
 }
 
-/// Create a mbuf pool.
-///
-/// This function creates and initializes a packet mbuf pool.
-/// It is a wrapper to rte_mempool_create() with the proper packet constructor
-/// and mempool constructor.
-pub fn pool_create<S: AsRef<str>>(
-    name: S,
-    n: u32,
-    cache_size: u32,
-    priv_size: u16,
-    data_room_size: u16,
-    socket_id: i32,
-) -> Result<mempool::MemoryPool> {
-    let name = name.as_cstring();
+/// The common number of packets in a burst.
+pub const DEFAULT_BURST: usize = 32;
 
-    unsafe { ffi::rte_pktmbuf_pool_create(name.as_ptr(), n, cache_size, priv_size, data_room_size, socket_id) }
-        .as_result()
-        .map(|p| p.as_ptr())
-        .map(mempool::MemoryPool::from)
+/// The burst of the common size.
+pub type DefaultBurst = ArrayBurst<DEFAULT_BURST>;
+
+/// A fixed capacity array of mbufs, which are received or allocated in bursts.
+///
+/// The array is not initialized, only the prefix of `len()` mbufs is owned by the burst,
+/// so the hot loop handles plain `MBuf` without the `Option` checks and unwraps.
+///
+/// ```no_run
+/// use rte::ethdev::EthDevice;
+/// use rte::mbuf::DefaultBurst;
+///
+/// let mut pkts = DefaultBurst::new();
+///
+/// loop {
+///     if pkts.rx(0, 0) == 0 {
+///         continue;
+///     }
+///
+///     for m in pkts.iter_mut() {
+///         // handle the packet
+///     }
+///
+///     // the unsent packets are kept for the next round
+///     pkts.tx(1, 0);
+/// }
+/// ```
+pub struct ArrayBurst<const N: usize> {
+    pkts: [mem::MaybeUninit<MBuf>; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for ArrayBurst<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Drop for ArrayBurst<N> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<const N: usize> Deref for ArrayBurst<N> {
+    type Target = [MBuf];
+
+    fn deref(&self) -> &[MBuf] {
+        unsafe { slice::from_raw_parts(self.pkts.as_ptr() as *const MBuf, self.len) }
+    }
+}
+
+impl<const N: usize> DerefMut for ArrayBurst<N> {
+    fn deref_mut(&mut self) -> &mut [MBuf] {
+        unsafe { slice::from_raw_parts_mut(self.pkts.as_mut_ptr() as *mut MBuf, self.len) }
+    }
+}
+
+impl<const N: usize> ArrayBurst<N> {
+    /// Create an empty burst.
+    pub fn new() -> Self {
+        ArrayBurst {
+            // an array of `MaybeUninit` doesn't need initialization
+            pkts: unsafe { mem::MaybeUninit::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// The maximum number of mbufs in the burst.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The number of mbufs in the burst.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The burst has no mbuf.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The burst has no room for more mbufs.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// The uninitialized room after the mbufs.
+    #[inline]
+    pub fn spare_mut(&mut self) -> &mut [mem::MaybeUninit<MBuf>] {
+        &mut self.pkts[self.len..]
+    }
+
+    /// Mark the first `len` mbufs of the burst as initialized.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed `N`, and the caller must have written the new mbufs to the front of `spare_mut()`.
+    #[inline]
+    pub unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= N);
+
+        self.len = len;
+    }
+
+    /// Append a mbuf, or give it back if the burst is full.
+    pub fn push(&mut self, m: MBuf) -> ::std::result::Result<(), MBuf> {
+        if self.is_full() {
+            Err(m)
+        } else {
+            self.pkts[self.len] = mem::MaybeUninit::new(m);
+            self.len += 1;
+
+            Ok(())
+        }
+    }
+
+    /// Remove the last mbuf.
+    pub fn pop(&mut self) -> Option<MBuf> {
+        if self.is_empty() {
+            None
+        } else {
+            self.len -= 1;
+
+            Some(unsafe { ptr::read(self.pkts[self.len].as_ptr()) })
+        }
+    }
+
+    /// Free all the mbufs.
+    pub fn clear(&mut self) {
+        let len = self.len;
+
+        // don't free the mbufs twice if a drop panics
+        self.len = 0;
+
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.pkts.as_mut_ptr() as *mut MBuf, len)) }
+    }
+
+    /// Move all the mbufs out of the burst.
+    pub fn drain(&mut self) -> Drain<'_, N> {
+        let end = self.len;
+
+        self.len = 0;
+
+        Drain {
+            burst: self,
+            idx: 0,
+            end,
+        }
+    }
+
+    /// Receive the packets into the spare room, returns the number of received packets.
+    pub fn rx(&mut self, port_id: PortId, queue_id: QueueId) -> usize {
+        use ethdev::EthDevice;
+
+        let n = port_id.rx_burst_uninit(queue_id, self.spare_mut());
+
+        self.len += n;
+
+        n
+    }
+
+    /// Allocate mbufs from the pool to fill the spare room.
+    ///
+    /// The mbufs are allocated all or nothing.
+    pub fn alloc<P: MBufPool>(&mut self, pool: &mut P) -> Result<usize> {
+        let n = pool.alloc_bulk_uninit(self.spare_mut())?;
+
+        self.len += n;
+
+        Ok(n)
+    }
+
+    /// Send the packets, returns the number of sent packets.
+    ///
+    /// The sent packets are owned by the driver, the unsent ones are moved to the front of the burst,
+    /// so they could be sent again or freed.
+    pub fn tx(&mut self, port_id: PortId, queue_id: QueueId) -> usize {
+        use ethdev::EthDevice;
+
+        let n = port_id.tx_burst(queue_id, &mut self[..]);
+
+        self.consume(n);
+
+        n
+    }
+
+    /// Send all the packets, retrying the unsent ones with the policy.
+    ///
+    /// The packets still unsent after the retries are freed, so the burst is empty afterwards.
+    pub fn tx_all(&mut self, port_id: PortId, queue_id: QueueId, policy: &TxRetryPolicy) -> TxBurstResult {
+        use ethdev::EthDevice;
+
+        let total = self.len;
+        let res = policy.run(total, |off| port_id.tx_burst(queue_id, &mut self[off..total]));
+
+        self.consume(res.sent);
+        self.clear();
+
+        res
+    }
+
+    /// Forget the first `n` mbufs, which are owned by someone else, and move the rest to the front.
+    fn consume(&mut self, n: usize) {
+        let n = cmp::min(n, self.len);
+
+        if n > 0 {
+            unsafe {
+                let p = self.pkts.as_mut_ptr();
+
+                ptr::copy(p.add(n), p, self.len - n);
+            }
+
+            self.len -= n;
+        }
+    }
+}
+
+/// The iterator which moves the mbufs out of a `ArrayBurst`.
+pub struct Drain<'a, const N: usize> {
+    burst: &'a mut ArrayBurst<N>,
+    idx: usize,
+    end: usize,
+}
+
+impl<'a, const N: usize> Iterator for Drain<'a, N> {
+    type Item = MBuf;
+
+    fn next(&mut self) -> Option<MBuf> {
+        if self.idx < self.end {
+            let m = unsafe { ptr::read(self.burst.pkts[self.idx].as_ptr()) };
+
+            self.idx += 1;
+
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.idx;
+
+        (n, Some(n))
+    }
+}
+
+impl<'a, const N: usize> ExactSizeIterator for Drain<'a, N> {}
+
+impl<'a, const N: usize> Drop for Drain<'a, N> {
+    fn drop(&mut self) {
+        self.for_each(drop)
+    }
+}
+
+/// The statistics of `linearize_burst`, which show how often the chained mbufs are received.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LinearizeStats {
+    /// The number of checked packets.
+    pub packets: u64,
+    /// The number of chained packets, which were linearized in place.
+    pub linearized: u64,
+    /// The number of chained packets, which were dropped for lack of tailroom in the first segment.
+    pub dropped: u64,
+}
+
+impl LinearizeStats {
+    /// The ratio of chained packets in the checked packets.
+    pub fn chained_ratio(&self) -> f64 {
+        if self.packets == 0 {
+            0.0
+        } else {
+            (self.linearized + self.dropped) as f64 / self.packets as f64
+        }
+    }
+}
+
+/// Linearize the chained mbufs of a burst, for the code paths which cannot handle segments.
+///
+/// The packets which cannot be linearized in place are freed, and the remaining packets
+/// are moved to the front of the burst in order, returns the number of remaining packets.
+pub fn linearize_burst(pkts: &mut [Option<MBuf>], stats: &mut LinearizeStats) -> usize {
+    let mut n = 0;
+
+    for i in 0..pkts.len() {
+        let keep = match pkts[i] {
+            Some(ref m) => {
+                stats.packets += 1;
+
+                if m.is_contiguous() {
+                    true
+                } else if m.linearize().is_ok() {
+                    stats.linearized += 1;
+                    true
+                } else {
+                    stats.dropped += 1;
+                    false
+                }
+            }
+            None => continue,
+        };
+
+        if keep {
+            pkts.swap(n, i);
+            n += 1;
+        } else {
+            pkts[i] = None;
+        }
+    }
+
+    n
+}
+
+/// Create a mbuf pool.
+///
+/// This function creates and initializes a packet mbuf pool.
+/// It is a wrapper to rte_mempool_create() with the proper packet constructor
+/// and mempool constructor.
+pub fn pool_create<S: AsRef<str>>(
+    name: S,
+    n: u32,
+    cache_size: u32,
+    priv_size: u16,
+    data_room_size: u16,
+    socket: Socket,
+) -> Result<mempool::MemoryPool> {
+    let name = Name::new(NameKind::MemPool, name)?;
+
+    let socket = socket.validate()?;
+
+    unsafe { ffi::rte_pktmbuf_pool_create(name.as_ptr(), n, cache_size, priv_size, data_room_size, socket.raw()) }
+        .as_result()
+        .map(|p| p.as_ptr())
+        .map(mempool::MemoryPool::from)
 }
 
 /// Create a mbuf pool with a given mempool ops name
@@ -762,11 +1735,12 @@ pub fn pool_create_by_ops<S: AsRef<str>>(
     cache_size: u32,
     priv_size: u16,
     data_room_size: u16,
-    socket_id: i32,
+    socket: Socket,
     ops_name: S,
 ) -> Result<mempool::MemoryPool> {
-    let name = name.as_cstring();
-    let ops_name = ops_name.as_cstring();
+    let name = Name::new(NameKind::MemPool, name)?;
+    let ops_name = Name::new(NameKind::MemPoolOps, ops_name)?;
+    let socket = socket.validate()?;
 
     unsafe {
         ffi::rte_pktmbuf_pool_create_by_ops(
@@ -775,7 +1749,7 @@ pub fn pool_create_by_ops<S: AsRef<str>>(
             cache_size,
             priv_size,
             data_room_size,
-            socket_id,
+            socket.raw(),
             ops_name.as_ptr(),
         )
     }
@@ -783,3 +1757,748 @@ pub fn pool_create_by_ops<S: AsRef<str>>(
     .map(|p| p.as_ptr())
     .map(mempool::MemoryPool::from)
 }
+
+/// The mempool ops which are benchmarked by `pool_create_auto` by default.
+pub const CANDIDATE_OPS: &[&str] = &["ring_mp_mc", "stack", "bucket"];
+
+/// The parameters of the mempool ops benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpsBenchConfig {
+    /// The mempool ops to compare, the ones which are not registered are skipped.
+    pub candidates: Vec<String>,
+    /// The number of mbufs allocated and freed in a burst.
+    pub burst: usize,
+    /// The number of bursts of each ops.
+    pub rounds: usize,
+}
+
+impl Default for OpsBenchConfig {
+    fn default() -> Self {
+        OpsBenchConfig {
+            candidates: CANDIDATE_OPS.iter().map(|s| s.to_string()).collect(),
+            burst: 32,
+            rounds: 10_000,
+        }
+    }
+}
+
+/// The alloc/free throughput of a mempool ops.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpsScore {
+    /// The name of the mempool ops.
+    pub ops: String,
+    /// The number of mbufs allocated and freed.
+    pub mbufs: u64,
+    /// The TSC cycles spent to allocate and free the mbufs.
+    pub cycles: u64,
+}
+
+impl OpsScore {
+    /// The average TSC cycles to allocate and free a mbuf.
+    pub fn cycles_per_mbuf(&self) -> f64 {
+        if self.mbufs == 0 {
+            f64::INFINITY
+        } else {
+            self.cycles as f64 / self.mbufs as f64
+        }
+    }
+
+    /// The number of mbufs allocated and freed per second.
+    pub fn mbufs_per_sec(&self) -> f64 {
+        get_tsc_hz() as f64 / self.cycles_per_mbuf()
+    }
+}
+
+/// The fastest mempool ops of the scores.
+pub fn fastest_ops(scores: &[OpsScore]) -> Option<&OpsScore> {
+    scores
+        .iter()
+        .filter(|score| score.mbufs > 0)
+        .min_by(|lhs, rhs| lhs.cycles_per_mbuf().partial_cmp(&rhs.cycles_per_mbuf()).unwrap())
+}
+
+/// Measure the alloc/free throughput of the mempool ops, with the parameters of the mbuf pool.
+///
+/// A temporary pool is created for each registered candidate, and freed after the measure,
+/// so there must be enough memory for one more pool. The candidates which fail to create
+/// the pool, for example the `bucket` ops with a too small pool, are skipped.
+///
+/// It should be called on an EAL thread, so the per-lcore cache of the pool is used.
+pub fn benchmark_ops(
+    n: u32,
+    cache_size: u32,
+    priv_size: u16,
+    data_room_size: u16,
+    socket: Socket,
+    conf: &OpsBenchConfig,
+) -> Vec<OpsScore> {
+    let registered = mempool::registered_ops();
+    let burst = cmp::max(cmp::min(conf.burst, n as usize), 1);
+    let mut scores = vec![];
+
+    for (idx, ops) in conf.candidates.iter().enumerate() {
+        if !registered.contains(ops) {
+            debug!("skip the mempool ops `{}`, which is not registered", ops);
+
+            continue;
+        }
+
+        let name = format!("ops_bench_{}", idx);
+        let mut pool = match pool_create_by_ops(
+            name.as_str(),
+            n,
+            cache_size,
+            priv_size,
+            data_room_size,
+            socket,
+            ops.as_str(),
+        ) {
+            Ok(pool) => pool,
+            Err(err) => {
+                warn!("skip the mempool ops `{}`, fail to create pool, {}", ops, err);
+
+                continue;
+            }
+        };
+
+        let mut mbufs: Vec<Option<MBuf>> = (0..burst).map(|_| None).collect();
+
+        // warm up the caches
+        if pool.alloc_bulk(&mut mbufs).is_ok() {
+            mbufs.iter_mut().for_each(|m| drop(m.take()));
+        }
+
+        let mut score = OpsScore {
+            ops: ops.clone(),
+            mbufs: 0,
+            cycles: 0,
+        };
+
+        for _ in 0..conf.rounds {
+            let start = rdtsc();
+
+            if pool.alloc_bulk(&mut mbufs).is_err() {
+                break;
+            }
+
+            mbufs.iter_mut().for_each(|m| drop(m.take()));
+
+            score.cycles += rdtsc() - start;
+            score.mbufs += burst as u64;
+        }
+
+        debug!(
+            "mempool ops `{}`: {:.1} cycles/mbuf, {:.2} Mmbufs/s",
+            ops,
+            score.cycles_per_mbuf(),
+            score.mbufs_per_sec() / 1e6
+        );
+
+        scores.push(score);
+    }
+
+    scores
+}
+
+/// The parameters of a mbuf pool, which the ops picked by the benchmark are reused for.
+type PoolParams = (u32, u32, u16, u16, Socket);
+
+lazy_static! {
+    static ref AUTO_OPS: Mutex<HashMap<PoolParams, String>> = Mutex::new(HashMap::new());
+}
+
+/// Create a mbuf pool with the fastest mempool ops on this machine.
+///
+/// The benchmark is opt-in: without the `bench` parameters, the ops picked by an earlier
+/// benchmark with the same pool parameters is reused, or the default ops of the platform is used.
+/// The decision is cached per pool parameters, so only the first pool pays for the benchmark.
+pub fn pool_create_auto<S: AsRef<str>>(
+    name: S,
+    n: u32,
+    cache_size: u32,
+    priv_size: u16,
+    data_room_size: u16,
+    socket: Socket,
+    bench: Option<&OpsBenchConfig>,
+) -> Result<mempool::MemoryPool> {
+    let key = (n, cache_size, priv_size, data_room_size, socket);
+    let mut picked = AUTO_OPS.lock().unwrap().get(&key).cloned();
+
+    if picked.is_none() {
+        if let Some(conf) = bench {
+            let scores = benchmark_ops(n, cache_size, priv_size, data_room_size, socket, conf);
+
+            if let Some(best) = fastest_ops(&scores) {
+                info!(
+                    "pick the mempool ops `{}` for {} mbufs, {:.1} cycles/mbuf, out of {}",
+                    best.ops,
+                    n,
+                    best.cycles_per_mbuf(),
+                    scores
+                        .iter()
+                        .map(|score| format!("{} ({:.1})", score.ops, score.cycles_per_mbuf()))
+                        .join(", ")
+                );
+
+                AUTO_OPS.lock().unwrap().insert(key, best.ops.clone());
+
+                picked = Some(best.ops.clone());
+            } else {
+                warn!("no mempool ops could be benchmarked, use the default ops");
+            }
+        }
+    }
+
+    match picked {
+        Some(ops) => pool_create_by_ops(
+            name.as_ref(),
+            n,
+            cache_size,
+            priv_size,
+            data_room_size,
+            socket,
+            ops.as_str(),
+        ),
+        None => {
+            let pool = pool_create(name, n, cache_size, priv_size, data_room_size, socket)?;
+
+            info!("use the default mempool ops `{}` for {} mbufs", pool.ops_name(), n);
+
+            Ok(pool)
+        }
+    }
+}
+
+/// Sanity checks of the mbufs, enabled by the `debug-mbuf` feature.
+///
+/// The mbufs passed to the safe wrappers are checked for consistency,
+/// the data room of the freed mbufs is poisoned, and freeing an mbuf
+/// which was already freed, or handed to the driver by `tx_burst`, panics.
+///
+/// The mbufs handed back by DPDK as raw pointers, e.g. dequeued from a ring,
+/// should be taken with `MBuf::acquire`, since their addresses may have been freed or transmitted before.
+#[cfg(feature = "debug-mbuf")]
+pub mod debug {
+    use std::collections::HashMap;
+    use std::ptr;
+    use std::sync::Mutex;
+
+    use ffi;
+
+    use utils::AsRaw;
+
+    use super::{MBuf, OffloadFlags, RawMBuf};
+
+    /// The byte pattern filled in the data room of the freed mbufs.
+    pub const MBUF_POISON: u8 = 0x6b;
+
+    /// The reason why an mbuf is no longer owned by the application.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Released {
+        Freed,
+        Transmitted,
+    }
+
+    lazy_static! {
+        static ref RELEASED: Mutex<HashMap<usize, Released>> = Mutex::new(HashMap::new());
+    }
+
+    fn sanity_check(m: *mut RawMBuf) {
+        unsafe { ffi::rte_mbuf_sanity_check(m, 1) }
+    }
+
+    /// Check the mbuf and mark it as owned by the application.
+    pub fn on_alloc(m: &MBuf) {
+        sanity_check(m.as_raw());
+
+        RELEASED.lock().unwrap().remove(&(m.as_raw() as usize));
+    }
+
+    /// Mark the mbuf handed back by DPDK as owned by the application,
+    /// its address may be reused since it was freed or transmitted.
+    ///
+    /// The mbuf may be a segment of a chain, so it isn't checked as a packet.
+    pub fn on_acquire(m: &MBuf) {
+        RELEASED.lock().unwrap().remove(&(m.as_raw() as usize));
+    }
+
+    /// Check the mbufs and mark them as owned by the application.
+    pub fn on_alloc_bulk(mbufs: &[Option<MBuf>]) {
+        for m in mbufs.iter().flatten() {
+            on_alloc(m)
+        }
+    }
+
+    /// Check the mbufs about to be transmitted.
+    pub fn check_burst<T: AsRaw<Raw = RawMBuf>>(mbufs: &[T]) -> Vec<*mut RawMBuf> {
+        mbufs
+            .iter()
+            .map(|m| {
+                sanity_check(m.as_raw());
+
+                m.as_raw()
+            })
+            .collect()
+    }
+
+    /// Mark the mbufs as handed to the driver.
+    pub fn on_transmit(mbufs: &[*mut RawMBuf]) {
+        let mut released = RELEASED.lock().unwrap();
+
+        for &m in mbufs {
+            released.insert(m as usize, Released::Transmitted);
+        }
+    }
+
+    /// Check the mbuf is not freed twice, and poison its data room if it is the last reference.
+    pub fn on_free(m: &MBuf) {
+        let p = m.as_raw();
+
+        if let Some(&released) = RELEASED.lock().unwrap().get(&(p as usize)) {
+            match released {
+                Released::Freed => panic!("double free of mbuf {:p}", p),
+                Released::Transmitted => panic!("free of mbuf {:p} which was transmitted", p),
+            }
+        }
+
+        sanity_check(p);
+
+        if m.refcnt_read() == 1 {
+            let mut seg = p;
+
+            while let Some(m) = unsafe { seg.as_mut() } {
+                let flags = OffloadFlags::from_bits_truncate(m.ol_flags);
+
+                if !flags.intersects(OffloadFlags::IND_ATTACHED_MBUF | OffloadFlags::EXT_ATTACHED_MBUF)
+                    && unsafe { ffi::_rte_mbuf_refcnt_read(m) } == 1
+                {
+                    unsafe { ptr::write_bytes(m.buf_addr as *mut u8, MBUF_POISON, m.buf_len as usize) }
+                }
+
+                seg = m.next;
+            }
+
+            RELEASED.lock().unwrap().insert(p as usize, Released::Freed);
+        }
+    }
+}
+
+/// Always-on accounting of the mbufs got from and put back to the mempools, per mempool and per lcore.
+///
+/// The safe wrappers count an mbuf as got when it is allocated, cloned or received,
+/// and as put when it is dropped or handed to the driver by `tx_burst`.
+/// Each lcore only writes its own counters, the non-EAL threads share atomic counters.
+///
+/// The mbufs may be received on a lcore and freed on another one in a pipeline,
+/// so the outstanding mbufs of a lcore may grow or go negative,
+/// the `Checker` only flags the outstanding mbufs of a mempool summed over all the lcores.
+///
+/// The mbufs obtained from the raw API should be counted with `on_get`, before being dropped as `MBuf`,
+/// and the mbufs passed to the raw API with `on_put`.
+/// Use the `debug-mbuf` feature to catch the individual double freed mbufs.
+pub mod balance {
+    use std::collections::{HashMap, VecDeque};
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use lcore::{self, RTE_MAX_LCORE};
+    use mempool::{MemPool, MemPoolRef};
+
+    use super::RawMBuf;
+
+    /// The max number of the tracked mempools, the mbufs of the other mempools are not counted.
+    pub const MAX_POOLS: usize = 32;
+
+    #[derive(Debug, Default)]
+    struct Slot {
+        pool: AtomicUsize,
+        gets: AtomicU64,
+        puts: AtomicU64,
+    }
+
+    #[repr(align(64))]
+    #[derive(Debug, Default)]
+    struct Row {
+        slots: [Slot; MAX_POOLS],
+    }
+
+    lazy_static! {
+        // the rows of the lcores, and a shared row of the non-EAL threads
+        static ref ROWS: Vec<Row> = (0..=RTE_MAX_LCORE).map(|_| Row::default()).collect();
+
+        // the names of the tracked mempools, saved when their slots are claimed
+        static ref NAMES: Mutex<HashMap<usize, String>> = Mutex::new(HashMap::new());
+    }
+
+    static UNTRACKED: AtomicU64 = AtomicU64::new(0);
+
+    impl Row {
+        #[inline]
+        fn slot(&self, pool: usize) -> Option<&Slot> {
+            self.slots
+                .iter()
+                .find(|slot| slot.pool.load(Ordering::Acquire) == pool)
+                .or_else(|| self.claim(pool))
+        }
+
+        #[cold]
+        fn claim(&self, pool: usize) -> Option<&Slot> {
+            for slot in self.slots.iter() {
+                match slot.pool.compare_exchange(0, pool, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => {
+                        // the mempool is alive while its mbufs are counted
+                        let name = unsafe { MemPoolRef::from_ptr(pool as *mut _) }.name().to_owned();
+
+                        NAMES.lock().unwrap().insert(pool, name);
+
+                        return Some(slot);
+                    }
+                    Err(p) if p == pool => return Some(slot),
+                    Err(_) => {}
+                }
+            }
+
+            None
+        }
+    }
+
+    #[inline]
+    fn add(shared: bool, counter: &AtomicU64, n: u64) {
+        if shared {
+            counter.fetch_add(n, Ordering::Relaxed);
+        } else {
+            // only the owner lcore writes its counters
+            counter.store(counter.load(Ordering::Relaxed).wrapping_add(n), Ordering::Relaxed)
+        }
+    }
+
+    #[inline]
+    fn count(pool: usize, n: u64, get: bool) {
+        let (idx, shared) =
+            lcore::current().map_or((RTE_MAX_LCORE as usize, true), |lcore_id| (*lcore_id as usize, false));
+
+        match ROWS[idx].slot(pool) {
+            Some(slot) => add(shared, if get { &slot.gets } else { &slot.puts }, n),
+            None => {
+                UNTRACKED.fetch_add(n, Ordering::Relaxed);
+            }
+        }
+    }
+
+    #[inline]
+    fn count_burst<I: IntoIterator<Item = *mut RawMBuf>>(mbufs: I, get: bool) {
+        let mut pool = 0;
+        let mut n = 0;
+
+        // the mbufs of a burst tend to come from the same mempool
+        for m in mbufs.into_iter().filter(|m| !m.is_null()) {
+            let p = unsafe { (*m).pool as usize };
+
+            if p != pool {
+                if n > 0 {
+                    count(pool, n, get);
+                }
+
+                pool = p;
+                n = 0;
+            }
+
+            n += 1;
+        }
+
+        if n > 0 {
+            count(pool, n, get);
+        }
+    }
+
+    /// Count the mbuf as got from its mempool.
+    #[inline]
+    pub fn on_get(m: *mut RawMBuf) {
+        count(unsafe { (*m).pool as usize }, 1, true)
+    }
+
+    /// Count the mbuf as put back to its mempool.
+    #[inline]
+    pub fn on_put(m: *mut RawMBuf) {
+        count(unsafe { (*m).pool as usize }, 1, false)
+    }
+
+    /// Count the mbufs as got from their mempools.
+    #[inline]
+    pub fn on_get_burst<I: IntoIterator<Item = *mut RawMBuf>>(mbufs: I) {
+        count_burst(mbufs, true)
+    }
+
+    /// Count the mbufs as put back to their mempools.
+    #[inline]
+    pub fn on_put_burst<I: IntoIterator<Item = *mut RawMBuf>>(mbufs: I) {
+        count_burst(mbufs, false)
+    }
+
+    /// The number of the mbufs which were not counted, since too many mempools are tracked.
+    pub fn untracked() -> u64 {
+        UNTRACKED.load(Ordering::Relaxed)
+    }
+
+    /// The number of the mbufs got from and put back to a mempool.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Counts {
+        pub gets: u64,
+        pub puts: u64,
+    }
+
+    impl Counts {
+        /// The number of the outstanding mbufs, which is negative if more mbufs were put than got.
+        pub fn outstanding(&self) -> i64 {
+            self.gets.wrapping_sub(self.puts) as i64
+        }
+
+        fn merge(mut self, other: Counts) -> Self {
+            self.gets += other.gets;
+            self.puts += other.puts;
+            self
+        }
+    }
+
+    /// The counts of a mempool.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct PoolBalance {
+        /// The address of the mempool.
+        pub pool: usize,
+        /// The name of the mempool.
+        pub name: String,
+        /// The counts of the lcores, `lcore::Id::any()` for the non-EAL threads.
+        pub lcores: Vec<(lcore::Id, Counts)>,
+    }
+
+    impl PoolBalance {
+        /// The counts summed over all the lcores.
+        pub fn total(&self) -> Counts {
+            self.lcores
+                .iter()
+                .fold(Counts::default(), |total, &(_, counts)| total.merge(counts))
+        }
+    }
+
+    /// Take a snapshot of the counts of all the tracked mempools.
+    pub fn snapshot() -> Vec<PoolBalance> {
+        let mut pools: Vec<PoolBalance> = vec![];
+
+        for (idx, row) in ROWS.iter().enumerate() {
+            let lcore_id = if idx == RTE_MAX_LCORE as usize {
+                lcore::Id::any()
+            } else {
+                lcore::id(idx as u32)
+            };
+
+            for slot in row.slots.iter() {
+                let pool = slot.pool.load(Ordering::Acquire);
+
+                if pool == 0 {
+                    continue;
+                }
+
+                let counts = Counts {
+                    gets: slot.gets.load(Ordering::Relaxed),
+                    puts: slot.puts.load(Ordering::Relaxed),
+                };
+
+                if counts == Counts::default() {
+                    continue;
+                }
+
+                match pools.iter().position(|p| p.pool == pool) {
+                    Some(i) => pools[i].lcores.push((lcore_id, counts)),
+                    None => pools.push(PoolBalance {
+                        pool,
+                        name: NAMES.lock().unwrap().get(&pool).cloned().unwrap_or_default(),
+                        lcores: vec![(lcore_id, counts)],
+                    }),
+                }
+            }
+        }
+
+        pools
+    }
+
+    /// Reset the counters of a mempool, which is still tracked, see `release` to stop tracking it.
+    ///
+    /// The counters updated by the lcores at the same time may be lost.
+    pub fn reset<P: MemPool>(pool: &P) {
+        let pool = pool.as_raw() as usize;
+
+        for slot in ROWS.iter().flat_map(|row| row.slots.iter()) {
+            if slot.pool.load(Ordering::Acquire) == pool {
+                slot.gets.store(0, Ordering::Relaxed);
+                slot.puts.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Stop tracking a mempool before it is freed, its slots are given back for the other mempools.
+    ///
+    /// It is called when the owned `MemoryPool` is dropped,
+    /// the mbufs of the mempool must not be used by the other lcores at the same time.
+    pub fn release<P: MemPool>(pool: &P) {
+        let pool = pool.as_raw() as usize;
+
+        for slot in ROWS.iter().flat_map(|row| row.slots.iter()) {
+            if slot.pool.load(Ordering::Acquire) == pool {
+                slot.gets.store(0, Ordering::Relaxed);
+                slot.puts.store(0, Ordering::Relaxed);
+                slot.pool.store(0, Ordering::Release);
+            }
+        }
+
+        NAMES.lock().unwrap().remove(&pool);
+    }
+
+    /// An imbalance of a mempool detected by the `Checker`.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Imbalance {
+        /// The outstanding mbufs of the mempool have grown on every check of the window.
+        Leak {
+            pool: String,
+            outstanding: i64,
+            growth: i64,
+        },
+        /// More mbufs were put back to the mempool than got from it.
+        DoubleFree { pool: String, outstanding: i64 },
+    }
+
+    impl fmt::Display for Imbalance {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                Imbalance::Leak {
+                    ref pool,
+                    outstanding,
+                    growth,
+                } => write!(
+                    f,
+                    "mempool `{}` may leak mbufs, {} outstanding, grown by {}",
+                    pool, outstanding, growth
+                ),
+                Imbalance::DoubleFree { ref pool, outstanding } => write!(
+                    f,
+                    "mempool `{}` may have double freed mbufs, {} outstanding",
+                    pool, outstanding
+                ),
+            }
+        }
+    }
+
+    /// A checker which flags the imbalance of the mempools, it should be called periodically,
+    /// e.g. from the stats reporting timer.
+    #[derive(Debug)]
+    pub struct Checker {
+        window: usize,
+        min_growth: i64,
+        history: HashMap<usize, VecDeque<i64>>,
+    }
+
+    impl Checker {
+        /// Create a checker which flags a leak if the outstanding mbufs grow on `window` consecutive checks.
+        pub fn new(window: usize) -> Self {
+            Checker {
+                window: window.max(1),
+                min_growth: 1,
+                history: HashMap::new(),
+            }
+        }
+
+        /// Only flag a leak if the outstanding mbufs have grown at least `min_growth` in the window.
+        pub fn with_min_growth(mut self, min_growth: i64) -> Self {
+            self.min_growth = min_growth;
+            self
+        }
+
+        /// Check the current counts of the mempools.
+        pub fn check(&mut self) -> Vec<Imbalance> {
+            self.check_pools(&snapshot())
+        }
+
+        fn check_pools(&mut self, pools: &[PoolBalance]) -> Vec<Imbalance> {
+            let mut imbalances = vec![];
+
+            // forget the released mempools, their addresses may be reused
+            self.history
+                .retain(|&addr, _| pools.iter().any(|pool| pool.pool == addr));
+
+            for pool in pools {
+                let outstanding = pool.total().outstanding();
+                let history = self.history.entry(pool.pool).or_default();
+
+                history.push_back(outstanding);
+
+                while history.len() > self.window + 1 {
+                    history.pop_front();
+                }
+
+                if outstanding < 0 {
+                    imbalances.push(Imbalance::DoubleFree {
+                        pool: pool.name.clone(),
+                        outstanding,
+                    });
+                } else if history.len() == self.window + 1 && is_growing(history, self.min_growth) {
+                    imbalances.push(Imbalance::Leak {
+                        pool: pool.name.clone(),
+                        outstanding,
+                        growth: outstanding - history[0],
+                    });
+                }
+            }
+
+            imbalances
+        }
+    }
+
+    fn is_growing(samples: &VecDeque<i64>, min_growth: i64) -> bool {
+        samples
+            .iter()
+            .zip(samples.iter().skip(1))
+            .all(|(prev, next)| next > prev)
+            && samples.back().unwrap_or(&0) - samples.front().unwrap_or(&0) >= min_growth
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn pool(gets: u64, puts: u64) -> PoolBalance {
+            PoolBalance {
+                pool: 1,
+                name: "pool".to_owned(),
+                lcores: vec![
+                    (lcore::id(1), Counts { gets, puts: 0 }),
+                    (lcore::id(2), Counts { gets: 0, puts }),
+                ],
+            }
+        }
+
+        #[test]
+        fn test_checker() {
+            let mut checker = Checker::new(3).with_min_growth(10);
+
+            assert!(checker.check_pools(&[pool(100, 90)]).is_empty());
+            assert!(checker.check_pools(&[pool(200, 185)]).is_empty());
+            assert!(checker.check_pools(&[pool(300, 280)]).is_empty());
+            assert_eq!(
+                checker.check_pools(&[pool(400, 370)]),
+                vec![Imbalance::Leak {
+                    pool: "pool".to_owned(),
+                    outstanding: 30,
+                    growth: 20,
+                }]
+            );
+            assert!(checker.check_pools(&[pool(500, 470)]).is_empty());
+            assert_eq!(
+                checker.check_pools(&[pool(500, 501)]),
+                vec![Imbalance::DoubleFree {
+                    pool: "pool".to_owned(),
+                    outstanding: -1,
+                }]
+            );
+        }
+    }
+}
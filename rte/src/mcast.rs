@@ -0,0 +1,601 @@
+//!
+//! IGMP and MLD snooping.
+//!
+//! The IGMPv1/v2/v3 and MLDv1/v2 messages are parsed into membership events,
+//! which maintain a `McastGroupTable` of the member ports of each group,
+//! so the multicast frames could be forwarded only to the ports with listeners.
+//!
+//! The source lists of IGMPv3 and MLDv2 are not tracked, a group is joined by any
+//! record except an empty include, and the leave is fast, without the last member query.
+//!
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::slice;
+use std::time::{Duration, Instant};
+
+use ffi;
+
+use ethdev::PortId;
+use ether::{EtherAddr, ETHER_ADDR_LEN};
+use ip::{self, IPV4_HDR_IHL_MASK, IPV4_HDR_LEN, IPV4_IHL_MULTIPLIER};
+use mbuf::MBuf;
+
+/// The IP protocol of IGMP.
+pub const IPPROTO_IGMP: u8 = 2;
+/// The IPv6 hop-by-hop options header, which carries the router alert of MLD.
+pub const IPPROTO_HOPOPTS: u8 = 0;
+/// The IP protocol of ICMPv6.
+pub const IPPROTO_ICMPV6: u8 = 58;
+
+/// The membership query.
+pub const IGMP_MEMBERSHIP_QUERY: u8 = 0x11;
+/// The IGMPv1 membership report.
+pub const IGMP_V1_MEMBERSHIP_REPORT: u8 = 0x12;
+/// The IGMPv2 membership report.
+pub const IGMP_V2_MEMBERSHIP_REPORT: u8 = 0x16;
+/// The IGMPv2 leave group.
+pub const IGMP_V2_LEAVE_GROUP: u8 = 0x17;
+/// The IGMPv3 membership report.
+pub const IGMP_V3_MEMBERSHIP_REPORT: u8 = 0x22;
+
+/// The multicast listener query.
+pub const MLD_LISTENER_QUERY: u8 = 130;
+/// The MLDv1 multicast listener report.
+pub const MLD_LISTENER_REPORT: u8 = 131;
+/// The MLDv1 multicast listener done.
+pub const MLD_LISTENER_DONE: u8 = 132;
+/// The MLDv2 multicast listener report.
+pub const MLD_V2_LISTENER_REPORT: u8 = 143;
+
+/// The current state is include the sources.
+pub const MODE_IS_INCLUDE: u8 = 1;
+/// The current state is exclude the sources.
+pub const MODE_IS_EXCLUDE: u8 = 2;
+/// The filter mode changed to include the sources.
+pub const CHANGE_TO_INCLUDE_MODE: u8 = 3;
+/// The filter mode changed to exclude the sources.
+pub const CHANGE_TO_EXCLUDE_MODE: u8 = 4;
+/// The sources are allowed.
+pub const ALLOW_NEW_SOURCES: u8 = 5;
+/// The sources are blocked.
+pub const BLOCK_OLD_SOURCES: u8 = 6;
+
+/// The length of IGMPv1/v2 message.
+pub const IGMP_HDR_LEN: usize = 8;
+/// The minimum length of IGMPv3 query.
+pub const IGMP_V3_QUERY_LEN: usize = 12;
+/// The length of MLDv1 message.
+pub const MLD_HDR_LEN: usize = 24;
+/// The minimum length of MLDv2 query.
+pub const MLD_V2_QUERY_LEN: usize = 28;
+
+const IGMP_V3_REPORT_HDR_LEN: usize = 8;
+const IGMP_V3_RECORD_LEN: usize = 8;
+const MLD_V2_REPORT_HDR_LEN: usize = 8;
+const MLD_V2_RECORD_LEN: usize = 20;
+
+const IPV6_HDR_LEN: usize = 40;
+const VLAN_HDR_LEN: usize = 4;
+
+/// The default group membership interval, which is `2 * 125s + 10s` of the robustness variable and query interval.
+pub const DEFAULT_MEMBERSHIP_INTERVAL: Duration = Duration::from_secs(260);
+
+/// The membership event of a IGMP or MLD message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum McastEvent {
+    /// A general query, or a query of the group, from a multicast router.
+    Query {
+        /// The queried group, or `None` for the general query.
+        group: Option<IpAddr>,
+        /// The maximum response delay.
+        max_resp: Duration,
+    },
+    /// A listener joined the group.
+    Join(IpAddr),
+    /// A listener left the group.
+    Leave(IpAddr),
+}
+
+impl McastEvent {
+    /// The group of the event.
+    pub fn group(&self) -> Option<IpAddr> {
+        match *self {
+            McastEvent::Query { group, .. } => group,
+            McastEvent::Join(group) | McastEvent::Leave(group) => Some(group),
+        }
+    }
+}
+
+fn be16(buf: &[u8], off: usize) -> Option<u16> {
+    buf.get(off..off + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn ipv4_addr(buf: &[u8], off: usize) -> Option<Ipv4Addr> {
+    buf.get(off..off + 4).map(|b| Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+}
+
+fn ipv6_addr(buf: &[u8], off: usize) -> Option<Ipv6Addr> {
+    buf.get(off..off + 16).map(|b| {
+        let mut octets = [0; 16];
+
+        octets.copy_from_slice(b);
+
+        Ipv6Addr::from(octets)
+    })
+}
+
+/// Decode the floating point max response code of IGMPv3 query.
+fn igmp_v3_max_resp(code: u8) -> u32 {
+    if code < 128 {
+        u32::from(code)
+    } else {
+        (u32::from(code & 0x0f) | 0x10) << ((code >> 4 & 0x07) + 3)
+    }
+}
+
+/// Decode the floating point max response code of MLDv2 query.
+fn mld_v2_max_resp(code: u16) -> u32 {
+    if code < 32768 {
+        u32::from(code)
+    } else {
+        (u32::from(code & 0x0fff) | 0x1000) << ((code >> 12 & 0x07) + 3)
+    }
+}
+
+/// The event of a IGMPv3 or MLDv2 group record, the source lists are not tracked.
+fn record_event(record_type: u8, nb_sources: u16, group: IpAddr) -> Option<McastEvent> {
+    match record_type {
+        MODE_IS_INCLUDE | CHANGE_TO_INCLUDE_MODE if nb_sources == 0 => Some(McastEvent::Leave(group)),
+        MODE_IS_INCLUDE | MODE_IS_EXCLUDE | CHANGE_TO_INCLUDE_MODE | CHANGE_TO_EXCLUDE_MODE | ALLOW_NEW_SOURCES => {
+            Some(McastEvent::Join(group))
+        }
+        _ => None,
+    }
+}
+
+/// Parse a IGMP message, which starts from the IGMP header.
+///
+/// Returns `None` if the message is truncated, has a wrong checksum or an unknown type.
+pub fn parse_igmp(buf: &[u8]) -> Option<Vec<McastEvent>> {
+    if buf.len() < IGMP_HDR_LEN || ip::raw_cksum(buf) != 0xffff {
+        return None;
+    }
+
+    let group = ipv4_addr(buf, 4)?;
+
+    match buf[0] {
+        IGMP_MEMBERSHIP_QUERY => {
+            // IGMPv1 query has no max response time, which is 10 seconds
+            let max_resp = match (buf.len(), buf[1]) {
+                (IGMP_HDR_LEN, 0) => 100,
+                (IGMP_HDR_LEN, code) => u32::from(code),
+                (len, code) if len >= IGMP_V3_QUERY_LEN => igmp_v3_max_resp(code),
+                _ => return None,
+            };
+
+            Some(vec![McastEvent::Query {
+                group: if group.is_unspecified() {
+                    None
+                } else {
+                    Some(group.into())
+                },
+                max_resp: Duration::from_millis(u64::from(max_resp) * 100),
+            }])
+        }
+        IGMP_V1_MEMBERSHIP_REPORT | IGMP_V2_MEMBERSHIP_REPORT if group.is_multicast() => {
+            Some(vec![McastEvent::Join(group.into())])
+        }
+        IGMP_V2_LEAVE_GROUP if group.is_multicast() => Some(vec![McastEvent::Leave(group.into())]),
+        IGMP_V3_MEMBERSHIP_REPORT => {
+            let nb_records = be16(buf, 6)?;
+            let mut off = IGMP_V3_REPORT_HDR_LEN;
+            let mut events = Vec::with_capacity(nb_records as usize);
+
+            for _ in 0..nb_records {
+                let record_type = *buf.get(off)?;
+                let aux_len = *buf.get(off + 1)? as usize * 4;
+                let nb_sources = be16(buf, off + 2)?;
+                let group = ipv4_addr(buf, off + 4)?;
+
+                off += IGMP_V3_RECORD_LEN + nb_sources as usize * 4 + aux_len;
+
+                if off > buf.len() {
+                    return None;
+                }
+
+                if group.is_multicast() {
+                    events.extend(record_event(record_type, nb_sources, group.into()));
+                }
+            }
+
+            Some(events)
+        }
+        _ => None,
+    }
+}
+
+/// Parse a MLD message, which starts from the ICMPv6 header.
+///
+/// The checksum is not verified, since it covers the IPv6 pseudo header.
+/// Returns `None` if the message is truncated or not a MLD message.
+pub fn parse_mld(buf: &[u8]) -> Option<Vec<McastEvent>> {
+    match *buf.first()? {
+        MLD_LISTENER_QUERY => {
+            let group = ipv6_addr(buf, 8)?;
+            let max_resp = match buf.len() {
+                len if len >= MLD_V2_QUERY_LEN => mld_v2_max_resp(be16(buf, 4)?),
+                MLD_HDR_LEN => u32::from(be16(buf, 4)?),
+                _ => return None,
+            };
+
+            Some(vec![McastEvent::Query {
+                group: if group.is_unspecified() {
+                    None
+                } else {
+                    Some(group.into())
+                },
+                max_resp: Duration::from_millis(u64::from(max_resp)),
+            }])
+        }
+        MLD_LISTENER_REPORT => ipv6_addr(buf, 8)
+            .filter(|group| group.is_multicast())
+            .map(|group| vec![McastEvent::Join(group.into())]),
+        MLD_LISTENER_DONE => ipv6_addr(buf, 8)
+            .filter(|group| group.is_multicast())
+            .map(|group| vec![McastEvent::Leave(group.into())]),
+        MLD_V2_LISTENER_REPORT => {
+            let nb_records = be16(buf, 6)?;
+            let mut off = MLD_V2_REPORT_HDR_LEN;
+            let mut events = Vec::with_capacity(nb_records as usize);
+
+            for _ in 0..nb_records {
+                let record_type = *buf.get(off)?;
+                let aux_len = *buf.get(off + 1)? as usize * 4;
+                let nb_sources = be16(buf, off + 2)?;
+                let group = ipv6_addr(buf, off + 4)?;
+
+                off += MLD_V2_RECORD_LEN + nb_sources as usize * 16 + aux_len;
+
+                if off > buf.len() {
+                    return None;
+                }
+
+                if group.is_multicast() {
+                    events.extend(record_event(record_type, nb_sources, group.into()));
+                }
+            }
+
+            Some(events)
+        }
+        _ => None,
+    }
+}
+
+/// Snoop the IGMP or MLD message of a frame, which starts from the Ethernet header.
+///
+/// Returns `None` if the frame is not a valid IGMP or MLD message.
+pub fn snoop(frame: &[u8]) -> Option<Vec<McastEvent>> {
+    let mut off = ETHER_ADDR_LEN * 2;
+    let mut ether_type = be16(frame, off)?;
+
+    off += 2;
+
+    while ether_type == ffi::ETHER_TYPE_VLAN as u16 || ether_type == ffi::ETHER_TYPE_QINQ as u16 {
+        ether_type = be16(frame, off + 2)?;
+        off += VLAN_HDR_LEN;
+    }
+
+    let ip = &frame[off..];
+
+    if ether_type == ffi::ETHER_TYPE_IPv4 as u16 {
+        if ip.len() < IPV4_HDR_LEN || ip[9] != IPPROTO_IGMP {
+            return None;
+        }
+
+        let ihl = (ip[0] & IPV4_HDR_IHL_MASK) as usize * IPV4_IHL_MULTIPLIER;
+        let total_len = (be16(ip, 2)? as usize).min(ip.len());
+
+        parse_igmp(ip.get(ihl..total_len)?)
+    } else if ether_type == ffi::ETHER_TYPE_IPv6 as u16 {
+        let payload_len = be16(ip, 4)? as usize;
+        let payload = ip.get(IPV6_HDR_LEN..(IPV6_HDR_LEN + payload_len).min(ip.len()))?;
+
+        match *ip.get(6)? {
+            IPPROTO_ICMPV6 => parse_mld(payload),
+            IPPROTO_HOPOPTS if payload.first() == Some(&IPPROTO_ICMPV6) => {
+                let hdr_len = (*payload.get(1)? as usize + 1) * 8;
+
+                parse_mld(payload.get(hdr_len..)?)
+            }
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// The multicast MAC address of the group, `01:00:5e` or `33:33` with the low bits of the group.
+pub fn mcast_ether_addr(group: &IpAddr) -> EtherAddr {
+    match *group {
+        IpAddr::V4(ref addr) => EtherAddr::ipv4_mcast(addr),
+        IpAddr::V6(ref addr) => EtherAddr::ipv6_mcast(addr),
+    }
+}
+
+/// The group is in the link-local scope, `224.0.0.0/24` or `ff02::/16`, which is always flooded.
+pub fn is_link_local_group(group: &IpAddr) -> bool {
+    match *group {
+        IpAddr::V4(ref addr) => {
+            let o = addr.octets();
+
+            o[0] == 224 && o[1] == 0 && o[2] == 0
+        }
+        IpAddr::V6(ref addr) => addr.segments()[0] & 0xff0f == 0xff02,
+    }
+}
+
+/// The member ports of the multicast groups, maintained from the snooped joins and leaves.
+#[derive(Clone, Debug)]
+pub struct McastGroupTable {
+    members: HashMap<IpAddr, HashMap<PortId, Instant>>,
+    routers: HashMap<PortId, Instant>,
+    membership_interval: Duration,
+}
+
+impl Default for McastGroupTable {
+    fn default() -> Self {
+        McastGroupTable::new(DEFAULT_MEMBERSHIP_INTERVAL)
+    }
+}
+
+impl McastGroupTable {
+    /// Create a table, which expires the members and routers not refreshed in the interval.
+    pub fn new(membership_interval: Duration) -> Self {
+        McastGroupTable {
+            members: HashMap::new(),
+            routers: HashMap::new(),
+            membership_interval,
+        }
+    }
+
+    /// The number of groups.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// The table has no group.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The groups with members.
+    pub fn groups(&self) -> impl Iterator<Item = &IpAddr> {
+        self.members.keys()
+    }
+
+    /// The member ports of the group.
+    pub fn members(&self, group: &IpAddr) -> impl Iterator<Item = PortId> + '_ {
+        self.members
+            .get(group)
+            .into_iter()
+            .flat_map(|ports| ports.keys().cloned())
+    }
+
+    /// The ports where the queries of multicast routers were received.
+    pub fn router_ports(&self) -> impl Iterator<Item = PortId> + '_ {
+        self.routers.keys().cloned()
+    }
+
+    /// Add or refresh the port as a member of the group, returns `true` if it's a new member.
+    pub fn join(&mut self, group: IpAddr, port_id: PortId, now: Instant) -> bool {
+        self.members.entry(group).or_default().insert(port_id, now).is_none()
+    }
+
+    /// Remove the port from the members of the group, returns `true` if it was a member.
+    pub fn leave(&mut self, group: &IpAddr, port_id: PortId) -> bool {
+        let left = match self.members.get_mut(group) {
+            Some(ports) => ports.remove(&port_id).is_some(),
+            None => return false,
+        };
+
+        if self.members.get(group).is_some_and(HashMap::is_empty) {
+            self.members.remove(group);
+        }
+
+        left
+    }
+
+    /// Apply a membership event received on the port, returns `true` if the members changed.
+    pub fn update(&mut self, port_id: PortId, event: &McastEvent, now: Instant) -> bool {
+        match *event {
+            McastEvent::Query { .. } => {
+                self.routers.insert(port_id, now);
+
+                false
+            }
+            McastEvent::Join(group) => !is_link_local_group(&group) && self.join(group, port_id, now),
+            McastEvent::Leave(ref group) => self.leave(group, port_id),
+        }
+    }
+
+    /// Snoop a frame received on the port, returns `true` if it's a IGMP or MLD message.
+    ///
+    /// The messages should be flooded, or at least forwarded to the router ports.
+    pub fn snoop(&mut self, port_id: PortId, frame: &[u8], now: Instant) -> bool {
+        match snoop(frame) {
+            Some(events) => {
+                for event in &events {
+                    self.update(port_id, event, now);
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Snoop a mbuf received on the port, only the first segment is parsed.
+    pub fn snoop_mbuf(&mut self, port_id: PortId, m: &MBuf, now: Instant) -> bool {
+        let frame = unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), m.data_len()) };
+
+        self.snoop(port_id, frame, now)
+    }
+
+    /// The output ports of a multicast frame of the group received on the port.
+    ///
+    /// Returns `None` if the group is in the link-local scope and the frame should be flooded,
+    /// or the member ports and router ports except the input port.
+    pub fn forward_ports(&self, group: &IpAddr, in_port: PortId) -> Option<Vec<PortId>> {
+        if is_link_local_group(group) {
+            return None;
+        }
+
+        let mut ports: Vec<PortId> = self
+            .members(group)
+            .chain(self.router_ports())
+            .filter(|&port_id| port_id != in_port)
+            .collect();
+
+        ports.sort();
+        ports.dedup();
+
+        Some(ports)
+    }
+
+    /// Remove the members and routers which were not refreshed in the interval,
+    /// returns the number of expired members.
+    pub fn expire(&mut self, now: Instant) -> usize {
+        let interval = self.membership_interval;
+        let alive = |last: &Instant| now.saturating_duration_since(*last) < interval;
+        let mut expired = 0;
+
+        for ports in self.members.values_mut() {
+            let n = ports.len();
+
+            ports.retain(|_, last| alive(last));
+
+            expired += n - ports.len();
+        }
+
+        self.members.retain(|_, ports| !ports.is_empty());
+        self.routers.retain(|_, last| alive(last));
+
+        expired
+    }
+
+    /// The multicast MAC addresses of the groups,
+    /// which could be set as the multicast filter of the ports with `set_mc_addr_list`.
+    pub fn mac_addrs(&self) -> Vec<EtherAddr> {
+        let mut addrs: Vec<EtherAddr> = self.members.keys().map(mcast_ether_addr).collect();
+
+        addrs.sort_by(|a, b| a.octets().cmp(b.octets()));
+        addrs.dedup();
+
+        addrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn igmp(mut msg: Vec<u8>) -> Vec<u8> {
+        let cksum = !ip::raw_cksum(&msg);
+
+        msg[2..4].copy_from_slice(&cksum.to_ne_bytes());
+        msg
+    }
+
+    #[test]
+    fn test_parse_igmp() {
+        let group: IpAddr = Ipv4Addr::new(239, 1, 2, 3).into();
+
+        assert_eq!(
+            parse_igmp(&igmp(vec![0x16, 0, 0, 0, 239, 1, 2, 3])),
+            Some(vec![McastEvent::Join(group)])
+        );
+        assert_eq!(
+            parse_igmp(&igmp(vec![0x17, 0, 0, 0, 239, 1, 2, 3])),
+            Some(vec![McastEvent::Leave(group)])
+        );
+        assert_eq!(
+            parse_igmp(&igmp(vec![0x11, 100, 0, 0, 0, 0, 0, 0])),
+            Some(vec![McastEvent::Query {
+                group: None,
+                max_resp: Duration::from_secs(10),
+            }])
+        );
+
+        // wrong checksum
+        assert_eq!(parse_igmp(&[0x16, 0, 0, 0, 239, 1, 2, 3]), None);
+
+        // IGMPv3 report, exclude {} joins 239.1.2.3, to include {} leaves 239.1.2.4
+        let report = igmp(vec![
+            0x22, 0, 0, 0, 0, 0, 0, 2, 4, 0, 0, 0, 239, 1, 2, 3, 3, 0, 0, 0, 239, 1, 2, 4,
+        ]);
+
+        assert_eq!(
+            parse_igmp(&report),
+            Some(vec![
+                McastEvent::Join(group),
+                McastEvent::Leave(Ipv4Addr::new(239, 1, 2, 4).into())
+            ])
+        );
+        assert_eq!(parse_igmp(&igmp(report[..20].to_vec())), None);
+    }
+
+    #[test]
+    fn test_parse_mld() {
+        let group: Ipv6Addr = "ff0e::1:3".parse().unwrap();
+        let mut msg = vec![131, 0, 0, 0, 0, 0, 0, 0];
+
+        msg.extend_from_slice(&group.octets());
+
+        assert_eq!(parse_mld(&msg), Some(vec![McastEvent::Join(group.into())]));
+
+        // MLDv2 report, allow {::1} joins the group
+        let mut report = vec![143, 0, 0, 0, 0, 0, 0, 1, 5, 0, 0, 1];
+
+        report.extend_from_slice(&group.octets());
+        report.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+
+        assert_eq!(parse_mld(&report), Some(vec![McastEvent::Join(group.into())]));
+        assert_eq!(parse_mld(&report[..report.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_group_table() {
+        let now = Instant::now();
+        let group: IpAddr = Ipv4Addr::new(239, 1, 2, 3).into();
+        let mut table = McastGroupTable::new(Duration::from_secs(10));
+
+        assert_eq!(
+            mcast_ether_addr(&group),
+            EtherAddr::new(0x01, 0x00, 0x5e, 0x01, 0x02, 0x03)
+        );
+        assert!(is_link_local_group(&Ipv4Addr::new(224, 0, 0, 22).into()));
+        assert!(is_link_local_group(&"ff02::16".parse().unwrap()));
+
+        assert!(table.update(1, &McastEvent::Join(group), now));
+        assert!(!table.update(1, &McastEvent::Join(group), now));
+        assert!(table.update(2, &McastEvent::Join(group), now + Duration::from_secs(5)));
+        assert!(!table.update(3, &McastEvent::Join(Ipv4Addr::new(224, 0, 0, 251).into()), now));
+        table.update(
+            0,
+            &McastEvent::Query {
+                group: None,
+                max_resp: Duration::from_secs(10),
+            },
+            now,
+        );
+
+        assert_eq!(table.forward_ports(&group, 1), Some(vec![0, 2]));
+        assert_eq!(table.mac_addrs(), vec![mcast_ether_addr(&group)]);
+
+        assert_eq!(table.expire(now + Duration::from_secs(12)), 1);
+        assert_eq!(table.forward_ports(&group, 1), Some(vec![2]));
+
+        assert!(table.update(2, &McastEvent::Leave(group), now));
+        assert!(table.is_empty());
+    }
+}
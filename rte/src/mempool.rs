@@ -24,7 +24,10 @@
 //! created with rte_mempool_cache_create().
 //!
 use std::ffi::CStr;
+use std::io;
+use std::marker::PhantomData;
 use std::mem;
+use std::ops::Deref;
 use std::os::raw::{c_uint, c_void};
 use std::os::unix::io::AsRawFd;
 use std::ptr::{self, NonNull};
@@ -33,11 +36,13 @@ use cfile;
 use ffi;
 use libc;
 
+use common::name::{Name, NameKind};
 use errors::{AsResult, Result};
-use lcore;
-use memory::SocketId;
+use lcore::{self, RTE_MAX_LCORE};
+use mbuf;
+use memory::Socket;
 use ring;
-use utils::{AsCString, AsRaw, CallbackContext, FromRaw, IntoRaw, Raw};
+use utils::{self, AsRaw, CallbackContext, FromRaw, IntoRaw, Raw};
 
 pub use ffi::{
     MEMPOOL_PG_NUM_DEFAULT, RTE_MEMPOOL_ALIGN, RTE_MEMPOOL_ALIGN_MASK, RTE_MEMPOOL_HEADER_COOKIE1,
@@ -66,9 +71,9 @@ bitflags! {
 }
 
 pub trait Pooled<T>: Raw<T> {
-    /// Return a pointer to the mempool owning this object.
-    fn pool(&self) -> MemoryPool {
-        unsafe { ffi::_rte_mempool_from_obj(self.as_raw() as *mut _) }.into()
+    /// Return a reference to the mempool owning this object.
+    fn pool(&self) -> MemPoolRef<'_> {
+        unsafe { MemPoolRef::from_ptr(ffi::_rte_mempool_from_obj(self.as_raw() as *mut _)) }
     }
 
     /// Return the IO address of elt, which is an element of the pool mp.
@@ -78,44 +83,120 @@ pub trait Pooled<T>: Raw<T> {
 }
 
 /// A mempool constructor callback function.
-pub type Constructor<T> = fn(pool: &MemoryPool, arg: Option<T>);
+pub type Constructor<T> = fn(pool: MemPoolRef, arg: Option<T>);
 
 /// A mempool walk callback function.
-pub type PoolWalkCallback<T> = fn(pool: &MemoryPool, arg: Option<T>);
+pub type PoolWalkCallback<T> = fn(pool: MemPoolRef, arg: Option<T>);
 
 /// A mempool object iterator callback function.
-pub type ObjectCallback<T, O> = fn(pool: &MemoryPool, arg: Option<T>, obj: &mut O, idx: usize);
+pub type ObjectCallback<T, O> = fn(pool: MemPoolRef, arg: Option<T>, obj: &mut O, idx: usize);
 
-pub type MemoryChunkCallback<T> = fn(pool: &MemoryPool, arg: Option<T>, mem: &ffi::rte_mempool_memhdr, idx: usize);
+pub type MemoryChunkCallback<T> = fn(pool: MemPoolRef, arg: Option<T>, mem: &ffi::rte_mempool_memhdr, idx: usize);
 
 pub type RawMemoryPool = ffi::rte_mempool;
 pub type RawMemoryPoolPtr = *mut ffi::rte_mempool;
 
-/// The RTE mempool structure.
+/// The RTE mempool structure, which is owned and freed when dropped.
 raw!(pub MemoryPool(RawMemoryPool));
 
+impl Drop for MemoryPool {
+    /// Free a mempool
+    ///
+    /// Unlink the mempool from global list, free the memory chunks, and all
+    /// memory referenced by the mempool. The objects must not be used by
+    /// other cores as they will be freed.
+    fn drop(&mut self) {
+        mbuf::balance::release(self);
+
+        unsafe { ffi::rte_mempool_free(self.as_raw()) }
+    }
+}
+
+impl MemPool for MemoryPool {}
+
 impl MemoryPool {
+    /// Borrow a reference to the mempool, which can't outlive it.
+    pub fn to_ref(&self) -> MemPoolRef<'_> {
+        unsafe { MemPoolRef::from_ptr(self.as_raw()) }
+    }
+
+    /// Leak the mempool, which will never be freed, and return a reference to it.
+    pub fn leak(self) -> MemPoolRef<'static> {
+        unsafe { MemPoolRef::from_ptr(self.into_raw()) }
+    }
+
+    /// Dump the status of all mempools on the console
+    pub fn list_dump<S: AsRawFd>(s: &S) -> Result<()> {
+        let mut f = cfile::fdopen(s, "w")?;
+
+        unsafe { ffi::rte_mempool_list_dump(&mut **f as *mut _ as *mut _) };
+
+        Ok(())
+    }
+}
+
+/// A borrowed reference to the RTE mempool, which never frees it.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MemPoolRef<'a>(NonNull<RawMemoryPool>, PhantomData<&'a RawMemoryPool>);
+
+// the mempool is shared by the lcores, the objects are got and put with the thread safe operations
+unsafe impl<'a> Send for MemPoolRef<'a> {}
+unsafe impl<'a> Sync for MemPoolRef<'a> {}
+
+impl<'a> Deref for MemPoolRef<'a> {
+    type Target = RawMemoryPool;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.0.as_ref() }
+    }
+}
+
+impl<'a> AsRaw for MemPoolRef<'a> {
+    type Raw = RawMemoryPool;
+
+    fn as_raw(&self) -> *mut Self::Raw {
+        self.0.as_ptr()
+    }
+}
+
+impl<'a> MemPool for MemPoolRef<'a> {}
+
+impl<'a> MemPoolRef<'a> {
+    /// Create a reference from the raw pointer of mempool.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to a mempool, which is valid during the lifetime `'a`.
+    pub unsafe fn from_ptr(p: RawMemoryPoolPtr) -> Self {
+        MemPoolRef(NonNull::new(p).expect("null mempool"), PhantomData)
+    }
+
     /// Search a mempool from its name
-    pub fn lookup<S: AsRef<str>>(name: S) -> Result<Self> {
-        let name = name.as_cstring();
+    ///
+    /// # Safety
+    ///
+    /// The mempool isn't owned by the reference, the caller must ensure that
+    /// it is not freed by its owner during the lifetime `'a`.
+    pub unsafe fn lookup<S: AsRef<str>>(name: S) -> Result<Self> {
+        let name = Name::new(NameKind::MemPool, name)?;
 
-        unsafe { ffi::rte_mempool_lookup(name.as_ptr()) }
+        ffi::rte_mempool_lookup(name.as_ptr())
             .as_result()
-            .map(MemoryPool)
+            .map(|p| MemPoolRef(p, PhantomData))
     }
+}
 
+/// The operations shared by the owned `MemoryPool` and the borrowed `MemPoolRef`.
+pub trait MemPool: Deref<Target = RawMemoryPool> + AsRaw<Raw = RawMemoryPool> {
     /// Name of mempool.
-    pub fn name(&self) -> &str {
+    fn name(&self) -> &str {
         unsafe { CStr::from_ptr((&self.name[..]).as_ptr()).to_str().unwrap() }
     }
 
-    /// Free a mempool
-    ///
-    /// Unlink the mempool from global list, free the memory chunks, and all
-    /// memory referenced by the mempool. The objects must not be used by
-    /// other cores as they will be freed.
-    fn free(&mut self) {
-        unsafe { ffi::rte_mempool_free(self.as_raw()) }
+    /// The socket the memory of the mempool is allocated on.
+    fn socket(&self) -> Socket {
+        Socket::from(self.socket_id)
     }
 
     /// Return the number of entries in the mempool.
@@ -123,7 +204,7 @@ impl MemoryPool {
     /// When cache is enabled, this function has to browse the length of
     /// all lcores, so it should not be used in a data path, but only for
     /// debug purposes. User-owned mempool caches are not accounted for.
-    pub fn avail_count(&self) -> usize {
+    fn avail_count(&self) -> usize {
         unsafe { ffi::rte_mempool_avail_count(self.as_raw()) as usize }
     }
 
@@ -132,17 +213,17 @@ impl MemoryPool {
     /// When cache is enabled, this function has to browse the length of
     /// all lcores, so it should not be used in a data path, but only for
     /// debug purposes.
-    pub fn in_use_count(&self) -> usize {
+    fn in_use_count(&self) -> usize {
         unsafe { ffi::rte_mempool_in_use_count(self.as_raw()) as usize }
     }
 
     /// Test if the mempool is full.
-    pub fn is_full(&self) -> bool {
+    fn is_full(&self) -> bool {
         self.avail_count() == self.size as usize
     }
 
     /// Test if the mempool is empty.
-    pub fn is_empty(&self) -> bool {
+    fn is_empty(&self) -> bool {
         self.avail_count() == 0
     }
 
@@ -152,17 +233,24 @@ impl MemoryPool {
     /// Also check that the cookies of mempool objects (even the ones that are not present in pool)
     /// have a correct value. If not, a panic will occur.
     ///
-    pub fn audit(&self) {
+    fn audit(&self) {
         unsafe { ffi::rte_mempool_audit(self.as_raw()) }
     }
 
+    /// Name of the mempool ops, which handle the objects of the mempool.
+    fn ops_name(&self) -> &str {
+        let ops = unsafe { &ffi::rte_mempool_ops_table.ops[self.ops_index as usize] };
+
+        unsafe { CStr::from_ptr(ops.name.as_ptr()).to_str().unwrap() }
+    }
+
     /// Return a pointer to the private data in an mempool structure.
-    pub fn get_priv<T>(&self) -> *const T {
+    fn get_priv<T>(&self) -> *const T {
         unsafe { ffi::_rte_mempool_get_priv(self.as_raw()) as *const _ }
     }
 
     /// Dump the status of the mempool to the console.
-    pub fn dump<S: AsRawFd>(&self, s: &S) -> Result<()> {
+    fn dump<S: AsRawFd>(&self, s: &S) -> Result<()> {
         let mut f = cfile::fdopen(s, "w")?;
 
         unsafe { ffi::rte_mempool_dump(&mut **f as *mut _ as *mut _, self.as_raw()) };
@@ -170,11 +258,9 @@ impl MemoryPool {
         Ok(())
     }
 
-    /// Dump the status of all mempools on the console
-    pub fn list_dump<S: AsRawFd>(s: &S) -> Result<()> {
-        let mut f = cfile::fdopen(s, "w")?;
-
-        unsafe { ffi::rte_mempool_list_dump(&mut **f as *mut _ as *mut _) };
+    /// Dump the status of the mempool to the writer.
+    fn dump_to<W: io::Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        utils::capture(w, |f| unsafe { ffi::rte_mempool_dump(f as *mut _, self.as_raw()) })?;
 
         Ok(())
     }
@@ -188,7 +274,7 @@ impl MemoryPool {
     /// This function is used to populate a mempool, or walk through all the elements of a mempool,
     /// or estimate how many elements of the given size could be created in the given memory buffer.
     ///
-    pub fn walk<T, O>(&mut self, callback: ObjectCallback<T, O>, arg: Option<T>) -> usize {
+    fn walk<T, O>(&mut self, callback: ObjectCallback<T, O>, arg: Option<T>) -> usize {
         unsafe {
             ffi::rte_mempool_obj_iter(
                 self.as_raw(),
@@ -197,6 +283,133 @@ impl MemoryPool {
             ) as usize
         }
     }
+
+    /// Flush a user-owned mempool cache to the specified mempool.
+    fn flush(&self, cache: &Cache) {
+        unsafe { ffi::_rte_mempool_cache_flush(cache.as_raw(), self.as_raw()) }
+    }
+
+    /// Get a pointer to the per-lcore default mempool cache.
+    fn default_cache(&self) -> Option<Cache> {
+        lcore::current().and_then(|lcore_id| {
+            NonNull::new(unsafe { ffi::_rte_mempool_default_cache(self.as_raw(), *lcore_id) }).map(Cache)
+        })
+    }
+
+    /// Put several objects back in the mempool.
+    ///
+    /// The cache should be a user-owned cache, or the default cache of the current lcore.
+    fn generic_put<T: Pooled<R>, R>(&mut self, objs: &[T], cache: Option<&Cache>) {
+        let cache = check_cache(self, cache);
+
+        unsafe { ffi::_rte_mempool_generic_put(self.as_raw(), objs.as_ptr() as *const _, objs.len() as u32, cache) }
+    }
+
+    /// Put several objects back in the mempool.
+    ///
+    /// This function calls the multi-producer or the single-producer
+    /// version depending on the default behavior that was specified at
+    /// mempool creation time (see flags).
+    fn put_bulk<T: Pooled<R>, R>(&mut self, objs: &[T]) {
+        unsafe { ffi::_rte_mempool_put_bulk(self.as_raw(), objs.as_ptr() as *const _, objs.len() as u32) }
+    }
+
+    /// Put several objects back in the mempool.
+    ///
+    /// This function calls the multi-producer or the single-producer
+    /// version depending on the default behavior that was specified at
+    /// mempool creation time (see flags).
+    fn put<T: Pooled<R>, R>(&mut self, obj: T) {
+        unsafe { ffi::_rte_mempool_put(self.as_raw(), obj.as_raw() as *mut _) }
+    }
+
+    /// Get several objects from the mempool.
+    ///
+    /// If cache is enabled, objects will be retrieved first from cache,
+    /// subsequently from the common pool. Note that it can return -ENOENT when
+    /// the local cache and common pool are empty, even if cache from other
+    /// lcores are full.
+    ///
+    /// The cache should be a user-owned cache, or the default cache of the current lcore.
+    fn generic_get<T: Pooled<R>, R>(&mut self, objs: &mut [T], cache: Option<&Cache>) -> Result<()> {
+        let cache = check_cache(self, cache);
+
+        unsafe { ffi::_rte_mempool_generic_get(self.as_raw(), objs.as_mut_ptr() as *mut _, objs.len() as u32, cache) }
+            .as_result()
+            .map(|_| ())
+    }
+
+    /// Get several objects from the mempool.
+    ///
+    /// This function calls the multi-consumers or the single-consumer
+    /// version, depending on the default behaviour that was specified at
+    /// mempool creation time (see flags).
+    ///
+    /// If cache is enabled, objects will be retrieved first from cache,
+    /// subsequently from the common pool. Note that it can return -ENOENT when
+    /// the local cache and common pool are empty, even if cache from other
+    /// lcores are full.
+    fn get_bulk<T: Pooled<R>, R>(&mut self, objs: &mut [T]) -> Result<()> {
+        unsafe { ffi::_rte_mempool_get_bulk(self.as_raw(), objs.as_mut_ptr() as *mut _, objs.len() as u32) }
+            .as_result()
+            .map(|_| ())
+    }
+
+    /// Get several objects from the mempool.
+    ///
+    /// This function calls the multi-consumers or the single-consumer
+    /// version, depending on the default behaviour that was specified at
+    /// mempool creation time (see flags).
+    ///
+    /// If cache is enabled, objects will be retrieved first from cache,
+    /// subsequently from the common pool. Note that it can return -ENOENT when
+    /// the local cache and common pool are empty, even if cache from other
+    /// lcores are full.
+    fn get<T: Pooled<R>, R>(&mut self) -> Result<T> {
+        let mut obj = ptr::null_mut();
+
+        unsafe { ffi::_rte_mempool_get(self.as_raw(), &mut obj) }
+            .as_result()
+            .map(|_| (obj as *mut T::Raw).into())
+    }
+
+    /// Get a contiguous blocks of objects from the mempool.
+    ///
+    /// If cache is enabled, consider to flush it first, to reuse objects
+    /// as soon as possible.
+    ///
+    /// The application should check that the driver supports the operation
+    /// by calling rte_mempool_ops_get_info() and checking that `contig_block_size`
+    /// is not zero.
+    fn get_contig_blocks<T: Pooled<R>, R>(&mut self, objs: &mut [T]) -> Result<()> {
+        unsafe { ffi::_rte_mempool_get_contig_blocks(self.as_raw(), objs.as_mut_ptr() as *mut _, objs.len() as u32) }
+            .as_result()
+            .map(|_| ())
+    }
+}
+
+/// Check the default cache of a lcore is only used on that lcore,
+/// the unregistered non-EAL threads must use a user-owned cache instead.
+fn check_cache(pool: &RawMemoryPool, cache: Option<&Cache>) -> RawCachePtr {
+    let cache = cache.map_or(ptr::null_mut(), |cache| cache.as_raw());
+
+    if cfg!(debug_assertions) && !cache.is_null() && !pool.local_cache.is_null() {
+        let local_cache = pool.local_cache as usize;
+        let off = (cache as usize).wrapping_sub(local_cache);
+
+        if off < RTE_MAX_LCORE as usize * mem::size_of::<RawCache>() {
+            let lcore_id = off / mem::size_of::<RawCache>();
+
+            debug_assert_eq!(
+                lcore::current().map(|id| *id as usize),
+                Some(lcore_id),
+                "the default cache of lcore {} is used by another thread",
+                lcore_id
+            );
+        }
+    }
+
+    cache
 }
 
 /// Create a new mempool named name in memory.
@@ -215,13 +428,14 @@ pub fn create<S, M, T, O>(
     mp_init_arg: Option<M>,
     obj_init: Option<ObjectCallback<T, O>>,
     obj_init_arg: Option<T>,
-    socket_id: SocketId,
+    socket: Socket,
     flags: MemoryPoolFlags,
 ) -> Result<MemoryPool>
 where
     S: AsRef<str>,
 {
-    let name = name.as_cstring();
+    let name = Name::new(NameKind::MemPool, name)?;
+    let socket = socket.validate()?;
 
     let mp_init_ctx = if let Some(callback) = mp_init {
         ConstructorContext::new(callback, mp_init_arg).into_raw()
@@ -253,7 +467,7 @@ where
                 Some(obj_cb_stub::<T, O>)
             },
             obj_init_ctx,
-            socket_id,
+            socket.raw(),
             flags.bits,
         )
     }
@@ -272,13 +486,14 @@ pub fn create_empty<S, O>(
     n: u32,
     cache_size: u32,
     private_data_size: u32,
-    socket_id: SocketId,
+    socket: Socket,
     flags: MemoryPoolFlags,
 ) -> Result<MemoryPool>
 where
     S: AsRef<str>,
 {
-    let name = name.as_cstring();
+    let name = Name::new(NameKind::MemPool, name)?;
+    let socket = socket.validate()?;
 
     unsafe {
         ffi::rte_mempool_create_empty(
@@ -287,7 +502,7 @@ where
             mem::size_of::<O>() as u32,
             cache_size,
             private_data_size,
-            socket_id,
+            socket.raw(),
             flags.bits,
         )
     }
@@ -298,23 +513,22 @@ where
 type ConstructorContext<T> = CallbackContext<Constructor<T>, Option<T>>;
 
 unsafe extern "C" fn mp_init_stub<T>(mp: *mut ffi::rte_mempool, arg: *mut c_void) {
-    let mp = MemoryPool::from(mp);
     let ctx = ConstructorContext::<T>::from_raw(arg);
 
-    (ctx.callback)(&mp, ctx.arg);
-
-    mem::forget(mp);
+    (ctx.callback)(MemPoolRef::from_ptr(mp), ctx.arg);
 }
 
 type ObjectContext<T, O> = CallbackContext<ObjectCallback<T, O>, Option<T>>;
 
 unsafe extern "C" fn obj_cb_stub<T, O>(mp: *mut ffi::rte_mempool, arg: *mut c_void, obj: *mut c_void, obj_idx: c_uint) {
-    let mp = MemoryPool::from(mp);
     let ctx = ObjectContext::<T, O>::from_raw(arg);
 
-    (ctx.callback)(&mp, ctx.arg, (obj as *mut O).as_mut().unwrap(), obj_idx as usize);
-
-    mem::forget(mp);
+    (ctx.callback)(
+        MemPoolRef::from_ptr(mp),
+        ctx.arg,
+        (obj as *mut O).as_mut().unwrap(),
+        obj_idx as usize,
+    );
 }
 
 type MemoryChunkContext<T> = CallbackContext<MemoryChunkCallback<T>, Option<T>>;
@@ -325,18 +539,31 @@ unsafe extern "C" fn mem_cb_stub<T>(
     memhdr: *mut ffi::rte_mempool_memhdr,
     mem_idx: c_uint,
 ) {
-    let mp = MemoryPool::from(mp);
     let ctx = MemoryChunkContext::<T>::from_raw(arg);
 
-    (ctx.callback)(&mp, ctx.arg, &*memhdr, mem_idx as usize);
+    (ctx.callback)(MemPoolRef::from_ptr(mp), ctx.arg, &*memhdr, mem_idx as usize);
+}
 
-    mem::forget(mp);
+/// Search a mempool from its name
+///
+/// # Safety
+///
+/// The caller must ensure that the mempool is not freed by its owner during the lifetime `'a`.
+pub unsafe fn lookup<'a>(name: &str) -> Result<MemPoolRef<'a>> {
+    MemPoolRef::lookup(name)
 }
 
-pub fn lookup(name: &str) -> Result<RawMemoryPoolPtr> {
-    let p = unsafe { ffi::rte_mempool_lookup(try!(to_cptr!(name))) };
+/// The names of the mempool ops registered in the current process.
+pub fn registered_ops() -> Vec<String> {
+    let num_ops = unsafe { ffi::rte_mempool_ops_table.num_ops as usize };
 
-    rte_check!(p, NonNull)
+    (0..num_ops)
+        .map(|idx| unsafe {
+            let ops = &ffi::rte_mempool_ops_table.ops[idx];
+
+            CStr::from_ptr(ops.name.as_ptr()).to_string_lossy().into_owned()
+        })
+        .collect()
 }
 
 /// Dump the status of all mempools on the console
@@ -348,6 +575,13 @@ pub fn list_dump<S: AsRawFd>(s: &S) {
     }
 }
 
+/// Dump the status of all mempools to the writer.
+pub fn list_dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    utils::capture(w, |f| unsafe { ffi::rte_mempool_list_dump(f as *mut _) })?;
+
+    Ok(())
+}
+
 /// Walk list of all memory pools
 pub fn walk<T>(callback: PoolWalkCallback<T>, arg: Option<T>) {
     unsafe {
@@ -361,12 +595,9 @@ pub fn walk<T>(callback: PoolWalkCallback<T>, arg: Option<T>) {
 type PoolWalkContext<T> = CallbackContext<PoolWalkCallback<T>, Option<T>>;
 
 unsafe extern "C" fn pool_walk_stub<T>(mp: *mut ffi::rte_mempool, arg: *mut libc::c_void) {
-    let mp = MemoryPool::from(mp);
     let ctxt = PoolWalkContext::<T>::from_raw(arg);
 
-    (ctxt.callback)(&mp, ctxt.arg);
-
-    mem::forget(mp)
+    (ctxt.callback)(MemPoolRef::from_ptr(mp), ctxt.arg);
 }
 
 pub type RawCache = ffi::rte_mempool_cache;
@@ -379,123 +610,68 @@ impl Cache {
     ///
     /// This can be used by non-EAL threads to enable caching
     /// when they interact with a mempool.
-    pub fn create(size: usize, socket_id: SocketId) -> Self {
-        unsafe { ffi::rte_mempool_cache_create(size as u32, socket_id) }.into()
+    ///
+    /// The cache must be flushed and freed by the user, consider to use `CacheGuard` instead.
+    pub fn create(size: usize, socket: Socket) -> Result<Self> {
+        let socket = socket.validate()?;
+
+        unsafe { ffi::rte_mempool_cache_create(size as u32, socket.raw()) }
+            .as_result()
+            .map(Cache)
     }
 
     /// Free a user-owned mempool cache.
-    fn free(self) {
+    pub fn free(self) {
         unsafe { ffi::rte_mempool_cache_free(self.as_raw()) }
     }
-}
 
-impl MemoryPool {
-    /// Flush a user-owned mempool cache to the specified mempool.
-    pub fn flush(&self, cache: &Cache) {
-        unsafe { ffi::_rte_mempool_cache_flush(cache.as_raw(), self.as_raw()) }
+    /// Size of the cache
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
 
-    /// Get a pointer to the per-lcore default mempool cache.
-    pub fn default_cache(&self) -> Option<Cache> {
-        lcore::current().and_then(|lcore_id| {
-            NonNull::new(unsafe { ffi::_rte_mempool_default_cache(self.as_raw(), *lcore_id) }).map(Cache)
-        })
+    /// Current cache count
+    pub fn len(&self) -> usize {
+        self.len as usize
     }
 
-    /// Put several objects back in the mempool.
-    pub fn generic_put<T: Pooled<R>, R>(&mut self, objs: &[T], cache: Option<Cache>) {
-        unsafe {
-            ffi::_rte_mempool_generic_put(
-                self.as_raw(),
-                objs.as_ptr() as *const _,
-                objs.len() as u32,
-                cache.map(|cache| cache.into_raw()).unwrap_or(ptr::null_mut()),
-            )
-        }
-    }
-
-    /// Put several objects back in the mempool.
-    ///
-    /// This function calls the multi-producer or the single-producer
-    /// version depending on the default behavior that was specified at
-    /// mempool creation time (see flags).
-    pub fn put_bulk<T: Pooled<R>, R>(&mut self, objs: &[T]) {
-        unsafe { ffi::_rte_mempool_put_bulk(self.as_raw(), objs.as_ptr() as *const _, objs.len() as u32) }
+    /// The cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
+}
 
-    /// Put several objects back in the mempool.
-    ///
-    /// This function calls the multi-producer or the single-producer
-    /// version depending on the default behavior that was specified at
-    /// mempool creation time (see flags).
-    pub fn put<T: Pooled<R>, R>(&mut self, obj: T) {
-        unsafe { ffi::_rte_mempool_put(self.as_raw(), obj.as_raw() as *mut _) }
-    }
+/// A user-owned mempool cache, which is flushed to the mempool and freed when dropped.
+#[derive(Debug)]
+pub struct CacheGuard<'a> {
+    cache: Cache,
+    pool: MemPoolRef<'a>,
+}
 
-    /// Get several objects from the mempool.
-    ///
-    /// If cache is enabled, objects will be retrieved first from cache,
-    /// subsequently from the common pool. Note that it can return -ENOENT when
-    /// the local cache and common pool are empty, even if cache from other
-    /// lcores are full.
-    pub fn generic_get<T: Pooled<R>, R>(&mut self, objs: &mut [T], cache: Option<Cache>) -> Result<()> {
-        unsafe {
-            ffi::_rte_mempool_generic_get(
-                self.as_raw(),
-                objs.as_mut_ptr() as *mut _,
-                objs.len() as u32,
-                cache.map(|cache| cache.into_raw()).unwrap_or(ptr::null_mut()),
-            )
-        }
-        .as_result()
-        .map(|_| ())
+impl<'a> CacheGuard<'a> {
+    /// Create a user-owned cache for the mempool.
+    pub fn new(pool: MemPoolRef<'a>, size: usize, socket: Socket) -> Result<Self> {
+        Cache::create(size, socket).map(|cache| CacheGuard { cache, pool })
     }
 
-    /// Get several objects from the mempool.
-    ///
-    /// This function calls the multi-consumers or the single-consumer
-    /// version, depending on the default behaviour that was specified at
-    /// mempool creation time (see flags).
-    ///
-    /// If cache is enabled, objects will be retrieved first from cache,
-    /// subsequently from the common pool. Note that it can return -ENOENT when
-    /// the local cache and common pool are empty, even if cache from other
-    /// lcores are full.
-    pub fn get_bulk<T: Pooled<R>, R>(&mut self, objs: &mut [T]) -> Result<()> {
-        unsafe { ffi::_rte_mempool_get_bulk(self.as_raw(), objs.as_mut_ptr() as *mut _, objs.len() as u32) }
-            .as_result()
-            .map(|_| ())
+    /// Flush the cache to the mempool.
+    pub fn flush(&self) {
+        self.pool.flush(&self.cache)
     }
+}
 
-    /// Get several objects from the mempool.
-    ///
-    /// This function calls the multi-consumers or the single-consumer
-    /// version, depending on the default behaviour that was specified at
-    /// mempool creation time (see flags).
-    ///
-    /// If cache is enabled, objects will be retrieved first from cache,
-    /// subsequently from the common pool. Note that it can return -ENOENT when
-    /// the local cache and common pool are empty, even if cache from other
-    /// lcores are full.
-    pub fn get<T: Pooled<R>, R>(&mut self) -> Result<T> {
-        let mut obj = ptr::null_mut();
+impl<'a> Deref for CacheGuard<'a> {
+    type Target = Cache;
 
-        unsafe { ffi::_rte_mempool_get(self.as_raw(), &mut obj) }
-            .as_result()
-            .map(|_| (obj as *mut T::Raw).into())
+    fn deref(&self) -> &Self::Target {
+        &self.cache
     }
+}
 
-    /// Get a contiguous blocks of objects from the mempool.
-    ///
-    /// If cache is enabled, consider to flush it first, to reuse objects
-    /// as soon as possible.
-    ///
-    /// The application should check that the driver supports the operation
-    /// by calling rte_mempool_ops_get_info() and checking that `contig_block_size`
-    /// is not zero.
-    pub fn get_contig_blocks<T: Pooled<R>, R>(&mut self, objs: &mut [T]) -> Result<()> {
-        unsafe { ffi::_rte_mempool_get_contig_blocks(self.as_raw(), objs.as_mut_ptr() as *mut _, objs.len() as u32) }
-            .as_result()
-            .map(|_| ())
+impl<'a> Drop for CacheGuard<'a> {
+    fn drop(&mut self) {
+        self.flush();
+
+        unsafe { ffi::rte_mempool_cache_free(self.cache.as_raw()) }
     }
 }
@@ -0,0 +1,200 @@
+//!
+//! Per-packet metadata propagated between pipeline stages.
+//!
+//! The metering, ACL and flow table stages of a pipeline share a small `PktMeta`,
+//! the color from the meter, the id of the matched flow and the verdict of the ACL,
+//! so the stages compose without bespoke glue between them.
+//!
+//! The metadata is stored in the private area of the mbuf, the mbuf pool must be created
+//! with at least `PRIV_SIZE` bytes of private area. A free bit of the offload flags marks
+//! the metadata as valid, the flags are reset when the mbuf is allocated or received,
+//! so the stale metadata of a recycled mbuf is never read.
+//!
+//! ```no_run
+//! use rte::mbuf::MBufPool;
+//! use rte::meta::{Color, Verdict, PRIV_SIZE};
+//! use rte::*;
+//!
+//! let mut pool = mbuf::pool_create("pool", 1024, 32, PRIV_SIZE, 2048, Socket::ANY).unwrap();
+//! let mut m = pool.alloc().unwrap();
+//!
+//! m.set_color(Color::Yellow);
+//! m.set_verdict(Verdict::Drop);
+//!
+//! assert_eq!(m.meta().color, Color::Yellow);
+//! assert_eq!(m.meta().verdict, Verdict::Drop);
+//! ```
+//!
+use std::mem;
+use std::ptr;
+
+use mbuf::{MBuf, MBufPool};
+
+/// The free bit of the offload flags which marks the metadata as valid.
+pub const PKT_META_VALID: u64 = 1 << 39;
+
+/// The minimum private area of the mbufs to store the metadata.
+pub const PRIV_SIZE: u16 = ((mem::size_of::<PktMeta>() + 7) & !7) as u16;
+
+/// The color of a packet, as marked by the meter.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Color {
+    #[default]
+    Green,
+    Yellow,
+    Red,
+}
+
+/// The decision of the pipeline stages on a packet.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Verdict {
+    /// No stage has decided yet.
+    #[default]
+    Continue,
+    /// Forward the packet.
+    Accept,
+    /// Drop the packet.
+    Drop,
+    /// Punt the packet to the slow path.
+    Punt,
+}
+
+/// The metadata of a packet shared by the pipeline stages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PktMeta {
+    /// The color marked by the meter.
+    pub color: Color,
+    /// The verdict of the ACL.
+    pub verdict: Verdict,
+    /// The id of the matched flow.
+    pub flow_id: Option<u32>,
+}
+
+impl PktMeta {
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_verdict(mut self, verdict: Verdict) -> Self {
+        self.verdict = verdict;
+        self
+    }
+
+    pub fn with_flow_id(mut self, flow_id: u32) -> Self {
+        self.flow_id = Some(flow_id);
+        self
+    }
+
+    /// A stage has decided the verdict of the packet.
+    pub fn is_decided(&self) -> bool {
+        self.verdict != Verdict::Continue
+    }
+}
+
+/// The mempool could store the metadata in the private area of its mbufs.
+pub fn has_room<P: MBufPool>(pool: &P) -> bool {
+    pool.priv_size() >= mem::size_of::<PktMeta>()
+}
+
+impl MBuf {
+    /// The private area of the mbuf could store the metadata.
+    #[inline]
+    pub fn has_meta_room(&self) -> bool {
+        self.priv_size as usize >= mem::size_of::<PktMeta>()
+    }
+
+    /// The metadata of the packet, or the default one if no stage has set it.
+    #[inline]
+    pub fn meta(&self) -> PktMeta {
+        if self.ol_flags & PKT_META_VALID != 0 && self.has_meta_room() {
+            unsafe { ptr::read(self.priv_addr().as_ptr() as *const PktMeta) }
+        } else {
+            PktMeta::default()
+        }
+    }
+
+    /// Set the metadata of the packet, returns `false` if the mbuf has no room for it.
+    #[inline]
+    pub fn set_meta(&mut self, meta: PktMeta) -> bool {
+        if self.has_meta_room() {
+            unsafe { ptr::write(self.priv_addr().as_ptr() as *mut PktMeta, meta) };
+
+            self.ol_flags |= PKT_META_VALID;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Update the metadata of the packet, returns `false` if the mbuf has no room for it.
+    #[inline]
+    pub fn update_meta<F: FnOnce(&mut PktMeta)>(&mut self, f: F) -> bool {
+        let mut meta = self.meta();
+
+        f(&mut meta);
+
+        self.set_meta(meta)
+    }
+
+    /// Invalidate the metadata of the packet.
+    #[inline]
+    pub fn clear_meta(&mut self) {
+        self.ol_flags &= !PKT_META_VALID;
+    }
+
+    /// Copy the metadata of the packet to its indirect mbuf.
+    ///
+    /// The offload flags are copied by the attachment, but not the private area.
+    pub(crate) fn copy_meta_to(&self, m: &mut MBuf) {
+        m.clear_meta();
+
+        if self.ol_flags & PKT_META_VALID != 0 {
+            m.set_meta(self.meta());
+        }
+    }
+
+    /// Set the color of the packet.
+    #[inline]
+    pub fn set_color(&mut self, color: Color) -> bool {
+        self.update_meta(|meta| meta.color = color)
+    }
+
+    /// Set the verdict of the packet.
+    #[inline]
+    pub fn set_verdict(&mut self, verdict: Verdict) -> bool {
+        self.update_meta(|meta| meta.verdict = verdict)
+    }
+
+    /// Set the id of the matched flow.
+    #[inline]
+    pub fn set_flow_id(&mut self, flow_id: u32) -> bool {
+        self.update_meta(|meta| meta.flow_id = Some(flow_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkt_meta() {
+        assert_eq!(PRIV_SIZE as usize % 8, 0);
+        assert!(PRIV_SIZE as usize >= mem::size_of::<PktMeta>());
+
+        let meta = PktMeta::default();
+
+        assert_eq!(meta.color, Color::Green);
+        assert!(!meta.is_decided());
+
+        let meta = meta.with_color(Color::Red).with_verdict(Verdict::Drop).with_flow_id(7);
+
+        assert_eq!(meta.color, Color::Red);
+        assert_eq!(meta.flow_id, Some(7));
+        assert!(meta.is_decided());
+        assert!(Color::Red > Color::Yellow);
+    }
+}
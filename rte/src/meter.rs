@@ -0,0 +1,181 @@
+//!
+//! Single Rate Three Color Marker (srTCM), as defined by RFC 2697.
+//!
+//! The meter has two token buckets, the committed bucket of `cbs` bytes and the excess bucket of `ebs` bytes,
+//! both are refilled at the committed information rate `cir`, the committed bucket is refilled first,
+//! and the overflowed tokens go to the excess bucket.
+//!
+//! The meter is color aware, the packet colored by a previous stage is never promoted to a better color,
+//! the uncolored packets are green in the metadata, so the meter works as color blind for them.
+//!
+use std::cmp;
+
+use libc;
+
+use common::{get_tsc_hz, rdtsc};
+use errors::{Result, RteError};
+use mbuf::MBuf;
+use meta::Color;
+
+/// The parameters of a srTCM meter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SrTcmParams {
+    /// Committed Information Rate (CIR), in bytes per second.
+    pub cir: u64,
+    /// Committed Burst Size (CBS), in bytes.
+    pub cbs: u64,
+    /// Excess Burst Size (EBS), in bytes.
+    pub ebs: u64,
+}
+
+/// The number of packets marked with each color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColorStats {
+    pub green: u64,
+    pub yellow: u64,
+    pub red: u64,
+}
+
+impl ColorStats {
+    fn add(&mut self, color: Color) {
+        match color {
+            Color::Green => self.green += 1,
+            Color::Yellow => self.yellow += 1,
+            Color::Red => self.red += 1,
+        }
+    }
+}
+
+/// A srTCM meter, which should be used by a single lcore.
+#[derive(Clone, Debug)]
+pub struct SrTcm {
+    params: SrTcmParams,
+    hz: u64,
+    tc: u64,
+    te: u64,
+    last: u64,
+    stats: ColorStats,
+}
+
+impl SrTcm {
+    /// Create a meter with the full buckets.
+    pub fn new(params: SrTcmParams) -> Result<Self> {
+        Self::with_clock(params, get_tsc_hz(), rdtsc())
+    }
+
+    fn with_clock(params: SrTcmParams, hz: u64, now: u64) -> Result<Self> {
+        if params.cir == 0 || params.cbs + params.ebs == 0 || hz == 0 {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        Ok(SrTcm {
+            params,
+            hz,
+            tc: params.cbs,
+            te: params.ebs,
+            last: now,
+            stats: ColorStats::default(),
+        })
+    }
+
+    /// The parameters of the meter.
+    pub fn params(&self) -> &SrTcmParams {
+        &self.params
+    }
+
+    /// The number of packets marked with each color.
+    pub fn stats(&self) -> &ColorStats {
+        &self.stats
+    }
+
+    fn refill(&mut self, now: u64) {
+        let elapsed = now.saturating_sub(self.last);
+        let tokens = u128::from(elapsed) * u128::from(self.params.cir) / u128::from(self.hz);
+
+        if tokens == 0 {
+            return;
+        }
+
+        // the partial token is kept by advancing the clock only for the whole tokens
+        self.last += (tokens * u128::from(self.hz) / u128::from(self.params.cir)) as u64;
+
+        let tokens = cmp::min(tokens, u128::from(self.params.cbs + self.params.ebs)) as u64;
+        let tc = cmp::min(self.tc + tokens, self.params.cbs);
+
+        self.te = cmp::min(self.te + tokens - (tc - self.tc), self.params.ebs);
+        self.tc = tc;
+    }
+
+    /// Mark a packet of `len` bytes at the TSC `now`, with the color of a previous stage.
+    pub fn color(&mut self, now: u64, len: u64, color: Color) -> Color {
+        self.refill(now);
+
+        let color = if color == Color::Green && self.tc >= len {
+            self.tc -= len;
+
+            Color::Green
+        } else if color != Color::Red && self.te >= len {
+            self.te -= len;
+
+            Color::Yellow
+        } else {
+            Color::Red
+        };
+
+        self.stats.add(color);
+
+        color
+    }
+
+    /// Mark a burst of packets, the color is read from and written to the metadata of the packets.
+    pub fn color_burst(&mut self, pkts: &mut [MBuf]) {
+        let now = rdtsc();
+
+        for m in pkts {
+            let color = self.color(now, m.pkt_len() as u64, m.meta().color);
+
+            m.set_color(color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srtcm() {
+        let params = SrTcmParams {
+            cir: 1000,
+            cbs: 100,
+            ebs: 200,
+        };
+
+        assert!(SrTcm::with_clock(SrTcmParams::default(), 1000, 0).is_err());
+
+        let mut meter = SrTcm::with_clock(params, 1000, 0).unwrap();
+
+        assert_eq!(meter.color(0, 100, Color::Green), Color::Green);
+        assert_eq!(meter.color(0, 100, Color::Green), Color::Yellow);
+        assert_eq!(meter.color(0, 100, Color::Green), Color::Yellow);
+        assert_eq!(meter.color(0, 100, Color::Green), Color::Red);
+
+        // 50 tokens are refilled after 50 cycles
+        assert_eq!(meter.color(50, 50, Color::Green), Color::Green);
+        assert_eq!(meter.color(50, 1, Color::Green), Color::Red);
+
+        // the committed bucket is full, and the overflowed tokens go to the excess bucket
+        assert_eq!(meter.color(1000, 50, Color::Yellow), Color::Yellow);
+        assert_eq!(meter.color(1000, 100, Color::Green), Color::Green);
+        assert_eq!(meter.color(1000, 1, Color::Red), Color::Red);
+
+        assert_eq!(
+            *meter.stats(),
+            ColorStats {
+                green: 3,
+                yellow: 3,
+                red: 3,
+            }
+        );
+    }
+}
@@ -0,0 +1,628 @@
+//!
+//! Metrics in the Prometheus text exposition format.
+//!
+//! The port stats, extended stats, mempool utilization, mbuf balance and the user registered counters
+//! are rendered in the Prometheus text format, and with the `metrics-prometheus` feature,
+//! an exporter serves them over HTTP from a std thread, so the operators could scrape DPDK apps directly.
+//!
+//! The exporter thread is a non-EAL thread, which doesn't use the per-lcore mempool caches.
+//!
+//! The values published to the rte_metrics library, e.g. the port bitrates calculated by
+//! `accounting::PortBitrate`, are exported as `rte_port_metrics`, once the library was initialized with `init`.
+//!
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ffi;
+
+use errors::{Result, RteError};
+use ethdev::{self, EthDevice, PortId, XStatsIds};
+use lcore;
+use mbuf::balance;
+use memory::Socket;
+use mempool::{MemPool, MemPoolRef, RawMemoryPool};
+
+#[cfg(feature = "metrics-prometheus")]
+pub use self::exporter::{serve, Exporter};
+
+/// Initialize the rte_metrics library, which must be called from the primary process.
+pub fn init(socket: Socket) {
+    unsafe { ffi::rte_metrics_init(socket.raw()) }
+}
+
+/// Get the values published to the rte_metrics library, of a port or the global ones.
+pub fn values(port_id: Option<PortId>) -> Result<Vec<(String, u64)>> {
+    let port_id = port_id.map_or(ffi::RTE_METRICS_GLOBAL, i32::from);
+
+    unsafe {
+        let n = ffi::rte_metrics_get_names(ptr::null_mut(), 0);
+
+        if n < 0 {
+            return Err(RteError(-n).into());
+        }
+
+        let mut names = vec![ffi::rte_metric_name::default(); n as usize];
+        let mut values = vec![ffi::rte_metric_value::default(); n as usize];
+
+        let ret = ffi::rte_metrics_get_names(names.as_mut_ptr(), n as u16);
+
+        if ret < 0 {
+            return Err(RteError(-ret).into());
+        }
+
+        let ret = ffi::rte_metrics_get_values(port_id, values.as_mut_ptr(), n as u16);
+
+        if ret < 0 {
+            return Err(RteError(-ret).into());
+        }
+
+        Ok(values
+            .iter()
+            .take(ret as usize)
+            .filter_map(|value| {
+                names.get(value.key as usize).map(|name| {
+                    (
+                        CStr::from_ptr(name.name.as_ptr()).to_string_lossy().into_owned(),
+                        value.value,
+                    )
+                })
+            })
+            .collect())
+    }
+}
+
+/// The type of a metric.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    /// A cumulative metric which only increases.
+    Counter,
+    /// A metric which can arbitrarily go up and down.
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+        }
+    }
+}
+
+/// A user registered metric value, which can be updated from any lcore.
+#[derive(Debug, Default)]
+pub struct Metric(AtomicU64);
+
+impl Metric {
+    /// Increase the value by 1.
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1)
+    }
+
+    /// Increase the value.
+    #[inline]
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Set the value, for a gauge.
+    #[inline]
+    pub fn set(&self, n: u64) {
+        self.0.store(n, Ordering::Relaxed)
+    }
+
+    /// Get the current value.
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+type Labels = Vec<(String, String)>;
+
+struct Family {
+    name: String,
+    help: String,
+    kind: MetricType,
+    metrics: Vec<(Labels, Arc<Metric>)>,
+}
+
+/// A registry of the metrics to export.
+#[derive(Default)]
+pub struct Registry {
+    ports: Mutex<Vec<PortId>>,
+    xstats: Mutex<Vec<XStatsIds>>,
+    families: Mutex<Vec<Family>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export the basic stats and extended stats of the port.
+    pub fn add_port(&self, port_id: PortId) -> &Self {
+        let mut ports = self.ports.lock().unwrap();
+
+        if !ports.contains(&port_id) {
+            ports.push(port_id);
+        }
+
+        self
+    }
+
+    /// Export the extended stats of the port matching the patterns, e.g. `rx_q*_errors`,
+    /// which are resolved to ids once, instead of retrieving the whole name table on every scrape.
+    pub fn add_port_xstats<S: AsRef<str>>(&self, port_id: PortId, patterns: &[S]) -> Result<&Self> {
+        let ids = XStatsIds::resolve(port_id, patterns)?;
+        let mut xstats = self.xstats.lock().unwrap();
+
+        xstats.retain(|ids| ids.port_id() != port_id);
+        xstats.push(ids);
+
+        drop(xstats);
+
+        Ok(self.add_port(port_id))
+    }
+
+    /// Register a counter.
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Arc<Metric> {
+        self.register(name, help, MetricType::Counter, labels)
+    }
+
+    /// Register a gauge.
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Arc<Metric> {
+        self.register(name, help, MetricType::Gauge, labels)
+    }
+
+    /// Register a metric, the metrics of the same name share the help and type.
+    ///
+    /// Returns the registered metric if the name and labels have been registered.
+    pub fn register(&self, name: &str, help: &str, kind: MetricType, labels: &[(&str, &str)]) -> Arc<Metric> {
+        let labels = labels
+            .iter()
+            .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+            .collect::<Vec<_>>();
+        let mut families = self.families.lock().unwrap();
+
+        let idx = match families.iter().position(|f| f.name == name) {
+            Some(idx) => idx,
+            None => {
+                families.push(Family {
+                    name: name.to_owned(),
+                    help: help.to_owned(),
+                    kind,
+                    metrics: vec![],
+                });
+
+                families.len() - 1
+            }
+        };
+        let family = &mut families[idx];
+
+        if let Some((_, metric)) = family.metrics.iter().find(|&(l, _)| *l == labels) {
+            return metric.clone();
+        }
+
+        let metric = Arc::new(Metric::default());
+
+        family.metrics.push((labels, metric.clone()));
+
+        metric
+    }
+
+    /// Render all the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        render_ports(&mut out, &self.ports.lock().unwrap(), &self.xstats.lock().unwrap());
+        render_mempools(&mut out);
+        render_mbuf_balance(&mut out);
+
+        for family in self.families.lock().unwrap().iter() {
+            write_header(&mut out, &family.name, &family.help, family.kind);
+
+            for (labels, metric) in &family.metrics {
+                let labels = labels.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect::<Vec<_>>();
+
+                write_sample(&mut out, &family.name, &labels, metric.get());
+            }
+        }
+
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: MetricType) {
+    let _ = writeln!(
+        out,
+        "# HELP {} {}",
+        name,
+        help.replace('\\', "\\\\").replace('\n', "\\n")
+    );
+    let _ = writeln!(out, "# TYPE {} {}", name, kind.as_str());
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: u64) {
+    out.push_str(name);
+
+    if !labels.is_empty() {
+        out.push('{');
+
+        for (i, &(k, v)) in labels.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+
+            let v = v.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            let _ = write!(out, "{}=\"{}\"", k, v);
+        }
+
+        out.push('}');
+    }
+
+    let _ = writeln!(out, " {}", value);
+}
+
+fn render_ports(out: &mut String, ports: &[PortId], xstats: &[XStatsIds]) {
+    let stats = ports
+        .iter()
+        .filter_map(|&port_id| port_id.stats().ok().map(|stats| (port_id.to_string(), stats)))
+        .collect::<Vec<_>>();
+
+    macro_rules! port_metric {
+        ($name:expr, $help:expr, $field:ident) => {
+            write_header(out, $name, $help, MetricType::Counter);
+
+            for &(ref port, ref stats) in &stats {
+                write_sample(out, $name, &[("port", port)], stats.$field);
+            }
+        };
+    }
+
+    port_metric!(
+        "rte_port_rx_packets_total",
+        "Total number of successfully received packets.",
+        ipackets
+    );
+    port_metric!(
+        "rte_port_tx_packets_total",
+        "Total number of successfully transmitted packets.",
+        opackets
+    );
+    port_metric!(
+        "rte_port_rx_bytes_total",
+        "Total number of successfully received bytes.",
+        ibytes
+    );
+    port_metric!(
+        "rte_port_tx_bytes_total",
+        "Total number of successfully transmitted bytes.",
+        obytes
+    );
+    port_metric!(
+        "rte_port_rx_missed_total",
+        "Total of RX packets dropped by the HW.",
+        imissed
+    );
+    port_metric!(
+        "rte_port_rx_errors_total",
+        "Total number of erroneous received packets.",
+        ierrors
+    );
+    port_metric!(
+        "rte_port_tx_errors_total",
+        "Total number of failed transmitted packets.",
+        oerrors
+    );
+    port_metric!(
+        "rte_port_rx_nombuf_total",
+        "Total number of RX mbuf allocation failures.",
+        rx_nombuf
+    );
+
+    write_header(
+        out,
+        "rte_port_xstats",
+        "Extended statistics of the port.",
+        MetricType::Counter,
+    );
+
+    for &port_id in ports {
+        let port = port_id.to_string();
+
+        match xstats.iter().find(|ids| ids.port_id() == port_id) {
+            Some(ids) => {
+                for (name, value) in ids.get().unwrap_or_default() {
+                    write_sample(out, "rte_port_xstats", &[("port", &port), ("name", name)], value);
+                }
+            }
+            None => {
+                for (name, value) in port_xstats(port_id) {
+                    write_sample(out, "rte_port_xstats", &[("port", &port), ("name", &name)], value);
+                }
+            }
+        }
+    }
+
+    write_header(
+        out,
+        "rte_port_metrics",
+        "The values published to the rte_metrics library.",
+        MetricType::Gauge,
+    );
+
+    for &port_id in ports {
+        let port = port_id.to_string();
+
+        for (name, value) in values(Some(port_id)).unwrap_or_default() {
+            write_sample(out, "rte_port_metrics", &[("port", &port), ("name", &name)], value);
+        }
+    }
+}
+
+fn port_xstats(port_id: PortId) -> Vec<(String, u64)> {
+    port_id.xstats().unwrap_or_default()
+}
+
+struct PoolUsage {
+    name: String,
+    size: u64,
+    avail: u64,
+    in_use: u64,
+}
+
+unsafe extern "C" fn pool_usage_stub(mp: *mut RawMemoryPool, arg: *mut c_void) {
+    let pools = &mut *(arg as *mut Vec<PoolUsage>);
+    let mp = MemPoolRef::from_ptr(mp);
+
+    pools.push(PoolUsage {
+        name: mp.name().to_owned(),
+        size: u64::from(mp.size),
+        avail: mp.avail_count() as u64,
+        in_use: mp.in_use_count() as u64,
+    });
+}
+
+fn render_mempools(out: &mut String) {
+    let mut pools: Vec<PoolUsage> = vec![];
+
+    unsafe { ffi::rte_mempool_walk(Some(pool_usage_stub), &mut pools as *mut _ as *mut c_void) }
+
+    macro_rules! pool_metric {
+        ($name:expr, $help:expr, $field:ident) => {
+            write_header(out, $name, $help, MetricType::Gauge);
+
+            for pool in &pools {
+                write_sample(out, $name, &[("pool", &pool.name)], pool.$field);
+            }
+        };
+    }
+
+    pool_metric!(
+        "rte_mempool_size",
+        "The maximum number of elements in the mempool.",
+        size
+    );
+    pool_metric!(
+        "rte_mempool_avail",
+        "The number of available elements in the mempool.",
+        avail
+    );
+    pool_metric!(
+        "rte_mempool_in_use",
+        "The number of elements allocated from the mempool.",
+        in_use
+    );
+}
+
+fn render_mbuf_balance(out: &mut String) {
+    let pools = balance::snapshot();
+
+    macro_rules! balance_metric {
+        ($name:expr, $help:expr, $field:ident) => {
+            write_header(out, $name, $help, MetricType::Counter);
+
+            for pool in &pools {
+                for &(lcore_id, counts) in &pool.lcores {
+                    let lcore = if lcore_id == lcore::LCORE_ID_ANY {
+                        "any".to_owned()
+                    } else {
+                        lcore_id.to_string()
+                    };
+
+                    write_sample(
+                        out,
+                        $name,
+                        &[("pool", &pool.name), ("lcore", &lcore)],
+                        counts.$field,
+                    );
+                }
+            }
+        };
+    }
+
+    balance_metric!(
+        "rte_mbuf_gets_total",
+        "Total number of mbufs got from the mempool by the lcore.",
+        gets
+    );
+    balance_metric!(
+        "rte_mbuf_puts_total",
+        "Total number of mbufs put back to the mempool by the lcore.",
+        puts
+    );
+}
+
+#[cfg(feature = "metrics-prometheus")]
+mod exporter {
+    use std::io::{self, BufRead, BufReader, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use errors::Result;
+    use lcore;
+
+    use super::Registry;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// A Prometheus exporter which serves the metrics at `/metrics`.
+    ///
+    /// The exporter thread is stopped when the exporter is dropped.
+    pub struct Exporter {
+        addr: SocketAddr,
+        stop: Arc<AtomicBool>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl Exporter {
+        /// The local address of the exporter.
+        pub fn local_addr(&self) -> SocketAddr {
+            self.addr
+        }
+
+        /// Stop the exporter thread.
+        pub fn stop(mut self) {
+            self.shutdown()
+        }
+
+        fn shutdown(&mut self) {
+            self.stop.store(true, Ordering::Relaxed);
+
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    impl Drop for Exporter {
+        fn drop(&mut self) {
+            self.shutdown()
+        }
+    }
+
+    /// Serve the metrics of the registry from a std thread.
+    ///
+    /// The thread should be spawned from an EAL thread after `eal::init`,
+    /// it runs as a non-EAL thread without lcore id.
+    pub fn serve<A: ToSocketAddrs>(addr: A, registry: Arc<Registry>) -> Result<Exporter> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        listener.set_nonblocking(true)?;
+
+        let handle = thread::Builder::new().name("rte-metrics".to_owned()).spawn({
+            let stop = stop.clone();
+
+            move || {
+                debug_assert!(lcore::current().is_none());
+
+                info!("serving Prometheus metrics at http://{}/metrics", addr);
+
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, peer)) => {
+                            if let Err(err) = handle_request(stream, &registry) {
+                                debug!("fail to serve metrics to {}, {}", peer, err);
+                            }
+                        }
+                        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                        Err(err) => {
+                            warn!("fail to accept metrics connection, {}", err);
+
+                            thread::sleep(POLL_INTERVAL)
+                        }
+                    }
+                }
+
+                debug!("metrics exporter at {} stopped", addr);
+            }
+        })?;
+
+        Ok(Exporter {
+            addr,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    fn handle_request(stream: TcpStream, registry: &Registry) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+
+        reader.read_line(&mut request)?;
+
+        // skip the request headers
+        let mut line = String::new();
+
+        while reader.read_line(&mut line)? > 2 {
+            line.clear();
+        }
+
+        let mut stream = reader.into_inner();
+        let mut parts = request.split_whitespace();
+
+        match (parts.next(), parts.next()) {
+            (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+                let body = registry.render();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            }
+            (Some("GET"), Some(_)) => write!(
+                stream,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+            _ => write!(
+                stream,
+                "HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let mut out = String::new();
+
+        write_header(&mut out, "app_drops_total", "Dropped packets.", MetricType::Counter);
+        write_sample(&mut out, "app_drops_total", &[], 3);
+        write_sample(
+            &mut out,
+            "app_drops_total",
+            &[("port", "0"), ("reason", "no \"route\"")],
+            42,
+        );
+
+        assert_eq!(
+            out,
+            "# HELP app_drops_total Dropped packets.\n\
+             # TYPE app_drops_total counter\n\
+             app_drops_total 3\n\
+             app_drops_total{port=\"0\",reason=\"no \\\"route\\\"\"} 42\n"
+        );
+    }
+}
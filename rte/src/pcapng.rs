@@ -0,0 +1,272 @@
+//!
+//! Write the captured packets to a pcapng file.
+//!
+//! Each captured port and direction is an interface of the section, with nanosecond timestamps,
+//! so the packets of the ports could be merged into a single file, which is readable by wireshark and tcpdump.
+//!
+//! The file could be rotated by size or time with `Rotation`, like `tcpdump -C` or `tcpdump -G`.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::time::SystemTime;
+//!
+//! use rte::pcapng::Writer;
+//!
+//! let mut w = Writer::new(File::create("capture.pcapng").unwrap(), "rte-capture").unwrap();
+//! let rx = w.add_interface("port0-rx", 0).unwrap();
+//!
+//! w.write_packet(rx, SystemTime::now(), &[0; 64], 64).unwrap();
+//! ```
+//!
+use std::cmp;
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use libc;
+
+use errors::{Result, RteError};
+use mbuf::MBuf;
+
+/// The block type of the section header block.
+const BLOCK_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+/// The block type of the interface description block.
+const BLOCK_INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+/// The block type of the enhanced packet block.
+const BLOCK_ENHANCED_PACKET: u32 = 0x0000_0006;
+
+/// The magic number to detect the byte order of the section.
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+const VERSION_MAJOR: u16 = 1;
+const VERSION_MINOR: u16 = 0;
+
+const OPT_ENDOFOPT: u16 = 0;
+const OPT_SHB_USERAPPL: u16 = 4;
+const OPT_IF_NAME: u16 = 2;
+const OPT_IF_TSRESOL: u16 = 9;
+
+/// The link type of Ethernet.
+pub const LINKTYPE_ETHERNET: u16 = 1;
+/// The max length of the captured packets.
+pub const MAX_SNAPLEN: u32 = 65535;
+
+/// The interface id in the section.
+pub type InterfaceId = u32;
+
+/// A pcapng file writer with a single section.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    w: W,
+    interfaces: Vec<u32>,
+    written: u64,
+    packets: u64,
+}
+
+impl<W: Write> Writer<W> {
+    /// Create a writer and write the section header, with the name of the capturing application.
+    pub fn new(w: W, application: &str) -> Result<Self> {
+        let mut writer = Writer {
+            w,
+            interfaces: vec![],
+            written: 0,
+            packets: 0,
+        };
+
+        let mut body = vec![];
+
+        body.extend_from_slice(&BYTE_ORDER_MAGIC.to_ne_bytes());
+        body.extend_from_slice(&VERSION_MAJOR.to_ne_bytes());
+        body.extend_from_slice(&VERSION_MINOR.to_ne_bytes());
+        // the section length is not specified
+        body.extend_from_slice(&(-1i64).to_ne_bytes());
+        push_option(&mut body, OPT_SHB_USERAPPL, application.as_bytes());
+        push_option(&mut body, OPT_ENDOFOPT, &[]);
+
+        writer.write_block(BLOCK_SECTION_HEADER, &body)?;
+
+        Ok(writer)
+    }
+
+    /// Add an Ethernet interface with the name and snap length, `0` for the max length.
+    pub fn add_interface(&mut self, name: &str, snaplen: u32) -> Result<InterfaceId> {
+        let snaplen = if snaplen == 0 { MAX_SNAPLEN } else { snaplen };
+        let mut body = vec![];
+
+        body.extend_from_slice(&LINKTYPE_ETHERNET.to_ne_bytes());
+        body.extend_from_slice(&0u16.to_ne_bytes());
+        body.extend_from_slice(&snaplen.to_ne_bytes());
+        push_option(&mut body, OPT_IF_NAME, name.as_bytes());
+        // the timestamps are in nanoseconds
+        push_option(&mut body, OPT_IF_TSRESOL, &[9]);
+        push_option(&mut body, OPT_ENDOFOPT, &[]);
+
+        self.write_block(BLOCK_INTERFACE_DESCRIPTION, &body)?;
+        self.interfaces.push(snaplen);
+
+        Ok(self.interfaces.len() as InterfaceId - 1)
+    }
+
+    /// Write a packet captured on the interface, the data is truncated to the snap length of the interface.
+    pub fn write_packet(&mut self, interface: InterfaceId, ts: SystemTime, data: &[u8], orig_len: usize) -> Result<()> {
+        let snaplen = *self.interfaces.get(interface as usize).ok_or(RteError(libc::EINVAL))?;
+        let data = &data[..cmp::min(data.len(), snaplen as usize)];
+        let ts = ts.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let ts = ts.as_secs() * 1_000_000_000 + u64::from(ts.subsec_nanos());
+
+        let mut body = Vec::with_capacity(20 + data.len() + 3);
+
+        body.extend_from_slice(&interface.to_ne_bytes());
+        body.extend_from_slice(&((ts >> 32) as u32).to_ne_bytes());
+        body.extend_from_slice(&(ts as u32).to_ne_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_ne_bytes());
+        body.extend_from_slice(&(cmp::max(orig_len, data.len()) as u32).to_ne_bytes());
+        body.extend_from_slice(data);
+        pad(&mut body);
+
+        self.write_block(BLOCK_ENHANCED_PACKET, &body)?;
+        self.packets += 1;
+
+        Ok(())
+    }
+
+    /// Write a packet in the mbuf captured on the interface, the segments are linearized.
+    pub fn write_mbuf(&mut self, interface: InterfaceId, ts: SystemTime, m: &MBuf) -> Result<()> {
+        let snaplen = self.interfaces.get(interface as usize).cloned().unwrap_or(MAX_SNAPLEN);
+        let mut buf = vec![0; cmp::min(m.pkt_len(), snaplen as usize)];
+        let data = m.read(0, &mut buf).ok_or(RteError(libc::EINVAL))?;
+
+        self.write_packet(interface, ts, data, m.pkt_len())
+    }
+
+    /// The number of interfaces.
+    pub fn interfaces(&self) -> usize {
+        self.interfaces.len()
+    }
+
+    /// The number of written bytes, including the headers.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// The number of written packets.
+    pub fn packets(&self) -> u64 {
+        self.packets
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.w.flush().map_err(From::from)
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+
+    fn write_block(&mut self, ty: u32, body: &[u8]) -> Result<()> {
+        let len = (12 + body.len()) as u32;
+
+        self.w.write_all(&ty.to_ne_bytes())?;
+        self.w.write_all(&len.to_ne_bytes())?;
+        self.w.write_all(body)?;
+        self.w.write_all(&len.to_ne_bytes())?;
+        self.written += u64::from(len);
+
+        Ok(())
+    }
+}
+
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    body.extend_from_slice(&code.to_ne_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_ne_bytes());
+    body.extend_from_slice(value);
+    pad(body);
+}
+
+fn pad(body: &mut Vec<u8>) {
+    while !body.len().is_multiple_of(4) {
+        body.push(0);
+    }
+}
+
+/// The policy to rotate the capture files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rotate the file when its size exceeds the bytes.
+    pub max_size: Option<u64>,
+    /// Rotate the file when it was opened for the duration.
+    pub max_duration: Option<Duration>,
+}
+
+impl Rotation {
+    /// Rotate the file by size.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Rotate the file by time.
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// The file of the written bytes, which was opened at the instant, should be rotated now.
+    pub fn is_due(&self, written: u64, opened: Instant, now: Instant) -> bool {
+        self.max_size.is_some_and(|max_size| written >= max_size)
+            || self
+                .max_duration
+                .is_some_and(|max_duration| now.duration_since(opened) >= max_duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer() {
+        let mut w = Writer::new(vec![], "test").unwrap();
+
+        // 12 + 16 + (4 + 4) + 4
+        assert_eq!(w.written(), 40);
+
+        assert_eq!(w.add_interface("port0-rx", 0).unwrap(), 0);
+        assert_eq!(w.add_interface("port0-tx", 60).unwrap(), 1);
+
+        // 12 + 8 + (4 + 8) + (4 + 4) + 4
+        assert_eq!(w.written(), 40 + 44 * 2);
+
+        let ts = UNIX_EPOCH + Duration::new(1, 2);
+
+        w.write_packet(1, ts, &[0xff; 64], 64).unwrap();
+        assert!(w.write_packet(2, ts, &[0xff; 64], 64).is_err());
+
+        assert_eq!(w.packets(), 1);
+
+        let buf = w.into_inner();
+        let epb = &buf[40 + 44 * 2..];
+
+        assert_eq!(epb.len(), 12 + 20 + 60);
+        assert_eq!(&epb[..4], &BLOCK_ENHANCED_PACKET.to_ne_bytes());
+        assert_eq!(&epb[8..12], &1u32.to_ne_bytes());
+        assert_eq!(&epb[16..20], &1_000_000_002u32.to_ne_bytes());
+        assert_eq!(&epb[20..24], &60u32.to_ne_bytes());
+        assert_eq!(&epb[24..28], &64u32.to_ne_bytes());
+        assert_eq!(&epb[epb.len() - 4..], &(epb.len() as u32).to_ne_bytes());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let now = Instant::now();
+
+        assert!(!Rotation::default().is_due(1 << 40, now, now + Duration::from_secs(3600)));
+
+        let rotation = Rotation::default()
+            .with_max_size(1000)
+            .with_max_duration(Duration::from_secs(60));
+
+        assert!(!rotation.is_due(999, now, now + Duration::from_secs(59)));
+        assert!(rotation.is_due(1000, now, now));
+        assert!(rotation.is_due(0, now, now + Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,154 @@
+//!
+//! Capture the packets of a running primary process from a secondary process.
+//!
+//! The primary process must initialize the packet capture framework with `init` after the EAL,
+//! then a secondary process could enable the capture on the ports and queues of the primary process,
+//! the RX or TX packets are copied to the mbufs of the pool, and enqueued to the ring of the secondary process.
+//!
+//! ```no_run
+//! use rte::memory::Socket;
+//! use rte::pdump::{self, Capture, Direction};
+//! use rte::ring::RingFlags;
+//! use rte::*;
+//!
+//! let pool = mbuf::pool_create("capture", 8192, 256, 0, mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16, Socket::ANY).unwrap();
+//! let ring = ring::create("capture", 4096, Socket::ANY, RingFlags::SC_DEQ).unwrap();
+//!
+//! let capture = Capture::enable(0, pdump::ALL_QUEUES, Direction::RTE_PDUMP_FLAG_RXTX, &ring, &pool).unwrap();
+//!
+//! let mut pkts = [::std::ptr::null_mut::<ffi::rte_mbuf>(); 32];
+//! let n = ring.dequeue_burst(&mut pkts);
+//! ```
+//!
+use std::ffi::CString;
+use std::ptr;
+
+use ffi;
+
+use errors::{rte_error, Result};
+use ethdev::{PortId, QueueId};
+use mempool::MemPool;
+use ring::Ring;
+use utils::AsRaw;
+
+/// Capture all the queues of a port.
+pub const ALL_QUEUES: QueueId = ffi::RTE_PDUMP_ALL_QUEUES as QueueId;
+
+bitflags! {
+    /// The directions of the captured packets.
+    pub struct Direction: u32 {
+        /// Capture the received packets.
+        const RTE_PDUMP_FLAG_RX = ffi::RTE_PDUMP_FLAG_RX;
+        /// Capture the transmitted packets.
+        const RTE_PDUMP_FLAG_TX = ffi::RTE_PDUMP_FLAG_TX;
+        /// Capture the received and transmitted packets.
+        const RTE_PDUMP_FLAG_RXTX = ffi::RTE_PDUMP_FLAG_RXTX;
+    }
+}
+
+/// Initialize the packet capture framework in the primary process,
+/// so the secondary processes could enable the capture on its ports.
+pub fn init() -> Result<()> {
+    rte_check!(unsafe { ffi::rte_pdump_init(ptr::null()) }; err => { rte_error() })
+}
+
+/// Uninitialize the packet capture framework in the primary process.
+pub fn uninit() -> Result<()> {
+    rte_check!(unsafe { ffi::rte_pdump_uninit() }; err => { rte_error() })
+}
+
+/// Enable the capture on the queue of the port, or `ALL_QUEUES` of the port.
+pub fn enable<P: MemPool>(port_id: PortId, queue_id: QueueId, flags: Direction, ring: &Ring, pool: &P) -> Result<()> {
+    rte_check!(unsafe {
+        ffi::rte_pdump_enable(port_id, queue_id, flags.bits, ring.as_raw(), pool.as_raw(), ptr::null_mut())
+    }; err => { rte_error() })
+}
+
+/// Enable the capture on the queue of the device, which is named with its name or PCI address.
+pub fn enable_by_device<P: MemPool>(
+    device: &str,
+    queue_id: QueueId,
+    flags: Direction,
+    ring: &Ring,
+    pool: &P,
+) -> Result<()> {
+    let device = CString::new(device)?;
+
+    rte_check!(unsafe {
+        ffi::rte_pdump_enable_by_deviceid(
+            device.as_ptr() as *mut _,
+            queue_id,
+            flags.bits,
+            ring.as_raw(),
+            pool.as_raw(),
+            ptr::null_mut(),
+        )
+    }; err => { rte_error() })
+}
+
+/// Disable the capture on the queue of the port, or `ALL_QUEUES` of the port.
+pub fn disable(port_id: PortId, queue_id: QueueId, flags: Direction) -> Result<()> {
+    rte_check!(unsafe { ffi::rte_pdump_disable(port_id, queue_id, flags.bits) }; err => { rte_error() })
+}
+
+/// Disable the capture on the queue of the device, which is named with its name or PCI address.
+pub fn disable_by_device(device: &str, queue_id: QueueId, flags: Direction) -> Result<()> {
+    let device = CString::new(device)?;
+
+    rte_check!(unsafe {
+        ffi::rte_pdump_disable_by_deviceid(device.as_ptr() as *mut _, queue_id, flags.bits)
+    }; err => { rte_error() })
+}
+
+/// An enabled capture on the queue of the port, which is disabled when dropped.
+#[derive(Debug)]
+pub struct Capture {
+    port_id: PortId,
+    queue_id: QueueId,
+    flags: Direction,
+}
+
+impl Capture {
+    /// Enable the capture on the queue of the port, or `ALL_QUEUES` of the port.
+    pub fn enable<P: MemPool>(
+        port_id: PortId,
+        queue_id: QueueId,
+        flags: Direction,
+        ring: &Ring,
+        pool: &P,
+    ) -> Result<Self> {
+        enable(port_id, queue_id, flags, ring, pool)?;
+
+        Ok(Capture {
+            port_id,
+            queue_id,
+            flags,
+        })
+    }
+
+    /// The captured port.
+    pub fn port_id(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The captured queue, or `ALL_QUEUES` of the port.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
+    }
+
+    /// The captured directions.
+    pub fn flags(&self) -> Direction {
+        self.flags
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        if let Err(err) = disable(self.port_id, self.queue_id, self.flags) {
+            warn!(
+                "fail to disable capture on port {} queue {}, {}",
+                self.port_id, self.queue_id, err
+            );
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//!
+//! The generic packet I/O traits, which decouple the packet processing from the sources and the sinks
+//! of the packets, e.g. the RX and TX queues of the Ethernet devices.
+//!
+use std::mem;
+use std::slice;
+
+use ethdev::{RxQueue, TxQueue};
+use mbuf::MBuf;
+
+/// A source of the packet bursts.
+pub trait PacketRx {
+    /// Receive a burst of at most `max` packets, which are appended to the vector,
+    /// returns the number of the received packets.
+    fn rx(&mut self, pkts: &mut Vec<MBuf>, max: usize) -> usize;
+}
+
+/// A sink of the packet bursts.
+pub trait PacketTx {
+    /// Send the packets, the sent ones are removed from the front of the vector,
+    /// returns the number of the sent packets.
+    fn tx(&mut self, pkts: &mut Vec<MBuf>) -> usize;
+}
+
+impl<'a> PacketRx for RxQueue<'a> {
+    fn rx(&mut self, pkts: &mut Vec<MBuf>, max: usize) -> usize {
+        let len = pkts.len();
+
+        pkts.reserve(max);
+
+        unsafe {
+            let room = slice::from_raw_parts_mut(pkts.as_mut_ptr().add(len) as *mut mem::MaybeUninit<MBuf>, max);
+            let n = self.rx_burst_uninit(room);
+
+            pkts.set_len(len + n);
+
+            n
+        }
+    }
+}
+
+impl PacketTx for TxQueue {
+    fn tx(&mut self, pkts: &mut Vec<MBuf>) -> usize {
+        let n = self.tx_burst(pkts);
+
+        // the sent mbufs are owned by the driver
+        for m in pkts.drain(..n) {
+            mem::forget(m);
+        }
+
+        n
+    }
+}
@@ -0,0 +1,657 @@
+//!
+//! A hugepage-backed append-only packet logger.
+//!
+//! The selected packets, or only their leading headers, are copied into a large ring file
+//! mapped from hugetlbfs, with an index of the records, so the datapath never touches the disk.
+//! The oldest records are overwritten when the ring is full.
+//!
+//! The file outlives the process, until it's removed or the host reboots, so the history before
+//! an incident could be extracted post-mortem, by reopening the log and exporting it to pcap.
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! use rte::filter::Filter;
+//! use rte::pktlog::{PktLog, PktLogConfig};
+//! use rte::*;
+//!
+//! let mut log = PktLog::create(&PktLogConfig::new("/dev/hugepages/pktlog", 1 << 30).with_snaplen(128))
+//!     .unwrap()
+//!     .with_filter(Filter::new("tcp and port 443").unwrap());
+//!
+//! let pkts: Vec<mbuf::MBuf> = vec![]; // received from a port
+//!
+//! log.log_burst(&pkts, 0, 0);
+//!
+//! // after the incident
+//!
+//! let log = PktLog::open("/dev/hugepages/pktlog").unwrap();
+//!
+//! log.export_pcap(File::create("incident.pcap").unwrap()).unwrap();
+//! ```
+//!
+use std::cmp;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::mem;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{fence, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use failure::Error;
+use libc;
+
+use errors::{os_error, ErrorKind, Result};
+use ethdev::{PortId, QueueId};
+use filter::Filter;
+use mbuf::MBuf;
+
+/// The magic number of the packet log file.
+pub const PKTLOG_MAGIC: u32 = 0x4c47_4b50;
+/// The version of the packet log file.
+pub const PKTLOG_VERSION: u32 = 1;
+
+/// The default path of the packet log file, on the default hugetlbfs mount.
+pub const DEFAULT_PKTLOG_PATH: &str = "/dev/hugepages/rte_pktlog";
+/// The default size of the packet log file, which is rounded up to the page size of the file system.
+pub const DEFAULT_PKTLOG_SIZE: usize = 1 << 30;
+/// The default number of records in the index.
+pub const DEFAULT_PKTLOG_INDEX: u32 = 1 << 20;
+
+/// The magic number of the pcap file with nanosecond timestamps.
+const PCAP_MAGIC_NSEC: u32 = 0xa1b2_3c4d;
+const PCAP_VERSION_MAJOR: u16 = 2;
+const PCAP_VERSION_MINOR: u16 = 4;
+const PCAP_LINKTYPE_ETHERNET: u32 = 1;
+const PCAP_MAX_SNAPLEN: u32 = 65535;
+
+/// The records are aligned in the data ring.
+const RECORD_ALIGN: u64 = 8;
+
+/// The header of the packet log file.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Header {
+    magic: u32,
+    version: u32,
+    index_cap: u32,
+    snaplen: u32,
+    data_off: u64,
+    data_len: u64,
+    /// The reserved end of the data ring, updated before the data is written.
+    head: u64,
+    /// The sequence number of the next record, updated after the record is written.
+    next_seq: u64,
+    reserved: [u64; 2],
+}
+
+/// An entry of the index.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct Entry {
+    seq: u64,
+    off: u64,
+    ts_ns: u64,
+    caplen: u32,
+    len: u32,
+    port_id: PortId,
+    queue_id: QueueId,
+    reserved: u32,
+}
+
+/// The configuration of a new packet log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PktLogConfig {
+    /// The path of the log file, which should be on a hugetlbfs mount.
+    pub path: PathBuf,
+    /// The size of the log file.
+    pub size: usize,
+    /// The maximum number of records in the index.
+    pub index_cap: u32,
+    /// The maximum bytes of a packet which are logged, or 0 to log the whole packet.
+    pub snaplen: u32,
+}
+
+impl Default for PktLogConfig {
+    fn default() -> Self {
+        PktLogConfig::new(DEFAULT_PKTLOG_PATH, DEFAULT_PKTLOG_SIZE)
+    }
+}
+
+impl PktLogConfig {
+    /// The configuration of a log file of the size.
+    pub fn new<P: AsRef<Path>>(path: P, size: usize) -> Self {
+        PktLogConfig {
+            path: path.as_ref().to_owned(),
+            size,
+            index_cap: DEFAULT_PKTLOG_INDEX,
+            snaplen: 0,
+        }
+    }
+
+    /// Set the maximum number of records in the index.
+    pub fn with_index_cap(mut self, index_cap: u32) -> Self {
+        self.index_cap = index_cap;
+        self
+    }
+
+    /// Only log the leading bytes of the packets, e.g. the headers.
+    pub fn with_snaplen(mut self, snaplen: u32) -> Self {
+        self.snaplen = snaplen;
+        self
+    }
+}
+
+/// A logged packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PktRecord {
+    /// The sequence number of the record.
+    pub seq: u64,
+    /// The time when the packet was logged.
+    pub timestamp: SystemTime,
+    /// The port which received or sent the packet.
+    pub port_id: PortId,
+    /// The queue which received or sent the packet.
+    pub queue_id: QueueId,
+    /// The original length of the packet.
+    pub len: u32,
+    /// The logged bytes of the packet.
+    pub data: Vec<u8>,
+}
+
+/// The counters of the packet log.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PktLogStats {
+    /// The number of logged packets.
+    pub logged: u64,
+    /// The number of logged bytes.
+    pub bytes: u64,
+    /// The number of packets which don't match the filter.
+    pub filtered: u64,
+}
+
+/// An append-only packet log in a mapped ring file.
+pub struct PktLog {
+    path: PathBuf,
+    addr: *mut u8,
+    map_len: usize,
+    filter: Option<Filter>,
+    stats: PktLogStats,
+    buf: Vec<u8>,
+}
+
+unsafe impl Send for PktLog {}
+
+impl Drop for PktLog {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.addr as *mut _, self.map_len);
+        }
+    }
+}
+
+fn invalid<S: Into<String>>(msg: S) -> Error {
+    ErrorKind::InvalidPktLog(msg.into()).into()
+}
+
+fn align_up(n: u64, align: u64) -> u64 {
+    n.div_ceil(align) * align
+}
+
+fn map_file(f: &File, len: usize) -> Result<*mut u8> {
+    let addr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            f.as_raw_fd(),
+            0,
+        )
+    };
+
+    if addr == libc::MAP_FAILED {
+        Err(os_error())
+    } else {
+        Ok(addr as *mut u8)
+    }
+}
+
+impl PktLog {
+    /// Create a packet log file, or truncate the existing one.
+    ///
+    /// The size is rounded up to the page size of the file system, e.g. the hugepage size of hugetlbfs.
+    pub fn create(conf: &PktLogConfig) -> Result<Self> {
+        let index_cap = u64::from(conf.index_cap);
+        let data_off = align_up(
+            mem::size_of::<Header>() as u64 + index_cap * mem::size_of::<Entry>() as u64,
+            64,
+        );
+
+        if index_cap == 0 || data_off >= conf.size as u64 {
+            return Err(invalid(format!(
+                "{} bytes is too small for {} records",
+                conf.size, conf.index_cap
+            )));
+        }
+
+        let f = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&conf.path)?;
+
+        let page_size = unsafe {
+            let mut st: libc::statfs = mem::zeroed();
+
+            if libc::fstatfs(f.as_raw_fd(), &mut st) < 0 {
+                return Err(os_error());
+            }
+
+            st.f_bsize as u64
+        };
+        let map_len = align_up(conf.size as u64, page_size) as usize;
+
+        if unsafe { libc::ftruncate(f.as_raw_fd(), map_len as libc::off_t) } < 0 {
+            return Err(os_error());
+        }
+
+        let addr = map_file(&f, map_len)?;
+
+        unsafe {
+            ptr::write(
+                addr as *mut Header,
+                Header {
+                    magic: PKTLOG_MAGIC,
+                    version: PKTLOG_VERSION,
+                    index_cap: conf.index_cap,
+                    snaplen: conf.snaplen,
+                    data_off,
+                    data_len: map_len as u64 - data_off,
+                    head: 0,
+                    next_seq: 0,
+                    reserved: [0; 2],
+                },
+            );
+        }
+
+        Ok(PktLog {
+            path: conf.path.clone(),
+            addr,
+            map_len,
+            filter: None,
+            stats: PktLogStats::default(),
+            buf: vec![],
+        })
+    }
+
+    /// Open an existing packet log file, to extract its records or append to it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let f = OpenOptions::new().read(true).write(true).open(path)?;
+        let map_len = f.metadata()?.len() as usize;
+
+        if map_len < mem::size_of::<Header>() {
+            return Err(invalid(format!("{:?} is truncated", path)));
+        }
+
+        let addr = map_file(&f, map_len)?;
+        let log = PktLog {
+            path: path.to_owned(),
+            addr,
+            map_len,
+            filter: None,
+            stats: PktLogStats::default(),
+            buf: vec![],
+        };
+
+        let hdr = log.header();
+
+        if hdr.magic != PKTLOG_MAGIC || hdr.version != PKTLOG_VERSION {
+            return Err(invalid(format!("{:?} is not a packet log", path)));
+        }
+
+        let index_end = mem::size_of::<Header>() as u64 + u64::from(hdr.index_cap) * mem::size_of::<Entry>() as u64;
+
+        if hdr.index_cap == 0 || hdr.data_off < index_end || hdr.data_off + hdr.data_len > map_len as u64 {
+            return Err(invalid(format!("{:?} has a corrupted header", path)));
+        }
+
+        Ok(log)
+    }
+
+    /// Remove the packet log file, the mapped log is still valid until it's dropped.
+    pub fn remove(&self) -> Result<()> {
+        fs::remove_file(&self.path).map_err(Error::from)
+    }
+
+    /// Only log the packets which match the filter.
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// The path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The maximum bytes of a packet which are logged, or 0 if the whole packet is logged.
+    pub fn snaplen(&self) -> u32 {
+        self.header().snaplen
+    }
+
+    /// The size of the data ring.
+    pub fn capacity(&self) -> usize {
+        self.header().data_len as usize
+    }
+
+    /// The counters of the log since it was created or opened.
+    pub fn stats(&self) -> &PktLogStats {
+        &self.stats
+    }
+
+    fn header(&self) -> &Header {
+        unsafe { &*(self.addr as *const Header) }
+    }
+
+    fn header_mut(&mut self) -> &mut Header {
+        unsafe { &mut *(self.addr as *mut Header) }
+    }
+
+    fn entry(&self, seq: u64) -> *mut Entry {
+        let slot = (seq % u64::from(self.header().index_cap)) as usize;
+
+        unsafe { (self.addr.add(mem::size_of::<Header>()) as *mut Entry).add(slot) }
+    }
+
+    fn data(&self) -> *mut u8 {
+        unsafe { self.addr.add(self.header().data_off as usize) }
+    }
+
+    /// Copy the bytes into the data ring at the offset, which may wrap around.
+    fn write_data(&mut self, off: u64, buf: &[u8]) {
+        let data_len = self.header().data_len;
+        let pos = (off % data_len) as usize;
+        let first = cmp::min(buf.len(), data_len as usize - pos);
+
+        unsafe {
+            ptr::copy_nonoverlapping(buf.as_ptr(), self.data().add(pos), first);
+            ptr::copy_nonoverlapping(buf[first..].as_ptr(), self.data(), buf.len() - first);
+        }
+    }
+
+    fn read_data(&self, off: u64, len: usize) -> Vec<u8> {
+        let data_len = self.header().data_len;
+        let pos = (off % data_len) as usize;
+        let first = cmp::min(len, data_len as usize - pos);
+        let mut buf = Vec::with_capacity(len);
+
+        unsafe {
+            buf.extend_from_slice(slice::from_raw_parts(self.data().add(pos), first));
+            buf.extend_from_slice(slice::from_raw_parts(self.data(), len - first));
+        }
+
+        buf
+    }
+
+    /// Log the leading bytes of a packet, which starts from the Ethernet header.
+    ///
+    /// The `data` is truncated to the snaplen, and `len` is the original length of the packet.
+    pub fn log_bytes(&mut self, data: &[u8], len: usize, port_id: PortId, queue_id: QueueId, now: SystemTime) {
+        let hdr = *self.header();
+        let caplen = match hdr.snaplen {
+            0 => data.len(),
+            snaplen => cmp::min(data.len(), snaplen as usize),
+        };
+        let caplen = cmp::min(caplen as u64, hdr.data_len) as usize;
+        let off = hdr.head;
+        let seq = hdr.next_seq;
+
+        // reserve the space before overwriting the oldest records, so they are invalid even if crashed
+        self.header_mut().head = off + align_up(caplen as u64, RECORD_ALIGN);
+        fence(Ordering::Release);
+
+        self.write_data(off, &data[..caplen]);
+
+        let ts = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        unsafe {
+            ptr::write_volatile(
+                self.entry(seq),
+                Entry {
+                    seq,
+                    off,
+                    ts_ns: ts.as_secs() * 1_000_000_000 + u64::from(ts.subsec_nanos()),
+                    caplen: caplen as u32,
+                    len: len as u32,
+                    port_id,
+                    queue_id,
+                    reserved: 0,
+                },
+            );
+        }
+
+        // publish the record after it was written
+        fence(Ordering::Release);
+        self.header_mut().next_seq = seq + 1;
+
+        self.stats.logged += 1;
+        self.stats.bytes += caplen as u64;
+    }
+
+    /// Log a packet if it matches the filter, returns `true` if it was logged.
+    pub fn log(&mut self, m: &MBuf, port_id: PortId, queue_id: QueueId, now: SystemTime) -> bool {
+        if let Some(ref filter) = self.filter {
+            if !filter.matches_mbuf(m) {
+                self.stats.filtered += 1;
+
+                return false;
+            }
+        }
+
+        let pkt_len = m.pkt_len();
+        let caplen = match self.snaplen() {
+            0 => pkt_len,
+            snaplen => cmp::min(pkt_len, snaplen as usize),
+        };
+
+        if m.data_len() >= caplen {
+            let data = unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), caplen) };
+
+            self.log_bytes(data, pkt_len, port_id, queue_id, now);
+        } else {
+            // the segmented packet is gathered into the buffer
+            let mut buf = mem::take(&mut self.buf);
+
+            buf.resize(caplen, 0);
+
+            if let Some(data) = m.read(0, &mut buf) {
+                self.log_bytes(data, pkt_len, port_id, queue_id, now);
+            }
+
+            self.buf = buf;
+        }
+
+        true
+    }
+
+    /// Log a burst of packets, returns the number of logged packets.
+    pub fn log_burst(&mut self, pkts: &[MBuf], port_id: PortId, queue_id: QueueId) -> usize {
+        if pkts.is_empty() {
+            return 0;
+        }
+
+        let now = SystemTime::now();
+
+        pkts.iter().filter(|m| self.log(m, port_id, queue_id, now)).count()
+    }
+
+    /// The sequence numbers of the records in the index, which may be overwritten in the data ring.
+    fn seqs(&self) -> (u64, u64) {
+        let hdr = self.header();
+        let end = unsafe { ptr::read_volatile(&hdr.next_seq) };
+
+        fence(Ordering::Acquire);
+
+        (end.saturating_sub(u64::from(hdr.index_cap)), end)
+    }
+
+    fn record(&self, seq: u64) -> Option<PktRecord> {
+        let e = unsafe { ptr::read_volatile(self.entry(seq)) };
+
+        if !self.entry_valid(seq, &e) {
+            return None;
+        }
+
+        Some(PktRecord {
+            seq,
+            timestamp: UNIX_EPOCH + Duration::from_nanos(e.ts_ns),
+            port_id: e.port_id,
+            queue_id: e.queue_id,
+            len: e.len,
+            data: self.read_data(e.off, e.caplen as usize),
+        })
+    }
+
+    /// The number of records which could be extracted.
+    pub fn len(&self) -> usize {
+        let (start, end) = self.seqs();
+
+        (start..end)
+            .filter(|&seq| self.entry_valid(seq, &unsafe { ptr::read_volatile(self.entry(seq)) }))
+            .count()
+    }
+
+    /// The log has no record.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The entry is of the record, which was not overwritten by the newer ones in the data ring.
+    fn entry_valid(&self, seq: u64, e: &Entry) -> bool {
+        let hdr = self.header();
+
+        e.seq == seq && e.off <= hdr.head && hdr.head - e.off <= hdr.data_len && u64::from(e.caplen) <= hdr.data_len
+    }
+
+    /// An iterator over the records, from the oldest one.
+    pub fn records<'a>(&'a self) -> impl Iterator<Item = PktRecord> + 'a {
+        let (start, end) = self.seqs();
+
+        (start..end).filter_map(move |seq| self.record(seq))
+    }
+
+    /// Export the records to a pcap file with nanosecond timestamps, returns the number of records.
+    pub fn export_pcap<W: Write>(&self, w: W) -> Result<usize> {
+        let mut w = BufWriter::new(w);
+        let snaplen = match self.snaplen() {
+            0 => PCAP_MAX_SNAPLEN,
+            snaplen => snaplen,
+        };
+
+        w.write_all(&PCAP_MAGIC_NSEC.to_ne_bytes())?;
+        w.write_all(&PCAP_VERSION_MAJOR.to_ne_bytes())?;
+        w.write_all(&PCAP_VERSION_MINOR.to_ne_bytes())?;
+        w.write_all(&0i32.to_ne_bytes())?;
+        w.write_all(&0u32.to_ne_bytes())?;
+        w.write_all(&snaplen.to_ne_bytes())?;
+        w.write_all(&PCAP_LINKTYPE_ETHERNET.to_ne_bytes())?;
+
+        let mut n = 0;
+
+        for record in self.records() {
+            let ts = record.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+            w.write_all(&(ts.as_secs() as u32).to_ne_bytes())?;
+            w.write_all(&ts.subsec_nanos().to_ne_bytes())?;
+            w.write_all(&(record.data.len() as u32).to_ne_bytes())?;
+            w.write_all(&record.len.to_ne_bytes())?;
+            w.write_all(&record.data)?;
+
+            n += 1;
+        }
+
+        w.flush()?;
+
+        Ok(n)
+    }
+
+    /// Remove all the records.
+    pub fn clear(&mut self) {
+        let seq = self.header().next_seq;
+        let head = self.header().head;
+
+        // skip the whole ring, so the indexed records are invalid
+        self.header_mut().head = head + self.header().data_len + RECORD_ALIGN;
+        fence(Ordering::Release);
+        self.header_mut().next_seq = seq + u64::from(self.header().index_cap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn test_pktlog() {
+        let path = env::temp_dir().join(format!("rte-pktlog-{}", process::id()));
+        let now = UNIX_EPOCH + Duration::new(1_500_000_000, 123);
+
+        {
+            let mut log = PktLog::create(&PktLogConfig::new(&path, 4096).with_index_cap(4).with_snaplen(64)).unwrap();
+            let capacity = log.capacity() as u64;
+
+            assert!(log.is_empty());
+
+            for i in 0..6u8 {
+                log.log_bytes(&[i; 100], 100, 1, u16::from(i), now);
+            }
+
+            // only the last 4 records are indexed
+            let records: Vec<_> = log.records().collect();
+
+            assert_eq!(records.len(), 4);
+            assert_eq!(records[0].seq, 2);
+            assert_eq!(records[0].data, vec![2; 64]);
+            assert_eq!(records[3].queue_id, 5);
+            assert_eq!(records[3].len, 100);
+            assert_eq!(records[3].timestamp, now);
+
+            // the oldest records are overwritten in the data ring
+            for _ in 0..(capacity / 64) {
+                log.log_bytes(&[0xff; 64], 64, 1, 0, now);
+            }
+
+            assert!(log.records().all(|r| r.data == vec![0xff; 64]));
+        }
+
+        {
+            let mut log = PktLog::open(&path).unwrap();
+
+            assert_eq!(log.len(), 4);
+
+            let mut pcap = vec![];
+
+            assert_eq!(log.export_pcap(&mut pcap).unwrap(), 4);
+            assert_eq!(pcap.len(), 24 + 4 * (16 + 64));
+
+            log.clear();
+
+            assert!(log.is_empty());
+
+            log.remove().unwrap();
+        }
+
+        assert!(PktLog::open(&path).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//!
+//! Schedule the polling of many RX queues on a few lcores.
+//!
+//! When the lcores are scarce, a single lcore must service many ports and queues fairly,
+//! `Schedule` owns a set of RX queues and picks the next queue to poll with the policy:
+//!
+//! - `Policy::RoundRobin` polls each queue once in turn.
+//! - `Policy::Weighted` polls each queue as many times in a row as its weight.
+//! - `Policy::Deficit` grants each queue `quantum * weight` packets per round,
+//!   so a busy queue can't starve the others with full bursts.
+//!
+//! ```no_run
+//! use rte::poller::{Policy, Schedule};
+//! use rte::*;
+//!
+//! let mut sched = Schedule::new(Policy::Deficit(32));
+//!
+//! sched.add(0, 0).add_weighted(1, 0, 2);
+//!
+//! let mut pkts: Vec<Option<mbuf::MBuf>> = (0..32).map(|_| None).collect();
+//!
+//! loop {
+//!     if let Some((queue, n)) = sched.poll_next(&mut pkts) {
+//!         // handle the packets received from the queue
+//!     }
+//! }
+//! ```
+//!
+use std::cmp;
+
+use ethdev::{EthDevice, PortId, QueueHandle, QueueId};
+use mbuf;
+
+/// The policy to pick the next queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Poll each queue once in turn.
+    #[default]
+    RoundRobin,
+    /// Poll each queue as many times in a row as its weight.
+    Weighted,
+    /// Deficit round robin, each queue could receive up to `quantum * weight` packets per round.
+    Deficit(usize),
+}
+
+/// The polling statistics of a queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct QueueStats {
+    /// The number of polls.
+    pub polls: u64,
+    /// The number of polls which received nothing.
+    pub empty_polls: u64,
+    /// The number of received packets.
+    pub packets: u64,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    port_id: PortId,
+    queue_id: QueueId,
+    weight: usize,
+    credit: usize,
+    stats: QueueStats,
+}
+
+impl Entry {
+    fn handle(&self) -> QueueHandle {
+        QueueHandle::Rx(self.port_id, self.queue_id)
+    }
+}
+
+/// A set of RX queues polled with a scheduling policy.
+#[derive(Clone, Debug, Default)]
+pub struct Schedule {
+    policy: Policy,
+    queues: Vec<Entry>,
+    cursor: usize,
+}
+
+impl Schedule {
+    /// Create an empty schedule with the policy.
+    pub fn new(policy: Policy) -> Self {
+        Schedule {
+            policy,
+            queues: vec![],
+            cursor: 0,
+        }
+    }
+
+    /// The policy of the schedule.
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// The number of queues.
+    pub fn len(&self) -> usize {
+        self.queues.len()
+    }
+
+    /// The schedule has no queue.
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
+
+    /// The queues of the schedule.
+    pub fn queues<'a>(&'a self) -> impl Iterator<Item = QueueHandle> + 'a {
+        self.queues.iter().map(Entry::handle)
+    }
+
+    /// Add the RX queue with weight 1.
+    pub fn add(&mut self, port_id: PortId, queue_id: QueueId) -> &mut Self {
+        self.add_weighted(port_id, queue_id, 1)
+    }
+
+    /// Add the RX queue with the weight, or update the weight if the queue was added.
+    pub fn add_weighted(&mut self, port_id: PortId, queue_id: QueueId, weight: usize) -> &mut Self {
+        let weight = cmp::max(weight, 1);
+
+        if let Some(entry) = self
+            .queues
+            .iter_mut()
+            .find(|e| e.port_id == port_id && e.queue_id == queue_id)
+        {
+            entry.weight = weight;
+        } else {
+            self.queues.push(Entry {
+                port_id,
+                queue_id,
+                weight,
+                credit: 0,
+                stats: QueueStats::default(),
+            });
+        }
+
+        self
+    }
+
+    /// Remove the RX queue.
+    pub fn remove(&mut self, port_id: PortId, queue_id: QueueId) -> &mut Self {
+        if let Some(idx) = self
+            .queues
+            .iter()
+            .position(|e| e.port_id == port_id && e.queue_id == queue_id)
+        {
+            self.queues.remove(idx);
+
+            if idx < self.cursor {
+                self.cursor -= 1;
+            }
+            if self.cursor >= self.queues.len() {
+                self.cursor = 0;
+            }
+        }
+
+        self
+    }
+
+    /// Poll the next queue with the policy, returns the queue and the number of received packets.
+    ///
+    /// Returns `None` if the schedule has no queue.
+    pub fn poll_next(&mut self, pkts: &mut [Option<mbuf::MBuf>]) -> Option<(QueueHandle, usize)> {
+        self.poll_with(pkts, |port_id, queue_id, pkts| port_id.rx_burst(queue_id, pkts))
+    }
+
+    fn poll_with<T, F>(&mut self, pkts: &mut [T], mut rx_burst: F) -> Option<(QueueHandle, usize)>
+    where
+        F: FnMut(PortId, QueueId, &mut [T]) -> usize,
+    {
+        if self.queues.is_empty() {
+            return None;
+        }
+
+        let policy = self.policy;
+        let idx = self.cursor;
+        let entry = &mut self.queues[idx];
+
+        // a new turn of the queue
+        if entry.credit == 0 {
+            entry.credit = match policy {
+                Policy::RoundRobin => 1,
+                Policy::Weighted => entry.weight,
+                Policy::Deficit(quantum) => cmp::max(quantum, 1) * entry.weight,
+            };
+        }
+
+        let burst = match policy {
+            Policy::Deficit(_) => cmp::min(entry.credit, pkts.len()),
+            _ => pkts.len(),
+        };
+
+        let n = rx_burst(entry.port_id, entry.queue_id, &mut pkts[..burst]);
+
+        entry.stats.polls += 1;
+        entry.stats.packets += n as u64;
+
+        if n == 0 {
+            entry.stats.empty_polls += 1;
+        }
+
+        let done = match policy {
+            Policy::RoundRobin | Policy::Weighted => {
+                entry.credit -= 1;
+                entry.credit == 0
+            }
+            Policy::Deficit(_) => {
+                entry.credit -= n;
+                // the idle queue doesn't accumulate the deficit
+                if n < burst {
+                    entry.credit = 0;
+                }
+                entry.credit == 0
+            }
+        };
+
+        let handle = entry.handle();
+
+        if done {
+            self.cursor = (idx + 1) % self.queues.len();
+        }
+
+        Some((handle, n))
+    }
+
+    /// The polling statistics of the queues.
+    pub fn stats<'a>(&'a self) -> impl Iterator<Item = (QueueHandle, &'a QueueStats)> + 'a {
+        self.queues.iter().map(|e| (e.handle(), &e.stats))
+    }
+
+    /// The polling statistics of the queue.
+    pub fn queue_stats(&self, port_id: PortId, queue_id: QueueId) -> Option<&QueueStats> {
+        self.queues
+            .iter()
+            .find(|e| e.port_id == port_id && e.queue_id == queue_id)
+            .map(|e| &e.stats)
+    }
+
+    /// Reset the polling statistics of the queues.
+    pub fn reset_stats(&mut self) {
+        for entry in &mut self.queues {
+            entry.stats = QueueStats::default();
+        }
+    }
+
+    /// Jain's fairness index of the received packets normalized by the weights of the busy queues,
+    /// from `1 / n` for the most unfair to `1.0` for the perfect fairness.
+    ///
+    /// The idle queues, which received nothing in some polls, are excluded,
+    /// since they were given more chances than they needed.
+    pub fn fairness(&self) -> f64 {
+        let shares = self
+            .queues
+            .iter()
+            .filter(|e| e.stats.polls > 0 && e.stats.empty_polls == 0)
+            .map(|e| e.stats.packets as f64 / e.weight as f64)
+            .collect::<Vec<_>>();
+
+        let sum = shares.iter().sum::<f64>();
+        let sum_sq = shares.iter().map(|x| x * x).sum::<f64>();
+
+        if sum_sq == 0.0 {
+            1.0
+        } else {
+            sum * sum / (shares.len() as f64 * sum_sq)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(sched: &mut Schedule, rounds: usize) -> Vec<(QueueHandle, usize)> {
+        let mut pkts = [0u8; 32];
+
+        // every queue is always busy
+        (0..rounds)
+            .map(|_| sched.poll_with(&mut pkts, |_, _, pkts| pkts.len()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut sched = Schedule::new(Policy::RoundRobin);
+
+        assert!(sched.poll_with(&mut [0u8; 32], |_, _, _| 0).is_none());
+
+        sched.add(0, 0).add_weighted(1, 0, 3);
+
+        assert_eq!(
+            run(&mut sched, 4),
+            vec![
+                (QueueHandle::Rx(0, 0), 32),
+                (QueueHandle::Rx(1, 0), 32),
+                (QueueHandle::Rx(0, 0), 32),
+                (QueueHandle::Rx(1, 0), 32),
+            ]
+        );
+        assert!((sched.fairness() - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let mut sched = Schedule::new(Policy::Weighted);
+
+        sched.add(0, 0).add_weighted(1, 0, 3);
+
+        let queues = run(&mut sched, 8)
+            .into_iter()
+            .map(|(q, _)| q.portid())
+            .collect::<Vec<_>>();
+
+        assert_eq!(queues, vec![0, 1, 1, 1, 0, 1, 1, 1]);
+        assert_eq!(sched.queue_stats(1, 0).unwrap().packets, 6 * 32);
+        assert_eq!(sched.fairness(), 1.0);
+
+        sched.remove(0, 0);
+
+        assert_eq!(sched.len(), 1);
+        assert_eq!(run(&mut sched, 1), vec![(QueueHandle::Rx(1, 0), 32)]);
+    }
+
+    #[test]
+    fn test_deficit() {
+        let mut sched = Schedule::new(Policy::Deficit(16));
+
+        sched.add(0, 0).add_weighted(1, 0, 3);
+
+        assert_eq!(
+            run(&mut sched, 4),
+            vec![
+                (QueueHandle::Rx(0, 0), 16),
+                (QueueHandle::Rx(1, 0), 32),
+                (QueueHandle::Rx(1, 0), 16),
+                (QueueHandle::Rx(0, 0), 16),
+            ]
+        );
+
+        // the idle queue gives up its turn
+        let mut pkts = [0u8; 32];
+
+        assert_eq!(
+            sched.poll_with(&mut pkts, |port_id, _, pkts| if port_id == 1 { 4 } else { pkts.len() }),
+            Some((QueueHandle::Rx(1, 0), 4))
+        );
+        assert_eq!(
+            sched.poll_with(&mut pkts, |_, _, pkts| pkts.len()),
+            Some((QueueHandle::Rx(0, 0), 16))
+        );
+
+        sched.reset_stats();
+
+        assert!(sched.stats().all(|(_, stats)| *stats == QueueStats::default()));
+    }
+}
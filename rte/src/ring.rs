@@ -1,5 +1,512 @@
+//!
+//! RTE Ring
+//!
+//! The Ring Manager is a fixed-size queue, implemented as a table of
+//! pointers. Head and tail pointers are modified atomically, allowing
+//! concurrent access to it. It has the following features:
+//!
+//! - FIFO (First In First Out)
+//! - Maximum size is fixed; the pointers are stored in a table.
+//! - Lockless implementation.
+//! - Multi- or single-consumer dequeue.
+//! - Multi- or single-producer enqueue.
+//! - Bulk dequeue.
+//! - Bulk enqueue.
+//!
+//! A `RingNotifier` attaches an event fd to the ring, so the producers could kick
+//! the consumers sleeping on the rarely-used control rings, while the datapath
+//! consumers keep polling the ring without any syscall.
+//!
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::ptr;
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use cfile;
+use libc;
+
+#[cfg(feature = "mio")]
+use mio;
+
 use ffi;
 
+use common::name::{Name, NameKind};
+use errors::{os_error, AsResult, Result, RteError};
+use ethdev::PortId;
+use memory::Socket;
+use utils::{self, AsRaw, IntoRaw};
+
 lazy_static! {
     pub static ref RTE_RING_NAMESIZE: usize = ffi::RTE_MEMZONE_NAMESIZE as usize - ffi::RTE_RING_MZ_PREFIX.len() + 1;
 }
+
+bitflags! {
+    pub struct RingFlags: u32 {
+        /// The default enqueue is "single-producer".
+        const SP_ENQ    = ffi::RING_F_SP_ENQ;
+        /// The default dequeue is "single-consumer".
+        const SC_DEQ    = ffi::RING_F_SC_DEQ;
+        /// Ring holds exactly requested number of entries.
+        const EXACT_SZ  = ffi::RING_F_EXACT_SZ;
+    }
+}
+
+pub type RawRing = ffi::rte_ring;
+pub type RawRingPtr = *mut ffi::rte_ring;
+
+/// An RTE ring structure.
+raw!(pub Ring(RawRing));
+
+/// Create a new ring named name in memory.
+///
+/// This function uses memzone_reserve() to allocate memory.
+/// The real usable ring size is count-1 instead of count to differentiate a free ring from an empty ring,
+/// unless `RingFlags::EXACT_SZ` is set.
+pub fn create<S: AsRef<str>>(name: S, count: usize, socket: Socket, flags: RingFlags) -> Result<Ring> {
+    let name = Name::new(NameKind::Ring, name)?;
+    let socket = socket.validate()?;
+
+    unsafe { ffi::rte_ring_create(name.as_ptr(), count as u32, socket.raw(), flags.bits) }
+        .as_result()
+        .map(Ring)
+}
+
+/// Dump the status of all rings on the console
+pub fn list_dump<S: AsRawFd>(s: &S) -> Result<()> {
+    let mut f = cfile::fdopen(s, "w")?;
+
+    unsafe { ffi::rte_ring_list_dump(&mut **f as *mut _ as *mut _) };
+
+    Ok(())
+}
+
+/// Dump the status of all rings to the writer.
+pub fn list_dump_to<W: io::Write + ?Sized>(w: &mut W) -> Result<()> {
+    utils::capture(w, |f| unsafe { ffi::rte_ring_list_dump(f as *mut _) })?;
+
+    Ok(())
+}
+
+impl Ring {
+    /// Search a ring from its name
+    pub fn lookup<S: AsRef<str>>(name: S) -> Result<Self> {
+        let name = Name::new(NameKind::Ring, name)?;
+
+        unsafe { ffi::rte_ring_lookup(name.as_ptr()) }.as_result().map(Ring)
+    }
+
+    /// Name of the ring.
+    pub fn name(&self) -> &str {
+        unsafe { CStr::from_ptr(self.name[..].as_ptr()).to_str().unwrap() }
+    }
+
+    /// Flags supplied at creation.
+    pub fn flags(&self) -> RingFlags {
+        RingFlags::from_bits_truncate(self.flags as u32)
+    }
+
+    /// Return the size of the ring.
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Return the number of elements which can be stored in the ring.
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    /// De-allocate all memory used by the ring.
+    pub fn free(self) {
+        unsafe { ffi::rte_ring_free(self.into_raw()) }
+    }
+
+    /// Dump the status of the ring to a file.
+    pub fn dump<S: AsRawFd>(&self, s: &S) -> Result<()> {
+        let mut f = cfile::fdopen(s, "w")?;
+
+        unsafe { ffi::rte_ring_dump(&mut **f as *mut _ as *mut _, self.as_raw()) };
+
+        Ok(())
+    }
+
+    /// Dump the status of the ring to the writer.
+    pub fn dump_to<W: io::Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        utils::capture(w, |f| unsafe { ffi::rte_ring_dump(f as *mut _, self.as_raw()) })?;
+
+        Ok(())
+    }
+
+    /// Return the number of entries in a ring.
+    pub fn count(&self) -> usize {
+        unsafe { ffi::_rte_ring_count(self.as_raw()) as usize }
+    }
+
+    /// Return the number of free entries in a ring.
+    pub fn free_count(&self) -> usize {
+        unsafe { ffi::_rte_ring_free_count(self.as_raw()) as usize }
+    }
+
+    /// Test if a ring is full.
+    pub fn is_full(&self) -> bool {
+        self.free_count() == 0
+    }
+
+    /// Test if a ring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Enqueue one object on a ring.
+    ///
+    /// Returns `ENOBUFS` error if there is not enough room in the ring.
+    pub fn enqueue<T>(&self, obj: *mut T) -> Result<()> {
+        let ret = unsafe { ffi::_rte_ring_enqueue(self.as_raw(), obj as *mut _) };
+
+        rte_check!(ret; err => { RteError(-ret).into() })
+    }
+
+    /// Dequeue one object from a ring.
+    ///
+    /// Returns `None` if the ring is empty.
+    pub fn dequeue<T>(&self) -> Option<*mut T> {
+        let mut obj = ptr::null_mut();
+
+        if unsafe { ffi::_rte_ring_dequeue(self.as_raw(), &mut obj) } == 0 {
+            Some(obj as *mut T)
+        } else {
+            None
+        }
+    }
+
+    /// Enqueue several objects on a ring, returns the actual number of objects enqueued.
+    pub fn enqueue_burst<T>(&self, objs: &[*mut T]) -> usize {
+        unsafe {
+            ffi::_rte_ring_enqueue_burst(
+                self.as_raw(),
+                objs.as_ptr() as *const _,
+                objs.len() as u32,
+                ptr::null_mut(),
+            ) as usize
+        }
+    }
+
+    /// Dequeue several objects from a ring up to the length of slice, returns the number of objects dequeued.
+    pub fn dequeue_burst<T>(&self, objs: &mut [*mut T]) -> usize {
+        unsafe {
+            ffi::_rte_ring_dequeue_burst(
+                self.as_raw(),
+                objs.as_mut_ptr() as *mut _,
+                objs.len() as u32,
+                ptr::null_mut(),
+            ) as usize
+        }
+    }
+}
+
+/// Create a new ethdev port from a set of rings,
+/// the packets sent on the port are enqueued to the tx rings,
+/// and the packets received on the port are dequeued from the rx rings.
+pub fn eth_from_rings<S: AsRef<str>>(
+    name: S,
+    rx_queues: &[&Ring],
+    tx_queues: &[&Ring],
+    socket: Socket,
+) -> Result<PortId> {
+    let name = Name::new(NameKind::EthDev, name)?;
+    let socket = socket.validate()?;
+    let rx_queues = rx_queues.iter().map(|r| r.as_raw()).collect::<Vec<_>>();
+    let tx_queues = tx_queues.iter().map(|r| r.as_raw()).collect::<Vec<_>>();
+
+    unsafe {
+        ffi::rte_eth_from_rings(
+            name.as_ptr(),
+            rx_queues.as_ptr(),
+            rx_queues.len() as u32,
+            tx_queues.as_ptr(),
+            tx_queues.len() as u32,
+            socket.into(),
+        )
+    }
+    .as_result()
+    .map(|port_id| port_id as PortId)
+}
+
+impl Ring {
+    /// Create a new ethdev port from the ring, which is used as both the rx and tx queue.
+    pub fn eth_port(&self) -> Result<PortId> {
+        unsafe { ffi::rte_eth_from_ring(self.as_raw()) }
+            .as_result()
+            .map(|port_id| port_id as PortId)
+    }
+}
+
+struct Notify {
+    ring: Ring,
+    fd: RawFd,
+    waiters: AtomicUsize,
+}
+
+// the ring is shared by the producers and consumers, the same as the raw pointer in C.
+unsafe impl Send for Notify {}
+unsafe impl Sync for Notify {}
+
+impl Drop for Notify {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// A handle of the ring with an event fd, which wakes up the sleeping consumers.
+///
+/// The producers only write the event fd when some consumer is waiting,
+/// so the enqueue stays cheap when all the consumers are polling.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use rte::ring::{self, RingFlags, RingNotifier};
+/// use rte::*;
+///
+/// let ring = ring::create("ctrl", 64, Socket::ANY, RingFlags::SC_DEQ).unwrap();
+/// let notifier = RingNotifier::new(ring).unwrap();
+/// let producer = notifier.clone();
+///
+/// producer.enqueue(Box::into_raw(Box::new(42u32))).unwrap();
+///
+/// if let Some(obj) = notifier.dequeue_timeout::<u32>(Some(Duration::from_secs(1))).unwrap() {
+///     assert_eq!(*unsafe { Box::from_raw(obj) }, 42);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RingNotifier(Arc<Notify>);
+
+impl RingNotifier {
+    /// Attach a new event fd to the ring.
+    pub fn new(ring: Ring) -> Result<Self> {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+
+        if fd < 0 {
+            Err(os_error())
+        } else {
+            Ok(RingNotifier(Arc::new(Notify {
+                ring,
+                fd,
+                waiters: AtomicUsize::new(0),
+            })))
+        }
+    }
+
+    /// The ring of the notifier, which could be polled directly.
+    pub fn ring(&self) -> &Ring {
+        &self.0.ring
+    }
+
+    /// The number of consumers waiting for the event.
+    pub fn waiters(&self) -> usize {
+        self.0.waiters.load(Ordering::SeqCst)
+    }
+
+    /// Enqueue one object on the ring, and wake up the waiting consumers.
+    pub fn enqueue<T>(&self, obj: *mut T) -> Result<()> {
+        self.0.ring.enqueue(obj)?;
+        self.notify()
+    }
+
+    /// Enqueue several objects on the ring, and wake up the waiting consumers if any object was enqueued.
+    pub fn enqueue_burst<T>(&self, objs: &[*mut T]) -> Result<usize> {
+        let n = self.0.ring.enqueue_burst(objs);
+
+        if n > 0 {
+            self.notify()?;
+        }
+
+        Ok(n)
+    }
+
+    /// Wake up the waiting consumers, if any.
+    pub fn notify(&self) -> Result<()> {
+        // pairs with the fence in `arm`, either the producer sees the waiter,
+        // or the waiter sees the objects in the ring.
+        fence(Ordering::SeqCst);
+
+        if self.0.waiters.load(Ordering::SeqCst) == 0 {
+            return Ok(());
+        }
+
+        let one = 1u64;
+
+        loop {
+            let n = unsafe { libc::write(self.0.fd, &one as *const u64 as *const _, 8) };
+
+            if n >= 0 {
+                return Ok(());
+            }
+
+            match io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                // the counter is saturated, the consumers will be woken up anyway
+                Some(libc::EAGAIN) => return Ok(()),
+                _ => return Err(os_error()),
+            }
+        }
+    }
+
+    /// Register the consumer as a waiter, the producers will write the event fd from now on.
+    ///
+    /// The consumers of an event loop should arm the notifier before registering the fd,
+    /// and check the ring again after arming it, since the objects enqueued before may not be notified.
+    pub fn arm(&self) {
+        self.0.waiters.fetch_add(1, Ordering::SeqCst);
+
+        fence(Ordering::SeqCst);
+    }
+
+    /// Unregister the consumer as a waiter.
+    pub fn disarm(&self) {
+        self.0.waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Drain the pending events of the fd, so the level triggered poll won't fire again.
+    pub fn ack(&self) -> Result<()> {
+        let mut buf = 0u64;
+
+        loop {
+            let n = unsafe { libc::read(self.0.fd, &mut buf as *mut u64 as *mut _, 8) };
+
+            if n >= 0 {
+                return Ok(());
+            }
+
+            match io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                Some(libc::EAGAIN) => return Ok(()),
+                _ => return Err(os_error()),
+            }
+        }
+    }
+
+    /// Wait until the ring is not empty, or the timeout expires.
+    ///
+    /// Returns `false` if the ring is still empty when the timeout expires.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<bool> {
+        if !self.0.ring.is_empty() {
+            return Ok(true);
+        }
+
+        let deadline = timeout.map(|d| Instant::now() + d);
+
+        self.arm();
+
+        let res = self.wait_armed(deadline);
+
+        self.disarm();
+
+        res
+    }
+
+    fn wait_armed(&self, deadline: Option<Instant>) -> Result<bool> {
+        let mut pfd = libc::pollfd {
+            fd: self.0.fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        loop {
+            if !self.0.ring.is_empty() {
+                return Ok(true);
+            }
+
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Ok(false);
+                    }
+
+                    (deadline - now).as_millis().max(1).min(i32::MAX as u128) as i32
+                }
+                None => -1,
+            };
+
+            if unsafe { libc::poll(&mut pfd, 1, timeout) } < 0
+                && io::Error::last_os_error().raw_os_error() != Some(libc::EINTR)
+            {
+                return Err(os_error());
+            }
+
+            if pfd.revents & libc::POLLIN != 0 {
+                self.ack()?;
+            }
+        }
+    }
+
+    /// Dequeue one object from the ring, sleep until an object is enqueued or the timeout expires.
+    pub fn dequeue_timeout<T>(&self, timeout: Option<Duration>) -> Result<Option<*mut T>> {
+        let deadline = timeout.map(|d| Instant::now() + d);
+
+        loop {
+            if let Some(obj) = self.0.ring.dequeue() {
+                return Ok(Some(obj));
+            }
+
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Ok(None);
+                    }
+
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+
+            // another consumer may take the object first, wait again
+            if !self.wait(timeout)? {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl AsRawFd for RingNotifier {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd
+    }
+}
+
+/// The event fd is readable when some objects were enqueued,
+/// the consumer should `arm` the notifier, and `ack` the fd before draining the ring.
+#[cfg(feature = "mio")]
+impl mio::Evented for RingNotifier {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0.fd).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: mio::Token,
+        interest: mio::Ready,
+        opts: mio::PollOpt,
+    ) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0.fd).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0.fd).deregister(poll)
+    }
+}
@@ -0,0 +1,1047 @@
+//!
+//! Next-hop resolution for simple L3 forwarding.
+//!
+//! The `RouteTable` finds the longest prefix match route of a destination address,
+//! the `NeighborCache` keeps the Ethernet addresses of neighbors learned from ARP and NDP,
+//! and the `NextHopResolver` combines them to resolve the next-hop MAC of the forwarded packets.
+//!
+//! The `RouteTable` is a `PrefixTable` of the routes, the same table could map the networks
+//! to other values, e.g. the ids of the next-hop groups in `ecmp`.
+//! The networks are looked up in the `rte_lpm` and `rte_lpm6` tables of the `lpm` module.
+//!
+//! The packets to an unresolved next-hop are queued, while an ARP request or
+//! a Neighbor Solicitation is sent, until the neighbor is resolved or timeout.
+//!
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use libc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use common::byteorder::be16;
+use common::coop;
+use common::{get_tsc_hz, rdtsc};
+use errors::{ErrorKind::InvalidTable, Result, RteError};
+use ethdev::PortId;
+use ether::{
+    ETHER_TYPE_IPv4, ETHER_TYPE_IPv6, EtherAddr, ETHER_ADDR_LEN, ETHER_HDR_LEN, ETHER_TYPE_ARP, ETHER_TYPE_ARP_BE,
+    ETHER_TYPE_IPV6_BE,
+};
+use ip;
+use lpm::{self, Lpm, Lpm6, LpmConf};
+use mbuf::{MBuf, MBufPool};
+use memory::Socket;
+use mempool;
+use table::{Table, TableKind};
+
+const ARP_PKT_LEN: usize = 28;
+const IPV6_HDR_LEN: usize = 40;
+const ND_PKT_LEN: usize = 32;
+
+const ND_NEIGHBOR_SOLICIT: u8 = 135;
+const ND_NEIGHBOR_ADVERT: u8 = 136;
+const ND_OPT_SOURCE_LINKADDR: u8 = 1;
+const ND_OPT_TARGET_LINKADDR: u8 = 2;
+
+/// A route to the destination network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Route {
+    /// The output port.
+    pub port: PortId,
+    /// The gateway, or `None` if the network is directly connected.
+    pub gateway: Option<IpAddr>,
+}
+
+impl Route {
+    /// The next-hop address of the destination.
+    pub fn next_hop(&self, dst: IpAddr) -> IpAddr {
+        self.gateway.unwrap_or(dst)
+    }
+}
+
+/// The capacity of a `PrefixTable`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrefixConf {
+    /// The socket to allocate the LPM tables.
+    pub socket: Socket,
+    /// The capacity of the IPv4 LPM table.
+    pub v4: LpmConf,
+    /// The capacity of the IPv6 LPM table.
+    pub v6: LpmConf,
+}
+
+impl Default for PrefixConf {
+    fn default() -> Self {
+        PrefixConf {
+            socket: Socket::ANY,
+            v4: LpmConf {
+                max_rules: 1024,
+                number_tbl8s: 256,
+            },
+            v6: LpmConf {
+                max_rules: 1024,
+                number_tbl8s: 1 << 12,
+            },
+        }
+    }
+}
+
+impl PrefixConf {
+    /// The capacity to hold the networks, at least the default one.
+    ///
+    /// A IPv4 network longer than 24 bits takes a tbl8 group at most,
+    /// and a IPv6 network takes a tbl8 group for each 8 bits beyond the first 24 bits.
+    pub fn for_networks<I: IntoIterator<Item = (IpAddr, u8)>>(networks: I) -> Self {
+        let mut conf = PrefixConf::default();
+        let (mut v4_rules, mut v4_tbl8s, mut v6_rules, mut v6_tbl8s) = (0u32, 0u32, 0u32, 0u32);
+
+        for (prefix, depth) in networks {
+            let tbl8s = u32::from(depth).saturating_sub(24).div_ceil(8);
+
+            if prefix.is_ipv4() {
+                v4_rules += 1;
+                v4_tbl8s += tbl8s;
+            } else {
+                v6_rules += 1;
+                v6_tbl8s += tbl8s;
+            }
+        }
+
+        conf.v4.max_rules = conf.v4.max_rules.max(v4_rules);
+        conf.v4.number_tbl8s = conf.v4.number_tbl8s.max(v4_tbl8s);
+        conf.v6.max_rules = conf.v6.max_rules.max(v6_rules);
+        conf.v6.number_tbl8s = conf.v6.number_tbl8s.max(v6_tbl8s);
+        conf
+    }
+}
+
+/// A longest prefix match table of the IPv4 and IPv6 networks, e.g. the routes or the next-hop groups.
+///
+/// The LPM tables map the networks to the indexes of their values,
+/// the default routes are kept aside, since the LPM tables don't support the prefix length 0.
+#[derive(Debug)]
+pub struct PrefixTable<T> {
+    conf: PrefixConf,
+    v4: Lpm,
+    v6: Lpm6,
+    default_v4: Option<u32>,
+    default_v6: Option<u32>,
+    networks: HashMap<(IpAddr, u8), u32>,
+    values: Vec<T>,
+    free: Vec<u32>,
+}
+
+/// A longest prefix match routing table for IPv4 and IPv6.
+pub type RouteTable = PrefixTable<Route>;
+
+// the addresses are looked up in chunks on the stack
+const LOOKUP_BULK_SIZE: usize = 64;
+
+fn mask_v4(addr: Ipv4Addr, depth: u8) -> u32 {
+    if depth == 0 {
+        0
+    } else {
+        u32::from(addr) & (!0u32 << (32 - u32::from(depth)))
+    }
+}
+
+fn mask_v6(addr: Ipv6Addr, depth: u8) -> u128 {
+    if depth == 0 {
+        0
+    } else {
+        u128::from(addr) & (!0u128 << (128 - u32::from(depth)))
+    }
+}
+
+/// The network of the prefix, fails with `EINVAL` if the prefix length exceeds the address length.
+fn network(prefix: IpAddr, depth: u8) -> Result<IpAddr> {
+    match prefix {
+        IpAddr::V4(addr) if u32::from(depth) <= lpm::RTE_LPM_MAX_DEPTH => {
+            Ok(Ipv4Addr::from(mask_v4(addr, depth)).into())
+        }
+        IpAddr::V6(addr) if u32::from(depth) <= lpm::RTE_LPM6_MAX_DEPTH => {
+            Ok(Ipv6Addr::from(mask_v6(addr, depth)).into())
+        }
+        _ => Err(RteError(libc::EINVAL).into()),
+    }
+}
+
+impl<T: Copy> PrefixTable<T> {
+    /// Create an empty table with the default capacity.
+    pub fn new() -> Result<Self> {
+        PrefixTable::with_conf(&PrefixConf::default())
+    }
+
+    /// Create an empty table with the capacity.
+    pub fn with_conf(conf: &PrefixConf) -> Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        // the LPM tables are named uniquely, the IPv4 and IPv6 tables are in the separated lists
+        let name = format!("prefix_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+
+        Ok(PrefixTable {
+            conf: *conf,
+            v4: lpm::create(&name, conf.socket, &conf.v4)?,
+            v6: lpm::create6(&name, conf.socket, &conf.v6)?,
+            default_v4: None,
+            default_v6: None,
+            networks: HashMap::new(),
+            values: vec![],
+            free: vec![],
+        })
+    }
+
+    /// Create a copy of the table with the same capacity, e.g. to change it while the original one is used.
+    pub fn try_clone(&self) -> Result<Self> {
+        let mut table = PrefixTable::with_conf(&self.conf)?;
+
+        for (prefix, depth, value) in self.iter() {
+            table.add(prefix, depth, value)?;
+        }
+
+        Ok(table)
+    }
+
+    /// The capacity of the table.
+    pub fn conf(&self) -> &PrefixConf {
+        &self.conf
+    }
+
+    /// Add a value to the network, returns the previous value of the network if any.
+    ///
+    /// Fails with `EINVAL` if the prefix length exceeds the address length,
+    /// or `ENOSPC` if the table is full.
+    pub fn add(&mut self, prefix: IpAddr, depth: u8, value: T) -> Result<Option<T>> {
+        let net = network(prefix, depth)?;
+
+        if let Some(&idx) = self.networks.get(&(net, depth)) {
+            return Ok(Some(mem::replace(&mut self.values[idx as usize], value)));
+        }
+
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.values[idx as usize] = value;
+                idx
+            }
+            None if self.values.len() as u32 <= lpm::RTE_LPM6_MAX_NEXT_HOP => {
+                self.values.push(value);
+                self.values.len() as u32 - 1
+            }
+            None => return Err(RteError(libc::ENOSPC).into()),
+        };
+
+        let res = match net {
+            IpAddr::V4(addr) if depth > 0 => self.v4.add(addr, depth, idx),
+            IpAddr::V6(addr) if depth > 0 => self.v6.add(addr, depth, idx),
+            IpAddr::V4(_) => {
+                self.default_v4 = Some(idx);
+                Ok(())
+            }
+            IpAddr::V6(_) => {
+                self.default_v6 = Some(idx);
+                Ok(())
+            }
+        };
+
+        match res {
+            Ok(()) => {
+                self.networks.insert((net, depth), idx);
+
+                Ok(None)
+            }
+            Err(err) => {
+                self.free.push(idx);
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Remove the value of the network.
+    pub fn remove(&mut self, prefix: IpAddr, depth: u8) -> Option<T> {
+        let net = network(prefix, depth).ok()?;
+        let idx = self.networks.remove(&(net, depth))?;
+
+        let res = match net {
+            IpAddr::V4(addr) if depth > 0 => self.v4.delete(addr, depth),
+            IpAddr::V6(addr) if depth > 0 => self.v6.delete(addr, depth),
+            IpAddr::V4(_) => {
+                self.default_v4 = None;
+                Ok(())
+            }
+            IpAddr::V6(_) => {
+                self.default_v6 = None;
+                Ok(())
+            }
+        };
+
+        if let Err(err) = res {
+            warn!("fail to delete network {}/{}, {}", net, depth, err);
+        }
+
+        self.free.push(idx);
+
+        Some(self.values[idx as usize])
+    }
+
+    /// Find the value of the longest matched prefix.
+    #[inline]
+    pub fn lookup(&self, addr: IpAddr) -> Option<T> {
+        match addr {
+            IpAddr::V4(addr) => self.v4.lookup(addr).or(self.default_v4),
+            IpAddr::V6(addr) => self.v6.lookup(addr).or(self.default_v6),
+        }
+        .map(|idx| self.values[idx as usize])
+    }
+
+    /// Find the values of the longest matched prefixes of a bulk of addresses.
+    ///
+    /// The IPv4 and IPv6 addresses are looked up in bulk in their LPM tables.
+    pub fn lookup_bulk(&self, addrs: &[IpAddr], values: &mut [Option<T>]) {
+        assert!(values.len() >= addrs.len());
+
+        for (addrs, values) in addrs.chunks(LOOKUP_BULK_SIZE).zip(values.chunks_mut(LOOKUP_BULK_SIZE)) {
+            let mut v4 = [0u32; LOOKUP_BULK_SIZE];
+            let mut v4_pos = [0usize; LOOKUP_BULK_SIZE];
+            let mut n4 = 0;
+            let mut v6 = [[0u8; 16]; LOOKUP_BULK_SIZE];
+            let mut v6_pos = [0usize; LOOKUP_BULK_SIZE];
+            let mut n6 = 0;
+
+            for (pos, addr) in addrs.iter().enumerate() {
+                match *addr {
+                    IpAddr::V4(addr) => {
+                        v4[n4] = addr.into();
+                        v4_pos[n4] = pos;
+                        n4 += 1;
+                    }
+                    IpAddr::V6(addr) => {
+                        v6[n6] = addr.octets();
+                        v6_pos[n6] = pos;
+                        n6 += 1;
+                    }
+                }
+            }
+
+            if n4 > 0 {
+                let mut next_hops = [0u32; LOOKUP_BULK_SIZE];
+
+                self.v4.lookup_bulk(&v4[..n4], &mut next_hops[..n4]);
+
+                for (&pos, &next_hop) in v4_pos[..n4].iter().zip(next_hops[..n4].iter()) {
+                    values[pos] = if next_hop & lpm::RTE_LPM_LOOKUP_SUCCESS != 0 {
+                        Some(next_hop & lpm::RTE_LPM_MAX_NEXT_HOP)
+                    } else {
+                        self.default_v4
+                    }
+                    .map(|idx| self.values[idx as usize]);
+                }
+            }
+
+            if n6 > 0 {
+                let mut next_hops = [0i32; LOOKUP_BULK_SIZE];
+
+                self.v6.lookup_bulk(&mut v6[..n6], &mut next_hops[..n6]);
+
+                for (&pos, &next_hop) in v6_pos[..n6].iter().zip(next_hops[..n6].iter()) {
+                    values[pos] = if next_hop >= 0 {
+                        Some(next_hop as u32)
+                    } else {
+                        self.default_v6
+                    }
+                    .map(|idx| self.values[idx as usize]);
+                }
+            }
+        }
+    }
+
+    /// The value of the network.
+    pub fn get(&self, prefix: IpAddr, depth: u8) -> Option<T> {
+        let net = network(prefix, depth).ok()?;
+
+        self.networks.get(&(net, depth)).map(|&idx| self.values[idx as usize])
+    }
+
+    /// Remove all the networks.
+    pub fn clear(&mut self) {
+        self.v4.delete_all();
+        self.v6.delete_all();
+        self.default_v4 = None;
+        self.default_v6 = None;
+        self.networks.clear();
+        self.values.clear();
+        self.free.clear();
+    }
+
+    /// The number of networks.
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    /// The table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// The networks and their values.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (IpAddr, u8, T)> + 'a {
+        self.networks
+            .iter()
+            .map(move |(&(net, depth), &idx)| (net, depth, self.values[idx as usize]))
+    }
+}
+
+impl<T: Copy + Serialize + DeserializeOwned> Table for PrefixTable<T> {
+    type Entry = (IpAddr, u8, T);
+
+    const KIND: TableKind = TableKind::Lpm;
+
+    fn entry_count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries<'a>(&'a self) -> Box<dyn Iterator<Item = Self::Entry> + 'a> {
+        Box::new(self.iter())
+    }
+
+    fn from_entries(entries: Vec<Self::Entry>) -> Result<Self> {
+        for &(prefix, depth, _) in &entries {
+            if network(prefix, depth).is_err() {
+                return Err(InvalidTable(format!("invalid prefix {}/{}", prefix, depth)).into());
+            }
+        }
+
+        // size the LPM tables for the entries, instead of failing with `ENOSPC` on a large table
+        let conf = PrefixConf::for_networks(entries.iter().map(|&(prefix, depth, _)| (prefix, depth)));
+        let mut table = PrefixTable::with_conf(&conf)?;
+
+        for (prefix, depth, value) in entries {
+            table.add(prefix, depth, value)?;
+
+            coop::yield_point();
+        }
+
+        Ok(table)
+    }
+}
+
+/// A neighbor learned from ARP or NDP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Neighbor {
+    /// The port which the neighbor is attached on.
+    pub port: PortId,
+    /// The Ethernet address of the neighbor.
+    pub mac: EtherAddr,
+    /// The TSC when the neighbor was updated.
+    pub updated: u64,
+}
+
+/// A cache of the neighbors, which are expired if not updated in time.
+#[derive(Clone, Debug)]
+pub struct NeighborCache {
+    neighbors: HashMap<IpAddr, Neighbor>,
+    timeout: u64,
+}
+
+impl NeighborCache {
+    /// Create an empty neighbor cache.
+    pub fn new(timeout: Duration) -> Self {
+        NeighborCache {
+            neighbors: HashMap::new(),
+            timeout: duration_to_cycles(timeout),
+        }
+    }
+
+    /// Find a neighbor which is not expired.
+    pub fn lookup(&self, addr: &IpAddr) -> Option<&Neighbor> {
+        let now = rdtsc();
+
+        self.neighbors
+            .get(addr)
+            .filter(|neighbor| now.saturating_sub(neighbor.updated) < self.timeout)
+    }
+
+    /// Add or update a neighbor.
+    pub fn update(&mut self, addr: IpAddr, port: PortId, mac: EtherAddr) {
+        self.neighbors.insert(
+            addr,
+            Neighbor {
+                port,
+                mac,
+                updated: rdtsc(),
+            },
+        );
+    }
+
+    /// Remove a neighbor.
+    pub fn remove(&mut self, addr: &IpAddr) -> Option<Neighbor> {
+        self.neighbors.remove(addr)
+    }
+
+    /// Remove the expired neighbors.
+    pub fn expire(&mut self) {
+        let now = rdtsc();
+        let timeout = self.timeout;
+
+        self.neighbors
+            .retain(|_, neighbor| now.saturating_sub(neighbor.updated) < timeout)
+    }
+
+    /// The number of neighbors.
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// The cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
+    }
+}
+
+fn duration_to_cycles(d: Duration) -> u64 {
+    let hz = get_tsc_hz();
+
+    d.as_secs() * hz + u64::from(d.subsec_nanos()) * hz / 1_000_000_000
+}
+
+/// The addresses of a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interface {
+    pub mac: EtherAddr,
+    pub ipv4: Option<Ipv4Addr>,
+    pub ipv6: Option<Ipv6Addr>,
+}
+
+/// The configuration of `NextHopResolver`.
+#[derive(Clone, Copy, Debug)]
+pub struct ResolverConf {
+    /// How long a resolved neighbor is valid.
+    pub neighbor_timeout: Duration,
+    /// The interval to resend the ARP request or Neighbor Solicitation.
+    pub request_interval: Duration,
+    /// How long the packets are queued before the next-hop is resolved.
+    pub request_timeout: Duration,
+    /// The maximum number of packets queued for a next-hop.
+    pub max_pending: usize,
+}
+
+impl Default for ResolverConf {
+    fn default() -> Self {
+        ResolverConf {
+            neighbor_timeout: Duration::from_secs(300),
+            request_interval: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(3),
+            max_pending: 16,
+        }
+    }
+}
+
+/// The result of resolving the next-hop of a packet.
+#[derive(Debug)]
+pub enum Action {
+    /// The next-hop is resolved, the Ethernet addresses of packet are rewritten
+    /// and the packet should be sent on the port.
+    Forward(PortId, MBuf),
+    /// The packet is queued until the next-hop is resolved,
+    /// with an ARP request or Neighbor Solicitation to be sent on the port if any.
+    Queued(Option<(PortId, MBuf)>),
+    /// The packet should be dropped, because there is no route or too many packets are queued.
+    Drop(MBuf),
+}
+
+struct Pending {
+    port: PortId,
+    pkts: VecDeque<MBuf>,
+    first_request: u64,
+    last_request: u64,
+}
+
+/// Resolve the next-hop MAC of the forwarded packets with the routing table and neighbor cache.
+///
+/// The forwarded packets should start with an Ethernet header.
+pub struct NextHopResolver {
+    routes: RouteTable,
+    neighbors: NeighborCache,
+    interfaces: HashMap<PortId, Interface>,
+    pending: HashMap<IpAddr, Pending>,
+    request_interval: u64,
+    request_timeout: u64,
+    max_pending: usize,
+}
+
+impl NextHopResolver {
+    /// Create a resolver with the routing table.
+    pub fn new(routes: RouteTable, conf: &ResolverConf) -> Self {
+        NextHopResolver {
+            routes,
+            neighbors: NeighborCache::new(conf.neighbor_timeout),
+            interfaces: HashMap::new(),
+            pending: HashMap::new(),
+            request_interval: duration_to_cycles(conf.request_interval),
+            request_timeout: duration_to_cycles(conf.request_timeout),
+            max_pending: conf.max_pending,
+        }
+    }
+
+    /// The routing table.
+    pub fn routes(&mut self) -> &mut RouteTable {
+        &mut self.routes
+    }
+
+    /// The neighbor cache.
+    pub fn neighbors(&mut self) -> &mut NeighborCache {
+        &mut self.neighbors
+    }
+
+    /// Set the addresses of a port, which are used to send requests and answer the ARP requests.
+    pub fn add_interface(&mut self, port: PortId, iface: Interface) -> &mut Self {
+        self.interfaces.insert(port, iface);
+        self
+    }
+
+    /// Resolve the next-hop of a packet to the destination.
+    pub fn resolve(&mut self, pool: mempool::MemPoolRef, mut m: MBuf, dst: IpAddr) -> Action {
+        let route = match self.routes.lookup(dst) {
+            Some(route) => route,
+            None => return Action::Drop(m),
+        };
+        let iface = match self.interfaces.get(&route.port) {
+            Some(iface) => *iface,
+            None => return Action::Drop(m),
+        };
+        let next_hop = route.next_hop(dst);
+
+        if let Some(neighbor) = self.neighbors.lookup(&next_hop) {
+            if neighbor.port == route.port {
+                set_ether_addrs(&mut m, &neighbor.mac, &iface.mac);
+
+                return Action::Forward(route.port, m);
+            }
+        }
+
+        let now = rdtsc();
+
+        if let Some(pending) = self.pending.get_mut(&next_hop) {
+            if pending.pkts.len() >= self.max_pending {
+                return Action::Drop(m);
+            }
+
+            pending.pkts.push_back(m);
+
+            return Action::Queued(None);
+        }
+
+        let req = match request(pool, &iface, next_hop) {
+            Some(req) => req,
+            None => return Action::Drop(m),
+        };
+
+        let mut pkts = VecDeque::with_capacity(self.max_pending);
+
+        pkts.push_back(m);
+
+        self.pending.insert(
+            next_hop,
+            Pending {
+                port: route.port,
+                pkts,
+                first_request: now,
+                last_request: now,
+            },
+        );
+
+        Action::Queued(Some((route.port, req)))
+    }
+
+    /// Process the ARP and NDP packet received on the port.
+    ///
+    /// The neighbors are learned from the packet, and the ARP requests for the port address are answered.
+    ///
+    /// Returns the packets to be sent, including the ARP reply and the queued packets to the resolved neighbor.
+    pub fn input(&mut self, pool: mempool::MemPoolRef, port: PortId, m: &MBuf) -> Vec<(PortId, MBuf)> {
+        let iface = match self.interfaces.get(&port) {
+            Some(iface) => *iface,
+            None => return vec![],
+        };
+        let data = unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), m.data_len()) };
+
+        if data.len() < ETHER_HDR_LEN as usize {
+            return vec![];
+        }
+
+        let ether_type = be16::from_bytes([data[12], data[13]]);
+        let payload = &data[ETHER_HDR_LEN as usize..];
+        let mut out = vec![];
+
+        let learned = match ether_type {
+            ETHER_TYPE_ARP_BE => parse_arp(payload).and_then(|arp| {
+                if iface.ipv4 != Some(arp.tip) {
+                    return None;
+                }
+
+                if arp.op == ARP_OP_REQUEST {
+                    if let Ok(reply) = arp_packet(pool, ARP_OP_REPLY, &iface.mac, arp.tip, &arp.sha, arp.sip) {
+                        out.push((port, reply));
+                    }
+                }
+
+                Some((IpAddr::V4(arp.sip), arp.sha))
+            }),
+            ETHER_TYPE_IPV6_BE => parse_nd(payload)
+                .map(|nd| (nd, EtherAddr::from_bytes(&data[6..12]).unwrap()))
+                .and_then(|(nd, src_mac)| match nd.kind {
+                    ND_NEIGHBOR_ADVERT => Some((IpAddr::V6(nd.target), nd.linkaddr.unwrap_or(src_mac))),
+                    ND_NEIGHBOR_SOLICIT if !nd.src.is_unspecified() && iface.ipv6 == Some(nd.target) => {
+                        nd.linkaddr.map(|mac| (IpAddr::V6(nd.src), mac))
+                    }
+                    _ => None,
+                }),
+            _ => None,
+        };
+
+        if let Some((addr, mac)) = learned {
+            self.neighbors.update(addr, port, mac);
+
+            if let Some(pending) = self.pending.remove(&addr) {
+                for mut m in pending.pkts {
+                    set_ether_addrs(&mut m, &mac, &iface.mac);
+
+                    out.push((port, m));
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Resend the requests for the unresolved next-hops, and drop the queued packets if timeout.
+    ///
+    /// This should be called periodically, returns the requests to be sent.
+    pub fn expire(&mut self, pool: mempool::MemPoolRef) -> Vec<(PortId, MBuf)> {
+        let now = rdtsc();
+        let request_timeout = self.request_timeout;
+
+        self.pending
+            .retain(|_, pending| now.saturating_sub(pending.first_request) < request_timeout);
+        self.neighbors.expire();
+
+        let mut out = vec![];
+
+        for (next_hop, pending) in &mut self.pending {
+            if now.saturating_sub(pending.last_request) < self.request_interval {
+                continue;
+            }
+
+            if let Some(req) = self
+                .interfaces
+                .get(&pending.port)
+                .and_then(|iface| request(pool, iface, *next_hop))
+            {
+                pending.last_request = now;
+
+                out.push((pending.port, req));
+            }
+        }
+
+        out
+    }
+}
+
+fn set_ether_addrs(m: &mut MBuf, dst: &EtherAddr, src: &EtherAddr) {
+    let data = unsafe { slice::from_raw_parts_mut(m.mtod::<u8>().as_ptr(), m.data_len()) };
+
+    data[..ETHER_ADDR_LEN].copy_from_slice(dst.octets());
+    data[ETHER_ADDR_LEN..ETHER_ADDR_LEN * 2].copy_from_slice(src.octets());
+}
+
+const ARP_OP_REQUEST: u16 = ::ffi::ARP_OP_REQUEST as u16;
+const ARP_OP_REPLY: u16 = ::ffi::ARP_OP_REPLY as u16;
+
+struct Arp {
+    op: u16,
+    sha: EtherAddr,
+    sip: Ipv4Addr,
+    tip: Ipv4Addr,
+}
+
+fn parse_arp(buf: &[u8]) -> Option<Arp> {
+    if buf.len() < ARP_PKT_LEN
+        || u16::from_be_bytes([buf[0], buf[1]]) != ::ffi::ARP_HRD_ETHER as u16
+        || u16::from_be_bytes([buf[2], buf[3]]) != ETHER_TYPE_IPv4 as u16
+        || buf[4] as usize != ETHER_ADDR_LEN
+        || buf[5] != 4
+    {
+        return None;
+    }
+
+    Some(Arp {
+        op: u16::from_be_bytes([buf[6], buf[7]]),
+        sha: EtherAddr::from_bytes(&buf[8..14]).unwrap(),
+        sip: Ipv4Addr::new(buf[14], buf[15], buf[16], buf[17]),
+        tip: Ipv4Addr::new(buf[24], buf[25], buf[26], buf[27]),
+    })
+}
+
+struct Nd {
+    kind: u8,
+    src: Ipv6Addr,
+    target: Ipv6Addr,
+    linkaddr: Option<EtherAddr>,
+}
+
+fn ipv6_addr(b: &[u8]) -> Ipv6Addr {
+    let mut octets = [0; 16];
+
+    octets.copy_from_slice(&b[..16]);
+
+    octets.into()
+}
+
+fn parse_nd(buf: &[u8]) -> Option<Nd> {
+    if buf.len() < IPV6_HDR_LEN + 24 || buf[0] >> 4 != 6 || buf[6] != libc::IPPROTO_ICMPV6 as u8 || buf[7] != 255 {
+        return None;
+    }
+
+    let icmp = &buf[IPV6_HDR_LEN..];
+    let kind = icmp[0];
+
+    if (kind != ND_NEIGHBOR_SOLICIT && kind != ND_NEIGHBOR_ADVERT) || icmp[1] != 0 {
+        return None;
+    }
+
+    let opt_type = if kind == ND_NEIGHBOR_SOLICIT {
+        ND_OPT_SOURCE_LINKADDR
+    } else {
+        ND_OPT_TARGET_LINKADDR
+    };
+    let mut linkaddr = None;
+    let mut opts = &icmp[24..];
+
+    while opts.len() >= 8 && opts[1] != 0 {
+        let len = opts[1] as usize * 8;
+
+        if len > opts.len() {
+            break;
+        }
+
+        if opts[0] == opt_type {
+            linkaddr = EtherAddr::from_bytes(&opts[2..8]).ok();
+        }
+
+        opts = &opts[len..];
+    }
+
+    Some(Nd {
+        kind,
+        src: ipv6_addr(&buf[8..24]),
+        target: ipv6_addr(&icmp[8..24]),
+        linkaddr,
+    })
+}
+
+/// The solicited-node multicast address of an IPv6 address.
+pub fn solicited_node(addr: &Ipv6Addr) -> Ipv6Addr {
+    let o = addr.octets();
+
+    Ipv6Addr::new(
+        0xff02,
+        0,
+        0,
+        0,
+        0,
+        1,
+        0xff00 | u16::from(o[13]),
+        u16::from_be_bytes([o[14], o[15]]),
+    )
+}
+
+/// The Ethernet address of an IPv6 multicast address.
+pub fn ipv6_multicast_mac(addr: &Ipv6Addr) -> EtherAddr {
+    let o = addr.octets();
+
+    EtherAddr::new(0x33, 0x33, o[12], o[13], o[14], o[15])
+}
+
+fn request(pool: mempool::MemPoolRef, iface: &Interface, next_hop: IpAddr) -> Option<MBuf> {
+    match next_hop {
+        IpAddr::V4(tip) => iface
+            .ipv4
+            .and_then(|sip| arp_packet(pool, ARP_OP_REQUEST, &iface.mac, sip, &EtherAddr::zeroed(), tip).ok()),
+        IpAddr::V6(target) => iface
+            .ipv6
+            .and_then(|src| neighbor_solicit(pool, &iface.mac, src, target).ok()),
+    }
+}
+
+fn build_packet<F: FnOnce(&mut [u8])>(mut pool: mempool::MemPoolRef, len: usize, f: F) -> Result<MBuf> {
+    let mut m = pool.alloc()?;
+
+    {
+        let p = m.append(len)?;
+
+        f(unsafe { slice::from_raw_parts_mut(p.as_ptr(), len) });
+    }
+
+    Ok(m)
+}
+
+fn arp_packet(
+    pool: mempool::MemPoolRef,
+    op: u16,
+    sha: &EtherAddr,
+    sip: Ipv4Addr,
+    tha: &EtherAddr,
+    tip: Ipv4Addr,
+) -> Result<MBuf> {
+    let dst = if op == ARP_OP_REQUEST {
+        EtherAddr::broadcast()
+    } else {
+        *tha
+    };
+
+    build_packet(pool, ETHER_HDR_LEN as usize + ARP_PKT_LEN, |buf| {
+        buf[..6].copy_from_slice(dst.octets());
+        buf[6..12].copy_from_slice(sha.octets());
+        buf[12..14].copy_from_slice(&(ETHER_TYPE_ARP as u16).to_be_bytes());
+
+        let arp = &mut buf[ETHER_HDR_LEN as usize..];
+
+        arp[0..2].copy_from_slice(&(::ffi::ARP_HRD_ETHER as u16).to_be_bytes());
+        arp[2..4].copy_from_slice(&(ETHER_TYPE_IPv4 as u16).to_be_bytes());
+        arp[4] = ETHER_ADDR_LEN as u8;
+        arp[5] = 4;
+        arp[6..8].copy_from_slice(&op.to_be_bytes());
+        arp[8..14].copy_from_slice(sha.octets());
+        arp[14..18].copy_from_slice(&sip.octets());
+        arp[18..24].copy_from_slice(tha.octets());
+        arp[24..28].copy_from_slice(&tip.octets());
+    })
+}
+
+fn neighbor_solicit(pool: mempool::MemPoolRef, mac: &EtherAddr, src: Ipv6Addr, target: Ipv6Addr) -> Result<MBuf> {
+    let dst = solicited_node(&target);
+
+    build_packet(pool, ETHER_HDR_LEN as usize + IPV6_HDR_LEN + ND_PKT_LEN, |buf| {
+        buf[..6].copy_from_slice(ipv6_multicast_mac(&dst).octets());
+        buf[6..12].copy_from_slice(mac.octets());
+        buf[12..14].copy_from_slice(&(ETHER_TYPE_IPv6 as u16).to_be_bytes());
+
+        let ip6 = &mut buf[ETHER_HDR_LEN as usize..];
+
+        ip6[0..4].copy_from_slice(&(6u32 << 28).to_be_bytes());
+        ip6[4..6].copy_from_slice(&(ND_PKT_LEN as u16).to_be_bytes());
+        ip6[6] = libc::IPPROTO_ICMPV6 as u8;
+        ip6[7] = 255;
+        ip6[8..24].copy_from_slice(&src.octets());
+        ip6[24..40].copy_from_slice(&dst.octets());
+
+        let (hdr, icmp) = ip6.split_at_mut(IPV6_HDR_LEN);
+
+        icmp[0] = ND_NEIGHBOR_SOLICIT;
+        icmp[1] = 0;
+        icmp[2..8].copy_from_slice(&[0; 6]);
+        icmp[8..24].copy_from_slice(&target.octets());
+        icmp[24] = ND_OPT_SOURCE_LINKADDR;
+        icmp[25] = 1;
+        icmp[26..32].copy_from_slice(mac.octets());
+
+        let cksum = icmpv6_cksum(&hdr[8..40], icmp);
+
+        icmp[2..4].copy_from_slice(&cksum.to_ne_bytes());
+    })
+}
+
+/// The ICMPv6 checksum with the IPv6 pseudo header, the addresses are the source and destination of IPv6 header.
+fn icmpv6_cksum(addrs: &[u8], icmp: &[u8]) -> u16 {
+    let mut buf = Vec::with_capacity(addrs.len() + 8 + icmp.len());
+
+    buf.extend_from_slice(addrs);
+    buf.extend_from_slice(&(icmp.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&u32::from(libc::IPPROTO_ICMPV6 as u8).to_be_bytes());
+    buf.extend_from_slice(icmp);
+
+    match ip::raw_cksum(&buf) {
+        0xffff => 0xffff,
+        cksum => !cksum,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_hop() {
+        let default = Route {
+            port: 0,
+            gateway: Some(Ipv4Addr::new(10, 0, 0, 1).into()),
+        };
+        let local = Route { port: 1, gateway: None };
+
+        assert_eq!(
+            local.next_hop(Ipv4Addr::new(192, 168, 1, 100).into()),
+            IpAddr::from(Ipv4Addr::new(192, 168, 1, 100))
+        );
+        assert_eq!(
+            default.next_hop(Ipv4Addr::new(8, 8, 8, 8).into()),
+            IpAddr::from(Ipv4Addr::new(10, 0, 0, 1))
+        );
+    }
+
+    #[test]
+    fn test_prefix_conf() {
+        assert_eq!(PrefixConf::for_networks(vec![]), PrefixConf::default());
+
+        let conf = PrefixConf::for_networks(
+            (0..2000u32)
+                .map(|i| (IpAddr::V4(Ipv4Addr::from(i << 8)), 32))
+                .chain((0..1000u32).map(|i| (IpAddr::V6(Ipv6Addr::from(u128::from(i) << 64)), 64))),
+        );
+
+        assert_eq!(conf.v4.max_rules, 2000);
+        assert_eq!(conf.v4.number_tbl8s, 2000);
+        assert_eq!(conf.v6.max_rules, 1024);
+        assert_eq!(conf.v6.number_tbl8s, 5000);
+    }
+
+    #[test]
+    fn test_solicited_node() {
+        let addr: Ipv6Addr = "fe80::2aa:ff:fe28:9c5a".parse().unwrap();
+        let node = solicited_node(&addr);
+
+        assert_eq!(node, "ff02::1:ff28:9c5a".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(
+            ipv6_multicast_mac(&node),
+            EtherAddr::new(0x33, 0x33, 0xff, 0x28, 0x9c, 0x5a)
+        );
+    }
+
+    #[test]
+    fn test_parse_nd() {
+        let mut pkt = vec![0u8; IPV6_HDR_LEN + ND_PKT_LEN];
+        let target: Ipv6Addr = "2001:db8::1".parse().unwrap();
+
+        pkt[0] = 0x60;
+        pkt[6] = libc::IPPROTO_ICMPV6 as u8;
+        pkt[7] = 255;
+        pkt[8..24].copy_from_slice(&target.octets());
+        pkt[IPV6_HDR_LEN] = ND_NEIGHBOR_ADVERT;
+        pkt[IPV6_HDR_LEN + 8..IPV6_HDR_LEN + 24].copy_from_slice(&target.octets());
+        pkt[IPV6_HDR_LEN + 24..].copy_from_slice(&[ND_OPT_TARGET_LINKADDR, 1, 0, 1, 2, 3, 4, 5]);
+
+        let nd = parse_nd(&pkt).unwrap();
+
+        assert_eq!(nd.kind, ND_NEIGHBOR_ADVERT);
+        assert_eq!(nd.target, target);
+        assert_eq!(nd.linkaddr, Some(EtherAddr::new(0, 1, 2, 3, 4, 5)));
+
+        pkt[7] = 64;
+
+        assert!(parse_nd(&pkt).is_none());
+    }
+}
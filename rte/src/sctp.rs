@@ -0,0 +1,98 @@
+//!
+//! SCTP header and its CRC32c checksum.
+//!
+use ffi;
+
+use hashers;
+use mbuf;
+
+/// SCTP Header
+pub type SctpHdr = ffi::sctp_hdr;
+
+unsafe impl mbuf::Header for SctpHdr {
+    const LAYER: mbuf::HeaderLayer = mbuf::HeaderLayer::L4;
+}
+
+/// The length of SCTP common header.
+pub const SCTP_HDR_LEN: usize = 12;
+
+const SCTP_CKSUM_OFF: usize = 8;
+
+pub trait SctpExt {
+    /// The source port in host byte order.
+    fn src_port(&self) -> u16;
+
+    /// The destination port in host byte order.
+    fn dst_port(&self) -> u16;
+
+    /// The verification tag in host byte order.
+    fn tag(&self) -> u32;
+}
+
+impl SctpExt for SctpHdr {
+    fn src_port(&self) -> u16 {
+        u16::from_be(self.src_port)
+    }
+
+    fn dst_port(&self) -> u16 {
+        u16::from_be(self.dst_port)
+    }
+
+    fn tag(&self) -> u32 {
+        u32::from_be(self.tag)
+    }
+}
+
+/// Calculate the CRC32c checksum of a SCTP packet, which starts from the SCTP common header.
+///
+/// The checksum field of the packet is regarded as zero,
+/// and the result could be stored to the `cksum` field of `SctpHdr` directly.
+pub fn sctp_cksum(pkt: &[u8]) -> u32 {
+    assert!(pkt.len() >= SCTP_HDR_LEN);
+
+    let crc = hashers::crc(&pkt[..SCTP_CKSUM_OFF], 0xffff_ffff);
+    let crc = hashers::crc(&[0; 4], crc);
+    let crc = hashers::crc(&pkt[SCTP_HDR_LEN..], crc);
+
+    // the CRC32c checksum is transmitted in little endian
+    (!crc).to_le()
+}
+
+/// Verify the CRC32c checksum of a SCTP packet.
+pub fn sctp_cksum_verify(pkt: &[u8]) -> bool {
+    pkt.len() >= SCTP_HDR_LEN && sctp_cksum(pkt).to_ne_bytes()[..] == pkt[SCTP_CKSUM_OFF..SCTP_CKSUM_OFF + 4]
+}
+
+/// Update the CRC32c checksum of a SCTP packet.
+pub fn sctp_cksum_update(pkt: &mut [u8]) {
+    let cksum = sctp_cksum(pkt);
+
+    pkt[SCTP_CKSUM_OFF..SCTP_CKSUM_OFF + 4].copy_from_slice(&cksum.to_ne_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sctp_cksum() {
+        // CRC32c check value
+        assert_eq!(!hashers::crc(b"123456789", 0xffff_ffff), 0xe306_9283);
+
+        // SCTP INIT chunk
+        let mut pkt = vec![
+            0x80, 0x44, 0x00, 0x50, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00, 0x00, 0x14, 0x12, 0x34,
+            0x56, 0x78, 0x00, 0x01, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x0a, 0x12, 0x34, 0x56, 0x78,
+        ];
+
+        assert!(!sctp_cksum_verify(&pkt));
+
+        sctp_cksum_update(&mut pkt);
+
+        assert!(sctp_cksum_verify(&pkt));
+
+        pkt[20] ^= 1;
+
+        assert!(!sctp_cksum_verify(&pkt));
+    }
+}
@@ -0,0 +1,386 @@
+//!
+//! Plugin module (SFP/QSFP) EEPROM parsing.
+//!
+//! The module EEPROM read by `EthDevice::module_eeprom` is laid out as defined by
+//! the SFF specifications, SFF-8079 and SFF-8472 for SFP modules,
+//! SFF-8436 and SFF-8636 for QSFP modules.
+//!
+//! Only the internally calibrated diagnostics are decoded.
+//!
+use std::fmt;
+
+use ffi;
+
+/// The type of plugin module EEPROM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleType {
+    /// SFP module without diagnostics.
+    Sff8079,
+    /// SFP module with digital diagnostic monitoring.
+    Sff8472,
+    /// QSFP+ module.
+    Sff8436,
+    /// QSFP28 module.
+    Sff8636,
+    /// Unknown module type.
+    Unknown(u32),
+}
+
+impl From<u32> for ModuleType {
+    fn from(ty: u32) -> Self {
+        match ty {
+            ffi::RTE_ETH_MODULE_SFF_8079 => ModuleType::Sff8079,
+            ffi::RTE_ETH_MODULE_SFF_8472 => ModuleType::Sff8472,
+            ffi::RTE_ETH_MODULE_SFF_8436 => ModuleType::Sff8436,
+            ffi::RTE_ETH_MODULE_SFF_8636 => ModuleType::Sff8636,
+            _ => ModuleType::Unknown(ty),
+        }
+    }
+}
+
+impl fmt::Display for ModuleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ModuleType::Sff8079 => write!(f, "SFF-8079"),
+            ModuleType::Sff8472 => write!(f, "SFF-8472"),
+            ModuleType::Sff8436 => write!(f, "SFF-8436"),
+            ModuleType::Sff8636 => write!(f, "SFF-8636"),
+            ModuleType::Unknown(ty) => write!(f, "unknown ({})", ty),
+        }
+    }
+}
+
+/// The type and length of plugin module EEPROM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModuleInfo {
+    /// Type of plugin module EEPROM.
+    pub kind: ModuleType,
+    /// Length of plugin module EEPROM.
+    pub eeprom_len: usize,
+}
+
+impl From<ffi::rte_eth_dev_module_info> for ModuleInfo {
+    fn from(info: ffi::rte_eth_dev_module_info) -> Self {
+        ModuleInfo {
+            kind: info.type_.into(),
+            eeprom_len: info.eeprom_len as usize,
+        }
+    }
+}
+
+/// The digital diagnostic monitoring values of plugin module.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diagnostics {
+    /// Internally measured module temperature in degrees Celsius.
+    pub temperature: f64,
+    /// Internally measured supply voltage in volts.
+    pub voltage: f64,
+    /// Laser bias current of each channel in milliamperes.
+    pub tx_bias: Vec<f64>,
+    /// Transmitted optical power of each channel in milliwatts.
+    pub tx_power: Vec<f64>,
+    /// Received optical power of each channel in milliwatts.
+    pub rx_power: Vec<f64>,
+}
+
+/// Convert the optical power from milliwatts to dBm.
+pub fn mw_to_dbm(mw: f64) -> f64 {
+    10.0 * mw.log10()
+}
+
+/// The parsed plugin module EEPROM.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    /// Type of plugin module EEPROM.
+    pub kind: ModuleType,
+    /// The physical device identifier, e.g. 0x03 for SFP/SFP+, 0x11 for QSFP28.
+    pub identifier: u8,
+    /// The vendor name.
+    pub vendor_name: String,
+    /// The IEEE company ID of vendor.
+    pub vendor_oui: [u8; 3],
+    /// The part number provided by vendor.
+    pub part_number: String,
+    /// The revision level for part number provided by vendor.
+    pub revision: String,
+    /// The serial number provided by vendor.
+    pub serial_number: String,
+    /// The manufacturing date code, in `YYMMDD` format.
+    pub date_code: String,
+    /// The digital diagnostic monitoring values, if implemented.
+    pub diagnostics: Option<Diagnostics>,
+}
+
+// SFF-8079/SFF-8472 page A0h
+const SFP_VENDOR_NAME: usize = 20;
+const SFP_VENDOR_OUI: usize = 37;
+const SFP_VENDOR_PN: usize = 40;
+const SFP_VENDOR_REV: usize = 56;
+const SFP_VENDOR_SN: usize = 68;
+const SFP_DATE_CODE: usize = 84;
+const SFP_DIAG_TYPE: usize = 92;
+
+const SFP_DIAG_IMPLEMENTED: u8 = 0x40;
+const SFP_DIAG_INTERNAL_CAL: u8 = 0x20;
+
+// SFF-8472 page A2h, follows page A0h
+const SFP_DIAG_PAGE: usize = 256;
+const SFP_DIAG_TEMP: usize = SFP_DIAG_PAGE + 96;
+
+// SFF-8436/SFF-8636 lower page 00h
+const QSFP_TEMP: usize = 22;
+const QSFP_VCC: usize = 26;
+const QSFP_RX_POWER: usize = 34;
+const QSFP_TX_BIAS: usize = 42;
+const QSFP_TX_POWER: usize = 50;
+const QSFP_CHANNELS: usize = 4;
+
+// SFF-8436/SFF-8636 upper page 00h
+const QSFP_VENDOR_NAME: usize = 148;
+const QSFP_VENDOR_OUI: usize = 165;
+const QSFP_VENDOR_PN: usize = 168;
+const QSFP_VENDOR_REV: usize = 184;
+const QSFP_VENDOR_SN: usize = 196;
+const QSFP_DATE_CODE: usize = 212;
+
+fn ascii(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).trim_end_matches([' ', '\0']).to_owned()
+}
+
+fn word(data: &[u8], off: usize) -> u16 {
+    u16::from_be_bytes([data[off], data[off + 1]])
+}
+
+// in 1/256 degrees Celsius
+fn temperature(data: &[u8], off: usize) -> f64 {
+    f64::from(word(data, off) as i16) / 256.0
+}
+
+// in 100 uV
+fn voltage(data: &[u8], off: usize) -> f64 {
+    f64::from(word(data, off)) / 10_000.0
+}
+
+// in 2 uA
+fn bias(data: &[u8], off: usize) -> f64 {
+    f64::from(word(data, off)) * 2.0 / 1000.0
+}
+
+// in 0.1 uW
+fn power(data: &[u8], off: usize) -> f64 {
+    f64::from(word(data, off)) / 10_000.0
+}
+
+impl Module {
+    /// Parse the plugin module EEPROM of the given type.
+    ///
+    /// Returns `None` if the type is unknown or the EEPROM is truncated.
+    pub fn parse(kind: ModuleType, data: &[u8]) -> Option<Module> {
+        match kind {
+            ModuleType::Sff8079 | ModuleType::Sff8472 => Self::parse_sfp(kind, data),
+            ModuleType::Sff8436 | ModuleType::Sff8636 => Self::parse_qsfp(kind, data),
+            ModuleType::Unknown(_) => None,
+        }
+    }
+
+    fn parse_sfp(kind: ModuleType, data: &[u8]) -> Option<Module> {
+        if data.len() < ffi::RTE_ETH_MODULE_SFF_8079_LEN as usize {
+            return None;
+        }
+
+        let diag_type = data[SFP_DIAG_TYPE];
+        let diagnostics = if kind == ModuleType::Sff8472
+            && data.len() >= ffi::RTE_ETH_MODULE_SFF_8472_LEN as usize
+            && diag_type & SFP_DIAG_IMPLEMENTED != 0
+            && diag_type & SFP_DIAG_INTERNAL_CAL != 0
+        {
+            Some(Diagnostics {
+                temperature: temperature(data, SFP_DIAG_TEMP),
+                voltage: voltage(data, SFP_DIAG_TEMP + 2),
+                tx_bias: vec![bias(data, SFP_DIAG_TEMP + 4)],
+                tx_power: vec![power(data, SFP_DIAG_TEMP + 6)],
+                rx_power: vec![power(data, SFP_DIAG_TEMP + 8)],
+            })
+        } else {
+            None
+        };
+
+        Some(Module {
+            kind,
+            identifier: data[0],
+            vendor_name: ascii(&data[SFP_VENDOR_NAME..SFP_VENDOR_NAME + 16]),
+            vendor_oui: [data[SFP_VENDOR_OUI], data[SFP_VENDOR_OUI + 1], data[SFP_VENDOR_OUI + 2]],
+            part_number: ascii(&data[SFP_VENDOR_PN..SFP_VENDOR_PN + 16]),
+            revision: ascii(&data[SFP_VENDOR_REV..SFP_VENDOR_REV + 4]),
+            serial_number: ascii(&data[SFP_VENDOR_SN..SFP_VENDOR_SN + 16]),
+            date_code: ascii(&data[SFP_DATE_CODE..SFP_DATE_CODE + 6]),
+            diagnostics,
+        })
+    }
+
+    fn parse_qsfp(kind: ModuleType, data: &[u8]) -> Option<Module> {
+        if data.len() < ffi::RTE_ETH_MODULE_SFF_8636_LEN as usize {
+            return None;
+        }
+
+        let channels = |off: usize, f: fn(&[u8], usize) -> f64| -> Vec<f64> {
+            (0..QSFP_CHANNELS).map(|ch| f(data, off + ch * 2)).collect()
+        };
+
+        let diagnostics = Diagnostics {
+            temperature: temperature(data, QSFP_TEMP),
+            voltage: voltage(data, QSFP_VCC),
+            tx_bias: channels(QSFP_TX_BIAS, bias),
+            // the transmitted power is only defined by SFF-8636
+            tx_power: if kind == ModuleType::Sff8636 {
+                channels(QSFP_TX_POWER, power)
+            } else {
+                vec![]
+            },
+            rx_power: channels(QSFP_RX_POWER, power),
+        };
+
+        Some(Module {
+            kind,
+            identifier: data[0],
+            vendor_name: ascii(&data[QSFP_VENDOR_NAME..QSFP_VENDOR_NAME + 16]),
+            vendor_oui: [
+                data[QSFP_VENDOR_OUI],
+                data[QSFP_VENDOR_OUI + 1],
+                data[QSFP_VENDOR_OUI + 2],
+            ],
+            part_number: ascii(&data[QSFP_VENDOR_PN..QSFP_VENDOR_PN + 16]),
+            revision: ascii(&data[QSFP_VENDOR_REV..QSFP_VENDOR_REV + 2]),
+            serial_number: ascii(&data[QSFP_VENDOR_SN..QSFP_VENDOR_SN + 16]),
+            date_code: ascii(&data[QSFP_DATE_CODE..QSFP_DATE_CODE + 6]),
+            diagnostics: Some(diagnostics),
+        })
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Module type: {} (identifier 0x{:02x})", self.kind, self.identifier)?;
+        writeln!(
+            f,
+            "Vendor: {} (OUI {:02x}:{:02x}:{:02x})",
+            self.vendor_name, self.vendor_oui[0], self.vendor_oui[1], self.vendor_oui[2]
+        )?;
+        writeln!(f, "Part number: {} rev {}", self.part_number, self.revision)?;
+        writeln!(f, "Serial number: {}", self.serial_number)?;
+        write!(f, "Date code: {}", self.date_code)?;
+
+        if let Some(ref diag) = self.diagnostics {
+            write!(f, "\nTemperature: {:.2} C", diag.temperature)?;
+            write!(f, "\nVoltage: {:.4} V", diag.voltage)?;
+
+            for (ch, bias) in diag.tx_bias.iter().enumerate() {
+                write!(f, "\nChannel {} TX bias: {:.3} mA", ch, bias)?;
+            }
+            for (ch, power) in diag.tx_power.iter().enumerate() {
+                write!(
+                    f,
+                    "\nChannel {} TX power: {:.4} mW ({:.2} dBm)",
+                    ch,
+                    power,
+                    mw_to_dbm(*power)
+                )?;
+            }
+            for (ch, power) in diag.rx_power.iter().enumerate() {
+                write!(
+                    f,
+                    "\nChannel {} RX power: {:.4} mW ({:.2} dBm)",
+                    ch,
+                    power,
+                    mw_to_dbm(*power)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put(data: &mut [u8], off: usize, s: &[u8]) {
+        data[off..off + s.len()].copy_from_slice(s);
+    }
+
+    #[test]
+    fn test_parse_sfp() {
+        let mut data = vec![0u8; ffi::RTE_ETH_MODULE_SFF_8472_LEN as usize];
+
+        data[0] = 0x03;
+        put(&mut data, SFP_VENDOR_NAME, b"FINISAR CORP.   ");
+        put(&mut data, SFP_VENDOR_OUI, &[0x00, 0x90, 0x65]);
+        put(&mut data, SFP_VENDOR_PN, b"FTLX8571D3BCL   ");
+        put(&mut data, SFP_VENDOR_REV, b"A   ");
+        put(&mut data, SFP_VENDOR_SN, b"ALN1D5X         ");
+        put(&mut data, SFP_DATE_CODE, b"190523  ");
+        data[SFP_DIAG_TYPE] = SFP_DIAG_IMPLEMENTED | SFP_DIAG_INTERNAL_CAL;
+        // 35.5 C, 3.3 V, 6 mA, 0.5 mW, 0.25 mW
+        put(
+            &mut data,
+            SFP_DIAG_TEMP,
+            &[0x23, 0x80, 0x80, 0xe8, 0x0b, 0xb8, 0x13, 0x88, 0x09, 0xc4],
+        );
+
+        let m = Module::parse(ModuleType::Sff8472, &data).unwrap();
+
+        assert_eq!(m.identifier, 0x03);
+        assert_eq!(m.vendor_name, "FINISAR CORP.");
+        assert_eq!(m.vendor_oui, [0x00, 0x90, 0x65]);
+        assert_eq!(m.part_number, "FTLX8571D3BCL");
+        assert_eq!(m.revision, "A");
+        assert_eq!(m.serial_number, "ALN1D5X");
+        assert_eq!(m.date_code, "190523");
+
+        let diag = m.diagnostics.unwrap();
+
+        assert_eq!(diag.temperature, 35.5);
+        assert_eq!(diag.voltage, 3.3);
+        assert_eq!(diag.tx_bias, vec![6.0]);
+        assert_eq!(diag.tx_power, vec![0.5]);
+        assert_eq!(diag.rx_power, vec![0.25]);
+
+        // SFF-8079 has no diagnostics page
+        let m = Module::parse(ModuleType::Sff8079, &data[..256]).unwrap();
+
+        assert_eq!(m.diagnostics, None);
+
+        // truncated
+        assert!(Module::parse(ModuleType::Sff8472, &data[..128]).is_none());
+    }
+
+    #[test]
+    fn test_parse_qsfp() {
+        let mut data = vec![0u8; ffi::RTE_ETH_MODULE_SFF_8636_LEN as usize];
+
+        data[0] = 0x11;
+        // -5 C
+        put(&mut data, QSFP_TEMP, &[0xfb, 0x00]);
+        put(&mut data, QSFP_RX_POWER + 2, &[0x27, 0x10]);
+        put(&mut data, QSFP_TX_POWER + 6, &[0x13, 0x88]);
+        put(&mut data, QSFP_VENDOR_NAME, b"Mellanox        ");
+        put(&mut data, QSFP_VENDOR_REV, b"A3");
+
+        let m = Module::parse(ModuleType::Sff8636, &data).unwrap();
+
+        assert_eq!(m.vendor_name, "Mellanox");
+        assert_eq!(m.revision, "A3");
+
+        let diag = m.diagnostics.unwrap();
+
+        assert_eq!(diag.temperature, -5.0);
+        assert_eq!(diag.rx_power, vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(diag.tx_power, vec![0.0, 0.0, 0.0, 0.5]);
+        assert_eq!(mw_to_dbm(diag.rx_power[1]), 0.0);
+
+        let m = Module::parse(ModuleType::Sff8436, &data).unwrap();
+
+        assert!(m.diagnostics.unwrap().tx_power.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//!
+//! Separate the slow-path protocols to a dedicated RX queue.
+//!
+//! The control plane protocols, e.g. LACP, LLDP, ARP or BGP, are rare but sensitive to the latency and loss,
+//! they shouldn't be queued behind the bursts of the datapath, or spread over the RSS queues of the workers.
+//!
+//! A `ControlQueue` installs the flow rules which steer the packets of the protocols to its RX queue,
+//! and receives them with its own `rx_burst`, e.g. on the master lcore. The queue must be set up
+//! like the other RX queues of the port, and excluded from the RSS redirection table of the datapath.
+//!
+//! If the NIC can't offload the rules of a protocol, the protocol falls back to the software,
+//! the datapath must `divert` its packets from the received bursts to the control queue.
+//!
+//! ```no_run
+//! use rte::slowpath::{ControlQueue, Protocol};
+//! use rte::*;
+//!
+//! let mut ctrlq = ControlQueue::new(0, 3);
+//!
+//! ctrlq.add(Protocol::Lacp).unwrap();
+//! ctrlq.add(Protocol::Bgp).unwrap();
+//!
+//! let mut pkts: Vec<Option<mbuf::MBuf>> = (0..32).map(|_| None).collect();
+//!
+//! let n = ctrlq.rx_burst(&mut pkts);
+//!
+//! for m in pkts[..n].iter().flat_map(|m| m.as_ref()) {
+//!     // handle the control packets
+//! }
+//! ```
+//!
+use std::net::IpAddr;
+
+use libc;
+
+use errors::Result;
+use ethdev::{EthDevice, PortId, QueueId};
+use ether::{EtherAddr, ETHER_TYPE_1588, ETHER_TYPE_ARP, ETHER_TYPE_LLDP, ETHER_TYPE_SLOW};
+use filter::{dissect_mbuf, Dissection};
+use flow::{FlowAction, FlowAttr, FlowItem, FlowRule, FlowTable};
+use mbuf::MBuf;
+
+/// The bridge group address of STP.
+const STP_GROUP_ADDR: [u8; 6] = [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00];
+
+const IPPROTO_OSPF: u8 = 89;
+const IPPROTO_VRRP: u8 = 112;
+const BGP_PORT: u16 = 179;
+const BFD_PORT: u16 = 3784;
+
+/// A slow-path protocol.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Protocol {
+    /// The slow protocols, i.e. LACP and the marker protocol.
+    Lacp,
+    /// The link layer discovery protocol.
+    Lldp,
+    /// The address resolution protocol.
+    Arp,
+    /// The precision time protocol over Ethernet.
+    Ptp,
+    /// The spanning tree protocol, sent to the bridge group address.
+    Stp,
+    /// OSPF over IPv4.
+    Ospf,
+    /// VRRP over IPv4.
+    Vrrp,
+    /// BGP over IPv4 or IPv6.
+    Bgp,
+    /// The single-hop BFD over IPv4 or IPv6.
+    Bfd,
+    /// The packets matching a pattern.
+    Custom(Vec<FlowItem>),
+}
+
+fn ether_type(ether_type: u16) -> Vec<FlowItem> {
+    vec![FlowItem::Eth {
+        dst: None,
+        src: None,
+        ether_type: Some(ether_type),
+    }]
+}
+
+fn ipv4_proto(proto: u8) -> Vec<FlowItem> {
+    vec![
+        FlowItem::eth(),
+        FlowItem::Ipv4 {
+            src: None,
+            dst: None,
+            proto: Some(proto),
+        },
+    ]
+}
+
+impl Protocol {
+    /// The patterns of the protocol, a packet of the protocol matches any of them.
+    pub fn patterns(&self) -> Vec<Vec<FlowItem>> {
+        match *self {
+            Protocol::Lacp => vec![ether_type(ETHER_TYPE_SLOW as u16)],
+            Protocol::Lldp => vec![ether_type(ETHER_TYPE_LLDP as u16)],
+            Protocol::Arp => vec![ether_type(ETHER_TYPE_ARP as u16)],
+            Protocol::Ptp => vec![ether_type(ETHER_TYPE_1588 as u16)],
+            Protocol::Stp => vec![vec![FlowItem::Eth {
+                dst: Some(STP_GROUP_ADDR),
+                src: None,
+                ether_type: None,
+            }]],
+            Protocol::Ospf => vec![ipv4_proto(IPPROTO_OSPF)],
+            Protocol::Vrrp => vec![ipv4_proto(IPPROTO_VRRP)],
+            Protocol::Bgp => [FlowItem::ipv4(), FlowItem::ipv6()]
+                .iter()
+                .flat_map(|ip| {
+                    vec![
+                        vec![
+                            FlowItem::eth(),
+                            ip.clone(),
+                            FlowItem::Tcp {
+                                src_port: None,
+                                dst_port: Some(BGP_PORT),
+                            },
+                        ],
+                        vec![
+                            FlowItem::eth(),
+                            ip.clone(),
+                            FlowItem::Tcp {
+                                src_port: Some(BGP_PORT),
+                                dst_port: None,
+                            },
+                        ],
+                    ]
+                })
+                .collect(),
+            Protocol::Bfd => [FlowItem::ipv4(), FlowItem::ipv6()]
+                .iter()
+                .map(|ip| {
+                    vec![
+                        FlowItem::eth(),
+                        ip.clone(),
+                        FlowItem::Udp {
+                            src_port: None,
+                            dst_port: Some(BFD_PORT),
+                        },
+                    ]
+                })
+                .collect(),
+            Protocol::Custom(ref pattern) => vec![pattern.clone()],
+        }
+    }
+
+    /// The dissected packet is a packet of the protocol.
+    pub fn matches(&self, d: &Dissection) -> bool {
+        self.patterns()
+            .iter()
+            .any(|pattern| pattern.iter().all(|item| item_matches(item, d)))
+    }
+}
+
+fn prefix_matches(addr: &IpAddr, prefix: &IpAddr, len: u8) -> bool {
+    match (*addr, *prefix) {
+        (IpAddr::V4(addr), IpAddr::V4(prefix)) => {
+            let mask = if len == 0 {
+                0
+            } else {
+                !0u32 << (32 - u32::from(len.min(32)))
+            };
+
+            u32::from(addr) & mask == u32::from(prefix) & mask
+        }
+        (IpAddr::V6(addr), IpAddr::V6(prefix)) => {
+            let mask = if len == 0 {
+                0
+            } else {
+                !0u128 << (128 - u32::from(len.min(128)))
+            };
+
+            u128::from(addr) & mask == u128::from(prefix) & mask
+        }
+        _ => false,
+    }
+}
+
+fn item_matches(item: &FlowItem, d: &Dissection) -> bool {
+    let ip = |addr: Option<IpAddr>, prefix: Option<(IpAddr, u8)>| match (addr, prefix) {
+        (_, None) => true,
+        (Some(addr), Some((prefix, len))) => prefix_matches(&addr, &prefix, len),
+        (None, Some(_)) => false,
+    };
+    let exact = |value: Option<u16>, expected: Option<u16>| expected.is_none_or(|expected| value == Some(expected));
+
+    match *item {
+        FlowItem::Eth { dst, src, ether_type } => {
+            dst.is_none_or(|dst| d.dst_mac == EtherAddr::from(dst))
+                && src.is_none_or(|src| d.src_mac == EtherAddr::from(src))
+                && ether_type.is_none_or(|ether_type| d.ether_type == ether_type)
+        }
+        FlowItem::Vlan { vid } => d.nb_vlans > 0 && vid.is_none_or(|vid| d.vlans[0] == vid),
+        FlowItem::Ipv4 { src, dst, proto } => {
+            d.src_ip.is_some_and(|addr| addr.is_ipv4())
+                && ip(d.src_ip, src.map(|(addr, len)| (addr.into(), len)))
+                && ip(d.dst_ip, dst.map(|(addr, len)| (addr.into(), len)))
+                && proto.is_none_or(|proto| d.proto == Some(proto))
+        }
+        FlowItem::Ipv6 { src, dst, proto } => {
+            d.src_ip.is_some_and(|addr| addr.is_ipv6())
+                && ip(d.src_ip, src.map(|(addr, len)| (addr.into(), len)))
+                && ip(d.dst_ip, dst.map(|(addr, len)| (addr.into(), len)))
+                && proto.is_none_or(|proto| d.proto == Some(proto))
+        }
+        FlowItem::Udp { src_port, dst_port } => {
+            d.proto == Some(libc::IPPROTO_UDP as u8) && exact(d.src_port, src_port) && exact(d.dst_port, dst_port)
+        }
+        FlowItem::Tcp { src_port, dst_port } => {
+            d.proto == Some(libc::IPPROTO_TCP as u8) && exact(d.src_port, src_port) && exact(d.dst_port, dst_port)
+        }
+        // the tunnel headers are not dissected
+        FlowItem::Vxlan { .. } => false,
+    }
+}
+
+/// The statistics of a control queue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ControlStats {
+    /// The packets received from the control queue.
+    pub received: u64,
+    /// The packets diverted from the datapath in the software.
+    pub diverted: u64,
+}
+
+/// A dedicated RX queue of the slow-path protocols.
+///
+/// The installed rules are destroyed when the queue is dropped.
+pub struct ControlQueue {
+    port_id: PortId,
+    queue_id: QueueId,
+    attr: FlowAttr,
+    table: FlowTable,
+    // the protocols and the ids of their rules, which are empty if the protocol falls back to the software
+    protocols: Vec<(Protocol, Vec<u32>)>,
+    stats: ControlStats,
+}
+
+impl ControlQueue {
+    /// Create a control queue with the RX queue of the port.
+    pub fn new(port_id: PortId, queue_id: QueueId) -> Self {
+        ControlQueue {
+            port_id,
+            queue_id,
+            attr: FlowAttr::ingress(),
+            table: FlowTable::new(port_id),
+            protocols: vec![],
+            stats: ControlStats::default(),
+        }
+    }
+
+    /// Install the rules in the group with the priority, instead of the group 0 with the highest priority.
+    pub fn with_attr(mut self, group: u32, priority: u32) -> Self {
+        self.attr.group = group;
+        self.attr.priority = priority;
+        self
+    }
+
+    /// The port of the queue.
+    pub fn portid(&self) -> PortId {
+        self.port_id
+    }
+
+    /// The RX queue.
+    pub fn queue_id(&self) -> QueueId {
+        self.queue_id
+    }
+
+    /// The protocols of the queue, and whether they are steered by the NIC.
+    pub fn protocols<'a>(&'a self) -> impl Iterator<Item = (&'a Protocol, bool)> + 'a {
+        self.protocols.iter().map(|(protocol, ids)| (protocol, !ids.is_empty()))
+    }
+
+    /// All the protocols are steered by the NIC, the datapath doesn't need to `divert` the packets.
+    pub fn is_offloaded(&self) -> bool {
+        self.protocols.iter().all(|(_, ids)| !ids.is_empty())
+    }
+
+    /// The statistics of the queue.
+    pub fn stats(&self) -> &ControlStats {
+        &self.stats
+    }
+
+    /// Steer the protocol to the queue, returns whether the rules were offloaded to the NIC.
+    ///
+    /// If any rule of the protocol can't be created, the created ones are destroyed,
+    /// and the protocol falls back to the software.
+    pub fn add(&mut self, protocol: Protocol) -> Result<bool> {
+        if self.protocols.iter().any(|(p, _)| *p == protocol) {
+            return Ok(self.protocols().any(|(p, offloaded)| *p == protocol && offloaded));
+        }
+
+        let mut ids = vec![];
+
+        for pattern in protocol.patterns() {
+            let rule = FlowRule {
+                attr: self.attr,
+                pattern,
+                actions: vec![FlowAction::Queue(self.queue_id)],
+            };
+
+            match self.table.create(rule) {
+                Ok(id) => ids.push(id),
+                Err(err) => {
+                    debug!(
+                        "fail to steer {:?} to port {} queue {} in the NIC, {}",
+                        protocol, self.port_id, self.queue_id, err
+                    );
+
+                    for id in ids.drain(..) {
+                        self.table.destroy(id)?;
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        let offloaded = !ids.is_empty();
+
+        self.protocols.push((protocol, ids));
+
+        Ok(offloaded)
+    }
+
+    /// Stop steering the protocol to the queue, returns whether the protocol was added.
+    pub fn remove(&mut self, protocol: &Protocol) -> Result<bool> {
+        match self.protocols.iter().position(|(p, _)| p == protocol) {
+            Some(idx) => {
+                for &id in &self.protocols[idx].1 {
+                    self.table.destroy(id)?;
+                }
+
+                self.protocols.remove(idx);
+
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// The protocol of the packet, if any.
+    pub fn classify(&self, m: &MBuf) -> Option<&Protocol> {
+        let d = dissect_mbuf(m)?;
+
+        self.protocols
+            .iter()
+            .map(|(protocol, _)| protocol)
+            .find(|protocol| protocol.matches(&d))
+    }
+
+    /// Retrieve a burst of the control packets from the queue.
+    pub fn rx_burst(&mut self, pkts: &mut [Option<MBuf>]) -> usize {
+        let n = self.port_id.rx_burst(self.queue_id, pkts);
+
+        self.stats.received += n as u64;
+
+        n
+    }
+
+    /// Remove the packets of the software protocols from a burst of the datapath, returns the removed packets.
+    pub fn divert(&mut self, pkts: &mut Vec<MBuf>) -> Vec<MBuf> {
+        if self.is_offloaded() {
+            return vec![];
+        }
+
+        let (diverted, kept): (Vec<_>, Vec<_>) = pkts.drain(..).partition(|m| {
+            dissect_mbuf(m).is_some_and(|d| {
+                self.protocols
+                    .iter()
+                    .any(|(protocol, ids)| ids.is_empty() && protocol.matches(&d))
+            })
+        });
+
+        *pkts = kept;
+
+        self.stats.diverted += diverted.len() as u64;
+
+        diverted
+    }
+}
+
+impl Drop for ControlQueue {
+    fn drop(&mut self) {
+        for (protocol, ids) in self.protocols.drain(..) {
+            for id in ids {
+                if let Err(err) = self.table.destroy(id) {
+                    warn!(
+                        "fail to destroy the rule of {:?} on port {}, {}",
+                        protocol, self.port_id, err
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filter::dissect;
+
+    fn frame(ether_type: u16, payload: &[u8]) -> Vec<u8> {
+        let mut pkt = vec![0x01, 0x80, 0xc2, 0, 0, 0x02, 0x02, 0, 0, 0, 0, 1];
+
+        pkt.extend_from_slice(&ether_type.to_be_bytes());
+        pkt.extend_from_slice(payload);
+        pkt
+    }
+
+    fn tcp(sport: u16, dport: u16) -> Vec<u8> {
+        let mut ip = vec![
+            0x45,
+            0,
+            0,
+            40,
+            0,
+            1,
+            0x40,
+            0,
+            64,
+            libc::IPPROTO_TCP as u8,
+            0,
+            0,
+            10,
+            0,
+            0,
+            1,
+            10,
+            0,
+            0,
+            2,
+        ];
+
+        ip.extend_from_slice(&sport.to_be_bytes());
+        ip.extend_from_slice(&dport.to_be_bytes());
+        ip.extend_from_slice(&[0; 16]);
+
+        frame(0x0800, &ip)
+    }
+
+    #[test]
+    fn test_matches() {
+        let lacp = frame(ETHER_TYPE_SLOW as u16, &[1; 46]);
+        let d = dissect(&lacp, lacp.len());
+
+        assert!(Protocol::Lacp.matches(&d));
+        assert!(!Protocol::Lldp.matches(&d));
+        assert!(!Protocol::Bgp.matches(&d));
+
+        let bgp = tcp(40000, 179);
+        let d = dissect(&bgp, bgp.len());
+
+        assert!(Protocol::Bgp.matches(&d));
+        assert!(!Protocol::Bfd.matches(&d));
+        assert!(!Protocol::Ospf.matches(&d));
+        assert!(Protocol::Custom(vec![
+            FlowItem::eth(),
+            FlowItem::Ipv4 {
+                src: Some(("10.0.0.0".parse().unwrap(), 8)),
+                dst: None,
+                proto: None,
+            },
+        ])
+        .matches(&d));
+        assert!(!Protocol::Custom(vec![FlowItem::ipv6()]).matches(&d));
+
+        let http = tcp(40000, 80);
+        let d = dissect(&http, http.len());
+
+        assert!(!Protocol::Bgp.matches(&d));
+        assert_eq!(Protocol::Bgp.patterns().len(), 4);
+    }
+}
@@ -0,0 +1,263 @@
+//!
+//! Soft NIC PMD
+//!
+//! The softnic is a virtual device which runs the DPDK packet framework pipelines, described by a firmware
+//! of CLI commands, on the lcore which polls it with `run`. A hybrid design could offload the standard
+//! stages, e.g. the traffic manager or the ACL tables, to the softnic, while the Rust application focuses
+//! on the custom logic of the other ports.
+//!
+//! The pipelines could be changed at runtime with the CLI commands, which are executed by `manage`
+//! from the TCP connection of the `conn_port`, `Cli` passes the commands through that connection.
+//!
+//! ```no_run
+//! use rte::softnic::{self, SoftNicConf};
+//!
+//! let port_id = softnic::create(
+//!     "net_softnic0",
+//!     &SoftNicConf::default()
+//!         .with_firmware("firmware.cli")
+//!         .with_conn_port(8086),
+//! )
+//! .unwrap();
+//!
+//! let mut cli = softnic::Cli::connect(port_id, 8086).unwrap();
+//!
+//! println!("{}", cli.execute("pipeline RX table 0 rule read stats").unwrap());
+//! ```
+//!
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use libc;
+
+use ffi;
+
+use common::dev;
+use common::features;
+use common::name::{Name, NameKind};
+use errors::{Result, RteError};
+use ethdev::PortId;
+
+/// The driver name of the softnic PMD, which prefixes the device names.
+pub const DRIVER_NAME: &str = "net_softnic";
+
+/// The prompt of the softnic CLI.
+pub const CLI_PROMPT: &str = "softnic> ";
+
+/// The default timeout of a CLI command.
+pub const DEFAULT_CLI_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The configuration of a softnic device, which is passed as the device arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoftNicConf {
+    /// The CLI script which creates the pipelines when the device is started.
+    pub firmware: Option<PathBuf>,
+    /// The TCP port of the CLI connection.
+    pub conn_port: Option<u16>,
+    /// The CPU socket of the device memory.
+    pub cpu_id: Option<u32>,
+    /// The number of the queues of the traffic manager.
+    pub tm_n_queues: Option<u32>,
+    /// The other arguments of the PMD.
+    pub args: Vec<(String, String)>,
+}
+
+impl SoftNicConf {
+    pub fn with_firmware<P: AsRef<Path>>(mut self, firmware: P) -> Self {
+        self.firmware = Some(firmware.as_ref().to_owned());
+        self
+    }
+
+    pub fn with_conn_port(mut self, port: u16) -> Self {
+        self.conn_port = Some(port);
+        self
+    }
+
+    pub fn with_cpu_id(mut self, cpu_id: u32) -> Self {
+        self.cpu_id = Some(cpu_id);
+        self
+    }
+
+    pub fn with_tm_n_queues(mut self, n: u32) -> Self {
+        self.tm_n_queues = Some(n);
+        self
+    }
+
+    /// Pass an argument through to the PMD.
+    pub fn with_arg<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.args.push((key.into(), value.into()));
+        self
+    }
+
+    /// The device arguments, e.g. `firmware=firmware.cli,conn_port=8086`.
+    pub fn devargs(&self) -> String {
+        self.firmware
+            .as_ref()
+            .map(|path| ("firmware".to_owned(), path.display().to_string()))
+            .into_iter()
+            .chain(self.conn_port.map(|port| ("conn_port".to_owned(), port.to_string())))
+            .chain(self.cpu_id.map(|id| ("cpu_id".to_owned(), id.to_string())))
+            .chain(self.tm_n_queues.map(|n| ("tm_n_queues".to_owned(), n.to_string())))
+            .chain(self.args.iter().cloned())
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Create a softnic device, the name must start with `net_softnic`, returns the port of the device.
+///
+/// The device should be configured and started as the other ports, and the firmware is run when it is started.
+pub fn create(name: &str, conf: &SoftNicConf) -> Result<PortId> {
+    if !features().softnic() {
+        return Err(RteError(libc::ENOTSUP).into());
+    }
+
+    if !name.starts_with(DRIVER_NAME) {
+        return Err(RteError(libc::EINVAL).into());
+    }
+
+    dev::hotplug_add("vdev", name, &conf.devargs())?;
+
+    let name = Name::new(NameKind::EthDev, name)?;
+
+    let mut port_id = 0;
+
+    let ret = unsafe { ffi::rte_eth_dev_get_port_by_name(name.as_ptr(), &mut port_id) };
+
+    rte_check!(ret; ok => { port_id }; err => { RteError(-ret).into() })
+}
+
+/// Remove a softnic device, which should be stopped and closed before.
+pub fn destroy(name: &str) -> Result<()> {
+    dev::hotplug_remove("vdev", name)
+}
+
+/// Run the traffic manager and the pipelines of a softnic port.
+///
+/// It should be called from the main loop of the lcore which polls the port.
+#[inline]
+pub fn run(port_id: PortId) -> Result<()> {
+    let ret = unsafe { ffi::_rte_pmd_softnic_run(port_id) };
+
+    rte_check!(ret; err => { RteError(-ret).into() })
+}
+
+/// Service the CLI connection of a softnic port, which executes the received commands.
+///
+/// It should be called periodically from a control-plane thread, e.g. with `coop::every`.
+pub fn manage(port_id: PortId) -> Result<()> {
+    let ret = unsafe { ffi::_rte_pmd_softnic_manage(port_id) };
+
+    rte_check!(ret; err => { RteError(-ret).into() })
+}
+
+/// A client of the CLI connection of a softnic port, which passes the commands through.
+///
+/// The connection is serviced by the client itself when it waits for the response,
+/// so `manage` must not be called for the port by the other threads during the life of the client.
+pub struct Cli {
+    port_id: PortId,
+    stream: TcpStream,
+    timeout: Duration,
+}
+
+impl Cli {
+    /// Connect to the CLI of a softnic port, which listens on the `conn_port` of its configuration.
+    pub fn connect(port_id: PortId, conn_port: u16) -> Result<Cli> {
+        let stream = TcpStream::connect(SocketAddr::from((Ipv4Addr::LOCALHOST, conn_port)))?;
+
+        stream.set_nonblocking(true)?;
+
+        let mut cli = Cli {
+            port_id,
+            stream,
+            timeout: DEFAULT_CLI_TIMEOUT,
+        };
+
+        // skip the welcome message
+        cli.read_response()?;
+
+        Ok(cli)
+    }
+
+    /// Set the timeout of a command.
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Execute a command, returns its output.
+    pub fn execute(&mut self, cmd: &str) -> Result<String> {
+        self.stream.write_all(format!("{}\n", cmd.trim_end()).as_bytes())?;
+
+        self.read_response()
+    }
+
+    /// Execute the commands of a CLI script, returns their outputs.
+    pub fn execute_script<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<String>> {
+        let mut script = String::new();
+
+        fs::File::open(path)?.read_to_string(&mut script)?;
+
+        script
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with(';'))
+            .map(|line| self.execute(line))
+            .collect()
+    }
+
+    /// Read the response until the prompt, servicing the connection meanwhile.
+    fn read_response(&mut self) -> Result<String> {
+        let deadline = Instant::now() + self.timeout;
+        let mut buf = Vec::new();
+        let mut chunk = [0; 1024];
+
+        loop {
+            manage(self.port_id)?;
+
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into()),
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(1)),
+                Err(err) => return Err(err.into()),
+            }
+
+            if buf.ends_with(CLI_PROMPT.as_bytes()) {
+                buf.truncate(buf.len() - CLI_PROMPT.len());
+
+                return Ok(String::from_utf8_lossy(&buf).trim().to_owned());
+            }
+
+            if Instant::now() > deadline {
+                return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_devargs() {
+        assert_eq!(SoftNicConf::default().devargs(), "");
+
+        let conf = SoftNicConf::default()
+            .with_firmware("/etc/softnic/firmware.cli")
+            .with_conn_port(8086)
+            .with_cpu_id(1)
+            .with_arg("sc", "on");
+
+        assert_eq!(
+            conf.devargs(),
+            "firmware=/etc/softnic/firmware.cli,conn_port=8086,cpu_id=1,sc=on"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//!
+//! SR-IOV VF management on the PF ports.
+//!
+//! DPDK 18.11 exposes the VF controls only through the PMD specific APIs,
+//! `SriovDevice` dispatches them by the driver of the PF port, and returns `ENOTSUP`
+//! if the driver doesn't support the control, so a PF management daemon could
+//! administer the VFs handed to the VMs without knowing the NIC.
+//!
+//! ```no_run
+//! use rte::ether::EtherAddr;
+//! use rte::sriov::SriovDevice;
+//! use rte::*;
+//!
+//! let port: PortId = 0;
+//!
+//! for vf in 0..port.max_vfs() {
+//!     port.set_vf_mac_addr(vf, &EtherAddr::from([0x02, 0, 0, 0, 0, vf as u8]))
+//!         .unwrap()
+//!         .set_vf_mac_anti_spoof(vf, true)
+//!         .unwrap()
+//!         .set_vf_rate_limit(vf, 1000)
+//!         .unwrap();
+//! }
+//! ```
+//!
+use std::mem;
+
+use libc;
+
+use ffi;
+
+use errors::{Result, RteError};
+use ethdev::{EthDevice, EthDeviceInfo, PortId, RawEthDeviceStats};
+use ether::EtherAddr;
+
+/// The index of the VF on the PF port.
+pub type VfId = u16;
+
+bitflags! {
+    /// The RX L2 filtering mode of a VF.
+    pub struct VfRxMode: u16 {
+        /// Accept the untagged packets.
+        const ETH_VMDQ_ACCEPT_UNTAG     = 0x0001;
+        /// Accept the packets in the multicast hash table.
+        const ETH_VMDQ_ACCEPT_HASH_MC   = 0x0002;
+        /// Accept the packets in the unicast hash table.
+        const ETH_VMDQ_ACCEPT_HASH_UC   = 0x0004;
+        /// Accept the broadcast packets.
+        const ETH_VMDQ_ACCEPT_BROADCAST = 0x0008;
+        /// Accept all the multicast packets.
+        const ETH_VMDQ_ACCEPT_MULTICAST = 0x0010;
+    }
+}
+
+/// The PF drivers which support the VF management.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PfDriver {
+    /// Intel 82599/X540/X550 family.
+    Ixgbe,
+    /// Intel X710/XL710/XXV710 family.
+    I40e,
+}
+
+impl PfDriver {
+    /// The PF driver of the driver name.
+    pub fn from_driver_name(name: &str) -> Option<Self> {
+        match name {
+            "net_ixgbe" => Some(PfDriver::Ixgbe),
+            "net_i40e" => Some(PfDriver::I40e),
+            _ => None,
+        }
+    }
+}
+
+fn not_supported<T>() -> Result<T> {
+    Err(RteError(libc::ENOTSUP).into())
+}
+
+pub trait SriovDevice {
+    /// The PF driver of the port, or `ENOTSUP` if the driver doesn't support the VF management.
+    fn pf_driver(&self) -> Result<PfDriver>;
+
+    /// The maximum number of VFs of the port.
+    fn max_vfs(&self) -> u16;
+
+    /// Set the MAC address of the VF.
+    ///
+    /// The MAC address should be set before the VF is initialized,
+    /// otherwise it won't be effective until the VF is reinitialized.
+    fn set_vf_mac_addr(&self, vf: VfId, addr: &EtherAddr) -> Result<&Self>;
+
+    /// Enable/Disable the MAC anti spoofing of the VF.
+    fn set_vf_mac_anti_spoof(&self, vf: VfId, on: bool) -> Result<&Self>;
+
+    /// Enable/Disable the VLAN anti spoofing of the VF.
+    fn set_vf_vlan_anti_spoof(&self, vf: VfId, on: bool) -> Result<&Self>;
+
+    /// Enable/Disable the VLAN filter of the VFs in the mask.
+    fn set_vf_vlan_filter(&self, vlan_id: u16, vf_mask: u64, on: bool) -> Result<&Self>;
+
+    /// Insert the VLAN tag to the packets sent by the VF, or disable the insertion if `vlan_id` is 0.
+    fn set_vf_vlan_insert(&self, vf: VfId, vlan_id: u16) -> Result<&Self>;
+
+    /// Limit the TX rate of the VF in Mbps, or remove the limitation if `rate` is 0.
+    fn set_vf_rate_limit(&self, vf: VfId, rate: u32) -> Result<&Self>;
+
+    /// Enable/Disable the unicast promiscuous mode of the VF.
+    fn set_vf_unicast_promisc(&self, vf: VfId, on: bool) -> Result<&Self>;
+
+    /// Enable/Disable the multicast promiscuous mode of the VF.
+    fn set_vf_multicast_promisc(&self, vf: VfId, on: bool) -> Result<&Self>;
+
+    /// Enable/Disable the broadcast receipt of the VF.
+    fn set_vf_broadcast(&self, vf: VfId, on: bool) -> Result<&Self>;
+
+    /// Enable/Disable the RX L2 filtering mode of the VF, which is supported by `ixgbe` only.
+    fn set_vf_rx_mode(&self, vf: VfId, mode: VfRxMode, on: bool) -> Result<&Self>;
+
+    /// Retrieve the statistics of the VF, which is supported by `i40e` only.
+    fn vf_stats(&self, vf: VfId) -> Result<RawEthDeviceStats>;
+
+    /// Reset the statistics of the VF, which is supported by `i40e` only.
+    fn reset_vf_stats(&self, vf: VfId) -> Result<&Self>;
+
+    /// Notify the VF that the link status of PF changed.
+    fn ping_vf(&self, vf: VfId) -> Result<&Self>;
+
+    /// Enable/Disable the TX loopback between the VFs and PF.
+    fn set_tx_loopback(&self, on: bool) -> Result<&Self>;
+}
+
+impl SriovDevice for PortId {
+    fn pf_driver(&self) -> Result<PfDriver> {
+        PfDriver::from_driver_name(self.info().driver_name()).ok_or_else(|| RteError(libc::ENOTSUP).into())
+    }
+
+    fn max_vfs(&self) -> u16 {
+        self.info().max_vfs
+    }
+
+    fn set_vf_mac_addr(&self, vf: VfId, addr: &EtherAddr) -> Result<&Self> {
+        let addr = addr.as_ptr() as *mut _;
+
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_vf_mac_addr(*self, vf, addr) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_mac_addr(*self, vf, addr) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_mac_anti_spoof(&self, vf: VfId, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_vf_mac_anti_spoof(*self, vf, bool_value!(on)) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_mac_anti_spoof(*self, vf, bool_value!(on)) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_vlan_anti_spoof(&self, vf: VfId, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_vf_vlan_anti_spoof(*self, vf, bool_value!(on)) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_vlan_anti_spoof(*self, vf, bool_value!(on)) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_vlan_filter(&self, vlan_id: u16, vf_mask: u64, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe {
+                ffi::rte_pmd_ixgbe_set_vf_vlan_filter(*self, vlan_id, vf_mask, bool_value!(on))
+            },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_vlan_filter(*self, vlan_id, vf_mask, bool_value!(on)) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_vlan_insert(&self, vf: VfId, vlan_id: u16) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_vf_vlan_insert(*self, vf, vlan_id) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_vlan_insert(*self, vf, vlan_id) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_rate_limit(&self, vf: VfId, rate: u32) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => {
+                if rate > u32::from(u16::MAX) {
+                    return Err(RteError(libc::EINVAL).into());
+                }
+
+                // limit all the queues of the VF
+                unsafe { ffi::rte_pmd_ixgbe_set_vf_rate_limit(*self, vf, rate as u16, u64::MAX) }
+            }
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_max_bw(*self, vf, rate) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_unicast_promisc(&self, vf: VfId, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => return not_supported(),
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_vf_unicast_promisc(*self, vf, bool_value!(on)) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_vf_multicast_promisc(&self, vf: VfId, on: bool) -> Result<&Self> {
+        match self.pf_driver()? {
+            PfDriver::Ixgbe => self.set_vf_rx_mode(vf, VfRxMode::ETH_VMDQ_ACCEPT_MULTICAST, on),
+            PfDriver::I40e => {
+                let ret = unsafe { ffi::rte_pmd_i40e_set_vf_multicast_promisc(*self, vf, bool_value!(on)) };
+
+                rte_check!(ret; ok => { self })
+            }
+        }
+    }
+
+    fn set_vf_broadcast(&self, vf: VfId, on: bool) -> Result<&Self> {
+        match self.pf_driver()? {
+            PfDriver::Ixgbe => self.set_vf_rx_mode(vf, VfRxMode::ETH_VMDQ_ACCEPT_BROADCAST, on),
+            PfDriver::I40e => {
+                let ret = unsafe { ffi::rte_pmd_i40e_set_vf_broadcast(*self, vf, bool_value!(on)) };
+
+                rte_check!(ret; ok => { self })
+            }
+        }
+    }
+
+    fn set_vf_rx_mode(&self, vf: VfId, mode: VfRxMode, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_vf_rxmode(*self, vf, mode.bits, bool_value!(on)) },
+            PfDriver::I40e => return not_supported(),
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn vf_stats(&self, vf: VfId) -> Result<RawEthDeviceStats> {
+        let mut stats: RawEthDeviceStats = unsafe { mem::zeroed() };
+
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => return not_supported(),
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_get_vf_stats(*self, vf, &mut stats) },
+        };
+
+        rte_check!(ret; ok => { stats })
+    }
+
+    fn reset_vf_stats(&self, vf: VfId) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => return not_supported(),
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_reset_vf_stats(*self, vf) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn ping_vf(&self, vf: VfId) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_ping_vf(*self, vf) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_ping_vfs(*self, vf) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+
+    fn set_tx_loopback(&self, on: bool) -> Result<&Self> {
+        let ret = match self.pf_driver()? {
+            PfDriver::Ixgbe => unsafe { ffi::rte_pmd_ixgbe_set_tx_loopback(*self, bool_value!(on)) },
+            PfDriver::I40e => unsafe { ffi::rte_pmd_i40e_set_tx_loopback(*self, bool_value!(on)) },
+        };
+
+        rte_check!(ret; ok => { self })
+    }
+}
@@ -0,0 +1,257 @@
+//!
+//! Export and import of the table contents.
+//!
+//! The entries of a table, e.g. the routes of a `RouteTable`, the prefixes of an `IpSet`,
+//! the keys and user data of a hash map, or the rules and user data of an ACL,
+//! are serialized with `bincode` after a versioned header, which identifies the kind of the table
+//! and the number of entries, so a restarted instance could warm up from the exported state,
+//! or an instance could replicate the configuration of another one through a socket.
+//!
+//! The imported entries are collected first, and the table is built in bulk from them,
+//! the services of the current thread are run at the yield points while building a large table.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::io::{BufReader, BufWriter};
+//!
+//! use rte::route::RouteTable;
+//! use rte::table;
+//!
+//! let routes = RouteTable::new().unwrap();
+//!
+//! table::export(&routes, BufWriter::new(File::create("routes.bin").unwrap())).unwrap();
+//!
+//! let routes: RouteTable = table::import(BufReader::new(File::open("routes.bin").unwrap())).unwrap();
+//! ```
+//!
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::io::{Read, Write};
+
+use bincode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use common::coop;
+use errors::{ErrorKind::InvalidTable, Result};
+use filter::Filter;
+
+/// The magic number of the exported tables.
+pub const MAGIC: u32 = 0x5254_4254;
+
+/// The version of the export format.
+pub const VERSION: u16 = 1;
+
+/// The kind of the exported table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableKind {
+    /// A hash table of the keys and user data.
+    Hash,
+    /// A longest prefix match routing table.
+    Lpm,
+    /// A set of IP prefixes.
+    IpSet,
+    /// An ordered list of the filter rules and user data.
+    Acl,
+}
+
+/// The header of an exported table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    magic: u32,
+    /// The version of the export format.
+    pub version: u16,
+    /// The kind of the table.
+    pub kind: TableKind,
+    /// The number of entries which follow the header.
+    pub len: u64,
+}
+
+impl Header {
+    fn new(kind: TableKind, len: usize) -> Self {
+        Header {
+            magic: MAGIC,
+            version: VERSION,
+            kind,
+            len: len as u64,
+        }
+    }
+
+    fn check(&self, kind: TableKind) -> Result<()> {
+        if self.magic != MAGIC {
+            Err(InvalidTable(format!("bad magic {:#x}", self.magic)).into())
+        } else if self.version != VERSION {
+            Err(InvalidTable(format!("unsupported version {}", self.version)).into())
+        } else if self.kind != kind {
+            Err(InvalidTable(format!("expected {:?} table, got {:?}", kind, self.kind)).into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A table whose contents could be exported and imported.
+pub trait Table: Sized {
+    /// The serialized entry of the table.
+    type Entry: Serialize + DeserializeOwned;
+
+    /// The kind of the table.
+    const KIND: TableKind;
+
+    /// The number of entries.
+    fn entry_count(&self) -> usize;
+
+    /// The entries of the table, in the order they should be imported.
+    fn entries<'a>(&'a self) -> Box<dyn Iterator<Item = Self::Entry> + 'a>;
+
+    /// Build the table from the imported entries.
+    fn from_entries(entries: Vec<Self::Entry>) -> Result<Self>;
+}
+
+/// Read the header of an exported table.
+pub fn read_header<R: Read>(r: R) -> Result<Header> {
+    Ok(bincode::deserialize_from(r)?)
+}
+
+/// Export the contents of a table.
+pub fn export<T: Table, W: Write>(table: &T, mut w: W) -> Result<()> {
+    let header = Header::new(T::KIND, table.entry_count());
+
+    bincode::serialize_into(&mut w, &header)?;
+
+    let mut n = 0;
+
+    for entry in table.entries() {
+        bincode::serialize_into(&mut w, &entry)?;
+
+        n += 1;
+    }
+
+    if n != header.len {
+        return Err(InvalidTable(format!("expected {} entries, exported {}", header.len, n)).into());
+    }
+
+    w.flush()?;
+
+    Ok(())
+}
+
+/// Import the contents of a table, which is built in bulk from the entries.
+pub fn import<T: Table, R: Read>(mut r: R) -> Result<T> {
+    let header = read_header(&mut r)?;
+
+    header.check(T::KIND)?;
+
+    // the number of entries is untrusted, don't reserve too much memory for it
+    let mut entries = Vec::with_capacity(header.len.min(0x10000) as usize);
+
+    for _ in 0..header.len {
+        entries.push(bincode::deserialize_from(&mut r)?);
+
+        coop::yield_point();
+    }
+
+    T::from_entries(entries)
+}
+
+impl<K, V, S> Table for HashMap<K, V, S>
+where
+    K: Serialize + DeserializeOwned + Eq + Hash + Clone,
+    V: Serialize + DeserializeOwned + Clone,
+    S: BuildHasher + Default,
+{
+    type Entry = (K, V);
+
+    const KIND: TableKind = TableKind::Hash;
+
+    fn entry_count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries<'a>(&'a self) -> Box<dyn Iterator<Item = Self::Entry> + 'a> {
+        Box::new(self.iter().map(|(key, value)| (key.clone(), value.clone())))
+    }
+
+    fn from_entries(entries: Vec<Self::Entry>) -> Result<Self> {
+        let mut table = HashMap::with_capacity_and_hasher(entries.len(), S::default());
+
+        table.extend(entries);
+
+        Ok(table)
+    }
+}
+
+/// The ACL is an ordered list of the filter rules and user data, the first matched rule wins.
+impl<T> Table for Vec<(Filter, T)>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    type Entry = (String, T);
+
+    const KIND: TableKind = TableKind::Acl;
+
+    fn entry_count(&self) -> usize {
+        self.len()
+    }
+
+    fn entries<'a>(&'a self) -> Box<dyn Iterator<Item = Self::Entry> + 'a> {
+        Box::new(
+            self.iter()
+                .map(|(filter, data)| (filter.expr().to_owned(), data.clone())),
+        )
+    }
+
+    fn from_entries(entries: Vec<Self::Entry>) -> Result<Self> {
+        entries
+            .into_iter()
+            .map(|(expr, data)| {
+                let filter = Filter::new(&expr)?;
+
+                coop::yield_point();
+
+                Ok((filter, data))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ipset::IpSet;
+    use meta::Verdict;
+
+    use super::*;
+
+    #[test]
+    fn test_export_import() {
+        let mut buf = Vec::new();
+        let mut hash = HashMap::new();
+
+        hash.insert(1u32, "one".to_owned());
+        hash.insert(2u32, "two".to_owned());
+
+        export(&hash, &mut buf).unwrap();
+
+        assert_eq!(read_header(&buf[..]).unwrap(), Header::new(TableKind::Hash, 2));
+        assert_eq!(import::<HashMap<u32, String>, _>(&buf[..]).unwrap(), hash);
+        assert!(import::<IpSet, _>(&buf[..]).is_err());
+
+        let acl = vec![
+            (Filter::new("tcp dst port 22").unwrap(), Verdict::Accept),
+            (Filter::new("").unwrap(), Verdict::Drop),
+        ];
+
+        buf.clear();
+        export(&acl, &mut buf).unwrap();
+
+        let imported: Vec<(Filter, Verdict)> = import(&buf[..]).unwrap();
+
+        assert_eq!(
+            imported.iter().map(|&(ref f, v)| (f.expr(), v)).collect::<Vec<_>>(),
+            vec![("tcp dst port 22", Verdict::Accept), ("", Verdict::Drop)]
+        );
+
+        // the truncated table
+        assert!(import::<Vec<(Filter, Verdict)>, _>(&buf[..buf.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,583 @@
+//!
+//! Testing helpers for the forwarding logic.
+//!
+//! A `PortPair` creates two ring-backed ports which are cross-connected,
+//! the packets sent on one port are received on the other one,
+//! so the forwarding code can be tested deterministically without any NIC.
+//!
+//! `verify_offloads` checks which RX checksum offloads a driver actually honors,
+//! with the crafted packets looped back by the port.
+//!
+use std::fmt;
+use std::mem;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::slice;
+use std::time::{Duration, Instant};
+
+use libc;
+
+use errors::Result;
+use ethdev::{EthDevice, PortId, RxOffload};
+use ether::{ETHER_TYPE_IPv4, EtherAddr, ETHER_HDR_LEN};
+use ip;
+use mbuf::{self, MBufPool};
+use memory::Socket;
+use mempool;
+use ring::{self, Ring, RingFlags};
+
+/// The size of rings between the ports.
+pub const PORT_PAIR_RING_SIZE: usize = 1024;
+
+const UDP_HDR_LEN: usize = 8;
+
+/// Two ring-backed ports, cross-connected.
+///
+/// The packets sent on port A are received on port B, and vice versa.
+///
+/// Port B is usually handed to the forwarding code under test,
+/// while the test injects packets and collects the forwarded packets on port A.
+pub struct PortPair {
+    a: PortId,
+    b: PortId,
+    a_to_b: Ring,
+    b_to_a: Ring,
+}
+
+impl PortPair {
+    /// Create and start a pair of ports, named `<name>_a` and `<name>_b`.
+    ///
+    /// The rings and ports are never released, so the name must be unique.
+    pub fn new<S: AsRef<str>>(name: S, socket: Socket) -> Result<Self> {
+        let name = name.as_ref();
+        let flags = RingFlags::SP_ENQ | RingFlags::SC_DEQ;
+
+        let a_to_b = ring::create(format!("{}_ab", name), PORT_PAIR_RING_SIZE, socket, flags)?;
+        let b_to_a = ring::create(format!("{}_ba", name), PORT_PAIR_RING_SIZE, socket, flags)?;
+
+        let a = ring::eth_from_rings(format!("{}_a", name), &[&b_to_a], &[&a_to_b], socket)?;
+        let b = ring::eth_from_rings(format!("{}_b", name), &[&a_to_b], &[&b_to_a], socket)?;
+
+        a.start()?;
+        b.start()?;
+
+        Ok(PortPair { a, b, a_to_b, b_to_a })
+    }
+
+    /// Port A
+    pub fn a(&self) -> PortId {
+        self.a
+    }
+
+    /// Port B
+    pub fn b(&self) -> PortId {
+        self.b
+    }
+
+    /// The ring of packets sent from port A to port B.
+    pub fn a_to_b(&self) -> &Ring {
+        &self.a_to_b
+    }
+
+    /// The ring of packets sent from port B to port A.
+    pub fn b_to_a(&self) -> &Ring {
+        &self.b_to_a
+    }
+
+    /// Send packets on port A, which will be received on port B.
+    ///
+    /// Returns the number of packets injected, the remaining packets are freed.
+    pub fn inject(&self, mut pkts: Vec<mbuf::MBuf>) -> usize {
+        let n = self.a.tx_burst(0, &mut pkts);
+
+        for m in pkts.drain(..n) {
+            mem::forget(m);
+        }
+
+        n
+    }
+
+    /// Receive packets on port A, which were sent on port B.
+    pub fn collect(&self, pkts: &mut [Option<mbuf::MBuf>]) -> usize {
+        self.a.rx_burst(0, pkts)
+    }
+}
+
+impl Drop for PortPair {
+    fn drop(&mut self) {
+        self.a.stop();
+        self.b.stop();
+    }
+}
+
+/// Build an Ethernet frame with the payload.
+pub fn ether_frame(
+    mut pool: mempool::MemPoolRef,
+    dst_addr: &EtherAddr,
+    src_addr: &EtherAddr,
+    ether_type: u16,
+    payload: &[u8],
+) -> Result<mbuf::MBuf> {
+    let mut m = pool.alloc()?;
+    let len = ETHER_HDR_LEN as usize + payload.len();
+
+    {
+        let buf = m.append(len)?;
+        let buf = unsafe { slice::from_raw_parts_mut(buf.as_ptr(), len) };
+
+        buf[..6].copy_from_slice(dst_addr.octets());
+        buf[6..12].copy_from_slice(src_addr.octets());
+        buf[12..14].copy_from_slice(&ether_type.to_be_bytes());
+        buf[ETHER_HDR_LEN as usize..].copy_from_slice(payload);
+    }
+
+    Ok(m)
+}
+
+/// Build an Ethernet/IPv4/UDP packet with the payload.
+///
+/// The IPv4 checksum is calculated, and the UDP checksum is left as zero.
+pub fn udp_packet(
+    pool: mempool::MemPoolRef,
+    dst_mac: &EtherAddr,
+    src_mac: &EtherAddr,
+    src: &SocketAddrV4,
+    dst: &SocketAddrV4,
+    payload: &[u8],
+) -> Result<mbuf::MBuf> {
+    let total_len = ip::IPV4_HDR_LEN + UDP_HDR_LEN + payload.len();
+    let mut pkt = Vec::with_capacity(total_len);
+
+    pkt.push(ip::IPVERSION << 4 | (ip::IPV4_HDR_LEN / ip::IPV4_IHL_MULTIPLIER) as u8);
+    pkt.push(0);
+    pkt.extend_from_slice(&(total_len as u16).to_be_bytes());
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(&ip::IPV4_HDR_DF_FLAG.to_be_bytes());
+    pkt.push(64);
+    pkt.push(libc::IPPROTO_UDP as u8);
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(&src.ip().octets());
+    pkt.extend_from_slice(&dst.ip().octets());
+
+    let cksum = ip::ipv4_cksum(&pkt);
+    pkt[10..12].copy_from_slice(&cksum.to_ne_bytes());
+
+    pkt.extend_from_slice(&src.port().to_be_bytes());
+    pkt.extend_from_slice(&dst.port().to_be_bytes());
+    pkt.extend_from_slice(&((UDP_HDR_LEN + payload.len()) as u16).to_be_bytes());
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(payload);
+
+    ether_frame(pool, dst_mac, src_mac, ETHER_TYPE_IPv4 as u16, &pkt)
+}
+
+/// The data of the first segment of packet.
+pub fn packet_data(m: &mbuf::MBuf) -> &[u8] {
+    unsafe { slice::from_raw_parts(m.mtod::<u8>().as_ptr(), m.data_len()) }
+}
+
+/// The timeout to receive the looped back packets of `verify_offloads`.
+pub const VERIFY_OFFLOADS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The high byte of the IPv4 identification of the crafted packets, the low byte is the index of the case.
+const VERIFY_OFFLOADS_MAGIC: u16 = 0xc500;
+
+const TCP_HDR_LEN: usize = 20;
+
+/// The checksum state reported by the RX offload flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CksumState {
+    /// No information about the checksum.
+    Unknown,
+    /// The checksum in the packet is wrong.
+    Bad,
+    /// The checksum in the packet is valid.
+    Good,
+    /// The checksum is not correct in the packet data, but the integrity of the header is verified.
+    None,
+}
+
+impl CksumState {
+    /// The IP checksum state of the offload flags.
+    pub fn ip(flags: mbuf::OffloadFlags) -> Self {
+        Self::from_bits(
+            flags & mbuf::OffloadFlags::PKT_RX_IP_CKSUM_MASK,
+            mbuf::OffloadFlags::PKT_RX_IP_CKSUM_GOOD,
+            mbuf::OffloadFlags::PKT_RX_IP_CKSUM_BAD,
+            mbuf::OffloadFlags::PKT_RX_IP_CKSUM_NONE,
+        )
+    }
+
+    /// The L4 checksum state of the offload flags.
+    pub fn l4(flags: mbuf::OffloadFlags) -> Self {
+        Self::from_bits(
+            flags & mbuf::OffloadFlags::PKT_RX_L4_CKSUM_MASK,
+            mbuf::OffloadFlags::PKT_RX_L4_CKSUM_GOOD,
+            mbuf::OffloadFlags::PKT_RX_L4_CKSUM_BAD,
+            mbuf::OffloadFlags::PKT_RX_L4_CKSUM_NONE,
+        )
+    }
+
+    fn from_bits(
+        flags: mbuf::OffloadFlags,
+        good: mbuf::OffloadFlags,
+        bad: mbuf::OffloadFlags,
+        none: mbuf::OffloadFlags,
+    ) -> Self {
+        if flags == none {
+            CksumState::None
+        } else if flags == good {
+            CksumState::Good
+        } else if flags == bad {
+            CksumState::Bad
+        } else {
+            CksumState::Unknown
+        }
+    }
+}
+
+/// The checksum verified by the RX offload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CksumLayer {
+    Ipv4,
+    Udp,
+    Tcp,
+}
+
+impl CksumLayer {
+    /// The RX offload of the checksum.
+    pub fn rx_offload(self) -> RxOffload {
+        match self {
+            CksumLayer::Ipv4 => RxOffload::DEV_RX_OFFLOAD_IPV4_CKSUM,
+            CksumLayer::Udp => RxOffload::DEV_RX_OFFLOAD_UDP_CKSUM,
+            CksumLayer::Tcp => RxOffload::DEV_RX_OFFLOAD_TCP_CKSUM,
+        }
+    }
+
+    fn proto(self) -> u8 {
+        match self {
+            CksumLayer::Tcp => libc::IPPROTO_TCP as u8,
+            _ => libc::IPPROTO_UDP as u8,
+        }
+    }
+}
+
+/// The crafted packets, with the good and deliberately wrong checksums.
+const OFFLOAD_CASES: &[(CksumLayer, bool)] = &[
+    (CksumLayer::Ipv4, false),
+    (CksumLayer::Ipv4, true),
+    (CksumLayer::Udp, false),
+    (CksumLayer::Udp, true),
+    (CksumLayer::Tcp, false),
+    (CksumLayer::Tcp, true),
+];
+
+/// The result of a crafted packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffloadCheck {
+    /// The checksum under test.
+    pub layer: CksumLayer,
+    /// The checksum of the packet was deliberately wrong.
+    pub corrupted: bool,
+    /// The state reported by the driver, or `None` if the packet was not received.
+    pub reported: Option<CksumState>,
+}
+
+impl OffloadCheck {
+    /// The state should be reported by a driver which honors the offload.
+    pub fn expected(&self) -> CksumState {
+        if self.corrupted {
+            CksumState::Bad
+        } else {
+            CksumState::Good
+        }
+    }
+
+    /// The driver reported the expected state.
+    pub fn is_honored(&self) -> bool {
+        self.reported == Some(self.expected())
+    }
+}
+
+/// The report of `verify_offloads`.
+#[derive(Clone, Debug)]
+pub struct OffloadReport {
+    pub port_id: PortId,
+    /// The RX offloads advertised by the driver.
+    pub capa: RxOffload,
+    /// The RX offloads enabled on the port.
+    pub enabled: RxOffload,
+    pub checks: Vec<OffloadCheck>,
+}
+
+impl OffloadReport {
+    /// The driver honors the offload, it reports both the good and bad checksums as expected.
+    pub fn is_honored(&self, layer: CksumLayer) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.layer == layer)
+            .all(OffloadCheck::is_honored)
+    }
+
+    /// The offloads which are enabled on the port, but not honored by the driver.
+    pub fn misreported(&self) -> Vec<CksumLayer> {
+        let mut layers = vec![CksumLayer::Ipv4, CksumLayer::Udp, CksumLayer::Tcp];
+
+        layers.retain(|&layer| self.enabled.contains(layer.rx_offload()) && !self.is_honored(layer));
+        layers
+    }
+
+    /// Assert that the driver honors all the enabled offloads.
+    pub fn assert_honored(&self) {
+        assert!(
+            self.misreported().is_empty(),
+            "checksum offloads not honored by the driver\n{}",
+            self
+        );
+    }
+}
+
+impl fmt::Display for OffloadReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "port {} checksum offloads", self.port_id)?;
+
+        for layer in &[CksumLayer::Ipv4, CksumLayer::Udp, CksumLayer::Tcp] {
+            let offload = layer.rx_offload();
+
+            write!(
+                f,
+                "  {:<5} capa {:<3} enabled {:<3} honored {:<3}",
+                format!("{:?}", layer),
+                if self.capa.contains(offload) { "yes" } else { "no" },
+                if self.enabled.contains(offload) { "yes" } else { "no" },
+                if self.is_honored(*layer) { "yes" } else { "no" },
+            )?;
+
+            for check in self.checks.iter().filter(|check| check.layer == *layer) {
+                write!(
+                    f,
+                    ", {} => {}",
+                    if check.corrupted { "bad" } else { "good" },
+                    check
+                        .reported
+                        .map(|state| format!("{:?}", state))
+                        .unwrap_or_else(|| "lost".to_owned())
+                )?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the Ethernet/IPv4/UDP or TCP packet of a case, with the good or wrong checksum.
+fn offload_packet(
+    pool: mempool::MemPoolRef,
+    dst_mac: &EtherAddr,
+    src_mac: &EtherAddr,
+    idx: usize,
+    layer: CksumLayer,
+    corrupted: bool,
+) -> Result<mbuf::MBuf> {
+    let src = Ipv4Addr::new(192, 0, 2, 1);
+    let dst = Ipv4Addr::new(192, 0, 2, 2);
+    let payload = b"checksum offload verification";
+    let proto = layer.proto();
+    let l4_len = if layer == CksumLayer::Tcp {
+        TCP_HDR_LEN
+    } else {
+        UDP_HDR_LEN
+    } + payload.len();
+
+    let mut l4 = Vec::with_capacity(l4_len);
+
+    l4.extend_from_slice(&9u16.to_be_bytes());
+    l4.extend_from_slice(&9u16.to_be_bytes());
+
+    if layer == CksumLayer::Tcp {
+        l4.extend_from_slice(&[0; 8]); // sequence and acknowledgment number
+        l4.push((TCP_HDR_LEN / 4) as u8 * 16);
+        l4.push(0x18); // PSH | ACK
+        l4.extend_from_slice(&0xffffu16.to_be_bytes());
+        l4.extend_from_slice(&[0, 0, 0, 0]); // checksum and urgent pointer
+    } else {
+        l4.extend_from_slice(&(l4_len as u16).to_be_bytes());
+        l4.extend_from_slice(&[0, 0]);
+    }
+
+    l4.extend_from_slice(payload);
+
+    let mut cksum = ip::ipv4_udptcp_cksum(src, dst, proto, &l4);
+
+    if corrupted && layer != CksumLayer::Ipv4 {
+        cksum = !cksum;
+    }
+
+    let cksum_off = if layer == CksumLayer::Tcp { 16 } else { 6 };
+
+    l4[cksum_off..cksum_off + 2].copy_from_slice(&cksum.to_ne_bytes());
+
+    let mut pkt = Vec::with_capacity(ip::IPV4_HDR_LEN + l4_len);
+
+    pkt.push(ip::IPVERSION << 4 | (ip::IPV4_HDR_LEN / ip::IPV4_IHL_MULTIPLIER) as u8);
+    pkt.push(0);
+    pkt.extend_from_slice(&((ip::IPV4_HDR_LEN + l4_len) as u16).to_be_bytes());
+    pkt.extend_from_slice(&(VERIFY_OFFLOADS_MAGIC | idx as u16).to_be_bytes());
+    pkt.extend_from_slice(&ip::IPV4_HDR_DF_FLAG.to_be_bytes());
+    pkt.push(64);
+    pkt.push(proto);
+    pkt.extend_from_slice(&[0, 0]);
+    pkt.extend_from_slice(&src.octets());
+    pkt.extend_from_slice(&dst.octets());
+
+    let mut cksum = ip::ipv4_cksum(&pkt);
+
+    if corrupted && layer == CksumLayer::Ipv4 {
+        cksum = !cksum;
+    }
+
+    pkt[10..12].copy_from_slice(&cksum.to_ne_bytes());
+    pkt.extend_from_slice(&l4);
+
+    ether_frame(pool, dst_mac, src_mac, ETHER_TYPE_IPv4 as u16, &pkt)
+}
+
+/// The index of the case of a received packet.
+fn offload_case(data: &[u8]) -> Option<usize> {
+    let hdr_len = ETHER_HDR_LEN as usize;
+
+    if data.len() < hdr_len + ip::IPV4_HDR_LEN || data[12..14] != (ETHER_TYPE_IPv4 as u16).to_be_bytes() {
+        return None;
+    }
+
+    let id = u16::from_be_bytes([data[hdr_len + 4], data[hdr_len + 5]]);
+
+    if id & 0xff00 == VERIFY_OFFLOADS_MAGIC && ((id & 0xff) as usize) < OFFLOAD_CASES.len() {
+        Some((id & 0xff) as usize)
+    } else {
+        None
+    }
+}
+
+/// Verify which RX checksum offloads the driver actually honors.
+///
+/// The crafted IPv4/UDP/TCP packets with the good and deliberately wrong checksums are sent on the queue 0
+/// of the started port, which must loop them back, e.g. with a loopback cable or the `lpbk_mode` of the port,
+/// and the RX offload flags of the packets received on the queue 0 are compared with the expected states.
+///
+/// The packets received from the other sources are freed, and the packets not received before
+/// `VERIFY_OFFLOADS_TIMEOUT` are reported as lost.
+pub fn verify_offloads(port_id: PortId, pool: mempool::MemPoolRef) -> Result<OffloadReport> {
+    let mac = port_id.mac_addr();
+    let mut pkts = OFFLOAD_CASES
+        .iter()
+        .enumerate()
+        .map(|(idx, &(layer, corrupted))| offload_packet(pool, &mac, &mac, idx, layer, corrupted))
+        .collect::<Result<Vec<_>>>()?;
+
+    let n = port_id.tx_burst(0, &mut pkts);
+
+    for m in pkts.drain(..n) {
+        mem::forget(m);
+    }
+
+    let mut reported = vec![None; OFFLOAD_CASES.len()];
+    let mut received = 0;
+    let mut rx_pkts = (0..32).map(|_| None).collect::<Vec<Option<mbuf::MBuf>>>();
+    let deadline = Instant::now() + VERIFY_OFFLOADS_TIMEOUT;
+
+    while received < n && Instant::now() < deadline {
+        let nb_rx = port_id.rx_burst(0, &mut rx_pkts);
+
+        for m in rx_pkts[..nb_rx].iter_mut().filter_map(Option::take) {
+            if let Some(idx) = offload_case(packet_data(&m)) {
+                if reported[idx].is_none() {
+                    received += 1;
+                }
+
+                reported[idx] = Some(m.offload());
+            }
+        }
+    }
+
+    let info = port_id.info();
+
+    Ok(OffloadReport {
+        port_id,
+        capa: RxOffload::from_bits_truncate(info.rx_offload_capa),
+        enabled: port_id.current_conf().rxmode.map_or_else(RxOffload::empty, |rxmode| {
+            RxOffload::from_bits_truncate(rxmode.offloads)
+        }),
+        checks: OFFLOAD_CASES
+            .iter()
+            .zip(reported)
+            .map(|(&(layer, corrupted), flags)| OffloadCheck {
+                layer,
+                corrupted,
+                reported: flags.map(|flags| match layer {
+                    CksumLayer::Ipv4 => CksumState::ip(flags),
+                    _ => CksumState::l4(flags),
+                }),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cksum_state() {
+        use mbuf::OffloadFlags;
+
+        assert_eq!(CksumState::ip(OffloadFlags::empty()), CksumState::Unknown);
+        assert_eq!(CksumState::ip(OffloadFlags::PKT_RX_IP_CKSUM_GOOD), CksumState::Good);
+        assert_eq!(CksumState::ip(OffloadFlags::PKT_RX_IP_CKSUM_BAD), CksumState::Bad);
+        assert_eq!(CksumState::ip(OffloadFlags::PKT_RX_IP_CKSUM_NONE), CksumState::None);
+        assert_eq!(
+            CksumState::l4(OffloadFlags::PKT_RX_IP_CKSUM_GOOD | OffloadFlags::PKT_RX_L4_CKSUM_BAD),
+            CksumState::Bad
+        );
+
+        let report = OffloadReport {
+            port_id: 0,
+            capa: RxOffload::DEV_RX_OFFLOAD_CHECKSUM,
+            enabled: RxOffload::DEV_RX_OFFLOAD_IPV4_CKSUM | RxOffload::DEV_RX_OFFLOAD_UDP_CKSUM,
+            checks: vec![
+                OffloadCheck {
+                    layer: CksumLayer::Ipv4,
+                    corrupted: true,
+                    reported: Some(CksumState::Bad),
+                },
+                OffloadCheck {
+                    layer: CksumLayer::Udp,
+                    corrupted: true,
+                    reported: Some(CksumState::Good),
+                },
+                OffloadCheck {
+                    layer: CksumLayer::Tcp,
+                    corrupted: false,
+                    reported: None,
+                },
+            ],
+        };
+
+        assert!(report.is_honored(CksumLayer::Ipv4));
+        assert!(!report.is_honored(CksumLayer::Udp));
+        assert_eq!(report.misreported(), vec![CksumLayer::Udp]);
+    }
+
+    #[test]
+    fn test_offload_case() {
+        let mut data = vec![0; ETHER_HDR_LEN as usize + ip::IPV4_HDR_LEN];
+
+        assert_eq!(offload_case(&data), None);
+
+        data[12..14].copy_from_slice(&(ETHER_TYPE_IPv4 as u16).to_be_bytes());
+        data[18..20].copy_from_slice(&(VERIFY_OFFLOADS_MAGIC | 3).to_be_bytes());
+
+        assert_eq!(offload_case(&data), Some(3));
+    }
+}
@@ -1,21 +1,38 @@
 extern crate num_cpus;
 extern crate pretty_env_logger;
 
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr};
 use std::os::raw::c_void;
 use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
 use cfile;
 use log::Level::Debug;
 
 use ffi;
 
-use common::memory::SOCKET_ID_ANY;
+use common::memory::Socket;
+use ctrlmsg;
+use diag;
 use eal::{self, ProcType};
+use ecmp::{GroupConf, Health, MultipathConf, MultipathReader, MultipathTable, NextHop};
+use ether;
+use ip;
+use ipset::IpSet;
 use launch;
 use lcore;
-use mbuf;
+use malloc::{self, DpdkBox, DpdkVec};
+use mbuf::{self, MBufPool};
 use memory::AsMutRef;
-use mempool::{self, MemoryPool, MemoryPoolFlags};
+use mempool::{self, MemPool, MemoryPool, MemoryPoolFlags};
+use ring::{self, RingFlags, RingNotifier};
+use route::{Route, RouteTable};
+use table;
+use timerwheel::{self, TimerWheel};
 use utils::AsRaw;
 
 #[test]
@@ -37,7 +54,7 @@ fn test_eal() {
     assert_eq!(eal::process_type(), ProcType::Primary);
     assert!(!eal::primary_proc_alive());
     assert!(eal::has_hugepages());
-    assert_eq!(lcore::socket_id(), 0);
+    assert_eq!(lcore::socket_id(), Socket::new(0).unwrap());
 
     test_config();
 
@@ -48,6 +65,28 @@ fn test_eal() {
     test_mempool();
 
     test_mbuf();
+
+    test_pool_create_auto();
+
+    test_mbuf_balance();
+
+    test_route_table();
+
+    test_ipset();
+
+    test_multipath_table();
+
+    test_ctrlmsg();
+
+    test_ring_notifier();
+
+    test_malloc();
+
+    test_timer_wheel();
+
+    test_diag();
+
+    test_dump_to();
 }
 
 fn test_config() {
@@ -77,7 +116,7 @@ fn test_lcore() {
     let lcore_id = lcore::current().unwrap();
 
     assert_eq!(lcore_id.role(), lcore::Role::Rte);
-    assert_eq!(lcore_id.socket_id(), 0);
+    assert_eq!(lcore_id.socket_id().id(), Some(0));
     assert!(lcore_id.is_enabled());
 
     assert_eq!(lcore::master(), 0);
@@ -159,7 +198,7 @@ fn test_mempool() {
         16,
         128,
         0,
-        SOCKET_ID_ANY,
+        Socket::ANY,
         MemoryPoolFlags::MEMPOOL_F_SP_PUT | MemoryPoolFlags::MEMPOOL_F_SC_GET,
     )
     .unwrap();
@@ -171,6 +210,8 @@ fn test_mempool() {
     assert_eq!(p.header_size, 64);
     assert_eq!(p.trailer_size, 0);
     assert_eq!(p.private_data_size, 64);
+    assert!(!p.is_pktmbuf_pool());
+    assert!(p.check_rx_pool().is_err());
 
     assert_eq!(p.avail_count(), 16);
     assert_eq!(p.in_use_count(), 0);
@@ -188,7 +229,7 @@ fn test_mempool() {
     let mut elements: Vec<(usize, *mut ())> = Vec::new();
 
     fn walk_element(
-        _pool: &mempool::MemoryPool,
+        _pool: mempool::MemPoolRef,
         elements: Option<&mut Vec<(usize, *mut ())>>,
         obj: &mut (),
         idx: usize,
@@ -202,11 +243,11 @@ fn test_mempool() {
 
     let raw_ptr = p.as_raw();
 
-    assert_eq!(raw_ptr, mempool::lookup("test").unwrap());
+    assert_eq!(raw_ptr, unsafe { mempool::lookup("test") }.unwrap().as_raw());
 
     let mut pools: Vec<mempool::RawMemoryPoolPtr> = Vec::new();
 
-    fn walk_mempool(pool: &mempool::MemoryPool, pools: Option<&mut Vec<mempool::RawMemoryPoolPtr>>) {
+    fn walk_mempool(pool: mempool::MemPoolRef, pools: Option<&mut Vec<mempool::RawMemoryPoolPtr>>) {
         pools.unwrap().push(pool.as_raw());
     }
 
@@ -233,7 +274,7 @@ fn test_mbuf() {
         CACHE_SIZE,
         PRIV_SIZE as u16,
         mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
-        lcore::socket_id() as i32,
+        lcore::socket_id(),
     )
     .unwrap();
 
@@ -245,10 +286,534 @@ fn test_mbuf() {
     assert_eq!(p.trailer_size, 0);
     assert_eq!(p.private_data_size, 64);
 
+    let private = p.pool_private().unwrap();
+
+    assert_eq!(private.mbuf_data_room_size as u32, mbuf::RTE_MBUF_DEFAULT_BUF_SIZE);
+    assert_eq!(private.mbuf_priv_size as u32, PRIV_SIZE);
+    assert!(p.check_rx_pool().is_ok());
+
     assert_eq!(p.avail_count(), NB_MBUF as usize);
     assert_eq!(p.in_use_count(), 0);
     assert!(p.is_full());
     assert!(!p.is_empty());
 
     p.audit();
+
+    let mut p = p;
+    let mut pkts = mbuf::DefaultBurst::new();
+
+    assert_eq!(pkts.alloc(&mut p).unwrap(), mbuf::DEFAULT_BURST);
+    assert!(pkts.is_full());
+    assert_eq!(p.in_use_count(), mbuf::DEFAULT_BURST);
+
+    let m = pkts.pop().unwrap();
+
+    assert_eq!(pkts.len(), mbuf::DEFAULT_BURST - 1);
+    assert!(pkts.push(m).is_ok());
+
+    assert_eq!(pkts.drain().take(2).count(), 2);
+    assert!(pkts.is_empty());
+    assert_eq!(p.in_use_count(), 0);
+
+    let mut m = p.alloc().unwrap();
+
+    m.push_tail(&ip::Ipv4Hdr::default()).unwrap().version_ihl = 0x45;
+    m.push_header(&ether::EtherHdr::default()).unwrap();
+
+    assert_eq!(m.data_len(), ether::ETHER_HDR_LEN as usize + 20);
+    assert_eq!(m.l2_len(), ether::ETHER_HDR_LEN as usize);
+    assert_eq!(m.l3_len(), 20);
+    assert_eq!(
+        m.header::<ip::Ipv4Hdr>(ether::ETHER_HDR_LEN as usize)
+            .unwrap()
+            .version_ihl,
+        0x45
+    );
+
+    let headroom = m.headroom() as usize;
+
+    assert!(m.prepend(headroom).is_ok());
+    assert!(m.push_header(&ether::VlanHdr::default()).is_err());
+}
+
+fn test_pool_create_auto() {
+    assert!(mempool::registered_ops().iter().any(|ops| ops == "ring_mp_mc"));
+
+    let conf = mbuf::OpsBenchConfig {
+        rounds: 100,
+        ..Default::default()
+    };
+    let scores = mbuf::benchmark_ops(1024, 32, 0, 2048, lcore::socket_id(), &conf);
+    let best = mbuf::fastest_ops(&scores).unwrap();
+
+    let p = mbuf::pool_create_auto("auto_pool", 1024, 32, 0, 2048, lcore::socket_id(), Some(&conf)).unwrap();
+
+    assert!(scores.iter().any(|score| score.ops == p.ops_name()));
+    assert!(best.mbufs > 0);
+    assert_eq!(p.avail_count(), 1024);
+}
+
+fn test_mbuf_balance() {
+    let mut p = mbuf::pool_create(
+        "balance_pool",
+        256,
+        0,
+        0,
+        mbuf::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        lcore::socket_id(),
+    )
+    .unwrap();
+
+    let outstanding = || {
+        mbuf::balance::snapshot()
+            .into_iter()
+            .find(|pool| pool.name == "balance_pool")
+            .map(|pool| pool.total())
+            .unwrap_or_default()
+    };
+
+    let m = p.alloc().unwrap();
+    let m2 = m.clone();
+    let mut pkts = mbuf::DefaultBurst::new();
+
+    assert_eq!(pkts.alloc(&mut p).unwrap(), mbuf::DEFAULT_BURST);
+    assert_eq!(outstanding().outstanding(), 2 + mbuf::DEFAULT_BURST as i64);
+
+    drop(m);
+    drop(m2);
+    drop(pkts);
+
+    let counts = outstanding();
+
+    assert_eq!(counts.gets, 2 + mbuf::DEFAULT_BURST as u64);
+    assert_eq!(counts.outstanding(), 0);
+
+    let mut checker = mbuf::balance::Checker::new(2);
+
+    for _ in 0..3 {
+        assert!(checker.check().is_empty());
+    }
+
+    mbuf::balance::reset(&p);
+
+    assert_eq!(outstanding(), mbuf::balance::Counts::default());
+
+    let m = p.alloc().unwrap();
+
+    drop(m);
+    drop(p);
+
+    assert!(mbuf::balance::snapshot().iter().all(|pool| pool.name != "balance_pool"));
+}
+
+fn test_route_table() {
+    let mut routes = RouteTable::new().unwrap();
+    let default = Route {
+        port: 0,
+        gateway: Some(Ipv4Addr::new(10, 0, 0, 1).into()),
+    };
+    let local = Route { port: 1, gateway: None };
+
+    assert!(routes.add(Ipv4Addr::UNSPECIFIED.into(), 0, default).unwrap().is_none());
+    assert!(routes
+        .add(Ipv4Addr::new(192, 168, 1, 0).into(), 24, local)
+        .unwrap()
+        .is_none());
+    assert!(routes
+        .add("2001:db8::".parse().unwrap(), 32, Route { port: 2, gateway: None })
+        .unwrap()
+        .is_none());
+    assert_eq!(routes.len(), 3);
+
+    assert!(routes.add(Ipv4Addr::new(10, 0, 0, 0).into(), 33, local).is_err());
+    assert!(routes.add("2001:db8::".parse().unwrap(), 129, local).is_err());
+    assert_eq!(routes.len(), 3);
+
+    assert_eq!(routes.lookup(Ipv4Addr::new(192, 168, 1, 100).into()), Some(local));
+    assert_eq!(routes.lookup(Ipv4Addr::new(192, 168, 2, 100).into()), Some(default));
+    assert_eq!(routes.lookup("2001:db8::1".parse().unwrap()).map(|r| r.port), Some(2));
+    assert_eq!(routes.lookup("2001:db9::1".parse().unwrap()), None);
+
+    let copy = routes.try_clone().unwrap();
+
+    assert_eq!(routes.remove(Ipv4Addr::new(192, 168, 1, 1).into(), 24), Some(local));
+    assert_eq!(routes.lookup(Ipv4Addr::new(192, 168, 1, 100).into()), Some(default));
+    assert_eq!(copy.lookup(Ipv4Addr::new(192, 168, 1, 100).into()), Some(local));
+
+    assert_eq!(routes.remove(Ipv4Addr::UNSPECIFIED.into(), 0), Some(default));
+    assert_eq!(routes.lookup(Ipv4Addr::new(192, 168, 2, 100).into()), None);
+    assert_eq!(routes.len(), 1);
+
+    let mut buf = Vec::new();
+
+    table::export(&copy, &mut buf).unwrap();
+
+    let imported: RouteTable = table::import(&buf[..]).unwrap();
+
+    assert_eq!(imported.len(), 3);
+    assert_eq!(imported.lookup(Ipv4Addr::new(192, 168, 1, 100).into()), Some(local));
+    assert_eq!(imported.lookup(Ipv4Addr::new(8, 8, 8, 8).into()), Some(default));
+    assert_eq!(imported.lookup("2001:db8::1".parse().unwrap()).map(|r| r.port), Some(2));
+}
+
+fn test_ipset() {
+    let mut set = IpSet::new().unwrap();
+
+    set.extend(
+        ["10.0.0.0/8", "192.168.1.0/24", "2001:db8::/32"]
+            .iter()
+            .map(|s| s.parse().unwrap()),
+    )
+    .unwrap();
+
+    assert_eq!(set.len(), 3);
+    assert!(!set.insert("10.0.0.0/8".parse().unwrap()).unwrap());
+    assert!(set.insert("10.1.0.0/16".parse().unwrap()).unwrap());
+    assert!(set.has_prefix("10.1.0.0/16".parse().unwrap()));
+
+    assert!(set.contains("10.2.3.4".parse().unwrap()));
+    assert_eq!(
+        set.matched_prefix("10.1.3.4".parse().unwrap()),
+        Some("10.1.0.0/16".parse().unwrap())
+    );
+    assert!(set.contains("2001:db8::1".parse().unwrap()));
+    assert!(!set.contains("192.168.2.1".parse().unwrap()));
+
+    let addrs: Vec<IpAddr> = ["192.168.1.1", "8.8.8.8", "10.0.0.1", "2001:db8::1", "2001:db9::1"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+
+    assert_eq!(set.lookup_bulk(&addrs), 0b01101);
+
+    assert!(set.remove("10.0.0.0/8".parse().unwrap()));
+    assert!(!set.remove("10.0.0.0/8".parse().unwrap()));
+    assert!(!set.contains("10.2.3.4".parse().unwrap()));
+    assert!(set.contains("10.1.3.4".parse().unwrap()));
+    assert_eq!(set.iter().count(), 3);
+
+    let mut buf = Vec::new();
+
+    table::export(&set, &mut buf).unwrap();
+
+    let imported: IpSet = table::import(&buf[..]).unwrap();
+
+    assert_eq!(imported.len(), 3);
+    assert!(imported.contains(IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+
+    set.clear();
+
+    assert!(set.is_empty());
+    assert!(!set.contains("10.1.3.4".parse().unwrap()));
+}
+
+fn test_multipath_table() {
+    let route = |port: u16| Route {
+        port,
+        gateway: Some(Ipv4Addr::new(10, 0, port as u8, 1).into()),
+    };
+    let table = Arc::new(MultipathTable::new().unwrap());
+    let mut reader = MultipathReader::new(&table);
+    let dst = IpAddr::from(Ipv4Addr::new(192, 168, 1, 1));
+    let conf = MultipathConf {
+        groups: vec![
+            GroupConf {
+                id: 1,
+                next_hops: vec![NextHop::new(route(0), 1), NextHop::new(route(1), 1)],
+            },
+            GroupConf {
+                id: 2,
+                next_hops: vec![NextHop::new(route(2), 1)],
+            },
+        ],
+        routes: vec![
+            (Ipv4Addr::UNSPECIFIED.into(), 0, 1),
+            (Ipv4Addr::new(192, 168, 0, 0).into(), 16, 2),
+        ],
+    };
+
+    assert_eq!(reader.lookup(dst, 0), None);
+
+    table.apply(&conf).unwrap();
+
+    assert_eq!(reader.lookup(dst, 0), None);
+    assert!(reader.refresh());
+    assert!(!reader.refresh());
+    assert_eq!(reader.lookup(dst, 0), Some(route(2)));
+
+    // the health states are shared with the readers
+    assert_eq!(table.set_health(&route(0), Health::Down), 1);
+
+    for hash in 0..16 {
+        assert_eq!(reader.lookup(Ipv4Addr::new(8, 8, 8, 8).into(), hash), Some(route(1)));
+    }
+
+    // the routes of the replaced group keep their health states
+    let mut new_conf = conf.clone();
+
+    new_conf.groups[0].next_hops.push(NextHop::new(route(3), 1));
+    table.apply(&new_conf).unwrap();
+    reader.refresh();
+
+    let group = reader.group(Ipv4Addr::new(8, 8, 8, 8).into()).unwrap();
+
+    assert_eq!(group.next_hops().len(), 3);
+    assert_eq!(group.health(0), Some(Health::Down));
+    assert_eq!(group.live(), 2);
+
+    // an invalid configuration is not applied
+    let generation = table.generation();
+
+    new_conf.routes.push((Ipv4Addr::new(10, 0, 0, 0).into(), 8, 3));
+
+    assert!(table.apply(&new_conf).is_err());
+    assert_eq!(table.generation(), generation);
+
+    assert_eq!(
+        table.remove_route(Ipv4Addr::new(192, 168, 0, 0).into(), 16).unwrap(),
+        Some(2)
+    );
+    assert!(table.remove_route(Ipv4Addr::new(192, 168, 0, 0).into(), 33).is_err());
+    assert!(table.add_route("2001:db8::".parse().unwrap(), 129, 1).is_err());
+    assert!(table.add_route("2001:db8::".parse().unwrap(), 32, 3).is_err());
+    assert_eq!(table.add_route("2001:db8::".parse().unwrap(), 32, 1).unwrap(), None);
+    assert!(table.remove_group(2).is_some());
+    assert!(reader.refresh());
+    assert_eq!(reader.lookup(dst, 0).map(|route| route.port), Some(1));
+}
+
+fn test_ctrlmsg() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Command {
+        AddRule { id: u32, pattern: String },
+        DrainQueue(u16),
+        Quit,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Reply {
+        Ok,
+        Drained(usize),
+    }
+
+    let (mut tx, mut rx) = ctrlmsg::channel::<_, Command, Reply>("ctrl", 4, Socket::ANY).unwrap();
+
+    assert_eq!(
+        tx.send(&Command::AddRule {
+            id: 1,
+            pattern: "tcp port 80".to_owned(),
+        })
+        .unwrap(),
+        0
+    );
+    assert_eq!(tx.pending(), 1);
+
+    let req = rx.try_recv().unwrap().unwrap();
+
+    assert_eq!(req.seq, 0);
+    assert_eq!(
+        req.msg,
+        Command::AddRule {
+            id: 1,
+            pattern: "tcp port 80".to_owned(),
+        }
+    );
+    assert!(rx.try_recv().unwrap().is_none());
+
+    // not acknowledged yet
+    assert!(tx.call(&Command::DrainQueue(0), Duration::from_millis(10)).is_err());
+    assert_eq!(rx.poll(8, |_| Reply::Drained(0)).unwrap(), 1);
+
+    // too large
+    assert!(tx
+        .send(&Command::AddRule {
+            id: 2,
+            pattern: "x".repeat(ctrlmsg::CTRLMSG_MAX_LEN),
+        })
+        .is_err());
+
+    let datapath = thread::spawn(move || {
+        let mut running = true;
+        let mut drained = 0;
+
+        while running {
+            drained += rx
+                .poll(8, |cmd| match cmd {
+                    Command::DrainQueue(queue_id) => Reply::Drained(queue_id as usize * 10),
+                    Command::Quit => {
+                        running = false;
+
+                        Reply::Ok
+                    }
+                    _ => Reply::Ok,
+                })
+                .unwrap();
+        }
+
+        drained
+    });
+
+    // the stale acknowledgment of timed out request is dropped
+    assert_eq!(
+        tx.call(&Command::DrainQueue(3), Duration::from_secs(1)).unwrap(),
+        Reply::Drained(30)
+    );
+    assert_eq!(tx.call(&Command::Quit, Duration::from_secs(1)).unwrap(), Reply::Ok);
+
+    assert_eq!(datapath.join().unwrap(), 2);
+}
+
+fn test_ring_notifier() {
+    let r = ring::create("notify", 16, Socket::ANY, RingFlags::SC_DEQ).unwrap();
+    let notifier = RingNotifier::new(r).unwrap();
+
+    assert_eq!(notifier.waiters(), 0);
+    assert!(!notifier.wait(Some(Duration::from_millis(10))).unwrap());
+    assert!(notifier
+        .dequeue_timeout::<u32>(Some(Duration::from_millis(10)))
+        .unwrap()
+        .is_none());
+
+    let producer = notifier.clone();
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+
+        producer.enqueue(Box::into_raw(Box::new(42u32))).unwrap();
+    });
+
+    let obj = notifier
+        .dequeue_timeout::<u32>(Some(Duration::from_secs(5)))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(*unsafe { Box::from_raw(obj) }, 42);
+    assert_eq!(notifier.waiters(), 0);
+
+    t.join().unwrap();
+
+    unsafe { ffi::rte_ring_free(notifier.ring().as_raw()) };
+}
+
+fn test_malloc() {
+    let mut b = malloc::alloc::<[u64; 4]>(Socket::ANY, 0).unwrap();
+
+    assert_eq!(*b, [0; 4]);
+    assert_eq!(b.as_ptr() as usize % ffi::RTE_CACHE_LINE_SIZE as usize, 0);
+
+    b[1] = 42;
+
+    let p = b.into_raw();
+    let b = unsafe { DpdkBox::from_raw(p) }.unwrap();
+
+    assert_eq!(b[1], 42);
+
+    let s = Arc::new(());
+    let mut v = DpdkVec::with_capacity(1, lcore::socket_id(), 0).unwrap();
+
+    for _ in 0..10 {
+        v.push(s.clone()).unwrap();
+    }
+
+    assert_eq!(v.len(), 10);
+    assert!(v.capacity() >= 10);
+    assert_eq!(Arc::strong_count(&s), 11);
+
+    assert!(v.pop().is_some());
+    v.truncate(5);
+
+    assert_eq!(Arc::strong_count(&s), 6);
+
+    drop(v);
+
+    assert_eq!(Arc::strong_count(&s), 1);
+}
+
+fn test_timer_wheel() {
+    let mut wheel = TimerWheel::<u32>::for_lcore(4, Duration::from_millis(1)).unwrap();
+
+    assert_eq!(wheel.capacity(), 4);
+    assert!(wheel.is_empty());
+
+    let t1 = wheel.schedule_at(10, 1).unwrap();
+    let t2 = wheel.schedule_at(300, 2).unwrap();
+    let t3 = wheel.schedule_at(70_000, 3).unwrap();
+    let t4 = wheel.schedule_at(u64::MAX, 4).unwrap();
+
+    assert!(wheel.schedule_at(1, 5).is_err());
+    assert_eq!(wheel.len(), 4);
+    assert_eq!(wheel.get(t2), Some(&2));
+
+    assert_eq!(wheel.cancel(t4), Some(4));
+    assert_eq!(wheel.cancel(t4), None);
+    assert!(!wheel.is_pending(t4));
+
+    let mut expired = vec![];
+
+    assert_eq!(wheel.advance_to(9, |batch| expired.extend_from_slice(batch)), 0);
+    assert_eq!(wheel.advance_to(10, |batch| expired.extend_from_slice(batch)), 1);
+    assert_eq!(expired, vec![1]);
+    assert!(!wheel.is_pending(t1));
+
+    // the idle timeout of the flow is extended
+    assert!(wheel.reschedule_at(t2, 1_000));
+    assert_eq!(wheel.advance_to(999, |batch| expired.extend_from_slice(batch)), 0);
+    assert_eq!(wheel.advance_to(1_000, |batch| expired.extend_from_slice(batch)), 1);
+    assert_eq!(wheel.advance_to(69_999, |batch| expired.extend_from_slice(batch)), 0);
+    assert_eq!(wheel.advance_to(70_000, |batch| expired.extend_from_slice(batch)), 1);
+    assert_eq!(expired, vec![1, 2, 3]);
+    assert!(!wheel.reschedule_at(t3, 80_000));
+
+    let stats = *wheel.stats();
+
+    assert_eq!(stats.scheduled, 4);
+    assert_eq!(stats.cancelled, 1);
+    assert_eq!(stats.expired, 3);
+    assert!(stats.cascaded > 0);
+
+    let mut wheel = TimerWheel::<u32>::for_lcore(1_000, Duration::from_millis(1)).unwrap();
+
+    for i in 0..1_000 {
+        wheel.schedule_at(100, i).unwrap();
+    }
+
+    let mut batches = vec![];
+
+    assert_eq!(wheel.advance_to(100, |batch| batches.push(batch.len())), 1_000);
+    assert!(batches.iter().all(|&n| n <= timerwheel::BATCH_SIZE));
+    assert!(wheel.is_empty());
+}
+
+fn test_diag() {
+    let dir = env::temp_dir().join(format!("rte-diag-test-{}", ::std::process::id()));
+
+    diag::register("custom.txt", |w| {
+        writeln!(w, "hello")?;
+
+        Ok(())
+    });
+
+    let dump = diag::dump_all(&dir).unwrap();
+
+    assert!(dump.written.iter().any(|name| name == "lcores.txt"));
+    assert!(dump.written.iter().any(|name| name == "custom.txt"));
+    assert_eq!(fs::read_to_string(dir.join("custom.txt")).unwrap(), "hello\n");
+    assert!(diag::unregister("custom.txt"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn test_dump_to() {
+    let mut buf = vec![];
+
+    ::common::log::dump_to(&mut buf).unwrap();
+
+    assert!(String::from_utf8(buf).unwrap().contains("global log level"));
+
+    let mut buf = vec![];
+
+    mempool::list_dump_to(&mut buf).unwrap();
+    ring::list_dump_to(&mut buf).unwrap();
+
+    assert!(!buf.is_empty());
 }
@@ -0,0 +1,422 @@
+//!
+//! Hierarchical timing wheel for millions of timers, e.g. the per-flow idle timeouts.
+//!
+//! The `rte_timer` keeps the timers in a skiplist with a lock per lcore, which scales poorly
+//! for a very large number of timers. The `TimerWheel` keeps them in the intrusive lists of
+//! 4 levels of 256 slots, the timers are scheduled, rescheduled and cancelled in O(1),
+//! and cascaded to the lower levels when their expiration is approaching.
+//!
+//! The timers are preallocated from the huge-page memory of the socket, and the wheel is owned
+//! by an lcore without any lock, so each polling lcore should have its own wheel for its flows.
+//! The expired timers are passed to the callback in batches, so the flow table could remove
+//! the idle flows in bulk.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use rte::timerwheel::TimerWheel;
+//!
+//! // the flow ids of the lcore, with a 10ms resolution
+//! let mut wheel = TimerWheel::<u32>::for_lcore(1 << 20, Duration::from_millis(10)).unwrap();
+//!
+//! let timer = wheel.schedule(Duration::from_secs(30), 42).unwrap();
+//!
+//! // a packet of the flow is received
+//! wheel.reschedule(timer, Duration::from_secs(30));
+//!
+//! loop {
+//!     wheel.expire(|flows| {
+//!         for flow_id in flows {
+//!             println!("flow {} is idle", flow_id);
+//!         }
+//!     });
+//! #   break;
+//! }
+//! ```
+//!
+use std::cmp;
+use std::time::Duration;
+
+use libc;
+
+use common::malloc::DpdkVec;
+use common::{get_tsc_hz, lcore, rdtsc};
+use errors::{Result, RteError};
+use memory::Socket;
+
+/// The number of the bits of the slot index of each level.
+const LEVEL_BITS: u32 = 8;
+
+/// The number of the slots of each level.
+const SLOTS: usize = 1 << LEVEL_BITS;
+
+/// The number of the levels.
+const LEVELS: usize = 4;
+
+/// The max number of ticks before the expiration, the later timers are clamped to it.
+pub const MAX_TICKS: u64 = (1 << (LEVEL_BITS as usize * LEVELS)) - 1;
+
+/// The max number of the expired timers passed to the callback at once.
+pub const BATCH_SIZE: usize = 64;
+
+const NIL: u32 = u32::MAX;
+
+/// The handle of a scheduled timer, which is invalidated when the timer expires or is cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId {
+    idx: u32,
+    gen: u32,
+}
+
+/// The statistics of a timer wheel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WheelStats {
+    pub scheduled: u64,
+    pub rescheduled: u64,
+    pub cancelled: u64,
+    pub expired: u64,
+    /// The number of the timers moved to a lower level.
+    pub cascaded: u64,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Node<T> {
+    expires: u64,
+    prev: u32,
+    next: u32,
+    // the slot of the scheduled timer, or `NIL` if it is free
+    slot: u32,
+    gen: u32,
+    data: T,
+}
+
+/// A hierarchical timing wheel of the timers carrying the data, e.g. the flow ids.
+pub struct TimerWheel<T> {
+    nodes: DpdkVec<Node<T>>,
+    heads: DpdkVec<u32>,
+    free: u32,
+    len: usize,
+    // the next tick to be processed
+    current: u64,
+    start: u64,
+    cycles_per_tick: u64,
+    tick: Duration,
+    batch: Vec<T>,
+    stats: WheelStats,
+}
+
+unsafe impl<T: Send> Send for TimerWheel<T> {}
+
+impl<T: Copy + Default> TimerWheel<T> {
+    /// Create a wheel of `capacity` timers with the resolution of `tick`, on the heap of the socket.
+    pub fn new(capacity: usize, tick: Duration, socket: Socket) -> Result<Self> {
+        let hz = u128::from(get_tsc_hz());
+        let cycles_per_tick = (tick.as_nanos() * hz / 1_000_000_000) as u64;
+
+        if capacity == 0 || capacity >= NIL as usize || cycles_per_tick == 0 {
+            return Err(RteError(libc::EINVAL).into());
+        }
+
+        let mut nodes = DpdkVec::with_capacity(capacity, socket, 0)?;
+        let mut heads = DpdkVec::with_capacity(SLOTS * LEVELS, socket, 0)?;
+
+        for idx in 0..capacity {
+            nodes.push(Node {
+                next: if idx + 1 < capacity { idx as u32 + 1 } else { NIL },
+                prev: NIL,
+                slot: NIL,
+                ..Default::default()
+            })?;
+        }
+
+        for _ in 0..SLOTS * LEVELS {
+            heads.push(NIL)?;
+        }
+
+        Ok(TimerWheel {
+            nodes,
+            heads,
+            free: 0,
+            len: 0,
+            current: 0,
+            start: rdtsc(),
+            cycles_per_tick,
+            tick,
+            batch: Vec::with_capacity(BATCH_SIZE),
+            stats: WheelStats::default(),
+        })
+    }
+
+    /// Create a wheel on the socket of the current lcore.
+    pub fn for_lcore(capacity: usize, tick: Duration) -> Result<Self> {
+        Self::new(capacity, tick, lcore::socket_id())
+    }
+
+    /// The resolution of the timers.
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+
+    /// The max number of the scheduled timers.
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of the scheduled timers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// No timer is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The statistics of the wheel.
+    pub fn stats(&self) -> &WheelStats {
+        &self.stats
+    }
+
+    /// The timer is still scheduled.
+    pub fn is_pending(&self, id: TimerId) -> bool {
+        self.node(id).is_some()
+    }
+
+    /// The data of a scheduled timer.
+    pub fn get(&self, id: TimerId) -> Option<&T> {
+        self.node(id).map(|node| &node.data)
+    }
+
+    fn node(&self, id: TimerId) -> Option<&Node<T>> {
+        self.nodes
+            .get(id.idx as usize)
+            .filter(|node| node.slot != NIL && node.gen == id.gen)
+    }
+
+    /// The tick of the TSC.
+    fn tick_of(&self, tsc: u64) -> u64 {
+        tsc.saturating_sub(self.start) / self.cycles_per_tick
+    }
+
+    /// The tick of the expiration after the delay, at least the next tick.
+    fn expires_after(&self, delay: Duration) -> u64 {
+        let ticks =
+            (delay.as_nanos() * u128::from(get_tsc_hz()) / 1_000_000_000 / u128::from(self.cycles_per_tick)) as u64;
+
+        self.tick_of(rdtsc()) + cmp::max(ticks, 1)
+    }
+
+    /// Schedule a timer, which expires after the delay, fails with `ENOSPC` if the wheel is full.
+    pub fn schedule(&mut self, delay: Duration, data: T) -> Result<TimerId> {
+        let expires = self.expires_after(delay);
+
+        self.schedule_at(expires, data)
+    }
+
+    pub(crate) fn schedule_at(&mut self, expires: u64, data: T) -> Result<TimerId> {
+        if self.free == NIL {
+            return Err(RteError(libc::ENOSPC).into());
+        }
+
+        let idx = self.free;
+        let node = &mut self.nodes[idx as usize];
+
+        self.free = node.next;
+        node.data = data;
+
+        self.len += 1;
+        self.stats.scheduled += 1;
+        self.link(idx, expires);
+
+        Ok(TimerId {
+            idx,
+            gen: self.nodes[idx as usize].gen,
+        })
+    }
+
+    /// Reschedule a pending timer to expire after the delay, returns `false` if it has expired or been cancelled.
+    pub fn reschedule(&mut self, id: TimerId, delay: Duration) -> bool {
+        let expires = self.expires_after(delay);
+
+        self.reschedule_at(id, expires)
+    }
+
+    pub(crate) fn reschedule_at(&mut self, id: TimerId, expires: u64) -> bool {
+        if !self.is_pending(id) {
+            return false;
+        }
+
+        self.unlink(id.idx);
+        self.link(id.idx, expires);
+        self.stats.rescheduled += 1;
+
+        true
+    }
+
+    /// Cancel a pending timer, returns its data.
+    pub fn cancel(&mut self, id: TimerId) -> Option<T> {
+        if !self.is_pending(id) {
+            return None;
+        }
+
+        self.stats.cancelled += 1;
+
+        Some(self.release(id.idx))
+    }
+
+    /// Cancel all the timers.
+    pub fn clear(&mut self) {
+        for slot in 0..SLOTS * LEVELS {
+            while self.heads[slot] != NIL {
+                let idx = self.heads[slot];
+
+                self.release(idx);
+            }
+        }
+    }
+
+    /// Expire the timers up to the TSC, the data of the expired timers are passed to the callback in batches.
+    ///
+    /// Returns the number of the expired timers.
+    pub fn advance<F: FnMut(&mut [T])>(&mut self, now: u64, f: F) -> usize {
+        let tick = self.tick_of(now);
+
+        self.advance_to(tick, f)
+    }
+
+    /// Expire the timers up to now.
+    pub fn expire<F: FnMut(&mut [T])>(&mut self, f: F) -> usize {
+        self.advance(rdtsc(), f)
+    }
+
+    /// Process the ticks up to and including `tick`.
+    pub(crate) fn advance_to<F: FnMut(&mut [T])>(&mut self, tick: u64, mut f: F) -> usize {
+        let mut expired = 0;
+
+        while self.current <= tick {
+            if self.len == 0 {
+                self.current = tick + 1;
+                break;
+            }
+
+            let idx = self.current as usize & (SLOTS - 1);
+
+            if idx == 0 {
+                for level in 1..LEVELS {
+                    let idx = (self.current >> (LEVEL_BITS as usize * level)) as usize & (SLOTS - 1);
+
+                    self.cascade(level * SLOTS + idx);
+
+                    if idx != 0 {
+                        break;
+                    }
+                }
+            }
+
+            while self.heads[idx] != NIL {
+                let head = self.heads[idx];
+                let data = self.release(head);
+
+                self.batch.push(data);
+                expired += 1;
+
+                if self.batch.len() == BATCH_SIZE {
+                    f(&mut self.batch);
+                    self.batch.clear();
+                }
+            }
+
+            self.current += 1;
+        }
+
+        if !self.batch.is_empty() {
+            f(&mut self.batch);
+            self.batch.clear();
+        }
+
+        self.stats.expired += expired as u64;
+
+        expired
+    }
+
+    /// Move the timers of the slot to the lower levels.
+    fn cascade(&mut self, slot: usize) {
+        let mut idx = self.heads[slot];
+
+        self.heads[slot] = NIL;
+
+        while idx != NIL {
+            let node = &self.nodes[idx as usize];
+            let (next, expires) = (node.next, node.expires);
+
+            self.link(idx, expires);
+            self.stats.cascaded += 1;
+
+            idx = next;
+        }
+    }
+
+    /// The slot of the expiration, relative to the current tick.
+    fn slot_of(&self, expires: u64) -> usize {
+        let delta = expires - self.current;
+        let level = (1..LEVELS)
+            .take_while(|&level| delta >> (LEVEL_BITS as usize * level) != 0)
+            .last()
+            .unwrap_or(0);
+
+        level * SLOTS + ((expires >> (LEVEL_BITS as usize * level)) as usize & (SLOTS - 1))
+    }
+
+    /// Insert the timer at the head of the slot of its expiration.
+    fn link(&mut self, idx: u32, expires: u64) {
+        let expires = cmp::min(cmp::max(expires, self.current), self.current + MAX_TICKS);
+        let slot = self.slot_of(expires);
+        let head = self.heads[slot];
+
+        {
+            let node = &mut self.nodes[idx as usize];
+
+            node.expires = expires;
+            node.slot = slot as u32;
+            node.prev = NIL;
+            node.next = head;
+        }
+
+        if head != NIL {
+            self.nodes[head as usize].prev = idx;
+        }
+
+        self.heads[slot] = idx;
+    }
+
+    /// Remove the timer from its slot.
+    fn unlink(&mut self, idx: u32) {
+        let Node { prev, next, slot, .. } = self.nodes[idx as usize];
+
+        if prev == NIL {
+            self.heads[slot as usize] = next;
+        } else {
+            self.nodes[prev as usize].next = next;
+        }
+
+        if next != NIL {
+            self.nodes[next as usize].prev = prev;
+        }
+    }
+
+    /// Remove the timer and put it back to the free list, returns its data.
+    fn release(&mut self, idx: u32) -> T {
+        self.unlink(idx);
+
+        let node = &mut self.nodes[idx as usize];
+
+        node.slot = NIL;
+        node.gen = node.gen.wrapping_add(1);
+        node.next = self.free;
+
+        self.free = idx;
+        self.len -= 1;
+
+        node.data
+    }
+}
@@ -0,0 +1,135 @@
+//!
+//! RX timestamping of the packets.
+//!
+//! The NIC RX timestamp offload is enabled if the port supports it,
+//! otherwise the packets are stamped with TSC in a RX callback when they are received.
+//!
+//! The timestamp could be read uniformly with `MBuf::rx_timestamp`,
+//! and its clock could be checked with `timestamp::source`.
+//!
+use std::collections::HashMap;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+use std::sync::RwLock;
+
+use ffi;
+
+use common::rdtsc;
+use errors::{rte_error, Result};
+use ethdev::{EthConf, EthDevice, PortId, QueueId, RxOffload};
+use mbuf::OffloadFlags;
+
+/// The clock of the RX timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The NIC clock, the unit and time reference depend on the driver.
+    Hardware,
+    /// The TSC of the lcore which receives the packets, see `get_tsc_hz`.
+    Tsc,
+}
+
+lazy_static! {
+    static ref SOURCES: RwLock<HashMap<PortId, Source>> = RwLock::new(HashMap::new());
+}
+
+/// The clock of the RX timestamp of the port, `None` if the timestamping is not enabled.
+pub fn source(port_id: PortId) -> Option<Source> {
+    SOURCES.read().unwrap().get(&port_id).cloned()
+}
+
+/// The port supports the NIC RX timestamp offload.
+pub fn has_hw_timestamp(port_id: PortId) -> bool {
+    RxOffload::from_bits_truncate(port_id.info().rx_offload_capa).contains(RxOffload::DEV_RX_OFFLOAD_TIMESTAMP)
+}
+
+/// Prepare the port configuration for the RX timestamping, before the port is configured.
+///
+/// The NIC RX timestamp offload is requested if the port supports it.
+pub fn prepare(port_id: PortId, conf: &mut EthConf) -> Source {
+    if has_hw_timestamp(port_id) {
+        let rxmode = conf.rxmode.get_or_insert_with(Default::default);
+
+        rxmode.offloads |= RxOffload::DEV_RX_OFFLOAD_TIMESTAMP.bits();
+
+        Source::Hardware
+    } else {
+        Source::Tsc
+    }
+}
+
+/// The RX timestamping of a port, which is disabled when dropped.
+pub struct RxTimestamping {
+    port_id: PortId,
+    source: Source,
+    callbacks: Vec<(QueueId, *const ffi::rte_eth_rxtx_callback)>,
+}
+
+unsafe impl Send for RxTimestamping {}
+
+impl Drop for RxTimestamping {
+    fn drop(&mut self) {
+        SOURCES.write().unwrap().remove(&self.port_id);
+
+        for &(queue_id, cb) in &self.callbacks {
+            // the memory of the callback is not freed by the ethdev layer, it is safe for the running RX burst
+            unsafe {
+                ffi::rte_eth_remove_rx_callback(self.port_id, queue_id, cb);
+            }
+        }
+    }
+}
+
+impl RxTimestamping {
+    /// The clock of the RX timestamp.
+    pub fn source(&self) -> Source {
+        self.source
+    }
+}
+
+/// Enable the RX timestamping on the RX queues of the port, after the RX queues were setup.
+///
+/// The NIC RX timestamp is used if the offload was requested with `prepare`,
+/// otherwise a RX callback stamps the packets with TSC on each RX queue.
+pub fn enable(port_id: PortId, nb_rx_queues: QueueId, source: Source) -> Result<RxTimestamping> {
+    let mut timestamping = RxTimestamping {
+        port_id,
+        source,
+        callbacks: vec![],
+    };
+
+    if source == Source::Tsc {
+        for queue_id in 0..nb_rx_queues {
+            let cb = unsafe { ffi::rte_eth_add_rx_callback(port_id, queue_id, Some(tsc_stamp_stub), ptr::null_mut()) };
+
+            if cb.is_null() {
+                return Err(rte_error());
+            }
+
+            timestamping.callbacks.push((queue_id, cb));
+        }
+    }
+
+    SOURCES.write().unwrap().insert(port_id, source);
+
+    Ok(timestamping)
+}
+
+unsafe extern "C" fn tsc_stamp_stub(
+    _port_id: PortId,
+    _queue_id: QueueId,
+    pkts: *mut *mut ffi::rte_mbuf,
+    nb_pkts: u16,
+    _max_pkts: u16,
+    _user_param: *mut c_void,
+) -> u16 {
+    // the packets of a burst are received at the same time
+    let now = rdtsc();
+
+    for &m in slice::from_raw_parts(pkts, nb_pkts as usize) {
+        (*m).timestamp = now;
+        (*m).ol_flags |= OffloadFlags::PKT_RX_TIMESTAMP.bits();
+    }
+
+    nb_pkts
+}
@@ -1,7 +1,13 @@
 use std::borrow::Borrow;
 use std::ffi::CString;
+use std::io;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
+use std::slice;
+
+use libc;
+
+use ffi;
 
 pub trait Raw<T>: Deref<Target = T> + DerefMut + AsRaw<Raw = T> + IntoRaw + FromRaw + From<*mut T> {}
 
@@ -70,7 +76,8 @@ macro_rules! raw {
 
         impl $crate::utils::IntoRaw for $wrapper {
             fn into_raw(self) -> *mut Self::Raw {
-                self.0.as_ptr()
+                // the ownership is transferred to the caller
+                ::std::mem::ManuallyDrop::new(self).0.as_ptr()
             }
         }
 
@@ -123,3 +130,76 @@ impl<F, T> CallbackContext<F, T> {
         unsafe { Box::from_raw(raw as *mut _) }
     }
 }
+
+/// Capture the output of a C function writing to a `FILE` stream, and write it to the writer.
+///
+/// The stream is opened with `open_memstream`, the captured output is written after the stream is closed,
+/// so the dumps of DPDK could be written to any `io::Write`, e.g. a `Vec<u8>` or a `String` buffer.
+pub fn capture<W, F, T>(w: &mut W, f: F) -> io::Result<T>
+where
+    W: io::Write + ?Sized,
+    F: FnOnce(*mut ffi::FILE) -> T,
+{
+    let mut buf = ptr::null_mut();
+    let mut len = 0;
+
+    let stream = unsafe { libc::open_memstream(&mut buf, &mut len) };
+
+    if stream.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+
+    let ret = f(stream as *mut ffi::FILE);
+
+    // the buffer and its size are only updated when the stream is flushed or closed
+    let res = if unsafe { libc::fclose(stream) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    };
+
+    let res = res.and_then(|_| {
+        if buf.is_null() || len == 0 {
+            Ok(())
+        } else {
+            w.write_all(unsafe { slice::from_raw_parts(buf as *const u8, len) })
+        }
+    });
+
+    unsafe { libc::free(buf as *mut _) };
+
+    res.map(|_| ret)
+}
+
+/// Capture the output of a C function writing to a `FILE` stream as a string.
+pub fn capture_string<F, T>(f: F) -> io::Result<(T, String)>
+where
+    F: FnOnce(*mut ffi::FILE) -> T,
+{
+    let mut buf = vec![];
+    let ret = capture(&mut buf, f)?;
+
+    Ok((ret, String::from_utf8_lossy(&buf).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let mut buf = b"dump: ".to_vec();
+
+        let ret = capture(&mut buf, |f| unsafe {
+            libc::fputs(b"hello\0".as_ptr() as *const _, f as *mut _)
+        })
+        .unwrap();
+
+        assert!(ret >= 0);
+        assert_eq!(buf, b"dump: hello");
+
+        let (_, s) = capture_string(|_| ()).unwrap();
+
+        assert_eq!(s, "");
+    }
+}